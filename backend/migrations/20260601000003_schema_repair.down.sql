@@ -0,0 +1,128 @@
+ALTER TABLE match_disputes
+    DROP COLUMN IF EXISTS updated_at,
+    DROP COLUMN IF EXISTS resolved_by,
+    ALTER COLUMN disputing_player_id DROP NOT NULL,
+    DROP CONSTRAINT IF EXISTS match_disputes_disputing_player_id_fkey,
+    ADD CONSTRAINT match_disputes_disputing_player_id_fkey
+        FOREIGN KEY (disputing_player_id) REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE matches
+    ALTER COLUMN player1_id DROP NOT NULL,
+    DROP CONSTRAINT IF EXISTS matches_player1_id_fkey,
+    ADD CONSTRAINT matches_player1_id_fkey
+        FOREIGN KEY (player1_id) REFERENCES users(id) ON DELETE SET NULL;
+
+ALTER TABLE reputation_events
+    ALTER COLUMN user_id DROP NOT NULL;
+
+ALTER TABLE elo_history
+    ALTER COLUMN user_id DROP NOT NULL;
+
+ALTER TABLE user_elo
+    ALTER COLUMN user_id DROP NOT NULL,
+    ALTER COLUMN current_rating DROP NOT NULL,
+    ALTER COLUMN peak_rating DROP NOT NULL,
+    ALTER COLUMN games_played DROP NOT NULL,
+    ALTER COLUMN wins DROP NOT NULL,
+    ALTER COLUMN losses DROP NOT NULL,
+    ALTER COLUMN draws DROP NOT NULL,
+    ALTER COLUMN win_streak DROP NOT NULL,
+    ALTER COLUMN loss_streak DROP NOT NULL;
+
+ALTER TABLE matchmaking_queue
+    ALTER COLUMN user_id DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE match_disputes
+    ALTER COLUMN match_id DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE match_scores
+    ALTER COLUMN match_id DROP NOT NULL,
+    ALTER COLUMN player_id DROP NOT NULL,
+    ALTER COLUMN verified DROP NOT NULL;
+
+ALTER TABLE matches
+    ALTER COLUMN match_type DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE tournament_matches
+    ALTER COLUMN tournament_id DROP NOT NULL,
+    ALTER COLUMN round_id DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE tournament_rounds
+    ALTER COLUMN tournament_id DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE prize_pools
+    ALTER COLUMN tournament_id DROP NOT NULL,
+    ALTER COLUMN total_amount DROP NOT NULL,
+    ALTER COLUMN currency DROP NOT NULL,
+    ALTER COLUMN distribution_percentages DROP NOT NULL;
+
+ALTER TABLE tournament_participants
+    ALTER COLUMN tournament_id DROP NOT NULL,
+    ALTER COLUMN user_id DROP NOT NULL,
+    DROP COLUMN IF EXISTS updated_at,
+    DROP COLUMN IF EXISTS created_at,
+    DROP COLUMN IF EXISTS entry_fee_currency,
+    ALTER COLUMN entry_fee_paid DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE tournaments
+    ALTER COLUMN entry_fee DROP NOT NULL,
+    ALTER COLUMN entry_fee_currency DROP NOT NULL,
+    ALTER COLUMN prize_pool DROP NOT NULL,
+    ALTER COLUMN prize_pool_currency DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL,
+    ALTER COLUMN bracket_type DROP NOT NULL;
+
+ALTER TABLE stellar_transactions
+    ALTER COLUMN asset_code DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE stellar_accounts
+    ALTER COLUMN user_id DROP NOT NULL,
+    ALTER COLUMN account_type DROP NOT NULL,
+    ALTER COLUMN is_funded DROP NOT NULL,
+    ALTER COLUMN is_active DROP NOT NULL,
+    ALTER COLUMN balance_xlm DROP NOT NULL;
+
+ALTER TABLE transactions
+    ALTER COLUMN currency DROP NOT NULL,
+    ALTER COLUMN status DROP NOT NULL;
+
+ALTER TABLE wallets
+    ALTER COLUMN user_id DROP NOT NULL,
+    ALTER COLUMN balance DROP NOT NULL,
+    ALTER COLUMN escrow_balance DROP NOT NULL,
+    ALTER COLUMN currency DROP NOT NULL,
+    ALTER COLUMN balance_ngn DROP NOT NULL,
+    ALTER COLUMN balance_arenax_tokens DROP NOT NULL,
+    ALTER COLUMN balance_xlm DROP NOT NULL,
+    ALTER COLUMN is_active DROP NOT NULL;
+
+ALTER TABLE users
+    ALTER COLUMN country_code DROP NOT NULL,
+    ALTER COLUMN is_verified DROP NOT NULL,
+    ALTER COLUMN is_active DROP NOT NULL,
+    ALTER COLUMN role DROP NOT NULL,
+    ALTER COLUMN skill_score DROP NOT NULL,
+    ALTER COLUMN fair_play_score DROP NOT NULL,
+    ALTER COLUMN anticheat_flags_count DROP NOT NULL,
+    ALTER COLUMN is_bad_actor DROP NOT NULL;
+
+DROP TABLE IF EXISTS idempotency_configs;
+DROP TABLE IF EXISTS idempotency_keys;
+
+ALTER TABLE users
+    DROP COLUMN IF EXISTS device_fingerprint,
+    DROP COLUMN IF EXISTS banned_until,
+    DROP COLUMN IF EXISTS is_banned,
+    DROP COLUMN IF EXISTS total_earnings,
+    DROP COLUMN IF EXISTS stellar_public_key,
+    DROP COLUMN IF EXISTS stellar_account_id,
+    DROP COLUMN IF EXISTS reputation_score,
+    DROP COLUMN IF EXISTS profile_image_url,
+    DROP COLUMN IF EXISTS password_hash;
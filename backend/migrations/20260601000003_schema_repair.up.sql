@@ -0,0 +1,237 @@
+-- Bring the schema in line with what the backend queries expect.
+
+-- Account columns read and written by the auth service.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS password_hash      TEXT,
+    ADD COLUMN IF NOT EXISTS profile_image_url  TEXT,
+    ADD COLUMN IF NOT EXISTS reputation_score   INTEGER,
+    ADD COLUMN IF NOT EXISTS stellar_account_id UUID REFERENCES stellar_accounts(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS stellar_public_key VARCHAR(56),
+    ADD COLUMN IF NOT EXISTS total_earnings     NUMERIC,
+    ADD COLUMN IF NOT EXISTS is_banned          BOOLEAN NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS banned_until       TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS device_fingerprint TEXT;
+
+-- Idempotency keys and per-route settings used by the idempotency middleware.
+-- These tables previously only existed in the standalone idempotency_migration.sql
+-- script, so databases set up through sqlx migrations never had them.
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    id                 UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    key                VARCHAR(255) NOT NULL UNIQUE,
+    request_hash       VARCHAR(64)  NOT NULL,
+    response_status    SMALLINT     NOT NULL,
+    response_headers   JSONB,
+    response_body      JSONB,
+    created_at         TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    expires_at         TIMESTAMPTZ  NOT NULL,
+    used_at            TIMESTAMPTZ,
+    user_id            UUID         REFERENCES users(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_expires_at ON idempotency_keys(expires_at);
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_user_id ON idempotency_keys(user_id);
+
+CREATE TABLE IF NOT EXISTS idempotency_configs (
+    id                   UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    route_pattern        TEXT        NOT NULL UNIQUE,
+    enabled              BOOLEAN     NOT NULL DEFAULT TRUE,
+    ttl_seconds          INTEGER     NOT NULL DEFAULT 86400,
+    max_response_size_kb INTEGER     NOT NULL DEFAULT 1024,
+    created_at           TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at           TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+INSERT INTO idempotency_configs (route_pattern, enabled, ttl_seconds, max_response_size_kb) VALUES
+    ('/api/payments/create', TRUE, 86400, 1024),
+    ('/api/payments/refund', TRUE, 86400, 1024),
+    ('/api/wallets/deposit', TRUE, 86400, 512),
+    ('/api/wallets/withdraw', TRUE, 86400, 512),
+    ('/api/tournaments/join', TRUE, 86400, 256),
+    ('/api/matchmaking/join', TRUE, 3600, 256)
+ON CONFLICT (route_pattern) DO NOTHING;
+
+-- Columns with a default, and owner foreign keys that cascade on delete, were
+-- left nullable while the models treat them as required.
+
+UPDATE users SET country_code = 'NGA' WHERE country_code IS NULL;
+UPDATE users SET is_verified = false WHERE is_verified IS NULL;
+UPDATE users SET is_active = true WHERE is_active IS NULL;
+UPDATE users SET role = 'player' WHERE role IS NULL;
+UPDATE users SET skill_score = 1000 WHERE skill_score IS NULL;
+UPDATE users SET fair_play_score = 100 WHERE fair_play_score IS NULL;
+UPDATE users SET anticheat_flags_count = 0 WHERE anticheat_flags_count IS NULL;
+UPDATE users SET is_bad_actor = false WHERE is_bad_actor IS NULL;
+ALTER TABLE users
+    ALTER COLUMN country_code SET NOT NULL,
+    ALTER COLUMN is_verified SET NOT NULL,
+    ALTER COLUMN is_active SET NOT NULL,
+    ALTER COLUMN role SET NOT NULL,
+    ALTER COLUMN skill_score SET NOT NULL,
+    ALTER COLUMN fair_play_score SET NOT NULL,
+    ALTER COLUMN anticheat_flags_count SET NOT NULL,
+    ALTER COLUMN is_bad_actor SET NOT NULL;
+
+DELETE FROM wallets WHERE user_id IS NULL;
+UPDATE wallets SET balance = 0.00 WHERE balance IS NULL;
+UPDATE wallets SET escrow_balance = 0.00 WHERE escrow_balance IS NULL;
+UPDATE wallets SET currency = 'NGN' WHERE currency IS NULL;
+UPDATE wallets SET balance_ngn = 0 WHERE balance_ngn IS NULL;
+UPDATE wallets SET balance_arenax_tokens = 0 WHERE balance_arenax_tokens IS NULL;
+UPDATE wallets SET balance_xlm = 0 WHERE balance_xlm IS NULL;
+UPDATE wallets SET is_active = true WHERE is_active IS NULL;
+ALTER TABLE wallets
+    ALTER COLUMN user_id SET NOT NULL,
+    ALTER COLUMN balance SET NOT NULL,
+    ALTER COLUMN escrow_balance SET NOT NULL,
+    ALTER COLUMN currency SET NOT NULL,
+    ALTER COLUMN balance_ngn SET NOT NULL,
+    ALTER COLUMN balance_arenax_tokens SET NOT NULL,
+    ALTER COLUMN balance_xlm SET NOT NULL,
+    ALTER COLUMN is_active SET NOT NULL;
+
+UPDATE transactions SET currency = 'NGN' WHERE currency IS NULL;
+UPDATE transactions SET status = 'pending' WHERE status IS NULL;
+ALTER TABLE transactions
+    ALTER COLUMN currency SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
+
+DELETE FROM stellar_accounts WHERE user_id IS NULL;
+UPDATE stellar_accounts SET account_type = 'user' WHERE account_type IS NULL;
+UPDATE stellar_accounts SET is_funded = false WHERE is_funded IS NULL;
+UPDATE stellar_accounts SET is_active = true WHERE is_active IS NULL;
+UPDATE stellar_accounts SET balance_xlm = 0 WHERE balance_xlm IS NULL;
+ALTER TABLE stellar_accounts
+    ALTER COLUMN user_id SET NOT NULL,
+    ALTER COLUMN account_type SET NOT NULL,
+    ALTER COLUMN is_funded SET NOT NULL,
+    ALTER COLUMN is_active SET NOT NULL,
+    ALTER COLUMN balance_xlm SET NOT NULL;
+
+UPDATE stellar_transactions SET asset_code = 'XLM' WHERE asset_code IS NULL;
+UPDATE stellar_transactions SET status = 'pending' WHERE status IS NULL;
+ALTER TABLE stellar_transactions
+    ALTER COLUMN asset_code SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
+
+UPDATE tournaments SET entry_fee = 0 WHERE entry_fee IS NULL;
+UPDATE tournaments SET entry_fee_currency = 'NGN' WHERE entry_fee_currency IS NULL;
+UPDATE tournaments SET prize_pool = 0 WHERE prize_pool IS NULL;
+UPDATE tournaments SET prize_pool_currency = 'NGN' WHERE prize_pool_currency IS NULL;
+UPDATE tournaments SET status = 0 WHERE status IS NULL;
+UPDATE tournaments SET bracket_type = 0 WHERE bracket_type IS NULL;
+ALTER TABLE tournaments
+    ALTER COLUMN entry_fee SET NOT NULL,
+    ALTER COLUMN entry_fee_currency SET NOT NULL,
+    ALTER COLUMN prize_pool SET NOT NULL,
+    ALTER COLUMN prize_pool_currency SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL,
+    ALTER COLUMN bracket_type SET NOT NULL;
+
+DELETE FROM tournament_participants WHERE tournament_id IS NULL;
+DELETE FROM tournament_participants WHERE user_id IS NULL;
+UPDATE tournament_participants SET entry_fee_paid = false WHERE entry_fee_paid IS NULL;
+UPDATE tournament_participants SET status = 0 WHERE status IS NULL;
+ALTER TABLE tournament_participants
+    ALTER COLUMN tournament_id SET NOT NULL,
+    ALTER COLUMN user_id SET NOT NULL,
+    ALTER COLUMN entry_fee_paid SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL,
+    ADD COLUMN IF NOT EXISTS entry_fee_currency VARCHAR(20),
+    ADD COLUMN IF NOT EXISTS created_at         TIMESTAMPTZ DEFAULT NOW(),
+    ADD COLUMN IF NOT EXISTS updated_at         TIMESTAMPTZ DEFAULT NOW();
+
+DELETE FROM prize_pools WHERE tournament_id IS NULL;
+UPDATE prize_pools SET total_amount = 0 WHERE total_amount IS NULL;
+UPDATE prize_pools SET currency = 'NGN' WHERE currency IS NULL;
+UPDATE prize_pools SET distribution_percentages = '[50, 30, 20]' WHERE distribution_percentages IS NULL;
+ALTER TABLE prize_pools
+    ALTER COLUMN tournament_id SET NOT NULL,
+    ALTER COLUMN total_amount SET NOT NULL,
+    ALTER COLUMN currency SET NOT NULL,
+    ALTER COLUMN distribution_percentages SET NOT NULL;
+
+DELETE FROM tournament_rounds WHERE tournament_id IS NULL;
+UPDATE tournament_rounds SET status = 'pending' WHERE status IS NULL;
+ALTER TABLE tournament_rounds
+    ALTER COLUMN tournament_id SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
+
+DELETE FROM tournament_matches WHERE tournament_id IS NULL;
+DELETE FROM tournament_matches WHERE round_id IS NULL;
+UPDATE tournament_matches SET status = 'pending' WHERE status IS NULL;
+ALTER TABLE tournament_matches
+    ALTER COLUMN tournament_id SET NOT NULL,
+    ALTER COLUMN round_id SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
+
+UPDATE matches SET match_type = 1 WHERE match_type IS NULL;
+UPDATE matches SET status = 0 WHERE status IS NULL;
+ALTER TABLE matches
+    ALTER COLUMN match_type SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
+
+DELETE FROM match_scores WHERE match_id IS NULL;
+DELETE FROM match_scores WHERE player_id IS NULL;
+UPDATE match_scores SET verified = false WHERE verified IS NULL;
+ALTER TABLE match_scores
+    ALTER COLUMN match_id SET NOT NULL,
+    ALTER COLUMN player_id SET NOT NULL,
+    ALTER COLUMN verified SET NOT NULL;
+
+DELETE FROM match_disputes WHERE match_id IS NULL;
+UPDATE match_disputes SET status = 0 WHERE status IS NULL;
+ALTER TABLE match_disputes
+    ALTER COLUMN match_id SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
+
+DELETE FROM matchmaking_queue WHERE user_id IS NULL;
+UPDATE matchmaking_queue SET status = 0 WHERE status IS NULL;
+ALTER TABLE matchmaking_queue
+    ALTER COLUMN user_id SET NOT NULL,
+    ALTER COLUMN status SET NOT NULL;
+
+DELETE FROM user_elo WHERE user_id IS NULL;
+UPDATE user_elo SET current_rating = 1200 WHERE current_rating IS NULL;
+UPDATE user_elo SET peak_rating = 1200 WHERE peak_rating IS NULL;
+UPDATE user_elo SET games_played = 0 WHERE games_played IS NULL;
+UPDATE user_elo SET wins = 0 WHERE wins IS NULL;
+UPDATE user_elo SET losses = 0 WHERE losses IS NULL;
+UPDATE user_elo SET draws = 0 WHERE draws IS NULL;
+UPDATE user_elo SET win_streak = 0 WHERE win_streak IS NULL;
+UPDATE user_elo SET loss_streak = 0 WHERE loss_streak IS NULL;
+ALTER TABLE user_elo
+    ALTER COLUMN user_id SET NOT NULL,
+    ALTER COLUMN current_rating SET NOT NULL,
+    ALTER COLUMN peak_rating SET NOT NULL,
+    ALTER COLUMN games_played SET NOT NULL,
+    ALTER COLUMN wins SET NOT NULL,
+    ALTER COLUMN losses SET NOT NULL,
+    ALTER COLUMN draws SET NOT NULL,
+    ALTER COLUMN win_streak SET NOT NULL,
+    ALTER COLUMN loss_streak SET NOT NULL;
+
+DELETE FROM elo_history WHERE user_id IS NULL;
+ALTER TABLE elo_history
+    ALTER COLUMN user_id SET NOT NULL;
+
+DELETE FROM reputation_events WHERE user_id IS NULL;
+ALTER TABLE reputation_events
+    ALTER COLUMN user_id SET NOT NULL;
+
+-- Every match has a first player and every dispute a disputing player, so these
+-- follow their user like the other owner keys instead of being nulled out.
+DELETE FROM matches WHERE player1_id IS NULL;
+ALTER TABLE matches
+    DROP CONSTRAINT IF EXISTS matches_player1_id_fkey,
+    ADD CONSTRAINT matches_player1_id_fkey
+        FOREIGN KEY (player1_id) REFERENCES users(id) ON DELETE CASCADE,
+    ALTER COLUMN player1_id SET NOT NULL;
+
+DELETE FROM match_disputes WHERE disputing_player_id IS NULL;
+ALTER TABLE match_disputes
+    DROP CONSTRAINT IF EXISTS match_disputes_disputing_player_id_fkey,
+    ADD CONSTRAINT match_disputes_disputing_player_id_fkey
+        FOREIGN KEY (disputing_player_id) REFERENCES users(id) ON DELETE CASCADE,
+    ALTER COLUMN disputing_player_id SET NOT NULL,
+    ADD COLUMN IF NOT EXISTS resolved_by UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS updated_at  TIMESTAMPTZ;
@@ -0,0 +1,6 @@
+DROP TABLE IF EXISTS chain_prize_events;
+DROP TABLE IF EXISTS chain_stake_events;
+DROP TABLE IF EXISTS chain_match_events;
+DROP TABLE IF EXISTS chain_escrow_events;
+DROP TABLE IF EXISTS chain_events;
+DROP TABLE IF EXISTS chain_indexer_cursors;
@@ -0,0 +1,79 @@
+-- Checkpoints for the Soroban event indexer. `live` tracks the tail of the
+-- chain; backfills run under their own names so they never move it.
+CREATE TABLE IF NOT EXISTS chain_indexer_cursors (
+    name        TEXT        PRIMARY KEY,
+    cursor      TEXT,
+    last_ledger BIGINT      NOT NULL DEFAULT 0,
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Every indexed contract event as returned by Soroban RPC. `event_id` is the
+-- RPC event id, which makes re-ingesting a ledger range idempotent.
+CREATE TABLE IF NOT EXISTS chain_events (
+    event_id         TEXT        PRIMARY KEY,
+    contract_id      TEXT        NOT NULL,
+    contract_kind    TEXT        NOT NULL,
+    topic            TEXT        NOT NULL,
+    ledger           BIGINT      NOT NULL,
+    ledger_closed_at TIMESTAMPTZ NOT NULL,
+    tx_hash          TEXT        NOT NULL,
+    payload          JSONB       NOT NULL,
+    indexed_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_events_contract_ledger
+    ON chain_events (contract_kind, ledger);
+
+-- Normalized views of the events the platform acts on. Amounts are i128 on
+-- chain, so they are kept as NUMERIC(39, 0).
+CREATE TABLE IF NOT EXISTS chain_escrow_events (
+    event_id TEXT           PRIMARY KEY REFERENCES chain_events(event_id) ON DELETE CASCADE,
+    match_id TEXT           NOT NULL,
+    action   TEXT           NOT NULL,
+    account  TEXT,
+    amount   NUMERIC(39, 0),
+    asset    TEXT,
+    ledger   BIGINT         NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_escrow_events_match
+    ON chain_escrow_events (match_id);
+
+CREATE TABLE IF NOT EXISTS chain_match_events (
+    event_id     TEXT           PRIMARY KEY REFERENCES chain_events(event_id) ON DELETE CASCADE,
+    match_id     TEXT           NOT NULL,
+    action       TEXT           NOT NULL,
+    account      TEXT,
+    stake_amount NUMERIC(39, 0),
+    stake_asset  TEXT,
+    ledger       BIGINT         NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_match_events_match
+    ON chain_match_events (match_id);
+
+CREATE TABLE IF NOT EXISTS chain_stake_events (
+    event_id      TEXT           PRIMARY KEY REFERENCES chain_events(event_id) ON DELETE CASCADE,
+    tournament_id TEXT           NOT NULL,
+    action        TEXT           NOT NULL,
+    user_address  TEXT           NOT NULL,
+    amount        NUMERIC(39, 0) NOT NULL,
+    ledger        BIGINT         NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_stake_events_user
+    ON chain_stake_events (user_address);
+
+CREATE TABLE IF NOT EXISTS chain_prize_events (
+    event_id TEXT           PRIMARY KEY REFERENCES chain_events(event_id) ON DELETE CASCADE,
+    pool_id  BIGINT         NOT NULL,
+    action   TEXT           NOT NULL,
+    match_id TEXT,
+    amount   NUMERIC(39, 0),
+    asset    TEXT,
+    winners  TEXT[],
+    ledger   BIGINT         NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_prize_events_pool
+    ON chain_prize_events (pool_id);
@@ -0,0 +1,29 @@
+-- Soroban transactions the platform relayer builds, signs and submits. One row
+-- per logical invocation; retries reuse the row and bump `attempts`.
+CREATE TABLE IF NOT EXISTS relayer_submissions (
+    id              UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    contract_id     TEXT        NOT NULL,
+    function_name   TEXT        NOT NULL,
+    args            JSONB       NOT NULL,
+    -- queued | submitted | confirmed | failed | expired
+    status          TEXT        NOT NULL DEFAULT 'queued',
+    source_account  TEXT        NOT NULL,
+    sequence_number BIGINT,
+    fee             BIGINT,
+    fee_bumped      BOOLEAN     NOT NULL DEFAULT FALSE,
+    tx_hash         TEXT,
+    envelope_xdr    TEXT,
+    valid_until     TIMESTAMPTZ,
+    attempts        INT         NOT NULL DEFAULT 0,
+    last_error      TEXT,
+    ledger          BIGINT,
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    confirmed_at    TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_relayer_submissions_status
+    ON relayer_submissions (status, updated_at);
+
+CREATE INDEX IF NOT EXISTS idx_relayer_submissions_tx_hash
+    ON relayer_submissions (tx_hash);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS bracket_slots;
+DROP TABLE IF EXISTS bracket_rounds;
+DROP TABLE IF EXISTS tournament_brackets;
@@ -0,0 +1,48 @@
+-- Bracket state for a tournament, independent of the bracket format. Slots
+-- link to the slot their winner (and in double elimination, their loser)
+-- moves to, so advancement never needs to recompute the tree.
+CREATE TABLE IF NOT EXISTS tournament_brackets (
+    tournament_id UUID        PRIMARY KEY REFERENCES tournaments(id) ON DELETE CASCADE,
+    format        TEXT        NOT NULL,
+    player_count  INTEGER     NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at  TIMESTAMPTZ
+);
+
+CREATE TABLE IF NOT EXISTS bracket_rounds (
+    id            UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id UUID        NOT NULL REFERENCES tournament_brackets(tournament_id) ON DELETE CASCADE,
+    side          TEXT        NOT NULL CHECK (side IN ('winners', 'losers', 'grand_final', 'pool')),
+    round_number  INTEGER     NOT NULL,
+    ordinal       INTEGER     NOT NULL,
+    status        TEXT        NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'in_progress', 'completed')),
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at  TIMESTAMPTZ,
+    UNIQUE (tournament_id, side, round_number)
+);
+
+-- winner_to/loser_to reference other slots of the same bracket; they are not
+-- foreign keys so a whole bracket can be inserted in any order.
+CREATE TABLE IF NOT EXISTS bracket_slots (
+    id                 UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id      UUID        NOT NULL REFERENCES tournament_brackets(tournament_id) ON DELETE CASCADE,
+    round_id           UUID        NOT NULL REFERENCES bracket_rounds(id) ON DELETE CASCADE,
+    position           INTEGER     NOT NULL,
+    player1_id         UUID        REFERENCES users(id),
+    player1_seed       INTEGER,
+    player2_id         UUID        REFERENCES users(id),
+    player2_seed       INTEGER,
+    winner_id          UUID        REFERENCES users(id),
+    loser_id           UUID        REFERENCES users(id),
+    status             TEXT        NOT NULL DEFAULT 'pending' CHECK (status IN ('pending', 'ready', 'completed', 'bye')),
+    winner_to_slot_id  UUID,
+    winner_to_position SMALLINT    CHECK (winner_to_position IN (1, 2)),
+    loser_to_slot_id   UUID,
+    loser_to_position  SMALLINT    CHECK (loser_to_position IN (1, 2)),
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at       TIMESTAMPTZ,
+    UNIQUE (round_id, position)
+);
+
+CREATE INDEX IF NOT EXISTS idx_bracket_slots_tournament ON bracket_slots (tournament_id, status);
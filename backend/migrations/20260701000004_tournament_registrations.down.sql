@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS tournament_registrations;
+DROP TABLE IF EXISTS tournament_registration_requirements;
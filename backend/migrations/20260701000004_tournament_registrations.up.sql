@@ -0,0 +1,27 @@
+-- Optional entry requirements checked at registration. Reputation is compared
+-- against users.skill_score / fair_play_score, mirrored from the chain.
+CREATE TABLE IF NOT EXISTS tournament_registration_requirements (
+    tournament_id  UUID        PRIMARY KEY REFERENCES tournaments(id) ON DELETE CASCADE,
+    min_reputation INTEGER,
+    min_fair_play  INTEGER,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- One registration attempt per player and tournament. A registration waits in
+-- `pending_stake` until the player's stake is visible on the staking manager;
+-- repeating the request with the same idempotency key re-checks it.
+CREATE TABLE IF NOT EXISTS tournament_registrations (
+    id               UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    tournament_id    UUID        NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    user_id          UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    idempotency_key  TEXT        NOT NULL,
+    status           TEXT        NOT NULL CHECK (status IN ('pending_stake', 'registered', 'rejected')),
+    stellar_address  TEXT,
+    participant_id   UUID        REFERENCES tournament_participants(id) ON DELETE SET NULL,
+    rejection_reason TEXT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tournament_id, user_id),
+    UNIQUE (user_id, idempotency_key)
+);
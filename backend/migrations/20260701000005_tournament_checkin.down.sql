@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS bracket_checkins;
+DROP INDEX IF EXISTS idx_bracket_slots_checkin_deadline;
+ALTER TABLE bracket_slots
+    DROP COLUMN IF EXISTS checkin_deadline,
+    DROP COLUMN IF EXISTS checkin_opens_at,
+    DROP COLUMN IF EXISTS on_chain_match_id,
+    DROP COLUMN IF EXISTS forfeit;
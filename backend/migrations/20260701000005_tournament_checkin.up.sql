@@ -0,0 +1,20 @@
+-- Check-in windows for bracket matches. A ready slot gets a window when its
+-- round comes up; players missing at the deadline forfeit and are withdrawn
+-- from the rest of the bracket.
+ALTER TABLE bracket_slots
+    ADD COLUMN IF NOT EXISTS forfeit           BOOLEAN     NOT NULL DEFAULT FALSE,
+    ADD COLUMN IF NOT EXISTS on_chain_match_id TEXT,
+    ADD COLUMN IF NOT EXISTS checkin_opens_at  TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS checkin_deadline  TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_bracket_slots_checkin_deadline
+    ON bracket_slots (checkin_deadline)
+    WHERE status = 'ready';
+
+CREATE TABLE IF NOT EXISTS bracket_checkins (
+    slot_id       UUID        NOT NULL REFERENCES bracket_slots(id) ON DELETE CASCADE,
+    user_id       UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    tournament_id UUID        NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    checked_in_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (slot_id, user_id)
+);
@@ -0,0 +1,20 @@
+-- Matches created by the staked matchmaking queue. The match row lives in
+-- `matches`; this table tracks the stake and the on-chain match and escrow
+-- created for it by the relayer.
+CREATE TABLE IF NOT EXISTS staked_matches (
+    match_id          UUID        PRIMARY KEY REFERENCES matches(id) ON DELETE CASCADE,
+    game              VARCHAR(50) NOT NULL,
+    stake_tier        TEXT        NOT NULL,
+    stake_amount      BIGINT      NOT NULL CHECK (stake_amount > 0),
+    on_chain_match_id TEXT        NOT NULL UNIQUE,
+    chain_status      TEXT        NOT NULL DEFAULT 'pending' CHECK (chain_status IN ('pending', 'created', 'failed')),
+    match_tx_hash     TEXT,
+    escrow_tx_hash    TEXT,
+    error             TEXT,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_staked_matches_chain_status
+    ON staked_matches (chain_status)
+    WHERE chain_status <> 'created';
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS match_result_reports;
+DROP TABLE IF EXISTS match_evidence;
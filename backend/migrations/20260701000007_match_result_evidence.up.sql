@@ -0,0 +1,31 @@
+-- Evidence uploaded with match result reports. Files live in object storage;
+-- this table keeps their location and content hash.
+CREATE TABLE IF NOT EXISTS match_evidence (
+    id           UUID        PRIMARY KEY,
+    match_id     UUID        NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    player_id    UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind         TEXT        NOT NULL CHECK (kind IN ('screenshot', 'replay')),
+    content_type TEXT        NOT NULL,
+    size_bytes   BIGINT      NOT NULL CHECK (size_bytes > 0),
+    sha256       TEXT        NOT NULL,
+    object_key   TEXT        NOT NULL UNIQUE,
+    url          TEXT        NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_match_evidence_match_player
+    ON match_evidence (match_id, player_id);
+
+-- One row per result report, tracking the combined evidence hash and the
+-- report's submission to the match lifecycle contract.
+CREATE TABLE IF NOT EXISTS match_result_reports (
+    match_id      UUID        NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    player_id     UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    evidence_hash TEXT        NOT NULL,
+    chain_status  TEXT        NOT NULL DEFAULT 'skipped' CHECK (chain_status IN ('skipped', 'pending', 'submitted', 'failed')),
+    tx_hash       TEXT,
+    error         TEXT,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (match_id, player_id)
+);
@@ -0,0 +1,12 @@
+DROP TABLE IF EXISTS dispute_events;
+DROP TABLE IF EXISTS dispute_evidence;
+DROP INDEX IF EXISTS idx_match_disputes_reviewer;
+
+ALTER TABLE match_disputes
+    DROP COLUMN IF EXISTS updated_at,
+    DROP COLUMN IF EXISTS chain_error,
+    DROP COLUMN IF EXISTS chain_resolve_tx,
+    DROP COLUMN IF EXISTS chain_open_tx,
+    DROP COLUMN IF EXISTS chain_status,
+    DROP COLUMN IF EXISTS decision,
+    DROP COLUMN IF EXISTS category;
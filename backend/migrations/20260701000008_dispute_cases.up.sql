@@ -0,0 +1,42 @@
+-- Dispute cases: referee assignment (admin_reviewer_id), decisions and their
+-- execution on the dispute resolution contract, evidence files and an audit
+-- history of every action taken on a case.
+ALTER TABLE match_disputes
+    ADD COLUMN IF NOT EXISTS category         INTEGER     NOT NULL DEFAULT 0 CHECK (category BETWEEN 0 AND 2),
+    ADD COLUMN IF NOT EXISTS decision         JSONB,
+    ADD COLUMN IF NOT EXISTS chain_status     TEXT        NOT NULL DEFAULT 'off_chain' CHECK (chain_status IN ('off_chain', 'pending', 'opened', 'resolved', 'failed')),
+    ADD COLUMN IF NOT EXISTS chain_open_tx    TEXT,
+    ADD COLUMN IF NOT EXISTS chain_resolve_tx TEXT,
+    ADD COLUMN IF NOT EXISTS chain_error      TEXT,
+    ADD COLUMN IF NOT EXISTS updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+CREATE INDEX IF NOT EXISTS idx_match_disputes_reviewer
+    ON match_disputes (admin_reviewer_id, status);
+
+CREATE TABLE IF NOT EXISTS dispute_evidence (
+    id           UUID        PRIMARY KEY,
+    dispute_id   UUID        NOT NULL REFERENCES match_disputes(id) ON DELETE CASCADE,
+    uploaded_by  UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind         TEXT        NOT NULL CHECK (kind IN ('screenshot', 'replay')),
+    content_type TEXT        NOT NULL,
+    size_bytes   BIGINT      NOT NULL CHECK (size_bytes > 0),
+    sha256       TEXT        NOT NULL,
+    object_key   TEXT        NOT NULL UNIQUE,
+    url          TEXT        NOT NULL,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_dispute_evidence_dispute
+    ON dispute_evidence (dispute_id, created_at);
+
+CREATE TABLE IF NOT EXISTS dispute_events (
+    id         UUID        PRIMARY KEY,
+    dispute_id UUID        NOT NULL REFERENCES match_disputes(id) ON DELETE CASCADE,
+    actor_id   UUID        REFERENCES users(id) ON DELETE SET NULL,
+    action     TEXT        NOT NULL,
+    details    JSONB       NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_dispute_events_dispute
+    ON dispute_events (dispute_id, created_at);
@@ -0,0 +1,8 @@
+DROP TABLE IF EXISTS custody_withdrawals;
+DROP TRIGGER IF EXISTS ledger_entries_balanced ON ledger_entries;
+DROP FUNCTION IF EXISTS ledger_check_journal_balanced();
+DROP TABLE IF EXISTS ledger_entries;
+DROP TABLE IF EXISTS ledger_journals;
+DROP TABLE IF EXISTS ledger_accounts;
+DROP TABLE IF EXISTS custody_stream_cursors;
+DROP TABLE IF EXISTS custody_deposit_addresses;
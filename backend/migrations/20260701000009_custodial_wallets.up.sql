@@ -0,0 +1,100 @@
+-- Custodial Stellar wallets: per-user muxed deposit addresses on the platform
+-- account, the Horizon stream cursor, a double-entry ledger and withdrawal
+-- requests.
+
+CREATE TABLE IF NOT EXISTS custody_deposit_addresses (
+    user_id    UUID        PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    muxed_id   BIGSERIAL   NOT NULL UNIQUE,
+    address    TEXT        NOT NULL UNIQUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Last Horizon paging token processed per custody account.
+CREATE TABLE IF NOT EXISTS custody_stream_cursors (
+    account    TEXT        PRIMARY KEY,
+    cursor     TEXT        NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Double-entry ledger. Amounts are signed stroops and the entries of every
+-- journal sum to zero. User accounts carry what the platform owes each user
+-- and may never go negative; platform accounts (custody, unallocated) are
+-- their counterparts.
+CREATE TABLE IF NOT EXISTS ledger_accounts (
+    id             UUID        PRIMARY KEY,
+    account_key    TEXT        NOT NULL UNIQUE,
+    user_id        UUID        REFERENCES users(id) ON DELETE RESTRICT,
+    asset          TEXT        NOT NULL,
+    balance        BIGINT      NOT NULL DEFAULT 0,
+    allow_negative BOOLEAN     NOT NULL DEFAULT FALSE,
+    created_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at     TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    CONSTRAINT ledger_accounts_non_negative CHECK (allow_negative OR balance >= 0)
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_accounts_user ON ledger_accounts (user_id);
+
+CREATE TABLE IF NOT EXISTS ledger_journals (
+    id         UUID        PRIMARY KEY,
+    -- Idempotency key: posting the same reference twice is a no-op.
+    reference  TEXT        NOT NULL UNIQUE,
+    kind       TEXT        NOT NULL,
+    user_id    UUID        REFERENCES users(id) ON DELETE SET NULL,
+    metadata   JSONB       NOT NULL DEFAULT '{}'::jsonb,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_journals_user ON ledger_journals (user_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS ledger_entries (
+    id         UUID        PRIMARY KEY,
+    journal_id UUID        NOT NULL REFERENCES ledger_journals(id) ON DELETE RESTRICT,
+    account_id UUID        NOT NULL REFERENCES ledger_accounts(id) ON DELETE RESTRICT,
+    amount     BIGINT      NOT NULL CHECK (amount <> 0),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_journal ON ledger_entries (journal_id);
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_account ON ledger_entries (account_id, created_at);
+
+-- Reject unbalanced journals at commit time.
+CREATE OR REPLACE FUNCTION ledger_check_journal_balanced() RETURNS TRIGGER AS $$
+BEGIN
+    IF (SELECT COALESCE(SUM(amount), 0) FROM ledger_entries WHERE journal_id = NEW.journal_id) <> 0 THEN
+        RAISE EXCEPTION 'ledger journal % is unbalanced', NEW.journal_id;
+    END IF;
+    RETURN NULL;
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS ledger_entries_balanced ON ledger_entries;
+CREATE CONSTRAINT TRIGGER ledger_entries_balanced
+    AFTER INSERT ON ledger_entries
+    DEFERRABLE INITIALLY DEFERRED
+    FOR EACH ROW EXECUTE FUNCTION ledger_check_journal_balanced();
+
+CREATE TABLE IF NOT EXISTS custody_withdrawals (
+    id                UUID        PRIMARY KEY,
+    user_id           UUID        NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    asset             TEXT        NOT NULL,
+    amount            BIGINT      NOT NULL CHECK (amount > 0),
+    destination       TEXT        NOT NULL,
+    status            TEXT        NOT NULL CHECK (status IN ('pending_approval', 'approved', 'submitting', 'completed', 'rejected', 'failed', 'unconfirmed')),
+    requires_approval BOOLEAN     NOT NULL,
+    idempotency_key   TEXT,
+    transaction_id    UUID        NOT NULL REFERENCES transactions(id),
+    reviewed_by       UUID        REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at       TIMESTAMPTZ,
+    review_note       TEXT,
+    tx_hash           TEXT,
+    error             TEXT,
+    created_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at        TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at      TIMESTAMPTZ,
+    UNIQUE (user_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_custody_withdrawals_user
+    ON custody_withdrawals (user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_custody_withdrawals_status
+    ON custody_withdrawals (status, created_at);
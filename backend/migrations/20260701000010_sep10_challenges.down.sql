@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_users_stellar_public_key;
+ALTER TABLE users DROP COLUMN IF EXISTS stellar_public_key;
+DROP TABLE IF EXISTS sep10_challenges;
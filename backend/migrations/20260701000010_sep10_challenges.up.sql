@@ -0,0 +1,22 @@
+-- SEP-10 Stellar wallet authentication: issued challenge transactions, each
+-- redeemable once before it expires, and at most one ArenaX account per
+-- Stellar address.
+
+CREATE TABLE IF NOT EXISTS sep10_challenges (
+    -- Hex hash of the challenge transaction on the configured network.
+    tx_hash     TEXT        PRIMARY KEY,
+    account     TEXT        NOT NULL,
+    -- Base64 `Transaction` XDR as issued, before the client signs it.
+    transaction TEXT        NOT NULL,
+    expires_at  TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_sep10_challenges_expires_at ON sep10_challenges(expires_at);
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS stellar_public_key VARCHAR(56);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_users_stellar_public_key
+    ON users(stellar_public_key)
+    WHERE stellar_public_key IS NOT NULL;
@@ -0,0 +1,38 @@
+-- Fiat on/off-ramps through a SEP-24 anchor. Each row mirrors one interactive
+-- anchor transaction; amounts are in stroops of the anchored asset.
+
+CREATE TABLE IF NOT EXISTS anchor_ramps (
+    id                     UUID        PRIMARY KEY,
+    user_id                UUID        NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    kind                   TEXT        NOT NULL CHECK (kind IN ('deposit', 'withdrawal')),
+    asset_code             TEXT        NOT NULL,
+    asset_issuer           TEXT        NOT NULL,
+    anchor_id              TEXT        NOT NULL UNIQUE,
+    status                 TEXT        NOT NULL CHECK (status IN ('interactive', 'pending', 'paying', 'completed', 'failed', 'expired', 'unconfirmed')),
+    -- Last status reported by the anchor, e.g. 'pending_user_transfer_start'.
+    anchor_status          TEXT        NOT NULL,
+    interactive_url        TEXT        NOT NULL,
+    -- Requested amount; for withdrawals this is what is held from the balance.
+    amount                 BIGINT      CHECK (amount > 0),
+    amount_in              BIGINT,
+    amount_out             BIGINT,
+    amount_fee             BIGINT,
+    -- Our payment to the anchor for withdrawals.
+    payout_tx_hash         TEXT,
+    -- The anchor's Stellar payment for deposits.
+    stellar_transaction_id TEXT,
+    message                TEXT,
+    transaction_id         UUID        REFERENCES transactions(id),
+    created_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at             TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at           TIMESTAMPTZ,
+    CHECK (kind = 'deposit' OR amount IS NOT NULL)
+);
+
+CREATE INDEX IF NOT EXISTS idx_anchor_ramps_user ON anchor_ramps (user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_anchor_ramps_open
+    ON anchor_ramps (updated_at)
+    WHERE status IN ('interactive', 'pending');
+
+-- Anchored asset codes are up to 12 characters.
+ALTER TABLE transactions ALTER COLUMN currency TYPE VARCHAR(12);
@@ -0,0 +1,29 @@
+-- Payments API v1: payments to the platform, paid from the custodial wallet or
+-- through a card provider. Each payment is created under a client idempotency
+-- key; retrying with the same key and request returns the original payment.
+
+CREATE TABLE IF NOT EXISTS payments (
+    id                 UUID        PRIMARY KEY,
+    user_id            UUID        NOT NULL REFERENCES users(id) ON DELETE RESTRICT,
+    -- In minor units: stroops for XLM, kobo for NGN.
+    amount             BIGINT      NOT NULL CHECK (amount > 0),
+    currency           VARCHAR(12) NOT NULL,
+    source             TEXT        NOT NULL CHECK (source IN ('wallet', 'paystack', 'flutterwave')),
+    purpose            TEXT        NOT NULL,
+    provider_reference TEXT,
+    status             TEXT        NOT NULL CHECK (status IN ('pending', 'processing', 'completed', 'failed', 'cancelled')),
+    failure_reason     TEXT,
+    metadata           JSONB       NOT NULL DEFAULT '{}'::jsonb,
+    idempotency_key    TEXT        NOT NULL,
+    -- SHA-256 of the original request, to reject a reused key.
+    request_hash       TEXT        NOT NULL,
+    created_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at         TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at       TIMESTAMPTZ,
+    UNIQUE (user_id, idempotency_key)
+);
+
+CREATE INDEX IF NOT EXISTS idx_payments_user ON payments (user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_payments_open
+    ON payments (created_at)
+    WHERE status IN ('pending', 'processing');
@@ -0,0 +1,24 @@
+-- TOTP two-factor authentication. A user has at most one secret, enforced once
+-- the user confirmed it with a valid code, plus hashed one-time recovery codes.
+
+CREATE TABLE IF NOT EXISTS user_two_factor (
+    user_id          UUID        PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    -- Base64 of nonce || AES-256-GCM ciphertext of the raw secret.
+    secret_encrypted TEXT        NOT NULL,
+    enabled          BOOLEAN     NOT NULL DEFAULT FALSE,
+    -- Last 30-second step whose code was accepted; codes of this step or
+    -- earlier are rejected so an observed code cannot be replayed.
+    last_used_step   BIGINT,
+    confirmed_at     TIMESTAMPTZ,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS two_factor_recovery_codes (
+    user_id    UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    -- Hex SHA-256 of the normalized code.
+    code_hash  TEXT        NOT NULL,
+    used_at    TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, code_hash)
+);
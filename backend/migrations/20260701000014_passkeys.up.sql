@@ -0,0 +1,35 @@
+-- WebAuthn passkeys. Each credential belongs to the device it was registered
+-- on, so revoking or blocking a device also retires its passkeys.
+
+CREATE TABLE IF NOT EXISTS device_passkeys (
+    id            UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    device_id     UUID        NOT NULL REFERENCES devices(id) ON DELETE CASCADE,
+    user_id       UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    credential_id BYTEA       NOT NULL UNIQUE,
+    -- COSE_Key as sent by the authenticator.
+    public_key    BYTEA       NOT NULL,
+    -- COSE algorithm: -7 (ES256) or -8 (EdDSA).
+    algorithm     INTEGER     NOT NULL,
+    sign_count    BIGINT      NOT NULL DEFAULT 0,
+    name          VARCHAR(100),
+    aaguid        UUID,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at  TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_passkeys_user_id ON device_passkeys(user_id);
+CREATE INDEX IF NOT EXISTS idx_device_passkeys_device_id ON device_passkeys(device_id);
+
+-- Issued ceremony challenges, each redeemable once before it expires.
+CREATE TABLE IF NOT EXISTS webauthn_challenges (
+    id          UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Unset for usernameless login with a discoverable credential.
+    user_id     UUID        REFERENCES users(id) ON DELETE CASCADE,
+    purpose     VARCHAR(20) NOT NULL CHECK (purpose IN ('register', 'login', 'step_up')),
+    challenge   BYTEA       NOT NULL,
+    expires_at  TIMESTAMPTZ NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webauthn_challenges_expires_at ON webauthn_challenges(expires_at);
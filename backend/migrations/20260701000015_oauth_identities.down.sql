@@ -0,0 +1,3 @@
+-- users.phone_number stays nullable: social sign-ups may have none.
+DROP TABLE IF EXISTS oauth_states;
+DROP TABLE IF EXISTS user_identities;
@@ -0,0 +1,37 @@
+-- Social sign-in: external accounts linked to ArenaX users, and the pending
+-- authorization requests they come back from.
+
+CREATE TABLE IF NOT EXISTS user_identities (
+    id            UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id       UUID         NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider      VARCHAR(20)  NOT NULL CHECK (provider IN ('google', 'discord', 'twitch')),
+    -- The provider's stable account ID (OIDC `sub`, Discord/Twitch user ID).
+    subject       TEXT         NOT NULL,
+    email         VARCHAR(255),
+    username      VARCHAR(100),
+    display_name  VARCHAR(100),
+    avatar_url    TEXT,
+    created_at    TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    last_login_at TIMESTAMPTZ,
+    UNIQUE (provider, subject),
+    -- One account per provider per user.
+    UNIQUE (user_id, provider)
+);
+
+CREATE TABLE IF NOT EXISTS oauth_states (
+    -- Hex SHA-256 of the `state` parameter.
+    state_hash    TEXT        PRIMARY KEY,
+    provider      VARCHAR(20) NOT NULL,
+    -- PKCE verifier sent with the code exchange.
+    code_verifier TEXT        NOT NULL,
+    -- Set when a signed-in user is linking an account rather than signing in.
+    user_id       UUID        REFERENCES users(id) ON DELETE CASCADE,
+    expires_at    TIMESTAMPTZ NOT NULL,
+    consumed_at   TIMESTAMPTZ,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_oauth_states_expires_at ON oauth_states(expires_at);
+
+-- Users who sign up through a provider have no phone number.
+ALTER TABLE users ALTER COLUMN phone_number DROP NOT NULL;
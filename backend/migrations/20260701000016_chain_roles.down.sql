@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS chain_account_roles;
+DROP TABLE IF EXISTS chain_role_events;
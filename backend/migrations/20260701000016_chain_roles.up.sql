@@ -0,0 +1,22 @@
+-- Role events from the auth gateway, normalized like the other chain events.
+CREATE TABLE IF NOT EXISTS chain_role_events (
+    event_id TEXT    PRIMARY KEY REFERENCES chain_events(event_id) ON DELETE CASCADE,
+    address  TEXT    NOT NULL,
+    action   TEXT    NOT NULL,
+    role     INTEGER NOT NULL,
+    actor    TEXT    NOT NULL,
+    ledger   BIGINT  NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_role_events_address
+    ON chain_role_events (address);
+
+-- Current on-chain role of each address, as of `ledger`. The gateway holds
+-- one role per address; a revocation sets it to 0 (`Role::None`) so that a
+-- backfill of older events cannot bring a revoked role back.
+CREATE TABLE IF NOT EXISTS chain_account_roles (
+    address    TEXT        PRIMARY KEY,
+    role       INTEGER     NOT NULL,
+    ledger     BIGINT      NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS login_risk_events;
+DROP TABLE IF EXISTS device_security_policies;
@@ -0,0 +1,55 @@
+-- Sign-in risk policies: admin-managed rules mapping a login's risk score and
+-- factors to an action, and the scored sign-in attempts they were applied to.
+
+CREATE TABLE IF NOT EXISTS device_security_policies (
+    id          UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    name        VARCHAR(100) NOT NULL UNIQUE,
+    description TEXT,
+    -- The rule matches scores in [min_score, max_score] ...
+    min_score   INTEGER      NOT NULL DEFAULT 0 CHECK (min_score BETWEEN 0 AND 100),
+    max_score   INTEGER      NOT NULL DEFAULT 100 CHECK (max_score BETWEEN 0 AND 100),
+    -- ... and, when set, only logins where this risk factor was found.
+    factor      VARCHAR(30)  CHECK (factor IN (
+                    'new_device', 'untrusted_device', 'impossible_travel',
+                    'new_country', 'failed_attempts')),
+    action      VARCHAR(20)  NOT NULL CHECK (action IN ('allow', 'require_2fa', 'block', 'notify')),
+    -- Lower runs first; the first matching non-notify rule decides.
+    priority    INTEGER      NOT NULL DEFAULT 100,
+    enabled     BOOLEAN      NOT NULL DEFAULT TRUE,
+    created_by  UUID         REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    CHECK (min_score <= max_score)
+);
+
+CREATE INDEX IF NOT EXISTS idx_device_security_policies_priority
+    ON device_security_policies(priority) WHERE enabled;
+
+INSERT INTO device_security_policies (name, description, min_score, factor, action, priority)
+VALUES
+    ('block-critical-risk', 'Refuse sign-ins scoring 90 or more', 90, NULL, 'block', 10),
+    ('step-up-high-risk', 'Ask for a second factor from 50', 50, NULL, 'require_2fa', 20),
+    ('notify-impossible-travel', 'Alert on sign-ins too far from the last one', 0, 'impossible_travel', 'notify', 30),
+    ('notify-new-device', 'Alert on sign-ins from a new device', 0, 'new_device', 'notify', 40)
+ON CONFLICT (name) DO NOTHING;
+
+CREATE TABLE IF NOT EXISTS login_risk_events (
+    id          UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id     UUID         NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    fingerprint TEXT         NOT NULL,
+    ip_address  VARCHAR(64)  NOT NULL,
+    country     CHAR(2),
+    latitude    DOUBLE PRECISION,
+    longitude   DOUBLE PRECISION,
+    -- 'succeeded', 'failed' (bad password or second factor) or 'blocked'.
+    outcome     VARCHAR(20)  NOT NULL CHECK (outcome IN ('succeeded', 'failed', 'blocked')),
+    -- Unscored for failed attempts, which are only counted.
+    score       INTEGER,
+    factors     JSONB        NOT NULL DEFAULT '[]',
+    action      VARCHAR(20),
+    policy_id   UUID         REFERENCES device_security_policies(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ  NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_login_risk_events_user_created
+    ON login_risk_events(user_id, created_at DESC);
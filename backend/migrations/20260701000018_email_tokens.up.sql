@@ -0,0 +1,18 @@
+-- Single-use tokens mailed for email verification and password reset.
+
+CREATE TABLE IF NOT EXISTS email_tokens (
+    id          UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id     UUID         NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    purpose     VARCHAR(20)  NOT NULL CHECK (purpose IN ('verify_email', 'reset_password')),
+    -- Hex SHA-256 of the token sent by email.
+    token_hash  TEXT         NOT NULL UNIQUE,
+    -- Address the token was sent to; it is only honoured while the user
+    -- still has this address.
+    email       VARCHAR(255) NOT NULL,
+    expires_at  TIMESTAMPTZ  NOT NULL,
+    consumed_at TIMESTAMPTZ,
+    created_at  TIMESTAMPTZ  NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_email_tokens_user_purpose ON email_tokens(user_id, purpose);
+CREATE INDEX IF NOT EXISTS idx_email_tokens_expires_at ON email_tokens(expires_at);
@@ -0,0 +1,3 @@
+-- Phone numbers are verified with a code texted to them. Pending codes live
+-- in Redis; only the outcome is stored.
+ALTER TABLE users ADD COLUMN IF NOT EXISTS phone_verified_at TIMESTAMPTZ;
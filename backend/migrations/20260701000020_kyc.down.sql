@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS kyc_events;
+DROP TABLE IF EXISTS kyc_profiles;
@@ -0,0 +1,36 @@
+-- Identity verification (KYC). Each user has at most one profile holding
+-- the level approved so far and the state of the latest request. Personal
+-- details are AES-256-GCM encrypted by the application.
+CREATE TABLE IF NOT EXISTS kyc_profiles (
+    user_id            UUID         PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    level              TEXT         NOT NULL DEFAULT 'none' CHECK (level IN ('none', 'basic', 'full')),
+    status             TEXT         NOT NULL DEFAULT 'not_started'
+                                    CHECK (status IN ('not_started', 'pending', 'in_review', 'approved', 'rejected')),
+    requested_level    TEXT         CHECK (requested_level IN ('none', 'basic', 'full')),
+    provider           TEXT,
+    provider_reference TEXT,
+    pii_encrypted      TEXT,
+    rejection_reason   TEXT,
+    reviewed_by        UUID         REFERENCES users(id) ON DELETE SET NULL,
+    reviewed_at        TIMESTAMPTZ,
+    created_at         TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    updated_at         TIMESTAMPTZ  NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_kyc_profiles_status
+    ON kyc_profiles (status, updated_at);
+
+-- Every status change, from the user, the provider or an admin.
+CREATE TABLE IF NOT EXISTS kyc_events (
+    id         UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id    UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    source     TEXT        NOT NULL CHECK (source IN ('user', 'provider', 'admin')),
+    status     TEXT        NOT NULL,
+    level      TEXT        NOT NULL,
+    actor_id   UUID        REFERENCES users(id) ON DELETE SET NULL,
+    details    JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_kyc_events_user
+    ON kyc_events (user_id, created_at DESC);
@@ -0,0 +1,47 @@
+-- In-app notifications. The inbox table was used before any migration
+-- created it, so it is created here if missing and then extended.
+CREATE TABLE IF NOT EXISTS notifications (
+    id         UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id    UUID         NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    type       VARCHAR(50)  NOT NULL DEFAULT 'info',
+    title      TEXT         NOT NULL,
+    message    TEXT         NOT NULL DEFAULT '',
+    link       TEXT,
+    link_label TEXT,
+    read       BOOLEAN      NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMPTZ  NOT NULL DEFAULT NOW()
+);
+
+ALTER TABLE notifications
+    ADD COLUMN IF NOT EXISTS category TEXT NOT NULL DEFAULT 'system'
+        CHECK (category IN ('security', 'wallet', 'match', 'tournament', 'social', 'system')),
+    ADD COLUMN IF NOT EXISTS read_at TIMESTAMPTZ;
+
+CREATE INDEX IF NOT EXISTS idx_notifications_user
+    ON notifications (user_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_notifications_unread
+    ON notifications (user_id) WHERE read = FALSE;
+
+-- Channels a user turned on or off per category. Categories without a row
+-- use the defaults in the application.
+CREATE TABLE IF NOT EXISTS notification_preferences (
+    user_id    UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category   TEXT        NOT NULL
+                           CHECK (category IN ('security', 'wallet', 'match', 'tournament', 'social', 'system')),
+    email      BOOLEAN     NOT NULL,
+    push       BOOLEAN     NOT NULL,
+    in_app     BOOLEAN     NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (user_id, category)
+);
+
+-- FCM registration tokens of the user's app installs and browsers.
+CREATE TABLE IF NOT EXISTS push_tokens (
+    token        TEXT        PRIMARY KEY,
+    user_id      UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    platform     TEXT        NOT NULL CHECK (platform IN ('android', 'ios', 'web')),
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_used_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_push_tokens_user ON push_tokens (user_id);
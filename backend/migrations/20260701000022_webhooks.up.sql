@@ -0,0 +1,52 @@
+-- Outbound webhooks. Integrators register endpoints with event type
+-- filters; each matching event becomes a delivery that is retried with
+-- backoff, and every HTTP attempt is kept for debugging.
+CREATE TABLE IF NOT EXISTS webhook_endpoints (
+    id          UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    owner_id    UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    url         TEXT        NOT NULL,
+    description TEXT,
+    event_types TEXT[]      NOT NULL,
+    -- HMAC-SHA256 key deliveries are signed with.
+    secret      TEXT        NOT NULL,
+    active      BOOLEAN     NOT NULL DEFAULT TRUE,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_endpoints_owner ON webhook_endpoints (owner_id);
+
+CREATE TABLE IF NOT EXISTS webhook_deliveries (
+    id               UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    endpoint_id      UUID        NOT NULL REFERENCES webhook_endpoints(id) ON DELETE CASCADE,
+    event_id         UUID        NOT NULL,
+    event_type       TEXT        NOT NULL,
+    payload          JSONB       NOT NULL,
+    status           TEXT        NOT NULL DEFAULT 'pending'
+                                 CHECK (status IN ('pending', 'succeeded', 'failed')),
+    attempts         INTEGER     NOT NULL DEFAULT 0,
+    next_attempt_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_status_code INTEGER,
+    last_error       TEXT,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    delivered_at     TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_due
+    ON webhook_deliveries (next_attempt_at) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_webhook_deliveries_endpoint
+    ON webhook_deliveries (endpoint_id, created_at DESC);
+
+CREATE TABLE IF NOT EXISTS webhook_attempts (
+    id            UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    delivery_id   UUID        NOT NULL REFERENCES webhook_deliveries(id) ON DELETE CASCADE,
+    attempt       INTEGER     NOT NULL,
+    status_code   INTEGER,
+    error         TEXT,
+    response_body TEXT,
+    duration_ms   INTEGER     NOT NULL,
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_webhook_attempts_delivery
+    ON webhook_attempts (delivery_id, attempt);
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS leaderboard_credits;
+DROP TABLE IF EXISTS chain_reputation_events;
+ALTER TABLE chain_prize_events DROP COLUMN IF EXISTS weights;
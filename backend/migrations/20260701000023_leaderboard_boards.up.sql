@@ -0,0 +1,33 @@
+-- Leaderboards served from Redis sorted sets. Reputation changes and prize
+-- payouts indexed from chain are credited to players here, once per event;
+-- the sorted sets are rebuilt from these rows whenever Redis loses them.
+ALTER TABLE chain_prize_events ADD COLUMN IF NOT EXISTS weights INTEGER[];
+
+CREATE TABLE IF NOT EXISTS chain_reputation_events (
+    event_id        TEXT           PRIMARY KEY REFERENCES chain_events(event_id) ON DELETE CASCADE,
+    player          TEXT           NOT NULL,
+    action          TEXT           NOT NULL,
+    skill_delta     NUMERIC(39, 0) NOT NULL,
+    fair_play_delta NUMERIC(39, 0) NOT NULL,
+    match_id        BIGINT,
+    ledger          BIGINT         NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_chain_reputation_events_player
+    ON chain_reputation_events (player);
+
+CREATE TABLE IF NOT EXISTS leaderboard_credits (
+    event_id    TEXT           NOT NULL REFERENCES chain_events(event_id) ON DELETE CASCADE,
+    user_id     UUID           NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    metric      TEXT           NOT NULL CHECK (metric IN ('skill', 'earnings')),
+    -- Game of the match a payout was for; NULL for reputation changes.
+    game        TEXT,
+    amount      NUMERIC(39, 0) NOT NULL,
+    occurred_at TIMESTAMPTZ    NOT NULL,
+    PRIMARY KEY (event_id, user_id, metric)
+);
+
+CREATE INDEX IF NOT EXISTS idx_leaderboard_credits_board
+    ON leaderboard_credits (metric, game, occurred_at);
+CREATE INDEX IF NOT EXISTS idx_leaderboard_credits_user
+    ON leaderboard_credits (user_id, metric);
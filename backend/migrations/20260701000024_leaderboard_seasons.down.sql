@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS leaderboard_season_standings;
+DROP TABLE IF EXISTS leaderboard_seasons;
@@ -0,0 +1,28 @@
+-- Final standings of finished leaderboard seasons, frozen by the rollover
+-- job, with the reward tier each player earned and the winners manifest
+-- handed to the prize distribution contract.
+CREATE TABLE IF NOT EXISTS leaderboard_seasons (
+    board       TEXT        NOT NULL,
+    season_id   TEXT        NOT NULL,
+    starts_at   TIMESTAMPTZ NOT NULL,
+    ends_at     TIMESTAMPTZ NOT NULL,
+    players     INTEGER     NOT NULL DEFAULT 0,
+    manifest    JSONB,
+    archived_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (board, season_id)
+);
+
+CREATE TABLE IF NOT EXISTS leaderboard_season_standings (
+    board       TEXT           NOT NULL,
+    season_id   TEXT           NOT NULL,
+    rank        INTEGER        NOT NULL,
+    user_id     UUID           NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    score       NUMERIC(39, 0) NOT NULL,
+    reward_tier TEXT,
+    PRIMARY KEY (board, season_id, user_id),
+    FOREIGN KEY (board, season_id)
+        REFERENCES leaderboard_seasons (board, season_id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_leaderboard_season_standings_rank
+    ON leaderboard_season_standings (board, season_id, rank);
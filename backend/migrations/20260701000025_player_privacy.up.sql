@@ -0,0 +1,7 @@
+-- Per-player privacy settings for the public profile. Players without a row
+-- use the defaults.
+CREATE TABLE IF NOT EXISTS player_privacy (
+    user_id       UUID        PRIMARY KEY REFERENCES users(id) ON DELETE CASCADE,
+    hide_earnings BOOLEAN     NOT NULL DEFAULT FALSE,
+    updated_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
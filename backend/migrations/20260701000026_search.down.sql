@@ -0,0 +1,9 @@
+DROP INDEX IF EXISTS idx_users_updated_at;
+DROP INDEX IF EXISTS idx_tournaments_updated_at;
+DROP INDEX IF EXISTS idx_users_username_trgm;
+DROP INDEX IF EXISTS idx_users_search;
+DROP INDEX IF EXISTS idx_tournaments_name_trgm;
+DROP INDEX IF EXISTS idx_tournaments_search;
+ALTER TABLE users DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE tournaments DROP COLUMN IF EXISTS search_vector;
+ALTER TABLE tournaments DROP COLUMN IF EXISTS region;
@@ -0,0 +1,26 @@
+-- Full-text search over tournaments and players. Trigram indexes on names
+-- match misspelled queries.
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+ALTER TABLE tournaments ADD COLUMN IF NOT EXISTS region VARCHAR(32);
+
+ALTER TABLE tournaments ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple'::regconfig, COALESCE(name, '')), 'A') ||
+        setweight(to_tsvector('simple'::regconfig, COALESCE(game, '')), 'B') ||
+        setweight(to_tsvector('english'::regconfig, COALESCE(description, '')), 'C')
+    ) STORED;
+
+ALTER TABLE users ADD COLUMN IF NOT EXISTS search_vector tsvector
+    GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple'::regconfig, COALESCE(username, '')), 'A') ||
+        setweight(to_tsvector('simple'::regconfig, COALESCE(display_name, '')), 'B')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_tournaments_search ON tournaments USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_tournaments_name_trgm ON tournaments USING GIN (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_users_search ON users USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_users_username_trgm ON users USING GIN (username gin_trgm_ops);
+-- Changed rows are synced to Meilisearch when it is configured.
+CREATE INDEX IF NOT EXISTS idx_tournaments_updated_at ON tournaments (updated_at);
+CREATE INDEX IF NOT EXISTS idx_users_updated_at ON users (updated_at);
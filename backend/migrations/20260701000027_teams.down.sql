@@ -0,0 +1,7 @@
+DROP TABLE IF EXISTS tournament_team_lineups;
+DROP INDEX IF EXISTS idx_tournament_participants_team;
+ALTER TABLE tournament_participants DROP COLUMN IF EXISTS team_id;
+ALTER TABLE tournaments DROP COLUMN IF EXISTS team_size;
+DROP TABLE IF EXISTS team_invitations;
+DROP TABLE IF EXISTS team_members;
+DROP TABLE IF EXISTS teams;
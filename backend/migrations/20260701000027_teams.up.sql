@@ -0,0 +1,79 @@
+-- Teams with a captain, invited members and shares of the team wallet.
+-- Team tournaments (`team_size` set) take one entry per team: the captain's
+-- participant row, linked to the team, with the members playing locked in
+-- `tournament_team_lineups`.
+CREATE TABLE IF NOT EXISTS teams (
+    id           UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    name         VARCHAR(50) NOT NULL,
+    tag          VARCHAR(8)  NOT NULL,
+    game         VARCHAR(50),
+    description  TEXT,
+    avatar_url   TEXT,
+    created_by   UUID        NOT NULL REFERENCES users(id),
+    search_vector tsvector GENERATED ALWAYS AS (
+        setweight(to_tsvector('simple'::regconfig, COALESCE(name, '')), 'A') ||
+        setweight(to_tsvector('simple'::regconfig, COALESCE(tag, '')), 'A') ||
+        setweight(to_tsvector('simple'::regconfig, COALESCE(game, '')), 'B')
+    ) STORED,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    disbanded_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_teams_name
+    ON teams (LOWER(name)) WHERE disbanded_at IS NULL;
+CREATE UNIQUE INDEX IF NOT EXISTS idx_teams_tag
+    ON teams (UPPER(tag)) WHERE disbanded_at IS NULL;
+CREATE INDEX IF NOT EXISTS idx_teams_search ON teams USING GIN (search_vector);
+CREATE INDEX IF NOT EXISTS idx_teams_name_trgm ON teams USING GIN (name gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_teams_updated_at ON teams (updated_at);
+
+-- Shares are basis points of team wallet distributions and always sum to
+-- 10000 across a team.
+CREATE TABLE IF NOT EXISTS team_members (
+    team_id   UUID        NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    user_id   UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    role      TEXT        NOT NULL CHECK (role IN ('captain', 'member')),
+    share_bps INTEGER     NOT NULL DEFAULT 0 CHECK (share_bps BETWEEN 0 AND 10000),
+    joined_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (team_id, user_id)
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_team_members_captain
+    ON team_members (team_id) WHERE role = 'captain';
+CREATE INDEX IF NOT EXISTS idx_team_members_user ON team_members (user_id);
+
+CREATE TABLE IF NOT EXISTS team_invitations (
+    id           UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    team_id      UUID        NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    user_id      UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    invited_by   UUID        NOT NULL REFERENCES users(id),
+    status       TEXT        NOT NULL DEFAULT 'pending'
+                 CHECK (status IN ('pending', 'accepted', 'declined', 'revoked')),
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    expires_at   TIMESTAMPTZ NOT NULL,
+    responded_at TIMESTAMPTZ
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_team_invitations_pending
+    ON team_invitations (team_id, user_id) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_team_invitations_user
+    ON team_invitations (user_id, status);
+
+ALTER TABLE tournaments ADD COLUMN IF NOT EXISTS team_size INTEGER CHECK (team_size >= 2);
+ALTER TABLE tournament_participants
+    ADD COLUMN IF NOT EXISTS team_id UUID REFERENCES teams(id);
+
+CREATE INDEX IF NOT EXISTS idx_tournament_participants_team
+    ON tournament_participants (team_id) WHERE team_id IS NOT NULL;
+
+-- A player plays for at most one team per tournament.
+CREATE TABLE IF NOT EXISTS tournament_team_lineups (
+    tournament_id UUID NOT NULL REFERENCES tournaments(id) ON DELETE CASCADE,
+    team_id       UUID NOT NULL REFERENCES teams(id) ON DELETE CASCADE,
+    user_id       UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    PRIMARY KEY (tournament_id, user_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_tournament_team_lineups_team
+    ON tournament_team_lineups (tournament_id, team_id);
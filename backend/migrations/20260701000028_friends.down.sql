@@ -0,0 +1,2 @@
+ALTER TABLE friends DROP CONSTRAINT IF EXISTS friends_status_check;
+DROP INDEX IF EXISTS idx_friend_requests_pending_pair;
@@ -0,0 +1,17 @@
+-- Friend requests and blocks. A `friends` row with status 'blocked' means
+-- `user_id` blocked `friend_id`; blocking replaces any friendship between the
+-- two. At most one request per direction may be pending.
+DELETE FROM friend_requests a
+USING friend_requests b
+WHERE a.status = 'pending'
+  AND b.status = 'pending'
+  AND a.from_user_id = b.from_user_id
+  AND a.to_user_id = b.to_user_id
+  AND (a.created_at, a.id) < (b.created_at, b.id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_friend_requests_pending_pair
+    ON friend_requests (from_user_id, to_user_id)
+    WHERE status = 'pending';
+
+ALTER TABLE friends
+    ADD CONSTRAINT friends_status_check CHECK (status IN ('accepted', 'blocked'));
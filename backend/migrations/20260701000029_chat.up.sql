@@ -0,0 +1,33 @@
+-- Chat in match lobbies, tournament channels and direct messages between
+-- friends. `channel` is `match:<id>`, `tournament:<id>` or `dm:<a>:<b>` with
+-- the two user IDs in ascending order.
+CREATE TABLE IF NOT EXISTS chat_messages (
+    id         UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    channel    VARCHAR(100) NOT NULL,
+    sender_id  UUID         NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    body       TEXT         NOT NULL,
+    created_at TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    deleted_at TIMESTAMPTZ,
+    deleted_by UUID         REFERENCES users(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_messages_channel
+    ON chat_messages (channel, created_at DESC, id DESC);
+
+-- Moderator mutes, in one channel or everywhere (`channel` NULL), until
+-- `expires_at` or until lifted.
+CREATE TABLE IF NOT EXISTS chat_mutes (
+    id         UUID         PRIMARY KEY DEFAULT gen_random_uuid(),
+    user_id    UUID         NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    channel    VARCHAR(100),
+    muted_by   UUID         NOT NULL REFERENCES users(id),
+    reason     TEXT,
+    expires_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ  NOT NULL DEFAULT NOW(),
+    lifted_at  TIMESTAMPTZ,
+    lifted_by  UUID         REFERENCES users(id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_chat_mutes_active
+    ON chat_mutes (user_id)
+    WHERE lifted_at IS NULL;
@@ -0,0 +1,14 @@
+-- Live scores players post while a match is in progress. Spectators see them
+-- as the match's round timeline, behind the broadcast delay.
+CREATE TABLE IF NOT EXISTS match_live_scores (
+    id            UUID        PRIMARY KEY DEFAULT gen_random_uuid(),
+    match_id      UUID        NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    reported_by   UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    round         INTEGER     NOT NULL CHECK (round >= 1),
+    player1_score INTEGER     NOT NULL CHECK (player1_score >= 0),
+    player2_score INTEGER     NOT NULL CHECK (player2_score >= 0),
+    created_at    TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_match_live_scores_match
+    ON match_live_scores (match_id, created_at);
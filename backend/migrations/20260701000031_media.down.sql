@@ -0,0 +1,5 @@
+ALTER TABLE match_disputes
+    DROP COLUMN IF EXISTS evidence_hashes,
+    DROP COLUMN IF EXISTS evidence_ref;
+DROP TABLE IF EXISTS media_upload_parts;
+DROP TABLE IF EXISTS media_objects;
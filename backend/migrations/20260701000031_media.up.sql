@@ -0,0 +1,46 @@
+-- Replays and screenshots uploaded in parts through the media service. Each
+-- object is hashed and scanned before it can be used as evidence, and is
+-- deleted once its retention period is over.
+CREATE TABLE IF NOT EXISTS media_objects (
+    id               UUID        PRIMARY KEY,
+    owner_id         UUID        NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    kind             TEXT        NOT NULL CHECK (kind IN ('screenshot', 'replay')),
+    content_type     TEXT        NOT NULL,
+    size_bytes       BIGINT      NOT NULL CHECK (size_bytes > 0),
+    -- Declared by the uploader; the stored bytes must hash to it.
+    sha256           TEXT        NOT NULL,
+    object_key       TEXT        NOT NULL UNIQUE,
+    -- Multipart upload ID while parts are being uploaded.
+    upload_id        TEXT,
+    part_size        BIGINT      NOT NULL CHECK (part_size > 0),
+    status           TEXT        NOT NULL DEFAULT 'uploading'
+                     CHECK (status IN ('uploading', 'processing', 'ready', 'rejected', 'expired')),
+    rejection_reason TEXT,
+    match_id         UUID        REFERENCES matches(id) ON DELETE SET NULL,
+    -- Dispute the object is evidence in; holds it until the case is closed.
+    dispute_id       UUID        REFERENCES match_disputes(id) ON DELETE SET NULL,
+    retain_until     TIMESTAMPTZ,
+    created_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at       TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    completed_at     TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_media_objects_owner
+    ON media_objects (owner_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_media_objects_status
+    ON media_objects (status, updated_at);
+
+CREATE TABLE IF NOT EXISTS media_upload_parts (
+    media_id    UUID        NOT NULL REFERENCES media_objects(id) ON DELETE CASCADE,
+    part_number INTEGER     NOT NULL CHECK (part_number >= 1),
+    etag        TEXT        NOT NULL,
+    size_bytes  BIGINT      NOT NULL,
+    uploaded_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (media_id, part_number)
+);
+
+-- The evidence reference a dispute was opened with on chain, and the file
+-- hashes it was computed from, in order.
+ALTER TABLE match_disputes
+    ADD COLUMN IF NOT EXISTS evidence_ref TEXT,
+    ADD COLUMN IF NOT EXISTS evidence_hashes TEXT[] NOT NULL DEFAULT '{}';
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS anticheat_audit;
+DROP TABLE IF EXISTS anticheat_signals;
+DROP TABLE IF EXISTS anticheat_detections;
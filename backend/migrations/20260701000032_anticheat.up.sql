@@ -0,0 +1,65 @@
+-- Anti-cheat pipeline: client telemetry and statistical anomaly reports are
+-- scored by detectors; players scoring above the review threshold in a match
+-- are queued as detections for a referee, and confirmed detections are
+-- flagged on the anti-cheat oracle contract.
+CREATE TABLE IF NOT EXISTS anticheat_detections (
+    id           UUID             PRIMARY KEY,
+    player_id    UUID             NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    match_id     UUID             NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    -- Highest signal score seen, 0 to 1.
+    score        DOUBLE PRECISION NOT NULL,
+    findings     JSONB            NOT NULL DEFAULT '[]'::jsonb,
+    signal_count INTEGER          NOT NULL DEFAULT 0,
+    status       TEXT             NOT NULL DEFAULT 'pending_review'
+                 CHECK (status IN ('pending_review', 'confirmed', 'dismissed')),
+    -- Oracle severity set on confirmation: 1 low, 2 medium, 3 high.
+    severity     INTEGER          CHECK (severity BETWEEN 1 AND 3),
+    reviewer_id  UUID             REFERENCES users(id) ON DELETE SET NULL,
+    review_notes TEXT,
+    reviewed_at  TIMESTAMPTZ,
+    chain_status TEXT             NOT NULL DEFAULT 'not_submitted'
+                 CHECK (chain_status IN ('not_submitted', 'off_chain', 'pending', 'submitted', 'failed')),
+    chain_tx     TEXT,
+    chain_error  TEXT,
+    created_at   TIMESTAMPTZ      NOT NULL DEFAULT NOW(),
+    updated_at   TIMESTAMPTZ      NOT NULL DEFAULT NOW()
+);
+
+-- One case per player and match awaiting review; later signals join it.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_anticheat_detections_open
+    ON anticheat_detections (player_id, match_id) WHERE status = 'pending_review';
+CREATE INDEX IF NOT EXISTS idx_anticheat_detections_queue
+    ON anticheat_detections (status, score DESC, created_at);
+
+CREATE TABLE IF NOT EXISTS anticheat_signals (
+    id           UUID             PRIMARY KEY,
+    player_id    UUID             NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    match_id     UUID             NOT NULL REFERENCES matches(id) ON DELETE CASCADE,
+    -- `telemetry` from the game client or `report` from an analytics service.
+    source       TEXT             NOT NULL CHECK (source IN ('telemetry', 'report')),
+    -- Who sent it: the player for telemetry, the operator for reports.
+    submitted_by UUID             REFERENCES users(id) ON DELETE SET NULL,
+    payload      JSONB            NOT NULL,
+    score        DOUBLE PRECISION NOT NULL,
+    findings     JSONB            NOT NULL DEFAULT '[]'::jsonb,
+    detection_id UUID             REFERENCES anticheat_detections(id) ON DELETE SET NULL,
+    created_at   TIMESTAMPTZ      NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_anticheat_signals_player
+    ON anticheat_signals (player_id, source, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_anticheat_signals_detection
+    ON anticheat_signals (detection_id);
+
+-- Every action on a detection, from queueing to the oracle submission.
+CREATE TABLE IF NOT EXISTS anticheat_audit (
+    id           UUID        PRIMARY KEY,
+    detection_id UUID        NOT NULL REFERENCES anticheat_detections(id) ON DELETE CASCADE,
+    actor_id     UUID        REFERENCES users(id) ON DELETE SET NULL,
+    action       TEXT        NOT NULL,
+    details      JSONB       NOT NULL DEFAULT '{}'::jsonb,
+    created_at   TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_anticheat_audit_detection
+    ON anticheat_audit (detection_id, created_at);
@@ -0,0 +1,14 @@
+DROP TABLE IF EXISTS feature_flags;
+ALTER TABLE match_disputes
+    DROP COLUMN IF EXISTS escalation_reason,
+    DROP COLUMN IF EXISTS escalated_by,
+    DROP COLUMN IF EXISTS escalated_at;
+ALTER TABLE audit_logs
+    DROP COLUMN IF EXISTS reason,
+    DROP COLUMN IF EXISTS after_state,
+    DROP COLUMN IF EXISTS before_state;
+DROP INDEX IF EXISTS idx_users_banned;
+ALTER TABLE users
+    DROP COLUMN IF EXISTS ban_reason,
+    DROP COLUMN IF EXISTS banned_until,
+    DROP COLUMN IF EXISTS is_banned;
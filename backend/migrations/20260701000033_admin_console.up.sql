@@ -0,0 +1,31 @@
+-- Admin console: user bans, dispute escalation and feature toggles. Every
+-- admin action is written to audit_logs with the state it changed and why.
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS is_banned    BOOLEAN NOT NULL DEFAULT FALSE,
+    -- NULL while banned means the ban is permanent.
+    ADD COLUMN IF NOT EXISTS banned_until TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS ban_reason   TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_users_banned ON users (is_banned) WHERE is_banned;
+
+-- `user_id` is the actor; before/after hold only the fields that changed.
+ALTER TABLE audit_logs
+    ADD COLUMN IF NOT EXISTS before_state JSONB,
+    ADD COLUMN IF NOT EXISTS after_state  JSONB,
+    ADD COLUMN IF NOT EXISTS reason       TEXT;
+
+-- Escalated cases go to the front of the referee queue.
+ALTER TABLE match_disputes
+    ADD COLUMN IF NOT EXISTS escalated_at      TIMESTAMPTZ,
+    ADD COLUMN IF NOT EXISTS escalated_by      UUID REFERENCES users(id) ON DELETE SET NULL,
+    ADD COLUMN IF NOT EXISTS escalation_reason TEXT;
+
+CREATE TABLE IF NOT EXISTS feature_flags (
+    id          UUID        PRIMARY KEY,
+    key         TEXT        NOT NULL UNIQUE,
+    enabled     BOOLEAN     NOT NULL DEFAULT FALSE,
+    description TEXT,
+    updated_by  UUID        REFERENCES users(id) ON DELETE SET NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,18 @@
+DROP TRIGGER IF EXISTS audit_logs_append_only ON audit_logs;
+DROP FUNCTION IF EXISTS audit_logs_append_only();
+UPDATE audit_logs SET user_id = NULL
+    WHERE user_id IS NOT NULL AND user_id NOT IN (SELECT id FROM users);
+ALTER TABLE audit_logs
+    ADD CONSTRAINT audit_logs_user_id_fkey
+    FOREIGN KEY (user_id) REFERENCES users(id) ON DELETE SET NULL;
+DROP INDEX IF EXISTS idx_audit_logs_request;
+DROP INDEX IF EXISTS idx_audit_logs_seq;
+ALTER TABLE audit_logs
+    DROP COLUMN IF EXISTS entry_hash,
+    DROP COLUMN IF EXISTS prev_hash,
+    DROP COLUMN IF EXISTS request_id,
+    DROP COLUMN IF EXISTS latency_ms,
+    DROP COLUMN IF EXISTS status_code,
+    DROP COLUMN IF EXISTS path,
+    DROP COLUMN IF EXISTS method,
+    DROP COLUMN IF EXISTS seq;
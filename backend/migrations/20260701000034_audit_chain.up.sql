@@ -0,0 +1,30 @@
+-- Tamper-evident audit trail. Every entry carries the hash of the one before
+-- it, so editing, removing or reordering an entry breaks every hash after it.
+-- Entries written before this migration are left out of the chain.
+ALTER TABLE audit_logs
+    ADD COLUMN IF NOT EXISTS seq         BIGSERIAL,
+    ADD COLUMN IF NOT EXISTS method      TEXT,
+    ADD COLUMN IF NOT EXISTS path        TEXT,
+    ADD COLUMN IF NOT EXISTS status_code SMALLINT,
+    ADD COLUMN IF NOT EXISTS latency_ms  INTEGER,
+    ADD COLUMN IF NOT EXISTS request_id  TEXT,
+    ADD COLUMN IF NOT EXISTS prev_hash   TEXT,
+    ADD COLUMN IF NOT EXISTS entry_hash  TEXT;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_audit_logs_seq ON audit_logs (seq);
+CREATE INDEX IF NOT EXISTS idx_audit_logs_request ON audit_logs (request_id)
+    WHERE request_id IS NOT NULL;
+
+-- The actor is part of the hash, so deleting a user must not null it out.
+ALTER TABLE audit_logs DROP CONSTRAINT IF EXISTS audit_logs_user_id_fkey;
+
+CREATE OR REPLACE FUNCTION audit_logs_append_only() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit_logs is append-only';
+END;
+$$ LANGUAGE plpgsql;
+
+DROP TRIGGER IF EXISTS audit_logs_append_only ON audit_logs;
+CREATE TRIGGER audit_logs_append_only
+    BEFORE UPDATE OR DELETE ON audit_logs
+    FOR EACH ROW EXECUTE FUNCTION audit_logs_append_only();
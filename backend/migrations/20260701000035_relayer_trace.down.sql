@@ -0,0 +1,3 @@
+DROP INDEX IF EXISTS idx_relayer_submissions_trace;
+ALTER TABLE relayer_submissions
+    DROP COLUMN IF EXISTS trace_id;
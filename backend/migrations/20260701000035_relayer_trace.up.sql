@@ -0,0 +1,7 @@
+-- Trace of the request that asked for each relayed invocation, so a
+-- submission can be followed back to the call that caused it.
+ALTER TABLE relayer_submissions
+    ADD COLUMN IF NOT EXISTS trace_id TEXT;
+
+CREATE INDEX IF NOT EXISTS idx_relayer_submissions_trace
+    ON relayer_submissions (trace_id) WHERE trace_id IS NOT NULL;
@@ -0,0 +1,3 @@
+DROP TABLE IF EXISTS analytics_domain_events;
+DROP TABLE IF EXISTS domain_event_receipts;
+DROP TABLE IF EXISTS domain_event_outbox;
@@ -0,0 +1,43 @@
+-- Transactional outbox for domain events. Services insert a row in the
+-- transaction that makes the change; the dispatcher moves committed rows to
+-- the Redis stream and marks them published.
+CREATE TABLE IF NOT EXISTS domain_event_outbox (
+    id              UUID PRIMARY KEY,
+    seq             BIGSERIAL NOT NULL,
+    event_type      TEXT NOT NULL,
+    payload         JSONB NOT NULL,
+    -- W3C trace context of the publishing request
+    trace           JSONB NOT NULL DEFAULT '{}',
+    created_at      TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    attempts        INTEGER NOT NULL DEFAULT 0,
+    next_attempt_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    last_error      TEXT,
+    published_at    TIMESTAMPTZ
+);
+
+CREATE INDEX IF NOT EXISTS idx_domain_event_outbox_pending
+    ON domain_event_outbox (seq) WHERE published_at IS NULL;
+
+CREATE INDEX IF NOT EXISTS idx_domain_event_outbox_published
+    ON domain_event_outbox (published_at) WHERE published_at IS NOT NULL;
+
+-- Events each consumer is done with, so redeliveries are not handled twice.
+-- A receipt with an error is an event the consumer gave up on.
+CREATE TABLE IF NOT EXISTS domain_event_receipts (
+    consumer   TEXT NOT NULL,
+    event_id   UUID NOT NULL,
+    error      TEXT,
+    handled_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (consumer, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_domain_event_receipts_failed
+    ON domain_event_receipts (consumer, handled_at) WHERE error IS NOT NULL;
+
+-- Daily counts of domain events, kept by the analytics consumer.
+CREATE TABLE IF NOT EXISTS analytics_domain_events (
+    event_type TEXT NOT NULL,
+    day        DATE NOT NULL,
+    events     BIGINT NOT NULL DEFAULT 0,
+    PRIMARY KEY (event_type, day)
+);
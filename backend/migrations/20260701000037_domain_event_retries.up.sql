@@ -0,0 +1,16 @@
+-- Events a consumer's handler failed on, waiting to be handed to it again.
+-- The broker message is acknowledged once the row is written, so retries
+-- work the same whichever broker carries the events.
+CREATE TABLE IF NOT EXISTS domain_event_retries (
+    consumer        TEXT NOT NULL,
+    event_id        UUID NOT NULL,
+    envelope        JSONB NOT NULL,
+    -- Failed deliveries so far
+    deliveries      INTEGER NOT NULL,
+    last_error      TEXT NOT NULL,
+    next_attempt_at TIMESTAMPTZ NOT NULL,
+    PRIMARY KEY (consumer, event_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_domain_event_retries_due
+    ON domain_event_retries (consumer, next_attempt_at);
@@ -0,0 +1,446 @@
+//! Email verification and password reset.
+//!
+//! Both flows mail a link carrying a single-use token: random bytes plus an
+//! HMAC binding them to the flow, so forged or cross-flow tokens are turned
+//! away before the database is asked. Only the token's SHA-256 is stored. A
+//! token is honoured once, before it expires, and only while the user still
+//! has the address it was sent to; asking for a new one retires the old.
+//!
+//! Mails are throttled per user and flow: one a minute and
+//! [`MAX_MAILS_PER_HOUR`] an hour.
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::service::mailer::{EmailTemplate, Mailer};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine;
+use bcrypt::{hash, DEFAULT_COST};
+use chrono::{Duration, Utc};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+type HmacSha256 = Hmac<Sha256>;
+
+const MAX_MAILS_PER_HOUR: u32 = 5;
+const RESEND_INTERVAL_SECS: u64 = 60;
+const NONCE_LEN: usize = 24;
+const SIGNATURE_LEN: usize = 16;
+
+/// What an emailed token is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EmailTokenPurpose {
+    VerifyEmail,
+    ResetPassword,
+}
+
+impl EmailTokenPurpose {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EmailTokenPurpose::VerifyEmail => "verify_email",
+            EmailTokenPurpose::ResetPassword => "reset_password",
+        }
+    }
+
+    fn lifetime(&self) -> Duration {
+        match self {
+            EmailTokenPurpose::VerifyEmail => Duration::hours(24),
+            EmailTokenPurpose::ResetPassword => Duration::minutes(30),
+        }
+    }
+
+    /// Frontend page the emailed link opens.
+    fn page(&self) -> &'static str {
+        match self {
+            EmailTokenPurpose::VerifyEmail => "verify-email",
+            EmailTokenPurpose::ResetPassword => "reset-password",
+        }
+    }
+}
+
+#[derive(Debug, sqlx::FromRow)]
+struct Recipient {
+    id: Uuid,
+    username: String,
+    email: Option<String>,
+    is_verified: Option<bool>,
+}
+
+/// Signs emailed tokens.
+struct TokenSigner {
+    key: Vec<u8>,
+}
+
+impl TokenSigner {
+    fn new(secret: &str) -> Self {
+        // A key of its own, so tokens can't be confused with other HMACs of
+        // the secret.
+        let mut mac =
+            HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+        mac.update(b"arenax-email-tokens");
+        Self {
+            key: mac.finalize().into_bytes().to_vec(),
+        }
+    }
+
+    fn signature(&self, purpose: EmailTokenPurpose, nonce: &str) -> String {
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(purpose.as_str().as_bytes());
+        mac.update(b".");
+        mac.update(nonce.as_bytes());
+        URL_SAFE_NO_PAD.encode(&mac.finalize().into_bytes()[..SIGNATURE_LEN])
+    }
+
+    fn issue(&self, purpose: EmailTokenPurpose) -> String {
+        let mut bytes = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        let nonce = URL_SAFE_NO_PAD.encode(bytes);
+        let signature = self.signature(purpose, &nonce);
+        format!("{}.{}", nonce, signature)
+    }
+
+    fn verify(&self, purpose: EmailTokenPurpose, token: &str) -> bool {
+        let Some((nonce, signature)) = token.split_once('.') else {
+            return false;
+        };
+        let Ok(signature) = URL_SAFE_NO_PAD.decode(signature) else {
+            return false;
+        };
+        if signature.len() != SIGNATURE_LEN {
+            return false;
+        }
+        let mut mac =
+            HmacSha256::new_from_slice(&self.key).expect("HMAC accepts keys of any length");
+        mac.update(purpose.as_str().as_bytes());
+        mac.update(b".");
+        mac.update(nonce.as_bytes());
+        mac.verify_truncated_left(&signature).is_ok()
+    }
+}
+
+fn token_hash(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// Mails verification and password reset links and redeems their tokens.
+pub struct AccountEmailService {
+    db_pool: DbPool,
+    redis: ConnectionManager,
+    mailer: Arc<Mailer>,
+    signer: TokenSigner,
+    app_url: String,
+}
+
+impl AccountEmailService {
+    /// `secret` keys the token signatures; `app_url` is the frontend the
+    /// links point to.
+    pub fn new(
+        db_pool: DbPool,
+        redis: ConnectionManager,
+        mailer: Arc<Mailer>,
+        secret: &str,
+        app_url: String,
+    ) -> Self {
+        Self {
+            db_pool,
+            redis,
+            mailer,
+            signer: TokenSigner::new(secret),
+            app_url,
+        }
+    }
+
+    /// Mail the user a link confirming their email address.
+    pub async fn send_verification(&self, user_id: Uuid) -> Result<(), ApiError> {
+        let user = sqlx::query_as::<_, Recipient>(
+            "SELECT id, username, email, is_verified FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+        let email = user
+            .email
+            .clone()
+            .ok_or_else(|| ApiError::bad_request("No email address on this account"))?;
+        if user.is_verified.unwrap_or(false) {
+            return Err(ApiError::bad_request("Email address is already verified"));
+        }
+
+        let purpose = EmailTokenPurpose::VerifyEmail;
+        if !self.throttle(purpose, user.id).await? {
+            return Err(ApiError::TooManyRequests(
+                "A verification email was sent recently; try again later".to_string(),
+            ));
+        }
+        let link = self.issue(purpose, user.id, &email).await?;
+        self.mail(
+            &email,
+            &EmailTemplate::VerifyEmail {
+                username: user.username,
+                link,
+            },
+        )
+        .await
+    }
+
+    /// Confirm the email address a verification token was sent to.
+    pub async fn verify_email(&self, token: &str) -> Result<Uuid, ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let (user_id, email) = self
+            .consume(&mut tx, EmailTokenPurpose::VerifyEmail, token)
+            .await?;
+
+        let updated = sqlx::query(
+            "UPDATE users SET is_verified = true, updated_at = NOW()
+             WHERE id = $1 AND email = $2",
+        )
+        .bind(user_id)
+        .bind(&email)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        if updated.rows_affected() == 0 {
+            return Err(invalid_token());
+        }
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(user_id = %user_id, "Email address verified");
+        Ok(user_id)
+    }
+
+    /// Mail a password reset link to the user with `email`, if there is one.
+    ///
+    /// Succeeds either way, and also when throttled, so the endpoint does not
+    /// tell which addresses have accounts.
+    pub async fn request_password_reset(&self, email: &str) -> Result<(), ApiError> {
+        let user = sqlx::query_as::<_, Recipient>(
+            "SELECT id, username, email, is_verified FROM users
+             WHERE LOWER(email) = LOWER($1) AND is_active = true",
+        )
+        .bind(email.trim())
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        let Some(Recipient {
+            id,
+            username,
+            email: Some(email),
+            ..
+        }) = user
+        else {
+            return Ok(());
+        };
+
+        let purpose = EmailTokenPurpose::ResetPassword;
+        if !self.throttle(purpose, id).await? {
+            warn!(user_id = %id, "Password reset email throttled");
+            return Ok(());
+        }
+        let link = self.issue(purpose, id, &email).await?;
+        self.mail(&email, &EmailTemplate::PasswordReset { username, link })
+            .await
+    }
+
+    /// Set a new password with a reset token. Returns the user, whose
+    /// sessions the caller should revoke.
+    pub async fn reset_password(&self, token: &str, new_password: &str) -> Result<Uuid, ApiError> {
+        if new_password.len() < 8 {
+            return Err(ApiError::bad_request(
+                "Password must be at least 8 characters",
+            ));
+        }
+        let password_hash = hash(new_password, DEFAULT_COST)
+            .map_err(|e| ApiError::internal_error(format!("Password hashing failed: {}", e)))?;
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let (user_id, email) = self
+            .consume(&mut tx, EmailTokenPurpose::ResetPassword, token)
+            .await?;
+
+        // Receiving the link proves the address, too.
+        let user = sqlx::query_as::<_, Recipient>(
+            "UPDATE users SET password_hash = $1, is_verified = true, updated_at = NOW()
+             WHERE id = $2 AND email = $3
+             RETURNING id, username, email, is_verified",
+        )
+        .bind(&password_hash)
+        .bind(user_id)
+        .bind(&email)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(invalid_token)?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(user_id = %user_id, "Password reset");
+        if let Err(e) = self
+            .mail(
+                &email,
+                &EmailTemplate::PasswordChanged {
+                    username: user.username,
+                },
+            )
+            .await
+        {
+            warn!(user_id = %user_id, error = %e, "Password change notice not sent");
+        }
+        Ok(user.id)
+    }
+
+    /// Store a fresh token for `email`, retiring the user's unused ones for
+    /// the same purpose, and return the link carrying it.
+    async fn issue(
+        &self,
+        purpose: EmailTokenPurpose,
+        user_id: Uuid,
+        email: &str,
+    ) -> Result<String, ApiError> {
+        let token = self.signer.issue(purpose);
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        sqlx::query(
+            "DELETE FROM email_tokens
+             WHERE user_id = $1 AND purpose = $2 AND consumed_at IS NULL",
+        )
+        .bind(user_id)
+        .bind(purpose.as_str())
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        sqlx::query(
+            "INSERT INTO email_tokens (user_id, purpose, token_hash, email, expires_at)
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(user_id)
+        .bind(purpose.as_str())
+        .bind(token_hash(&token))
+        .bind(email)
+        .bind(Utc::now() + purpose.lifetime())
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        Ok(format!(
+            "{}/{}?token={}",
+            self.app_url,
+            purpose.page(),
+            token
+        ))
+    }
+
+    /// Spend a token, returning the user and address it was sent to.
+    async fn consume(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        purpose: EmailTokenPurpose,
+        token: &str,
+    ) -> Result<(Uuid, String), ApiError> {
+        let token = token.trim();
+        if !self.signer.verify(purpose, token) {
+            return Err(invalid_token());
+        }
+
+        sqlx::query_as::<_, (Uuid, String)>(
+            "UPDATE email_tokens SET consumed_at = NOW()
+             WHERE token_hash = $1 AND purpose = $2
+               AND consumed_at IS NULL AND expires_at > NOW()
+             RETURNING user_id, email",
+        )
+        .bind(token_hash(token))
+        .bind(purpose.as_str())
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(invalid_token)
+    }
+
+    /// Count a mail against the user's allowance; `false` once it is used up.
+    async fn throttle(&self, purpose: EmailTokenPurpose, user_id: Uuid) -> Result<bool, ApiError> {
+        let mut conn = self.redis.clone();
+        let recent_key = format!("email:recent:{}:{}", purpose.as_str(), user_id);
+        let first: bool = redis::cmd("SET")
+            .arg(&recent_key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(RESEND_INTERVAL_SECS)
+            .query_async::<Option<String>>(&mut conn)
+            .await
+            .map_err(|e| ApiError::RedisError(e.to_string()))?
+            .is_some();
+        if !first {
+            return Ok(false);
+        }
+
+        let hourly_key = format!("email:hourly:{}:{}", purpose.as_str(), user_id);
+        let sent: u32 = conn
+            .incr(&hourly_key, 1)
+            .await
+            .map_err(|e| ApiError::RedisError(e.to_string()))?;
+        if sent == 1 {
+            conn.expire::<_, ()>(&hourly_key, 3600)
+                .await
+                .map_err(|e| ApiError::RedisError(e.to_string()))?;
+        }
+        Ok(sent <= MAX_MAILS_PER_HOUR)
+    }
+
+    async fn mail(&self, to: &str, template: &EmailTemplate) -> Result<(), ApiError> {
+        self.mailer
+            .send(to, template)
+            .await
+            .map_err(|e| ApiError::internal_error(format!("Email delivery failed: {}", e)))
+    }
+}
+
+fn invalid_token() -> ApiError {
+    ApiError::bad_request("Invalid or expired link")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_token_signature() {
+        let signer = TokenSigner::new("secret");
+        let token = signer.issue(EmailTokenPurpose::ResetPassword);
+
+        assert!(signer.verify(EmailTokenPurpose::ResetPassword, &token));
+        // Bound to the flow and the key.
+        assert!(!signer.verify(EmailTokenPurpose::VerifyEmail, &token));
+        assert!(!TokenSigner::new("other").verify(EmailTokenPurpose::ResetPassword, &token));
+
+        let (nonce, signature) = token.split_once('.').unwrap();
+        assert!(!signer.verify(EmailTokenPurpose::ResetPassword, nonce));
+        assert!(!signer.verify(
+            EmailTokenPurpose::ResetPassword,
+            &format!("{}x.{}", nonce, signature)
+        ));
+        assert!(!signer.verify(
+            EmailTokenPurpose::ResetPassword,
+            &format!("{}.{}", nonce, &signature[..4])
+        ));
+    }
+}
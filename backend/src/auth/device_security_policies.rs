@@ -0,0 +1,829 @@
+//! Sign-in risk scoring and the admin-managed policies acting on it.
+//!
+//! Each password sign-in is scored from 0 to 100 from the device it comes
+//! from (unknown or untrusted fingerprint), where it comes from compared with
+//! the last successful sign-in (impossible travel, new country) and the failed
+//! attempts since then. Enabled [`SecurityPolicy`] rules are then walked by
+//! priority: matching `notify` rules raise a security alert on the device, and
+//! the first other matching rule decides whether the sign-in is allowed, needs
+//! a second factor or is blocked. Sign-ins no rule matches are allowed.
+//!
+//! Locations come from the `CF-IPCountry`, `CF-IPLatitude` and
+//! `CF-IPLongitude` headers set by the edge; without them only the device and
+//! failure history are scored.
+
+use crate::api_error::ApiError;
+use crate::auth::device_service::{
+    AlertSeverity, AlertType, Device, DeviceError, DeviceInfo, DeviceService, SecurityAlert,
+};
+use crate::db::DbPool;
+use actix_web::HttpRequest;
+use chrono::{DateTime, Duration, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Faster than this (km/h) between two sign-ins is impossible travel.
+const MAX_TRAVEL_SPEED_KMH: f64 = 900.0;
+/// Shorter hops are ignored; IP geolocation is not that precise.
+const MIN_TRAVEL_DISTANCE_KM: f64 = 300.0;
+/// How far back failed attempts count, at most back to the last success.
+const FAILURE_WINDOW_MINUTES: i64 = 60;
+const POINTS_PER_FAILURE: u32 = 10;
+const MAX_FAILURE_POINTS: u32 = 40;
+const MAX_SCORE: u32 = 100;
+
+/// Something that made a sign-in riskier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskFactor {
+    /// The device fingerprint was never used by this user.
+    NewDevice,
+    /// A known device the user has not marked as trusted.
+    UntrustedDevice,
+    /// Too far from the last sign-in to have travelled in between.
+    ImpossibleTravel,
+    /// A different country than the last sign-in.
+    NewCountry,
+    /// Failed attempts since the last successful sign-in.
+    FailedAttempts,
+}
+
+impl RiskFactor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RiskFactor::NewDevice => "new_device",
+            RiskFactor::UntrustedDevice => "untrusted_device",
+            RiskFactor::ImpossibleTravel => "impossible_travel",
+            RiskFactor::NewCountry => "new_country",
+            RiskFactor::FailedAttempts => "failed_attempts",
+        }
+    }
+}
+
+/// What a policy does with a matching sign-in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PolicyAction {
+    #[serde(rename = "allow")]
+    Allow,
+    #[serde(rename = "require_2fa")]
+    RequireTwoFactor,
+    #[serde(rename = "block")]
+    Block,
+    #[serde(rename = "notify")]
+    Notify,
+}
+
+impl PolicyAction {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PolicyAction::Allow => "allow",
+            PolicyAction::RequireTwoFactor => "require_2fa",
+            PolicyAction::Block => "block",
+            PolicyAction::Notify => "notify",
+        }
+    }
+}
+
+impl fmt::Display for PolicyAction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PolicyAction {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "allow" => Ok(PolicyAction::Allow),
+            "require_2fa" => Ok(PolicyAction::RequireTwoFactor),
+            "block" => Ok(PolicyAction::Block),
+            "notify" => Ok(PolicyAction::Notify),
+            other => Err(ApiError::bad_request(format!(
+                "Unknown policy action: {}",
+                other
+            ))),
+        }
+    }
+}
+
+/// Where a sign-in comes from, as far as the edge could tell.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GeoLocation {
+    /// ISO 3166-1 alpha-2 country code.
+    pub country: Option<String>,
+    pub latitude: Option<f64>,
+    pub longitude: Option<f64>,
+}
+
+impl GeoLocation {
+    pub fn from_request(req: &HttpRequest) -> Self {
+        let header = |name: &str| {
+            req.headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(|v| v.trim().to_string())
+                .filter(|v| !v.is_empty())
+        };
+        let coordinate = |name: &str| header(name).and_then(|v| v.parse::<f64>().ok());
+
+        Self {
+            // "XX" is Cloudflare's unknown country.
+            country: header("CF-IPCountry")
+                .map(|c| c.to_ascii_uppercase())
+                .filter(|c| c.len() == 2 && c != "XX"),
+            latitude: coordinate("CF-IPLatitude").filter(|v| (-90.0..=90.0).contains(v)),
+            longitude: coordinate("CF-IPLongitude").filter(|v| (-180.0..=180.0).contains(v)),
+        }
+    }
+
+    fn coordinates(&self) -> Option<(f64, f64)> {
+        Some((self.latitude?, self.longitude?))
+    }
+}
+
+/// The device and location a password sign-in is made from.
+#[derive(Debug, Clone)]
+pub struct LoginContext {
+    pub device: DeviceInfo,
+    pub location: GeoLocation,
+}
+
+/// Movement since the previous successful sign-in.
+#[derive(Debug, Clone, Copy)]
+pub struct Travel {
+    pub distance_km: f64,
+    pub hours: f64,
+}
+
+impl Travel {
+    fn is_impossible(&self) -> bool {
+        // Within a minute of each other counts as simultaneous.
+        self.distance_km >= MIN_TRAVEL_DISTANCE_KM
+            && self.distance_km / self.hours.max(1.0 / 60.0) > MAX_TRAVEL_SPEED_KMH
+    }
+}
+
+/// What is known about a sign-in before scoring it.
+#[derive(Debug, Clone, Default)]
+pub struct RiskSignals {
+    /// `None` for a new device, otherwise whether it is trusted.
+    pub known_device: Option<bool>,
+    pub travel: Option<Travel>,
+    pub country_changed: bool,
+    pub recent_failures: u32,
+}
+
+/// A sign-in's risk score (0-100) and the factors behind it.
+#[derive(Debug, Clone, Serialize)]
+pub struct RiskAssessment {
+    pub score: u32,
+    pub factors: Vec<RiskFactor>,
+}
+
+impl RiskAssessment {
+    pub fn from_signals(signals: &RiskSignals) -> Self {
+        let mut score = 0;
+        let mut factors = Vec::new();
+        let mut add = |factor, points| {
+            factors.push(factor);
+            score += points;
+        };
+
+        match signals.known_device {
+            None => add(RiskFactor::NewDevice, 35),
+            Some(false) => add(RiskFactor::UntrustedDevice, 10),
+            Some(true) => {}
+        }
+        // A new country is part of impossible travel, not a separate risk.
+        if signals.travel.is_some_and(|t| t.is_impossible()) {
+            add(RiskFactor::ImpossibleTravel, 45);
+        } else if signals.country_changed {
+            add(RiskFactor::NewCountry, 20);
+        }
+        if signals.recent_failures > 0 {
+            add(
+                RiskFactor::FailedAttempts,
+                (signals.recent_failures * POINTS_PER_FAILURE).min(MAX_FAILURE_POINTS),
+            );
+        }
+
+        Self {
+            score: score.min(MAX_SCORE),
+            factors,
+        }
+    }
+
+    fn severity(&self) -> AlertSeverity {
+        match self.score {
+            70.. => AlertSeverity::High,
+            40.. => AlertSeverity::Medium,
+            _ => AlertSeverity::Low,
+        }
+    }
+
+    fn alert_type(&self) -> AlertType {
+        if self.factors.contains(&RiskFactor::ImpossibleTravel)
+            || self.factors.contains(&RiskFactor::NewCountry)
+        {
+            AlertType::SuspiciousLocation
+        } else if self.factors.contains(&RiskFactor::FailedAttempts) {
+            AlertType::MultipleFailedLogins
+        } else if self.factors.contains(&RiskFactor::NewDevice) {
+            AlertType::DeviceMismatch
+        } else {
+            AlertType::UnusualActivity
+        }
+    }
+}
+
+/// Admin-managed rule mapping risky sign-ins to an action.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct SecurityPolicy {
+    pub id: Uuid,
+    pub name: String,
+    pub description: Option<String>,
+    pub min_score: i32,
+    pub max_score: i32,
+    /// Only sign-ins with this [`RiskFactor`] match when set.
+    pub factor: Option<String>,
+    pub action: String,
+    pub priority: i32,
+    pub enabled: bool,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl SecurityPolicy {
+    fn matches(&self, assessment: &RiskAssessment) -> bool {
+        let score = assessment.score as i32;
+        self.enabled
+            && (self.min_score..=self.max_score).contains(&score)
+            && self
+                .factor
+                .as_deref()
+                .is_none_or(|factor| assessment.factors.iter().any(|f| f.as_str() == factor))
+    }
+}
+
+/// Body of `POST` and `PUT /api/auth/admin/security-policies`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SecurityPolicyRequest {
+    pub name: String,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub min_score: Option<i32>,
+    #[serde(default)]
+    pub max_score: Option<i32>,
+    #[serde(default)]
+    pub factor: Option<RiskFactor>,
+    pub action: PolicyAction,
+    #[serde(default)]
+    pub priority: Option<i32>,
+    #[serde(default)]
+    pub enabled: Option<bool>,
+}
+
+impl SecurityPolicyRequest {
+    fn validate(&self) -> Result<(i32, i32), ApiError> {
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > 100 {
+            return Err(ApiError::bad_request(
+                "Policy name must be 1 to 100 characters",
+            ));
+        }
+        let min_score = self.min_score.unwrap_or(0);
+        let max_score = self.max_score.unwrap_or(MAX_SCORE as i32);
+        let range = 0..=MAX_SCORE as i32;
+        if !range.contains(&min_score) || !range.contains(&max_score) || min_score > max_score {
+            return Err(ApiError::bad_request(
+                "Scores must satisfy 0 <= min_score <= max_score <= 100",
+            ));
+        }
+        Ok((min_score, max_score))
+    }
+}
+
+/// Outcome of the policies for one sign-in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PolicyDecision {
+    /// Never [`PolicyAction::Notify`].
+    pub action: PolicyAction,
+    /// The rule that decided the action, if any.
+    pub policy_id: Option<Uuid>,
+    /// Names of the matching `notify` rules.
+    pub notify: Vec<String>,
+}
+
+impl PolicyDecision {
+    /// Apply `policies`, ordered by priority, to an assessment.
+    pub fn from_policies(policies: &[SecurityPolicy], assessment: &RiskAssessment) -> Self {
+        let mut notify = Vec::new();
+        for policy in policies.iter().filter(|p| p.matches(assessment)) {
+            match policy.action.parse::<PolicyAction>() {
+                Ok(PolicyAction::Notify) => notify.push(policy.name.clone()),
+                Ok(action) => {
+                    return Self {
+                        action,
+                        policy_id: Some(policy.id),
+                        notify,
+                    }
+                }
+                Err(_) => warn!(policy = %policy.name, action = %policy.action, "Skipping policy"),
+            }
+        }
+        Self {
+            action: PolicyAction::Allow,
+            policy_id: None,
+            notify,
+        }
+    }
+}
+
+/// A scored sign-in and what the policies decided for it.
+#[derive(Debug, Clone)]
+pub struct LoginEvaluation {
+    pub assessment: RiskAssessment,
+    pub decision: PolicyDecision,
+    /// The user's device with this fingerprint, if already registered.
+    pub device: Option<Device>,
+}
+
+/// How a sign-in attempt ended, for the failure history.
+#[derive(Debug, Clone, Copy)]
+pub enum LoginOutcome {
+    Succeeded,
+    Failed,
+    Blocked,
+}
+
+impl LoginOutcome {
+    fn as_str(&self) -> &'static str {
+        match self {
+            LoginOutcome::Succeeded => "succeeded",
+            LoginOutcome::Failed => "failed",
+            LoginOutcome::Blocked => "blocked",
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct LastLogin {
+    country: Option<String>,
+    latitude: Option<f64>,
+    longitude: Option<f64>,
+    created_at: DateTime<Utc>,
+}
+
+/// Great-circle distance between two points in kilometres.
+fn haversine_km((lat1, lon1): (f64, f64), (lat2, lon2): (f64, f64)) -> f64 {
+    const EARTH_RADIUS_KM: f64 = 6371.0;
+    let (dlat, dlon) = ((lat2 - lat1).to_radians(), (lon2 - lon1).to_radians());
+    let a = (dlat / 2.0).sin().powi(2)
+        + lat1.to_radians().cos() * lat2.to_radians().cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * EARTH_RADIUS_KM * a.sqrt().asin()
+}
+
+/// Scores password sign-ins and applies the stored policies to them.
+pub struct DeviceSecurityPolicies {
+    db_pool: DbPool,
+    devices: Arc<DeviceService>,
+}
+
+impl DeviceSecurityPolicies {
+    pub fn new(db_pool: DbPool, devices: Arc<DeviceService>) -> Self {
+        Self { db_pool, devices }
+    }
+
+    /// Score a sign-in by `user_id` with correct credentials and decide on it.
+    ///
+    /// Sign-ins from a blocked device are blocked whatever the policies say.
+    pub async fn evaluate(
+        &self,
+        user_id: Uuid,
+        context: &LoginContext,
+    ) -> Result<LoginEvaluation, ApiError> {
+        let fingerprint = self.devices.generate_fingerprint(&context.device);
+        let device = sqlx::query_as::<_, Device>(
+            "SELECT * FROM devices WHERE user_id = $1 AND fingerprint = $2",
+        )
+        .bind(user_id)
+        .bind(&fingerprint)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let last = sqlx::query_as::<_, LastLogin>(
+            "SELECT country, latitude, longitude, created_at FROM login_risk_events
+             WHERE user_id = $1 AND outcome = 'succeeded'
+             ORDER BY created_at DESC LIMIT 1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let window_start = Utc::now() - Duration::minutes(FAILURE_WINDOW_MINUTES);
+        let since = last
+            .as_ref()
+            .map_or(window_start, |l| l.created_at.max(window_start));
+        let recent_failures: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM login_risk_events
+             WHERE user_id = $1 AND outcome = 'failed' AND created_at > $2",
+        )
+        .bind(user_id)
+        .bind(since)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let location = &context.location;
+        let signals = RiskSignals {
+            known_device: device.as_ref().map(|d| d.is_trusted),
+            travel: last.as_ref().and_then(|l| {
+                let from = (l.latitude?, l.longitude?);
+                Some(Travel {
+                    distance_km: haversine_km(from, location.coordinates()?),
+                    hours: (Utc::now() - l.created_at).num_seconds() as f64 / 3600.0,
+                })
+            }),
+            country_changed: match (
+                last.as_ref().and_then(|l| l.country.as_deref()),
+                &location.country,
+            ) {
+                (Some(previous), Some(current)) => previous != current,
+                _ => false,
+            },
+            recent_failures: recent_failures as u32,
+        };
+        let assessment = RiskAssessment::from_signals(&signals);
+
+        let decision = if device.as_ref().is_some_and(|d| d.is_blocked) {
+            PolicyDecision {
+                action: PolicyAction::Block,
+                policy_id: None,
+                notify: Vec::new(),
+            }
+        } else {
+            PolicyDecision::from_policies(&self.enabled_policies().await?, &assessment)
+        };
+
+        Ok(LoginEvaluation {
+            assessment,
+            decision,
+            device,
+        })
+    }
+
+    /// Count a failed attempt (bad password or second factor) against the user.
+    pub async fn record_failure(&self, user_id: Uuid, context: &LoginContext) {
+        if let Err(e) = self
+            .record(user_id, context, None, LoginOutcome::Failed)
+            .await
+        {
+            warn!(user_id = %user_id, error = %e, "Failed to record failed sign-in");
+        }
+    }
+
+    /// Record a blocked sign-in, alerting on the device when it is known.
+    pub async fn record_blocked(
+        &self,
+        user_id: Uuid,
+        context: &LoginContext,
+        evaluation: &LoginEvaluation,
+    ) -> Result<(), ApiError> {
+        self.record(user_id, context, Some(evaluation), LoginOutcome::Blocked)
+            .await?;
+        warn!(
+            user_id = %user_id,
+            score = evaluation.assessment.score,
+            "Sign-in blocked by device security policy"
+        );
+        if let Some(device) = &evaluation.device {
+            self.alert(device, evaluation, "Sign-in blocked by security policy")
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Record a successful sign-in, registering its device and raising the
+    /// alert asked for by `notify` policies. Returns the device, unless the
+    /// user already has as many as allowed.
+    pub async fn record_success(
+        &self,
+        user_id: Uuid,
+        context: &LoginContext,
+        evaluation: &LoginEvaluation,
+    ) -> Result<Option<Device>, ApiError> {
+        self.record(user_id, context, Some(evaluation), LoginOutcome::Succeeded)
+            .await?;
+
+        let device = match self
+            .devices
+            .register_device(user_id, context.device.clone(), None)
+            .await
+        {
+            Ok(device) => device,
+            // The sign-in is not refused for it; the device stays unknown.
+            Err(DeviceError::DeviceLimitExceeded(max)) => {
+                warn!(user_id = %user_id, max, "Device limit reached, sign-in device unregistered");
+                return Ok(None);
+            }
+            Err(e) => return Err(e.into()),
+        };
+
+        if !evaluation.decision.notify.is_empty() {
+            self.alert(&device, evaluation, "Risky sign-in").await?;
+        }
+        Ok(Some(device))
+    }
+
+    async fn record(
+        &self,
+        user_id: Uuid,
+        context: &LoginContext,
+        evaluation: Option<&LoginEvaluation>,
+        outcome: LoginOutcome,
+    ) -> Result<(), ApiError> {
+        let factors: Vec<&str> = evaluation
+            .map(|e| e.assessment.factors.iter().map(|f| f.as_str()).collect())
+            .unwrap_or_default();
+
+        sqlx::query(
+            "INSERT INTO login_risk_events (
+                user_id, fingerprint, ip_address, country, latitude, longitude,
+                outcome, score, factors, action, policy_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+        )
+        .bind(user_id)
+        .bind(self.devices.generate_fingerprint(&context.device))
+        .bind(&context.device.ip_address)
+        .bind(&context.location.country)
+        .bind(context.location.latitude)
+        .bind(context.location.longitude)
+        .bind(outcome.as_str())
+        .bind(evaluation.map(|e| e.assessment.score as i32))
+        .bind(serde_json::json!(factors))
+        .bind(evaluation.map(|e| e.decision.action.as_str()))
+        .bind(evaluation.and_then(|e| e.decision.policy_id))
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        Ok(())
+    }
+
+    async fn alert(
+        &self,
+        device: &Device,
+        evaluation: &LoginEvaluation,
+        message: &str,
+    ) -> Result<(), ApiError> {
+        let assessment = &evaluation.assessment;
+        let alert = SecurityAlert {
+            device_id: device.id,
+            user_id: device.user_id,
+            alert_type: assessment.alert_type(),
+            severity: assessment.severity(),
+            message: format!("{} (risk score {})", message, assessment.score),
+            details: Some(serde_json::json!({
+                "score": assessment.score,
+                "factors": assessment.factors,
+                "action": evaluation.decision.action,
+                "policies": evaluation.decision.notify,
+            })),
+            created_at: Utc::now(),
+        };
+        self.devices.store_security_alert(&alert).await?;
+        Ok(())
+    }
+
+    async fn enabled_policies(&self) -> Result<Vec<SecurityPolicy>, ApiError> {
+        sqlx::query_as::<_, SecurityPolicy>(
+            "SELECT * FROM device_security_policies WHERE enabled
+             ORDER BY priority, created_at",
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    // ── Admin CRUD ───────────────────────────────────────────────────────────
+
+    pub async fn list_policies(&self) -> Result<Vec<SecurityPolicy>, ApiError> {
+        sqlx::query_as::<_, SecurityPolicy>(
+            "SELECT * FROM device_security_policies ORDER BY priority, created_at",
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    pub async fn create_policy(
+        &self,
+        admin_id: Uuid,
+        request: SecurityPolicyRequest,
+    ) -> Result<SecurityPolicy, ApiError> {
+        let (min_score, max_score) = request.validate()?;
+
+        let policy = sqlx::query_as::<_, SecurityPolicy>(
+            "INSERT INTO device_security_policies (
+                name, description, min_score, max_score, factor, action, priority,
+                enabled, created_by
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            ON CONFLICT (name) DO NOTHING
+            RETURNING *",
+        )
+        .bind(request.name.trim())
+        .bind(&request.description)
+        .bind(min_score)
+        .bind(max_score)
+        .bind(request.factor.map(|f| f.as_str()))
+        .bind(request.action.as_str())
+        .bind(request.priority.unwrap_or(100))
+        .bind(request.enabled.unwrap_or(true))
+        .bind(admin_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::conflict("A policy with this name already exists"))?;
+
+        info!(policy_id = %policy.id, admin_id = %admin_id, "Device security policy created");
+        Ok(policy)
+    }
+
+    /// Replace a policy's settings.
+    pub async fn update_policy(
+        &self,
+        policy_id: Uuid,
+        request: SecurityPolicyRequest,
+    ) -> Result<SecurityPolicy, ApiError> {
+        let (min_score, max_score) = request.validate()?;
+
+        let policy = sqlx::query_as::<_, SecurityPolicy>(
+            "UPDATE device_security_policies
+             SET name = $2, description = $3, min_score = $4, max_score = $5, factor = $6,
+                 action = $7, priority = $8, enabled = $9, updated_at = NOW()
+             WHERE id = $1
+             RETURNING *",
+        )
+        .bind(policy_id)
+        .bind(request.name.trim())
+        .bind(&request.description)
+        .bind(min_score)
+        .bind(max_score)
+        .bind(request.factor.map(|f| f.as_str()))
+        .bind(request.action.as_str())
+        .bind(request.priority.unwrap_or(100))
+        .bind(request.enabled.unwrap_or(true))
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(|e| match &e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                ApiError::conflict("A policy with this name already exists")
+            }
+            _ => ApiError::database_error(e),
+        })?
+        .ok_or_else(|| ApiError::not_found("Security policy not found"))?;
+
+        info!(policy_id = %policy.id, "Device security policy updated");
+        Ok(policy)
+    }
+
+    pub async fn delete_policy(&self, policy_id: Uuid) -> Result<(), ApiError> {
+        let deleted = sqlx::query("DELETE FROM device_security_policies WHERE id = $1")
+            .bind(policy_id)
+            .execute(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?
+            .rows_affected();
+        if deleted == 0 {
+            return Err(ApiError::not_found("Security policy not found"));
+        }
+
+        info!(policy_id = %policy_id, "Device security policy deleted");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(action: &str, min_score: i32, factor: Option<&str>, priority: i32) -> SecurityPolicy {
+        SecurityPolicy {
+            id: Uuid::new_v4(),
+            name: format!("{}-{}", action, priority),
+            description: None,
+            min_score,
+            max_score: 100,
+            factor: factor.map(str::to_string),
+            action: action.to_string(),
+            priority,
+            enabled: true,
+            created_by: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+        }
+    }
+
+    #[test]
+    fn test_scoring() {
+        let trusted = RiskAssessment::from_signals(&RiskSignals {
+            known_device: Some(true),
+            ..Default::default()
+        });
+        assert_eq!(trusted.score, 0);
+        assert!(trusted.factors.is_empty());
+
+        // Paris to New York half an hour apart, from a new device.
+        let travel = Travel {
+            distance_km: haversine_km((48.86, 2.35), (40.71, -74.01)),
+            hours: 0.5,
+        };
+        assert!((5800.0..5900.0).contains(&travel.distance_km));
+        let risky = RiskAssessment::from_signals(&RiskSignals {
+            known_device: None,
+            travel: Some(travel),
+            country_changed: true,
+            recent_failures: 7,
+        });
+        assert_eq!(
+            risky.factors,
+            vec![
+                RiskFactor::NewDevice,
+                RiskFactor::ImpossibleTravel,
+                RiskFactor::FailedAttempts
+            ]
+        );
+        assert_eq!(risky.score, 100);
+
+        // The same trip a day later is only a new country.
+        let later = RiskAssessment::from_signals(&RiskSignals {
+            known_device: Some(false),
+            travel: Some(Travel {
+                hours: 24.0,
+                ..travel
+            }),
+            country_changed: true,
+            recent_failures: 0,
+        });
+        assert_eq!(
+            later.factors,
+            vec![RiskFactor::UntrustedDevice, RiskFactor::NewCountry]
+        );
+        assert_eq!(later.score, 30);
+    }
+
+    #[test]
+    fn test_policy_decision() {
+        let policies = vec![
+            policy("notify", 0, Some("new_device"), 5),
+            policy("block", 90, None, 10),
+            policy("require_2fa", 50, None, 20),
+            policy("allow", 0, None, 30),
+        ];
+        let assessment = |score, factors| RiskAssessment { score, factors };
+
+        let decision =
+            PolicyDecision::from_policies(&policies, &assessment(60, vec![RiskFactor::NewDevice]));
+        assert_eq!(decision.action, PolicyAction::RequireTwoFactor);
+        assert_eq!(decision.policy_id, Some(policies[2].id));
+        assert_eq!(decision.notify, vec![policies[0].name.clone()]);
+
+        let decision = PolicyDecision::from_policies(&policies, &assessment(95, vec![]));
+        assert_eq!(decision.action, PolicyAction::Block);
+        assert!(decision.notify.is_empty());
+
+        // Disabled and unmatched rules fall through to allow.
+        let mut disabled = policies[1].clone();
+        disabled.enabled = false;
+        let decision = PolicyDecision::from_policies(&[disabled], &assessment(95, vec![]));
+        assert_eq!(decision.action, PolicyAction::Allow);
+        assert_eq!(decision.policy_id, None);
+    }
+
+    #[test]
+    fn test_policy_request_validation() {
+        let request: SecurityPolicyRequest = serde_json::from_str(
+            r#"{"name":"travel","factor":"impossible_travel","action":"require_2fa"}"#,
+        )
+        .unwrap();
+        assert_eq!(request.action, PolicyAction::RequireTwoFactor);
+        assert_eq!(request.validate().unwrap(), (0, 100));
+
+        let inverted = SecurityPolicyRequest {
+            min_score: Some(80),
+            max_score: Some(20),
+            ..request
+        };
+        assert!(inverted.validate().is_err());
+    }
+}
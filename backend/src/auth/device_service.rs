@@ -60,6 +60,35 @@ pub struct Device {
     pub updated_at: DateTime<Utc>,
 }
 
+/// WebAuthn credential registered on a device
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Passkey {
+    pub id: Uuid,
+    pub device_id: Uuid,
+    pub user_id: Uuid,
+    #[serde(skip_serializing)]
+    pub credential_id: Vec<u8>,
+    #[serde(skip_serializing)]
+    pub public_key: Vec<u8>,
+    pub algorithm: i32,
+    pub sign_count: i64,
+    pub name: Option<String>,
+    pub aaguid: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+/// New passkey to attach to a device
+#[derive(Debug, Clone)]
+pub struct NewPasskey {
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub algorithm: i32,
+    pub sign_count: i64,
+    pub name: Option<String>,
+    pub aaguid: Option<Uuid>,
+}
+
 /// Security alert for suspicious device activity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SecurityAlert {
@@ -157,7 +186,7 @@ impl SecurityMonitor {
         redis::cmd("LPUSH")
             .arg(&key)
             .arg(value)
-            .query_async(&mut conn)
+            .query_async::<()>(&mut conn)
             .await
             .map_err(|e| DeviceError::RedisError(e.to_string()))?;
 
@@ -166,7 +195,7 @@ impl SecurityMonitor {
             .arg(&key)
             .arg(0)
             .arg(99)
-            .query_async(&mut conn)
+            .query_async::<()>(&mut conn)
             .await
             .map_err(|e| DeviceError::RedisError(e.to_string()))?;
 
@@ -174,7 +203,7 @@ impl SecurityMonitor {
         redis::cmd("EXPIRE")
             .arg(&key)
             .arg(2592000)
-            .query_async(&mut conn)
+            .query_async::<()>(&mut conn)
             .await
             .map_err(|e| DeviceError::RedisError(e.to_string()))?;
 
@@ -235,6 +264,9 @@ pub enum DeviceError {
 
     #[error("Security alert: {0}")]
     SecurityAlert(String),
+
+    #[error("Passkey is already registered")]
+    PasskeyExists,
 }
 
 impl From<DeviceError> for ApiError {
@@ -248,6 +280,7 @@ impl From<DeviceError> for ApiError {
             DeviceError::DeviceValidationFailed => ApiError::Unauthorized,
             DeviceError::InvalidDeviceInfo(_) => ApiError::BadRequest(err.to_string()),
             DeviceError::SecurityAlert(_) => ApiError::BadRequest(err.to_string()),
+            DeviceError::PasskeyExists => ApiError::Conflict(err.to_string()),
         }
     }
 }
@@ -494,7 +527,7 @@ impl DeviceService {
         let key = format!("device:login:{}", device_id);
         redis::cmd("DEL")
             .arg(&key)
-            .query_async(&mut conn)
+            .query_async::<()>(&mut conn)
             .await
             .map_err(|e| DeviceError::RedisError(e.to_string()))?;
 
@@ -611,7 +644,10 @@ impl DeviceService {
     }
 
     /// Store a security alert
-    async fn store_security_alert(&self, alert: &SecurityAlert) -> Result<(), DeviceError> {
+    pub(crate) async fn store_security_alert(
+        &self,
+        alert: &SecurityAlert,
+    ) -> Result<(), DeviceError> {
         sqlx::query(
             "INSERT INTO device_security_alerts (
                 id, device_id, user_id, alert_type, severity, message, details, created_at
@@ -691,6 +727,35 @@ impl DeviceService {
         Ok(())
     }
 
+    /// Rename a device
+    pub async fn rename_device(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+        name: &str,
+    ) -> Result<Device, DeviceError> {
+        let name = name.trim();
+        if name.is_empty() || name.chars().count() > 100 {
+            return Err(DeviceError::InvalidDeviceInfo(
+                "Device name must be 1 to 100 characters".to_string(),
+            ));
+        }
+
+        let device = sqlx::query_as::<_, Device>(
+            "UPDATE devices SET name = $1, updated_at = $2
+             WHERE id = $3 AND user_id = $4 RETURNING *",
+        )
+        .bind(name)
+        .bind(Utc::now())
+        .bind(device_id)
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(DeviceError::DeviceNotFound)?;
+
+        Ok(device)
+    }
+
     /// Update device last seen timestamp
     pub async fn update_last_seen(&self, device_id: Uuid) -> Result<(), DeviceError> {
         sqlx::query("UPDATE devices SET last_seen = $1, is_active = true WHERE id = $2")
@@ -875,6 +940,133 @@ impl DeviceService {
 
         Ok(true)
     }
+
+    /// Attach a passkey to one of the user's devices
+    pub async fn add_passkey(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+        passkey: NewPasskey,
+    ) -> Result<Passkey, DeviceError> {
+        let device = self.get_device(device_id).await?;
+        if device.user_id != user_id {
+            return Err(DeviceError::DeviceNotFound);
+        }
+        if device.is_blocked {
+            return Err(DeviceError::DeviceBlocked);
+        }
+
+        let passkey = sqlx::query_as::<_, Passkey>(
+            "INSERT INTO device_passkeys (
+                device_id, user_id, credential_id, public_key, algorithm, sign_count, name, aaguid
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (credential_id) DO NOTHING
+            RETURNING *",
+        )
+        .bind(device_id)
+        .bind(user_id)
+        .bind(&passkey.credential_id)
+        .bind(&passkey.public_key)
+        .bind(passkey.algorithm)
+        .bind(passkey.sign_count)
+        .bind(&passkey.name)
+        .bind(passkey.aaguid)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(DeviceError::PasskeyExists)?;
+
+        info!(
+            passkey_id = %passkey.id,
+            device_id = %device_id,
+            user_id = %user_id,
+            "Passkey registered"
+        );
+
+        Ok(passkey)
+    }
+
+    /// Find a passkey by its WebAuthn credential ID
+    pub async fn find_passkey(&self, credential_id: &[u8]) -> Result<Passkey, DeviceError> {
+        let passkey =
+            sqlx::query_as::<_, Passkey>("SELECT * FROM device_passkeys WHERE credential_id = $1")
+                .bind(credential_id)
+                .fetch_optional(&self.db_pool)
+                .await?
+                .ok_or(DeviceError::DeviceNotFound)?;
+
+        Ok(passkey)
+    }
+
+    /// Get all passkeys for a user
+    pub async fn get_user_passkeys(&self, user_id: Uuid) -> Result<Vec<Passkey>, DeviceError> {
+        let passkeys = sqlx::query_as::<_, Passkey>(
+            "SELECT * FROM device_passkeys WHERE user_id = $1 ORDER BY created_at DESC",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(passkeys)
+    }
+
+    /// Record a successful assertion: store the new signature counter and
+    /// mark the device as seen. The counter only moves forward, so of two
+    /// concurrent assertions with the same counter only one succeeds.
+    pub async fn record_passkey_use(
+        &self,
+        passkey: &Passkey,
+        sign_count: i64,
+    ) -> Result<(), DeviceError> {
+        let updated = sqlx::query(
+            "UPDATE device_passkeys SET sign_count = $1, last_used_at = $2
+             WHERE id = $3 AND (sign_count < $1 OR (sign_count = 0 AND $1 = 0))",
+        )
+        .bind(sign_count)
+        .bind(Utc::now())
+        .bind(passkey.id)
+        .execute(&self.db_pool)
+        .await?;
+
+        if updated.rows_affected() == 0 {
+            warn!(
+                passkey_id = %passkey.id,
+                device_id = %passkey.device_id,
+                "Passkey signature counter regressed"
+            );
+            self.security_monitor
+                .record_login_attempt(passkey.device_id, false)
+                .await?;
+            return Err(DeviceError::DeviceValidationFailed);
+        }
+
+        self.update_last_seen(passkey.device_id).await?;
+        self.security_monitor
+            .record_login_attempt(passkey.device_id, true)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Remove one of the user's passkeys
+    pub async fn remove_passkey(&self, user_id: Uuid, passkey_id: Uuid) -> Result<(), DeviceError> {
+        let removed = sqlx::query("DELETE FROM device_passkeys WHERE id = $1 AND user_id = $2")
+            .bind(passkey_id)
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+
+        if removed.rows_affected() == 0 {
+            return Err(DeviceError::DeviceNotFound);
+        }
+
+        info!(
+            passkey_id = %passkey_id,
+            user_id = %user_id,
+            "Passkey removed"
+        );
+
+        Ok(())
+    }
 }
 
 impl ToString for DeviceType {
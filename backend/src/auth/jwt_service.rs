@@ -29,6 +29,9 @@ pub enum JwtError {
     #[error("Session not found")]
     SessionNotFound,
 
+    #[error("Refresh token reuse detected")]
+    TokenReuse,
+
     #[error("Redis error: {0}")]
     RedisError(String),
 
@@ -64,6 +67,14 @@ pub struct Claims {
     pub roles: Vec<String>,
 }
 
+impl Claims {
+    /// The authenticated user's ID, parsed from the `sub` claim.
+    pub fn user_id(&self) -> Result<Uuid, crate::api_error::ApiError> {
+        Uuid::parse_str(&self.sub)
+            .map_err(|_| crate::api_error::ApiError::unauthorized("Invalid subject claim"))
+    }
+}
+
 /// Token type enumeration
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -176,6 +187,74 @@ pub struct RefreshTokenRecord {
     pub device_id: Option<String>,
     pub created_at: i64,
     pub last_used_at: i64,
+    /// Family the token belongs to; empty for records written before
+    /// families existed.
+    #[serde(default)]
+    pub family_id: String,
+    /// Access-token session issued together with this token.
+    #[serde(default)]
+    pub session_id: String,
+}
+
+/// The chain of rotated refresh tokens descending from one sign-in, stored
+/// in Redis under `refresh_family:{family_id}`.
+///
+/// Only the newest token of a family can be used. Rotated-out tokens are
+/// remembered under `refresh_retired:{token_hash}` until they would have
+/// expired, so presenting one again — a sign it was stolen — revokes the
+/// whole family along with the access session of its current token.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefreshTokenFamily {
+    pub family_id: String,
+    pub user_id: Uuid,
+    pub device_id: Option<String>,
+    /// Hash of the one refresh token of the family that is still valid.
+    pub current_hash: String,
+    /// Access-token session issued together with the current token.
+    pub session_id: String,
+    pub rotations: u32,
+    pub created_at: i64,
+    pub rotated_at: i64,
+    /// Where the family was last used from: sign-in or latest rotation.
+    #[serde(default)]
+    pub client: SessionClient,
+}
+
+/// Client a session was used from, as shown in the sessions list.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SessionClient {
+    pub ip_address: Option<String>,
+    /// Country code reported by the edge.
+    pub location: Option<String>,
+}
+
+impl SessionClient {
+    /// Take over what `other` knows, keeping the rest.
+    fn update(&mut self, other: SessionClient) {
+        if other.ip_address.is_some() {
+            self.ip_address = other.ip_address;
+        }
+        if other.location.is_some() {
+            self.location = other.location;
+        }
+    }
+}
+
+impl RefreshTokenFamily {
+    fn new(user_id: Uuid, device_id: Option<String>) -> Self {
+        let now = Utc::now().timestamp();
+        Self {
+            family_id: Uuid::new_v4().to_string(),
+            user_id,
+            device_id,
+            current_hash: String::new(),
+            session_id: String::new(),
+            rotations: 0,
+            created_at: now,
+            rotated_at: now,
+            client: SessionClient::default(),
+        }
+    }
 }
 
 /// Compute the SHA-256 hex digest of a token string.
@@ -189,6 +268,7 @@ fn token_hash(token: &str) -> String {
 }
 
 /// Main JWT Service
+#[derive(Clone)]
 pub struct JwtService {
     config: JwtConfig,
     redis: ConnectionManager,
@@ -216,22 +296,15 @@ impl JwtService {
     ) -> Result<String, JwtError> {
         let session_id = Uuid::new_v4().to_string();
 
-        let claims = Claims {
-            sub: user_id.to_string(),
-            exp: (Utc::now() + self.config.access_token_expiry).timestamp(),
-            iat: Utc::now().timestamp(),
-            jti: Uuid::new_v4().to_string(),
-            token_type: TokenType::Access,
-            device_id: device_id.clone(),
-            session_id: session_id.clone(),
-            roles: roles.clone(),
-        };
-
-        let key_rotation = self.key_rotation.read().await;
-        let encoding_key = EncodingKey::from_secret(key_rotation.current_key.as_bytes());
-
-        let token = encode(&Header::new(self.config.algorithm), &claims, &encoding_key)
-            .map_err(|e| JwtError::TokenGeneration(e.to_string()))?;
+        let token = self
+            .encode_claims(
+                user_id,
+                roles,
+                device_id.clone(),
+                &session_id,
+                TokenType::Access,
+            )
+            .await?;
 
         // Store session in Redis
         self.store_session(&session_id, user_id, device_id).await?;
@@ -241,68 +314,84 @@ impl JwtService {
         Ok(token)
     }
 
-    /// Generate refresh token
+    /// Generate refresh token, starting a new token family
     pub async fn generate_refresh_token(
         &self,
         user_id: Uuid,
         roles: Vec<String>,
         device_id: Option<String>,
     ) -> Result<String, JwtError> {
-        let session_id = Uuid::new_v4().to_string();
+        let mut family = RefreshTokenFamily::new(user_id, device_id);
+        family.session_id = Uuid::new_v4().to_string();
 
-        let claims = Claims {
-            sub: user_id.to_string(),
-            exp: (Utc::now() + self.config.refresh_token_expiry).timestamp(),
-            iat: Utc::now().timestamp(),
-            jti: Uuid::new_v4().to_string(),
-            token_type: TokenType::Refresh,
-            device_id: device_id.clone(),
-            session_id: session_id.clone(),
-            roles,
-        };
-
-        let key_rotation = self.key_rotation.read().await;
-        let encoding_key = EncodingKey::from_secret(key_rotation.current_key.as_bytes());
+        let token = self.issue_refresh_token(roles, &mut family).await?;
 
-        let token = encode(&Header::new(self.config.algorithm), &claims, &encoding_key)
-            .map_err(|e| JwtError::TokenGeneration(e.to_string()))?;
-
-        // Store refresh token record in Redis so we can validate, rotate, and
-        // revoke it explicitly.
-        drop(key_rotation); // release read-lock before async Redis call
-        self.store_refresh_token(&token, user_id, device_id.clone(), None)
-            .await?;
-
-        // Track hash in user's refresh-token set (for revoke-all and sessions list)
-        let hash = token_hash(&token);
-        let user_refresh_set = format!("user_refresh_tokens:{}", user_id);
-        let mut conn = self.redis.clone();
-        conn.sadd::<_, _, ()>(&user_refresh_set, &hash).await?;
-        conn.expire::<_, ()>(
-            &user_refresh_set,
-            self.config.refresh_token_expiry.num_seconds() as i64,
-        )
-        .await?;
-
-        info!(user_id = %user_id, session_id = %session_id, "Refresh token generated");
+        info!(
+            user_id = %user_id,
+            family_id = %family.family_id,
+            "Refresh token generated"
+        );
 
         Ok(token)
     }
 
-    /// Generate both access and refresh tokens
+    /// Generate both access and refresh tokens, starting a new token family
     pub async fn generate_token_pair(
         &self,
         user_id: Uuid,
         roles: Vec<String>,
         device_id: Option<String>,
     ) -> Result<TokenPair, JwtError> {
+        self.generate_client_token_pair(user_id, roles, device_id, SessionClient::default())
+            .await
+    }
+
+    /// [`generate_token_pair`](Self::generate_token_pair) for a sign-in from
+    /// `client`.
+    pub async fn generate_client_token_pair(
+        &self,
+        user_id: Uuid,
+        roles: Vec<String>,
+        device_id: Option<String>,
+        client: SessionClient,
+    ) -> Result<TokenPair, JwtError> {
+        let mut family = RefreshTokenFamily::new(user_id, device_id);
+        family.client = client;
+        self.issue_token_pair(roles, family).await
+    }
+
+    /// Issue an access token and the next refresh token of `family`, sharing
+    /// one session so that rotating or revoking the refresh token also ends
+    /// the access token's session.
+    async fn issue_token_pair(
+        &self,
+        roles: Vec<String>,
+        mut family: RefreshTokenFamily,
+    ) -> Result<TokenPair, JwtError> {
+        let user_id = family.user_id;
+        family.session_id = Uuid::new_v4().to_string();
+
         let access_token = self
-            .generate_access_token(user_id, roles.clone(), device_id.clone())
+            .encode_claims(
+                user_id,
+                roles.clone(),
+                family.device_id.clone(),
+                &family.session_id,
+                TokenType::Access,
+            )
             .await?;
-        let refresh_token = self
-            .generate_refresh_token(user_id, roles, device_id)
+        self.store_session(&family.session_id, user_id, family.device_id.clone())
             .await?;
 
+        let refresh_token = self.issue_refresh_token(roles, &mut family).await?;
+
+        info!(
+            user_id = %user_id,
+            session_id = %family.session_id,
+            family_id = %family.family_id,
+            "Token pair generated"
+        );
+
         Ok(TokenPair {
             access_token,
             refresh_token,
@@ -311,6 +400,76 @@ impl JwtService {
         })
     }
 
+    /// Sign a refresh token for `family` and make it the family's current
+    /// token.
+    async fn issue_refresh_token(
+        &self,
+        roles: Vec<String>,
+        family: &mut RefreshTokenFamily,
+    ) -> Result<String, JwtError> {
+        let token = self
+            .encode_claims(
+                family.user_id,
+                roles,
+                family.device_id.clone(),
+                &family.session_id,
+                TokenType::Refresh,
+            )
+            .await?;
+
+        family.current_hash = token_hash(&token);
+        family.rotated_at = Utc::now().timestamp();
+
+        // Store refresh token record in Redis so we can validate, rotate, and
+        // revoke it explicitly.
+        self.store_refresh_token(&token, family).await?;
+        self.store_token_family(family).await?;
+
+        // Track hash in user's refresh-token set (for revoke-all and sessions list)
+        let user_refresh_set = format!("user_refresh_tokens:{}", family.user_id);
+        let mut conn = self.redis.clone();
+        conn.sadd::<_, _, ()>(&user_refresh_set, &family.current_hash)
+            .await?;
+        conn.expire::<_, ()>(
+            &user_refresh_set,
+            self.config.refresh_token_expiry.num_seconds(),
+        )
+        .await?;
+
+        Ok(token)
+    }
+
+    /// Sign claims of the given type with the current key.
+    async fn encode_claims(
+        &self,
+        user_id: Uuid,
+        roles: Vec<String>,
+        device_id: Option<String>,
+        session_id: &str,
+        token_type: TokenType,
+    ) -> Result<String, JwtError> {
+        let expiry = match token_type {
+            TokenType::Access => self.config.access_token_expiry,
+            TokenType::Refresh => self.config.refresh_token_expiry,
+        };
+        let claims = Claims {
+            sub: user_id.to_string(),
+            exp: (Utc::now() + expiry).timestamp(),
+            iat: Utc::now().timestamp(),
+            jti: Uuid::new_v4().to_string(),
+            token_type,
+            device_id,
+            session_id: session_id.to_string(),
+            roles,
+        };
+
+        let key_rotation = self.key_rotation.read().await;
+        let encoding_key = EncodingKey::from_secret(key_rotation.current_key.as_bytes());
+
+        encode(&Header::new(self.config.algorithm), &claims, &encoding_key)
+            .map_err(|e| JwtError::TokenGeneration(e.to_string()))
+    }
+
     /// Validate token and return claims
     pub async fn validate_token(&self, token: &str) -> Result<Claims, JwtError> {
         // Check if token is blacklisted
@@ -318,21 +477,7 @@ impl JwtService {
             return Err(JwtError::TokenBlacklisted);
         }
 
-        let key_rotation = self.key_rotation.read().await;
-
-        // Try with current key
-        let claims = match self.decode_token(token, &key_rotation.current_key) {
-            Ok(claims) => claims,
-            Err(e) => {
-                // If current key fails and we have a previous key, try it
-                if let Some(ref prev_key) = key_rotation.previous_key {
-                    debug!("Trying previous key for token validation");
-                    self.decode_token(token, prev_key)?
-                } else {
-                    return Err(e);
-                }
-            }
-        };
+        let claims = self.decode_with_rotated_keys(token).await?;
 
         // Verify session exists
         if !self.session_exists(&claims.session_id).await? {
@@ -348,6 +493,26 @@ impl JwtService {
         Ok(claims)
     }
 
+    /// Decode a token with the current key, falling back to the previous one
+    /// for tokens signed before the last key rotation.
+    async fn decode_with_rotated_keys(&self, token: &str) -> Result<Claims, JwtError> {
+        let key_rotation = self.key_rotation.read().await;
+
+        // Try with current key
+        match self.decode_token(token, &key_rotation.current_key) {
+            Ok(claims) => Ok(claims),
+            Err(e) => {
+                // If current key fails and we have a previous key, try it
+                if let Some(ref prev_key) = key_rotation.previous_key {
+                    debug!("Trying previous key for token validation");
+                    self.decode_token(token, prev_key)
+                } else {
+                    Err(e)
+                }
+            }
+        }
+    }
+
     /// Decode token with specific key.
     ///
     /// A 30-second leeway is applied to the `exp` and `nbf` claims to tolerate
@@ -376,40 +541,96 @@ impl JwtService {
 
     /// Refresh access token using refresh token.
     ///
-    /// Implements single-use (rotating) refresh tokens:
-    /// 1. Validate the presented token (JWT signature + Redis record presence)
-    /// 2. Delete the old refresh-token record from Redis — replaying the same
-    ///    token after this point will return 401 `TokenBlacklisted` (record gone)
-    /// 3. Issue a brand-new access + refresh token pair
+    /// Implements single-use (rotating) refresh tokens with reuse detection:
+    /// 1. Validate the presented token (JWT signature, expiry, blacklist)
+    /// 2. Atomically take its Redis record, so of two concurrent refreshes
+    ///    with the same token only one succeeds
+    /// 3. Remember the token as retired and end the access session issued
+    ///    with it
+    /// 4. Issue a fresh token pair in the same family
+    ///
+    /// Presenting a retired token revokes its whole family and fails with
+    /// [`JwtError::TokenReuse`]: either the legitimate client or an attacker
+    /// holds a copy, and neither can be told apart from the other.
     pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenPair, JwtError> {
-        // Step 1: Decode and validate the JWT (signature, expiry, blacklist, session)
-        let claims = self.validate_token(refresh_token).await?;
+        self.refresh_client_token(refresh_token, SessionClient::default())
+            .await
+    }
+
+    /// [`refresh_token`](Self::refresh_token) from `client`, which becomes
+    /// where the session was last used from.
+    pub async fn refresh_client_token(
+        &self,
+        refresh_token: &str,
+        client: SessionClient,
+    ) -> Result<TokenPair, JwtError> {
+        // Step 1: Decode and validate the JWT
+        if self.is_token_blacklisted(refresh_token).await? {
+            return Err(JwtError::TokenBlacklisted);
+        }
+        let claims = self.decode_with_rotated_keys(refresh_token).await?;
 
         if claims.token_type != TokenType::Refresh {
             return Err(JwtError::InvalidToken);
         }
 
-        // Step 2: Verify the refresh-token record exists in Redis
-        let record = self.get_refresh_token_record(refresh_token).await?;
-
         let user_id =
             Uuid::parse_str(&claims.sub).map_err(|e| JwtError::TokenValidation(e.to_string()))?;
 
-        // Step 3: Invalidate the old refresh token (delete its Redis record)
-        // From this moment any replay of the old token will fail with "record missing".
-        self.invalidate_refresh_token(refresh_token, user_id).await?;
+        // Step 2: Take the refresh-token record; GETDEL makes this the single
+        // point where a token is spent.
+        let hash = token_hash(refresh_token);
+        let mut conn = self.redis.clone();
+        let json: Option<String> = conn.get_del(format!("refresh:{}", hash)).await?;
+
+        let Some(json) = json else {
+            let retired: Option<String> = conn.get(format!("refresh_retired:{}", hash)).await?;
+            if let Some(family_id) = retired {
+                warn!(
+                    user_id = %user_id,
+                    family_id = %family_id,
+                    "Refresh token reuse detected — revoking token family"
+                );
+                self.revoke_token_family(&family_id).await?;
+                self.increment_analytics("reused").await?;
+                return Err(JwtError::TokenReuse);
+            }
+            // Expired or revoked
+            return Err(JwtError::SessionNotFound);
+        };
+        let record: RefreshTokenRecord =
+            serde_json::from_str(&json).map_err(|e| JwtError::RedisError(e.to_string()))?;
+
+        let mut family = match self.get_token_family(&record.family_id).await? {
+            Some(family) => family,
+            None => {
+                // Record written before families existed
+                let mut family = RefreshTokenFamily::new(user_id, record.device_id.clone());
+                family.created_at = record.created_at;
+                family
+            }
+        };
 
-        // Also remove the old session entry tied to this refresh token
+        // Step 3: Retire the old token and end its access session
+        conn.set_ex::<_, _, ()>(
+            format!("refresh_retired:{}", hash),
+            &family.family_id,
+            self.config.refresh_token_expiry.num_seconds() as u64,
+        )
+        .await?;
+        conn.srem::<_, _, ()>(format!("user_refresh_tokens:{}", user_id), &hash)
+            .await?;
         self.revoke_session(&claims.session_id).await?;
 
-        // Step 4: Issue a fresh token pair (new session, new refresh record)
-        let token_pair = self
-            .generate_token_pair(user_id, claims.roles, record.device_id)
-            .await?;
+        // Step 4: Issue a fresh token pair in the same family
+        family.rotations += 1;
+        family.client.update(client);
+        let family_id = family.family_id.clone();
+        let token_pair = self.issue_token_pair(claims.roles, family).await?;
 
         self.increment_analytics("refreshed").await?;
 
-        info!(user_id = %user_id, "Token refreshed — old refresh token invalidated");
+        info!(user_id = %user_id, family_id = %family_id, "Token refreshed — old refresh token retired");
 
         Ok(token_pair)
     }
@@ -429,7 +650,7 @@ impl JwtService {
         let blacklist_key = format!("blacklist:{}", claims.jti);
 
         let mut conn = self.redis.clone();
-        conn.set_ex(&blacklist_key, reason, exp_duration as u64)
+        conn.set_ex::<_, _, ()>(&blacklist_key, reason, exp_duration as u64)
             .await?;
 
         // Increment analytics
@@ -492,7 +713,7 @@ impl JwtService {
             .map_err(|e| JwtError::RedisError(e.to_string()))?;
 
         let mut conn = self.redis.clone();
-        conn.set_ex(
+        conn.set_ex::<_, _, ()>(
             &session_key,
             session_json,
             self.config.access_token_expiry.num_seconds() as u64,
@@ -502,8 +723,8 @@ impl JwtService {
         // Add to user's active sessions set; expire the set with the refresh TTL
         // so it outlives individual access token sessions.
         let user_sessions_key = format!("user_sessions:{}", user_id);
-        conn.sadd(&user_sessions_key, session_id).await?;
-        conn.expire(
+        conn.sadd::<_, _, ()>(&user_sessions_key, session_id).await?;
+        conn.expire::<_, ()>(
             &user_sessions_key,
             self.config.refresh_token_expiry.num_seconds() as i64,
         )
@@ -538,7 +759,7 @@ impl JwtService {
                 serde_json::to_string(&session).map_err(|e| JwtError::RedisError(e.to_string()))?;
 
             // Refresh the TTL using access_token_expiry (consistent with store_session).
-            conn.set_ex(
+            conn.set_ex::<_, _, ()>(
                 &session_key,
                 updated_json,
                 self.config.access_token_expiry.num_seconds() as u64,
@@ -597,7 +818,7 @@ impl JwtService {
     pub async fn revoke_session(&self, session_id: &str) -> Result<(), JwtError> {
         let session_key = format!("session:{}", session_id);
         let mut conn = self.redis.clone();
-        conn.del(&session_key).await?;
+        conn.del::<_, ()>(&session_key).await?;
 
         info!(session_id = %session_id, "Session revoked");
 
@@ -608,24 +829,23 @@ impl JwtService {
 
     /// Persist a `refresh:{hash}` record in Redis with the refresh-token TTL.
     ///
-    /// `previous_last_used_at` carries forward the original creation timestamp
-    /// when rotating so callers can inspect when the original session began.
+    /// `created_at` is the start of the token's family, so a session keeps
+    /// its sign-in time across rotations.
     async fn store_refresh_token(
         &self,
         token: &str,
-        user_id: Uuid,
-        device_id: Option<String>,
-        previous_last_used_at: Option<i64>,
+        family: &RefreshTokenFamily,
     ) -> Result<(), JwtError> {
         let hash = token_hash(token);
         let key = format!("refresh:{}", hash);
 
-        let now = Utc::now().timestamp();
         let record = RefreshTokenRecord {
-            user_id,
-            device_id,
-            created_at: now,
-            last_used_at: previous_last_used_at.unwrap_or(now),
+            user_id: family.user_id,
+            device_id: family.device_id.clone(),
+            created_at: family.created_at,
+            last_used_at: Utc::now().timestamp(),
+            family_id: family.family_id.clone(),
+            session_id: family.session_id.clone(),
         };
 
         let json = serde_json::to_string(&record)
@@ -699,6 +919,16 @@ impl JwtService {
 
         conn.del::<_, ()>(&user_refresh_set).await?;
 
+        // Drop the families too; their retired tokens stay remembered so a
+        // replay is still reported as reuse.
+        let user_families_set = format!("user_refresh_families:{}", user_id);
+        let family_ids: Vec<String> = conn.smembers(&user_families_set).await?;
+        for family_id in &family_ids {
+            conn.del::<_, ()>(format!("refresh_family:{}", family_id))
+                .await?;
+        }
+        conn.del::<_, ()>(&user_families_set).await?;
+
         info!(user_id = %user_id, count = count, "All refresh tokens revoked");
 
         Ok(count)
@@ -747,11 +977,114 @@ impl JwtService {
         Ok(records)
     }
 
+    // ── Refresh-token family helpers ─────────────────────────────────────────
+
+    /// Persist a `refresh_family:{family_id}` record and index it under the
+    /// user. Each rotation extends its TTL to the refresh-token lifetime.
+    async fn store_token_family(&self, family: &RefreshTokenFamily) -> Result<(), JwtError> {
+        let json =
+            serde_json::to_string(family).map_err(|e| JwtError::RedisError(e.to_string()))?;
+        let ttl = self.config.refresh_token_expiry.num_seconds();
+
+        let mut conn = self.redis.clone();
+        conn.set_ex::<_, _, ()>(
+            format!("refresh_family:{}", family.family_id),
+            json,
+            ttl as u64,
+        )
+        .await?;
+
+        let user_families_set = format!("user_refresh_families:{}", family.user_id);
+        conn.sadd::<_, _, ()>(&user_families_set, &family.family_id)
+            .await?;
+        conn.expire::<_, ()>(&user_families_set, ttl).await?;
+
+        Ok(())
+    }
+
+    /// Look up a token family; `None` once it expired or was revoked.
+    pub async fn get_token_family(
+        &self,
+        family_id: &str,
+    ) -> Result<Option<RefreshTokenFamily>, JwtError> {
+        if family_id.is_empty() {
+            return Ok(None);
+        }
+        let mut conn = self.redis.clone();
+        let json: Option<String> = conn.get(format!("refresh_family:{}", family_id)).await?;
+        json.map(|json| {
+            serde_json::from_str(&json).map_err(|e| JwtError::RedisError(e.to_string()))
+        })
+        .transpose()
+    }
+
+    /// Return the live token families (signed-in sessions) of a user.
+    pub async fn get_token_families(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<RefreshTokenFamily>, JwtError> {
+        let user_families_set = format!("user_refresh_families:{}", user_id);
+        let mut conn = self.redis.clone();
+
+        let family_ids: Vec<String> = conn.smembers(&user_families_set).await?;
+
+        let mut families = Vec::new();
+        for family_id in family_ids {
+            match self.get_token_family(&family_id).await? {
+                Some(family) => families.push(family),
+                None => {
+                    // Family expired — clean up the stale set member
+                    let _ = conn.srem::<_, _, ()>(&user_families_set, &family_id).await;
+                }
+            }
+        }
+        families.sort_by_key(|f| std::cmp::Reverse(f.rotated_at));
+
+        Ok(families)
+    }
+
+    /// Revoke a token family: its current refresh token stops working and the
+    /// access session issued with it ends. Returns whether the family was
+    /// still live.
+    pub async fn revoke_token_family(&self, family_id: &str) -> Result<bool, JwtError> {
+        let mut conn = self.redis.clone();
+        let json: Option<String> = conn
+            .get_del(format!("refresh_family:{}", family_id))
+            .await?;
+        let Some(json) = json else {
+            return Ok(false);
+        };
+        let family: RefreshTokenFamily =
+            serde_json::from_str(&json).map_err(|e| JwtError::RedisError(e.to_string()))?;
+
+        conn.del::<_, ()>(format!("refresh:{}", family.current_hash))
+            .await?;
+        conn.srem::<_, _, ()>(
+            format!("user_refresh_tokens:{}", family.user_id),
+            &family.current_hash,
+        )
+        .await?;
+        conn.srem::<_, _, ()>(
+            format!("user_refresh_families:{}", family.user_id),
+            family_id,
+        )
+        .await?;
+        self.revoke_session(&family.session_id).await?;
+
+        info!(
+            user_id = %family.user_id,
+            family_id = %family_id,
+            "Refresh token family revoked"
+        );
+
+        Ok(true)
+    }
+
     /// Increment analytics counter
     async fn increment_analytics(&self, metric: &str) -> Result<(), JwtError> {
         let analytics_key = format!("analytics:jwt:{}", metric);
         let mut conn = self.redis.clone();
-        conn.incr(&analytics_key, 1).await?;
+        conn.incr::<_, _, ()>(&analytics_key, 1).await?;
         Ok(())
     }
 
@@ -787,7 +1120,7 @@ impl JwtService {
             let ttl: i64 = conn.ttl(&key).await.unwrap_or(-2);
             if ttl == -2 {
                 // Key doesn't exist or expired
-                conn.del(&key).await?;
+                conn.del::<_, ()>(&key).await?;
                 cleaned += 1;
             }
         }
@@ -1039,4 +1372,16 @@ mod tests {
         assert_eq!(deserialized.sub, claims.sub);
         assert_eq!(deserialized.token_type, claims.token_type);
     }
+
+    #[test]
+    fn test_refresh_token_record_without_family() {
+        let json = format!(
+            r#"{{"user_id":"{}","device_id":null,"created_at":1,"last_used_at":2}}"#,
+            Uuid::new_v4()
+        );
+        let record: RefreshTokenRecord = serde_json::from_str(&json).unwrap();
+
+        assert!(record.family_id.is_empty());
+        assert!(record.session_id.is_empty());
+    }
 }
@@ -127,6 +127,64 @@ mod integration_tests {
         assert_ne!(new_pair.access_token, initial_pair.access_token);
     }
 
+    #[tokio::test]
+    async fn test_refresh_token_reuse_revokes_family() {
+        let service = create_test_service().await;
+        let user_id = Uuid::new_v4();
+        let roles = vec!["user".to_string()];
+
+        let initial_pair = service
+            .generate_token_pair(user_id, roles, None)
+            .await
+            .unwrap();
+        let rotated_pair = service
+            .refresh_token(&initial_pair.refresh_token)
+            .await
+            .unwrap();
+        assert!(service.validate_token(&rotated_pair.access_token).await.is_ok());
+
+        // Replaying the retired token is reuse...
+        let result = service.refresh_token(&initial_pair.refresh_token).await;
+        assert!(matches!(result.unwrap_err(), JwtError::TokenReuse));
+
+        // ...which signs out the newest tokens of the family as well.
+        assert!(service.validate_token(&rotated_pair.access_token).await.is_err());
+        assert!(service
+            .refresh_token(&rotated_pair.refresh_token)
+            .await
+            .is_err());
+        assert!(service.get_token_families(user_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_token_family() {
+        let service = create_test_service().await;
+        let user_id = Uuid::new_v4();
+
+        let first = service
+            .generate_token_pair(user_id, vec!["user".to_string()], None)
+            .await
+            .unwrap();
+        service
+            .generate_token_pair(user_id, vec!["user".to_string()], None)
+            .await
+            .unwrap();
+
+        let families = service.get_token_families(user_id).await.unwrap();
+        assert_eq!(families.len(), 2);
+
+        let family = service
+            .get_refresh_token_record(&first.refresh_token)
+            .await
+            .unwrap()
+            .family_id;
+        assert!(service.revoke_token_family(&family).await.unwrap());
+        assert!(!service.revoke_token_family(&family).await.unwrap());
+
+        assert!(service.validate_token(&first.access_token).await.is_err());
+        assert_eq!(service.get_token_families(user_id).await.unwrap().len(), 1);
+    }
+
     #[tokio::test]
     async fn test_refresh_with_access_token_fails() {
         let service = create_test_service().await;
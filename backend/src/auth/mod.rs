@@ -1,13 +1,34 @@
+pub mod account_email;
+pub mod device_security_policies;
 pub mod device_service;
 pub mod jwt_service;
 pub mod middleware;
+pub mod oauth;
+pub mod phone_verification;
+pub mod rbac;
+pub mod sep10;
+pub mod step_up;
+pub mod totp;
+pub mod two_factor;
+pub mod webauthn;
 
+pub use account_email::{AccountEmailService, EmailTokenPurpose};
+pub use device_security_policies::{
+    DeviceSecurityPolicies, GeoLocation, LoginContext, PolicyAction, RiskAssessment, SecurityPolicy,
+};
 pub use device_service::{
     AlertSeverity, AlertType, Device, DeviceAnalytics, DeviceConfig, DeviceError, DeviceInfo,
-    DeviceService, DeviceType, SecurityAlert,
+    DeviceService, DeviceType, NewPasskey, Passkey, SecurityAlert,
 };
 pub use jwt_service::{
-    Claims, JwtConfig, JwtError, JwtService, KeyRotation, SessionData, TokenAnalytics, TokenPair,
-    TokenType,
+    Claims, JwtConfig, JwtError, JwtService, KeyRotation, SessionClient, SessionData,
+    TokenAnalytics, TokenPair, TokenType,
 };
 pub use middleware::AuthMiddleware;
+pub use oauth::{OAuthConfig, OAuthProvider, OAuthService};
+pub use phone_verification::{PhoneStatus, PhoneVerificationService};
+pub use rbac::{RequireRole, RoleCache, RoleMiddleware};
+pub use sep10::{Sep10Challenge, Sep10Config, Sep10Service};
+pub use step_up::StepUp;
+pub use two_factor::{TwoFactorConfig, TwoFactorService, TwoFactorSetup, TwoFactorStatus};
+pub use webauthn::{PasskeyService, WebAuthnConfig};
@@ -0,0 +1,587 @@
+//! Social sign-in with Google (OpenID Connect), Discord and Twitch.
+//!
+//! The authorization code flow with PKCE: `authorize` records a random
+//! `state` with its code verifier and returns the provider's consent URL; the
+//! provider redirects the browser to the frontend, which posts the code and
+//! state back. `complete` redeems the state once, exchanges the code and
+//! reads the account's profile from the provider. What to do with that
+//! profile — sign in, sign up or link — is up to the auth service.
+//!
+//! Providers that do not implement PKCE ignore it; the state still binds the
+//! callback to the flow it started.
+
+use crate::api_error::ApiError;
+use crate::config::{AuthConfig, OAuthClientConfig};
+use crate::db::DbPool;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use tracing::warn;
+use uuid::Uuid;
+
+const HTTP_TIMEOUT_SECS: u64 = 15;
+/// How long the user has to consent at the provider (seconds).
+const STATE_TTL_SECS: i64 = 600;
+/// Redeemed and expired states are kept this long for auditing (hours).
+const STATE_RETENTION_HOURS: i64 = 24;
+const STATE_BYTES: usize = 32;
+const VERIFIER_BYTES: usize = 32;
+
+#[derive(Debug, Error)]
+pub enum OAuthError {
+    #[error("Provider request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Provider returned {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("Invalid provider response: {0}")]
+    InvalidResponse(String),
+}
+
+impl From<OAuthError> for ApiError {
+    fn from(err: OAuthError) -> Self {
+        match err {
+            // The token endpoint answers 400/401 to stale or forged codes.
+            OAuthError::Status {
+                status: 400 | 401, ..
+            } => ApiError::unauthorized("Authorization code rejected"),
+            _ => ApiError::internal_error(err.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OAuthProvider {
+    Google,
+    Discord,
+    Twitch,
+}
+
+impl OAuthProvider {
+    pub const ALL: [OAuthProvider; 3] = [
+        OAuthProvider::Google,
+        OAuthProvider::Discord,
+        OAuthProvider::Twitch,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "google",
+            OAuthProvider::Discord => "discord",
+            OAuthProvider::Twitch => "twitch",
+        }
+    }
+
+    fn authorize_url(self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "https://accounts.google.com/o/oauth2/v2/auth",
+            OAuthProvider::Discord => "https://discord.com/oauth2/authorize",
+            OAuthProvider::Twitch => "https://id.twitch.tv/oauth2/authorize",
+        }
+    }
+
+    fn token_url(self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "https://oauth2.googleapis.com/token",
+            OAuthProvider::Discord => "https://discord.com/api/oauth2/token",
+            OAuthProvider::Twitch => "https://id.twitch.tv/oauth2/token",
+        }
+    }
+
+    fn profile_url(self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "https://openidconnect.googleapis.com/v1/userinfo",
+            OAuthProvider::Discord => "https://discord.com/api/users/@me",
+            OAuthProvider::Twitch => "https://api.twitch.tv/helix/users",
+        }
+    }
+
+    fn scopes(self) -> &'static str {
+        match self {
+            OAuthProvider::Google => "openid email profile",
+            OAuthProvider::Discord => "identify email",
+            OAuthProvider::Twitch => "user:read:email",
+        }
+    }
+}
+
+impl fmt::Display for OAuthProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OAuthProvider {
+    type Err = ApiError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OAuthProvider::ALL
+            .into_iter()
+            .find(|p| p.as_str() == s)
+            .ok_or_else(|| ApiError::bad_request(format!("Unknown sign-in provider: {}", s)))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct OAuthConfig {
+    pub redirect_url: String,
+    pub google: Option<OAuthClientConfig>,
+    pub discord: Option<OAuthClientConfig>,
+    pub twitch: Option<OAuthClientConfig>,
+    pub state_ttl: Duration,
+}
+
+impl OAuthConfig {
+    pub fn from_config(auth: &AuthConfig) -> Self {
+        Self {
+            redirect_url: auth.oauth_redirect_url.trim_end_matches('/').to_string(),
+            google: auth.google_oauth.clone(),
+            discord: auth.discord_oauth.clone(),
+            twitch: auth.twitch_oauth.clone(),
+            state_ttl: Duration::seconds(STATE_TTL_SECS),
+        }
+    }
+
+    fn client(&self, provider: OAuthProvider) -> Option<&OAuthClientConfig> {
+        match provider {
+            OAuthProvider::Google => self.google.as_ref(),
+            OAuthProvider::Discord => self.discord.as_ref(),
+            OAuthProvider::Twitch => self.twitch.as_ref(),
+        }
+    }
+
+    fn redirect_uri(&self, provider: OAuthProvider) -> String {
+        format!("{}/{}", self.redirect_url, provider)
+    }
+}
+
+/// Response to `GET /api/auth/oauth/{provider}`.
+#[derive(Debug, Serialize)]
+pub struct AuthorizationRequest {
+    /// Where to send the browser for consent.
+    pub authorization_url: String,
+    pub state: String,
+}
+
+/// The external account, normalized across providers.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExternalProfile {
+    pub provider: OAuthProvider,
+    /// The provider's stable account ID.
+    pub subject: String,
+    pub email: Option<String>,
+    /// Whether the provider vouches for `email`.
+    pub email_verified: bool,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+}
+
+/// A completed authorization.
+#[derive(Debug)]
+pub struct OAuthCallback {
+    pub profile: ExternalProfile,
+    /// The signed-in user who started the flow to link this account.
+    pub link_to: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access_token: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GoogleUserInfo {
+    sub: String,
+    email: Option<String>,
+    #[serde(default)]
+    email_verified: bool,
+    name: Option<String>,
+    picture: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DiscordUser {
+    id: String,
+    username: String,
+    global_name: Option<String>,
+    avatar: Option<String>,
+    email: Option<String>,
+    #[serde(default)]
+    verified: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct TwitchUsers {
+    data: Vec<TwitchUser>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TwitchUser {
+    id: String,
+    login: String,
+    display_name: Option<String>,
+    email: Option<String>,
+    profile_image_url: Option<String>,
+}
+
+pub struct OAuthService {
+    db_pool: DbPool,
+    http: reqwest::Client,
+    config: OAuthConfig,
+}
+
+impl OAuthService {
+    pub fn new(db_pool: DbPool, config: OAuthConfig) -> Self {
+        Self {
+            db_pool,
+            http: reqwest::Client::builder()
+                .timeout(std::time::Duration::from_secs(HTTP_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            config,
+        }
+    }
+
+    /// Providers with a configured client.
+    pub fn providers(&self) -> Vec<OAuthProvider> {
+        OAuthProvider::ALL
+            .into_iter()
+            .filter(|p| self.config.client(*p).is_some())
+            .collect()
+    }
+
+    /// Start a flow with `provider`. With `link_to`, the account will be
+    /// linked to that signed-in user instead of signing in.
+    pub async fn authorize(
+        &self,
+        provider: OAuthProvider,
+        link_to: Option<Uuid>,
+    ) -> Result<AuthorizationRequest, ApiError> {
+        let client = self.client(provider)?;
+        let state = random_token(STATE_BYTES);
+        let verifier = random_token(VERIFIER_BYTES);
+
+        sqlx::query(
+            "DELETE FROM oauth_states WHERE expires_at < NOW() - make_interval(hours => $1)",
+        )
+        .bind(STATE_RETENTION_HOURS as i32)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        sqlx::query(
+            r#"
+            INSERT INTO oauth_states (state_hash, provider, code_verifier, user_id, expires_at)
+            VALUES ($1, $2, $3, $4, $5)
+            "#,
+        )
+        .bind(hash_state(&state))
+        .bind(provider.as_str())
+        .bind(&verifier)
+        .bind(link_to)
+        .bind(Utc::now() + self.config.state_ttl)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        Ok(AuthorizationRequest {
+            authorization_url: authorization_url(
+                provider,
+                &client.client_id,
+                &self.config.redirect_uri(provider),
+                &state,
+                &pkce_challenge(&verifier),
+            ),
+            state,
+        })
+    }
+
+    /// Redeem `state`, exchange `code` and fetch the account's profile.
+    pub async fn complete(
+        &self,
+        provider: OAuthProvider,
+        code: &str,
+        state: &str,
+    ) -> Result<OAuthCallback, ApiError> {
+        let client = self.client(provider)?;
+
+        // Two concurrent redemptions: only one of them flips consumed_at.
+        let pending: Option<(String, Option<Uuid>)> = sqlx::query_as(
+            r#"
+            UPDATE oauth_states SET consumed_at = NOW()
+            WHERE state_hash = $1 AND provider = $2 AND consumed_at IS NULL AND expires_at > NOW()
+            RETURNING code_verifier, user_id
+            "#,
+        )
+        .bind(hash_state(state))
+        .bind(provider.as_str())
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        let Some((verifier, link_to)) = pending else {
+            warn!(provider = %provider, "Unknown, used or expired OAuth state");
+            return Err(ApiError::unauthorized("Unknown, used or expired state"));
+        };
+
+        let access_token = self
+            .exchange_code(provider, client, code, &verifier)
+            .await?;
+        let profile = self.fetch_profile(provider, client, &access_token).await?;
+
+        Ok(OAuthCallback { profile, link_to })
+    }
+
+    fn client(&self, provider: OAuthProvider) -> Result<&OAuthClientConfig, ApiError> {
+        self.config
+            .client(provider)
+            .ok_or_else(|| ApiError::bad_request(format!("{} sign-in is not available", provider)))
+    }
+
+    async fn exchange_code(
+        &self,
+        provider: OAuthProvider,
+        client: &OAuthClientConfig,
+        code: &str,
+        verifier: &str,
+    ) -> Result<String, OAuthError> {
+        let redirect_uri = self.config.redirect_uri(provider);
+        let response = self
+            .http
+            .post(provider.token_url())
+            .form(&[
+                ("grant_type", "authorization_code"),
+                ("code", code),
+                ("redirect_uri", redirect_uri.as_str()),
+                ("client_id", client.client_id.as_str()),
+                ("client_secret", client.client_secret.as_str()),
+                ("code_verifier", verifier),
+            ])
+            .send()
+            .await?;
+        let token: TokenResponse = json_response(response).await?;
+        Ok(token.access_token)
+    }
+
+    async fn fetch_profile(
+        &self,
+        provider: OAuthProvider,
+        client: &OAuthClientConfig,
+        access_token: &str,
+    ) -> Result<ExternalProfile, OAuthError> {
+        let mut request = self
+            .http
+            .get(provider.profile_url())
+            .bearer_auth(access_token);
+        if provider == OAuthProvider::Twitch {
+            request = request.header("Client-Id", &client.client_id);
+        }
+        let body: serde_json::Value = json_response(request.send().await?).await?;
+        parse_profile(provider, body)
+    }
+}
+
+async fn json_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, OAuthError> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(OAuthError::Status {
+            status: status.as_u16(),
+            body: response.text().await.unwrap_or_default(),
+        });
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| OAuthError::InvalidResponse(e.to_string()))
+}
+
+/// Normalize a provider's profile response.
+fn parse_profile(
+    provider: OAuthProvider,
+    body: serde_json::Value,
+) -> Result<ExternalProfile, OAuthError> {
+    let invalid = |e: serde_json::Error| OAuthError::InvalidResponse(e.to_string());
+    match provider {
+        OAuthProvider::Google => {
+            let user: GoogleUserInfo = serde_json::from_value(body).map_err(invalid)?;
+            Ok(ExternalProfile {
+                provider,
+                subject: user.sub,
+                email: user.email,
+                email_verified: user.email_verified,
+                username: None,
+                display_name: user.name,
+                avatar_url: user.picture,
+            })
+        }
+        OAuthProvider::Discord => {
+            let user: DiscordUser = serde_json::from_value(body).map_err(invalid)?;
+            let avatar_url = user.avatar.map(|hash| {
+                format!(
+                    "https://cdn.discordapp.com/avatars/{}/{}.png",
+                    user.id, hash
+                )
+            });
+            Ok(ExternalProfile {
+                provider,
+                subject: user.id,
+                email: user.email,
+                email_verified: user.verified,
+                username: Some(user.username),
+                display_name: user.global_name,
+                avatar_url,
+            })
+        }
+        OAuthProvider::Twitch => {
+            let users: TwitchUsers = serde_json::from_value(body).map_err(invalid)?;
+            let user = users
+                .data
+                .into_iter()
+                .next()
+                .ok_or_else(|| OAuthError::InvalidResponse("no user returned".to_string()))?;
+            Ok(ExternalProfile {
+                provider,
+                subject: user.id,
+                email: user.email,
+                // Helix does not say whether the address was confirmed.
+                email_verified: false,
+                username: Some(user.login),
+                display_name: user.display_name,
+                avatar_url: user.profile_image_url,
+            })
+        }
+    }
+}
+
+fn authorization_url(
+    provider: OAuthProvider,
+    client_id: &str,
+    redirect_uri: &str,
+    state: &str,
+    code_challenge: &str,
+) -> String {
+    let mut url = reqwest::Url::parse(provider.authorize_url()).expect("static provider URL");
+    url.query_pairs_mut()
+        .append_pair("response_type", "code")
+        .append_pair("client_id", client_id)
+        .append_pair("redirect_uri", redirect_uri)
+        .append_pair("scope", provider.scopes())
+        .append_pair("state", state)
+        .append_pair("code_challenge", code_challenge)
+        .append_pair("code_challenge_method", "S256");
+    if provider == OAuthProvider::Google {
+        url.query_pairs_mut()
+            .append_pair("prompt", "select_account");
+    }
+    url.into()
+}
+
+/// RFC 7636 S256 challenge for `verifier`.
+fn pkce_challenge(verifier: &str) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(Sha256::digest(verifier.as_bytes()))
+}
+
+fn random_token(bytes: usize) -> String {
+    let mut token = vec![0u8; bytes];
+    rand::rngs::OsRng.fill_bytes(&mut token);
+    general_purpose::URL_SAFE_NO_PAD.encode(token)
+}
+
+fn hash_state(state: &str) -> String {
+    hex::encode(Sha256::digest(state.as_bytes()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pkce_challenge_rfc7636_vector() {
+        assert_eq!(
+            pkce_challenge("dBjftJeZ4CVP-mB92K27uhbUJU1p1r_wW1gFWFOEjXk"),
+            "E9Melhoa2OwvFrEMTJguCHaoeK1t8URWbuGJSstw-cM"
+        );
+    }
+
+    #[test]
+    fn test_authorization_url() {
+        let url = authorization_url(
+            OAuthProvider::Discord,
+            "client",
+            "https://arenax.gg/auth/callback/discord",
+            "state123",
+            "challenge",
+        );
+        let url = reqwest::Url::parse(&url).unwrap();
+        let query: std::collections::HashMap<_, _> = url.query_pairs().into_owned().collect();
+
+        assert_eq!(url.host_str(), Some("discord.com"));
+        assert_eq!(
+            query["redirect_uri"],
+            "https://arenax.gg/auth/callback/discord"
+        );
+        assert_eq!(query["scope"], "identify email");
+        assert_eq!(query["state"], "state123");
+        assert_eq!(query["code_challenge_method"], "S256");
+        assert!(!query.contains_key("prompt"));
+    }
+
+    #[test]
+    fn test_parse_profiles() {
+        let google = parse_profile(
+            OAuthProvider::Google,
+            serde_json::json!({
+                "sub": "1234", "email": "ada@example.com", "email_verified": true,
+                "name": "Ada", "picture": "https://lh3.example/ada.png"
+            }),
+        )
+        .unwrap();
+        assert_eq!(google.subject, "1234");
+        assert!(google.email_verified);
+        assert_eq!(google.username, None);
+
+        let discord = parse_profile(
+            OAuthProvider::Discord,
+            serde_json::json!({
+                "id": "80351110224678912", "username": "nelly", "global_name": "Nelly",
+                "avatar": "8342729096ea3675442027381ff50dfe", "email": "nelly@discord.com",
+                "verified": true
+            }),
+        )
+        .unwrap();
+        assert_eq!(discord.username.as_deref(), Some("nelly"));
+        assert_eq!(
+            discord.avatar_url.as_deref(),
+            Some("https://cdn.discordapp.com/avatars/80351110224678912/8342729096ea3675442027381ff50dfe.png")
+        );
+
+        let twitch = parse_profile(
+            OAuthProvider::Twitch,
+            serde_json::json!({ "data": [{
+                "id": "141981764", "login": "twitchdev", "display_name": "TwitchDev",
+                "email": "dev@twitch.tv", "profile_image_url": "https://static-cdn.jtvnw.net/x.png"
+            }]}),
+        )
+        .unwrap();
+        assert_eq!(twitch.subject, "141981764");
+        assert_eq!(twitch.username.as_deref(), Some("twitchdev"));
+        assert!(!twitch.email_verified);
+
+        assert!(parse_profile(OAuthProvider::Twitch, serde_json::json!({ "data": [] })).is_err());
+    }
+
+    #[test]
+    fn test_provider_from_str() {
+        assert_eq!(
+            "twitch".parse::<OAuthProvider>().unwrap(),
+            OAuthProvider::Twitch
+        );
+        assert!("github".parse::<OAuthProvider>().is_err());
+    }
+}
@@ -0,0 +1,304 @@
+//! Phone number verification with one-time codes over SMS or WhatsApp.
+//!
+//! A six-digit code is texted to the number and kept, hashed, in Redis for
+//! [`CODE_TTL_SECS`] with the number it was sent to. It allows
+//! [`MAX_ATTEMPTS`] guesses before it is discarded. A user can ask for a new
+//! code once every [`RESEND_COOLDOWN_SECS`], and a number receives at most
+//! [`MAX_SENDS_PER_HOUR`] codes an hour whoever asks, which keeps the
+//! endpoint from being used to flood a phone or run up the SMS bill.
+//!
+//! A correct code makes the number the user's verified phone. When
+//! `PHONE_VERIFICATION_REQUIRED` is on, withdrawals need one.
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::service::sms::{SmsChannel, SmsError, SmsGateway};
+use rand::Rng;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+const CODE_TTL_SECS: u64 = 600;
+const MAX_ATTEMPTS: u32 = 5;
+const RESEND_COOLDOWN_SECS: u64 = 60;
+const MAX_SENDS_PER_HOUR: u32 = 5;
+
+/// Response to `POST /api/auth/phone/send`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhoneCodeSent {
+    pub phone_number: String,
+    pub channel: SmsChannel,
+    pub expires_in: u64,
+    /// Seconds until another code can be requested.
+    pub resend_in: u64,
+}
+
+/// Response to `GET /api/auth/phone`.
+#[derive(Debug, Clone, Serialize)]
+pub struct PhoneStatus {
+    pub phone_number: Option<String>,
+    pub verified: bool,
+    /// Whether withdrawals need a verified phone number.
+    pub required: bool,
+}
+
+/// Normalise `input` to E.164: a `+`, then 8 to 15 digits not starting with
+/// 0. Spaces, dashes, dots and parentheses are dropped.
+pub fn normalize_phone(input: &str) -> Result<String, ApiError> {
+    let cleaned: String = input
+        .chars()
+        .filter(|c| !matches!(c, ' ' | '-' | '.' | '(' | ')'))
+        .collect();
+    let digits = cleaned
+        .strip_prefix('+')
+        .or_else(|| cleaned.strip_prefix("00"))
+        .filter(|digits| {
+            (8..=15).contains(&digits.len())
+                && !digits.starts_with('0')
+                && digits.bytes().all(|b| b.is_ascii_digit())
+        })
+        .ok_or_else(|| {
+            ApiError::bad_request(
+                "Phone number must be in international format, e.g. +2348012345678",
+            )
+        })?;
+    Ok(format!("+{}", digits))
+}
+
+fn code_hash(user_id: Uuid, phone: &str, code: &str) -> String {
+    hex::encode(Sha256::digest(
+        format!("{}:{}:{}", user_id, phone, code).as_bytes(),
+    ))
+}
+
+fn redis_error(e: redis::RedisError) -> ApiError {
+    ApiError::RedisError(e.to_string())
+}
+
+pub struct PhoneVerificationService {
+    db_pool: DbPool,
+    redis: ConnectionManager,
+    sms: Arc<SmsGateway>,
+    required: bool,
+}
+
+impl PhoneVerificationService {
+    /// With `required`, withdrawals need a verified phone number.
+    pub fn new(
+        db_pool: DbPool,
+        redis: ConnectionManager,
+        sms: Arc<SmsGateway>,
+        required: bool,
+    ) -> Self {
+        Self {
+            db_pool,
+            redis,
+            sms,
+            required,
+        }
+    }
+
+    pub async fn status(&self, user_id: Uuid) -> Result<PhoneStatus, ApiError> {
+        let (phone_number, verified) = sqlx::query_as::<_, (Option<String>, bool)>(
+            "SELECT phone_number, phone_verified_at IS NOT NULL FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+
+        Ok(PhoneStatus {
+            phone_number,
+            verified,
+            required: self.required,
+        })
+    }
+
+    /// Refuse users without a verified phone number, when one is required.
+    pub async fn require_verified(&self, user_id: Uuid) -> Result<(), ApiError> {
+        if !self.required || self.status(user_id).await?.verified {
+            return Ok(());
+        }
+        Err(ApiError::forbidden("Verify your phone number to continue"))
+    }
+
+    /// Text a fresh code to `phone`, replacing any pending one.
+    pub async fn send_code(
+        &self,
+        user_id: Uuid,
+        phone: &str,
+        channel: SmsChannel,
+    ) -> Result<PhoneCodeSent, ApiError> {
+        let phone = normalize_phone(phone)?;
+        let taken: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM users WHERE phone_number = $1 AND id <> $2)",
+        )
+        .bind(&phone)
+        .bind(user_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        if taken {
+            return Err(ApiError::conflict(
+                "Phone number is in use by another account",
+            ));
+        }
+
+        let mut conn = self.redis.clone();
+        let cooldown_key = format!("phone_otp:cooldown:{}", user_id);
+        let cooldown: Option<String> = redis::cmd("SET")
+            .arg(&cooldown_key)
+            .arg(1)
+            .arg("NX")
+            .arg("EX")
+            .arg(RESEND_COOLDOWN_SECS)
+            .query_async(&mut conn)
+            .await
+            .map_err(redis_error)?;
+        if cooldown.is_none() {
+            return Err(ApiError::TooManyRequests(format!(
+                "Wait {} seconds before requesting another code",
+                RESEND_COOLDOWN_SECS
+            )));
+        }
+        let sends_key = format!("phone_otp:sends:{}", phone);
+        let sends: u32 = conn.incr(&sends_key, 1).await.map_err(redis_error)?;
+        if sends == 1 {
+            conn.expire::<_, ()>(&sends_key, 3600)
+                .await
+                .map_err(redis_error)?;
+        }
+        if sends > MAX_SENDS_PER_HOUR {
+            warn!(user_id = %user_id, "Phone verification codes throttled for number");
+            return Err(ApiError::TooManyRequests(
+                "Too many codes sent to this number; try again later".to_string(),
+            ));
+        }
+
+        let code = format!("{:06}", rand::thread_rng().gen_range(0..1_000_000));
+        let key = format!("phone_otp:{}", user_id);
+        redis::pipe()
+            .atomic()
+            .del(&key)
+            .hset_multiple(
+                &key,
+                &[
+                    ("phone", phone.clone()),
+                    ("code_hash", code_hash(user_id, &phone, &code)),
+                    ("attempts", "0".to_string()),
+                ],
+            )
+            .expire(&key, CODE_TTL_SECS as i64)
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(redis_error)?;
+
+        let text = format!(
+            "Your ArenaX verification code is {}. It expires in {} minutes.",
+            code,
+            CODE_TTL_SECS / 60
+        );
+        if let Err(e) = self.sms.send(&phone, channel, &text).await {
+            // Nothing reached the phone, so the user may retry right away.
+            conn.del::<_, ()>(&[&key, &cooldown_key])
+                .await
+                .map_err(redis_error)?;
+            return Err(match e {
+                SmsError::UnsupportedChannel(_) => {
+                    ApiError::bad_request("WhatsApp is not available for this number; use SMS")
+                }
+                e => ApiError::internal_error(format!("Sending verification code failed: {}", e)),
+            });
+        }
+
+        info!(user_id = %user_id, channel = ?channel, "Phone verification code sent");
+        Ok(PhoneCodeSent {
+            phone_number: phone,
+            channel,
+            expires_in: CODE_TTL_SECS,
+            resend_in: RESEND_COOLDOWN_SECS,
+        })
+    }
+
+    /// Check `code` and, when it matches, make the number it was sent to the
+    /// user's verified phone.
+    pub async fn verify_code(&self, user_id: Uuid, code: &str) -> Result<PhoneStatus, ApiError> {
+        let mut conn = self.redis.clone();
+        let key = format!("phone_otp:{}", user_id);
+        let pending: HashMap<String, String> = conn.hgetall(&key).await.map_err(redis_error)?;
+        let (Some(phone), Some(expected)) = (pending.get("phone"), pending.get("code_hash")) else {
+            return Err(ApiError::bad_request(
+                "No verification code pending; request a new one",
+            ));
+        };
+
+        if code_hash(user_id, phone, code.trim()) != *expected {
+            let attempts: u32 = conn.hincr(&key, "attempts", 1).await.map_err(redis_error)?;
+            warn!(user_id = %user_id, attempts, "Invalid phone verification code");
+            if attempts >= MAX_ATTEMPTS {
+                conn.del::<_, ()>(&key).await.map_err(redis_error)?;
+                return Err(ApiError::TooManyRequests(
+                    "Too many incorrect codes; request a new one".to_string(),
+                ));
+            }
+            return Err(ApiError::bad_request("Invalid verification code"));
+        }
+        // Spend the code before anything else can try it.
+        let removed: u32 = conn.del(&key).await.map_err(redis_error)?;
+        if removed == 0 {
+            return Err(ApiError::bad_request(
+                "No verification code pending; request a new one",
+            ));
+        }
+
+        sqlx::query(
+            "UPDATE users SET phone_number = $1, phone_verified_at = NOW(), updated_at = NOW()
+             WHERE id = $2",
+        )
+        .bind(phone)
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                ApiError::conflict("Phone number is in use by another account")
+            }
+            e => ApiError::database_error(e),
+        })?;
+
+        info!(user_id = %user_id, "Phone number verified");
+        Ok(PhoneStatus {
+            phone_number: Some(phone.clone()),
+            verified: true,
+            required: self.required,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_phone() {
+        assert_eq!(
+            normalize_phone("+234 801 234-5678").unwrap(),
+            "+2348012345678"
+        );
+        assert_eq!(
+            normalize_phone("0044 (20) 7946.0018").unwrap(),
+            "+442079460018"
+        );
+        assert!(normalize_phone("08012345678").is_err());
+        assert!(normalize_phone("+0123456789").is_err());
+        assert!(normalize_phone("+1234").is_err());
+        assert!(normalize_phone("+1234567890123456").is_err());
+        assert!(normalize_phone("+23480123x5678").is_err());
+    }
+}
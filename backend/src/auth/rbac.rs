@@ -0,0 +1,422 @@
+//! Role-based access control.
+//!
+//! Platform roles (`user`, `streamer`, `admin`, ...) are minted into the access
+//! token at sign-in. On-chain roles live on the auth gateway contract: the
+//! chain indexer keeps `chain_account_roles` in step with its
+//! `RoleAssigned`/`RoleRevoked` events, and [`RoleCache`] maps a user's
+//! Stellar addresses onto them.
+//!
+//! [`RoleMiddleware`] authenticates the bearer token, if there is one, and
+//! stores its claims with the on-chain roles added. It never rejects a
+//! request; handlers state what they need with the [`RequireRole`] extractor:
+//!
+//! ```ignore
+//! pub async fn start_tournament(_admin: RequireRole<Admin>, ...) -> ...
+//! ```
+//!
+//! Role lookups are cached in Redis per user and dropped by the indexer when
+//! a role event names one of the user's addresses. Addresses linked after the
+//! lookup are picked up when the entry expires.
+
+use crate::api_error::ApiError;
+use crate::auth::jwt_service::{Claims, JwtService, TokenType};
+use crate::db::DbPool;
+use actix_web::{
+    dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform},
+    Error, FromRequest, HttpMessage, HttpRequest,
+};
+use futures::future::LocalBoxFuture;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::future::{ready, Ready};
+use std::marker::PhantomData;
+use std::rc::Rc;
+use std::sync::Arc;
+use tracing::{debug, warn};
+use uuid::Uuid;
+
+/// How long a user's on-chain roles are cached (seconds).
+const ROLE_CACHE_TTL_SECS: u64 = 300;
+
+/// A role on the auth gateway, by its `Role` discriminant in
+/// `arenax-events`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatewayRole {
+    Admin,
+    Operator,
+    Referee,
+    Player,
+    TournamentManager,
+    Treasury,
+}
+
+impl GatewayRole {
+    /// `None` for `Role::None` (0) and unknown discriminants.
+    pub fn from_discriminant(role: u32) -> Option<Self> {
+        match role {
+            1 => Some(GatewayRole::Admin),
+            2 => Some(GatewayRole::Operator),
+            3 => Some(GatewayRole::Referee),
+            4 => Some(GatewayRole::Player),
+            5 => Some(GatewayRole::TournamentManager),
+            6 => Some(GatewayRole::Treasury),
+            _ => None,
+        }
+    }
+
+    /// Name of the role in JWT claims.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            GatewayRole::Admin => "admin",
+            GatewayRole::Operator => "operator",
+            GatewayRole::Referee => "referee",
+            GatewayRole::Player => "player",
+            GatewayRole::TournamentManager => "tournament_manager",
+            GatewayRole::Treasury => "treasury",
+        }
+    }
+}
+
+// ============================================================================
+// ROLE CACHE
+// ============================================================================
+
+pub struct RoleCache {
+    db_pool: DbPool,
+    redis: ConnectionManager,
+}
+
+impl RoleCache {
+    pub fn new(db_pool: DbPool, redis: ConnectionManager) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// On-chain roles held by any of the user's Stellar addresses.
+    pub async fn roles(&self, user_id: Uuid) -> Result<Vec<String>, ApiError> {
+        let key = cache_key(user_id);
+        let mut redis = self.redis.clone();
+        match redis.get::<_, Option<String>>(&key).await {
+            Ok(Some(cached)) => return Ok(split_roles(&cached)),
+            Ok(None) => {}
+            Err(e) => warn!(error = %e, "Role cache read failed"),
+        }
+
+        let roles = self.load(user_id).await?;
+        if let Err(e) = redis
+            .set_ex::<_, _, ()>(&key, roles.join(","), ROLE_CACHE_TTL_SECS)
+            .await
+        {
+            warn!(error = %e, "Role cache write failed");
+        }
+        Ok(roles)
+    }
+
+    /// Forget the cached roles of every user holding `address`.
+    pub async fn invalidate_address(&self, address: &str) {
+        let user_ids: Vec<Uuid> = match sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM wallets WHERE stellar_public_key = $1
+            UNION
+            SELECT id FROM users WHERE stellar_public_key = $1
+            "#,
+        )
+        .bind(address)
+        .fetch_all(&self.db_pool)
+        .await
+        {
+            Ok(user_ids) => user_ids,
+            Err(e) => {
+                warn!(error = %e, address, "Failed to resolve users for role invalidation");
+                return;
+            }
+        };
+
+        let mut redis = self.redis.clone();
+        for user_id in user_ids {
+            match redis.del::<_, ()>(cache_key(user_id)).await {
+                Ok(()) => debug!(%user_id, address, "Cached roles invalidated"),
+                Err(e) => warn!(error = %e, %user_id, "Role cache invalidation failed"),
+            }
+        }
+    }
+
+    /// Add the user's on-chain roles to `claims`. Claims are left as they are
+    /// when the lookup fails, so the request only loses on-chain privileges.
+    pub async fn enrich(&self, claims: &mut Claims) {
+        let Ok(user_id) = Uuid::parse_str(&claims.sub) else {
+            return;
+        };
+        match self.roles(user_id).await {
+            Ok(roles) => merge_roles(&mut claims.roles, roles),
+            Err(e) => warn!(error = %e, %user_id, "On-chain role lookup failed"),
+        }
+    }
+
+    async fn load(&self, user_id: Uuid) -> Result<Vec<String>, ApiError> {
+        let discriminants: Vec<i32> = sqlx::query_scalar(
+            r#"
+            SELECT role FROM chain_account_roles
+            WHERE address IN (
+                SELECT stellar_public_key FROM wallets WHERE user_id = $1
+                UNION
+                SELECT stellar_public_key FROM users WHERE id = $1
+            )
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let mut roles = Vec::new();
+        let names = discriminants
+            .into_iter()
+            .filter_map(|role| GatewayRole::from_discriminant(role as u32))
+            .map(|role| role.as_str().to_string());
+        merge_roles(&mut roles, names);
+        Ok(roles)
+    }
+}
+
+fn cache_key(user_id: Uuid) -> String {
+    format!("rbac:roles:{}", user_id)
+}
+
+fn split_roles(cached: &str) -> Vec<String> {
+    cached
+        .split(',')
+        .filter(|role| !role.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+fn merge_roles(roles: &mut Vec<String>, extra: impl IntoIterator<Item = String>) {
+    for role in extra {
+        if !roles.contains(&role) {
+            roles.push(role);
+        }
+    }
+}
+
+// ============================================================================
+// MIDDLEWARE
+// ============================================================================
+
+/// Optional authentication with on-chain roles; see the module docs.
+pub struct RoleMiddleware {
+    jwt_service: Arc<JwtService>,
+    roles: Arc<RoleCache>,
+}
+
+impl RoleMiddleware {
+    pub fn new(jwt_service: Arc<JwtService>, roles: Arc<RoleCache>) -> Self {
+        Self { jwt_service, roles }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RoleMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = RoleMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RoleMiddlewareService {
+            service: Rc::new(service),
+            jwt_service: self.jwt_service.clone(),
+            roles: self.roles.clone(),
+        }))
+    }
+}
+
+pub struct RoleMiddlewareService<S> {
+    service: Rc<S>,
+    jwt_service: Arc<JwtService>,
+    roles: Arc<RoleCache>,
+}
+
+impl<S, B> Service<ServiceRequest> for RoleMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    S::Future: 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let jwt_service = self.jwt_service.clone();
+        let roles = self.roles.clone();
+        let service = self.service.clone();
+
+        Box::pin(async move {
+            let token = req
+                .headers()
+                .get("Authorization")
+                .and_then(|h| h.to_str().ok())
+                .and_then(|s| s.strip_prefix("Bearer "))
+                .map(str::to_string);
+
+            // Invalid tokens are ignored here: endpoints that need a user
+            // reject the request for lack of claims.
+            if let Some(token) = token {
+                match jwt_service.validate_token(&token).await {
+                    Ok(mut claims) if claims.token_type == TokenType::Access => {
+                        roles.enrich(&mut claims).await;
+                        req.extensions_mut().insert(claims);
+                    }
+                    Ok(_) => debug!("Refresh token presented as bearer token"),
+                    Err(e) => debug!(error = %e, "Bearer token rejected"),
+                }
+            }
+
+            service.call(req).await
+        })
+    }
+}
+
+// ============================================================================
+// EXTRACTORS
+// ============================================================================
+
+/// Roles that satisfy a [`RequireRole`] extractor.
+pub trait RoleRequirement {
+    /// Holding any one of these is enough.
+    const ROLES: &'static [&'static str];
+}
+
+macro_rules! role_requirement {
+    ($(#[$doc:meta])* $name:ident => [$($role:literal),+ $(,)?]) => {
+        $(#[$doc])*
+        pub struct $name;
+
+        impl RoleRequirement for $name {
+            const ROLES: &'static [&'static str] = &[$($role),+];
+        }
+    };
+}
+
+role_requirement!(
+    /// Platform or gateway administrators.
+    Admin => ["admin"]
+);
+role_requirement!(
+    /// Gateway operators.
+    Operator => ["operator", "admin"]
+);
+role_requirement!(
+    /// Match referees.
+    Referee => ["referee", "admin"]
+);
+role_requirement!(
+    /// Tournament organizers, platform or on-chain.
+    TournamentManager => ["tournament_manager", "organizer", "admin"]
+);
+role_requirement!(
+    /// Treasury managers.
+    Treasury => ["treasury", "admin"]
+);
+role_requirement!(
+    /// Compliance staff reading the audit trail.
+    Auditor => ["auditor", "admin"]
+);
+
+/// Extractor that rejects the request with 401 without a signed-in user, and
+/// with 403 unless the user holds one of `R::ROLES`.
+pub struct RequireRole<R: RoleRequirement> {
+    pub user_id: Uuid,
+    pub claims: Claims,
+    _requirement: PhantomData<R>,
+}
+
+impl<R: RoleRequirement> RequireRole<R> {
+    fn check(claims: Option<Claims>) -> Result<Self, ApiError> {
+        let claims = claims.ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+        if !claims
+            .roles
+            .iter()
+            .any(|role| R::ROLES.contains(&role.as_str()))
+        {
+            return Err(ApiError::forbidden(format!(
+                "Requires one of the roles: {}",
+                R::ROLES.join(", ")
+            )));
+        }
+        let user_id = Uuid::parse_str(&claims.sub)
+            .map_err(|_| ApiError::unauthorized("Invalid token subject"))?;
+        Ok(Self {
+            user_id,
+            claims,
+            _requirement: PhantomData,
+        })
+    }
+}
+
+impl<R: RoleRequirement> FromRequest for RequireRole<R> {
+    type Error = ApiError;
+    type Future = Ready<Result<Self, Self::Error>>;
+
+    fn from_request(req: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        ready(Self::check(req.extensions().get::<Claims>().cloned()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn claims(roles: &[&str]) -> Claims {
+        Claims {
+            sub: Uuid::new_v4().to_string(),
+            exp: 9999999999,
+            iat: 0,
+            jti: "test-jti".to_string(),
+            token_type: TokenType::Access,
+            device_id: None,
+            session_id: "test-session".to_string(),
+            roles: roles.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_gateway_role_discriminants() {
+        assert_eq!(GatewayRole::from_discriminant(0), None);
+        assert_eq!(
+            GatewayRole::from_discriminant(5),
+            Some(GatewayRole::TournamentManager)
+        );
+        assert_eq!(GatewayRole::from_discriminant(7), None);
+        assert_eq!(GatewayRole::Referee.as_str(), "referee");
+    }
+
+    #[test]
+    fn test_require_role() {
+        assert!(matches!(
+            RequireRole::<Admin>::check(None),
+            Err(ApiError::Unauthorized)
+        ));
+        assert!(matches!(
+            RequireRole::<Admin>::check(Some(claims(&["user", "referee"]))),
+            Err(ApiError::Forbidden)
+        ));
+        assert!(RequireRole::<Referee>::check(Some(claims(&["user", "referee"]))).is_ok());
+        assert!(RequireRole::<Treasury>::check(Some(claims(&["admin"]))).is_ok());
+    }
+
+    #[test]
+    fn test_merge_and_split_roles() {
+        let mut roles = vec!["user".to_string()];
+        merge_roles(&mut roles, split_roles("admin,user,"));
+        assert_eq!(roles, vec!["user", "admin"]);
+        assert!(split_roles("").is_empty());
+    }
+}
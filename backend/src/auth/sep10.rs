@@ -0,0 +1,372 @@
+//! SEP-10 Stellar web authentication.
+//!
+//! A wallet (Freighter, Albedo, ...) asks for a challenge for its account,
+//! co-signs the returned transaction and posts it back. The challenge is a
+//! `ManageData` transaction from the server signing account with sequence
+//! number 0, so it can never be submitted to the network:
+//!
+//! 1. `<home domain> auth`, sourced from the client account, carrying a random
+//!    64-byte nonce;
+//! 2. `web_auth_domain`, sourced from the server account.
+//!
+//! Challenges are recorded when issued and redeemed at most once before they
+//! expire, so the transaction is never parsed back: its hash identifies the
+//! challenge we built. Only the account's master key is accepted as client
+//! signer; accounts that disabled it or rely on multisig cannot sign in.
+
+use crate::api_error::ApiError;
+use crate::config::{AuthConfig, StellarConfig};
+use crate::db::DbPool;
+use crate::service::stellar_relayer::xdr::{
+    self, DecoratedSignature, Keypair, Limits, ManageDataOp, ManageDataTx, WriteXdr,
+};
+use crate::service::stellar_relayer::RelayerError;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{DateTime, Duration, Utc};
+use rand::RngCore;
+use serde::Serialize;
+use tracing::{info, warn};
+
+/// How long a challenge may be signed and redeemed (seconds).
+const CHALLENGE_TTL_SECS: i64 = 900;
+/// Redeemed and expired challenges are kept this long for auditing (hours).
+const CHALLENGE_RETENTION_HOURS: i64 = 24;
+/// Random bytes in the nonce; base64-encoded they fill the 64-byte value.
+const NONCE_BYTES: usize = 48;
+const BASE_FEE: u32 = 100;
+
+#[derive(Debug, Clone)]
+pub struct Sep10Config {
+    pub home_domain: String,
+    pub web_auth_domain: String,
+    pub signing_secret: String,
+    pub network_passphrase: String,
+    pub challenge_ttl: Duration,
+}
+
+impl Sep10Config {
+    pub fn from_config(auth: &AuthConfig, stellar: &StellarConfig) -> Self {
+        Self {
+            home_domain: auth.sep10_home_domain.clone(),
+            web_auth_domain: auth.sep10_web_auth_domain.clone(),
+            signing_secret: auth.sep10_signing_secret.clone(),
+            network_passphrase: stellar.network_passphrase().to_string(),
+            challenge_ttl: Duration::seconds(CHALLENGE_TTL_SECS),
+        }
+    }
+}
+
+/// Response to `GET /api/auth/sep10`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Sep10Challenge {
+    /// Base64 `TransactionEnvelope` signed by the server.
+    pub transaction: String,
+    pub network_passphrase: String,
+}
+
+pub struct Sep10Service {
+    db_pool: DbPool,
+    config: Sep10Config,
+    signer: Keypair,
+}
+
+impl Sep10Service {
+    pub fn new(db_pool: DbPool, config: Sep10Config) -> Result<Self, RelayerError> {
+        let signer = Keypair::from_secret(&config.signing_secret)?;
+        Ok(Self {
+            db_pool,
+            config,
+            signer,
+        })
+    }
+
+    /// Account published as `SIGNING_KEY` in `stellar.toml`.
+    pub fn signing_key(&self) -> String {
+        self.signer.account_id()
+    }
+
+    /// Issue a challenge for `account`. `home_domain`, when the client names
+    /// one, must be the configured home domain.
+    pub async fn challenge(
+        &self,
+        account: &str,
+        home_domain: Option<&str>,
+    ) -> Result<Sep10Challenge, ApiError> {
+        if home_domain.is_some_and(|domain| domain != self.config.home_domain) {
+            return Err(ApiError::bad_request("Unsupported home_domain"));
+        }
+        let client = xdr::account_public_key(account)
+            .map_err(|_| ApiError::bad_request("account must be a Stellar account ID (G...)"))?;
+
+        let mut nonce = [0u8; NONCE_BYTES];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        let now = Utc::now();
+        let expires_at = now + self.config.challenge_ttl;
+        let build_error = |e: String| {
+            ApiError::internal_error(format!("Failed to build SEP-10 challenge: {}", e))
+        };
+        let tx = build_challenge(
+            &self.signer.public_key(),
+            &client,
+            &self.config,
+            &nonce,
+            now,
+            expires_at,
+        )
+        .transaction()
+        .map_err(|e| build_error(e.to_string()))?;
+        let signed = xdr::sign_transaction(&tx, &self.config.network_passphrase, &self.signer)
+            .map_err(|e| build_error(e.to_string()))?;
+        let tx_xdr = tx
+            .to_xdr_base64(Limits::none())
+            .map_err(|e| build_error(e.to_string()))?;
+
+        sqlx::query(
+            "DELETE FROM sep10_challenges WHERE expires_at < NOW() - make_interval(hours => $1)",
+        )
+        .bind(CHALLENGE_RETENTION_HOURS as i32)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        sqlx::query(
+            r#"
+            INSERT INTO sep10_challenges (tx_hash, account, transaction, expires_at)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(&signed.hash)
+        .bind(account)
+        .bind(&tx_xdr)
+        .bind(expires_at)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        Ok(Sep10Challenge {
+            transaction: signed.xdr,
+            network_passphrase: self.config.network_passphrase.clone(),
+        })
+    }
+
+    /// Redeem a co-signed challenge and return the Stellar account it proves.
+    pub async fn verify(&self, envelope_xdr: &str) -> Result<String, ApiError> {
+        let (tx, signatures) = xdr::split_envelope(envelope_xdr)
+            .map_err(|_| ApiError::bad_request("Malformed challenge transaction"))?;
+        let hash = xdr::transaction_hash(&self.config.network_passphrase, &tx)
+            .map_err(|_| ApiError::bad_request("Malformed challenge transaction"))?;
+        let tx_hash = hex::encode(hash);
+
+        let challenge: Option<(String, DateTime<Utc>, Option<DateTime<Utc>>)> = sqlx::query_as(
+            "SELECT account, expires_at, consumed_at FROM sep10_challenges WHERE tx_hash = $1",
+        )
+        .bind(&tx_hash)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        let Some((account, expires_at, consumed_at)) = challenge else {
+            return Err(ApiError::unauthorized("Unknown challenge"));
+        };
+        if consumed_at.is_some() {
+            warn!(account = %account, tx_hash = %tx_hash, "SEP-10 challenge replayed");
+            return Err(ApiError::unauthorized("Challenge already used"));
+        }
+        if expires_at <= Utc::now() {
+            return Err(ApiError::unauthorized("Challenge expired"));
+        }
+
+        let client = xdr::account_public_key(&account)
+            .map_err(|e| ApiError::internal_error(format!("Stored SEP-10 account: {}", e)))?;
+        check_signatures(&signatures, &self.signer.public_key(), &client, &hash)
+            .map_err(ApiError::unauthorized)?;
+
+        // Two concurrent redemptions: only one of them flips consumed_at.
+        let consumed = sqlx::query(
+            "UPDATE sep10_challenges SET consumed_at = NOW() WHERE tx_hash = $1 AND consumed_at IS NULL",
+        )
+        .bind(&tx_hash)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        if consumed.rows_affected() == 0 {
+            return Err(ApiError::unauthorized("Challenge already used"));
+        }
+
+        info!(account = %account, "SEP-10 challenge verified");
+        Ok(account)
+    }
+}
+
+/// The unsigned challenge transaction for `client`.
+fn build_challenge(
+    server: &[u8; 32],
+    client: &[u8; 32],
+    config: &Sep10Config,
+    nonce: &[u8; NONCE_BYTES],
+    issued_at: DateTime<Utc>,
+    expires_at: DateTime<Utc>,
+) -> ManageDataTx {
+    ManageDataTx {
+        source: *server,
+        fee: BASE_FEE * 2,
+        sequence: 0,
+        min_time: issued_at.timestamp() as u64,
+        max_time: expires_at.timestamp() as u64,
+        operations: vec![
+            ManageDataOp {
+                source: *client,
+                name: format!("{} auth", config.home_domain),
+                value: general_purpose::STANDARD.encode(nonce).into_bytes(),
+            },
+            ManageDataOp {
+                source: *server,
+                name: "web_auth_domain".to_string(),
+                value: config.web_auth_domain.clone().into_bytes(),
+            },
+        ],
+    }
+}
+
+/// A redeemable challenge carries exactly the server's and the client's
+/// signatures.
+fn check_signatures(
+    signatures: &[DecoratedSignature],
+    server: &[u8; 32],
+    client: &[u8; 32],
+    hash: &[u8; 32],
+) -> Result<(), &'static str> {
+    let signed_by = |key: &[u8; 32]| signatures.iter().any(|s| s.is_valid_for(key, hash));
+    if !signed_by(server) {
+        return Err("Challenge is not signed by the server");
+    }
+    if !signed_by(client) {
+        return Err("Challenge is not signed by the account");
+    }
+    if signatures.len() != 2 {
+        return Err("Challenge carries unrecognised signatures");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::stellar_relayer::xdr::Transaction;
+    use crate::service::stellar_service::stellar_strkey_encode;
+    use chrono::TimeZone;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn keypair(seed: u8) -> Keypair {
+        let secret = stellar_strkey_encode(18 << 3, &[seed; 32]).unwrap();
+        Keypair::from_secret(&secret).unwrap()
+    }
+
+    fn at(secs: i64) -> DateTime<Utc> {
+        Utc.timestamp_opt(secs, 0).unwrap()
+    }
+
+    fn config() -> Sep10Config {
+        Sep10Config {
+            home_domain: "arenax.gg".to_string(),
+            web_auth_domain: "api.arenax.gg".to_string(),
+            signing_secret: String::new(),
+            network_passphrase: PASSPHRASE.to_string(),
+            challenge_ttl: Duration::seconds(CHALLENGE_TTL_SECS),
+        }
+    }
+
+    fn challenge(server: &Keypair, client: &Keypair) -> Transaction {
+        build_challenge(
+            &server.public_key(),
+            &client.public_key(),
+            &config(),
+            &[7; NONCE_BYTES],
+            at(1_700_000_000),
+            at(1_700_000_900),
+        )
+        .transaction()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_challenge_operations() {
+        let tx = build_challenge(
+            &[1; 32],
+            &[2; 32],
+            &config(),
+            &[7; NONCE_BYTES],
+            at(1_700_000_000),
+            at(1_700_000_900),
+        );
+        assert_eq!(tx.sequence, 0);
+        assert_eq!(tx.max_time - tx.min_time, CHALLENGE_TTL_SECS as u64);
+        assert_eq!(tx.operations[0].source, [2; 32]);
+        assert_eq!(tx.operations[0].name, "arenax.gg auth");
+        assert_eq!(tx.operations[0].value.len(), 64);
+        assert_eq!(tx.operations[1].source, [1; 32]);
+        assert_eq!(tx.operations[1].value, b"api.arenax.gg");
+    }
+
+    #[test]
+    fn test_check_signatures_requires_server_and_client() {
+        let server = keypair(1);
+        let client = keypair(2);
+        let tx = challenge(&server, &client);
+        let hash = xdr::transaction_hash(PASSPHRASE, &tx).unwrap();
+        let issued = xdr::sign_transaction(&tx, PASSPHRASE, &server).unwrap().xdr;
+
+        let (_, signatures) = xdr::split_envelope(&issued).unwrap();
+        assert_eq!(
+            check_signatures(
+                &signatures,
+                &server.public_key(),
+                &client.public_key(),
+                &hash
+            ),
+            Err("Challenge is not signed by the account")
+        );
+
+        let cosigned = xdr::cosign_envelope(&issued, PASSPHRASE, &client).unwrap();
+        let (_, signatures) = xdr::split_envelope(&cosigned).unwrap();
+        assert!(check_signatures(
+            &signatures,
+            &server.public_key(),
+            &client.public_key(),
+            &hash
+        )
+        .is_ok());
+
+        let extra = xdr::cosign_envelope(&cosigned, PASSPHRASE, &keypair(3)).unwrap();
+        let (_, signatures) = xdr::split_envelope(&extra).unwrap();
+        assert_eq!(
+            check_signatures(
+                &signatures,
+                &server.public_key(),
+                &client.public_key(),
+                &hash
+            ),
+            Err("Challenge carries unrecognised signatures")
+        );
+    }
+
+    #[test]
+    fn test_check_signatures_rejects_client_only() {
+        let server = keypair(1);
+        let client = keypair(2);
+        let tx = challenge(&server, &client);
+        let hash = xdr::transaction_hash(PASSPHRASE, &tx).unwrap();
+        let forged = xdr::sign_transaction(&tx, PASSPHRASE, &client).unwrap().xdr;
+        let forged = xdr::cosign_envelope(&forged, PASSPHRASE, &client).unwrap();
+
+        let (_, signatures) = xdr::split_envelope(&forged).unwrap();
+        assert_eq!(
+            check_signatures(
+                &signatures,
+                &server.public_key(),
+                &client.public_key(),
+                &hash
+            ),
+            Err("Challenge is not signed by the server")
+        );
+    }
+}
@@ -0,0 +1,61 @@
+//! Step-up authentication for high-risk actions such as withdrawals.
+//!
+//! The caller proves presence again with either a passkey step-up token
+//! (`X-Step-Up-Token`, from `POST /api/auth/passkeys/step-up` on one of their
+//! devices) or a two-factor code (`X-2FA-Code`). A token, when sent, must be
+//! valid; otherwise the two-factor rules apply, so users without a passkey
+//! are unaffected.
+
+use crate::api_error::ApiError;
+use crate::auth::two_factor::{self, TwoFactorService};
+use crate::auth::webauthn::PasskeyService;
+use actix_web::HttpRequest;
+use std::sync::Arc;
+use uuid::Uuid;
+
+pub const TOKEN_HEADER: &str = "X-Step-Up-Token";
+
+/// The step-up token sent with `req`, if any.
+pub fn token_from_request(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(TOKEN_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+}
+
+#[derive(Clone, Default)]
+pub struct StepUp {
+    two_factor: Option<Arc<TwoFactorService>>,
+    passkeys: Option<Arc<PasskeyService>>,
+}
+
+impl StepUp {
+    pub fn new(
+        two_factor: Option<Arc<TwoFactorService>>,
+        passkeys: Option<Arc<PasskeyService>>,
+    ) -> Self {
+        Self {
+            two_factor,
+            passkeys,
+        }
+    }
+
+    /// Check that `user_id` stepped up for this request.
+    pub async fn require(&self, req: &HttpRequest, user_id: Uuid) -> Result<(), ApiError> {
+        if let (Some(passkeys), Some(token)) = (&self.passkeys, token_from_request(req)) {
+            return match passkeys.consume_step_up(user_id, token).await? {
+                true => Ok(()),
+                false => Err(ApiError::unauthorized("Invalid or expired step-up token")),
+            };
+        }
+        match &self.two_factor {
+            Some(service) => {
+                service
+                    .require(user_id, two_factor::code_from_request(req))
+                    .await
+            }
+            None => Ok(()),
+        }
+    }
+}
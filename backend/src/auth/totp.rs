@@ -0,0 +1,262 @@
+//! TOTP codes (RFC 6238), their secrets, and recovery codes.
+//!
+//! Secrets are 20 random bytes, shown to the user once as base32 inside an
+//! `otpauth://` URI (rendered as a QR code by the client) and stored
+//! encrypted with AES-256-GCM. Codes are 6 digits over 30-second steps with
+//! HMAC-SHA1 — the parameters every authenticator app supports — and one step
+//! of clock drift is tolerated either way.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Nonce};
+use base64::{engine::general_purpose, Engine as _};
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha1::Sha1;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+pub const SECRET_BYTES: usize = 20;
+pub const DIGITS: usize = 6;
+pub const STEP_SECS: i64 = 30;
+/// Steps of clock drift accepted either side of the current one.
+pub const DRIFT_STEPS: i64 = 1;
+/// Recovery codes handed out per enrolment.
+pub const RECOVERY_CODES: usize = 10;
+
+const NONCE_BYTES: usize = 12;
+const BASE32_ALPHABET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+#[derive(Debug, Error)]
+pub enum TotpError {
+    #[error("Invalid encryption key: {0}")]
+    InvalidKey(String),
+
+    #[error("Secret could not be decrypted")]
+    Decrypt,
+}
+
+pub fn generate_secret() -> Vec<u8> {
+    let mut secret = vec![0u8; SECRET_BYTES];
+    rand::rngs::OsRng.fill_bytes(&mut secret);
+    secret
+}
+
+/// RFC 4648 base32 without padding, as authenticator apps expect.
+pub fn base32_encode(data: &[u8]) -> String {
+    let mut out = String::with_capacity(data.len().div_ceil(5) * 8);
+    for chunk in data.chunks(5) {
+        let mut buf = [0u8; 5];
+        buf[..chunk.len()].copy_from_slice(chunk);
+        let bits = buf.iter().fold(0u64, |acc, b| (acc << 8) | *b as u64);
+        let chars = (chunk.len() * 8).div_ceil(5);
+        for i in 0..chars {
+            let index = (bits >> (35 - i * 5)) & 0x1f;
+            out.push(BASE32_ALPHABET[index as usize] as char);
+        }
+    }
+    out
+}
+
+/// `otpauth://` URI to enrol `secret` in an authenticator app.
+pub fn provisioning_uri(secret: &[u8], issuer: &str, account: &str) -> String {
+    format!(
+        "otpauth://totp/{}:{}?secret={}&issuer={}&algorithm=SHA1&digits={}&period={}",
+        uri_encode(issuer),
+        uri_encode(account),
+        base32_encode(secret),
+        uri_encode(issuer),
+        DIGITS,
+        STEP_SECS
+    )
+}
+
+fn uri_encode(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+/// Time step containing `unix_time`.
+pub fn step_at(unix_time: i64) -> i64 {
+    unix_time.div_euclid(STEP_SECS)
+}
+
+/// The code for `step` (HOTP, RFC 4226, with the step as counter).
+pub fn code_at_step(secret: &[u8], step: i64) -> String {
+    let mut mac = <Hmac<Sha1> as Mac>::new_from_slice(secret).expect("HMAC accepts any key length");
+    mac.update(&step.to_be_bytes());
+    let digest = mac.finalize().into_bytes();
+
+    let offset = (digest[digest.len() - 1] & 0x0f) as usize;
+    let binary = u32::from_be_bytes([
+        digest[offset] & 0x7f,
+        digest[offset + 1],
+        digest[offset + 2],
+        digest[offset + 3],
+    ]);
+    format!(
+        "{:0width$}",
+        binary % 10u32.pow(DIGITS as u32),
+        width = DIGITS
+    )
+}
+
+/// The step whose code is `code`, within the drift window around
+/// `unix_time`.
+pub fn verify(secret: &[u8], code: &str, unix_time: i64) -> Option<i64> {
+    if code.len() != DIGITS || !code.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let now = step_at(unix_time);
+    (now - DRIFT_STEPS..=now + DRIFT_STEPS)
+        .find(|step| constant_time_eq(code_at_step(secret, *step).as_bytes(), code.as_bytes()))
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+/// Fresh recovery codes, formatted `xxxxx-xxxxx`.
+pub fn generate_recovery_codes() -> Vec<String> {
+    (0..RECOVERY_CODES)
+        .map(|_| {
+            let mut bytes = [0u8; 10];
+            rand::rngs::OsRng.fill_bytes(&mut bytes);
+            let chars: String = bytes
+                .iter()
+                .map(|b| BASE32_ALPHABET[(*b & 0x1f) as usize].to_ascii_lowercase() as char)
+                .collect();
+            format!("{}-{}", &chars[..5], &chars[5..])
+        })
+        .collect()
+}
+
+/// Whether `code` looks like a TOTP code rather than a recovery code.
+pub fn is_totp_code(code: &str) -> bool {
+    code.len() == DIGITS && code.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// SHA-256 of a recovery code, ignoring case, dashes and whitespace.
+pub fn hash_recovery_code(code: &str) -> String {
+    let normalized: String = code
+        .chars()
+        .filter(|c| *c != '-' && !c.is_whitespace())
+        .map(|c| c.to_ascii_lowercase())
+        .collect();
+    hex::encode(Sha256::digest(normalized.as_bytes()))
+}
+
+/// Encrypts TOTP secrets at rest with AES-256-GCM; the stored form is
+/// base64 of nonce followed by ciphertext.
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    /// `key` is 32 bytes as 64 hex characters.
+    pub fn from_hex(key: &str) -> Result<Self, TotpError> {
+        let key = hex::decode(key.trim()).map_err(|e| TotpError::InvalidKey(e.to_string()))?;
+        let cipher = Aes256Gcm::new_from_slice(&key)
+            .map_err(|_| TotpError::InvalidKey("expected 32 bytes".to_string()))?;
+        Ok(Self { cipher })
+    }
+
+    pub fn encrypt(&self, secret: &[u8]) -> String {
+        let mut nonce = [0u8; NONCE_BYTES];
+        rand::rngs::OsRng.fill_bytes(&mut nonce);
+        let ciphertext = self
+            .cipher
+            .encrypt(Nonce::from_slice(&nonce), secret)
+            .expect("AES-GCM encryption of a short secret cannot fail");
+        let mut stored = nonce.to_vec();
+        stored.extend_from_slice(&ciphertext);
+        general_purpose::STANDARD.encode(stored)
+    }
+
+    pub fn decrypt(&self, stored: &str) -> Result<Vec<u8>, TotpError> {
+        let stored = general_purpose::STANDARD
+            .decode(stored)
+            .map_err(|_| TotpError::Decrypt)?;
+        if stored.len() <= NONCE_BYTES {
+            return Err(TotpError::Decrypt);
+        }
+        let (nonce, ciphertext) = stored.split_at(NONCE_BYTES);
+        self.cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| TotpError::Decrypt)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// RFC 6238 appendix B SHA-1 secret.
+    const RFC_SECRET: &[u8] = b"12345678901234567890";
+
+    #[test]
+    fn test_rfc6238_vectors() {
+        // The RFC lists 8-digit codes; these are their last 6 digits.
+        for (time, code) in [
+            (59, "287082"),
+            (1_111_111_109, "081804"),
+            (1_234_567_890, "005924"),
+            (2_000_000_000, "279037"),
+        ] {
+            assert_eq!(code_at_step(RFC_SECRET, step_at(time)), code);
+        }
+    }
+
+    #[test]
+    fn test_verify_allows_one_step_of_drift() {
+        let now = 1_234_567_890;
+        let previous = code_at_step(RFC_SECRET, step_at(now) - 1);
+        let stale = code_at_step(RFC_SECRET, step_at(now) - 2);
+
+        assert_eq!(verify(RFC_SECRET, "005924", now), Some(step_at(now)));
+        assert_eq!(verify(RFC_SECRET, &previous, now), Some(step_at(now) - 1));
+        assert_eq!(verify(RFC_SECRET, &stale, now), None);
+        assert_eq!(verify(RFC_SECRET, "5924", now), None);
+    }
+
+    #[test]
+    fn test_base32_and_provisioning_uri() {
+        assert_eq!(base32_encode(b"foobar"), "MZXW6YTBOI");
+        assert_eq!(base32_encode(b"fooba"), "MZXW6YTB");
+
+        let uri = provisioning_uri(b"foobar", "ArenaX", "ada@arenax.gg");
+        assert_eq!(
+            uri,
+            "otpauth://totp/ArenaX:ada%40arenax.gg?secret=MZXW6YTBOI&issuer=ArenaX\
+             &algorithm=SHA1&digits=6&period=30"
+        );
+    }
+
+    #[test]
+    fn test_recovery_codes() {
+        let codes = generate_recovery_codes();
+        assert_eq!(codes.len(), RECOVERY_CODES);
+        assert!(codes.iter().all(|c| c.len() == 11 && !is_totp_code(c)));
+        assert_eq!(
+            hash_recovery_code(&codes[0]),
+            hash_recovery_code(&codes[0].replace('-', " ").to_uppercase())
+        );
+    }
+
+    #[test]
+    fn test_secret_cipher_roundtrip() {
+        let cipher = SecretCipher::from_hex(&"2a".repeat(32)).unwrap();
+        let stored = cipher.encrypt(RFC_SECRET);
+        assert_eq!(cipher.decrypt(&stored).unwrap(), RFC_SECRET);
+
+        let other = SecretCipher::from_hex(&"3b".repeat(32)).unwrap();
+        assert!(matches!(other.decrypt(&stored), Err(TotpError::Decrypt)));
+        assert!(SecretCipher::from_hex("abcd").is_err());
+    }
+}
@@ -0,0 +1,382 @@
+//! Two-factor authentication with TOTP and recovery codes.
+//!
+//! Enrolment takes two steps: `setup` stores a fresh secret, not yet
+//! enforced, and returns it with its `otpauth://` URI; `enable` checks that
+//! the user's authenticator produces valid codes and hands out one-time
+//! recovery codes. From then on a code is needed to sign in and for sensitive
+//! actions — withdrawals and linking a Stellar wallet. Each TOTP code is
+//! accepted once: the last step used is kept, so a code seen by someone else
+//! cannot be replayed within its window.
+//!
+//! Users whose role is listed in `TWO_FACTOR_REQUIRED_ROLES` must enrol. They
+//! can still sign in to do so, but sensitive actions are refused until they
+//! have.
+
+use crate::api_error::ApiError;
+use crate::auth::totp::{self, SecretCipher, TotpError};
+use crate::config::AuthConfig;
+use crate::db::DbPool;
+use actix_web::HttpRequest;
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::FromRow;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+/// Header carrying the code for sensitive actions.
+pub const CODE_HEADER: &str = "X-2FA-Code";
+
+/// The two-factor code sent with a request, if any.
+pub fn code_from_request(req: &HttpRequest) -> Option<&str> {
+    req.headers()
+        .get(CODE_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(str::trim)
+        .filter(|code| !code.is_empty())
+}
+
+#[derive(Debug, Clone)]
+pub struct TwoFactorConfig {
+    pub encryption_key: String,
+    pub issuer: String,
+    pub required_roles: Vec<String>,
+}
+
+impl TwoFactorConfig {
+    /// `None` when no encryption key is configured.
+    pub fn from_config(auth: &AuthConfig) -> Option<Self> {
+        Some(Self {
+            encryption_key: auth.totp_encryption_key.clone()?,
+            issuer: auth.totp_issuer.clone(),
+            required_roles: auth.two_factor_required_roles.clone(),
+        })
+    }
+}
+
+/// Response to `POST /api/auth/2fa/setup`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TwoFactorSetup {
+    /// Base32 secret, for manual entry.
+    pub secret: String,
+    /// URI to render as a QR code.
+    pub otpauth_uri: String,
+}
+
+/// Response to `GET /api/auth/2fa`.
+#[derive(Debug, Clone, Serialize)]
+pub struct TwoFactorStatus {
+    pub enabled: bool,
+    /// Whether the user's role must use two-factor authentication.
+    pub required: bool,
+    pub recovery_codes_remaining: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct TwoFactorRow {
+    secret_encrypted: String,
+    enabled: bool,
+}
+
+pub struct TwoFactorService {
+    db_pool: DbPool,
+    config: TwoFactorConfig,
+    cipher: SecretCipher,
+}
+
+impl TwoFactorService {
+    pub fn new(db_pool: DbPool, config: TwoFactorConfig) -> Result<Self, TotpError> {
+        let cipher = SecretCipher::from_hex(&config.encryption_key)?;
+        Ok(Self {
+            db_pool,
+            config,
+            cipher,
+        })
+    }
+
+    /// Whether users with `role` must enable two-factor authentication.
+    pub fn is_required_for(&self, role: &str) -> bool {
+        self.config.required_roles.iter().any(|r| r == role)
+    }
+
+    pub async fn status(&self, user_id: Uuid) -> Result<TwoFactorStatus, ApiError> {
+        let role = self.user_role(user_id).await?;
+        let enabled = self.load(user_id).await?.is_some_and(|row| row.enabled);
+        let recovery_codes_remaining: i64 = sqlx::query_scalar(
+            "SELECT COUNT(*) FROM two_factor_recovery_codes WHERE user_id = $1 AND used_at IS NULL",
+        )
+        .bind(user_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        Ok(TwoFactorStatus {
+            enabled,
+            required: self.is_required_for(&role),
+            recovery_codes_remaining,
+        })
+    }
+
+    /// Start enrolment with a fresh secret, replacing any unconfirmed one.
+    pub async fn setup(&self, user_id: Uuid) -> Result<TwoFactorSetup, ApiError> {
+        let account: Option<String> =
+            sqlx::query_scalar("SELECT COALESCE(email, username) FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        let account = account.ok_or_else(|| ApiError::not_found("User not found"))?;
+
+        let secret = totp::generate_secret();
+        let stored = sqlx::query(
+            r#"
+            INSERT INTO user_two_factor (user_id, secret_encrypted)
+            VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE
+            SET secret_encrypted = EXCLUDED.secret_encrypted,
+                last_used_step = NULL,
+                updated_at = NOW()
+            WHERE user_two_factor.enabled = FALSE
+            "#,
+        )
+        .bind(user_id)
+        .bind(self.cipher.encrypt(&secret))
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        if stored.rows_affected() == 0 {
+            return Err(ApiError::conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+
+        Ok(TwoFactorSetup {
+            secret: totp::base32_encode(&secret),
+            otpauth_uri: totp::provisioning_uri(&secret, &self.config.issuer, &account),
+        })
+    }
+
+    /// Confirm enrolment with a code from the authenticator. Returns the
+    /// recovery codes, which are shown only this once.
+    pub async fn enable(&self, user_id: Uuid, code: &str) -> Result<Vec<String>, ApiError> {
+        let row = self
+            .load(user_id)
+            .await?
+            .ok_or_else(|| ApiError::bad_request("Start two-factor setup first"))?;
+        if row.enabled {
+            return Err(ApiError::conflict(
+                "Two-factor authentication is already enabled",
+            ));
+        }
+        if !self.consume_totp(user_id, &row, code).await? {
+            return Err(ApiError::bad_request("Invalid two-factor code"));
+        }
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        sqlx::query(
+            "UPDATE user_two_factor SET enabled = TRUE, confirmed_at = NOW(), updated_at = NOW() WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        let codes = replace_recovery_codes(&mut tx, user_id).await?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(user_id = %user_id, "Two-factor authentication enabled");
+        Ok(codes)
+    }
+
+    /// Turn two-factor authentication off, given a valid code.
+    pub async fn disable(&self, user_id: Uuid, code: &str) -> Result<(), ApiError> {
+        self.verify(user_id, code).await?;
+
+        let role = self.user_role(user_id).await?;
+        if self.is_required_for(&role) {
+            return Err(ApiError::forbidden(
+                "Two-factor authentication is required for this account",
+            ));
+        }
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        sqlx::query("DELETE FROM two_factor_recovery_codes WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+        sqlx::query("DELETE FROM user_two_factor WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(user_id = %user_id, "Two-factor authentication disabled");
+        Ok(())
+    }
+
+    /// Replace the recovery codes, given a valid code.
+    pub async fn regenerate_recovery_codes(
+        &self,
+        user_id: Uuid,
+        code: &str,
+    ) -> Result<Vec<String>, ApiError> {
+        self.verify(user_id, code).await?;
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let codes = replace_recovery_codes(&mut tx, user_id).await?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(user_id = %user_id, "Recovery codes regenerated");
+        Ok(codes)
+    }
+
+    /// Second factor at sign-in: a code is needed once enrolled.
+    pub async fn verify_login(&self, user_id: Uuid, code: Option<&str>) -> Result<(), ApiError> {
+        if !self.load(user_id).await?.is_some_and(|row| row.enabled) {
+            return Ok(());
+        }
+        let code =
+            code.ok_or_else(|| ApiError::bad_request("Two-factor authentication code required"))?;
+        self.verify(user_id, code).await
+    }
+
+    /// Step-up for sensitive actions: a code is needed once enrolled, and
+    /// users whose role requires two-factor authentication must enrol first.
+    pub async fn require(&self, user_id: Uuid, code: Option<&str>) -> Result<(), ApiError> {
+        if !self.load(user_id).await?.is_some_and(|row| row.enabled) {
+            let role = self.user_role(user_id).await?;
+            if self.is_required_for(&role) {
+                return Err(ApiError::forbidden(
+                    "Enable two-factor authentication to continue",
+                ));
+            }
+            return Ok(());
+        }
+        let code =
+            code.ok_or_else(|| ApiError::bad_request("Two-factor authentication code required"))?;
+        self.verify(user_id, code).await
+    }
+
+    /// Accept a TOTP code or an unused recovery code of an enrolled user.
+    async fn verify(&self, user_id: Uuid, code: &str) -> Result<(), ApiError> {
+        let row = self
+            .load(user_id)
+            .await?
+            .filter(|row| row.enabled)
+            .ok_or_else(|| ApiError::bad_request("Two-factor authentication is not enabled"))?;
+
+        let code = code.trim();
+        let accepted = if totp::is_totp_code(code) {
+            self.consume_totp(user_id, &row, code).await?
+        } else {
+            self.consume_recovery_code(user_id, code).await?
+        };
+        if !accepted {
+            warn!(user_id = %user_id, "Invalid two-factor code");
+            return Err(ApiError::unauthorized("Invalid two-factor code"));
+        }
+        Ok(())
+    }
+
+    /// Check a TOTP code and record its step, so it is accepted only once.
+    async fn consume_totp(
+        &self,
+        user_id: Uuid,
+        row: &TwoFactorRow,
+        code: &str,
+    ) -> Result<bool, ApiError> {
+        let secret = self.cipher.decrypt(&row.secret_encrypted).map_err(|e| {
+            ApiError::internal_error(format!("TOTP secret of {} unreadable: {}", user_id, e))
+        })?;
+        let Some(step) = totp::verify(&secret, code, Utc::now().timestamp()) else {
+            return Ok(false);
+        };
+
+        let consumed = sqlx::query(
+            r#"
+            UPDATE user_two_factor
+            SET last_used_step = $2, updated_at = NOW()
+            WHERE user_id = $1 AND (last_used_step IS NULL OR last_used_step < $2)
+            "#,
+        )
+        .bind(user_id)
+        .bind(step)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        Ok(consumed.rows_affected() == 1)
+    }
+
+    async fn consume_recovery_code(&self, user_id: Uuid, code: &str) -> Result<bool, ApiError> {
+        let used = sqlx::query(
+            r#"
+            UPDATE two_factor_recovery_codes
+            SET used_at = NOW()
+            WHERE user_id = $1 AND code_hash = $2 AND used_at IS NULL
+            "#,
+        )
+        .bind(user_id)
+        .bind(totp::hash_recovery_code(code))
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        if used.rows_affected() == 1 {
+            info!(user_id = %user_id, "Recovery code used");
+        }
+        Ok(used.rows_affected() == 1)
+    }
+
+    async fn load(&self, user_id: Uuid) -> Result<Option<TwoFactorRow>, ApiError> {
+        sqlx::query_as("SELECT secret_encrypted, enabled FROM user_two_factor WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)
+    }
+
+    async fn user_role(&self, user_id: Uuid) -> Result<String, ApiError> {
+        let role: Option<String> =
+            sqlx::query_scalar("SELECT COALESCE(role, '') FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        role.ok_or_else(|| ApiError::not_found("User not found"))
+    }
+}
+
+async fn replace_recovery_codes(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    user_id: Uuid,
+) -> Result<Vec<String>, ApiError> {
+    sqlx::query("DELETE FROM two_factor_recovery_codes WHERE user_id = $1")
+        .bind(user_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(ApiError::database_error)?;
+
+    let codes = totp::generate_recovery_codes();
+    let hashes: Vec<String> = codes.iter().map(|c| totp::hash_recovery_code(c)).collect();
+    sqlx::query(
+        "INSERT INTO two_factor_recovery_codes (user_id, code_hash) SELECT $1, UNNEST($2::text[])",
+    )
+    .bind(user_id)
+    .bind(&hashes)
+    .execute(&mut **tx)
+    .await
+    .map_err(ApiError::database_error)?;
+
+    Ok(codes)
+}
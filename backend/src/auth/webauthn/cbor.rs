@@ -0,0 +1,237 @@
+//! Just enough CBOR (RFC 8949) to read WebAuthn attestation objects and COSE
+//! keys: definite-length integers, byte and text strings, arrays, maps,
+//! booleans and null. Anything else is rejected.
+
+use thiserror::Error;
+
+/// Arrays and maps nest no deeper than this in WebAuthn structures.
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Bytes(Vec<u8>),
+    Text(String),
+    Array(Vec<Value>),
+    Map(Vec<(Value, Value)>),
+    Bool(bool),
+    Null,
+}
+
+impl Value {
+    /// Map entry under an integer key (COSE labels).
+    pub fn get_int(&self, key: i64) -> Option<&Value> {
+        self.get(|k| *k == Value::Int(key))
+    }
+
+    /// Map entry under a text key.
+    pub fn get_text(&self, key: &str) -> Option<&Value> {
+        self.get(|k| matches!(k, Value::Text(t) if t == key))
+    }
+
+    fn get(&self, matches: impl Fn(&Value) -> bool) -> Option<&Value> {
+        match self {
+            Value::Map(entries) => entries.iter().find(|(k, _)| matches(k)).map(|(_, v)| v),
+            _ => None,
+        }
+    }
+
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            Value::Int(i) => Some(*i),
+            _ => None,
+        }
+    }
+
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(b) => Some(b),
+            _ => None,
+        }
+    }
+
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            Value::Text(t) => Some(t),
+            _ => None,
+        }
+    }
+
+    pub fn is_empty_map(&self) -> bool {
+        matches!(self, Value::Map(entries) if entries.is_empty())
+    }
+}
+
+#[derive(Debug, Error, PartialEq)]
+pub enum CborError {
+    #[error("Unexpected end of CBOR data")]
+    Eof,
+
+    #[error("Unsupported CBOR item (major type {0}, info {1})")]
+    Unsupported(u8, u8),
+
+    #[error("CBOR nested too deeply")]
+    TooDeep,
+
+    #[error("Invalid UTF-8 in CBOR text")]
+    InvalidText,
+
+    #[error("Trailing bytes after CBOR item")]
+    TrailingBytes,
+}
+
+/// Decode a buffer holding exactly one item.
+pub fn decode(data: &[u8]) -> Result<Value, CborError> {
+    let (value, used) = decode_prefix(data)?;
+    if used != data.len() {
+        return Err(CborError::TrailingBytes);
+    }
+    Ok(value)
+}
+
+/// Decode the item at the start of `data`, returning it with the number of
+/// bytes it took. Authenticator data embeds the credential key this way.
+pub fn decode_prefix(data: &[u8]) -> Result<(Value, usize), CborError> {
+    let mut decoder = Decoder { data, pos: 0 };
+    let value = decoder.item(0)?;
+    Ok((value, decoder.pos))
+}
+
+struct Decoder<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Decoder<'_> {
+    fn take(&mut self, n: usize) -> Result<&[u8], CborError> {
+        let end = self.pos.checked_add(n).ok_or(CborError::Eof)?;
+        let bytes = self.data.get(self.pos..end).ok_or(CborError::Eof)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Major type and argument of the next item.
+    fn head(&mut self) -> Result<(u8, u64), CborError> {
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let arg = match info {
+            0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into().unwrap()) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into().unwrap()) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into().unwrap()),
+            _ => return Err(CborError::Unsupported(major, info)),
+        };
+        Ok((major, arg))
+    }
+
+    /// A length that cannot exceed what is left of the input, so corrupt
+    /// data cannot make us allocate.
+    fn len(&self, arg: u64) -> Result<usize, CborError> {
+        let remaining = (self.data.len() - self.pos) as u64;
+        if arg > remaining {
+            return Err(CborError::Eof);
+        }
+        Ok(arg as usize)
+    }
+
+    fn item(&mut self, depth: usize) -> Result<Value, CborError> {
+        if depth > MAX_DEPTH {
+            return Err(CborError::TooDeep);
+        }
+        let (major, arg) = self.head()?;
+        match major {
+            0 => i64::try_from(arg)
+                .map(Value::Int)
+                .map_err(|_| CborError::Unsupported(major, 27)),
+            1 => i64::try_from(arg)
+                .map(|n| Value::Int(-1 - n))
+                .map_err(|_| CborError::Unsupported(major, 27)),
+            2 => {
+                let len = self.len(arg)?;
+                Ok(Value::Bytes(self.take(len)?.to_vec()))
+            }
+            3 => {
+                let len = self.len(arg)?;
+                let bytes = self.take(len)?.to_vec();
+                String::from_utf8(bytes)
+                    .map(Value::Text)
+                    .map_err(|_| CborError::InvalidText)
+            }
+            4 => {
+                let len = self.len(arg)?;
+                (0..len)
+                    .map(|_| self.item(depth + 1))
+                    .collect::<Result<_, _>>()
+                    .map(Value::Array)
+            }
+            5 => {
+                let len = self.len(arg)?;
+                (0..len)
+                    .map(|_| Ok((self.item(depth + 1)?, self.item(depth + 1)?)))
+                    .collect::<Result<_, _>>()
+                    .map(Value::Map)
+            }
+            7 => match arg {
+                20 => Ok(Value::Bool(false)),
+                21 => Ok(Value::Bool(true)),
+                22 => Ok(Value::Null),
+                _ => Err(CborError::Unsupported(major, arg as u8)),
+            },
+            _ => Err(CborError::Unsupported(major, arg as u8)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_cose_key() {
+        // {1: 2, 3: -7, -1: 1, -2: h'0102', -3: h'0304'}
+        let data = [
+            0xa5, 0x01, 0x02, 0x03, 0x26, 0x20, 0x01, 0x21, 0x42, 0x01, 0x02, 0x22, 0x42, 0x03,
+            0x04,
+        ];
+        let key = decode(&data).unwrap();
+        assert_eq!(key.get_int(1).and_then(Value::as_int), Some(2));
+        assert_eq!(key.get_int(3).and_then(Value::as_int), Some(-7));
+        assert_eq!(
+            key.get_int(-2).and_then(Value::as_bytes),
+            Some(&[1u8, 2][..])
+        );
+        assert_eq!(
+            key.get_int(-3).and_then(Value::as_bytes),
+            Some(&[3u8, 4][..])
+        );
+    }
+
+    #[test]
+    fn test_decode_prefix_and_text_keys() {
+        // {"fmt": "none", "attStmt": {}} followed by two extra bytes
+        let mut data = vec![0xa2, 0x63, b'f', b'm', b't', 0x64, b'n', b'o', b'n', b'e'];
+        data.extend_from_slice(&[0x67, b'a', b't', b't', b'S', b't', b'm', b't', 0xa0]);
+        let item_len = data.len();
+        data.extend_from_slice(&[0xff, 0xff]);
+
+        let (value, used) = decode_prefix(&data).unwrap();
+        assert_eq!(used, item_len);
+        assert_eq!(value.get_text("fmt").and_then(Value::as_text), Some("none"));
+        assert!(value.get_text("attStmt").unwrap().is_empty_map());
+        assert_eq!(decode(&data), Err(CborError::TrailingBytes));
+    }
+
+    #[test]
+    fn test_rejects_truncated_and_indefinite_items() {
+        // Byte string claiming 4 GiB
+        assert_eq!(
+            decode(&[0x5a, 0xff, 0xff, 0xff, 0xff, 0x00]),
+            Err(CborError::Eof)
+        );
+        // Indefinite-length map
+        assert_eq!(decode(&[0xbf, 0xff]), Err(CborError::Unsupported(5, 31)));
+        // Floats are not used by WebAuthn
+        assert!(decode(&[0xf9, 0x3c, 0x00]).is_err());
+    }
+}
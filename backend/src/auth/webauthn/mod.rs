@@ -0,0 +1,650 @@
+//! Passkey (WebAuthn) registration, sign-in and step-up.
+//!
+//! Every ceremony starts with an options request that records a single-use
+//! challenge and returns it by id alongside the `publicKey` options for
+//! `navigator.credentials`; the client posts the credential back with that
+//! id. Credentials are attached to a device record, so blocking or revoking a
+//! device disables its passkeys too.
+//!
+//! User verification is required for sign-in and step-up, which makes a
+//! passkey assertion a two-factor proof on its own: it is not combined with
+//! TOTP. Password plus TOTP sign-in remains available for users without a
+//! passkey on hand.
+
+pub mod cbor;
+pub mod verify;
+
+use crate::api_error::ApiError;
+use crate::auth::device_service::{DeviceInfo, DeviceService, NewPasskey, Passkey};
+use crate::config::AuthConfig;
+use crate::db::DbPool;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::{Duration, Utc};
+use rand::RngCore;
+use redis::{aio::ConnectionManager, AsyncCommands};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+use self::verify::{Ceremony, Expected, WebAuthnError, ALG_EDDSA, ALG_ES256};
+
+/// How long a ceremony challenge may be answered (seconds).
+const CHALLENGE_TTL_SECS: i64 = 300;
+/// Answered and expired challenges are kept this long for auditing (hours).
+const CHALLENGE_RETENTION_HOURS: i64 = 24;
+const CHALLENGE_BYTES: usize = 32;
+/// How long a step-up token may be presented (seconds).
+const STEP_UP_TTL_SECS: u64 = 300;
+const STEP_UP_TOKEN_BYTES: usize = 32;
+
+#[derive(Debug, Clone)]
+pub struct WebAuthnConfig {
+    pub rp_id: String,
+    pub rp_name: String,
+    pub origins: Vec<String>,
+    pub challenge_ttl: Duration,
+}
+
+impl WebAuthnConfig {
+    pub fn from_config(auth: &AuthConfig) -> Self {
+        Self {
+            rp_id: auth.webauthn_rp_id.clone(),
+            rp_name: auth.webauthn_rp_name.clone(),
+            origins: auth.webauthn_origins.clone(),
+            challenge_ttl: Duration::seconds(CHALLENGE_TTL_SECS),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Purpose {
+    Register,
+    Login,
+    StepUp,
+}
+
+impl Purpose {
+    fn as_str(self) -> &'static str {
+        match self {
+            Purpose::Register => "register",
+            Purpose::Login => "login",
+            Purpose::StepUp => "step_up",
+        }
+    }
+}
+
+/// Response to the options endpoints.
+#[derive(Debug, Serialize)]
+pub struct CeremonyOptions<T> {
+    /// Sent back with the credential to name the challenge being answered.
+    pub challenge_id: Uuid,
+    pub public_key: T,
+}
+
+/// `PublicKeyCredentialCreationOptions`, binary fields base64url-encoded.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreationOptions {
+    pub challenge: String,
+    pub rp: RelyingParty,
+    pub user: UserEntity,
+    pub pub_key_cred_params: Vec<CredentialParameters>,
+    pub timeout: u64,
+    pub attestation: &'static str,
+    pub authenticator_selection: AuthenticatorSelection,
+    pub exclude_credentials: Vec<CredentialDescriptor>,
+}
+
+/// `PublicKeyCredentialRequestOptions`, binary fields base64url-encoded.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestOptions {
+    pub challenge: String,
+    pub timeout: u64,
+    pub rp_id: String,
+    pub allow_credentials: Vec<CredentialDescriptor>,
+    pub user_verification: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RelyingParty {
+    pub id: String,
+    pub name: String,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserEntity {
+    pub id: String,
+    pub name: String,
+    pub display_name: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialParameters {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub alg: i64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticatorSelection {
+    pub resident_key: &'static str,
+    pub user_verification: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CredentialDescriptor {
+    #[serde(rename = "type")]
+    pub kind: &'static str,
+    pub id: String,
+}
+
+/// Body of `POST /api/auth/passkeys/register`.
+#[derive(Debug, Deserialize)]
+pub struct RegisterPasskeyRequest {
+    pub challenge_id: Uuid,
+    /// Device to attach the passkey to; the requesting device when omitted.
+    pub device_id: Option<Uuid>,
+    pub name: Option<String>,
+    pub credential: AttestationCredential,
+}
+
+/// Body of the login and step-up endpoints.
+#[derive(Debug, Deserialize)]
+pub struct PasskeyAssertionRequest {
+    pub challenge_id: Uuid,
+    pub credential: AssertionCredential,
+}
+
+/// `PublicKeyCredential.toJSON()` of a created credential.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationCredential {
+    pub raw_id: String,
+    pub response: AttestationResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub attestation_object: String,
+}
+
+/// `PublicKeyCredential.toJSON()` of an assertion.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionCredential {
+    pub raw_id: String,
+    pub response: AssertionResponse,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub user_handle: Option<String>,
+}
+
+/// A verified passkey sign-in.
+#[derive(Debug)]
+pub struct PasskeyLogin {
+    pub user_id: Uuid,
+    pub device_id: Uuid,
+}
+
+/// Response to `POST /api/auth/passkeys/step-up`.
+#[derive(Debug, Serialize)]
+pub struct StepUpToken {
+    /// Single-use; sent as `X-Step-Up-Token` with the high-risk request.
+    pub step_up_token: String,
+    pub expires_in: u64,
+}
+
+pub struct PasskeyService {
+    db_pool: DbPool,
+    redis: ConnectionManager,
+    devices: Arc<DeviceService>,
+    config: WebAuthnConfig,
+}
+
+impl PasskeyService {
+    pub fn new(
+        db_pool: DbPool,
+        redis: ConnectionManager,
+        devices: Arc<DeviceService>,
+        config: WebAuthnConfig,
+    ) -> Self {
+        Self {
+            db_pool,
+            redis,
+            devices,
+            config,
+        }
+    }
+
+    /// Options to create a passkey for a signed-in user.
+    pub async fn registration_options(
+        &self,
+        user_id: Uuid,
+    ) -> Result<CeremonyOptions<CreationOptions>, ApiError> {
+        let (username, display_name): (String, Option<String>) =
+            sqlx::query_as("SELECT username, display_name FROM users WHERE id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?
+                .ok_or_else(|| ApiError::not_found("User not found"))?;
+        let existing = self.devices.get_user_passkeys(user_id).await?;
+        let (challenge_id, challenge) = self
+            .issue_challenge(Purpose::Register, Some(user_id))
+            .await?;
+
+        Ok(CeremonyOptions {
+            challenge_id,
+            public_key: CreationOptions {
+                challenge: b64url(&challenge),
+                rp: RelyingParty {
+                    id: self.config.rp_id.clone(),
+                    name: self.config.rp_name.clone(),
+                },
+                user: UserEntity {
+                    id: b64url(user_id.as_bytes()),
+                    display_name: display_name.unwrap_or_else(|| username.clone()),
+                    name: username,
+                },
+                pub_key_cred_params: [ALG_ES256, ALG_EDDSA]
+                    .into_iter()
+                    .map(|alg| CredentialParameters {
+                        kind: "public-key",
+                        alg,
+                    })
+                    .collect(),
+                timeout: self.timeout_ms(),
+                attestation: "none",
+                authenticator_selection: AuthenticatorSelection {
+                    resident_key: "preferred",
+                    user_verification: "required",
+                },
+                exclude_credentials: descriptors(&existing),
+            },
+        })
+    }
+
+    /// Verify a new credential and attach it to `device_id`, or to the
+    /// device described by `device_info` when the client named none.
+    pub async fn register(
+        &self,
+        user_id: Uuid,
+        device_info: DeviceInfo,
+        request: RegisterPasskeyRequest,
+    ) -> Result<Passkey, ApiError> {
+        let challenge = self
+            .consume_challenge(request.challenge_id, Purpose::Register, Some(user_id))
+            .await?;
+        let response = &request.credential.response;
+        let credential = verify::verify_registration(
+            &b64url_decode(&response.client_data_json, "clientDataJSON")?,
+            &b64url_decode(&response.attestation_object, "attestationObject")?,
+            &self.expected(Ceremony::Create, &challenge),
+        )
+        .map_err(rejected)?;
+        if b64url_decode(&request.credential.raw_id, "rawId")? != credential.credential_id {
+            return Err(ApiError::bad_request(
+                "rawId does not match the attested credential",
+            ));
+        }
+
+        let device_id = match request.device_id {
+            Some(device_id) => device_id,
+            None => {
+                self.devices
+                    .register_device(user_id, device_info, None)
+                    .await?
+                    .id
+            }
+        };
+        let aaguid = Uuid::from_bytes(credential.aaguid);
+        let passkey = self
+            .devices
+            .add_passkey(
+                user_id,
+                device_id,
+                NewPasskey {
+                    credential_id: credential.credential_id,
+                    public_key: credential.public_key,
+                    algorithm: credential.algorithm as i32,
+                    sign_count: credential.sign_count as i64,
+                    name: request.name,
+                    aaguid: (!aaguid.is_nil()).then_some(aaguid),
+                },
+            )
+            .await?;
+
+        Ok(passkey)
+    }
+
+    /// Options to sign in. Without an email, or with one we do not know, any
+    /// discoverable credential may answer.
+    pub async fn login_options(
+        &self,
+        email: Option<&str>,
+    ) -> Result<CeremonyOptions<RequestOptions>, ApiError> {
+        let user_id = match email {
+            Some(email) => sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+                .bind(email)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?,
+            None => None,
+        };
+        let passkeys = match user_id {
+            Some(user_id) => self.devices.get_user_passkeys(user_id).await?,
+            None => Vec::new(),
+        };
+        // The challenge is not bound to the user: which passkey answers is
+        // checked against the credential itself.
+        self.request_options(Purpose::Login, None, &passkeys).await
+    }
+
+    /// Verify a sign-in assertion.
+    pub async fn authenticate(
+        &self,
+        request: PasskeyAssertionRequest,
+    ) -> Result<PasskeyLogin, ApiError> {
+        let passkey = self.verify_assertion(request, Purpose::Login, None).await?;
+        info!(user_id = %passkey.user_id, device_id = %passkey.device_id, "Passkey sign-in");
+        Ok(PasskeyLogin {
+            user_id: passkey.user_id,
+            device_id: passkey.device_id,
+        })
+    }
+
+    /// Options to confirm a high-risk action with one of the user's passkeys.
+    pub async fn step_up_options(
+        &self,
+        user_id: Uuid,
+    ) -> Result<CeremonyOptions<RequestOptions>, ApiError> {
+        let passkeys = self.devices.get_user_passkeys(user_id).await?;
+        if passkeys.is_empty() {
+            return Err(ApiError::bad_request("No passkey is registered"));
+        }
+        self.request_options(Purpose::StepUp, Some(user_id), &passkeys)
+            .await
+    }
+
+    /// Verify a step-up assertion and issue a single-use step-up token.
+    pub async fn step_up(
+        &self,
+        user_id: Uuid,
+        request: PasskeyAssertionRequest,
+    ) -> Result<StepUpToken, ApiError> {
+        let passkey = self
+            .verify_assertion(request, Purpose::StepUp, Some(user_id))
+            .await?;
+
+        let mut token = [0u8; STEP_UP_TOKEN_BYTES];
+        rand::rngs::OsRng.fill_bytes(&mut token);
+        let token = b64url(&token);
+        let mut conn = self.redis.clone();
+        conn.set_ex::<_, _, ()>(step_up_key(&token), user_id.to_string(), STEP_UP_TTL_SECS)
+            .await
+            .map_err(|e| ApiError::RedisError(e.to_string()))?;
+
+        info!(user_id = %user_id, device_id = %passkey.device_id, "Passkey step-up");
+        Ok(StepUpToken {
+            step_up_token: token,
+            expires_in: STEP_UP_TTL_SECS,
+        })
+    }
+
+    /// Redeem a step-up token issued to `user_id`.
+    pub async fn consume_step_up(&self, user_id: Uuid, token: &str) -> Result<bool, ApiError> {
+        let mut conn = self.redis.clone();
+        let owner: Option<String> = conn
+            .get_del(step_up_key(token))
+            .await
+            .map_err(|e| ApiError::RedisError(e.to_string()))?;
+        Ok(owner.is_some_and(|owner| owner == user_id.to_string()))
+    }
+
+    pub async fn list(&self, user_id: Uuid) -> Result<Vec<Passkey>, ApiError> {
+        Ok(self.devices.get_user_passkeys(user_id).await?)
+    }
+
+    pub async fn remove(&self, user_id: Uuid, passkey_id: Uuid) -> Result<(), ApiError> {
+        Ok(self.devices.remove_passkey(user_id, passkey_id).await?)
+    }
+
+    async fn request_options(
+        &self,
+        purpose: Purpose,
+        user_id: Option<Uuid>,
+        passkeys: &[Passkey],
+    ) -> Result<CeremonyOptions<RequestOptions>, ApiError> {
+        let (challenge_id, challenge) = self.issue_challenge(purpose, user_id).await?;
+        Ok(CeremonyOptions {
+            challenge_id,
+            public_key: RequestOptions {
+                challenge: b64url(&challenge),
+                timeout: self.timeout_ms(),
+                rp_id: self.config.rp_id.clone(),
+                allow_credentials: descriptors(passkeys),
+                user_verification: "required",
+            },
+        })
+    }
+
+    /// Check an assertion against the stored passkey and its device, and
+    /// record the new signature counter.
+    async fn verify_assertion(
+        &self,
+        request: PasskeyAssertionRequest,
+        purpose: Purpose,
+        user_id: Option<Uuid>,
+    ) -> Result<Passkey, ApiError> {
+        let challenge = self
+            .consume_challenge(request.challenge_id, purpose, user_id)
+            .await?;
+        let credential = &request.credential;
+        let credential_id = b64url_decode(&credential.raw_id, "rawId")?;
+        let passkey = self
+            .devices
+            .find_passkey(&credential_id)
+            .await
+            .map_err(|_| ApiError::unauthorized("Unknown passkey"))?;
+
+        if user_id.is_some_and(|user_id| user_id != passkey.user_id) {
+            return Err(ApiError::unauthorized("Passkey belongs to another user"));
+        }
+        if let Some(handle) = &credential.response.user_handle {
+            if b64url_decode(handle, "userHandle")? != passkey.user_id.as_bytes() {
+                return Err(ApiError::unauthorized("Passkey belongs to another user"));
+            }
+        }
+        let device = self.devices.get_device(passkey.device_id).await?;
+        if device.is_blocked {
+            warn!(device_id = %device.id, "Passkey used from a blocked device");
+            return Err(ApiError::forbidden("Device is blocked"));
+        }
+
+        let response = &credential.response;
+        let assertion = verify::verify_assertion(
+            &b64url_decode(&response.client_data_json, "clientDataJSON")?,
+            &b64url_decode(&response.authenticator_data, "authenticatorData")?,
+            &b64url_decode(&response.signature, "signature")?,
+            &passkey.public_key,
+            passkey.sign_count as u32,
+            &self.expected(Ceremony::Get, &challenge),
+        )
+        .map_err(|e| {
+            if matches!(e, WebAuthnError::CounterRegression) {
+                warn!(passkey_id = %passkey.id, "Passkey counter regressed; possible clone");
+            }
+            rejected(e)
+        })?;
+        self.devices
+            .record_passkey_use(&passkey, assertion.sign_count as i64)
+            .await
+            .map_err(|_| ApiError::unauthorized("Passkey assertion rejected"))?;
+
+        Ok(passkey)
+    }
+
+    async fn issue_challenge(
+        &self,
+        purpose: Purpose,
+        user_id: Option<Uuid>,
+    ) -> Result<(Uuid, Vec<u8>), ApiError> {
+        let mut challenge = vec![0u8; CHALLENGE_BYTES];
+        rand::rngs::OsRng.fill_bytes(&mut challenge);
+
+        sqlx::query(
+            "DELETE FROM webauthn_challenges WHERE expires_at < NOW() - make_interval(hours => $1)",
+        )
+        .bind(CHALLENGE_RETENTION_HOURS as i32)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        let id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO webauthn_challenges (user_id, purpose, challenge, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(user_id)
+        .bind(purpose.as_str())
+        .bind(&challenge)
+        .bind(Utc::now() + self.config.challenge_ttl)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        Ok((id, challenge))
+    }
+
+    /// Redeem a challenge once. It is spent even if the response then fails
+    /// verification, so every attempt needs fresh options.
+    async fn consume_challenge(
+        &self,
+        challenge_id: Uuid,
+        purpose: Purpose,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<u8>, ApiError> {
+        let challenge: Option<(Vec<u8>, Option<Uuid>)> = sqlx::query_as(
+            r#"
+            UPDATE webauthn_challenges SET consumed_at = NOW()
+            WHERE id = $1 AND purpose = $2 AND consumed_at IS NULL AND expires_at > NOW()
+            RETURNING challenge, user_id
+            "#,
+        )
+        .bind(challenge_id)
+        .bind(purpose.as_str())
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        match challenge {
+            Some((challenge, owner)) if owner == user_id => Ok(challenge),
+            Some(_) => Err(ApiError::unauthorized(
+                "Challenge was issued to another user",
+            )),
+            None => Err(ApiError::unauthorized("Unknown, used or expired challenge")),
+        }
+    }
+
+    fn expected<'a>(&'a self, ceremony: Ceremony, challenge: &'a [u8]) -> Expected<'a> {
+        Expected {
+            ceremony,
+            challenge,
+            rp_id: &self.config.rp_id,
+            origins: &self.config.origins,
+            require_user_verification: true,
+        }
+    }
+
+    fn timeout_ms(&self) -> u64 {
+        self.config.challenge_ttl.num_milliseconds() as u64
+    }
+}
+
+fn descriptors(passkeys: &[Passkey]) -> Vec<CredentialDescriptor> {
+    passkeys
+        .iter()
+        .map(|p| CredentialDescriptor {
+            kind: "public-key",
+            id: b64url(&p.credential_id),
+        })
+        .collect()
+}
+
+fn b64url(data: &[u8]) -> String {
+    general_purpose::URL_SAFE_NO_PAD.encode(data)
+}
+
+fn b64url_decode(value: &str, field: &str) -> Result<Vec<u8>, ApiError> {
+    general_purpose::URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .map_err(|_| ApiError::bad_request(format!("{} must be base64url", field)))
+}
+
+fn step_up_key(token: &str) -> String {
+    format!("step_up:{}", hex::encode(Sha256::digest(token.as_bytes())))
+}
+
+fn rejected(e: WebAuthnError) -> ApiError {
+    match e {
+        WebAuthnError::Malformed(_)
+        | WebAuthnError::UnsupportedAttestation(_)
+        | WebAuthnError::UnsupportedAlgorithm(_)
+        | WebAuthnError::Cbor(_) => ApiError::bad_request(e.to_string()),
+        _ => ApiError::unauthorized(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assertion_request_accepts_browser_json() {
+        let request: PasskeyAssertionRequest = serde_json::from_value(serde_json::json!({
+            "challenge_id": "5f0c6a8e-4c63-4d5e-9a1b-2f6c1d3e4b5a",
+            "credential": {
+                "id": "Y3JlZA",
+                "rawId": "Y3JlZA",
+                "type": "public-key",
+                "response": {
+                    "clientDataJSON": "e30",
+                    "authenticatorData": "AAAA",
+                    "signature": "AAAA",
+                    "userHandle": null
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            b64url_decode(&request.credential.raw_id, "rawId").unwrap(),
+            b"cred"
+        );
+        assert_eq!(request.credential.response.client_data_json, "e30");
+        assert!(request.credential.response.user_handle.is_none());
+    }
+
+    #[test]
+    fn test_step_up_key_hides_token() {
+        let key = step_up_key("token");
+        assert!(key.starts_with("step_up:"));
+        assert!(!key.contains("token"));
+        assert_eq!(key, step_up_key("token"));
+    }
+}
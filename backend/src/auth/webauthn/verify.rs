@@ -0,0 +1,626 @@
+//! WebAuthn ceremony checks (Level 2, §7.1 registration and §7.2
+//! authentication), independent of storage.
+//!
+//! Only `none` and self-attested `packed` attestation are accepted: we use
+//! passkeys to tie a credential to a device, not to vet authenticator makes,
+//! so certificate chains are not evaluated and are refused rather than
+//! trusted blindly.
+
+use base64::{engine::general_purpose, Engine as _};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+
+use super::cbor::{self, CborError, Value};
+
+/// COSE algorithm identifiers we offer and accept.
+pub const ALG_ES256: i64 = -7;
+pub const ALG_EDDSA: i64 = -8;
+
+const FLAG_USER_PRESENT: u8 = 0x01;
+const FLAG_USER_VERIFIED: u8 = 0x04;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+/// rpIdHash, flags and signature counter.
+const AUTH_DATA_HEADER: usize = 37;
+const AAGUID_BYTES: usize = 16;
+
+#[derive(Debug, Error)]
+pub enum WebAuthnError {
+    #[error("Malformed {0}")]
+    Malformed(&'static str),
+
+    #[error("Client data mismatch: {0}")]
+    ClientData(&'static str),
+
+    #[error("Credential was created for another relying party")]
+    RpIdMismatch,
+
+    #[error("User presence was not confirmed")]
+    UserNotPresent,
+
+    #[error("User verification is required")]
+    UserNotVerified,
+
+    #[error("Unsupported attestation: {0}")]
+    UnsupportedAttestation(String),
+
+    #[error("Unsupported public key algorithm {0}")]
+    UnsupportedAlgorithm(i64),
+
+    #[error("Invalid signature")]
+    BadSignature,
+
+    #[error("Signature counter did not increase; the authenticator may be cloned")]
+    CounterRegression,
+
+    #[error(transparent)]
+    Cbor(#[from] CborError),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ceremony {
+    Create,
+    Get,
+}
+
+impl Ceremony {
+    fn client_data_type(self) -> &'static str {
+        match self {
+            Ceremony::Create => "webauthn.create",
+            Ceremony::Get => "webauthn.get",
+        }
+    }
+}
+
+/// What the relying party expects a response to be bound to.
+pub struct Expected<'a> {
+    pub ceremony: Ceremony,
+    pub challenge: &'a [u8],
+    pub rp_id: &'a str,
+    pub origins: &'a [String],
+    pub require_user_verification: bool,
+}
+
+#[derive(Deserialize)]
+struct CollectedClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+/// Parsed authenticator data.
+#[derive(Debug)]
+pub struct AuthenticatorData {
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+#[derive(Debug)]
+pub struct AttestedCredential {
+    pub aaguid: [u8; AAGUID_BYTES],
+    pub credential_id: Vec<u8>,
+    /// The COSE_Key exactly as the authenticator encoded it.
+    pub public_key: Vec<u8>,
+}
+
+/// A newly registered credential, ready to store.
+#[derive(Debug)]
+pub struct RegisteredCredential {
+    pub credential_id: Vec<u8>,
+    pub public_key: Vec<u8>,
+    pub algorithm: i64,
+    pub sign_count: u32,
+    pub aaguid: [u8; AAGUID_BYTES],
+}
+
+/// Outcome of a verified assertion.
+#[derive(Debug)]
+pub struct VerifiedAssertion {
+    pub sign_count: u32,
+    pub user_verified: bool,
+}
+
+/// A credential public key.
+pub enum CoseKey {
+    Es256(p256::ecdsa::VerifyingKey),
+    EdDsa(ed25519_dalek::VerifyingKey),
+}
+
+impl CoseKey {
+    /// Parse a COSE_Key (RFC 8152 §13) for ES256 over P-256 or EdDSA over
+    /// Ed25519.
+    pub fn from_cose(bytes: &[u8]) -> Result<Self, WebAuthnError> {
+        let key = cbor::decode(bytes)?;
+        let int = |label| key.get_int(label).and_then(Value::as_int);
+        let coordinate = |label| {
+            key.get_int(label)
+                .and_then(Value::as_bytes)
+                .filter(|b| b.len() == 32)
+                .ok_or(WebAuthnError::Malformed("credential public key"))
+        };
+
+        let algorithm = int(3).ok_or(WebAuthnError::Malformed("credential public key"))?;
+        match (algorithm, int(1), int(-1)) {
+            // kty EC2, crv P-256
+            (ALG_ES256, Some(2), Some(1)) => {
+                let mut sec1 = vec![0x04];
+                sec1.extend_from_slice(coordinate(-2)?);
+                sec1.extend_from_slice(coordinate(-3)?);
+                p256::ecdsa::VerifyingKey::from_sec1_bytes(&sec1)
+                    .map(CoseKey::Es256)
+                    .map_err(|_| WebAuthnError::Malformed("credential public key"))
+            }
+            // kty OKP, crv Ed25519
+            (ALG_EDDSA, Some(1), Some(6)) => {
+                let x: [u8; 32] = coordinate(-2)?.try_into().unwrap();
+                ed25519_dalek::VerifyingKey::from_bytes(&x)
+                    .map(CoseKey::EdDsa)
+                    .map_err(|_| WebAuthnError::Malformed("credential public key"))
+            }
+            (ALG_ES256 | ALG_EDDSA, _, _) => Err(WebAuthnError::Malformed("credential public key")),
+            (other, _, _) => Err(WebAuthnError::UnsupportedAlgorithm(other)),
+        }
+    }
+
+    pub fn algorithm(&self) -> i64 {
+        match self {
+            CoseKey::Es256(_) => ALG_ES256,
+            CoseKey::EdDsa(_) => ALG_EDDSA,
+        }
+    }
+
+    /// Check `signature` over `message`; ES256 signatures are DER encoded.
+    pub fn verify(&self, message: &[u8], signature: &[u8]) -> Result<(), WebAuthnError> {
+        match self {
+            CoseKey::Es256(key) => {
+                use p256::ecdsa::signature::Verifier;
+                let signature = p256::ecdsa::Signature::from_der(signature)
+                    .map_err(|_| WebAuthnError::BadSignature)?;
+                key.verify(message, &signature)
+                    .map_err(|_| WebAuthnError::BadSignature)
+            }
+            CoseKey::EdDsa(key) => {
+                let signature = ed25519_dalek::Signature::from_slice(signature)
+                    .map_err(|_| WebAuthnError::BadSignature)?;
+                key.verify_strict(message, &signature)
+                    .map_err(|_| WebAuthnError::BadSignature)
+            }
+        }
+    }
+}
+
+/// Verify a `navigator.credentials.create()` response and extract the new
+/// credential.
+pub fn verify_registration(
+    client_data_json: &[u8],
+    attestation_object: &[u8],
+    expected: &Expected<'_>,
+) -> Result<RegisteredCredential, WebAuthnError> {
+    check_client_data(client_data_json, expected)?;
+
+    let attestation = cbor::decode(attestation_object)?;
+    let format = attestation
+        .get_text("fmt")
+        .and_then(Value::as_text)
+        .ok_or(WebAuthnError::Malformed("attestation object"))?;
+    let statement = attestation
+        .get_text("attStmt")
+        .ok_or(WebAuthnError::Malformed("attestation object"))?;
+    let auth_data_bytes = attestation
+        .get_text("authData")
+        .and_then(Value::as_bytes)
+        .ok_or(WebAuthnError::Malformed("attestation object"))?;
+
+    let auth_data = parse_authenticator_data(auth_data_bytes)?;
+    check_authenticator_data(&auth_data, expected)?;
+    let credential = auth_data
+        .attested_credential
+        .ok_or(WebAuthnError::Malformed("authenticator data"))?;
+    let key = CoseKey::from_cose(&credential.public_key)?;
+
+    match format {
+        "none" if statement.is_empty_map() => {}
+        "packed" if statement.get_text("x5c").is_some() => {
+            return Err(WebAuthnError::UnsupportedAttestation(
+                "packed attestation with a certificate chain".to_string(),
+            ));
+        }
+        "packed" => {
+            // Self attestation: signed by the credential key itself.
+            let algorithm = statement.get_text("alg").and_then(Value::as_int);
+            if algorithm != Some(key.algorithm()) {
+                return Err(WebAuthnError::Malformed("attestation statement"));
+            }
+            let signature = statement
+                .get_text("sig")
+                .and_then(Value::as_bytes)
+                .ok_or(WebAuthnError::Malformed("attestation statement"))?;
+            key.verify(&signed_data(auth_data_bytes, client_data_json), signature)?;
+        }
+        other => return Err(WebAuthnError::UnsupportedAttestation(other.to_string())),
+    }
+
+    Ok(RegisteredCredential {
+        credential_id: credential.credential_id,
+        public_key: credential.public_key,
+        algorithm: key.algorithm(),
+        sign_count: auth_data.sign_count,
+        aaguid: credential.aaguid,
+    })
+}
+
+/// Verify a `navigator.credentials.get()` response against a stored
+/// credential.
+pub fn verify_assertion(
+    client_data_json: &[u8],
+    authenticator_data: &[u8],
+    signature: &[u8],
+    public_key: &[u8],
+    stored_sign_count: u32,
+    expected: &Expected<'_>,
+) -> Result<VerifiedAssertion, WebAuthnError> {
+    check_client_data(client_data_json, expected)?;
+
+    let auth_data = parse_authenticator_data(authenticator_data)?;
+    check_authenticator_data(&auth_data, expected)?;
+
+    CoseKey::from_cose(public_key)?.verify(
+        &signed_data(authenticator_data, client_data_json),
+        signature,
+    )?;
+
+    // Authenticators that do not keep a counter always report zero.
+    if (auth_data.sign_count != 0 || stored_sign_count != 0)
+        && auth_data.sign_count <= stored_sign_count
+    {
+        return Err(WebAuthnError::CounterRegression);
+    }
+
+    Ok(VerifiedAssertion {
+        sign_count: auth_data.sign_count,
+        user_verified: auth_data.flags & FLAG_USER_VERIFIED != 0,
+    })
+}
+
+/// authenticatorData || SHA-256(clientDataJSON)
+fn signed_data(authenticator_data: &[u8], client_data_json: &[u8]) -> Vec<u8> {
+    let mut data = authenticator_data.to_vec();
+    data.extend_from_slice(&Sha256::digest(client_data_json));
+    data
+}
+
+fn check_client_data(
+    client_data_json: &[u8],
+    expected: &Expected<'_>,
+) -> Result<(), WebAuthnError> {
+    let client_data: CollectedClientData = serde_json::from_slice(client_data_json)
+        .map_err(|_| WebAuthnError::Malformed("client data"))?;
+
+    if client_data.kind != expected.ceremony.client_data_type() {
+        return Err(WebAuthnError::ClientData("type"));
+    }
+    let challenge = general_purpose::URL_SAFE_NO_PAD
+        .decode(client_data.challenge.trim_end_matches('='))
+        .map_err(|_| WebAuthnError::ClientData("challenge"))?;
+    if challenge != expected.challenge {
+        return Err(WebAuthnError::ClientData("challenge"));
+    }
+    if !expected.origins.contains(&client_data.origin) {
+        return Err(WebAuthnError::ClientData("origin"));
+    }
+    Ok(())
+}
+
+pub fn parse_authenticator_data(data: &[u8]) -> Result<AuthenticatorData, WebAuthnError> {
+    const MALFORMED: WebAuthnError = WebAuthnError::Malformed("authenticator data");
+
+    if data.len() < AUTH_DATA_HEADER {
+        return Err(MALFORMED);
+    }
+    let rp_id_hash: [u8; 32] = data[..32].try_into().unwrap();
+    let flags = data[32];
+    let sign_count = u32::from_be_bytes(data[33..37].try_into().unwrap());
+
+    let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+        let rest = &data[AUTH_DATA_HEADER..];
+        if rest.len() < AAGUID_BYTES + 2 {
+            return Err(MALFORMED);
+        }
+        let aaguid: [u8; AAGUID_BYTES] = rest[..AAGUID_BYTES].try_into().unwrap();
+        let id_len = u16::from_be_bytes([rest[AAGUID_BYTES], rest[AAGUID_BYTES + 1]]) as usize;
+        let rest = &rest[AAGUID_BYTES + 2..];
+        if rest.len() < id_len {
+            return Err(MALFORMED);
+        }
+        let (credential_id, rest) = rest.split_at(id_len);
+        // Extensions may follow the key; they are not used.
+        let (_, key_len) = cbor::decode_prefix(rest)?;
+        Some(AttestedCredential {
+            aaguid,
+            credential_id: credential_id.to_vec(),
+            public_key: rest[..key_len].to_vec(),
+        })
+    } else {
+        None
+    };
+
+    Ok(AuthenticatorData {
+        rp_id_hash,
+        flags,
+        sign_count,
+        attested_credential,
+    })
+}
+
+fn check_authenticator_data(
+    auth_data: &AuthenticatorData,
+    expected: &Expected<'_>,
+) -> Result<(), WebAuthnError> {
+    if auth_data.rp_id_hash[..] != Sha256::digest(expected.rp_id.as_bytes())[..] {
+        return Err(WebAuthnError::RpIdMismatch);
+    }
+    if auth_data.flags & FLAG_USER_PRESENT == 0 {
+        return Err(WebAuthnError::UserNotPresent);
+    }
+    if expected.require_user_verification && auth_data.flags & FLAG_USER_VERIFIED == 0 {
+        return Err(WebAuthnError::UserNotVerified);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ed25519_dalek::Signer as _;
+
+    const RP_ID: &str = "arenax.gg";
+    const CHALLENGE: &[u8] = b"0123456789abcdef0123456789abcdef";
+
+    fn origins() -> Vec<String> {
+        vec!["https://arenax.gg".to_string()]
+    }
+
+    fn expected(ceremony: Ceremony, origins: &[String]) -> Expected<'_> {
+        Expected {
+            ceremony,
+            challenge: CHALLENGE,
+            rp_id: RP_ID,
+            origins,
+            require_user_verification: true,
+        }
+    }
+
+    fn client_data(kind: &str, origin: &str) -> Vec<u8> {
+        serde_json::json!({
+            "type": kind,
+            "challenge": general_purpose::URL_SAFE_NO_PAD.encode(CHALLENGE),
+            "origin": origin,
+            "crossOrigin": false,
+        })
+        .to_string()
+        .into_bytes()
+    }
+
+    /// CBOR head for major type `major` with argument `arg` (< 65536).
+    fn head(major: u8, arg: usize) -> Vec<u8> {
+        match arg {
+            0..=23 => vec![(major << 5) | arg as u8],
+            24..=255 => vec![(major << 5) | 24, arg as u8],
+            _ => vec![(major << 5) | 25, (arg >> 8) as u8, arg as u8],
+        }
+    }
+
+    fn bytes(data: &[u8]) -> Vec<u8> {
+        [head(2, data.len()), data.to_vec()].concat()
+    }
+
+    fn text(data: &str) -> Vec<u8> {
+        [head(3, data.len()), data.as_bytes().to_vec()].concat()
+    }
+
+    fn int(value: i64) -> Vec<u8> {
+        if value >= 0 {
+            head(0, value as usize)
+        } else {
+            head(1, (-1 - value) as usize)
+        }
+    }
+
+    fn ed25519_cose(key: &ed25519_dalek::SigningKey) -> Vec<u8> {
+        [
+            head(5, 4),
+            int(1),
+            int(1),
+            int(3),
+            int(ALG_EDDSA),
+            int(-1),
+            int(6),
+            int(-2),
+            bytes(key.verifying_key().as_bytes()),
+        ]
+        .concat()
+    }
+
+    fn authenticator_data(flags: u8, sign_count: u32, credential: Option<&[u8]>) -> Vec<u8> {
+        let mut data = Sha256::digest(RP_ID.as_bytes()).to_vec();
+        data.push(flags);
+        data.extend_from_slice(&sign_count.to_be_bytes());
+        if let Some(cose) = credential {
+            data.extend_from_slice(&[7u8; AAGUID_BYTES]);
+            data.extend_from_slice(&4u16.to_be_bytes());
+            data.extend_from_slice(b"cred");
+            data.extend_from_slice(cose);
+        }
+        data
+    }
+
+    fn attestation_object(fmt: &str, statement: Vec<u8>, auth_data: &[u8]) -> Vec<u8> {
+        [
+            head(5, 3),
+            text("fmt"),
+            text(fmt),
+            text("attStmt"),
+            statement,
+            text("authData"),
+            bytes(auth_data),
+        ]
+        .concat()
+    }
+
+    #[test]
+    fn test_registration_with_none_attestation() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let cose = ed25519_cose(&key);
+        let auth_data = authenticator_data(0x45, 0, Some(&cose));
+        let object = attestation_object("none", head(5, 0), &auth_data);
+        let origins = origins();
+
+        let credential = verify_registration(
+            &client_data("webauthn.create", "https://arenax.gg"),
+            &object,
+            &expected(Ceremony::Create, &origins),
+        )
+        .unwrap();
+        assert_eq!(credential.credential_id, b"cred");
+        assert_eq!(credential.public_key, cose);
+        assert_eq!(credential.algorithm, ALG_EDDSA);
+        assert_eq!(credential.aaguid, [7u8; AAGUID_BYTES]);
+
+        let wrong_origin = verify_registration(
+            &client_data("webauthn.create", "https://evil.example"),
+            &object,
+            &expected(Ceremony::Create, &origins),
+        );
+        assert!(matches!(
+            wrong_origin,
+            Err(WebAuthnError::ClientData("origin"))
+        ));
+
+        let wrong_type = verify_registration(
+            &client_data("webauthn.get", "https://arenax.gg"),
+            &object,
+            &expected(Ceremony::Create, &origins),
+        );
+        assert!(matches!(wrong_type, Err(WebAuthnError::ClientData("type"))));
+    }
+
+    #[test]
+    fn test_registration_with_self_attestation() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let auth_data = authenticator_data(0x45, 1, Some(&ed25519_cose(&key)));
+        let client_data = client_data("webauthn.create", "https://arenax.gg");
+        let signature = key.sign(&signed_data(&auth_data, &client_data));
+        let statement = [
+            head(5, 2),
+            text("alg"),
+            int(ALG_EDDSA),
+            text("sig"),
+            bytes(&signature.to_bytes()),
+        ]
+        .concat();
+        let origins = origins();
+
+        let object = attestation_object("packed", statement, &auth_data);
+        assert!(
+            verify_registration(&client_data, &object, &expected(Ceremony::Create, &origins))
+                .is_ok()
+        );
+
+        let other_key = ed25519_dalek::SigningKey::from_bytes(&[8u8; 32]);
+        let forged = other_key.sign(&signed_data(&auth_data, &client_data));
+        let statement = [
+            head(5, 2),
+            text("alg"),
+            int(ALG_EDDSA),
+            text("sig"),
+            bytes(&forged.to_bytes()),
+        ]
+        .concat();
+        let object = attestation_object("packed", statement, &auth_data);
+        assert!(matches!(
+            verify_registration(&client_data, &object, &expected(Ceremony::Create, &origins)),
+            Err(WebAuthnError::BadSignature)
+        ));
+    }
+
+    #[test]
+    fn test_assertion_checks_signature_flags_and_counter() {
+        let key = ed25519_dalek::SigningKey::from_bytes(&[9u8; 32]);
+        let cose = ed25519_cose(&key);
+        let client_data = client_data("webauthn.get", "https://arenax.gg");
+        let origins = origins();
+        let expected = expected(Ceremony::Get, &origins);
+
+        let auth_data = authenticator_data(0x05, 6, None);
+        let signature = key.sign(&signed_data(&auth_data, &client_data)).to_bytes();
+        let verified =
+            verify_assertion(&client_data, &auth_data, &signature, &cose, 5, &expected).unwrap();
+        assert_eq!(verified.sign_count, 6);
+        assert!(verified.user_verified);
+
+        assert!(matches!(
+            verify_assertion(&client_data, &auth_data, &signature, &cose, 6, &expected),
+            Err(WebAuthnError::CounterRegression)
+        ));
+
+        let mut tampered = signature;
+        tampered[0] ^= 1;
+        assert!(matches!(
+            verify_assertion(&client_data, &auth_data, &tampered, &cose, 5, &expected),
+            Err(WebAuthnError::BadSignature)
+        ));
+
+        // Presence without verification
+        let auth_data = authenticator_data(0x01, 7, None);
+        let signature = key.sign(&signed_data(&auth_data, &client_data)).to_bytes();
+        assert!(matches!(
+            verify_assertion(&client_data, &auth_data, &signature, &cose, 5, &expected),
+            Err(WebAuthnError::UserNotVerified)
+        ));
+    }
+
+    #[test]
+    fn test_es256_key_and_der_signature() {
+        use p256::ecdsa::{signature::Signer, Signature, SigningKey};
+
+        let signing_key = SigningKey::from_slice(&[3u8; 32]).unwrap();
+        let point = signing_key.verifying_key().to_encoded_point(false);
+        let cose = [
+            head(5, 5),
+            int(1),
+            int(2),
+            int(3),
+            int(ALG_ES256),
+            int(-1),
+            int(1),
+            int(-2),
+            bytes(point.x().unwrap()),
+            int(-3),
+            bytes(point.y().unwrap()),
+        ]
+        .concat();
+
+        let key = CoseKey::from_cose(&cose).unwrap();
+        assert_eq!(key.algorithm(), ALG_ES256);
+
+        let signature: Signature = signing_key.sign(b"message");
+        assert!(key
+            .verify(b"message", signature.to_der().as_bytes())
+            .is_ok());
+        assert!(key.verify(b"other", signature.to_der().as_bytes()).is_err());
+    }
+
+    #[test]
+    fn test_rejects_unsupported_algorithms() {
+        // RS256
+        let cose = [head(5, 2), int(1), int(3), int(3), int(-257)].concat();
+        assert!(matches!(
+            CoseKey::from_cose(&cose),
+            Err(WebAuthnError::UnsupportedAlgorithm(-257))
+        ));
+    }
+}
@@ -0,0 +1,209 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::rbac::{Admin, RequireRole};
+use crate::models::{
+    AdminReasonRequest, BanUserRequest, EscalateDisputeRequest, SetFeatureFlagRequest,
+    TournamentOverrideRequest,
+};
+use crate::service::admin_service::AdminService;
+
+#[derive(Debug, Deserialize)]
+pub struct UserSearchQuery {
+    /// Part of a username or email, or a user id.
+    pub q: Option<String>,
+    pub banned: Option<bool>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct PayoutQueueQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+fn validate<T: Validate>(body: &T) -> Result<(), ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))
+}
+
+// =============================================================================
+// USERS
+// =============================================================================
+
+/// GET /api/admin/users
+pub async fn search_users(
+    svc: web::Data<Arc<AdminService>>,
+    _admin: RequireRole<Admin>,
+    query: web::Query<UserSearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let users = svc
+        .search_users(query.q.as_deref(), query.banned, limit, offset)
+        .await?;
+    Ok(HttpResponse::Ok().json(users))
+}
+
+/// POST /api/admin/users/{id}/ban
+///
+/// Ban a user, for good unless `until` is given, and end their sessions.
+pub async fn ban_user(
+    svc: web::Data<Arc<AdminService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<BanUserRequest>,
+) -> Result<HttpResponse, ApiError> {
+    validate(&*body)?;
+    let user = svc
+        .ban_user(admin.user_id, path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+/// POST /api/admin/users/{id}/unban
+pub async fn unban_user(
+    svc: web::Data<Arc<AdminService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<AdminReasonRequest>,
+) -> Result<HttpResponse, ApiError> {
+    validate(&*body)?;
+    let user = svc
+        .unban_user(admin.user_id, path.into_inner(), &body.reason)
+        .await?;
+    Ok(HttpResponse::Ok().json(user))
+}
+
+// =============================================================================
+// TOURNAMENTS AND DISPUTES
+// =============================================================================
+
+/// PATCH /api/admin/tournaments/{id}
+///
+/// Override a tournament's status, capacity or schedule.
+pub async fn override_tournament(
+    svc: web::Data<Arc<AdminService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<TournamentOverrideRequest>,
+) -> Result<HttpResponse, ApiError> {
+    validate(&*body)?;
+    let tournament = svc
+        .override_tournament(admin.user_id, path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Ok().json(tournament))
+}
+
+/// POST /api/admin/disputes/{id}/escalate
+///
+/// Move a dispute to the front of the referee queue, optionally handing it to
+/// another referee.
+pub async fn escalate_dispute(
+    svc: web::Data<Arc<AdminService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<EscalateDisputeRequest>,
+) -> Result<HttpResponse, ApiError> {
+    validate(&*body)?;
+    let case = svc
+        .escalate_dispute(admin.user_id, path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Ok().json(case))
+}
+
+// =============================================================================
+// PAYOUTS
+// =============================================================================
+
+/// GET /api/admin/payouts
+///
+/// Withdrawals waiting for approval.
+pub async fn pending_payouts(
+    svc: web::Data<Arc<AdminService>>,
+    _admin: RequireRole<Admin>,
+    query: web::Query<PayoutQueueQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let payouts = svc.pending_payouts(limit, offset).await?;
+    Ok(HttpResponse::Ok().json(payouts))
+}
+
+/// POST /api/admin/payouts/{id}/approve
+pub async fn approve_payout(
+    svc: web::Data<Arc<AdminService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<AdminReasonRequest>,
+) -> Result<HttpResponse, ApiError> {
+    validate(&*body)?;
+    let payout = svc
+        .approve_payout(admin.user_id, path.into_inner(), &body.reason)
+        .await?;
+    Ok(HttpResponse::Ok().json(payout))
+}
+
+/// POST /api/admin/payouts/{id}/reject
+///
+/// Rejects the withdrawal and returns the held funds to the user.
+pub async fn reject_payout(
+    svc: web::Data<Arc<AdminService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<AdminReasonRequest>,
+) -> Result<HttpResponse, ApiError> {
+    validate(&*body)?;
+    let payout = svc
+        .reject_payout(admin.user_id, path.into_inner(), &body.reason)
+        .await?;
+    Ok(HttpResponse::Ok().json(payout))
+}
+
+// =============================================================================
+// FEATURE TOGGLES
+// =============================================================================
+
+/// GET /api/admin/features
+pub async fn list_features(
+    svc: web::Data<Arc<AdminService>>,
+    _admin: RequireRole<Admin>,
+) -> Result<HttpResponse, ApiError> {
+    let flags = svc.feature_flags().await?;
+    Ok(HttpResponse::Ok().json(flags))
+}
+
+/// PUT /api/admin/features/{key}
+pub async fn set_feature(
+    svc: web::Data<Arc<AdminService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<String>,
+    body: web::Json<SetFeatureFlagRequest>,
+) -> Result<HttpResponse, ApiError> {
+    validate(&*body)?;
+    let flag = svc
+        .set_feature_flag(admin.user_id, &path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Ok().json(flag))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/admin")
+            .route("/users", web::get().to(search_users))
+            .route("/users/{id}/ban", web::post().to(ban_user))
+            .route("/users/{id}/unban", web::post().to(unban_user))
+            .route("/tournaments/{id}", web::patch().to(override_tournament))
+            .route("/disputes/{id}/escalate", web::post().to(escalate_dispute))
+            .route("/payouts", web::get().to(pending_payouts))
+            .route("/payouts/{id}/approve", web::post().to(approve_payout))
+            .route("/payouts/{id}/reject", web::post().to(reject_payout))
+            .route("/features", web::get().to(list_features))
+            .route("/features/{key}", web::put().to(set_feature)),
+    );
+}
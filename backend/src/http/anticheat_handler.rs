@@ -0,0 +1,121 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::auth::rbac::{Admin, Operator, Referee, RequireRole};
+use crate::models::{
+    DetectionStatus, ReviewDetectionRequest, SubmitAnomalyReportRequest, SubmitTelemetryRequest,
+};
+use crate::service::anticheat::AntiCheatService;
+
+#[derive(Debug, Deserialize)]
+pub struct DetectionsQuery {
+    pub status: Option<DetectionStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// POST /api/anticheat/telemetry
+///
+/// Per-match telemetry from the game client for the signed-in player.
+pub async fn submit_telemetry(
+    anticheat: web::Data<Arc<AntiCheatService>>,
+    req: HttpRequest,
+    body: web::Json<SubmitTelemetryRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let receipt = anticheat.ingest_telemetry(user_id, &body).await?;
+    Ok(HttpResponse::Accepted().json(receipt))
+}
+
+/// POST /api/anticheat/reports
+///
+/// A statistical anomaly report from an analytics service. Operators only.
+pub async fn submit_report(
+    anticheat: web::Data<Arc<AntiCheatService>>,
+    operator: RequireRole<Operator>,
+    body: web::Json<SubmitAnomalyReportRequest>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let receipt = anticheat.ingest_report(operator.user_id, &body).await?;
+    Ok(HttpResponse::Accepted().json(receipt))
+}
+
+/// GET /api/anticheat/detections
+///
+/// Detections by status, highest score first; defaults to the review queue.
+pub async fn list_detections(
+    anticheat: web::Data<Arc<AntiCheatService>>,
+    _referee: RequireRole<Referee>,
+    query: web::Query<DetectionsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let status = query.status.unwrap_or(DetectionStatus::PendingReview);
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let detections = anticheat.list(status, limit, offset).await?;
+    Ok(HttpResponse::Ok().json(detections))
+}
+
+/// GET /api/anticheat/detections/{id}
+///
+/// A detection with its signals and audit trail.
+pub async fn get_detection(
+    anticheat: web::Data<Arc<AntiCheatService>>,
+    _referee: RequireRole<Referee>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let detail = anticheat.get(path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(detail))
+}
+
+/// POST /api/anticheat/detections/{id}/review
+///
+/// Confirm or dismiss a detection. Confirmed detections are flagged on the
+/// anti-cheat oracle in the background.
+pub async fn review_detection(
+    anticheat: web::Data<Arc<AntiCheatService>>,
+    referee: RequireRole<Referee>,
+    path: web::Path<Uuid>,
+    body: web::Json<ReviewDetectionRequest>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let detection = anticheat
+        .review(referee.user_id, path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Ok().json(detection))
+}
+
+/// POST /api/anticheat/detections/{id}/resubmit
+///
+/// Retry a failed oracle submission. Admins only.
+pub async fn resubmit_detection(
+    anticheat: web::Data<Arc<AntiCheatService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let detection = anticheat.resubmit(admin.user_id, path.into_inner()).await?;
+    Ok(HttpResponse::Accepted().json(detection))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/anticheat")
+            .route("/telemetry", web::post().to(submit_telemetry))
+            .route("/reports", web::post().to(submit_report))
+            .route("/detections", web::get().to(list_detections))
+            .route("/detections/{id}", web::get().to(get_detection))
+            .route("/detections/{id}/review", web::post().to(review_detection))
+            .route(
+                "/detections/{id}/resubmit",
+                web::post().to(resubmit_detection),
+            ),
+    );
+}
@@ -0,0 +1,56 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api_error::ApiError;
+use crate::auth::rbac::{Auditor, RequireRole};
+use crate::models::AuditLogFilter;
+use crate::service::audit_log::{AuditLogService, MAX_VERIFY_BATCH};
+
+#[derive(Debug, Deserialize)]
+pub struct VerifyChainQuery {
+    /// First entry to check; the start of the chain if omitted.
+    pub from_seq: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/audit/logs
+///
+/// Search the audit trail by actor, action, resource, request id or time,
+/// newest first.
+pub async fn search_logs(
+    svc: web::Data<Arc<AuditLogService>>,
+    _auditor: RequireRole<Auditor>,
+    query: web::Query<AuditLogFilter>,
+) -> Result<HttpResponse, ApiError> {
+    if let (Some(from), Some(to)) = (query.from, query.to) {
+        if from >= to {
+            return Err(ApiError::bad_request("`from` must be before `to`"));
+        }
+    }
+    let entries = svc.search(&query).await?;
+    Ok(HttpResponse::Ok().json(entries))
+}
+
+/// GET /api/audit/verify
+///
+/// Check a stretch of the hash chain and report the first entry that does
+/// not match.
+pub async fn verify_chain(
+    svc: web::Data<Arc<AuditLogService>>,
+    _auditor: RequireRole<Auditor>,
+    query: web::Query<VerifyChainQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let report = svc
+        .verify(query.from_seq, query.limit.unwrap_or(MAX_VERIFY_BATCH))
+        .await?;
+    Ok(HttpResponse::Ok().json(report))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/audit")
+            .route("/logs", web::get().to(search_logs))
+            .route("/verify", web::get().to(verify_chain)),
+    );
+}
@@ -1,11 +1,25 @@
 use crate::api_error::ApiError;
-use crate::auth::jwt_service::TokenPair;
+use crate::auth::account_email::AccountEmailService;
+use crate::auth::device_security_policies::{
+    DeviceSecurityPolicies, GeoLocation, LoginContext, SecurityPolicyRequest,
+};
+use crate::auth::device_service::{DeviceInfo, DeviceService, DeviceType};
+use crate::auth::jwt_service::{SessionClient, TokenPair};
 use crate::auth::middleware::ClaimsExt;
+use crate::auth::oauth::{OAuthProvider, OAuthService};
+use crate::auth::phone_verification::PhoneVerificationService;
+use crate::auth::rbac::{Admin, RequireRole};
+use crate::auth::sep10::Sep10Service;
+use crate::auth::step_up::StepUp;
+use crate::auth::two_factor::TwoFactorService;
+use crate::auth::webauthn::{PasskeyAssertionRequest, PasskeyService, RegisterPasskeyRequest};
 use crate::models::user::{AuthResponse, CreateUserRequest, LoginRequest};
-use crate::service::auth_service::{ActiveSession, AuthService};
+use crate::service::auth_service::{ActiveSession, AuthService, LinkedIdentity};
+use crate::service::sms::SmsChannel;
 use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use serde::{Deserialize, Serialize};
-use tracing::info;
+use std::sync::Arc;
+use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Refresh token request
@@ -27,6 +41,67 @@ pub struct LogoutRequest {
     pub token: String,
 }
 
+/// SEP-10 challenge query
+#[derive(Debug, Deserialize)]
+pub struct Sep10ChallengeQuery {
+    pub account: String,
+    pub home_domain: Option<String>,
+}
+
+/// SEP-10 signed challenge
+#[derive(Debug, Deserialize)]
+pub struct Sep10TokenRequest {
+    pub transaction: String,
+    /// TOTP or recovery code, for users with two-factor authentication.
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+/// OAuth provider redirect, posted back by the frontend
+#[derive(Debug, Deserialize)]
+pub struct OAuthCallbackRequest {
+    pub code: String,
+    pub state: String,
+    /// TOTP or recovery code, for users with two-factor authentication.
+    #[serde(default)]
+    pub totp_code: Option<String>,
+}
+
+/// Social sign-in providers response
+#[derive(Debug, Serialize)]
+pub struct OAuthProvidersResponse {
+    pub providers: Vec<OAuthProvider>,
+}
+
+/// Linked external accounts response
+#[derive(Debug, Serialize)]
+pub struct LinkedIdentitiesResponse {
+    pub identities: Vec<LinkedIdentity>,
+    /// A linked Twitch account unlocks streamer features.
+    pub streamer: bool,
+}
+
+/// Two-factor code, from the authenticator app or a recovery code
+#[derive(Debug, Deserialize)]
+pub struct TwoFactorCodeRequest {
+    pub code: String,
+}
+
+/// Recovery codes response
+#[derive(Debug, Serialize)]
+pub struct RecoveryCodesResponse {
+    pub recovery_codes: Vec<String>,
+}
+
+/// Passkey sign-in options request
+#[derive(Debug, Default, Deserialize)]
+pub struct PasskeyLoginOptionsRequest {
+    /// Narrows the allowed credentials to this account's passkeys; omit for
+    /// discoverable credentials.
+    #[serde(default)]
+    pub email: Option<String>,
+}
+
 /// Sessions response
 #[derive(Debug, Serialize)]
 pub struct SessionsResponse {
@@ -34,19 +109,73 @@ pub struct SessionsResponse {
     pub total: usize,
 }
 
+/// Device update request (`PATCH /api/auth/devices/{device_id}`)
+#[derive(Debug, Deserialize)]
+pub struct UpdateDeviceRequest {
+    #[serde(default)]
+    pub name: Option<String>,
+    /// Only `true`: trusting a device needs step-up, untrusting is done by
+    /// removing it.
+    #[serde(default)]
+    pub trusted: Option<bool>,
+}
+
+/// Emailed token, from a verification or password reset link
+#[derive(Debug, Deserialize)]
+pub struct EmailTokenRequest {
+    pub token: String,
+}
+
+/// Password reset link request
+#[derive(Debug, Deserialize)]
+pub struct PasswordResetLinkRequest {
+    pub email: String,
+}
+
+/// Password reset with an emailed token
+#[derive(Debug, Deserialize)]
+pub struct ResetPasswordRequest {
+    pub token: String,
+    pub new_password: String,
+}
+
+/// Phone verification code request
+#[derive(Debug, Deserialize)]
+pub struct SendPhoneCodeRequest {
+    pub phone_number: String,
+    #[serde(default)]
+    pub channel: SmsChannel,
+}
+
+/// Phone verification code, as received
+#[derive(Debug, Deserialize)]
+pub struct VerifyPhoneRequest {
+    pub code: String,
+}
+
 /// POST /api/auth/register
 /// Register a new user
 pub async fn register(
     auth_service: web::Data<AuthService>,
+    account_email: web::Data<Arc<AccountEmailService>>,
+    req: HttpRequest,
     request: web::Json<CreateUserRequest>,
 ) -> Result<impl Responder, ApiError> {
     info!(
         username = %request.username,
-        email = %request.email,
+        email = ?request.email,
         "Registration request received"
     );
 
-    let response = auth_service.register(request.into_inner()).await?;
+    let response = auth_service
+        .register(request.into_inner(), session_client(&req))
+        .await?;
+
+    // The account works before the address is confirmed, so a failed mail
+    // only means the user asks for another link.
+    if let Err(e) = account_email.send_verification(response.user.id).await {
+        warn!(user_id = %response.user.id, error = %e, "Verification email not sent");
+    }
 
     Ok(HttpResponse::Created().json(response))
 }
@@ -55,11 +184,16 @@ pub async fn register(
 /// Login user and get tokens
 pub async fn login(
     auth_service: web::Data<AuthService>,
+    req: HttpRequest,
     request: web::Json<LoginRequest>,
 ) -> Result<impl Responder, ApiError> {
     info!(email = %request.email, "Login request received");
 
-    let response = auth_service.login(request.into_inner()).await?;
+    let context = LoginContext {
+        device: device_info_from_request(&req),
+        location: GeoLocation::from_request(&req),
+    };
+    let response = auth_service.login(request.into_inner(), context).await?;
 
     Ok(HttpResponse::Ok().json(response))
 }
@@ -68,11 +202,14 @@ pub async fn login(
 /// Refresh access token using refresh token
 pub async fn refresh_token(
     auth_service: web::Data<AuthService>,
+    req: HttpRequest,
     request: web::Json<RefreshTokenRequest>,
 ) -> Result<impl Responder, ApiError> {
     info!("Token refresh request received");
 
-    let token_pair = auth_service.refresh_token(&request.refresh_token).await?;
+    let token_pair = auth_service
+        .refresh_token(&request.refresh_token, session_client(&req))
+        .await?;
 
     Ok(HttpResponse::Ok().json(token_pair))
 }
@@ -100,6 +237,154 @@ pub async fn logout(
     })))
 }
 
+/// GET /api/auth/sep10?account=G...
+/// Issue a SEP-10 challenge for a Stellar wallet to sign
+pub async fn sep10_challenge(
+    sep10: web::Data<Option<Arc<Sep10Service>>>,
+    query: web::Query<Sep10ChallengeQuery>,
+) -> Result<impl Responder, ApiError> {
+    let sep10 = sep10
+        .as_ref()
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Stellar wallet login is not available"))?;
+
+    let challenge = sep10
+        .challenge(&query.account, query.home_domain.as_deref())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(challenge))
+}
+
+/// POST /api/auth/sep10
+/// Exchange a co-signed SEP-10 challenge for tokens. With a valid access
+/// token, the Stellar account is linked to the caller's account first.
+pub async fn sep10_token(
+    auth_service: web::Data<AuthService>,
+    sep10: web::Data<Option<Arc<Sep10Service>>>,
+    req: HttpRequest,
+    request: web::Json<Sep10TokenRequest>,
+) -> Result<impl Responder, ApiError> {
+    let sep10 = sep10
+        .as_ref()
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Stellar wallet login is not available"))?;
+
+    let link_to = bearer_user(&auth_service, &req).await?;
+
+    let account = sep10.verify(&request.transaction).await?;
+    let response = auth_service
+        .stellar_login(
+            &account,
+            link_to,
+            request.totp_code.as_deref(),
+            session_client(&req),
+        )
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// The caller, if the request carries an access token. Sign-in endpoints use
+/// it to link a new sign-in method to an already signed-in user.
+async fn bearer_user(
+    auth_service: &AuthService,
+    req: &HttpRequest,
+) -> Result<Option<Uuid>, ApiError> {
+    match req
+        .headers()
+        .get("Authorization")
+        .and_then(|h| h.to_str().ok())
+        .and_then(|s| s.strip_prefix("Bearer "))
+    {
+        Some(token) => Ok(Some(auth_service.verify_token(token).await?)),
+        None => Ok(None),
+    }
+}
+
+/// GET /api/auth/oauth/providers
+/// Social sign-in providers that are configured
+pub async fn oauth_providers(oauth: web::Data<Arc<OAuthService>>) -> impl Responder {
+    HttpResponse::Ok().json(OAuthProvidersResponse {
+        providers: oauth.providers(),
+    })
+}
+
+/// GET /api/auth/oauth/{provider}
+/// Start social sign-in: returns the provider's consent URL. With a valid
+/// access token, the external account will be linked to the caller instead.
+pub async fn oauth_authorize(
+    auth_service: web::Data<AuthService>,
+    oauth: web::Data<Arc<OAuthService>>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let provider: OAuthProvider = path.parse()?;
+    let link_to = bearer_user(&auth_service, &req).await?;
+
+    let authorization = oauth.authorize(provider, link_to).await?;
+
+    Ok(HttpResponse::Ok().json(authorization))
+}
+
+/// POST /api/auth/oauth/{provider}/callback
+/// Finish social sign-in with the code and state the provider redirected with
+pub async fn oauth_callback(
+    auth_service: web::Data<AuthService>,
+    oauth: web::Data<Arc<OAuthService>>,
+    path: web::Path<String>,
+    req: HttpRequest,
+    request: web::Json<OAuthCallbackRequest>,
+) -> Result<impl Responder, ApiError> {
+    let provider: OAuthProvider = path.parse()?;
+
+    let callback = oauth
+        .complete(provider, &request.code, &request.state)
+        .await?;
+    let response = auth_service
+        .oauth_login(callback, request.totp_code.as_deref(), session_client(&req))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// GET /api/auth/oauth/identities
+/// External accounts linked to the current user (requires authentication)
+pub async fn list_linked_identities(
+    auth_service: web::Data<AuthService>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let identities = auth_service.linked_identities(user_id).await?;
+    let streamer = identities
+        .iter()
+        .any(|identity| identity.provider == OAuthProvider::Twitch.as_str());
+
+    Ok(HttpResponse::Ok().json(LinkedIdentitiesResponse {
+        identities,
+        streamer,
+    }))
+}
+
+/// DELETE /api/auth/oauth/identities/{provider}
+/// Unlink an external account (requires authentication)
+pub async fn unlink_identity(
+    auth_service: web::Data<AuthService>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    let provider: OAuthProvider = path.parse()?;
+
+    auth_service.unlink_identity(user_id, provider).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
 /// GET /api/auth/me
 /// Get current user profile (requires authentication)
 pub async fn get_current_user(
@@ -163,37 +448,652 @@ pub async fn revoke_all_sessions(
     })))
 }
 
+/// POST /api/auth/verify-email/send
+/// Email a new verification link (requires authentication)
+pub async fn send_verification_email(
+    account_email: web::Data<Arc<AccountEmailService>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    account_email.send_verification(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Verification email sent"
+    })))
+}
+
+/// POST /api/auth/verify-email
+/// Confirm an email address with the token from the verification link
+pub async fn verify_email(
+    account_email: web::Data<Arc<AccountEmailService>>,
+    request: web::Json<EmailTokenRequest>,
+) -> Result<impl Responder, ApiError> {
+    account_email.verify_email(&request.token).await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Email address verified"
+    })))
+}
+
+/// POST /api/auth/reset-password/request
+/// Email a password reset link; the response is the same whether or not an
+/// account has the address
+pub async fn request_password_reset(
+    account_email: web::Data<Arc<AccountEmailService>>,
+    request: web::Json<PasswordResetLinkRequest>,
+) -> Result<impl Responder, ApiError> {
+    account_email.request_password_reset(&request.email).await?;
+
+    Ok(HttpResponse::Accepted().json(serde_json::json!({
+        "message": "If an account uses this address, a reset link is on its way"
+    })))
+}
+
+/// POST /api/auth/reset-password
+/// Set a new password with the token from the reset link; signs out all
+/// sessions
+pub async fn reset_password(
+    auth_service: web::Data<AuthService>,
+    account_email: web::Data<Arc<AccountEmailService>>,
+    request: web::Json<ResetPasswordRequest>,
+) -> Result<impl Responder, ApiError> {
+    let request = request.into_inner();
+    let user_id = account_email
+        .reset_password(&request.token, &request.new_password)
+        .await?;
+    let count = auth_service.revoke_all_sessions(user_id).await?;
+
+    info!(user_id = %user_id, count = count, "Password reset; sessions revoked");
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Password reset successfully. All sessions have been revoked."
+    })))
+}
+
+/// GET /api/auth/phone
+/// Phone number and whether it is verified (requires authentication)
+pub async fn get_phone_status(
+    phone: web::Data<Arc<PhoneVerificationService>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    Ok(HttpResponse::Ok().json(phone.status(user_id).await?))
+}
+
+/// POST /api/auth/phone/send
+/// Text a verification code to a phone number by SMS or WhatsApp (requires
+/// authentication)
+pub async fn send_phone_code(
+    phone: web::Data<Arc<PhoneVerificationService>>,
+    req: HttpRequest,
+    request: web::Json<SendPhoneCodeRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let sent = phone
+        .send_code(user_id, &request.phone_number, request.channel)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(sent))
+}
+
+/// POST /api/auth/phone/verify
+/// Verify the phone number with the code texted to it (requires
+/// authentication)
+pub async fn verify_phone(
+    phone: web::Data<Arc<PhoneVerificationService>>,
+    req: HttpRequest,
+    request: web::Json<VerifyPhoneRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    Ok(HttpResponse::Ok().json(phone.verify_code(user_id, &request.code).await?))
+}
+
 /// GET /api/auth/sessions
 /// Get all active sessions for current user (requires authentication)
 pub async fn get_sessions(
     auth_service: web::Data<AuthService>,
+    devices: web::Data<Arc<DeviceService>>,
     req: HttpRequest,
 ) -> Result<impl Responder, ApiError> {
+    let claims = req
+        .claims()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
     let user_id = req
         .user_id()
         .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
 
-    let sessions = auth_service.get_sessions(user_id).await?;
+    let mut sessions = auth_service
+        .get_sessions(user_id, Some(&claims.session_id))
+        .await?;
+    attach_devices(&devices, user_id, &mut sessions).await?;
     let total = sessions.len();
 
     Ok(HttpResponse::Ok().json(SessionsResponse { sessions, total }))
 }
 
-/// GET /api/auth/analytics
-/// Get token analytics (admin only)
-pub async fn get_analytics(
+/// DELETE /api/auth/sessions/{session_id}
+/// Sign out one of the current user's sessions (requires authentication)
+pub async fn revoke_session(
+    auth_service: web::Data<AuthService>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    auth_service
+        .revoke_user_session(user_id, &path.into_inner())
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /api/auth/sessions/revoke-others
+/// Sign out every session but the current one (requires authentication)
+pub async fn revoke_other_sessions(
     auth_service: web::Data<AuthService>,
     req: HttpRequest,
 ) -> Result<impl Responder, ApiError> {
-    // Check if user is admin
     let claims = req
         .claims()
         .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let count = auth_service
+        .revoke_other_sessions(user_id, &claims.session_id)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} session(s) revoked successfully", count),
+        "count": count
+    })))
+}
+
+/// GET /api/auth/devices
+/// Devices the current user signed in from (requires authentication)
+pub async fn list_devices(
+    devices: web::Data<Arc<DeviceService>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let devices = devices.get_user_devices(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(devices))
+}
+
+/// PATCH /api/auth/devices/{device_id}
+/// Rename a device or mark it trusted (requires authentication; trusting
+/// needs step-up)
+pub async fn update_device(
+    devices: web::Data<Arc<DeviceService>>,
+    step_up: web::Data<StepUp>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    request: web::Json<UpdateDeviceRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    let device_id = path.into_inner();
+
+    match request.trusted {
+        Some(true) => {
+            step_up.require(&req, user_id).await?;
+            devices.trust_device(user_id, device_id).await?;
+        }
+        Some(false) => {
+            return Err(ApiError::bad_request(
+                "Remove the device to stop trusting it",
+            ))
+        }
+        None => {}
+    }
+    let device = match &request.name {
+        Some(name) => devices.rename_device(user_id, device_id, name).await?,
+        None => devices.get_device(device_id).await?,
+    };
+    if device.user_id != user_id {
+        return Err(ApiError::not_found("Device not found"));
+    }
+
+    Ok(HttpResponse::Ok().json(device))
+}
+
+/// DELETE /api/auth/devices/{device_id}
+/// Remove a device and sign out its sessions (requires authentication)
+pub async fn delete_device(
+    auth_service: web::Data<AuthService>,
+    devices: web::Data<Arc<DeviceService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    let device_id = path.into_inner();
+
+    devices.revoke_device(user_id, device_id).await?;
+    auth_service
+        .revoke_device_sessions(user_id, device_id)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
 
-    if !claims.roles.contains(&"admin".to_string()) {
-        return Err(ApiError::forbidden("Admin access required"));
+/// Fill in the devices sessions are bound to.
+async fn attach_devices(
+    devices: &DeviceService,
+    user_id: Uuid,
+    sessions: &mut [ActiveSession],
+) -> Result<(), ApiError> {
+    if sessions.iter().all(|s| s.device_id.is_none()) {
+        return Ok(());
     }
+    let user_devices = devices.get_user_devices(user_id).await?;
+    for session in sessions.iter_mut() {
+        session.device = user_devices
+            .iter()
+            .find(|d| session.device_id.as_deref() == Some(d.id.to_string().as_str()))
+            .cloned();
+    }
+    Ok(())
+}
+
+/// Where a sign-in or refresh comes from, for the sessions list.
+fn session_client(req: &HttpRequest) -> SessionClient {
+    SessionClient {
+        ip_address: req
+            .connection_info()
+            .realip_remote_addr()
+            .map(str::to_string),
+        location: GeoLocation::from_request(req).country,
+    }
+}
+
+fn two_factor_service(
+    two_factor: &web::Data<Option<Arc<TwoFactorService>>>,
+) -> Result<&Arc<TwoFactorService>, ApiError> {
+    two_factor
+        .as_ref()
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Two-factor authentication is not available"))
+}
+
+/// GET /api/auth/2fa
+/// Two-factor status of the current user (requires authentication)
+pub async fn get_two_factor_status(
+    two_factor: web::Data<Option<Arc<TwoFactorService>>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let status = two_factor_service(&two_factor)?.status(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(status))
+}
+
+/// POST /api/auth/2fa/setup
+/// Start two-factor enrolment: returns the secret and its otpauth:// URI
+pub async fn setup_two_factor(
+    two_factor: web::Data<Option<Arc<TwoFactorService>>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let setup = two_factor_service(&two_factor)?.setup(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(setup))
+}
+
+/// POST /api/auth/2fa/enable
+/// Confirm enrolment with a first code; returns the recovery codes
+pub async fn enable_two_factor(
+    two_factor: web::Data<Option<Arc<TwoFactorService>>>,
+    req: HttpRequest,
+    request: web::Json<TwoFactorCodeRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let recovery_codes = two_factor_service(&two_factor)?
+        .enable(user_id, &request.code)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(RecoveryCodesResponse { recovery_codes }))
+}
+
+/// POST /api/auth/2fa/disable
+/// Turn two-factor authentication off
+pub async fn disable_two_factor(
+    two_factor: web::Data<Option<Arc<TwoFactorService>>>,
+    req: HttpRequest,
+    request: web::Json<TwoFactorCodeRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    two_factor_service(&two_factor)?
+        .disable(user_id, &request.code)
+        .await?;
 
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": "Two-factor authentication disabled"
+    })))
+}
+
+/// POST /api/auth/2fa/recovery-codes
+/// Replace the recovery codes
+pub async fn regenerate_recovery_codes(
+    two_factor: web::Data<Option<Arc<TwoFactorService>>>,
+    req: HttpRequest,
+    request: web::Json<TwoFactorCodeRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let recovery_codes = two_factor_service(&two_factor)?
+        .regenerate_recovery_codes(user_id, &request.code)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(RecoveryCodesResponse { recovery_codes }))
+}
+
+/// The requesting device as far as its headers describe it, for passkeys
+/// registered without naming a device and for scoring password sign-ins.
+fn device_info_from_request(req: &HttpRequest) -> DeviceInfo {
+    let header = |name: &str| {
+        req.headers()
+            .get(name)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.trim().trim_matches('"').to_string())
+            .filter(|v| !v.is_empty())
+    };
+    let platform = header("Sec-CH-UA-Platform").unwrap_or_else(|| "unknown".to_string());
+    let device_type = match header("Sec-CH-UA-Mobile").as_deref() {
+        Some("?1") => DeviceType::Mobile,
+        Some("?0") => DeviceType::Desktop,
+        _ => DeviceType::Unknown,
+    };
+
+    DeviceInfo {
+        user_agent: header("User-Agent").unwrap_or_default(),
+        os: platform.clone(),
+        platform,
+        browser: None,
+        screen_resolution: None,
+        timezone: None,
+        language: header("Accept-Language"),
+        ip_address: req
+            .connection_info()
+            .realip_remote_addr()
+            .unwrap_or("unknown")
+            .to_string(),
+        device_type,
+    }
+}
+
+/// POST /api/auth/passkeys/register/options
+/// Options to create a passkey (requires authentication)
+pub async fn passkey_registration_options(
+    passkeys: web::Data<Arc<PasskeyService>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let options = passkeys.registration_options(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(options))
+}
+
+/// POST /api/auth/passkeys/register
+/// Register a passkey on the caller's device (requires authentication)
+pub async fn register_passkey(
+    passkeys: web::Data<Arc<PasskeyService>>,
+    req: HttpRequest,
+    request: web::Json<RegisterPasskeyRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let passkey = passkeys
+        .register(
+            user_id,
+            device_info_from_request(&req),
+            request.into_inner(),
+        )
+        .await?;
+
+    Ok(HttpResponse::Created().json(passkey))
+}
+
+/// POST /api/auth/passkeys/login/options
+/// Options to sign in with a passkey
+pub async fn passkey_login_options(
+    passkeys: web::Data<Arc<PasskeyService>>,
+    request: Option<web::Json<PasskeyLoginOptionsRequest>>,
+) -> Result<impl Responder, ApiError> {
+    let request = request.map(|r| r.into_inner()).unwrap_or_default();
+
+    let options = passkeys.login_options(request.email.as_deref()).await?;
+
+    Ok(HttpResponse::Ok().json(options))
+}
+
+/// POST /api/auth/passkeys/login
+/// Sign in with a passkey; the session is bound to the passkey's device
+pub async fn passkey_login(
+    auth_service: web::Data<AuthService>,
+    passkeys: web::Data<Arc<PasskeyService>>,
+    req: HttpRequest,
+    request: web::Json<PasskeyAssertionRequest>,
+) -> Result<impl Responder, ApiError> {
+    let login = passkeys.authenticate(request.into_inner()).await?;
+    let response = auth_service
+        .passkey_login(login.user_id, login.device_id, session_client(&req))
+        .await?;
+
+    Ok(HttpResponse::Ok().json(response))
+}
+
+/// POST /api/auth/passkeys/step-up/options
+/// Options to confirm a high-risk action with a passkey (requires
+/// authentication)
+pub async fn passkey_step_up_options(
+    passkeys: web::Data<Arc<PasskeyService>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let options = passkeys.step_up_options(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(options))
+}
+
+/// POST /api/auth/passkeys/step-up
+/// Exchange a passkey assertion for a single-use step-up token (requires
+/// authentication)
+pub async fn passkey_step_up(
+    passkeys: web::Data<Arc<PasskeyService>>,
+    req: HttpRequest,
+    request: web::Json<PasskeyAssertionRequest>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let token = passkeys.step_up(user_id, request.into_inner()).await?;
+
+    Ok(HttpResponse::Ok().json(token))
+}
+
+/// GET /api/auth/passkeys
+/// List the caller's passkeys (requires authentication)
+pub async fn list_passkeys(
+    passkeys: web::Data<Arc<PasskeyService>>,
+    req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let list = passkeys.list(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(list))
+}
+
+/// DELETE /api/auth/passkeys/{passkey_id}
+/// Remove one of the caller's passkeys (requires authentication)
+pub async fn delete_passkey(
+    passkeys: web::Data<Arc<PasskeyService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    passkeys.remove(user_id, path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/auth/admin/users/{user_id}/sessions
+/// List a user's active sessions (admin only)
+pub async fn admin_get_user_sessions(
+    auth_service: web::Data<AuthService>,
+    devices: web::Data<Arc<DeviceService>>,
+    _admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = path.into_inner();
+    let mut sessions = auth_service.get_sessions(user_id, None).await?;
+    attach_devices(&devices, user_id, &mut sessions).await?;
+    let total = sessions.len();
+
+    Ok(HttpResponse::Ok().json(SessionsResponse { sessions, total }))
+}
+
+/// POST /api/auth/admin/users/{user_id}/revoke-sessions
+/// Revoke all sessions of a user (admin only)
+pub async fn admin_revoke_user_sessions(
+    auth_service: web::Data<AuthService>,
+    _admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, ApiError> {
+    let user_id = path.into_inner();
+
+    let count = auth_service.revoke_all_sessions(user_id).await?;
+
+    info!(user_id = %user_id, count = count, "Sessions revoked by admin");
+
+    Ok(HttpResponse::Ok().json(serde_json::json!({
+        "message": format!("{} session(s) revoked successfully", count),
+        "count": count
+    })))
+}
+
+/// DELETE /api/auth/admin/sessions/{session_id}
+/// Revoke a single session (admin only)
+pub async fn admin_revoke_session(
+    auth_service: web::Data<AuthService>,
+    _admin: RequireRole<Admin>,
+    path: web::Path<String>,
+) -> Result<impl Responder, ApiError> {
+    let session_id = path.into_inner();
+
+    auth_service.revoke_session(&session_id).await?;
+
+    info!(session_id = %session_id, "Session revoked by admin");
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/auth/admin/security-policies
+/// List the device security policies (admin only)
+pub async fn list_security_policies(
+    policies: web::Data<Arc<DeviceSecurityPolicies>>,
+    _admin: RequireRole<Admin>,
+) -> Result<impl Responder, ApiError> {
+    Ok(HttpResponse::Ok().json(policies.list_policies().await?))
+}
+
+/// POST /api/auth/admin/security-policies
+/// Create a device security policy (admin only)
+pub async fn create_security_policy(
+    policies: web::Data<Arc<DeviceSecurityPolicies>>,
+    admin: RequireRole<Admin>,
+    request: web::Json<SecurityPolicyRequest>,
+) -> Result<impl Responder, ApiError> {
+    let policy = policies
+        .create_policy(admin.user_id, request.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Created().json(policy))
+}
+
+/// PUT /api/auth/admin/security-policies/{policy_id}
+/// Replace a device security policy (admin only)
+pub async fn update_security_policy(
+    policies: web::Data<Arc<DeviceSecurityPolicies>>,
+    _admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    request: web::Json<SecurityPolicyRequest>,
+) -> Result<impl Responder, ApiError> {
+    let policy = policies
+        .update_policy(path.into_inner(), request.into_inner())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(policy))
+}
+
+/// DELETE /api/auth/admin/security-policies/{policy_id}
+/// Delete a device security policy (admin only)
+pub async fn delete_security_policy(
+    policies: web::Data<Arc<DeviceSecurityPolicies>>,
+    _admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+) -> Result<impl Responder, ApiError> {
+    policies.delete_policy(path.into_inner()).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/auth/analytics
+/// Get token analytics (admin only)
+pub async fn get_analytics(
+    auth_service: web::Data<AuthService>,
+    _admin: RequireRole<Admin>,
+) -> Result<impl Responder, ApiError> {
     Ok(HttpResponse::Ok().json(serde_json::json!({
         "total_generated": 0,
         "total_validated": 0,
@@ -216,10 +1116,94 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             .route("/login", web::post().to(login))
             .route("/refresh", web::post().to(refresh_token))
             .route("/logout", web::post().to(logout))
+            .route("/sep10", web::get().to(sep10_challenge))
+            .route("/sep10", web::post().to(sep10_token))
+            .route("/oauth/providers", web::get().to(oauth_providers))
+            .route("/oauth/identities", web::get().to(list_linked_identities))
+            .route(
+                "/oauth/identities/{provider}",
+                web::delete().to(unlink_identity),
+            )
+            .route("/oauth/{provider}", web::get().to(oauth_authorize))
+            .route("/oauth/{provider}/callback", web::post().to(oauth_callback))
             .route("/me", web::get().to(get_current_user))
             .route("/change-password", web::post().to(change_password))
+            .route(
+                "/verify-email/send",
+                web::post().to(send_verification_email),
+            )
+            .route("/verify-email", web::post().to(verify_email))
+            .route(
+                "/reset-password/request",
+                web::post().to(request_password_reset),
+            )
+            .route("/reset-password", web::post().to(reset_password))
+            .route("/phone", web::get().to(get_phone_status))
+            .route("/phone/send", web::post().to(send_phone_code))
+            .route("/phone/verify", web::post().to(verify_phone))
             .route("/revoke-sessions", web::post().to(revoke_all_sessions))
             .route("/sessions", web::get().to(get_sessions))
+            .route(
+                "/sessions/revoke-others",
+                web::post().to(revoke_other_sessions),
+            )
+            .route("/sessions/{session_id}", web::delete().to(revoke_session))
+            .route("/devices", web::get().to(list_devices))
+            .route("/devices/{device_id}", web::patch().to(update_device))
+            .route("/devices/{device_id}", web::delete().to(delete_device))
+            .route("/2fa", web::get().to(get_two_factor_status))
+            .route("/2fa/setup", web::post().to(setup_two_factor))
+            .route("/2fa/enable", web::post().to(enable_two_factor))
+            .route("/2fa/disable", web::post().to(disable_two_factor))
+            .route(
+                "/2fa/recovery-codes",
+                web::post().to(regenerate_recovery_codes),
+            )
+            .route("/passkeys", web::get().to(list_passkeys))
+            .route(
+                "/passkeys/register/options",
+                web::post().to(passkey_registration_options),
+            )
+            .route("/passkeys/register", web::post().to(register_passkey))
+            .route(
+                "/passkeys/login/options",
+                web::post().to(passkey_login_options),
+            )
+            .route("/passkeys/login", web::post().to(passkey_login))
+            .route(
+                "/passkeys/step-up/options",
+                web::post().to(passkey_step_up_options),
+            )
+            .route("/passkeys/step-up", web::post().to(passkey_step_up))
+            .route("/passkeys/{passkey_id}", web::delete().to(delete_passkey))
+            .route(
+                "/admin/users/{user_id}/sessions",
+                web::get().to(admin_get_user_sessions),
+            )
+            .route(
+                "/admin/users/{user_id}/revoke-sessions",
+                web::post().to(admin_revoke_user_sessions),
+            )
+            .route(
+                "/admin/sessions/{session_id}",
+                web::delete().to(admin_revoke_session),
+            )
+            .route(
+                "/admin/security-policies",
+                web::get().to(list_security_policies),
+            )
+            .route(
+                "/admin/security-policies",
+                web::post().to(create_security_policy),
+            )
+            .route(
+                "/admin/security-policies/{policy_id}",
+                web::put().to(update_security_policy),
+            )
+            .route(
+                "/admin/security-policies/{policy_id}",
+                web::delete().to(delete_security_policy),
+            )
             .route("/analytics", web::get().to(get_analytics)),
     );
 }
@@ -242,4 +1226,87 @@ mod tests {
         assert_eq!(req.old_password, "old123");
         assert_eq!(req.new_password, "new456");
     }
+
+    #[test]
+    fn test_sep10_token_request_totp_code_is_optional() {
+        let req: Sep10TokenRequest = serde_json::from_str(r#"{"transaction":"AAAA"}"#).unwrap();
+        assert_eq!(req.totp_code, None);
+
+        let req: Sep10TokenRequest =
+            serde_json::from_str(r#"{"transaction":"AAAA","totp_code":"123456"}"#).unwrap();
+        assert_eq!(req.totp_code.as_deref(), Some("123456"));
+    }
+
+    #[test]
+    fn test_sep10_challenge_query() {
+        let query =
+            web::Query::<Sep10ChallengeQuery>::from_query("account=GABC&home_domain=arenax.gg")
+                .unwrap();
+        assert_eq!(query.account, "GABC");
+        assert_eq!(query.home_domain.as_deref(), Some("arenax.gg"));
+    }
+
+    #[test]
+    fn test_oauth_callback_request() {
+        let req: OAuthCallbackRequest =
+            serde_json::from_str(r#"{"code":"abc","state":"xyz"}"#).unwrap();
+        assert_eq!(req.code, "abc");
+        assert_eq!(req.state, "xyz");
+        assert_eq!(req.totp_code, None);
+    }
+
+    #[test]
+    fn test_device_info_from_request() {
+        let req = actix_web::test::TestRequest::default()
+            .insert_header(("User-Agent", "Mozilla/5.0"))
+            .insert_header(("Sec-CH-UA-Platform", "\"Android\""))
+            .insert_header(("Sec-CH-UA-Mobile", "?1"))
+            .peer_addr("203.0.113.7:4000".parse().unwrap())
+            .to_http_request();
+
+        let info = device_info_from_request(&req);
+        assert_eq!(info.user_agent, "Mozilla/5.0");
+        assert_eq!(info.platform, "Android");
+        assert!(matches!(info.device_type, DeviceType::Mobile));
+        assert_eq!(info.ip_address, "203.0.113.7");
+    }
+
+    #[test]
+    fn test_session_client() {
+        let req = actix_web::test::TestRequest::default()
+            .insert_header(("CF-IPCountry", "ng"))
+            .peer_addr("203.0.113.7:4000".parse().unwrap())
+            .to_http_request();
+
+        let client = session_client(&req);
+        assert_eq!(client.ip_address.as_deref(), Some("203.0.113.7"));
+        assert_eq!(client.location.as_deref(), Some("NG"));
+    }
+
+    #[test]
+    fn test_update_device_request() {
+        let req: UpdateDeviceRequest = serde_json::from_str(r#"{"name":"Work laptop"}"#).unwrap();
+        assert_eq!(req.name.as_deref(), Some("Work laptop"));
+        assert_eq!(req.trusted, None);
+    }
+
+    #[test]
+    fn test_reset_password_request() {
+        let json = r#"{"token":"abc.def","new_password":"hunter22"}"#;
+        let req: ResetPasswordRequest = serde_json::from_str(json).unwrap();
+        assert_eq!(req.token, "abc.def");
+        assert_eq!(req.new_password, "hunter22");
+    }
+
+    #[test]
+    fn test_send_phone_code_request() {
+        let req: SendPhoneCodeRequest =
+            serde_json::from_str(r#"{"phone_number":"+2348012345678"}"#).unwrap();
+        assert_eq!(req.channel, SmsChannel::Sms);
+
+        let req: SendPhoneCodeRequest =
+            serde_json::from_str(r#"{"phone_number":"+2348012345678","channel":"whatsapp"}"#)
+                .unwrap();
+        assert_eq!(req.channel, SmsChannel::WhatsApp);
+    }
 }
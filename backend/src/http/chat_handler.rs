@@ -0,0 +1,200 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::{ChatChannel, MuteChatUserRequest, SendChatMessageRequest};
+use crate::service::chat::{ChatActor, ChatService};
+
+#[derive(Debug, Deserialize)]
+pub struct HistoryQuery {
+    /// Message ID from the previous page's `next_before`.
+    pub before: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct MutesQuery {
+    pub user_id: Option<Uuid>,
+}
+
+fn actor(req: &HttpRequest) -> Result<ChatActor, ApiError> {
+    let claims = req
+        .claims()
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| ApiError::unauthorized("Invalid token subject"))?;
+    let has_role = |role: &str| claims.roles.iter().any(|r| r == role);
+    Ok(ChatActor {
+        user_id,
+        is_moderator: has_role("moderator") || has_role("admin"),
+    })
+}
+
+async fn history(
+    chat: &ChatService,
+    req: &HttpRequest,
+    channel: ChatChannel,
+    query: HistoryQuery,
+) -> Result<HttpResponse, ApiError> {
+    let history = chat
+        .history(actor(req)?, channel, query.before, query.limit)
+        .await?;
+    Ok(HttpResponse::Ok().json(history))
+}
+
+async fn send(
+    chat: &ChatService,
+    req: &HttpRequest,
+    channel: ChatChannel,
+    body: SendChatMessageRequest,
+) -> Result<HttpResponse, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let message = chat.send(actor(req)?, channel, &body.body).await?;
+    Ok(HttpResponse::Created().json(message))
+}
+
+/// GET /api/chat/matches/{id}/messages
+pub async fn match_history(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    query: web::Query<HistoryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let channel = ChatChannel::Match(path.into_inner());
+    history(&chat, &req, channel, query.into_inner()).await
+}
+
+/// POST /api/chat/matches/{id}/messages
+pub async fn send_match_message(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<SendChatMessageRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let channel = ChatChannel::Match(path.into_inner());
+    send(&chat, &req, channel, body.into_inner()).await
+}
+
+/// GET /api/chat/tournaments/{id}/messages
+pub async fn tournament_history(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    query: web::Query<HistoryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let channel = ChatChannel::Tournament(path.into_inner());
+    history(&chat, &req, channel, query.into_inner()).await
+}
+
+/// POST /api/chat/tournaments/{id}/messages
+pub async fn send_tournament_message(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<SendChatMessageRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let channel = ChatChannel::Tournament(path.into_inner());
+    send(&chat, &req, channel, body.into_inner()).await
+}
+
+/// GET /api/chat/direct/{user_id}/messages
+///
+/// The caller's conversation with `user_id`.
+pub async fn direct_history(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    query: web::Query<HistoryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let channel = ChatChannel::direct(user_id, path.into_inner());
+    history(&chat, &req, channel, query.into_inner()).await
+}
+
+/// POST /api/chat/direct/{user_id}/messages
+pub async fn send_direct_message(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<SendChatMessageRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let channel = ChatChannel::direct(user_id, path.into_inner());
+    send(&chat, &req, channel, body.into_inner()).await
+}
+
+/// DELETE /api/chat/messages/{id}
+///
+/// Senders delete their own messages; moderators delete any.
+pub async fn delete_message(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    chat.delete_message(actor(&req)?, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/chat/mutes
+///
+/// Mutes in force, optionally of one user. Moderators only.
+pub async fn list_mutes(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    query: web::Query<MutesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let mutes = chat.mutes(actor(&req)?, query.user_id).await?;
+    Ok(HttpResponse::Ok().json(mutes))
+}
+
+/// POST /api/chat/mutes
+pub async fn mute_user(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    body: web::Json<MuteChatUserRequest>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let mute = chat.mute(actor(&req)?, &body).await?;
+    Ok(HttpResponse::Created().json(mute))
+}
+
+/// DELETE /api/chat/mutes/{id}
+pub async fn lift_mute(
+    chat: web::Data<Arc<ChatService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    chat.lift_mute(actor(&req)?, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/chat")
+            .route("/matches/{id}/messages", web::get().to(match_history))
+            .route("/matches/{id}/messages", web::post().to(send_match_message))
+            .route(
+                "/tournaments/{id}/messages",
+                web::get().to(tournament_history),
+            )
+            .route(
+                "/tournaments/{id}/messages",
+                web::post().to(send_tournament_message),
+            )
+            .route("/direct/{user_id}/messages", web::get().to(direct_history))
+            .route(
+                "/direct/{user_id}/messages",
+                web::post().to(send_direct_message),
+            )
+            .route("/messages/{id}", web::delete().to(delete_message))
+            .route("/mutes", web::get().to(list_mutes))
+            .route("/mutes", web::post().to(mute_user))
+            .route("/mutes/{id}", web::delete().to(lift_mute)),
+    );
+}
@@ -0,0 +1,172 @@
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::{
+    AssignDisputeRequest, AttachEvidenceRequest, AttachMediaRequest, DisputeCaseStatus,
+    DisputeDecisionRequest, OpenDisputeRequest,
+};
+use crate::service::dispute_service::{DisputeActor, DisputeService};
+use crate::service::match_result_service::{MAX_EVIDENCE_FILES, MAX_REPLAY_BYTES};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Evidence is base64-encoded inside the JSON body; leave room for every file
+/// at the replay limit.
+const DISPUTE_BODY_LIMIT: usize = MAX_EVIDENCE_FILES * MAX_REPLAY_BYTES * 4 / 3 + 64 * 1024;
+
+#[derive(Debug, Deserialize)]
+pub struct QueueQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AssignedQuery {
+    pub status: Option<DisputeCaseStatus>,
+}
+
+fn actor(http_req: &HttpRequest) -> Result<DisputeActor, ApiError> {
+    let claims = http_req
+        .claims()
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| ApiError::unauthorized("Invalid token subject"))?;
+    let has_role = |role: &str| claims.roles.iter().any(|r| r == role);
+    Ok(DisputeActor {
+        user_id,
+        is_referee: has_role("referee"),
+        is_admin: has_role("admin"),
+    })
+}
+
+// =============================================================================
+// PLAYER ENDPOINTS
+// =============================================================================
+
+/// POST /api/disputes
+pub async fn open_dispute(
+    svc: web::Data<Arc<DisputeService>>,
+    body: web::Json<OpenDisputeRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let case = svc.open(actor(&http_req)?, body.into_inner()).await?;
+    Ok(HttpResponse::Created().json(case))
+}
+
+/// GET /api/disputes/{id}
+///
+/// The case with its evidence and full history.
+pub async fn get_dispute(
+    svc: web::Data<Arc<DisputeService>>,
+    path: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let case = svc.get(actor(&http_req)?, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(case))
+}
+
+/// POST /api/disputes/{id}/evidence
+pub async fn attach_evidence(
+    svc: web::Data<Arc<DisputeService>>,
+    path: web::Path<Uuid>,
+    body: web::Json<AttachEvidenceRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let evidence = svc
+        .attach_evidence(actor(&http_req)?, path.into_inner(), &body.evidence)
+        .await?;
+    Ok(HttpResponse::Created().json(evidence))
+}
+
+/// POST /api/disputes/{id}/media
+///
+/// Attach verified uploads from `/api/media` as evidence.
+pub async fn attach_media(
+    svc: web::Data<Arc<DisputeService>>,
+    path: web::Path<Uuid>,
+    body: web::Json<AttachMediaRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let evidence = svc
+        .attach_media(actor(&http_req)?, path.into_inner(), &body.media_ids)
+        .await?;
+    Ok(HttpResponse::Created().json(evidence))
+}
+
+// =============================================================================
+// REFEREE CONSOLE
+// =============================================================================
+
+/// GET /api/disputes/queue
+///
+/// Open cases waiting for a referee.
+pub async fn get_queue(
+    svc: web::Data<Arc<DisputeService>>,
+    query: web::Query<QueueQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let cases = svc.queue(actor(&http_req)?, limit, offset).await?;
+    Ok(HttpResponse::Ok().json(cases))
+}
+
+/// GET /api/disputes/assigned
+///
+/// Cases assigned to the calling referee.
+pub async fn get_assigned(
+    svc: web::Data<Arc<DisputeService>>,
+    query: web::Query<AssignedQuery>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let cases = svc.assigned(actor(&http_req)?, query.status).await?;
+    Ok(HttpResponse::Ok().json(cases))
+}
+
+/// POST /api/disputes/{id}/assign
+pub async fn assign_dispute(
+    svc: web::Data<Arc<DisputeService>>,
+    path: web::Path<Uuid>,
+    body: web::Json<AssignDisputeRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let case = svc
+        .assign(actor(&http_req)?, path.into_inner(), body.referee_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(case))
+}
+
+/// POST /api/disputes/{id}/decision
+///
+/// Records the decision; the relayer executes it on the dispute resolution
+/// contract in the background.
+pub async fn decide_dispute(
+    svc: web::Data<Arc<DisputeService>>,
+    path: web::Path<Uuid>,
+    body: web::Json<DisputeDecisionRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let case = svc
+        .decide(actor(&http_req)?, path.into_inner(), body.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(case))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/disputes")
+            .app_data(web::JsonConfig::default().limit(DISPUTE_BODY_LIMIT))
+            .route("", web::post().to(open_dispute))
+            .route("/queue", web::get().to(get_queue))
+            .route("/assigned", web::get().to(get_assigned))
+            .route("/{id}", web::get().to(get_dispute))
+            .route("/{id}/evidence", web::post().to(attach_evidence))
+            .route("/{id}/media", web::post().to(attach_media))
+            .route("/{id}/assign", web::post().to(assign_dispute))
+            .route("/{id}/decision", web::post().to(decide_dispute)),
+    );
+}
@@ -0,0 +1,136 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::AddFriendRequest;
+use crate::service::friend_service::FriendService;
+
+/// GET /api/friends
+///
+/// The caller's friends with their presence, online friends first.
+pub async fn list_friends(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    Ok(HttpResponse::Ok().json(friends.friends(user_id).await?))
+}
+
+/// DELETE /api/friends/{user_id}
+pub async fn remove_friend(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    friends.remove_friend(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /api/friends/requests
+pub async fn send_request(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+    body: web::Json<AddFriendRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let request = friends.send_request(user_id, body.friend_id).await?;
+    Ok(HttpResponse::Created().json(request))
+}
+
+/// GET /api/friends/requests
+///
+/// Pending requests sent to and by the caller.
+pub async fn list_requests(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    Ok(HttpResponse::Ok().json(friends.requests(user_id).await?))
+}
+
+/// POST /api/friends/requests/{id}/accept
+pub async fn accept_request(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let friend = friends.accept_request(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(friend))
+}
+
+/// POST /api/friends/requests/{id}/decline
+pub async fn decline_request(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    friends.decline_request(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// DELETE /api/friends/requests/{id}
+///
+/// Withdraw a request the caller sent.
+pub async fn cancel_request(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    friends.cancel_request(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/friends/blocks
+pub async fn list_blocked(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    Ok(HttpResponse::Ok().json(friends.blocked(user_id).await?))
+}
+
+/// PUT /api/friends/blocks/{user_id}
+///
+/// Block a user, ending any friendship. Matchmaking never pairs the two.
+pub async fn block_user(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    friends.block(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// DELETE /api/friends/blocks/{user_id}
+pub async fn unblock_user(
+    friends: web::Data<Arc<FriendService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    friends.unblock(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/friends")
+            .route("", web::get().to(list_friends))
+            .route("/requests", web::get().to(list_requests))
+            .route("/requests", web::post().to(send_request))
+            .route("/requests/{id}", web::delete().to(cancel_request))
+            .route("/requests/{id}/accept", web::post().to(accept_request))
+            .route("/requests/{id}/decline", web::post().to(decline_request))
+            .route("/blocks", web::get().to(list_blocked))
+            .route("/blocks/{user_id}", web::put().to(block_user))
+            .route("/blocks/{user_id}", web::delete().to(unblock_user))
+            .route("/{user_id}", web::delete().to(remove_friend)),
+    );
+}
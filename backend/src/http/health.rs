@@ -1,17 +1,94 @@
 use crate::api_error::ApiError;
-use crate::db::DbPool;
-use actix_web::{web, HttpResponse, Result};
+use crate::auth::rbac::{Admin, RequireRole};
+use crate::middleware::rate_limit::RateLimitMetrics;
+use crate::service::health_service::{DependencyStatus, HealthService, ReadinessReport};
+use actix_web::{http::header, web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
 
-pub async fn health_check(db_pool: web::Data<DbPool>) -> Result<HttpResponse, ApiError> {
-    // Check database
-    crate::db::health_check(&db_pool).await?;
+/// Prometheus text exposition format.
+const METRICS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
 
-    // TODO: Check Redis
-    // For now, assume Redis is ok
+/// GET /api/health
+pub async fn health_check(health: web::Data<Arc<HealthService>>) -> Result<HttpResponse, ApiError> {
+    let report = health.readiness().await;
+    let state = |name: &str| match report.check(name).map(|check| check.status) {
+        Some(DependencyStatus::Up) => "ok",
+        _ => "down",
+    };
+
+    let body = serde_json::json!({
+        "status": if report.ready { "healthy" } else { "unhealthy" },
+        "database": state("postgres"),
+        "redis": state("redis"),
+    });
+    Ok(readiness_status(&report).json(body))
+}
+
+/// GET /health/live
+///
+/// The process is up and serving requests; dependencies are not checked.
+pub async fn liveness() -> HttpResponse {
+    HttpResponse::Ok().json(serde_json::json!({ "status": "alive" }))
+}
+
+/// GET /health/ready
+///
+/// 200 when Postgres and Redis answer, 503 otherwise, with the state and
+/// latency of every dependency.
+pub async fn readiness(health: web::Data<Arc<HealthService>>) -> HttpResponse {
+    let report = health.readiness().await;
+    readiness_status(&report).json(report)
+}
+
+/// GET /metrics
+///
+/// Prometheus scrape endpoint; needs the bearer token when `METRICS_TOKEN`
+/// is set.
+pub async fn metrics(
+    req: HttpRequest,
+    health: web::Data<Arc<HealthService>>,
+) -> Result<HttpResponse, ApiError> {
+    let authorization = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    if !health.authorize_scrape(authorization) {
+        return Err(ApiError::unauthorized("Invalid metrics token"));
+    }
+
+    Ok(HttpResponse::Ok()
+        .content_type(METRICS_CONTENT_TYPE)
+        .body(health.render_metrics().await))
+}
+
+fn readiness_status(report: &ReadinessReport) -> actix_web::HttpResponseBuilder {
+    if report.ready {
+        HttpResponse::Ok()
+    } else {
+        HttpResponse::ServiceUnavailable()
+    }
+}
+
+/// GET /api/rate-limits
+///
+/// Requests rejected by the rate limiter, per bucket (admin only).
+pub async fn rate_limit_metrics(
+    metrics: web::Data<RateLimitMetrics>,
+    _admin: RequireRole<Admin>,
+) -> Result<HttpResponse, ApiError> {
+    let limited = metrics.limited().await?;
+    let total: u64 = limited.values().sum();
 
     Ok(HttpResponse::Ok().json(serde_json::json!({
-        "status": "healthy",
-        "database": "ok",
-        "redis": "ok"
+        "limited": limited,
+        "total_limited": total,
     })))
 }
+
+/// Probes and the scrape endpoint, served outside `/api` where load balancers
+/// and Prometheus expect them.
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/health/live", web::get().to(liveness))
+        .route("/health/ready", web::get().to(readiness))
+        .route("/metrics", web::get().to(metrics));
+}
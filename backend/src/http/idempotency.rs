@@ -2,7 +2,9 @@ use crate::api_error::ApiError;
 use crate::auth::Claims;
 use crate::db::DbPool;
 use crate::models::idempotency::*;
-use crate::service::idempotency_service::{IdempotencyService, IdempotencyKeyResponse};
+use crate::service::idempotency_service::{
+    IdempotencyKeyRequest, IdempotencyKeyResponse, IdempotencyService,
+};
 use actix_web::{web, HttpResponse, Result};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -65,7 +67,7 @@ pub async fn get_user_keys(
     let offset = query.offset.unwrap_or(0);
     
     let keys = idempotency_service
-        .get_user_keys(claims.user_id, limit, offset)
+        .get_user_keys(claims.user_id()?, limit, offset)
         .await?;
     
     Ok(HttpResponse::Ok().json(keys))
@@ -154,16 +156,14 @@ pub async fn validate_key(
         }))),
         Err(e) => Ok(HttpResponse::BadRequest().json(serde_json::json!({
             "valid": false,
-            "error": e.message
+            "error": e.to_string()
         }))),
     }
 }
 
 /// Helper function to check if user is admin
 fn is_admin(claims: &Claims) -> bool {
-    // Implement based on your auth system
-    // This could check user roles, permissions, etc.
-    claims.user_id.to_string().starts_with("admin-") // Example implementation
+    claims.roles.iter().any(|role| role == "admin")
 }
 
 #[derive(Debug, Deserialize)]
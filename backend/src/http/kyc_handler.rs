@@ -0,0 +1,147 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::auth::rbac::{Admin, RequireRole};
+use crate::models::{KycStatus, ReviewKycRequest, StartKycRequest};
+use crate::service::kyc::KycService;
+
+#[derive(Debug, Deserialize)]
+pub struct KycCaseQuery {
+    /// Defaults to cases waiting for a decision.
+    pub status: Option<KycStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+fn kyc_enabled(kyc: &Option<Arc<KycService>>) -> Result<&Arc<KycService>, ApiError> {
+    kyc.as_ref()
+        .ok_or_else(|| ApiError::bad_request("Identity verification is not available"))
+}
+
+fn header<'a>(req: &'a HttpRequest, name: &str) -> Option<&'a str> {
+    req.headers().get(name).and_then(|v| v.to_str().ok())
+}
+
+/// GET /api/kyc
+///
+/// The caller's verification level, case status and daily withdrawal limit.
+pub async fn get_kyc_status(
+    kyc: web::Data<Option<Arc<KycService>>>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let status = kyc_enabled(&kyc)?.status(user_id).await?;
+    Ok(HttpResponse::Ok().json(status))
+}
+
+/// POST /api/kyc
+///
+/// Start verifying to a higher level. With a provider, the response carries
+/// the access token for its web SDK; otherwise the submitted details go to
+/// admin review.
+pub async fn start_kyc(
+    kyc: web::Data<Option<Arc<KycService>>>,
+    req: HttpRequest,
+    body: web::Json<StartKycRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let status = kyc_enabled(&kyc)?.start(user_id, &body).await?;
+    Ok(HttpResponse::Created().json(status))
+}
+
+/// POST /api/kyc/webhooks/sumsub
+///
+/// Sumsub's applicant webhooks. Unauthenticated; the body is signed.
+pub async fn sumsub_webhook(
+    kyc: web::Data<Option<Arc<KycService>>>,
+    req: HttpRequest,
+    body: web::Bytes,
+) -> Result<HttpResponse, ApiError> {
+    kyc_enabled(&kyc)?
+        .handle_sumsub_webhook(
+            &body,
+            header(&req, "X-Payload-Digest"),
+            header(&req, "X-Payload-Digest-Alg"),
+        )
+        .await
+        .map_err(|e| {
+            tracing::warn!(error = %e, "Sumsub webhook failed");
+            ApiError::from(e)
+        })?;
+    Ok(HttpResponse::Ok().finish())
+}
+
+/// GET /api/kyc/admin/cases
+pub async fn list_kyc_cases(
+    kyc: web::Data<Option<Arc<KycService>>>,
+    _admin: RequireRole<Admin>,
+    query: web::Query<KycCaseQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let status = query.status.unwrap_or(KycStatus::InReview);
+
+    let cases = kyc_enabled(&kyc)?.list_cases(status, limit, offset).await?;
+    Ok(HttpResponse::Ok().json(cases))
+}
+
+/// GET /api/kyc/admin/cases/{user_id}
+///
+/// The case with the user's decrypted personal details and its history.
+pub async fn get_kyc_case(
+    kyc: web::Data<Option<Arc<KycService>>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = path.into_inner();
+    let case = kyc_enabled(&kyc)?.case(user_id).await?;
+
+    tracing::info!(admin_id = %admin.user_id, user_id = %user_id, "KYC case viewed");
+    Ok(HttpResponse::Ok().json(case))
+}
+
+/// POST /api/kyc/admin/cases/{user_id}/review
+///
+/// Approve the case at a level or reject it with a reason.
+pub async fn review_kyc_case(
+    kyc: web::Data<Option<Arc<KycService>>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<ReviewKycRequest>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let profile = kyc_enabled(&kyc)?
+        .decide(admin.user_id, path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Ok().json(profile))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/kyc")
+            .route("", web::get().to(get_kyc_status))
+            .route("", web::post().to(start_kyc))
+            .route("/webhooks/sumsub", web::post().to(sumsub_webhook))
+            .route("/admin/cases", web::get().to(list_kyc_cases))
+            .route("/admin/cases/{user_id}", web::get().to(get_kyc_case))
+            .route(
+                "/admin/cases/{user_id}/review",
+                web::post().to(review_kyc_case),
+            ),
+    );
+}
@@ -1,19 +1,152 @@
-use actix_web::{web, HttpResponse, Result};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
 use serde::Deserialize;
-use sqlx::PgPool;
+use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::api_error::ApiError;
-use crate::models::{PaginatedResponse, PaginationParams};
+use crate::auth::middleware::ClaimsExt;
+use crate::auth::rbac::{Admin, RequireRole, Treasury};
+use crate::models::{Board, PaginatedResponse, PaginationParams, Season, SeasonStandingsResponse};
 use crate::service::LeaderboardService;
 
+#[derive(Debug, Deserialize)]
+pub struct BoardQuery {
+    /// `current` or a season id such as `2026-q4`; all-time if absent.
+    pub season: Option<String>,
+    pub page: Option<i64>,
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RankAroundQuery {
+    pub season: Option<String>,
+    /// Places shown above and below the player (default 5, at most 25).
+    pub radius: Option<i64>,
+}
+
+fn parse_board(board: &str) -> Result<Board, ApiError> {
+    board.parse().map_err(ApiError::bad_request)
+}
+
+fn parse_season(season: Option<&str>) -> Result<Option<Season>, ApiError> {
+    match season {
+        None => Ok(None),
+        Some("current") => Ok(Some(Season::current())),
+        Some(id) => Season::parse(id)
+            .map(Some)
+            .ok_or_else(|| ApiError::bad_request(format!("Invalid season: {}", id))),
+    }
+}
+
+/// GET /api/leaderboards/{board}
+///
+/// `skill`, `earnings` or `earnings:<game>`, highest score first.
+pub async fn get_board(
+    service: web::Data<Arc<LeaderboardService>>,
+    board: web::Path<String>,
+    query: web::Query<BoardQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let board = parse_board(&board)?;
+    let season = parse_season(query.season.as_deref())?;
+    let pagination = PaginationParams {
+        page: query.page,
+        offset: query.offset,
+        limit: query.limit,
+    };
+    let limit = pagination.resolved_limit();
+
+    let (entries, total) = service
+        .board(&board, season.as_ref(), limit, pagination.sql_offset())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(PaginatedResponse {
+        total,
+        page: pagination.resolved_page(),
+        limit,
+        data: entries,
+    }))
+}
+
+/// GET /api/leaderboards/{board}/me - The players ranked around the caller
+pub async fn get_rank_around_me(
+    req: HttpRequest,
+    service: web::Data<Arc<LeaderboardService>>,
+    board: web::Path<String>,
+    query: web::Query<RankAroundQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let board = parse_board(&board)?;
+    let season = parse_season(query.season.as_deref())?;
+    let radius = query.radius.unwrap_or(5).clamp(0, 25);
+
+    let around = service
+        .rank_around(&board, season.as_ref(), user_id, radius)
+        .await?;
+
+    Ok(HttpResponse::Ok().json(around))
+}
+
+/// GET /api/leaderboards/{board}/seasons/{season_id} - Final standings of an archived season
+pub async fn get_archived_season(
+    service: web::Data<Arc<LeaderboardService>>,
+    path: web::Path<(String, String)>,
+    query: web::Query<PaginationParams>,
+) -> Result<HttpResponse, ApiError> {
+    let (board, season_id) = path.into_inner();
+    let board = parse_board(&board)?;
+    let limit = query.resolved_limit();
+
+    let (season, standings) = service
+        .season_standings(&board, &season_id, limit, query.sql_offset())
+        .await?;
+
+    Ok(HttpResponse::Ok().json(SeasonStandingsResponse {
+        total: season.players as i64,
+        page: query.resolved_page(),
+        limit,
+        data: standings,
+        season,
+    }))
+}
+
+/// GET /api/leaderboards/{board}/seasons/{season_id}/manifest
+///
+/// Winners and weights for the prize contract's `distribute` call.
+pub async fn get_winners_manifest(
+    service: web::Data<Arc<LeaderboardService>>,
+    _treasury: RequireRole<Treasury>,
+    path: web::Path<(String, String)>,
+) -> Result<HttpResponse, ApiError> {
+    let (board, season_id) = path.into_inner();
+    let board = parse_board(&board)?;
+
+    let manifest = service.winners_manifest(&board, &season_id).await?;
+    Ok(HttpResponse::Ok().json(manifest))
+}
+
+/// POST /api/leaderboards/seasons/{season_id}/archive
+///
+/// Archive a finished season now instead of waiting for the rollover job,
+/// e.g. after backfilling its events.
+pub async fn archive_season(
+    service: web::Data<Arc<LeaderboardService>>,
+    _admin: RequireRole<Admin>,
+    season_id: web::Path<String>,
+) -> Result<HttpResponse, ApiError> {
+    let season = parse_season(Some(season_id.as_str()))?
+        .ok_or_else(|| ApiError::bad_request("Season required"))?;
+
+    let archived = service.archive_season(&season).await?;
+    Ok(HttpResponse::Ok().json(archived))
+}
+
 /// GET /api/v1/leaderboards/:category
 pub async fn get_leaderboard(
-    pool: web::Data<PgPool>,
+    service: web::Data<Arc<LeaderboardService>>,
     category: web::Path<String>,
     query: web::Query<PaginationParams>,
 ) -> Result<HttpResponse, ApiError> {
-    let service = LeaderboardService::new(pool.get_ref().clone());
     let limit = query.resolved_limit();
     let offset = query.sql_offset();
 
@@ -31,12 +164,11 @@ pub async fn get_leaderboard(
 
 /// GET /api/v1/leaderboards/:category/season/:season
 pub async fn get_seasonal_leaderboard(
-    pool: web::Data<PgPool>,
+    service: web::Data<Arc<LeaderboardService>>,
     path: web::Path<(String, String)>,
     query: web::Query<PaginationParams>,
 ) -> Result<HttpResponse, ApiError> {
     let (category, season) = path.into_inner();
-    let service = LeaderboardService::new(pool.get_ref().clone());
     let limit = query.resolved_limit();
     let offset = query.sql_offset();
 
@@ -54,11 +186,10 @@ pub async fn get_seasonal_leaderboard(
 
 /// GET /api/v1/leaderboards/:category/player/:player_id
 pub async fn get_player_rank(
-    pool: web::Data<PgPool>,
+    service: web::Data<Arc<LeaderboardService>>,
     path: web::Path<(String, Uuid)>,
 ) -> Result<HttpResponse, ApiError> {
     let (category, player_id) = path.into_inner();
-    let service = LeaderboardService::new(pool.get_ref().clone());
 
     let player_rank = service.get_player_rank(&category, player_id).await?;
 
@@ -67,12 +198,11 @@ pub async fn get_player_rank(
 
 /// GET /api/v1/leaderboards/:category/history/:player_id
 pub async fn get_rank_history(
-    pool: web::Data<PgPool>,
+    service: web::Data<Arc<LeaderboardService>>,
     path: web::Path<(String, Uuid)>,
     query: web::Query<std::collections::HashMap<String, String>>,
 ) -> Result<HttpResponse, ApiError> {
     let (category, player_id) = path.into_inner();
-    let service = LeaderboardService::new(pool.get_ref().clone());
     let days = query
         .get("days")
         .and_then(|d| d.parse::<i64>().ok())
@@ -87,10 +217,9 @@ pub async fn get_rank_history(
 
 /// POST /api/v1/leaderboards/:category/refresh
 pub async fn refresh_leaderboard(
-    pool: web::Data<PgPool>,
+    service: web::Data<Arc<LeaderboardService>>,
     category: web::Path<String>,
 ) -> Result<HttpResponse, ApiError> {
-    let service = LeaderboardService::new(pool.get_ref().clone());
 
     service.refresh_leaderboard(&category).await?;
 
@@ -102,12 +231,28 @@ pub async fn refresh_leaderboard(
 
 /// GET /api/v1/leaderboards/:category/stats
 pub async fn get_leaderboard_stats(
-    pool: web::Data<PgPool>,
+    service: web::Data<Arc<LeaderboardService>>,
     category: web::Path<String>,
 ) -> Result<HttpResponse, ApiError> {
-    let service = LeaderboardService::new(pool.get_ref().clone());
 
     let stats = service.get_leaderboard_stats(&category).await?;
 
     Ok(HttpResponse::Ok().json(stats))
 }
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/leaderboards")
+            .route("/seasons/{season_id}/archive", web::post().to(archive_season))
+            .route("/{board}", web::get().to(get_board))
+            .route("/{board}/me", web::get().to(get_rank_around_me))
+            .route(
+                "/{board}/seasons/{season_id}",
+                web::get().to(get_archived_season),
+            )
+            .route(
+                "/{board}/seasons/{season_id}/manifest",
+                web::get().to(get_winners_manifest),
+            ),
+    );
+}
@@ -0,0 +1,40 @@
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::SubmitMatchResultRequest;
+use crate::service::match_result_service::{
+    MatchResultService, MAX_EVIDENCE_FILES, MAX_REPLAY_BYTES,
+};
+use actix_web::{web, HttpRequest, HttpResponse, Result};
+use std::sync::Arc;
+use uuid::Uuid;
+
+/// Evidence arrives base64-encoded inside the JSON body, which grows it by a
+/// third; leave room for every file at the replay limit.
+const RESULT_BODY_LIMIT: usize = MAX_EVIDENCE_FILES * MAX_REPLAY_BYTES * 4 / 3 + 64 * 1024;
+
+/// Submit the caller's result for a match with screenshot/replay evidence.
+///
+/// Responds with the dual-report status: `awaiting_opponent` until the other
+/// player reports, then `agreed` or `conflict`.
+pub async fn submit_result(
+    results: web::Data<Arc<MatchResultService>>,
+    path: web::Path<Uuid>,
+    request: web::Json<SubmitMatchResultRequest>,
+    http_req: HttpRequest,
+) -> Result<HttpResponse> {
+    let user_id = http_req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let response = results
+        .submit(path.into_inner(), user_id, request.into_inner())
+        .await?;
+    Ok(HttpResponse::Created().json(response))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::resource("/matches/{id}/result")
+            .app_data(web::JsonConfig::default().limit(RESULT_BODY_LIMIT))
+            .route(web::post().to(submit_result)),
+    );
+}
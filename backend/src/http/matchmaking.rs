@@ -2,10 +2,12 @@ use crate::api_error::ApiError;
 use crate::auth::Claims;
 use crate::db::DbPool;
 use crate::models::matchmaker::*;
-use crate::service::matchmaker::{MatchmakerService, EloEngine, MatchmakingConfig};
+use crate::service::matchmaker::{MatchmakerService, EloEngine, MatchmakingConfig, QueueEntry};
+use crate::service::matchmaking::{MatchmakingService, StakeTier};
 use actix_web::{web, HttpResponse, Result};
 use chrono::Utc;
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use uuid::Uuid;
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -43,6 +45,12 @@ pub struct QueueStatusResponse {
     pub wait_time_so_far: Option<i64>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct JoinStakedQueueRequest {
+    pub game: String,
+    pub tier: StakeTier,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MatchmakingStatsResponse {
     pub total_players_in_queue: usize,
@@ -76,7 +84,7 @@ pub async fn join_queue(
     claims: web::ReqData<Claims>,
     request: web::Json<JoinQueueRequest>,
 ) -> Result<HttpResponse> {
-    let user_id = claims.user_id;
+    let user_id = claims.user_id()?;
     let game = request.game.clone();
     let game_mode = request.game_mode.clone();
 
@@ -135,7 +143,7 @@ pub async fn leave_queue(
     claims: web::ReqData<Claims>,
     request: web::Json<LeaveQueueRequest>,
 ) -> Result<HttpResponse> {
-    let user_id = claims.user_id;
+    let user_id = claims.user_id()?;
     let game = request.game.clone();
     let game_mode = request.game_mode.clone();
 
@@ -174,7 +182,7 @@ pub async fn get_queue_status(
     claims: web::ReqData<Claims>,
     path: web::Path<(String, String)>,
 ) -> Result<HttpResponse> {
-    let user_id = claims.user_id;
+    let user_id = claims.user_id()?;
     let (game, game_mode) = path.into_inner();
 
     let queue_entry = matchmaker.is_user_in_queue(user_id, &game, &game_mode).await?;
@@ -262,7 +270,7 @@ pub async fn get_matchmaking_stats(
         FROM matchmaking_queue
         WHERE status = $1
           AND matched_at IS NOT NULL
-          AND created_at >= $2
+          AND joined_at >= $2
         GROUP BY game, game_mode
         "#,
         QueueStatus::Matched as _,
@@ -323,7 +331,7 @@ pub async fn get_elo(
     claims: web::ReqData<Claims>,
     path: web::Path<String>,
 ) -> Result<HttpResponse> {
-    let user_id = claims.user_id;
+    let user_id = claims.user_id()?;
     let game = path.into_inner();
 
     let elo_record = sqlx::query_as!(
@@ -352,7 +360,7 @@ pub async fn get_elo_history(
     claims: web::ReqData<Claims>,
     path: web::Path<(String, i32, i32)>,
 ) -> Result<HttpResponse> {
-    let user_id = claims.user_id;
+    let user_id = claims.user_id()?;
     let (game, page, limit) = path.into_inner();
     let offset = (page - 1) * limit;
 
@@ -366,8 +374,8 @@ pub async fn get_elo_history(
         "#,
         user_id,
         game,
-        limit,
-        offset
+        limit as i64,
+        offset as i64
     )
     .fetch_all(db_pool.as_ref())
     .await
@@ -376,6 +384,40 @@ pub async fn get_elo_history(
     Ok(HttpResponse::Ok().json(history))
 }
 
+/// Join the staked queue for a game at a stake tier
+pub async fn join_staked_queue(
+    matchmaking: web::Data<Arc<MatchmakingService>>,
+    claims: web::ReqData<Claims>,
+    request: web::Json<JoinStakedQueueRequest>,
+) -> Result<HttpResponse> {
+    let ticket = matchmaking
+        .enqueue(claims.user_id()?, &request.game, request.tier)
+        .await?;
+    Ok(HttpResponse::Ok().json(ticket))
+}
+
+/// Leave the staked queue
+pub async fn leave_staked_queue(
+    matchmaking: web::Data<Arc<MatchmakingService>>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    if !matchmaking.leave(claims.user_id()?).await? {
+        return Err(ApiError::not_found("Not in a staked queue").into());
+    }
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// Get the current user's staked queue ticket
+pub async fn get_staked_queue_status(
+    matchmaking: web::Data<Arc<MatchmakingService>>,
+    claims: web::ReqData<Claims>,
+) -> Result<HttpResponse> {
+    match matchmaking.status(claims.user_id()?).await? {
+        Some(ticket) => Ok(HttpResponse::Ok().json(ticket)),
+        None => Err(ApiError::not_found("Not in a staked queue").into()),
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Private helpers
 // ─────────────────────────────────────────────────────────────────────────────
@@ -394,16 +436,14 @@ async fn get_user_elo(db_pool: &DbPool, user_id: Uuid, game: &str) -> Result<Use
 
 async fn create_default_elo(db_pool: &DbPool, user_id: Uuid, game: &str) -> Result<(), ApiError> {
     sqlx::query!(
-        "INSERT INTO user_elo (user_id, game, current_rating, wins, losses, draws, created_at, updated_at) \
-         VALUES ($1, $2, $3, $4, $5, $6, $7, $8) ON CONFLICT (user_id, game) DO NOTHING",
+        "INSERT INTO user_elo (user_id, game, current_rating, wins, losses, draws) \
+         VALUES ($1, $2, $3, $4, $5, $6) ON CONFLICT (user_id, game) DO NOTHING",
         user_id,
         game,
         1200i32,
         0i32,
         0i32,
-        0i32,
-        Utc::now(),
-        Utc::now()
+        0i32
     )
     .execute(db_pool)
     .await
@@ -483,11 +523,11 @@ async fn get_matches_created_last_hour(db_pool: &DbPool) -> Result<i64, ApiError
 async fn get_overall_average_wait_time(db_pool: &DbPool) -> Result<f64, ApiError> {
     let result = sqlx::query!(
         r#"
-        SELECT AVG(EXTRACT(EPOCH FROM (matched_at - joined_at))::INTEGER) as avg_wait_seconds
+        SELECT AVG(EXTRACT(EPOCH FROM (matched_at - joined_at)))::FLOAT8 as avg_wait_seconds
         FROM matchmaking_queue
         WHERE status = $1
           AND matched_at IS NOT NULL
-          AND created_at >= $2
+          AND joined_at >= $2
         "#,
         QueueStatus::Matched as _,
         Utc::now() - chrono::Duration::hours(1)
@@ -0,0 +1,139 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::StartMediaUploadRequest;
+use crate::service::media::{MediaActor, MediaService, MAX_PART_BYTES};
+
+fn actor(req: &HttpRequest) -> Result<MediaActor, ApiError> {
+    let claims = req
+        .claims()
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| ApiError::unauthorized("Invalid token subject"))?;
+    let has_role = |role: &str| claims.roles.iter().any(|r| r == role);
+    Ok(MediaActor {
+        user_id,
+        is_referee: has_role("referee") || has_role("admin"),
+    })
+}
+
+/// POST /api/media/uploads
+///
+/// Start a multipart upload; the response says how many parts to send and
+/// their size.
+pub async fn start_upload(
+    media: web::Data<Arc<MediaService>>,
+    req: HttpRequest,
+    body: web::Json<StartMediaUploadRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let upload = media.start_upload(user_id, &body).await?;
+    Ok(HttpResponse::Created().json(upload))
+}
+
+/// GET /api/media/uploads/{id}
+///
+/// The upload's status and the parts received so far.
+pub async fn get_upload(
+    media: web::Data<Arc<MediaService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let upload = media.get(actor(&req)?, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(upload))
+}
+
+/// PUT /api/media/uploads/{id}/parts/{part_number}
+///
+/// The part's raw bytes.
+pub async fn upload_part(
+    media: web::Data<Arc<MediaService>>,
+    req: HttpRequest,
+    path: web::Path<(Uuid, i32)>,
+    body: web::Bytes,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let (media_id, part_number) = path.into_inner();
+    let part = media
+        .upload_part(user_id, media_id, part_number, body.to_vec())
+        .await?;
+    Ok(HttpResponse::Ok().json(part))
+}
+
+/// POST /api/media/uploads/{id}/complete
+///
+/// Assemble the parts. The upload is `processing` until it has been hashed
+/// and scanned; poll it until it is `ready` or `rejected`.
+pub async fn complete_upload(
+    media: web::Data<Arc<MediaService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let object = media.complete(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::Accepted().json(object))
+}
+
+/// DELETE /api/media/uploads/{id}
+pub async fn abort_upload(
+    media: web::Data<Arc<MediaService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    media.abort(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/media/{id}/download
+///
+/// A signed download URL that expires. Uploaders and referees only.
+pub async fn download_url(
+    media: web::Data<Arc<MediaService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let url = media.download_url(actor(&req)?, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(url))
+}
+
+/// GET /api/media/disputes/{id}/verification
+///
+/// Re-hash a dispute's evidence and match it against the evidence reference
+/// sent on chain, with signed download URLs. Referees only.
+pub async fn verify_dispute_evidence(
+    media: web::Data<Arc<MediaService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let verification = media
+        .verify_dispute(actor(&req)?, path.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(verification))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/media")
+            .app_data(web::PayloadConfig::new(MAX_PART_BYTES as usize))
+            .route("/uploads", web::post().to(start_upload))
+            .route("/uploads/{id}", web::get().to(get_upload))
+            .route("/uploads/{id}", web::delete().to(abort_upload))
+            .route(
+                "/uploads/{id}/parts/{part_number}",
+                web::put().to(upload_part),
+            )
+            .route("/uploads/{id}/complete", web::post().to(complete_upload))
+            .route("/{id}/download", web::get().to(download_url))
+            .route(
+                "/disputes/{id}/verification",
+                web::get().to(verify_dispute_evidence),
+            ),
+    );
+}
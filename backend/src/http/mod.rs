@@ -1,22 +1,38 @@
 pub mod auth_handler;
+pub mod chat_handler;
 pub mod health;
 pub mod idempotency;
 pub mod idempotency_examples;
+pub mod kyc_handler;
 pub mod achievement_handler;
+pub mod admin_handler;
+pub mod anticheat_handler;
+pub mod audit_handler;
+pub mod disputes;
+pub mod friend_handler;
+pub mod payments;
 pub mod leaderboard_handler;
 pub mod match_authority_handler;
+pub mod matches;
 pub mod matchmaking;
+pub mod media_handler;
 #[deprecated(note = "Use realtime::user_ws instead for authenticated WebSocket connections")]
 pub mod match_ws_handler;
 pub mod notification_handler;
+pub mod player_handler;
 pub mod reputation_handler;
+pub mod search_handler;
 pub mod social_handler;
+pub mod spectator_handler;
 pub mod staking_handler;
+pub mod team_handler;
 pub mod analytics_handler;
 pub mod tournament_handler;
+pub mod wallet;
+pub mod webhook_handler;
+pub mod ws;
 pub mod gas_estimation_handler;
 
 // TODO: Add more HTTP modules as implemented:
 // pub mod auth;
-// pub mod matches;
 // pub mod tournaments;
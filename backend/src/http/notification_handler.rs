@@ -1,150 +1,104 @@
 use actix_web::{web, HttpRequest, HttpResponse, Result};
-use chrono::Utc;
-use serde::{Deserialize, Serialize};
-use sqlx::FromRow;
+use serde::Deserialize;
+use std::sync::Arc;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::api_error::ApiError;
 use crate::auth::middleware::ClaimsExt;
-use crate::db::DbPool;
-use crate::models::{ApiResponse, PaginatedResponse, PaginationParams};
-
-#[derive(Debug, FromRow, Serialize)]
-struct NotificationRow {
-    id: Uuid,
-    user_id: Uuid,
-    #[sqlx(rename = "type")]
-    typ: String,
-    title: String,
-    message: String,
-    link: Option<String>,
-    link_label: Option<String>,
-    read: bool,
-    created_at: chrono::DateTime<Utc>,
-}
+use crate::models::{
+    ApiResponse, NotificationCategory, NotificationInbox, PaginatedResponse, PaginationParams,
+    RegisterPushTokenRequest, RemovePushTokenRequest, UpdateNotificationPreferencesRequest,
+};
+use crate::service::notification_service::{NotificationService, RenderedNotification};
 
 #[derive(Debug, Deserialize)]
 #[serde(rename_all = "camelCase")]
-struct CreateNotificationRequest {
+pub struct CreateNotificationRequest {
     #[serde(rename = "type")]
     typ: Option<String>,
+    category: Option<NotificationCategory>,
     title: String,
     message: Option<String>,
     link: Option<String>,
     link_label: Option<String>,
 }
 
-fn notification_to_json(row: NotificationRow) -> serde_json::Value {
-    serde_json::json!({
-        "id": row.id.to_string(),
-        "type": row.typ,
-        "title": row.title,
-        "message": row.message,
-        "link": row.link,
-        "linkLabel": row.link_label,
-        "read": row.read,
-        "createdAt": row.created_at.to_rfc3339()
-    })
+#[derive(Debug, Deserialize)]
+pub struct InboxQuery {
+    pub page: Option<i64>,
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+    /// Only unread notifications.
+    pub unread: Option<bool>,
 }
 
 /// GET /api/notifications - List user notifications (requires auth)
+///
+/// Newest first, with `?unread=true` for unread ones only. The response
+/// carries the number of unread notifications.
 pub async fn get_notifications(
     req: HttpRequest,
-    pool: web::Data<DbPool>,
-    query: web::Query<PaginationParams>,
+    notifications: web::Data<Arc<NotificationService>>,
+    query: web::Query<InboxQuery>,
 ) -> Result<HttpResponse, ApiError> {
     let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
 
-    let limit = query.resolved_limit();
-    let offset = query.sql_offset();
-
-    let rows = sqlx::query_as::<_, NotificationRow>(
-        r#"
-        SELECT id, user_id, type, title, message, link, link_label, read, created_at
-        FROM notifications
-        WHERE user_id = $1
-        ORDER BY created_at DESC
-        LIMIT $2 OFFSET $3
-        "#,
-    )
-    .bind(user_id)
-    .bind(limit)
-    .bind(offset)
-    .fetch_all(pool.as_ref())
-    .await
-    .map_err(ApiError::DatabaseError)?;
-
-    let total: i64 = sqlx::query_scalar(
-        "SELECT COUNT(*) FROM notifications WHERE user_id = $1",
-    )
-    .bind(user_id)
-    .fetch_one(pool.as_ref())
-    .await
-    .map_err(ApiError::DatabaseError)?;
-
-    let data: Vec<serde_json::Value> = rows.into_iter().map(notification_to_json).collect();
-
-    Ok(HttpResponse::Ok().json(PaginatedResponse::new(data, total, &query)))
+    let params = PaginationParams {
+        page: query.page,
+        offset: query.offset,
+        limit: query.limit,
+    };
+    let (data, total, unread_count) = notifications
+        .inbox(
+            user_id,
+            query.unread.unwrap_or(false),
+            params.resolved_limit(),
+            params.sql_offset(),
+        )
+        .await?;
+
+    let page = PaginatedResponse::new(data, total, &params);
+    Ok(HttpResponse::Ok().json(NotificationInbox {
+        data: page.data,
+        total: page.total,
+        page: page.page,
+        limit: page.limit,
+        unread_count,
+    }))
 }
 
 /// POST /api/notifications - Create notification (requires auth)
 pub async fn create_notification(
     req: HttpRequest,
-    pool: web::Data<DbPool>,
+    notifications: web::Data<Arc<NotificationService>>,
     body: web::Json<CreateNotificationRequest>,
 ) -> Result<HttpResponse, ApiError> {
     let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
-
-    let typ = body.typ.as_deref().unwrap_or("info").to_string();
-    let message = body.message.as_deref().unwrap_or("").to_string();
-
-    let row = sqlx::query_as::<_, NotificationRow>(
-        r#"
-        INSERT INTO notifications (user_id, type, title, message, link, link_label)
-        VALUES ($1, $2, $3, $4, $5, $6)
-        RETURNING id, user_id, type, title, message, link, link_label, read, created_at
-        "#,
-    )
-    .bind(user_id)
-    .bind(&typ)
-    .bind(&body.title)
-    .bind(&message)
-    .bind(&body.link)
-    .bind(&body.link_label)
-    .fetch_one(pool.as_ref())
-    .await
-    .map_err(ApiError::DatabaseError)?;
-
-    Ok(HttpResponse::Created().json(ApiResponse {
-        data: notification_to_json(row),
-    }))
+    let body = body.into_inner();
+
+    let notification = RenderedNotification {
+        category: body.category.unwrap_or(NotificationCategory::System),
+        kind: body.typ.unwrap_or_else(|| "info".to_string()),
+        title: body.title,
+        message: body.message.unwrap_or_default(),
+        link: body.link,
+        link_label: body.link_label,
+    };
+    let stored = notifications.create(user_id, &notification).await?;
+
+    Ok(HttpResponse::Created().json(ApiResponse { data: stored }))
 }
 
 /// PATCH /api/notifications/:id/read - Mark as read (requires auth)
 pub async fn mark_notification_read(
     req: HttpRequest,
-    pool: web::Data<DbPool>,
+    notifications: web::Data<Arc<NotificationService>>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
     let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
-    let id = path.into_inner();
-
-    let result = sqlx::query(
-        r#"
-        UPDATE notifications
-        SET read = true
-        WHERE id = $1 AND user_id = $2
-        "#,
-    )
-    .bind(id)
-    .bind(user_id)
-    .execute(pool.as_ref())
-    .await
-    .map_err(ApiError::DatabaseError)?;
-
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound);
-    }
+
+    notifications.mark_read(user_id, path.into_inner()).await?;
 
     Ok(HttpResponse::Ok().json(ApiResponse {
         data: serde_json::json!({ "ok": true }),
@@ -154,53 +108,101 @@ pub async fn mark_notification_read(
 /// PATCH /api/notifications/read-all - Mark all as read (requires auth)
 pub async fn mark_all_read(
     req: HttpRequest,
-    pool: web::Data<DbPool>,
+    notifications: web::Data<Arc<NotificationService>>,
 ) -> Result<HttpResponse, ApiError> {
     let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
 
-    sqlx::query(
-        r#"
-        UPDATE notifications
-        SET read = true
-        WHERE user_id = $1
-        "#,
-    )
-    .bind(user_id)
-    .execute(pool.as_ref())
-    .await
-    .map_err(ApiError::DatabaseError)?;
+    let updated = notifications.mark_all_read(user_id).await?;
 
     Ok(HttpResponse::Ok().json(ApiResponse {
-        data: serde_json::json!({ "ok": true }),
+        data: serde_json::json!({ "ok": true, "updated": updated }),
     }))
 }
 
 /// DELETE /api/notifications/:id - Delete notification (requires auth)
 pub async fn delete_notification(
     req: HttpRequest,
-    pool: web::Data<DbPool>,
+    notifications: web::Data<Arc<NotificationService>>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
     let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
-    let id = path.into_inner();
-
-    let result = sqlx::query(
-        r#"
-        DELETE FROM notifications
-        WHERE id = $1 AND user_id = $2
-        "#,
-    )
-    .bind(id)
-    .bind(user_id)
-    .execute(pool.as_ref())
-    .await
-    .map_err(ApiError::DatabaseError)?;
-
-    if result.rows_affected() == 0 {
-        return Err(ApiError::NotFound);
-    }
+
+    notifications.delete(user_id, path.into_inner()).await?;
 
     Ok(HttpResponse::Ok().json(ApiResponse {
         data: serde_json::json!({ "ok": true }),
     }))
 }
+
+/// GET /api/notifications/preferences - Channels per category (requires auth)
+pub async fn get_preferences(
+    req: HttpRequest,
+    notifications: web::Data<Arc<NotificationService>>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+
+    let preferences = notifications.preferences(user_id).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse { data: preferences }))
+}
+
+/// PUT /api/notifications/preferences - Change channels of the given
+/// categories (requires auth)
+pub async fn update_preferences(
+    req: HttpRequest,
+    notifications: web::Data<Arc<NotificationService>>,
+    body: web::Json<UpdateNotificationPreferencesRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+
+    let preferences = notifications.update_preferences(user_id, &body).await?;
+
+    Ok(HttpResponse::Ok().json(ApiResponse { data: preferences }))
+}
+
+/// POST /api/notifications/push-tokens - Register a device for push
+/// notifications (requires auth)
+pub async fn register_push_token(
+    req: HttpRequest,
+    notifications: web::Data<Arc<NotificationService>>,
+    body: web::Json<RegisterPushTokenRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    notifications
+        .register_push_token(user_id, &body.token, body.platform)
+        .await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// DELETE /api/notifications/push-tokens - Stop push notifications to a
+/// device (requires auth)
+pub async fn remove_push_token(
+    req: HttpRequest,
+    notifications: web::Data<Arc<NotificationService>>,
+    body: web::Json<RemovePushTokenRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+
+    notifications.remove_push_token(user_id, &body.token).await?;
+
+    Ok(HttpResponse::NoContent().finish())
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/notifications")
+            .route("", web::get().to(get_notifications))
+            .route("", web::post().to(create_notification))
+            .route("/read-all", web::patch().to(mark_all_read))
+            .route("/preferences", web::get().to(get_preferences))
+            .route("/preferences", web::put().to(update_preferences))
+            .route("/push-tokens", web::post().to(register_push_token))
+            .route("/push-tokens", web::delete().to(remove_push_token))
+            .route("/{id}/read", web::patch().to(mark_notification_read))
+            .route("/{id}", web::delete().to(delete_notification)),
+    );
+}
@@ -0,0 +1,154 @@
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::{CreatePaymentRequest, PaymentStatusView};
+use crate::service::payment_service::PaymentService;
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
+use futures_util::stream::{self, StreamExt};
+use serde::Serialize;
+use std::sync::Arc;
+use std::time::Duration;
+use uuid::Uuid;
+use validator::Validate;
+
+const IDEMPOTENCY_KEY_HEADER: &str = "Idempotency-Key";
+
+/// Comment frames keep proxies from closing a quiet event stream.
+const SSE_KEEPALIVE: Duration = Duration::from_secs(15);
+
+fn user_id(http_req: &HttpRequest) -> Result<Uuid, ApiError> {
+    http_req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))
+}
+
+/// One server-sent event frame.
+fn sse_frame(event: &str, data: &impl Serialize) -> web::Bytes {
+    let data = serde_json::to_string(data).unwrap_or_else(|_| "null".to_string());
+    web::Bytes::from(format!("event: {}\ndata: {}\n\n", event, data))
+}
+
+/// POST /api/payments
+///
+/// Requires an `Idempotency-Key` header. Replaying a key with the same body
+/// returns the original payment with `200 OK` instead of `201 Created`.
+pub async fn create_payment(
+    svc: web::Data<Arc<PaymentService>>,
+    body: web::Json<CreatePaymentRequest>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let user_id = user_id(&http_req)?;
+    let idempotency_key = http_req
+        .headers()
+        .get(IDEMPOTENCY_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| ApiError::bad_request("Idempotency-Key header is required"))?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let (payment, created) = svc
+        .create(user_id, idempotency_key, body.into_inner())
+        .await?;
+    if created {
+        Ok(HttpResponse::Created().json(payment))
+    } else {
+        Ok(HttpResponse::Ok()
+            .insert_header(("Idempotent-Replayed", "true"))
+            .json(payment))
+    }
+}
+
+/// GET /api/payments/{id}
+pub async fn get_payment(
+    svc: web::Data<Arc<PaymentService>>,
+    path: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let payment = svc.get(user_id(&http_req)?, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(payment))
+}
+
+/// GET /api/payments/{id}/status
+pub async fn get_payment_status(
+    svc: web::Data<Arc<PaymentService>>,
+    path: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let payment = svc.get(user_id(&http_req)?, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(PaymentStatusView::from(&payment)))
+}
+
+/// POST /api/payments/{id}/cancel
+///
+/// Only payments that have not started processing can be cancelled.
+pub async fn cancel_payment(
+    svc: web::Data<Arc<PaymentService>>,
+    path: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let payment = svc.cancel(user_id(&http_req)?, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(payment))
+}
+
+/// GET /api/payments/{id}/events
+///
+/// Server-sent events: a `snapshot` of the current status, then a `status`
+/// event per change. The stream ends once the payment reaches a final status.
+pub async fn stream_payment_events(
+    svc: web::Data<Arc<PaymentService>>,
+    path: web::Path<Uuid>,
+    http_req: HttpRequest,
+) -> Result<impl Responder, ApiError> {
+    let (payment, updates) = svc.watch(user_id(&http_req)?, path.into_inner()).await?;
+    let snapshot = PaymentStatusView::from(&payment);
+    let done = snapshot.status.is_terminal();
+    let first = stream::once(async move { sse_frame("snapshot", &snapshot) });
+
+    let changes = stream::unfold((updates, done), |(mut updates, done)| async move {
+        if done {
+            return None;
+        }
+        match tokio::time::timeout(SSE_KEEPALIVE, updates.next()).await {
+            Ok(Some(update)) => {
+                let done = update.status.is_terminal();
+                Some((sse_frame("status", &update), (updates, done)))
+            }
+            Ok(None) => None,
+            Err(_) => Some((
+                web::Bytes::from_static(b": keepalive\n\n"),
+                (updates, false),
+            )),
+        }
+    });
+
+    let body = first.chain(changes).map(Ok::<_, actix_web::Error>);
+    Ok(HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header(("Cache-Control", "no-cache"))
+        .insert_header(("X-Accel-Buffering", "no"))
+        .streaming(body))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/payments")
+            .route("", web::post().to(create_payment))
+            .route("/{id}", web::get().to(get_payment))
+            .route("/{id}/status", web::get().to(get_payment_status))
+            .route("/{id}/cancel", web::post().to(cancel_payment))
+            .route("/{id}/events", web::get().to(stream_payment_events)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sse_frame_format() {
+        let frame = sse_frame("status", &serde_json::json!({ "status": "completed" }));
+        assert_eq!(
+            &frame[..],
+            b"event: status\ndata: {\"status\":\"completed\"}\n\n"
+        );
+    }
+}
@@ -0,0 +1,104 @@
+use actix_web::http::header;
+use actix_web::{web, HttpRequest, HttpResponse};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use uuid::Uuid;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::UpdatePrivacySettingsRequest;
+use crate::service::profile_service::{ProfileService, PROFILE_CACHE_TTL_SECS};
+
+/// Strong ETag of a response body.
+fn etag(body: &[u8]) -> String {
+    format!("\"{}\"", &hex::encode(Sha256::digest(body))[..32])
+}
+
+/// Whether an `If-None-Match` header value matches `etag`.
+fn if_none_match(header: &str, etag: &str) -> bool {
+    header
+        .split(',')
+        .map(str::trim)
+        .any(|candidate| candidate == "*" || candidate.trim_start_matches("W/") == etag)
+}
+
+/// GET /api/players/{id}/profile
+///
+/// Earnings are left out when the player hides them, unless the caller is
+/// the player. Answers `304 Not Modified` when `If-None-Match` matches.
+pub async fn get_profile(
+    profiles: web::Data<Arc<ProfileService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let profile = profiles.profile(path.into_inner(), req.user_id()).await?;
+    let body = serde_json::to_vec(&profile)
+        .map_err(|e| ApiError::internal_error(format!("Failed to encode profile: {}", e)))?;
+
+    let tag = etag(&body);
+    let cache_control = format!("private, max-age={}", PROFILE_CACHE_TTL_SECS);
+    let not_modified = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| if_none_match(value, &tag));
+    if not_modified {
+        return Ok(HttpResponse::NotModified()
+            .insert_header((header::ETAG, tag))
+            .insert_header((header::CACHE_CONTROL, cache_control))
+            .finish());
+    }
+
+    Ok(HttpResponse::Ok()
+        .insert_header((header::ETAG, tag))
+        .insert_header((header::CACHE_CONTROL, cache_control))
+        .content_type("application/json")
+        .body(body))
+}
+
+/// GET /api/players/me/privacy
+pub async fn get_privacy(
+    profiles: web::Data<Arc<ProfileService>>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let settings = profiles.privacy(user_id).await?;
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+/// PUT /api/players/me/privacy
+pub async fn update_privacy(
+    profiles: web::Data<Arc<ProfileService>>,
+    req: HttpRequest,
+    body: web::Json<UpdatePrivacySettingsRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let settings = profiles.update_privacy(user_id, &body).await?;
+    Ok(HttpResponse::Ok().json(settings))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/players")
+            .route("/me/privacy", web::get().to(get_privacy))
+            .route("/me/privacy", web::put().to(update_privacy))
+            .route("/{id}/profile", web::get().to(get_profile)),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_if_none_match() {
+        let tag = etag(br#"{"username":"ada"}"#);
+        assert_eq!(tag.len(), 34);
+        assert_ne!(tag, etag(br#"{"username":"bob"}"#));
+
+        assert!(if_none_match(&tag, &tag));
+        assert!(if_none_match(&format!("\"other\", W/{}", tag), &tag));
+        assert!(if_none_match("*", &tag));
+        assert!(!if_none_match("\"other\"", &tag));
+    }
+}
@@ -70,10 +70,10 @@ pub async fn get_player_reputation(
         r#"
         SELECT 
             id as user_id,
-            COALESCE(skill_score, 1000) as skill_score,
-            COALESCE(fair_play_score, 100) as fair_play_score,
+            COALESCE(skill_score, 1000) as "skill_score!",
+            COALESCE(fair_play_score, 100) as "fair_play_score!",
             reputation_last_updated,
-            COALESCE(is_bad_actor, false) as is_bad_actor
+            COALESCE(is_bad_actor, false) as "is_bad_actor!"
         FROM users
         WHERE id = $1
         "#,
@@ -95,7 +95,7 @@ pub async fn get_player_reputation(
         is_bad_actor: reputation.is_bad_actor,
     };
 
-    Ok(HttpResponse::Ok().json(ApiResponse::success(reputation)))
+    Ok(HttpResponse::Ok().json(ApiResponse::success(rep)))
 }
 
 #[derive(sqlx::FromRow)]
@@ -119,10 +119,10 @@ pub async fn get_my_reputation(
         r#"
         SELECT 
             id as user_id,
-            COALESCE(skill_score, 1000) as skill_score,
-            COALESCE(fair_play_score, 100) as fair_play_score,
+            COALESCE(skill_score, 1000) as "skill_score!",
+            COALESCE(fair_play_score, 100) as "fair_play_score!",
             reputation_last_updated,
-            COALESCE(is_bad_actor, false) as is_bad_actor
+            COALESCE(is_bad_actor, false) as "is_bad_actor!"
         FROM users
         WHERE id = $1
         "#,
@@ -175,7 +175,7 @@ pub async fn get_reputation_history(
             fair_play_delta,
             match_id,
             transaction_hash,
-            created_at::text
+            created_at::text as "created_at!"
         FROM reputation_events
         WHERE user_id = $1
         ORDER BY created_at DESC
@@ -283,8 +283,8 @@ pub async fn get_reputation_stats(
             COUNT(*) FILTER (WHERE is_bad_actor = true) as bad_actors_count,
             COUNT(*) FILTER (WHERE COALESCE(fair_play_score, 100) < 50) as low_fair_play_count,
             COUNT(*) FILTER (WHERE COALESCE(skill_score, 1000) >= 1500) as high_skill_count,
-            AVG(COALESCE(skill_score, 1000)) as avg_skill,
-            AVG(COALESCE(fair_play_score, 100)) as avg_fair_play
+            AVG(COALESCE(skill_score, 1000))::FLOAT8 as avg_skill,
+            AVG(COALESCE(fair_play_score, 100))::FLOAT8 as avg_fair_play
         FROM users
         WHERE is_active = true
         "#
@@ -0,0 +1,58 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+
+use crate::api_error::ApiError;
+use crate::models::{PaginationParams, SearchFilters, SearchKind};
+use crate::service::search::SearchService;
+
+const MAX_QUERY_LEN: usize = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    /// `tournament`, `player` or `team`; all of them when absent.
+    #[serde(rename = "type")]
+    pub kind: Option<SearchKind>,
+    /// See [`SearchFilters`], e.g. `game:fifa,status:registration_open`.
+    pub filters: Option<String>,
+    pub page: Option<i64>,
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/search
+///
+/// Without `q`, everything matching the filters is returned.
+pub async fn search(
+    service: web::Data<Arc<SearchService>>,
+    query: web::Query<SearchQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let text = query.q.as_deref().unwrap_or_default().trim();
+    if text.chars().count() > MAX_QUERY_LEN {
+        return Err(ApiError::bad_request(format!(
+            "Query must be at most {} characters",
+            MAX_QUERY_LEN
+        )));
+    }
+    let filters: SearchFilters = query
+        .filters
+        .as_deref()
+        .unwrap_or_default()
+        .parse()
+        .map_err(ApiError::BadRequest)?;
+    let pagination = PaginationParams {
+        page: query.page,
+        offset: query.offset,
+        limit: query.limit,
+    };
+
+    let response = service
+        .search(text, query.kind, &filters, &pagination)
+        .await?;
+    Ok(HttpResponse::Ok().json(response))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(web::scope("/search").route("", web::get().to(search)));
+}
@@ -0,0 +1,67 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::ReportLiveScoreRequest;
+use crate::service::spectator_service::SpectatorService;
+
+#[derive(Debug, Deserialize)]
+pub struct LiveMatchesQuery {
+    pub tournament_id: Option<Uuid>,
+    pub limit: Option<i64>,
+}
+
+/// GET /api/spectate/matches
+///
+/// Matches in progress, optionally of one tournament.
+pub async fn live_matches(
+    spectator: web::Data<Arc<SpectatorService>>,
+    query: web::Query<LiveMatchesQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let matches = spectator
+        .live_matches(query.tournament_id, query.limit)
+        .await?;
+    Ok(HttpResponse::Ok().json(matches))
+}
+
+/// GET /api/spectate/matches/{id}
+///
+/// Scores, round timeline and dispute status, behind the broadcast delay.
+/// Follow `spectate:{id}` on the gateway for live updates.
+pub async fn spectate_match(
+    spectator: web::Data<Arc<SpectatorService>>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Ok().json(spectator.view(path.into_inner()).await?))
+}
+
+/// POST /api/spectate/matches/{id}/scores
+///
+/// A player posts the score after a round of their match.
+pub async fn report_live_score(
+    spectator: web::Data<Arc<SpectatorService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<ReportLiveScoreRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    let score = spectator
+        .report_score(user_id, path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Created().json(score))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/spectate")
+            .route("/matches", web::get().to(live_matches))
+            .route("/matches/{id}", web::get().to(spectate_match))
+            .route("/matches/{id}/scores", web::post().to(report_live_score)),
+    );
+}
@@ -0,0 +1,316 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::middleware::ClaimsExt;
+use crate::models::{
+    CreateTeamRequest, InviteTeamMemberRequest, PaginationParams, RegisterTeamRequest,
+    RegistrationStatus, TeamDepositRequest, TeamDistributionRequest, TransferCaptaincyRequest,
+    UpdateEarningsSplitsRequest, UpdateTeamRequest,
+};
+use crate::service::team_service::TeamService;
+
+#[derive(Debug, Deserialize)]
+pub struct TeamTournamentsQuery {
+    pub page: Option<i64>,
+    pub offset: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// POST /api/teams
+///
+/// The caller becomes the captain.
+pub async fn create_team(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    body: web::Json<CreateTeamRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let team = teams.create(user_id, &body).await?;
+    Ok(HttpResponse::Created().json(team))
+}
+
+/// GET /api/teams/me
+pub async fn my_teams(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    Ok(HttpResponse::Ok().json(teams.teams_of(user_id).await?))
+}
+
+/// GET /api/teams/{id}
+pub async fn get_team(
+    teams: web::Data<Arc<TeamService>>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    Ok(HttpResponse::Ok().json(teams.team(path.into_inner()).await?))
+}
+
+/// PATCH /api/teams/{id}
+pub async fn update_team(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateTeamRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let team = teams.update(user_id, path.into_inner(), &body).await?;
+    Ok(HttpResponse::Ok().json(team))
+}
+
+/// DELETE /api/teams/{id}
+pub async fn disband_team(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    teams.disband(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /api/teams/{id}/invitations
+pub async fn invite_member(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<InviteTeamMemberRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let invitation = teams
+        .invite(user_id, path.into_inner(), body.user_id)
+        .await?;
+    Ok(HttpResponse::Created().json(invitation))
+}
+
+/// DELETE /api/teams/{id}/invitations/{invitation_id}
+pub async fn revoke_invitation(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let (team_id, invitation_id) = path.into_inner();
+    teams
+        .revoke_invitation(user_id, team_id, invitation_id)
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// GET /api/teams/invitations
+///
+/// Pending invitations of the caller.
+pub async fn my_invitations(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    Ok(HttpResponse::Ok().json(teams.invitations(user_id).await?))
+}
+
+/// POST /api/teams/invitations/{id}/accept
+pub async fn accept_invitation(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let team = teams.accept_invitation(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::Ok().json(team))
+}
+
+/// POST /api/teams/invitations/{id}/decline
+pub async fn decline_invitation(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    teams.decline_invitation(user_id, path.into_inner()).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// DELETE /api/teams/{id}/members/{user_id}
+///
+/// The captain removes a member, or a member removes themselves to leave.
+pub async fn remove_member(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<(Uuid, Uuid)>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let (team_id, member_id) = path.into_inner();
+    teams.remove_member(user_id, team_id, member_id).await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /api/teams/{id}/captain
+pub async fn transfer_captaincy(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<TransferCaptaincyRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let team = teams
+        .transfer_captaincy(user_id, path.into_inner(), body.user_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(team))
+}
+
+/// PUT /api/teams/{id}/splits
+pub async fn update_splits(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateEarningsSplitsRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let splits = teams
+        .set_splits(user_id, path.into_inner(), &body.splits)
+        .await?;
+    Ok(HttpResponse::Ok().json(splits))
+}
+
+/// GET /api/teams/{id}/wallet
+pub async fn get_wallet(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    Ok(HttpResponse::Ok().json(teams.wallet(user_id, path.into_inner()).await?))
+}
+
+/// POST /api/teams/{id}/wallet/deposit
+pub async fn deposit(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<TeamDepositRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let wallet = teams
+        .deposit(user_id, path.into_inner(), body.amount)
+        .await?;
+    Ok(HttpResponse::Ok().json(wallet))
+}
+
+/// POST /api/teams/{id}/wallet/distribute
+pub async fn distribute(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+    body: web::Json<TeamDistributionRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let distribution = teams
+        .distribute(user_id, path.into_inner(), body.amount)
+        .await?;
+    Ok(HttpResponse::Ok().json(distribution))
+}
+
+/// GET /api/teams/{id}/tournaments
+pub async fn team_tournaments(
+    teams: web::Data<Arc<TeamService>>,
+    path: web::Path<Uuid>,
+    query: web::Query<TeamTournamentsQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let pagination = PaginationParams {
+        page: query.page,
+        offset: query.offset,
+        limit: query.limit,
+    };
+    let tournaments = teams.tournaments(path.into_inner(), &pagination).await?;
+    Ok(HttpResponse::Ok().json(tournaments))
+}
+
+/// POST /api/teams/{id}/tournaments/{tournament_id}/register
+///
+/// The captain enters the team with a lineup of members. Responds like
+/// `POST /api/tournaments/{id}/register`: `201` when registered, `202` while
+/// the captain's stake is pending and `403` when rejected.
+pub async fn register_for_tournament(
+    teams: web::Data<Arc<TeamService>>,
+    req: HttpRequest,
+    path: web::Path<(Uuid, Uuid)>,
+    body: web::Json<RegisterTeamRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req.user_id().ok_or(ApiError::Unauthorized)?;
+    let (team_id, tournament_id) = path.into_inner();
+    let idempotency_key = req
+        .headers()
+        .get("Idempotency-Key")
+        .map(|value| {
+            value
+                .to_str()
+                .map(str::to_string)
+                .map_err(|_| ApiError::bad_request("Invalid Idempotency-Key header format"))
+        })
+        .transpose()?;
+
+    let registration = teams
+        .register(
+            user_id,
+            team_id,
+            tournament_id,
+            idempotency_key,
+            body.into_inner(),
+        )
+        .await?;
+
+    let mut response = match registration.status {
+        RegistrationStatus::Registered => HttpResponse::Created(),
+        RegistrationStatus::PendingStake => HttpResponse::Accepted(),
+        RegistrationStatus::Rejected => HttpResponse::Forbidden(),
+    };
+    Ok(response
+        .insert_header(("Idempotency-Key", registration.idempotency_key.clone()))
+        .json(registration))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/teams")
+            .route("", web::post().to(create_team))
+            .route("/me", web::get().to(my_teams))
+            .route("/invitations", web::get().to(my_invitations))
+            .route(
+                "/invitations/{id}/accept",
+                web::post().to(accept_invitation),
+            )
+            .route(
+                "/invitations/{id}/decline",
+                web::post().to(decline_invitation),
+            )
+            .route("/{id}", web::get().to(get_team))
+            .route("/{id}", web::patch().to(update_team))
+            .route("/{id}", web::delete().to(disband_team))
+            .route("/{id}/invitations", web::post().to(invite_member))
+            .route(
+                "/{id}/invitations/{invitation_id}",
+                web::delete().to(revoke_invitation),
+            )
+            .route("/{id}/members/{user_id}", web::delete().to(remove_member))
+            .route("/{id}/captain", web::post().to(transfer_captaincy))
+            .route("/{id}/splits", web::put().to(update_splits))
+            .route("/{id}/wallet", web::get().to(get_wallet))
+            .route("/{id}/wallet/deposit", web::post().to(deposit))
+            .route("/{id}/wallet/distribute", web::post().to(distribute))
+            .route("/{id}/tournaments", web::get().to(team_tournaments))
+            .route(
+                "/{id}/tournaments/{tournament_id}/register",
+                web::post().to(register_for_tournament),
+            ),
+    );
+}
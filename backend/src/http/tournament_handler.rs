@@ -1,10 +1,12 @@
 use crate::api_error::ApiError;
 use crate::auth::middleware::ClaimsExt;
+use crate::auth::rbac::{Admin, RequireRole, TournamentManager};
 use crate::middleware::security::validate_uuid;
 use crate::models::{
-    CreateTournamentRequest, JoinTournamentRequest, PaginatedResponse,
+    CreateTournamentRequest, JoinTournamentRequest, PaginatedResponse, RegistrationStatus,
     TournamentStatus,
 };
+use crate::service::registration_service::RegistrationService;
 use crate::service::tournament_service::TournamentService;
 use actix_web::{web, HttpRequest, HttpResponse};
 use serde::Deserialize;
@@ -30,44 +32,18 @@ pub struct TournamentStatisticsQuery {
     pub include_matches: Option<bool>,
 }
 
-// ─── Helpers ──────────────────────────────────────────────────────────────────
-
-/// Return `ApiError::Forbidden` unless the caller has the `admin` role.
-fn require_admin(req: &HttpRequest) -> Result<(), ApiError> {
-    let claims = req
-        .claims()
-        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
-    if !claims.roles.contains(&"admin".to_string()) {
-        return Err(ApiError::forbidden("Admin access required"));
-    }
-    Ok(())
-}
-
 // ─── Handlers ─────────────────────────────────────────────────────────────────
 
 /// POST /api/tournaments
 ///
-/// Create a tournament.  Requires `admin` or `organizer` role.
+/// Create a tournament.  Requires `admin` or `organizer` role, or the
+/// tournament manager role on the auth gateway.
 pub async fn create_tournament(
     svc: web::Data<Arc<TournamentService>>,
-    req: HttpRequest,
+    organizer: RequireRole<TournamentManager>,
     body: web::Json<CreateTournamentRequest>,
 ) -> Result<HttpResponse, ApiError> {
-    let claims = req
-        .claims()
-        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
-
-    let is_privileged = claims.roles.contains(&"admin".to_string())
-        || claims.roles.contains(&"organizer".to_string());
-    if !is_privileged {
-        return Err(ApiError::forbidden(
-            "Only admins or organizers may create tournaments",
-        ));
-    }
-
-    let creator_id = req
-        .user_id()
-        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let creator_id = organizer.user_id;
 
     info!(creator_id = %creator_id, name = %body.name, "Creating tournament");
 
@@ -143,9 +119,12 @@ pub async fn get_tournament(
 /// POST /api/tournaments/{id}/register
 ///
 /// Register the authenticated user in the tournament, deducting the entry fee
-/// from their wallet.
+/// from their wallet. Role, reputation and (for stake-gated tournaments) the
+/// on-chain stake are checked first. Responds `201` when registered, `202`
+/// while the stake is pending and `403` when rejected; repeat the request with
+/// the returned `Idempotency-Key` to re-check a pending stake.
 pub async fn register_for_tournament(
-    svc: web::Data<Arc<TournamentService>>,
+    registrations: web::Data<Arc<RegistrationService>>,
     req: HttpRequest,
     path: web::Path<Uuid>,
     body: web::Json<JoinTournamentRequest>,
@@ -154,6 +133,16 @@ pub async fn register_for_tournament(
     let user_id = req
         .user_id()
         .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let idempotency_key = req
+        .headers()
+        .get("Idempotency-Key")
+        .map(|value| {
+            value
+                .to_str()
+                .map(str::to_string)
+                .map_err(|_| ApiError::bad_request("Invalid Idempotency-Key header format"))
+        })
+        .transpose()?;
 
     info!(
         user_id = %user_id,
@@ -162,11 +151,51 @@ pub async fn register_for_tournament(
         "Tournament registration request"
     );
 
-    let participant = svc
-        .join_tournament(user_id, tournament_id, body.into_inner())
+    let registration = registrations
+        .register(user_id, tournament_id, idempotency_key, body.into_inner())
         .await?;
 
-    Ok(HttpResponse::Created().json(participant))
+    let mut response = match registration.status {
+        RegistrationStatus::Registered => HttpResponse::Created(),
+        RegistrationStatus::PendingStake => HttpResponse::Accepted(),
+        RegistrationStatus::Rejected => HttpResponse::Forbidden(),
+    };
+    Ok(response
+        .insert_header(("Idempotency-Key", registration.idempotency_key.clone()))
+        .json(registration))
+}
+
+/// GET /api/tournaments/{id}/registration
+///
+/// The authenticated user's registration status for the tournament.
+pub async fn get_registration(
+    registrations: web::Data<Arc<RegistrationService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let registration = registrations
+        .get_registration(path.into_inner(), user_id)
+        .await?;
+    Ok(HttpResponse::Ok().json(registration))
+}
+
+/// POST /api/tournaments/{id}/checkin
+///
+/// Check the authenticated user in for their next bracket match. Players who
+/// miss the window forfeit the match.
+pub async fn check_in(
+    svc: web::Data<Arc<TournamentService>>,
+    req: HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("Authentication required"))?;
+    let check_in = svc.check_in(path.into_inner(), user_id).await?;
+    Ok(HttpResponse::Ok().json(check_in))
 }
 
 /// POST /api/tournaments/{id}/start
@@ -174,11 +203,9 @@ pub async fn register_for_tournament(
 /// Start the tournament and generate the initial bracket.  Admin only.
 pub async fn start_tournament(
     svc: web::Data<Arc<TournamentService>>,
-    req: HttpRequest,
+    _admin: RequireRole<Admin>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
-    require_admin(&req)?;
-
     let tournament_id = path.into_inner();
 
     info!(tournament_id = %tournament_id, "Admin starting tournament");
@@ -197,11 +224,9 @@ pub async fn start_tournament(
 /// Advance the tournament bracket to the next round.  Admin only.
 pub async fn advance_bracket(
     svc: web::Data<Arc<TournamentService>>,
-    req: HttpRequest,
+    _admin: RequireRole<Admin>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
-    require_admin(&req)?;
-
     let tournament_id = path.into_inner();
 
     info!(tournament_id = %tournament_id, "Advancing tournament bracket");
@@ -219,11 +244,9 @@ pub async fn advance_bracket(
 /// Trigger on-chain prize distribution for a completed tournament.  Admin only.
 pub async fn distribute_prizes(
     svc: web::Data<Arc<TournamentService>>,
-    req: HttpRequest,
+    _admin: RequireRole<Admin>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
-    require_admin(&req)?;
-
     let tournament_id = path.into_inner();
 
     info!(tournament_id = %tournament_id, "Triggering prize distribution");
@@ -242,11 +265,9 @@ pub async fn distribute_prizes(
 /// Admin only.
 pub async fn cancel_tournament(
     svc: web::Data<Arc<TournamentService>>,
-    req: HttpRequest,
+    _admin: RequireRole<Admin>,
     path: web::Path<Uuid>,
 ) -> Result<HttpResponse, ApiError> {
-    require_admin(&req)?;
-
     let tournament_id = path.into_inner();
 
     info!(tournament_id = %tournament_id, "Admin cancelling tournament");
@@ -286,6 +307,8 @@ pub fn configure_routes(cfg: &mut web::ServiceConfig) {
             .route("/{id}", web::get().to(get_tournament))
             .route("/{id}", web::delete().to(cancel_tournament))
             .route("/{id}/register", web::post().to(register_for_tournament))
+            .route("/{id}/registration", web::get().to(get_registration))
+            .route("/{id}/checkin", web::post().to(check_in))
             .route("/{id}/start", web::post().to(start_tournament))
             .route("/{id}/advance", web::post().to(advance_bracket))
             .route("/{id}/distribute-prizes", web::post().to(distribute_prizes))
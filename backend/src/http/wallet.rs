@@ -1,14 +1,21 @@
 use actix_web::{web, HttpResponse, Result};
 use serde::Deserialize;
 use sqlx::PgPool;
+use std::sync::Arc;
 use uuid::Uuid;
+use validator::Validate;
 
 use crate::api_error::ApiError;
 use crate::auth::middleware::ClaimsExt;
+use crate::auth::phone_verification::PhoneVerificationService;
+use crate::auth::rbac::{Admin, RequireRole};
+use crate::auth::step_up::StepUp;
 use crate::models::{
-    DepositRequest, PaginatedResponse, PaginationParams, TransactionResponse, TransactionStatus,
-    TransactionType, WalletResponse, WithdrawalRequest,
+    CustodialWithdrawalRequest, DepositRequest, PaginatedResponse, PaginationParams,
+    ReviewWithdrawalRequest, StartDepositRampRequest, StartWithdrawalRampRequest,
+    TransactionResponse, TransactionStatus, TransactionType, WalletResponse, WithdrawalRequest,
 };
+use crate::service::anchor::{AnchorCallback, RampError, RampService};
 use crate::service::WalletService;
 
 #[derive(Deserialize)]
@@ -17,6 +24,12 @@ pub struct PaymentVerificationRequest {
     pub provider: String,
 }
 
+#[derive(Deserialize)]
+pub struct WithdrawalListQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
 pub async fn get_wallet(
     pool: web::Data<PgPool>,
     req: actix_web::HttpRequest,
@@ -96,7 +109,7 @@ pub async fn initiate_deposit(
         .create_transaction(
             user_id,
             TransactionType::Deposit,
-            amount.mantissa(),
+            minor_units(amount)?,
             body.currency.clone(),
             format!("Wallet deposit via {}", body.payment_method),
             None,
@@ -137,10 +150,16 @@ pub async fn verify_deposit(
     }
 
     let verified = match body.provider.as_str() {
-        "paystack" => service.verify_paystack_payment(&body.reference, transaction.amount.mantissa()).await?,
-        "flutterwave" => service
-            .verify_flutterwave_payment(&body.reference, transaction.amount.mantissa())
-            .await?,
+        "paystack" => {
+            service
+                .verify_paystack_payment(&body.reference, minor_units(transaction.amount)?)
+                .await?
+        }
+        "flutterwave" => {
+            service
+                .verify_flutterwave_payment(&body.reference, minor_units(transaction.amount)?)
+                .await?
+        }
         _ => false,
     };
 
@@ -156,14 +175,21 @@ pub async fn verify_deposit(
     })))
 }
 
+/// Withdrawals, here and below, need the caller to step up: a passkey
+/// step-up token or, once they enabled it, a two-factor code. When
+/// `PHONE_VERIFICATION_REQUIRED` is on they also need a verified phone.
 pub async fn initiate_withdrawal(
     pool: web::Data<PgPool>,
+    step_up: web::Data<StepUp>,
+    phone: web::Data<Arc<PhoneVerificationService>>,
     req: actix_web::HttpRequest,
     body: web::Json<WithdrawalRequest>,
 ) -> Result<HttpResponse, ApiError> {
     let user_id = req
         .user_id()
         .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    phone.require_verified(user_id).await?;
+    step_up.require(&req, user_id).await?;
 
     let amount = body.amount;
     if amount <= rust_decimal::Decimal::ZERO {
@@ -190,9 +216,9 @@ pub async fn initiate_withdrawal(
     };
 
     let amount_in_smallest_unit = match body.currency.as_str() {
-        "NGN" | "ARENAX_TOKEN" => amount.mantissa(),
-        "XLM" => amount.mantissa() / 1_000_000,
-        _ => amount.mantissa(),
+        "NGN" | "ARENAX_TOKEN" => minor_units(amount)?,
+        "XLM" => minor_units(amount)? / 1_000_000,
+        _ => minor_units(amount)?,
     };
 
     if available_balance < amount_in_smallest_unit {
@@ -206,7 +232,7 @@ pub async fn initiate_withdrawal(
         .create_transaction(
             user_id,
             TransactionType::Withdrawal,
-            amount.mantissa(),
+            minor_units(amount)?,
             body.currency.clone(),
             format!("Withdrawal to {}", body.destination),
             None,
@@ -223,4 +249,223 @@ pub async fn initiate_withdrawal(
         "payment_method": body.payment_method,
         "message": "Withdrawal initiated. Processing may take a few minutes."
     })))
-}
\ No newline at end of file
+}
+
+// ============================================================================
+// CUSTODIAL STELLAR WALLET
+// ============================================================================
+
+/// GET /api/wallet/deposit-address
+///
+/// The caller's muxed address for XLM deposits into custody.
+pub async fn get_deposit_address(
+    wallets: web::Data<Arc<WalletService>>,
+    req: actix_web::HttpRequest,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let address = wallets.deposit_address(user_id).await?;
+    Ok(HttpResponse::Ok().json(address))
+}
+
+/// POST /api/wallet/withdrawals
+///
+/// Withdraw custodied XLM to a Stellar account. Large withdrawals are held
+/// for admin approval.
+pub async fn request_custodial_withdrawal(
+    wallets: web::Data<Arc<WalletService>>,
+    step_up: web::Data<StepUp>,
+    phone: web::Data<Arc<PhoneVerificationService>>,
+    req: actix_web::HttpRequest,
+    body: web::Json<CustodialWithdrawalRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    phone.require_verified(user_id).await?;
+    step_up.require(&req, user_id).await?;
+
+    let withdrawal = wallets
+        .request_withdrawal(
+            user_id,
+            body.amount,
+            &body.destination,
+            body.idempotency_key.as_deref(),
+        )
+        .await?;
+    Ok(HttpResponse::Accepted().json(withdrawal))
+}
+
+/// GET /api/wallet/withdrawals
+pub async fn list_custodial_withdrawals(
+    wallets: web::Data<Arc<WalletService>>,
+    req: actix_web::HttpRequest,
+    query: web::Query<WithdrawalListQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let withdrawals = wallets.list_withdrawals(user_id, limit, offset).await?;
+    Ok(HttpResponse::Ok().json(withdrawals))
+}
+
+/// GET /api/wallet/withdrawals/pending
+///
+/// Withdrawals waiting for admin approval.
+pub async fn list_pending_withdrawals(
+    wallets: web::Data<Arc<WalletService>>,
+    _admin: RequireRole<Admin>,
+    query: web::Query<WithdrawalListQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let withdrawals = wallets.pending_withdrawals(limit, offset).await?;
+    Ok(HttpResponse::Ok().json(withdrawals))
+}
+
+/// POST /api/wallet/withdrawals/{id}/approve
+pub async fn approve_withdrawal(
+    wallets: web::Data<Arc<WalletService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<ReviewWithdrawalRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let withdrawal = wallets
+        .approve_withdrawal(admin.user_id, path.into_inner(), body.note.as_deref())
+        .await?;
+    Ok(HttpResponse::Ok().json(withdrawal))
+}
+
+/// POST /api/wallet/withdrawals/{id}/reject
+///
+/// Rejects the withdrawal and returns the held funds to the user.
+pub async fn reject_withdrawal(
+    wallets: web::Data<Arc<WalletService>>,
+    admin: RequireRole<Admin>,
+    path: web::Path<Uuid>,
+    body: web::Json<ReviewWithdrawalRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let withdrawal = wallets
+        .reject_withdrawal(admin.user_id, path.into_inner(), body.note.as_deref())
+        .await?;
+    Ok(HttpResponse::Ok().json(withdrawal))
+}
+
+fn ramps_enabled(ramps: &Option<Arc<RampService>>) -> Result<&Arc<RampService>, ApiError> {
+    ramps
+        .as_ref()
+        .ok_or_else(|| ApiError::bad_request("Fiat on/off-ramps are not available"))
+}
+
+/// POST /api/wallet/ramps/deposit
+///
+/// Start a SEP-24 fiat deposit; the user continues at `interactive_url`.
+pub async fn start_deposit_ramp(
+    ramps: web::Data<Option<Arc<RampService>>>,
+    req: actix_web::HttpRequest,
+    body: web::Json<StartDepositRampRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let ramp = ramps_enabled(&ramps)?.start_deposit(user_id, &body).await?;
+    Ok(HttpResponse::Created().json(ramp))
+}
+
+/// POST /api/wallet/ramps/withdraw
+///
+/// Start a SEP-24 fiat withdrawal. The amount is held from the caller's
+/// balance of the asset until the ramp settles.
+pub async fn start_withdrawal_ramp(
+    ramps: web::Data<Option<Arc<RampService>>>,
+    step_up: web::Data<StepUp>,
+    phone: web::Data<Arc<PhoneVerificationService>>,
+    req: actix_web::HttpRequest,
+    body: web::Json<StartWithdrawalRampRequest>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+    phone.require_verified(user_id).await?;
+    step_up.require(&req, user_id).await?;
+
+    let ramp = ramps_enabled(&ramps)?
+        .start_withdrawal(user_id, &body)
+        .await?;
+    Ok(HttpResponse::Created().json(ramp))
+}
+
+/// GET /api/wallet/ramps
+///
+/// The caller's on/off-ramp history, newest first.
+pub async fn list_ramps(
+    ramps: web::Data<Option<Arc<RampService>>>,
+    req: actix_web::HttpRequest,
+    query: web::Query<WithdrawalListQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+    let history = ramps_enabled(&ramps)?
+        .list_ramps(user_id, limit, offset)
+        .await?;
+    Ok(HttpResponse::Ok().json(history))
+}
+
+/// GET /api/wallet/ramps/{id}
+pub async fn get_ramp(
+    ramps: web::Data<Option<Arc<RampService>>>,
+    req: actix_web::HttpRequest,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let user_id = req
+        .user_id()
+        .ok_or_else(|| ApiError::unauthorized("User not authenticated"))?;
+
+    let ramp = ramps_enabled(&ramps)?
+        .get_ramp(user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(ramp))
+}
+
+/// POST /api/wallet/ramps/callback
+///
+/// The anchor's `on_change_callback`. Unauthenticated: it only triggers a
+/// refresh of the transaction from the anchor.
+pub async fn anchor_callback(
+    ramps: web::Data<Option<Arc<RampService>>>,
+    body: web::Json<AnchorCallback>,
+) -> Result<HttpResponse, ApiError> {
+    match ramps_enabled(&ramps)?
+        .handle_callback(&body.transaction.id)
+        .await
+    {
+        Ok(()) => Ok(HttpResponse::NoContent().finish()),
+        Err(RampError::NotFound) => Err(ApiError::NotFound),
+        Err(e) => {
+            tracing::warn!(anchor_id = %body.transaction.id, error = %e, "Anchor callback failed");
+            // The poller will pick the change up; don't make the anchor retry.
+            Ok(HttpResponse::Accepted().finish())
+        }
+    }
+}
+
+/// The amount's unscaled digits, as stored by the wallet service.
+fn minor_units(amount: rust_decimal::Decimal) -> Result<i64, ApiError> {
+    i64::try_from(amount.mantissa()).map_err(|_| ApiError::bad_request("Amount out of range"))
+}
@@ -0,0 +1,180 @@
+use actix_web::{web, HttpResponse};
+use serde::Deserialize;
+use std::sync::Arc;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::api_error::ApiError;
+use crate::auth::rbac::{Operator, RequireRole};
+use crate::models::{
+    CreateWebhookEndpointRequest, UpdateWebhookEndpointRequest, WebhookDeliveryStatus,
+};
+use crate::service::webhooks::{WebhookService, EVENT_TYPES};
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryQuery {
+    pub status: Option<WebhookDeliveryStatus>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// GET /api/webhooks/event-types
+pub async fn list_event_types(_operator: RequireRole<Operator>) -> HttpResponse {
+    HttpResponse::Ok().json(EVENT_TYPES)
+}
+
+/// GET /api/webhooks/endpoints
+pub async fn list_endpoints(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+) -> Result<HttpResponse, ApiError> {
+    let endpoints = webhooks.list_endpoints(operator.user_id).await?;
+    Ok(HttpResponse::Ok().json(endpoints))
+}
+
+/// POST /api/webhooks/endpoints
+///
+/// The response carries the signing secret; it is not shown again.
+pub async fn create_endpoint(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    body: web::Json<CreateWebhookEndpointRequest>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let endpoint = webhooks.create_endpoint(operator.user_id, &body).await?;
+    Ok(HttpResponse::Created().json(endpoint))
+}
+
+/// GET /api/webhooks/endpoints/{id}
+pub async fn get_endpoint(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let endpoint = webhooks
+        .endpoint(operator.user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(endpoint))
+}
+
+/// PATCH /api/webhooks/endpoints/{id}
+pub async fn update_endpoint(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+    body: web::Json<UpdateWebhookEndpointRequest>,
+) -> Result<HttpResponse, ApiError> {
+    body.validate()
+        .map_err(|e| ApiError::ValidationError(e.to_string()))?;
+
+    let endpoint = webhooks
+        .update_endpoint(operator.user_id, path.into_inner(), &body)
+        .await?;
+    Ok(HttpResponse::Ok().json(endpoint))
+}
+
+/// DELETE /api/webhooks/endpoints/{id}
+pub async fn delete_endpoint(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    webhooks
+        .delete_endpoint(operator.user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::NoContent().finish())
+}
+
+/// POST /api/webhooks/endpoints/{id}/rotate-secret
+pub async fn rotate_secret(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let endpoint = webhooks
+        .rotate_secret(operator.user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(endpoint))
+}
+
+/// POST /api/webhooks/endpoints/{id}/ping
+///
+/// Queue a `ping` event to test the endpoint.
+pub async fn ping_endpoint(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let delivery = webhooks.ping(operator.user_id, path.into_inner()).await?;
+    Ok(HttpResponse::Accepted().json(delivery))
+}
+
+/// GET /api/webhooks/endpoints/{id}/deliveries
+pub async fn list_deliveries(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+    query: web::Query<DeliveryQuery>,
+) -> Result<HttpResponse, ApiError> {
+    let limit = query.limit.unwrap_or(20).clamp(1, 100);
+    let offset = query.offset.unwrap_or(0).max(0);
+
+    let deliveries = webhooks
+        .deliveries(
+            operator.user_id,
+            path.into_inner(),
+            query.status,
+            limit,
+            offset,
+        )
+        .await?;
+    Ok(HttpResponse::Ok().json(deliveries))
+}
+
+/// GET /api/webhooks/deliveries/{id}
+///
+/// A delivery with the status code, error and response of every attempt.
+pub async fn get_delivery(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let delivery = webhooks
+        .delivery(operator.user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::Ok().json(delivery))
+}
+
+/// POST /api/webhooks/deliveries/{id}/redeliver
+pub async fn redeliver(
+    webhooks: web::Data<Arc<WebhookService>>,
+    operator: RequireRole<Operator>,
+    path: web::Path<Uuid>,
+) -> Result<HttpResponse, ApiError> {
+    let delivery = webhooks
+        .redeliver(operator.user_id, path.into_inner())
+        .await?;
+    Ok(HttpResponse::Accepted().json(delivery))
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.service(
+        web::scope("/webhooks")
+            .route("/event-types", web::get().to(list_event_types))
+            .route("/endpoints", web::get().to(list_endpoints))
+            .route("/endpoints", web::post().to(create_endpoint))
+            .route("/endpoints/{id}", web::get().to(get_endpoint))
+            .route("/endpoints/{id}", web::patch().to(update_endpoint))
+            .route("/endpoints/{id}", web::delete().to(delete_endpoint))
+            .route(
+                "/endpoints/{id}/rotate-secret",
+                web::post().to(rotate_secret),
+            )
+            .route("/endpoints/{id}/ping", web::post().to(ping_endpoint))
+            .route("/endpoints/{id}/deliveries", web::get().to(list_deliveries))
+            .route("/deliveries/{id}", web::get().to(get_delivery))
+            .route("/deliveries/{id}/redeliver", web::post().to(redeliver)),
+    );
+}
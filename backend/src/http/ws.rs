@@ -0,0 +1,907 @@
+//! WebSocket gateway for live match and tournament updates.
+//!
+//! Clients connect to `GET /api/ws?token=<jwt>` and subscribe to topics:
+//! `user:{id}` (their own, joined automatically), `match:{id}` (participants
+//! only), `spectate:{id}` (anyone; see below), `tournament:{id}` (public) and
+//! `presence:{id}` (friends only; see [`PresenceTracker`]). Chat messages arrive on the topic of their match or
+//! tournament, and direct messages on `user:{id}`. Events reach the gateway
+//! over Redis pub/sub, published by services through `EventBus` and by the
+//! chain indexer, and are fanned out to every subscribed session as
+//! `{"type":"event","topic":...,"seq":...,"event":...}` frames.
+//!
+//! `spectate:{id}` carries the score, status, dispute and chain events of a
+//! match's channel, replayed by each instance after the spectator broadcast
+//! delay so the stream cannot be sniped.
+//!
+//! Every session has a bounded mailbox. A client that falls behind is
+//! disconnected instead of being buffered without limit. It reconnects with the
+//! resume token from its `welcome` frame (`?resume=<token>`) and gets back its
+//! subscriptions plus the events it missed from the replay buffer. If the
+//! buffer no longer reaches back far enough, each topic gets a `resync` frame
+//! and the client refetches that state over HTTP.
+
+use crate::auth::jwt_service::{Claims, JwtService};
+use crate::models::PresenceStatus;
+use crate::realtime::auth::RealtimeAuth;
+use crate::realtime::events::{channels, ClientMessage, RealtimeEvent};
+use crate::realtime::presence::PresenceTracker;
+use actix::{
+    Actor, ActorContext, ActorFutureExt, AsyncContext, Handler, Message, Recipient, StreamHandler,
+};
+use actix_web::{web, Error, HttpRequest, HttpResponse};
+use actix_web_actors::ws;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fmt;
+use std::str::FromStr;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+const CLIENT_TIMEOUT: Duration = Duration::from_secs(15);
+/// Frames a session may have queued before it is treated as a slow consumer.
+const MAILBOX_CAPACITY: usize = 256;
+/// Most recent frames kept for resuming sessions, across all topics.
+const REPLAY_CAPACITY: usize = 1024;
+/// How long a resume token stays valid after its session ends.
+const RESUME_TTL: Duration = Duration::from_secs(120);
+const MAX_TOPICS_PER_SESSION: usize = 64;
+/// Delay before the fan-out task reconnects to Redis.
+const FANOUT_RETRY_DELAY: Duration = Duration::from_secs(2);
+
+// ============================================================================
+// TOPICS AND FRAMES
+// ============================================================================
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Topic {
+    User(Uuid),
+    Match(Uuid),
+    Tournament(Uuid),
+    Presence(Uuid),
+    Spectate(Uuid),
+}
+
+impl fmt::Display for Topic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Topic::User(id) => write!(f, "{}", channels::user_channel(*id)),
+            Topic::Match(id) => write!(f, "{}", channels::match_channel(*id)),
+            Topic::Tournament(id) => write!(f, "{}", channels::tournament_channel(*id)),
+            Topic::Presence(id) => write!(f, "{}", channels::presence_channel(*id)),
+            Topic::Spectate(id) => write!(f, "{}", channels::spectate_channel(*id)),
+        }
+    }
+}
+
+impl FromStr for Topic {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, id) = s
+            .split_once(':')
+            .ok_or_else(|| format!("invalid topic: {}", s))?;
+        let id = Uuid::parse_str(id).map_err(|_| format!("invalid topic id: {}", s))?;
+        match kind {
+            "user" => Ok(Topic::User(id)),
+            "match" => Ok(Topic::Match(id)),
+            "tournament" => Ok(Topic::Tournament(id)),
+            "presence" => Ok(Topic::Presence(id)),
+            "spectate" => Ok(Topic::Spectate(id)),
+            _ => Err(format!("unknown topic: {}", s)),
+        }
+    }
+}
+
+/// Whether spectators see `event` when it is published on a match channel.
+/// Lobby chat and anything addressed to the players stays with them.
+fn visible_to_spectators(event: &RealtimeEvent) -> bool {
+    matches!(
+        event,
+        RealtimeEvent::MatchStatusChange { .. }
+            | RealtimeEvent::MatchCompleted { .. }
+            | RealtimeEvent::MatchDisputed { .. }
+            | RealtimeEvent::ChainEvent { .. }
+            | RealtimeEvent::LiveScoreUpdate { .. }
+    )
+}
+
+/// An event as delivered to clients. `seq` increases across all topics, so a
+/// client can drop duplicates and tell the gateway where it left off.
+#[derive(Debug, Clone, Serialize)]
+pub struct Frame {
+    pub topic: String,
+    pub seq: u64,
+    pub event: RealtimeEvent,
+}
+
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Deliver(pub Arc<Frame>);
+
+/// Sent when a session's mailbox is full; the session disconnects so the
+/// client can resume.
+#[derive(Message)]
+#[rtype(result = "()")]
+pub struct Lagged;
+
+// ============================================================================
+// REPLAY AND RESUME
+// ============================================================================
+
+/// Ring of the most recent frames.
+#[derive(Default)]
+struct ReplayBuffer {
+    frames: VecDeque<Arc<Frame>>,
+    /// Highest seq evicted from the ring.
+    evicted_through: u64,
+}
+
+impl ReplayBuffer {
+    fn push(&mut self, frame: Arc<Frame>) {
+        if self.frames.len() == REPLAY_CAPACITY {
+            if let Some(evicted) = self.frames.pop_front() {
+                self.evicted_through = evicted.seq;
+            }
+        }
+        self.frames.push_back(frame);
+    }
+
+    /// Frames after `last_seq` on `topics`, or `None` when some of them have
+    /// already been evicted.
+    fn since(&self, last_seq: u64, topics: &HashSet<Topic>) -> Option<Vec<Arc<Frame>>> {
+        if last_seq < self.evicted_through {
+            return None;
+        }
+        let names: HashSet<String> = topics.iter().map(Topic::to_string).collect();
+        Some(
+            self.frames
+                .iter()
+                .filter(|f| f.seq > last_seq && names.contains(&f.topic))
+                .cloned()
+                .collect(),
+        )
+    }
+}
+
+struct ResumeState {
+    user_id: Uuid,
+    topics: HashSet<Topic>,
+    last_seq: u64,
+    expires_at: Instant,
+}
+
+/// What a resumed session gets back.
+pub struct Resumed {
+    pub topics: HashSet<Topic>,
+    pub replay: Vec<Arc<Frame>>,
+    /// Topics whose missed events are gone; the client must refetch them.
+    pub resync: Vec<Topic>,
+}
+
+// ============================================================================
+// HUB
+// ============================================================================
+
+struct SessionEntry {
+    user_id: Uuid,
+    resume_token: String,
+    recipient: Recipient<Deliver>,
+    lagged: Recipient<Lagged>,
+    topics: HashSet<Topic>,
+    last_seq: u64,
+}
+
+#[derive(Default)]
+struct HubState {
+    seq: u64,
+    sessions: HashMap<Uuid, SessionEntry>,
+    subscribers: HashMap<Topic, HashSet<Uuid>>,
+    replay: ReplayBuffer,
+    resumable: HashMap<String, ResumeState>,
+}
+
+/// A match event waiting out the spectator delay.
+struct DelayedEvent {
+    due: Instant,
+    match_id: Uuid,
+    event: RealtimeEvent,
+}
+
+/// Routes events to gateway sessions and keeps what they need to resume.
+#[derive(Default)]
+pub struct GatewayHub {
+    state: Mutex<HubState>,
+    spectator_delay: Duration,
+}
+
+impl GatewayHub {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hold match events back from spectators for `delay`.
+    pub fn with_spectator_delay(mut self, delay: Duration) -> Self {
+        self.spectator_delay = delay;
+        self
+    }
+
+    /// Add a session subscribed to its user topic. With `resume`, it also takes
+    /// over the subscriptions and missed frames of an earlier session; the
+    /// token is single-use and must belong to `user_id`.
+    fn register(
+        &self,
+        session_id: Uuid,
+        user_id: Uuid,
+        resume_token: String,
+        recipient: Recipient<Deliver>,
+        lagged: Recipient<Lagged>,
+        resume: Option<&str>,
+    ) -> Option<Resumed> {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        state.resumable.retain(|_, r| r.expires_at > now);
+
+        let previous = resume
+            .and_then(|token| state.resumable.remove(token))
+            .filter(|r| r.user_id == user_id);
+
+        let mut topics = HashSet::from([Topic::User(user_id)]);
+        let resumed = previous.map(|previous| {
+            topics.extend(previous.topics.iter().copied());
+            match state.replay.since(previous.last_seq, &previous.topics) {
+                Some(replay) => Resumed {
+                    topics: topics.clone(),
+                    replay,
+                    resync: Vec::new(),
+                },
+                None => Resumed {
+                    topics: topics.clone(),
+                    replay: Vec::new(),
+                    resync: previous.topics.iter().copied().collect(),
+                },
+            }
+        });
+
+        for topic in &topics {
+            state
+                .subscribers
+                .entry(*topic)
+                .or_default()
+                .insert(session_id);
+        }
+        let last_seq = state.seq;
+        state.sessions.insert(
+            session_id,
+            SessionEntry {
+                user_id,
+                resume_token,
+                recipient,
+                lagged,
+                topics,
+                last_seq,
+            },
+        );
+        resumed
+    }
+
+    /// Remove a session, keeping its subscriptions resumable for [`RESUME_TTL`].
+    fn unregister(&self, session_id: Uuid, last_seq: u64) {
+        let mut state = self.state.lock().unwrap();
+        let Some(entry) = state.sessions.remove(&session_id) else {
+            return;
+        };
+        for topic in &entry.topics {
+            remove_subscriber(&mut state.subscribers, topic, session_id);
+        }
+        let last_seq = last_seq.max(entry.last_seq);
+        state.resumable.insert(
+            entry.resume_token,
+            ResumeState {
+                user_id: entry.user_id,
+                topics: entry.topics,
+                last_seq,
+                expires_at: Instant::now() + RESUME_TTL,
+            },
+        );
+    }
+
+    fn subscribe(&self, session_id: Uuid, topic: Topic) -> Result<(), &'static str> {
+        let mut state = self.state.lock().unwrap();
+        let Some(entry) = state.sessions.get_mut(&session_id) else {
+            return Err("Session closed");
+        };
+        if !entry.topics.contains(&topic) && entry.topics.len() >= MAX_TOPICS_PER_SESSION {
+            return Err("Too many subscriptions");
+        }
+        entry.topics.insert(topic);
+        state
+            .subscribers
+            .entry(topic)
+            .or_default()
+            .insert(session_id);
+        Ok(())
+    }
+
+    fn unsubscribe(&self, session_id: Uuid, topic: Topic) {
+        let mut state = self.state.lock().unwrap();
+        if let Some(entry) = state.sessions.get_mut(&session_id) {
+            entry.topics.remove(&topic);
+        }
+        remove_subscriber(&mut state.subscribers, &topic, session_id);
+    }
+
+    /// Deliver `event` to every session subscribed to `topic`. Sessions whose
+    /// mailbox is full are told to disconnect. A user told that a friend is
+    /// gone also stops following that friend's presence.
+    pub fn publish(&self, topic: Topic, event: RealtimeEvent) {
+        let targets: Vec<(Recipient<Deliver>, Recipient<Lagged>)>;
+        let frame;
+        {
+            let mut state = self.state.lock().unwrap();
+            if let (Topic::User(_), RealtimeEvent::FriendRemoved { user_id, .. }) = (topic, &event)
+            {
+                unfollow(&mut state, topic, Topic::Presence(*user_id));
+            }
+            state.seq += 1;
+            frame = Arc::new(Frame {
+                topic: topic.to_string(),
+                seq: state.seq,
+                event,
+            });
+            state.replay.push(frame.clone());
+
+            let Some(session_ids) = state.subscribers.get(&topic) else {
+                return;
+            };
+            targets = session_ids
+                .iter()
+                .filter_map(|id| state.sessions.get(id))
+                .map(|s| (s.recipient.clone(), s.lagged.clone()))
+                .collect();
+        }
+
+        for (recipient, lagged) in targets {
+            match recipient.try_send(Deliver(frame.clone())) {
+                Ok(()) => {}
+                Err(actix::prelude::SendError::Full(_)) => lagged.do_send(Lagged),
+                Err(actix::prelude::SendError::Closed(_)) => {}
+            }
+        }
+    }
+
+    /// Subscribe to the realtime Redis channels and feed every event into the
+    /// hub, reconnecting if the connection drops.
+    pub fn start_fanout(self: Arc<Self>, redis_url: String) -> JoinHandle<()> {
+        let (spectators, delayed) = mpsc::unbounded_channel();
+        tokio::spawn(self.clone().relay_to_spectators(delayed));
+        tokio::spawn(async move {
+            loop {
+                if let Err(e) = self.fanout(&redis_url, &spectators).await {
+                    error!(error = %e, "WebSocket gateway fan-out disconnected");
+                }
+                tokio::time::sleep(FANOUT_RETRY_DELAY).await;
+            }
+        })
+    }
+
+    /// Publish match events on their spectate topics once they are due. The
+    /// delay is the same for every event, so they come due in arrival order.
+    async fn relay_to_spectators(
+        self: Arc<Self>,
+        mut delayed: mpsc::UnboundedReceiver<DelayedEvent>,
+    ) {
+        while let Some(delayed) = delayed.recv().await {
+            tokio::time::sleep_until(delayed.due.into()).await;
+            self.publish(Topic::Spectate(delayed.match_id), delayed.event);
+        }
+    }
+
+    async fn fanout(
+        &self,
+        redis_url: &str,
+        spectators: &mpsc::UnboundedSender<DelayedEvent>,
+    ) -> Result<(), redis::RedisError> {
+        use futures_util::StreamExt;
+
+        let client = redis::Client::open(redis_url)?;
+        let mut pubsub = client.get_async_pubsub().await?;
+        for pattern in [
+            channels::USER_CHANNEL_PATTERN,
+            channels::MATCH_CHANNEL_PATTERN,
+            channels::TOURNAMENT_CHANNEL_PATTERN,
+            channels::PRESENCE_CHANNEL_PATTERN,
+        ] {
+            pubsub.psubscribe(pattern).await?;
+        }
+        info!("WebSocket gateway fan-out subscribed to Redis");
+
+        let mut stream = pubsub.on_message();
+        while let Some(msg) = stream.next().await {
+            let channel: String = msg.get_channel()?;
+            let payload: String = msg.get_payload()?;
+            let Ok(topic) = channel.parse::<Topic>() else {
+                continue;
+            };
+            match serde_json::from_str::<RealtimeEvent>(&payload) {
+                Ok(event) => {
+                    if let Topic::Match(match_id) = topic {
+                        if visible_to_spectators(&event) {
+                            let _ = spectators.send(DelayedEvent {
+                                due: Instant::now() + self.spectator_delay,
+                                match_id,
+                                event: event.clone(),
+                            });
+                        }
+                    }
+                    self.publish(topic, event)
+                }
+                Err(e) => warn!(channel = %channel, error = %e, "Dropping undecodable event"),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Unsubscribe every session following `via` from `topic`.
+fn unfollow(state: &mut HubState, via: Topic, topic: Topic) {
+    let session_ids: Vec<Uuid> = state
+        .subscribers
+        .get(&via)
+        .map(|ids| ids.iter().copied().collect())
+        .unwrap_or_default();
+    for session_id in session_ids {
+        if let Some(entry) = state.sessions.get_mut(&session_id) {
+            entry.topics.remove(&topic);
+        }
+        remove_subscriber(&mut state.subscribers, &topic, session_id);
+    }
+}
+
+fn remove_subscriber(
+    subscribers: &mut HashMap<Topic, HashSet<Uuid>>,
+    topic: &Topic,
+    session_id: Uuid,
+) {
+    if let Some(sessions) = subscribers.get_mut(topic) {
+        sessions.remove(&session_id);
+        if sessions.is_empty() {
+            subscribers.remove(topic);
+        }
+    }
+}
+
+// ============================================================================
+// SESSION
+// ============================================================================
+
+pub struct GatewaySession {
+    session_id: Uuid,
+    user_id: Uuid,
+    claims: Claims,
+    resume_token: String,
+    resume: Option<String>,
+    last_seq: u64,
+    hb: Instant,
+    hub: Arc<GatewayHub>,
+    auth: Arc<RealtimeAuth>,
+    presence: Arc<PresenceTracker>,
+    /// Matches followed through `spectate:` topics.
+    spectating: HashSet<Uuid>,
+}
+
+impl GatewaySession {
+    /// Keep the user online for another heartbeat window.
+    fn touch_presence(&self) {
+        let presence = self.presence.clone();
+        let (user_id, session_id) = (self.user_id, self.session_id);
+        actix::spawn(async move {
+            if let Err(e) = presence.touch(user_id, session_id).await {
+                warn!(user_id = %user_id, error = %e, "Failed to refresh presence");
+            }
+        });
+    }
+
+    /// Show the user as spectating `match_id`, or stop.
+    fn set_spectating(&mut self, match_id: Uuid, watching: bool) {
+        let changed = if watching {
+            self.spectating.insert(match_id)
+        } else {
+            self.spectating.remove(&match_id)
+        };
+        if !changed {
+            return;
+        }
+        let presence = self.presence.clone();
+        let user_id = self.user_id;
+        actix::spawn(async move {
+            let res = if watching {
+                presence
+                    .set_activity(user_id, PresenceStatus::Spectating, match_id)
+                    .await
+            } else {
+                presence.clear_activity(user_id, match_id).await
+            };
+            if let Err(e) = res {
+                warn!(user_id = %user_id, error = %e, "Failed to update spectating presence");
+            }
+        });
+    }
+
+    fn send_json(ctx: &mut ws::WebsocketContext<Self>, value: serde_json::Value) {
+        ctx.text(value.to_string());
+    }
+
+    fn send_frame(&mut self, ctx: &mut ws::WebsocketContext<Self>, frame: &Frame) {
+        if frame.seq <= self.last_seq {
+            return;
+        }
+        self.last_seq = frame.seq;
+        Self::send_json(
+            ctx,
+            serde_json::json!({
+                "type": "event",
+                "topic": frame.topic,
+                "seq": frame.seq,
+                "event": frame.event,
+            }),
+        );
+    }
+
+    fn start_heartbeat(&self, ctx: &mut ws::WebsocketContext<Self>) {
+        ctx.run_interval(HEARTBEAT_INTERVAL, |act, ctx| {
+            if Instant::now().duration_since(act.hb) > CLIENT_TIMEOUT {
+                warn!(session_id = %act.session_id, "Gateway heartbeat timeout, disconnecting");
+                ctx.stop();
+                return;
+            }
+            act.touch_presence();
+            ctx.ping(b"");
+        });
+    }
+
+    fn subscribe(&mut self, channel: String, ctx: &mut ws::WebsocketContext<Self>) {
+        let topic = match channel.parse::<Topic>() {
+            Ok(topic) => topic,
+            Err(reason) => {
+                Self::send_json(
+                    ctx,
+                    serde_json::json!({
+                        "type": "subscription_error",
+                        "channel": channel,
+                        "reason": reason,
+                    }),
+                );
+                return;
+            }
+        };
+
+        let auth = self.auth.clone();
+        let claims = self.claims.clone();
+        let fut = async move { auth.authorize_subscription(&claims, &channel).await };
+        ctx.wait(
+            actix::fut::wrap_future(fut).then(move |res, act: &mut Self, ctx| {
+                let res = res.map_err(|e| e.to_string()).and_then(|()| {
+                    act.hub
+                        .subscribe(act.session_id, topic)
+                        .map_err(str::to_string)
+                });
+                match res {
+                    Ok(()) => {
+                        if let Topic::Spectate(match_id) = topic {
+                            act.set_spectating(match_id, true);
+                        }
+                        Self::send_json(
+                            ctx,
+                            serde_json::json!({"type": "subscribed", "channel": topic.to_string()}),
+                        )
+                    }
+                    Err(reason) => Self::send_json(
+                        ctx,
+                        serde_json::json!({
+                            "type": "subscription_error",
+                            "channel": topic.to_string(),
+                            "reason": reason,
+                        }),
+                    ),
+                }
+                actix::fut::ready(())
+            }),
+        );
+    }
+}
+
+impl Actor for GatewaySession {
+    type Context = ws::WebsocketContext<Self>;
+
+    fn started(&mut self, ctx: &mut Self::Context) {
+        ctx.set_mailbox_capacity(MAILBOX_CAPACITY);
+        let address = ctx.address();
+        let resumed = self.hub.register(
+            self.session_id,
+            self.user_id,
+            self.resume_token.clone(),
+            address.clone().recipient(),
+            address.recipient(),
+            self.resume.as_deref(),
+        );
+        info!(
+            user_id = %self.user_id,
+            session_id = %self.session_id,
+            resumed = resumed.is_some(),
+            "Gateway session started"
+        );
+
+        let topics: Vec<String> = resumed
+            .as_ref()
+            .map(|r| r.topics.iter().map(Topic::to_string).collect())
+            .unwrap_or_else(|| vec![Topic::User(self.user_id).to_string()]);
+        Self::send_json(
+            ctx,
+            serde_json::json!({
+                "type": "welcome",
+                "session_id": self.session_id,
+                "resume_token": self.resume_token,
+                "resumed": resumed.is_some(),
+                "topics": topics,
+                "heartbeat_interval_secs": HEARTBEAT_INTERVAL.as_secs(),
+            }),
+        );
+        if let Some(resumed) = resumed {
+            for topic in &resumed.topics {
+                if let Topic::Spectate(match_id) = topic {
+                    self.set_spectating(*match_id, true);
+                }
+            }
+            for topic in resumed.resync {
+                Self::send_json(
+                    ctx,
+                    serde_json::json!({"type": "resync", "topic": topic.to_string()}),
+                );
+            }
+            for frame in resumed.replay {
+                self.send_frame(ctx, &frame);
+            }
+        }
+
+        self.touch_presence();
+        self.start_heartbeat(ctx);
+    }
+
+    fn stopped(&mut self, _ctx: &mut Self::Context) {
+        info!(
+            user_id = %self.user_id,
+            session_id = %self.session_id,
+            "Gateway session stopped"
+        );
+        self.hub.unregister(self.session_id, self.last_seq);
+        for match_id in self.spectating.clone() {
+            self.set_spectating(match_id, false);
+        }
+
+        let presence = self.presence.clone();
+        let (user_id, session_id) = (self.user_id, self.session_id);
+        actix::spawn(async move {
+            if let Err(e) = presence.disconnect(user_id, session_id).await {
+                warn!(user_id = %user_id, error = %e, "Failed to clear presence");
+            }
+        });
+    }
+}
+
+impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for GatewaySession {
+    fn handle(&mut self, msg: Result<ws::Message, ws::ProtocolError>, ctx: &mut Self::Context) {
+        let msg = match msg {
+            Ok(msg) => msg,
+            Err(e) => {
+                warn!(session_id = %self.session_id, error = %e, "Gateway protocol error");
+                ctx.stop();
+                return;
+            }
+        };
+
+        match msg {
+            ws::Message::Ping(data) => {
+                self.hb = Instant::now();
+                ctx.pong(&data);
+            }
+            ws::Message::Pong(_) => self.hb = Instant::now(),
+            ws::Message::Text(text) => {
+                self.hb = Instant::now();
+                match serde_json::from_str::<ClientMessage>(&text) {
+                    Ok(ClientMessage::Ping) => {
+                        Self::send_json(ctx, serde_json::json!({"type": "pong"}))
+                    }
+                    Ok(ClientMessage::Pong) => {}
+                    Ok(ClientMessage::Subscribe { channel }) => self.subscribe(channel, ctx),
+                    Ok(ClientMessage::Unsubscribe { channel }) => {
+                        if let Ok(topic) = channel.parse::<Topic>() {
+                            if topic != Topic::User(self.user_id) {
+                                self.hub.unsubscribe(self.session_id, topic);
+                            }
+                            if let Topic::Spectate(match_id) = topic {
+                                self.set_spectating(match_id, false);
+                            }
+                        }
+                        Self::send_json(
+                            ctx,
+                            serde_json::json!({"type": "unsubscribed", "channel": channel}),
+                        );
+                    }
+                    Ok(ClientMessage::Publish { .. }) => Self::send_json(
+                        ctx,
+                        serde_json::json!({
+                            "type": "error",
+                            "message": "Publishing is restricted to internal services",
+                        }),
+                    ),
+                    Err(_) => {
+                        debug!(session_id = %self.session_id, "Ignoring unrecognized message")
+                    }
+                }
+            }
+            ws::Message::Close(reason) => {
+                ctx.close(reason);
+                ctx.stop();
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Handler<Deliver> for GatewaySession {
+    type Result = ();
+
+    fn handle(&mut self, msg: Deliver, ctx: &mut Self::Context) {
+        self.send_frame(ctx, &msg.0);
+    }
+}
+
+impl Handler<Lagged> for GatewaySession {
+    type Result = ();
+
+    fn handle(&mut self, _msg: Lagged, ctx: &mut Self::Context) {
+        warn!(session_id = %self.session_id, "Gateway session fell behind, disconnecting");
+        ctx.close(Some(ws::CloseReason {
+            code: ws::CloseCode::Again,
+            description: Some("Too far behind; reconnect with your resume token".to_string()),
+        }));
+        ctx.stop();
+    }
+}
+
+// ============================================================================
+// HTTP
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+pub struct GatewayQuery {
+    pub token: String,
+    pub resume: Option<String>,
+}
+
+/// GET /api/ws?token=<jwt>[&resume=<token>]
+///
+/// Upgrade to a gateway WebSocket session.
+pub async fn ws_gateway(
+    req: HttpRequest,
+    stream: web::Payload,
+    query: web::Query<GatewayQuery>,
+    hub: web::Data<Arc<GatewayHub>>,
+    jwt_service: web::Data<Arc<JwtService>>,
+    auth_guard: web::Data<Arc<RealtimeAuth>>,
+    presence: web::Data<Arc<PresenceTracker>>,
+) -> Result<HttpResponse, Error> {
+    let query = query.into_inner();
+    let claims = jwt_service
+        .validate_token(&query.token)
+        .await
+        .map_err(|e| {
+            warn!(error = %e, "Gateway connection rejected: invalid token");
+            actix_web::error::ErrorUnauthorized(format!("Invalid token: {}", e))
+        })?;
+    let user_id = Uuid::parse_str(&claims.sub)
+        .map_err(|_| actix_web::error::ErrorUnauthorized("Invalid user ID in token"))?;
+
+    let session = GatewaySession {
+        session_id: Uuid::new_v4(),
+        user_id,
+        claims,
+        resume_token: Uuid::new_v4().simple().to_string(),
+        resume: query.resume,
+        last_seq: 0,
+        hb: Instant::now(),
+        hub: hub.get_ref().clone(),
+        auth: auth_guard.get_ref().clone(),
+        presence: presence.get_ref().clone(),
+        spectating: HashSet::new(),
+    };
+    ws::start(session, &req, stream)
+}
+
+pub fn configure_routes(cfg: &mut web::ServiceConfig) {
+    cfg.route("/ws", web::get().to(ws_gateway));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(topic: Topic, seq: u64) -> Arc<Frame> {
+        Arc::new(Frame {
+            topic: topic.to_string(),
+            seq,
+            event: RealtimeEvent::MatchDisputed {
+                match_id: Uuid::nil(),
+                reason: String::new(),
+                timestamp: String::new(),
+            },
+        })
+    }
+
+    #[test]
+    fn test_topic_round_trip() {
+        let id = Uuid::new_v4();
+        for topic in [
+            Topic::User(id),
+            Topic::Match(id),
+            Topic::Tournament(id),
+            Topic::Presence(id),
+            Topic::Spectate(id),
+        ] {
+            assert_eq!(topic.to_string().parse::<Topic>(), Ok(topic));
+        }
+        assert!("lobby:1".parse::<Topic>().is_err());
+        assert!("match:not-a-uuid".parse::<Topic>().is_err());
+    }
+
+    #[test]
+    fn test_removed_friend_is_unfollowed_by_that_user_only() {
+        let (user, friend) = (Uuid::new_v4(), Uuid::new_v4());
+        let (own_session, other_session) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut state = HubState::default();
+        state
+            .subscribers
+            .insert(Topic::User(user), HashSet::from([own_session]));
+        state.subscribers.insert(
+            Topic::Presence(friend),
+            HashSet::from([own_session, other_session]),
+        );
+
+        unfollow(&mut state, Topic::User(user), Topic::Presence(friend));
+        assert_eq!(
+            state.subscribers[&Topic::Presence(friend)],
+            HashSet::from([other_session])
+        );
+    }
+
+    #[test]
+    fn test_replay_returns_missed_frames_for_topics() {
+        let watched = Topic::Match(Uuid::new_v4());
+        let other = Topic::Tournament(Uuid::new_v4());
+        let mut buffer = ReplayBuffer::default();
+        for seq in 1..=6 {
+            buffer.push(frame(if seq % 2 == 0 { watched } else { other }, seq));
+        }
+
+        let replay = buffer.since(2, &HashSet::from([watched])).unwrap();
+        let seqs: Vec<u64> = replay.iter().map(|f| f.seq).collect();
+        assert_eq!(seqs, vec![4, 6]);
+    }
+
+    #[test]
+    fn test_replay_needs_resync_once_frames_are_evicted() {
+        let topic = Topic::Match(Uuid::new_v4());
+        let mut buffer = ReplayBuffer::default();
+        for seq in 1..=(REPLAY_CAPACITY as u64 + 10) {
+            buffer.push(frame(topic, seq));
+        }
+
+        let topics = HashSet::from([topic]);
+        assert!(buffer.since(5, &topics).is_none());
+        assert_eq!(buffer.since(10, &topics).unwrap().len(), REPLAY_CAPACITY);
+    }
+}
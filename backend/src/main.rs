@@ -17,16 +17,22 @@ mod telemetry;
 
 use crate::config::Config;
 use crate::db::{create_pool, run_startup_migrations};
+use crate::middleware::audit::AuditMiddleware;
 use crate::middleware::cors_middleware;
 use crate::middleware::idempotency_middleware::IdempotencyMiddleware;
-use crate::middleware::rate_limit::RateLimitMiddleware;
+use crate::middleware::metrics::MetricsMiddleware;
+use crate::middleware::rate_limit::{RateLimitMetrics, RateLimitMiddleware};
 use crate::middleware::security::{SecurityConfig, SecurityMiddleware};
+use crate::middleware::trace_context::TraceContextMiddleware;
 use crate::service::match_authority_service::MatchAuthorityService;
-use crate::service::ReaperService;
+use crate::service::{
+    ChainIndexer, ChainIndexerConfig, ReaperService, RelayerConfig, StellarRelayer,
+};
 use crate::realtime::event_bus::EventBus;
 use crate::realtime::session_registry::SessionRegistry;
 use crate::realtime::ws_broadcaster::{WsAddressBook, WsBroadcaster};
 use crate::service::matchmaker::{MatchmakerService, MatchmakingConfig, EloEngine};
+use crate::service::registration_service::RegistrationService;
 use crate::service::soroban_service::{NetworkConfig, SorobanService};
 use crate::service::tournament_service::TournamentService;
 use crate::telemetry::init_telemetry;
@@ -36,8 +42,8 @@ async fn main() -> io::Result<()> {
     // Load configuration
     let config = Config::from_env().expect("Failed to load configuration");
 
-    // Initialize telemetry
-    init_telemetry();
+    // Initialize telemetry; the guard flushes buffered spans on shutdown
+    let _telemetry = init_telemetry(&config.telemetry);
 
     // Create database pool
     let db_pool = create_pool(&config)
@@ -68,6 +74,61 @@ async fn main() -> io::Result<()> {
         .await
         .expect("Failed to create Redis connection manager");
 
+    // Outbound webhooks: match and tournament events published on the event
+    // bus are queued for subscribed integrator endpoints and sent by the
+    // delivery worker
+    let webhook_service = Arc::new(crate::service::webhooks::WebhookService::new(db_pool.clone()));
+    webhook_service.clone().run();
+
+    // Services publish realtime events to Redis through the event bus
+    let event_bus = EventBus::new(redis_conn.clone()).with_webhooks(webhook_service.clone());
+    // Who is online, playing or spectating, shared with friends over the gateway
+    let presence = Arc::new(crate::realtime::PresenceTracker::new(
+        redis_conn.clone(),
+        event_bus.clone(),
+    ));
+
+    // On-chain roles from the auth gateway, cached per user in Redis
+    let role_cache = Arc::new(crate::auth::rbac::RoleCache::new(
+        db_pool.clone(),
+        redis_conn.clone(),
+    ));
+
+    // Skill and earnings leaderboards, kept in Redis sorted sets; finished
+    // seasons are archived with their reward tiers by the rollover job
+    let leaderboard_service = Arc::new(crate::service::LeaderboardService::new(
+        db_pool.clone(),
+        redis_conn.clone(),
+    ));
+    leaderboard_service.clone().run();
+
+    // Player profiles, cached briefly in Redis
+    let profile_service = Arc::new(crate::service::ProfileService::new(
+        db_pool.clone(),
+        redis_conn.clone(),
+    ));
+
+    // Tournament and player search; synced to Meilisearch when configured
+    let search_service = Arc::new(crate::service::SearchService::new(
+        db_pool.clone(),
+        &config.search,
+    ));
+    search_service.clone().run();
+
+    // Spawn the chain indexer — tails contract events into Postgres,
+    // publishes them to the matching realtime channels, drops cached roles
+    // on role changes and credits reputation and payouts to the leaderboards
+    let chain_indexer = Arc::new(
+        ChainIndexer::new(
+            db_pool.clone(),
+            ChainIndexerConfig::from_stellar_config(&config.stellar),
+        )
+        .with_event_bus(event_bus.clone())
+        .with_role_cache(role_cache.clone())
+        .with_leaderboard(leaderboard_service.clone()),
+    );
+    chain_indexer.run();
+
     // Initialize matchmaking service — pass the shared ConnectionManager so
     // the service never opens a new connection per request.
     let matchmaking_config = MatchmakingConfig::default();
@@ -93,22 +154,70 @@ async fn main() -> io::Result<()> {
     // The network URL from config drives testnet vs mainnet selection.
     let soroban_network = NetworkConfig::custom(
         config.stellar.network_url.clone(),
-        if config.stellar.network_url.contains("testnet") {
-            "Test SDF Network ; September 2015".to_string()
-        } else {
-            "Public Global Stellar Network ; September 2015".to_string()
-        },
+        config.stellar.network_passphrase().to_string(),
     );
     let soroban_service = Arc::new(SorobanService::new(soroban_network));
 
+    // Relayer that signs and submits platform-initiated contract calls.
+    // Disabled when the admin or fee-bump secret is not a valid Stellar seed.
+    let stellar_relayer = match StellarRelayer::new(
+        db_pool.clone(),
+        RelayerConfig::from_stellar_config(&config.stellar),
+    ) {
+        Ok(relayer) => {
+            let relayer = Arc::new(relayer);
+            relayer.clone().run();
+            Some(relayer)
+        }
+        Err(e) => {
+            tracing::warn!("Stellar relayer disabled: {}", e);
+            None
+        }
+    };
+
     // Shared TournamentService wired with Soroban so distribute_prizes can
-    // execute real on-chain transfers via the prize contract.
-    let tournament_service = Arc::new(
-        TournamentService::new(db_pool.clone()).with_soroban(
+    // execute real on-chain transfers via the prize contract, and with the
+    // relayer so forfeited matches are cancelled on the match contract.
+    let mut tournament_service = TournamentService::new(db_pool.clone())
+        .with_soroban(
             soroban_service.clone(),
             config.stellar.soroban_contract_prize.clone(),
             config.stellar.admin_secret.clone(),
-        ),
+        )
+        .with_event_bus(event_bus.clone());
+    if let Some(relayer) = &stellar_relayer {
+        tournament_service = tournament_service.with_relayer(
+            relayer.clone(),
+            config.stellar.soroban_contract_match.clone(),
+        );
+    }
+    let tournament_service = Arc::new(tournament_service);
+    // Opens check-in windows before each round and forfeits no-shows.
+    tournament_service.clone().run_checkin_scheduler();
+
+    // Registration checks roles and stakes on chain through the relayer when
+    // it is available.
+    let mut registration_service =
+        RegistrationService::new(db_pool.clone(), tournament_service.clone());
+    if let Some(relayer) = &stellar_relayer {
+        registration_service = registration_service.with_relayer(relayer.clone(), &config.stellar);
+    }
+    let registration_service = Arc::new(registration_service);
+    let team_service = Arc::new(crate::service::TeamService::new(
+        db_pool.clone(),
+        registration_service.clone(),
+    ));
+    let friend_service = Arc::new(
+        crate::service::FriendService::new(db_pool.clone(), presence.clone())
+            .with_event_bus(event_bus.clone()),
+    );
+    let chat_service = Arc::new(
+        crate::service::ChatService::new(db_pool.clone(), redis_conn.clone(), &config.chat)
+            .with_event_bus(event_bus.clone()),
+    );
+    let spectator_service = Arc::new(
+        crate::service::SpectatorService::new(db_pool.clone(), &config.spectator)
+            .with_event_bus(event_bus.clone()),
     );
 
     // MatchAuthorityService — handles the on-chain match lifecycle FSM.
@@ -125,9 +234,135 @@ async fn main() -> io::Result<()> {
     let protocol_signer_secret =
         crate::http::match_authority_handler::SignerSecret(config.stellar.admin_secret.clone());
 
+    // Result reports upload evidence to object storage and, for staked
+    // matches, are submitted to the match contract through the relayer.
+    let object_storage = Arc::new(
+        crate::service::object_storage::ObjectStorage::new(&config.storage)
+            .expect("Invalid S3 storage configuration"),
+    );
+    let match_service = Arc::new(
+        crate::service::match_service::MatchService::new(db_pool.clone())
+            .with_event_bus(event_bus.clone())
+            .with_presence(presence.clone()),
+    );
+    let mut match_result_service = crate::service::match_result_service::MatchResultService::new(
+        db_pool.clone(),
+        match_service,
+        object_storage.clone(),
+    );
+    if let Some(relayer) = &stellar_relayer {
+        match_result_service = match_result_service
+            .with_relayer(relayer.clone(), config.stellar.soroban_contract_match.clone());
+    }
+    let match_result_service = Arc::new(match_result_service);
+
+    // Dispute cases and the referee console; decisions are executed on the
+    // dispute resolution contract when it is configured.
+    let mut dispute_service = crate::service::dispute_service::DisputeService::new(
+        db_pool.clone(),
+        object_storage.clone(),
+    );
+    if let Some(relayer) = &stellar_relayer {
+        dispute_service = dispute_service.with_relayer(relayer.clone(), &config.stellar);
+    }
+    let dispute_service = Arc::new(dispute_service);
+
+    // Multipart replay/screenshot uploads, verified and scanned before they
+    // can be attached to disputes; the sweep enforces retention.
+    let media_service = Arc::new(crate::service::MediaService::new(
+        db_pool.clone(),
+        object_storage,
+        &config.media,
+    ));
+    media_service.clone().run();
+
+    // Anti-cheat signal scoring and review queue; confirmed detections are
+    // flagged on the anti-cheat oracle when it is configured.
+    let mut anticheat_service =
+        crate::service::AntiCheatService::new(db_pool.clone(), &config.anticheat);
+    if let Some(relayer) = &stellar_relayer {
+        anticheat_service = anticheat_service.with_relayer(relayer.clone(), &config.stellar);
+    }
+    let anticheat_service = Arc::new(anticheat_service);
+
+    // Wallet service; with the relayer available it also holds XLM in custody,
+    // streaming deposits from Horizon and paying out withdrawals on chain.
+    let mut wallet_service = crate::service::wallet_service::WalletService::new(
+        Arc::new(db_pool.clone()),
+        Some(event_bus.clone()),
+    );
+    if let Some(relayer) = &stellar_relayer {
+        wallet_service = wallet_service.with_custody(relayer.clone(), &config.stellar);
+    }
+
+    // Identity verification; off without an encryption key for the personal
+    // details. While on, custodial withdrawals are limited by KYC level.
+    let kyc_service = config.kyc.encryption_key.as_deref().and_then(|key| {
+        match crate::service::kyc::KycService::new(db_pool.clone(), key, &config.kyc) {
+            Ok(kyc) => Some(Arc::new(kyc)),
+            Err(e) => {
+                tracing::warn!("KYC disabled: {}", e);
+                None
+            }
+        }
+    });
+    if kyc_service.is_some() {
+        wallet_service = wallet_service.with_kyc_limits(config.kyc.limits);
+    }
+    let wallet_service = Arc::new(wallet_service);
+    wallet_service.clone().run_custody();
+
+    // Payments API; payments left open by a restart are processed again.
+    let payment_service = Arc::new(crate::service::payment_service::PaymentService::new(
+        db_pool.clone(),
+        wallet_service.clone(),
+        event_bus.clone(),
+        config.redis.url.clone(),
+    ));
+    payment_service.clone().run();
+
+    // SEP-24 fiat on/off-ramps through the configured anchor, with the
+    // relayer's account as the custody account on the Stellar side.
+    let ramp_service = match (
+        &stellar_relayer,
+        crate::service::anchor::RampConfig::from_stellar_config(&config.stellar),
+    ) {
+        (Some(relayer), Some(ramp_config)) => {
+            match crate::service::anchor::RampService::new(
+                Arc::new(db_pool.clone()),
+                relayer.clone(),
+                ramp_config,
+            ) {
+                Ok(ramps) => {
+                    let ramps = Arc::new(ramps);
+                    ramps.clone().run();
+                    Some(ramps)
+                }
+                Err(e) => {
+                    tracing::warn!("Fiat on/off-ramps disabled: {}", e);
+                    None
+                }
+            }
+        }
+        _ => None,
+    };
+
     // Initialize real-time infrastructure
-    let event_bus = EventBus::new(redis_conn.clone());
     let session_registry = Arc::new(SessionRegistry::new());
+
+    // Staked matchmaking queue. Staked tiers open matches and escrows on
+    // chain through the relayer, so they stay disabled without it.
+    let mut matchmaking_service = crate::service::matchmaking::MatchmakingService::new(
+        db_pool.clone(),
+        redis_conn.clone(),
+    )
+    .with_event_bus(event_bus.clone())
+    .with_presence(presence.clone());
+    if let Some(relayer) = &stellar_relayer {
+        matchmaking_service = matchmaking_service.with_relayer(relayer.clone(), &config.stellar);
+    }
+    let matchmaking_service = Arc::new(matchmaking_service);
+    matchmaking_service.clone().run();
     let address_book = Arc::new(WsAddressBook::new());
 
     // Initialize Auth Services for Realtime
@@ -137,11 +372,177 @@ async fn main() -> io::Result<()> {
 
     // Build the AuthService used by HTTP handlers (refresh-token rotation,
     // session management, login, register, etc.)
-    let auth_service = crate::service::auth_service::AuthService::new(
+    let mut auth_service = crate::service::auth_service::AuthService::new(
         db_pool.clone(),
         crate::auth::jwt_service::JwtService::new(jwt_config, redis_conn.clone()),
     );
 
+    // TOTP two-factor authentication; disabled without an encryption key for
+    // the secrets.
+    let two_factor_service = crate::auth::two_factor::TwoFactorConfig::from_config(&config.auth)
+        .and_then(|two_factor_config| {
+            match crate::auth::two_factor::TwoFactorService::new(db_pool.clone(), two_factor_config) {
+                Ok(two_factor) => Some(Arc::new(two_factor)),
+                Err(e) => {
+                    tracing::warn!("Two-factor authentication disabled: {}", e);
+                    None
+                }
+            }
+        });
+    if let Some(two_factor) = &two_factor_service {
+        auth_service = auth_service.with_two_factor(two_factor.clone());
+    }
+
+    // Passkeys are stored per device; step-up for withdrawals accepts a
+    // passkey step-up token or a two-factor code.
+    let device_service = Arc::new(crate::auth::device_service::DeviceService::new(
+        db_pool.clone(),
+        Arc::new(redis_client.clone()),
+        None,
+    ));
+
+    // Password sign-ins are risk-scored and handled by the device security
+    // policies managed through the admin API.
+    let device_policies = Arc::new(
+        crate::auth::device_security_policies::DeviceSecurityPolicies::new(
+            db_pool.clone(),
+            device_service.clone(),
+        ),
+    );
+    auth_service = auth_service.with_device_policies(device_policies.clone());
+
+    // Admin console; bans also end the user's sessions. Every action is
+    // written to the audit log.
+    let admin_service = Arc::new(
+        crate::service::AdminService::new(
+            db_pool.clone(),
+            dispute_service.clone(),
+            wallet_service.clone(),
+        )
+        .with_sessions(jwt_service.clone()),
+    );
+
+    // Hash-chained audit trail: mutating API calls are recorded by the audit
+    // middleware, and compliance queries are served under /api/audit.
+    let audit_log_service = Arc::new(crate::service::AuditLogService::new(db_pool.clone()));
+
+    // Readiness probes and the Prometheus scrape; request latencies are
+    // recorded by the metrics middleware
+    let http_metrics = Arc::new(crate::telemetry::metrics::HttpMetrics::new());
+    let health_service = Arc::new(crate::service::health_service::HealthService::new(
+        db_pool.clone(),
+        redis_conn.clone(),
+        config.stellar.soroban_rpc_url.clone(),
+        http_metrics.clone(),
+        &config.telemetry,
+    ));
+
+    // Verification and password reset links are mailed through the configured
+    // transport (the log when none is set).
+    let mailer = Arc::new(
+        crate::service::mailer::Mailer::new(&config.mail).expect("Invalid mail configuration"),
+    );
+    let account_email = Arc::new(crate::auth::account_email::AccountEmailService::new(
+        db_pool.clone(),
+        redis_conn.clone(),
+        mailer.clone(),
+        &config.auth.jwt_secret,
+        config.mail.app_url.clone(),
+    ));
+
+    // Notifications land in the in-app inbox; email and FCM push deliveries
+    // are queued in Redis and retried by the delivery worker.
+    let push_gateway = Arc::new(
+        crate::service::push::PushGateway::new(config.notifications.fcm.as_ref())
+            .expect("Invalid FCM service account"),
+    );
+    let notification_service = Arc::new(
+        crate::service::notification_service::NotificationService::new(
+            db_pool.clone(),
+            redis_conn.clone(),
+            mailer,
+            push_gateway,
+            config.mail.app_url.clone(),
+        )
+        .with_event_bus(event_bus.clone())
+        .with_max_attempts(config.notifications.max_attempts),
+    );
+    notification_service.clone().run();
+
+    // Domain events: the outbox is drained onto the configured broker (Redis
+    // streams unless NATS or Kafka is set) read by the notification, webhook
+    // and analytics consumer groups
+    let event_broker = Arc::new(
+        crate::service::domain_events::EventBroker::connect(
+            &config.domain_events,
+            redis_conn.clone(),
+        )
+        .await
+        .expect("Failed to connect to the domain event broker"),
+    );
+    Arc::new(crate::service::domain_events::OutboxDispatcher::new(
+        db_pool.clone(),
+        event_broker.clone(),
+    ))
+    .run();
+    let domain_event_handlers: [Arc<dyn crate::service::domain_events::DomainEventHandler>; 3] = [
+        notification_service.clone(),
+        webhook_service.clone(),
+        Arc::new(crate::service::analytics_service::AnalyticsService::new(db_pool.clone())),
+    ];
+    for handler in domain_event_handlers {
+        crate::service::domain_events::EventConsumer::new(
+            db_pool.clone(),
+            event_broker.clone(),
+            handler,
+            &config.domain_events,
+        )
+        .run();
+    }
+
+    // Phone numbers are verified by SMS or WhatsApp through the provider
+    // routed for their region; withdrawals may require a verified one.
+    let phone_verification = Arc::new(
+        crate::auth::phone_verification::PhoneVerificationService::new(
+            db_pool.clone(),
+            redis_conn.clone(),
+            Arc::new(crate::service::sms::SmsGateway::new(&config.sms)),
+            config.sms.phone_verification_required,
+        ),
+    );
+    let passkey_service = Arc::new(crate::auth::webauthn::PasskeyService::new(
+        db_pool.clone(),
+        redis_conn.clone(),
+        device_service.clone(),
+        crate::auth::webauthn::WebAuthnConfig::from_config(&config.auth),
+    ));
+    let step_up = crate::auth::step_up::StepUp::new(
+        two_factor_service.clone(),
+        Some(passkey_service.clone()),
+    );
+
+    // Social sign-in; each provider is offered once its client is configured.
+    let oauth_service = Arc::new(crate::auth::oauth::OAuthService::new(
+        db_pool.clone(),
+        crate::auth::oauth::OAuthConfig::from_config(&config.auth),
+    ));
+
+    // SEP-10 wallet login; disabled when the signing secret is not a valid
+    // Stellar seed.
+    let sep10_service = match crate::auth::sep10::Sep10Service::new(
+        db_pool.clone(),
+        crate::auth::sep10::Sep10Config::from_config(&config.auth, &config.stellar),
+    ) {
+        Ok(sep10) => {
+            tracing::info!("SEP-10 signing key: {}", sep10.signing_key());
+            Some(Arc::new(sep10))
+        }
+        Err(e) => {
+            tracing::warn!("SEP-10 wallet login disabled: {}", e);
+            None
+        }
+    };
+
     // Start Redis Pub/Sub subscriber (broadcasts to local WebSocket actors)
     let broadcaster = WsBroadcaster::new(
         config.redis.url.clone(),
@@ -150,6 +551,12 @@ async fn main() -> io::Result<()> {
     );
     let _broadcaster_handles = broadcaster.start();
 
+    // WebSocket gateway for match, tournament and user topics
+    let gateway_hub = Arc::new(crate::http::ws::GatewayHub::new().with_spectator_delay(
+        std::time::Duration::from_secs(config.spectator.broadcast_delay_secs),
+    ));
+    let _gateway_fanout = gateway_hub.clone().start_fanout(config.redis.url.clone());
+
     tracing::info!(
         "Starting ArenaX backend server on {}:{}",
         config.server.host,
@@ -163,47 +570,74 @@ async fn main() -> io::Result<()> {
         App::new()
             .app_data(web::Data::new(db_pool.clone()))
             .app_data(web::Data::new(auth_service.clone()))
+            .app_data(web::Data::new(sep10_service.clone()))
+            .app_data(web::Data::new(two_factor_service.clone()))
+            .app_data(web::Data::new(device_service.clone()))
+            .app_data(web::Data::new(device_policies.clone()))
+            .app_data(web::Data::new(account_email.clone()))
+            .app_data(web::Data::new(phone_verification.clone()))
+            .app_data(web::Data::new(notification_service.clone()))
+            .app_data(web::Data::new(passkey_service.clone()))
+            .app_data(web::Data::new(step_up.clone()))
+            .app_data(web::Data::new(oauth_service.clone()))
             .app_data(web::Data::new(event_bus.clone()))
+            .app_data(web::Data::new(webhook_service.clone()))
+            .app_data(web::Data::new(leaderboard_service.clone()))
+            .app_data(web::Data::new(profile_service.clone()))
+            .app_data(web::Data::new(search_service.clone()))
             .app_data(web::Data::new(session_registry.clone()))
             .app_data(web::Data::new(address_book.clone()))
             .app_data(web::Data::new(jwt_service.clone()))
             .app_data(web::Data::new(auth_guard.clone()))
+            .app_data(web::Data::new(gateway_hub.clone()))
             .app_data(web::Data::new(matchmaker_service.clone()))
+            .app_data(web::Data::new(matchmaking_service.clone()))
             .app_data(web::Data::new(elo_engine.clone()))
             .app_data(web::Data::new(tournament_service.clone()))
+            .app_data(web::Data::new(registration_service.clone()))
+            .app_data(web::Data::new(team_service.clone()))
+            .app_data(web::Data::new(friend_service.clone()))
+            .app_data(web::Data::new(presence.clone()))
+            .app_data(web::Data::new(chat_service.clone()))
+            .app_data(web::Data::new(spectator_service.clone()))
             // Match authority service + protocol signer for on-chain match lifecycle
             .app_data(web::Data::new(match_authority_service.clone()))
+            .app_data(web::Data::new(match_result_service.clone()))
+            .app_data(web::Data::new(dispute_service.clone()))
+            .app_data(web::Data::new(media_service.clone()))
+            .app_data(web::Data::new(anticheat_service.clone()))
+            .app_data(web::Data::new(admin_service.clone()))
+            .app_data(web::Data::new(audit_log_service.clone()))
+            .app_data(web::Data::new(health_service.clone()))
+            .app_data(web::Data::new(wallet_service.clone()))
+            .app_data(web::Data::new(kyc_service.clone()))
+            .app_data(web::Data::new(ramp_service.clone()))
+            .app_data(web::Data::new(payment_service.clone()))
+            .app_data(web::Data::new(stellar_relayer.clone()))
             .app_data(web::Data::new(protocol_signer_secret.clone()))
+            .app_data(web::Data::new(RateLimitMetrics::new(redis_conn.clone())))
             .wrap(IdempotencyMiddleware::default(db_pool.clone()))
             .wrap(RateLimitMiddleware::new(redis_conn.clone(), rate_limit_config.clone()))
+            // Inside the role middleware so entries name the caller
+            .wrap(AuditMiddleware::new((*audit_log_service).clone()))
+            // Runs before rate limiting so authenticated requests are keyed on the user
+            .wrap(crate::auth::rbac::RoleMiddleware::new(jwt_service.clone(), role_cache.clone()))
             .wrap(SecurityMiddleware::new(redis_conn.clone(), SecurityConfig::default()))
             .wrap(cors_middleware())
+            .wrap(MetricsMiddleware::new(http_metrics.clone()))
             .wrap(actix_web::middleware::Logger::default())
+            // Outermost so every other middleware runs inside the request span
+            .wrap(TraceContextMiddleware::new())
+            // Liveness, readiness and /metrics
+            .configure(crate::http::health::configure_routes)
             .service(
                 web::scope("/api")
                     .route("/health", web::get().to(crate::http::health::health_check))
+                    .route("/rate-limits", web::get().to(crate::http::health::rate_limit_metrics))
+                    .configure(crate::http::ws::configure_routes)
                     // Auth endpoints (login, register, refresh are rate-limited strictly)
                     .configure(crate::http::auth_handler::configure_routes)
-                    .route(
-                        "/notifications",
-                        web::get().to(crate::http::notification_handler::get_notifications),
-                    )
-                    .route(
-                        "/notifications",
-                        web::post().to(crate::http::notification_handler::create_notification),
-                    )
-                    .route(
-                        "/notifications/read-all",
-                        web::patch().to(crate::http::notification_handler::mark_all_read),
-                    )
-                    .route(
-                        "/notifications/{id}/read",
-                        web::patch().to(crate::http::notification_handler::mark_notification_read),
-                    )
-                    .route(
-                        "/notifications/{id}",
-                        web::delete().to(crate::http::notification_handler::delete_notification),
-                    )
+                    .configure(crate::http::notification_handler::configure_routes)
                     // Wallet endpoints
                     .service(
                         web::scope("/wallet")
@@ -212,7 +646,28 @@ async fn main() -> io::Result<()> {
                             .route("/deposit", web::post().to(crate::http::wallet::initiate_deposit))
                             .route("/deposit/verify", web::post().to(crate::http::wallet::verify_deposit))
                             .route("/withdraw", web::post().to(crate::http::wallet::initiate_withdrawal))
+                            .route("/deposit-address", web::get().to(crate::http::wallet::get_deposit_address))
+                            .route("/withdrawals", web::post().to(crate::http::wallet::request_custodial_withdrawal))
+                            .route("/withdrawals", web::get().to(crate::http::wallet::list_custodial_withdrawals))
+                            .route("/withdrawals/pending", web::get().to(crate::http::wallet::list_pending_withdrawals))
+                            .route("/withdrawals/{id}/approve", web::post().to(crate::http::wallet::approve_withdrawal))
+                            .route("/withdrawals/{id}/reject", web::post().to(crate::http::wallet::reject_withdrawal))
+                            .route("/ramps", web::get().to(crate::http::wallet::list_ramps))
+                            .route("/ramps/deposit", web::post().to(crate::http::wallet::start_deposit_ramp))
+                            .route("/ramps/withdraw", web::post().to(crate::http::wallet::start_withdrawal_ramp))
+                            .route("/ramps/callback", web::post().to(crate::http::wallet::anchor_callback))
+                            .route("/ramps/{id}", web::get().to(crate::http::wallet::get_ramp))
                     )
+                    // Identity verification (KYC)
+                    .configure(crate::http::kyc_handler::configure_routes)
+                    .configure(crate::http::webhook_handler::configure_routes)
+                    .configure(crate::http::leaderboard_handler::configure_routes)
+                    .configure(crate::http::player_handler::configure_routes)
+                    .configure(crate::http::search_handler::configure_routes)
+                    .configure(crate::http::team_handler::configure_routes)
+                    .configure(crate::http::friend_handler::configure_routes)
+                    .configure(crate::http::chat_handler::configure_routes)
+                    .configure(crate::http::spectator_handler::configure_routes)
                     // Reputation endpoints
                     .route(
                         "/reputation/player/{user_id}",
@@ -254,8 +709,23 @@ async fn main() -> io::Result<()> {
                     )
                     // Tournament endpoints — full lifecycle
                     .configure(crate::http::tournament_handler::configure_routes)
+                    // Match result reports with evidence; registered before the
+                    // `/matches` scope so it is not shadowed by it.
+                    .configure(crate::http::matches::configure_routes)
                     // Match authority endpoints — on-chain match FSM
                     .configure(crate::http::match_authority_handler::configure_routes)
+                    // Dispute cases and referee console
+                    .configure(crate::http::disputes::configure_routes)
+                    // Replay/screenshot uploads and signed evidence downloads
+                    .configure(crate::http::media_handler::configure_routes)
+                    // Anti-cheat telemetry, review queue and oracle flags
+                    .configure(crate::http::anticheat_handler::configure_routes)
+                    // Admin console: bans, overrides, escalations, payouts, toggles
+                    .configure(crate::http::admin_handler::configure_routes)
+                    // Audit trail search and hash chain verification
+                    .configure(crate::http::audit_handler::configure_routes)
+                    // Payments with idempotency keys and SSE status streams
+                    .configure(crate::http::payments::configure_routes)
                     // Gas endpoints
                     .service(
                         web::scope("/gas")
@@ -270,6 +740,9 @@ async fn main() -> io::Result<()> {
                             .route("/stats", web::get().to(crate::http::matchmaking::get_matchmaking_stats))
                             .route("/elo/{game}", web::get().to(crate::http::matchmaking::get_elo))
                             .route("/elo/{game}/{page}/{limit}", web::get().to(crate::http::matchmaking::get_elo_history))
+                            .route("/staked", web::post().to(crate::http::matchmaking::join_staked_queue))
+                            .route("/staked", web::delete().to(crate::http::matchmaking::leave_staked_queue))
+                            .route("/staked", web::get().to(crate::http::matchmaking::get_staked_queue_status))
                     )
                     // Idempotency endpoints
                     .service(
@@ -0,0 +1,209 @@
+/// Audit trail of mutating API calls.
+///
+/// Every POST, PUT, PATCH and DELETE is appended to the hash-chained
+/// `audit_logs` (see [`crate::service::audit_log`]) once the response is
+/// ready: who made it, from where, what it hit, how it ended and how long it
+/// took. Request and response bodies are never recorded.
+///
+/// Each call is tagged with the `X-Request-Id` it came with, or a fresh one,
+/// and the id is echoed on the response so clients can quote it. The entry
+/// is written in the background; a failed write is logged and never fails
+/// the request.
+///
+/// Must be wrapped inside the role middleware so the caller's claims are
+/// known.
+use std::{
+    future::{ready, Ready},
+    net::IpAddr,
+    rc::Rc,
+    time::Instant,
+};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue, USER_AGENT},
+    Error,
+};
+use futures_util::future::LocalBoxFuture;
+use tracing::error;
+use uuid::Uuid;
+
+use crate::auth::middleware::ClaimsExt;
+use crate::service::audit_log::{AuditLogService, AuditRecord, RequestMeta};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+const MAX_REQUEST_ID_LEN: usize = 128;
+
+// ─── Transform (factory) ──────────────────────────────────────────────────────
+
+pub struct AuditMiddleware {
+    audit: AuditLogService,
+}
+
+impl AuditMiddleware {
+    pub fn new(audit: AuditLogService) -> Self {
+        Self { audit }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for AuditMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = AuditMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(AuditMiddlewareService {
+            service: Rc::new(service),
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+// ─── Service ──────────────────────────────────────────────────────────────────
+
+pub struct AuditMiddlewareService<S> {
+    service: Rc<S>,
+    audit: AuditLogService,
+}
+
+impl<S, B> Service<ServiceRequest> for AuditMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+        if !method_is_mutating(req.method().as_str()) {
+            return Box::pin(async move { svc.call(req).await });
+        }
+        let audit = self.audit.clone();
+        let start = Instant::now();
+
+        Box::pin(async move {
+            let request_id = req
+                .headers()
+                .get(REQUEST_ID_HEADER)
+                .and_then(|v| v.to_str().ok())
+                .and_then(clean_request_id)
+                .unwrap_or_else(|| Uuid::new_v4().to_string());
+            let mut meta = RequestMeta {
+                method: req.method().to_string(),
+                path: req.path().to_string(),
+                ip: client_ip(&req),
+                user_agent: req
+                    .headers()
+                    .get(USER_AGENT)
+                    .and_then(|v| v.to_str().ok())
+                    .map(str::to_string),
+                request_id: Some(request_id.clone()),
+                ..RequestMeta::default()
+            };
+
+            let result = svc.call(req).await;
+            meta.latency_ms = start.elapsed().as_millis().min(u32::MAX as u128) as u32;
+            let actor_id = match &result {
+                Ok(res) => {
+                    meta.status = res.status().as_u16();
+                    res.request().user_id()
+                }
+                Err(e) => {
+                    meta.status = e.as_response_error().status_code().as_u16();
+                    None
+                }
+            };
+
+            tokio::spawn(async move {
+                let path = meta.path.clone();
+                let entry = AuditRecord {
+                    actor_id,
+                    action: "http_request",
+                    resource_type: "api",
+                    request: Some(meta),
+                    ..AuditRecord::default()
+                };
+                if let Err(e) = audit.record(entry).await {
+                    error!(error = %e, path = %path, "Failed to write audit entry");
+                }
+            });
+
+            let mut res = result?;
+            if let Ok(value) = HeaderValue::from_str(&request_id) {
+                res.headers_mut()
+                    .insert(HeaderName::from_static(REQUEST_ID_HEADER), value);
+            }
+            Ok(res)
+        })
+    }
+}
+
+// ─── Helpers ──────────────────────────────────────────────────────────────────
+
+fn method_is_mutating(method: &str) -> bool {
+    matches!(method, "POST" | "PUT" | "PATCH" | "DELETE")
+}
+
+/// The caller's address, from `X-Forwarded-For` (set by the trusted reverse
+/// proxy) or the peer. Anything that is not an address is dropped.
+fn client_ip(req: &ServiceRequest) -> Option<IpAddr> {
+    let forwarded = req
+        .headers()
+        .get("x-forwarded-for")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| s.split(',').next())
+        .map(|s| s.trim().to_string());
+    let addr = forwarded.or_else(|| {
+        req.connection_info()
+            .realip_remote_addr()
+            .map(|s| s.to_string())
+    })?;
+    parse_ip(&addr)
+}
+
+/// An address as written in a header: bare, `[v6]:port` or `v4:port`.
+fn parse_ip(addr: &str) -> Option<IpAddr> {
+    if let Ok(ip) = addr.parse() {
+        return Some(ip);
+    }
+    addr.parse::<std::net::SocketAddr>().ok().map(|a| a.ip())
+}
+
+/// A client-supplied request id, if it is short and printable.
+fn clean_request_id(id: &str) -> Option<String> {
+    let id = id.trim();
+    let ok = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID_LEN
+        && id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.' | ':'));
+    ok.then(|| id.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_ip_and_request_id() {
+        assert_eq!(parse_ip("203.0.113.7"), "203.0.113.7".parse().ok());
+        assert_eq!(parse_ip("203.0.113.7:5123"), "203.0.113.7".parse().ok());
+        assert_eq!(parse_ip("[2001:db8::1]:443"), "2001:db8::1".parse().ok());
+        assert_eq!(parse_ip("unknown"), None);
+
+        assert_eq!(clean_request_id(" abc-123 ").as_deref(), Some("abc-123"));
+        assert_eq!(clean_request_id(""), None);
+        assert_eq!(clean_request_id("a b"), None);
+        assert_eq!(clean_request_id(&"x".repeat(129)), None);
+    }
+}
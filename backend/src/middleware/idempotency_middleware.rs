@@ -1,13 +1,14 @@
 use crate::api_error::ApiError;
 use crate::db::DbPool;
 use crate::models::idempotency::*;
-use actix_web::{dev, http::header::HeaderMap, web, HttpMessage, HttpRequest, HttpResponse};
+use actix_web::{body::EitherBody, dev, http::header::HeaderMap, web, HttpRequest, HttpResponse};
 use chrono::{Duration, Utc};
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
-use std::future::Ready;
-use std::pin::Pin;
+use futures_util::future::LocalBoxFuture;
+use std::future::{ready, Ready};
+use std::rc::Rc;
 use uuid::Uuid;
 
 pub struct IdempotencyMiddleware {
@@ -61,9 +62,7 @@ impl IdempotencyMiddleware {
         hasher.update(req.path());
         
         // Hash query parameters
-        if let Some(query) = req.query_string() {
-            hasher.update(query);
-        }
+        hasher.update(req.query_string());
         
         // Hash relevant headers (exclude idempotency key itself)
         for (name, value) in req.headers().iter() {
@@ -246,29 +245,29 @@ impl IdempotencyMiddleware {
 impl<S, B> dev::Transform<S, dev::ServiceRequest> for IdempotencyMiddleware
 where
     S: dev::Service<
-        dev::ServiceRequest,
-        Response = dev::ServiceResponse<B>,
-        Error = actix_web::Error,
-    >,
-    S::Future: 'static,
-    B: dev::MessageBody + 'static,
+            dev::ServiceRequest,
+            Response = dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: 'static,
 {
-    type Response = dev::ServiceResponse<B>;
+    type Response = dev::ServiceResponse<EitherBody<B>>;
     type Error = actix_web::Error;
     type Transform = IdempotencyService<S>;
     type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
 
-    fn new_transform(&self, service: S) -> Result<Self::Transform, Self::InitError> {
-        Ok(IdempotencyService {
-            service,
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(IdempotencyService {
+            service: Rc::new(service),
             policy: self.policy.clone(),
             db_pool: self.db_pool.clone(),
-        })
+        }))
     }
 }
 
 pub struct IdempotencyService<S> {
-    service: S,
+    service: Rc<S>,
     policy: IdempotencyPolicy,
     db_pool: DbPool,
 }
@@ -276,67 +275,59 @@ pub struct IdempotencyService<S> {
 impl<S, B> dev::Service<dev::ServiceRequest> for IdempotencyService<S>
 where
     S: dev::Service<
-        dev::ServiceRequest,
-        Response = dev::ServiceResponse<B>,
-        Error = actix_web::Error,
-    >,
-    S::Future: 'static,
-    B: dev::MessageBody + 'static,
+            dev::ServiceRequest,
+            Response = dev::ServiceResponse<B>,
+            Error = actix_web::Error,
+        > + 'static,
+    B: 'static,
 {
-    type Response = dev::ServiceResponse<B>;
+    type Response = dev::ServiceResponse<EitherBody<B>>;
     type Error = actix_web::Error;
-    type Future = Pin<Box<dyn std::future::Future<Output = Result<Self::Response, Self::Error>>>>;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
 
     dev::forward_ready!(service);
 
     fn call(&self, mut req: dev::ServiceRequest) -> Self::Future {
-        let policy = self.policy.clone();
-        let db_pool = self.db_pool.clone();
+        let service = self.service.clone();
+        let middleware = IdempotencyMiddleware::new(self.db_pool.clone(), self.policy.clone());
 
         Box::pin(async move {
             // Only process enabled routes
-            if !policy.enabled_routes.iter().any(|pattern| {
-                req.path().starts_with(pattern) || req.path() == pattern
-            }) {
-                return self.service.call(req).await;
+            if !middleware.is_route_enabled(req.path()) {
+                return Ok(service.call(req).await?.map_into_left_body());
             }
 
             // Extract idempotency key
-            let idempotency_key = match Self::extract_idempotency_key(&req.headers(), &policy) {
+            let idempotency_key = match middleware.extract_idempotency_key(req.headers()) {
                 Ok(key) => key,
                 Err(e) => {
                     let error_response = HttpResponse::BadRequest().json(serde_json::json!({
                         "error": "InvalidIdempotencyKey",
-                        "message": e.message
+                        "message": e.to_string()
                     }));
-                    return Ok(req.into_response(error_response));
+                    return Ok(req.into_response(error_response).map_into_right_body());
                 }
             };
 
-            // Generate request hash
-            let request_body = match req.extract_body().await {
-                Ok(bytes) => bytes.to_vec(),
-                Err(_) => Vec::new(),
-            };
-            
-            let request_hash = Self::generate_request_hash(&req, &request_body);
+            // Read the body for hashing, then hand it back to the request
+            let request_body = req.extract::<web::Bytes>().await.unwrap_or_default();
+            let request_hash = middleware.generate_request_hash(req.request(), &request_body);
+            req.set_payload(dev::Payload::from(request_body));
 
             // Check for cached response
-            let middleware = IdempotencyMiddleware::new(db_pool.clone(), policy.clone());
-            
             if let Ok(Some(cached)) = middleware.get_cached_response(&idempotency_key).await {
                 let cached_response = middleware.build_cached_response(cached);
-                return Ok(req.into_response(cached_response));
+                return Ok(req.into_response(cached_response).map_into_right_body());
             }
 
             // Check for conflicts
             if let Ok(Some(conflict)) = middleware.check_conflict(&idempotency_key, &request_hash).await {
                 let conflict_response = middleware.build_conflict_response(conflict);
-                return Ok(req.into_response(conflict_response));
+                return Ok(req.into_response(conflict_response).map_into_right_body());
             }
 
             // Process the request
-            let response = self.service.call(req).await?;
+            let response = service.call(req).await?;
             
             // Cache the response if it's successful
             if response.status().is_success() {
@@ -376,76 +367,7 @@ where
                 }
             }
 
-            Ok(response)
+            Ok(response.map_into_left_body())
         })
     }
 }
-
-// Extension methods for the middleware
-impl<S, B> IdempotencyService<S>
-where
-    S: dev::Service<
-        dev::ServiceRequest,
-        Response = dev::ServiceResponse<B>,
-        Error = actix_web::Error,
-    >,
-    S::Future: 'static,
-    B: dev::MessageBody + 'static,
-{
-    fn extract_idempotency_key(headers: &HeaderMap, policy: &IdempotencyPolicy) -> Result<String, ApiError> {
-        let key_header = headers
-            .get(&policy.key_header_name)
-            .ok_or_else(|| {
-                ApiError::bad_request(&format!(
-                    "Missing required header: {}",
-                    policy.key_header_name
-                ))
-            })?;
-
-        let key_str = key_header.to_str().map_err(|_| {
-            ApiError::bad_request(&format!(
-                "Invalid {} header format",
-                policy.key_header_name
-            ))
-        })?;
-
-        if key_str.trim().is_empty() {
-            return Err(ApiError::bad_request(&format!(
-                "{} header cannot be empty",
-                policy.key_header_name
-            )));
-        }
-
-        Ok(key_str.to_string())
-    }
-
-    fn generate_request_hash(req: &HttpRequest, body: &[u8]) -> String {
-        let mut hasher = Sha256::new();
-        
-        // Hash method
-        hasher.update(req.method().as_str());
-        
-        // Hash path
-        hasher.update(req.path());
-        
-        // Hash query parameters
-        if let Some(query) = req.query_string() {
-            hasher.update(query);
-        }
-        
-        // Hash relevant headers (exclude idempotency key itself)
-        for (name, value) in req.headers().iter() {
-            if name.as_str() != "Idempotency-Key" {
-                if let Ok(value_str) = value.to_str() {
-                    hasher.update(name.as_str());
-                    hasher.update(value_str);
-                }
-            }
-        }
-        
-        // Hash body
-        hasher.update(body);
-        
-        format!("{:x}", hasher.finalize())
-    }
-}
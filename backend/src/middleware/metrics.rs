@@ -0,0 +1,92 @@
+/// Request count and latency metrics.
+///
+/// Every request is timed and recorded in [`HttpMetrics`] under its route
+/// pattern (`/api/matches/{id}`, not the path itself) and response status.
+/// Requests that match no route are recorded as `unmatched`.
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+    sync::Arc,
+    time::Instant,
+};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    Error,
+};
+use futures_util::future::LocalBoxFuture;
+
+use crate::telemetry::metrics::HttpMetrics;
+
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+// ─── Transform (factory) ──────────────────────────────────────────────────────
+
+pub struct MetricsMiddleware {
+    metrics: Arc<HttpMetrics>,
+}
+
+impl MetricsMiddleware {
+    pub fn new(metrics: Arc<HttpMetrics>) -> Self {
+        Self { metrics }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for MetricsMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = MetricsMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(MetricsMiddlewareService {
+            service: Rc::new(service),
+            metrics: self.metrics.clone(),
+        }))
+    }
+}
+
+// ─── Service ──────────────────────────────────────────────────────────────────
+
+pub struct MetricsMiddlewareService<S> {
+    service: Rc<S>,
+    metrics: Arc<HttpMetrics>,
+}
+
+impl<S, B> Service<ServiceRequest> for MetricsMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+        let metrics = self.metrics.clone();
+        let start = Instant::now();
+
+        Box::pin(async move {
+            let method = req.method().to_string();
+            let route = req
+                .match_pattern()
+                .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+
+            let result = svc.call(req).await;
+            let status = match &result {
+                Ok(res) => res.status(),
+                Err(e) => e.as_response_error().status_code(),
+            };
+            metrics.observe(&method, &route, status.as_u16(), start.elapsed());
+            result
+        })
+    }
+}
@@ -1,11 +1,17 @@
 // Middleware module for ArenaX
+pub mod audit;
 pub mod idempotency_middleware;
+pub mod metrics;
 pub mod rate_limit;
 pub mod security;
+pub mod trace_context;
 
+pub use audit::AuditMiddleware;
 pub use idempotency_middleware::IdempotencyMiddleware;
+pub use metrics::MetricsMiddleware;
 pub use rate_limit::RateLimitMiddleware;
 pub use security::SecurityMiddleware;
+pub use trace_context::TraceContextMiddleware;
 
 use actix_cors::Cors;
 use std::env;
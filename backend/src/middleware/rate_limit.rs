@@ -17,22 +17,37 @@
 //!
 //! # Endpoint buckets and limits
 //!
-//! | Bucket prefix  | Matching paths              | Limit | Window |
-//! |----------------|-----------------------------|-------|--------|
-//! | `auth_strict`  | `/api/auth/login`           |  5    | 60 s   |
-//! | `auth_strict`  | `/api/auth/register`        |  5    | 60 s   |
-//! | `auth_strict`  | `/api/auth/refresh`         |  10   | 60 s   |
-//! | `auth`         | Other `/api/auth/*`         |  30   | 60 s   |
-//! | `game`         | `/api/matchmaking/*`        |  60   | 60 s   |
-//! | `game`         | `/api/matches/*`            |  60   | 60 s   |
-//! | `game`         | `/api/tournaments/*`        |  60   | 60 s   |
-//! | `default`      | Everything else             | configurable (from env) |
+//! | Bucket prefix  | Matching paths                      | Limit | Window |
+//! |----------------|-------------------------------------|-------|--------|
+//! | `auth_strict`  | `/api/auth/login`                   |  5    | 60 s   |
+//! | `auth_strict`  | `/api/auth/register`                |  5    | 60 s   |
+//! | `auth_strict`  | `/api/auth/refresh`                 |  10   | 60 s   |
+//! | `auth_strict`  | Email/phone verification, reset     |  5    | 60 s   |
+//! | `auth`         | Other `/api/auth/*`                 |  30   | 60 s   |
+//! | `payments`     | Payments, deposits, withdrawals     |  10   | 60 s   |
+//! | `match_report` | Match results, disputes, finalizing |  30   | 60 s   |
+//! | `game`         | `/api/matchmaking/*`                |  60   | 60 s   |
+//! | `game`         | `/api/matches/*`                    |  60   | 60 s   |
+//! | `game`         | `/api/tournaments/*`                |  60   | 60 s   |
+//! | `default`      | Everything else                     | configurable (from env) |
+//!
+//! Any class can be retuned with `RATE_LIMIT_POLICIES` (see
+//! [`RateLimitConfig`]).
 //!
 //! # Identity
 //!
 //! - Authenticated requests: keyed on user ID (from JWT `Claims` in extensions).
 //! - Unauthenticated requests: keyed on client IP.
 //!
+//! On top of its class limit, every request counts against the `ip` bucket of
+//! its client IP, so one address cannot get around the limits by rotating
+//! accounts.
+//!
+//! # Metrics
+//!
+//! Rejections are counted per bucket in the Redis hash `rl:metrics:limited`;
+//! [`RateLimitMetrics`] reads them back.
+//!
 //! # Headers
 //!
 //! Every response gets:
@@ -54,15 +69,22 @@ use actix_web::{
     body::EitherBody,
     dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
     http::header::{HeaderName, HeaderValue},
-    HttpResponse,
+    HttpMessage, HttpResponse,
 };
 use futures_util::future::LocalBoxFuture;
+use rand::Rng;
 use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::collections::HashMap;
 use tracing::warn;
 
+use crate::api_error::ApiError;
 use crate::auth::jwt_service::Claims;
 use crate::config::RateLimitConfig;
 
+/// Redis hash of rejection counts, keyed by bucket name.
+const METRICS_KEY: &str = "rl:metrics:limited";
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Bucket definitions
 // ─────────────────────────────────────────────────────────────────────────────
@@ -85,16 +107,40 @@ fn resolve_bucket(path: &str, default_limit: u32, default_window: u64) -> Bucket
     // Auth — strict (login, register, refresh are the highest-risk)
     if path == "/api/auth/login"
         || path == "/api/auth/register"
-        || path == "/api/auth/refresh"
+        || path == "/api/auth/passkeys/login"
+        || path.starts_with("/api/auth/verify-email")
+        || path.starts_with("/api/auth/reset-password")
+        || path.starts_with("/api/auth/phone/")
+        || (path.starts_with("/api/auth/oauth/") && path.ends_with("/callback"))
     {
         return Bucket { name: "auth_strict", limit: 5, window_secs: 60 };
     }
+    // Refreshes are routine, so they get more room in the same window.
+    if path == "/api/auth/refresh" {
+        return Bucket { name: "auth_strict", limit: 10, window_secs: 60 };
+    }
 
     // Auth — other (logout, me, change-password, sessions…)
     if path.starts_with("/api/auth") {
         return Bucket { name: "auth", limit: 30, window_secs: 60 };
     }
 
+    // Payments — anything that moves money
+    if path == "/api/payments"
+        || (path.starts_with("/api/payments/") && path.ends_with("/cancel"))
+        || matches!(
+            path,
+            "/api/wallet/deposit"
+                | "/api/wallet/deposit/verify"
+                | "/api/wallet/withdraw"
+                | "/api/wallet/withdrawals"
+                | "/api/wallet/ramps/deposit"
+                | "/api/wallet/ramps/withdraw"
+        )
+    {
+        return Bucket { name: "payments", limit: 10, window_secs: 60 };
+    }
+
     // Game — matchmaking (join/leave are mutation-heavy; stats/status are read)
     if path == "/api/matchmaking/join" || path == "/api/matchmaking/leave" {
         return Bucket { name: "matchmaking_mutate", limit: 20, window_secs: 60 };
@@ -104,7 +150,11 @@ fn resolve_bucket(path: &str, default_limit: u32, default_window: u64) -> Bucket
     }
 
     // Game — score reporting / match lifecycle (complete, dispute, finalize)
-    if path.contains("/complete") || path.contains("/dispute") || path.contains("/finalize") {
+    if path.contains("/complete")
+        || path.contains("/dispute")
+        || path.contains("/finalize")
+        || (path.starts_with("/api/matches/") && path.ends_with("/result"))
+    {
         return Bucket { name: "match_report", limit: 30, window_secs: 60 };
     }
     if path.starts_with("/api/matches") {
@@ -125,6 +175,21 @@ fn resolve_bucket(path: &str, default_limit: u32, default_window: u64) -> Bucket
     Bucket { name: "default", limit: default_limit, window_secs: default_window }
 }
 
+/// Resolve the bucket for `path` with the configured overrides applied.
+fn configured_bucket(path: &str, config: &RateLimitConfig) -> Bucket {
+    let mut bucket = resolve_bucket(path, config.requests, config.window);
+    if let Some(policy) = config.policies.get(bucket.name) {
+        bucket.limit = policy.requests;
+        bucket.window_secs = policy.window;
+    }
+    bucket
+}
+
+/// The per-IP ceiling shared by all endpoints.
+fn ip_bucket(config: &RateLimitConfig) -> Bucket {
+    Bucket { name: "ip", limit: config.ip_requests, window_secs: config.ip_window }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // Redis sliding-window check
 // ─────────────────────────────────────────────────────────────────────────────
@@ -148,13 +213,16 @@ async fn sliding_window_check(
     // Use a pipeline: ZADD → ZREMRANGEBYSCORE → ZCARD → EXPIRE
     // We need ZCARD result, so we can't use a fire-and-forget pipeline;
     // instead use individual pipelined commands and collect.
+    // Random suffix so concurrent requests in the same millisecond all count
+    let member = format!("{}-{:08x}", now_ms, rand::thread_rng().gen::<u32>());
+
     let result: Result<(i64, i64, i64, i64), redis::RedisError> = redis::pipe()
         .atomic()
-        // 1. Add this request (score = timestamp_ms, member = timestamp_ms as string)
+        // 1. Add this request (score = timestamp_ms, member = unique per request)
         .cmd("ZADD")
             .arg(&key)
             .arg(now_ms)
-            .arg(now_ms.to_string())
+            .arg(&member)
         // 2. Remove entries outside the window
         .cmd("ZREMRANGEBYSCORE")
             .arg(&key)
@@ -180,6 +248,37 @@ async fn sliding_window_check(
     }
 }
 
+// ─────────────────────────────────────────────────────────────────────────────
+// Metrics
+// ─────────────────────────────────────────────────────────────────────────────
+
+/// Counts of rate-limited requests, shared by all instances through Redis.
+#[derive(Clone)]
+pub struct RateLimitMetrics {
+    redis: ConnectionManager,
+}
+
+impl RateLimitMetrics {
+    pub fn new(redis: ConnectionManager) -> Self {
+        Self { redis }
+    }
+
+    async fn record_limited(&self, bucket: &str) {
+        let mut conn = self.redis.clone();
+        if let Err(e) = conn.hincr::<_, _, _, i64>(METRICS_KEY, bucket, 1).await {
+            warn!(error = %e, bucket, "Failed to record rate-limit hit");
+        }
+    }
+
+    /// Rejected requests per bucket since the counters were last reset.
+    pub async fn limited(&self) -> Result<HashMap<String, u64>, ApiError> {
+        let mut conn = self.redis.clone();
+        conn.hgetall(METRICS_KEY)
+            .await
+            .map_err(|e| ApiError::RedisError(e.to_string()))
+    }
+}
+
 // ─────────────────────────────────────────────────────────────────────────────
 // IP extraction (mirrors SecurityMiddleware)
 // ─────────────────────────────────────────────────────────────────────────────
@@ -213,7 +312,7 @@ fn now_ms() -> u64 {
 ///
 /// # Usage
 ///
-/// ```rust
+/// ```ignore
 /// App::new()
 ///     .wrap(RateLimitMiddleware::new(redis_conn.clone(), config.rate_limit.clone()))
 ///     // ... other middleware and routes
@@ -221,11 +320,13 @@ fn now_ms() -> u64 {
 pub struct RateLimitMiddleware {
     redis: Arc<ConnectionManager>,
     config: Arc<RateLimitConfig>,
+    metrics: RateLimitMetrics,
 }
 
 impl RateLimitMiddleware {
     pub fn new(redis: ConnectionManager, config: RateLimitConfig) -> Self {
         Self {
+            metrics: RateLimitMetrics::new(redis.clone()),
             redis: Arc::new(redis),
             config: Arc::new(config),
         }
@@ -248,6 +349,7 @@ where
             service: Rc::new(service),
             redis: self.redis.clone(),
             config: self.config.clone(),
+            metrics: self.metrics.clone(),
         }))
     }
 }
@@ -260,6 +362,7 @@ pub struct RateLimitMiddlewareService<S> {
     service: Rc<S>,
     redis: Arc<ConnectionManager>,
     config: Arc<RateLimitConfig>,
+    metrics: RateLimitMetrics,
 }
 
 impl<S, B> Service<ServiceRequest> for RateLimitMiddlewareService<S>
@@ -276,27 +379,34 @@ where
     fn call(&self, req: ServiceRequest) -> Self::Future {
         let svc = self.service.clone();
         let redis = self.redis.clone();
-        let default_limit = self.config.requests;
-        let default_window = self.config.window;
+        let config = self.config.clone();
+        let metrics = self.metrics.clone();
 
         Box::pin(async move {
             let path = req.path().to_string();
             let now = now_ms();
 
             // ── Resolve bucket ────────────────────────────────────────────────
-            let bucket = resolve_bucket(&path, default_limit, default_window);
+            let bucket = configured_bucket(&path, &config);
 
             // ── Resolve identity (user ID > IP) ───────────────────────────────
+            let ip = extract_ip(&req);
             let identity = req
                 .extensions()
                 .get::<Claims>()
                 .map(|c| format!("u:{}", c.sub))
-                .unwrap_or_else(|| format!("ip:{}", extract_ip(&req)));
+                .unwrap_or_else(|| format!("ip:{}", ip));
 
-            // ── Sliding window check ──────────────────────────────────────────
+            // ── Sliding window checks: IP ceiling, then endpoint class ────────
             let mut conn = (*redis).clone();
-            let (count, limit, reset_secs) =
-                sliding_window_check(&mut conn, &identity, &bucket, now).await;
+            let ip_bucket = ip_bucket(&config);
+            let ip_check = sliding_window_check(&mut conn, &ip, &ip_bucket, now).await;
+            let (limited, (count, limit, reset_secs)) = if ip_check.0 > ip_check.1 {
+                (&ip_bucket, ip_check)
+            } else {
+                let check = sliding_window_check(&mut conn, &identity, &bucket, now).await;
+                (&bucket, check)
+            };
 
             let remaining = limit.saturating_sub(count);
 
@@ -304,28 +414,29 @@ where
                 warn!(
                     identity = %identity,
                     path = %path,
-                    bucket = %bucket.name,
+                    bucket = %limited.name,
                     count = count,
                     limit = limit,
                     "Rate limit exceeded"
                 );
+                metrics.record_limited(limited.name).await;
 
                 let retry_after = reset_secs.saturating_sub(now / 1_000);
                 let mut response = HttpResponse::TooManyRequests().json(serde_json::json!({
                     "error": format!(
                         "Too many requests: limit is {} per {} seconds for this endpoint",
-                        limit, bucket.window_secs
+                        limit, limited.window_secs
                     ),
                     "code": 429,
                     "retry_after": retry_after,
-                    "bucket": bucket.name,
+                    "bucket": limited.name,
                 }));
 
                 // Rate limit headers on rejection
-                insert_header(&mut response, "X-RateLimit-Limit", limit);
-                insert_header(&mut response, "X-RateLimit-Remaining", 0u32);
-                insert_header(&mut response, "X-RateLimit-Reset", reset_secs);
-                insert_header(&mut response, "Retry-After", retry_after);
+                insert_header(&mut response, "x-ratelimit-limit", limit);
+                insert_header(&mut response, "x-ratelimit-remaining", 0u32);
+                insert_header(&mut response, "x-ratelimit-reset", reset_secs);
+                insert_header(&mut response, "retry-after", retry_after);
 
                 return Ok(req.into_response(response).map_into_right_body());
             }
@@ -335,9 +446,9 @@ where
 
             // Attach informational headers to every allowed response
             let headers = res.headers_mut();
-            try_insert(headers, "X-RateLimit-Limit", limit);
-            try_insert(headers, "X-RateLimit-Remaining", remaining);
-            try_insert(headers, "X-RateLimit-Reset", reset_secs);
+            try_insert(headers, "x-ratelimit-limit", limit);
+            try_insert(headers, "x-ratelimit-remaining", remaining);
+            try_insert(headers, "x-ratelimit-reset", reset_secs);
 
             Ok(res)
         })
@@ -348,11 +459,10 @@ where
 // Header helpers
 // ─────────────────────────────────────────────────────────────────────────────
 
+// `HeaderName::from_static` requires lowercase names.
 fn insert_header(response: &mut HttpResponse, name: &'static str, value: impl ToString) {
-    if let (Ok(n), Ok(v)) = (
-        HeaderName::from_static(name),
-        HeaderValue::from_str(&value.to_string()),
-    ) {
+    if let Ok(v) = HeaderValue::from_str(&value.to_string()) {
+        let n = HeaderName::from_static(name);
         response.headers_mut().insert(n, v);
     }
 }
@@ -362,10 +472,8 @@ fn try_insert(
     name: &'static str,
     value: impl ToString,
 ) {
-    if let (Ok(n), Ok(v)) = (
-        HeaderName::from_static(name),
-        HeaderValue::from_str(&value.to_string()),
-    ) {
+    if let Ok(v) = HeaderValue::from_str(&value.to_string()) {
+        let n = HeaderName::from_static(name);
         headers.insert(n, v);
     }
 }
@@ -377,6 +485,7 @@ fn try_insert(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::config::RateLimitPolicy;
 
     fn bucket_for(path: &str) -> Bucket {
         resolve_bucket(path, 100, 60)
@@ -403,6 +512,19 @@ mod tests {
         assert_eq!(b.limit, 10);
     }
 
+    #[test]
+    fn test_account_email_gets_strict_bucket() {
+        for path in [
+            "/api/auth/verify-email/send",
+            "/api/auth/reset-password/request",
+            "/api/auth/phone/send",
+        ] {
+            let b = bucket_for(path);
+            assert_eq!(b.name, "auth_strict");
+            assert_eq!(b.limit, 5);
+        }
+    }
+
     #[test]
     fn test_logout_gets_auth_bucket() {
         let b = bucket_for("/api/auth/logout");
@@ -452,6 +574,39 @@ mod tests {
         assert_eq!(b.limit, 100); // default from test args
     }
 
+    #[test]
+    fn test_payment_mutations_get_payments_bucket() {
+        assert_eq!(bucket_for("/api/payments").name, "payments");
+        assert_eq!(bucket_for("/api/payments/abc-123/cancel").name, "payments");
+        assert_eq!(bucket_for("/api/wallet/withdrawals").name, "payments");
+        assert_eq!(bucket_for("/api/payments/abc-123/status").name, "default");
+    }
+
+    #[test]
+    fn test_match_result_gets_report_bucket() {
+        assert_eq!(bucket_for("/api/matches/abc-123/result").name, "match_report");
+    }
+
+    #[test]
+    fn test_configured_policy_overrides_bucket() {
+        let config = RateLimitConfig {
+            requests: 100,
+            window: 60,
+            ip_requests: 600,
+            ip_window: 60,
+            policies: RateLimitPolicy::parse_list("payments=3/30, default=50/10").unwrap(),
+        };
+        let b = configured_bucket("/api/payments", &config);
+        assert_eq!((b.limit, b.window_secs), (3, 30));
+        let b = configured_bucket("/api/health", &config);
+        assert_eq!((b.limit, b.window_secs), (50, 10));
+        let b = configured_bucket("/api/auth/login", &config);
+        assert_eq!((b.limit, b.window_secs), (5, 60));
+
+        assert!(RateLimitPolicy::parse_list("payments=10").is_err());
+        assert!(RateLimitPolicy::parse_list("payments=10/0").is_err());
+    }
+
     #[test]
     fn test_remaining_never_underflows() {
         // limit.saturating_sub should never wrap to u32::MAX
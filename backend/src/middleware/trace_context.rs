@@ -0,0 +1,118 @@
+/// Request tracing.
+///
+/// Every request runs inside an `http_request` span named after its route
+/// pattern. A W3C `traceparent` header from the caller makes the span part of
+/// the caller's trace; otherwise a new trace starts here. The trace id is
+/// logged with every event of the request and returned in `x-trace-id`.
+use std::{
+    future::{ready, Ready},
+    rc::Rc,
+};
+
+use actix_web::{
+    dev::{forward_ready, Service, ServiceRequest, ServiceResponse, Transform},
+    http::header::{HeaderName, HeaderValue},
+    Error,
+};
+use futures_util::future::LocalBoxFuture;
+use tracing::{field::Empty, Instrument};
+
+use crate::telemetry::propagation;
+
+pub const TRACE_ID_HEADER: &str = "x-trace-id";
+const UNMATCHED_ROUTE: &str = "unmatched";
+
+// ─── Transform (factory) ──────────────────────────────────────────────────────
+
+#[derive(Default)]
+pub struct TraceContextMiddleware;
+
+impl TraceContextMiddleware {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for TraceContextMiddleware
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type InitError = ();
+    type Transform = TraceContextMiddlewareService<S>;
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(TraceContextMiddlewareService {
+            service: Rc::new(service),
+        }))
+    }
+}
+
+// ─── Service ──────────────────────────────────────────────────────────────────
+
+pub struct TraceContextMiddlewareService<S> {
+    service: Rc<S>,
+}
+
+impl<S, B> Service<ServiceRequest> for TraceContextMiddlewareService<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<B>;
+    type Error = Error;
+    type Future = LocalBoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    forward_ready!(service);
+
+    fn call(&self, req: ServiceRequest) -> Self::Future {
+        let svc = self.service.clone();
+
+        let route = req
+            .match_pattern()
+            .unwrap_or_else(|| UNMATCHED_ROUTE.to_string());
+        let span = tracing::info_span!(
+            "http_request",
+            otel.name = %format!("{} {}", req.method(), route),
+            otel.kind = "server",
+            otel.status_code = Empty,
+            http.request.method = %req.method(),
+            http.route = %route,
+            url.path = %req.path(),
+            http.response.status_code = Empty,
+            trace_id = Empty,
+        );
+        propagation::continue_from_headers(&span, req.headers());
+        let trace_id = propagation::trace_id(&span);
+        if let Some(trace_id) = &trace_id {
+            span.record("trace_id", trace_id.as_str());
+        }
+
+        let fut = span.in_scope(|| svc.call(req));
+        Box::pin(
+            async move {
+                let result = fut.await;
+                let status = match &result {
+                    Ok(res) => res.status(),
+                    Err(e) => e.as_response_error().status_code(),
+                };
+                let span = tracing::Span::current();
+                span.record("http.response.status_code", status.as_u16());
+                if status.is_server_error() {
+                    span.record("otel.status_code", "ERROR");
+                }
+
+                let mut res = result?;
+                if let Some(value) = trace_id.and_then(|id| HeaderValue::from_str(&id).ok()) {
+                    res.headers_mut()
+                        .insert(HeaderName::from_static(TRACE_ID_HEADER), value);
+                }
+                Ok(res)
+            }
+            .instrument(span),
+        )
+    }
+}
@@ -0,0 +1,78 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::models::TournamentStatus;
+
+/// A user as the admin console lists them.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AdminUserSummary {
+    pub id: Uuid,
+    pub username: String,
+    pub email: Option<String>,
+    pub role: String,
+    pub is_active: bool,
+    pub is_banned: bool,
+    /// `None` while banned means the ban is permanent.
+    pub banned_until: Option<DateTime<Utc>>,
+    pub ban_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct BanUserRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+    /// When the ban lifts; permanent if omitted.
+    pub until: Option<DateTime<Utc>>,
+}
+
+/// The reason an admin gives for an action that takes no other input.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct AdminReasonRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+/// Fields an admin may override on a tournament; omitted ones are kept.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct TournamentOverrideRequest {
+    pub status: Option<TournamentStatus>,
+    #[validate(range(min = 2, max = 100000))]
+    pub max_participants: Option<i32>,
+    pub registration_deadline: Option<DateTime<Utc>>,
+    pub start_time: Option<DateTime<Utc>>,
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct EscalateDisputeRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+    /// Referee to hand the case to; the current one keeps it if omitted.
+    pub referee_id: Option<Uuid>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FeatureFlag {
+    pub id: Uuid,
+    pub key: String,
+    pub enabled: bool,
+    pub description: Option<String>,
+    pub updated_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SetFeatureFlagRequest {
+    pub enabled: bool,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    #[validate(length(min = 1, max = 500))]
+    pub reason: String,
+}
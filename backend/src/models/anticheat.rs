@@ -0,0 +1,140 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Review state of a detection, stored as `anticheat_detections.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum DetectionStatus {
+    PendingReview,
+    Confirmed,
+    Dismissed,
+}
+
+/// What one detector saw in a signal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub detector: String,
+    /// Metric, integrity check or report kind the finding is about.
+    pub subject: String,
+    pub value: f64,
+    /// How strongly this points to cheating, 0 to 1.
+    pub weight: f64,
+    pub detail: String,
+}
+
+/// A player queued for review in one match.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Detection {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub match_id: Uuid,
+    pub score: f64,
+    pub findings: sqlx::types::Json<Vec<Finding>>,
+    pub signal_count: i32,
+    pub status: DetectionStatus,
+    /// 1 low, 2 medium, 3 high; set on confirmation.
+    pub severity: Option<i32>,
+    pub reviewer_id: Option<Uuid>,
+    pub review_notes: Option<String>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    /// `not_submitted`, `off_chain`, `pending`, `submitted` or `failed`.
+    pub chain_status: String,
+    pub chain_tx: Option<String>,
+    pub chain_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AntiCheatSignal {
+    pub id: Uuid,
+    pub player_id: Uuid,
+    pub match_id: Uuid,
+    /// `telemetry` or `report`.
+    pub source: String,
+    pub submitted_by: Option<Uuid>,
+    pub payload: serde_json::Value,
+    pub score: f64,
+    pub findings: sqlx::types::Json<Vec<Finding>>,
+    pub detection_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One entry in a detection's audit trail.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AntiCheatAuditEntry {
+    pub id: Uuid,
+    pub detection_id: Uuid,
+    /// `None` for actions taken by the platform, e.g. the oracle submission.
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A detection with the signals behind it and its audit trail.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DetectionDetail {
+    #[serde(flatten)]
+    pub detection: Detection,
+    pub signals: Vec<AntiCheatSignal>,
+    pub audit: Vec<AntiCheatAuditEntry>,
+}
+
+/// How a signal was scored.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SignalReceipt {
+    pub signal_id: Uuid,
+    pub score: f64,
+    /// Set when the signal queued the player for review.
+    pub detection_id: Option<Uuid>,
+}
+
+/// Telemetry the game client sends for the signed-in player.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SubmitTelemetryRequest {
+    pub match_id: Uuid,
+    /// Per-match statistics, e.g. `headshot_ratio` or `avg_reaction_ms`.
+    pub metrics: BTreeMap<String, f64>,
+    /// Client integrity checks that failed, e.g. `debugger_attached`.
+    #[serde(default)]
+    #[validate(length(max = 20))]
+    pub integrity_violations: Vec<String>,
+    #[validate(length(max = 64))]
+    pub client_version: Option<String>,
+}
+
+/// A statistical anomaly found by an analytics service.
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct SubmitAnomalyReportRequest {
+    pub player_id: Uuid,
+    pub match_id: Uuid,
+    #[validate(length(min = 1, max = 64))]
+    pub kind: String,
+    /// How sure the reporter is, 0 to 1.
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub confidence: f64,
+    pub details: Option<serde_json::Value>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewDecision {
+    Confirm,
+    Dismiss,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct ReviewDetectionRequest {
+    pub decision: ReviewDecision,
+    /// Required to confirm: 1 low, 2 medium, 3 high.
+    #[validate(range(min = 1, max = 3))]
+    pub severity: Option<i32>,
+    #[validate(length(max = 2000))]
+    pub notes: Option<String>,
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A row of `audit_logs`.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AuditLog {
+    /// Position in the chain.
+    pub seq: i64,
+    pub id: Uuid,
+    /// Who acted; `None` for the platform itself.
+    #[sqlx(rename = "user_id")]
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub resource_type: String,
+    pub resource_id: Option<Uuid>,
+    /// JSON text.
+    pub details: Option<String>,
+    /// Fields the action changed, as they were before.
+    pub before_state: Option<serde_json::Value>,
+    /// The same fields afterwards.
+    pub after_state: Option<serde_json::Value>,
+    pub reason: Option<String>,
+    pub ip_address: Option<String>,
+    pub user_agent: Option<String>,
+    /// HTTP method and path, for entries written by the audit middleware.
+    pub method: Option<String>,
+    pub path: Option<String>,
+    pub status_code: Option<i16>,
+    pub latency_ms: Option<i32>,
+    pub request_id: Option<String>,
+    /// `entry_hash` of the entry before this one. Both are `None` on entries
+    /// written before the log was chained.
+    pub prev_hash: Option<String>,
+    pub entry_hash: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Filters for searching the audit log; all are optional.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct AuditLogFilter {
+    pub actor_id: Option<Uuid>,
+    pub action: Option<String>,
+    pub resource_type: Option<String>,
+    pub resource_id: Option<Uuid>,
+    pub request_id: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// The outcome of checking a stretch of the chain.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditChainReport {
+    /// Entries checked.
+    pub checked: usize,
+    pub first_seq: Option<i64>,
+    pub last_seq: Option<i64>,
+    /// The first entry whose hash or link does not match, if any.
+    pub broken_at: Option<i64>,
+    pub reason: Option<String>,
+}
+
+impl AuditChainReport {
+    pub fn is_intact(&self) -> bool {
+        self.broken_at.is_none()
+    }
+}
@@ -0,0 +1,174 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Which part of a bracket a round belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BracketSide {
+    /// Single elimination, or the winners bracket of double elimination.
+    Winners,
+    Losers,
+    GrandFinal,
+    /// Round robin and Swiss rounds.
+    Pool,
+}
+
+impl BracketSide {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            BracketSide::Winners => "winners",
+            BracketSide::Losers => "losers",
+            BracketSide::GrandFinal => "grand_final",
+            BracketSide::Pool => "pool",
+        }
+    }
+}
+
+impl std::str::FromStr for BracketSide {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "winners" => Ok(BracketSide::Winners),
+            "losers" => Ok(BracketSide::Losers),
+            "grand_final" => Ok(BracketSide::GrandFinal),
+            "pool" => Ok(BracketSide::Pool),
+            other => Err(format!("unknown bracket side: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SlotStatus {
+    /// Waiting for one or both players.
+    Pending,
+    /// Both players known; the match can be played.
+    Ready,
+    Completed,
+    /// Resolved without a match because at most one player could arrive.
+    Bye,
+}
+
+impl SlotStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SlotStatus::Pending => "pending",
+            SlotStatus::Ready => "ready",
+            SlotStatus::Completed => "completed",
+            SlotStatus::Bye => "bye",
+        }
+    }
+
+    pub fn is_resolved(&self) -> bool {
+        matches!(self, SlotStatus::Completed | SlotStatus::Bye)
+    }
+}
+
+impl std::str::FromStr for SlotStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(SlotStatus::Pending),
+            "ready" => Ok(SlotStatus::Ready),
+            "completed" => Ok(SlotStatus::Completed),
+            "bye" => Ok(SlotStatus::Bye),
+            other => Err(format!("unknown slot status: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TournamentBracket {
+    pub tournament_id: Uuid,
+    /// `single_elimination`, `double_elimination`, `round_robin` or `swiss`.
+    pub format: String,
+    pub player_count: i32,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BracketRound {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub side: String,
+    pub round_number: i32,
+    /// Order in which rounds were created; later rounds have higher ordinals.
+    pub ordinal: i32,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BracketSlot {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub round_id: Uuid,
+    pub position: i32,
+    pub player1_id: Option<Uuid>,
+    pub player1_seed: Option<i32>,
+    pub player2_id: Option<Uuid>,
+    pub player2_seed: Option<i32>,
+    pub winner_id: Option<Uuid>,
+    pub loser_id: Option<Uuid>,
+    pub status: String,
+    /// Slot and side (1 or 2) the winner moves to.
+    pub winner_to_slot_id: Option<Uuid>,
+    pub winner_to_position: Option<i16>,
+    /// Slot and side the loser drops to (double elimination only).
+    pub loser_to_slot_id: Option<Uuid>,
+    pub loser_to_position: Option<i16>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Resolved because a player missed check-in.
+    pub forfeit: bool,
+    /// Match contract id for the slot's match, once it has been created on chain.
+    pub on_chain_match_id: Option<String>,
+    pub checkin_opens_at: Option<DateTime<Utc>>,
+    pub checkin_deadline: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BracketCheckIn {
+    pub slot_id: Uuid,
+    pub user_id: Uuid,
+    pub tournament_id: Uuid,
+    pub checked_in_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckInResponse {
+    pub check_in: BracketCheckIn,
+    pub checkin_deadline: Option<DateTime<Utc>>,
+    pub opponent_id: Option<Uuid>,
+    pub opponent_checked_in: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BracketRoundView {
+    pub round: BracketRound,
+    pub slots: Vec<BracketSlot>,
+}
+
+/// The team a bracket entrant plays for. Teams enter brackets through their
+/// captain, who takes the slots and checks in for the team.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BracketTeam {
+    pub entrant_id: Uuid,
+    pub team_id: Uuid,
+    pub name: String,
+    pub tag: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BracketView {
+    pub bracket: TournamentBracket,
+    pub rounds: Vec<BracketRoundView>,
+    /// Teams of team tournaments, keyed by the entrant in the slots.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub teams: Vec<BracketTeam>,
+}
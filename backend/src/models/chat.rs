@@ -0,0 +1,104 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use std::str::FromStr;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Where a chat message is posted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChatChannel {
+    Match(Uuid),
+    Tournament(Uuid),
+    /// Direct messages between two users, smaller ID first.
+    Direct(Uuid, Uuid),
+}
+
+impl ChatChannel {
+    pub fn direct(a: Uuid, b: Uuid) -> Self {
+        if a <= b {
+            ChatChannel::Direct(a, b)
+        } else {
+            ChatChannel::Direct(b, a)
+        }
+    }
+}
+
+impl fmt::Display for ChatChannel {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChatChannel::Match(id) => write!(f, "match:{}", id),
+            ChatChannel::Tournament(id) => write!(f, "tournament:{}", id),
+            ChatChannel::Direct(a, b) => write!(f, "dm:{}:{}", a, b),
+        }
+    }
+}
+
+impl FromStr for ChatChannel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("invalid chat channel: {}", s);
+        let (kind, rest) = s.split_once(':').ok_or_else(invalid)?;
+        let id = |value: &str| Uuid::parse_str(value).map_err(|_| invalid());
+        match kind {
+            "match" => Ok(ChatChannel::Match(id(rest)?)),
+            "tournament" => Ok(ChatChannel::Tournament(id(rest)?)),
+            "dm" => {
+                let (a, b) = rest.split_once(':').ok_or_else(invalid)?;
+                Ok(ChatChannel::direct(id(a)?, id(b)?))
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChatMessage {
+    pub id: Uuid,
+    pub channel: String,
+    pub sender_id: Uuid,
+    pub sender_username: String,
+    pub body: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChatHistory {
+    /// Newest first.
+    pub messages: Vec<ChatMessage>,
+    /// Pass as `before` for the next, older page; absent on the last page.
+    pub next_before: Option<Uuid>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct SendChatMessageRequest {
+    #[validate(length(min = 1, max = 500))]
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ChatMute {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Muted in this channel only; everywhere when absent.
+    pub channel: Option<String>,
+    pub muted_by: Uuid,
+    pub reason: Option<String>,
+    /// Until lifted when absent.
+    pub expires_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct MuteChatUserRequest {
+    pub user_id: Uuid,
+    /// e.g. `tournament:<id>`; everywhere when absent.
+    pub channel: Option<String>,
+    /// Until lifted when absent.
+    #[validate(range(min = 60, max = 31_536_000))]
+    pub duration_secs: Option<i64>,
+    #[validate(length(max = 500))]
+    pub reason: Option<String>,
+}
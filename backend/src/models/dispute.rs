@@ -0,0 +1,159 @@
+use super::match_models::{EvidenceKind, EvidenceUpload};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Status of a dispute case, stored as the integer `match_disputes.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[repr(i32)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeCaseStatus {
+    Open = 0,
+    /// Assigned to a referee.
+    UnderReview = 1,
+    Resolved = 2,
+    Rejected = 3,
+}
+
+/// Dispute category on the dispute resolution contract; sets the resolution
+/// window.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DisputeCategory {
+    #[default]
+    Standard,
+    HighStakes,
+    TournamentFinal,
+}
+
+impl DisputeCategory {
+    pub fn as_u32(&self) -> u32 {
+        match self {
+            DisputeCategory::Standard => 0,
+            DisputeCategory::HighStakes => 1,
+            DisputeCategory::TournamentFinal => 2,
+        }
+    }
+}
+
+/// A referee's decision. Player A is the match's first player.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum DisputeDecision {
+    AwardPlayerA,
+    AwardPlayerB,
+    /// Divide the pot in basis points; the shares must total 10000.
+    Split {
+        player_a_bps: u32,
+        player_b_bps: u32,
+    },
+    Replay,
+    Void,
+}
+
+impl DisputeDecision {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DisputeDecision::AwardPlayerA => "award_player_a",
+            DisputeDecision::AwardPlayerB => "award_player_b",
+            DisputeDecision::Split { .. } => "split",
+            DisputeDecision::Replay => "replay",
+            DisputeDecision::Void => "void",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DisputeCase {
+    pub id: Uuid,
+    pub match_id: Uuid,
+    pub disputing_player_id: Option<Uuid>,
+    pub reason: String,
+    pub category: i32,
+    pub status: DisputeCaseStatus,
+    /// Referee the case is assigned to.
+    pub admin_reviewer_id: Option<Uuid>,
+    pub admin_notes: Option<String>,
+    pub resolution: Option<String>,
+    pub decision: Option<serde_json::Value>,
+    /// `off_chain`, `pending`, `opened`, `resolved` or `failed`.
+    pub chain_status: String,
+    pub chain_open_tx: Option<String>,
+    pub chain_resolve_tx: Option<String>,
+    pub chain_error: Option<String>,
+    /// Evidence reference the case was opened with on chain.
+    pub evidence_ref: Option<String>,
+    /// File hashes `evidence_ref` was computed from, in order.
+    pub evidence_hashes: Vec<String>,
+    /// Set when an admin escalates the case; escalated cases are queued first.
+    pub escalated_at: Option<DateTime<Utc>>,
+    pub escalated_by: Option<Uuid>,
+    pub escalation_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DisputeEvidence {
+    pub id: Uuid,
+    pub dispute_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub kind: EvidenceKind,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub object_key: String,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// One entry in a case's audit history.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DisputeEvent {
+    pub id: Uuid,
+    pub dispute_id: Uuid,
+    /// `None` for actions taken by the platform, e.g. on-chain execution.
+    pub actor_id: Option<Uuid>,
+    pub action: String,
+    pub details: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct OpenDisputeRequest {
+    pub match_id: Uuid,
+    pub reason: String,
+    #[serde(default)]
+    pub category: DisputeCategory,
+    #[serde(default)]
+    pub evidence: Vec<EvidenceUpload>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AttachEvidenceRequest {
+    pub evidence: Vec<EvidenceUpload>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AssignDisputeRequest {
+    /// Referee to assign; defaults to the caller. Only admins may assign
+    /// someone else.
+    pub referee_id: Option<Uuid>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DisputeDecisionRequest {
+    pub decision: DisputeDecision,
+    pub notes: Option<String>,
+}
+
+/// A case with its evidence and full history.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct DisputeDetail {
+    #[serde(flatten)]
+    pub case: DisputeCase,
+    pub evidence: Vec<DisputeEvidence>,
+    pub history: Vec<DisputeEvent>,
+}
@@ -0,0 +1,93 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PresenceStatus {
+    Offline,
+    Online,
+    InMatch,
+    Spectating,
+}
+
+impl PresenceStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PresenceStatus::Offline => "offline",
+            PresenceStatus::Online => "online",
+            PresenceStatus::InMatch => "in_match",
+            PresenceStatus::Spectating => "spectating",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct Presence {
+    pub status: PresenceStatus,
+    /// The match played or watched, for `in_match` and `spectating`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub match_id: Option<Uuid>,
+}
+
+impl Presence {
+    pub fn offline() -> Self {
+        Self {
+            status: PresenceStatus::Offline,
+            match_id: None,
+        }
+    }
+
+    pub fn online() -> Self {
+        Self {
+            status: PresenceStatus::Online,
+            match_id: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FriendProfile {
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub friends_since: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FriendWithPresence {
+    #[serde(flatten)]
+    pub friend: FriendProfile,
+    pub presence: Presence,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FriendList {
+    pub friends: Vec<FriendWithPresence>,
+    pub online_count: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PendingFriendRequest {
+    pub id: Uuid,
+    pub from_user_id: Uuid,
+    pub from_username: String,
+    pub to_user_id: Uuid,
+    pub to_username: String,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FriendRequests {
+    pub incoming: Vec<PendingFriendRequest>,
+    pub outgoing: Vec<PendingFriendRequest>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BlockedUser {
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub blocked_at: DateTime<Utc>,
+}
@@ -0,0 +1,133 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+/// How thoroughly a user's identity has been verified. Each level raises the
+/// daily withdrawal limit.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, sqlx::Type,
+)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum KycLevel {
+    None,
+    /// Identity document checked.
+    Basic,
+    /// Identity document, liveness and proof of address checked.
+    Full,
+}
+
+impl std::fmt::Display for KycLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            KycLevel::None => write!(f, "none"),
+            KycLevel::Basic => write!(f, "basic"),
+            KycLevel::Full => write!(f, "full"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum KycStatus {
+    NotStarted,
+    /// Waiting for the user to finish the provider's flow.
+    Pending,
+    /// Waiting for the provider or an admin to decide.
+    InReview,
+    Approved,
+    /// The requested level was refused; the user keeps their current level.
+    Rejected,
+}
+
+/// A user's verification state. Personal details are stored encrypted and
+/// only shown to admins reviewing the case.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct KycProfile {
+    pub user_id: Uuid,
+    /// Level approved so far.
+    pub level: KycLevel,
+    pub status: KycStatus,
+    /// Level of the open or last request.
+    pub requested_level: Option<KycLevel>,
+    /// `sumsub`, or `None` for submissions reviewed by admins.
+    pub provider: Option<String>,
+    /// The provider's applicant ID.
+    pub provider_reference: Option<String>,
+    pub rejection_reason: Option<String>,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct KycEvent {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// `user`, `provider` or `admin`.
+    pub source: String,
+    pub status: KycStatus,
+    pub level: KycLevel,
+    pub actor_id: Option<Uuid>,
+    pub details: Option<serde_json::Value>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Personal details submitted for verification.
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct KycPersonalDetails {
+    #[validate(length(min = 1, max = 200))]
+    pub legal_name: String,
+    pub date_of_birth: NaiveDate,
+    /// ISO 3166-1 alpha-2 country code.
+    #[validate(length(equal = 2))]
+    pub nationality: String,
+    #[validate(length(max = 500))]
+    pub address: Option<String>,
+    /// e.g. `passport`, `national_id`, `drivers_license`.
+    #[validate(length(min = 1, max = 50))]
+    pub id_type: String,
+    #[validate(length(min = 1, max = 100))]
+    pub id_number: String,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct StartKycRequest {
+    pub level: KycLevel,
+    /// Required when admins review submissions; optional with a provider.
+    #[validate(nested)]
+    pub details: Option<KycPersonalDetails>,
+}
+
+/// Response to `GET /api/kyc` and `POST /api/kyc`.
+#[derive(Debug, Serialize)]
+pub struct KycStatusResponse {
+    pub profile: KycProfile,
+    /// In stroops.
+    pub daily_withdrawal_limit: i64,
+    /// Token for the provider's web SDK, when a verification was just started
+    /// with a provider.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub access_token: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReviewKycRequest {
+    pub approve: bool,
+    /// Level to grant; defaults to the requested level.
+    pub level: Option<KycLevel>,
+    #[validate(length(min = 1, max = 1000))]
+    pub reason: Option<String>,
+}
+
+/// A case as admins see it.
+#[derive(Debug, Serialize)]
+pub struct KycReview {
+    pub profile: KycProfile,
+    pub details: Option<KycPersonalDetails>,
+    pub events: Vec<KycEvent>,
+}
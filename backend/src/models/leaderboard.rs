@@ -1,6 +1,8 @@
+use chrono::{DateTime, Datelike, TimeZone, Utc};
 use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
 use uuid::Uuid;
-use chrono::{DateTime, Utc};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LeaderboardEntry {
@@ -87,3 +89,246 @@ pub struct LeaderboardStats {
     pub top_player_elo: i32,
     pub last_updated: DateTime<Utc>,
 }
+
+/// What a board ranks players by.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LeaderboardMetric {
+    /// Skill gained on the reputation index.
+    Skill,
+    /// Prize payouts received, in the pool asset's smallest unit.
+    Earnings,
+}
+
+impl LeaderboardMetric {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LeaderboardMetric::Skill => "skill",
+            LeaderboardMetric::Earnings => "earnings",
+        }
+    }
+}
+
+/// A leaderboard, written `skill`, `earnings` or `earnings:<game>`.
+/// Reputation changes carry no game, so skill is only ranked globally.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Board {
+    pub metric: LeaderboardMetric,
+    /// Lowercased game name for a per-game board.
+    pub game: Option<String>,
+}
+
+impl Board {
+    pub fn global(metric: LeaderboardMetric) -> Self {
+        Self { metric, game: None }
+    }
+}
+
+impl FromStr for Board {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (metric, game) = match s.split_once(':') {
+            Some((metric, game)) => (metric, Some(game.trim().to_lowercase())),
+            None => (s, None),
+        };
+        let metric = match metric {
+            "skill" => LeaderboardMetric::Skill,
+            "earnings" => LeaderboardMetric::Earnings,
+            _ => return Err(format!("Unknown leaderboard: {}", s)),
+        };
+        if let Some(game) = &game {
+            if metric == LeaderboardMetric::Skill {
+                return Err("Skill is only ranked globally".to_string());
+            }
+            if game.is_empty() || game.len() > 50 || game.contains(':') {
+                return Err(format!("Invalid game: {}", game));
+            }
+        }
+        Ok(Self { metric, game })
+    }
+}
+
+impl fmt::Display for Board {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.game {
+            Some(game) => write!(f, "{}:{}", self.metric.as_str(), game),
+            None => f.write_str(self.metric.as_str()),
+        }
+    }
+}
+
+/// A calendar quarter, e.g. `2026-q4`. Seasonal boards only count credits
+/// earned within it.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Season {
+    pub id: String,
+    pub starts_at: DateTime<Utc>,
+    pub ends_at: DateTime<Utc>,
+}
+
+impl Season {
+    pub fn current() -> Self {
+        Self::containing(Utc::now())
+    }
+
+    pub fn containing(at: DateTime<Utc>) -> Self {
+        Self::quarter(at.year(), (at.month() - 1) / 3 + 1)
+    }
+
+    /// The season with id `id`, or `None` if it is malformed.
+    pub fn parse(id: &str) -> Option<Self> {
+        let (year, quarter) = id.split_once("-q")?;
+        let year: i32 = year.parse().ok()?;
+        let quarter: u32 = quarter.parse().ok()?;
+        if !(2000..=9999).contains(&year) || !(1..=4).contains(&quarter) {
+            return None;
+        }
+        Some(Self::quarter(year, quarter))
+    }
+
+    /// The season after this one.
+    pub fn next(&self) -> Self {
+        Self::containing(self.ends_at)
+    }
+
+    /// The season before this one.
+    pub fn previous(&self) -> Self {
+        Self::containing(self.starts_at - chrono::Duration::seconds(1))
+    }
+
+    fn quarter(year: i32, quarter: u32) -> Self {
+        let start = |year, month| Utc.with_ymd_and_hms(year, month, 1, 0, 0, 0).unwrap();
+        let starts_at = start(year, (quarter - 1) * 3 + 1);
+        let ends_at = if quarter == 4 {
+            start(year + 1, 1)
+        } else {
+            start(year, quarter * 3 + 1)
+        };
+        Self {
+            id: format!("{}-q{}", year, quarter),
+            starts_at,
+            ends_at,
+        }
+    }
+}
+
+/// A player's standing on a board. Ranks start at 1.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BoardEntry {
+    pub rank: i64,
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub score: i64,
+}
+
+/// The players ranked around one player.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RankAroundResponse {
+    pub board: String,
+    pub season: Option<String>,
+    pub total: i64,
+    /// The player's own entry; `None` if they are not on the board.
+    pub player: Option<BoardEntry>,
+    pub entries: Vec<BoardEntry>,
+}
+
+/// A season's final standings on one board.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSeason {
+    pub board: String,
+    pub season: Season,
+    pub players: i32,
+    pub archived_at: DateTime<Utc>,
+}
+
+/// A player's final standing in an archived season.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct SeasonStanding {
+    pub rank: i32,
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub score: i64,
+    /// Reward tier earned, if any.
+    pub reward_tier: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SeasonStandingsResponse {
+    #[serde(flatten)]
+    pub season: ArchivedSeason,
+    pub total: i64,
+    pub page: i64,
+    pub limit: i64,
+    pub data: Vec<SeasonStanding>,
+}
+
+/// A rewarded player in a [`WinnersManifest`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub rank: i32,
+    pub user_id: Uuid,
+    pub address: String,
+    pub reward_tier: String,
+    /// Share of the pool in basis points.
+    pub weight: u32,
+}
+
+/// The rewarded players of an archived season. `winners` and `weights` are
+/// the arguments of the prize contract's `distribute` call; weights sum to
+/// 10000.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WinnersManifest {
+    pub board: String,
+    pub season_id: String,
+    pub generated_at: DateTime<Utc>,
+    pub winners: Vec<String>,
+    pub weights: Vec<u32>,
+    pub entries: Vec<ManifestEntry>,
+    /// Players who earned a tier but have no wallet to pay.
+    pub unpaid: Vec<Uuid>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_board_parsing() {
+        let board: Board = "earnings:FIFA".parse().unwrap();
+        assert_eq!(board.metric, LeaderboardMetric::Earnings);
+        assert_eq!(board.game.as_deref(), Some("fifa"));
+        assert_eq!(board.to_string(), "earnings:fifa");
+        assert_eq!(
+            "skill".parse::<Board>(),
+            Ok(Board::global(LeaderboardMetric::Skill))
+        );
+
+        assert!("skill:fifa".parse::<Board>().is_err());
+        assert!("earnings:".parse::<Board>().is_err());
+        assert!("elo".parse::<Board>().is_err());
+    }
+
+    #[test]
+    fn test_seasons_are_calendar_quarters() {
+        let at = Utc.with_ymd_and_hms(2026, 12, 31, 23, 59, 59).unwrap();
+        let season = Season::containing(at);
+        assert_eq!(season.id, "2026-q4");
+        assert_eq!(
+            season.starts_at,
+            Utc.with_ymd_and_hms(2026, 10, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            season.ends_at,
+            Utc.with_ymd_and_hms(2027, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(season.next().id, "2027-q1");
+        assert_eq!(season.next().previous(), season);
+        assert_eq!(Season::parse("2026-q4"), Some(season));
+
+        assert_eq!(Season::parse("2026-q5"), None);
+        assert_eq!(Season::parse("2026"), None);
+    }
+}
@@ -20,7 +20,6 @@ pub struct Match {
     pub player1_elo_after: Option<i32>,
     pub player2_elo_after: Option<i32>,
     pub scheduled_time: Option<DateTime<Utc>>,
-    pub scheduled_at: Option<DateTime<Utc>>,
     pub started_at: Option<DateTime<Utc>>,
     pub completed_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
@@ -28,8 +27,6 @@ pub struct Match {
     pub game_mode: String,
     pub map: Option<String>,
     pub match_duration: Option<i32>, // in seconds
-    pub round_number: Option<i32>,
-    pub match_number: Option<i32>,
     // --- Conflict & Reaper fields (migration 20250325000001) ---
     /// Deadline by which both players must submit a score report.
     /// Set automatically when the match moves to in_progress.
@@ -74,6 +71,17 @@ pub struct MatchDispute {
     pub created_at: DateTime<Utc>,
     pub updated_at: Option<DateTime<Utc>>,
     pub resolved_at: Option<DateTime<Utc>>,
+    pub category: i32,
+    pub decision: Option<serde_json::Value>,
+    pub chain_status: String,
+    pub chain_open_tx: Option<String>,
+    pub chain_resolve_tx: Option<String>,
+    pub chain_error: Option<String>,
+    pub evidence_ref: Option<String>,
+    pub evidence_hashes: Vec<String>,
+    pub escalated_at: Option<DateTime<Utc>>,
+    pub escalated_by: Option<Uuid>,
+    pub escalation_reason: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -106,8 +114,6 @@ pub struct UserElo {
     pub win_streak: i32,
     pub loss_streak: i32,
     pub last_updated: DateTime<Utc>,
-    pub created_at: Option<DateTime<Utc>>,
-    pub updated_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -126,54 +132,116 @@ pub struct EloHistory {
 }
 
 // Enums
+//
+// These are stored as INTEGER columns; the discriminants are the stored codes.
+// `From<i32>` lets `query_as!` map the raw column into the enum.
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[repr(i32)]
 pub enum MatchType {
-    Tournament,
-    Casual,
-    Ranked,
-    Practice,
+    Tournament = 0,
+    Casual = 1,
+    Ranked = 2,
+    Practice = 3,
+}
+
+impl From<i32> for MatchType {
+    fn from(code: i32) -> Self {
+        match code {
+            0 => MatchType::Tournament,
+            2 => MatchType::Ranked,
+            3 => MatchType::Practice,
+            _ => MatchType::Casual,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[repr(i32)]
 pub enum MatchStatus {
-    Pending,
-    Scheduled,
-    InProgress,
-    Completed,
+    Pending = 0,
+    Scheduled = 1,
+    InProgress = 2,
+    Completed = 3,
     /// Automatically set when both players submit contradictory score reports.
     /// Requires manual or oracle-based resolution before the match can be finalized.
-    Conflict,
-    Disputed,
-    Cancelled,
-    Abandoned,
+    Conflict = 7,
+    Disputed = 4,
+    Cancelled = 5,
+    Abandoned = 6,
+}
+
+impl From<i32> for MatchStatus {
+    fn from(code: i32) -> Self {
+        match code {
+            1 => MatchStatus::Scheduled,
+            2 => MatchStatus::InProgress,
+            3 => MatchStatus::Completed,
+            4 => MatchStatus::Disputed,
+            5 => MatchStatus::Cancelled,
+            6 => MatchStatus::Abandoned,
+            7 => MatchStatus::Conflict,
+            _ => MatchStatus::Pending,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[repr(i32)]
 pub enum DisputeStatus {
-    Pending,
-    UnderReview,
-    Resolved,
-    Rejected,
+    Pending = 0,
+    UnderReview = 1,
+    Resolved = 2,
+    Rejected = 3,
+}
+
+impl From<i32> for DisputeStatus {
+    fn from(code: i32) -> Self {
+        match code {
+            1 => DisputeStatus::UnderReview,
+            2 => DisputeStatus::Resolved,
+            3 => DisputeStatus::Rejected,
+            _ => DisputeStatus::Pending,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[repr(i32)]
 pub enum QueueStatus {
-    Waiting,
-    Matched,
-    Expired,
-    Cancelled,
+    Waiting = 0,
+    Matched = 1,
+    Expired = 2,
+    Cancelled = 3,
+}
+
+impl From<i32> for QueueStatus {
+    fn from(code: i32) -> Self {
+        match code {
+            1 => QueueStatus::Matched,
+            2 => QueueStatus::Expired,
+            3 => QueueStatus::Cancelled,
+            _ => QueueStatus::Waiting,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[repr(i32)]
 pub enum MatchResult {
-    Win,
-    Loss,
-    Draw,
+    Win = 0,
+    Loss = 1,
+    Draw = 2,
+}
+
+impl From<i32> for MatchResult {
+    fn from(code: i32) -> Self {
+        match code {
+            0 => MatchResult::Win,
+            1 => MatchResult::Loss,
+            _ => MatchResult::Draw,
+        }
+    }
 }
 
 // DTOs for API requests/responses
@@ -205,6 +273,73 @@ pub struct ScoreReportAttempt {
     pub rejection_reason: Option<String>,
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum EvidenceKind {
+    Screenshot,
+    Replay,
+}
+
+/// One evidence file attached to a result submission.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct EvidenceUpload {
+    pub kind: EvidenceKind,
+    pub content_type: String,
+    /// Base64-encoded file contents.
+    pub data: String,
+}
+
+/// Result submission with the evidence backing it.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitMatchResultRequest {
+    pub score: i32,
+    pub opponent_score: i32,
+    #[serde(default)]
+    pub evidence: Vec<EvidenceUpload>,
+    pub telemetry_data: Option<String>,
+}
+
+/// An evidence file stored in object storage.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MatchEvidence {
+    pub id: Uuid,
+    pub match_id: Uuid,
+    pub player_id: Uuid,
+    pub kind: EvidenceKind,
+    pub content_type: String,
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the file contents.
+    pub sha256: String,
+    pub object_key: String,
+    pub url: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Where a match stands once a player has reported its result.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum DualReportStatus {
+    /// Only one player has reported so far.
+    AwaitingOpponent,
+    /// Both players reported the same winner.
+    Agreed,
+    /// The two reports name different winners.
+    Conflict,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SubmitMatchResultResponse {
+    pub match_id: Uuid,
+    pub score: MatchScore,
+    pub evidence: Vec<MatchEvidence>,
+    /// Hex SHA-256 over the per-file hashes, in submission order.
+    pub evidence_hash: String,
+    pub status: DualReportStatus,
+    /// Whether the report was queued for submission to the match contract.
+    pub on_chain: bool,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct CreateDisputeRequest {
     pub reason: String,
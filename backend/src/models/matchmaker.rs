@@ -48,14 +48,13 @@ pub struct UserElo {
     pub game: String,
     pub current_rating: i32,
     pub peak_rating: i32,
+    pub games_played: i32,
     pub wins: i32,
     pub losses: i32,
     pub draws: i32,
-    pub win_rate: f64,
     pub win_streak: i32,
     pub loss_streak: i32,
-    pub created_at: DateTime<Utc>,
-    pub updated_at: DateTime<Utc>,
+    pub last_updated: DateTime<Utc>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
@@ -63,10 +62,13 @@ pub struct EloHistory {
     pub id: Uuid,
     pub user_id: Uuid,
     pub game: String,
-    pub old_rating: i32,
-    pub new_rating: i32,
-    pub change_amount: i32,
-    pub match_id: Uuid,
+    pub match_id: Option<Uuid>,
+    pub rating_before: i32,
+    pub rating_after: i32,
+    pub rating_change: i32,
+    pub opponent_id: Option<Uuid>,
+    pub opponent_rating: Option<i32>,
+    pub result: i32,
     pub created_at: DateTime<Utc>,
 }
 
@@ -0,0 +1,136 @@
+use super::match_models::EvidenceKind;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Where an uploaded object is in its lifecycle, stored as
+/// `media_objects.status`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MediaStatus {
+    /// Parts are still being uploaded.
+    Uploading,
+    /// Assembled; being hashed and scanned.
+    Processing,
+    /// Verified and usable as evidence.
+    Ready,
+    /// Hash mismatch or flagged by the malware scan; the object is deleted.
+    Rejected,
+    /// Deleted at the end of its retention period.
+    Expired,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MediaObject {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub kind: EvidenceKind,
+    pub content_type: String,
+    pub size_bytes: i64,
+    pub sha256: String,
+    pub object_key: String,
+    #[serde(skip_serializing)]
+    pub upload_id: Option<String>,
+    pub part_size: i64,
+    pub status: MediaStatus,
+    pub rejection_reason: Option<String>,
+    pub match_id: Option<Uuid>,
+    pub dispute_id: Option<Uuid>,
+    pub retain_until: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+impl MediaObject {
+    /// Number of parts the object is uploaded in; every part but the last is
+    /// exactly `part_size` bytes.
+    pub fn part_count(&self) -> i32 {
+        ((self.size_bytes + self.part_size - 1) / self.part_size) as i32
+    }
+
+    /// Expected size of part `part_number` (1-based).
+    pub fn part_len(&self, part_number: i32) -> i64 {
+        let offset = self.part_size * (part_number as i64 - 1);
+        (self.size_bytes - offset).min(self.part_size)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct MediaPart {
+    pub part_number: i32,
+    pub size_bytes: i64,
+    pub uploaded_at: DateTime<Utc>,
+}
+
+/// An upload and the parts received so far, so clients can resume.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaUpload {
+    #[serde(flatten)]
+    pub media: MediaObject,
+    pub part_count: i32,
+    pub parts: Vec<MediaPart>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct StartMediaUploadRequest {
+    pub kind: EvidenceKind,
+    #[validate(length(min = 1, max = 100))]
+    pub content_type: String,
+    #[validate(range(min = 1))]
+    pub size_bytes: i64,
+    /// Hex SHA-256 of the whole file.
+    #[validate(length(min = 64, max = 64))]
+    pub sha256: String,
+    /// Match the file is evidence for; the uploader must have played in it.
+    pub match_id: Option<Uuid>,
+}
+
+/// Attach finished uploads to a dispute as evidence.
+#[derive(Debug, Deserialize, Validate)]
+pub struct AttachMediaRequest {
+    #[validate(length(min = 1, max = 4))]
+    pub media_ids: Vec<Uuid>,
+}
+
+/// A time-limited download link.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedMediaUrl {
+    pub url: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// One dispute evidence file checked against its recorded hash.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceFileCheck {
+    pub evidence_id: Uuid,
+    pub uploaded_by: Uuid,
+    pub kind: EvidenceKind,
+    /// Hash recorded when the file was attached.
+    pub sha256: String,
+    /// Hash of the bytes in storage now; `None` if the object is gone.
+    pub stored_sha256: Option<String>,
+    pub intact: bool,
+    /// Part of the evidence reference the dispute was opened with.
+    pub on_chain: bool,
+    pub download: SignedMediaUrl,
+}
+
+/// A dispute's evidence re-hashed from storage and matched against the
+/// evidence reference sent to the dispute contract.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EvidenceVerification {
+    pub dispute_id: Uuid,
+    /// `off_chain`, `pending`, `opened`, `resolved` or `failed`.
+    pub chain_status: String,
+    pub chain_open_tx: Option<String>,
+    pub evidence_ref: Option<String>,
+    /// Reference recomputed from the stored files it covers.
+    pub recomputed_ref: Option<String>,
+    /// Every file is intact and the files reproduce `evidence_ref`.
+    pub verified: bool,
+    pub files: Vec<EvidenceFileCheck>,
+}
@@ -1,37 +1,104 @@
 // Core models
 pub mod achievement;
+pub mod admin;
+pub mod anticheat;
+pub mod audit_log;
+pub mod bracket;
+pub mod chat;
+pub mod dispute;
+pub mod friend;
 pub mod idempotency;
+pub mod kyc;
 pub mod leaderboard;
 pub mod pagination;
+pub mod payment;
+pub mod profile;
+pub mod registration;
 pub mod match_authority;
 pub mod match_models;
+pub mod media;
 pub mod matchmaker;
+pub mod notification;
 pub mod reward_settlement;
+pub mod search;
 pub mod social;
+pub mod spectator;
 pub mod stellar_account;
 pub mod stellar_transaction;
+pub mod team;
 pub mod tournament;
 pub mod user;
 pub mod wallet;
+pub mod webhook;
 
 // Re-export commonly used types - explicit to avoid ambiguity
 pub use achievement::*;
+pub use admin::{
+    AdminReasonRequest, AdminUserSummary, BanUserRequest, EscalateDisputeRequest, FeatureFlag,
+    SetFeatureFlagRequest, TournamentOverrideRequest,
+};
+pub use anticheat::{
+    AntiCheatAuditEntry, AntiCheatSignal, Detection, DetectionDetail, DetectionStatus, Finding,
+    ReviewDecision, ReviewDetectionRequest, SignalReceipt, SubmitAnomalyReportRequest,
+    SubmitTelemetryRequest,
+};
+pub use audit_log::{AuditChainReport, AuditLog, AuditLogFilter};
+pub use bracket::{
+    BracketCheckIn, BracketRound, BracketRoundView, BracketSide, BracketSlot, BracketTeam,
+    BracketView, CheckInResponse, SlotStatus, TournamentBracket,
+};
+pub use chat::{
+    ChatChannel, ChatHistory, ChatMessage, ChatMute, MuteChatUserRequest, SendChatMessageRequest,
+};
+pub use dispute::{
+    AssignDisputeRequest, AttachEvidenceRequest, DisputeCase, DisputeCaseStatus, DisputeCategory,
+    DisputeDecision, DisputeDecisionRequest, DisputeDetail, DisputeEvent, DisputeEvidence,
+    OpenDisputeRequest,
+};
+pub use friend::{
+    BlockedUser, FriendList, FriendProfile, FriendRequests, FriendWithPresence,
+    PendingFriendRequest, Presence, PresenceStatus,
+};
 pub use idempotency::*;
+pub use kyc::{
+    KycEvent, KycLevel, KycPersonalDetails, KycProfile, KycReview, KycStatus, KycStatusResponse,
+    ReviewKycRequest, StartKycRequest,
+};
 pub use pagination::{ApiResponse, PaginatedResponse, PaginationParams, DEFAULT_LIMIT, MAX_LIMIT};
+pub use payment::{
+    CreatePaymentRequest, Payment, PaymentSource, PaymentStatus, PaymentStatusView,
+};
 pub use leaderboard::*;
 pub use match_authority::*;
 pub use match_models::{
-    CreateDisputeRequest, DisputeListResponse, DisputeStatus, EloHistory, EloResponse,
-    JoinMatchmakingRequest, Match, MatchDispute, MatchResponse, MatchResult, MatchScore,
-    MatchStatus, MatchType, MatchmakingQueue, MatchmakingStatusResponse, PlayerInfo, QueueStatus,
-    ReportScoreRequest, UserElo,
+    CreateDisputeRequest, DisputeListResponse, DisputeStatus, DualReportStatus, EloHistory,
+    EloResponse, EvidenceKind, EvidenceUpload, JoinMatchmakingRequest, Match, MatchDispute,
+    MatchEvidence, MatchResponse, MatchResult, MatchScore, MatchStatus, MatchType,
+    MatchmakingQueue, MatchmakingStatusResponse, PlayerInfo, QueueStatus, ReportScoreRequest,
+    SubmitMatchResultRequest, SubmitMatchResultResponse, UserElo,
+};
+pub use media::{
+    AttachMediaRequest, EvidenceFileCheck, EvidenceVerification, MediaObject, MediaPart,
+    MediaStatus, MediaUpload, SignedMediaUrl, StartMediaUploadRequest,
 };
 pub use matchmaker::{
-    DisputeStatus, EloHistory, EloResponse, GameModeStats, GameQueueStats, JoinQueueRequest,
-    JoinQueueResponse, LeaveQueueRequest, LeaveQueueResponse, Match, MatchCandidate, MatchDispute,
-    MatchHistoryResponse, MatchmakingConfig, MatchmakingQueue, MatchmakingQueueResponse,
-    MatchmakingStats, MatchmakingStatsResponse, MatchmakingStatusResponse, MatchResult, MatchScore,
-    MatchStatus, MatchType, PlayerInfo, QueueEntry, QueueStatus, ReportScoreRequest, UserElo,
+    GameModeStats, GameQueueStats, JoinQueueRequest, JoinQueueResponse, LeaveQueueRequest,
+    LeaveQueueResponse, MatchCandidate, MatchHistoryResponse, MatchmakingConfig,
+    MatchmakingQueueResponse, MatchmakingStats, QueueEntry,
+};
+pub use notification::{
+    ChannelPreferences, Notification, NotificationCategory, NotificationChannel,
+    NotificationInbox, NotificationPreferences, PushPlatform, RegisterPushTokenRequest,
+    RemovePushTokenRequest, UpdateNotificationPreferencesRequest,
+};
+pub use profile::{
+    Badge, MatchOutcome, MatchStats, PlayerProfile, PrivacySettings, ProfileEarnings,
+    ProfileMatch, ProfileTournament, ReputationSummary, ReputationTrendPoint,
+    UpdatePrivacySettingsRequest,
+};
+pub use registration::{
+    RegistrationRequirements, RegistrationResponse, RegistrationStatus, StakeInstruction,
+    TournamentRegistration,
 };
 pub use reward_settlement::*;
 pub use stellar_account::{
@@ -41,7 +108,21 @@ pub use stellar_transaction::{
     CreateStellarTransactionRequest, StellarTransaction, StellarTransactionResponse,
     StellarTransactionStatus, StellarTransactionType,
 };
+pub use search::{
+    stake_bucket, FacetCount, PlayerHit, SearchFilters, SearchKind, SearchResponse, SearchResults,
+    StakeBucket, TeamHit, TournamentHit, STAKE_BUCKETS, TOURNAMENT_STATUS_NAMES,
+};
 pub use social::*;
+pub use spectator::{
+    LiveMatch, LiveScore, LiveScoreReport, ReportLiveScoreRequest, SpectatorDispute,
+    SpectatorPlayer, SpectatorView, TimelineEntry,
+};
+pub use team::{
+    CreateTeamRequest, EarningsSplit, InviteTeamMemberRequest, RegisterTeamRequest, Team,
+    TeamDepositRequest, TeamDetail, TeamDistribution, TeamDistributionRequest, TeamInvitation,
+    TeamInvitationStatus, TeamMember, TeamPayout, TeamRole, TeamTournament, TeamWallet,
+    TransferCaptaincyRequest, UpdateEarningsSplitsRequest, UpdateTeamRequest,
+};
 pub use tournament::{
     BracketType, CreateTournamentRequest, JoinTournamentRequest, ParticipantStatus, PrizePool,
     RoundStatus, RoundType, Tournament, TournamentListResponse, TournamentMatch,
@@ -50,7 +131,13 @@ pub use tournament::{
 };
 pub use user::*;
 pub use wallet::{
-    CreateWalletRequest, DepositRequest, PaymentMethod, PaymentProvider, Transaction,
+    AnchorRamp, CreateWalletRequest, CustodialWithdrawal, CustodialWithdrawalRequest,
+    DepositAddress, DepositRequest, PaymentMethod, PaymentProvider, RampKind, RampStatus,
+    ReviewWithdrawalRequest, StartDepositRampRequest, StartWithdrawalRampRequest, Transaction,
     TransactionResponse, TransactionStatus, TransactionType, UpdateWalletRequest, Wallet,
-    WalletBalance, WalletResponse, WithdrawalRequest,
+    WalletBalance, WalletResponse, WithdrawalRequest, WithdrawalStatus,
+};
+pub use webhook::{
+    CreateWebhookEndpointRequest, UpdateWebhookEndpointRequest, WebhookAttempt, WebhookDelivery,
+    WebhookDeliveryDetail, WebhookDeliveryStatus, WebhookEndpoint, WebhookEndpointWithSecret,
 };
@@ -0,0 +1,181 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+use validator::Validate;
+
+/// What a notification is about. Users choose channels per category.
+#[derive(
+    Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, PartialOrd, Ord, Hash, sqlx::Type,
+)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationCategory {
+    /// Sign-ins, password and two-factor changes.
+    Security,
+    /// Deposits, withdrawals and payouts.
+    Wallet,
+    Match,
+    Tournament,
+    Social,
+    /// Announcements and everything else.
+    System,
+}
+
+impl NotificationCategory {
+    pub const ALL: [NotificationCategory; 6] = [
+        NotificationCategory::Security,
+        NotificationCategory::Wallet,
+        NotificationCategory::Match,
+        NotificationCategory::Tournament,
+        NotificationCategory::Social,
+        NotificationCategory::System,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NotificationCategory::Security => "security",
+            NotificationCategory::Wallet => "wallet",
+            NotificationCategory::Match => "match",
+            NotificationCategory::Tournament => "tournament",
+            NotificationCategory::Social => "social",
+            NotificationCategory::System => "system",
+        }
+    }
+
+    /// Channels used until the user changes them.
+    pub fn default_channels(&self) -> ChannelPreferences {
+        let email = matches!(
+            self,
+            NotificationCategory::Security | NotificationCategory::Wallet
+        );
+        ChannelPreferences {
+            email,
+            push: *self != NotificationCategory::System,
+            in_app: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationChannel {
+    Email,
+    /// FCM push to the user's registered devices.
+    Push,
+    /// The inbox behind `GET /api/notifications`, plus a realtime event.
+    InApp,
+}
+
+/// Channels enabled for one category.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ChannelPreferences {
+    pub email: bool,
+    pub push: bool,
+    pub in_app: bool,
+}
+
+impl ChannelPreferences {
+    pub fn allows(&self, channel: NotificationChannel) -> bool {
+        match channel {
+            NotificationChannel::Email => self.email,
+            NotificationChannel::Push => self.push,
+            NotificationChannel::InApp => self.in_app,
+        }
+    }
+}
+
+/// A user's notification channels for every category.
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct NotificationPreferences {
+    pub categories: BTreeMap<NotificationCategory, ChannelPreferences>,
+}
+
+impl Default for NotificationPreferences {
+    fn default() -> Self {
+        Self {
+            categories: NotificationCategory::ALL
+                .iter()
+                .map(|category| (*category, category.default_channels()))
+                .collect(),
+        }
+    }
+}
+
+impl NotificationPreferences {
+    /// Channels to use for `category`. Security notifications always reach
+    /// the inbox and the user's email address.
+    pub fn channels(&self, category: NotificationCategory) -> ChannelPreferences {
+        let mut channels = self
+            .categories
+            .get(&category)
+            .copied()
+            .unwrap_or_else(|| category.default_channels());
+        if category == NotificationCategory::Security {
+            channels.email = true;
+            channels.in_app = true;
+        }
+        channels
+    }
+}
+
+/// Body of `PUT /api/notifications/preferences`. Categories left out keep
+/// their channels.
+#[derive(Debug, Deserialize)]
+pub struct UpdateNotificationPreferencesRequest {
+    pub categories: BTreeMap<NotificationCategory, ChannelPreferences>,
+}
+
+/// An inbox entry.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+#[serde(rename_all = "camelCase")]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// Finer-grained than the category, e.g. `match_found`.
+    #[serde(rename = "type")]
+    #[sqlx(rename = "type")]
+    pub kind: String,
+    pub category: NotificationCategory,
+    pub title: String,
+    pub message: String,
+    pub link: Option<String>,
+    pub link_label: Option<String>,
+    pub read: bool,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response to `GET /api/notifications`.
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NotificationInbox {
+    pub data: Vec<Notification>,
+    pub total: i64,
+    pub page: i64,
+    pub limit: i64,
+    pub unread_count: i64,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PushPlatform {
+    Android,
+    Ios,
+    Web,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct RegisterPushTokenRequest {
+    /// FCM registration token.
+    #[validate(length(min = 1, max = 4096))]
+    pub token: String,
+    pub platform: PushPlatform,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RemovePushTokenRequest {
+    pub token: String,
+}
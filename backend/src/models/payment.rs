@@ -0,0 +1,133 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+/// Where the money for a payment comes from.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentSource {
+    /// The user's custodied XLM.
+    Wallet,
+    Paystack,
+    Flutterwave,
+}
+
+/// `pending → processing → completed | failed`; only pending payments can be
+/// cancelled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum PaymentStatus {
+    Pending,
+    Processing,
+    Completed,
+    Failed,
+    Cancelled,
+}
+
+impl PaymentStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PaymentStatus::Pending => "pending",
+            PaymentStatus::Processing => "processing",
+            PaymentStatus::Completed => "completed",
+            PaymentStatus::Failed => "failed",
+            PaymentStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self,
+            PaymentStatus::Completed | PaymentStatus::Failed | PaymentStatus::Cancelled
+        )
+    }
+
+    pub fn can_transition_to(&self, next: PaymentStatus) -> bool {
+        matches!(
+            (self, next),
+            (PaymentStatus::Pending, PaymentStatus::Processing)
+                | (PaymentStatus::Pending, PaymentStatus::Cancelled)
+                | (PaymentStatus::Processing, PaymentStatus::Completed)
+                | (PaymentStatus::Processing, PaymentStatus::Failed)
+        )
+    }
+}
+
+impl std::str::FromStr for PaymentStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(PaymentStatus::Pending),
+            "processing" => Ok(PaymentStatus::Processing),
+            "completed" => Ok(PaymentStatus::Completed),
+            "failed" => Ok(PaymentStatus::Failed),
+            "cancelled" => Ok(PaymentStatus::Cancelled),
+            other => Err(format!("unknown payment status {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Payment {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// In minor units: stroops for XLM, kobo for NGN.
+    pub amount: i64,
+    pub currency: String,
+    pub source: PaymentSource,
+    pub purpose: String,
+    pub provider_reference: Option<String>,
+    pub status: PaymentStatus,
+    pub failure_reason: Option<String>,
+    pub metadata: serde_json::Value,
+    #[serde(skip_serializing)]
+    pub idempotency_key: String,
+    #[serde(skip_serializing)]
+    pub request_hash: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct CreatePaymentRequest {
+    /// In minor units: stroops for XLM, kobo for NGN.
+    #[validate(range(min = 1))]
+    pub amount: i64,
+    #[validate(length(min = 3, max = 12))]
+    pub currency: String,
+    pub source: PaymentSource,
+    /// What is being paid for, e.g. `tournament_entry`.
+    #[validate(length(min = 1, max = 64))]
+    pub purpose: String,
+    /// The provider's transaction reference; required for card payments.
+    #[validate(length(min = 1, max = 128))]
+    pub provider_reference: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+}
+
+/// Response to `GET /api/payments/{id}/status`, and the payload of its status
+/// stream.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PaymentStatusView {
+    pub payment_id: Uuid,
+    pub status: PaymentStatus,
+    pub failure_reason: Option<String>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl From<&Payment> for PaymentStatusView {
+    fn from(payment: &Payment) -> Self {
+        Self {
+            payment_id: payment.id,
+            status: payment.status,
+            failure_reason: payment.failure_reason.clone(),
+            updated_at: payment.updated_at,
+        }
+    }
+}
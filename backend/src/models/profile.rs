@@ -0,0 +1,207 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A player's public profile with match, reputation, earnings and
+/// tournament history.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PlayerProfile {
+    pub user_id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub bio: Option<String>,
+    pub country_code: Option<String>,
+    pub joined_at: DateTime<Utc>,
+    pub stats: MatchStats,
+    pub recent_matches: Vec<ProfileMatch>,
+    pub reputation: ReputationSummary,
+    /// `None` when the player hides their earnings from others.
+    pub earnings: Option<ProfileEarnings>,
+    pub earnings_hidden: bool,
+    pub badges: Vec<Badge>,
+    pub recent_tournaments: Vec<ProfileTournament>,
+}
+
+impl PlayerProfile {
+    /// The profile as `viewer` may see it: earnings and prize amounts are
+    /// removed unless they are public or `viewer` is the player.
+    pub fn visible_to(mut self, viewer: Option<Uuid>) -> Self {
+        if self.earnings_hidden && viewer != Some(self.user_id) {
+            self.earnings = None;
+            for tournament in &mut self.recent_tournaments {
+                tournament.prize_amount = None;
+            }
+        }
+        self
+    }
+}
+
+/// Record over completed matches.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MatchStats {
+    pub matches_played: i64,
+    pub wins: i64,
+    pub losses: i64,
+    pub draws: i64,
+    /// Percentage of matches won.
+    pub win_rate: f64,
+}
+
+impl MatchStats {
+    pub fn new(matches_played: i64, wins: i64, losses: i64) -> Self {
+        let win_rate = if matches_played > 0 {
+            (wins as f64 / matches_played as f64) * 100.0
+        } else {
+            0.0
+        };
+        Self {
+            matches_played,
+            wins,
+            losses,
+            draws: matches_played - wins - losses,
+            win_rate,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum MatchOutcome {
+    Win,
+    Loss,
+    Draw,
+}
+
+/// A completed match from the player's side.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProfileMatch {
+    pub match_id: Uuid,
+    pub game_mode: String,
+    pub opponent_id: Option<Uuid>,
+    pub opponent_username: Option<String>,
+    pub result: MatchOutcome,
+    pub score: Option<i32>,
+    pub opponent_score: Option<i32>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReputationSummary {
+    pub skill_score: i32,
+    pub fair_play_score: i32,
+    /// Daily on-chain reputation changes, oldest first.
+    pub trend: Vec<ReputationTrendPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReputationTrendPoint {
+    pub day: DateTime<Utc>,
+    pub skill_delta: i64,
+    pub fair_play_delta: i64,
+}
+
+/// Prize payouts received, in the pool asset's smallest unit.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProfileEarnings {
+    pub all_time: i64,
+    pub season: i64,
+    pub season_id: String,
+}
+
+/// An unlocked achievement.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Badge {
+    pub achievement_id: Uuid,
+    pub name: String,
+    pub icon_url: Option<String>,
+    pub rarity: String,
+    pub unlocked_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProfileTournament {
+    pub tournament_id: Uuid,
+    pub name: String,
+    pub game: String,
+    pub status: i32,
+    pub final_rank: Option<i32>,
+    pub prize_amount: Option<i64>,
+    pub registered_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacySettings {
+    /// Hide earnings and tournament prizes from other players.
+    pub hide_earnings: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdatePrivacySettingsRequest {
+    pub hide_earnings: Option<bool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(earnings_hidden: bool) -> PlayerProfile {
+        PlayerProfile {
+            user_id: Uuid::new_v4(),
+            username: "ada".to_string(),
+            display_name: None,
+            avatar_url: None,
+            bio: None,
+            country_code: None,
+            joined_at: Utc::now(),
+            stats: MatchStats::new(4, 3, 1),
+            recent_matches: Vec::new(),
+            reputation: ReputationSummary {
+                skill_score: 1000,
+                fair_play_score: 100,
+                trend: Vec::new(),
+            },
+            earnings: Some(ProfileEarnings {
+                all_time: 500,
+                season: 100,
+                season_id: "2026-q4".to_string(),
+            }),
+            earnings_hidden,
+            badges: Vec::new(),
+            recent_tournaments: vec![ProfileTournament {
+                tournament_id: Uuid::new_v4(),
+                name: "Cup".to_string(),
+                game: "fifa".to_string(),
+                status: 5,
+                final_rank: Some(1),
+                prize_amount: Some(400),
+                registered_at: Utc::now(),
+            }],
+        }
+    }
+
+    #[test]
+    fn test_hidden_earnings_are_only_shown_to_the_player() {
+        let hidden = profile(true);
+        let owner = hidden.user_id;
+
+        let public = hidden.clone().visible_to(None);
+        assert!(public.earnings.is_none());
+        assert_eq!(public.recent_tournaments[0].prize_amount, None);
+        assert_eq!(public.recent_tournaments[0].final_rank, Some(1));
+
+        let own = hidden.visible_to(Some(owner));
+        assert!(own.earnings.is_some());
+        assert!(profile(false).visible_to(None).earnings.is_some());
+    }
+
+    #[test]
+    fn test_match_stats_counts_draws() {
+        let stats = MatchStats::new(4, 2, 1);
+        assert_eq!(stats.draws, 1);
+        assert_eq!(stats.win_rate, 50.0);
+        assert_eq!(MatchStats::new(0, 0, 0).win_rate, 0.0);
+    }
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationStatus {
+    /// Eligible, but the stake has not been seen on the staking manager yet.
+    PendingStake,
+    Registered,
+    Rejected,
+}
+
+impl RegistrationStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RegistrationStatus::PendingStake => "pending_stake",
+            RegistrationStatus::Registered => "registered",
+            RegistrationStatus::Rejected => "rejected",
+        }
+    }
+}
+
+impl std::str::FromStr for RegistrationStatus {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending_stake" => Ok(RegistrationStatus::PendingStake),
+            "registered" => Ok(RegistrationStatus::Registered),
+            "rejected" => Ok(RegistrationStatus::Rejected),
+            other => Err(format!("unknown registration status: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TournamentRegistration {
+    pub id: Uuid,
+    pub tournament_id: Uuid,
+    pub user_id: Uuid,
+    pub idempotency_key: String,
+    pub status: String,
+    pub stellar_address: Option<String>,
+    pub participant_id: Option<Uuid>,
+    pub rejection_reason: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RegistrationRequirements {
+    pub tournament_id: Uuid,
+    pub min_reputation: Option<i32>,
+    pub min_fair_play: Option<i32>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// The call the player must sign to stake for a tournament.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StakeInstruction {
+    pub contract_id: String,
+    pub function: String,
+    pub stellar_address: String,
+    /// Hex-encoded `BytesN<32>` tournament id on the staking manager.
+    pub tournament_key: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistrationResponse {
+    pub registration_id: Uuid,
+    pub tournament_id: Uuid,
+    pub status: RegistrationStatus,
+    /// Repeat the request with this key to re-check a pending stake.
+    pub idempotency_key: String,
+    pub participant_id: Option<Uuid>,
+    pub rejection_reason: Option<String>,
+    pub stake: Option<StakeInstruction>,
+    pub updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,221 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::collections::BTreeMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Names of the `tournaments.status` codes, by code.
+pub const TOURNAMENT_STATUS_NAMES: [&str; 7] = [
+    "draft",
+    "upcoming",
+    "registration_open",
+    "registration_closed",
+    "in_progress",
+    "completed",
+    "cancelled",
+];
+
+/// Entry fee range in the pool currency's smallest unit.
+pub struct StakeBucket {
+    pub name: &'static str,
+    /// Highest fee in the bucket; the bucket starts above the previous one.
+    pub max: Option<i64>,
+}
+
+/// Buckets of the `stake` facet, in ascending order.
+pub const STAKE_BUCKETS: &[StakeBucket] = &[
+    StakeBucket {
+        name: "free",
+        max: Some(0),
+    },
+    StakeBucket {
+        name: "low",
+        max: Some(100_000),
+    },
+    StakeBucket {
+        name: "medium",
+        max: Some(1_000_000),
+    },
+    StakeBucket {
+        name: "high",
+        max: None,
+    },
+];
+
+pub fn stake_bucket(entry_fee: i64) -> &'static str {
+    STAKE_BUCKETS
+        .iter()
+        .find(|bucket| bucket.max.is_none_or(|max| entry_fee <= max))
+        .map_or("high", |bucket| bucket.name)
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum SearchKind {
+    Tournament,
+    Player,
+    Team,
+}
+
+/// Facet filters, written as `facet:value` pairs separated by commas, with
+/// alternatives separated by `|`:
+/// `game:fifa|pes,region:NGA,status:registration_open,stake:0..100000`.
+///
+/// `region` is a tournament's region or a player's country code. Players are
+/// only filtered by region.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SearchFilters {
+    pub games: Vec<String>,
+    pub regions: Vec<String>,
+    /// `tournaments.status` codes.
+    pub statuses: Vec<i32>,
+    pub stake_min: Option<i64>,
+    pub stake_max: Option<i64>,
+}
+
+impl FromStr for SearchFilters {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let mut filters = Self::default();
+        for filter in value.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+            let (facet, raw) = filter
+                .split_once(':')
+                .ok_or_else(|| format!("Invalid filter `{}`; expected facet:value", filter))?;
+            let values = raw.split('|').map(str::trim).filter(|v| !v.is_empty());
+            match facet.trim() {
+                "game" => filters.games.extend(values.map(str::to_lowercase)),
+                "region" => filters.regions.extend(values.map(str::to_uppercase)),
+                "status" => {
+                    for status in values {
+                        let code = TOURNAMENT_STATUS_NAMES
+                            .iter()
+                            .position(|name| *name == status)
+                            .ok_or_else(|| format!("Unknown tournament status `{}`", status))?;
+                        filters.statuses.push(code as i32);
+                    }
+                }
+                "stake" => {
+                    let range = raw.trim();
+                    let (min, max) = range.split_once("..").ok_or_else(|| {
+                        format!("Invalid stake range `{}`; expected min..max", range)
+                    })?;
+                    let bound = |bound: &str| {
+                        let bound = bound.trim();
+                        (!bound.is_empty())
+                            .then(|| bound.parse::<i64>())
+                            .transpose()
+                            .map_err(|_| format!("Invalid stake range `{}`", range))
+                    };
+                    filters.stake_min = bound(min)?;
+                    filters.stake_max = bound(max)?;
+                }
+                other => return Err(format!("Unknown filter `{}`", other)),
+            }
+        }
+        Ok(filters)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TournamentHit {
+    pub id: Uuid,
+    pub name: String,
+    pub game: String,
+    pub region: Option<String>,
+    pub status: String,
+    pub entry_fee: i64,
+    pub entry_fee_currency: Option<String>,
+    pub prize_pool: i64,
+    pub start_time: DateTime<Utc>,
+    /// Relevance; only comparable within one response.
+    #[serde(default, alias = "_rankingScore")]
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PlayerHit {
+    pub id: Uuid,
+    pub username: String,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
+    pub country_code: Option<String>,
+    pub skill_score: Option<i32>,
+    #[serde(default, alias = "_rankingScore")]
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TeamHit {
+    pub id: Uuid,
+    pub name: String,
+    pub tag: String,
+    pub game: Option<String>,
+    pub avatar_url: Option<String>,
+    pub member_count: i64,
+    #[serde(default, alias = "_rankingScore")]
+    pub score: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct FacetCount {
+    pub value: String,
+    pub count: i64,
+}
+
+/// One page of hits of a kind, with facet counts over all its hits.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResults<T> {
+    pub total: i64,
+    pub page: i64,
+    pub limit: i64,
+    pub hits: Vec<T>,
+    pub facets: BTreeMap<String, Vec<FacetCount>>,
+}
+
+/// Results of each kind searched for.
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchResponse {
+    pub query: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tournaments: Option<SearchResults<TournamentHit>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub players: Option<SearchResults<PlayerHit>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub teams: Option<SearchResults<TeamHit>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_filters() {
+        let filters: SearchFilters =
+            "game:FIFA|pes, region:nga,status:registration_open,stake:..100000"
+                .parse()
+                .unwrap();
+        assert_eq!(filters.games, vec!["fifa", "pes"]);
+        assert_eq!(filters.regions, vec!["NGA"]);
+        assert_eq!(filters.statuses, vec![2]);
+        assert_eq!(filters.stake_min, None);
+        assert_eq!(filters.stake_max, Some(100_000));
+
+        assert_eq!(
+            "".parse::<SearchFilters>().unwrap(),
+            SearchFilters::default()
+        );
+        assert!("status:live".parse::<SearchFilters>().is_err());
+        assert!("stake:100".parse::<SearchFilters>().is_err());
+        assert!("map:dust2".parse::<SearchFilters>().is_err());
+    }
+
+    #[test]
+    fn test_stake_bucket() {
+        assert_eq!(stake_bucket(0), "free");
+        assert_eq!(stake_bucket(100_000), "low");
+        assert_eq!(stake_bucket(100_001), "medium");
+        assert_eq!(stake_bucket(5_000_000), "high");
+    }
+}
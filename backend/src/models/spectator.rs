@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SpectatorPlayer {
+    pub id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+}
+
+/// A live score posted by one of the players.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LiveScoreReport {
+    pub reported_by: Uuid,
+    pub round: i32,
+    pub player1_score: i32,
+    pub player2_score: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Score after the latest reported round.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LiveScore {
+    pub round: i32,
+    pub player1_score: i32,
+    pub player2_score: i32,
+    /// Both players posted this score for the round.
+    pub confirmed: bool,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// One moment in a match, as spectators see it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TimelineEntry {
+    Started {
+        at: DateTime<Utc>,
+    },
+    RoundScore {
+        at: DateTime<Utc>,
+        round: i32,
+        player1_score: i32,
+        player2_score: i32,
+        reported_by: Uuid,
+    },
+    /// An action of the match lifecycle or escrow contracts.
+    Chain {
+        at: DateTime<Utc>,
+        contract: String,
+        action: String,
+        tx_hash: String,
+    },
+    DisputeOpened {
+        at: DateTime<Utc>,
+        dispute_id: Uuid,
+    },
+    Completed {
+        at: DateTime<Utc>,
+        winner_id: Option<Uuid>,
+    },
+}
+
+impl TimelineEntry {
+    pub fn at(&self) -> DateTime<Utc> {
+        match self {
+            TimelineEntry::Started { at }
+            | TimelineEntry::RoundScore { at, .. }
+            | TimelineEntry::Chain { at, .. }
+            | TimelineEntry::DisputeOpened { at, .. }
+            | TimelineEntry::Completed { at, .. } => *at,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorDispute {
+    pub id: Uuid,
+    /// `pending`, `under_review`, `resolved` or `rejected`.
+    pub status: String,
+    pub opened_at: DateTime<Utc>,
+    pub resolved_at: Option<DateTime<Utc>>,
+}
+
+/// A match as spectators see it: everything up to `as_of`, the broadcast delay
+/// ago.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SpectatorView {
+    pub match_id: Uuid,
+    pub tournament_id: Option<Uuid>,
+    pub game_mode: String,
+    pub map: Option<String>,
+    pub status: String,
+    pub player1: Option<SpectatorPlayer>,
+    pub player2: Option<SpectatorPlayer>,
+    pub score: Option<LiveScore>,
+    pub winner_id: Option<Uuid>,
+    /// Oldest first.
+    pub timeline: Vec<TimelineEntry>,
+    pub dispute: Option<SpectatorDispute>,
+    pub delay_secs: u64,
+    pub as_of: DateTime<Utc>,
+}
+
+/// A match in progress that can be spectated.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LiveMatch {
+    pub match_id: Uuid,
+    pub tournament_id: Option<Uuid>,
+    pub game_mode: String,
+    pub player1_username: Option<String>,
+    pub player2_username: Option<String>,
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+/// Score after a round, posted by a player of the match.
+#[derive(Debug, Deserialize, Validate)]
+pub struct ReportLiveScoreRequest {
+    #[validate(range(min = 1, max = 1000))]
+    pub round: i32,
+    /// The reporter's own score.
+    #[validate(range(min = 0))]
+    pub score: i32,
+    #[validate(range(min = 0))]
+    pub opponent_score: i32,
+}
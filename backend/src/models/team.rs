@@ -0,0 +1,172 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+use validator::Validate;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TeamRole {
+    Captain,
+    Member,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Team {
+    pub id: Uuid,
+    pub name: String,
+    pub tag: String,
+    pub game: Option<String>,
+    pub description: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_by: Uuid,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub disbanded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TeamMember {
+    pub user_id: Uuid,
+    pub username: String,
+    pub avatar_url: Option<String>,
+    pub role: TeamRole,
+    /// Share of team wallet distributions in basis points.
+    pub share_bps: i32,
+    pub joined_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamDetail {
+    #[serde(flatten)]
+    pub team: Team,
+    pub members: Vec<TeamMember>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum TeamInvitationStatus {
+    Pending,
+    Accepted,
+    Declined,
+    Revoked,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TeamInvitation {
+    pub id: Uuid,
+    pub team_id: Uuid,
+    pub team_name: String,
+    pub user_id: Uuid,
+    pub invited_by: Uuid,
+    pub status: TeamInvitationStatus,
+    pub created_at: DateTime<Utc>,
+    pub expires_at: DateTime<Utc>,
+    pub responded_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct CreateTeamRequest {
+    #[validate(length(min = 3, max = 50))]
+    pub name: String,
+    /// Short letters-and-digits tag shown next to member names.
+    #[validate(length(min = 2, max = 8))]
+    pub tag: String,
+    #[validate(length(min = 1, max = 50))]
+    pub game: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    #[validate(url)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct UpdateTeamRequest {
+    #[validate(length(min = 3, max = 50))]
+    pub name: Option<String>,
+    #[validate(length(min = 2, max = 8))]
+    pub tag: Option<String>,
+    #[validate(length(min = 1, max = 50))]
+    pub game: Option<String>,
+    #[validate(length(max = 500))]
+    pub description: Option<String>,
+    #[validate(url)]
+    pub avatar_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct InviteTeamMemberRequest {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TransferCaptaincyRequest {
+    pub user_id: Uuid,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, FromRow)]
+pub struct EarningsSplit {
+    pub user_id: Uuid,
+    pub share_bps: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateEarningsSplitsRequest {
+    /// One entry per member; shares must sum to 10000.
+    pub splits: Vec<EarningsSplit>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamWallet {
+    pub team_id: Uuid,
+    pub asset: String,
+    pub balance: i64,
+    pub splits: Vec<EarningsSplit>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamDepositRequest {
+    /// Amount moved from the member's balance, in stroops.
+    pub amount: i64,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct TeamDistributionRequest {
+    /// Amount to pay out; the whole balance when absent.
+    pub amount: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamPayout {
+    pub user_id: Uuid,
+    pub amount: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamDistribution {
+    pub team_id: Uuid,
+    pub amount: i64,
+    pub payouts: Vec<TeamPayout>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct RegisterTeamRequest {
+    /// Members playing; exactly the tournament's team size, captain included.
+    pub lineup: Vec<Uuid>,
+    pub payment_method: String,
+    pub payment_reference: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TeamTournament {
+    pub tournament_id: Uuid,
+    pub name: String,
+    pub game: String,
+    pub status: i32,
+    pub final_rank: Option<i32>,
+    pub prize_amount: Option<i64>,
+    pub registered_at: DateTime<Utc>,
+    pub lineup: Vec<Uuid>,
+}
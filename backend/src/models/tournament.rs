@@ -22,13 +22,17 @@ pub struct Tournament {
     pub start_time: DateTime<Utc>,
     pub end_time: Option<DateTime<Utc>>,
     pub registration_deadline: DateTime<Utc>,
-    pub created_by: Uuid,
+    pub created_by: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub bracket_type: BracketType,
     pub rules: Option<String>,
     pub min_skill_level: Option<i32>, // For skill-based matchmaking
     pub max_skill_level: Option<i32>,
+    pub cleaned_up_at: Option<DateTime<Utc>>,
+    pub region: Option<String>,
+    /// Players per team; `None` for individual tournaments.
+    pub team_size: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -51,15 +55,29 @@ impl std::fmt::Display for TournamentType {
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[repr(i32)]
 pub enum TournamentStatus {
-    Draft,
-    Upcoming,
-    RegistrationOpen,
-    RegistrationClosed,
-    InProgress,
-    Completed,
-    Cancelled,
+    Draft = 0,
+    Upcoming = 1,
+    RegistrationOpen = 2,
+    RegistrationClosed = 3,
+    InProgress = 4,
+    Completed = 5,
+    Cancelled = 6,
+}
+
+impl From<i32> for TournamentStatus {
+    fn from(code: i32) -> Self {
+        match code {
+            1 => TournamentStatus::Upcoming,
+            2 => TournamentStatus::RegistrationOpen,
+            3 => TournamentStatus::RegistrationClosed,
+            4 => TournamentStatus::InProgress,
+            5 => TournamentStatus::Completed,
+            6 => TournamentStatus::Cancelled,
+            _ => TournamentStatus::Draft,
+        }
+    }
 }
 
 impl std::fmt::Display for TournamentStatus {
@@ -111,6 +129,10 @@ pub struct CreateTournamentRequest {
     pub rules: Option<String>,
     pub min_skill_level: Option<i32>,
     pub max_skill_level: Option<i32>,
+    /// Players per team; set for team tournaments, which teams enter through
+    /// `/api/teams`.
+    #[validate(range(min = 2, max = 10))]
+    pub team_size: Option<i32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -171,17 +193,33 @@ pub struct TournamentParticipant {
     pub entry_fee_currency: Option<String>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub prize_tx_hash: Option<String>,
+    /// Team the captain registered; set for team tournaments only.
+    pub team_id: Option<Uuid>,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
-#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[repr(i32)]
 pub enum ParticipantStatus {
-    Registered,
-    Paid,
-    Active,
-    Eliminated,
-    Disqualified,
-    Withdrawn,
+    Registered = 0,
+    Paid = 1,
+    Active = 2,
+    Eliminated = 3,
+    Disqualified = 4,
+    Withdrawn = 5,
+}
+
+impl From<i32> for ParticipantStatus {
+    fn from(code: i32) -> Self {
+        match code {
+            1 => ParticipantStatus::Paid,
+            2 => ParticipantStatus::Active,
+            3 => ParticipantStatus::Eliminated,
+            4 => ParticipantStatus::Disqualified,
+            5 => ParticipantStatus::Withdrawn,
+            _ => ParticipantStatus::Registered,
+        }
+    }
 }
 
 impl std::fmt::Display for ParticipantStatus {
@@ -241,12 +279,23 @@ pub struct TournamentListResponse {
 // ===== Additional Types for Complete Tournament Management =====
 
 #[derive(Debug, Clone, Serialize, Deserialize, sqlx::Type, PartialEq, Eq)]
-#[sqlx(type_name = "bracket_type", rename_all = "lowercase")]
+#[repr(i32)]
 pub enum BracketType {
-    SingleElimination,
-    DoubleElimination,
-    RoundRobin,
-    Swiss,
+    SingleElimination = 0,
+    DoubleElimination = 1,
+    RoundRobin = 2,
+    Swiss = 3,
+}
+
+impl From<i32> for BracketType {
+    fn from(code: i32) -> Self {
+        match code {
+            1 => BracketType::DoubleElimination,
+            2 => BracketType::RoundRobin,
+            3 => BracketType::Swiss,
+            _ => BracketType::SingleElimination,
+        }
+    }
 }
 
 impl std::fmt::Display for BracketType {
@@ -346,7 +395,7 @@ pub struct TournamentMatch {
     pub tournament_id: Uuid,
     pub round_id: Uuid,
     pub match_number: i32,
-    pub player1_id: Uuid,
+    pub player1_id: Option<Uuid>,
     pub player2_id: Option<Uuid>,
     pub winner_id: Option<Uuid>,
     pub player1_score: Option<i32>,
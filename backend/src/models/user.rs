@@ -30,6 +30,13 @@ pub struct User {
     pub device_fingerprint: Option<String>,
 }
 
+impl User {
+    /// Whether a ban is in force at `now`.
+    pub fn is_banned_at(&self, now: DateTime<Utc>) -> bool {
+        self.is_banned.unwrap_or(false) && self.banned_until.is_none_or(|until| until > now)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CreateUserRequest {
     pub username: String,
@@ -42,6 +49,9 @@ pub struct CreateUserRequest {
 pub struct LoginRequest {
     pub email: String,
     pub password: String,
+    /// TOTP or recovery code, for users with two-factor authentication.
+    #[serde(default)]
+    pub totp_code: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
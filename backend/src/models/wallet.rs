@@ -5,7 +5,7 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Wallet {
@@ -18,7 +18,7 @@ pub struct Wallet {
     pub balance_ngn: Option<i64>, // in kobo
     pub balance_arenax_tokens: Option<i64>,
     pub balance_xlm: Option<i64>, // in stroops
-    pub stellar_account_id: Option<String>,
+    pub stellar_account_id: Option<Uuid>,
     pub stellar_public_key: Option<String>,
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
@@ -28,15 +28,15 @@ pub struct Wallet {
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Transaction {
     pub id: Uuid,
-    pub user_id: Uuid,
+    pub user_id: Option<Uuid>,
     pub transaction_type: TransactionType,
     pub amount: Decimal,
     pub currency: String,
     pub status: TransactionStatus,
     pub reference: String, // External payment reference
-    pub description: String,
+    pub description: Option<String>,
     pub metadata: Option<String>, // JSON object
-    pub stellar_transaction_id: Option<String>,
+    pub stellar_transaction_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
@@ -202,15 +202,22 @@ pub struct TransactionResponse {
     pub currency: String,
     pub status: TransactionStatus,
     pub reference: String,
-    pub description: String,
-    pub stellar_transaction_id: Option<String>,
+    pub description: Option<String>,
+    pub stellar_transaction_id: Option<Uuid>,
     pub created_at: DateTime<Utc>,
     pub completed_at: Option<DateTime<Utc>>,
 }
 
+fn validate_positive_amount(amount: &Decimal) -> Result<(), ValidationError> {
+    if *amount < Decimal::ONE {
+        return Err(ValidationError::new("range"));
+    }
+    Ok(())
+}
+
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct DepositRequest {
-    #[validate(range(min = 1))]
+    #[validate(custom(function = "validate_positive_amount"))]
     pub amount: Decimal,
     #[validate(length(min = 3, max = 10))]
     pub currency: String, // "NGN", "XLM", "ARENAX_TOKEN"
@@ -219,7 +226,7 @@ pub struct DepositRequest {
 
 #[derive(Debug, Serialize, Deserialize, Validate)]
 pub struct WithdrawalRequest {
-    #[validate(range(min = 1))]
+    #[validate(custom(function = "validate_positive_amount"))]
     pub amount: Decimal,
     #[validate(length(min = 3, max = 10))]
     pub currency: String,
@@ -227,3 +234,153 @@ pub struct WithdrawalRequest {
     pub destination: String, // Bank account, Stellar address, etc.
     pub payment_method: String,
 }
+
+// Custodial Stellar wallet
+
+/// Where a user sends XLM to fund their custodial balance: a muxed address on
+/// the platform's custody account.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DepositAddress {
+    /// Muxed address (`M...`) to pay.
+    pub address: String,
+    /// Underlying custody account (`G...`), for wallets without muxed support.
+    pub account: String,
+    /// Muxed id; wallets without muxed support cannot attribute deposits.
+    pub muxed_id: String,
+    pub asset: String,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum WithdrawalStatus {
+    /// Above the approval threshold; waiting for an admin.
+    PendingApproval,
+    Approved,
+    Submitting,
+    Completed,
+    Rejected,
+    /// Rejected on chain; the funds were returned to the user.
+    Failed,
+    /// The payout's outcome is unknown; the funds stay held until it is
+    /// reconciled.
+    Unconfirmed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CustodialWithdrawal {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub asset: String,
+    /// In stroops.
+    pub amount: i64,
+    pub destination: String,
+    pub status: WithdrawalStatus,
+    pub requires_approval: bool,
+    pub idempotency_key: Option<String>,
+    pub transaction_id: Uuid,
+    pub reviewed_by: Option<Uuid>,
+    pub reviewed_at: Option<DateTime<Utc>>,
+    pub review_note: Option<String>,
+    pub tx_hash: Option<String>,
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct CustodialWithdrawalRequest {
+    /// In stroops.
+    #[validate(range(min = 1))]
+    pub amount: i64,
+    /// Stellar account (`G...`) to pay out to.
+    #[validate(length(equal = 56))]
+    pub destination: String,
+    /// Retrying with the same key returns the original withdrawal.
+    #[validate(length(min = 1, max = 128))]
+    pub idempotency_key: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ReviewWithdrawalRequest {
+    pub note: Option<String>,
+}
+
+// Fiat on/off-ramps through a SEP-24 anchor
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RampKind {
+    Deposit,
+    Withdrawal,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, sqlx::Type)]
+#[sqlx(type_name = "text", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum RampStatus {
+    /// The user has not finished the anchor's interactive flow.
+    Interactive,
+    /// Waiting on the anchor, or on the user's fiat transfer.
+    Pending,
+    /// Our payment to the anchor is being submitted.
+    Paying,
+    Completed,
+    Failed,
+    Expired,
+    /// The payment to the anchor's outcome is unknown; the funds stay held
+    /// until it is reconciled.
+    Unconfirmed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AnchorRamp {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: RampKind,
+    pub asset_code: String,
+    pub asset_issuer: String,
+    /// The anchor's transaction id.
+    pub anchor_id: String,
+    pub status: RampStatus,
+    /// Last SEP-24 status reported by the anchor.
+    pub anchor_status: String,
+    /// Anchor page the user completes the ramp on.
+    pub interactive_url: String,
+    /// Requested amount in stroops; held from the balance for withdrawals.
+    pub amount: Option<i64>,
+    pub amount_in: Option<i64>,
+    pub amount_out: Option<i64>,
+    pub amount_fee: Option<i64>,
+    pub payout_tx_hash: Option<String>,
+    pub stellar_transaction_id: Option<String>,
+    pub message: Option<String>,
+    pub transaction_id: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct StartDepositRampRequest {
+    #[validate(length(min = 1, max = 12))]
+    pub asset_code: String,
+    /// In stroops; the user can still change it in the anchor's flow.
+    #[validate(range(min = 1))]
+    pub amount: Option<i64>,
+    #[validate(length(min = 2, max = 5))]
+    pub lang: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Validate)]
+pub struct StartWithdrawalRampRequest {
+    #[validate(length(min = 1, max = 12))]
+    pub asset_code: String,
+    /// In stroops; held from the balance until the ramp settles.
+    #[validate(range(min = 1))]
+    pub amount: i64,
+    #[validate(length(min = 2, max = 5))]
+    pub lang: Option<String>,
+}
@@ -201,7 +201,7 @@ struct ParticipantWithElo {
 /// Generates standard tournament bracket seeding order.
 /// For bracket_size=8: returns [1, 8, 4, 5, 2, 7, 3, 6]
 /// This ensures seed 1 and 2 can only meet in the final.
-fn generate_bracket_order(bracket_size: usize) -> Vec<usize> {
+pub(crate) fn generate_bracket_order(bracket_size: usize) -> Vec<usize> {
     if bracket_size == 1 {
         return vec![1];
     }
@@ -37,6 +37,12 @@ impl RealtimeAuth {
             self.authorize_user_channel(user_id, channel)
         } else if channel.starts_with("match:") {
             self.authorize_match_channel(user_id, channel).await
+        } else if channel.starts_with("tournament:") {
+            self.authorize_tournament_channel(channel).await
+        } else if channel.starts_with("presence:") {
+            self.authorize_presence_channel(user_id, channel).await
+        } else if channel.starts_with("spectate:") {
+            self.authorize_spectate_channel(channel).await
         } else {
             Err(AuthError::InvalidChannel(format!("Unknown channel prefix: {}", channel)))
         }
@@ -106,6 +112,80 @@ impl RealtimeAuth {
         }
     }
 
+    /// Tournament channels are public: brackets and results can be followed by
+    /// anyone, so only the tournament's existence is checked.
+    async fn authorize_tournament_channel(&self, channel: &str) -> Result<(), AuthError> {
+        let tournament_id_str = channel.strip_prefix("tournament:").unwrap();
+        let tournament_id = Uuid::parse_str(tournament_id_str)
+            .map_err(|_| AuthError::InvalidChannel("Invalid tournament ID in channel name".to_string()))?;
+
+        let exists: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM tournaments WHERE id = $1)")
+                .bind(tournament_id)
+                .fetch_one(&self.db_pool)
+                .await
+                .map_err(|e| AuthError::Database(e.to_string()))?;
+
+        if exists {
+            Ok(())
+        } else {
+            Err(AuthError::InvalidChannel("Tournament not found".to_string()))
+        }
+    }
+
+    /// Anyone may spectate a match; what they see is delayed and leaves out
+    /// the players' private events (see `http::ws`).
+    async fn authorize_spectate_channel(&self, channel: &str) -> Result<(), AuthError> {
+        let match_id_str = channel.strip_prefix("spectate:").unwrap();
+        let match_id = Uuid::parse_str(match_id_str)
+            .map_err(|_| AuthError::InvalidChannel("Invalid match ID in channel name".to_string()))?;
+
+        let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM matches WHERE id = $1)")
+            .bind(match_id)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| AuthError::Database(e.to_string()))?;
+
+        if exists {
+            Ok(())
+        } else {
+            Err(AuthError::InvalidChannel("Match not found".to_string()))
+        }
+    }
+
+    /// Presence is shared with friends only. Blocking ends a friendship, so a
+    /// blocked user never qualifies.
+    async fn authorize_presence_channel(&self, user_id: Uuid, channel: &str) -> Result<(), AuthError> {
+        let target_id_str = channel.strip_prefix("presence:").unwrap();
+        let target_id = Uuid::parse_str(target_id_str)
+            .map_err(|_| AuthError::InvalidChannel("Invalid user ID in channel name".to_string()))?;
+        if target_id == user_id {
+            return Ok(());
+        }
+
+        let are_friends: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM friends
+                WHERE status = 'accepted'
+                  AND ((user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1))
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(target_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(|e| AuthError::Database(e.to_string()))?;
+
+        if are_friends {
+            Ok(())
+        } else {
+            warn!(user_id = %user_id, target_id = %target_id, "Unauthorized attempt to follow presence");
+            Err(AuthError::Unauthorized("Presence is only shared with friends".to_string()))
+        }
+    }
+
     /// Authorize publishing to a channel (if clients are allowed to publish).
     pub async fn authorize_publish(
         &self,
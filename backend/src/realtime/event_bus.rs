@@ -1,6 +1,8 @@
 use crate::realtime::events::{channels, RealtimeEvent};
+use crate::service::webhooks::{WebhookEvent, WebhookService};
 use redis::aio::ConnectionManager;
 use redis::AsyncCommands;
+use std::sync::Arc;
 use tracing::{error, debug};
 use uuid::Uuid;
 
@@ -9,11 +11,21 @@ use uuid::Uuid;
 #[derive(Clone)]
 pub struct EventBus {
     redis: ConnectionManager,
+    webhooks: Option<Arc<WebhookService>>,
 }
 
 impl EventBus {
     pub fn new(redis: ConnectionManager) -> Self {
-        Self { redis }
+        Self {
+            redis,
+            webhooks: None,
+        }
+    }
+
+    /// Also hand match and tournament events to outbound webhooks.
+    pub fn with_webhooks(mut self, webhooks: Arc<WebhookService>) -> Self {
+        self.webhooks = Some(webhooks);
+        self
     }
 
     /// Publish an event to a specific user's channel.
@@ -26,6 +38,35 @@ impl EventBus {
     pub async fn publish_to_match(&self, match_id: Uuid, event: &RealtimeEvent) {
         let channel = channels::match_channel(match_id);
         self.publish(&channel, event).await;
+        self.record_webhook(WebhookEvent::for_match(match_id, event)).await;
+    }
+
+    /// Publish an event to a specific tournament's channel.
+    pub async fn publish_to_tournament(&self, tournament_id: Uuid, event: &RealtimeEvent) {
+        let channel = channels::tournament_channel(tournament_id);
+        self.publish(&channel, event).await;
+        self.record_webhook(WebhookEvent::for_tournament(tournament_id, event))
+            .await;
+    }
+
+    /// Publish an event to a user's presence channel, followed by friends.
+    pub async fn publish_presence(&self, user_id: Uuid, event: &RealtimeEvent) {
+        let channel = channels::presence_channel(user_id);
+        self.publish(&channel, event).await;
+    }
+
+    /// Queue webhook deliveries for `event`, if it is offered as a webhook.
+    async fn record_webhook(&self, event: Option<WebhookEvent>) {
+        let (Some(webhooks), Some(event)) = (&self.webhooks, event) else {
+            return;
+        };
+        if let Err(e) = webhooks.record(&event).await {
+            error!(
+                event_type = %event.event_type,
+                error = %e,
+                "Failed to queue webhook deliveries"
+            );
+        }
     }
 
     /// Publish a serialized event to a Redis Pub/Sub channel.
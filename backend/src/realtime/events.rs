@@ -44,6 +44,62 @@ pub enum RealtimeEvent {
         reason: String,
         timestamp: String,
     },
+    /// Tournament state change (bracket advanced, forfeit, check-in window).
+    TournamentUpdate {
+        tournament_id: Uuid,
+        payload: serde_json::Value,
+        timestamp: String,
+    },
+    /// Contract event picked up by the chain indexer.
+    ChainEvent {
+        contract: String,
+        action: String,
+        tx_hash: String,
+        ledger: u64,
+        timestamp: String,
+    },
+    /// A payment moved through its state machine.
+    PaymentStatusChange {
+        payment_id: Uuid,
+        from_status: String,
+        to_status: String,
+        reason: Option<String>,
+        timestamp: String,
+    },
+    /// A user came online, went offline, or started or stopped playing or
+    /// watching a match. Published on their presence channel.
+    PresenceUpdate {
+        user_id: Uuid,
+        status: String,
+        match_id: Option<Uuid>,
+        timestamp: String,
+    },
+    /// `user_id` is no longer a friend, so their presence stops being shared.
+    FriendRemoved { user_id: Uuid, timestamp: String },
+    /// A chat message posted in a match lobby, tournament or DM channel.
+    ChatMessage {
+        message_id: Uuid,
+        channel: String,
+        sender_id: Uuid,
+        sender_username: String,
+        body: String,
+        timestamp: String,
+    },
+    /// A chat message removed by its sender or a moderator.
+    ChatMessageDeleted {
+        message_id: Uuid,
+        channel: String,
+        timestamp: String,
+    },
+    /// Score after a round, posted by a player of a live match.
+    LiveScoreUpdate {
+        match_id: Uuid,
+        round: i32,
+        player1_score: i32,
+        player2_score: i32,
+        confirmed: bool,
+        timestamp: String,
+    },
 }
 
 /// Envelope wrapping a realtime event for WebSocket delivery.
@@ -81,6 +137,8 @@ pub mod channels {
 
     pub const USER_CHANNEL_PATTERN: &str = "user:*";
     pub const MATCH_CHANNEL_PATTERN: &str = "match:*";
+    pub const TOURNAMENT_CHANNEL_PATTERN: &str = "tournament:*";
+    pub const PRESENCE_CHANNEL_PATTERN: &str = "presence:*";
 
     pub fn user_channel(user_id: Uuid) -> String {
         format!("user:{}", user_id)
@@ -89,4 +147,18 @@ pub mod channels {
     pub fn match_channel(match_id: Uuid) -> String {
         format!("match:{}", match_id)
     }
+
+    pub fn tournament_channel(tournament_id: Uuid) -> String {
+        format!("tournament:{}", tournament_id)
+    }
+
+    pub fn presence_channel(user_id: Uuid) -> String {
+        format!("presence:{}", user_id)
+    }
+
+    /// Spectators' view of a match. Not published to Redis: the gateway
+    /// derives it from the match channel, behind the broadcast delay.
+    pub fn spectate_channel(match_id: Uuid) -> String {
+        format!("spectate:{}", match_id)
+    }
 }
@@ -4,9 +4,11 @@ pub mod ws_broadcaster;
 pub mod user_ws;
 pub mod session_registry;
 pub mod auth;
+pub mod presence;
 pub mod redis_client;
 
 pub use events::*;
 pub use event_bus::EventBus;
+pub use presence::PresenceTracker;
 pub use session_registry::SessionRegistry;
 pub use redis_client::RedisClient;
@@ -0,0 +1,226 @@
+//! Redis-backed presence: offline, online, in a match or spectating one.
+//!
+//! Every gateway session keeps a member in `presence:sessions:<user>`, a
+//! sorted set scored by expiry that the session refreshes on each heartbeat. A
+//! user is online while any member is unexpired, across all instances, so the
+//! sessions of a crashed instance age out on their own (without an offline
+//! event; friends see it on their next `GET /api/friends`). What an online user
+//! is doing lives in `presence:activity:<user>`. Changes are published on the
+//! user's presence channel, which friends subscribe to through the gateway.
+
+use crate::models::{Presence, PresenceStatus};
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
+use chrono::Utc;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use std::collections::HashMap;
+use uuid::Uuid;
+
+const SESSIONS_KEY_PREFIX: &str = "presence:sessions";
+const ACTIVITY_KEY_PREFIX: &str = "presence:activity";
+/// A session counts as connected this long after its last heartbeat.
+pub const SESSION_TTL_SECS: i64 = 30;
+/// Activity that is never cleared, e.g. of an abandoned match, expires.
+const ACTIVITY_TTL_SECS: u64 = 3 * 60 * 60;
+
+pub struct PresenceTracker {
+    redis: ConnectionManager,
+    event_bus: EventBus,
+}
+
+impl PresenceTracker {
+    pub fn new(redis: ConnectionManager, event_bus: EventBus) -> Self {
+        Self { redis, event_bus }
+    }
+
+    /// Mark `session_id` connected for another [`SESSION_TTL_SECS`]. Announces
+    /// the user when this brings them online.
+    pub async fn touch(&self, user_id: Uuid, session_id: Uuid) -> Result<(), redis::RedisError> {
+        let key = sessions_key(user_id);
+        let now = Utc::now().timestamp();
+        let mut conn = self.redis.clone();
+        let (live_before,): (u64,) = redis::pipe()
+            .atomic()
+            .zrembyscore(&key, "-inf", now)
+            .ignore()
+            .zcard(&key)
+            .zadd(&key, session_id.to_string(), now + SESSION_TTL_SECS)
+            .ignore()
+            .expire(&key, SESSION_TTL_SECS)
+            .ignore()
+            .query_async(&mut conn)
+            .await?;
+
+        if live_before == 0 {
+            let presence = resolve(1, self.activity(user_id).await?);
+            self.announce(user_id, &presence).await;
+        }
+        Ok(())
+    }
+
+    /// Drop `session_id`. Announces the user offline when it was their last.
+    pub async fn disconnect(
+        &self,
+        user_id: Uuid,
+        session_id: Uuid,
+    ) -> Result<(), redis::RedisError> {
+        let key = sessions_key(user_id);
+        let now = Utc::now().timestamp();
+        let mut conn = self.redis.clone();
+        let (removed, live): (u64, u64) = redis::pipe()
+            .atomic()
+            .zrem(&key, session_id.to_string())
+            .zrembyscore(&key, "-inf", now)
+            .ignore()
+            .zcard(&key)
+            .query_async(&mut conn)
+            .await?;
+
+        if removed > 0 && live == 0 {
+            self.announce(user_id, &Presence::offline()).await;
+        }
+        Ok(())
+    }
+
+    /// Record that the user is playing or watching `match_id`.
+    pub async fn set_activity(
+        &self,
+        user_id: Uuid,
+        status: PresenceStatus,
+        match_id: Uuid,
+    ) -> Result<(), redis::RedisError> {
+        let activity = Presence {
+            status,
+            match_id: Some(match_id),
+        };
+        let Ok(json) = serde_json::to_string(&activity) else {
+            return Ok(());
+        };
+        let mut conn = self.redis.clone();
+        let _: () = conn
+            .set_ex(activity_key(user_id), json, ACTIVITY_TTL_SECS)
+            .await?;
+
+        if self.live_sessions(user_id).await? > 0 {
+            self.announce(user_id, &activity).await;
+        }
+        Ok(())
+    }
+
+    /// Clear the user's activity if it is still about `match_id`.
+    pub async fn clear_activity(
+        &self,
+        user_id: Uuid,
+        match_id: Uuid,
+    ) -> Result<(), redis::RedisError> {
+        let current = self.activity(user_id).await?;
+        if current.and_then(|a| a.match_id) != Some(match_id) {
+            return Ok(());
+        }
+        let mut conn = self.redis.clone();
+        let _: () = conn.del(activity_key(user_id)).await?;
+
+        if self.live_sessions(user_id).await? > 0 {
+            self.announce(user_id, &Presence::online()).await;
+        }
+        Ok(())
+    }
+
+    pub async fn get(&self, user_id: Uuid) -> Result<Presence, redis::RedisError> {
+        let live = self.live_sessions(user_id).await?;
+        let activity = if live > 0 {
+            self.activity(user_id).await?
+        } else {
+            None
+        };
+        Ok(resolve(live, activity))
+    }
+
+    /// Presence of each of `user_ids`, in one round trip.
+    pub async fn get_many(
+        &self,
+        user_ids: &[Uuid],
+    ) -> Result<HashMap<Uuid, Presence>, redis::RedisError> {
+        if user_ids.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let now = Utc::now().timestamp();
+        let mut pipe = redis::pipe();
+        for user_id in user_ids {
+            pipe.zcount(sessions_key(*user_id), format!("({}", now), "+inf")
+                .get(activity_key(*user_id));
+        }
+        let mut conn = self.redis.clone();
+        let rows: Vec<(u64, Option<String>)> = pipe.query_async(&mut conn).await?;
+
+        Ok(user_ids
+            .iter()
+            .zip(rows)
+            .map(|(user_id, (live, activity))| {
+                let activity = activity.and_then(|json| serde_json::from_str(&json).ok());
+                (*user_id, resolve(live, activity))
+            })
+            .collect())
+    }
+
+    async fn live_sessions(&self, user_id: Uuid) -> Result<u64, redis::RedisError> {
+        let now = Utc::now().timestamp();
+        let mut conn = self.redis.clone();
+        conn.zcount(sessions_key(user_id), format!("({}", now), "+inf")
+            .await
+    }
+
+    async fn activity(&self, user_id: Uuid) -> Result<Option<Presence>, redis::RedisError> {
+        let mut conn = self.redis.clone();
+        let json: Option<String> = conn.get(activity_key(user_id)).await?;
+        Ok(json.and_then(|json| serde_json::from_str(&json).ok()))
+    }
+
+    async fn announce(&self, user_id: Uuid, presence: &Presence) {
+        let event = RealtimeEvent::PresenceUpdate {
+            user_id,
+            status: presence.status.as_str().to_string(),
+            match_id: presence.match_id,
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        self.event_bus.publish_presence(user_id, &event).await;
+    }
+}
+
+/// Presence from a user's live session count and recorded activity. Activity
+/// only shows while connected.
+fn resolve(live_sessions: u64, activity: Option<Presence>) -> Presence {
+    if live_sessions == 0 {
+        return Presence::offline();
+    }
+    activity
+        .filter(|a| a.status != PresenceStatus::Offline)
+        .unwrap_or_else(Presence::online)
+}
+
+fn sessions_key(user_id: Uuid) -> String {
+    format!("{}:{}", SESSIONS_KEY_PREFIX, user_id)
+}
+
+fn activity_key(user_id: Uuid) -> String {
+    format!("{}:{}", ACTIVITY_KEY_PREFIX, user_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_activity_only_shows_while_connected() {
+        let match_id = Uuid::new_v4();
+        let playing = Presence {
+            status: PresenceStatus::InMatch,
+            match_id: Some(match_id),
+        };
+
+        assert_eq!(resolve(0, Some(playing.clone())), Presence::offline());
+        assert_eq!(resolve(2, Some(playing.clone())), playing);
+        assert_eq!(resolve(1, None), Presence::online());
+    }
+}
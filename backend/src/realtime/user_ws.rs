@@ -155,12 +155,13 @@ impl StreamHandler<Result<ws::Message, ws::ProtocolError>> for UserWebSocket {
                         let session_id = self.session_id;
                         let registry = self.registry.clone();
 
+                        let requested = channel.clone();
                         let fut = async move {
-                            auth.authorize_subscription(&claims, &channel).await
+                            auth.authorize_subscription(&claims, &requested).await
                         };
 
                         ctx.wait(actix::fut::wrap_future(fut).then(
-                            move |res, _act, ctx| {
+                            move |res, _act: &mut Self, ctx| {
                                 match res {
                                     Ok(_) => {
                                         registry.subscribe(session_id, channel.clone());
@@ -0,0 +1,573 @@
+//! The admin console: user search and bans, tournament overrides, dispute
+//! escalation, payout approvals and feature toggles.
+//!
+//! Every action is written to `audit_logs` with the admin as actor, the
+//! fields it changed before and after, and the reason given. Where the
+//! action is carried out by another service (dispute escalation, payout
+//! review) the entry is written once that service has committed it.
+
+use crate::api_error::ApiError;
+use crate::auth::jwt_service::JwtService;
+use crate::db::DbPool;
+use crate::models::{
+    AdminUserSummary, BanUserRequest, CustodialWithdrawal, DisputeCase, EscalateDisputeRequest,
+    FeatureFlag, SetFeatureFlagRequest, Tournament, TournamentOverrideRequest,
+};
+use crate::service::audit_log::{self, AuditRecord};
+use crate::service::dispute_service::{DisputeActor, DisputeService};
+use crate::service::WalletService;
+use chrono::Utc;
+use serde::Serialize;
+use serde_json::Value;
+use std::sync::Arc;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+const USER_COLUMNS: &str = "id, username, email, role, is_active, is_banned, banned_until, \
+     ban_reason, created_at, last_login_at";
+const MAX_FLAG_KEY_LEN: usize = 64;
+
+pub struct AdminService {
+    db_pool: DbPool,
+    disputes: Arc<DisputeService>,
+    wallets: Arc<WalletService>,
+    sessions: Option<Arc<JwtService>>,
+}
+
+impl AdminService {
+    pub fn new(
+        db_pool: DbPool,
+        disputes: Arc<DisputeService>,
+        wallets: Arc<WalletService>,
+    ) -> Self {
+        Self {
+            db_pool,
+            disputes,
+            wallets,
+            sessions: None,
+        }
+    }
+
+    /// Sign banned users out of every session.
+    pub fn with_sessions(mut self, sessions: Arc<JwtService>) -> Self {
+        self.sessions = Some(sessions);
+        self
+    }
+
+    // ========================================================================
+    // USERS
+    // ========================================================================
+
+    /// Users whose username or email contains `query`, or whose id it is,
+    /// newest first.
+    pub async fn search_users(
+        &self,
+        query: Option<&str>,
+        banned: Option<bool>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AdminUserSummary>, ApiError> {
+        let query = query.map(str::trim).filter(|q| !q.is_empty());
+        let sql = format!(
+            r#"
+            SELECT {} FROM users
+            WHERE ($1::TEXT IS NULL OR username ILIKE $1 OR email ILIKE $1 OR id = $2)
+              AND ($3::BOOLEAN IS NULL OR is_banned = $3)
+            ORDER BY created_at DESC
+            LIMIT $4 OFFSET $5
+            "#,
+            USER_COLUMNS
+        );
+        sqlx::query_as::<_, AdminUserSummary>(&sql)
+            .bind(query.map(like_pattern))
+            .bind(query.and_then(|q| Uuid::parse_str(q).ok()))
+            .bind(banned)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)
+    }
+
+    /// Ban a user until `request.until`, or for good, and end their sessions.
+    pub async fn ban_user(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        request: &BanUserRequest,
+    ) -> Result<AdminUserSummary, ApiError> {
+        if user_id == admin_id {
+            return Err(ApiError::bad_request("You cannot ban yourself"));
+        }
+        if request.until.is_some_and(|until| until <= Utc::now()) {
+            return Err(ApiError::bad_request("Ban must end in the future"));
+        }
+        let user = self
+            .update_user(
+                admin_id,
+                user_id,
+                "UPDATE users SET is_banned = TRUE, banned_until = $2, ban_reason = $3, \
+                 updated_at = NOW() WHERE id = $1",
+                request.until,
+                Some(&request.reason),
+                "user_banned",
+                &request.reason,
+            )
+            .await?;
+
+        if let Some(sessions) = &self.sessions {
+            if let Err(e) = sessions.revoke_user_sessions(user_id).await {
+                warn!(user_id = %user_id, error = %e, "Failed to end banned user's sessions");
+            }
+        }
+        info!(user_id = %user_id, admin_id = %admin_id, "User banned");
+        Ok(user)
+    }
+
+    pub async fn unban_user(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        reason: &str,
+    ) -> Result<AdminUserSummary, ApiError> {
+        let user = self
+            .update_user(
+                admin_id,
+                user_id,
+                "UPDATE users SET is_banned = FALSE, banned_until = $2, ban_reason = $3, \
+                 updated_at = NOW() WHERE id = $1",
+                None,
+                None,
+                "user_unbanned",
+                reason,
+            )
+            .await?;
+        info!(user_id = %user_id, admin_id = %admin_id, "User unbanned");
+        Ok(user)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn update_user(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        update: &str,
+        banned_until: Option<chrono::DateTime<Utc>>,
+        ban_reason: Option<&str>,
+        action: &str,
+        reason: &str,
+    ) -> Result<AdminUserSummary, ApiError> {
+        let select = format!("SELECT {} FROM users WHERE id = $1", USER_COLUMNS);
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let before = sqlx::query_as::<_, AdminUserSummary>(&format!("{} FOR UPDATE", select))
+            .bind(user_id)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?
+            .ok_or_else(|| ApiError::not_found("User not found"))?;
+        sqlx::query(update)
+            .bind(user_id)
+            .bind(banned_until)
+            .bind(ban_reason)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+        let after = sqlx::query_as::<_, AdminUserSummary>(&select)
+            .bind(user_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+        audit_log::record(
+            &mut tx,
+            AuditRecord {
+                actor_id: Some(admin_id),
+                action,
+                resource_type: "user",
+                resource_id: Some(user_id),
+                before: to_json(&before)?,
+                after: to_json(&after)?,
+                reason: Some(reason),
+                ..AuditRecord::default()
+            },
+        )
+        .await
+        .map_err(ApiError::database_error)?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+        Ok(after)
+    }
+
+    // ========================================================================
+    // TOURNAMENTS
+    // ========================================================================
+
+    /// Override a tournament's status, capacity or schedule, bypassing the
+    /// organizer's lifecycle checks.
+    pub async fn override_tournament(
+        &self,
+        admin_id: Uuid,
+        tournament_id: Uuid,
+        request: &TournamentOverrideRequest,
+    ) -> Result<Tournament, ApiError> {
+        if request.status.is_none()
+            && request.max_participants.is_none()
+            && request.registration_deadline.is_none()
+            && request.start_time.is_none()
+        {
+            return Err(ApiError::bad_request("Nothing to override"));
+        }
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let before =
+            sqlx::query_as::<_, Tournament>("SELECT * FROM tournaments WHERE id = $1 FOR UPDATE")
+                .bind(tournament_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(ApiError::database_error)?
+                .ok_or_else(|| ApiError::not_found("Tournament not found"))?;
+
+        let deadline = request
+            .registration_deadline
+            .unwrap_or(before.registration_deadline);
+        let start_time = request.start_time.unwrap_or(before.start_time);
+        if deadline > start_time {
+            return Err(ApiError::bad_request(
+                "Registration must close before the tournament starts",
+            ));
+        }
+        if let Some(max_participants) = request.max_participants {
+            let registered: i64 = sqlx::query_scalar(
+                "SELECT COUNT(*) FROM tournament_participants WHERE tournament_id = $1",
+            )
+            .bind(tournament_id)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+            if registered > max_participants as i64 {
+                return Err(ApiError::bad_request(format!(
+                    "{} players are already registered",
+                    registered
+                )));
+            }
+        }
+
+        let after = sqlx::query_as::<_, Tournament>(
+            r#"
+            UPDATE tournaments
+            SET status = COALESCE($2, status),
+                max_participants = COALESCE($3, max_participants),
+                registration_deadline = $4,
+                start_time = $5,
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(request.status)
+        .bind(request.max_participants)
+        .bind(deadline)
+        .bind(start_time)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        audit_log::record(
+            &mut tx,
+            AuditRecord {
+                actor_id: Some(admin_id),
+                action: "tournament_overridden",
+                resource_type: "tournament",
+                resource_id: Some(tournament_id),
+                before: to_json(&before)?,
+                after: to_json(&after)?,
+                reason: Some(&request.reason),
+                ..AuditRecord::default()
+            },
+        )
+        .await
+        .map_err(ApiError::database_error)?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(tournament_id = %tournament_id, admin_id = %admin_id, "Tournament overridden");
+        Ok(after)
+    }
+
+    // ========================================================================
+    // DISPUTES
+    // ========================================================================
+
+    pub async fn escalate_dispute(
+        &self,
+        admin_id: Uuid,
+        dispute_id: Uuid,
+        request: &EscalateDisputeRequest,
+    ) -> Result<DisputeCase, ApiError> {
+        let before = sqlx::query_as::<_, DisputeCase>("SELECT * FROM match_disputes WHERE id = $1")
+            .bind(dispute_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?
+            .ok_or_else(|| ApiError::not_found("Dispute not found"))?;
+        let actor = DisputeActor {
+            user_id: admin_id,
+            is_referee: false,
+            is_admin: true,
+        };
+        let after = self
+            .disputes
+            .escalate(actor, dispute_id, &request.reason, request.referee_id)
+            .await?;
+        self.audit(
+            admin_id,
+            "dispute_escalated",
+            "dispute",
+            dispute_id,
+            &before,
+            &after,
+            &request.reason,
+        )
+        .await?;
+        Ok(after)
+    }
+
+    // ========================================================================
+    // PAYOUTS
+    // ========================================================================
+
+    /// Withdrawals held for approval, oldest first.
+    pub async fn pending_payouts(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<CustodialWithdrawal>, ApiError> {
+        Ok(self.wallets.pending_withdrawals(limit, offset).await?)
+    }
+
+    pub async fn approve_payout(
+        &self,
+        admin_id: Uuid,
+        withdrawal_id: Uuid,
+        reason: &str,
+    ) -> Result<CustodialWithdrawal, ApiError> {
+        let before = self.withdrawal(withdrawal_id).await?;
+        let after = self
+            .wallets
+            .approve_withdrawal(admin_id, withdrawal_id, Some(reason))
+            .await?;
+        self.audit(
+            admin_id,
+            "payout_approved",
+            "withdrawal",
+            withdrawal_id,
+            &before,
+            &after,
+            reason,
+        )
+        .await?;
+        Ok(after)
+    }
+
+    /// Reject a held withdrawal; the funds go back to the user.
+    pub async fn reject_payout(
+        &self,
+        admin_id: Uuid,
+        withdrawal_id: Uuid,
+        reason: &str,
+    ) -> Result<CustodialWithdrawal, ApiError> {
+        let before = self.withdrawal(withdrawal_id).await?;
+        let after = self
+            .wallets
+            .reject_withdrawal(admin_id, withdrawal_id, Some(reason))
+            .await?;
+        self.audit(
+            admin_id,
+            "payout_rejected",
+            "withdrawal",
+            withdrawal_id,
+            &before,
+            &after,
+            reason,
+        )
+        .await?;
+        Ok(after)
+    }
+
+    async fn withdrawal(&self, withdrawal_id: Uuid) -> Result<CustodialWithdrawal, ApiError> {
+        sqlx::query_as::<_, CustodialWithdrawal>("SELECT * FROM custody_withdrawals WHERE id = $1")
+            .bind(withdrawal_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?
+            .ok_or_else(|| ApiError::not_found("Withdrawal not found"))
+    }
+
+    // ========================================================================
+    // FEATURE TOGGLES
+    // ========================================================================
+
+    pub async fn feature_flags(&self) -> Result<Vec<FeatureFlag>, ApiError> {
+        sqlx::query_as::<_, FeatureFlag>("SELECT * FROM feature_flags ORDER BY key")
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)
+    }
+
+    /// Whether a feature is on; unknown features are off.
+    pub async fn is_enabled(&self, key: &str) -> Result<bool, ApiError> {
+        let enabled: Option<bool> =
+            sqlx::query_scalar("SELECT enabled FROM feature_flags WHERE key = $1")
+                .bind(key)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        Ok(enabled.unwrap_or(false))
+    }
+
+    /// Turn a feature on or off, creating the flag if needed.
+    pub async fn set_feature_flag(
+        &self,
+        admin_id: Uuid,
+        key: &str,
+        request: &SetFeatureFlagRequest,
+    ) -> Result<FeatureFlag, ApiError> {
+        if !is_valid_flag_key(key) {
+            return Err(ApiError::bad_request(format!(
+                "Feature keys are 1 to {} lowercase letters, digits, `_`, `-` or `.`",
+                MAX_FLAG_KEY_LEN
+            )));
+        }
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let before = sqlx::query_as::<_, FeatureFlag>(
+            "SELECT * FROM feature_flags WHERE key = $1 FOR UPDATE",
+        )
+        .bind(key)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        let after = sqlx::query_as::<_, FeatureFlag>(
+            r#"
+            INSERT INTO feature_flags (id, key, enabled, description, updated_by, created_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, NOW(), NOW())
+            ON CONFLICT (key) DO UPDATE SET
+                enabled = EXCLUDED.enabled,
+                description = COALESCE(EXCLUDED.description, feature_flags.description),
+                updated_by = EXCLUDED.updated_by,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(key)
+        .bind(request.enabled)
+        .bind(&request.description)
+        .bind(admin_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        audit_log::record(
+            &mut tx,
+            AuditRecord {
+                actor_id: Some(admin_id),
+                action: "feature_flag_set",
+                resource_type: "feature_flag",
+                resource_id: Some(after.id),
+                before: before
+                    .as_ref()
+                    .map(to_json)
+                    .transpose()?
+                    .unwrap_or(Value::Null),
+                after: to_json(&after)?,
+                reason: Some(&request.reason),
+                ..AuditRecord::default()
+            },
+        )
+        .await
+        .map_err(ApiError::database_error)?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(key, enabled = request.enabled, admin_id = %admin_id, "Feature flag set");
+        Ok(after)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn audit<T: Serialize>(
+        &self,
+        admin_id: Uuid,
+        action: &str,
+        resource_type: &str,
+        resource_id: Uuid,
+        before: &T,
+        after: &T,
+        reason: &str,
+    ) -> Result<(), ApiError> {
+        audit_log::append(
+            &self.db_pool,
+            AuditRecord {
+                actor_id: Some(admin_id),
+                action,
+                resource_type,
+                resource_id: Some(resource_id),
+                before: to_json(before)?,
+                after: to_json(after)?,
+                reason: Some(reason),
+                ..AuditRecord::default()
+            },
+        )
+        .await
+        .map_err(ApiError::database_error)
+    }
+}
+
+fn to_json<T: Serialize>(value: &T) -> Result<Value, ApiError> {
+    serde_json::to_value(value).map_err(|e| ApiError::internal_error(e.to_string()))
+}
+
+/// An `ILIKE` pattern matching `query` anywhere, with its own wildcards
+/// escaped.
+fn like_pattern(query: &str) -> String {
+    let escaped = query
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_");
+    format!("%{}%", escaped)
+}
+
+fn is_valid_flag_key(key: &str) -> bool {
+    !key.is_empty()
+        && key.len() <= MAX_FLAG_KEY_LEN
+        && key
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"_-.".contains(&b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_like_pattern_escapes_wildcards() {
+        assert_eq!(like_pattern("ann"), "%ann%");
+        assert_eq!(like_pattern("100%_a"), "%100\\%\\_a%");
+    }
+
+    #[test]
+    fn test_feature_flag_keys() {
+        assert!(is_valid_flag_key("chat.dm_enabled"));
+        assert!(is_valid_flag_key("anticheat-v2"));
+        assert!(!is_valid_flag_key(""));
+        assert!(!is_valid_flag_key("Chat"));
+        assert!(!is_valid_flag_key("a b"));
+        assert!(!is_valid_flag_key(&"a".repeat(65)));
+    }
+}
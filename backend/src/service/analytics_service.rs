@@ -1,7 +1,9 @@
 /// Analytics service — aggregates on-chain and off-chain metrics.
 /// Privacy: player-level data is only returned to the player themselves or admins.
 use crate::api_error::ApiError;
+use crate::service::domain_events::{DomainEventEnvelope, DomainEventHandler};
 use chrono::Utc;
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use uuid::Uuid;
@@ -211,7 +213,7 @@ impl AnalyticsService {
             active_players_30d: r.active_players_30d,
             total_staked: r.total_staked,
             total_volume: r.total_volume,
-            last_updated: r.last_updated.unwrap_or_else(Utc::now),
+            last_updated: r.last_updated,
         }).unwrap_or(PlatformMetricsResponse {
             total_matches_all_time: 0,
             active_players_30d: 0,
@@ -230,7 +232,7 @@ impl AnalyticsService {
         game_id: i32,
     ) -> Result<Option<PlayerInsightsResponse>, ApiError> {
         if requesting_user_id != target_user_id && !is_admin {
-            return Err(ApiError::Forbidden("not authorised to view this data".into()));
+            return Err(ApiError::forbidden("not authorised to view this data"));
         }
 
         let row = sqlx::query_as!(
@@ -264,3 +266,31 @@ impl AnalyticsService {
         }))
     }
 }
+
+// ─── Domain events ────────────────────────────────────────────────────────────
+
+/// Counts domain events per type and day.
+impl DomainEventHandler for AnalyticsService {
+    fn name(&self) -> &'static str {
+        "analytics"
+    }
+
+    fn handle<'a>(&'a self, event: &'a DomainEventEnvelope) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            sqlx::query(
+                r#"
+                INSERT INTO analytics_domain_events (event_type, day, events)
+                VALUES ($1, $2, 1)
+                ON CONFLICT (event_type, day) DO UPDATE SET
+                    events = analytics_domain_events.events + 1
+                "#,
+            )
+            .bind(event.event.event_type())
+            .bind(event.occurred_at.date_naive())
+            .execute(&self.db)
+            .await
+            .map_err(|e| e.to_string())?;
+            Ok(())
+        })
+    }
+}
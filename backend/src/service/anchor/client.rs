@@ -0,0 +1,480 @@
+//! HTTP client for a SEP-24 anchor.
+//!
+//! The anchor's endpoints and signing key come from its `stellar.toml`. The
+//! platform authenticates with SEP-10 as the custody account: the anchor's
+//! challenge is checked before the relayer co-signs it, since signing an
+//! arbitrary transaction with the custody key would be a blank cheque.
+
+use crate::models::RampKind;
+use crate::service::stellar_relayer::xdr::{self, ManageDataTx};
+use crate::service::stellar_relayer::{RelayerError, StellarRelayer};
+use base64::{engine::general_purpose, Engine as _};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::{Mutex, OnceCell};
+
+const HTTP_TIMEOUT_SECS: u64 = 15;
+/// Tokens are refreshed this long before they expire (seconds).
+const TOKEN_EXPIRY_MARGIN_SECS: u64 = 60;
+/// Lifetime assumed for tokens without a readable `exp` (seconds).
+const DEFAULT_TOKEN_TTL_SECS: u64 = 300;
+
+#[derive(Debug, Error)]
+pub enum AnchorError {
+    #[error("Anchor request failed: {0}")]
+    Http(#[from] reqwest::Error),
+    #[error("Anchor returned {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("Invalid anchor response: {0}")]
+    InvalidResponse(String),
+    #[error("Unsafe SEP-10 challenge: {0}")]
+    InvalidChallenge(String),
+    #[error("Relayer error: {0}")]
+    Relayer(#[from] RelayerError),
+}
+
+/// Endpoints and signing key published in the anchor's `stellar.toml`.
+#[derive(Debug, Clone)]
+pub struct AnchorInfo {
+    pub transfer_server: String,
+    pub web_auth_endpoint: String,
+    pub signing_key: String,
+}
+
+/// Response to `POST /transactions/{deposit,withdraw}/interactive`.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InteractiveResponse {
+    pub url: String,
+    pub id: String,
+}
+
+/// A SEP-24 transaction as reported by `GET /transaction`.
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct AnchorTransaction {
+    pub id: String,
+    pub status: String,
+    pub amount_in: Option<String>,
+    pub amount_out: Option<String>,
+    pub amount_fee: Option<String>,
+    pub stellar_transaction_id: Option<String>,
+    pub message: Option<String>,
+    /// Where withdrawals are paid, once the anchor is ready for them.
+    pub withdraw_anchor_account: Option<String>,
+    pub withdraw_memo: Option<String>,
+    pub withdraw_memo_type: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionEnvelope {
+    transaction: AnchorTransaction,
+}
+
+/// Body the anchor posts to `on_change_callback`. Only the id is used: the
+/// transaction is fetched again from the anchor, so the body need not be
+/// trusted.
+#[derive(Debug, Deserialize)]
+pub struct AnchorCallback {
+    pub transaction: AnchorCallbackTransaction,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AnchorCallbackTransaction {
+    pub id: String,
+}
+
+/// Parameters of an interactive deposit or withdrawal.
+#[derive(Debug, Serialize)]
+pub struct InteractiveRequest<'a> {
+    pub asset_code: &'a str,
+    pub asset_issuer: &'a str,
+    pub account: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lang: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub on_change_callback: Option<&'a str>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChallengeResponse {
+    transaction: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    token: String,
+}
+
+pub struct AnchorClient {
+    http: reqwest::Client,
+    domain: String,
+    network_passphrase: String,
+    relayer: Arc<StellarRelayer>,
+    info: OnceCell<AnchorInfo>,
+    token: Mutex<Option<(String, Instant)>>,
+}
+
+impl AnchorClient {
+    pub fn new(domain: String, network_passphrase: String, relayer: Arc<StellarRelayer>) -> Self {
+        Self {
+            http: reqwest::Client::builder()
+                .timeout(Duration::from_secs(HTTP_TIMEOUT_SECS))
+                .build()
+                .unwrap_or_default(),
+            domain,
+            network_passphrase,
+            relayer,
+            info: OnceCell::new(),
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Start an interactive flow; the user completes it at the returned URL.
+    pub async fn start_interactive(
+        &self,
+        kind: RampKind,
+        request: &InteractiveRequest<'_>,
+    ) -> Result<InteractiveResponse, AnchorError> {
+        let path = match kind {
+            RampKind::Deposit => "deposit",
+            RampKind::Withdrawal => "withdraw",
+        };
+        let url = format!(
+            "{}/transactions/{}/interactive",
+            self.info().await?.transfer_server,
+            path
+        );
+        self.send_authenticated(|token| self.http.post(&url).bearer_auth(token).json(request))
+            .await
+    }
+
+    /// The anchor's current view of one of our transactions.
+    pub async fn transaction(&self, id: &str) -> Result<AnchorTransaction, AnchorError> {
+        let url = format!("{}/transaction", self.info().await?.transfer_server);
+        let envelope: TransactionEnvelope = self
+            .send_authenticated(|token| self.http.get(&url).bearer_auth(token).query(&[("id", id)]))
+            .await?;
+        Ok(envelope.transaction)
+    }
+
+    /// Send a request with the cached token, authenticating again once if the
+    /// anchor rejects it.
+    async fn send_authenticated<T, F>(&self, request: F) -> Result<T, AnchorError>
+    where
+        T: serde::de::DeserializeOwned,
+        F: Fn(&str) -> reqwest::RequestBuilder,
+    {
+        let token = self.token().await?;
+        let response = request(&token).send().await?;
+        let response = if response.status() == reqwest::StatusCode::UNAUTHORIZED
+            || response.status() == reqwest::StatusCode::FORBIDDEN
+        {
+            *self.token.lock().await = None;
+            request(&self.token().await?).send().await?
+        } else {
+            response
+        };
+        json_response(response).await
+    }
+
+    async fn info(&self) -> Result<&AnchorInfo, AnchorError> {
+        self.info
+            .get_or_try_init(|| async {
+                let url = format!("https://{}/.well-known/stellar.toml", self.domain);
+                let response = self.http.get(&url).send().await?;
+                if !response.status().is_success() {
+                    return Err(AnchorError::Status {
+                        status: response.status().as_u16(),
+                        body: String::new(),
+                    });
+                }
+                parse_stellar_toml(&response.text().await?)
+            })
+            .await
+    }
+
+    async fn token(&self) -> Result<String, AnchorError> {
+        let mut cached = self.token.lock().await;
+        if let Some((token, expires)) = cached.as_ref() {
+            if Instant::now() < *expires {
+                return Ok(token.clone());
+            }
+        }
+        let token = self.authenticate().await?;
+        let ttl = token_ttl(&token)
+            .unwrap_or(DEFAULT_TOKEN_TTL_SECS)
+            .saturating_sub(TOKEN_EXPIRY_MARGIN_SECS);
+        *cached = Some((token.clone(), Instant::now() + Duration::from_secs(ttl)));
+        Ok(token)
+    }
+
+    /// SEP-10: fetch a challenge for the custody account, check it, co-sign it
+    /// and exchange it for a token.
+    async fn authenticate(&self) -> Result<String, AnchorError> {
+        let info = self.info().await?;
+        let account = self.relayer.account_id();
+        let response = self
+            .http
+            .get(&info.web_auth_endpoint)
+            .query(&[("account", account.as_str())])
+            .send()
+            .await?;
+        let challenge: ChallengeResponse = json_response(response).await?;
+
+        let web_auth_domain = reqwest::Url::parse(&info.web_auth_endpoint)
+            .ok()
+            .and_then(|url| url.host_str().map(str::to_string))
+            .ok_or_else(|| {
+                AnchorError::InvalidResponse("WEB_AUTH_ENDPOINT is not a URL".to_string())
+            })?;
+        check_challenge(
+            &challenge.transaction,
+            &self.network_passphrase,
+            &info.signing_key,
+            &account,
+            &self.domain,
+            &web_auth_domain,
+            chrono::Utc::now().timestamp() as u64,
+        )?;
+        let signed = self.relayer.cosign_challenge(&challenge.transaction)?;
+
+        let response = self
+            .http
+            .post(&info.web_auth_endpoint)
+            .json(&serde_json::json!({ "transaction": signed }))
+            .send()
+            .await?;
+        let token: TokenResponse = json_response(response).await?;
+        Ok(token.token)
+    }
+}
+
+async fn json_response<T: serde::de::DeserializeOwned>(
+    response: reqwest::Response,
+) -> Result<T, AnchorError> {
+    let status = response.status();
+    if !status.is_success() {
+        return Err(AnchorError::Status {
+            status: status.as_u16(),
+            body: response.text().await.unwrap_or_default(),
+        });
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| AnchorError::InvalidResponse(e.to_string()))
+}
+
+/// Read the SEP-24 endpoints and signing key from `stellar.toml`. Only the
+/// top-level `KEY = "value"` lines are needed, so tables are skipped.
+fn parse_stellar_toml(toml: &str) -> Result<AnchorInfo, AnchorError> {
+    let value = |key: &str| {
+        toml.lines()
+            .map(str::trim)
+            .take_while(|line| !line.starts_with('['))
+            .filter_map(|line| line.split_once('='))
+            .find(|(name, _)| name.trim() == key)
+            .map(|(_, value)| {
+                value
+                    .trim()
+                    .trim_matches('"')
+                    .trim_end_matches('/')
+                    .to_string()
+            })
+            .filter(|value| !value.is_empty())
+            .ok_or_else(|| AnchorError::InvalidResponse(format!("stellar.toml has no {}", key)))
+    };
+    Ok(AnchorInfo {
+        transfer_server: value("TRANSFER_SERVER_SEP0024")?,
+        web_auth_endpoint: value("WEB_AUTH_ENDPOINT")?,
+        signing_key: value("SIGNING_KEY")?,
+    })
+}
+
+/// Check an anchor's SEP-10 challenge before the custody account signs it: it
+/// must be an unsubmittable (sequence 0) `ManageData` transaction from the
+/// anchor's signing key, currently valid, asking `account` to authenticate to
+/// `home_domain`, and already signed by the anchor.
+fn check_challenge(
+    envelope_xdr: &str,
+    network_passphrase: &str,
+    signing_key: &str,
+    account: &str,
+    home_domain: &str,
+    web_auth_domain: &str,
+    now: u64,
+) -> Result<(), AnchorError> {
+    let invalid = |reason: &str| AnchorError::InvalidChallenge(reason.to_string());
+    let server = xdr::account_public_key(signing_key)?;
+    let client = xdr::account_public_key(account)?;
+    let (transaction, signatures) = xdr::split_envelope(envelope_xdr)?;
+    let tx = ManageDataTx::from_transaction(&transaction)?;
+
+    if tx.source != server {
+        return Err(invalid("source is not the anchor's signing key"));
+    }
+    if tx.sequence != 0 {
+        return Err(invalid("sequence number is not 0"));
+    }
+    if tx.max_time == 0 || now < tx.min_time || now > tx.max_time {
+        return Err(invalid("outside its time bounds"));
+    }
+    let Some((first, rest)) = tx.operations.split_first() else {
+        return Err(invalid("no operations"));
+    };
+    if first.source != client || first.name != format!("{} auth", home_domain) {
+        return Err(invalid(
+            "first operation is not for this account and domain",
+        ));
+    }
+    for op in rest {
+        if op.source != server {
+            return Err(invalid("operation sourced from another account"));
+        }
+        if op.name == "web_auth_domain" && op.value != web_auth_domain.as_bytes() {
+            return Err(invalid("web_auth_domain does not match"));
+        }
+    }
+    let hash = xdr::transaction_hash(network_passphrase, &transaction)?;
+    if !signatures.iter().any(|s| s.is_valid_for(&server, &hash)) {
+        return Err(invalid("not signed by the anchor"));
+    }
+    Ok(())
+}
+
+/// Seconds until a JWT's `exp`, read without verifying the token: it is only
+/// used to decide when to authenticate again.
+fn token_ttl(token: &str) -> Option<u64> {
+    let payload = token.split('.').nth(1)?;
+    let payload = general_purpose::URL_SAFE_NO_PAD
+        .decode(payload.trim_end_matches('='))
+        .ok()?;
+    let exp = serde_json::from_slice::<serde_json::Value>(&payload)
+        .ok()?
+        .get("exp")?
+        .as_u64()?;
+    exp.checked_sub(chrono::Utc::now().timestamp() as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::stellar_relayer::xdr::{Keypair, ManageDataOp};
+    use crate::service::stellar_service::stellar_strkey_encode;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+    const NOW: u64 = 1_700_000_100;
+
+    fn keypair(seed: u8) -> Keypair {
+        let secret = stellar_strkey_encode(18 << 3, &[seed; 32]).unwrap();
+        Keypair::from_secret(&secret).unwrap()
+    }
+
+    fn challenge(server: &Keypair, client: &Keypair, home_domain: &str, sequence: i64) -> String {
+        let tx = ManageDataTx {
+            source: server.public_key(),
+            fee: 200,
+            sequence,
+            min_time: 1_700_000_000,
+            max_time: 1_700_000_900,
+            operations: vec![
+                ManageDataOp {
+                    source: client.public_key(),
+                    name: format!("{} auth", home_domain),
+                    value: vec![b'n'; 64],
+                },
+                ManageDataOp {
+                    source: server.public_key(),
+                    name: "web_auth_domain".to_string(),
+                    value: b"auth.anchor.example".to_vec(),
+                },
+            ],
+        };
+        xdr::sign_transaction(&tx.transaction().unwrap(), PASSPHRASE, server)
+            .unwrap()
+            .xdr
+    }
+
+    fn check(envelope: &str, server: &Keypair, client: &Keypair, now: u64) -> Result<(), String> {
+        check_challenge(
+            envelope,
+            PASSPHRASE,
+            &server.account_id(),
+            &client.account_id(),
+            "anchor.example",
+            "auth.anchor.example",
+            now,
+        )
+        .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn test_parse_stellar_toml() {
+        let toml = r#"
+            # Anchor
+            NETWORK_PASSPHRASE="Test SDF Network ; September 2015"
+            SIGNING_KEY = "GBDYDBJKQBJK4GY4V7FAONSFF2IBJSKNTBYJ65F5KCGBY2BIXPGGLJOH"
+            TRANSFER_SERVER_SEP0024="https://anchor.example/sep24/"
+            WEB_AUTH_ENDPOINT="https://auth.anchor.example/auth"
+
+            [[CURRENCIES]]
+            SIGNING_KEY="GOTHER"
+        "#;
+        let info = parse_stellar_toml(toml).unwrap();
+        assert_eq!(info.transfer_server, "https://anchor.example/sep24");
+        assert_eq!(info.web_auth_endpoint, "https://auth.anchor.example/auth");
+        assert_eq!(
+            info.signing_key,
+            "GBDYDBJKQBJK4GY4V7FAONSFF2IBJSKNTBYJ65F5KCGBY2BIXPGGLJOH"
+        );
+
+        assert!(parse_stellar_toml("SIGNING_KEY=\"G\"\n").is_err());
+    }
+
+    #[test]
+    fn test_check_challenge() {
+        let server = keypair(1);
+        let client = keypair(2);
+        let valid = challenge(&server, &client, "anchor.example", 0);
+        assert_eq!(check(&valid, &server, &client, NOW), Ok(()));
+
+        assert!(check(&valid, &server, &client, 1_700_001_000)
+            .unwrap_err()
+            .contains("time bounds"));
+        assert!(check(&valid, &server, &keypair(3), NOW)
+            .unwrap_err()
+            .contains("first operation"));
+        assert!(check(&valid, &keypair(3), &client, NOW)
+            .unwrap_err()
+            .contains("signing key"));
+
+        let submittable = challenge(&server, &client, "anchor.example", 42);
+        assert!(check(&submittable, &server, &client, NOW)
+            .unwrap_err()
+            .contains("sequence"));
+        let other_domain = challenge(&server, &client, "evil.example", 0);
+        assert!(check(&other_domain, &server, &client, NOW).is_err());
+
+        // Signed by someone other than the anchor.
+        let tx = xdr::split_envelope(&valid).unwrap().0;
+        let forged = xdr::sign_transaction(&tx, PASSPHRASE, &keypair(3))
+            .unwrap()
+            .xdr;
+        assert!(check(&forged, &server, &client, NOW)
+            .unwrap_err()
+            .contains("not signed"));
+    }
+
+    #[test]
+    fn test_token_ttl() {
+        let exp = chrono::Utc::now().timestamp() + 3600;
+        let payload = general_purpose::URL_SAFE_NO_PAD.encode(format!(r#"{{"exp":{}}}"#, exp));
+        let ttl = token_ttl(&format!("e30.{}.sig", payload)).unwrap();
+        assert!((3590..=3600).contains(&ttl));
+
+        assert_eq!(token_ttl("not-a-jwt"), None);
+    }
+}
@@ -0,0 +1,861 @@
+//! Fiat on/off-ramps through a SEP-24 anchor.
+//!
+//! Users deposit or withdraw fiat in the anchor's interactive flow, while the
+//! platform's custody account is the Stellar side of every ramp: the anchor
+//! pays deposits to it and withdrawals are paid from it, so anchored assets
+//! are custodied like XLM and tracked in the double-entry ledger under
+//! `CODE:ISSUER`. Only issued assets are supported; anchored XLM would also
+//! show up in the custody deposit stream.
+//!
+//! Ramps follow the anchor's transaction status, polled periodically and
+//! refreshed early when the anchor calls back. A deposit is credited once the
+//! anchor reports it completed. A withdrawal holds its amount from the start,
+//! is paid to the anchor (with the anchor's memo) when it asks for the user's
+//! transfer, and is released if it fails before that payment.
+
+pub mod client;
+
+use crate::api_error::ApiError;
+use crate::config::StellarConfig;
+use crate::models::{
+    AnchorRamp, RampKind, RampStatus, StartDepositRampRequest, StartWithdrawalRampRequest,
+    TransactionStatus, TransactionType,
+};
+use crate::service::horizon_stream::{format_stroops, parse_stroops};
+use crate::service::ledger::{self, Journal, LedgerAccount};
+use crate::service::stellar_relayer::xdr::{self, Memo};
+use crate::service::stellar_relayer::{RelayerError, StellarRelayer};
+use crate::service::wallet_service::DbPool;
+use rust_decimal::Decimal;
+use serde_json::json;
+use sqlx::Postgres;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use client::InteractiveRequest;
+pub use client::{AnchorCallback, AnchorClient, AnchorError, AnchorTransaction};
+
+/// How often open ramps are polled (seconds).
+const POLL_INTERVAL_SECS: u64 = 30;
+
+#[derive(Debug, Error)]
+pub enum RampError {
+    #[error("Asset {0} is not offered by the anchor")]
+    UnsupportedAsset(String),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Insufficient balance: required {required}, available {available}")]
+    InsufficientBalance { required: i64, available: i64 },
+    #[error("Ramp not found")]
+    NotFound,
+    #[error("Anchor error: {0}")]
+    Anchor(#[from] AnchorError),
+    #[error("Invalid configuration: {0}")]
+    Config(#[from] RelayerError),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<RampError> for ApiError {
+    fn from(err: RampError) -> Self {
+        match err {
+            RampError::DatabaseError(e) => ApiError::DatabaseError(e),
+            RampError::NotFound => ApiError::NotFound,
+            RampError::Anchor(e) => ApiError::StellarError(e.to_string()),
+            RampError::Config(e) => ApiError::InternalServerError(e.to_string()),
+            RampError::UnsupportedAsset(_)
+            | RampError::InvalidAmount(_)
+            | RampError::InsufficientBalance { .. } => ApiError::BadRequest(err.to_string()),
+        }
+    }
+}
+
+/// An issued asset the anchor ramps.
+#[derive(Debug, Clone)]
+pub struct AnchorAsset {
+    pub code: String,
+    pub issuer: String,
+    /// Its Stellar Asset Contract, used for withdrawal payments.
+    pub contract: String,
+}
+
+impl AnchorAsset {
+    /// Ledger asset name.
+    pub fn ledger_asset(&self) -> String {
+        format!("{}:{}", self.code, self.issuer)
+    }
+}
+
+/// Settings of the anchor integration.
+#[derive(Debug, Clone)]
+pub struct RampConfig {
+    pub anchor_domain: String,
+    pub assets: Vec<String>,
+    pub callback_url: Option<String>,
+    pub network_passphrase: String,
+}
+
+impl RampConfig {
+    /// `None` when no anchor is configured.
+    pub fn from_stellar_config(stellar: &StellarConfig) -> Option<Self> {
+        Some(Self {
+            anchor_domain: stellar.anchor_domain.clone()?,
+            assets: stellar.anchor_assets.clone(),
+            callback_url: stellar.anchor_callback_url.clone(),
+            network_passphrase: stellar.network_passphrase().to_string(),
+        })
+    }
+}
+
+pub struct RampService {
+    db_pool: DbPool,
+    relayer: Arc<StellarRelayer>,
+    anchor: AnchorClient,
+    assets: Vec<AnchorAsset>,
+    callback_url: Option<String>,
+}
+
+/// Where the anchor wants a withdrawal paid.
+struct PayoutInstructions {
+    account: String,
+    memo: Option<Memo>,
+    /// What the anchor expects to receive, in stroops.
+    amount: i64,
+}
+
+impl RampService {
+    /// Ramp through the anchor with the relayer's account as custody account.
+    pub fn new(
+        db_pool: DbPool,
+        relayer: Arc<StellarRelayer>,
+        config: RampConfig,
+    ) -> Result<Self, RampError> {
+        let assets = config
+            .assets
+            .iter()
+            .map(|asset| {
+                let (code, issuer) = asset.split_once(':').ok_or_else(|| {
+                    RelayerError::InvalidAccount(format!("{} is not CODE:ISSUER", asset))
+                })?;
+                Ok(AnchorAsset {
+                    code: code.to_string(),
+                    issuer: issuer.to_string(),
+                    contract: xdr::issued_asset_contract(&config.network_passphrase, code, issuer)?,
+                })
+            })
+            .collect::<Result<Vec<_>, RelayerError>>()?;
+        Ok(Self {
+            db_pool,
+            anchor: AnchorClient::new(
+                config.anchor_domain,
+                config.network_passphrase,
+                relayer.clone(),
+            ),
+            relayer,
+            assets,
+            callback_url: config.callback_url,
+        })
+    }
+
+    fn asset(&self, code: &str) -> Result<&AnchorAsset, RampError> {
+        self.assets
+            .iter()
+            .find(|asset| asset.code.eq_ignore_ascii_case(code))
+            .ok_or_else(|| RampError::UnsupportedAsset(code.to_string()))
+    }
+
+    fn asset_of(&self, ramp: &AnchorRamp) -> Result<&AnchorAsset, RampError> {
+        self.assets
+            .iter()
+            .find(|asset| asset.code == ramp.asset_code && asset.issuer == ramp.asset_issuer)
+            .ok_or_else(|| RampError::UnsupportedAsset(ramp.asset_code.clone()))
+    }
+
+    /// Start an interactive fiat deposit; the user continues at the returned
+    /// ramp's `interactive_url`.
+    pub async fn start_deposit(
+        &self,
+        user_id: Uuid,
+        request: &StartDepositRampRequest,
+    ) -> Result<AnchorRamp, RampError> {
+        let asset = self.asset(&request.asset_code)?;
+        let account = self.relayer.account_id();
+        let interactive = self
+            .anchor
+            .start_interactive(
+                RampKind::Deposit,
+                &InteractiveRequest {
+                    asset_code: &asset.code,
+                    asset_issuer: &asset.issuer,
+                    account: &account,
+                    amount: request.amount.map(format_stroops),
+                    lang: request.lang.as_deref(),
+                    on_change_callback: self.callback_url.as_deref(),
+                },
+            )
+            .await?;
+
+        let ramp: AnchorRamp = sqlx::query_as(
+            r#"
+            INSERT INTO anchor_ramps (
+                id, user_id, kind, asset_code, asset_issuer, anchor_id,
+                status, anchor_status, interactive_url, amount
+            )
+            VALUES ($1, $2, 'deposit', $3, $4, $5, 'interactive', 'incomplete', $6, $7)
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(&asset.code)
+        .bind(&asset.issuer)
+        .bind(&interactive.id)
+        .bind(&interactive.url)
+        .bind(request.amount)
+        .fetch_one(&*self.db_pool)
+        .await?;
+        info!(ramp_id = %ramp.id, %user_id, asset = %asset.code, "Anchor deposit started");
+        Ok(ramp)
+    }
+
+    /// Start an interactive fiat withdrawal. The amount is held from the
+    /// user's balance of the asset straight away.
+    pub async fn start_withdrawal(
+        &self,
+        user_id: Uuid,
+        request: &StartWithdrawalRampRequest,
+    ) -> Result<AnchorRamp, RampError> {
+        let asset = self.asset(&request.asset_code)?;
+        if request.amount <= 0 {
+            return Err(RampError::InvalidAmount(
+                "Amount must be positive".to_string(),
+            ));
+        }
+        let account = self.relayer.account_id();
+        let interactive = self
+            .anchor
+            .start_interactive(
+                RampKind::Withdrawal,
+                &InteractiveRequest {
+                    asset_code: &asset.code,
+                    asset_issuer: &asset.issuer,
+                    account: &account,
+                    amount: Some(format_stroops(request.amount)),
+                    lang: request.lang.as_deref(),
+                    on_change_callback: self.callback_url.as_deref(),
+                },
+            )
+            .await?;
+
+        // An anchor transaction left behind by a failed check simply expires.
+        let ledger_asset = asset.ledger_asset();
+        let mut tx = self.db_pool.begin().await?;
+        let available =
+            ledger::balance_for_update(&mut tx, LedgerAccount::Available(user_id), &ledger_asset)
+                .await?;
+        if available < request.amount {
+            return Err(RampError::InsufficientBalance {
+                required: request.amount,
+                available,
+            });
+        }
+
+        let ramp_id = Uuid::new_v4();
+        let transaction_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO transactions (
+                id, user_id, transaction_type, amount, currency,
+                status, reference, description, metadata
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(TransactionType::Withdrawal)
+        .bind(Decimal::from(request.amount))
+        .bind(&asset.code)
+        .bind(TransactionStatus::Pending)
+        .bind(format!("anchor-withdrawal:{}", ramp_id))
+        .bind(format!("{} withdrawal to fiat", asset.code))
+        .bind(json!({ "ramp_id": ramp_id, "anchor_id": interactive.id }).to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        ledger::post(
+            &mut tx,
+            &Journal::transfer(
+                format!("ramp:{}:hold", ramp_id),
+                "ramp_withdrawal_hold",
+                Some(user_id),
+                LedgerAccount::Available(user_id),
+                LedgerAccount::PendingWithdrawal(user_id),
+                request.amount,
+            )
+            .with_asset(&ledger_asset),
+        )
+        .await?;
+
+        let ramp: AnchorRamp = sqlx::query_as(
+            r#"
+            INSERT INTO anchor_ramps (
+                id, user_id, kind, asset_code, asset_issuer, anchor_id, status,
+                anchor_status, interactive_url, amount, transaction_id
+            )
+            VALUES ($1, $2, 'withdrawal', $3, $4, $5, 'interactive', 'incomplete', $6, $7, $8)
+            RETURNING *
+            "#,
+        )
+        .bind(ramp_id)
+        .bind(user_id)
+        .bind(&asset.code)
+        .bind(&asset.issuer)
+        .bind(&interactive.id)
+        .bind(&interactive.url)
+        .bind(request.amount)
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        info!(
+            %ramp_id,
+            %user_id,
+            asset = %asset.code,
+            amount = request.amount,
+            "Anchor withdrawal started"
+        );
+        Ok(ramp)
+    }
+
+    /// A user's ramps, newest first.
+    pub async fn list_ramps(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<AnchorRamp>, RampError> {
+        let ramps = sqlx::query_as(
+            r#"
+            SELECT * FROM anchor_ramps
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.db_pool)
+        .await?;
+        Ok(ramps)
+    }
+
+    pub async fn get_ramp(&self, user_id: Uuid, ramp_id: Uuid) -> Result<AnchorRamp, RampError> {
+        sqlx::query_as("SELECT * FROM anchor_ramps WHERE id = $1 AND user_id = $2")
+            .bind(ramp_id)
+            .bind(user_id)
+            .fetch_optional(&*self.db_pool)
+            .await?
+            .ok_or(RampError::NotFound)
+    }
+
+    /// The anchor reported a status change: fetch the transaction again
+    /// rather than trusting the callback body.
+    pub async fn handle_callback(self: &Arc<Self>, anchor_id: &str) -> Result<(), RampError> {
+        let ramp_id: Uuid = sqlx::query_scalar("SELECT id FROM anchor_ramps WHERE anchor_id = $1")
+            .bind(anchor_id)
+            .fetch_optional(&*self.db_pool)
+            .await?
+            .ok_or(RampError::NotFound)?;
+        let transaction = self.anchor.transaction(anchor_id).await?;
+        self.apply(ramp_id, &transaction).await
+    }
+
+    /// Poll open ramps in a detached task. Payments to the anchor that were
+    /// mid-submission at a restart are left unconfirmed for reconciliation.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            if let Err(e) = sqlx::query(
+                r#"
+                UPDATE anchor_ramps
+                SET status = 'unconfirmed', message = 'interrupted during payment', updated_at = NOW()
+                WHERE status = 'paying'
+                "#,
+            )
+            .execute(&*self.db_pool)
+            .await
+            {
+                error!(error = %e, "Failed to mark interrupted anchor payments");
+            }
+            info!("Anchor ramp poller started");
+            let mut interval = tokio::time::interval(Duration::from_secs(POLL_INTERVAL_SECS));
+            loop {
+                interval.tick().await;
+                if let Err(e) = self.poll_open_ramps().await {
+                    warn!(error = %e, "Anchor ramp poll failed");
+                }
+            }
+        });
+    }
+
+    async fn poll_open_ramps(self: &Arc<Self>) -> Result<(), RampError> {
+        let open: Vec<(Uuid, String)> = sqlx::query_as(
+            r#"
+            SELECT id, anchor_id FROM anchor_ramps
+            WHERE status IN ('interactive', 'pending')
+            ORDER BY updated_at
+            "#,
+        )
+        .fetch_all(&*self.db_pool)
+        .await?;
+        for (ramp_id, anchor_id) in open {
+            let result = match self.anchor.transaction(&anchor_id).await {
+                Ok(transaction) => self.apply(ramp_id, &transaction).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                warn!(%ramp_id, error = %e, "Failed to refresh anchor ramp");
+            }
+        }
+        Ok(())
+    }
+
+    /// Bring a ramp in line with the anchor's view of it.
+    async fn apply(
+        self: &Arc<Self>,
+        ramp_id: Uuid,
+        transaction: &AnchorTransaction,
+    ) -> Result<(), RampError> {
+        let mut tx = self.db_pool.begin().await?;
+        let ramp: AnchorRamp =
+            sqlx::query_as("SELECT * FROM anchor_ramps WHERE id = $1 FOR UPDATE")
+                .bind(ramp_id)
+                .fetch_optional(&mut *tx)
+                .await?
+                .ok_or(RampError::NotFound)?;
+        if ramp.anchor_id != transaction.id || is_final(ramp.status) {
+            return Ok(());
+        }
+
+        let amount = |value: &Option<String>| value.as_deref().and_then(parse_stroops);
+        let amount_in = amount(&transaction.amount_in);
+        let amount_out = amount(&transaction.amount_out);
+        let paid = ramp.payout_tx_hash.is_some();
+        // Our own payment's state wins over the anchor's until it is settled.
+        let payment_in_flight = matches!(ramp.status, RampStatus::Paying | RampStatus::Unconfirmed);
+
+        let mut payout = None;
+        let status = match (ramp.kind, anchor_ramp_status(&transaction.status)) {
+            _ if payment_in_flight => ramp.status,
+            (RampKind::Deposit, RampStatus::Completed) => {
+                let Some(amount_out) = amount_out else {
+                    return Err(AnchorError::InvalidResponse(
+                        "completed deposit without amount_out".to_string(),
+                    )
+                    .into());
+                };
+                self.credit_deposit(&mut tx, &ramp, amount_out, transaction)
+                    .await?;
+                RampStatus::Completed
+            }
+            (RampKind::Withdrawal, RampStatus::Completed) if !paid => {
+                warn!(%ramp_id, "Anchor completed a withdrawal we have not paid");
+                ramp.status
+            }
+            (RampKind::Withdrawal, RampStatus::Completed) => {
+                if let Some(transaction_id) = ramp.transaction_id {
+                    sqlx::query(
+                        r#"
+                        UPDATE transactions
+                        SET status = $2, completed_at = NOW(), updated_at = NOW()
+                        WHERE id = $1
+                        "#,
+                    )
+                    .bind(transaction_id)
+                    .bind(TransactionStatus::Completed)
+                    .execute(&mut *tx)
+                    .await?;
+                }
+                RampStatus::Completed
+            }
+            (RampKind::Withdrawal, failed @ (RampStatus::Failed | RampStatus::Expired)) => {
+                if paid {
+                    warn!(
+                        %ramp_id,
+                        anchor_status = %transaction.status,
+                        "Anchor failed a paid withdrawal; any refund needs reconciling"
+                    );
+                } else {
+                    self.release_hold(&mut tx, &ramp).await?;
+                }
+                failed
+            }
+            (RampKind::Withdrawal, RampStatus::Pending)
+                if !paid && transaction.status == "pending_user_transfer_start" =>
+            {
+                match payout_instructions(&ramp, transaction, amount_in) {
+                    Ok(instructions) => {
+                        payout = Some(instructions);
+                        RampStatus::Pending
+                    }
+                    // We can't pay what the anchor asks for; the anchor
+                    // transaction is left to expire.
+                    Err(e) => {
+                        warn!(%ramp_id, error = %e, "Cannot pay anchor withdrawal; returning funds");
+                        self.release_hold(&mut tx, &ramp).await?;
+                        RampStatus::Failed
+                    }
+                }
+            }
+            (_, reported) => reported,
+        };
+
+        sqlx::query(
+            r#"
+            UPDATE anchor_ramps
+            SET status = $2, anchor_status = $3, amount_in = $4, amount_out = $5,
+                amount_fee = $6, stellar_transaction_id = $7, message = $8,
+                completed_at = CASE WHEN $2 = 'completed' THEN NOW() END,
+                updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(ramp_id)
+        .bind(status)
+        .bind(&transaction.status)
+        .bind(amount_in)
+        .bind(amount_out)
+        .bind(amount(&transaction.amount_fee))
+        .bind(&transaction.stellar_transaction_id)
+        .bind(&transaction.message)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        if status != ramp.status {
+            info!(%ramp_id, from = ?ramp.status, to = ?status, "Anchor ramp updated");
+        }
+
+        if let Some(payout) = payout {
+            self.spawn_payout(ramp_id, payout);
+        }
+        Ok(())
+    }
+
+    /// Credit a completed deposit: the anchor's payment arrived in custody.
+    async fn credit_deposit(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        ramp: &AnchorRamp,
+        amount: i64,
+        transaction: &AnchorTransaction,
+    ) -> Result<(), RampError> {
+        let asset = self.asset_of(ramp)?;
+        let metadata = json!({
+            "ramp_id": ramp.id,
+            "anchor_id": ramp.anchor_id,
+            "tx_hash": transaction.stellar_transaction_id,
+        });
+        let journal = Journal::transfer(
+            format!("ramp:{}:deposit", ramp.id),
+            "ramp_deposit",
+            Some(ramp.user_id),
+            LedgerAccount::Custody,
+            LedgerAccount::Available(ramp.user_id),
+            amount,
+        )
+        .with_asset(&asset.ledger_asset())
+        .with_metadata(metadata.clone());
+        if ledger::post(tx, &journal).await?.is_none() {
+            return Ok(());
+        }
+
+        let transaction_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO transactions (
+                id, user_id, transaction_type, amount, currency, status,
+                reference, description, metadata, stellar_transaction_id, completed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, NOW())
+            RETURNING id
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(ramp.user_id)
+        .bind(TransactionType::Deposit)
+        .bind(Decimal::from(amount))
+        .bind(&asset.code)
+        .bind(TransactionStatus::Completed)
+        .bind(format!("anchor-deposit:{}", ramp.id))
+        .bind(format!("{} deposit from fiat", asset.code))
+        .bind(metadata.to_string())
+        .bind(&transaction.stellar_transaction_id)
+        .fetch_one(&mut **tx)
+        .await?;
+        sqlx::query("UPDATE anchor_ramps SET transaction_id = $2 WHERE id = $1")
+            .bind(ramp.id)
+            .bind(transaction_id)
+            .execute(&mut **tx)
+            .await?;
+        info!(ramp_id = %ramp.id, user_id = %ramp.user_id, amount, "Anchor deposit credited");
+        Ok(())
+    }
+
+    fn spawn_payout(self: &Arc<Self>, ramp_id: Uuid, payout: PayoutInstructions) {
+        let service = self.clone();
+        // Relayed calls wait for ledger inclusion; don't hold up the caller.
+        tokio::spawn(async move {
+            if let Err(e) = service.execute_payout(ramp_id, payout).await {
+                error!(%ramp_id, error = %e, "Anchor withdrawal payment failed");
+            }
+        });
+    }
+
+    async fn execute_payout(
+        &self,
+        ramp_id: Uuid,
+        payout: PayoutInstructions,
+    ) -> Result<(), RampError> {
+        // Claim the ramp so the anchor is only ever paid once.
+        let ramp: Option<AnchorRamp> = sqlx::query_as(
+            r#"
+            UPDATE anchor_ramps SET status = 'paying', updated_at = NOW()
+            WHERE id = $1 AND status = 'pending' AND payout_tx_hash IS NULL
+            RETURNING *
+            "#,
+        )
+        .bind(ramp_id)
+        .fetch_optional(&*self.db_pool)
+        .await?;
+        let Some(ramp) = ramp else {
+            return Ok(());
+        };
+        let asset = self.asset_of(&ramp)?;
+
+        let result = match payout.memo {
+            Some(memo) => {
+                self.relayer
+                    .transfer_with_memo(
+                        &asset.contract,
+                        &payout.account,
+                        payout.amount as i128,
+                        memo,
+                    )
+                    .await
+            }
+            None => {
+                self.relayer
+                    .transfer(&asset.contract, &payout.account, payout.amount as i128)
+                    .await
+            }
+        };
+        match result {
+            Ok(receipt) => {
+                self.settle_payout(&ramp, payout.amount, &receipt.tx_hash)
+                    .await
+            }
+            // The contract rejected the transfer, so nothing left custody.
+            Err(e @ (RelayerError::SimulationFailed(_) | RelayerError::TransactionFailed(_))) => {
+                warn!(%ramp_id, error = %e, "Anchor payment rejected; returning funds");
+                let mut tx = self.db_pool.begin().await?;
+                sqlx::query(
+                    r#"
+                    UPDATE anchor_ramps
+                    SET status = 'failed', message = $2, updated_at = NOW()
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(ramp_id)
+                .bind(e.to_string())
+                .execute(&mut *tx)
+                .await?;
+                self.release_hold(&mut tx, &ramp).await?;
+                tx.commit().await?;
+                Ok(())
+            }
+            Err(e) => {
+                sqlx::query(
+                    r#"
+                    UPDATE anchor_ramps
+                    SET status = 'unconfirmed', message = $2, updated_at = NOW()
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(ramp_id)
+                .bind(e.to_string())
+                .execute(&*self.db_pool)
+                .await?;
+                warn!(%ramp_id, error = %e, "Anchor payment outcome unknown; funds stay held");
+                Ok(())
+            }
+        }
+    }
+
+    /// Book a landed payment to the anchor: `paid` leaves custody and anything
+    /// held beyond it returns to the user. The ramp then waits for the anchor
+    /// to complete the fiat payout.
+    async fn settle_payout(
+        &self,
+        ramp: &AnchorRamp,
+        paid: i64,
+        tx_hash: &str,
+    ) -> Result<(), RampError> {
+        let asset = self.asset_of(ramp)?;
+        let held = ramp.amount.unwrap_or(paid);
+        let mut journal = Journal::transfer(
+            format!("ramp:{}:settle", ramp.id),
+            "ramp_withdrawal_settle",
+            Some(ramp.user_id),
+            LedgerAccount::PendingWithdrawal(ramp.user_id),
+            LedgerAccount::Custody,
+            held,
+        )
+        .with_asset(&asset.ledger_asset())
+        .with_metadata(json!({ "tx_hash": tx_hash }));
+        if held > paid {
+            journal.entries = vec![
+                (LedgerAccount::PendingWithdrawal(ramp.user_id), -held),
+                (LedgerAccount::Custody, paid),
+                (LedgerAccount::Available(ramp.user_id), held - paid),
+            ];
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+        ledger::post(&mut tx, &journal).await?;
+        sqlx::query(
+            r#"
+            UPDATE anchor_ramps
+            SET status = 'pending', payout_tx_hash = $2, updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(ramp.id)
+        .bind(tx_hash)
+        .execute(&mut *tx)
+        .await?;
+        if let Some(transaction_id) = ramp.transaction_id {
+            sqlx::query(
+                r#"
+                UPDATE transactions
+                SET status = $2, amount = $3, stellar_transaction_id = $4, updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(transaction_id)
+            .bind(TransactionStatus::Processing)
+            .bind(Decimal::from(paid))
+            .bind(tx_hash)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+        info!(ramp_id = %ramp.id, tx_hash, paid, "Anchor withdrawal paid to anchor");
+        Ok(())
+    }
+
+    /// Return a withdrawal's held funds to the user's available balance.
+    async fn release_hold(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        ramp: &AnchorRamp,
+    ) -> Result<(), RampError> {
+        let (Some(amount), RampKind::Withdrawal) = (ramp.amount, ramp.kind) else {
+            return Ok(());
+        };
+        let asset = self.asset_of(ramp)?;
+        ledger::post(
+            tx,
+            &Journal::transfer(
+                format!("ramp:{}:release", ramp.id),
+                "ramp_withdrawal_release",
+                Some(ramp.user_id),
+                LedgerAccount::PendingWithdrawal(ramp.user_id),
+                LedgerAccount::Available(ramp.user_id),
+                amount,
+            )
+            .with_asset(&asset.ledger_asset()),
+        )
+        .await?;
+        if let Some(transaction_id) = ramp.transaction_id {
+            sqlx::query("UPDATE transactions SET status = $2, updated_at = NOW() WHERE id = $1")
+                .bind(transaction_id)
+                .bind(TransactionStatus::Failed)
+                .execute(&mut **tx)
+                .await?;
+        }
+        Ok(())
+    }
+}
+
+fn is_final(status: RampStatus) -> bool {
+    matches!(
+        status,
+        RampStatus::Completed | RampStatus::Failed | RampStatus::Expired
+    )
+}
+
+/// Map a SEP-24 transaction status onto a ramp status.
+fn anchor_ramp_status(status: &str) -> RampStatus {
+    match status {
+        "incomplete" => RampStatus::Interactive,
+        "completed" => RampStatus::Completed,
+        "expired" => RampStatus::Expired,
+        "error" | "refunded" | "no_market" | "too_small" | "too_large" => RampStatus::Failed,
+        _ => RampStatus::Pending,
+    }
+}
+
+/// Where and how much to pay the anchor for a withdrawal. The anchor may
+/// charge less than was held, never more.
+fn payout_instructions(
+    ramp: &AnchorRamp,
+    transaction: &AnchorTransaction,
+    amount_in: Option<i64>,
+) -> Result<PayoutInstructions, RampError> {
+    let invalid = |reason: &str| AnchorError::InvalidResponse(reason.to_string());
+    let account = transaction
+        .withdraw_anchor_account
+        .clone()
+        .ok_or_else(|| invalid("withdrawal without withdraw_anchor_account"))?;
+    xdr::account_public_key(&account).map_err(AnchorError::from)?;
+    let memo = match (&transaction.withdraw_memo_type, &transaction.withdraw_memo) {
+        (Some(memo_type), Some(memo)) => {
+            Some(Memo::parse(memo_type, memo).map_err(AnchorError::from)?)
+        }
+        _ => None,
+    };
+    let held = ramp.amount.unwrap_or(0);
+    let amount = amount_in.unwrap_or(held);
+    if amount <= 0 || amount > held {
+        return Err(RampError::InvalidAmount(format!(
+            "anchor expects {} but {} is held",
+            amount, held
+        )));
+    }
+    Ok(PayoutInstructions {
+        account,
+        memo,
+        amount,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_ramp_status() {
+        assert_eq!(anchor_ramp_status("incomplete"), RampStatus::Interactive);
+        assert_eq!(
+            anchor_ramp_status("pending_user_transfer_start"),
+            RampStatus::Pending
+        );
+        assert_eq!(anchor_ramp_status("pending_anchor"), RampStatus::Pending);
+        assert_eq!(anchor_ramp_status("completed"), RampStatus::Completed);
+        assert_eq!(anchor_ramp_status("too_small"), RampStatus::Failed);
+        assert_eq!(anchor_ramp_status("expired"), RampStatus::Expired);
+    }
+}
@@ -0,0 +1,253 @@
+//! Detectors scoring anti-cheat signals.
+//!
+//! Each detector looks at one signal and reports zero or more [`Finding`]s,
+//! each weighted 0 to 1. A signal's score combines the weights as independent
+//! evidence, `1 - Π(1 - w)`, so several weak findings add up without the
+//! score ever exceeding 1.
+
+use crate::config::AntiCheatRule;
+use crate::models::Finding;
+use std::collections::{BTreeMap, HashMap};
+
+/// Weight of a failed client integrity check.
+const INTEGRITY_WEIGHT: f64 = 0.8;
+/// Weight of a metric far outside the player's own history.
+const ZSCORE_WEIGHT: f64 = 0.5;
+
+/// A player's history of one telemetry metric.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MetricBaseline {
+    pub mean: f64,
+    pub stddev: f64,
+    pub samples: i64,
+}
+
+/// A signal as detectors see it.
+#[derive(Debug)]
+pub enum SignalInput<'a> {
+    Telemetry {
+        metrics: &'a BTreeMap<String, f64>,
+        integrity_violations: &'a [String],
+        /// The player's earlier telemetry, by metric.
+        baseline: &'a HashMap<String, MetricBaseline>,
+    },
+    Report {
+        kind: &'a str,
+        confidence: f64,
+    },
+}
+
+pub trait Detector: Send + Sync {
+    fn evaluate(&self, signal: &SignalInput<'_>) -> Vec<Finding>;
+}
+
+/// Flags a metric above or below a fixed bound.
+pub struct ThresholdDetector {
+    rule: AntiCheatRule,
+}
+
+impl ThresholdDetector {
+    pub fn new(rule: AntiCheatRule) -> Self {
+        Self { rule }
+    }
+}
+
+impl Detector for ThresholdDetector {
+    fn evaluate(&self, signal: &SignalInput<'_>) -> Vec<Finding> {
+        let SignalInput::Telemetry { metrics, .. } = signal else {
+            return Vec::new();
+        };
+        let Some(&value) = metrics.get(&self.rule.metric) else {
+            return Vec::new();
+        };
+        let (outside, bound) = if self.rule.above {
+            (value > self.rule.limit, "above")
+        } else {
+            (value < self.rule.limit, "below")
+        };
+        if !outside {
+            return Vec::new();
+        }
+        vec![Finding {
+            detector: "threshold".to_string(),
+            subject: self.rule.metric.clone(),
+            value,
+            weight: self.rule.weight,
+            detail: format!("{} is {} {}", value, bound, self.rule.limit),
+        }]
+    }
+}
+
+/// Flags metrics many standard deviations from the player's own history.
+pub struct ZScoreDetector {
+    threshold: f64,
+    min_samples: i64,
+}
+
+impl ZScoreDetector {
+    pub fn new(threshold: f64, min_samples: i64) -> Self {
+        Self {
+            threshold,
+            min_samples,
+        }
+    }
+}
+
+impl Detector for ZScoreDetector {
+    fn evaluate(&self, signal: &SignalInput<'_>) -> Vec<Finding> {
+        let SignalInput::Telemetry {
+            metrics, baseline, ..
+        } = signal
+        else {
+            return Vec::new();
+        };
+        metrics
+            .iter()
+            .filter_map(|(metric, &value)| {
+                let history = baseline.get(metric)?;
+                // A constant history says nothing about spread.
+                if history.samples < self.min_samples || history.stddev <= f64::EPSILON {
+                    return None;
+                }
+                let z = (value - history.mean) / history.stddev;
+                (z.abs() >= self.threshold).then(|| Finding {
+                    detector: "zscore".to_string(),
+                    subject: metric.clone(),
+                    value,
+                    weight: ZSCORE_WEIGHT,
+                    detail: format!(
+                        "{:.1} standard deviations from the player's mean of {:.3}",
+                        z, history.mean
+                    ),
+                })
+            })
+            .collect()
+    }
+}
+
+/// Turns failed client integrity checks into findings.
+pub struct IntegrityDetector;
+
+impl Detector for IntegrityDetector {
+    fn evaluate(&self, signal: &SignalInput<'_>) -> Vec<Finding> {
+        let SignalInput::Telemetry {
+            integrity_violations,
+            ..
+        } = signal
+        else {
+            return Vec::new();
+        };
+        integrity_violations
+            .iter()
+            .map(|check| Finding {
+                detector: "integrity".to_string(),
+                subject: check.clone(),
+                value: 1.0,
+                weight: INTEGRITY_WEIGHT,
+                detail: format!("Client integrity check `{}` failed", check),
+            })
+            .collect()
+    }
+}
+
+/// Takes an anomaly report at the reporter's confidence.
+pub struct ReportDetector;
+
+impl Detector for ReportDetector {
+    fn evaluate(&self, signal: &SignalInput<'_>) -> Vec<Finding> {
+        let SignalInput::Report { kind, confidence } = signal else {
+            return Vec::new();
+        };
+        vec![Finding {
+            detector: "report".to_string(),
+            subject: kind.to_string(),
+            value: *confidence,
+            weight: *confidence,
+            detail: format!("Anomaly report `{}`", kind),
+        }]
+    }
+}
+
+/// Combined score of a signal's findings.
+pub fn score(findings: &[Finding]) -> f64 {
+    1.0 - findings
+        .iter()
+        .map(|f| 1.0 - f.weight.clamp(0.0, 1.0))
+        .product::<f64>()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn telemetry<'a>(
+        metrics: &'a BTreeMap<String, f64>,
+        baseline: &'a HashMap<String, MetricBaseline>,
+    ) -> SignalInput<'a> {
+        SignalInput::Telemetry {
+            metrics,
+            integrity_violations: &[],
+            baseline,
+        }
+    }
+
+    #[test]
+    fn test_threshold_and_zscore_detectors() {
+        let metrics = BTreeMap::from([
+            ("headshot_ratio".to_string(), 0.95),
+            ("avg_reaction_ms".to_string(), 180.0),
+        ]);
+        let baseline = HashMap::from([(
+            "avg_reaction_ms".to_string(),
+            MetricBaseline {
+                mean: 260.0,
+                stddev: 15.0,
+                samples: 30,
+            },
+        )]);
+        let signal = telemetry(&metrics, &baseline);
+
+        let above = ThresholdDetector::new(AntiCheatRule {
+            metric: "headshot_ratio".to_string(),
+            above: true,
+            limit: 0.85,
+            weight: 0.6,
+        });
+        assert_eq!(above.evaluate(&signal).len(), 1);
+        let below = ThresholdDetector::new(AntiCheatRule {
+            metric: "avg_reaction_ms".to_string(),
+            above: false,
+            limit: 120.0,
+            weight: 0.5,
+        });
+        assert!(below.evaluate(&signal).is_empty());
+
+        let zscore = ZScoreDetector::new(4.0, 10);
+        let findings = zscore.evaluate(&signal);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].subject, "avg_reaction_ms");
+        // Too little history to judge.
+        assert!(ZScoreDetector::new(4.0, 50).evaluate(&signal).is_empty());
+    }
+
+    #[test]
+    fn test_score_combines_findings_as_independent_evidence() {
+        let finding = |weight| Finding {
+            detector: "test".to_string(),
+            subject: "m".to_string(),
+            value: 0.0,
+            weight,
+            detail: String::new(),
+        };
+        assert_eq!(score(&[]), 0.0);
+        assert!((score(&[finding(0.5), finding(0.5)]) - 0.75).abs() < 1e-9);
+        assert_eq!(score(&[finding(1.5)]), 1.0);
+
+        let report = SignalInput::Report {
+            kind: "aim_snap",
+            confidence: 0.9,
+        };
+        assert!((score(&ReportDetector.evaluate(&report)) - 0.9).abs() < 1e-9);
+        assert!(IntegrityDetector.evaluate(&report).is_empty());
+    }
+}
@@ -0,0 +1,775 @@
+//! Anti-cheat signal ingestion, review queue and oracle submission.
+//!
+//! Game clients send per-match telemetry for the signed-in player, and
+//! analytics services (operators) send statistical anomaly reports. Every
+//! signal is stored and scored by the configured [`Detector`]s; a player whose
+//! signal scores at or above the review threshold is queued as a detection for
+//! that match, and later signals for the same match join the open case.
+//!
+//! Referees confirm or dismiss detections. Confirmed detections are flagged on
+//! the anti-cheat oracle contract through the relayer, with the platform
+//! account as oracle; it must therefore be an authorized oracle on the
+//! contract. Every action, including the on-chain submission, is appended to
+//! the detection's audit trail.
+
+pub mod detectors;
+
+pub use detectors::{
+    Detector, IntegrityDetector, MetricBaseline, ReportDetector, SignalInput, ThresholdDetector,
+    ZScoreDetector,
+};
+
+use crate::api_error::ApiError;
+use crate::config::{AntiCheatConfig, StellarConfig};
+use crate::db::DbPool;
+use crate::models::match_models::Match;
+use crate::models::{
+    AntiCheatAuditEntry, AntiCheatSignal, Detection, DetectionDetail, DetectionStatus, Finding,
+    ReviewDecision, ReviewDetectionRequest, SignalReceipt, SubmitAnomalyReportRequest,
+    SubmitTelemetryRequest,
+};
+use crate::service::stellar_relayer::StellarRelayer;
+use serde_json::json;
+use std::collections::HashMap;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const MAX_METRICS: usize = 50;
+const MAX_NAME_LEN: usize = 64;
+/// Past telemetry signals a player's baseline is computed from.
+const BASELINE_WINDOW: i64 = 200;
+
+#[derive(Debug, Error)]
+pub enum AntiCheatError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("Detection not found")]
+    NotFound,
+    #[error("Match not found")]
+    MatchNotFound,
+    #[error("{0}")]
+    Forbidden(String),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<AntiCheatError> for ApiError {
+    fn from(err: AntiCheatError) -> Self {
+        match err {
+            AntiCheatError::InvalidRequest(_) => ApiError::BadRequest(err.to_string()),
+            AntiCheatError::Conflict(_) => ApiError::Conflict(err.to_string()),
+            AntiCheatError::NotFound | AntiCheatError::MatchNotFound => ApiError::NotFound,
+            AntiCheatError::Forbidden(msg) => ApiError::forbidden(msg),
+            AntiCheatError::DatabaseError(e) => ApiError::DatabaseError(e),
+        }
+    }
+}
+
+/// The anti-cheat oracle identifies matches by `u64`: the first eight bytes
+/// of the match UUID, big-endian.
+pub fn oracle_match_id(match_id: Uuid) -> u64 {
+    let bytes = match_id.as_bytes();
+    u64::from_be_bytes([
+        bytes[0], bytes[1], bytes[2], bytes[3], bytes[4], bytes[5], bytes[6], bytes[7],
+    ])
+}
+
+pub struct AntiCheatService {
+    db_pool: DbPool,
+    detectors: Vec<Box<dyn Detector>>,
+    review_threshold: f64,
+    relayer: Option<Arc<StellarRelayer>>,
+    oracle_contract: Option<String>,
+}
+
+impl AntiCheatService {
+    pub fn new(db_pool: DbPool, config: &AntiCheatConfig) -> Self {
+        let mut detectors: Vec<Box<dyn Detector>> = config
+            .rules
+            .iter()
+            .map(|rule| Box::new(ThresholdDetector::new(rule.clone())) as Box<dyn Detector>)
+            .collect();
+        if config.zscore_threshold > 0.0 {
+            detectors.push(Box::new(ZScoreDetector::new(
+                config.zscore_threshold,
+                config.min_baseline_samples,
+            )));
+        }
+        detectors.push(Box::new(IntegrityDetector));
+        detectors.push(Box::new(ReportDetector));
+        Self {
+            db_pool,
+            detectors,
+            review_threshold: config.review_threshold,
+            relayer: None,
+            oracle_contract: None,
+        }
+    }
+
+    /// Score signals with `detector` as well as the configured ones.
+    pub fn with_detector(mut self, detector: Box<dyn Detector>) -> Self {
+        self.detectors.push(detector);
+        self
+    }
+
+    /// Flag confirmed detections on the anti-cheat oracle, if one is configured.
+    pub fn with_relayer(mut self, relayer: Arc<StellarRelayer>, stellar: &StellarConfig) -> Self {
+        self.relayer = Some(relayer);
+        self.oracle_contract = stellar.soroban_contract_anticheat.clone();
+        self
+    }
+
+    // ========================================================================
+    // SIGNALS
+    // ========================================================================
+
+    /// Score telemetry the game client sent for `player_id`.
+    pub async fn ingest_telemetry(
+        &self,
+        player_id: Uuid,
+        request: &SubmitTelemetryRequest,
+    ) -> Result<SignalReceipt, AntiCheatError> {
+        validate_telemetry(request)?;
+        self.ensure_participant(request.match_id, player_id).await?;
+
+        let baseline = self.baseline(player_id, request.match_id).await?;
+        let findings = self.evaluate(&SignalInput::Telemetry {
+            metrics: &request.metrics,
+            integrity_violations: &request.integrity_violations,
+            baseline: &baseline,
+        });
+        let payload = json!({
+            "metrics": request.metrics,
+            "integrity_violations": request.integrity_violations,
+            "client_version": request.client_version,
+        });
+        self.record(
+            player_id,
+            request.match_id,
+            "telemetry",
+            player_id,
+            payload,
+            findings,
+        )
+        .await
+    }
+
+    /// Score an anomaly report from an analytics service.
+    pub async fn ingest_report(
+        &self,
+        reporter_id: Uuid,
+        request: &SubmitAnomalyReportRequest,
+    ) -> Result<SignalReceipt, AntiCheatError> {
+        self.ensure_participant(request.match_id, request.player_id)
+            .await?;
+        let findings = self.evaluate(&SignalInput::Report {
+            kind: &request.kind,
+            confidence: request.confidence,
+        });
+        let payload = json!({
+            "kind": request.kind,
+            "confidence": request.confidence,
+            "details": request.details,
+        });
+        self.record(
+            request.player_id,
+            request.match_id,
+            "report",
+            reporter_id,
+            payload,
+            findings,
+        )
+        .await
+    }
+
+    fn evaluate(&self, signal: &SignalInput<'_>) -> Vec<Finding> {
+        self.detectors
+            .iter()
+            .flat_map(|detector| detector.evaluate(signal))
+            .collect()
+    }
+
+    /// The player's telemetry history per metric, leaving out `match_id` so a
+    /// match's own signals don't dilute its anomalies.
+    async fn baseline(
+        &self,
+        player_id: Uuid,
+        match_id: Uuid,
+    ) -> Result<HashMap<String, MetricBaseline>, AntiCheatError> {
+        let rows: Vec<(String, f64, f64, i64)> = sqlx::query_as(
+            r#"
+            SELECT m.key,
+                   AVG(m.value::float8),
+                   COALESCE(STDDEV_SAMP(m.value::float8), 0),
+                   COUNT(*)
+            FROM (
+                SELECT payload FROM anticheat_signals
+                WHERE player_id = $1 AND source = 'telemetry' AND match_id <> $2
+                ORDER BY created_at DESC
+                LIMIT $3
+            ) s
+            CROSS JOIN LATERAL jsonb_each_text(s.payload->'metrics') m
+            GROUP BY m.key
+            "#,
+        )
+        .bind(player_id)
+        .bind(match_id)
+        .bind(BASELINE_WINDOW)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(rows
+            .into_iter()
+            .map(|(metric, mean, stddev, samples)| {
+                (
+                    metric,
+                    MetricBaseline {
+                        mean,
+                        stddev,
+                        samples,
+                    },
+                )
+            })
+            .collect())
+    }
+
+    /// Store a scored signal, queueing the player for review if it reaches
+    /// the threshold.
+    async fn record(
+        &self,
+        player_id: Uuid,
+        match_id: Uuid,
+        source: &str,
+        submitted_by: Uuid,
+        payload: serde_json::Value,
+        findings: Vec<Finding>,
+    ) -> Result<SignalReceipt, AntiCheatError> {
+        let score = detectors::score(&findings);
+        let signal_id = Uuid::new_v4();
+        let findings = sqlx::types::Json(findings);
+        let mut tx = self.db_pool.begin().await?;
+
+        let mut detection_id = None;
+        if score >= self.review_threshold {
+            // Once confirmed, the match needs no further review.
+            let confirmed: Option<Uuid> = sqlx::query_scalar(
+                "SELECT id FROM anticheat_detections WHERE player_id = $1 AND match_id = $2 AND status = $3",
+            )
+            .bind(player_id)
+            .bind(match_id)
+            .bind(DetectionStatus::Confirmed)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if confirmed.is_none() {
+                let (id, signal_count): (Uuid, i32) = sqlx::query_as(
+                    r#"
+                    INSERT INTO anticheat_detections (
+                        id, player_id, match_id, score, findings, signal_count, status,
+                        created_at, updated_at
+                    ) VALUES ($1, $2, $3, $4, $5, 1, $6, NOW(), NOW())
+                    ON CONFLICT (player_id, match_id) WHERE status = 'pending_review'
+                    DO UPDATE SET
+                        score = GREATEST(anticheat_detections.score, EXCLUDED.score),
+                        findings = anticheat_detections.findings || EXCLUDED.findings,
+                        signal_count = anticheat_detections.signal_count + 1,
+                        updated_at = NOW()
+                    RETURNING id, signal_count
+                    "#,
+                )
+                .bind(Uuid::new_v4())
+                .bind(player_id)
+                .bind(match_id)
+                .bind(score)
+                .bind(&findings)
+                .bind(DetectionStatus::PendingReview)
+                .fetch_one(&mut *tx)
+                .await?;
+                let action = if signal_count == 1 {
+                    "queued"
+                } else {
+                    "signal_added"
+                };
+                insert_audit(
+                    &mut tx,
+                    id,
+                    None,
+                    action,
+                    json!({ "signal_id": signal_id, "source": source, "score": score }),
+                )
+                .await?;
+                detection_id = Some(id);
+            }
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO anticheat_signals (
+                id, player_id, match_id, source, submitted_by, payload, score, findings,
+                detection_id, created_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
+            "#,
+        )
+        .bind(signal_id)
+        .bind(player_id)
+        .bind(match_id)
+        .bind(source)
+        .bind(submitted_by)
+        .bind(payload)
+        .bind(score)
+        .bind(&findings)
+        .bind(detection_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        if let Some(id) = detection_id {
+            info!(
+                detection_id = %id,
+                player_id = %player_id,
+                match_id = %match_id,
+                score,
+                "Player queued for anti-cheat review"
+            );
+        }
+        Ok(SignalReceipt {
+            signal_id,
+            score,
+            detection_id,
+        })
+    }
+
+    // ========================================================================
+    // REVIEW
+    // ========================================================================
+
+    /// Detections in `status`, highest score first.
+    pub async fn list(
+        &self,
+        status: DetectionStatus,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<Detection>, AntiCheatError> {
+        let detections = sqlx::query_as::<_, Detection>(
+            r#"
+            SELECT * FROM anticheat_detections
+            WHERE status = $1
+            ORDER BY score DESC, created_at ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(detections)
+    }
+
+    pub async fn get(&self, detection_id: Uuid) -> Result<DetectionDetail, AntiCheatError> {
+        let detection = self.find(detection_id).await?;
+        let signals = sqlx::query_as::<_, AntiCheatSignal>(
+            "SELECT * FROM anticheat_signals WHERE detection_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(detection_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        let audit = sqlx::query_as::<_, AntiCheatAuditEntry>(
+            "SELECT * FROM anticheat_audit WHERE detection_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(detection_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(DetectionDetail {
+            detection,
+            signals,
+            audit,
+        })
+    }
+
+    /// Confirm or dismiss a queued detection. Confirmed detections are
+    /// flagged on the oracle in the background.
+    pub async fn review(
+        &self,
+        reviewer_id: Uuid,
+        detection_id: Uuid,
+        request: &ReviewDetectionRequest,
+    ) -> Result<Detection, AntiCheatError> {
+        let (status, severity) = match request.decision {
+            ReviewDecision::Confirm => {
+                let severity = request.severity.ok_or_else(|| {
+                    AntiCheatError::InvalidRequest(
+                        "Severity is required to confirm a detection".to_string(),
+                    )
+                })?;
+                (DetectionStatus::Confirmed, Some(severity))
+            }
+            ReviewDecision::Dismiss => (DetectionStatus::Dismissed, None),
+        };
+        let chain_status = match (status, self.chain().is_some()) {
+            (DetectionStatus::Confirmed, true) => "pending",
+            (DetectionStatus::Confirmed, false) => "off_chain",
+            _ => "not_submitted",
+        };
+        let notes = request
+            .notes
+            .as_deref()
+            .map(str::trim)
+            .filter(|n| !n.is_empty());
+
+        let mut tx = self.db_pool.begin().await?;
+        let detection = sqlx::query_as::<_, Detection>(
+            "SELECT * FROM anticheat_detections WHERE id = $1 FOR UPDATE",
+        )
+        .bind(detection_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(AntiCheatError::NotFound)?;
+        if detection.player_id == reviewer_id {
+            return Err(AntiCheatError::Forbidden(
+                "You cannot review a detection against yourself".to_string(),
+            ));
+        }
+        if detection.status != DetectionStatus::PendingReview {
+            return Err(AntiCheatError::Conflict(
+                "Detection has already been reviewed".to_string(),
+            ));
+        }
+        let detection = sqlx::query_as::<_, Detection>(
+            r#"
+            UPDATE anticheat_detections
+            SET status = $2, severity = $3, reviewer_id = $4, review_notes = $5,
+                reviewed_at = NOW(), chain_status = $6, updated_at = NOW()
+            WHERE id = $1
+            RETURNING *
+            "#,
+        )
+        .bind(detection_id)
+        .bind(status)
+        .bind(severity)
+        .bind(reviewer_id)
+        .bind(notes)
+        .bind(chain_status)
+        .fetch_one(&mut *tx)
+        .await?;
+        let action = match status {
+            DetectionStatus::Confirmed => "confirmed",
+            _ => "dismissed",
+        };
+        insert_audit(
+            &mut tx,
+            detection_id,
+            Some(reviewer_id),
+            action,
+            json!({ "severity": severity, "notes": notes }),
+        )
+        .await?;
+        tx.commit().await?;
+
+        if status == DetectionStatus::Confirmed {
+            self.spawn_submit(&detection);
+        }
+        Ok(detection)
+    }
+
+    /// Submit a confirmed detection to the oracle again after a failure, or
+    /// once the player has linked a Stellar wallet.
+    pub async fn resubmit(
+        &self,
+        admin_id: Uuid,
+        detection_id: Uuid,
+    ) -> Result<Detection, AntiCheatError> {
+        if self.chain().is_none() {
+            return Err(AntiCheatError::InvalidRequest(
+                "No anti-cheat oracle is configured".to_string(),
+            ));
+        }
+        let mut tx = self.db_pool.begin().await?;
+        let detection = sqlx::query_as::<_, Detection>(
+            r#"
+            UPDATE anticheat_detections
+            SET chain_status = 'pending', chain_error = NULL, updated_at = NOW()
+            WHERE id = $1 AND status = $2 AND chain_status IN ('failed', 'off_chain')
+            RETURNING *
+            "#,
+        )
+        .bind(detection_id)
+        .bind(DetectionStatus::Confirmed)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(detection) = detection else {
+            self.find(detection_id).await?;
+            return Err(AntiCheatError::Conflict(
+                "Only confirmed detections whose submission failed can be resubmitted".to_string(),
+            ));
+        };
+        insert_audit(
+            &mut tx,
+            detection_id,
+            Some(admin_id),
+            "resubmitted",
+            json!({}),
+        )
+        .await?;
+        tx.commit().await?;
+
+        self.spawn_submit(&detection);
+        Ok(detection)
+    }
+
+    fn spawn_submit(&self, detection: &Detection) {
+        let (Some(chain), Some(severity)) = (self.chain(), detection.severity) else {
+            return;
+        };
+        let (detection_id, player_id, match_id) =
+            (detection.id, detection.player_id, detection.match_id);
+        // Relayed calls wait for ledger inclusion; don't hold up the response.
+        tokio::spawn(async move {
+            chain
+                .submit(detection_id, player_id, match_id, severity as u32)
+                .await;
+        });
+    }
+
+    async fn find(&self, detection_id: Uuid) -> Result<Detection, AntiCheatError> {
+        sqlx::query_as::<_, Detection>("SELECT * FROM anticheat_detections WHERE id = $1")
+            .bind(detection_id)
+            .fetch_optional(&self.db_pool)
+            .await?
+            .ok_or(AntiCheatError::NotFound)
+    }
+
+    async fn ensure_participant(
+        &self,
+        match_id: Uuid,
+        player_id: Uuid,
+    ) -> Result<(), AntiCheatError> {
+        let match_record = sqlx::query_as::<_, Match>("SELECT * FROM matches WHERE id = $1")
+            .bind(match_id)
+            .fetch_optional(&self.db_pool)
+            .await?
+            .ok_or(AntiCheatError::MatchNotFound)?;
+        if match_record.player1_id != player_id && match_record.player2_id != Some(player_id) {
+            return Err(AntiCheatError::Forbidden(
+                "Player did not play in this match".to_string(),
+            ));
+        }
+        Ok(())
+    }
+
+    fn chain(&self) -> Option<ChainExecutor> {
+        Some(ChainExecutor {
+            db_pool: self.db_pool.clone(),
+            relayer: self.relayer.clone()?,
+            oracle_contract: self.oracle_contract.clone()?,
+        })
+    }
+}
+
+/// What the spawned oracle submission needs from the service.
+struct ChainExecutor {
+    db_pool: DbPool,
+    relayer: Arc<StellarRelayer>,
+    oracle_contract: String,
+}
+
+impl ChainExecutor {
+    async fn submit(&self, detection_id: Uuid, player_id: Uuid, match_id: Uuid, severity: u32) {
+        let address = sqlx::query_scalar::<_, Option<String>>(
+            "SELECT stellar_public_key FROM wallets WHERE user_id = $1",
+        )
+        .bind(player_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map(Option::flatten);
+        let address = match address {
+            Ok(Some(address)) => address,
+            Ok(None) => {
+                let reason = "Player has no Stellar wallet";
+                self.record(
+                    detection_id,
+                    "off_chain",
+                    None,
+                    Some(reason),
+                    "chain_skipped",
+                    json!({ "reason": reason }),
+                )
+                .await;
+                return;
+            }
+            Err(e) => {
+                self.fail(detection_id, &e.to_string()).await;
+                return;
+            }
+        };
+
+        let oracle_match = oracle_match_id(match_id);
+        match self
+            .relayer
+            .submit_cheat_flag(&self.oracle_contract, &address, oracle_match, severity)
+            .await
+        {
+            Ok(receipt) => {
+                info!(
+                    detection_id = %detection_id,
+                    tx_hash = %receipt.tx_hash,
+                    "Cheat flag submitted to the oracle"
+                );
+                self.record(
+                    detection_id,
+                    "submitted",
+                    Some(&receipt.tx_hash),
+                    None,
+                    "chain_submitted",
+                    json!({
+                        "tx_hash": receipt.tx_hash,
+                        "player": address,
+                        "oracle_match_id": oracle_match,
+                        "severity": severity,
+                    }),
+                )
+                .await
+            }
+            Err(e) => self.fail(detection_id, &e.to_string()).await,
+        }
+    }
+
+    async fn fail(&self, detection_id: Uuid, reason: &str) {
+        warn!(detection_id = %detection_id, error = %reason, "Cheat flag submission failed");
+        self.record(
+            detection_id,
+            "failed",
+            None,
+            Some(reason),
+            "chain_failed",
+            json!({ "error": reason }),
+        )
+        .await
+    }
+
+    async fn record(
+        &self,
+        detection_id: Uuid,
+        chain_status: &str,
+        tx_hash: Option<&str>,
+        chain_error: Option<&str>,
+        action: &str,
+        details: serde_json::Value,
+    ) {
+        let result = async {
+            let mut tx = self.db_pool.begin().await?;
+            sqlx::query(
+                r#"
+                UPDATE anticheat_detections
+                SET chain_status = $2, chain_tx = COALESCE($3, chain_tx), chain_error = $4,
+                    updated_at = NOW()
+                WHERE id = $1
+                "#,
+            )
+            .bind(detection_id)
+            .bind(chain_status)
+            .bind(tx_hash)
+            .bind(chain_error)
+            .execute(&mut *tx)
+            .await?;
+            insert_audit(&mut tx, detection_id, None, action, details).await?;
+            tx.commit().await
+        }
+        .await;
+        if let Err(e) = result {
+            error!(detection_id = %detection_id, error = %e, "Failed to record cheat flag submission");
+        }
+    }
+}
+
+fn validate_telemetry(request: &SubmitTelemetryRequest) -> Result<(), AntiCheatError> {
+    if request.metrics.is_empty() && request.integrity_violations.is_empty() {
+        return Err(AntiCheatError::InvalidRequest(
+            "Telemetry must include metrics or integrity violations".to_string(),
+        ));
+    }
+    if request.metrics.len() > MAX_METRICS {
+        return Err(AntiCheatError::InvalidRequest(format!(
+            "At most {} metrics can be sent at once",
+            MAX_METRICS
+        )));
+    }
+    let names = request
+        .metrics
+        .keys()
+        .chain(request.integrity_violations.iter());
+    for name in names {
+        if name.is_empty() || name.len() > MAX_NAME_LEN {
+            return Err(AntiCheatError::InvalidRequest(format!(
+                "Metric and check names must be between 1 and {} characters",
+                MAX_NAME_LEN
+            )));
+        }
+    }
+    if let Some((metric, _)) = request.metrics.iter().find(|(_, v)| !v.is_finite()) {
+        return Err(AntiCheatError::InvalidRequest(format!(
+            "Metric `{}` is not a finite number",
+            metric
+        )));
+    }
+    Ok(())
+}
+
+async fn insert_audit(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    detection_id: Uuid,
+    actor_id: Option<Uuid>,
+    action: &str,
+    details: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO anticheat_audit (id, detection_id, actor_id, action, details, created_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(detection_id)
+    .bind(actor_id)
+    .bind(action)
+    .bind(details)
+    .execute(&mut **tx)
+    .await
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_oracle_match_id_takes_leading_uuid_bytes() {
+        let id = Uuid::parse_str("01020304-0506-0708-090a-0b0c0d0e0f10").unwrap();
+        assert_eq!(oracle_match_id(id), 0x0102_0304_0506_0708);
+    }
+
+    #[test]
+    fn test_validate_telemetry() {
+        let request = |metrics: BTreeMap<String, f64>| SubmitTelemetryRequest {
+            match_id: Uuid::new_v4(),
+            metrics,
+            integrity_violations: Vec::new(),
+            client_version: None,
+        };
+        assert!(validate_telemetry(&request(BTreeMap::new())).is_err());
+        assert!(validate_telemetry(&request(BTreeMap::from([(
+            "headshot_ratio".to_string(),
+            0.4
+        )])))
+        .is_ok());
+        assert!(validate_telemetry(&request(BTreeMap::from([(
+            "headshot_ratio".to_string(),
+            f64::NAN
+        )])))
+        .is_err());
+        assert!(validate_telemetry(&request(BTreeMap::from([(String::new(), 1.0)]))).is_err());
+    }
+}
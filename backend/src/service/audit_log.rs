@@ -0,0 +1,423 @@
+//! The audit trail in `audit_logs`.
+//!
+//! Admin actions, mutating API calls (see [`crate::middleware::audit`]) and
+//! relayer transactions are all written here. Each entry names the actor,
+//! the resource and the reason given, and keeps only the fields the action
+//! changed, as they were before and after.
+//!
+//! Entries form a hash chain: each stores the SHA-256 of its own fields
+//! together with the hash of the entry before it, so editing, dropping or
+//! reordering an entry breaks every link after it. [`verify_chain`] walks a
+//! stretch of the chain and reports the first break.
+//!
+//! Appending takes a transaction-scoped advisory lock so two writers never
+//! link to the same predecessor. The lock is held until the surrounding
+//! transaction ends, so callers writing inside their own transaction should
+//! keep it short.
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{AuditChainReport, AuditLog, AuditLogFilter};
+use chrono::{DateTime, SecondsFormat, SubsecRound, Utc};
+use serde_json::{json, Map, Value};
+use sha2::{Digest, Sha256};
+use sqlx::PgConnection;
+use std::net::IpAddr;
+use uuid::Uuid;
+
+/// `prev_hash` of the first chained entry.
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Advisory lock serialising appends to the chain.
+const CHAIN_LOCK_KEY: i64 = 0x6175_6469_745f_6c6f;
+
+/// Fields that change on every write and say nothing about the action.
+const IGNORED_FIELDS: &[&str] = &["updated_at"];
+
+/// Most entries [`AuditLogService::verify`] checks in one call.
+pub const MAX_VERIFY_BATCH: i64 = 10_000;
+
+const AUDIT_COLUMNS: &str = "seq, id, user_id, action, resource_type, resource_id, details, \
+     before_state, after_state, reason, host(ip_address) AS ip_address, user_agent, method, \
+     path, status_code, latency_ms, request_id, prev_hash, entry_hash, created_at";
+
+/// An action to record.
+#[derive(Debug, Default)]
+pub struct AuditRecord<'a> {
+    pub actor_id: Option<Uuid>,
+    pub action: &'a str,
+    pub resource_type: &'a str,
+    pub resource_id: Option<Uuid>,
+    /// The resource before the action; `Value::Null` if it did not exist.
+    pub before: Value,
+    pub after: Value,
+    pub reason: Option<&'a str>,
+    pub details: Option<Value>,
+    /// The API call the action was made through, if any.
+    pub request: Option<RequestMeta>,
+}
+
+/// What the audit middleware knows about an API call. Bodies are never
+/// recorded.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMeta {
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub latency_ms: u32,
+    pub ip: Option<IpAddr>,
+    pub user_agent: Option<String>,
+    pub request_id: Option<String>,
+}
+
+/// Append an entry to the chain within the caller's transaction.
+pub async fn record(conn: &mut PgConnection, record: AuditRecord<'_>) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT pg_advisory_xact_lock($1)")
+        .bind(CHAIN_LOCK_KEY)
+        .execute(&mut *conn)
+        .await?;
+    let prev_hash: Option<String> = sqlx::query_scalar(
+        "SELECT entry_hash FROM audit_logs WHERE entry_hash IS NOT NULL ORDER BY seq DESC LIMIT 1",
+    )
+    .fetch_optional(&mut *conn)
+    .await?;
+    let entry = build_entry(
+        record,
+        prev_hash.unwrap_or_else(|| GENESIS_HASH.to_string()),
+        Utc::now(),
+    );
+    sqlx::query(
+        r#"
+        INSERT INTO audit_logs (
+            id, user_id, action, resource_type, resource_id, details, before_state,
+            after_state, reason, ip_address, user_agent, method, path, status_code,
+            latency_ms, request_id, prev_hash, entry_hash, created_at
+        ) VALUES (
+            $1, $2, $3, $4, $5, $6, $7, $8, $9, $10::INET, $11, $12, $13, $14, $15, $16, $17,
+            $18, $19
+        )
+        "#,
+    )
+    .bind(entry.id)
+    .bind(entry.actor_id)
+    .bind(&entry.action)
+    .bind(&entry.resource_type)
+    .bind(entry.resource_id)
+    .bind(&entry.details)
+    .bind(&entry.before_state)
+    .bind(&entry.after_state)
+    .bind(&entry.reason)
+    .bind(&entry.ip_address)
+    .bind(&entry.user_agent)
+    .bind(&entry.method)
+    .bind(&entry.path)
+    .bind(entry.status_code)
+    .bind(entry.latency_ms)
+    .bind(&entry.request_id)
+    .bind(&entry.prev_hash)
+    .bind(&entry.entry_hash)
+    .bind(entry.created_at)
+    .execute(&mut *conn)
+    .await
+    .map(|_| ())
+}
+
+/// Append an entry in a transaction of its own.
+pub async fn append(pool: &DbPool, entry: AuditRecord<'_>) -> Result<(), sqlx::Error> {
+    let mut tx = pool.begin().await?;
+    record(&mut tx, entry).await?;
+    tx.commit().await
+}
+
+/// The row to insert for `record`, hashed onto `prev_hash`.
+fn build_entry(record: AuditRecord<'_>, prev_hash: String, now: DateTime<Utc>) -> AuditLog {
+    let (before, after) = diff(&record.before, &record.after);
+    let request = record.request.unwrap_or_default();
+    let mut entry = AuditLog {
+        seq: 0,
+        id: Uuid::new_v4(),
+        actor_id: record.actor_id,
+        action: record.action.to_string(),
+        resource_type: record.resource_type.to_string(),
+        resource_id: record.resource_id,
+        details: record.details.map(|details| details.to_string()),
+        before_state: (!before.is_null()).then_some(before),
+        after_state: (!after.is_null()).then_some(after),
+        reason: record.reason.map(str::to_string),
+        ip_address: request.ip.map(|ip| ip.to_string()),
+        user_agent: request.user_agent,
+        method: (!request.method.is_empty()).then_some(request.method),
+        path: (!request.path.is_empty()).then_some(request.path),
+        status_code: (request.status != 0).then(|| request.status.min(i16::MAX as u16) as i16),
+        latency_ms: (request.status != 0).then(|| request.latency_ms.min(i32::MAX as u32) as i32),
+        request_id: request.request_id,
+        prev_hash: Some(prev_hash),
+        entry_hash: None,
+        // Postgres keeps microseconds; hash what will be read back.
+        created_at: now.trunc_subsecs(6),
+    };
+    entry.entry_hash = Some(entry_hash(&entry));
+    entry
+}
+
+/// SHA-256 over everything in the entry but its position and its own hash.
+pub fn entry_hash(entry: &AuditLog) -> String {
+    let canonical = json!([
+        entry.prev_hash,
+        entry.id,
+        entry.actor_id,
+        entry.action,
+        entry.resource_type,
+        entry.resource_id,
+        entry.details,
+        entry.before_state,
+        entry.after_state,
+        entry.reason,
+        entry.ip_address,
+        entry.user_agent,
+        entry.method,
+        entry.path,
+        entry.status_code,
+        entry.latency_ms,
+        entry.request_id,
+        entry
+            .created_at
+            .to_rfc3339_opts(SecondsFormat::Micros, true),
+    ]);
+    hex::encode(Sha256::digest(canonical.to_string().as_bytes()))
+}
+
+/// Check that `entries`, in chain order, each link to the one before
+/// (`prev_hash` for the first) and still match their hash.
+pub fn verify_chain<'a>(prev_hash: &'a str, entries: &'a [AuditLog]) -> AuditChainReport {
+    let mut report = AuditChainReport {
+        checked: 0,
+        first_seq: entries.first().map(|e| e.seq),
+        last_seq: entries.last().map(|e| e.seq),
+        broken_at: None,
+        reason: None,
+    };
+    let mut expected = prev_hash;
+    for entry in entries {
+        report.checked += 1;
+        let fault = if entry.prev_hash.as_deref() != Some(expected) {
+            Some("does not link to the entry before it")
+        } else if entry.entry_hash.as_deref() != Some(entry_hash(entry).as_str()) {
+            Some("contents do not match its hash")
+        } else {
+            None
+        };
+        if let Some(reason) = fault {
+            report.broken_at = Some(entry.seq);
+            report.reason = Some(reason.to_string());
+            break;
+        }
+        expected = entry.entry_hash.as_deref().unwrap_or_default();
+    }
+    report
+}
+
+/// The top-level fields that differ between two objects, as they were in
+/// each. Anything other than two objects is kept whole.
+pub fn diff(before: &Value, after: &Value) -> (Value, Value) {
+    let (Value::Object(old), Value::Object(new)) = (before, after) else {
+        return (before.clone(), after.clone());
+    };
+    let mut changed_before = Map::new();
+    let mut changed_after = Map::new();
+    let keys = old
+        .keys()
+        .chain(new.keys().filter(|k| !old.contains_key(*k)));
+    for key in keys {
+        if IGNORED_FIELDS.contains(&key.as_str()) {
+            continue;
+        }
+        let (was, now) = (
+            old.get(key).unwrap_or(&Value::Null),
+            new.get(key).unwrap_or(&Value::Null),
+        );
+        if was != now {
+            changed_before.insert(key.clone(), was.clone());
+            changed_after.insert(key.clone(), now.clone());
+        }
+    }
+    (Value::Object(changed_before), Value::Object(changed_after))
+}
+
+/// Writes entries outside any other transaction, and serves compliance
+/// queries over the log.
+#[derive(Clone)]
+pub struct AuditLogService {
+    db_pool: DbPool,
+}
+
+impl AuditLogService {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    pub async fn record(&self, entry: AuditRecord<'_>) -> Result<(), sqlx::Error> {
+        append(&self.db_pool, entry).await
+    }
+
+    /// Entries matching `filter`, newest first.
+    pub async fn search(&self, filter: &AuditLogFilter) -> Result<Vec<AuditLog>, ApiError> {
+        let limit = filter.limit.unwrap_or(50).clamp(1, 500);
+        let offset = filter.offset.unwrap_or(0).max(0);
+        let sql = format!(
+            r#"
+            SELECT {} FROM audit_logs
+            WHERE ($1::UUID IS NULL OR user_id = $1)
+              AND ($2::TEXT IS NULL OR action = $2)
+              AND ($3::TEXT IS NULL OR resource_type = $3)
+              AND ($4::UUID IS NULL OR resource_id = $4)
+              AND ($5::TEXT IS NULL OR request_id = $5)
+              AND ($6::TIMESTAMPTZ IS NULL OR created_at >= $6)
+              AND ($7::TIMESTAMPTZ IS NULL OR created_at < $7)
+            ORDER BY seq DESC
+            LIMIT $8 OFFSET $9
+            "#,
+            AUDIT_COLUMNS
+        );
+        sqlx::query_as::<_, AuditLog>(&sql)
+            .bind(filter.actor_id)
+            .bind(filter.action.as_deref())
+            .bind(filter.resource_type.as_deref())
+            .bind(filter.resource_id)
+            .bind(filter.request_id.as_deref())
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(limit)
+            .bind(offset)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)
+    }
+
+    /// Check up to `limit` chained entries starting at `from_seq`, or at the
+    /// start of the chain.
+    pub async fn verify(
+        &self,
+        from_seq: Option<i64>,
+        limit: i64,
+    ) -> Result<AuditChainReport, ApiError> {
+        let from_seq = from_seq.unwrap_or(0);
+        let prev_hash: Option<String> = sqlx::query_scalar(
+            r#"
+            SELECT entry_hash FROM audit_logs
+            WHERE entry_hash IS NOT NULL AND seq < $1
+            ORDER BY seq DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(from_seq)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        let sql = format!(
+            r#"
+            SELECT {} FROM audit_logs
+            WHERE entry_hash IS NOT NULL AND seq >= $1
+            ORDER BY seq ASC
+            LIMIT $2
+            "#,
+            AUDIT_COLUMNS
+        );
+        let entries = sqlx::query_as::<_, AuditLog>(&sql)
+            .bind(from_seq)
+            .bind(limit.clamp(1, MAX_VERIFY_BATCH))
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?;
+        Ok(verify_chain(
+            prev_hash.as_deref().unwrap_or(GENESIS_HASH),
+            &entries,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn chain(len: usize) -> Vec<AuditLog> {
+        let mut prev = GENESIS_HASH.to_string();
+        (0..len)
+            .map(|i| {
+                let mut entry = build_entry(
+                    AuditRecord {
+                        actor_id: Some(Uuid::new_v4()),
+                        action: "http_request",
+                        resource_type: "api",
+                        request: Some(RequestMeta {
+                            method: "POST".into(),
+                            path: format!("/api/matches/{i}"),
+                            status: 200,
+                            latency_ms: 12,
+                            ip: "2001:db8::1".parse().ok(),
+                            ..RequestMeta::default()
+                        }),
+                        ..AuditRecord::default()
+                    },
+                    prev.clone(),
+                    Utc::now(),
+                );
+                entry.seq = i as i64 + 1;
+                prev = entry.entry_hash.clone().unwrap();
+                entry
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_keeps_changed_fields_only() {
+        let before = json!({ "status": "open", "name": "Cup", "updated_at": 1, "notes": "x" });
+        let after = json!({ "status": "closed", "name": "Cup", "updated_at": 2, "extra": true });
+        let (was, now) = diff(&before, &after);
+        assert_eq!(
+            was,
+            json!({ "status": "open", "notes": "x", "extra": null })
+        );
+        assert_eq!(
+            now,
+            json!({ "status": "closed", "notes": null, "extra": true })
+        );
+
+        let (was, now) = diff(&Value::Null, &json!({ "enabled": true }));
+        assert_eq!(was, Value::Null);
+        assert_eq!(now, json!({ "enabled": true }));
+    }
+
+    #[test]
+    fn test_chain_detects_edits_and_removals() {
+        let entries = chain(4);
+        assert_eq!(entries[0].prev_hash.as_deref(), Some(GENESIS_HASH));
+        assert_eq!(entries[0].created_at.timestamp_subsec_nanos() % 1000, 0);
+        let report = verify_chain(GENESIS_HASH, &entries);
+        assert!(report.is_intact());
+        assert_eq!(
+            (report.checked, report.first_seq, report.last_seq),
+            (4, Some(1), Some(4))
+        );
+
+        // A stretch further along checks against the hash before it.
+        let prev = entries[1].entry_hash.clone().unwrap();
+        assert!(verify_chain(&prev, &entries[2..]).is_intact());
+
+        let mut edited = entries.clone();
+        edited[1].status_code = Some(500);
+        let report = verify_chain(GENESIS_HASH, &edited);
+        assert_eq!(report.broken_at, Some(2));
+        assert_eq!(report.checked, 2);
+
+        let mut removed = entries.clone();
+        removed.remove(2);
+        assert_eq!(verify_chain(GENESIS_HASH, &removed).broken_at, Some(4));
+
+        let mut rehashed = entries;
+        rehashed[0].actor_id = None;
+        rehashed[0].entry_hash = Some(entry_hash(&rehashed[0]));
+        assert_eq!(verify_chain(GENESIS_HASH, &rehashed).broken_at, Some(2));
+    }
+}
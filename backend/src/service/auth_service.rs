@@ -1,30 +1,65 @@
 use crate::api_error::ApiError;
-use crate::auth::jwt_service::{JwtService, RefreshTokenRecord, TokenPair};
+use crate::auth::device_security_policies::{DeviceSecurityPolicies, LoginContext, PolicyAction};
+use crate::auth::device_service::Device;
+use crate::auth::jwt_service::{JwtService, RefreshTokenFamily, SessionClient, TokenPair};
+use crate::auth::oauth::{ExternalProfile, OAuthCallback, OAuthProvider};
+use crate::auth::two_factor::TwoFactorService;
 use crate::db::DbPool;
 use crate::models::user::{AuthResponse, CreateUserRequest, LoginRequest, User, UserProfile};
 use bcrypt::{hash, verify, DEFAULT_COST};
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use rand::Rng;
+use std::sync::Arc;
 use tracing::{info, warn};
 use uuid::Uuid;
 
 /// Active session info returned by `GET /api/auth/sessions`.
+///
+/// A session is one refresh-token family: everything issued from a single
+/// sign-in, across rotations.
 #[derive(Debug, serde::Serialize)]
 pub struct ActiveSession {
+    pub id: String,
     pub device_id: Option<String>,
+    /// The registered device, when the session is bound to one.
+    pub device: Option<Device>,
     pub created_at: i64,
     pub last_used_at: i64,
+    /// IP address and country of the sign-in or latest refresh.
+    pub ip_address: Option<String>,
+    pub location: Option<String>,
+    /// Whether this is the session making the request.
+    pub current: bool,
 }
 
-impl From<RefreshTokenRecord> for ActiveSession {
-    fn from(r: RefreshTokenRecord) -> Self {
+impl From<RefreshTokenFamily> for ActiveSession {
+    fn from(f: RefreshTokenFamily) -> Self {
         Self {
-            device_id: r.device_id,
-            created_at: r.created_at,
-            last_used_at: r.last_used_at,
+            id: f.family_id,
+            device_id: f.device_id,
+            device: None,
+            created_at: f.created_at,
+            last_used_at: f.rotated_at,
+            ip_address: f.client.ip_address,
+            location: f.client.location,
+            current: false,
         }
     }
 }
 
+/// External account linked for social sign-in
+/// (`GET /api/auth/oauth/identities`).
+#[derive(Debug, serde::Serialize, sqlx::FromRow)]
+pub struct LinkedIdentity {
+    pub provider: String,
+    pub username: Option<String>,
+    pub display_name: Option<String>,
+    pub email: Option<String>,
+    pub avatar_url: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub last_login_at: Option<DateTime<Utc>>,
+}
+
 /// Authentication service.
 ///
 /// Owns the [`JwtService`] so it can perform refresh-token rotation,
@@ -33,17 +68,42 @@ impl From<RefreshTokenRecord> for ActiveSession {
 pub struct AuthService {
     pool: DbPool,
     jwt_service: JwtService,
+    two_factor: Option<Arc<TwoFactorService>>,
+    device_policies: Option<Arc<DeviceSecurityPolicies>>,
 }
 
 impl AuthService {
     pub fn new(pool: DbPool, jwt_service: JwtService) -> Self {
-        Self { pool, jwt_service }
+        Self {
+            pool,
+            jwt_service,
+            two_factor: None,
+            device_policies: None,
+        }
+    }
+
+    /// Ask users who enabled two-factor authentication for a code at sign-in
+    /// and before linking a Stellar wallet.
+    pub fn with_two_factor(mut self, two_factor: Arc<TwoFactorService>) -> Self {
+        self.two_factor = Some(two_factor);
+        self
+    }
+
+    /// Score password sign-ins by device, location and failed attempts, and
+    /// allow, step up or block them as the device security policies say.
+    pub fn with_device_policies(mut self, policies: Arc<DeviceSecurityPolicies>) -> Self {
+        self.device_policies = Some(policies);
+        self
     }
 
     // ── Registration & Login ─────────────────────────────────────────────────
 
     /// Register a new user and return a fresh token pair.
-    pub async fn register(&self, request: CreateUserRequest) -> Result<AuthResponse, ApiError> {
+    pub async fn register(
+        &self,
+        request: CreateUserRequest,
+        client: SessionClient,
+    ) -> Result<AuthResponse, ApiError> {
         if request.username.is_empty() || request.password.is_empty() {
             return Err(ApiError::bad_request("username and password are required"));
         }
@@ -112,7 +172,7 @@ impl AuthService {
         let roles = vec!["user".to_string()];
         let token_pair = self
             .jwt_service
-            .generate_token_pair(user_id, roles, None)
+            .generate_client_token_pair(user_id, roles, None, client)
             .await
             .map_err(|e| ApiError::internal_error(format!("Token generation failed: {}", e)))?;
 
@@ -137,7 +197,11 @@ impl AuthService {
     }
 
     /// Authenticate a user and return a fresh token pair.
-    pub async fn login(&self, request: LoginRequest) -> Result<AuthResponse, ApiError> {
+    pub async fn login(
+        &self,
+        request: LoginRequest,
+        context: LoginContext,
+    ) -> Result<AuthResponse, ApiError> {
         let user = sqlx::query_as!(
             User,
             r#"
@@ -159,6 +223,9 @@ impl AuthService {
         if !user.is_active {
             return Err(ApiError::forbidden("Account is deactivated"));
         }
+        if user.is_banned_at(Utc::now()) {
+            return Err(ApiError::forbidden("Account is banned"));
+        }
 
         let password_hash = user
             .password_hash
@@ -169,9 +236,48 @@ impl AuthService {
             .map_err(|e| ApiError::internal_error(format!("Password check failed: {}", e)))?;
 
         if !valid {
+            if let Some(policies) = &self.device_policies {
+                policies.record_failure(user.id, &context).await;
+            }
             return Err(ApiError::unauthorized("Invalid credentials"));
         }
 
+        let evaluation = match &self.device_policies {
+            Some(policies) => Some(policies.evaluate(user.id, &context).await?),
+            None => None,
+        };
+        if let (Some(policies), Some(evaluation)) = (&self.device_policies, &evaluation) {
+            match evaluation.decision.action {
+                PolicyAction::Block => {
+                    policies
+                        .record_blocked(user.id, &context, evaluation)
+                        .await?;
+                    return Err(ApiError::forbidden("Sign-in blocked for security reasons"));
+                }
+                // Enrolled users are asked for their code below; others have
+                // no second factor to step up with but a passkey.
+                PolicyAction::RequireTwoFactor if !self.has_two_factor(user.id).await? => {
+                    return Err(ApiError::forbidden(
+                        "Additional verification required: sign in with a passkey",
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        if let Some(two_factor) = &self.two_factor {
+            if let Err(e) = two_factor
+                .verify_login(user.id, request.totp_code.as_deref())
+                .await
+            {
+                // A missing code is the first step of the sign-in, not a failure.
+                if let (Some(policies), Some(_)) = (&self.device_policies, &request.totp_code) {
+                    policies.record_failure(user.id, &context).await;
+                }
+                return Err(e);
+            }
+        }
+
         sqlx::query!(
             "UPDATE users SET last_login_at = $1 WHERE id = $2",
             Utc::now(),
@@ -181,31 +287,477 @@ impl AuthService {
         .await
         .map_err(ApiError::database_error)?;
 
-        let roles = vec!["user".to_string()];
+        // The session is bound to the device the policies registered.
+        let device = match (&self.device_policies, &evaluation) {
+            (Some(policies), Some(evaluation)) => policies
+                .record_success(user.id, &context, evaluation)
+                .await?
+                .map(|device| device.id.to_string()),
+            _ => None,
+        };
+        let client = SessionClient {
+            ip_address: Some(context.device.ip_address),
+            location: context.location.country,
+        };
+
+        let roles = self.session_roles(user.id).await?;
         let token_pair = self
             .jwt_service
-            .generate_token_pair(user.id, roles, None)
+            .generate_client_token_pair(user.id, roles, device, client)
             .await
             .map_err(|e| ApiError::internal_error(format!("Token generation failed: {}", e)))?;
 
         info!(user_id = %user.id, "User logged in");
 
-        Ok(AuthResponse {
-            token: token_pair.access_token,
-            refresh_token: token_pair.refresh_token,
-            user: UserProfile {
-                id: user.id,
-                username: user.username,
-                email: user.email,
-                display_name: user.display_name,
-                avatar_url: user.avatar_url,
-                is_verified: user.is_verified,
-                created_at: user.created_at,
-                skill_score: None,
-                fair_play_score: None,
-                is_bad_actor: None,
-            },
-        })
+        Ok(auth_response(user, token_pair))
+    }
+
+    /// Sign in with a Stellar account proven through SEP-10.
+    ///
+    /// With `link_to`, the account is first linked to that (already signed-in)
+    /// user, replacing any address linked before. Otherwise the user the
+    /// account was linked to is signed in. Either way `totp_code` is checked
+    /// for users with two-factor authentication.
+    pub async fn stellar_login(
+        &self,
+        account: &str,
+        link_to: Option<Uuid>,
+        totp_code: Option<&str>,
+        client: SessionClient,
+    ) -> Result<AuthResponse, ApiError> {
+        if let Some(user_id) = link_to {
+            if let Some(two_factor) = &self.two_factor {
+                two_factor.require(user_id, totp_code).await?;
+            }
+            sqlx::query!(
+                "UPDATE users SET stellar_public_key = $1, updated_at = $2 WHERE id = $3",
+                account,
+                Utc::now(),
+                user_id,
+            )
+            .execute(&self.pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db) if db.is_unique_violation() => {
+                    ApiError::conflict("Stellar account is already linked to another user")
+                }
+                e => ApiError::database_error(e),
+            })?;
+            info!(user_id = %user_id, account = %account, "Stellar account linked");
+        }
+
+        let user = sqlx::query_as!(
+            User,
+            r#"
+            SELECT id, username, email, phone_number, display_name, avatar_url, bio,
+                   country_code, is_verified, is_active, role, created_at, updated_at,
+                   last_login_at, password_hash, profile_image_url, reputation_score,
+                   stellar_account_id, stellar_public_key, total_earnings, is_banned,
+                   banned_until, device_fingerprint
+            FROM users
+            WHERE stellar_public_key = $1
+            "#,
+            account,
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("No account is linked to this Stellar address"))?;
+
+        if !user.is_active {
+            return Err(ApiError::forbidden("Account is deactivated"));
+        }
+        if user.is_banned_at(Utc::now()) {
+            return Err(ApiError::forbidden("Account is banned"));
+        }
+
+        if let (Some(two_factor), None) = (&self.two_factor, link_to) {
+            two_factor.verify_login(user.id, totp_code).await?;
+        }
+
+        sqlx::query!(
+            "UPDATE users SET last_login_at = $1 WHERE id = $2",
+            Utc::now(),
+            user.id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let roles = self.session_roles(user.id).await?;
+        let token_pair = self
+            .jwt_service
+            .generate_client_token_pair(user.id, roles, None, client)
+            .await
+            .map_err(|e| ApiError::internal_error(format!("Token generation failed: {}", e)))?;
+
+        info!(user_id = %user.id, account = %account, "User logged in with Stellar wallet");
+
+        Ok(auth_response(user, token_pair))
+    }
+
+    /// Sign in a user whose passkey assertion was verified on `device_id`.
+    ///
+    /// The assertion carried user verification, so no TOTP code is asked
+    /// for. The session is bound to the device.
+    pub async fn passkey_login(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+        client: SessionClient,
+    ) -> Result<AuthResponse, ApiError> {
+        let user = self.get_user(user_id).await?;
+
+        if !user.is_active {
+            return Err(ApiError::forbidden("Account is deactivated"));
+        }
+        if user.is_banned_at(Utc::now()) {
+            return Err(ApiError::forbidden("Account is banned"));
+        }
+
+        sqlx::query!(
+            "UPDATE users SET last_login_at = $1 WHERE id = $2",
+            Utc::now(),
+            user.id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let roles = self.session_roles(user.id).await?;
+        let token_pair = self
+            .jwt_service
+            .generate_client_token_pair(user.id, roles, Some(device_id.to_string()), client)
+            .await
+            .map_err(|e| ApiError::internal_error(format!("Token generation failed: {}", e)))?;
+
+        info!(user_id = %user.id, device_id = %device_id, "User logged in with passkey");
+
+        Ok(auth_response(user, token_pair))
+    }
+
+    /// Sign in with an external account from a completed OAuth flow.
+    ///
+    /// A flow started by a signed-in user links the account to them.
+    /// Otherwise the user the account is linked to is signed in, or a new user
+    /// is created. Accounts are never merged by email: when the address
+    /// already belongs to a user, they must sign in and link the account
+    /// themselves.
+    pub async fn oauth_login(
+        &self,
+        callback: OAuthCallback,
+        totp_code: Option<&str>,
+        client: SessionClient,
+    ) -> Result<AuthResponse, ApiError> {
+        let profile = &callback.profile;
+        let linked: Option<Uuid> = sqlx::query_scalar(
+            "SELECT user_id FROM user_identities WHERE provider = $1 AND subject = $2",
+        )
+        .bind(profile.provider.as_str())
+        .bind(&profile.subject)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let user_id = match (callback.link_to, linked) {
+            (Some(user_id), Some(owner)) if owner != user_id => {
+                return Err(ApiError::conflict(format!(
+                    "This {} account is linked to another user",
+                    profile.provider
+                )));
+            }
+            (Some(user_id), Some(_)) => user_id,
+            (Some(user_id), None) => {
+                if let Some(two_factor) = &self.two_factor {
+                    two_factor.require(user_id, totp_code).await?;
+                }
+                self.link_identity(user_id, profile).await?;
+                info!(user_id = %user_id, provider = %profile.provider, "External account linked");
+                user_id
+            }
+            (None, Some(owner)) => {
+                if let Some(two_factor) = &self.two_factor {
+                    two_factor.verify_login(owner, totp_code).await?;
+                }
+                owner
+            }
+            (None, None) => self.create_oauth_user(profile).await?,
+        };
+
+        let user = self.get_user(user_id).await?;
+        if !user.is_active {
+            return Err(ApiError::forbidden("Account is deactivated"));
+        }
+        if user.is_banned_at(Utc::now()) {
+            return Err(ApiError::forbidden("Account is banned"));
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE user_identities
+            SET email = $1, username = $2, display_name = $3, avatar_url = $4, last_login_at = $5
+            WHERE provider = $6 AND subject = $7
+            "#,
+        )
+        .bind(&profile.email)
+        .bind(&profile.username)
+        .bind(&profile.display_name)
+        .bind(&profile.avatar_url)
+        .bind(Utc::now())
+        .bind(profile.provider.as_str())
+        .bind(&profile.subject)
+        .execute(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        sqlx::query!(
+            "UPDATE users SET last_login_at = $1 WHERE id = $2",
+            Utc::now(),
+            user.id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let roles = self.session_roles(user.id).await?;
+        let token_pair = self
+            .jwt_service
+            .generate_client_token_pair(user.id, roles, None, client)
+            .await
+            .map_err(|e| ApiError::internal_error(format!("Token generation failed: {}", e)))?;
+
+        info!(
+            user_id = %user.id,
+            provider = %profile.provider,
+            "User logged in with external account"
+        );
+
+        Ok(auth_response(user, token_pair))
+    }
+
+    /// External accounts linked to a user.
+    pub async fn linked_identities(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<LinkedIdentity>, ApiError> {
+        sqlx::query_as::<_, LinkedIdentity>(
+            r#"
+            SELECT provider, username, display_name, email, avatar_url, created_at, last_login_at
+            FROM user_identities
+            WHERE user_id = $1
+            ORDER BY created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    /// Unlink an external account, unless it is the user's only way to sign
+    /// in.
+    pub async fn unlink_identity(
+        &self,
+        user_id: Uuid,
+        provider: OAuthProvider,
+    ) -> Result<(), ApiError> {
+        let other_sign_in: bool = sqlx::query_scalar(
+            r#"
+            SELECT u.password_hash IS NOT NULL
+                OR u.stellar_public_key IS NOT NULL
+                OR EXISTS (
+                    SELECT 1 FROM user_identities i
+                    WHERE i.user_id = u.id AND i.provider <> $2
+                )
+                OR EXISTS (SELECT 1 FROM device_passkeys p WHERE p.user_id = u.id)
+            FROM users u
+            WHERE u.id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(provider.as_str())
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+        if !other_sign_in {
+            return Err(ApiError::conflict(format!(
+                "Set a password or link another sign-in method before unlinking {}",
+                provider
+            )));
+        }
+
+        let removed =
+            sqlx::query("DELETE FROM user_identities WHERE user_id = $1 AND provider = $2")
+                .bind(user_id)
+                .bind(provider.as_str())
+                .execute(&self.pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        if removed.rows_affected() == 0 {
+            return Err(ApiError::not_found("No such linked account"));
+        }
+
+        info!(user_id = %user_id, provider = %provider, "External account unlinked");
+        Ok(())
+    }
+
+    async fn link_identity(
+        &self,
+        user_id: Uuid,
+        profile: &ExternalProfile,
+    ) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            INSERT INTO user_identities
+                (user_id, provider, subject, email, username, display_name, avatar_url)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            "#,
+        )
+        .bind(user_id)
+        .bind(profile.provider.as_str())
+        .bind(&profile.subject)
+        .bind(&profile.email)
+        .bind(&profile.username)
+        .bind(&profile.display_name)
+        .bind(&profile.avatar_url)
+        .execute(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db) if db.is_unique_violation() => {
+                if db.constraint() == Some("user_identities_user_id_provider_key") {
+                    ApiError::conflict(format!(
+                        "A {} account is already linked; unlink it first",
+                        profile.provider
+                    ))
+                } else {
+                    ApiError::conflict(format!(
+                        "This {} account is linked to another user",
+                        profile.provider
+                    ))
+                }
+            }
+            e => ApiError::database_error(e),
+        })?;
+        Ok(())
+    }
+
+    /// Sign up with an external account. Only an email the provider verified
+    /// is kept, so an unconfirmed address cannot claim someone else's.
+    async fn create_oauth_user(&self, profile: &ExternalProfile) -> Result<Uuid, ApiError> {
+        let email = profile.email.as_ref().filter(|_| profile.email_verified);
+        if let Some(email) = email {
+            let taken: Option<Uuid> = sqlx::query_scalar("SELECT id FROM users WHERE email = $1")
+                .bind(email)
+                .fetch_optional(&self.pool)
+                .await
+                .map_err(ApiError::database_error)?;
+            if taken.is_some() {
+                return Err(ApiError::conflict(format!(
+                    "An account with this email already exists; \
+                     sign in and link {} from your account settings",
+                    profile.provider
+                )));
+            }
+        }
+
+        let base = username_base(profile);
+        let user_id = Uuid::new_v4();
+        for attempt in 0..USERNAME_ATTEMPTS {
+            let username = if attempt == 0 {
+                base.clone()
+            } else {
+                format!("{}{}", base, rand::thread_rng().gen_range(1000..10000))
+            };
+            let now = Utc::now();
+
+            let mut tx = self.pool.begin().await.map_err(ApiError::database_error)?;
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO users (
+                    id, username, email, display_name, avatar_url,
+                    is_active, is_verified, role, created_at, updated_at
+                )
+                VALUES ($1, $2, $3, $4, $5, true, $6, 'user', $7, $7)
+                ON CONFLICT (username) DO NOTHING
+                "#,
+            )
+            .bind(user_id)
+            .bind(&username)
+            .bind(email)
+            .bind(&profile.display_name)
+            .bind(&profile.avatar_url)
+            .bind(email.is_some())
+            .bind(now)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+            if inserted.rows_affected() == 0 {
+                continue;
+            }
+            sqlx::query(
+                r#"
+                INSERT INTO user_identities
+                    (user_id, provider, subject, email, username, display_name, avatar_url)
+                VALUES ($1, $2, $3, $4, $5, $6, $7)
+                "#,
+            )
+            .bind(user_id)
+            .bind(profile.provider.as_str())
+            .bind(&profile.subject)
+            .bind(&profile.email)
+            .bind(&profile.username)
+            .bind(&profile.display_name)
+            .bind(&profile.avatar_url)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db) if db.is_unique_violation() => {
+                    ApiError::conflict("Sign-up already in progress for this account")
+                }
+                e => ApiError::database_error(e),
+            })?;
+            tx.commit().await.map_err(ApiError::database_error)?;
+
+            info!(
+                user_id = %user_id,
+                username = %username,
+                provider = %profile.provider,
+                "User registered with external account"
+            );
+            return Ok(user_id);
+        }
+
+        Err(ApiError::conflict(
+            "Could not find a free username; register with a password instead",
+        ))
+    }
+
+    /// Whether the user enabled two-factor authentication.
+    async fn has_two_factor(&self, user_id: Uuid) -> Result<bool, ApiError> {
+        match &self.two_factor {
+            Some(two_factor) => Ok(two_factor.status(user_id).await?.enabled),
+            None => Ok(false),
+        }
+    }
+
+    /// Roles for a new session. A linked Twitch account unlocks streamer
+    /// features.
+    async fn session_roles(&self, user_id: Uuid) -> Result<Vec<String>, ApiError> {
+        let streamer: bool = sqlx::query_scalar(
+            "SELECT EXISTS (
+                SELECT 1 FROM user_identities WHERE user_id = $1 AND provider = 'twitch'
+            )",
+        )
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let mut roles = vec!["user".to_string()];
+        if streamer {
+            roles.push("streamer".to_string());
+        }
+        Ok(roles)
     }
 
     // ── Token operations ─────────────────────────────────────────────────────
@@ -222,13 +774,17 @@ impl AuthService {
             .map_err(|e| ApiError::internal_error(format!("Invalid user ID in token: {}", e)))
     }
 
-    /// Rotate refresh token: invalidate the old one, issue a fresh pair.
+    /// Rotate refresh token: retire the old one, issue a fresh pair.
     ///
     /// Replaying the old refresh token after a successful rotation returns
-    /// 401 because the Redis record has been deleted.
-    pub async fn refresh_token(&self, refresh_token: &str) -> Result<TokenPair, ApiError> {
+    /// 401 and signs out every device holding a token of the same family.
+    pub async fn refresh_token(
+        &self,
+        refresh_token: &str,
+        client: SessionClient,
+    ) -> Result<TokenPair, ApiError> {
         self.jwt_service
-            .refresh_token(refresh_token)
+            .refresh_client_token(refresh_token, client)
             .await
             .map_err(|e| ApiError::unauthorized(format!("Token refresh failed: {}", e)))
     }
@@ -248,16 +804,127 @@ impl AuthService {
 
     /// Return the list of active refresh-token sessions for a user.
     ///
-    /// Each entry carries device info and the timestamp the token was last
-    /// used, so the user can identify and revoke unfamiliar sessions.
-    pub async fn get_sessions(&self, user_id: Uuid) -> Result<Vec<ActiveSession>, ApiError> {
-        let records = self
+    /// Each entry carries device info, where and when the token was last
+    /// used, so the user can identify and revoke unfamiliar sessions. The
+    /// session issued with the access session `current` is marked as such.
+    pub async fn get_sessions(
+        &self,
+        user_id: Uuid,
+        current: Option<&str>,
+    ) -> Result<Vec<ActiveSession>, ApiError> {
+        let families = self.token_families(user_id).await?;
+
+        Ok(families
+            .into_iter()
+            .map(|family| {
+                let is_current = current == Some(family.session_id.as_str());
+                ActiveSession {
+                    current: is_current,
+                    ..ActiveSession::from(family)
+                }
+            })
+            .collect())
+    }
+
+    /// Revoke one of the user's own sessions. Sessions of other users are
+    /// reported as not found.
+    pub async fn revoke_user_session(
+        &self,
+        user_id: Uuid,
+        session_id: &str,
+    ) -> Result<(), ApiError> {
+        let family = self
             .jwt_service
-            .get_active_refresh_tokens(user_id)
+            .get_token_family(session_id)
             .await
             .map_err(|e| ApiError::internal_error(format!("Session fetch failed: {}", e)))?;
+        if !family.is_some_and(|f| f.user_id == user_id) {
+            return Err(ApiError::not_found("Session not found"));
+        }
+
+        self.revoke_session(session_id).await?;
+        info!(user_id = %user_id, session_id = %session_id, "Session revoked");
+        Ok(())
+    }
+
+    /// Revoke every session of the user except the one issued with the access
+    /// session `current`.
+    pub async fn revoke_other_sessions(
+        &self,
+        user_id: Uuid,
+        current: &str,
+    ) -> Result<u32, ApiError> {
+        let families = self.token_families(user_id).await?;
+        let count = self
+            .revoke_families(families.iter().filter(|f| f.session_id != current))
+            .await?;
+
+        info!(user_id = %user_id, count, "Other sessions revoked");
+        Ok(count)
+    }
+
+    /// Revoke the sessions bound to one of the user's devices, when the
+    /// device is removed.
+    pub async fn revoke_device_sessions(
+        &self,
+        user_id: Uuid,
+        device_id: Uuid,
+    ) -> Result<u32, ApiError> {
+        let device_id = device_id.to_string();
+        let families = self.token_families(user_id).await?;
+        let count = self
+            .revoke_families(
+                families
+                    .iter()
+                    .filter(|f| f.device_id.as_deref() == Some(device_id.as_str())),
+            )
+            .await?;
+
+        info!(user_id = %user_id, device_id = %device_id, count, "Device sessions revoked");
+        Ok(count)
+    }
+
+    async fn token_families(&self, user_id: Uuid) -> Result<Vec<RefreshTokenFamily>, ApiError> {
+        self.jwt_service
+            .get_token_families(user_id)
+            .await
+            .map_err(|e| ApiError::internal_error(format!("Session fetch failed: {}", e)))
+    }
 
-        Ok(records.into_iter().map(ActiveSession::from).collect())
+    /// Revoke token families, returning how many were still live.
+    async fn revoke_families(
+        &self,
+        families: impl Iterator<Item = &RefreshTokenFamily>,
+    ) -> Result<u32, ApiError> {
+        let mut count = 0;
+        for family in families {
+            let revoked = self
+                .jwt_service
+                .revoke_token_family(&family.family_id)
+                .await
+                .map_err(|e| {
+                    ApiError::internal_error(format!("Session revocation failed: {}", e))
+                })?;
+            if revoked {
+                count += 1;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Revoke one session (refresh-token family) of any user. Used by the
+    /// admin session-revocation endpoint.
+    pub async fn revoke_session(&self, session_id: &str) -> Result<(), ApiError> {
+        let revoked = self
+            .jwt_service
+            .revoke_token_family(session_id)
+            .await
+            .map_err(|e| ApiError::internal_error(format!("Session revocation failed: {}", e)))?;
+
+        if !revoked {
+            return Err(ApiError::not_found("Session not found"));
+        }
+        Ok(())
     }
 
     /// Invalidate **all** refresh tokens and access-token sessions for a user.
@@ -346,3 +1013,90 @@ impl AuthService {
         Ok(())
     }
 }
+
+/// Usernames tried when signing up with an external account.
+const USERNAME_ATTEMPTS: usize = 5;
+const USERNAME_MAX_LEN: usize = 40;
+
+/// Username for a new user, from the external account's handle, name or
+/// email. Suffixed with digits when taken.
+fn username_base(profile: &ExternalProfile) -> String {
+    let source = profile
+        .username
+        .as_deref()
+        .or(profile.display_name.as_deref())
+        .or(profile
+            .email
+            .as_deref()
+            .and_then(|email| email.split('@').next()))
+        .unwrap_or_default();
+    let username: String = source
+        .chars()
+        .filter_map(|c| match c {
+            'a'..='z' | '0'..='9' | '_' => Some(c),
+            'A'..='Z' => Some(c.to_ascii_lowercase()),
+            ' ' | '.' | '-' => Some('_'),
+            _ => None,
+        })
+        .take(USERNAME_MAX_LEN)
+        .collect();
+    if username.len() < 3 {
+        format!("player_{}", username)
+    } else {
+        username
+    }
+}
+
+fn auth_response(user: User, token_pair: TokenPair) -> AuthResponse {
+    AuthResponse {
+        token: token_pair.access_token,
+        refresh_token: token_pair.refresh_token,
+        user: UserProfile {
+            id: user.id,
+            username: user.username,
+            email: user.email,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
+            is_verified: user.is_verified,
+            created_at: user.created_at,
+            skill_score: None,
+            fair_play_score: None,
+            is_bad_actor: None,
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile(
+        username: Option<&str>,
+        display_name: Option<&str>,
+        email: Option<&str>,
+    ) -> ExternalProfile {
+        ExternalProfile {
+            provider: OAuthProvider::Google,
+            subject: "1".to_string(),
+            email: email.map(str::to_string),
+            email_verified: true,
+            username: username.map(str::to_string),
+            display_name: display_name.map(str::to_string),
+            avatar_url: None,
+        }
+    }
+
+    #[test]
+    fn test_username_base() {
+        let base = |username, display_name, email| {
+            username_base(&profile(username, display_name, email))
+        };
+        assert_eq!(base(Some("TwitchDev"), None, None), "twitchdev");
+        assert_eq!(base(None, Some("Ada Lovelace"), None), "ada_lovelace");
+        assert_eq!(base(None, None, Some("a.b@example.com")), "a_b");
+        assert_eq!(base(None, None, Some("x@example.com")), "player_x");
+        assert_eq!(base(None, Some("日本"), None), "player_");
+        let long = "a".repeat(60);
+        assert_eq!(base(Some(&long), None, None).len(), USERNAME_MAX_LEN);
+    }
+}
@@ -0,0 +1,743 @@
+//! Tournament bracket generation and advancement.
+//!
+//! Players are seeded by their reputation (`users.skill_score`, mirrored from
+//! the on-chain reputation contract) and placed into a [`Bracket`] for the
+//! tournament's format. The bracket is persisted as rounds and slots; every
+//! result is applied by loading the bracket, advancing it in memory and writing
+//! back the slots that changed, all under a lock on the tournament row.
+//!
+//! Teams enter team tournaments through their captain, so the slots of team
+//! events hold captains; a team is seeded by the average reputation of its
+//! locked lineup.
+
+pub mod planner;
+
+pub use planner::{Bracket, BracketError, SeededPlayer, Slot, SlotLink};
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{
+    BracketCheckIn, BracketRound, BracketRoundView, BracketSide, BracketSlot, BracketTeam,
+    BracketType, BracketView, CheckInResponse, TournamentBracket,
+};
+use chrono::Utc;
+use sqlx::{Postgres, Row, Transaction};
+use std::collections::HashMap;
+use tracing::info;
+use uuid::Uuid;
+
+type RoundKey = (BracketSide, i32);
+
+pub struct BracketEngine {
+    db_pool: DbPool,
+}
+
+impl BracketEngine {
+    pub fn new(db_pool: DbPool) -> Self {
+        Self { db_pool }
+    }
+
+    /// Seed the tournament's active participants and generate its bracket. The
+    /// tournament must have closed registration and not have a bracket yet.
+    pub async fn generate(
+        &self,
+        tournament_id: Uuid,
+        format: BracketType,
+    ) -> Result<BracketView, ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+
+        let status: String =
+            sqlx::query_scalar("SELECT status::TEXT FROM tournaments WHERE id = $1 FOR UPDATE")
+                .bind(tournament_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(ApiError::database_error)?
+                .ok_or_else(|| ApiError::not_found("Tournament not found"))?;
+        if status != "registration_closed" && status != "in_progress" {
+            return Err(ApiError::bad_request(
+                "Tournament must be in RegistrationClosed or InProgress status to generate a bracket",
+            ));
+        }
+
+        let exists: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM tournament_brackets WHERE tournament_id = $1)",
+        )
+        .bind(tournament_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        if exists {
+            return Err(ApiError::conflict("Bracket already generated"));
+        }
+
+        // Reputation first, then fair play, then whoever registered earliest.
+        // Team entries rank by their lineup's average reputation.
+        let rows = sqlx::query(
+            r#"
+            SELECT tp.id, tp.user_id
+            FROM tournament_participants tp
+            JOIN users u ON u.id = tp.user_id
+            WHERE tp.tournament_id = $1
+              AND (tp.status = 'active' OR tp.status = 'paid')
+            ORDER BY COALESCE(
+                         (SELECT AVG(COALESCE(lu.skill_score, 0))
+                          FROM tournament_team_lineups l
+                          JOIN users lu ON lu.id = l.user_id
+                          WHERE l.tournament_id = tp.tournament_id AND l.team_id = tp.team_id),
+                         u.skill_score,
+                         0
+                     ) DESC,
+                     COALESCE(u.fair_play_score, 0) DESC,
+                     tp.registered_at ASC
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let mut players = Vec::with_capacity(rows.len());
+        for (idx, row) in rows.iter().enumerate() {
+            let participant_id: Uuid = row.try_get("id").map_err(ApiError::database_error)?;
+            let user_id: Uuid = row.try_get("user_id").map_err(ApiError::database_error)?;
+            let seed = (idx + 1) as i32;
+            sqlx::query(
+                "UPDATE tournament_participants SET seed_number = $1, status = 'active' WHERE id = $2",
+            )
+            .bind(seed)
+            .bind(participant_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+            players.push(SeededPlayer { user_id, seed });
+        }
+
+        let mut bracket = Bracket::generate(format.clone(), &players)
+            .map_err(|e| ApiError::bad_request(e.to_string()))?;
+
+        sqlx::query(
+            "INSERT INTO tournament_brackets (tournament_id, format, player_count) VALUES ($1, $2, $3)",
+        )
+        .bind(tournament_id)
+        .bind(format.to_string())
+        .bind(players.len() as i32)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let mut rounds = HashMap::new();
+        save(&mut tx, tournament_id, &mut bracket, &mut rounds).await?;
+
+        sqlx::query("UPDATE tournaments SET status = 'in_progress', updated_at = $2 WHERE id = $1")
+            .bind(tournament_id)
+            .bind(Utc::now())
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(
+            tournament_id = %tournament_id,
+            format = %format,
+            players = players.len(),
+            "Tournament bracket generated"
+        );
+        self.get_bracket(tournament_id).await
+    }
+
+    /// Record the winner of a ready slot and advance the bracket. Returns
+    /// whether the bracket is complete, in which case final ranks are written to
+    /// the participants.
+    pub async fn record_result(
+        &self,
+        tournament_id: Uuid,
+        slot_id: Uuid,
+        winner_id: Uuid,
+    ) -> Result<bool, ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        lock_tournament(&mut tx, tournament_id).await?;
+
+        let (mut bracket, mut rounds) = load(&mut tx, tournament_id)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Bracket not found"))?;
+        let complete = bracket
+            .record_result(slot_id, winner_id)
+            .map_err(|e| match e {
+                BracketError::SlotNotFound => ApiError::not_found(e.to_string()),
+                BracketError::SlotNotReady => ApiError::conflict(e.to_string()),
+                _ => ApiError::bad_request(e.to_string()),
+            })?;
+        save(&mut tx, tournament_id, &mut bracket, &mut rounds).await?;
+
+        if complete {
+            finish(&mut tx, tournament_id, &bracket).await?;
+        }
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        if complete {
+            info!(tournament_id = %tournament_id, "Tournament bracket completed");
+        }
+        Ok(complete)
+    }
+
+    /// Apply a finished tournament match to the bracket slot between the two
+    /// players. Tournaments without a bracket, or matches that do not map to a
+    /// ready slot, are ignored.
+    pub async fn on_match_result(
+        &self,
+        tournament_id: Uuid,
+        player_a: Uuid,
+        player_b: Uuid,
+        winner_id: Uuid,
+    ) -> Result<Option<bool>, ApiError> {
+        let slot_id: Option<Uuid> = sqlx::query_scalar(
+            r#"
+            SELECT id FROM bracket_slots
+            WHERE tournament_id = $1
+              AND status = 'ready'
+              AND ((player1_id = $2 AND player2_id = $3) OR (player1_id = $3 AND player2_id = $2))
+            LIMIT 1
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(player_a)
+        .bind(player_b)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        match slot_id {
+            Some(slot_id) => self
+                .record_result(tournament_id, slot_id, winner_id)
+                .await
+                .map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Withdraw players who missed check-in for a ready slot. They are marked
+    /// disqualified and forfeit this and every later match. Returns whether the
+    /// bracket is complete.
+    pub async fn forfeit(
+        &self,
+        tournament_id: Uuid,
+        slot_id: Uuid,
+        no_shows: &[Uuid],
+    ) -> Result<bool, ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        lock_tournament(&mut tx, tournament_id).await?;
+
+        let (mut bracket, mut rounds) = load(&mut tx, tournament_id)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Bracket not found"))?;
+        let complete = bracket.forfeit(slot_id, no_shows).map_err(|e| match e {
+            BracketError::SlotNotFound => ApiError::not_found(e.to_string()),
+            BracketError::SlotNotReady => ApiError::conflict(e.to_string()),
+            _ => ApiError::bad_request(e.to_string()),
+        })?;
+
+        sqlx::query(
+            "UPDATE tournament_participants SET status = 'disqualified' WHERE tournament_id = $1 AND user_id = ANY($2)",
+        )
+        .bind(tournament_id)
+        .bind(no_shows)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        save(&mut tx, tournament_id, &mut bracket, &mut rounds).await?;
+        if complete {
+            finish(&mut tx, tournament_id, &bracket).await?;
+        }
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(
+            tournament_id = %tournament_id,
+            slot_id = %slot_id,
+            no_shows = no_shows.len(),
+            "Bracket slot forfeited"
+        );
+        Ok(complete)
+    }
+
+    /// Check `user_id` in for their slot whose check-in window is open.
+    pub async fn check_in(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<CheckInResponse, ApiError> {
+        let slot = sqlx::query_as::<_, BracketSlot>(
+            r#"
+            SELECT * FROM bracket_slots
+            WHERE tournament_id = $1
+              AND status = 'ready'
+              AND (player1_id = $2 OR player2_id = $2)
+              AND checkin_opens_at <= NOW()
+              AND checkin_deadline > NOW()
+            ORDER BY checkin_deadline
+            LIMIT 1
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| {
+            ApiError::bad_request("No check-in window is open for you in this tournament")
+        })?;
+
+        let check_in = sqlx::query_as::<_, BracketCheckIn>(
+            r#"
+            INSERT INTO bracket_checkins (slot_id, user_id, tournament_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (slot_id, user_id) DO UPDATE SET slot_id = EXCLUDED.slot_id
+            RETURNING *
+            "#,
+        )
+        .bind(slot.id)
+        .bind(user_id)
+        .bind(tournament_id)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let opponent_id = [slot.player1_id, slot.player2_id]
+            .into_iter()
+            .flatten()
+            .find(|id| *id != user_id);
+        let opponent_checked_in = match opponent_id {
+            Some(opponent) => sqlx::query_scalar(
+                "SELECT EXISTS(SELECT 1 FROM bracket_checkins WHERE slot_id = $1 AND user_id = $2)",
+            )
+            .bind(slot.id)
+            .bind(opponent)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?,
+            None => false,
+        };
+
+        Ok(CheckInResponse {
+            check_in,
+            checkin_deadline: slot.checkin_deadline,
+            opponent_id,
+            opponent_checked_in,
+        })
+    }
+
+    /// Open check-in for ready slots in the earliest unfinished round of each
+    /// bracket side, so a round's matches are checked in together. Returns the
+    /// slots whose window was opened.
+    pub async fn open_checkin_windows(
+        &self,
+        window: chrono::Duration,
+    ) -> Result<Vec<BracketSlot>, ApiError> {
+        let now = Utc::now();
+        sqlx::query_as::<_, BracketSlot>(
+            r#"
+            UPDATE bracket_slots s
+            SET checkin_opens_at = $1, checkin_deadline = $2, updated_at = $1
+            FROM bracket_rounds r, tournaments t
+            WHERE s.round_id = r.id
+              AND t.id = s.tournament_id
+              AND t.status = 'in_progress'
+              AND s.status = 'ready'
+              AND s.checkin_deadline IS NULL
+              AND r.ordinal = (
+                  SELECT MIN(r2.ordinal) FROM bracket_rounds r2
+                  WHERE r2.tournament_id = r.tournament_id
+                    AND r2.side = r.side
+                    AND r2.status <> 'completed'
+              )
+            RETURNING s.*
+            "#,
+        )
+        .bind(now)
+        .bind(now + window)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    /// Ready slots whose check-in deadline has passed with at least one player
+    /// missing, paired with the players who did not check in.
+    pub async fn expired_checkins(&self) -> Result<Vec<(BracketSlot, Vec<Uuid>)>, ApiError> {
+        let slots = sqlx::query_as::<_, BracketSlot>(
+            r#"
+            SELECT s.* FROM bracket_slots s
+            WHERE s.status = 'ready'
+              AND s.checkin_deadline <= NOW()
+              AND (
+                  SELECT COUNT(*) FROM bracket_checkins c WHERE c.slot_id = s.id
+              ) < 2
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let mut expired = Vec::with_capacity(slots.len());
+        for slot in slots {
+            let checked_in: Vec<Uuid> =
+                sqlx::query_scalar("SELECT user_id FROM bracket_checkins WHERE slot_id = $1")
+                    .bind(slot.id)
+                    .fetch_all(&self.db_pool)
+                    .await
+                    .map_err(ApiError::database_error)?;
+            let no_shows = [slot.player1_id, slot.player2_id]
+                .into_iter()
+                .flatten()
+                .filter(|id| !checked_in.contains(id))
+                .collect();
+            expired.push((slot, no_shows));
+        }
+        Ok(expired)
+    }
+
+    pub async fn get_bracket(&self, tournament_id: Uuid) -> Result<BracketView, ApiError> {
+        let bracket = sqlx::query_as::<_, TournamentBracket>(
+            "SELECT * FROM tournament_brackets WHERE tournament_id = $1",
+        )
+        .bind(tournament_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("Bracket not found"))?;
+
+        let rounds = sqlx::query_as::<_, BracketRound>(
+            "SELECT * FROM bracket_rounds WHERE tournament_id = $1 ORDER BY ordinal",
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let slots = sqlx::query_as::<_, BracketSlot>(
+            "SELECT * FROM bracket_slots WHERE tournament_id = $1 ORDER BY position",
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let mut by_round: HashMap<Uuid, Vec<BracketSlot>> = HashMap::new();
+        for slot in slots {
+            by_round.entry(slot.round_id).or_default().push(slot);
+        }
+        let rounds = rounds
+            .into_iter()
+            .map(|round| BracketRoundView {
+                slots: by_round.remove(&round.id).unwrap_or_default(),
+                round,
+            })
+            .collect();
+
+        let teams = sqlx::query_as::<_, BracketTeam>(
+            r#"
+            SELECT tp.user_id AS entrant_id, t.id AS team_id, t.name, t.tag
+            FROM tournament_participants tp
+            JOIN teams t ON t.id = tp.team_id
+            WHERE tp.tournament_id = $1
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        Ok(BracketView {
+            bracket,
+            rounds,
+            teams,
+        })
+    }
+}
+
+// ============================================================================
+// PERSISTENCE
+// ============================================================================
+
+async fn lock_tournament(
+    tx: &mut Transaction<'_, Postgres>,
+    tournament_id: Uuid,
+) -> Result<(), ApiError> {
+    sqlx::query("SELECT id FROM tournaments WHERE id = $1 FOR UPDATE")
+        .bind(tournament_id)
+        .fetch_optional(&mut **tx)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("Tournament not found"))?;
+    Ok(())
+}
+
+fn parse_format(format: &str) -> Result<BracketType, ApiError> {
+    match format {
+        "single_elimination" => Ok(BracketType::SingleElimination),
+        "double_elimination" => Ok(BracketType::DoubleElimination),
+        "round_robin" => Ok(BracketType::RoundRobin),
+        "swiss" => Ok(BracketType::Swiss),
+        other => Err(ApiError::internal_error(format!(
+            "Unknown bracket format: {}",
+            other
+        ))),
+    }
+}
+
+/// Load a persisted bracket along with its round ids.
+async fn load(
+    tx: &mut Transaction<'_, Postgres>,
+    tournament_id: Uuid,
+) -> Result<Option<(Bracket, HashMap<RoundKey, Uuid>)>, ApiError> {
+    let Some(header) = sqlx::query_as::<_, TournamentBracket>(
+        "SELECT * FROM tournament_brackets WHERE tournament_id = $1",
+    )
+    .bind(tournament_id)
+    .fetch_optional(&mut **tx)
+    .await
+    .map_err(ApiError::database_error)?
+    else {
+        return Ok(None);
+    };
+    let format = parse_format(&header.format)?;
+
+    let rounds = sqlx::query_as::<_, BracketRound>(
+        "SELECT * FROM bracket_rounds WHERE tournament_id = $1 ORDER BY ordinal",
+    )
+    .bind(tournament_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(ApiError::database_error)?;
+
+    let mut round_keys: HashMap<Uuid, RoundKey> = HashMap::new();
+    let mut round_ids: HashMap<RoundKey, Uuid> = HashMap::new();
+    for round in &rounds {
+        let side: BracketSide = round.side.parse().map_err(ApiError::internal_error)?;
+        round_keys.insert(round.id, (side, round.round_number));
+        round_ids.insert((side, round.round_number), round.id);
+    }
+
+    let rows = sqlx::query_as::<_, BracketSlot>(
+        r#"
+        SELECT s.* FROM bracket_slots s
+        JOIN bracket_rounds r ON r.id = s.round_id
+        WHERE s.tournament_id = $1
+        ORDER BY r.ordinal, s.position
+        "#,
+    )
+    .bind(tournament_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(ApiError::database_error)?;
+
+    let index: HashMap<Uuid, usize> = rows.iter().enumerate().map(|(i, s)| (s.id, i)).collect();
+    let link = |slot_id: Option<Uuid>, position: Option<i16>| -> Option<SlotLink> {
+        Some(SlotLink {
+            slot: *index.get(&slot_id?)?,
+            position: (position? - 1) as usize,
+        })
+    };
+    let player = |user_id: Option<Uuid>, seed: Option<i32>| -> Option<SeededPlayer> {
+        Some(SeededPlayer {
+            user_id: user_id?,
+            seed: seed.unwrap_or(i32::MAX),
+        })
+    };
+
+    let mut slots = Vec::with_capacity(rows.len());
+    for row in &rows {
+        let (side, round) = *round_keys
+            .get(&row.round_id)
+            .ok_or_else(|| ApiError::internal_error("Bracket slot without a round"))?;
+        slots.push(Slot {
+            id: row.id,
+            side,
+            round,
+            position: row.position,
+            players: [
+                player(row.player1_id, row.player1_seed),
+                player(row.player2_id, row.player2_seed),
+            ],
+            winner: row.winner_id,
+            loser: row.loser_id,
+            status: row.status.parse().map_err(ApiError::internal_error)?,
+            winner_to: link(row.winner_to_slot_id, row.winner_to_position),
+            loser_to: link(row.loser_to_slot_id, row.loser_to_position),
+            forfeit: row.forfeit,
+        });
+    }
+
+    let withdrawn: Vec<Uuid> = sqlx::query_scalar(
+        "SELECT user_id FROM tournament_participants WHERE tournament_id = $1 AND status = 'disqualified'",
+    )
+    .bind(tournament_id)
+    .fetch_all(&mut **tx)
+    .await
+    .map_err(ApiError::database_error)?;
+
+    let mut bracket = Bracket::from_slots(format, slots);
+    bracket.withdrawn.extend(withdrawn);
+    Ok(Some((bracket, round_ids)))
+}
+
+/// Write the bracket's dirty slots, creating any rounds they belong to, and
+/// refresh round statuses.
+async fn save(
+    tx: &mut Transaction<'_, Postgres>,
+    tournament_id: Uuid,
+    bracket: &mut Bracket,
+    rounds: &mut HashMap<RoundKey, Uuid>,
+) -> Result<(), ApiError> {
+    let mut dirty: Vec<usize> = bracket.dirty.drain().collect();
+    dirty.sort_unstable();
+
+    // Create missing rounds in slot order so ordinals follow the bracket.
+    let mut ordinal = rounds.len() as i32;
+    for &idx in &dirty {
+        let slot = &bracket.slots[idx];
+        let key = (slot.side, slot.round);
+        if rounds.contains_key(&key) {
+            continue;
+        }
+        ordinal += 1;
+        let round_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO bracket_rounds (tournament_id, side, round_number, ordinal)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(slot.side.as_str())
+        .bind(slot.round)
+        .bind(ordinal)
+        .fetch_one(&mut **tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        rounds.insert(key, round_id);
+    }
+
+    let now = Utc::now();
+    for &idx in &dirty {
+        let slot = &bracket.slots[idx];
+        let link_id = |link: Option<SlotLink>| link.map(|l| bracket.slots[l.slot].id);
+        let link_position = |link: Option<SlotLink>| link.map(|l| l.position as i16 + 1);
+        let completed_at = slot.status.is_resolved().then_some(now);
+
+        sqlx::query(
+            r#"
+            INSERT INTO bracket_slots (
+                id, tournament_id, round_id, position,
+                player1_id, player1_seed, player2_id, player2_seed,
+                winner_id, loser_id, status, forfeit,
+                winner_to_slot_id, winner_to_position, loser_to_slot_id, loser_to_position,
+                created_at, updated_at, completed_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $17, $18)
+            ON CONFLICT (id) DO UPDATE SET
+                player1_id = EXCLUDED.player1_id,
+                player1_seed = EXCLUDED.player1_seed,
+                player2_id = EXCLUDED.player2_id,
+                player2_seed = EXCLUDED.player2_seed,
+                winner_id = EXCLUDED.winner_id,
+                loser_id = EXCLUDED.loser_id,
+                status = EXCLUDED.status,
+                forfeit = EXCLUDED.forfeit,
+                updated_at = EXCLUDED.updated_at,
+                completed_at = COALESCE(bracket_slots.completed_at, EXCLUDED.completed_at)
+            "#,
+        )
+        .bind(slot.id)
+        .bind(tournament_id)
+        .bind(rounds[&(slot.side, slot.round)])
+        .bind(slot.position)
+        .bind(slot.players[0].map(|p| p.user_id))
+        .bind(slot.players[0].map(|p| p.seed))
+        .bind(slot.players[1].map(|p| p.user_id))
+        .bind(slot.players[1].map(|p| p.seed))
+        .bind(slot.winner)
+        .bind(slot.loser)
+        .bind(slot.status.as_str())
+        .bind(slot.forfeit)
+        .bind(link_id(slot.winner_to))
+        .bind(link_position(slot.winner_to))
+        .bind(link_id(slot.loser_to))
+        .bind(link_position(slot.loser_to))
+        .bind(now)
+        .bind(completed_at)
+        .execute(&mut **tx)
+        .await
+        .map_err(ApiError::database_error)?;
+    }
+
+    sqlx::query(
+        r#"
+        UPDATE bracket_rounds r SET
+            status = CASE
+                WHEN NOT EXISTS (
+                    SELECT 1 FROM bracket_slots s
+                    WHERE s.round_id = r.id AND s.status IN ('pending', 'ready')
+                ) THEN 'completed'
+                WHEN EXISTS (
+                    SELECT 1 FROM bracket_slots s
+                    WHERE s.round_id = r.id AND s.status <> 'pending'
+                ) THEN 'in_progress'
+                ELSE 'pending'
+            END,
+            completed_at = CASE
+                WHEN NOT EXISTS (
+                    SELECT 1 FROM bracket_slots s
+                    WHERE s.round_id = r.id AND s.status IN ('pending', 'ready')
+                ) THEN COALESCE(r.completed_at, $2)
+            END
+        WHERE r.tournament_id = $1
+        "#,
+    )
+    .bind(tournament_id)
+    .bind(now)
+    .execute(&mut **tx)
+    .await
+    .map_err(ApiError::database_error)?;
+
+    Ok(())
+}
+
+/// Write final ranks and close the bracket.
+async fn finish(
+    tx: &mut Transaction<'_, Postgres>,
+    tournament_id: Uuid,
+    bracket: &Bracket,
+) -> Result<(), ApiError> {
+    for (idx, user_id) in bracket.ranking().into_iter().enumerate() {
+        sqlx::query(
+            "UPDATE tournament_participants SET final_rank = $1 WHERE tournament_id = $2 AND user_id = $3",
+        )
+        .bind((idx + 1) as i32)
+        .bind(tournament_id)
+        .bind(user_id)
+        .execute(&mut **tx)
+        .await
+        .map_err(ApiError::database_error)?;
+    }
+
+    sqlx::query("UPDATE tournament_brackets SET completed_at = $2 WHERE tournament_id = $1")
+        .bind(tournament_id)
+        .bind(Utc::now())
+        .execute(&mut **tx)
+        .await
+        .map_err(ApiError::database_error)?;
+
+    Ok(())
+}
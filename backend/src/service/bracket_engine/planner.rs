@@ -0,0 +1,796 @@
+//! In-memory bracket model shared by every format.
+//!
+//! A bracket is a list of slots, each holding up to two players. Elimination
+//! slots link to the slot (and side) their winner, and in double elimination
+//! their loser, moves to. [`Bracket::settle`] resolves slots that can no longer
+//! receive a second player as byes, so byes cascade through any format without
+//! special cases. Swiss rounds are paired one at a time from the standings.
+
+use crate::models::{BracketSide, BracketType, SlotStatus};
+use crate::orchestrator::seeding_engine::generate_bracket_order;
+use std::collections::{HashMap, HashSet};
+use thiserror::Error;
+use uuid::Uuid;
+
+pub const MIN_PLAYERS: usize = 2;
+pub const MAX_PLAYERS: usize = 256;
+
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum BracketError {
+    #[error("A bracket needs between {MIN_PLAYERS} and {MAX_PLAYERS} players, got {0}")]
+    PlayerCount(usize),
+    #[error("Bracket slot not found")]
+    SlotNotFound,
+    #[error("Bracket slot is not ready for a result")]
+    SlotNotReady,
+    #[error("Player is not playing in this slot")]
+    NotInSlot,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SeededPlayer {
+    pub user_id: Uuid,
+    /// 1 is the strongest seed.
+    pub seed: i32,
+}
+
+/// Destination of a player leaving a slot. `position` is 0 or 1.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlotLink {
+    pub slot: usize,
+    pub position: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Slot {
+    pub id: Uuid,
+    pub side: BracketSide,
+    pub round: i32,
+    pub position: i32,
+    pub players: [Option<SeededPlayer>; 2],
+    pub winner: Option<Uuid>,
+    pub loser: Option<Uuid>,
+    pub status: SlotStatus,
+    pub winner_to: Option<SlotLink>,
+    pub loser_to: Option<SlotLink>,
+    /// Resolved because a player did not show up.
+    pub forfeit: bool,
+}
+
+impl Slot {
+    fn new(side: BracketSide, round: i32, position: i32) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            side,
+            round,
+            position,
+            players: [None, None],
+            winner: None,
+            loser: None,
+            status: SlotStatus::Pending,
+            winner_to: None,
+            loser_to: None,
+            forfeit: false,
+        }
+    }
+
+    fn player(&self, user_id: Uuid) -> Option<SeededPlayer> {
+        self.players
+            .iter()
+            .flatten()
+            .find(|p| p.user_id == user_id)
+            .copied()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Bracket {
+    pub format: BracketType,
+    pub slots: Vec<Slot>,
+    /// Slots modified since the bracket was built or loaded.
+    pub dirty: HashSet<usize>,
+    /// Players removed for not showing up. They forfeit every later match and
+    /// are left out of future Swiss pairings.
+    pub withdrawn: HashSet<Uuid>,
+}
+
+impl Bracket {
+    /// Rebuild a bracket from persisted slots.
+    pub fn from_slots(format: BracketType, slots: Vec<Slot>) -> Self {
+        Self {
+            format,
+            slots,
+            dirty: HashSet::new(),
+            withdrawn: HashSet::new(),
+        }
+    }
+
+    /// Generate a bracket for `players`, ordered by seed (strongest first). For
+    /// Swiss only the first round is generated.
+    pub fn generate(format: BracketType, players: &[SeededPlayer]) -> Result<Self, BracketError> {
+        if players.len() < MIN_PLAYERS || players.len() > MAX_PLAYERS {
+            return Err(BracketError::PlayerCount(players.len()));
+        }
+        let mut bracket = Self::from_slots(format.clone(), Vec::new());
+        match format {
+            BracketType::SingleElimination => {
+                bracket.build_winners(players);
+            }
+            BracketType::DoubleElimination => bracket.build_double_elimination(players),
+            BracketType::RoundRobin => bracket.build_round_robin(players),
+            BracketType::Swiss => {
+                let standings = players
+                    .iter()
+                    .map(|&player| SwissStanding {
+                        player,
+                        points: 0,
+                        had_bye: false,
+                    })
+                    .collect();
+                bracket.push_swiss_round(1, standings, &HashSet::new());
+            }
+        }
+        bracket.settle();
+        Ok(bracket)
+    }
+
+    pub fn index_of(&self, slot_id: Uuid) -> Option<usize> {
+        self.slots.iter().position(|s| s.id == slot_id)
+    }
+
+    /// Ready slot between `a` and `b`, in either order.
+    pub fn find_ready_slot(&self, a: Uuid, b: Uuid) -> Option<usize> {
+        self.slots.iter().position(|s| {
+            s.status == SlotStatus::Ready && s.player(a).is_some() && s.player(b).is_some()
+        })
+    }
+
+    /// Record `winner` for the slot and advance both players. Returns whether the
+    /// bracket is now complete.
+    pub fn record_result(&mut self, slot_id: Uuid, winner: Uuid) -> Result<bool, BracketError> {
+        let idx = self.index_of(slot_id).ok_or(BracketError::SlotNotFound)?;
+        let slot = &mut self.slots[idx];
+        if slot.status != SlotStatus::Ready {
+            return Err(BracketError::SlotNotReady);
+        }
+        slot.player(winner).ok_or(BracketError::NotInSlot)?;
+        slot.winner = Some(winner);
+        slot.loser = slot
+            .players
+            .iter()
+            .flatten()
+            .map(|p| p.user_id)
+            .find(|id| *id != winner);
+        slot.status = SlotStatus::Completed;
+        self.dirty.insert(idx);
+        self.route(idx);
+
+        self.settle();
+        if self.push_next_swiss_round() {
+            self.settle();
+        }
+        Ok(self.is_complete())
+    }
+
+    /// Withdraw `no_shows` from a ready slot. The opponent advances by forfeit;
+    /// if both players are absent the slot resolves with no winner. Returns
+    /// whether the bracket is now complete.
+    pub fn forfeit(&mut self, slot_id: Uuid, no_shows: &[Uuid]) -> Result<bool, BracketError> {
+        let idx = self.index_of(slot_id).ok_or(BracketError::SlotNotFound)?;
+        let slot = &self.slots[idx];
+        if slot.status != SlotStatus::Ready {
+            return Err(BracketError::SlotNotReady);
+        }
+        if no_shows.iter().any(|id| slot.player(*id).is_none()) {
+            return Err(BracketError::NotInSlot);
+        }
+        self.withdrawn.extend(no_shows.iter().copied());
+
+        self.settle();
+        if self.push_next_swiss_round() {
+            self.settle();
+        }
+        Ok(self.is_complete())
+    }
+
+    pub fn is_complete(&self) -> bool {
+        let all_resolved = self.slots.iter().all(|s| s.status.is_resolved());
+        match self.format {
+            BracketType::Swiss => {
+                all_resolved
+                    && (self.latest_round() >= self.swiss_round_count()
+                        || self.swiss_field().len() < MIN_PLAYERS)
+            }
+            _ => all_resolved,
+        }
+    }
+
+    /// Final placings, best first. Elimination formats order players by how late
+    /// they were knocked out; pool formats by points. Seed breaks ties.
+    pub fn ranking(&self) -> Vec<Uuid> {
+        let mut seeds: HashMap<Uuid, i32> = HashMap::new();
+        for player in self.slots.iter().flat_map(|s| s.players.iter().flatten()) {
+            seeds.insert(player.user_id, player.seed);
+        }
+
+        let mut ranked: Vec<(Uuid, (i64, i64))> = match self.format {
+            BracketType::SingleElimination | BracketType::DoubleElimination => {
+                // The champion is never eliminated and ranks above everyone.
+                let mut stage: HashMap<Uuid, (i64, i64)> =
+                    seeds.keys().map(|id| (*id, (i64::MAX, 0))).collect();
+                for slot in &self.slots {
+                    if let (Some(loser), None) = (slot.loser, slot.loser_to) {
+                        stage.insert(loser, (side_order(slot.side), slot.round as i64));
+                    }
+                }
+                stage.into_iter().collect()
+            }
+            BracketType::RoundRobin | BracketType::Swiss => self
+                .points()
+                .into_iter()
+                .map(|(id, points)| (id, (points, 0)))
+                .collect(),
+        };
+        ranked.sort_by(|(a, key_a), (b, key_b)| key_b.cmp(key_a).then(seeds[a].cmp(&seeds[b])));
+        ranked.into_iter().map(|(id, _)| id).collect()
+    }
+
+    // ========================================================================
+    // GENERATION
+    // ========================================================================
+
+    fn push_slot(&mut self, side: BracketSide, round: i32, position: i32) -> usize {
+        self.slots.push(Slot::new(side, round, position));
+        let idx = self.slots.len() - 1;
+        self.dirty.insert(idx);
+        idx
+    }
+
+    fn link_winner(&mut self, from: usize, slot: usize, position: usize) {
+        self.slots[from].winner_to = Some(SlotLink { slot, position });
+    }
+
+    fn link_loser(&mut self, from: usize, slot: usize, position: usize) {
+        self.slots[from].loser_to = Some(SlotLink { slot, position });
+    }
+
+    /// Seeded elimination tree. Returns the slot indices of every round.
+    fn build_winners(&mut self, players: &[SeededPlayer]) -> Vec<Vec<usize>> {
+        let size = players.len().next_power_of_two();
+        let order = generate_bracket_order(size);
+        let round_count = size.trailing_zeros() as i32;
+
+        let mut rounds: Vec<Vec<usize>> = Vec::new();
+        for round in 1..=round_count {
+            let count = size >> round;
+            let current: Vec<usize> = (0..count)
+                .map(|i| self.push_slot(BracketSide::Winners, round, i as i32))
+                .collect();
+            match rounds.last() {
+                None => {
+                    for (i, &idx) in current.iter().enumerate() {
+                        self.slots[idx].players = [
+                            players.get(order[2 * i] - 1).copied(),
+                            players.get(order[2 * i + 1] - 1).copied(),
+                        ];
+                    }
+                }
+                Some(previous) => {
+                    for (i, &from) in previous.clone().iter().enumerate() {
+                        self.link_winner(from, current[i / 2], i % 2);
+                    }
+                }
+            }
+            rounds.push(current);
+        }
+        rounds
+    }
+
+    /// Winners bracket, losers bracket and a single grand final (no reset match).
+    /// Losers of winners round `m + 1` drop into losers round `2m`, crossed on
+    /// alternate rounds to delay rematches.
+    fn build_double_elimination(&mut self, players: &[SeededPlayer]) {
+        let winners = self.build_winners(players);
+        let size = players.len().next_power_of_two();
+        let k = winners.len();
+
+        let mut losers: Vec<Vec<usize>> = Vec::new();
+        for round in 1..=2 * (k - 1) {
+            let count = size >> (round.div_ceil(2) + 1);
+            let current: Vec<usize> = (0..count)
+                .map(|i| self.push_slot(BracketSide::Losers, round as i32, i as i32))
+                .collect();
+            if round == 1 {
+                for (j, &idx) in current.iter().enumerate() {
+                    self.link_loser(winners[0][2 * j], idx, 0);
+                    self.link_loser(winners[0][2 * j + 1], idx, 1);
+                }
+            } else if round % 2 == 1 {
+                let previous = losers[round - 2].clone();
+                for (j, &idx) in current.iter().enumerate() {
+                    self.link_winner(previous[2 * j], idx, 0);
+                    self.link_winner(previous[2 * j + 1], idx, 1);
+                }
+            } else {
+                let previous = losers[round - 2].clone();
+                let m = round / 2;
+                for (j, &idx) in current.iter().enumerate() {
+                    self.link_winner(previous[j], idx, 0);
+                    let dropping = if m % 2 == 1 { count - 1 - j } else { j };
+                    self.link_loser(winners[m][dropping], idx, 1);
+                }
+            }
+            losers.push(current);
+        }
+
+        let grand_final = self.push_slot(BracketSide::GrandFinal, 1, 0);
+        let winners_final = winners[k - 1][0];
+        self.link_winner(winners_final, grand_final, 0);
+        match losers.last() {
+            Some(losers_final) => self.link_winner(losers_final[0], grand_final, 1),
+            None => self.link_loser(winners_final, grand_final, 1),
+        }
+    }
+
+    /// Circle method: every player meets every other player once.
+    fn build_round_robin(&mut self, players: &[SeededPlayer]) {
+        let mut ring: Vec<Option<SeededPlayer>> = players.iter().copied().map(Some).collect();
+        if ring.len() % 2 == 1 {
+            ring.push(None);
+        }
+        let m = ring.len();
+        for round in 1..m {
+            for i in 0..m / 2 {
+                let idx = self.push_slot(BracketSide::Pool, round as i32, i as i32);
+                self.slots[idx].players = [ring[i], ring[m - 1 - i]];
+            }
+            ring[1..].rotate_right(1);
+        }
+    }
+
+    // ========================================================================
+    // SWISS
+    // ========================================================================
+
+    fn latest_round(&self) -> i32 {
+        self.slots.iter().map(|s| s.round).max().unwrap_or(0)
+    }
+
+    fn swiss_round_count(&self) -> i32 {
+        let players = self
+            .slots
+            .iter()
+            .filter(|s| s.round == 1)
+            .flat_map(|s| s.players.iter().flatten())
+            .count();
+        (players.next_power_of_two().trailing_zeros() as i32).max(1)
+    }
+
+    /// Pair the next Swiss round once the current one is resolved.
+    fn push_next_swiss_round(&mut self) -> bool {
+        if self.format != BracketType::Swiss {
+            return false;
+        }
+        let latest = self.latest_round();
+        let current_done = self
+            .slots
+            .iter()
+            .filter(|s| s.round == latest)
+            .all(|s| s.status.is_resolved());
+        if !current_done
+            || latest >= self.swiss_round_count()
+            || self.swiss_field().len() < MIN_PLAYERS
+        {
+            return false;
+        }
+
+        let points = self.points();
+        let mut had_bye: HashSet<Uuid> = HashSet::new();
+        let mut played: HashSet<(Uuid, Uuid)> = HashSet::new();
+        let mut players: HashMap<Uuid, SeededPlayer> = HashMap::new();
+        for slot in &self.slots {
+            match slot.players {
+                [Some(a), Some(b)] => {
+                    played.insert((a.user_id, b.user_id));
+                    played.insert((b.user_id, a.user_id));
+                }
+                [Some(p), None] | [None, Some(p)] => {
+                    had_bye.insert(p.user_id);
+                }
+                [None, None] => {}
+            }
+            for player in slot.players.iter().flatten() {
+                players.insert(player.user_id, *player);
+            }
+        }
+        let standings = players
+            .values()
+            .filter(|p| !self.withdrawn.contains(&p.user_id))
+            .map(|&player| SwissStanding {
+                player,
+                points: points.get(&player.user_id).copied().unwrap_or(0),
+                had_bye: had_bye.contains(&player.user_id),
+            })
+            .collect();
+        self.push_swiss_round(latest + 1, standings, &played);
+        true
+    }
+
+    /// Dutch-style pairing: within each score group the top half meets the
+    /// bottom half, skipping rematches where possible. With an odd field the
+    /// lowest-ranked player without a bye sits out.
+    fn push_swiss_round(
+        &mut self,
+        round: i32,
+        mut standings: Vec<SwissStanding>,
+        played: &HashSet<(Uuid, Uuid)>,
+    ) {
+        standings.sort_by(|a, b| {
+            b.points
+                .cmp(&a.points)
+                .then(a.player.seed.cmp(&b.player.seed))
+        });
+
+        let mut pairs: Vec<[Option<SeededPlayer>; 2]> = Vec::new();
+        if standings.len() % 2 == 1 {
+            let bye = standings
+                .iter()
+                .rposition(|s| !s.had_bye)
+                .unwrap_or(standings.len() - 1);
+            pairs.push([Some(standings.remove(bye).player), None]);
+        }
+
+        let mut pool: Vec<SeededPlayer> = standings.iter().map(|s| s.player).collect();
+        let points: HashMap<Uuid, i64> = standings
+            .iter()
+            .map(|s| (s.player.user_id, s.points))
+            .collect();
+        let mut paired: Vec<[Option<SeededPlayer>; 2]> = Vec::new();
+        while !pool.is_empty() {
+            let top_points = points[&pool[0].user_id];
+            let group_len = pool
+                .iter()
+                .take_while(|p| points[&p.user_id] == top_points)
+                .count();
+            let mut group: Vec<SeededPlayer> = pool.drain(..group_len).collect();
+            if group.len() % 2 == 1 {
+                // Float the best player of the next group down.
+                group.push(pool.remove(0));
+            }
+            let mut bottom = group.split_off(group.len() / 2);
+            for top in group {
+                let opponent = bottom
+                    .iter()
+                    .position(|b| !played.contains(&(top.user_id, b.user_id)))
+                    .unwrap_or(0);
+                paired.push([Some(top), Some(bottom.remove(opponent))]);
+            }
+        }
+        paired.extend(pairs);
+
+        for (i, players) in paired.into_iter().enumerate() {
+            let idx = self.push_slot(BracketSide::Pool, round, i as i32);
+            self.slots[idx].players = players;
+        }
+    }
+
+    /// Players still taking part in a Swiss event.
+    fn swiss_field(&self) -> HashSet<Uuid> {
+        self.slots
+            .iter()
+            .flat_map(|s| s.players.iter().flatten())
+            .map(|p| p.user_id)
+            .filter(|id| !self.withdrawn.contains(id))
+            .collect()
+    }
+
+    /// Wins per player in pool formats. Swiss byes count as wins; round robin
+    /// byes do not.
+    fn points(&self) -> HashMap<Uuid, i64> {
+        let mut points: HashMap<Uuid, i64> = HashMap::new();
+        for slot in &self.slots {
+            for player in slot.players.iter().flatten() {
+                points.entry(player.user_id).or_insert(0);
+            }
+            let counts = match slot.status {
+                SlotStatus::Completed => true,
+                SlotStatus::Bye => self.format == BracketType::Swiss,
+                _ => false,
+            };
+            if let (true, Some(winner)) = (counts, slot.winner) {
+                *points.entry(winner).or_insert(0) += 1;
+            }
+        }
+        points
+    }
+
+    // ========================================================================
+    // ADVANCEMENT
+    // ========================================================================
+
+    /// Move the winner and loser of a resolved slot to their next slots.
+    fn route(&mut self, idx: usize) {
+        let slot = &self.slots[idx];
+        let moves = [
+            (slot.winner_to, slot.winner.and_then(|id| slot.player(id))),
+            (slot.loser_to, slot.loser.and_then(|id| slot.player(id))),
+        ];
+        for (link, player) in moves {
+            if let (Some(link), Some(player)) = (link, player) {
+                self.slots[link.slot].players[link.position] = Some(player);
+                self.dirty.insert(link.slot);
+            }
+        }
+    }
+
+    /// Mark slots with two players as ready, forfeit slots with a withdrawn
+    /// player, and resolve slots that cannot receive a second player as byes,
+    /// until nothing changes.
+    pub fn settle(&mut self) {
+        loop {
+            let mut open: HashSet<(usize, usize)> = HashSet::new();
+            for slot in self.slots.iter().filter(|s| !s.status.is_resolved()) {
+                for link in [slot.winner_to, slot.loser_to].into_iter().flatten() {
+                    open.insert((link.slot, link.position));
+                }
+            }
+
+            let mut progressed = false;
+            for idx in 0..self.slots.len() {
+                let slot = &mut self.slots[idx];
+                if slot.status.is_resolved() {
+                    continue;
+                }
+                let seated: Vec<Uuid> = slot.players.iter().flatten().map(|p| p.user_id).collect();
+                let (active, absent): (Vec<Uuid>, Vec<Uuid>) =
+                    seated.iter().partition(|id| !self.withdrawn.contains(id));
+
+                if seated.len() == 2 && absent.is_empty() {
+                    if slot.status == SlotStatus::Pending {
+                        slot.status = SlotStatus::Ready;
+                        self.dirty.insert(idx);
+                    }
+                    continue;
+                }
+                let waiting = open.contains(&(idx, 0)) || open.contains(&(idx, 1));
+                if seated.len() == 2 || !waiting {
+                    slot.winner = active.first().copied();
+                    slot.loser = absent.first().copied();
+                    slot.forfeit = !absent.is_empty();
+                    slot.status = match (seated.len(), slot.winner) {
+                        (2, Some(_)) => SlotStatus::Completed,
+                        _ => SlotStatus::Bye,
+                    };
+                    self.dirty.insert(idx);
+                    self.route(idx);
+                    progressed = true;
+                }
+            }
+            if !progressed {
+                return;
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SwissStanding {
+    player: SeededPlayer,
+    points: i64,
+    had_bye: bool,
+}
+
+fn side_order(side: BracketSide) -> i64 {
+    match side {
+        BracketSide::Winners | BracketSide::Pool => 0,
+        BracketSide::Losers => 1,
+        BracketSide::GrandFinal => 2,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn players(n: usize) -> Vec<SeededPlayer> {
+        (0..n)
+            .map(|i| SeededPlayer {
+                user_id: Uuid::new_v4(),
+                seed: i as i32 + 1,
+            })
+            .collect()
+    }
+
+    /// Play every ready slot, the better seed winning, until the bracket ends.
+    fn play_out(bracket: &mut Bracket) {
+        while let Some(idx) = bracket
+            .slots
+            .iter()
+            .position(|s| s.status == SlotStatus::Ready)
+        {
+            let slot = &bracket.slots[idx];
+            let winner = slot
+                .players
+                .iter()
+                .flatten()
+                .min_by_key(|p| p.seed)
+                .unwrap()
+                .user_id;
+            let id = slot.id;
+            bracket.record_result(id, winner).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_single_elimination_byes_advance_top_seeds() {
+        let field = players(6);
+        let bracket = Bracket::generate(BracketType::SingleElimination, &field).unwrap();
+        assert_eq!(bracket.slots.len(), 7);
+        let byes: Vec<_> = bracket
+            .slots
+            .iter()
+            .filter(|s| s.status == SlotStatus::Bye)
+            .collect();
+        assert_eq!(byes.len(), 2);
+        assert!(byes.iter().any(|s| s.winner == Some(field[0].user_id)));
+        assert!(byes.iter().any(|s| s.winner == Some(field[1].user_id)));
+    }
+
+    #[test]
+    fn test_single_elimination_plays_to_champion() {
+        let field = players(8);
+        let mut bracket = Bracket::generate(BracketType::SingleElimination, &field).unwrap();
+        play_out(&mut bracket);
+        assert!(bracket.is_complete());
+        let ranking = bracket.ranking();
+        assert_eq!(ranking[0], field[0].user_id);
+        assert_eq!(ranking[1], field[1].user_id);
+        assert_eq!(ranking.len(), 8);
+    }
+
+    #[test]
+    fn test_double_elimination_structure_and_completion() {
+        for n in [2, 3, 5, 8, 13] {
+            let field = players(n);
+            let mut bracket = Bracket::generate(BracketType::DoubleElimination, &field).unwrap();
+            let size = n.next_power_of_two();
+            // Winners tree, losers tree and the grand final.
+            assert_eq!(bracket.slots.len(), (size - 1) + (size - 2) + 1);
+            play_out(&mut bracket);
+            assert!(bracket.is_complete(), "{} players", n);
+            assert_eq!(bracket.ranking()[0], field[0].user_id);
+        }
+    }
+
+    #[test]
+    fn test_double_elimination_loser_gets_second_life() {
+        let field = players(4);
+        let mut bracket = Bracket::generate(BracketType::DoubleElimination, &field).unwrap();
+        // Seed 4 upsets seed 1 in the first round.
+        let first = bracket
+            .find_ready_slot(field[0].user_id, field[3].user_id)
+            .unwrap();
+        let id = bracket.slots[first].id;
+        bracket.record_result(id, field[3].user_id).unwrap();
+        let dropped = bracket.slots[first].loser_to.unwrap();
+        assert_eq!(bracket.slots[dropped.slot].side, BracketSide::Losers);
+        assert_eq!(
+            bracket.slots[dropped.slot].players[dropped.position].map(|p| p.user_id),
+            Some(field[0].user_id)
+        );
+    }
+
+    #[test]
+    fn test_round_robin_everyone_meets_once() {
+        let field = players(5);
+        let mut bracket = Bracket::generate(BracketType::RoundRobin, &field).unwrap();
+        let mut pairs = HashSet::new();
+        for slot in &bracket.slots {
+            if let [Some(a), Some(b)] = slot.players {
+                assert!(pairs.insert((a.seed.min(b.seed), a.seed.max(b.seed))));
+            }
+        }
+        assert_eq!(pairs.len(), 10);
+        play_out(&mut bracket);
+        assert!(bracket.is_complete());
+        assert_eq!(bracket.ranking()[0], field[0].user_id);
+    }
+
+    #[test]
+    fn test_swiss_pairs_rounds_from_standings_without_rematches() {
+        let field = players(8);
+        let mut bracket = Bracket::generate(BracketType::Swiss, &field).unwrap();
+        assert_eq!(bracket.slots.len(), 4);
+        // Round one: top half against bottom half.
+        assert_eq!(bracket.slots[0].players.map(|p| p.unwrap().seed), [1, 5]);
+        play_out(&mut bracket);
+        assert!(bracket.is_complete());
+        assert_eq!(bracket.latest_round(), 3);
+
+        let mut pairs = HashSet::new();
+        for slot in &bracket.slots {
+            if let [Some(a), Some(b)] = slot.players {
+                assert!(pairs.insert((a.seed.min(b.seed), a.seed.max(b.seed))));
+            }
+        }
+        assert_eq!(bracket.ranking()[0], field[0].user_id);
+    }
+
+    #[test]
+    fn test_no_show_forfeits_every_later_match() {
+        let field = players(4);
+        let mut bracket = Bracket::generate(BracketType::DoubleElimination, &field).unwrap();
+        let first = bracket
+            .find_ready_slot(field[0].user_id, field[3].user_id)
+            .unwrap();
+        let id = bracket.slots[first].id;
+        bracket.forfeit(id, &[field[0].user_id]).unwrap();
+
+        let slot = &bracket.slots[first];
+        assert!(slot.forfeit);
+        assert_eq!(slot.status, SlotStatus::Completed);
+        assert_eq!(slot.winner, Some(field[3].user_id));
+        // Seed 1 dropped to the losers bracket, where they forfeit again.
+        let dropped = slot.loser_to.unwrap();
+        play_out(&mut bracket);
+        assert!(bracket.slots[dropped.slot].forfeit);
+        assert!(bracket.is_complete());
+        assert_ne!(bracket.ranking()[0], field[0].user_id);
+    }
+
+    #[test]
+    fn test_double_no_show_gives_next_opponent_a_bye() {
+        let field = players(4);
+        let mut bracket = Bracket::generate(BracketType::SingleElimination, &field).unwrap();
+        let first = bracket
+            .find_ready_slot(field[0].user_id, field[3].user_id)
+            .unwrap();
+        let id = bracket.slots[first].id;
+        bracket
+            .forfeit(id, &[field[0].user_id, field[3].user_id])
+            .unwrap();
+        assert_eq!(bracket.slots[first].status, SlotStatus::Bye);
+        assert_eq!(bracket.slots[first].winner, None);
+
+        play_out(&mut bracket);
+        let final_slot = bracket.slots.last().unwrap();
+        assert_eq!(final_slot.status, SlotStatus::Bye);
+        assert_eq!(final_slot.winner, Some(field[1].user_id));
+        assert!(bracket.is_complete());
+    }
+
+    #[test]
+    fn test_swiss_leaves_withdrawn_players_unpaired() {
+        let field = players(8);
+        let mut bracket = Bracket::generate(BracketType::Swiss, &field).unwrap();
+        let id = bracket.slots[0].id;
+        bracket.forfeit(id, &[field[0].user_id]).unwrap();
+        play_out(&mut bracket);
+        assert!(bracket.is_complete());
+        assert!(bracket.slots.iter().filter(|s| s.round > 1).all(|s| s
+            .players
+            .iter()
+            .flatten()
+            .all(|p| p.user_id != field[0].user_id)));
+    }
+
+    #[test]
+    fn test_result_validation() {
+        let field = players(4);
+        let mut bracket = Bracket::generate(BracketType::SingleElimination, &field).unwrap();
+        let final_id = bracket.slots.last().unwrap().id;
+        assert_eq!(
+            bracket.record_result(final_id, field[0].user_id),
+            Err(BracketError::SlotNotReady)
+        );
+        let first = bracket.slots[0].id;
+        assert_eq!(
+            bracket.record_result(first, Uuid::new_v4()),
+            Err(BracketError::NotInSlot)
+        );
+        assert_eq!(
+            Bracket::generate(BracketType::Swiss, &field[..1]).err(),
+            Some(BracketError::PlayerCount(1))
+        );
+    }
+}
@@ -0,0 +1,563 @@
+//! Decoding of `#[contractevent]` payloads from their JSON `ScVal` form.
+//!
+//! A contract event has two topics, the versioned contract prefix (e.g.
+//! `ArenaXEscrow_v1`) and the action (e.g. `DEPOSIT`), and a map of its remaining
+//! fields keyed by field name. Events the platform acts on are turned into a
+//! [`NormalizedEvent`]; anything else is only kept in the raw event log.
+
+use super::ChainIndexerError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// The contracts the indexer follows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ContractKind {
+    EscrowVault,
+    MatchLifecycle,
+    StakingManager,
+    PrizeDistribution,
+    AuthGateway,
+    ReputationIndex,
+}
+
+impl ContractKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ContractKind::EscrowVault => "escrow_vault",
+            ContractKind::MatchLifecycle => "match_lifecycle",
+            ContractKind::StakingManager => "staking_manager",
+            ContractKind::PrizeDistribution => "prize_distribution",
+            ContractKind::AuthGateway => "auth_gateway",
+            ContractKind::ReputationIndex => "reputation_index",
+        }
+    }
+
+    /// First topic of every event the contract emits (see `arenax-events`).
+    pub fn topic_prefix(&self) -> &'static str {
+        match self {
+            ContractKind::EscrowVault => "ArenaXEscrow_v1",
+            ContractKind::MatchLifecycle => "ArenaXMLf_v1",
+            ContractKind::StakingManager => "ArenaXStake_v1",
+            ContractKind::PrizeDistribution => "ArenaXPrize_v1",
+            ContractKind::AuthGateway => "ArenaXAuth_v1",
+            ContractKind::ReputationIndex => "ArenaXRepIdx_v1",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscrowEventRow {
+    pub match_id: String,
+    pub action: &'static str,
+    pub account: Option<String>,
+    pub amount: Option<i128>,
+    pub asset: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchEventRow {
+    pub match_id: String,
+    pub action: &'static str,
+    pub account: Option<String>,
+    pub stake_amount: Option<i128>,
+    pub stake_asset: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StakeEventRow {
+    pub tournament_id: String,
+    pub action: &'static str,
+    pub user_address: String,
+    pub amount: i128,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrizeEventRow {
+    pub pool_id: u64,
+    pub action: &'static str,
+    pub match_id: Option<String>,
+    pub amount: Option<i128>,
+    pub asset: Option<String>,
+    pub winners: Option<Vec<String>>,
+    /// Payout weight of each winner, in `winners` order.
+    pub weights: Option<Vec<u32>>,
+}
+
+/// A role granted or revoked on the auth gateway. `role` is the gateway's
+/// `Role` discriminant.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleEventRow {
+    pub address: String,
+    pub action: &'static str,
+    pub role: u32,
+    pub actor: String,
+}
+
+/// A change to a player's skill and fair-play scores on the reputation index.
+/// Decays are recorded as negative deltas.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReputationEventRow {
+    pub player: String,
+    pub action: &'static str,
+    pub skill_delta: i128,
+    pub fair_play_delta: i128,
+    pub match_id: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NormalizedEvent {
+    Escrow(EscrowEventRow),
+    Match(MatchEventRow),
+    Stake(StakeEventRow),
+    Prize(PrizeEventRow),
+    Role(RoleEventRow),
+    Reputation(ReputationEventRow),
+}
+
+/// Render the topics as `PREFIX:ACTION` for the raw event log.
+pub fn topic_string(topics: &[Value]) -> String {
+    topics
+        .iter()
+        .map(|t| symbol(t).unwrap_or_else(|_| t.to_string()))
+        .collect::<Vec<_>>()
+        .join(":")
+}
+
+/// Decode an event emitted by a contract of `kind`. Returns `None` for events that
+/// have no normalized table (configuration changes, pauses, ...).
+pub fn decode_event(
+    kind: ContractKind,
+    topics: &[Value],
+    value: &Value,
+) -> Result<Option<NormalizedEvent>, ChainIndexerError> {
+    if topics.len() < 2 || symbol(&topics[0])? != kind.topic_prefix() {
+        return Ok(None);
+    }
+    let action = symbol(&topics[1])?;
+    let fields = Fields::new(value)?;
+
+    let event = match (kind, action.as_str()) {
+        (ContractKind::EscrowVault, "DEPOSIT") => NormalizedEvent::Escrow(EscrowEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "deposit",
+            account: Some(fields.address("player")?),
+            amount: Some(fields.i128("amount")?),
+            asset: Some(fields.address("asset")?),
+        }),
+        (ContractKind::EscrowVault, "LOCKED") => NormalizedEvent::Escrow(EscrowEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "locked",
+            account: None,
+            amount: None,
+            asset: None,
+        }),
+        (ContractKind::EscrowVault, "RELEASED") => NormalizedEvent::Escrow(EscrowEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "released",
+            account: Some(fields.address("winner")?),
+            amount: Some(fields.i128("amount")?),
+            asset: Some(fields.address("asset")?),
+        }),
+        (ContractKind::EscrowVault, "REFUNDED") => NormalizedEvent::Escrow(EscrowEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "refunded",
+            account: None,
+            amount: Some(fields.i128("amount")?),
+            asset: Some(fields.address("asset")?),
+        }),
+        (ContractKind::EscrowVault, "SLASHED") => NormalizedEvent::Escrow(EscrowEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "slashed",
+            account: Some(fields.address("subject")?),
+            amount: Some(fields.i128("amount")?),
+            asset: Some(fields.address("asset")?),
+        }),
+        (ContractKind::MatchLifecycle, "CREATED") => NormalizedEvent::Match(MatchEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "created",
+            account: None,
+            stake_amount: Some(fields.i128("stake_amount")?),
+            stake_asset: Some(fields.address("stake_asset")?),
+        }),
+        (ContractKind::MatchLifecycle, "RESULT") => NormalizedEvent::Match(MatchEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "result_submitted",
+            account: Some(fields.address("reporter")?),
+            stake_amount: None,
+            stake_asset: None,
+        }),
+        (ContractKind::MatchLifecycle, "FINALIZED") => NormalizedEvent::Match(MatchEventRow {
+            match_id: fields.bytes("match_id")?,
+            action: "finalized",
+            account: Some(fields.address("winner")?),
+            stake_amount: None,
+            stake_asset: None,
+        }),
+        (ContractKind::StakingManager, "STAKED" | "WITHDRAWN" | "SLASHED") => {
+            NormalizedEvent::Stake(StakeEventRow {
+                tournament_id: fields.bytes("tournament_id")?,
+                action: match action.as_str() {
+                    "STAKED" => "staked",
+                    "WITHDRAWN" => "withdrawn",
+                    _ => "slashed",
+                },
+                user_address: fields.address("user")?,
+                amount: fields.i128("amount")?,
+            })
+        }
+        (ContractKind::PrizeDistribution, "CREATED") => NormalizedEvent::Prize(PrizeEventRow {
+            pool_id: fields.u64("pool_id")?,
+            action: "created",
+            match_id: Some(fields.bytes("match_id")?),
+            amount: Some(fields.i128("amount_locked")?),
+            asset: Some(fields.address("asset")?),
+            winners: None,
+            weights: None,
+        }),
+        (ContractKind::PrizeDistribution, "LOCKED") => NormalizedEvent::Prize(PrizeEventRow {
+            pool_id: fields.u64("pool_id")?,
+            action: "locked",
+            match_id: None,
+            amount: Some(fields.i128("amount_locked")?),
+            asset: None,
+            winners: None,
+            weights: None,
+        }),
+        (ContractKind::PrizeDistribution, "EXECUTED") => NormalizedEvent::Prize(PrizeEventRow {
+            pool_id: fields.u64("pool_id")?,
+            action: "executed",
+            match_id: None,
+            amount: None,
+            asset: None,
+            winners: Some(
+                fields
+                    .vec("winners")?
+                    .iter()
+                    .map(address)
+                    .collect::<Result<_, _>>()?,
+            ),
+            weights: Some(
+                fields
+                    .vec("weights")?
+                    .iter()
+                    .map(u32)
+                    .collect::<Result<_, _>>()?,
+            ),
+        }),
+        (ContractKind::PrizeDistribution, "HELD" | "RELEASED") => {
+            NormalizedEvent::Prize(PrizeEventRow {
+                pool_id: fields.u64("pool_id")?,
+                action: if action == "HELD" { "held" } else { "released" },
+                match_id: Some(fields.bytes("match_id")?),
+                amount: None,
+                asset: None,
+                winners: None,
+                weights: None,
+            })
+        }
+        (ContractKind::AuthGateway, "ROLE_SET") => NormalizedEvent::Role(RoleEventRow {
+            address: fields.address("address")?,
+            action: "assigned",
+            role: fields.u32("role")?,
+            actor: fields.address("assigned_by")?,
+        }),
+        (ContractKind::AuthGateway, "ROLE_REV") => NormalizedEvent::Role(RoleEventRow {
+            address: fields.address("address")?,
+            action: "revoked",
+            role: fields.u32("role")?,
+            actor: fields.address("revoked_by")?,
+        }),
+        (ContractKind::ReputationIndex, "REPUTATION_CHANGED") => {
+            NormalizedEvent::Reputation(ReputationEventRow {
+                player: fields.address("player")?,
+                action: "changed",
+                skill_delta: fields.i128("skill_delta")?,
+                fair_play_delta: fields.i128("fair_play_delta")?,
+                match_id: Some(fields.u64("match_id")?),
+            })
+        }
+        (ContractKind::ReputationIndex, "REPUTATION_DECAYED") => {
+            NormalizedEvent::Reputation(ReputationEventRow {
+                player: fields.address("player")?,
+                action: "decayed",
+                skill_delta: -fields.i128("skill_decayed")?,
+                fair_play_delta: -fields.i128("fair_play_decayed")?,
+                match_id: None,
+            })
+        }
+        _ => return Ok(None),
+    };
+    Ok(Some(event))
+}
+
+/// Named fields of an event's data map.
+struct Fields<'a> {
+    entries: &'a [Value],
+}
+
+impl<'a> Fields<'a> {
+    fn new(value: &'a Value) -> Result<Self, ChainIndexerError> {
+        let entries = value
+            .get("map")
+            .and_then(Value::as_array)
+            .ok_or_else(|| decode_error("event data is not a map", value))?;
+        Ok(Self { entries })
+    }
+
+    fn get(&self, name: &str) -> Result<&'a Value, ChainIndexerError> {
+        self.entries
+            .iter()
+            .find(|entry| {
+                entry.get("key").and_then(|k| k.get("symbol")) == Some(&Value::from(name))
+            })
+            .and_then(|entry| entry.get("val"))
+            .ok_or_else(|| ChainIndexerError::Decode(format!("missing field `{}`", name)))
+    }
+
+    fn address(&self, name: &str) -> Result<String, ChainIndexerError> {
+        address(self.get(name)?)
+    }
+
+    fn bytes(&self, name: &str) -> Result<String, ChainIndexerError> {
+        let value = self.get(name)?;
+        value
+            .get("bytes")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| decode_error("expected bytes", value))
+    }
+
+    fn i128(&self, name: &str) -> Result<i128, ChainIndexerError> {
+        let value = self.get(name)?;
+        value
+            .get("i128")
+            .and_then(parse_i128)
+            .ok_or_else(|| decode_error("expected i128", value))
+    }
+
+    fn u64(&self, name: &str) -> Result<u64, ChainIndexerError> {
+        let value = self.get(name)?;
+        match value.get("u64") {
+            Some(Value::String(s)) => s.parse().ok(),
+            Some(v) => v.as_u64(),
+            None => None,
+        }
+        .ok_or_else(|| decode_error("expected u64", value))
+    }
+
+    fn u32(&self, name: &str) -> Result<u32, ChainIndexerError> {
+        u32(self.get(name)?)
+    }
+
+    fn vec(&self, name: &str) -> Result<&'a Vec<Value>, ChainIndexerError> {
+        let value = self.get(name)?;
+        value
+            .get("vec")
+            .and_then(Value::as_array)
+            .ok_or_else(|| decode_error("expected vec", value))
+    }
+}
+
+fn symbol(value: &Value) -> Result<String, ChainIndexerError> {
+    value
+        .get("symbol")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| decode_error("expected symbol", value))
+}
+
+fn address(value: &Value) -> Result<String, ChainIndexerError> {
+    value
+        .get("address")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .ok_or_else(|| decode_error("expected address", value))
+}
+
+fn u32(value: &Value) -> Result<u32, ChainIndexerError> {
+    value
+        .get("u32")
+        .and_then(Value::as_u64)
+        .and_then(|n| u32::try_from(n).ok())
+        .ok_or_else(|| decode_error("expected u32", value))
+}
+
+/// i128 is rendered as a decimal string by current RPC versions and as
+/// `{ "hi", "lo" }` parts by older ones.
+fn parse_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Object(parts) => {
+            let hi = parts.get("hi")?.as_i64()?;
+            let lo = parts.get("lo")?.as_u64()?;
+            Some(((hi as i128) << 64) | lo as i128)
+        }
+        _ => None,
+    }
+}
+
+fn decode_error(message: &str, value: &Value) -> ChainIndexerError {
+    ChainIndexerError::Decode(format!("{}: {}", message, value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const PLAYER: &str = "GBPLAYERAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+    const ASSET: &str = "CASSETAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA";
+
+    fn topics(prefix: &str, action: &str) -> Vec<Value> {
+        vec![json!({ "symbol": prefix }), json!({ "symbol": action })]
+    }
+
+    fn entry(key: &str, val: Value) -> Value {
+        json!({ "key": { "symbol": key }, "val": val })
+    }
+
+    #[test]
+    fn test_decode_escrow_deposit() {
+        let value = json!({ "map": [
+            entry("amount", json!({ "i128": "5000" })),
+            entry("asset", json!({ "address": ASSET })),
+            entry("match_id", json!({ "bytes": "ab01" })),
+            entry("player", json!({ "address": PLAYER })),
+        ]});
+
+        let event = decode_event(
+            ContractKind::EscrowVault,
+            &topics("ArenaXEscrow_v1", "DEPOSIT"),
+            &value,
+        )
+        .unwrap();
+        assert_eq!(
+            event,
+            Some(NormalizedEvent::Escrow(EscrowEventRow {
+                match_id: "ab01".to_string(),
+                action: "deposit",
+                account: Some(PLAYER.to_string()),
+                amount: Some(5000),
+                asset: Some(ASSET.to_string()),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_decode_i128_parts() {
+        assert_eq!(parse_i128(&json!({ "hi": 0, "lo": 42 })), Some(42));
+        assert_eq!(parse_i128(&json!({ "hi": 1, "lo": 0 })), Some(1i128 << 64));
+        assert_eq!(parse_i128(&json!("-7")), Some(-7));
+    }
+
+    #[test]
+    fn test_decode_prize_payout_winners() {
+        let value = json!({ "map": [
+            entry("pool_id", json!({ "u64": "3" })),
+            entry("weights", json!({ "vec": [{ "u32": 10000 }] })),
+            entry("winners", json!({ "vec": [{ "address": PLAYER }] })),
+        ]});
+
+        let event = decode_event(
+            ContractKind::PrizeDistribution,
+            &topics("ArenaXPrize_v1", "EXECUTED"),
+            &value,
+        )
+        .unwrap();
+        match event {
+            Some(NormalizedEvent::Prize(row)) => {
+                assert_eq!(row.pool_id, 3);
+                assert_eq!(row.winners, Some(vec![PLAYER.to_string()]));
+                assert_eq!(row.weights, Some(vec![10000]));
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_decode_reputation_decay_as_negative_delta() {
+        let value = json!({ "map": [
+            entry("fair_play_decayed", json!({ "i128": "2" })),
+            entry("player", json!({ "address": PLAYER })),
+            entry("skill_decayed", json!({ "i128": "15" })),
+        ]});
+
+        let event = decode_event(
+            ContractKind::ReputationIndex,
+            &topics("ArenaXRepIdx_v1", "REPUTATION_DECAYED"),
+            &value,
+        )
+        .unwrap();
+        assert_eq!(
+            event,
+            Some(NormalizedEvent::Reputation(ReputationEventRow {
+                player: PLAYER.to_string(),
+                action: "decayed",
+                skill_delta: -15,
+                fair_play_delta: -2,
+                match_id: None,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_decode_role_revoked() {
+        let value = json!({ "map": [
+            entry("address", json!({ "address": PLAYER })),
+            entry("revoked_by", json!({ "address": ASSET })),
+            entry("role", json!({ "u32": 3 })),
+        ]});
+
+        let event = decode_event(
+            ContractKind::AuthGateway,
+            &topics("ArenaXAuth_v1", "ROLE_REV"),
+            &value,
+        )
+        .unwrap();
+        assert_eq!(
+            event,
+            Some(NormalizedEvent::Role(RoleEventRow {
+                address: PLAYER.to_string(),
+                action: "revoked",
+                role: 3,
+                actor: ASSET.to_string(),
+            }))
+        );
+    }
+
+    #[test]
+    fn test_unnormalized_and_foreign_events_skipped() {
+        let value = json!({ "map": [] });
+        let paused = decode_event(
+            ContractKind::StakingManager,
+            &topics("ArenaXStake_v1", "PAUSED"),
+            &value,
+        )
+        .unwrap();
+        assert_eq!(paused, None);
+
+        let foreign = decode_event(
+            ContractKind::StakingManager,
+            &topics("ArenaXEscrow_v1", "DEPOSIT"),
+            &value,
+        )
+        .unwrap();
+        assert_eq!(foreign, None);
+    }
+
+    #[test]
+    fn test_missing_field_is_decode_error() {
+        let value = json!({ "map": [entry("match_id", json!({ "bytes": "ab01" }))] });
+        let result = decode_event(
+            ContractKind::MatchLifecycle,
+            &topics("ArenaXMLf_v1", "FINALIZED"),
+            &value,
+        );
+        assert!(matches!(result, Err(ChainIndexerError::Decode(_))));
+        assert_eq!(
+            topic_string(&topics("ArenaXMLf_v1", "FINALIZED")),
+            "ArenaXMLf_v1:FINALIZED"
+        );
+    }
+}
@@ -0,0 +1,513 @@
+//! # Chain Indexer
+//!
+//! Background service that tails Soroban RPC `getEvents` for the escrow vault,
+//! match lifecycle, staking manager, prize distribution, auth gateway and
+//! reputation index contracts, decodes their `#[contractevent]` payloads and
+//! writes them to Postgres:
+//!
+//! | Table                     | Contents                                    |
+//! |---------------------------|---------------------------------------------|
+//! | `chain_events`            | Every event, raw topic and JSON payload     |
+//! | `chain_escrow_events`     | Deposits, locks, releases, refunds, slashes |
+//! | `chain_match_events`      | Match creation, results, finalization       |
+//! | `chain_stake_events`      | Tournament stakes, withdrawals, slashes     |
+//! | `chain_prize_events`      | Prize pool creation, payouts, holds         |
+//! | `chain_role_events`       | Auth gateway role grants and revocations    |
+//! | `chain_reputation_events` | Skill and fair-play changes and decays      |
+//! | `chain_account_roles`     | Current on-chain role of each address       |
+//! | `chain_indexer_cursors`   | RPC cursor checkpoints                      |
+//!
+//! The live tail resumes from its checkpoint after a restart. An arbitrary ledger
+//! range can be re-ingested with [`ChainIndexer::backfill`], or the tail rewound
+//! with [`ChainIndexer::replay_from`]; both are safe because events are keyed on
+//! their RPC id. RPC nodes only retain recent ledgers (about a week by default),
+//! so older ranges need an archive RPC endpoint.
+//!
+//! With an [`EventBus`] attached, newly tailed events whose match or tournament
+//! id maps to a platform UUID are also published to that match's or
+//! tournament's realtime channel. Backfills are not published.
+//!
+//! With a [`RoleCache`] attached, the cached roles of addresses named in
+//! indexed role events are dropped, so RBAC picks up grants and revocations on
+//! the next request.
+//!
+//! With a [`LeaderboardService`] attached, reputation changes and prize
+//! payouts update the leaderboards. Crediting is keyed on the event id, so
+//! backfills and replays are applied at most once.
+//!
+//! Escrow and stake slashes are published as
+//! [`DomainEvent::StakeSlashed`](crate::service::domain_events::DomainEvent)
+//! through the outbox, once per chain event.
+
+pub mod decode;
+pub mod rpc;
+pub mod store;
+
+use crate::auth::rbac::RoleCache;
+use crate::config::StellarConfig;
+use crate::db::DbPool;
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
+use crate::service::leaderboard_service::LeaderboardService;
+use chrono::{DateTime, Utc};
+use decode::{decode_event, topic_string, ContractKind, NormalizedEvent};
+use rpc::{EventPage, EventRpcClient, EventStart, RpcEvent};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use store::{Checkpoint, IndexedEvent};
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Checkpoint name of the live tail.
+const LIVE_CHECKPOINT: &str = "live";
+
+/// How often the indexer polls RPC for new events (seconds).
+const DEFAULT_POLL_INTERVAL_SECS: u64 = 5;
+
+/// Events requested per `getEvents` page.
+const DEFAULT_PAGE_LIMIT: u32 = 200;
+
+#[derive(Debug, Error)]
+pub enum ChainIndexerError {
+    #[error("RPC request failed: {0}")]
+    Rpc(String),
+    #[error("Network error: {0}")]
+    Network(#[from] reqwest::Error),
+    #[error("Serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Decode error: {0}")]
+    Decode(String),
+}
+
+#[derive(Debug, Clone)]
+pub struct ChainIndexerConfig {
+    pub rpc_url: String,
+    pub contracts: HashMap<String, ContractKind>,
+    /// Ledger to start from when there is no checkpoint; the latest ledger if unset.
+    pub start_ledger: Option<u64>,
+    pub poll_interval_secs: u64,
+    pub page_limit: u32,
+}
+
+impl ChainIndexerConfig {
+    /// Build the configuration from the Stellar settings. Contracts that are not
+    /// configured are not indexed.
+    pub fn from_stellar_config(stellar: &StellarConfig) -> Self {
+        let mut config = Self {
+            rpc_url: stellar.soroban_rpc_url.clone(),
+            contracts: HashMap::new(),
+            start_ledger: stellar.chain_indexer_start_ledger,
+            poll_interval_secs: DEFAULT_POLL_INTERVAL_SECS,
+            page_limit: DEFAULT_PAGE_LIMIT,
+        };
+        if let Some(escrow) = &stellar.soroban_contract_escrow {
+            config = config.with_contract(escrow, ContractKind::EscrowVault);
+        }
+        if let Some(staking) = &stellar.soroban_contract_staking {
+            config = config.with_contract(staking, ContractKind::StakingManager);
+        }
+        if let Some(gateway) = &stellar.soroban_contract_auth_gateway {
+            config = config.with_contract(gateway, ContractKind::AuthGateway);
+        }
+        config
+            .with_contract(
+                &stellar.soroban_contract_reputation,
+                ContractKind::ReputationIndex,
+            )
+            .with_contract(
+                &stellar.soroban_contract_prize,
+                ContractKind::PrizeDistribution,
+            )
+            .with_contract(
+                &stellar.soroban_contract_match,
+                ContractKind::MatchLifecycle,
+            )
+    }
+
+    /// Follow `contract_id` as a contract of `kind`. A contract id is indexed under
+    /// the first kind it is registered with.
+    pub fn with_contract(mut self, contract_id: &str, kind: ContractKind) -> Self {
+        if let Some(existing) = self.contracts.get(contract_id) {
+            warn!(
+                contract_id,
+                kind = existing.as_str(),
+                ignored = kind.as_str(),
+                "Contract already indexed under another kind"
+            );
+        } else {
+            self.contracts.insert(contract_id.to_string(), kind);
+        }
+        self
+    }
+}
+
+// ============================================================================
+// SERVICE STRUCT
+// ============================================================================
+
+pub struct ChainIndexer {
+    db_pool: DbPool,
+    rpc: EventRpcClient,
+    config: ChainIndexerConfig,
+    contract_ids: Vec<String>,
+    event_bus: Option<EventBus>,
+    role_cache: Option<Arc<RoleCache>>,
+    leaderboard: Option<Arc<LeaderboardService>>,
+}
+
+impl ChainIndexer {
+    pub fn new(db_pool: DbPool, config: ChainIndexerConfig) -> Self {
+        let mut contract_ids: Vec<String> = config.contracts.keys().cloned().collect();
+        contract_ids.sort();
+        Self {
+            db_pool,
+            rpc: EventRpcClient::new(config.rpc_url.clone()),
+            config,
+            contract_ids,
+            event_bus: None,
+            role_cache: None,
+            leaderboard: None,
+        }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    pub fn with_role_cache(mut self, role_cache: Arc<RoleCache>) -> Self {
+        self.role_cache = Some(role_cache);
+        self
+    }
+
+    pub fn with_leaderboard(mut self, leaderboard: Arc<LeaderboardService>) -> Self {
+        self.leaderboard = Some(leaderboard);
+        self
+    }
+
+    // ========================================================================
+    // BACKGROUND TASK
+    // ========================================================================
+
+    /// Spawn the live tail as a detached Tokio task.
+    pub fn run(self: Arc<Self>) {
+        let interval_secs = self.config.poll_interval_secs;
+        tokio::spawn(async move {
+            info!(
+                interval_secs,
+                contracts = self.contract_ids.len(),
+                "Chain indexer started"
+            );
+            let mut ticker = tokio::time::interval(Duration::from_secs(interval_secs));
+            loop {
+                ticker.tick().await;
+                match self.poll().await {
+                    Ok(0) => {}
+                    Ok(inserted) => info!(inserted, "Chain indexer stored new events"),
+                    Err(e) => error!(error = %e, "Chain indexer tick failed"),
+                }
+            }
+        });
+    }
+
+    // ========================================================================
+    // CORE LOGIC
+    // ========================================================================
+
+    /// Ingest everything after the live checkpoint. Returns the number of new events.
+    pub async fn poll(&self) -> Result<u64, ChainIndexerError> {
+        let checkpoint = match store::load_checkpoint(&self.db_pool, LIVE_CHECKPOINT).await? {
+            Some(checkpoint) => checkpoint,
+            None => {
+                let start = match self.config.start_ledger {
+                    Some(ledger) => ledger,
+                    None => self.rpc.get_latest_ledger().await?,
+                };
+                Checkpoint {
+                    cursor: None,
+                    last_ledger: start.saturating_sub(1),
+                }
+            }
+        };
+        self.ingest(LIVE_CHECKPOINT, checkpoint, None).await
+    }
+
+    /// Re-ingest `from_ledger..to_ledger` without moving the live tail. An
+    /// interrupted backfill of the same range resumes where it stopped.
+    pub async fn backfill(
+        &self,
+        from_ledger: u64,
+        to_ledger: u64,
+    ) -> Result<u64, ChainIndexerError> {
+        let name = format!("backfill:{}-{}", from_ledger, to_ledger);
+        let checkpoint = store::load_checkpoint(&self.db_pool, &name)
+            .await?
+            .unwrap_or(Checkpoint {
+                cursor: None,
+                last_ledger: from_ledger.saturating_sub(1),
+            });
+        let inserted = self.ingest(&name, checkpoint, Some(to_ledger)).await?;
+        store::delete_checkpoint(&self.db_pool, &name).await?;
+        info!(
+            from_ledger,
+            to_ledger, inserted, "Chain indexer backfill complete"
+        );
+        Ok(inserted)
+    }
+
+    /// Rewind the live tail so the next poll re-reads from `ledger`.
+    pub async fn replay_from(&self, ledger: u64) -> Result<(), ChainIndexerError> {
+        let checkpoint = Checkpoint {
+            cursor: None,
+            last_ledger: ledger.saturating_sub(1),
+        };
+        store::store_page(&self.db_pool, LIVE_CHECKPOINT, &[], &checkpoint).await?;
+        warn!(ledger, "Chain indexer live tail rewound");
+        Ok(())
+    }
+
+    /// Page through events from `checkpoint` until caught up, saving the
+    /// checkpoint after every page.
+    async fn ingest(
+        &self,
+        name: &str,
+        mut checkpoint: Checkpoint,
+        end_ledger: Option<u64>,
+    ) -> Result<u64, ChainIndexerError> {
+        if self.contract_ids.is_empty() {
+            return Ok(0);
+        }
+        let mut inserted = 0;
+        loop {
+            let start = match &checkpoint.cursor {
+                Some(cursor) => EventStart::Cursor(cursor.clone()),
+                None => EventStart::Ledger(checkpoint.last_ledger + 1),
+            };
+            let page = self
+                .rpc
+                .get_events(
+                    &self.contract_ids,
+                    &start,
+                    end_ledger,
+                    self.config.page_limit,
+                )
+                .await?;
+            let full_page = page.events.len() as u32 >= self.config.page_limit;
+
+            let events = self.index_page(&page);
+            if let Some(cursor) = next_cursor(&page) {
+                checkpoint = Checkpoint {
+                    cursor: Some(cursor),
+                    last_ledger: page
+                        .events
+                        .last()
+                        .map(|e| e.ledger)
+                        .unwrap_or(checkpoint.last_ledger),
+                };
+            }
+            inserted += store::store_page(&self.db_pool, name, &events, &checkpoint).await?;
+            self.invalidate_roles(&events).await;
+            self.update_leaderboards(&events).await;
+            if name == LIVE_CHECKPOINT {
+                self.publish(&events).await;
+            }
+
+            if !full_page {
+                return Ok(inserted);
+            }
+        }
+    }
+
+    fn index_page(&self, page: &EventPage) -> Vec<IndexedEvent> {
+        page.events
+            .iter()
+            .filter(|event| event.in_successful_contract_call)
+            .filter_map(|event| {
+                let kind = *self.config.contracts.get(&event.contract_id)?;
+                Some(self.index_event(kind, event))
+            })
+            .collect()
+    }
+
+    /// Undecodable events are still stored raw so a schema mismatch never stalls
+    /// the tail.
+    fn index_event(&self, kind: ContractKind, event: &RpcEvent) -> IndexedEvent {
+        let normalized =
+            decode_event(kind, &event.topic_json, &event.value_json).unwrap_or_else(|e| {
+                warn!(event_id = %event.id, error = %e, "Failed to decode contract event");
+                None
+            });
+        let ledger_closed_at = DateTime::parse_from_rfc3339(&event.ledger_closed_at)
+            .map(|t| t.with_timezone(&Utc))
+            .unwrap_or_else(|_| Utc::now());
+
+        IndexedEvent {
+            event_id: event.id.clone(),
+            kind,
+            contract_id: event.contract_id.clone(),
+            topic: topic_string(&event.topic_json),
+            ledger: event.ledger,
+            ledger_closed_at,
+            tx_hash: event.tx_hash.clone(),
+            payload: serde_json::json!({
+                "topics": event.topic_json,
+                "value": event.value_json,
+            }),
+            normalized,
+        }
+    }
+}
+
+impl ChainIndexer {
+    /// Fan tailed events out to the realtime channels of the matches and
+    /// tournaments they concern.
+    async fn publish(&self, events: &[IndexedEvent]) {
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+        for event in events {
+            let Some(normalized) = &event.normalized else {
+                continue;
+            };
+            let (action, match_id, tournament_id) = match normalized {
+                NormalizedEvent::Escrow(row) => (row.action, platform_id(&row.match_id), None),
+                NormalizedEvent::Match(row) => (row.action, platform_id(&row.match_id), None),
+                NormalizedEvent::Stake(row) => (row.action, None, platform_id(&row.tournament_id)),
+                NormalizedEvent::Prize(row) => (
+                    row.action,
+                    row.match_id.as_deref().and_then(platform_id),
+                    None,
+                ),
+                NormalizedEvent::Role(_) | NormalizedEvent::Reputation(_) => continue,
+            };
+            let realtime = RealtimeEvent::ChainEvent {
+                contract: event.kind.as_str().to_string(),
+                action: action.to_string(),
+                tx_hash: event.tx_hash.clone(),
+                ledger: event.ledger,
+                timestamp: event.ledger_closed_at.to_rfc3339(),
+            };
+            if let Some(match_id) = match_id {
+                event_bus.publish_to_match(match_id, &realtime).await;
+            }
+            if let Some(tournament_id) = tournament_id {
+                event_bus
+                    .publish_to_tournament(tournament_id, &realtime)
+                    .await;
+            }
+        }
+    }
+}
+
+impl ChainIndexer {
+    /// Drop cached roles of the addresses whose on-chain role changed.
+    async fn invalidate_roles(&self, events: &[IndexedEvent]) {
+        let Some(role_cache) = &self.role_cache else {
+            return;
+        };
+        for event in events {
+            if let Some(NormalizedEvent::Role(row)) = &event.normalized {
+                role_cache.invalidate_address(&row.address).await;
+            }
+        }
+    }
+}
+
+impl ChainIndexer {
+    /// Credit reputation changes and prize payouts to the leaderboards.
+    async fn update_leaderboards(&self, events: &[IndexedEvent]) {
+        let Some(leaderboard) = &self.leaderboard else {
+            return;
+        };
+        if let Err(e) = leaderboard.record_chain_events(events).await {
+            error!(error = %e, "Failed to update leaderboards from chain events");
+        }
+    }
+}
+
+/// Platform UUID behind an on-chain `BytesN<32>` id. The platform derives
+/// those ids by zero-padding a UUID; anything else is not ours.
+pub(crate) fn platform_id(hex_id: &str) -> Option<Uuid> {
+    let bytes = hex::decode(hex_id).ok()?;
+    if bytes.len() != 32 || bytes[16..].iter().any(|b| *b != 0) {
+        return None;
+    }
+    Uuid::from_slice(&bytes[..16]).ok()
+}
+
+/// Cursor to resume after `page`: the RPC-provided cursor, or the id of the last
+/// event for nodes that do not return one.
+fn next_cursor(page: &EventPage) -> Option<String> {
+    page.cursor
+        .clone()
+        .filter(|c| !c.is_empty())
+        .or_else(|| page.events.last().map(|e| e.id.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::service::domain_events::DomainEvent;
+
+    #[test]
+    fn test_platform_id_accepts_only_padded_uuids() {
+        let id = Uuid::new_v4();
+        let mut key = [0u8; 32];
+        key[..16].copy_from_slice(id.as_bytes());
+        assert_eq!(platform_id(&hex::encode(key)), Some(id));
+
+        key[31] = 1;
+        assert_eq!(platform_id(&hex::encode(key)), None);
+        assert_eq!(platform_id("abcd"), None);
+        assert_eq!(platform_id("not hex"), None);
+    }
+
+    #[test]
+    fn test_slashes_become_domain_events() {
+        let mut event = IndexedEvent {
+            event_id: "0000123-0000000001".to_string(),
+            kind: ContractKind::StakingManager,
+            contract_id: "CSTAKE".to_string(),
+            topic: "SLASHED".to_string(),
+            ledger: 123,
+            ledger_closed_at: Utc::now(),
+            tx_hash: "ab".repeat(32),
+            payload: serde_json::json!({}),
+            normalized: Some(NormalizedEvent::Stake(decode::StakeEventRow {
+                tournament_id: "cd".repeat(32),
+                action: "slashed",
+                user_address: "GUSER".to_string(),
+                amount: 2_500_000,
+            })),
+        };
+        match event.slash() {
+            Some(DomainEvent::StakeSlashed {
+                account,
+                amount,
+                tournament_id,
+                match_id,
+                ledger,
+                ..
+            }) => {
+                assert_eq!(account, "GUSER");
+                assert_eq!(amount, "2500000");
+                assert_eq!(tournament_id, Some("cd".repeat(32)));
+                assert_eq!(match_id, None);
+                assert_eq!(ledger, 123);
+            }
+            other => panic!("unexpected {:?}", other),
+        }
+
+        event.normalized = Some(NormalizedEvent::Stake(decode::StakeEventRow {
+            tournament_id: "cd".repeat(32),
+            action: "staked",
+            user_address: "GUSER".to_string(),
+            amount: 2_500_000,
+        }));
+        assert!(event.slash().is_none());
+    }
+}
@@ -0,0 +1,176 @@
+//! Minimal Soroban RPC client for `getEvents`.
+//!
+//! Events are requested with `xdrFormat: "json"` so topics and values come back
+//! as JSON-encoded `ScVal`s, which [`super::decode`] reads without an XDR library.
+
+use super::ChainIndexerError;
+use crate::telemetry::propagation;
+use serde::{Deserialize, Serialize};
+use tracing::Instrument;
+
+/// Soroban RPC accepts at most this many contract ids per filter.
+pub const MAX_CONTRACTS_PER_FILTER: usize = 5;
+
+/// One contract event as returned by `getEvents`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcEvent {
+    pub id: String,
+    pub ledger: u64,
+    pub ledger_closed_at: String,
+    pub contract_id: String,
+    pub tx_hash: String,
+    #[serde(default)]
+    pub in_successful_contract_call: bool,
+    #[serde(default)]
+    pub topic_json: Vec<serde_json::Value>,
+    #[serde(default)]
+    pub value_json: serde_json::Value,
+}
+
+/// A page of events. `cursor` resumes right after the last event of the page.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventPage {
+    #[serde(default)]
+    pub events: Vec<RpcEvent>,
+    pub latest_ledger: u64,
+    #[serde(default)]
+    pub cursor: Option<String>,
+}
+
+/// Where a `getEvents` request starts reading.
+#[derive(Debug, Clone)]
+pub enum EventStart {
+    Ledger(u64),
+    Cursor(String),
+}
+
+#[derive(Debug, Serialize)]
+struct RpcRequest<'a> {
+    jsonrpc: &'a str,
+    id: u64,
+    method: &'a str,
+    params: serde_json::Value,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LatestLedger {
+    sequence: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+#[derive(Clone)]
+pub struct EventRpcClient {
+    rpc_url: String,
+    client: reqwest::Client,
+}
+
+impl EventRpcClient {
+    pub fn new(rpc_url: String) -> Self {
+        Self {
+            rpc_url,
+            client: reqwest::Client::new(),
+        }
+    }
+
+    /// Fetch up to `limit` events emitted by `contract_ids`, starting at `start`
+    /// and, for bounded backfills, stopping before `end_ledger`.
+    pub async fn get_events(
+        &self,
+        contract_ids: &[String],
+        start: &EventStart,
+        end_ledger: Option<u64>,
+        limit: u32,
+    ) -> Result<EventPage, ChainIndexerError> {
+        let filters: Vec<serde_json::Value> = contract_ids
+            .chunks(MAX_CONTRACTS_PER_FILTER)
+            .map(|ids| serde_json::json!({ "type": "contract", "contractIds": ids }))
+            .collect();
+
+        let mut params = serde_json::json!({
+            "filters": filters,
+            "xdrFormat": "json",
+        });
+        let mut pagination = serde_json::json!({ "limit": limit });
+        match start {
+            EventStart::Ledger(ledger) => params["startLedger"] = serde_json::json!(ledger),
+            EventStart::Cursor(cursor) => pagination["cursor"] = serde_json::json!(cursor),
+        }
+        params["pagination"] = pagination;
+        if let Some(end) = end_ledger {
+            params["endLedger"] = serde_json::json!(end);
+        }
+
+        self.rpc_call("getEvents", params).await
+    }
+
+    /// Sequence of the most recent ledger known to the RPC node.
+    pub async fn get_latest_ledger(&self) -> Result<u64, ChainIndexerError> {
+        let latest: LatestLedger = self
+            .rpc_call("getLatestLedger", serde_json::json!({}))
+            .await?;
+        Ok(latest.sequence)
+    }
+
+    async fn rpc_call<T>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T, ChainIndexerError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let request = RpcRequest {
+            jsonrpc: "2.0",
+            id: 1,
+            method,
+            params,
+        };
+        let span = tracing::info_span!(
+            "soroban_rpc",
+            otel.name = %format!("soroban_rpc {}", method),
+            otel.kind = "client",
+            rpc.system = "jsonrpc",
+            rpc.method = method,
+        );
+        let (status, text) = async {
+            let response = propagation::inject_headers(&span, self.client.post(&self.rpc_url))
+                .json(&request)
+                .send()
+                .await?;
+            let status = response.status();
+            Ok::<_, ChainIndexerError>((status, response.text().await?))
+        }
+        .instrument(span.clone())
+        .await?;
+        if !status.is_success() {
+            return Err(ChainIndexerError::Rpc(format!("HTTP {}: {}", status, text)));
+        }
+
+        let rpc_response: RpcResponse<T> = serde_json::from_str(&text)?;
+        match (rpc_response.result, rpc_response.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => Err(ChainIndexerError::Rpc(format!(
+                "RPC error {}: {}",
+                error.code, error.message
+            ))),
+            (None, None) => Err(ChainIndexerError::Rpc(format!(
+                "{} returned neither result nor error",
+                method
+            ))),
+        }
+    }
+}
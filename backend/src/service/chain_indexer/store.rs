@@ -0,0 +1,297 @@
+//! Postgres persistence for indexed events and cursor checkpoints.
+//!
+//! A page of events and the cursor that follows it are written in one
+//! transaction, so a crash never records a cursor past unsaved events. Inserts
+//! are keyed on the RPC event id and skip rows that already exist, which makes
+//! replaying any ledger range safe. Slashes are put in the domain event outbox
+//! in the same transaction, when first stored.
+
+use super::decode::{ContractKind, NormalizedEvent};
+use super::ChainIndexerError;
+use crate::db::DbPool;
+use crate::service::domain_events::{self, DomainEvent};
+use chrono::{DateTime, Utc};
+use sqlx::{Postgres, Transaction};
+
+/// An event ready to be persisted.
+#[derive(Debug, Clone)]
+pub struct IndexedEvent {
+    pub event_id: String,
+    pub kind: ContractKind,
+    pub contract_id: String,
+    pub topic: String,
+    pub ledger: u64,
+    pub ledger_closed_at: DateTime<Utc>,
+    pub tx_hash: String,
+    pub payload: serde_json::Value,
+    pub normalized: Option<NormalizedEvent>,
+}
+
+impl IndexedEvent {
+    /// The [`DomainEvent::StakeSlashed`] reported by an escrow or staking
+    /// slash.
+    pub fn slash(&self) -> Option<DomainEvent> {
+        let (account, amount, asset, match_id, tournament_id) = match &self.normalized {
+            Some(NormalizedEvent::Escrow(row)) if row.action == "slashed" => (
+                row.account.clone()?,
+                row.amount?,
+                row.asset.clone(),
+                Some(row.match_id.clone()),
+                None,
+            ),
+            Some(NormalizedEvent::Stake(row)) if row.action == "slashed" => (
+                row.user_address.clone(),
+                row.amount,
+                None,
+                None,
+                Some(row.tournament_id.clone()),
+            ),
+            _ => return None,
+        };
+        Some(DomainEvent::StakeSlashed {
+            chain_event_id: self.event_id.clone(),
+            account,
+            amount: amount.to_string(),
+            asset,
+            match_id,
+            tournament_id,
+            ledger: self.ledger,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Checkpoint {
+    pub cursor: Option<String>,
+    pub last_ledger: u64,
+}
+
+pub async fn load_checkpoint(
+    pool: &DbPool,
+    name: &str,
+) -> Result<Option<Checkpoint>, ChainIndexerError> {
+    let row: Option<(Option<String>, i64)> =
+        sqlx::query_as("SELECT cursor, last_ledger FROM chain_indexer_cursors WHERE name = $1")
+            .bind(name)
+            .fetch_optional(pool)
+            .await?;
+    Ok(row.map(|(cursor, last_ledger)| Checkpoint {
+        cursor,
+        last_ledger: last_ledger as u64,
+    }))
+}
+
+pub async fn delete_checkpoint(pool: &DbPool, name: &str) -> Result<(), ChainIndexerError> {
+    sqlx::query("DELETE FROM chain_indexer_cursors WHERE name = $1")
+        .bind(name)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Persist `events` and move checkpoint `name` to `checkpoint`. Returns how many
+/// events were new.
+pub async fn store_page(
+    pool: &DbPool,
+    name: &str,
+    events: &[IndexedEvent],
+    checkpoint: &Checkpoint,
+) -> Result<u64, ChainIndexerError> {
+    let mut tx = pool.begin().await?;
+    let mut inserted = 0;
+    for event in events {
+        if insert_event(&mut tx, event).await? {
+            inserted += 1;
+        }
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO chain_indexer_cursors (name, cursor, last_ledger, updated_at)
+        VALUES ($1, $2, $3, NOW())
+        ON CONFLICT (name) DO UPDATE
+            SET cursor      = EXCLUDED.cursor,
+                last_ledger = EXCLUDED.last_ledger,
+                updated_at  = NOW()
+        "#,
+    )
+    .bind(name)
+    .bind(&checkpoint.cursor)
+    .bind(checkpoint.last_ledger as i64)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+    Ok(inserted)
+}
+
+async fn insert_event(
+    tx: &mut Transaction<'_, Postgres>,
+    event: &IndexedEvent,
+) -> Result<bool, ChainIndexerError> {
+    let result = sqlx::query(
+        r#"
+        INSERT INTO chain_events
+            (event_id, contract_id, contract_kind, topic, ledger, ledger_closed_at, tx_hash, payload)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        ON CONFLICT (event_id) DO NOTHING
+        "#,
+    )
+    .bind(&event.event_id)
+    .bind(&event.contract_id)
+    .bind(event.kind.as_str())
+    .bind(&event.topic)
+    .bind(event.ledger as i64)
+    .bind(event.ledger_closed_at)
+    .bind(&event.tx_hash)
+    .bind(&event.payload)
+    .execute(&mut **tx)
+    .await?;
+    if result.rows_affected() == 0 {
+        return Ok(false);
+    }
+
+    let ledger = event.ledger as i64;
+    match &event.normalized {
+        Some(NormalizedEvent::Escrow(row)) => {
+            sqlx::query(
+                r#"
+                INSERT INTO chain_escrow_events
+                    (event_id, match_id, action, account, amount, asset, ledger)
+                VALUES ($1, $2, $3, $4, $5::numeric, $6, $7)
+                "#,
+            )
+            .bind(&event.event_id)
+            .bind(&row.match_id)
+            .bind(row.action)
+            .bind(&row.account)
+            .bind(row.amount.map(|a| a.to_string()))
+            .bind(&row.asset)
+            .bind(ledger)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Some(NormalizedEvent::Match(row)) => {
+            sqlx::query(
+                r#"
+                INSERT INTO chain_match_events
+                    (event_id, match_id, action, account, stake_amount, stake_asset, ledger)
+                VALUES ($1, $2, $3, $4, $5::numeric, $6, $7)
+                "#,
+            )
+            .bind(&event.event_id)
+            .bind(&row.match_id)
+            .bind(row.action)
+            .bind(&row.account)
+            .bind(row.stake_amount.map(|a| a.to_string()))
+            .bind(&row.stake_asset)
+            .bind(ledger)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Some(NormalizedEvent::Stake(row)) => {
+            sqlx::query(
+                r#"
+                INSERT INTO chain_stake_events
+                    (event_id, tournament_id, action, user_address, amount, ledger)
+                VALUES ($1, $2, $3, $4, $5::numeric, $6)
+                "#,
+            )
+            .bind(&event.event_id)
+            .bind(&row.tournament_id)
+            .bind(row.action)
+            .bind(&row.user_address)
+            .bind(row.amount.to_string())
+            .bind(ledger)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Some(NormalizedEvent::Prize(row)) => {
+            sqlx::query(
+                r#"
+                INSERT INTO chain_prize_events
+                    (event_id, pool_id, action, match_id, amount, asset, winners, weights, ledger)
+                VALUES ($1, $2, $3, $4, $5::numeric, $6, $7, $8, $9)
+                "#,
+            )
+            .bind(&event.event_id)
+            .bind(row.pool_id as i64)
+            .bind(row.action)
+            .bind(&row.match_id)
+            .bind(row.amount.map(|a| a.to_string()))
+            .bind(&row.asset)
+            .bind(&row.winners)
+            .bind(
+                row.weights
+                    .as_ref()
+                    .map(|w| w.iter().map(|&w| w as i32).collect::<Vec<_>>()),
+            )
+            .bind(ledger)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Some(NormalizedEvent::Role(row)) => {
+            sqlx::query(
+                r#"
+                INSERT INTO chain_role_events (event_id, address, action, role, actor, ledger)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(&event.event_id)
+            .bind(&row.address)
+            .bind(row.action)
+            .bind(row.role as i32)
+            .bind(&row.actor)
+            .bind(ledger)
+            .execute(&mut **tx)
+            .await?;
+
+            // A backfill may deliver events older than the current role.
+            let role = if row.action == "revoked" {
+                0
+            } else {
+                row.role as i32
+            };
+            sqlx::query(
+                r#"
+                INSERT INTO chain_account_roles (address, role, ledger, updated_at)
+                VALUES ($1, $2, $3, NOW())
+                ON CONFLICT (address) DO UPDATE
+                    SET role       = EXCLUDED.role,
+                        ledger     = EXCLUDED.ledger,
+                        updated_at = NOW()
+                    WHERE chain_account_roles.ledger <= EXCLUDED.ledger
+                "#,
+            )
+            .bind(&row.address)
+            .bind(role)
+            .bind(ledger)
+            .execute(&mut **tx)
+            .await?;
+        }
+        Some(NormalizedEvent::Reputation(row)) => {
+            sqlx::query(
+                r#"
+                INSERT INTO chain_reputation_events
+                    (event_id, player, action, skill_delta, fair_play_delta, match_id, ledger)
+                VALUES ($1, $2, $3, $4::numeric, $5::numeric, $6, $7)
+                "#,
+            )
+            .bind(&event.event_id)
+            .bind(&row.player)
+            .bind(row.action)
+            .bind(row.skill_delta.to_string())
+            .bind(row.fair_play_delta.to_string())
+            .bind(row.match_id.map(|id| id as i64))
+            .bind(ledger)
+            .execute(&mut **tx)
+            .await?;
+        }
+        None => {}
+    }
+
+    if let Some(slash) = event.slash() {
+        domain_events::publish(tx, &slash).await?;
+    }
+    Ok(true)
+}
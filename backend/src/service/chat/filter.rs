@@ -0,0 +1,131 @@
+//! Hooks screening chat messages before they are stored.
+//!
+//! Filters run in the order they were added to the chat service. Each may let
+//! a message through, rewrite it for the filters after it, or reject it.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterVerdict {
+    Allow,
+    /// Store this text instead, e.g. with words masked.
+    Replace(String),
+    /// Refuse the message, telling the sender why.
+    Reject(String),
+}
+
+pub trait MessageFilter: Send + Sync {
+    fn check(&self, sender_id: Uuid, body: &str) -> FilterVerdict;
+}
+
+/// Masks blocked words with asterisks. Words match whole and regardless of
+/// case, so a blocked word inside a longer one is left alone.
+pub struct WordFilter {
+    words: HashSet<String>,
+}
+
+impl WordFilter {
+    pub fn new(words: impl IntoIterator<Item = String>) -> Self {
+        Self {
+            words: words.into_iter().map(|w| w.to_lowercase()).collect(),
+        }
+    }
+
+    fn push_word(&self, out: &mut String, word: &mut String) -> bool {
+        let blocked = self.words.contains(&word.to_lowercase());
+        if blocked {
+            out.extend(std::iter::repeat_n('*', word.chars().count()));
+        } else {
+            out.push_str(word);
+        }
+        word.clear();
+        blocked
+    }
+}
+
+impl MessageFilter for WordFilter {
+    fn check(&self, _sender_id: Uuid, body: &str) -> FilterVerdict {
+        let mut out = String::with_capacity(body.len());
+        let mut word = String::new();
+        let mut masked = false;
+        for c in body.chars() {
+            if c.is_alphanumeric() {
+                word.push(c);
+            } else {
+                masked |= self.push_word(&mut out, &mut word);
+                out.push(c);
+            }
+        }
+        masked |= self.push_word(&mut out, &mut word);
+
+        if masked {
+            FilterVerdict::Replace(out)
+        } else {
+            FilterVerdict::Allow
+        }
+    }
+}
+
+/// Run `body` through `filters`; the text to store, or why it was rejected.
+pub fn screen(
+    filters: &[Arc<dyn MessageFilter>],
+    sender_id: Uuid,
+    body: String,
+) -> Result<String, String> {
+    let mut body = body;
+    for filter in filters {
+        match filter.check(sender_id, &body) {
+            FilterVerdict::Allow => {}
+            FilterVerdict::Replace(text) => body = text,
+            FilterVerdict::Reject(reason) => return Err(reason),
+        }
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct NoLinks;
+
+    impl MessageFilter for NoLinks {
+        fn check(&self, _sender_id: Uuid, body: &str) -> FilterVerdict {
+            if body.contains("http") {
+                FilterVerdict::Reject("Links are not allowed".to_string())
+            } else {
+                FilterVerdict::Allow
+            }
+        }
+    }
+
+    #[test]
+    fn test_word_filter_masks_whole_words_only() {
+        let filter = WordFilter::new(vec!["darn".to_string()]);
+        let sender = Uuid::new_v4();
+        assert_eq!(
+            filter.check(sender, "Darn, that was close. darned lag!"),
+            FilterVerdict::Replace("****, that was close. darned lag!".to_string())
+        );
+        assert_eq!(filter.check(sender, "gg"), FilterVerdict::Allow);
+    }
+
+    #[test]
+    fn test_screen_runs_filters_in_order() {
+        let filters: Vec<Arc<dyn MessageFilter>> = vec![
+            Arc::new(WordFilter::new(vec!["darn".to_string()])),
+            Arc::new(NoLinks),
+        ];
+        let sender = Uuid::new_v4();
+        assert_eq!(
+            screen(&filters, sender, "darn it".to_string()),
+            Ok("**** it".to_string())
+        );
+        assert_eq!(
+            screen(&filters, sender, "see http://x".to_string()),
+            Err("Links are not allowed".to_string())
+        );
+    }
+}
@@ -0,0 +1,598 @@
+//! Chat in match lobbies, tournament channels and direct messages.
+//!
+//! Messages are stored in Postgres and delivered over the WebSocket gateway on
+//! the topic of the channel they were posted in: `match:{id}` to its players,
+//! `tournament:{id}` to anyone following the tournament, and DMs to both
+//! users' private `user:{id}` topics. History is paged newest first with a
+//! `before` cursor.
+//!
+//! Posting is rate limited per user in Redis and screened by the configured
+//! [`MessageFilter`]s. Moderators (and admins) delete messages and mute users
+//! in one channel or everywhere; senders may delete their own messages.
+
+pub mod filter;
+
+pub use filter::{FilterVerdict, MessageFilter, WordFilter};
+
+use crate::api_error::ApiError;
+use crate::config::ChatConfig;
+use crate::db::DbPool;
+use crate::models::{ChatChannel, ChatHistory, ChatMessage, ChatMute, MuteChatUserRequest};
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
+use chrono::{Duration, Utc};
+use redis::aio::ConnectionManager;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::info;
+use uuid::Uuid;
+
+pub const DEFAULT_HISTORY_LIMIT: i64 = 50;
+pub const MAX_HISTORY_LIMIT: i64 = 100;
+const RATE_KEY_PREFIX: &str = "chat:rate";
+
+const MESSAGE_SELECT: &str = "SELECT m.id, m.channel, m.sender_id, u.username AS sender_username, \
+     m.body, m.created_at \
+     FROM chat_messages m \
+     JOIN users u ON u.id = m.sender_id";
+
+const MUTE_COLUMNS: &str = "id, user_id, channel, muted_by, reason, expires_at, created_at";
+
+#[derive(Debug, Error)]
+pub enum ChatError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("{0}")]
+    Rejected(String),
+    #[error("Chat channel not found")]
+    ChannelNotFound,
+    #[error("Message not found")]
+    MessageNotFound,
+    #[error("Mute not found")]
+    MuteNotFound,
+    #[error("You cannot access this chat channel")]
+    Forbidden,
+    #[error("You can only message your friends")]
+    NotFriends,
+    #[error("You are muted in this channel")]
+    Muted,
+    #[error("You are sending messages too fast; try again in {retry_after}s")]
+    RateLimited { retry_after: u64 },
+    #[error("Rate limit check failed: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<ChatError> for ApiError {
+    fn from(err: ChatError) -> Self {
+        match err {
+            ChatError::InvalidRequest(_) | ChatError::Rejected(_) => {
+                ApiError::BadRequest(err.to_string())
+            }
+            ChatError::ChannelNotFound | ChatError::MessageNotFound | ChatError::MuteNotFound => {
+                ApiError::NotFound
+            }
+            ChatError::Forbidden | ChatError::NotFriends | ChatError::Muted => ApiError::Forbidden,
+            ChatError::RateLimited { .. } => ApiError::TooManyRequests(err.to_string()),
+            ChatError::Redis(e) => ApiError::RedisError(e.to_string()),
+            ChatError::DatabaseError(e) => ApiError::DatabaseError(e),
+        }
+    }
+}
+
+/// Who is acting on a chat request.
+#[derive(Debug, Clone, Copy)]
+pub struct ChatActor {
+    pub user_id: Uuid,
+    /// Holds the `moderator` or `admin` role.
+    pub is_moderator: bool,
+}
+
+pub struct ChatService {
+    db_pool: DbPool,
+    redis: ConnectionManager,
+    rate_limit_messages: u32,
+    rate_limit_window: u64,
+    filters: Vec<Arc<dyn MessageFilter>>,
+    event_bus: Option<EventBus>,
+}
+
+impl ChatService {
+    pub fn new(db_pool: DbPool, redis: ConnectionManager, config: &ChatConfig) -> Self {
+        let mut filters: Vec<Arc<dyn MessageFilter>> = Vec::new();
+        if !config.blocked_words.is_empty() {
+            filters.push(Arc::new(WordFilter::new(config.blocked_words.clone())));
+        }
+        Self {
+            db_pool,
+            redis,
+            rate_limit_messages: config.rate_limit_messages,
+            rate_limit_window: config.rate_limit_window,
+            filters,
+            event_bus: None,
+        }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Screen messages with `filter` too, after those already added.
+    pub fn with_filter(mut self, filter: Arc<dyn MessageFilter>) -> Self {
+        self.filters.push(filter);
+        self
+    }
+
+    // ========================================================================
+    // MESSAGES
+    // ========================================================================
+
+    /// A page of `channel`'s messages, newest first, older than `before`.
+    pub async fn history(
+        &self,
+        actor: ChatActor,
+        channel: ChatChannel,
+        before: Option<Uuid>,
+        limit: Option<i64>,
+    ) -> Result<ChatHistory, ChatError> {
+        self.authorize_read(actor, channel).await?;
+        let limit = limit
+            .unwrap_or(DEFAULT_HISTORY_LIMIT)
+            .clamp(1, MAX_HISTORY_LIMIT);
+
+        let messages = sqlx::query_as::<_, ChatMessage>(&format!(
+            "{} WHERE m.channel = $1 AND m.deleted_at IS NULL \
+             AND ($2::uuid IS NULL OR (m.created_at, m.id) < \
+                 (SELECT c.created_at, c.id FROM chat_messages c \
+                  WHERE c.id = $2 AND c.channel = $1)) \
+             ORDER BY m.created_at DESC, m.id DESC \
+             LIMIT $3",
+            MESSAGE_SELECT
+        ))
+        .bind(channel.to_string())
+        .bind(before)
+        .bind(limit + 1)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(page(messages, limit as usize))
+    }
+
+    /// Post `body` in `channel` as `actor`.
+    pub async fn send(
+        &self,
+        actor: ChatActor,
+        channel: ChatChannel,
+        body: &str,
+    ) -> Result<ChatMessage, ChatError> {
+        let body = body.trim();
+        if body.is_empty() {
+            return Err(ChatError::InvalidRequest(
+                "Message cannot be empty".to_string(),
+            ));
+        }
+        self.authorize_post(actor, channel).await?;
+        if self.is_muted(actor.user_id, channel).await? {
+            return Err(ChatError::Muted);
+        }
+        self.check_rate_limit(actor.user_id).await?;
+        let body = filter::screen(&self.filters, actor.user_id, body.to_string())
+            .map_err(ChatError::Rejected)?;
+
+        let message = sqlx::query_as::<_, ChatMessage>(
+            r#"
+            WITH inserted AS (
+                INSERT INTO chat_messages (channel, sender_id, body)
+                VALUES ($1, $2, $3)
+                RETURNING id, channel, sender_id, body, created_at
+            )
+            SELECT i.id, i.channel, i.sender_id, u.username AS sender_username,
+                   i.body, i.created_at
+            FROM inserted i
+            JOIN users u ON u.id = i.sender_id
+            "#,
+        )
+        .bind(channel.to_string())
+        .bind(actor.user_id)
+        .bind(&body)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        let event = RealtimeEvent::ChatMessage {
+            message_id: message.id,
+            channel: message.channel.clone(),
+            sender_id: message.sender_id,
+            sender_username: message.sender_username.clone(),
+            body: message.body.clone(),
+            timestamp: message.created_at.to_rfc3339(),
+        };
+        self.publish(channel, &event).await;
+        Ok(message)
+    }
+
+    /// Remove a message; its sender or a moderator may.
+    pub async fn delete_message(
+        &self,
+        actor: ChatActor,
+        message_id: Uuid,
+    ) -> Result<(), ChatError> {
+        let row: Option<(String, Uuid)> = sqlx::query_as(
+            "SELECT channel, sender_id FROM chat_messages WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(message_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        let (channel, sender_id) = row.ok_or(ChatError::MessageNotFound)?;
+        if sender_id != actor.user_id && !actor.is_moderator {
+            return Err(ChatError::Forbidden);
+        }
+
+        let deleted = sqlx::query(
+            "UPDATE chat_messages SET deleted_at = NOW(), deleted_by = $2 \
+             WHERE id = $1 AND deleted_at IS NULL",
+        )
+        .bind(message_id)
+        .bind(actor.user_id)
+        .execute(&self.db_pool)
+        .await?;
+        if deleted.rows_affected() == 0 {
+            return Err(ChatError::MessageNotFound);
+        }
+        if sender_id != actor.user_id {
+            info!(
+                message_id = %message_id,
+                moderator_id = %actor.user_id,
+                "Chat message removed by moderator"
+            );
+        }
+
+        if let Ok(parsed) = channel.parse::<ChatChannel>() {
+            let event = RealtimeEvent::ChatMessageDeleted {
+                message_id,
+                channel,
+                timestamp: Utc::now().to_rfc3339(),
+            };
+            self.publish(parsed, &event).await;
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // MODERATION
+    // ========================================================================
+
+    pub async fn mute(
+        &self,
+        actor: ChatActor,
+        req: &MuteChatUserRequest,
+    ) -> Result<ChatMute, ChatError> {
+        if !actor.is_moderator {
+            return Err(ChatError::Forbidden);
+        }
+        if req.user_id == actor.user_id {
+            return Err(ChatError::InvalidRequest(
+                "You cannot mute yourself".to_string(),
+            ));
+        }
+        let channel = req
+            .channel
+            .as_deref()
+            .map(|c| c.parse::<ChatChannel>().map(|c| c.to_string()))
+            .transpose()
+            .map_err(ChatError::InvalidRequest)?;
+        let expires_at = req
+            .duration_secs
+            .map(|secs| Utc::now() + Duration::seconds(secs));
+
+        let mute = sqlx::query_as::<_, ChatMute>(&format!(
+            "INSERT INTO chat_mutes (user_id, channel, muted_by, reason, expires_at) \
+             SELECT id, $2, $3, $4, $5 FROM users WHERE id = $1 \
+             RETURNING {}",
+            MUTE_COLUMNS
+        ))
+        .bind(req.user_id)
+        .bind(&channel)
+        .bind(actor.user_id)
+        .bind(&req.reason)
+        .bind(expires_at)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| ChatError::InvalidRequest("User not found".to_string()))?;
+
+        info!(
+            user_id = %mute.user_id,
+            moderator_id = %actor.user_id,
+            channel = ?mute.channel,
+            "User muted in chat"
+        );
+        Ok(mute)
+    }
+
+    /// Mutes in force, of `user_id` or of everyone.
+    pub async fn mutes(
+        &self,
+        actor: ChatActor,
+        user_id: Option<Uuid>,
+    ) -> Result<Vec<ChatMute>, ChatError> {
+        if !actor.is_moderator {
+            return Err(ChatError::Forbidden);
+        }
+        let mutes = sqlx::query_as::<_, ChatMute>(&format!(
+            "SELECT {} FROM chat_mutes \
+             WHERE lifted_at IS NULL AND (expires_at IS NULL OR expires_at > NOW()) \
+             AND ($1::uuid IS NULL OR user_id = $1) \
+             ORDER BY created_at DESC",
+            MUTE_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(mutes)
+    }
+
+    pub async fn lift_mute(&self, actor: ChatActor, mute_id: Uuid) -> Result<(), ChatError> {
+        if !actor.is_moderator {
+            return Err(ChatError::Forbidden);
+        }
+        let lifted = sqlx::query(
+            "UPDATE chat_mutes SET lifted_at = NOW(), lifted_by = $2 \
+             WHERE id = $1 AND lifted_at IS NULL \
+             AND (expires_at IS NULL OR expires_at > NOW())",
+        )
+        .bind(mute_id)
+        .bind(actor.user_id)
+        .execute(&self.db_pool)
+        .await?;
+        if lifted.rows_affected() == 0 {
+            return Err(ChatError::MuteNotFound);
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // ACCESS
+    // ========================================================================
+
+    /// Match lobbies are readable by their players, tournament channels by
+    /// anyone and DMs by the two users. Moderators read lobbies too.
+    async fn authorize_read(
+        &self,
+        actor: ChatActor,
+        channel: ChatChannel,
+    ) -> Result<(), ChatError> {
+        match channel {
+            ChatChannel::Match(match_id) => self.authorize_match(actor, match_id).await,
+            ChatChannel::Tournament(tournament_id) => {
+                let exists: bool =
+                    sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM tournaments WHERE id = $1)")
+                        .bind(tournament_id)
+                        .fetch_one(&self.db_pool)
+                        .await?;
+                if exists {
+                    Ok(())
+                } else {
+                    Err(ChatError::ChannelNotFound)
+                }
+            }
+            ChatChannel::Direct(a, b) => {
+                if actor.user_id == a || actor.user_id == b {
+                    Ok(())
+                } else {
+                    Err(ChatError::Forbidden)
+                }
+            }
+        }
+    }
+
+    /// Tournament channels take posts from participants, lineup members and
+    /// the organiser; DMs only between accepted friends.
+    async fn authorize_post(
+        &self,
+        actor: ChatActor,
+        channel: ChatChannel,
+    ) -> Result<(), ChatError> {
+        self.authorize_read(actor, channel).await?;
+        match channel {
+            ChatChannel::Match(_) => Ok(()),
+            ChatChannel::Tournament(tournament_id) => {
+                if actor.is_moderator {
+                    return Ok(());
+                }
+                let involved: bool = sqlx::query_scalar(
+                    r#"
+                    SELECT EXISTS (
+                        SELECT 1 FROM tournament_participants
+                        WHERE tournament_id = $1 AND user_id = $2
+                        UNION ALL
+                        SELECT 1 FROM tournament_team_lineups
+                        WHERE tournament_id = $1 AND user_id = $2
+                        UNION ALL
+                        SELECT 1 FROM tournaments
+                        WHERE id = $1 AND created_by = $2
+                    )
+                    "#,
+                )
+                .bind(tournament_id)
+                .bind(actor.user_id)
+                .fetch_one(&self.db_pool)
+                .await?;
+                if involved {
+                    Ok(())
+                } else {
+                    Err(ChatError::Forbidden)
+                }
+            }
+            ChatChannel::Direct(a, b) => {
+                if a == b {
+                    return Err(ChatError::InvalidRequest(
+                        "You cannot message yourself".to_string(),
+                    ));
+                }
+                let friends: bool = sqlx::query_scalar(
+                    r#"
+                    SELECT EXISTS (
+                        SELECT 1 FROM friends
+                        WHERE status = 'accepted'
+                          AND ((user_id = $1 AND friend_id = $2)
+                            OR (user_id = $2 AND friend_id = $1))
+                    )
+                    "#,
+                )
+                .bind(a)
+                .bind(b)
+                .fetch_one(&self.db_pool)
+                .await?;
+                if friends {
+                    Ok(())
+                } else {
+                    Err(ChatError::NotFriends)
+                }
+            }
+        }
+    }
+
+    async fn authorize_match(&self, actor: ChatActor, match_id: Uuid) -> Result<(), ChatError> {
+        let (exists, is_player): (bool, bool) = sqlx::query_as(
+            r#"
+            SELECT
+                EXISTS (SELECT 1 FROM matches WHERE id = $1)
+                    OR EXISTS (SELECT 1 FROM match_authority WHERE id = $1),
+                EXISTS (
+                    SELECT 1 FROM matches
+                    WHERE id = $1 AND (player1_id = $2 OR player2_id = $2)
+                ) OR EXISTS (
+                    SELECT 1 FROM match_authority
+                    WHERE id = $1 AND (player_a = $2::text OR player_b = $2::text)
+                )
+            "#,
+        )
+        .bind(match_id)
+        .bind(actor.user_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        if !exists {
+            Err(ChatError::ChannelNotFound)
+        } else if is_player || actor.is_moderator {
+            Ok(())
+        } else {
+            Err(ChatError::Forbidden)
+        }
+    }
+
+    async fn is_muted(&self, user_id: Uuid, channel: ChatChannel) -> Result<bool, ChatError> {
+        let muted: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM chat_mutes
+                WHERE user_id = $1
+                  AND (channel IS NULL OR channel = $2)
+                  AND lifted_at IS NULL
+                  AND (expires_at IS NULL OR expires_at > NOW())
+            )
+            "#,
+        )
+        .bind(user_id)
+        .bind(channel.to_string())
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(muted)
+    }
+
+    /// Fixed window counter: the first message of a window starts its expiry.
+    async fn check_rate_limit(&self, user_id: Uuid) -> Result<(), ChatError> {
+        let key = format!("{}:{}", RATE_KEY_PREFIX, user_id);
+        let mut conn = self.redis.clone();
+        let (count, ttl): (u32, i64) = redis::pipe()
+            .atomic()
+            .cmd("SET")
+            .arg(&key)
+            .arg(0)
+            .arg("EX")
+            .arg(self.rate_limit_window)
+            .arg("NX")
+            .ignore()
+            .incr(&key, 1)
+            .ttl(&key)
+            .query_async(&mut conn)
+            .await?;
+
+        if count > self.rate_limit_messages {
+            return Err(ChatError::RateLimited {
+                retry_after: ttl.max(1) as u64,
+            });
+        }
+        Ok(())
+    }
+
+    async fn publish(&self, channel: ChatChannel, event: &RealtimeEvent) {
+        let Some(bus) = &self.event_bus else {
+            return;
+        };
+        match channel {
+            ChatChannel::Match(match_id) => bus.publish_to_match(match_id, event).await,
+            ChatChannel::Tournament(tournament_id) => {
+                bus.publish_to_tournament(tournament_id, event).await
+            }
+            ChatChannel::Direct(a, b) => {
+                bus.publish_to_user(a, event).await;
+                bus.publish_to_user(b, event).await;
+            }
+        }
+    }
+}
+
+/// Trim a page fetched with one extra row; the extra row means there is more.
+fn page(mut messages: Vec<ChatMessage>, limit: usize) -> ChatHistory {
+    let has_more = messages.len() > limit;
+    messages.truncate(limit);
+    let next_before = if has_more {
+        messages.last().map(|m| m.id)
+    } else {
+        None
+    };
+    ChatHistory {
+        messages,
+        next_before,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(minutes_ago: i64) -> ChatMessage {
+        ChatMessage {
+            id: Uuid::new_v4(),
+            channel: ChatChannel::Match(Uuid::nil()).to_string(),
+            sender_id: Uuid::new_v4(),
+            sender_username: "player".to_string(),
+            body: "gg".to_string(),
+            created_at: Utc::now() - Duration::minutes(minutes_ago),
+        }
+    }
+
+    #[test]
+    fn test_page_cursor_points_at_oldest_returned_message() {
+        let messages: Vec<ChatMessage> = (0..3).map(message).collect();
+        let oldest_kept = messages[1].id;
+
+        let history = page(messages.clone(), 2);
+        assert_eq!(history.messages.len(), 2);
+        assert_eq!(history.next_before, Some(oldest_kept));
+
+        let last = page(messages, 3);
+        assert_eq!(last.messages.len(), 3);
+        assert_eq!(last.next_before, None);
+    }
+
+    #[test]
+    fn test_direct_channel_is_the_same_from_either_side() {
+        let (a, b) = (Uuid::new_v4(), Uuid::new_v4());
+        let channel = ChatChannel::direct(a, b);
+        assert_eq!(channel, ChatChannel::direct(b, a));
+        assert_eq!(channel.to_string().parse::<ChatChannel>(), Ok(channel));
+    }
+}
@@ -0,0 +1,966 @@
+//! Dispute cases and the referee console.
+//!
+//! Players open a dispute on a match and attach screenshot/replay evidence,
+//! which is stored in object storage like result evidence, or attach files
+//! uploaded through the media service. Referees pick cases from the open queue
+//! (or are assigned by an admin), review them and submit a decision. Every
+//! action is appended to the case's audit history.
+//!
+//! When the dispute resolution contract is configured, each case is mirrored
+//! on chain through the relayer: opened with the platform account as opener,
+//! then claimed and resolved by the platform account once the referee decides.
+//! The platform account must therefore be an operator on the contract.
+
+use crate::api_error::ApiError;
+use crate::config::StellarConfig;
+use crate::db::DbPool;
+use crate::models::match_models::Match;
+use crate::models::{
+    DisputeCase, DisputeCaseStatus, DisputeDecision, DisputeDecisionRequest, DisputeDetail,
+    DisputeEvent, DisputeEvidence, EvidenceUpload, MediaObject, MediaStatus, OpenDisputeRequest,
+};
+use crate::service::match_result_service::{decode_evidence, evidence_hash, kind_str};
+use crate::service::matchmaking::match_key;
+use crate::service::object_storage::ObjectStorage;
+use crate::service::stellar_relayer::xdr::ScArg;
+use crate::service::stellar_relayer::StellarRelayer;
+use chrono::Utc;
+use serde_json::json;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const MAX_REASON_LEN: usize = 2000;
+
+/// Who is acting on a case, taken from the caller's token.
+#[derive(Debug, Clone, Copy)]
+pub struct DisputeActor {
+    pub user_id: Uuid,
+    pub is_referee: bool,
+    pub is_admin: bool,
+}
+
+impl DisputeActor {
+    fn can_referee(&self) -> bool {
+        self.is_referee || self.is_admin
+    }
+}
+
+pub struct DisputeService {
+    db_pool: DbPool,
+    storage: Arc<ObjectStorage>,
+    relayer: Option<Arc<StellarRelayer>>,
+    dispute_contract: Option<String>,
+}
+
+impl DisputeService {
+    pub fn new(db_pool: DbPool, storage: Arc<ObjectStorage>) -> Self {
+        Self {
+            db_pool,
+            storage,
+            relayer: None,
+            dispute_contract: None,
+        }
+    }
+
+    /// Mirror cases on the dispute resolution contract, if one is configured.
+    pub fn with_relayer(mut self, relayer: Arc<StellarRelayer>, stellar: &StellarConfig) -> Self {
+        self.relayer = Some(relayer);
+        self.dispute_contract = stellar.soroban_contract_dispute.clone();
+        self
+    }
+
+    // ========================================================================
+    // PLAYERS
+    // ========================================================================
+
+    /// Open a dispute on a match the caller played in.
+    pub async fn open(
+        &self,
+        actor: DisputeActor,
+        request: OpenDisputeRequest,
+    ) -> Result<DisputeDetail, ApiError> {
+        let reason = request.reason.trim();
+        if reason.is_empty() || reason.len() > MAX_REASON_LEN {
+            return Err(ApiError::bad_request(format!(
+                "Reason must be between 1 and {} characters",
+                MAX_REASON_LEN
+            )));
+        }
+        let files = if request.evidence.is_empty() {
+            Vec::new()
+        } else {
+            decode_evidence(&request.evidence)?
+        };
+
+        let match_record = self.get_match(request.match_id).await?;
+        if !is_participant(&match_record, actor.user_id) {
+            return Err(ApiError::forbidden("Only match players can open a dispute"));
+        }
+
+        let existing: Option<Uuid> =
+            sqlx::query_scalar("SELECT id FROM match_disputes WHERE match_id = $1")
+                .bind(request.match_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        if existing.is_some() {
+            return Err(ApiError::conflict("This match already has a dispute"));
+        }
+
+        // The evidence reference sent on chain, kept so the stored files can be
+        // checked against it later.
+        let hashes: Vec<String> = files.iter().map(|f| f.sha256.clone()).collect();
+        let evidence_ref = if hashes.is_empty() {
+            None
+        } else {
+            let hashes: Vec<&str> = hashes.iter().map(String::as_str).collect();
+            Some(evidence_hash(&hashes))
+        };
+
+        let dispute_id = Uuid::new_v4();
+        let chain_status = if self.chain().is_some() {
+            "pending"
+        } else {
+            "off_chain"
+        };
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        sqlx::query(
+            r#"
+            INSERT INTO match_disputes (
+                id, match_id, disputing_player_id, reason, status, category,
+                chain_status, evidence_ref, evidence_hashes, created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW(), NOW())
+            "#,
+        )
+        .bind(dispute_id)
+        .bind(request.match_id)
+        .bind(actor.user_id)
+        .bind(reason)
+        .bind(DisputeCaseStatus::Open)
+        .bind(request.category.as_u32() as i32)
+        .bind(chain_status)
+        .bind(&evidence_ref)
+        .bind(&hashes)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        record_event(
+            &mut tx,
+            dispute_id,
+            Some(actor.user_id),
+            "opened",
+            json!({ "reason": reason, "category": request.category }),
+        )
+        .await?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        if !files.is_empty() {
+            self.store_evidence(dispute_id, actor.user_id, files)
+                .await?;
+        }
+
+        if let Some(chain) = self.chain() {
+            let evidence_ref = evidence_ref.unwrap_or_default();
+            let (match_id, category, reason) = (
+                request.match_id,
+                request.category.as_u32(),
+                reason.to_string(),
+            );
+            // Relayed calls wait for ledger inclusion; don't hold up the response.
+            tokio::spawn(async move {
+                chain
+                    .open(dispute_id, match_id, category, &reason, &evidence_ref)
+                    .await;
+            });
+        }
+
+        info!(dispute_id = %dispute_id, match_id = %request.match_id, "Dispute opened");
+        self.detail(dispute_id).await
+    }
+
+    /// Attach more evidence to a case that is still under review.
+    pub async fn attach_evidence(
+        &self,
+        actor: DisputeActor,
+        dispute_id: Uuid,
+        uploads: &[EvidenceUpload],
+    ) -> Result<Vec<DisputeEvidence>, ApiError> {
+        let files = decode_evidence(uploads)?;
+        self.ensure_can_attach(actor, dispute_id).await?;
+        self.store_evidence(dispute_id, actor.user_id, files).await
+    }
+
+    /// Attach the caller's verified media uploads to a case that is still
+    /// under review. They are kept for as long as the case is open.
+    pub async fn attach_media(
+        &self,
+        actor: DisputeActor,
+        dispute_id: Uuid,
+        media_ids: &[Uuid],
+    ) -> Result<Vec<DisputeEvidence>, ApiError> {
+        self.ensure_can_attach(actor, dispute_id).await?;
+        let mut media_ids = media_ids.to_vec();
+        media_ids.sort();
+        media_ids.dedup();
+        let media = sqlx::query_as::<_, MediaObject>(
+            "SELECT * FROM media_objects WHERE id = ANY($1) AND owner_id = $2 AND status = $3",
+        )
+        .bind(&media_ids)
+        .bind(actor.user_id)
+        .bind(MediaStatus::Ready)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        if media.len() != media_ids.len() {
+            return Err(ApiError::bad_request(
+                "Only your own verified uploads can be attached",
+            ));
+        }
+
+        let stored = media
+            .into_iter()
+            .map(|m| DisputeEvidence {
+                id: Uuid::new_v4(),
+                dispute_id,
+                uploaded_by: actor.user_id,
+                kind: m.kind,
+                content_type: m.content_type,
+                size_bytes: m.size_bytes,
+                sha256: m.sha256,
+                url: self.storage.object_url(&m.object_key),
+                object_key: m.object_key,
+                created_at: Utc::now(),
+            })
+            .collect();
+        let attached = self
+            .record_evidence(dispute_id, actor.user_id, stored)
+            .await?;
+        // Held from the retention sweep until the case is closed.
+        sqlx::query(
+            "UPDATE media_objects SET dispute_id = $1, updated_at = NOW() WHERE id = ANY($2)",
+        )
+        .bind(dispute_id)
+        .bind(&media_ids)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        Ok(attached)
+    }
+
+    /// A case with its evidence and history. Visible to the match players and
+    /// to referees.
+    pub async fn get(
+        &self,
+        actor: DisputeActor,
+        dispute_id: Uuid,
+    ) -> Result<DisputeDetail, ApiError> {
+        let case = self.get_case(dispute_id).await?;
+        if !actor.can_referee() {
+            let match_record = self.get_match(case.match_id).await?;
+            if !is_participant(&match_record, actor.user_id) {
+                return Err(ApiError::forbidden("Not a party to this dispute"));
+            }
+        }
+        self.detail(dispute_id).await
+    }
+
+    // ========================================================================
+    // REFEREE CONSOLE
+    // ========================================================================
+
+    /// Open cases nobody has picked up yet, escalated ones first, then oldest
+    /// first.
+    pub async fn queue(
+        &self,
+        actor: DisputeActor,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<DisputeCase>, ApiError> {
+        require_referee(actor)?;
+        sqlx::query_as::<_, DisputeCase>(
+            r#"
+            SELECT * FROM match_disputes
+            WHERE status = $1 AND admin_reviewer_id IS NULL
+            ORDER BY escalated_at IS NULL, created_at ASC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(DisputeCaseStatus::Open)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    /// Cases assigned to the calling referee, optionally filtered by status.
+    pub async fn assigned(
+        &self,
+        actor: DisputeActor,
+        status: Option<DisputeCaseStatus>,
+    ) -> Result<Vec<DisputeCase>, ApiError> {
+        require_referee(actor)?;
+        sqlx::query_as::<_, DisputeCase>(
+            r#"
+            SELECT * FROM match_disputes
+            WHERE admin_reviewer_id = $1 AND ($2::INTEGER IS NULL OR status = $2)
+            ORDER BY updated_at DESC
+            "#,
+        )
+        .bind(actor.user_id)
+        .bind(status.map(|s| s as i32))
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    /// Assign a case. Referees take open cases for themselves; admins may
+    /// (re)assign any undecided case to any referee.
+    pub async fn assign(
+        &self,
+        actor: DisputeActor,
+        dispute_id: Uuid,
+        referee_id: Option<Uuid>,
+    ) -> Result<DisputeCase, ApiError> {
+        require_referee(actor)?;
+        let referee_id = referee_id.unwrap_or(actor.user_id);
+        if referee_id != actor.user_id && !actor.is_admin {
+            return Err(ApiError::forbidden(
+                "Only admins can assign cases to others",
+            ));
+        }
+
+        let case = self.get_case(dispute_id).await?;
+        let match_record = self.get_match(case.match_id).await?;
+        if is_participant(&match_record, referee_id) {
+            return Err(ApiError::bad_request(
+                "A match player cannot referee their own dispute",
+            ));
+        }
+
+        // Referees may only take unassigned cases; admins may reassign.
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let updated = sqlx::query_as::<_, DisputeCase>(
+            r#"
+            UPDATE match_disputes
+            SET admin_reviewer_id = $2, status = $3, updated_at = NOW()
+            WHERE id = $1
+              AND status IN ($4, $3)
+              AND (admin_reviewer_id IS NULL OR $5)
+            RETURNING *
+            "#,
+        )
+        .bind(dispute_id)
+        .bind(referee_id)
+        .bind(DisputeCaseStatus::UnderReview)
+        .bind(DisputeCaseStatus::Open)
+        .bind(actor.is_admin)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::conflict("Dispute is already assigned or closed"))?;
+        record_event(
+            &mut tx,
+            dispute_id,
+            Some(actor.user_id),
+            "assigned",
+            json!({ "referee_id": referee_id, "previous": case.admin_reviewer_id }),
+        )
+        .await?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(dispute_id = %dispute_id, referee_id = %referee_id, "Dispute assigned");
+        Ok(updated)
+    }
+
+    /// Escalate an undecided case (admins only), optionally handing it to
+    /// another referee.
+    pub async fn escalate(
+        &self,
+        actor: DisputeActor,
+        dispute_id: Uuid,
+        reason: &str,
+        referee_id: Option<Uuid>,
+    ) -> Result<DisputeCase, ApiError> {
+        if !actor.is_admin {
+            return Err(ApiError::forbidden("Only admins can escalate disputes"));
+        }
+        let case = self.get_case(dispute_id).await?;
+        if let Some(referee_id) = referee_id {
+            let match_record = self.get_match(case.match_id).await?;
+            if is_participant(&match_record, referee_id) {
+                return Err(ApiError::bad_request(
+                    "A match player cannot referee their own dispute",
+                ));
+            }
+        }
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let updated = sqlx::query_as::<_, DisputeCase>(
+            r#"
+            UPDATE match_disputes
+            SET escalated_at = NOW(), escalated_by = $2, escalation_reason = $3,
+                admin_reviewer_id = COALESCE($4, admin_reviewer_id),
+                status = CASE WHEN $4::UUID IS NULL THEN status ELSE $5 END,
+                updated_at = NOW()
+            WHERE id = $1 AND status IN ($6, $5)
+            RETURNING *
+            "#,
+        )
+        .bind(dispute_id)
+        .bind(actor.user_id)
+        .bind(reason)
+        .bind(referee_id)
+        .bind(DisputeCaseStatus::UnderReview)
+        .bind(DisputeCaseStatus::Open)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::conflict("Dispute is already closed"))?;
+        record_event(
+            &mut tx,
+            dispute_id,
+            Some(actor.user_id),
+            "escalated",
+            json!({
+                "reason": reason,
+                "referee_id": referee_id,
+                "previous": case.admin_reviewer_id,
+            }),
+        )
+        .await?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(dispute_id = %dispute_id, "Dispute escalated");
+        Ok(updated)
+    }
+
+    /// Record the assigned referee's decision and, for cases mirrored on
+    /// chain, have the relayer execute it on the dispute resolution contract.
+    pub async fn decide(
+        &self,
+        actor: DisputeActor,
+        dispute_id: Uuid,
+        request: DisputeDecisionRequest,
+    ) -> Result<DisputeDetail, ApiError> {
+        require_referee(actor)?;
+        validate_decision(&request.decision)?;
+
+        let case = self.get_case(dispute_id).await?;
+        if case.status != DisputeCaseStatus::UnderReview {
+            return Err(ApiError::bad_request(
+                "Dispute must be assigned before it is decided",
+            ));
+        }
+        if case.admin_reviewer_id != Some(actor.user_id) && !actor.is_admin {
+            return Err(ApiError::forbidden(
+                "Dispute is assigned to another referee",
+            ));
+        }
+        if case.chain_status == "pending" && case.chain_open_tx.is_none() {
+            return Err(ApiError::conflict("Dispute is still being opened on chain"));
+        }
+
+        let decision_json = serde_json::to_value(request.decision)
+            .map_err(|e| ApiError::internal_error(e.to_string()))?;
+        let chain = self.chain().filter(|_| case.chain_status != "off_chain");
+
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let updated = sqlx::query(
+            r#"
+            UPDATE match_disputes
+            SET status = $2, decision = $3, resolution = $4, admin_notes = $5,
+                chain_status = CASE WHEN $6 THEN 'pending' ELSE chain_status END,
+                resolved_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status = $7
+            "#,
+        )
+        .bind(dispute_id)
+        .bind(DisputeCaseStatus::Resolved)
+        .bind(&decision_json)
+        .bind(request.decision.as_str())
+        .bind(&request.notes)
+        .bind(chain.is_some())
+        .bind(DisputeCaseStatus::UnderReview)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        if updated.rows_affected() == 0 {
+            return Err(ApiError::conflict("Dispute was decided concurrently"));
+        }
+        record_event(
+            &mut tx,
+            dispute_id,
+            Some(actor.user_id),
+            "decided",
+            json!({ "decision": decision_json, "notes": request.notes }),
+        )
+        .await?;
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        if let Some(chain) = chain {
+            let (match_id, category, reason) =
+                (case.match_id, case.category as u32, case.reason.clone());
+            let opened = case.chain_open_tx.is_some();
+            let decision = request.decision;
+            tokio::spawn(async move {
+                if !opened
+                    && !chain
+                        .open(dispute_id, match_id, category, &reason, "")
+                        .await
+                {
+                    return;
+                }
+                chain.resolve(dispute_id, match_id, decision).await;
+            });
+        }
+
+        info!(
+            dispute_id = %dispute_id,
+            decision = request.decision.as_str(),
+            "Dispute decided"
+        );
+        self.detail(dispute_id).await
+    }
+
+    // ========================================================================
+    // HELPERS
+    // ========================================================================
+
+    async fn store_evidence(
+        &self,
+        dispute_id: Uuid,
+        user_id: Uuid,
+        files: Vec<crate::service::match_result_service::EvidenceFile>,
+    ) -> Result<Vec<DisputeEvidence>, ApiError> {
+        let mut stored = Vec::with_capacity(files.len());
+        for file in files {
+            let key = format!(
+                "disputes/{}/{}/{}-{}",
+                dispute_id,
+                user_id,
+                kind_str(file.kind),
+                file.sha256
+            );
+            let size_bytes = file.bytes.len() as i64;
+            let object = self
+                .storage
+                .put_object(&key, &file.content_type, file.bytes)
+                .await
+                .map_err(|e| ApiError::internal_error(format!("Evidence upload failed: {}", e)))?;
+            stored.push(DisputeEvidence {
+                id: Uuid::new_v4(),
+                dispute_id,
+                uploaded_by: user_id,
+                kind: file.kind,
+                content_type: file.content_type,
+                size_bytes,
+                sha256: file.sha256,
+                object_key: object.key,
+                url: object.url,
+                created_at: Utc::now(),
+            });
+        }
+        self.record_evidence(dispute_id, user_id, stored).await
+    }
+
+    async fn record_evidence(
+        &self,
+        dispute_id: Uuid,
+        user_id: Uuid,
+        stored: Vec<DisputeEvidence>,
+    ) -> Result<Vec<DisputeEvidence>, ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let mut attached = Vec::with_capacity(stored.len());
+        for evidence in stored {
+            // Re-uploading the same file is a no-op.
+            let inserted = sqlx::query(
+                r#"
+                INSERT INTO dispute_evidence (
+                    id, dispute_id, uploaded_by, kind, content_type, size_bytes,
+                    sha256, object_key, url, created_at
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (object_key) DO NOTHING
+                "#,
+            )
+            .bind(evidence.id)
+            .bind(evidence.dispute_id)
+            .bind(evidence.uploaded_by)
+            .bind(evidence.kind)
+            .bind(&evidence.content_type)
+            .bind(evidence.size_bytes)
+            .bind(&evidence.sha256)
+            .bind(&evidence.object_key)
+            .bind(&evidence.url)
+            .bind(evidence.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+            if inserted.rows_affected() == 1 {
+                attached.push(evidence);
+            }
+        }
+        if !attached.is_empty() {
+            let hashes: Vec<&str> = attached.iter().map(|e| e.sha256.as_str()).collect();
+            record_event(
+                &mut tx,
+                dispute_id,
+                Some(user_id),
+                "evidence_attached",
+                json!({ "sha256": hashes }),
+            )
+            .await?;
+        }
+        tx.commit().await.map_err(ApiError::database_error)?;
+        Ok(attached)
+    }
+
+    /// Evidence may be added while a case is open, by the match players, the
+    /// assigned referee and admins.
+    async fn ensure_can_attach(
+        &self,
+        actor: DisputeActor,
+        dispute_id: Uuid,
+    ) -> Result<(), ApiError> {
+        let case = self.get_case(dispute_id).await?;
+        if !matches!(
+            case.status,
+            DisputeCaseStatus::Open | DisputeCaseStatus::UnderReview
+        ) {
+            return Err(ApiError::bad_request("Dispute is already closed"));
+        }
+        let match_record = self.get_match(case.match_id).await?;
+        let is_assigned = case.admin_reviewer_id == Some(actor.user_id);
+        if !is_participant(&match_record, actor.user_id) && !is_assigned && !actor.is_admin {
+            return Err(ApiError::forbidden(
+                "Only match players and the assigned referee can attach evidence",
+            ));
+        }
+        Ok(())
+    }
+
+    async fn detail(&self, dispute_id: Uuid) -> Result<DisputeDetail, ApiError> {
+        let case = self.get_case(dispute_id).await?;
+        let evidence = sqlx::query_as::<_, DisputeEvidence>(
+            "SELECT * FROM dispute_evidence WHERE dispute_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(dispute_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        let history = sqlx::query_as::<_, DisputeEvent>(
+            "SELECT * FROM dispute_events WHERE dispute_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(dispute_id)
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        Ok(DisputeDetail {
+            case,
+            evidence,
+            history,
+        })
+    }
+
+    async fn get_case(&self, dispute_id: Uuid) -> Result<DisputeCase, ApiError> {
+        sqlx::query_as::<_, DisputeCase>("SELECT * FROM match_disputes WHERE id = $1")
+            .bind(dispute_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?
+            .ok_or_else(|| ApiError::not_found("Dispute not found"))
+    }
+
+    async fn get_match(&self, match_id: Uuid) -> Result<Match, ApiError> {
+        sqlx::query_as::<_, Match>("SELECT * FROM matches WHERE id = $1")
+            .bind(match_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?
+            .ok_or_else(|| ApiError::not_found("Match not found"))
+    }
+
+    fn chain(&self) -> Option<ChainExecutor> {
+        Some(ChainExecutor {
+            db_pool: self.db_pool.clone(),
+            relayer: self.relayer.clone()?,
+            dispute_contract: self.dispute_contract.clone()?,
+        })
+    }
+}
+
+/// What the spawned on-chain calls need from the service.
+struct ChainExecutor {
+    db_pool: DbPool,
+    relayer: Arc<StellarRelayer>,
+    dispute_contract: String,
+}
+
+impl ChainExecutor {
+    /// Open the case on chain. Returns whether it succeeded.
+    async fn open(
+        &self,
+        dispute_id: Uuid,
+        match_id: Uuid,
+        category: u32,
+        reason: &str,
+        evidence_ref: &str,
+    ) -> bool {
+        let result = self
+            .relayer
+            .open_dispute(
+                &self.dispute_contract,
+                match_key(match_id),
+                category,
+                reason,
+                evidence_ref,
+            )
+            .await;
+        match result {
+            Ok(receipt) => {
+                self.update(
+                    dispute_id,
+                    "UPDATE match_disputes SET chain_status = 'opened', chain_open_tx = $2, chain_error = NULL, updated_at = NOW() WHERE id = $1",
+                    &receipt.tx_hash,
+                    "chain_opened",
+                )
+                .await;
+                true
+            }
+            Err(e) => {
+                self.fail(dispute_id, &e.to_string()).await;
+                false
+            }
+        }
+    }
+
+    /// Claim the case for the platform account and execute the decision.
+    async fn resolve(&self, dispute_id: Uuid, match_id: Uuid, decision: DisputeDecision) {
+        let key = match_key(match_id);
+        let result = match self
+            .relayer
+            .claim_dispute(&self.dispute_contract, key)
+            .await
+        {
+            Ok(_) => {
+                self.relayer
+                    .resolve_dispute(&self.dispute_contract, key, decision_arg(decision))
+                    .await
+            }
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(receipt) => {
+                self.update(
+                    dispute_id,
+                    "UPDATE match_disputes SET chain_status = 'resolved', chain_resolve_tx = $2, chain_error = NULL, updated_at = NOW() WHERE id = $1",
+                    &receipt.tx_hash,
+                    "chain_resolved",
+                )
+                .await
+            }
+            Err(e) => self.fail(dispute_id, &e.to_string()).await,
+        }
+    }
+
+    async fn update(&self, dispute_id: Uuid, query: &str, tx_hash: &str, action: &str) {
+        let result = async {
+            let mut tx = self.db_pool.begin().await?;
+            sqlx::query(query)
+                .bind(dispute_id)
+                .bind(tx_hash)
+                .execute(&mut *tx)
+                .await?;
+            insert_event(
+                &mut tx,
+                dispute_id,
+                None,
+                action,
+                json!({ "tx_hash": tx_hash }),
+            )
+            .await?;
+            tx.commit().await
+        }
+        .await;
+        match result {
+            Ok(()) => info!(dispute_id = %dispute_id, tx_hash, action, "Dispute executed on chain"),
+            Err(e) => {
+                error!(dispute_id = %dispute_id, error = %e, "Failed to record on-chain dispute update")
+            }
+        }
+    }
+
+    async fn fail(&self, dispute_id: Uuid, reason: &str) {
+        warn!(dispute_id = %dispute_id, error = %reason, "Dispute call on chain failed");
+        let result = async {
+            let mut tx = self.db_pool.begin().await?;
+            sqlx::query(
+                "UPDATE match_disputes SET chain_status = 'failed', chain_error = $2, updated_at = NOW() WHERE id = $1",
+            )
+            .bind(dispute_id)
+            .bind(reason)
+            .execute(&mut *tx)
+            .await?;
+            insert_event(&mut tx, dispute_id, None, "chain_failed", json!({ "error": reason })).await?;
+            tx.commit().await
+        }
+        .await;
+        if let Err(e) = result {
+            error!(dispute_id = %dispute_id, error = %e, "Failed to record on-chain dispute failure");
+        }
+    }
+}
+
+/// The contract's `Decision` enum as an `ScVal`: a vector holding the variant
+/// symbol followed by its fields.
+fn decision_arg(decision: DisputeDecision) -> ScArg {
+    let symbol = |name: &str| ScArg::Symbol(name.to_string());
+    ScArg::Vec(match decision {
+        DisputeDecision::AwardPlayerA => vec![symbol("AwardPlayerA")],
+        DisputeDecision::AwardPlayerB => vec![symbol("AwardPlayerB")],
+        DisputeDecision::Split {
+            player_a_bps,
+            player_b_bps,
+        } => vec![
+            symbol("Split"),
+            ScArg::U32(player_a_bps),
+            ScArg::U32(player_b_bps),
+        ],
+        DisputeDecision::Replay => vec![symbol("Replay")],
+        DisputeDecision::Void => vec![symbol("Void")],
+    })
+}
+
+fn validate_decision(decision: &DisputeDecision) -> Result<(), ApiError> {
+    if let DisputeDecision::Split {
+        player_a_bps,
+        player_b_bps,
+    } = decision
+    {
+        if player_a_bps.checked_add(*player_b_bps) != Some(10_000) {
+            return Err(ApiError::bad_request("Split shares must total 10000 bps"));
+        }
+    }
+    Ok(())
+}
+
+fn require_referee(actor: DisputeActor) -> Result<(), ApiError> {
+    if actor.can_referee() {
+        Ok(())
+    } else {
+        Err(ApiError::forbidden("Referee role required"))
+    }
+}
+
+fn is_participant(match_record: &Match, user_id: Uuid) -> bool {
+    match_record.player1_id == user_id || match_record.player2_id == Some(user_id)
+}
+
+async fn record_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    dispute_id: Uuid,
+    actor_id: Option<Uuid>,
+    action: &str,
+    details: serde_json::Value,
+) -> Result<(), ApiError> {
+    insert_event(tx, dispute_id, actor_id, action, details)
+        .await
+        .map_err(ApiError::database_error)
+}
+
+async fn insert_event(
+    tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+    dispute_id: Uuid,
+    actor_id: Option<Uuid>,
+    action: &str,
+    details: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO dispute_events (id, dispute_id, actor_id, action, details, created_at)
+        VALUES ($1, $2, $3, $4, $5, NOW())
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(dispute_id)
+    .bind(actor_id)
+    .bind(action)
+    .bind(details)
+    .execute(&mut **tx)
+    .await
+    .map(|_| ())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decision_arg_encodes_variant_and_fields() {
+        assert_eq!(
+            decision_arg(DisputeDecision::AwardPlayerB),
+            ScArg::Vec(vec![ScArg::Symbol("AwardPlayerB".to_string())])
+        );
+        assert_eq!(
+            decision_arg(DisputeDecision::Split {
+                player_a_bps: 2500,
+                player_b_bps: 7500
+            }),
+            ScArg::Vec(vec![
+                ScArg::Symbol("Split".to_string()),
+                ScArg::U32(2500),
+                ScArg::U32(7500),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_split_must_total_full_pot() {
+        let split = |a, b| DisputeDecision::Split {
+            player_a_bps: a,
+            player_b_bps: b,
+        };
+        assert!(validate_decision(&split(5000, 5000)).is_ok());
+        assert!(validate_decision(&split(5000, 4000)).is_err());
+        assert!(validate_decision(&split(u32::MAX, 1)).is_err());
+        assert!(validate_decision(&DisputeDecision::Void).is_ok());
+    }
+
+    #[test]
+    fn test_decision_json_is_tagged() {
+        let decision: DisputeDecision = serde_json::from_value(json!({
+            "type": "split",
+            "player_a_bps": 6000,
+            "player_b_bps": 4000
+        }))
+        .unwrap();
+        assert_eq!(
+            decision,
+            DisputeDecision::Split {
+                player_a_bps: 6000,
+                player_b_bps: 4000
+            }
+        );
+    }
+}
@@ -0,0 +1,106 @@
+//! Kafka.
+//!
+//! Each topic is a Kafka topic, keyed by event id, and each handler group a
+//! Kafka consumer group on the events topic. Offsets of acknowledged
+//! messages are committed in the background; since a consumer acknowledges
+//! in order and drops its subscription on the first error, a new
+//! subscription resumes at the first message not acknowledged. Topics are
+//! expected to exist or to be created automatically by the cluster.
+
+use super::{AckHandle, BrokerError, BrokerMessage, Topic, FETCH_COUNT};
+use rdkafka::consumer::{Consumer, StreamConsumer};
+use rdkafka::producer::{FutureProducer, FutureRecord};
+use rdkafka::{ClientConfig, Message as _};
+use std::time::Duration;
+
+pub const EVENTS_TOPIC: &str = "arenax.domain_events";
+pub const DEAD_LETTER_TOPIC: &str = "arenax.domain_events.dead";
+/// How long a message may wait for room in the producer's queue.
+const QUEUE_TIMEOUT: Duration = Duration::from_secs(5);
+/// How long a fetch waits for each further message.
+const FETCH_WAIT: Duration = Duration::from_millis(200);
+
+fn topic_name(topic: Topic) -> &'static str {
+    match topic {
+        Topic::Events => EVENTS_TOPIC,
+        Topic::DeadLetters => DEAD_LETTER_TOPIC,
+    }
+}
+
+pub struct KafkaTopics {
+    /// Bootstrap servers, comma-separated.
+    brokers: String,
+    producer: FutureProducer,
+}
+
+impl KafkaTopics {
+    pub fn connect(brokers: &str) -> Result<Self, BrokerError> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", brokers)
+            .set("enable.idempotence", "true")
+            .create()?;
+        Ok(Self {
+            brokers: brokers.to_string(),
+            producer,
+        })
+    }
+
+    /// Returns once the cluster has acknowledged the message.
+    pub async fn publish(&self, topic: Topic, key: &str, payload: &str) -> Result<(), BrokerError> {
+        self.producer
+            .send(
+                FutureRecord::to(topic_name(topic))
+                    .key(key)
+                    .payload(payload),
+                QUEUE_TIMEOUT,
+            )
+            .await
+            .map_err(|(e, _)| e)?;
+        Ok(())
+    }
+
+    pub fn subscribe(&self, group: &str, consumer: &str) -> Result<Subscription, BrokerError> {
+        let kafka_consumer: StreamConsumer = ClientConfig::new()
+            .set("bootstrap.servers", &self.brokers)
+            .set("group.id", group)
+            .set("client.id", consumer)
+            // Only offsets of acknowledged messages are committed.
+            .set("enable.auto.offset.store", "false")
+            .set("auto.offset.reset", "latest")
+            .create()?;
+        kafka_consumer.subscribe(&[EVENTS_TOPIC])?;
+        Ok(Subscription {
+            consumer: kafka_consumer,
+        })
+    }
+}
+
+pub struct Subscription {
+    consumer: StreamConsumer,
+}
+
+impl Subscription {
+    pub async fn fetch(&mut self) -> Result<Vec<BrokerMessage>, BrokerError> {
+        let mut messages = Vec::new();
+        while messages.len() < FETCH_COUNT {
+            let message = match tokio::time::timeout(FETCH_WAIT, self.consumer.recv()).await {
+                Ok(message) => message?,
+                Err(_) => break,
+            };
+            messages.push(BrokerMessage {
+                payload: message.payload().unwrap_or_default().to_vec(),
+                ack: AckHandle::Kafka {
+                    topic: message.topic().to_string(),
+                    partition: message.partition(),
+                    offset: message.offset(),
+                },
+            });
+        }
+        Ok(messages)
+    }
+
+    pub fn ack(&mut self, topic: &str, partition: i32, offset: i64) -> Result<(), BrokerError> {
+        self.consumer.store_offset(topic, partition, offset)?;
+        Ok(())
+    }
+}
@@ -0,0 +1,170 @@
+//! Brokers carrying domain events from the dispatcher to the consumers.
+//!
+//! Every broker offers the same two topics, the events and the dead letters,
+//! and the same consumer groups: one per handler, shared by its instances.
+//! Which one is used is picked by `DOMAIN_EVENTS_BROKER`; publishers only
+//! ever write to the outbox, so switching brokers changes no call site.
+//!
+//! A message that is fetched but never acknowledged is handed out again:
+//! Redis and NATS give it to a member of the group after [`CLAIM_IDLE`],
+//! Kafka from the group's committed offset once the subscription is
+//! re-created.
+
+pub mod kafka;
+pub mod nats;
+pub mod redis_streams;
+
+use crate::config::{DomainEventsConfig, EventBrokerKind};
+use redis::aio::ConnectionManager;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::info;
+
+pub use kafka::KafkaTopics;
+pub use nats::JetStream;
+pub use redis_streams::RedisStreams;
+
+/// How long a fetched message stays with a consumer before another may take
+/// it.
+pub const CLAIM_IDLE: Duration = Duration::from_secs(60);
+/// Most messages a subscription fetches at once.
+pub const FETCH_COUNT: usize = 32;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Topic {
+    Events,
+    /// Events consumers gave up on, as [`super::DeadLetter`]s.
+    DeadLetters,
+}
+
+#[derive(Debug, Error)]
+pub enum BrokerError {
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("NATS error: {0}")]
+    Nats(String),
+    #[error("Kafka error: {0}")]
+    Kafka(#[from] rdkafka::error::KafkaError),
+}
+
+// One per process, so the variants' sizes do not matter.
+#[allow(clippy::large_enum_variant)]
+pub enum EventBroker {
+    Redis(RedisStreams),
+    Nats(JetStream),
+    Kafka(KafkaTopics),
+}
+
+impl EventBroker {
+    /// Connect to the configured broker; `redis` is the platform's Redis
+    /// connection, used when no other broker is configured.
+    pub async fn connect(
+        config: &DomainEventsConfig,
+        redis: ConnectionManager,
+    ) -> Result<Self, BrokerError> {
+        // `DomainEventsConfig` only picks brokers it has an address for.
+        let broker = match (config.broker, &config.nats_url, &config.kafka_brokers) {
+            (EventBrokerKind::Nats, Some(url), _) => {
+                EventBroker::Nats(JetStream::connect(url).await?)
+            }
+            (EventBrokerKind::Kafka, _, Some(brokers)) => {
+                EventBroker::Kafka(KafkaTopics::connect(brokers)?)
+            }
+            _ => EventBroker::Redis(RedisStreams::new(redis)),
+        };
+        info!(broker = ?broker.kind(), "Domain event broker connected");
+        Ok(broker)
+    }
+
+    pub fn kind(&self) -> EventBrokerKind {
+        match self {
+            EventBroker::Redis(_) => EventBrokerKind::Redis,
+            EventBroker::Nats(_) => EventBrokerKind::Nats,
+            EventBroker::Kafka(_) => EventBrokerKind::Kafka,
+        }
+    }
+
+    /// Append `payload` to `topic`. `key` is the event id, which brokers
+    /// that can use it to drop duplicates or pick a partition do.
+    pub async fn publish(&self, topic: Topic, key: &str, payload: &str) -> Result<(), BrokerError> {
+        match self {
+            EventBroker::Redis(redis) => redis.publish(topic, key, payload).await,
+            EventBroker::Nats(nats) => nats.publish(topic, key, payload).await,
+            EventBroker::Kafka(kafka) => kafka.publish(topic, key, payload).await,
+        }
+    }
+
+    /// Join `group` on the events topic as `consumer`. A group created by
+    /// this call starts at events published from now on.
+    pub async fn subscribe(
+        &self,
+        group: &str,
+        consumer: &str,
+    ) -> Result<Subscription, BrokerError> {
+        Ok(match self {
+            EventBroker::Redis(redis) => {
+                Subscription::Redis(redis.subscribe(group, consumer).await?)
+            }
+            EventBroker::Nats(nats) => Subscription::Nats(nats.subscribe(group).await?),
+            EventBroker::Kafka(kafka) => Subscription::Kafka(kafka.subscribe(group, consumer)?),
+        })
+    }
+}
+
+/// A fetched event.
+pub struct BrokerMessage {
+    /// The event as the dispatcher published it.
+    pub payload: Vec<u8>,
+    ack: AckHandle,
+}
+
+enum AckHandle {
+    /// Stream entry id.
+    Redis(String),
+    Nats(Box<async_nats::jetstream::Message>),
+    Kafka {
+        topic: String,
+        partition: i32,
+        offset: i64,
+    },
+}
+
+/// A consumer's membership of its group.
+#[allow(clippy::large_enum_variant)]
+pub enum Subscription {
+    Redis(redis_streams::Subscription),
+    Nats(nats::Subscription),
+    Kafka(kafka::Subscription),
+}
+
+impl Subscription {
+    /// The next messages for this consumer, including ones other members of
+    /// the group left unacknowledged. Returns what is available without
+    /// waiting for more.
+    pub async fn fetch(&mut self) -> Result<Vec<BrokerMessage>, BrokerError> {
+        match self {
+            Subscription::Redis(redis) => redis.fetch().await,
+            Subscription::Nats(nats) => nats.fetch().await,
+            Subscription::Kafka(kafka) => kafka.fetch().await,
+        }
+    }
+
+    /// Tell the broker the group is done with `message`.
+    pub async fn ack(&mut self, message: &BrokerMessage) -> Result<(), BrokerError> {
+        match (self, &message.ack) {
+            (Subscription::Redis(redis), AckHandle::Redis(entry_id)) => redis.ack(entry_id).await,
+            (Subscription::Nats(nats), AckHandle::Nats(message)) => nats.ack(message).await,
+            (
+                Subscription::Kafka(kafka),
+                AckHandle::Kafka {
+                    topic,
+                    partition,
+                    offset,
+                },
+            ) => kafka.ack(topic, *partition, *offset),
+            // Messages are only acknowledged on the subscription they came
+            // from.
+            _ => unreachable!("message acknowledged on another broker's subscription"),
+        }
+    }
+}
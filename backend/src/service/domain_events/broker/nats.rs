@@ -0,0 +1,116 @@
+//! NATS JetStream.
+//!
+//! Both topics are subjects of one stream. Each handler group is a durable
+//! pull consumer on the events subject; JetStream hands a message out again
+//! when it is not acknowledged within [`CLAIM_IDLE`]. Events are published
+//! with their id as message id, so the stream drops one the dispatcher sends
+//! twice within its duplicate window.
+
+use super::{AckHandle, BrokerError, BrokerMessage, Topic, CLAIM_IDLE, FETCH_COUNT};
+use async_nats::jetstream::{self, consumer, context::Publish, stream};
+use futures::StreamExt;
+
+pub const STREAM_NAME: &str = "ARENAX_DOMAIN_EVENTS";
+pub const EVENTS_SUBJECT: &str = "arenax.domain_events";
+pub const DEAD_LETTER_SUBJECT: &str = "arenax.domain_events.dead";
+/// Messages kept in the stream; consumers further behind than this miss
+/// events.
+const STREAM_MAX_MESSAGES: i64 = 100_000;
+
+fn subject(topic: Topic) -> &'static str {
+    match topic {
+        Topic::Events => EVENTS_SUBJECT,
+        Topic::DeadLetters => DEAD_LETTER_SUBJECT,
+    }
+}
+
+fn nats_error(e: impl std::fmt::Display) -> BrokerError {
+    BrokerError::Nats(e.to_string())
+}
+
+pub struct JetStream {
+    context: jetstream::Context,
+    stream: stream::Stream,
+}
+
+impl JetStream {
+    /// Connect to `url` and create the stream if it does not exist yet.
+    pub async fn connect(url: &str) -> Result<Self, BrokerError> {
+        let client = async_nats::connect(url).await.map_err(nats_error)?;
+        let context = jetstream::new(client);
+        let stream = context
+            .get_or_create_stream(stream::Config {
+                name: STREAM_NAME.to_string(),
+                subjects: vec![EVENTS_SUBJECT.to_string(), DEAD_LETTER_SUBJECT.to_string()],
+                max_messages: STREAM_MAX_MESSAGES,
+                ..Default::default()
+            })
+            .await
+            .map_err(nats_error)?;
+        Ok(Self { context, stream })
+    }
+
+    /// Returns once the stream has stored the message.
+    pub async fn publish(&self, topic: Topic, key: &str, payload: &str) -> Result<(), BrokerError> {
+        let ack = self
+            .context
+            .send_publish(
+                subject(topic),
+                Publish::build()
+                    .payload(payload.to_string().into())
+                    .message_id(key),
+            )
+            .await
+            .map_err(nats_error)?;
+        ack.await.map_err(nats_error)?;
+        Ok(())
+    }
+
+    pub async fn subscribe(&self, group: &str) -> Result<Subscription, BrokerError> {
+        let consumer = self
+            .stream
+            .get_or_create_consumer(
+                group,
+                consumer::pull::Config {
+                    durable_name: Some(group.to_string()),
+                    filter_subject: EVENTS_SUBJECT.to_string(),
+                    deliver_policy: consumer::DeliverPolicy::New,
+                    ack_policy: consumer::AckPolicy::Explicit,
+                    ack_wait: CLAIM_IDLE,
+                    ..Default::default()
+                },
+            )
+            .await
+            .map_err(nats_error)?;
+        Ok(Subscription { consumer })
+    }
+}
+
+pub struct Subscription {
+    consumer: consumer::PullConsumer,
+}
+
+impl Subscription {
+    pub async fn fetch(&mut self) -> Result<Vec<BrokerMessage>, BrokerError> {
+        let mut batch = self
+            .consumer
+            .fetch()
+            .max_messages(FETCH_COUNT)
+            .messages()
+            .await
+            .map_err(nats_error)?;
+        let mut messages = Vec::new();
+        while let Some(message) = batch.next().await {
+            let message = message.map_err(nats_error)?;
+            messages.push(BrokerMessage {
+                payload: message.message.payload.to_vec(),
+                ack: AckHandle::Nats(Box::new(message)),
+            });
+        }
+        Ok(messages)
+    }
+
+    pub async fn ack(&mut self, message: &jetstream::Message) -> Result<(), BrokerError> {
+        message.ack().await.map_err(nats_error)
+    }
+}
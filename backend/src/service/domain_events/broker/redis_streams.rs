@@ -0,0 +1,125 @@
+//! Redis streams on the platform's Redis server, the default broker.
+//!
+//! Each topic is a capped stream and each handler group a stream consumer
+//! group. Entries a consumer left pending longer than [`CLAIM_IDLE`] are
+//! claimed by the next member that fetches.
+
+use super::{AckHandle, BrokerError, BrokerMessage, Topic, CLAIM_IDLE, FETCH_COUNT};
+use redis::aio::ConnectionManager;
+use redis::streams::{
+    StreamAutoClaimOptions, StreamAutoClaimReply, StreamId, StreamMaxlen, StreamReadOptions,
+    StreamReadReply,
+};
+use redis::AsyncCommands;
+
+pub const STREAM_KEY: &str = "arenax:domain_events";
+pub const DEAD_LETTER_KEY: &str = "arenax:domain_events:dead";
+/// Entries kept in each stream; consumers further behind than this miss
+/// events.
+const STREAM_MAX_LEN: usize = 100_000;
+
+fn stream_key(topic: Topic) -> &'static str {
+    match topic {
+        Topic::Events => STREAM_KEY,
+        Topic::DeadLetters => DEAD_LETTER_KEY,
+    }
+}
+
+pub struct RedisStreams {
+    redis: ConnectionManager,
+}
+
+impl RedisStreams {
+    pub fn new(redis: ConnectionManager) -> Self {
+        Self { redis }
+    }
+
+    pub async fn publish(&self, topic: Topic, key: &str, payload: &str) -> Result<(), BrokerError> {
+        let mut conn = self.redis.clone();
+        conn.xadd_maxlen::<_, _, _, _, ()>(
+            stream_key(topic),
+            StreamMaxlen::Approx(STREAM_MAX_LEN),
+            "*",
+            &[("id", key), ("envelope", payload)],
+        )
+        .await?;
+        Ok(())
+    }
+
+    pub async fn subscribe(
+        &self,
+        group: &str,
+        consumer: &str,
+    ) -> Result<Subscription, BrokerError> {
+        let mut conn = self.redis.clone();
+        let result: Result<(), redis::RedisError> =
+            conn.xgroup_create_mkstream(STREAM_KEY, group, "$").await;
+        match result {
+            Err(e) if e.code() != Some("BUSYGROUP") => return Err(e.into()),
+            _ => {}
+        }
+        Ok(Subscription {
+            redis: self.redis.clone(),
+            group: group.to_string(),
+            consumer: consumer.to_string(),
+        })
+    }
+}
+
+pub struct Subscription {
+    redis: ConnectionManager,
+    group: String,
+    consumer: String,
+}
+
+impl Subscription {
+    pub async fn fetch(&mut self) -> Result<Vec<BrokerMessage>, BrokerError> {
+        let stale: StreamAutoClaimReply = self
+            .redis
+            .xautoclaim_options(
+                STREAM_KEY,
+                &self.group,
+                &self.consumer,
+                CLAIM_IDLE.as_millis() as usize,
+                "0-0",
+                StreamAutoClaimOptions::default().count(FETCH_COUNT),
+            )
+            .await?;
+
+        // Reads return at once; blocking would hold up the connection
+        // shared with the rest of the platform.
+        let options = StreamReadOptions::default()
+            .group(&self.group, &self.consumer)
+            .count(FETCH_COUNT);
+        let reply: StreamReadReply = self
+            .redis
+            .xread_options(&[STREAM_KEY], &[">"], &options)
+            .await?;
+
+        Ok(stale
+            .claimed
+            .into_iter()
+            .chain(reply.keys.into_iter().flat_map(|key| key.ids))
+            .map(message)
+            .collect())
+    }
+
+    pub async fn ack(&mut self, entry_id: &str) -> Result<(), BrokerError> {
+        self.redis
+            .xack::<_, _, _, ()>(STREAM_KEY, &self.group, &[entry_id])
+            .await?;
+        Ok(())
+    }
+}
+
+/// An entry without an envelope gives an empty payload, which consumers
+/// drop as unreadable.
+fn message(entry: StreamId) -> BrokerMessage {
+    BrokerMessage {
+        payload: entry
+            .get::<String>("envelope")
+            .unwrap_or_default()
+            .into_bytes(),
+        ack: AckHandle::Redis(entry.id),
+    }
+}
@@ -0,0 +1,281 @@
+//! Feeds the event stream to a [`DomainEventHandler`].
+//!
+//! Every handler reads through a consumer group of its own on the
+//! [`EventBroker`]; instances of the same handler share the group, so each
+//! event is handled by one of them. A message is acknowledged once its
+//! event was handled or, when the handler failed, once the failure was
+//! recorded in `domain_event_retries`. From there the event is handed to
+//! the handler again after the backoff of its type's [`RetryPolicy`]; after
+//! the policy's last delivery it is sent to the dead-letter topic and its
+//! receipt keeps the error.
+
+use super::broker::{EventBroker, Subscription, Topic, CLAIM_IDLE, FETCH_COUNT};
+use super::{DeadLetter, DomainEventEnvelope, DomainEventError, DomainEventHandler};
+use crate::config::{DomainEventsConfig, RetryPolicy};
+use crate::db::DbPool;
+use crate::telemetry::propagation;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, info_span, warn, Instrument};
+use uuid::Uuid;
+
+const POLL_INTERVAL_MS: u64 = 1000;
+/// Longest wait between two deliveries, however often the event failed.
+const RETRY_MAX_SECS: u64 = 3600;
+
+pub struct EventConsumer {
+    db_pool: DbPool,
+    broker: Arc<EventBroker>,
+    handler: Arc<dyn DomainEventHandler>,
+    /// This instance within the handler's group.
+    consumer: String,
+    default_retry: RetryPolicy,
+    retry_policies: HashMap<String, RetryPolicy>,
+}
+
+impl EventConsumer {
+    pub fn new(
+        db_pool: DbPool,
+        broker: Arc<EventBroker>,
+        handler: Arc<dyn DomainEventHandler>,
+        config: &DomainEventsConfig,
+    ) -> Self {
+        let consumer = format!("{}-{}", handler.name(), Uuid::new_v4().simple());
+        Self {
+            db_pool,
+            broker,
+            handler,
+            consumer,
+            default_retry: config.default_retry,
+            retry_policies: config.retry_policies.clone(),
+        }
+    }
+
+    /// Spawn the worker handing events to the handler.
+    pub fn run(self) {
+        tokio::spawn(async move {
+            let group = self.handler.name();
+            info!(group, consumer = %self.consumer, "Domain event consumer started");
+            let mut ticker = tokio::time::interval(Duration::from_millis(POLL_INTERVAL_MS));
+            let mut subscription = None;
+            loop {
+                ticker.tick().await;
+                if subscription.is_none() {
+                    match self.broker.subscribe(group, &self.consumer).await {
+                        Ok(joined) => subscription = Some(joined),
+                        Err(e) => error!(group, error = %e, "Failed to join consumer group"),
+                    }
+                }
+                if let Some(joined) = subscription.as_mut() {
+                    if let Err(e) = self.poll(joined).await {
+                        error!(group, error = %e, "Domain event consumer tick failed");
+                        // Whatever was left unacknowledged is handed out
+                        // again once the group is joined anew.
+                        subscription = None;
+                    }
+                }
+                if let Err(e) = self.retry_due().await {
+                    error!(group, error = %e, "Failed to retry domain events");
+                }
+            }
+        });
+    }
+
+    async fn poll(&self, subscription: &mut Subscription) -> Result<(), DomainEventError> {
+        for message in subscription.fetch().await? {
+            match serde_json::from_slice::<DomainEventEnvelope>(&message.payload) {
+                Ok(envelope) => self.deliver(&envelope).await?,
+                Err(e) => warn!(
+                    group = self.handler.name(),
+                    error = %e,
+                    "Dropping unreadable domain event"
+                ),
+            }
+            subscription.ack(&message).await?;
+        }
+        Ok(())
+    }
+
+    /// Hand events whose retry is due to the handler again.
+    async fn retry_due(&self) -> Result<(), DomainEventError> {
+        // Claimed rows are pushed back by CLAIM_IDLE, so an instance that
+        // goes away mid-retry leaves them to the others.
+        let due: Vec<(sqlx::types::Json<DomainEventEnvelope>,)> = sqlx::query_as(
+            r#"
+            UPDATE domain_event_retries
+            SET next_attempt_at = NOW() + make_interval(secs => $3)
+            WHERE (consumer, event_id) IN (
+                SELECT consumer, event_id
+                FROM domain_event_retries
+                WHERE consumer = $1 AND next_attempt_at <= NOW()
+                ORDER BY next_attempt_at
+                LIMIT $2
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING envelope
+            "#,
+        )
+        .bind(self.handler.name())
+        .bind(FETCH_COUNT as i64)
+        .bind(CLAIM_IDLE.as_secs_f64())
+        .fetch_all(&self.db_pool)
+        .await?;
+        for (envelope,) in due {
+            self.deliver(&envelope.0).await?;
+        }
+        Ok(())
+    }
+
+    async fn deliver(&self, envelope: &DomainEventEnvelope) -> Result<(), DomainEventError> {
+        if self.handled(envelope.id).await? {
+            // Also clears a retry left by a delivery that failed alongside.
+            return self.remember(envelope.id, None).await;
+        }
+
+        let span = info_span!(
+            "domain_event.handle",
+            otel.kind = "consumer",
+            consumer = self.handler.name(),
+            event_type = envelope.event.event_type(),
+            event_id = %envelope.id,
+        );
+        propagation::continue_from(&span, &envelope.trace);
+        match self.handler.handle(envelope).instrument(span).await {
+            Ok(()) => self.remember(envelope.id, None).await,
+            Err(e) => self.failed(envelope, &e).await,
+        }
+    }
+
+    /// Schedule a retry of `envelope`, or dead-letter it once its policy's
+    /// deliveries are used up.
+    async fn failed(
+        &self,
+        envelope: &DomainEventEnvelope,
+        error: &str,
+    ) -> Result<(), DomainEventError> {
+        let group = self.handler.name();
+        let policy = self.retry_policy(envelope.event.event_type());
+        // Waits backoff_secs after the first failure, doubling after each
+        // further one up to RETRY_MAX_SECS.
+        let (deliveries,): (i32,) = sqlx::query_as(
+            r#"
+            INSERT INTO domain_event_retries
+                (consumer, event_id, envelope, deliveries, last_error, next_attempt_at)
+            VALUES ($1, $2, $3, 1, $4, NOW() + make_interval(secs => LEAST($5, $6)))
+            ON CONFLICT (consumer, event_id) DO UPDATE
+            SET deliveries      = domain_event_retries.deliveries + 1,
+                last_error      = EXCLUDED.last_error,
+                next_attempt_at = NOW() + make_interval(
+                    secs => LEAST($5 * POWER(2, LEAST(domain_event_retries.deliveries, 16)), $6)
+                )
+            RETURNING deliveries
+            "#,
+        )
+        .bind(group)
+        .bind(envelope.id)
+        .bind(sqlx::types::Json(envelope))
+        .bind(error)
+        .bind(policy.backoff_secs as f64)
+        .bind(RETRY_MAX_SECS as f64)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        if i64::from(deliveries) < i64::from(policy.max_deliveries) {
+            warn!(
+                group,
+                event_id = %envelope.id,
+                deliveries,
+                error = %error,
+                "Domain event handler failed, will retry"
+            );
+            return Ok(());
+        }
+        error!(
+            group,
+            event_id = %envelope.id,
+            deliveries,
+            error = %error,
+            "Giving up on domain event"
+        );
+        let dead_letter = DeadLetter {
+            consumer: group.to_string(),
+            deliveries,
+            error: error.to_string(),
+            envelope: envelope.clone(),
+        };
+        self.broker
+            .publish(
+                Topic::DeadLetters,
+                &envelope.id.to_string(),
+                &serde_json::to_string(&dead_letter)?,
+            )
+            .await?;
+        self.remember(envelope.id, Some(error)).await
+    }
+
+    fn retry_policy(&self, event_type: &str) -> RetryPolicy {
+        self.retry_policies
+            .get(event_type)
+            .copied()
+            .unwrap_or(self.default_retry)
+    }
+
+    async fn handled(&self, event_id: Uuid) -> Result<bool, DomainEventError> {
+        let handled: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM domain_event_receipts \
+                            WHERE consumer = $1 AND event_id = $2)",
+        )
+        .bind(self.handler.name())
+        .bind(event_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(handled)
+    }
+
+    /// Record that the handler is done with `event_id`, successfully unless
+    /// `error` is given.
+    async fn remember(&self, event_id: Uuid, error: Option<&str>) -> Result<(), DomainEventError> {
+        let mut tx = self.db_pool.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO domain_event_receipts (consumer, event_id, error)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (consumer, event_id) DO NOTHING
+            "#,
+        )
+        .bind(self.handler.name())
+        .bind(event_id)
+        .bind(error)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query("DELETE FROM domain_event_retries WHERE consumer = $1 AND event_id = $2")
+            .bind(self.handler.name())
+            .bind(event_id)
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::config::RetryPolicy;
+
+    #[test]
+    fn test_retry_policies_parse() {
+        let policies =
+            RetryPolicy::parse_list("payment.completed=20/30, match.finalized = 5/10,").unwrap();
+        assert_eq!(
+            policies["payment.completed"],
+            RetryPolicy {
+                max_deliveries: 20,
+                backoff_secs: 30,
+            }
+        );
+        assert_eq!(policies["match.finalized"].max_deliveries, 5);
+        assert!(RetryPolicy::parse_list("payment.completed=0/30").is_err());
+        assert!(RetryPolicy::parse_list("payment.completed=20").is_err());
+    }
+}
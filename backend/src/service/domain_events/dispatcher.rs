@@ -0,0 +1,206 @@
+//! Moves committed events from the outbox onto the broker.
+//!
+//! Pending rows are claimed with `FOR UPDATE SKIP LOCKED`, so several
+//! instances can dispatch side by side. A row is marked dispatched in the
+//! same transaction that claimed it, after the broker accepted the event; a
+//! crash in between sends it again, which consumers tolerate.
+
+use super::broker::{EventBroker, Topic};
+use super::DomainEventError;
+use crate::db::DbPool;
+use crate::telemetry::propagation::TraceCarrier;
+use chrono::{DateTime, Utc};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const POLL_INTERVAL_MS: u64 = 500;
+const BATCH_SIZE: i64 = 100;
+const RETRY_BASE_SECS: u64 = 1;
+const RETRY_MAX_SECS: u64 = 300;
+/// Dispatched events and consumer receipts are kept this long.
+const RETENTION_DAYS: i32 = 7;
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+#[derive(sqlx::FromRow)]
+struct OutboxRow {
+    id: Uuid,
+    payload: sqlx::types::Json<serde_json::Value>,
+    trace: sqlx::types::Json<TraceCarrier>,
+    created_at: DateTime<Utc>,
+}
+
+impl OutboxRow {
+    /// The row as a [`super::DomainEventEnvelope`], without decoding the
+    /// event, so a row written by a newer version is passed on untouched.
+    fn envelope(&self) -> serde_json::Value {
+        serde_json::json!({
+            "id": self.id,
+            "occurred_at": self.created_at,
+            "trace": self.trace.0,
+            "event": self.payload.0,
+        })
+    }
+}
+
+pub struct OutboxDispatcher {
+    db_pool: DbPool,
+    broker: Arc<EventBroker>,
+}
+
+impl OutboxDispatcher {
+    pub fn new(db_pool: DbPool, broker: Arc<EventBroker>) -> Self {
+        Self { db_pool, broker }
+    }
+
+    /// Spawn the worker draining the outbox.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!("Domain event dispatcher started");
+            let mut ticker = tokio::time::interval(Duration::from_millis(POLL_INTERVAL_MS));
+            let mut last_prune = Instant::now();
+            loop {
+                ticker.tick().await;
+                loop {
+                    match self.dispatch().await {
+                        // A full batch means more may be waiting.
+                        Ok(dispatched) if dispatched as i64 == BATCH_SIZE => continue,
+                        Ok(_) => break,
+                        Err(e) => {
+                            error!(error = %e, "Domain event dispatch failed");
+                            break;
+                        }
+                    }
+                }
+                if last_prune.elapsed() >= PRUNE_INTERVAL {
+                    last_prune = Instant::now();
+                    if let Err(e) = self.prune().await {
+                        warn!(error = %e, "Failed to prune dispatched domain events");
+                    }
+                }
+            }
+        });
+    }
+
+    /// Send the next batch of pending events, oldest first. Stops at the
+    /// first event the broker refuses, which is retried after a backoff. Returns
+    /// how many were sent.
+    pub async fn dispatch(&self) -> Result<usize, DomainEventError> {
+        let mut tx = self.db_pool.begin().await?;
+        let rows: Vec<OutboxRow> = sqlx::query_as(
+            r#"
+            SELECT id, payload, trace, created_at
+            FROM domain_event_outbox
+            WHERE published_at IS NULL AND next_attempt_at <= NOW()
+            ORDER BY seq
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+            "#,
+        )
+        .bind(BATCH_SIZE)
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut dispatched = Vec::with_capacity(rows.len());
+        let mut failure = None;
+        for row in &rows {
+            let payload = serde_json::to_string(&row.envelope())?;
+            let result = self
+                .broker
+                .publish(Topic::Events, &row.id.to_string(), &payload)
+                .await;
+            match result {
+                Ok(_) => dispatched.push(row.id),
+                Err(e) => {
+                    failure = Some((row.id, e));
+                    break;
+                }
+            }
+        }
+
+        if !dispatched.is_empty() {
+            sqlx::query(
+                "UPDATE domain_event_outbox SET published_at = NOW(), last_error = NULL \
+                 WHERE id = ANY($1)",
+            )
+            .bind(&dispatched)
+            .execute(&mut *tx)
+            .await?;
+        }
+        if let Some((id, e)) = &failure {
+            // Backs off exponentially from RETRY_BASE_SECS up to RETRY_MAX_SECS.
+            let (attempts,): (i32,) = sqlx::query_as(
+                r#"
+                UPDATE domain_event_outbox
+                SET attempts        = attempts + 1,
+                    last_error      = $2,
+                    next_attempt_at = NOW() + make_interval(
+                        secs => LEAST($3 * POWER(2, LEAST(attempts, 16)), $4)
+                    )
+                WHERE id = $1
+                RETURNING attempts
+                "#,
+            )
+            .bind(id)
+            .bind(e.to_string())
+            .bind(RETRY_BASE_SECS as f64)
+            .bind(RETRY_MAX_SECS as f64)
+            .fetch_one(&mut *tx)
+            .await?;
+            warn!(event_id = %id, attempts, error = %e, "Failed to dispatch domain event");
+        }
+        tx.commit().await?;
+
+        Ok(dispatched.len())
+    }
+
+    async fn prune(&self) -> Result<(), DomainEventError> {
+        sqlx::query(
+            "DELETE FROM domain_event_outbox \
+             WHERE published_at < NOW() - make_interval(days => $1)",
+        )
+        .bind(RETENTION_DAYS)
+        .execute(&self.db_pool)
+        .await?;
+        // Receipts of events consumers gave up on are kept for inspection.
+        sqlx::query(
+            "DELETE FROM domain_event_receipts \
+             WHERE error IS NULL AND handled_at < NOW() - make_interval(days => $1)",
+        )
+        .bind(RETENTION_DAYS)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::DomainEventEnvelope;
+    use super::*;
+
+    #[test]
+    fn test_envelope_from_row() {
+        let row = OutboxRow {
+            id: Uuid::new_v4(),
+            payload: sqlx::types::Json(serde_json::json!({
+                "type": "payment.completed",
+                "payment_id": Uuid::new_v4(),
+                "user_id": Uuid::new_v4(),
+                "amount": 1500,
+                "currency": "XLM",
+                "purpose": "deposit",
+            })),
+            trace: sqlx::types::Json(TraceCarrier::from([(
+                "traceparent".to_string(),
+                "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01".to_string(),
+            )])),
+            created_at: Utc::now(),
+        };
+        let envelope: DomainEventEnvelope = serde_json::from_value(row.envelope()).unwrap();
+        assert_eq!(envelope.id, row.id);
+        assert_eq!(envelope.event.event_type(), "payment.completed");
+        assert_eq!(envelope.trace, row.trace.0);
+    }
+}
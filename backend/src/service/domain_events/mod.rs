@@ -0,0 +1,186 @@
+//! Domain events with a transactional outbox.
+//!
+//! Services [`publish`] a [`DomainEvent`] on the connection of the
+//! transaction that makes the change it describes, so the event exists if
+//! and only if the change was committed. The [`OutboxDispatcher`] moves
+//! committed events onto the [`EventBroker`] (Redis streams, NATS JetStream
+//! or Kafka), and every [`DomainEventHandler`] (notifications, webhooks,
+//! analytics) reads them through a consumer group of its own, see
+//! [`EventConsumer`].
+//!
+//! Delivery is at least once: an event is marked dispatched only after the
+//! broker accepted it, and acknowledged by a group only after its handler
+//! succeeded or the failure was recorded for a retry. Consumers remember the
+//! events they handled, so a redelivered event is acknowledged without being
+//! handled twice.
+//!
+//! The trace that published an event travels with it; handlers run in a
+//! span of that trace.
+
+pub mod broker;
+pub mod consumer;
+pub mod dispatcher;
+
+use crate::telemetry::propagation::{self, TraceCarrier};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use sqlx::PgConnection;
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use broker::{BrokerError, EventBroker};
+pub use consumer::EventConsumer;
+pub use dispatcher::OutboxDispatcher;
+
+#[derive(Debug, Error)]
+pub enum DomainEventError {
+    #[error("Database error: {0}")]
+    Database(#[from] sqlx::Error),
+    #[error("Broker error: {0}")]
+    Broker(#[from] BrokerError),
+    #[error("Invalid event: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Something that happened in the platform other parts of it react to.
+///
+/// Chain amounts are decimal strings of the contract's `i128`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum DomainEvent {
+    /// A match was settled on chain.
+    #[serde(rename = "match.finalized")]
+    MatchFinalized {
+        match_id: Uuid,
+        on_chain_match_id: String,
+        player_a: String,
+        player_b: String,
+        winner: Option<String>,
+        tx_hash: String,
+    },
+    #[serde(rename = "payment.completed")]
+    PaymentCompleted {
+        payment_id: Uuid,
+        user_id: Uuid,
+        /// In minor units of `currency`.
+        amount: i64,
+        currency: String,
+        purpose: String,
+    },
+    /// A contract slashed a stake, either a match escrow or a tournament
+    /// stake.
+    #[serde(rename = "stake.slashed")]
+    StakeSlashed {
+        /// Id of the chain event reporting the slash.
+        chain_event_id: String,
+        account: String,
+        amount: String,
+        asset: Option<String>,
+        /// Hex id of the match, for escrow slashes.
+        match_id: Option<String>,
+        /// Hex id of the tournament, for stake slashes.
+        tournament_id: Option<String>,
+        ledger: u64,
+    },
+}
+
+impl DomainEvent {
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            DomainEvent::MatchFinalized { .. } => "match.finalized",
+            DomainEvent::PaymentCompleted { .. } => "payment.completed",
+            DomainEvent::StakeSlashed { .. } => "stake.slashed",
+        }
+    }
+}
+
+/// A published event as consumers receive it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DomainEventEnvelope {
+    /// Stays the same across redeliveries.
+    pub id: Uuid,
+    pub occurred_at: DateTime<Utc>,
+    /// Trace of the change that published the event.
+    #[serde(default)]
+    pub trace: TraceCarrier,
+    pub event: DomainEvent,
+}
+
+/// An event a consumer gave up on, as sent to the dead-letter topic.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DeadLetter {
+    /// Name of the handler that gave up.
+    pub consumer: String,
+    pub deliveries: i32,
+    /// The handler's last error.
+    pub error: String,
+    pub envelope: DomainEventEnvelope,
+}
+
+/// Reacts to published events. Each handler reads the stream through its
+/// own consumer group named after it, so renaming a handler starts it over
+/// from new events only.
+pub trait DomainEventHandler: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    /// Handle `event`. An error leaves it pending, to be handed out again.
+    fn handle<'a>(&'a self, event: &'a DomainEventEnvelope) -> BoxFuture<'a, Result<(), String>>;
+}
+
+/// Add `event` to the outbox as part of the transaction `conn` is in.
+pub async fn publish(conn: &mut PgConnection, event: &DomainEvent) -> Result<Uuid, sqlx::Error> {
+    let (id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO domain_event_outbox (id, event_type, payload, trace)
+        VALUES ($1, $2, $3, $4)
+        RETURNING id
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(event.event_type())
+    .bind(sqlx::types::Json(event))
+    .bind(sqlx::types::Json(propagation::current_carrier()))
+    .fetch_one(conn)
+    .await?;
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let envelope = DomainEventEnvelope {
+            id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+            trace: TraceCarrier::new(),
+            event: DomainEvent::StakeSlashed {
+                chain_event_id: "0000123-0000000001".to_string(),
+                account: "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5".to_string(),
+                amount: "170141183460469231731687303715884105727".to_string(),
+                asset: None,
+                match_id: None,
+                tournament_id: Some("ab".repeat(32)),
+                ledger: 123,
+            },
+        };
+
+        let json = serde_json::to_value(&envelope).unwrap();
+        assert_eq!(json["event"]["type"], envelope.event.event_type());
+        let decoded: DomainEventEnvelope = serde_json::from_value(json).unwrap();
+        assert_eq!(decoded, envelope);
+
+        let payment = DomainEvent::PaymentCompleted {
+            payment_id: Uuid::new_v4(),
+            user_id: Uuid::new_v4(),
+            amount: 50_000,
+            currency: "NGN".to_string(),
+            purpose: "tournament_entry".to_string(),
+        };
+        let json = serde_json::to_value(&payment).unwrap();
+        assert_eq!(json["type"], "payment.completed");
+        assert_eq!(json["amount"], 50_000);
+    }
+}
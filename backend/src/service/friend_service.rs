@@ -0,0 +1,599 @@
+//! Friends, friend requests and blocks.
+//!
+//! A friendship is one `friends` row with status `accepted`, in whichever
+//! direction the request went. Blocking replaces it with a `blocked` row from
+//! the blocker, and turns down pending requests between the two; blocked
+//! users cannot befriend each other, follow each other's presence or be
+//! paired by matchmaking (see [`blocks_among`]).
+//!
+//! The friends list carries each friend's presence from the
+//! [`PresenceTracker`]. Friends follow live changes through `presence:{id}`
+//! topics on the WebSocket gateway.
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{
+    BlockedUser, FriendList, FriendProfile, FriendRequests, FriendWithPresence,
+    PendingFriendRequest, Presence, PresenceStatus,
+};
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
+use crate::realtime::presence::PresenceTracker;
+use chrono::Utc;
+use sqlx::{PgExecutor, Postgres, Transaction};
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::info;
+use uuid::Uuid;
+
+const REQUEST_SELECT: &str = "SELECT r.id, r.from_user_id, f.username AS from_username, \
+     r.to_user_id, t.username AS to_username, r.created_at \
+     FROM friend_requests r \
+     JOIN users f ON f.id = r.from_user_id \
+     JOIN users t ON t.id = r.to_user_id";
+
+#[derive(Debug, Error)]
+pub enum FriendError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("User not found")]
+    UserNotFound,
+    #[error("Friend request not found")]
+    RequestNotFound,
+    #[error("You are not friends with this user")]
+    NotFriends,
+    #[error("You have not blocked this user")]
+    NotBlocked,
+    #[error("You cannot interact with this user")]
+    Blocked,
+    #[error("{0}")]
+    Conflict(String),
+    #[error("Presence lookup failed: {0}")]
+    Presence(#[from] redis::RedisError),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<FriendError> for ApiError {
+    fn from(err: FriendError) -> Self {
+        match err {
+            FriendError::InvalidRequest(_) => ApiError::BadRequest(err.to_string()),
+            FriendError::UserNotFound
+            | FriendError::RequestNotFound
+            | FriendError::NotFriends
+            | FriendError::NotBlocked => ApiError::NotFound,
+            FriendError::Blocked => ApiError::Forbidden,
+            FriendError::Conflict(msg) => ApiError::Conflict(msg),
+            FriendError::Presence(e) => ApiError::RedisError(e.to_string()),
+            FriendError::DatabaseError(e) => ApiError::DatabaseError(e),
+        }
+    }
+}
+
+/// Blocks between a set of users, in either direction.
+#[derive(Debug, Clone, Default)]
+pub struct BlockList(HashSet<(Uuid, Uuid)>);
+
+impl BlockList {
+    /// Whether either of `a` and `b` blocked the other.
+    pub fn between(&self, a: Uuid, b: Uuid) -> bool {
+        self.0.contains(&ordered(a, b))
+    }
+}
+
+impl FromIterator<(Uuid, Uuid)> for BlockList {
+    fn from_iter<I: IntoIterator<Item = (Uuid, Uuid)>>(blocks: I) -> Self {
+        Self(blocks.into_iter().map(|(a, b)| ordered(a, b)).collect())
+    }
+}
+
+fn ordered(a: Uuid, b: Uuid) -> (Uuid, Uuid) {
+    if a <= b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+/// Blocks among `user_ids`, for keeping them apart in matchmaking.
+pub async fn blocks_among<'e>(
+    executor: impl PgExecutor<'e>,
+    user_ids: &[Uuid],
+) -> Result<BlockList, sqlx::Error> {
+    if user_ids.len() < 2 {
+        return Ok(BlockList::default());
+    }
+    let rows: Vec<(Uuid, Uuid)> = sqlx::query_as(
+        "SELECT user_id, friend_id FROM friends \
+         WHERE status = 'blocked' AND user_id = ANY($1) AND friend_id = ANY($1)",
+    )
+    .bind(user_ids)
+    .fetch_all(executor)
+    .await?;
+    Ok(rows.into_iter().collect())
+}
+
+pub struct FriendService {
+    db_pool: DbPool,
+    presence: Arc<PresenceTracker>,
+    event_bus: Option<EventBus>,
+}
+
+impl FriendService {
+    pub fn new(db_pool: DbPool, presence: Arc<PresenceTracker>) -> Self {
+        Self {
+            db_pool,
+            presence,
+            event_bus: None,
+        }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    // ========================================================================
+    // FRIENDS
+    // ========================================================================
+
+    /// Friends of `user_id` with their presence; whoever is online first.
+    pub async fn friends(&self, user_id: Uuid) -> Result<FriendList, FriendError> {
+        let profiles = sqlx::query_as::<_, FriendProfile>(
+            r#"
+            SELECT DISTINCT ON (u.id)
+                u.id AS user_id, u.username, u.avatar_url, f.created_at AS friends_since
+            FROM friends f
+            JOIN users u ON u.id = CASE WHEN f.user_id = $1 THEN f.friend_id ELSE f.user_id END
+            WHERE (f.user_id = $1 OR f.friend_id = $1) AND f.status = 'accepted'
+            ORDER BY u.id, f.created_at
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let ids: Vec<Uuid> = profiles.iter().map(|p| p.user_id).collect();
+        let mut presence = self.presence.get_many(&ids).await?;
+        let mut friends: Vec<FriendWithPresence> = profiles
+            .into_iter()
+            .map(|friend| FriendWithPresence {
+                presence: presence
+                    .remove(&friend.user_id)
+                    .unwrap_or_else(Presence::offline),
+                friend,
+            })
+            .collect();
+        sort_friends(&mut friends);
+
+        let online_count = friends
+            .iter()
+            .filter(|f| f.presence.status != PresenceStatus::Offline)
+            .count();
+        Ok(FriendList {
+            friends,
+            online_count,
+        })
+    }
+
+    /// End the friendship between `user_id` and `friend_id`.
+    pub async fn remove_friend(&self, user_id: Uuid, friend_id: Uuid) -> Result<(), FriendError> {
+        let removed = unfriend(&self.db_pool, user_id, friend_id).await?;
+        if !removed {
+            return Err(FriendError::NotFriends);
+        }
+        info!(user_id = %user_id, friend_id = %friend_id, "Friendship ended");
+        self.notify_removed(user_id, friend_id).await;
+        Ok(())
+    }
+
+    // ========================================================================
+    // REQUESTS
+    // ========================================================================
+
+    pub async fn send_request(
+        &self,
+        from_user_id: Uuid,
+        to_user_id: Uuid,
+    ) -> Result<PendingFriendRequest, FriendError> {
+        if from_user_id == to_user_id {
+            return Err(FriendError::InvalidRequest(
+                "You cannot send a friend request to yourself".to_string(),
+            ));
+        }
+        let mut tx = self.db_pool.begin().await?;
+        require_user(&mut *tx, to_user_id).await?;
+
+        let statuses: Vec<String> = sqlx::query_scalar(
+            "SELECT status FROM friends \
+             WHERE (user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1)",
+        )
+        .bind(from_user_id)
+        .bind(to_user_id)
+        .fetch_all(&mut *tx)
+        .await?;
+        if statuses.iter().any(|s| s == "blocked") {
+            return Err(FriendError::Blocked);
+        }
+        if !statuses.is_empty() {
+            return Err(FriendError::Conflict("You are already friends".to_string()));
+        }
+
+        let incoming: bool = sqlx::query_scalar(
+            "SELECT EXISTS (SELECT 1 FROM friend_requests \
+             WHERE from_user_id = $1 AND to_user_id = $2 AND status = 'pending')",
+        )
+        .bind(to_user_id)
+        .bind(from_user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if incoming {
+            return Err(FriendError::Conflict(
+                "This user already sent you a friend request; accept it instead".to_string(),
+            ));
+        }
+
+        let request_id: Option<Uuid> = sqlx::query_scalar(
+            "INSERT INTO friend_requests (from_user_id, to_user_id, status) \
+             VALUES ($1, $2, 'pending') \
+             ON CONFLICT (from_user_id, to_user_id) WHERE status = 'pending' DO NOTHING \
+             RETURNING id",
+        )
+        .bind(from_user_id)
+        .bind(to_user_id)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let request_id = request_id
+            .ok_or_else(|| FriendError::Conflict("Friend request already sent".to_string()))?;
+        tx.commit().await?;
+
+        let request = self.request(request_id).await?;
+        self.notify(
+            to_user_id,
+            request.id,
+            "Friend request",
+            format!("{} wants to be your friend", request.from_username),
+        )
+        .await;
+        Ok(request)
+    }
+
+    /// Pending requests sent to and by `user_id`, newest first.
+    pub async fn requests(&self, user_id: Uuid) -> Result<FriendRequests, FriendError> {
+        let pending = |column: &str| {
+            format!(
+                "{} WHERE r.{} = $1 AND r.status = 'pending' ORDER BY r.created_at DESC",
+                REQUEST_SELECT, column
+            )
+        };
+        let incoming = sqlx::query_as::<_, PendingFriendRequest>(&pending("to_user_id"))
+            .bind(user_id)
+            .fetch_all(&self.db_pool)
+            .await?;
+        let outgoing = sqlx::query_as::<_, PendingFriendRequest>(&pending("from_user_id"))
+            .bind(user_id)
+            .fetch_all(&self.db_pool)
+            .await?;
+        Ok(FriendRequests { incoming, outgoing })
+    }
+
+    /// Accept a request sent to `user_id`, settling any request the other way
+    /// too.
+    pub async fn accept_request(
+        &self,
+        user_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<FriendWithPresence, FriendError> {
+        let mut tx = self.db_pool.begin().await?;
+        let from_user_id: Uuid = sqlx::query_scalar(
+            "SELECT from_user_id FROM friend_requests \
+             WHERE id = $1 AND to_user_id = $2 AND status = 'pending' FOR UPDATE",
+        )
+        .bind(request_id)
+        .bind(user_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or(FriendError::RequestNotFound)?;
+
+        if blocks_among(&mut *tx, &[user_id, from_user_id])
+            .await?
+            .between(user_id, from_user_id)
+        {
+            return Err(FriendError::Blocked);
+        }
+        sqlx::query(
+            "INSERT INTO friends (user_id, friend_id, status) VALUES ($1, $2, 'accepted') \
+             ON CONFLICT (user_id, friend_id) DO NOTHING",
+        )
+        .bind(from_user_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        settle_requests(&mut tx, user_id, from_user_id, "accepted").await?;
+        tx.commit().await?;
+
+        info!(user_id = %user_id, friend_id = %from_user_id, "Friend request accepted");
+        let friend = self.friend(user_id, from_user_id).await?;
+        self.notify(
+            from_user_id,
+            request_id,
+            "Friend request accepted",
+            "Your friend request was accepted".to_string(),
+        )
+        .await;
+        Ok(friend)
+    }
+
+    /// Turn down a request sent to `user_id`.
+    pub async fn decline_request(
+        &self,
+        user_id: Uuid,
+        request_id: Uuid,
+    ) -> Result<(), FriendError> {
+        let result = sqlx::query(
+            "UPDATE friend_requests SET status = 'rejected', updated_at = NOW() \
+             WHERE id = $1 AND to_user_id = $2 AND status = 'pending'",
+        )
+        .bind(request_id)
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(FriendError::RequestNotFound);
+        }
+        Ok(())
+    }
+
+    /// Withdraw a request `user_id` sent.
+    pub async fn cancel_request(&self, user_id: Uuid, request_id: Uuid) -> Result<(), FriendError> {
+        let result = sqlx::query(
+            "DELETE FROM friend_requests \
+             WHERE id = $1 AND from_user_id = $2 AND status = 'pending'",
+        )
+        .bind(request_id)
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(FriendError::RequestNotFound);
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // BLOCKS
+    // ========================================================================
+
+    /// Block `target_id`, ending any friendship and turning down pending
+    /// requests between the two. Blocking twice is a no-op.
+    pub async fn block(&self, user_id: Uuid, target_id: Uuid) -> Result<(), FriendError> {
+        if user_id == target_id {
+            return Err(FriendError::InvalidRequest(
+                "You cannot block yourself".to_string(),
+            ));
+        }
+        let mut tx = self.db_pool.begin().await?;
+        require_user(&mut *tx, target_id).await?;
+
+        let were_friends = unfriend(&mut *tx, user_id, target_id).await?;
+        settle_requests(&mut tx, user_id, target_id, "rejected").await?;
+        sqlx::query(
+            "INSERT INTO friends (user_id, friend_id, status) VALUES ($1, $2, 'blocked') \
+             ON CONFLICT (user_id, friend_id) DO NOTHING",
+        )
+        .bind(user_id)
+        .bind(target_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!(user_id = %user_id, target_id = %target_id, "User blocked");
+        if were_friends {
+            self.notify_removed(user_id, target_id).await;
+        }
+        Ok(())
+    }
+
+    pub async fn unblock(&self, user_id: Uuid, target_id: Uuid) -> Result<(), FriendError> {
+        let result = sqlx::query(
+            "DELETE FROM friends WHERE user_id = $1 AND friend_id = $2 AND status = 'blocked'",
+        )
+        .bind(user_id)
+        .bind(target_id)
+        .execute(&self.db_pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(FriendError::NotBlocked);
+        }
+        Ok(())
+    }
+
+    /// Users `user_id` has blocked, most recent first.
+    pub async fn blocked(&self, user_id: Uuid) -> Result<Vec<BlockedUser>, FriendError> {
+        Ok(sqlx::query_as::<_, BlockedUser>(
+            r#"
+            SELECT u.id AS user_id, u.username, u.avatar_url, f.created_at AS blocked_at
+            FROM friends f
+            JOIN users u ON u.id = f.friend_id
+            WHERE f.user_id = $1 AND f.status = 'blocked'
+            ORDER BY f.created_at DESC
+            "#,
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?)
+    }
+
+    // ========================================================================
+    // HELPERS
+    // ========================================================================
+
+    async fn friend(
+        &self,
+        user_id: Uuid,
+        friend_id: Uuid,
+    ) -> Result<FriendWithPresence, FriendError> {
+        let friend = sqlx::query_as::<_, FriendProfile>(
+            r#"
+            SELECT u.id AS user_id, u.username, u.avatar_url, f.created_at AS friends_since
+            FROM friends f
+            JOIN users u ON u.id = $2
+            WHERE f.status = 'accepted'
+              AND ((f.user_id = $1 AND f.friend_id = $2) OR (f.user_id = $2 AND f.friend_id = $1))
+            ORDER BY f.created_at
+            LIMIT 1
+            "#,
+        )
+        .bind(user_id)
+        .bind(friend_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(FriendError::NotFriends)?;
+        let presence = self.presence.get(friend_id).await?;
+        Ok(FriendWithPresence { friend, presence })
+    }
+
+    async fn request(&self, request_id: Uuid) -> Result<PendingFriendRequest, FriendError> {
+        sqlx::query_as::<_, PendingFriendRequest>(&format!("{} WHERE r.id = $1", REQUEST_SELECT))
+            .bind(request_id)
+            .fetch_optional(&self.db_pool)
+            .await?
+            .ok_or(FriendError::RequestNotFound)
+    }
+
+    async fn notify(&self, user_id: Uuid, id: Uuid, title: &str, body: String) {
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+        let event = RealtimeEvent::Notification {
+            id,
+            title: title.to_string(),
+            body,
+            category: "friend_request".to_string(),
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        event_bus.publish_to_user(user_id, &event).await;
+    }
+
+    /// Tell both users they are no longer friends, which also stops their
+    /// gateway sessions following each other's presence.
+    async fn notify_removed(&self, a: Uuid, b: Uuid) {
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+        for (user_id, former_friend) in [(a, b), (b, a)] {
+            let event = RealtimeEvent::FriendRemoved {
+                user_id: former_friend,
+                timestamp: Utc::now().to_rfc3339(),
+            };
+            event_bus.publish_to_user(user_id, &event).await;
+        }
+    }
+}
+
+async fn require_user<'e>(executor: impl PgExecutor<'e>, user_id: Uuid) -> Result<(), FriendError> {
+    let exists: bool = sqlx::query_scalar(
+        "SELECT EXISTS (SELECT 1 FROM users WHERE id = $1 AND is_active IS NOT FALSE)",
+    )
+    .bind(user_id)
+    .fetch_one(executor)
+    .await?;
+    if exists {
+        Ok(())
+    } else {
+        Err(FriendError::UserNotFound)
+    }
+}
+
+/// Delete the friendship between `a` and `b`; whether there was one.
+async fn unfriend<'e>(
+    executor: impl PgExecutor<'e>,
+    a: Uuid,
+    b: Uuid,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "DELETE FROM friends WHERE status = 'accepted' \
+         AND ((user_id = $1 AND friend_id = $2) OR (user_id = $2 AND friend_id = $1))",
+    )
+    .bind(a)
+    .bind(b)
+    .execute(executor)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+/// Close pending requests between `a` and `b` in both directions.
+async fn settle_requests(
+    tx: &mut Transaction<'_, Postgres>,
+    a: Uuid,
+    b: Uuid,
+    status: &str,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "UPDATE friend_requests SET status = $3, updated_at = NOW() \
+         WHERE status = 'pending' \
+         AND ((from_user_id = $1 AND to_user_id = $2) OR (from_user_id = $2 AND to_user_id = $1))",
+    )
+    .bind(a)
+    .bind(b)
+    .bind(status)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+/// Friends who are online, playing or spectating first, then by name.
+fn sort_friends(friends: &mut [FriendWithPresence]) {
+    friends.sort_by(|a, b| {
+        let offline = |f: &FriendWithPresence| f.presence.status == PresenceStatus::Offline;
+        offline(a).cmp(&offline(b)).then_with(|| {
+            a.friend
+                .username
+                .to_lowercase()
+                .cmp(&b.friend.username.to_lowercase())
+        })
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn friend(username: &str, presence: Presence) -> FriendWithPresence {
+        FriendWithPresence {
+            friend: FriendProfile {
+                user_id: Uuid::new_v4(),
+                username: username.to_string(),
+                avatar_url: None,
+                friends_since: Utc::now(),
+            },
+            presence,
+        }
+    }
+
+    #[test]
+    fn test_block_list_is_symmetric() {
+        let (a, b, c) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let blocks: BlockList = vec![(a, b)].into_iter().collect();
+        assert!(blocks.between(a, b));
+        assert!(blocks.between(b, a));
+        assert!(!blocks.between(a, c));
+    }
+
+    #[test]
+    fn test_online_friends_sort_first() {
+        let playing = Presence {
+            status: PresenceStatus::InMatch,
+            match_id: Some(Uuid::new_v4()),
+        };
+        let mut friends = vec![
+            friend("amara", Presence::offline()),
+            friend("Zainab", playing),
+            friend("bayo", Presence::online()),
+        ];
+        sort_friends(&mut friends);
+        let names: Vec<&str> = friends.iter().map(|f| f.friend.username.as_str()).collect();
+        assert_eq!(names, vec!["bayo", "Zainab", "amara"]);
+    }
+}
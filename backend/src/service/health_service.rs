@@ -0,0 +1,447 @@
+//! Liveness, readiness and the `/metrics` scrape.
+//!
+//! Readiness checks Postgres, Redis and Soroban RPC in parallel, each within
+//! the probe timeout, and reports every dependency's state and latency.
+//! Postgres and Redis are required; an unreachable Soroban RPC is reported
+//! but leaves the instance ready, since most of the API does not touch the
+//! chain and pulling every instance out of rotation would not bring it back.
+//!
+//! Metrics other than request latencies are read from their source on each
+//! scrape: queue depths from Postgres and Redis, relayer outcomes over the
+//! last hour from `relayer_submissions`, and indexer lag from the indexer's
+//! checkpoints against the latest ledger on RPC.
+
+use crate::config::TelemetryConfig;
+use crate::db::DbPool;
+use crate::service::chain_indexer::rpc::EventRpcClient;
+use crate::service::notification_service;
+use crate::telemetry::metrics::{Exposition, HttpMetrics};
+use redis::aio::ConnectionManager;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::warn;
+
+/// Checkpoint of the indexer following the tail of the chain.
+const LIVE_CHECKPOINT: &str = "live";
+/// Window over which relayer outcomes are counted.
+const RELAYER_WINDOW: &str = "1 hour";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyStatus {
+    Up,
+    Down,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DependencyCheck {
+    pub name: &'static str,
+    pub status: DependencyStatus,
+    /// Whether the instance is unready while this dependency is down.
+    pub required: bool,
+    pub latency_ms: u64,
+    pub detail: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadinessReport {
+    pub ready: bool,
+    pub checks: Vec<DependencyCheck>,
+}
+
+impl ReadinessReport {
+    pub fn new(checks: Vec<DependencyCheck>) -> Self {
+        let ready = checks
+            .iter()
+            .all(|check| !check.required || check.status == DependencyStatus::Up);
+        Self { ready, checks }
+    }
+
+    pub fn check(&self, name: &str) -> Option<&DependencyCheck> {
+        self.checks.iter().find(|check| check.name == name)
+    }
+}
+
+pub struct HealthService {
+    db_pool: DbPool,
+    redis: ConnectionManager,
+    rpc: EventRpcClient,
+    http_metrics: Arc<HttpMetrics>,
+    probe_timeout: Duration,
+    /// SHA-256 of the scrape token, if one is required.
+    metrics_token_hash: Option<[u8; 32]>,
+}
+
+impl HealthService {
+    pub fn new(
+        db_pool: DbPool,
+        redis: ConnectionManager,
+        rpc_url: String,
+        http_metrics: Arc<HttpMetrics>,
+        config: &TelemetryConfig,
+    ) -> Self {
+        Self {
+            db_pool,
+            redis,
+            rpc: EventRpcClient::new(rpc_url),
+            http_metrics,
+            probe_timeout: Duration::from_millis(config.probe_timeout_ms),
+            metrics_token_hash: config
+                .metrics_token
+                .as_deref()
+                .map(|token| Sha256::digest(token.as_bytes()).into()),
+        }
+    }
+
+    // ========================================================================
+    // PROBES
+    // ========================================================================
+
+    pub async fn readiness(&self) -> ReadinessReport {
+        let (postgres, redis, soroban) = tokio::join!(
+            self.probe("postgres", true, self.check_postgres()),
+            self.probe("redis", true, self.check_redis()),
+            self.probe("soroban_rpc", false, self.check_soroban()),
+        );
+        ReadinessReport::new(vec![postgres, redis, soroban])
+    }
+
+    async fn probe<F>(&self, name: &'static str, required: bool, check: F) -> DependencyCheck
+    where
+        F: Future<Output = Result<Option<String>, String>>,
+    {
+        let start = Instant::now();
+        let outcome = tokio::time::timeout(self.probe_timeout, check).await;
+        let latency_ms = start.elapsed().as_millis() as u64;
+        let (status, detail) = match outcome {
+            Ok(Ok(detail)) => (DependencyStatus::Up, detail),
+            Ok(Err(error)) => (DependencyStatus::Down, Some(error)),
+            Err(_) => (
+                DependencyStatus::Down,
+                Some(format!(
+                    "no response within {} ms",
+                    self.probe_timeout.as_millis()
+                )),
+            ),
+        };
+        if status == DependencyStatus::Down {
+            warn!(dependency = name, detail = ?detail, "Dependency check failed");
+        }
+        DependencyCheck {
+            name,
+            status,
+            required,
+            latency_ms,
+            detail,
+        }
+    }
+
+    async fn check_postgres(&self) -> Result<Option<String>, String> {
+        sqlx::query("SELECT 1")
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Some(format!(
+            "{} of {} connections idle",
+            self.db_pool.num_idle(),
+            self.db_pool.size()
+        )))
+    }
+
+    async fn check_redis(&self) -> Result<Option<String>, String> {
+        let mut conn = self.redis.clone();
+        redis::cmd("PING")
+            .query_async::<String>(&mut conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(None)
+    }
+
+    async fn check_soroban(&self) -> Result<Option<String>, String> {
+        let ledger = self
+            .rpc
+            .get_latest_ledger()
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(Some(format!("latest ledger {}", ledger)))
+    }
+
+    // ========================================================================
+    // METRICS
+    // ========================================================================
+
+    /// Whether an `Authorization` header value may scrape `/metrics`.
+    pub fn authorize_scrape(&self, authorization: Option<&str>) -> bool {
+        let Some(expected) = &self.metrics_token_hash else {
+            return true;
+        };
+        authorization
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|token| Sha256::digest(token.trim().as_bytes()).as_slice() == expected)
+    }
+
+    /// Everything `/metrics` exposes, in the Prometheus text format. A source
+    /// that cannot be read is logged and left out.
+    pub async fn render_metrics(&self) -> String {
+        let mut out = Exposition::new();
+        self.http_metrics.write(&mut out);
+
+        let readiness = self.readiness().await;
+        out.header(
+            "arenax_dependency_up",
+            "gauge",
+            "Whether a dependency answered its readiness check.",
+        );
+        for check in &readiness.checks {
+            let up = (check.status == DependencyStatus::Up) as u8;
+            out.sample(
+                "arenax_dependency_up",
+                &[("dependency", check.name)],
+                up.into(),
+            );
+        }
+        out.header(
+            "arenax_dependency_check_seconds",
+            "gauge",
+            "Time taken by a dependency's readiness check.",
+        );
+        for check in &readiness.checks {
+            out.sample(
+                "arenax_dependency_check_seconds",
+                &[("dependency", check.name)],
+                check.latency_ms as f64 / 1000.0,
+            );
+        }
+
+        match self.queue_depths().await {
+            Ok(depths) => {
+                out.header(
+                    "arenax_queue_depth",
+                    "gauge",
+                    "Items waiting to be processed, by queue.",
+                );
+                for (queue, depth) in &depths {
+                    out.sample("arenax_queue_depth", &[("queue", queue)], *depth as f64);
+                }
+            }
+            Err(e) => warn!(error = %e, "Failed to read queue depths"),
+        }
+
+        match self.relayer_outcomes().await {
+            Ok(outcomes) => write_relayer_outcomes(&mut out, &outcomes),
+            Err(e) => warn!(error = %e, "Failed to read relayer outcomes"),
+        }
+
+        match self.indexer_checkpoints().await {
+            Ok(checkpoints) => {
+                let latest = tokio::time::timeout(self.probe_timeout, self.rpc.get_latest_ledger())
+                    .await
+                    .ok()
+                    .and_then(Result::ok);
+                write_indexer_lag(&mut out, &checkpoints, latest);
+            }
+            Err(e) => warn!(error = %e, "Failed to read indexer checkpoints"),
+        }
+
+        out.finish()
+    }
+
+    async fn queue_depths(&self) -> Result<Vec<(String, i64)>, String> {
+        let mut depths: Vec<(String, i64)> = sqlx::query_as(
+            r#"
+            SELECT 'relayer_queued', COUNT(*) FROM relayer_submissions WHERE status = 'queued'
+            UNION ALL
+            SELECT 'relayer_in_flight', COUNT(*) FROM relayer_submissions WHERE status = 'submitted'
+            UNION ALL
+            SELECT 'webhook_deliveries', COUNT(*) FROM webhook_deliveries WHERE status = 'pending'
+            UNION ALL
+            SELECT 'anticheat_review', COUNT(*) FROM anticheat_detections
+                WHERE status = 'pending_review'
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(|e| e.to_string())?;
+
+        let mut conn = self.redis.clone();
+        let notifications: i64 = redis::cmd("ZCARD")
+            .arg(notification_service::QUEUE_KEY)
+            .query_async(&mut conn)
+            .await
+            .map_err(|e| e.to_string())?;
+        depths.push(("notifications".to_string(), notifications));
+        Ok(depths)
+    }
+
+    /// Submissions settled within [`RELAYER_WINDOW`], by final status.
+    async fn relayer_outcomes(&self) -> Result<Vec<(String, i64)>, sqlx::Error> {
+        sqlx::query_as(&format!(
+            r#"
+            SELECT status, COUNT(*) FROM relayer_submissions
+            WHERE status IN ('confirmed', 'failed', 'expired')
+              AND updated_at > NOW() - INTERVAL '{}'
+            GROUP BY status
+            ORDER BY status
+            "#,
+            RELAYER_WINDOW
+        ))
+        .fetch_all(&self.db_pool)
+        .await
+    }
+
+    /// Each indexer checkpoint's ledger and seconds since it last moved.
+    async fn indexer_checkpoints(&self) -> Result<Vec<(String, i64, f64)>, sqlx::Error> {
+        sqlx::query_as(
+            r#"
+            SELECT name, last_ledger, EXTRACT(EPOCH FROM NOW() - updated_at)::FLOAT8
+            FROM chain_indexer_cursors
+            ORDER BY name
+            "#,
+        )
+        .fetch_all(&self.db_pool)
+        .await
+    }
+}
+
+fn write_relayer_outcomes(out: &mut Exposition, outcomes: &[(String, i64)]) {
+    out.header(
+        "arenax_relayer_submissions_settled",
+        "gauge",
+        "Relayer submissions settled in the last hour, by outcome.",
+    );
+    for (status, count) in outcomes {
+        out.sample(
+            "arenax_relayer_submissions_settled",
+            &[("status", status)],
+            *count as f64,
+        );
+    }
+    if let Some(ratio) = success_ratio(outcomes) {
+        out.gauge(
+            "arenax_relayer_success_ratio",
+            "Share of relayer submissions settled in the last hour that were confirmed.",
+            ratio,
+        );
+    }
+}
+
+fn success_ratio(outcomes: &[(String, i64)]) -> Option<f64> {
+    let total: i64 = outcomes.iter().map(|(_, count)| count).sum();
+    let confirmed: i64 = outcomes
+        .iter()
+        .filter(|(status, _)| status == "confirmed")
+        .map(|(_, count)| count)
+        .sum();
+    (total > 0).then(|| confirmed as f64 / total as f64)
+}
+
+fn write_indexer_lag(
+    out: &mut Exposition,
+    checkpoints: &[(String, i64, f64)],
+    latest: Option<u64>,
+) {
+    out.header(
+        "arenax_indexer_last_ledger",
+        "gauge",
+        "Last ledger indexed, by checkpoint.",
+    );
+    for (name, ledger, _) in checkpoints {
+        out.sample(
+            "arenax_indexer_last_ledger",
+            &[("checkpoint", name)],
+            *ledger as f64,
+        );
+    }
+    out.header(
+        "arenax_indexer_checkpoint_age_seconds",
+        "gauge",
+        "Time since a checkpoint last moved.",
+    );
+    for (name, _, age) in checkpoints {
+        out.sample(
+            "arenax_indexer_checkpoint_age_seconds",
+            &[("checkpoint", name)],
+            *age,
+        );
+    }
+    let Some(latest) = latest else {
+        return;
+    };
+    out.gauge(
+        "arenax_chain_latest_ledger",
+        "Latest ledger known to Soroban RPC.",
+        latest as f64,
+    );
+    if let Some((_, live, _)) = checkpoints
+        .iter()
+        .find(|(name, _, _)| name == LIVE_CHECKPOINT)
+    {
+        out.gauge(
+            "arenax_indexer_lag_ledgers",
+            "Ledgers the live indexer is behind Soroban RPC.",
+            latest.saturating_sub((*live).max(0) as u64) as f64,
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(name: &'static str, required: bool, status: DependencyStatus) -> DependencyCheck {
+        DependencyCheck {
+            name,
+            status,
+            required,
+            latency_ms: 1,
+            detail: None,
+        }
+    }
+
+    #[test]
+    fn test_readiness_ignores_optional_dependencies() {
+        let report = ReadinessReport::new(vec![
+            check("postgres", true, DependencyStatus::Up),
+            check("soroban_rpc", false, DependencyStatus::Down),
+        ]);
+        assert!(report.ready);
+        assert_eq!(
+            report.check("soroban_rpc").map(|c| c.status),
+            Some(DependencyStatus::Down)
+        );
+
+        let report = ReadinessReport::new(vec![
+            check("postgres", true, DependencyStatus::Down),
+            check("soroban_rpc", false, DependencyStatus::Up),
+        ]);
+        assert!(!report.ready);
+    }
+
+    #[test]
+    fn test_relayer_and_indexer_metrics() {
+        let outcomes = vec![
+            ("confirmed".to_string(), 9),
+            ("expired".to_string(), 0),
+            ("failed".to_string(), 3),
+        ];
+        assert_eq!(success_ratio(&outcomes), Some(0.75));
+        assert_eq!(success_ratio(&[]), None);
+
+        let checkpoints = vec![
+            ("backfill".to_string(), 100, 3600.0),
+            ("live".to_string(), 1_000, 4.5),
+        ];
+        let mut out = Exposition::new();
+        write_indexer_lag(&mut out, &checkpoints, Some(1_012));
+        let text = out.finish();
+        assert!(text.contains("arenax_indexer_lag_ledgers 12\n"));
+        assert!(text.contains("arenax_indexer_last_ledger{checkpoint=\"live\"} 1000\n"));
+
+        let mut out = Exposition::new();
+        write_indexer_lag(&mut out, &checkpoints, None);
+        assert!(!out.finish().contains("arenax_indexer_lag_ledgers"));
+    }
+}
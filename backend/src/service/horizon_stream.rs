@@ -0,0 +1,243 @@
+//! Horizon payment streaming over server-sent events.
+//!
+//! Horizon keeps `/accounts/{id}/payments` open as an event stream when asked
+//! for `text/event-stream`, sending each operation as it lands. Streams are
+//! resumed from the `paging_token` of the last processed record, so callers
+//! persist that token and reconnect from it.
+
+use serde::Deserialize;
+use std::collections::VecDeque;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Reconnect when the stream has been silent this long; a dead connection
+/// would otherwise wait forever.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(300);
+
+const STROOPS_PER_UNIT: i64 = 10_000_000;
+
+#[derive(Debug, Error)]
+pub enum HorizonError {
+    #[error("Horizon request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Horizon responded with status {status}: {body}")]
+    Status { status: u16, body: String },
+    #[error("Invalid Horizon record: {0}")]
+    Decode(#[from] serde_json::Error),
+    #[error("Horizon stream idle for {0:?}")]
+    Idle(Duration),
+}
+
+/// The fields of a Horizon payment operation record the custody service uses.
+#[derive(Debug, Clone, Deserialize)]
+pub struct HorizonPayment {
+    pub id: String,
+    pub paging_token: String,
+    #[serde(rename = "type")]
+    pub kind: String,
+    #[serde(default)]
+    pub transaction_successful: bool,
+    pub transaction_hash: String,
+    #[serde(default)]
+    pub asset_type: Option<String>,
+    #[serde(default)]
+    pub from: Option<String>,
+    #[serde(default)]
+    pub to: Option<String>,
+    /// Muxed sub-account id of the destination, as a decimal string.
+    #[serde(default)]
+    pub to_muxed_id: Option<String>,
+    #[serde(default)]
+    pub amount: Option<String>,
+}
+
+impl HorizonPayment {
+    /// Amount in stroops if this is a successful native payment into `account`.
+    pub fn incoming_native_amount(&self, account: &str) -> Option<i64> {
+        if self.kind != "payment"
+            || !self.transaction_successful
+            || self.asset_type.as_deref() != Some("native")
+            || self.to.as_deref() != Some(account)
+        {
+            return None;
+        }
+        parse_stroops(self.amount.as_deref()?)
+    }
+
+    pub fn muxed_id(&self) -> Option<u64> {
+        self.to_muxed_id.as_deref()?.parse().ok()
+    }
+}
+
+/// An open payments stream for one account.
+pub struct PaymentStream {
+    response: reqwest::Response,
+    decoder: SseDecoder,
+    pending: VecDeque<String>,
+}
+
+impl PaymentStream {
+    /// Open the stream after `cursor`; `"now"` starts from the latest ledger.
+    pub async fn connect(
+        client: &reqwest::Client,
+        horizon_url: &str,
+        account: &str,
+        cursor: &str,
+    ) -> Result<Self, HorizonError> {
+        let url = format!(
+            "{}/accounts/{}/payments",
+            horizon_url.trim_end_matches('/'),
+            account
+        );
+        let response = client
+            .get(url)
+            .query(&[("cursor", cursor), ("order", "asc")])
+            .header(reqwest::header::ACCEPT, "text/event-stream")
+            .send()
+            .await?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(HorizonError::Status { status, body });
+        }
+        Ok(Self {
+            response,
+            decoder: SseDecoder::default(),
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Next payment record, or `None` once Horizon closes the stream.
+    pub async fn next(&mut self) -> Result<Option<HorizonPayment>, HorizonError> {
+        loop {
+            while let Some(data) = self.pending.pop_front() {
+                // Horizon greets with `"hello"` and says `"byebye"` on close;
+                // only JSON objects are records.
+                if data.starts_with('{') {
+                    return Ok(Some(serde_json::from_str(&data)?));
+                }
+            }
+            let chunk = tokio::time::timeout(IDLE_TIMEOUT, self.response.chunk())
+                .await
+                .map_err(|_| HorizonError::Idle(IDLE_TIMEOUT))??;
+            match chunk {
+                Some(bytes) => self.pending.extend(self.decoder.push(&bytes)),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Incremental decoder of an event stream into the `data` of each event.
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buf: Vec<u8>,
+}
+
+impl SseDecoder {
+    /// Feed bytes from the wire and return the data of every event completed
+    /// by them.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buf.extend(chunk.iter().filter(|b| **b != b'\r'));
+        let mut events = Vec::new();
+        while let Some(end) = self.buf.windows(2).position(|w| w == b"\n\n") {
+            let raw: Vec<u8> = self.buf.drain(..end + 2).collect();
+            let raw = String::from_utf8_lossy(&raw[..end]);
+            let data: Vec<&str> = raw
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(|value| value.strip_prefix(' ').unwrap_or(value))
+                .collect();
+            if !data.is_empty() {
+                events.push(data.join("\n"));
+            }
+        }
+        events
+    }
+}
+
+/// Parse a Horizon amount such as `"12.5000000"` into stroops.
+pub fn parse_stroops(amount: &str) -> Option<i64> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    if whole.is_empty()
+        || fraction.len() > 7
+        || !whole.bytes().all(|b| b.is_ascii_digit())
+        || !fraction.bytes().all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let fraction = format!("{:0<7}", fraction).parse::<i64>().ok()?;
+    whole
+        .parse::<i64>()
+        .ok()?
+        .checked_mul(STROOPS_PER_UNIT)?
+        .checked_add(fraction)
+}
+
+/// Format a non-negative number of stroops as an amount such as `"12.5"`;
+/// the inverse of [`parse_stroops`].
+pub fn format_stroops(stroops: i64) -> String {
+    let whole = stroops / STROOPS_PER_UNIT;
+    let fraction = stroops % STROOPS_PER_UNIT;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{:07}", fraction);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decoder_joins_events_split_across_chunks() {
+        let mut decoder = SseDecoder::default();
+        let events =
+            decoder.push(b"retry: 1000\nevent: open\ndata: \"hello\"\n\nid: 1\ndata: {\"a\":");
+        assert_eq!(events, vec!["\"hello\"".to_string()]);
+        assert_eq!(decoder.push(b"1}\r\n\r\n"), vec!["{\"a\":1}".to_string()]);
+        assert!(decoder.push(b": keepalive\n\n").is_empty());
+    }
+
+    #[test]
+    fn test_parse_stroops() {
+        assert_eq!(parse_stroops("12.5000000"), Some(125_000_000));
+        assert_eq!(parse_stroops("0.0000001"), Some(1));
+        assert_eq!(parse_stroops("3"), Some(30_000_000));
+        assert_eq!(parse_stroops("1.00000001"), None);
+        assert_eq!(parse_stroops("-1.0"), None);
+        assert_eq!(parse_stroops(".5"), None);
+        assert_eq!(format_stroops(125_000_000), "12.5");
+        assert_eq!(format_stroops(1), "0.0000001");
+        assert_eq!(format_stroops(30_000_000), "3");
+    }
+
+    #[test]
+    fn test_incoming_native_amount_filters_records() {
+        let record = |kind: &str, asset: &str, to: &str| HorizonPayment {
+            id: "1".to_string(),
+            paging_token: "1".to_string(),
+            kind: kind.to_string(),
+            transaction_successful: true,
+            transaction_hash: "ab".to_string(),
+            asset_type: Some(asset.to_string()),
+            from: Some("GFROM".to_string()),
+            to: Some(to.to_string()),
+            to_muxed_id: Some("7".to_string()),
+            amount: Some("1.5".to_string()),
+        };
+        let deposit = record("payment", "native", "GCUSTODY");
+        assert_eq!(deposit.incoming_native_amount("GCUSTODY"), Some(15_000_000));
+        assert_eq!(deposit.muxed_id(), Some(7));
+        assert_eq!(deposit.incoming_native_amount("GOTHER"), None);
+        assert_eq!(
+            record("payment", "credit_alphanum4", "GCUSTODY").incoming_native_amount("GCUSTODY"),
+            None
+        );
+        assert_eq!(
+            record("create_account", "native", "GCUSTODY").incoming_native_amount("GCUSTODY"),
+            None
+        );
+    }
+}
@@ -2,6 +2,7 @@ use crate::api_error::ApiError;
 use crate::db::DbPool;
 use crate::models::idempotency::*;
 use chrono::Utc;
+use serde::{Deserialize, Serialize};
 use sqlx;
 use uuid::Uuid;
 
@@ -140,7 +141,7 @@ impl IdempotencyService {
         .await
         .map_err(|e| ApiError::database_error(e))?;
 
-        Ok(result.rows_affected())
+        Ok(result.rows_affected() as i64)
     }
 
     /// Get statistics about idempotency key usage
@@ -192,10 +193,9 @@ impl IdempotencyService {
         let keys = sqlx::query_as!(
             IdempotencyKey,
             r#"
-            SELECT ik.* FROM idempotency_keys ik
-            JOIN request_logs rl ON ik.key = rl.idempotency_key
-            WHERE rl.user_id = $1
-            ORDER BY ik.created_at DESC
+            SELECT * FROM idempotency_keys
+            WHERE user_id = $1
+            ORDER BY created_at DESC
             LIMIT $2 OFFSET $3
             "#,
             user_id,
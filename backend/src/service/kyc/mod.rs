@@ -0,0 +1,494 @@
+//! Identity verification (KYC) for withdrawals.
+//!
+//! Users verify to a [`KycLevel`], and each level has its own daily limit on
+//! custodial withdrawals (see [`daily_limit`]). With Sumsub configured, users
+//! go through Sumsub's web SDK and its webhooks move the case along;
+//! otherwise they submit their personal details and an admin decides. Admins
+//! can also decide any case by hand, e.g. to grant a level Sumsub put on
+//! hold, or to lower one.
+//!
+//! Submitted personal details are stored AES-256-GCM encrypted and are only
+//! decrypted for admins reviewing the case. Every status change is recorded
+//! in `kyc_events`.
+
+pub mod sumsub;
+
+use crate::api_error::ApiError;
+use crate::auth::totp::SecretCipher;
+use crate::config::{KycConfig, KycLimits};
+use crate::db::DbPool;
+use crate::models::{
+    KycEvent, KycLevel, KycPersonalDetails, KycProfile, KycReview, KycStatus, KycStatusResponse,
+    ReviewKycRequest, StartKycRequest,
+};
+use serde_json::json;
+use sqlx::Postgres;
+use thiserror::Error;
+use tracing::{info, warn};
+use uuid::Uuid;
+
+pub use sumsub::{SumsubClient, SumsubWebhook};
+
+const PROFILE_COLUMNS: &str = "user_id, level, status, requested_level, provider, \
+     provider_reference, rejection_reason, reviewed_by, reviewed_at, created_at, updated_at";
+
+#[derive(Debug, Error)]
+pub enum KycError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("{0}")]
+    InvalidState(String),
+    #[error("KYC case not found")]
+    NotFound,
+    #[error("Invalid webhook signature")]
+    InvalidSignature,
+    #[error("Invalid KYC encryption key")]
+    InvalidKey,
+    #[error("Stored personal details could not be decrypted")]
+    Decrypt,
+    #[error("KYC provider request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("KYC provider returned status {status}: {body}")]
+    Provider { status: u16, body: String },
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<KycError> for ApiError {
+    fn from(err: KycError) -> Self {
+        match err {
+            KycError::DatabaseError(e) => ApiError::DatabaseError(e),
+            KycError::NotFound => ApiError::NotFound,
+            KycError::InvalidSignature => ApiError::Unauthorized,
+            KycError::InvalidState(_) => ApiError::Conflict(err.to_string()),
+            KycError::InvalidRequest(_) => ApiError::BadRequest(err.to_string()),
+            KycError::InvalidKey
+            | KycError::Decrypt
+            | KycError::Request(_)
+            | KycError::Provider { .. } => ApiError::internal_error(err.to_string()),
+        }
+    }
+}
+
+/// Most a user at `level` may withdraw from custody in any 24 hours
+/// (stroops).
+pub fn daily_limit(limits: &KycLimits, level: KycLevel) -> i64 {
+    match level {
+        KycLevel::None => limits.none,
+        KycLevel::Basic => limits.basic,
+        KycLevel::Full => limits.full,
+    }
+}
+
+/// The level `user_id` is verified to, read within `tx`.
+pub async fn level_in(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<KycLevel, sqlx::Error> {
+    let level: Option<KycLevel> =
+        sqlx::query_scalar("SELECT level FROM kyc_profiles WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(&mut **tx)
+            .await?;
+    Ok(level.unwrap_or(KycLevel::None))
+}
+
+#[derive(sqlx::FromRow)]
+struct CaseRow {
+    #[sqlx(flatten)]
+    profile: KycProfile,
+    pii_encrypted: Option<String>,
+}
+
+pub struct KycService {
+    db_pool: DbPool,
+    cipher: SecretCipher,
+    sumsub: Option<SumsubClient>,
+    limits: KycLimits,
+}
+
+impl KycService {
+    /// `encryption_key` is 32 bytes as hex.
+    pub fn new(
+        db_pool: DbPool,
+        encryption_key: &str,
+        config: &KycConfig,
+    ) -> Result<Self, KycError> {
+        Ok(Self {
+            db_pool,
+            cipher: SecretCipher::from_hex(encryption_key).map_err(|_| KycError::InvalidKey)?,
+            sumsub: config.sumsub.clone().map(SumsubClient::new),
+            limits: config.limits,
+        })
+    }
+
+    pub async fn status(&self, user_id: Uuid) -> Result<KycStatusResponse, KycError> {
+        let profile = self.profile(user_id).await?;
+        Ok(self.response(profile, None))
+    }
+
+    /// Start verifying the user to `request.level`.
+    pub async fn start(
+        &self,
+        user_id: Uuid,
+        request: &StartKycRequest,
+    ) -> Result<KycStatusResponse, KycError> {
+        let current = self.profile(user_id).await?;
+        if request.level <= current.level {
+            return Err(KycError::InvalidRequest(format!(
+                "Already verified at the {} level",
+                current.level
+            )));
+        }
+        if current.status == KycStatus::InReview {
+            return Err(KycError::InvalidState(
+                "A verification is already under review".to_string(),
+            ));
+        }
+        if self.sumsub.is_none() && request.details.is_none() {
+            return Err(KycError::InvalidRequest(
+                "Personal details are required".to_string(),
+            ));
+        }
+        let pii = request
+            .details
+            .as_ref()
+            .map(|details| self.encrypt(details));
+
+        let (provider, status, access_token) = match &self.sumsub {
+            Some(sumsub) => (
+                Some("sumsub"),
+                KycStatus::Pending,
+                Some(sumsub.access_token(user_id, request.level).await?),
+            ),
+            None => (None, KycStatus::InReview, None),
+        };
+
+        let mut tx = self.db_pool.begin().await?;
+        let profile: KycProfile = sqlx::query_as(&format!(
+            r#"
+            UPDATE kyc_profiles
+            SET status = $2, requested_level = $3, provider = $4,
+                pii_encrypted = COALESCE($5, pii_encrypted),
+                rejection_reason = NULL, updated_at = NOW()
+            WHERE user_id = $1
+            RETURNING {PROFILE_COLUMNS}
+            "#
+        ))
+        .bind(user_id)
+        .bind(status)
+        .bind(request.level)
+        .bind(provider)
+        .bind(pii)
+        .fetch_one(&mut *tx)
+        .await?;
+        record_event(
+            &mut tx,
+            user_id,
+            "user",
+            &profile,
+            None,
+            json!({ "requested_level": request.level, "details": request.details.is_some() }),
+        )
+        .await?;
+        tx.commit().await?;
+
+        info!(user_id = %user_id, level = %request.level, "KYC verification started");
+        Ok(self.response(profile, access_token))
+    }
+
+    /// Apply a Sumsub webhook. `digest` and `algorithm` are its
+    /// `X-Payload-Digest` and `X-Payload-Digest-Alg` headers.
+    pub async fn handle_sumsub_webhook(
+        &self,
+        body: &[u8],
+        digest: Option<&str>,
+        algorithm: Option<&str>,
+    ) -> Result<(), KycError> {
+        let sumsub = self.sumsub.as_ref().ok_or(KycError::NotFound)?;
+        if !digest.is_some_and(|digest| sumsub.verify_webhook(body, digest, algorithm)) {
+            return Err(KycError::InvalidSignature);
+        }
+        let webhook: SumsubWebhook = serde_json::from_slice(body)
+            .map_err(|e| KycError::InvalidRequest(format!("Invalid webhook: {}", e)))?;
+        let Some(status) = webhook.status() else {
+            return Ok(());
+        };
+        let Some(user_id) = webhook
+            .external_user_id
+            .as_deref()
+            .and_then(|id| Uuid::parse_str(id).ok())
+        else {
+            warn!(applicant_id = %webhook.applicant_id, "Sumsub webhook for an unknown user");
+            return Ok(());
+        };
+
+        let mut tx = self.db_pool.begin().await?;
+        let Some(current) = profile_for_update(&mut tx, user_id).await? else {
+            warn!(user_id = %user_id, "Sumsub webhook without a KYC case");
+            return Ok(());
+        };
+        let verified = webhook
+            .level_name
+            .as_deref()
+            .and_then(|name| sumsub.level_for(name))
+            .or(current.requested_level);
+        let level = match (status, verified) {
+            (KycStatus::Approved, Some(verified)) => current.level.max(verified),
+            _ => current.level,
+        };
+
+        let profile: KycProfile = sqlx::query_as(&format!(
+            r#"
+            UPDATE kyc_profiles
+            SET status = $2, level = $3, provider = 'sumsub', provider_reference = $4,
+                rejection_reason = $5, reviewed_by = NULL,
+                reviewed_at = CASE WHEN $2 IN ('approved', 'rejected') THEN NOW() END,
+                updated_at = NOW()
+            WHERE user_id = $1
+            RETURNING {PROFILE_COLUMNS}
+            "#
+        ))
+        .bind(user_id)
+        .bind(status)
+        .bind(level)
+        .bind(&webhook.applicant_id)
+        .bind(webhook.rejection_reason())
+        .fetch_one(&mut *tx)
+        .await?;
+        record_event(
+            &mut tx,
+            user_id,
+            "provider",
+            &profile,
+            None,
+            json!({
+                "type": webhook.kind,
+                "level_name": webhook.level_name,
+                "review_result": webhook.review_result.as_ref().map(|review| json!({
+                    "review_answer": review.review_answer,
+                    "review_reject_type": review.review_reject_type,
+                    "reject_labels": review.reject_labels,
+                })),
+            }),
+        )
+        .await?;
+        tx.commit().await?;
+
+        info!(user_id = %user_id, status = ?status, level = %level, "KYC case updated by Sumsub");
+        Ok(())
+    }
+
+    /// Cases with `status`, oldest first.
+    pub async fn list_cases(
+        &self,
+        status: KycStatus,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<KycProfile>, KycError> {
+        Ok(sqlx::query_as(&format!(
+            "SELECT {PROFILE_COLUMNS} FROM kyc_profiles WHERE status = $1
+             ORDER BY updated_at LIMIT $2 OFFSET $3"
+        ))
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?)
+    }
+
+    /// A case with its decrypted personal details and history, for admins.
+    pub async fn case(&self, user_id: Uuid) -> Result<KycReview, KycError> {
+        let case: CaseRow = sqlx::query_as(&format!(
+            "SELECT {PROFILE_COLUMNS}, pii_encrypted FROM kyc_profiles WHERE user_id = $1"
+        ))
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(KycError::NotFound)?;
+        let details = case
+            .pii_encrypted
+            .map(|pii| self.decrypt(&pii))
+            .transpose()?;
+        let events: Vec<KycEvent> = sqlx::query_as(
+            "SELECT * FROM kyc_events WHERE user_id = $1 ORDER BY created_at DESC LIMIT 100",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(KycReview {
+            profile: case.profile,
+            details,
+            events,
+        })
+    }
+
+    /// An admin's decision on a case.
+    pub async fn decide(
+        &self,
+        admin_id: Uuid,
+        user_id: Uuid,
+        request: &ReviewKycRequest,
+    ) -> Result<KycProfile, KycError> {
+        let mut tx = self.db_pool.begin().await?;
+        let current = profile_for_update(&mut tx, user_id)
+            .await?
+            .ok_or(KycError::NotFound)?;
+
+        let (status, level, reason) = if request.approve {
+            let level = request
+                .level
+                .or(current.requested_level)
+                .ok_or_else(|| KycError::InvalidRequest("Choose a level to grant".to_string()))?;
+            (KycStatus::Approved, level, None)
+        } else {
+            let reason = request.reason.clone().ok_or_else(|| {
+                KycError::InvalidRequest("A reason is required to reject".to_string())
+            })?;
+            (KycStatus::Rejected, current.level, Some(reason))
+        };
+
+        let profile: KycProfile = sqlx::query_as(&format!(
+            r#"
+            UPDATE kyc_profiles
+            SET status = $2, level = $3, rejection_reason = $4,
+                reviewed_by = $5, reviewed_at = NOW(), updated_at = NOW()
+            WHERE user_id = $1
+            RETURNING {PROFILE_COLUMNS}
+            "#
+        ))
+        .bind(user_id)
+        .bind(status)
+        .bind(level)
+        .bind(&reason)
+        .bind(admin_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        record_event(
+            &mut tx,
+            user_id,
+            "admin",
+            &profile,
+            Some(admin_id),
+            json!({ "previous_level": current.level, "reason": reason }),
+        )
+        .await?;
+        tx.commit().await?;
+
+        info!(
+            admin_id = %admin_id,
+            user_id = %user_id,
+            status = ?status,
+            level = %level,
+            "KYC case decided"
+        );
+        Ok(profile)
+    }
+
+    /// The user's case, opened on first use.
+    async fn profile(&self, user_id: Uuid) -> Result<KycProfile, KycError> {
+        sqlx::query("INSERT INTO kyc_profiles (user_id) VALUES ($1) ON CONFLICT DO NOTHING")
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(sqlx::query_as(&format!(
+            "SELECT {PROFILE_COLUMNS} FROM kyc_profiles WHERE user_id = $1"
+        ))
+        .bind(user_id)
+        .fetch_one(&self.db_pool)
+        .await?)
+    }
+
+    fn response(&self, profile: KycProfile, access_token: Option<String>) -> KycStatusResponse {
+        KycStatusResponse {
+            daily_withdrawal_limit: daily_limit(&self.limits, profile.level),
+            profile,
+            access_token,
+        }
+    }
+
+    fn encrypt(&self, details: &KycPersonalDetails) -> String {
+        let plaintext = serde_json::to_vec(details).expect("personal details serialize");
+        self.cipher.encrypt(&plaintext)
+    }
+
+    fn decrypt(&self, stored: &str) -> Result<KycPersonalDetails, KycError> {
+        let plaintext = self.cipher.decrypt(stored).map_err(|_| KycError::Decrypt)?;
+        serde_json::from_slice(&plaintext).map_err(|_| KycError::Decrypt)
+    }
+}
+
+async fn profile_for_update(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    user_id: Uuid,
+) -> Result<Option<KycProfile>, sqlx::Error> {
+    sqlx::query_as(&format!(
+        "SELECT {PROFILE_COLUMNS} FROM kyc_profiles WHERE user_id = $1 FOR UPDATE"
+    ))
+    .bind(user_id)
+    .fetch_optional(&mut **tx)
+    .await
+}
+
+async fn record_event(
+    tx: &mut sqlx::Transaction<'_, Postgres>,
+    user_id: Uuid,
+    source: &str,
+    profile: &KycProfile,
+    actor_id: Option<Uuid>,
+    details: serde_json::Value,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        "INSERT INTO kyc_events (user_id, source, status, level, actor_id, details)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(user_id)
+    .bind(source)
+    .bind(profile.status)
+    .bind(profile.level)
+    .bind(actor_id)
+    .bind(details)
+    .execute(&mut **tx)
+    .await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    #[test]
+    fn test_personal_details_round_trip_encrypted() {
+        let cipher = SecretCipher::from_hex(&"2a".repeat(32)).unwrap();
+        let details = KycPersonalDetails {
+            legal_name: "Ada Obi".to_string(),
+            date_of_birth: NaiveDate::from_ymd_opt(1995, 4, 12).unwrap(),
+            nationality: "NG".to_string(),
+            address: None,
+            id_type: "passport".to_string(),
+            id_number: "A01234567".to_string(),
+        };
+
+        let stored = cipher.encrypt(&serde_json::to_vec(&details).unwrap());
+        assert!(!stored.contains("A01234567"));
+        let decrypted: KycPersonalDetails =
+            serde_json::from_slice(&cipher.decrypt(&stored).unwrap()).unwrap();
+        assert_eq!(decrypted.id_number, "A01234567");
+        assert_eq!(decrypted.date_of_birth, details.date_of_birth);
+    }
+
+    #[test]
+    fn test_daily_limit_by_level() {
+        let limits = KycLimits::parse("none=0, full=5").unwrap();
+        assert_eq!(daily_limit(&limits, KycLevel::None), 0);
+        assert_eq!(
+            daily_limit(&limits, KycLevel::Basic),
+            KycLimits::default().basic
+        );
+        assert_eq!(daily_limit(&limits, KycLevel::Full), 5);
+        assert!(KycLimits::parse("gold=5").is_err());
+        assert!(KycLimits::parse("basic").is_err());
+    }
+}
@@ -0,0 +1,265 @@
+//! Sumsub client: web SDK access tokens and signed webhooks.
+//!
+//! Users go through Sumsub's web SDK with a short-lived access token issued
+//! for their user ID (Sumsub's `externalUserId`) and a verification level.
+//! Sumsub creates the applicant on first use and reports progress through
+//! webhooks whose body is signed with the webhook secret.
+
+use super::KycError;
+use crate::config::SumsubConfig;
+use crate::models::{KycLevel, KycStatus};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use reqwest::Client;
+use serde::Deserialize;
+use sha2::{Sha256, Sha512};
+use uuid::Uuid;
+
+const ACCESS_TOKEN_PATH: &str = "/resources/accessTokens/sdk";
+const ACCESS_TOKEN_TTL_SECS: u64 = 1800;
+
+#[derive(Debug, Deserialize)]
+struct AccessTokenResponse {
+    token: String,
+}
+
+/// A Sumsub webhook.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SumsubWebhook {
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub applicant_id: String,
+    #[serde(default)]
+    pub external_user_id: Option<String>,
+    #[serde(default)]
+    pub level_name: Option<String>,
+    #[serde(default)]
+    pub review_result: Option<ReviewResult>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReviewResult {
+    /// `GREEN` or `RED`.
+    #[serde(default)]
+    pub review_answer: Option<String>,
+    /// `FINAL`, or `RETRY` when the user may resubmit.
+    #[serde(default)]
+    pub review_reject_type: Option<String>,
+    #[serde(default)]
+    pub moderation_comment: Option<String>,
+    #[serde(default)]
+    pub reject_labels: Vec<String>,
+}
+
+impl SumsubWebhook {
+    /// Status the event moves the case to; `None` for events that don't.
+    pub fn status(&self) -> Option<KycStatus> {
+        match self.kind.as_str() {
+            "applicantCreated" => Some(KycStatus::Pending),
+            "applicantPending" | "applicantOnHold" => Some(KycStatus::InReview),
+            "applicantReviewed" => {
+                let review = self.review_result.as_ref()?;
+                match review.review_answer.as_deref()? {
+                    "GREEN" => Some(KycStatus::Approved),
+                    "RED" if review.review_reject_type.as_deref() == Some("RETRY") => {
+                        Some(KycStatus::Pending)
+                    }
+                    "RED" => Some(KycStatus::Rejected),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Why the applicant was turned down, for the user.
+    pub fn rejection_reason(&self) -> Option<String> {
+        let review = self.review_result.as_ref()?;
+        if review.review_answer.as_deref() != Some("RED") {
+            return None;
+        }
+        review
+            .moderation_comment
+            .clone()
+            .or_else(|| (!review.reject_labels.is_empty()).then(|| review.reject_labels.join(", ")))
+    }
+}
+
+pub struct SumsubClient {
+    client: Client,
+    config: SumsubConfig,
+}
+
+impl SumsubClient {
+    pub fn new(config: SumsubConfig) -> Self {
+        Self {
+            client: Client::new(),
+            config,
+        }
+    }
+
+    /// Sumsub level name of `level`; `None` for [`KycLevel::None`].
+    pub fn level_name(&self, level: KycLevel) -> Option<&str> {
+        match level {
+            KycLevel::None => None,
+            KycLevel::Basic => Some(&self.config.basic_level),
+            KycLevel::Full => Some(&self.config.full_level),
+        }
+    }
+
+    /// Level that the Sumsub level `name` verifies.
+    pub fn level_for(&self, name: &str) -> Option<KycLevel> {
+        if name == self.config.full_level {
+            Some(KycLevel::Full)
+        } else if name == self.config.basic_level {
+            Some(KycLevel::Basic)
+        } else {
+            None
+        }
+    }
+
+    /// Token for the web SDK to verify `user_id` at `level`.
+    pub async fn access_token(&self, user_id: Uuid, level: KycLevel) -> Result<String, KycError> {
+        let level_name = self
+            .level_name(level)
+            .ok_or_else(|| KycError::InvalidRequest("Choose a verification level".to_string()))?;
+        let body = serde_json::json!({
+            "userId": user_id.to_string(),
+            "levelName": level_name,
+            "ttlInSecs": ACCESS_TOKEN_TTL_SECS,
+        })
+        .to_string();
+        let ts = Utc::now().timestamp().to_string();
+        let signature = request_signature(
+            &self.config.secret_key,
+            &ts,
+            "POST",
+            ACCESS_TOKEN_PATH,
+            &body,
+        );
+
+        let response = self
+            .client
+            .post(format!("{}{}", self.config.base_url, ACCESS_TOKEN_PATH))
+            .header("content-type", "application/json")
+            .header("X-App-Token", &self.config.app_token)
+            .header("X-App-Access-Ts", ts)
+            .header("X-App-Access-Sig", signature)
+            .body(body)
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(KycError::Provider {
+                status: status.as_u16(),
+                body,
+            });
+        }
+        Ok(response.json::<AccessTokenResponse>().await?.token)
+    }
+
+    /// Check the `X-Payload-Digest` of a webhook body. `algorithm` is the
+    /// `X-Payload-Digest-Alg` header.
+    pub fn verify_webhook(&self, body: &[u8], digest: &str, algorithm: Option<&str>) -> bool {
+        let Ok(digest) = hex::decode(digest.trim()) else {
+            return false;
+        };
+        let key = self.config.webhook_secret.as_bytes();
+        match algorithm.unwrap_or("HMAC_SHA256_HEX") {
+            "HMAC_SHA256_HEX" => Hmac::<Sha256>::new_from_slice(key)
+                .map(|mac| mac.chain_update(body).verify_slice(&digest).is_ok())
+                .unwrap_or(false),
+            "HMAC_SHA512_HEX" => Hmac::<Sha512>::new_from_slice(key)
+                .map(|mac| mac.chain_update(body).verify_slice(&digest).is_ok())
+                .unwrap_or(false),
+            _ => false,
+        }
+    }
+}
+
+/// `X-App-Access-Sig` of a request.
+fn request_signature(secret: &str, ts: &str, method: &str, path: &str, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(ts.as_bytes());
+    mac.update(method.as_bytes());
+    mac.update(path.as_bytes());
+    mac.update(body.as_bytes());
+    hex::encode(mac.finalize().into_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client() -> SumsubClient {
+        SumsubClient::new(SumsubConfig {
+            app_token: "app".to_string(),
+            secret_key: "secret".to_string(),
+            webhook_secret: "hook".to_string(),
+            base_url: "https://api.sumsub.com".to_string(),
+            basic_level: "basic-kyc-level".to_string(),
+            full_level: "full-kyc-level".to_string(),
+        })
+    }
+
+    #[test]
+    fn test_verify_webhook() {
+        let body = br#"{"type":"applicantReviewed"}"#;
+        let digest = hex::encode(
+            Hmac::<Sha256>::new_from_slice(b"hook")
+                .unwrap()
+                .chain_update(body)
+                .finalize()
+                .into_bytes(),
+        );
+        let client = client();
+
+        assert!(client.verify_webhook(body, &digest, None));
+        assert!(client.verify_webhook(body, &digest, Some("HMAC_SHA256_HEX")));
+        assert!(!client.verify_webhook(body, &digest, Some("HMAC_SHA512_HEX")));
+        assert!(!client.verify_webhook(b"{}", &digest, None));
+        assert!(!client.verify_webhook(body, "not hex", None));
+    }
+
+    #[test]
+    fn test_webhook_status() {
+        let webhook = |json: &str| serde_json::from_str::<SumsubWebhook>(json).unwrap();
+
+        let approved = webhook(
+            r#"{"type":"applicantReviewed","applicantId":"a1","externalUserId":"u",
+                "levelName":"full-kyc-level","reviewResult":{"reviewAnswer":"GREEN"}}"#,
+        );
+        assert_eq!(approved.status(), Some(KycStatus::Approved));
+        assert_eq!(approved.rejection_reason(), None);
+        assert_eq!(client().level_for("full-kyc-level"), Some(KycLevel::Full));
+
+        let retry = webhook(
+            r#"{"type":"applicantReviewed","applicantId":"a1","reviewResult":
+                {"reviewAnswer":"RED","reviewRejectType":"RETRY","rejectLabels":["BAD_PROOF_OF_ADDRESS"]}}"#,
+        );
+        assert_eq!(retry.status(), Some(KycStatus::Pending));
+        assert_eq!(
+            retry.rejection_reason().as_deref(),
+            Some("BAD_PROOF_OF_ADDRESS")
+        );
+
+        let rejected = webhook(
+            r#"{"type":"applicantReviewed","applicantId":"a1","reviewResult":
+                {"reviewAnswer":"RED","reviewRejectType":"FINAL","moderationComment":"Forged document"}}"#,
+        );
+        assert_eq!(rejected.status(), Some(KycStatus::Rejected));
+        assert_eq!(
+            rejected.rejection_reason().as_deref(),
+            Some("Forged document")
+        );
+
+        let pending = webhook(r#"{"type":"applicantPending","applicantId":"a1"}"#);
+        assert_eq!(pending.status(), Some(KycStatus::InReview));
+        let other = webhook(r#"{"type":"applicantPersonalInfoChanged","applicantId":"a1"}"#);
+        assert_eq!(other.status(), None);
+    }
+}
@@ -1,19 +1,84 @@
+//! Leaderboards.
+//!
+//! Elo standings per game live in the `leaderboards` table. The `skill` and
+//! `earnings` boards are Redis sorted sets fed by the chain indexer:
+//! reputation index changes credit skill, prize payouts credit each winner's
+//! weighted share of the pool to `earnings` and to the board of the match's
+//! game. Every board exists all-time and per [`Season`].
+//!
+//! Credits are stored in `leaderboard_credits` before Redis is touched and
+//! scores are always written as totals summed from there, so a failed Redis
+//! write heals on the next credit. A board missing from Redis is rebuilt from
+//! Postgres on first read.
+//!
+//! An hour after a season ends, the rollover job freezes each board's final
+//! standings into `leaderboard_season_standings`, assigns [`REWARD_TIERS`]
+//! and stores a [`WinnersManifest`] for paying the season's prize pool
+//! through the prize distribution contract.
+
 use crate::api_error::ApiError;
 use crate::models::{
-    LeaderboardEntry, LeaderboardResponse, PlayerRankResponse, RankHistory, RankHistoryEntry,
-    SeasonalLeaderboard, LeaderboardStats,
+    ArchivedSeason, Board, BoardEntry, LeaderboardEntry, LeaderboardMetric, LeaderboardResponse,
+    LeaderboardStats, ManifestEntry, PlayerRankResponse, RankAroundResponse, RankHistory,
+    RankHistoryEntry, Season, SeasonStanding, SeasonalLeaderboard, WinnersManifest,
 };
+use crate::service::chain_indexer::decode::NormalizedEvent;
+use crate::service::chain_indexer::platform_id;
+use crate::service::chain_indexer::store::IndexedEvent;
 use chrono::{DateTime, Utc, Duration};
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
 use sqlx::PgPool;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// Members written per `ZADD` when rebuilding a board.
+const REBUILD_CHUNK: usize = 1000;
+
+/// How long after a season ends it is archived, so events indexed late still
+/// count.
+const ARCHIVE_GRACE_SECS: i64 = 3600;
+const ROLLOVER_INTERVAL_SECS: u64 = 600;
+
+/// Ranks rewarded at the end of a season.
+pub struct RewardTier {
+    pub name: &'static str,
+    /// Lowest rank in the tier; the tier starts below the previous one.
+    pub max_rank: i32,
+    /// Share of the season pool in basis points, split evenly in the tier.
+    pub share_bps: u32,
+}
+
+/// Only players with a positive score are rewarded. Shares of tiers nobody
+/// can be paid in go to the other tiers.
+pub const REWARD_TIERS: &[RewardTier] = &[
+    RewardTier {
+        name: "champion",
+        max_rank: 1,
+        share_bps: 3000,
+    },
+    RewardTier {
+        name: "top_10",
+        max_rank: 10,
+        share_bps: 3000,
+    },
+    RewardTier {
+        name: "top_100",
+        max_rank: 100,
+        share_bps: 4000,
+    },
+];
+
 pub struct LeaderboardService {
     db_pool: PgPool,
+    redis: ConnectionManager,
 }
 
 impl LeaderboardService {
-    pub fn new(db_pool: PgPool) -> Self {
-        Self { db_pool }
+    pub fn new(db_pool: PgPool, redis: ConnectionManager) -> Self {
+        Self { db_pool, redis }
     }
 
     /// Get leaderboard rankings for a category
@@ -386,3 +451,730 @@ impl LeaderboardService {
         })
     }
 }
+
+/// Points one indexed event earns the holder of one address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Credit {
+    event_id: String,
+    address: String,
+    metric: LeaderboardMetric,
+    game: Option<String>,
+    amount: i128,
+    occurred_at: DateTime<Utc>,
+}
+
+impl LeaderboardService {
+    // ========================================================================
+    // REDIS BOARDS
+    // ========================================================================
+
+    /// A page of `board`, all-time or for `season`. Returns the entries and
+    /// the number of players on the board.
+    pub async fn board(
+        &self,
+        board: &Board,
+        season: Option<&Season>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<BoardEntry>, i64), ApiError> {
+        let key = self.ensure_board(board, season).await?;
+        let mut conn = self.redis.clone();
+        let members: Vec<(String, f64)> = conn
+            .zrevrange_withscores(&key, offset as isize, (offset + limit - 1) as isize)
+            .await
+            .map_err(redis_error)?;
+        let total: i64 = conn.zcard(&key).await.map_err(redis_error)?;
+
+        let entries = self.entries(offset, members).await?;
+        Ok((entries, total))
+    }
+
+    /// The players up to `radius` places above and below `user_id`.
+    pub async fn rank_around(
+        &self,
+        board: &Board,
+        season: Option<&Season>,
+        user_id: Uuid,
+        radius: i64,
+    ) -> Result<RankAroundResponse, ApiError> {
+        let key = self.ensure_board(board, season).await?;
+        let mut conn = self.redis.clone();
+        let total: i64 = conn.zcard(&key).await.map_err(redis_error)?;
+        let position: Option<i64> = conn
+            .zrevrank(&key, user_id.to_string())
+            .await
+            .map_err(redis_error)?;
+
+        let mut response = RankAroundResponse {
+            board: board.to_string(),
+            season: season.map(|s| s.id.clone()),
+            total,
+            player: None,
+            entries: Vec::new(),
+        };
+        let Some(position) = position else {
+            return Ok(response);
+        };
+        let start = (position - radius).max(0);
+        let members: Vec<(String, f64)> = conn
+            .zrevrange_withscores(&key, start as isize, (position + radius) as isize)
+            .await
+            .map_err(redis_error)?;
+
+        response.entries = self.entries(start, members).await?;
+        response.player = response
+            .entries
+            .iter()
+            .find(|entry| entry.user_id == user_id)
+            .cloned();
+        Ok(response)
+    }
+
+    /// Credit the reputation changes and prize payouts among `events`.
+    /// Events credited before are skipped.
+    pub async fn record_chain_events(&self, events: &[IndexedEvent]) -> Result<(), ApiError> {
+        let mut credits = Vec::new();
+        for event in events {
+            match &event.normalized {
+                Some(NormalizedEvent::Reputation(row)) if row.skill_delta != 0 => {
+                    credits.push(Credit {
+                        event_id: event.event_id.clone(),
+                        address: row.player.clone(),
+                        metric: LeaderboardMetric::Skill,
+                        game: None,
+                        amount: row.skill_delta,
+                        occurred_at: event.ledger_closed_at,
+                    });
+                }
+                Some(NormalizedEvent::Prize(row)) if row.action == "executed" => {
+                    let (Some(winners), Some(weights)) = (&row.winners, &row.weights) else {
+                        continue;
+                    };
+                    let Some((pool_amount, game)) = self.prize_pool(row.pool_id).await? else {
+                        warn!(pool_id = row.pool_id, "Payout for an unindexed prize pool");
+                        continue;
+                    };
+                    for (winner, share) in winners.iter().zip(payout_shares(pool_amount, weights)) {
+                        credits.push(Credit {
+                            event_id: event.event_id.clone(),
+                            address: winner.clone(),
+                            metric: LeaderboardMetric::Earnings,
+                            game: game.clone(),
+                            amount: share,
+                            occurred_at: event.ledger_closed_at,
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+        if credits.is_empty() {
+            return Ok(());
+        }
+
+        let addresses: Vec<String> = credits.iter().map(|c| c.address.clone()).collect();
+        let users: HashMap<String, Uuid> = sqlx::query_as::<_, (String, Uuid)>(
+            "SELECT stellar_public_key, user_id FROM wallets WHERE stellar_public_key = ANY($1)",
+        )
+        .bind(&addresses)
+        .fetch_all(&self.db_pool)
+        .await?
+        .into_iter()
+        .collect();
+
+        // (board, season) -> players whose score changed
+        let mut changed: HashMap<(Board, Option<Season>), HashSet<Uuid>> = HashMap::new();
+        for credit in &credits {
+            let Some(&user_id) = users.get(&credit.address) else {
+                continue;
+            };
+            let inserted = sqlx::query(
+                "INSERT INTO leaderboard_credits \
+                     (event_id, user_id, metric, game, amount, occurred_at) \
+                 VALUES ($1, $2, $3, $4, $5::numeric, $6) \
+                 ON CONFLICT DO NOTHING",
+            )
+            .bind(&credit.event_id)
+            .bind(user_id)
+            .bind(credit.metric.as_str())
+            .bind(&credit.game)
+            .bind(credit.amount.to_string())
+            .bind(credit.occurred_at)
+            .execute(&self.db_pool)
+            .await?
+            .rows_affected();
+            if inserted == 0 {
+                continue;
+            }
+
+            let season = Season::containing(credit.occurred_at);
+            let mut boards = vec![Board::global(credit.metric)];
+            if let Some(game) = &credit.game {
+                boards.push(Board {
+                    metric: credit.metric,
+                    game: Some(game.clone()),
+                });
+            }
+            for board in boards {
+                for scope in [None, Some(season.clone())] {
+                    changed
+                        .entry((board.clone(), scope))
+                        .or_default()
+                        .insert(user_id);
+                }
+            }
+        }
+
+        for ((board, season), user_ids) in changed {
+            let user_ids: Vec<Uuid> = user_ids.into_iter().collect();
+            self.refresh_scores(&board, season.as_ref(), &user_ids)
+                .await?;
+        }
+        Ok(())
+    }
+
+    /// Rewrite the Redis scores of `user_ids` from their credits. Boards not
+    /// in Redis are left for [`Self::ensure_board`] to rebuild.
+    async fn refresh_scores(
+        &self,
+        board: &Board,
+        season: Option<&Season>,
+        user_ids: &[Uuid],
+    ) -> Result<(), ApiError> {
+        let key = board_key(board, season);
+        let mut conn = self.redis.clone();
+        let exists: bool = conn.exists(&key).await.map_err(redis_error)?;
+        if !exists {
+            return Ok(());
+        }
+        let scores = self.scores(board, season, Some(user_ids)).await?;
+        if scores.is_empty() {
+            return Ok(());
+        }
+        let members: Vec<(f64, String)> = scores
+            .into_iter()
+            .map(|(user_id, score)| (score, user_id.to_string()))
+            .collect();
+        conn.zadd_multiple::<_, _, _, ()>(&key, &members)
+            .await
+            .map_err(redis_error)
+    }
+
+    /// Redis key of `board`, rebuilt from Postgres first if it is missing.
+    /// The rebuilt set is swapped in with `RENAME` so readers never see it
+    /// half written.
+    async fn ensure_board(
+        &self,
+        board: &Board,
+        season: Option<&Season>,
+    ) -> Result<String, ApiError> {
+        let key = board_key(board, season);
+        let mut conn = self.redis.clone();
+        let exists: bool = conn.exists(&key).await.map_err(redis_error)?;
+        if exists {
+            return Ok(key);
+        }
+
+        let scores = self.scores(board, season, None).await?;
+        if scores.is_empty() {
+            return Ok(key);
+        }
+        let staging = format!("{}:rebuild:{}", key, Uuid::new_v4());
+        let mut pipe = redis::pipe();
+        pipe.atomic();
+        for chunk in scores.chunks(REBUILD_CHUNK) {
+            let members: Vec<(f64, String)> = chunk
+                .iter()
+                .map(|(user_id, score)| (*score, user_id.to_string()))
+                .collect();
+            pipe.zadd_multiple(&staging, &members).ignore();
+        }
+        pipe.rename(&staging, &key).ignore();
+        pipe.query_async::<()>(&mut conn)
+            .await
+            .map_err(redis_error)?;
+        info!(key = %key, players = scores.len(), "Rebuilt leaderboard from Postgres");
+        Ok(key)
+    }
+
+    /// Total credits per player on `board`, optionally only for `user_ids`.
+    async fn scores(
+        &self,
+        board: &Board,
+        season: Option<&Season>,
+        user_ids: Option<&[Uuid]>,
+    ) -> Result<Vec<(Uuid, f64)>, ApiError> {
+        let scores = sqlx::query_as::<_, (Uuid, f64)>(
+            "SELECT user_id, SUM(amount)::float8 FROM leaderboard_credits \
+             WHERE metric = $1 \
+               AND ($2::text IS NULL OR game = $2) \
+               AND ($3::timestamptz IS NULL OR occurred_at >= $3) \
+               AND ($4::timestamptz IS NULL OR occurred_at < $4) \
+               AND ($5::uuid[] IS NULL OR user_id = ANY($5)) \
+             GROUP BY user_id",
+        )
+        .bind(board.metric.as_str())
+        .bind(&board.game)
+        .bind(season.map(|s| s.starts_at))
+        .bind(season.map(|s| s.ends_at))
+        .bind(user_ids)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(scores)
+    }
+
+    /// Amount locked in prize pool `pool_id` and the game of its match.
+    async fn prize_pool(&self, pool_id: u64) -> Result<Option<(i128, Option<String>)>, ApiError> {
+        let pool = sqlx::query_as::<_, (Option<String>, Option<String>)>(
+            "SELECT \
+                 (SELECT amount::text FROM chain_prize_events \
+                  WHERE pool_id = $1 AND action IN ('created', 'locked') AND amount IS NOT NULL \
+                  ORDER BY ledger DESC LIMIT 1), \
+                 (SELECT match_id FROM chain_prize_events \
+                  WHERE pool_id = $1 AND action = 'created' LIMIT 1)",
+        )
+        .bind(pool_id as i64)
+        .fetch_one(&self.db_pool)
+        .await?;
+        let (Some(amount), match_id) = pool else {
+            return Ok(None);
+        };
+        let amount = amount
+            .parse::<i128>()
+            .map_err(|e| ApiError::internal_error(format!("Invalid pool amount: {}", e)))?;
+
+        let game = match match_id.as_deref().and_then(platform_id) {
+            Some(match_id) => {
+                sqlx::query_scalar::<_, String>(
+                    "SELECT LOWER(COALESCE(t.game, m.game_mode)) FROM matches m \
+                 LEFT JOIN tournaments t ON t.id = m.tournament_id WHERE m.id = $1",
+                )
+                .bind(match_id)
+                .fetch_optional(&self.db_pool)
+                .await?
+            }
+            None => None,
+        };
+        Ok(Some((amount, game)))
+    }
+
+    /// Attach usernames to ranked members, the first ranked at `offset`.
+    /// Members whose user no longer exists are dropped.
+    async fn entries(
+        &self,
+        offset: i64,
+        members: Vec<(String, f64)>,
+    ) -> Result<Vec<BoardEntry>, ApiError> {
+        let ids: Vec<Uuid> = members
+            .iter()
+            .filter_map(|(member, _)| member.parse().ok())
+            .collect();
+        let users: HashMap<Uuid, (String, Option<String>)> =
+            sqlx::query_as::<_, (Uuid, String, Option<String>)>(
+                "SELECT id, username, avatar_url FROM users WHERE id = ANY($1)",
+            )
+            .bind(&ids)
+            .fetch_all(&self.db_pool)
+            .await?
+            .into_iter()
+            .map(|(id, username, avatar_url)| (id, (username, avatar_url)))
+            .collect();
+
+        Ok(members
+            .into_iter()
+            .zip(offset + 1..)
+            .filter_map(|((member, score), rank)| {
+                let user_id: Uuid = member.parse().ok()?;
+                let (username, avatar_url) = users.get(&user_id)?.clone();
+                Some(BoardEntry {
+                    rank,
+                    user_id,
+                    username,
+                    avatar_url,
+                    score: score as i64,
+                })
+            })
+            .collect())
+    }
+}
+
+impl LeaderboardService {
+    // ========================================================================
+    // SEASON ROLLOVER
+    // ========================================================================
+
+    /// Spawn the job archiving each season once it has ended. Several
+    /// instances can run side by side.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!("Leaderboard season rollover started");
+            let mut ticker =
+                tokio::time::interval(std::time::Duration::from_secs(ROLLOVER_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+                let settled = Utc::now() - Duration::seconds(ARCHIVE_GRACE_SECS);
+                let season = Season::containing(settled).previous();
+                match self.archive_season(&season).await {
+                    Ok(archived) if !archived.is_empty() => {
+                        info!(season = %season.id, boards = archived.len(), "Archived leaderboard season");
+                    }
+                    Ok(_) => {}
+                    Err(e) => error!(season = %season.id, error = %e, "Season rollover failed"),
+                }
+            }
+        });
+    }
+
+    /// Freeze the final standings of every board in `season`. Boards already
+    /// archived are skipped; returns the ones archived now.
+    pub async fn archive_season(&self, season: &Season) -> Result<Vec<ArchivedSeason>, ApiError> {
+        if season.ends_at > Utc::now() {
+            return Err(ApiError::bad_request(format!(
+                "Season {} has not ended",
+                season.id
+            )));
+        }
+        let games = sqlx::query_scalar::<_, String>(
+            "SELECT DISTINCT game FROM leaderboard_credits \
+             WHERE metric = 'earnings' AND game IS NOT NULL \
+               AND occurred_at >= $1 AND occurred_at < $2",
+        )
+        .bind(season.starts_at)
+        .bind(season.ends_at)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut boards = vec![
+            Board::global(LeaderboardMetric::Skill),
+            Board::global(LeaderboardMetric::Earnings),
+        ];
+        boards.extend(games.into_iter().map(|game| Board {
+            metric: LeaderboardMetric::Earnings,
+            game: Some(game),
+        }));
+
+        let mut archived = Vec::new();
+        for board in boards {
+            if let Some(season) = self.archive_board(&board, season).await? {
+                archived.push(season);
+            }
+        }
+        Ok(archived)
+    }
+
+    async fn archive_board(
+        &self,
+        board: &Board,
+        season: &Season,
+    ) -> Result<Option<ArchivedSeason>, ApiError> {
+        let board_id = board.to_string();
+        let mut tx = self.db_pool.begin().await?;
+        // The season row is the claim: whoever inserts it archives the board.
+        let archived_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            "INSERT INTO leaderboard_seasons (board, season_id, starts_at, ends_at) \
+             VALUES ($1, $2, $3, $4) \
+             ON CONFLICT DO NOTHING \
+             RETURNING archived_at",
+        )
+        .bind(&board_id)
+        .bind(&season.id)
+        .bind(season.starts_at)
+        .bind(season.ends_at)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(archived_at) = archived_at else {
+            return Ok(None);
+        };
+
+        // Ties are ordered like the Redis board orders them.
+        let players = sqlx::query(
+            "INSERT INTO leaderboard_season_standings (board, season_id, rank, user_id, score) \
+             SELECT $1, $2, \
+                    ROW_NUMBER() OVER (ORDER BY SUM(amount) DESC, user_id::text DESC), \
+                    user_id, SUM(amount) \
+             FROM leaderboard_credits \
+             WHERE metric = $3 AND ($4::text IS NULL OR game = $4) \
+               AND occurred_at >= $5 AND occurred_at < $6 \
+             GROUP BY user_id",
+        )
+        .bind(&board_id)
+        .bind(&season.id)
+        .bind(board.metric.as_str())
+        .bind(&board.game)
+        .bind(season.starts_at)
+        .bind(season.ends_at)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+
+        let mut above = 0;
+        for tier in REWARD_TIERS {
+            sqlx::query(
+                "UPDATE leaderboard_season_standings SET reward_tier = $3 \
+                 WHERE board = $1 AND season_id = $2 \
+                   AND rank > $4 AND rank <= $5 AND score > 0",
+            )
+            .bind(&board_id)
+            .bind(&season.id)
+            .bind(tier.name)
+            .bind(above)
+            .bind(tier.max_rank)
+            .execute(&mut *tx)
+            .await?;
+            above = tier.max_rank;
+        }
+
+        let rewarded = sqlx::query_as::<_, (i32, Uuid, String, Option<String>)>(
+            "SELECT s.rank, s.user_id, s.reward_tier, w.stellar_public_key \
+             FROM leaderboard_season_standings s \
+             LEFT JOIN wallets w ON w.user_id = s.user_id \
+             WHERE s.board = $1 AND s.season_id = $2 AND s.reward_tier IS NOT NULL \
+             ORDER BY s.rank",
+        )
+        .bind(&board_id)
+        .bind(&season.id)
+        .fetch_all(&mut *tx)
+        .await?;
+        let manifest = winners_manifest(&board_id, &season.id, rewarded);
+
+        sqlx::query(
+            "UPDATE leaderboard_seasons SET players = $3, manifest = $4 \
+             WHERE board = $1 AND season_id = $2",
+        )
+        .bind(&board_id)
+        .bind(&season.id)
+        .bind(players as i32)
+        .bind(sqlx::types::Json(&manifest))
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        // Live reads of a past season rebuild the board from Postgres.
+        let mut conn = self.redis.clone();
+        if let Err(e) = conn.del::<_, ()>(board_key(board, Some(season))).await {
+            warn!(board = %board_id, season = %season.id, error = %e, "Failed to drop archived board");
+        }
+
+        Ok(Some(ArchivedSeason {
+            board: board_id,
+            season: season.clone(),
+            players: players as i32,
+            archived_at,
+        }))
+    }
+
+    /// A page of an archived season's final standings.
+    pub async fn season_standings(
+        &self,
+        board: &Board,
+        season_id: &str,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(ArchivedSeason, Vec<SeasonStanding>), ApiError> {
+        let board_id = board.to_string();
+        let (starts_at, ends_at, players, archived_at) =
+            sqlx::query_as::<_, (DateTime<Utc>, DateTime<Utc>, i32, DateTime<Utc>)>(
+                "SELECT starts_at, ends_at, players, archived_at FROM leaderboard_seasons \
+                 WHERE board = $1 AND season_id = $2",
+            )
+            .bind(&board_id)
+            .bind(season_id)
+            .fetch_optional(&self.db_pool)
+            .await?
+            .ok_or(ApiError::NotFound)?;
+
+        let standings = sqlx::query_as::<_, SeasonStanding>(
+            "SELECT s.rank, s.user_id, u.username, u.avatar_url, \
+                    s.score::bigint AS score, s.reward_tier \
+             FROM leaderboard_season_standings s \
+             JOIN users u ON u.id = s.user_id \
+             WHERE s.board = $1 AND s.season_id = $2 \
+             ORDER BY s.rank \
+             LIMIT $3 OFFSET $4",
+        )
+        .bind(&board_id)
+        .bind(season_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let season = ArchivedSeason {
+            board: board_id,
+            season: Season {
+                id: season_id.to_string(),
+                starts_at,
+                ends_at,
+            },
+            players,
+            archived_at,
+        };
+        Ok((season, standings))
+    }
+
+    /// The winners manifest of an archived season.
+    pub async fn winners_manifest(
+        &self,
+        board: &Board,
+        season_id: &str,
+    ) -> Result<WinnersManifest, ApiError> {
+        let manifest = sqlx::query_scalar::<_, Option<sqlx::types::Json<WinnersManifest>>>(
+            "SELECT manifest FROM leaderboard_seasons WHERE board = $1 AND season_id = $2",
+        )
+        .bind(board.to_string())
+        .bind(season_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .flatten()
+        .ok_or(ApiError::NotFound)?;
+        Ok(manifest.0)
+    }
+}
+
+fn board_key(board: &Board, season: Option<&Season>) -> String {
+    format!(
+        "leaderboard:{}:{}",
+        board,
+        season.map_or("all", |s| s.id.as_str())
+    )
+}
+
+fn redis_error(e: redis::RedisError) -> ApiError {
+    ApiError::RedisError(e.to_string())
+}
+
+/// Split `amount` by basis-point `weights` the way the prize contract does:
+/// shares are rounded down and the last winner gets the remainder.
+fn payout_shares(amount: i128, weights: &[u32]) -> Vec<i128> {
+    let mut shares: Vec<i128> = weights
+        .iter()
+        .map(|&w| amount.saturating_mul(w as i128) / 10_000)
+        .collect();
+    if let Some(last) = shares.len().checked_sub(1) {
+        shares[last] = amount - shares[..last].iter().sum::<i128>();
+    }
+    shares
+}
+
+/// Scale `raw` weights to basis points summing to 10000. Rounding leftovers
+/// go to the first entries.
+fn normalize_weights(raw: &[u64]) -> Vec<u32> {
+    let total: u128 = raw.iter().map(|&w| w as u128).sum();
+    if total == 0 {
+        return vec![0; raw.len()];
+    }
+    let mut weights: Vec<u32> = raw
+        .iter()
+        .map(|&w| (w as u128 * 10_000 / total) as u32)
+        .collect();
+    let leftover = 10_000 - weights.iter().sum::<u32>();
+    for weight in weights.iter_mut().take(leftover as usize) {
+        *weight += 1;
+    }
+    weights
+}
+
+/// Manifest paying the rewarded players of a season. `rewarded` holds each
+/// player's rank, id, tier and wallet address, best rank first.
+fn winners_manifest(
+    board: &str,
+    season_id: &str,
+    rewarded: Vec<(i32, Uuid, String, Option<String>)>,
+) -> WinnersManifest {
+    let mut unpaid = Vec::new();
+    let mut paid = Vec::new();
+    for (rank, user_id, tier, address) in rewarded {
+        match address {
+            Some(address) => paid.push((rank, user_id, tier, address)),
+            None => unpaid.push(user_id),
+        }
+    }
+
+    let mut tier_sizes: HashMap<&str, u64> = HashMap::new();
+    for (_, _, tier, _) in &paid {
+        *tier_sizes.entry(tier.as_str()).or_default() += 1;
+    }
+    let raw: Vec<u64> = paid
+        .iter()
+        .map(|(_, _, tier, _)| {
+            let share = REWARD_TIERS
+                .iter()
+                .find(|t| t.name == tier)
+                .map_or(0, |t| t.share_bps as u64);
+            share * 1_000_000 / tier_sizes[tier.as_str()]
+        })
+        .collect();
+    let weights = normalize_weights(&raw);
+
+    let entries: Vec<ManifestEntry> = paid
+        .into_iter()
+        .zip(&weights)
+        .map(
+            |((rank, user_id, reward_tier, address), &weight)| ManifestEntry {
+                rank,
+                user_id,
+                address,
+                reward_tier,
+                weight,
+            },
+        )
+        .collect();
+    WinnersManifest {
+        board: board.to_string(),
+        season_id: season_id.to_string(),
+        generated_at: Utc::now(),
+        winners: entries.iter().map(|e| e.address.clone()).collect(),
+        weights,
+        entries,
+        unpaid,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payout_shares_follow_weights() {
+        assert_eq!(
+            payout_shares(1000, &[5000, 3000, 2000]),
+            vec![500, 300, 200]
+        );
+        assert_eq!(payout_shares(100, &[3333, 3333, 3334]), vec![33, 33, 34]);
+        assert_eq!(payout_shares(100, &[]), Vec::<i128>::new());
+    }
+
+    #[test]
+    fn test_winners_manifest_weights() {
+        let user = |n: u128| Uuid::from_u128(n);
+        let address = |n: u128| Some(format!("G{}", n));
+        let rewarded = vec![
+            (1, user(1), "champion".to_string(), address(1)),
+            (2, user(2), "top_10".to_string(), address(2)),
+            (3, user(3), "top_10".to_string(), None),
+            (4, user(4), "top_10".to_string(), address(4)),
+        ];
+
+        let manifest = winners_manifest("earnings", "2026-q3", rewarded);
+        assert_eq!(manifest.winners, vec!["G1", "G2", "G4"]);
+        assert_eq!(manifest.unpaid, vec![user(3)]);
+        // Nobody is in top_100, so its share is spread over the other tiers.
+        assert_eq!(manifest.weights, vec![5000, 2500, 2500]);
+
+        assert_eq!(normalize_weights(&[1, 1, 1]), vec![3334, 3333, 3333]);
+        assert!(winners_manifest("skill", "2026-q3", Vec::new())
+            .winners
+            .is_empty());
+    }
+
+    #[test]
+    fn test_board_keys() {
+        let board: Board = "earnings:fifa".parse().unwrap();
+        assert_eq!(board_key(&board, None), "leaderboard:earnings:fifa:all");
+        let season = Season::parse("2026-q4").unwrap();
+        assert_eq!(
+            board_key(&Board::global(LeaderboardMetric::Skill), Some(&season)),
+            "leaderboard:skill:2026-q4"
+        );
+    }
+}
@@ -0,0 +1,284 @@
+//! Double-entry ledger for custodial balances.
+//!
+//! Every movement of funds is a journal of signed entries that sum to zero.
+//! User and team accounts hold what the platform owes them and can never go
+//! negative; the custody account is their counterpart for funds held on the
+//! platform's Stellar account, so its balance is minus the custodied amount.
+//!
+//! Journals carry a unique reference, which makes posting idempotent: a second
+//! post with the same reference changes nothing.
+
+use serde_json::Value;
+use sqlx::{Postgres, Transaction};
+use uuid::Uuid;
+
+/// Native XLM, in stroops.
+pub const ASSET_XLM: &str = "XLM";
+
+/// A ledger account, identified by its owner and role.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerAccount {
+    /// Spendable balance of a user.
+    Available(Uuid),
+    /// Funds held for a user's withdrawal until it settles or is released.
+    PendingWithdrawal(Uuid),
+    /// Funds held on the platform's Stellar account.
+    Custody,
+    /// Deposits that could not be attributed to a user.
+    Unallocated,
+    /// Payments users made to the platform.
+    Revenue,
+    /// Wallet of a team, paid out to its members.
+    Team(Uuid),
+}
+
+impl LedgerAccount {
+    pub fn key(&self, asset: &str) -> String {
+        match self {
+            LedgerAccount::Available(user_id) => format!("user:{}:available:{}", user_id, asset),
+            LedgerAccount::PendingWithdrawal(user_id) => {
+                format!("user:{}:pending_withdrawal:{}", user_id, asset)
+            }
+            LedgerAccount::Custody => format!("platform:custody:{}", asset),
+            LedgerAccount::Unallocated => format!("platform:unallocated:{}", asset),
+            LedgerAccount::Revenue => format!("platform:revenue:{}", asset),
+            LedgerAccount::Team(team_id) => format!("team:{}:available:{}", team_id, asset),
+        }
+    }
+
+    pub fn user_id(&self) -> Option<Uuid> {
+        match self {
+            LedgerAccount::Available(user_id) | LedgerAccount::PendingWithdrawal(user_id) => {
+                Some(*user_id)
+            }
+            LedgerAccount::Custody
+            | LedgerAccount::Unallocated
+            | LedgerAccount::Revenue
+            | LedgerAccount::Team(_) => None,
+        }
+    }
+
+    fn allows_negative(&self) -> bool {
+        matches!(
+            self,
+            LedgerAccount::Custody | LedgerAccount::Unallocated | LedgerAccount::Revenue
+        )
+    }
+}
+
+/// A set of entries posted together under one reference.
+#[derive(Debug, Clone)]
+pub struct Journal {
+    pub reference: String,
+    pub kind: &'static str,
+    pub user_id: Option<Uuid>,
+    pub asset: String,
+    pub entries: Vec<(LedgerAccount, i64)>,
+    pub metadata: Value,
+}
+
+impl Journal {
+    /// Move `amount` from one account to another.
+    pub fn transfer(
+        reference: String,
+        kind: &'static str,
+        user_id: Option<Uuid>,
+        from: LedgerAccount,
+        to: LedgerAccount,
+        amount: i64,
+    ) -> Self {
+        Self {
+            reference,
+            kind,
+            user_id,
+            asset: ASSET_XLM.to_string(),
+            entries: vec![(from, -amount), (to, amount)],
+            metadata: Value::Object(Default::default()),
+        }
+    }
+
+    pub fn with_metadata(mut self, metadata: Value) -> Self {
+        self.metadata = metadata;
+        self
+    }
+
+    /// Post in `asset` instead of XLM, e.g. an anchored asset from a ramp.
+    pub fn with_asset(mut self, asset: &str) -> Self {
+        self.asset = asset.to_string();
+        self
+    }
+
+    fn is_balanced(&self) -> bool {
+        !self.entries.is_empty()
+            && self.entries.iter().all(|(_, amount)| *amount != 0)
+            && self
+                .entries
+                .iter()
+                .try_fold(0i64, |sum, (_, amount)| sum.checked_add(*amount))
+                == Some(0)
+    }
+}
+
+/// Post `journal` inside `tx`. Returns `None` when the reference was already
+/// posted. Balances that would go negative fail with a check violation.
+pub async fn post(
+    tx: &mut Transaction<'_, Postgres>,
+    journal: &Journal,
+) -> Result<Option<Uuid>, sqlx::Error> {
+    if !journal.is_balanced() {
+        return Err(sqlx::Error::Protocol(format!(
+            "ledger journal {} is unbalanced",
+            journal.reference
+        )));
+    }
+
+    let journal_id: Option<Uuid> = sqlx::query_scalar(
+        r#"
+        INSERT INTO ledger_journals (id, reference, kind, user_id, metadata)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (reference) DO NOTHING
+        RETURNING id
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(&journal.reference)
+    .bind(journal.kind)
+    .bind(journal.user_id)
+    .bind(&journal.metadata)
+    .fetch_optional(&mut **tx)
+    .await?;
+    let Some(journal_id) = journal_id else {
+        return Ok(None);
+    };
+
+    // Touch accounts in key order so concurrent journals can't deadlock.
+    let mut entries: Vec<(String, LedgerAccount, i64)> = journal
+        .entries
+        .iter()
+        .map(|(account, amount)| (account.key(&journal.asset), *account, *amount))
+        .collect();
+    entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (key, account, amount) in entries {
+        let account_id = ensure_account(tx, &key, account, &journal.asset).await?;
+        sqlx::query(
+            "UPDATE ledger_accounts SET balance = balance + $1, updated_at = NOW() WHERE id = $2",
+        )
+        .bind(amount)
+        .bind(account_id)
+        .execute(&mut **tx)
+        .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO ledger_entries (id, journal_id, account_id, amount)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(journal_id)
+        .bind(account_id)
+        .bind(amount)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(Some(journal_id))
+}
+
+/// Balance of `account`, locking its row until `tx` ends.
+pub async fn balance_for_update(
+    tx: &mut Transaction<'_, Postgres>,
+    account: LedgerAccount,
+    asset: &str,
+) -> Result<i64, sqlx::Error> {
+    let key = account.key(asset);
+    ensure_account(tx, &key, account, asset).await?;
+    sqlx::query_scalar("SELECT balance FROM ledger_accounts WHERE account_key = $1 FOR UPDATE")
+        .bind(&key)
+        .fetch_one(&mut **tx)
+        .await
+}
+
+async fn ensure_account(
+    tx: &mut Transaction<'_, Postgres>,
+    key: &str,
+    account: LedgerAccount,
+    asset: &str,
+) -> Result<Uuid, sqlx::Error> {
+    sqlx::query(
+        r#"
+        INSERT INTO ledger_accounts (id, account_key, user_id, asset, allow_negative)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (account_key) DO NOTHING
+        "#,
+    )
+    .bind(Uuid::new_v4())
+    .bind(key)
+    .bind(account.user_id())
+    .bind(asset)
+    .bind(account.allows_negative())
+    .execute(&mut **tx)
+    .await?;
+    sqlx::query_scalar("SELECT id FROM ledger_accounts WHERE account_key = $1")
+        .bind(key)
+        .fetch_one(&mut **tx)
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transfer_is_balanced() {
+        let user = Uuid::new_v4();
+        let journal = Journal::transfer(
+            "deposit:1".to_string(),
+            "deposit",
+            Some(user),
+            LedgerAccount::Custody,
+            LedgerAccount::Available(user),
+            50,
+        );
+        assert!(journal.is_balanced());
+        assert_eq!(journal.entries[0], (LedgerAccount::Custody, -50));
+    }
+
+    #[test]
+    fn test_unbalanced_and_zero_entries_are_rejected() {
+        let user = Uuid::new_v4();
+        let mut journal = Journal::transfer(
+            "deposit:2".to_string(),
+            "deposit",
+            Some(user),
+            LedgerAccount::Custody,
+            LedgerAccount::Available(user),
+            50,
+        );
+        journal.entries[1].1 = 49;
+        assert!(!journal.is_balanced());
+
+        let zero = Journal::transfer(
+            "deposit:3".to_string(),
+            "deposit",
+            Some(user),
+            LedgerAccount::Custody,
+            LedgerAccount::Available(user),
+            0,
+        );
+        assert!(!zero.is_balanced());
+    }
+
+    #[test]
+    fn test_only_platform_accounts_may_go_negative() {
+        let user = Uuid::new_v4();
+        assert!(LedgerAccount::Custody.allows_negative());
+        assert!(LedgerAccount::Unallocated.allows_negative());
+        assert!(!LedgerAccount::Available(user).allows_negative());
+        assert!(!LedgerAccount::PendingWithdrawal(user).allows_negative());
+        assert!(!LedgerAccount::Team(user).allows_negative());
+        assert_eq!(
+            LedgerAccount::PendingWithdrawal(user).key(ASSET_XLM),
+            format!("user:{}:pending_withdrawal:XLM", user)
+        );
+    }
+}
@@ -0,0 +1,339 @@
+//! Transactional email.
+//!
+//! Messages are rendered from an [`EmailTemplate`] and handed to the
+//! configured transport: an SMTP server, the Amazon SES v2 API (signed with
+//! AWS Signature Version 4 like [`ObjectStorage`](super::ObjectStorage)), or
+//! the log when no transport is configured.
+
+use crate::config::{MailConfig, MailTransport};
+use crate::service::object_storage::{amz_date, hmac, signing_key};
+use chrono::Utc;
+use lettre::message::{header::ContentType, Mailbox, MultiPart, SinglePart};
+use lettre::transport::smtp::AsyncSmtpTransport;
+use lettre::{AsyncTransport, Message, Tokio1Executor};
+use reqwest::Client;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use tracing::info;
+
+const SES_SERVICE: &str = "ses";
+
+#[derive(Debug, Error)]
+pub enum MailError {
+    #[error("Invalid email address: {0}")]
+    InvalidAddress(String),
+    #[error("Invalid mail transport: {0}")]
+    InvalidTransport(String),
+    #[error("SMTP delivery failed: {0}")]
+    Smtp(String),
+    #[error("SES request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("SES rejected email with status {status}: {body}")]
+    Rejected { status: u16, body: String },
+}
+
+/// A transactional email and what it needs to be rendered.
+#[derive(Debug, Clone)]
+pub enum EmailTemplate {
+    VerifyEmail {
+        username: String,
+        link: String,
+    },
+    PasswordReset {
+        username: String,
+        link: String,
+    },
+    PasswordChanged {
+        username: String,
+    },
+    /// A notification sent to the email channel.
+    Notification {
+        username: String,
+        title: String,
+        message: String,
+        /// Button label and URL.
+        action: Option<(String, String)>,
+    },
+}
+
+/// A rendered email.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RenderedEmail {
+    pub subject: String,
+    pub text: String,
+    pub html: String,
+}
+
+impl EmailTemplate {
+    pub fn render(&self) -> RenderedEmail {
+        let (subject, greeting, body, action) = match self {
+            EmailTemplate::VerifyEmail { username, link } => (
+                "Confirm your ArenaX email address",
+                username,
+                "Confirm this email address to finish setting up your account. \
+                 The link expires in 24 hours.",
+                Some(("Confirm email", link)),
+            ),
+            EmailTemplate::PasswordReset { username, link } => (
+                "Reset your ArenaX password",
+                username,
+                "Someone asked to reset your password. The link expires in 30 minutes; \
+                 ignore this email if it wasn't you.",
+                Some(("Reset password", link)),
+            ),
+            EmailTemplate::PasswordChanged { username } => (
+                "Your ArenaX password was changed",
+                username,
+                "Your password was just changed and all sessions were signed out. \
+                 If this wasn't you, reset your password right away.",
+                None,
+            ),
+            EmailTemplate::Notification {
+                username,
+                title,
+                message,
+                action,
+            } => (
+                title.as_str(),
+                username,
+                message.as_str(),
+                action.as_ref().map(|(label, link)| (label.as_str(), link)),
+            ),
+        };
+
+        let mut text = format!("Hi {},\n\n{}\n", greeting, body);
+        let mut html = format!(
+            "<p>Hi {},</p>\n<p>{}</p>\n",
+            escape_html(greeting),
+            escape_html(body)
+        );
+        if let Some((label, link)) = action {
+            text.push_str(&format!("\n{}: {}\n", label, link));
+            html.push_str(&format!(
+                "<p><a href=\"{}\">{}</a></p>\n",
+                escape_html(link),
+                escape_html(label)
+            ));
+        }
+        text.push_str("\n— The ArenaX team\n");
+        html.push_str("<p>— The ArenaX team</p>\n");
+
+        RenderedEmail {
+            subject: subject.to_string(),
+            text,
+            html,
+        }
+    }
+}
+
+fn escape_html(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&#39;")
+}
+
+enum Transport {
+    Log,
+    Smtp(AsyncSmtpTransport<Tokio1Executor>),
+    Ses {
+        client: Client,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+/// Sends transactional email through the configured transport.
+pub struct Mailer {
+    from: Mailbox,
+    transport: Transport,
+}
+
+impl Mailer {
+    pub fn new(config: &MailConfig) -> Result<Self, MailError> {
+        let from = config
+            .from
+            .parse()
+            .map_err(|_| MailError::InvalidAddress(config.from.clone()))?;
+        let transport = match &config.transport {
+            MailTransport::Log => Transport::Log,
+            MailTransport::Smtp { url } => Transport::Smtp(
+                AsyncSmtpTransport::<Tokio1Executor>::from_url(url)
+                    .map_err(|e| MailError::InvalidTransport(e.to_string()))?
+                    .build(),
+            ),
+            MailTransport::Ses {
+                region,
+                access_key,
+                secret_key,
+            } => Transport::Ses {
+                client: Client::new(),
+                region: region.clone(),
+                access_key: access_key.clone(),
+                secret_key: secret_key.clone(),
+            },
+        };
+
+        Ok(Self { from, transport })
+    }
+
+    /// Render `template` and send it to `to`.
+    pub async fn send(&self, to: &str, template: &EmailTemplate) -> Result<(), MailError> {
+        let to: Mailbox = to
+            .parse()
+            .map_err(|_| MailError::InvalidAddress(to.to_string()))?;
+        let email = template.render();
+
+        match &self.transport {
+            Transport::Log => {
+                info!(to = %to, subject = %email.subject, body = %email.text, "Email (not sent)");
+            }
+            Transport::Smtp(smtp) => {
+                let message = Message::builder()
+                    .from(self.from.clone())
+                    .to(to.clone())
+                    .subject(&email.subject)
+                    .multipart(
+                        MultiPart::alternative()
+                            .singlepart(
+                                SinglePart::builder()
+                                    .header(ContentType::TEXT_PLAIN)
+                                    .body(email.text),
+                            )
+                            .singlepart(
+                                SinglePart::builder()
+                                    .header(ContentType::TEXT_HTML)
+                                    .body(email.html),
+                            ),
+                    )
+                    .map_err(|e| MailError::Smtp(e.to_string()))?;
+                smtp.send(message)
+                    .await
+                    .map_err(|e| MailError::Smtp(e.to_string()))?;
+            }
+            Transport::Ses {
+                client,
+                region,
+                access_key,
+                secret_key,
+            } => {
+                let body = serde_json::json!({
+                    "FromEmailAddress": self.from.to_string(),
+                    "Destination": { "ToAddresses": [to.to_string()] },
+                    "Content": { "Simple": {
+                        "Subject": { "Data": email.subject, "Charset": "UTF-8" },
+                        "Body": {
+                            "Text": { "Data": email.text, "Charset": "UTF-8" },
+                            "Html": { "Data": email.html, "Charset": "UTF-8" },
+                        },
+                    }},
+                })
+                .to_string();
+                let host = format!("email.{}.amazonaws.com", region);
+                let path = "/v2/email/outbound-emails";
+                let now = Utc::now();
+                let authorization =
+                    ses_authorization(&host, path, &body, region, access_key, secret_key, now);
+
+                let response = client
+                    .post(format!("https://{}{}", host, path))
+                    .header("content-type", "application/json")
+                    .header("x-amz-date", amz_date(now))
+                    .header("authorization", authorization)
+                    .body(body)
+                    .send()
+                    .await?;
+                let status = response.status();
+                if !status.is_success() {
+                    let body = response.text().await.unwrap_or_default();
+                    return Err(MailError::Rejected {
+                        status: status.as_u16(),
+                        body,
+                    });
+                }
+            }
+        }
+
+        info!(to = %to, subject = %email.subject, "Email sent");
+        Ok(())
+    }
+}
+
+/// `Authorization` header value for a JSON `POST` to the SES API.
+fn ses_authorization(
+    host: &str,
+    path: &str,
+    body: &str,
+    region: &str,
+    access_key: &str,
+    secret_key: &str,
+    now: chrono::DateTime<Utc>,
+) -> String {
+    let amz_date = amz_date(now);
+    let date = now.format("%Y%m%d").to_string();
+    let scope = format!("{date}/{region}/{SES_SERVICE}/aws4_request");
+    let signed_headers = "content-type;host;x-amz-date";
+    let payload_hash = hex::encode(Sha256::digest(body.as_bytes()));
+
+    let canonical_request = format!(
+        "POST\n{path}\n\ncontent-type:application/json\nhost:{host}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}"
+    );
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+        hex::encode(Sha256::digest(canonical_request.as_bytes()))
+    );
+
+    let key = signing_key(secret_key, &date, region, SES_SERVICE);
+    let signature = hex::encode(hmac(&key, string_to_sign.as_bytes()));
+
+    format!(
+        "AWS4-HMAC-SHA256 Credential={access_key}/{scope}, SignedHeaders={signed_headers}, Signature={signature}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_escapes_html() {
+        let email = EmailTemplate::PasswordReset {
+            username: "<ada>".to_string(),
+            link: "https://arenax.gg/reset-password?token=a.b".to_string(),
+        }
+        .render();
+
+        assert_eq!(email.subject, "Reset your ArenaX password");
+        assert!(email.text.starts_with("Hi <ada>,"));
+        assert!(email
+            .text
+            .contains("Reset password: https://arenax.gg/reset-password?token=a.b"));
+        assert!(email.html.contains("Hi &lt;ada&gt;,"));
+        assert!(email
+            .html
+            .contains("<a href=\"https://arenax.gg/reset-password?token=a.b\">"));
+    }
+
+    #[test]
+    fn test_ses_authorization_scope() {
+        let now = chrono::DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        let authorization = ses_authorization(
+            "email.eu-west-1.amazonaws.com",
+            "/v2/email/outbound-emails",
+            "{}",
+            "eu-west-1",
+            "AKIDEXAMPLE",
+            "secret",
+            now,
+        );
+
+        assert!(authorization.starts_with(
+            "AWS4-HMAC-SHA256 Credential=AKIDEXAMPLE/20260102/eu-west-1/ses/aws4_request, \
+             SignedHeaders=content-type;host;x-amz-date, Signature="
+        ));
+    }
+}
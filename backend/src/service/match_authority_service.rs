@@ -1,6 +1,7 @@
 use crate::api_error::ApiError;
 use crate::db::DbPool;
 use crate::models::match_authority::*;
+use crate::service::domain_events::{self, DomainEvent};
 use crate::service::soroban_service::{SorobanService, SorobanTxResult};
 use chrono::Utc;
 use sqlx::Row;
@@ -396,7 +397,12 @@ impl MatchAuthorityService {
                 ApiError::internal_error(format!("Blockchain finalization failed: {}", e))
             })?;
 
-        // Step 2: Update match state
+        // Step 2: Update match state and announce the settlement with it
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
         sqlx::query!(
             r#"
             UPDATE match_authority
@@ -407,9 +413,23 @@ impl MatchAuthorityService {
             chain_result.hash,
             match_id
         )
-        .execute(&self.db_pool)
+        .execute(&mut *tx)
         .await
         .map_err(|e| ApiError::database_error(e))?;
+        domain_events::publish(
+            &mut tx,
+            &DomainEvent::MatchFinalized {
+                match_id,
+                on_chain_match_id: match_entity.on_chain_match_id.clone(),
+                player_a: match_entity.player_a.clone(),
+                player_b: match_entity.player_b.clone(),
+                winner: match_entity.winner.clone(),
+                tx_hash: chain_result.hash.clone(),
+            },
+        )
+        .await
+        .map_err(ApiError::database_error)?;
+        tx.commit().await.map_err(ApiError::database_error)?;
 
         // Step 3: Record blockchain sync
         self.record_chain_sync(
@@ -809,10 +829,10 @@ impl MatchAuthorityService {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_validate_transition() {
+    #[tokio::test]
+    async fn test_validate_transition() {
         let service = MatchAuthorityService {
-            db_pool: DbPool::default(),
+            db_pool: DbPool::connect_lazy("postgres://localhost/arenax").unwrap(),
             soroban_service: Arc::new(SorobanService::new(
                 crate::service::soroban_service::NetworkConfig::testnet(),
             )),
@@ -0,0 +1,475 @@
+//! Match result submission with evidence.
+//!
+//! A player reports their score together with screenshots or replays. The
+//! files go to object storage, their SHA-256 hashes are folded into a single
+//! evidence hash, and the score is recorded through [`MatchService`] so the
+//! usual dual-report conflict detection applies. For staked matches opened on
+//! chain the report is also submitted to the match lifecycle contract through
+//! the relayer.
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{
+    EvidenceKind, EvidenceUpload, Match, MatchEvidence, ReportScoreRequest,
+    SubmitMatchResultRequest, SubmitMatchResultResponse,
+};
+use crate::service::match_service::MatchService;
+use crate::service::matchmaking::match_key;
+use crate::service::object_storage::ObjectStorage;
+use crate::service::stellar_relayer::StellarRelayer;
+use base64::{engine::general_purpose, Engine as _};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+pub const MAX_EVIDENCE_FILES: usize = 4;
+pub const MAX_SCREENSHOT_BYTES: usize = 5 * 1024 * 1024;
+pub const MAX_REPLAY_BYTES: usize = 20 * 1024 * 1024;
+pub(crate) const SCREENSHOT_TYPES: &[&str] = &["image/png", "image/jpeg", "image/webp"];
+
+/// An evidence file decoded and hashed, ready to upload.
+#[derive(Debug)]
+pub(crate) struct EvidenceFile {
+    pub(crate) kind: EvidenceKind,
+    pub(crate) content_type: String,
+    pub(crate) bytes: Vec<u8>,
+    pub(crate) sha256: String,
+}
+
+pub struct MatchResultService {
+    db_pool: DbPool,
+    match_service: Arc<MatchService>,
+    storage: Arc<ObjectStorage>,
+    relayer: Option<Arc<StellarRelayer>>,
+    match_contract: Option<String>,
+}
+
+impl MatchResultService {
+    pub fn new(
+        db_pool: DbPool,
+        match_service: Arc<MatchService>,
+        storage: Arc<ObjectStorage>,
+    ) -> Self {
+        Self {
+            db_pool,
+            match_service,
+            storage,
+            relayer: None,
+            match_contract: None,
+        }
+    }
+
+    /// Submit reports for on-chain staked matches to the lifecycle contract.
+    pub fn with_relayer(mut self, relayer: Arc<StellarRelayer>, match_contract: String) -> Self {
+        self.relayer = Some(relayer);
+        self.match_contract = Some(match_contract);
+        self
+    }
+
+    /// Record a player's result report with its evidence.
+    pub async fn submit(
+        &self,
+        match_id: Uuid,
+        user_id: Uuid,
+        request: SubmitMatchResultRequest,
+    ) -> Result<SubmitMatchResultResponse, ApiError> {
+        if request.score < 0 || request.opponent_score < 0 {
+            return Err(ApiError::bad_request("Scores cannot be negative"));
+        }
+        let files = decode_evidence(&request.evidence)?;
+
+        // Reject the report before anything is uploaded.
+        let match_record = self
+            .match_service
+            .ensure_can_report(match_id, user_id)
+            .await?;
+
+        let mut uploaded = Vec::with_capacity(files.len());
+        for file in &files {
+            let key = format!(
+                "matches/{}/{}/{}-{}",
+                match_id,
+                user_id,
+                kind_str(file.kind),
+                file.sha256
+            );
+            let object = self
+                .storage
+                .put_object(&key, &file.content_type, file.bytes.clone())
+                .await
+                .map_err(|e| ApiError::internal_error(format!("Evidence upload failed: {}", e)))?;
+            uploaded.push(MatchEvidence {
+                id: Uuid::new_v4(),
+                match_id,
+                player_id: user_id,
+                kind: file.kind,
+                content_type: file.content_type.clone(),
+                size_bytes: file.bytes.len() as i64,
+                sha256: file.sha256.clone(),
+                object_key: object.key,
+                url: object.url,
+                created_at: Utc::now(),
+            });
+        }
+
+        let hashes: Vec<&str> = files.iter().map(|f| f.sha256.as_str()).collect();
+        let evidence_hash = evidence_hash(&hashes);
+        let proof_url = uploaded
+            .iter()
+            .find(|e| e.kind == EvidenceKind::Screenshot)
+            .or_else(|| uploaded.first())
+            .map(|e| e.url.clone());
+
+        let score = self
+            .match_service
+            .report_score(
+                match_id,
+                user_id,
+                ReportScoreRequest {
+                    score: request.score,
+                    opponent_score: request.opponent_score,
+                    proof_url,
+                    telemetry_data: request.telemetry_data,
+                },
+            )
+            .await?;
+
+        let winner_index = claimed_winner_index(
+            &match_record,
+            user_id,
+            request.score,
+            request.opponent_score,
+        );
+        let on_chain = match winner_index {
+            Some(_) => self.is_on_chain(match_id).await?,
+            None => false,
+        };
+        self.record(match_id, user_id, &uploaded, &evidence_hash, on_chain)
+            .await?;
+
+        if let (true, Some(winner_index)) = (on_chain, winner_index) {
+            // Relayed calls wait for ledger inclusion; don't hold up the response.
+            if let Some(submitter) = self.chain_submitter() {
+                tokio::spawn(async move {
+                    submitter.submit(match_id, user_id, winner_index).await;
+                });
+            }
+        }
+
+        let status = self.match_service.report_status(match_id).await?;
+        info!(
+            match_id = %match_id,
+            user_id = %user_id,
+            status = ?status,
+            on_chain,
+            "Match result submitted"
+        );
+
+        Ok(SubmitMatchResultResponse {
+            match_id,
+            score,
+            evidence: uploaded,
+            evidence_hash,
+            status,
+            on_chain,
+        })
+    }
+
+    /// Whether the match is a staked match that was opened on chain and the
+    /// relayer is available to report it.
+    async fn is_on_chain(&self, match_id: Uuid) -> Result<bool, ApiError> {
+        if self.chain_submitter().is_none() {
+            return Ok(false);
+        }
+        let status: Option<String> =
+            sqlx::query_scalar("SELECT chain_status FROM staked_matches WHERE match_id = $1")
+                .bind(match_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        Ok(status.as_deref() == Some("created"))
+    }
+
+    async fn record(
+        &self,
+        match_id: Uuid,
+        user_id: Uuid,
+        evidence: &[MatchEvidence],
+        evidence_hash: &str,
+        on_chain: bool,
+    ) -> Result<(), ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+
+        for file in evidence {
+            sqlx::query(
+                r#"
+                INSERT INTO match_evidence (
+                    id, match_id, player_id, kind, content_type, size_bytes,
+                    sha256, object_key, url, created_at
+                ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                "#,
+            )
+            .bind(file.id)
+            .bind(file.match_id)
+            .bind(file.player_id)
+            .bind(file.kind)
+            .bind(&file.content_type)
+            .bind(file.size_bytes)
+            .bind(&file.sha256)
+            .bind(&file.object_key)
+            .bind(&file.url)
+            .bind(file.created_at)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO match_result_reports (match_id, player_id, evidence_hash, chain_status)
+            VALUES ($1, $2, $3, $4)
+            "#,
+        )
+        .bind(match_id)
+        .bind(user_id)
+        .bind(evidence_hash)
+        .bind(if on_chain { "pending" } else { "skipped" })
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        tx.commit().await.map_err(ApiError::database_error)
+    }
+
+    fn chain_submitter(&self) -> Option<ChainSubmitter> {
+        Some(ChainSubmitter {
+            db_pool: self.db_pool.clone(),
+            relayer: self.relayer.clone()?,
+            match_contract: self.match_contract.clone()?,
+        })
+    }
+}
+
+/// What the spawned on-chain submission needs from the service.
+struct ChainSubmitter {
+    db_pool: DbPool,
+    relayer: Arc<StellarRelayer>,
+    match_contract: String,
+}
+
+impl ChainSubmitter {
+    async fn submit(&self, match_id: Uuid, user_id: Uuid, winner_index: i64) {
+        let result = match self.reporter_address(user_id).await {
+            Ok(Some(address)) => self
+                .relayer
+                .submit_result(
+                    &self.match_contract,
+                    match_key(match_id),
+                    &address,
+                    winner_index,
+                )
+                .await
+                .map_err(|e| e.to_string()),
+            Ok(None) => Err(format!("player {} has no Stellar wallet", user_id)),
+            Err(e) => Err(e.to_string()),
+        };
+
+        let update = match &result {
+            Ok(receipt) => {
+                info!(match_id = %match_id, tx_hash = %receipt.tx_hash, "Match result submitted on chain");
+                sqlx::query(
+                    r#"
+                    UPDATE match_result_reports
+                    SET chain_status = 'submitted', tx_hash = $3, updated_at = NOW()
+                    WHERE match_id = $1 AND player_id = $2
+                    "#,
+                )
+                .bind(match_id)
+                .bind(user_id)
+                .bind(&receipt.tx_hash)
+                .execute(&self.db_pool)
+                .await
+            }
+            Err(reason) => {
+                warn!(match_id = %match_id, error = %reason, "Failed to submit match result on chain");
+                sqlx::query(
+                    r#"
+                    UPDATE match_result_reports
+                    SET chain_status = 'failed', error = $3, updated_at = NOW()
+                    WHERE match_id = $1 AND player_id = $2
+                    "#,
+                )
+                .bind(match_id)
+                .bind(user_id)
+                .bind(reason)
+                .execute(&self.db_pool)
+                .await
+            }
+        };
+        if let Err(e) = update {
+            error!(match_id = %match_id, error = %e, "Failed to record on-chain result submission");
+        }
+    }
+
+    async fn reporter_address(&self, user_id: Uuid) -> Result<Option<String>, ApiError> {
+        let address: Option<Option<String>> =
+            sqlx::query_scalar("SELECT stellar_public_key FROM wallets WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        Ok(address.flatten())
+    }
+}
+
+/// Decode, validate and hash the uploaded evidence.
+pub(crate) fn decode_evidence(uploads: &[EvidenceUpload]) -> Result<Vec<EvidenceFile>, ApiError> {
+    if uploads.is_empty() {
+        return Err(ApiError::bad_request(
+            "At least one evidence file is required",
+        ));
+    }
+    if uploads.len() > MAX_EVIDENCE_FILES {
+        return Err(ApiError::bad_request(format!(
+            "At most {} evidence files are allowed",
+            MAX_EVIDENCE_FILES
+        )));
+    }
+
+    let mut files: Vec<EvidenceFile> = Vec::with_capacity(uploads.len());
+    for upload in uploads {
+        let content_type = upload.content_type.trim().to_ascii_lowercase();
+        let max_bytes = match upload.kind {
+            EvidenceKind::Screenshot => {
+                if !SCREENSHOT_TYPES.contains(&content_type.as_str()) {
+                    return Err(ApiError::bad_request(format!(
+                        "Unsupported screenshot type: {}",
+                        upload.content_type
+                    )));
+                }
+                MAX_SCREENSHOT_BYTES
+            }
+            EvidenceKind::Replay => MAX_REPLAY_BYTES,
+        };
+
+        let bytes = general_purpose::STANDARD
+            .decode(upload.data.as_bytes())
+            .map_err(|_| ApiError::bad_request("Evidence data must be base64"))?;
+        if bytes.is_empty() {
+            return Err(ApiError::bad_request("Evidence file is empty"));
+        }
+        if bytes.len() > max_bytes {
+            return Err(ApiError::bad_request(format!(
+                "{} evidence exceeds {} bytes",
+                kind_str(upload.kind),
+                max_bytes
+            )));
+        }
+
+        let sha256 = hex::encode(Sha256::digest(&bytes));
+        if files.iter().any(|f| f.sha256 == sha256) {
+            return Err(ApiError::bad_request("Duplicate evidence file"));
+        }
+        files.push(EvidenceFile {
+            kind: upload.kind,
+            content_type,
+            bytes,
+            sha256,
+        });
+    }
+    Ok(files)
+}
+
+/// SHA-256 over the concatenated hex file hashes, in submission order.
+pub(crate) fn evidence_hash(file_hashes: &[&str]) -> String {
+    let mut hasher = Sha256::new();
+    for hash in file_hashes {
+        hasher.update(hash.as_bytes());
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Index of the winner the reporter claims, in the contract's player order
+/// (player 1 first). `None` for a claimed draw, which the contract cannot
+/// express.
+fn claimed_winner_index(
+    match_record: &Match,
+    reporter: Uuid,
+    score: i32,
+    opponent_score: i32,
+) -> Option<i64> {
+    let reporter_index = if reporter == match_record.player1_id {
+        0
+    } else {
+        1
+    };
+    match score.cmp(&opponent_score) {
+        Ordering::Greater => Some(reporter_index),
+        Ordering::Less => Some(1 - reporter_index),
+        Ordering::Equal => None,
+    }
+}
+
+pub(crate) fn kind_str(kind: EvidenceKind) -> &'static str {
+    match kind {
+        EvidenceKind::Screenshot => "screenshot",
+        EvidenceKind::Replay => "replay",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn upload(kind: EvidenceKind, content_type: &str, bytes: &[u8]) -> EvidenceUpload {
+        EvidenceUpload {
+            kind,
+            content_type: content_type.to_string(),
+            data: general_purpose::STANDARD.encode(bytes),
+        }
+    }
+
+    #[test]
+    fn test_decode_evidence_hashes_each_file() {
+        let files = decode_evidence(&[
+            upload(EvidenceKind::Screenshot, "image/PNG", b"png"),
+            upload(EvidenceKind::Replay, "application/octet-stream", b"replay"),
+        ])
+        .unwrap();
+
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].content_type, "image/png");
+        assert_eq!(files[0].sha256, hex::encode(Sha256::digest(b"png")));
+        assert_eq!(files[1].bytes, b"replay");
+    }
+
+    #[test]
+    fn test_decode_evidence_rejects_bad_uploads() {
+        assert!(decode_evidence(&[]).is_err());
+        assert!(decode_evidence(&[upload(EvidenceKind::Screenshot, "image/gif", b"gif")]).is_err());
+        assert!(decode_evidence(&[upload(EvidenceKind::Replay, "video/mp4", b"")]).is_err());
+        assert!(decode_evidence(&[
+            upload(EvidenceKind::Screenshot, "image/png", b"same"),
+            upload(EvidenceKind::Screenshot, "image/png", b"same"),
+        ])
+        .is_err());
+
+        let mut not_base64 = upload(EvidenceKind::Replay, "video/mp4", b"x");
+        not_base64.data = "%%%".to_string();
+        assert!(decode_evidence(&[not_base64]).is_err());
+    }
+
+    #[test]
+    fn test_evidence_hash_depends_on_order() {
+        let forward = evidence_hash(&["aa", "bb"]);
+        assert_eq!(forward, hex::encode(Sha256::digest(b"aabb")));
+        assert_ne!(forward, evidence_hash(&["bb", "aa"]));
+    }
+}
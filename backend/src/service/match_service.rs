@@ -4,17 +4,21 @@ use crate::db::DbPool;
 use crate::models::*;
 use crate::service::reputation_service::ReputationService;
 use chrono::{DateTime, Utc};
+use redis::Client as RedisClient;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::sync::Arc;
+use tracing::error;
 use uuid::Uuid;
 
 pub struct MatchService {
-    db_pool: DbPool,
+    pub(crate) db_pool: DbPool,
     redis_client: Option<Arc<RedisClient>>,
-    reputation_service: Option<Arc<ReputationService>>,
-    event_bus: Option<crate::realtime::event_bus::EventBus>,
+    pub(crate) reputation_service: Option<Arc<ReputationService>>,
+    pub(crate) event_bus: Option<crate::realtime::event_bus::EventBus>,
+    presence: Option<Arc<crate::realtime::presence::PresenceTracker>>,
 }
 
 impl MatchService {
@@ -24,6 +28,7 @@ impl MatchService {
             redis_client: None,
             reputation_service: None,
             event_bus: None,
+            presence: None,
         }
     }
 
@@ -32,6 +37,15 @@ impl MatchService {
         self
     }
 
+    /// Show players back online once their match completes.
+    pub fn with_presence(
+        mut self,
+        presence: Arc<crate::realtime::presence::PresenceTracker>,
+    ) -> Self {
+        self.presence = Some(presence);
+        self
+    }
+
     pub fn with_reputation_service(mut self, reputation_service: ReputationService) -> Self {
         self.reputation_service = Some(Arc::new(reputation_service));
         self
@@ -206,6 +220,34 @@ impl MatchService {
         Ok(score_record)
     }
 
+    /// Check that `user_id` may report a result for the match right now,
+    /// without recording anything. Lets callers reject a report before doing
+    /// expensive work such as uploading evidence.
+    pub async fn ensure_can_report(
+        &self,
+        match_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Match, ApiError> {
+        let match_record = self.get_match_by_id(match_id).await?;
+        self.validate_score_report(&match_record, user_id).await?;
+        Ok(match_record)
+    }
+
+    /// Dual-report status of a match, as seen after a report is recorded.
+    pub async fn report_status(&self, match_id: Uuid) -> Result<DualReportStatus, ApiError> {
+        let match_record = self.get_match_by_id(match_id).await?;
+        if match_record.player2_id.is_some()
+            && !self.both_players_reported_scores(match_id).await?
+        {
+            return Ok(DualReportStatus::AwaitingOpponent);
+        }
+        if self.detect_score_conflict(match_id).await? {
+            Ok(DualReportStatus::Conflict)
+        } else {
+            Ok(DualReportStatus::Agreed)
+        }
+    }
+
     /// Create a match dispute
     pub async fn create_dispute(
         &self,
@@ -276,7 +318,7 @@ impl MatchService {
         // Check player reputation (filter bad actors)
         if let Some(rep_service) = &self.reputation_service {
             let reputation = rep_service.get_player_reputation(user_id).await
-                .map_err(|e| ApiError::internal_server_error(&format!("Reputation check failed: {}", e)))?;
+                .map_err(|e| ApiError::internal_error(&format!("Reputation check failed: {}", e)))?;
             
             // Filter players with very low fair play score
             if reputation.should_filter(30) {
@@ -433,7 +475,7 @@ impl MatchService {
     }
 
     async fn get_player_info(&self, user_id: Uuid) -> Result<PlayerInfo, ApiError> {
-        let user = sqlx::query_as!(User, "SELECT * FROM users WHERE id = $1", user_id)
+        let user = sqlx::query!("SELECT username, avatar_url FROM users WHERE id = $1", user_id)
             .fetch_optional(&self.db_pool)
             .await
             .map_err(|e| ApiError::database_error(e))?
@@ -443,7 +485,7 @@ impl MatchService {
         let elo_rating = self.get_user_elo(user_id, "default").await?;
 
         Ok(PlayerInfo {
-            id: user.id,
+            id: user_id,
             username: user.username,
             elo_rating,
             avatar_url: user.avatar_url,
@@ -728,6 +770,15 @@ impl MatchService {
         }))
         .await?;
 
+        if let Some(presence) = &self.presence {
+            let players = std::iter::once(match_record.player1_id).chain(match_record.player2_id);
+            for player in players {
+                if let Err(e) = presence.clear_activity(player, match_id).await {
+                    error!("Failed to update presence of {}: {}", player, e);
+                }
+            }
+        }
+
         // Publish global event if it's a ranked match
         if match_record.match_type == MatchType::Ranked {
             if let Some(winner) = winner_id {
@@ -743,6 +794,20 @@ impl MatchService {
 
         // If this was a tournament match, trigger round advancement
         if let Some(tournament_id) = match_record.tournament_id {
+            if let (Some(winner), Some(player2_id)) = (winner_id, match_record.player2_id) {
+                let brackets = crate::service::BracketEngine::new(self.db_pool.clone());
+                if let Err(e) = brackets
+                    .on_match_result(tournament_id, match_record.player1_id, player2_id, winner)
+                    .await
+                {
+                    tracing::error!(
+                        "Bracket advancement failed for tournament {} match {}: {}",
+                        tournament_id,
+                        match_id,
+                        e
+                    );
+                }
+            }
             if let Some(round_id) = match_record.round_id {
                 let advancement = crate::orchestrator::RoundAdvancementWorker::new(self.db_pool.clone());
                 if let Err(e) = advancement.on_match_completed(tournament_id, round_id).await {
@@ -1414,8 +1479,8 @@ impl MatchService {
             user_id,
             game,
             MatchStatus::Completed as _,
-            per_page,
-            offset
+            per_page as i64,
+            offset as i64
         )
         .fetch_all(&self.db_pool)
         .await
@@ -1498,8 +1563,8 @@ impl MatchService {
             LIMIT $2 OFFSET $3
             "#,
             game,
-            per_page,
-            offset
+            per_page as i64,
+            offset as i64
         )
         .fetch_all(&self.db_pool)
         .await
@@ -1529,10 +1594,7 @@ impl MatchService {
             leaderboard_entries.push(LeaderboardEntry {
                 rank: rank as i32,
                 user_id: ranking.user_id,
-                username: ranking
-                    .username
-                    .clone()
-                    .unwrap_or_else(|| "Unknown".to_string()),
+                username: ranking.username.clone(),
                 avatar_url: ranking.avatar_url.clone(),
                 current_rating: ranking.current_rating,
                 peak_rating: ranking.peak_rating,
@@ -2114,8 +2176,8 @@ impl MatchService {
             LIMIT $2 OFFSET $3
             "#,
             DisputeStatus::Pending as _,
-            per_page,
-            offset
+            per_page as i64,
+            offset as i64
         )
         .fetch_all(&self.db_pool)
         .await
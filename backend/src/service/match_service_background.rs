@@ -1,6 +1,7 @@
 use crate::api_error::ApiError;
 use crate::db::DbPool;
 use crate::models::{Match, MatchType, MatchmakingQueue, QueueStatus, UserElo};
+use crate::realtime::events::RealtimeEvent;
 use crate::service::reputation_service::ReputationService;
 use chrono::Utc;
 use std::collections::HashSet;
@@ -148,13 +149,32 @@ impl crate::service::match_service::MatchService {
 
                     // Notify players via WebSocket if event bus is available
                     if let Some(bus) = event_bus {
-                        let _ = bus.publish(serde_json::json!({
-                            "type": "match_created",
-                            "match_id": match_record.id,
-                            "player1_id": player1.user_id,
-                            "player2_id": player2.user_id,
-                            "game_mode": game_mode,
-                        })).await;
+                        for (user_id, opponent_id) in [
+                            (player1.user_id, player2.user_id),
+                            (player2.user_id, player1.user_id),
+                        ] {
+                            let opponent_name = sqlx::query_scalar!(
+                                "SELECT username FROM users WHERE id = $1",
+                                opponent_id
+                            )
+                            .fetch_optional(db_pool)
+                            .await
+                            .ok()
+                            .flatten()
+                            .unwrap_or_default();
+
+                            bus.publish_to_user(
+                                user_id,
+                                &RealtimeEvent::MatchFound {
+                                    match_id: match_record.id,
+                                    opponent_id,
+                                    opponent_name,
+                                    game_mode: game_mode.to_string(),
+                                    timestamp: Utc::now().to_rfc3339(),
+                                },
+                            )
+                            .await;
+                        }
                     }
 
                     tracing::info!(
@@ -231,7 +251,7 @@ impl crate::service::match_service::MatchService {
     async fn get_user_elo_static(db_pool: &DbPool, user_id: Uuid, game_mode: &str) -> Result<i32, ApiError> {
         let elo = sqlx::query_as!(
             UserElo,
-            "SELECT * FROM user_elo WHERE user_id = $1 AND game_mode = $2",
+            "SELECT * FROM user_elo WHERE user_id = $1 AND game = $2",
             user_id,
             game_mode
         )
@@ -1,6 +1,7 @@
 use crate::api_error::ApiError;
 use crate::db::DbPool;
 use crate::models::{Match, MatchType, MatchStatus, QueueStatus, UserElo};
+use crate::service::friend_service::{blocks_among, BlockList};
 use chrono::{DateTime, Utc};
 use redis::AsyncCommands;
 use serde::{Deserialize, Serialize};
@@ -153,6 +154,11 @@ impl MatchmakerService {
         let mut sorted_entries = queue_entries;
         sorted_entries.sort_by(|a, b| a.joined_at.cmp(&b.joined_at));
 
+        let user_ids: Vec<Uuid> = sorted_entries.iter().map(|e| e.user_id).collect();
+        let blocks = blocks_among(&self.db_pool, &user_ids)
+            .await
+            .map_err(ApiError::database_error)?;
+
         let mut matches_found = Vec::new();
         let mut processed_players = std::collections::HashSet::new();
 
@@ -162,7 +168,7 @@ impl MatchmakerService {
             }
 
             if let Some(candidate) =
-                find_best_match_for_player(entry, &sorted_entries[i..], &processed_players)
+                find_best_match_for_player(entry, &sorted_entries[i..], &processed_players, &blocks)
             {
                 processed_players.insert(candidate.player1.user_id);
                 processed_players.insert(candidate.player2.user_id);
@@ -431,12 +437,14 @@ impl MatchmakerService {
 // Pure (sync) helpers — no async needed
 // ─────────────────────────────────────────────────────────────────────────────
 
-/// Find the best opponent for `player` from `candidates`.
+/// Find the best opponent for `player` from `candidates`, skipping anyone
+/// either of them blocked.
 /// This is pure CPU work — no I/O — so it does not need to be async.
 fn find_best_match_for_player(
     player: &QueueEntry,
     candidates: &[QueueEntry],
     processed_players: &std::collections::HashSet<Uuid>,
+    blocks: &BlockList,
 ) -> Option<MatchCandidate> {
     let mut best_candidate: Option<MatchCandidate> = None;
     let mut best_score = -1.0_f64;
@@ -446,6 +454,7 @@ fn find_best_match_for_player(
     for candidate in candidates {
         if candidate.user_id == player.user_id
             || processed_players.contains(&candidate.user_id)
+            || blocks.between(player.user_id, candidate.user_id)
         {
             continue;
         }
@@ -574,7 +583,7 @@ impl EloEngine {
             .map_err(|e| ApiError::database_error(e))?;
 
         let (new_elo1, new_elo2) = self.calculate_elo_change(
-            match_record.player1_elo_before,
+            match_record.player1_elo_before.unwrap_or(1200),
             match_record.player2_elo_before.unwrap_or(1200),
             winner_id,
             match_record.player1_id,
@@ -582,7 +591,7 @@ impl EloEngine {
         );
 
         sqlx::query!(
-            "UPDATE user_elo SET current_rating = $1, updated_at = $2 WHERE user_id = $3 AND game = $4",
+            "UPDATE user_elo SET current_rating = $1, last_updated = $2 WHERE user_id = $3 AND game = $4",
             new_elo1,
             Utc::now(),
             match_record.player1_id,
@@ -594,7 +603,7 @@ impl EloEngine {
 
         if let Some(p2_id) = match_record.player2_id {
             sqlx::query!(
-                "UPDATE user_elo SET current_rating = $1, updated_at = $2 WHERE user_id = $3 AND game = $4",
+                "UPDATE user_elo SET current_rating = $1, last_updated = $2 WHERE user_id = $3 AND game = $4",
                 new_elo2,
                 Utc::now(),
                 p2_id,
@@ -609,9 +618,10 @@ impl EloEngine {
             db_pool,
             match_record.player1_id,
             &match_record.game_mode,
-            match_record.player1_elo_before,
+            match_record.player1_elo_before.unwrap_or(1200),
             new_elo1,
             match_id,
+            winner_id,
         )
         .await?;
 
@@ -623,6 +633,7 @@ impl EloEngine {
                 match_record.player2_elo_before.unwrap_or(1200),
                 new_elo2,
                 match_id,
+                winner_id,
             )
             .await?;
         }
@@ -638,16 +649,24 @@ impl EloEngine {
         old_elo: i32,
         new_elo: i32,
         match_id: Uuid,
+        winner_id: Option<Uuid>,
     ) -> Result<(), ApiError> {
+        // elo_history.result: 0=win, 1=loss, 2=draw
+        let result = match winner_id {
+            Some(w) if w == user_id => 0,
+            Some(_) => 1,
+            None => 2,
+        };
         sqlx::query!(
-            "INSERT INTO elo_history (user_id, game, old_rating, new_rating, change_amount, match_id, created_at) \
-             VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            "INSERT INTO elo_history (user_id, game, rating_before, rating_after, rating_change, match_id, result, created_at) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7, $8)",
             user_id,
             game,
             old_elo,
             new_elo,
             new_elo - old_elo,
             match_id,
+            result,
             Utc::now()
         )
         .execute(db_pool)
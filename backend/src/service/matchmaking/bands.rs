@@ -0,0 +1,150 @@
+//! Rating-band pairing for the staked matchmaking queue.
+//!
+//! Every ticket accepts opponents within a rating band that widens the longer
+//! the player waits. Pairing is pure: the queue service loads the tickets of one
+//! (game, stake tier) queue and the blocks between their players, calls [`pair`]
+//! and claims the returned pairs.
+
+use crate::service::friend_service::BlockList;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// Band a player accepts as soon as they join.
+pub const BASE_BAND: i32 = 50;
+/// How much the band widens for every [`BAND_STEP_SECS`] spent waiting.
+pub const BAND_STEP: i32 = 50;
+pub const BAND_STEP_SECS: i64 = 15;
+/// The band never widens past this, however long the wait.
+pub const MAX_BAND: i32 = 400;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Ticket {
+    pub user_id: Uuid,
+    pub rating: i32,
+    pub joined_at: DateTime<Utc>,
+}
+
+impl Ticket {
+    /// Largest rating gap this ticket accepts at `now`.
+    pub fn band(&self, now: DateTime<Utc>) -> i32 {
+        let waited = (now - self.joined_at).num_seconds().max(0);
+        let steps = (waited / BAND_STEP_SECS).min(i32::MAX as i64) as i32;
+        BASE_BAND
+            .saturating_add(steps.saturating_mul(BAND_STEP))
+            .min(MAX_BAND)
+    }
+}
+
+/// Pair tickets from one queue. The longest-waiting players pick first, and
+/// each takes the closest-rated opponent inside its band; ties go to whoever
+/// has waited longer. Players who blocked one another are never paired.
+/// Players left over stay queued for the next pass.
+pub fn pair(tickets: &[Ticket], now: DateTime<Utc>, blocks: &BlockList) -> Vec<(Ticket, Ticket)> {
+    let mut queue: Vec<&Ticket> = tickets.iter().collect();
+    queue.sort_by_key(|t| (t.joined_at, t.user_id));
+
+    let mut taken = vec![false; queue.len()];
+    let mut pairs = Vec::new();
+
+    for i in 0..queue.len() {
+        if taken[i] {
+            continue;
+        }
+        let player = queue[i];
+        let band = player.band(now);
+
+        let opponent = (i + 1..queue.len())
+            .filter(|&j| !taken[j] && !blocks.between(player.user_id, queue[j].user_id))
+            .map(|j| (j, (queue[j].rating - player.rating).abs()))
+            .filter(|&(_, gap)| gap <= band)
+            .min_by_key(|&(j, gap)| (gap, j));
+
+        if let Some((j, _)) = opponent {
+            taken[i] = true;
+            taken[j] = true;
+            pairs.push((player.clone(), queue[j].clone()));
+        }
+    }
+
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn ticket(rating: i32, waited_secs: i64, now: DateTime<Utc>) -> Ticket {
+        Ticket {
+            user_id: Uuid::new_v4(),
+            rating,
+            joined_at: now - Duration::seconds(waited_secs),
+        }
+    }
+
+    #[test]
+    fn test_band_widens_with_wait_and_caps() {
+        let now = Utc::now();
+        assert_eq!(ticket(1200, 0, now).band(now), BASE_BAND);
+        assert_eq!(ticket(1200, 14, now).band(now), BASE_BAND);
+        assert_eq!(ticket(1200, 15, now).band(now), BASE_BAND + BAND_STEP);
+        assert_eq!(ticket(1200, 3600, now).band(now), MAX_BAND);
+    }
+
+    #[test]
+    fn test_pairs_closest_rating_within_band() {
+        let now = Utc::now();
+        let a = ticket(1200, 10, now);
+        let far = ticket(1245, 5, now);
+        let near = ticket(1210, 1, now);
+
+        let pairs = pair(
+            &[a.clone(), far.clone(), near.clone()],
+            now,
+            &BlockList::default(),
+        );
+        assert_eq!(pairs, vec![(a, near)]);
+    }
+
+    #[test]
+    fn test_waiting_longer_reaches_wider_gaps() {
+        let now = Utc::now();
+        let fresh = [ticket(1200, 0, now), ticket(1350, 0, now)];
+        assert!(pair(&fresh, now, &BlockList::default()).is_empty());
+
+        let waited = [ticket(1200, 60, now), ticket(1350, 0, now)];
+        assert_eq!(pair(&waited, now, &BlockList::default()).len(), 1);
+    }
+
+    #[test]
+    fn test_each_player_is_paired_once() {
+        let now = Utc::now();
+        let tickets: Vec<Ticket> = (0..7)
+            .map(|i| ticket(1200 + i * 5, i as i64, now))
+            .collect();
+
+        let pairs = pair(&tickets, now, &BlockList::default());
+        assert_eq!(pairs.len(), 3);
+        let mut seen: Vec<Uuid> = pairs
+            .iter()
+            .flat_map(|(a, b)| [a.user_id, b.user_id])
+            .collect();
+        seen.sort();
+        seen.dedup();
+        assert_eq!(seen.len(), 6);
+    }
+
+    #[test]
+    fn test_blocked_players_are_never_paired() {
+        let now = Utc::now();
+        let a = ticket(1200, 10, now);
+        let blocked = ticket(1205, 5, now);
+        let other = ticket(1240, 1, now);
+        let blocks: BlockList = vec![(blocked.user_id, a.user_id)].into_iter().collect();
+
+        let pairs = pair(&[a.clone(), blocked.clone(), other.clone()], now, &blocks);
+        assert_eq!(pairs, vec![(a, other)]);
+        assert!(pair(&[ticket(1200, 0, now)], now, &blocks).is_empty());
+    }
+}
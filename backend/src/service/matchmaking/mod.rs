@@ -0,0 +1,772 @@
+//! Staked matchmaking queue.
+//!
+//! Players queue for a game at a stake tier. Tickets live in Redis, one hash
+//! per (game, tier) queue, with a per-player key so nobody sits in two queues
+//! at once. A background matcher pairs each queue within rating bands that
+//! widen with waiting time (see [`bands`]), never pairing players who blocked
+//! each other, claims both tickets atomically and creates the match. Staked
+//! matches are then opened on chain through the relayer: a lifecycle match
+//! plus an escrow for both stakes. Both players are told about the match over
+//! their WebSocket channel, and their friends see them in the match.
+
+pub mod bands;
+
+pub use bands::{pair, Ticket};
+
+use crate::api_error::ApiError;
+use crate::config::StellarConfig;
+use crate::db::DbPool;
+use crate::models::{MatchStatus, MatchType, PresenceStatus};
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
+use crate::realtime::presence::PresenceTracker;
+use crate::service::friend_service::blocks_among;
+use crate::service::stellar_relayer::{RelayerError, StellarRelayer};
+use chrono::Utc;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// Redis hash of tickets for one queue: `<prefix>:<game>:<tier>`.
+const TICKETS_KEY_PREFIX: &str = "matchmaking:staked:tickets";
+/// Redis set of `<game>:<tier>` queues that may hold tickets.
+const QUEUES_KEY: &str = "matchmaking:staked:queues";
+/// Per-player key holding the `<game>:<tier>` queue they are in.
+const PLAYER_KEY_PREFIX: &str = "matchmaking:staked:player";
+/// Tickets are dropped if a player is left unmatched this long.
+const TICKET_TTL_SECS: u64 = 1800;
+/// How often the matcher pairs every queue.
+const MATCH_INTERVAL_SECS: u64 = 2;
+const DEFAULT_RATING: i32 = 1200;
+
+/// Claim two tickets only if both are still queued, so a player who left or
+/// was matched elsewhere is never paired.
+const CLAIM_SCRIPT: &str = r#"
+if redis.call('HEXISTS', KEYS[1], ARGV[1]) == 1 and redis.call('HEXISTS', KEYS[1], ARGV[2]) == 1 then
+    redis.call('HDEL', KEYS[1], ARGV[1], ARGV[2])
+    redis.call('DEL', KEYS[2], KEYS[3])
+    return 1
+end
+return 0
+"#;
+
+pub type RedisConn = redis::aio::ConnectionManager;
+
+/// Stake each player puts up, in AX token base units (7 decimals).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StakeTier {
+    Free,
+    Bronze,
+    Silver,
+    Gold,
+}
+
+impl StakeTier {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StakeTier::Free => "free",
+            StakeTier::Bronze => "bronze",
+            StakeTier::Silver => "silver",
+            StakeTier::Gold => "gold",
+        }
+    }
+
+    pub fn stake_amount(&self) -> i64 {
+        match self {
+            StakeTier::Free => 0,
+            StakeTier::Bronze => 10_0000000,
+            StakeTier::Silver => 50_0000000,
+            StakeTier::Gold => 250_0000000,
+        }
+    }
+
+    pub fn is_staked(&self) -> bool {
+        self.stake_amount() > 0
+    }
+}
+
+impl std::str::FromStr for StakeTier {
+    type Err = String;
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "free" => Ok(StakeTier::Free),
+            "bronze" => Ok(StakeTier::Bronze),
+            "silver" => Ok(StakeTier::Silver),
+            "gold" => Ok(StakeTier::Gold),
+            other => Err(format!("unknown stake tier: {}", other)),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueueTicket {
+    pub game: String,
+    pub tier: StakeTier,
+    pub rating: i32,
+    /// Rating gap currently accepted; widens while waiting.
+    pub band: i32,
+    pub joined_at: chrono::DateTime<Utc>,
+    pub queue_size: usize,
+}
+
+pub struct MatchmakingService {
+    db_pool: DbPool,
+    redis: RedisConn,
+    event_bus: Option<EventBus>,
+    presence: Option<Arc<PresenceTracker>>,
+    relayer: Option<Arc<StellarRelayer>>,
+    match_contract: Option<String>,
+    escrow_contract: Option<String>,
+    stake_asset: Option<String>,
+}
+
+impl MatchmakingService {
+    pub fn new(db_pool: DbPool, redis: RedisConn) -> Self {
+        Self {
+            db_pool,
+            redis,
+            event_bus: None,
+            presence: None,
+            relayer: None,
+            match_contract: None,
+            escrow_contract: None,
+            stake_asset: None,
+        }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Show matched players as in their match.
+    pub fn with_presence(mut self, presence: Arc<PresenceTracker>) -> Self {
+        self.presence = Some(presence);
+        self
+    }
+
+    /// Enable staked tiers, opening matches and escrows on the contracts
+    /// configured in `stellar`. Stakes are held in the AX token.
+    pub fn with_relayer(mut self, relayer: Arc<StellarRelayer>, stellar: &StellarConfig) -> Self {
+        self.relayer = Some(relayer);
+        self.match_contract = Some(stellar.soroban_contract_match.clone());
+        self.escrow_contract = stellar.soroban_contract_escrow.clone();
+        self.stake_asset = Some(stellar.soroban_contract_arenax_token.clone());
+        self
+    }
+
+    fn staking_enabled(&self) -> bool {
+        self.relayer.is_some() && self.escrow_contract.is_some()
+    }
+
+    // ========================================================================
+    // QUEUE
+    // ========================================================================
+
+    /// Queue `user_id` for `game` at `tier`. A player can be in one queue at a
+    /// time; staked tiers also need a linked Stellar wallet.
+    pub async fn enqueue(
+        &self,
+        user_id: Uuid,
+        game: &str,
+        tier: StakeTier,
+    ) -> Result<QueueTicket, ApiError> {
+        if game.is_empty() || game.len() > 50 || game.contains(':') {
+            return Err(ApiError::bad_request("Invalid game"));
+        }
+        if tier.is_staked() {
+            if !self.staking_enabled() {
+                return Err(ApiError::bad_request("Staked matchmaking is not available"));
+            }
+            if self.stellar_address(user_id).await?.is_none() {
+                return Err(ApiError::bad_request(
+                    "A Stellar wallet is required for staked matches",
+                ));
+            }
+        }
+
+        let ticket = Ticket {
+            user_id,
+            rating: self.rating(user_id, game).await?,
+            joined_at: Utc::now(),
+        };
+        let ticket_json = serde_json::to_string(&ticket)
+            .map_err(|e| ApiError::internal_error(format!("JSON serialization error: {}", e)))?;
+        let queue = queue_member(game, tier);
+
+        let mut conn = self.redis.clone();
+        let claimed: bool = redis::cmd("SET")
+            .arg(player_key(user_id))
+            .arg(&queue)
+            .arg("NX")
+            .arg("EX")
+            .arg(TICKET_TTL_SECS)
+            .query_async::<Option<String>>(&mut conn)
+            .await
+            .map_err(redis_error)?
+            .is_some();
+        if !claimed {
+            return Err(ApiError::conflict("Already in a matchmaking queue"));
+        }
+
+        let tickets_key = tickets_key(game, tier);
+        let (queue_size,): (usize,) = redis::pipe()
+            .cmd("HSET")
+            .arg(&tickets_key)
+            .arg(user_id.to_string())
+            .arg(&ticket_json)
+            .ignore()
+            .cmd("EXPIRE")
+            .arg(&tickets_key)
+            .arg(TICKET_TTL_SECS)
+            .ignore()
+            .cmd("SADD")
+            .arg(QUEUES_KEY)
+            .arg(&queue)
+            .ignore()
+            .cmd("HLEN")
+            .arg(&tickets_key)
+            .query_async(&mut conn)
+            .await
+            .map_err(redis_error)?;
+
+        info!(user_id = %user_id, game, tier = tier.as_str(), rating = ticket.rating, "Player queued");
+
+        Ok(QueueTicket {
+            game: game.to_string(),
+            tier,
+            rating: ticket.rating,
+            band: ticket.band(ticket.joined_at),
+            joined_at: ticket.joined_at,
+            queue_size,
+        })
+    }
+
+    /// Take `user_id` out of whichever queue they are in. Returns false when
+    /// they were not queued.
+    pub async fn leave(&self, user_id: Uuid) -> Result<bool, ApiError> {
+        let mut conn = self.redis.clone();
+        let queue: Option<String> = conn.get(player_key(user_id)).await.map_err(redis_error)?;
+        let Some((game, tier)) = queue.as_deref().and_then(parse_queue_member) else {
+            return Ok(false);
+        };
+
+        redis::pipe()
+            .cmd("HDEL")
+            .arg(tickets_key(&game, tier))
+            .arg(user_id.to_string())
+            .ignore()
+            .cmd("DEL")
+            .arg(player_key(user_id))
+            .ignore()
+            .query_async::<()>(&mut conn)
+            .await
+            .map_err(redis_error)?;
+        Ok(true)
+    }
+
+    /// The player's ticket, if they are queued.
+    pub async fn status(&self, user_id: Uuid) -> Result<Option<QueueTicket>, ApiError> {
+        let mut conn = self.redis.clone();
+        let queue: Option<String> = conn.get(player_key(user_id)).await.map_err(redis_error)?;
+        let Some((game, tier)) = queue.as_deref().and_then(parse_queue_member) else {
+            return Ok(None);
+        };
+
+        let tickets_key = tickets_key(&game, tier);
+        let (ticket_json, queue_size): (Option<String>, usize) = redis::pipe()
+            .cmd("HGET")
+            .arg(&tickets_key)
+            .arg(user_id.to_string())
+            .cmd("HLEN")
+            .arg(&tickets_key)
+            .query_async(&mut conn)
+            .await
+            .map_err(redis_error)?;
+
+        let Some(ticket) = ticket_json.and_then(|json| serde_json::from_str::<Ticket>(&json).ok())
+        else {
+            return Ok(None);
+        };
+        Ok(Some(QueueTicket {
+            game,
+            tier,
+            rating: ticket.rating,
+            band: ticket.band(Utc::now()),
+            joined_at: ticket.joined_at,
+            queue_size,
+        }))
+    }
+
+    // ========================================================================
+    // MATCHER
+    // ========================================================================
+
+    /// Spawn the matcher as a detached Tokio task.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!(
+                interval_secs = MATCH_INTERVAL_SECS,
+                "Staked matchmaking matcher started"
+            );
+            let mut ticker = tokio::time::interval(Duration::from_secs(MATCH_INTERVAL_SECS));
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.match_queues().await {
+                    error!(error = %e, "Matchmaking pass failed");
+                }
+            }
+        });
+    }
+
+    /// Pair every active queue once.
+    pub async fn match_queues(&self) -> Result<(), ApiError> {
+        let mut conn = self.redis.clone();
+        let queues: Vec<String> = conn.smembers(QUEUES_KEY).await.map_err(redis_error)?;
+
+        for member in queues {
+            let Some((game, tier)) = parse_queue_member(&member) else {
+                let _: () = conn.srem(QUEUES_KEY, &member).await.map_err(redis_error)?;
+                continue;
+            };
+            if let Err(e) = self.match_queue(&mut conn, &member, &game, tier).await {
+                error!(queue = %member, error = %e, "Failed to match queue");
+            }
+        }
+        Ok(())
+    }
+
+    async fn match_queue(
+        &self,
+        conn: &mut RedisConn,
+        member: &str,
+        game: &str,
+        tier: StakeTier,
+    ) -> Result<(), ApiError> {
+        let tickets_key = tickets_key(game, tier);
+        let raw: Vec<String> = conn.hvals(&tickets_key).await.map_err(redis_error)?;
+        if raw.is_empty() {
+            let _: () = conn.srem(QUEUES_KEY, member).await.map_err(redis_error)?;
+            return Ok(());
+        }
+
+        let tickets: Vec<Ticket> = raw
+            .iter()
+            .filter_map(|json| serde_json::from_str(json).ok())
+            .collect();
+
+        let user_ids: Vec<Uuid> = tickets.iter().map(|t| t.user_id).collect();
+        let blocks = blocks_among(&self.db_pool, &user_ids)
+            .await
+            .map_err(ApiError::database_error)?;
+
+        for (a, b) in pair(&tickets, Utc::now(), &blocks) {
+            let claimed: i32 = redis::Script::new(CLAIM_SCRIPT)
+                .key(&tickets_key)
+                .key(player_key(a.user_id))
+                .key(player_key(b.user_id))
+                .arg(a.user_id.to_string())
+                .arg(b.user_id.to_string())
+                .invoke_async(conn)
+                .await
+                .map_err(redis_error)?;
+            if claimed == 0 {
+                continue;
+            }
+
+            if let Err(e) = self.create_match(game, tier, &a, &b).await {
+                error!(
+                    player_a = %a.user_id,
+                    player_b = %b.user_id,
+                    error = %e,
+                    "Failed to create matched game; requeueing players"
+                );
+                self.requeue(conn, member, &tickets_key, &[a, b]).await;
+            }
+        }
+        Ok(())
+    }
+
+    /// Put claimed tickets back after a failed match creation, keeping their
+    /// original join time so their band is not reset.
+    async fn requeue(
+        &self,
+        conn: &mut RedisConn,
+        member: &str,
+        tickets_key: &str,
+        tickets: &[Ticket],
+    ) {
+        let mut pipe = redis::pipe();
+        for ticket in tickets {
+            let Ok(json) = serde_json::to_string(ticket) else {
+                continue;
+            };
+            pipe.cmd("HSET")
+                .arg(tickets_key)
+                .arg(ticket.user_id.to_string())
+                .arg(json)
+                .ignore()
+                .cmd("SET")
+                .arg(player_key(ticket.user_id))
+                .arg(member)
+                .arg("EX")
+                .arg(TICKET_TTL_SECS)
+                .ignore();
+        }
+        if let Err(e) = pipe.query_async::<()>(conn).await {
+            error!(error = %e, "Failed to requeue players");
+        }
+    }
+
+    async fn create_match(
+        &self,
+        game: &str,
+        tier: StakeTier,
+        a: &Ticket,
+        b: &Ticket,
+    ) -> Result<Uuid, ApiError> {
+        let match_id = Uuid::new_v4();
+        let now = Utc::now();
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+
+        sqlx::query(
+            r#"
+            INSERT INTO matches (
+                id, match_type, status, player1_id, player2_id,
+                player1_elo_before, player2_elo_before, game_mode, created_at, updated_at
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $9)
+            "#,
+        )
+        .bind(match_id)
+        .bind(MatchType::Ranked)
+        .bind(MatchStatus::Pending)
+        .bind(a.user_id)
+        .bind(b.user_id)
+        .bind(a.rating)
+        .bind(b.rating)
+        .bind(game)
+        .bind(now)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        if tier.is_staked() {
+            sqlx::query(
+                r#"
+                INSERT INTO staked_matches (match_id, game, stake_tier, stake_amount, on_chain_match_id)
+                VALUES ($1, $2, $3, $4, $5)
+                "#,
+            )
+            .bind(match_id)
+            .bind(game)
+            .bind(tier.as_str())
+            .bind(tier.stake_amount())
+            .bind(hex::encode(match_key(match_id)))
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+        }
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        info!(
+            match_id = %match_id,
+            game,
+            tier = tier.as_str(),
+            player_a = %a.user_id,
+            player_b = %b.user_id,
+            rating_gap = (a.rating - b.rating).abs(),
+            "Matchmaking paired players"
+        );
+
+        self.notify_match_found(match_id, game, a.user_id, b.user_id)
+            .await;
+        if let Some(presence) = &self.presence {
+            for player in [a.user_id, b.user_id] {
+                if let Err(e) = presence
+                    .set_activity(player, PresenceStatus::InMatch, match_id)
+                    .await
+                {
+                    warn!(user_id = %player, error = %e, "Failed to update presence");
+                }
+            }
+        }
+
+        if tier.is_staked() {
+            // Relayed calls wait for ledger inclusion; don't hold up the queue.
+            let service = self.on_chain_handle();
+            let (player_a, player_b) = (a.user_id, b.user_id);
+            tokio::spawn(async move {
+                service
+                    .open_on_chain(match_id, player_a, player_b, tier)
+                    .await;
+            });
+        }
+        Ok(match_id)
+    }
+
+    // ========================================================================
+    // ON-CHAIN
+    // ========================================================================
+
+    fn on_chain_handle(&self) -> OnChain {
+        OnChain {
+            db_pool: self.db_pool.clone(),
+            event_bus: self.event_bus.clone(),
+            relayer: self.relayer.clone(),
+            match_contract: self.match_contract.clone(),
+            escrow_contract: self.escrow_contract.clone(),
+            stake_asset: self.stake_asset.clone(),
+        }
+    }
+
+    // ========================================================================
+    // HELPERS
+    // ========================================================================
+
+    async fn notify_match_found(&self, match_id: Uuid, game: &str, a: Uuid, b: Uuid) {
+        let Some(event_bus) = &self.event_bus else {
+            return;
+        };
+        for (player, opponent) in [(a, b), (b, a)] {
+            let opponent_name = self.username(opponent).await.unwrap_or_default();
+            let event = RealtimeEvent::MatchFound {
+                match_id,
+                opponent_id: opponent,
+                opponent_name,
+                game_mode: game.to_string(),
+                timestamp: Utc::now().to_rfc3339(),
+            };
+            event_bus.publish_to_user(player, &event).await;
+        }
+    }
+
+    async fn rating(&self, user_id: Uuid, game: &str) -> Result<i32, ApiError> {
+        let rating: Option<Option<i32>> = sqlx::query_scalar(
+            "SELECT current_rating FROM user_elo WHERE user_id = $1 AND game = $2",
+        )
+        .bind(user_id)
+        .bind(game)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        Ok(rating.flatten().unwrap_or(DEFAULT_RATING))
+    }
+
+    async fn username(&self, user_id: Uuid) -> Option<String> {
+        sqlx::query_scalar("SELECT username FROM users WHERE id = $1")
+            .bind(user_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .ok()
+            .flatten()
+    }
+
+    async fn stellar_address(&self, user_id: Uuid) -> Result<Option<String>, ApiError> {
+        stellar_address(&self.db_pool, user_id).await
+    }
+}
+
+/// What the spawned on-chain task needs from the service.
+struct OnChain {
+    db_pool: DbPool,
+    event_bus: Option<EventBus>,
+    relayer: Option<Arc<StellarRelayer>>,
+    match_contract: Option<String>,
+    escrow_contract: Option<String>,
+    stake_asset: Option<String>,
+}
+
+impl OnChain {
+    /// Create the lifecycle match and the escrow for a staked match. If either
+    /// call fails the match is cancelled so neither player is left waiting.
+    async fn open_on_chain(&self, match_id: Uuid, a: Uuid, b: Uuid, tier: StakeTier) {
+        match self.create_on_chain(match_id, a, b, tier).await {
+            Ok(()) => info!(match_id = %match_id, "Staked match opened on chain"),
+            Err(e) => {
+                warn!(match_id = %match_id, error = %e, "Failed to open staked match on chain");
+                if let Err(db_err) = self.fail(match_id, &e.to_string()).await {
+                    error!(match_id = %match_id, error = %db_err, "Failed to cancel staked match");
+                }
+                if let Some(event_bus) = &self.event_bus {
+                    let event = RealtimeEvent::MatchStatusChange {
+                        match_id,
+                        from_status: "pending".to_string(),
+                        to_status: "cancelled".to_string(),
+                        timestamp: Utc::now().to_rfc3339(),
+                    };
+                    event_bus.publish_to_user(a, &event).await;
+                    event_bus.publish_to_user(b, &event).await;
+                }
+            }
+        }
+    }
+
+    async fn create_on_chain(
+        &self,
+        match_id: Uuid,
+        a: Uuid,
+        b: Uuid,
+        tier: StakeTier,
+    ) -> Result<(), OnChainError> {
+        let (Some(relayer), Some(match_contract), Some(escrow_contract), Some(asset)) = (
+            &self.relayer,
+            &self.match_contract,
+            &self.escrow_contract,
+            &self.stake_asset,
+        ) else {
+            return Err(OnChainError::NotConfigured);
+        };
+        let address_a = stellar_address(&self.db_pool, a)
+            .await?
+            .ok_or(OnChainError::MissingWallet(a))?;
+        let address_b = stellar_address(&self.db_pool, b)
+            .await?
+            .ok_or(OnChainError::MissingWallet(b))?;
+        let key = match_key(match_id);
+        let amount = tier.stake_amount() as i128;
+
+        let created = relayer
+            .create_match(
+                match_contract,
+                key,
+                &[&address_a, &address_b],
+                asset,
+                amount,
+            )
+            .await?;
+        sqlx::query(
+            "UPDATE staked_matches SET match_tx_hash = $2, updated_at = NOW() WHERE match_id = $1",
+        )
+        .bind(match_id)
+        .bind(&created.tx_hash)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let escrow = relayer
+            .create_escrow(escrow_contract, key, &address_a, &address_b, amount, asset)
+            .await?;
+        sqlx::query(
+            r#"
+            UPDATE staked_matches
+            SET chain_status = 'created', escrow_tx_hash = $2, updated_at = NOW()
+            WHERE match_id = $1
+            "#,
+        )
+        .bind(match_id)
+        .bind(&escrow.tx_hash)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        Ok(())
+    }
+
+    async fn fail(&self, match_id: Uuid, reason: &str) -> Result<(), ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        sqlx::query(
+            r#"
+            UPDATE staked_matches
+            SET chain_status = 'failed', error = $2, updated_at = NOW()
+            WHERE match_id = $1
+            "#,
+        )
+        .bind(match_id)
+        .bind(reason)
+        .execute(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        sqlx::query("UPDATE matches SET status = $2, updated_at = NOW() WHERE id = $1")
+            .bind(match_id)
+            .bind(MatchStatus::Cancelled)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+        tx.commit().await.map_err(ApiError::database_error)
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+enum OnChainError {
+    #[error("staked matches are not configured")]
+    NotConfigured,
+    #[error("player {0} has no Stellar wallet")]
+    MissingWallet(Uuid),
+    #[error(transparent)]
+    Relayer(#[from] RelayerError),
+    #[error(transparent)]
+    Api(#[from] ApiError),
+}
+
+/// `BytesN<32>` id of the match on chain: the match UUID, zero-padded.
+pub fn match_key(match_id: Uuid) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    key[..16].copy_from_slice(match_id.as_bytes());
+    key
+}
+
+async fn stellar_address(db_pool: &DbPool, user_id: Uuid) -> Result<Option<String>, ApiError> {
+    let address: Option<Option<String>> =
+        sqlx::query_scalar("SELECT stellar_public_key FROM wallets WHERE user_id = $1")
+            .bind(user_id)
+            .fetch_optional(db_pool)
+            .await
+            .map_err(ApiError::database_error)?;
+    Ok(address.flatten())
+}
+
+fn tickets_key(game: &str, tier: StakeTier) -> String {
+    format!("{}:{}:{}", TICKETS_KEY_PREFIX, game, tier.as_str())
+}
+
+fn player_key(user_id: Uuid) -> String {
+    format!("{}:{}", PLAYER_KEY_PREFIX, user_id)
+}
+
+fn queue_member(game: &str, tier: StakeTier) -> String {
+    format!("{}:{}", game, tier.as_str())
+}
+
+fn parse_queue_member(member: &str) -> Option<(String, StakeTier)> {
+    let (game, tier) = member.rsplit_once(':')?;
+    Some((game.to_string(), tier.parse().ok()?))
+}
+
+fn redis_error(e: redis::RedisError) -> ApiError {
+    ApiError::internal_error(format!("Redis error: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_queue_member_round_trips() {
+        let member = queue_member("street-fighter", StakeTier::Silver);
+        assert_eq!(
+            parse_queue_member(&member),
+            Some(("street-fighter".to_string(), StakeTier::Silver))
+        );
+        assert_eq!(parse_queue_member("chess:platinum"), None);
+    }
+
+    #[test]
+    fn test_only_free_tier_is_unstaked() {
+        assert!(!StakeTier::Free.is_staked());
+        assert!(StakeTier::Bronze.is_staked());
+        assert!(StakeTier::Gold.stake_amount() > StakeTier::Silver.stake_amount());
+    }
+}
@@ -0,0 +1,807 @@
+//! Replay and screenshot uploads for match and dispute evidence.
+//!
+//! Files too large for a JSON body are uploaded in parts: the client declares
+//! the file's size and SHA-256, then PUTs each part, which is forwarded to an
+//! S3 multipart upload. Once assembled, the object is re-hashed from storage
+//! and handed to the [`MediaScanner`], if one is configured; only then is it
+//! `ready` to be attached to a dispute. Objects failing either check are
+//! deleted.
+//!
+//! Downloads go through signed URLs that expire, issued to the uploader and to
+//! referees. Referees can also re-hash a dispute's evidence and match it
+//! against the evidence reference the case was opened with on chain.
+//!
+//! A background sweep aborts abandoned uploads, retries processing that did
+//! not finish and deletes objects past their retention period. Objects that
+//! are evidence in an open dispute are kept until the case is closed.
+
+pub mod scan;
+
+pub use scan::{HttpScanner, MediaScanner, ScanRequest, ScanVerdict};
+
+use crate::api_error::ApiError;
+use crate::config::MediaConfig;
+use crate::db::DbPool;
+use crate::models::{
+    DisputeCase, DisputeCaseStatus, DisputeEvidence, EvidenceFileCheck, EvidenceKind,
+    EvidenceVerification, MediaObject, MediaPart, MediaStatus, MediaUpload, SignedMediaUrl,
+    StartMediaUploadRequest,
+};
+use crate::service::match_result_service::{
+    evidence_hash, kind_str, MAX_SCREENSHOT_BYTES, SCREENSHOT_TYPES,
+};
+use crate::service::object_storage::{ObjectStorage, StorageError};
+use chrono::Utc;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+/// S3's minimum size for every part but the last.
+const MIN_PART_BYTES: i64 = 5 * 1024 * 1024;
+/// Largest part the upload endpoint accepts in one request body.
+pub const MAX_PART_BYTES: i64 = 64 * 1024 * 1024;
+/// S3's limit on parts per upload.
+const MAX_PARTS: i64 = 10_000;
+/// Signed URLs cannot outlive SigV4's seven-day limit.
+const MAX_DOWNLOAD_TTL_SECS: u64 = 7 * 24 * 60 * 60;
+/// Uploads not completed within this many hours are aborted.
+const UPLOAD_TTL_HOURS: i64 = 24;
+/// Processing still unfinished after this many minutes is retried.
+const PROCESSING_RETRY_MINUTES: i64 = 10;
+const SWEEP_INTERVAL_SECS: u64 = 300;
+const SWEEP_BATCH: i64 = 100;
+
+#[derive(Debug, Error)]
+pub enum MediaError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("{0}")]
+    Conflict(String),
+    #[error("Upload not found")]
+    NotFound,
+    #[error("Dispute not found")]
+    DisputeNotFound,
+    #[error("You cannot access this upload")]
+    Forbidden,
+    #[error("Malware scan failed: {0}")]
+    Scanner(String),
+    #[error("Storage error: {0}")]
+    Storage(#[from] StorageError),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<MediaError> for ApiError {
+    fn from(err: MediaError) -> Self {
+        match err {
+            MediaError::InvalidRequest(_) => ApiError::BadRequest(err.to_string()),
+            MediaError::Conflict(_) => ApiError::Conflict(err.to_string()),
+            MediaError::NotFound | MediaError::DisputeNotFound => ApiError::NotFound,
+            MediaError::Forbidden => ApiError::Forbidden,
+            MediaError::Scanner(_) | MediaError::Storage(_) => {
+                ApiError::InternalServerError(err.to_string())
+            }
+            MediaError::DatabaseError(e) => ApiError::DatabaseError(e),
+        }
+    }
+}
+
+/// Who is acting on a media request.
+#[derive(Debug, Clone, Copy)]
+pub struct MediaActor {
+    pub user_id: Uuid,
+    /// Holds the `referee` or `admin` role.
+    pub is_referee: bool,
+}
+
+pub struct MediaService {
+    db_pool: DbPool,
+    storage: Arc<ObjectStorage>,
+    scanner: Option<Arc<dyn MediaScanner>>,
+    max_replay_bytes: i64,
+    part_size: i64,
+    download_ttl: Duration,
+    retention_days: i64,
+}
+
+impl MediaService {
+    pub fn new(db_pool: DbPool, storage: Arc<ObjectStorage>, config: &MediaConfig) -> Self {
+        let scanner = config
+            .scanner_url
+            .as_deref()
+            .map(|url| Arc::new(HttpScanner::new(url)) as Arc<dyn MediaScanner>);
+        Self {
+            db_pool,
+            storage,
+            scanner,
+            max_replay_bytes: config.max_replay_bytes,
+            part_size: config.part_size_bytes.clamp(MIN_PART_BYTES, MAX_PART_BYTES),
+            download_ttl: Duration::from_secs(
+                config.download_url_ttl_secs.clamp(1, MAX_DOWNLOAD_TTL_SECS),
+            ),
+            retention_days: config.retention_days,
+        }
+    }
+
+    /// Scan uploads with `scanner` instead of the configured one.
+    pub fn with_scanner(mut self, scanner: Arc<dyn MediaScanner>) -> Self {
+        self.scanner = Some(scanner);
+        self
+    }
+
+    // ========================================================================
+    // UPLOADS
+    // ========================================================================
+
+    /// Start a multipart upload of a file whose size and SHA-256 the caller
+    /// declares up front.
+    pub async fn start_upload(
+        &self,
+        user_id: Uuid,
+        request: &StartMediaUploadRequest,
+    ) -> Result<MediaUpload, MediaError> {
+        let content_type = request.content_type.trim().to_ascii_lowercase();
+        let sha256 = request.sha256.to_ascii_lowercase();
+        if !is_sha256_hex(&sha256) {
+            return Err(MediaError::InvalidRequest(
+                "sha256 must be 64 hex characters".to_string(),
+            ));
+        }
+        let max_bytes = match request.kind {
+            EvidenceKind::Screenshot => {
+                if !SCREENSHOT_TYPES.contains(&content_type.as_str()) {
+                    return Err(MediaError::InvalidRequest(format!(
+                        "Unsupported screenshot type: {}",
+                        request.content_type
+                    )));
+                }
+                MAX_SCREENSHOT_BYTES as i64
+            }
+            EvidenceKind::Replay => self.max_replay_bytes,
+        };
+        if request.size_bytes > max_bytes {
+            return Err(MediaError::InvalidRequest(format!(
+                "File exceeds the {} byte limit",
+                max_bytes
+            )));
+        }
+        if (request.size_bytes + self.part_size - 1) / self.part_size > MAX_PARTS {
+            return Err(MediaError::InvalidRequest(format!(
+                "File needs more than {} parts",
+                MAX_PARTS
+            )));
+        }
+        if let Some(match_id) = request.match_id {
+            let played: Option<bool> = sqlx::query_scalar(
+                "SELECT COALESCE($2 IN (player1_id, player2_id), FALSE) FROM matches WHERE id = $1",
+            )
+            .bind(match_id)
+            .bind(user_id)
+            .fetch_optional(&self.db_pool)
+            .await?;
+            match played {
+                None => return Err(MediaError::InvalidRequest("Match not found".to_string())),
+                Some(false) => return Err(MediaError::Forbidden),
+                Some(true) => {}
+            }
+        }
+
+        let media_id = Uuid::new_v4();
+        let key = format!(
+            "media/{}/{}/{}-{}",
+            user_id,
+            media_id,
+            kind_str(request.kind),
+            sha256
+        );
+        let upload_id = self
+            .storage
+            .create_multipart_upload(&key, &content_type)
+            .await?;
+        let inserted = sqlx::query_as::<_, MediaObject>(
+            r#"
+            INSERT INTO media_objects (
+                id, owner_id, kind, content_type, size_bytes, sha256, object_key,
+                upload_id, part_size, status, match_id
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+            RETURNING *
+            "#,
+        )
+        .bind(media_id)
+        .bind(user_id)
+        .bind(request.kind)
+        .bind(&content_type)
+        .bind(request.size_bytes)
+        .bind(&sha256)
+        .bind(&key)
+        .bind(&upload_id)
+        .bind(self.part_size)
+        .bind(MediaStatus::Uploading)
+        .bind(request.match_id)
+        .fetch_one(&self.db_pool)
+        .await;
+        let media = match inserted {
+            Ok(media) => media,
+            Err(e) => {
+                if let Err(abort) = self.storage.abort_multipart_upload(&key, &upload_id).await {
+                    warn!(key = %key, error = %abort, "Failed to abort orphaned upload");
+                }
+                return Err(e.into());
+            }
+        };
+
+        info!(media_id = %media_id, user_id = %user_id, size = media.size_bytes, "Media upload started");
+        Ok(MediaUpload {
+            part_count: media.part_count(),
+            media,
+            parts: Vec::new(),
+        })
+    }
+
+    /// Upload part `part_number` of the caller's upload. Parts may arrive in
+    /// any order; sending a part again replaces it.
+    pub async fn upload_part(
+        &self,
+        user_id: Uuid,
+        media_id: Uuid,
+        part_number: i32,
+        body: Vec<u8>,
+    ) -> Result<MediaPart, MediaError> {
+        let media = self.owned(user_id, media_id).await?;
+        let upload_id = in_progress(&media)?;
+        if part_number < 1 || part_number > media.part_count() {
+            return Err(MediaError::InvalidRequest(format!(
+                "Part number must be between 1 and {}",
+                media.part_count()
+            )));
+        }
+        let expected = media.part_len(part_number);
+        if body.len() as i64 != expected {
+            return Err(MediaError::InvalidRequest(format!(
+                "Part {} must be {} bytes",
+                part_number, expected
+            )));
+        }
+
+        let etag = self
+            .storage
+            .upload_part(&media.object_key, upload_id, part_number, body)
+            .await?;
+        let part = sqlx::query_as::<_, MediaPart>(
+            r#"
+            INSERT INTO media_upload_parts (media_id, part_number, etag, size_bytes)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (media_id, part_number)
+            DO UPDATE SET etag = EXCLUDED.etag, uploaded_at = NOW()
+            RETURNING part_number, size_bytes, uploaded_at
+            "#,
+        )
+        .bind(media_id)
+        .bind(part_number)
+        .bind(&etag)
+        .bind(expected)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(part)
+    }
+
+    /// Assemble the uploaded parts. The upload stays `processing` until the
+    /// object has been hashed and scanned.
+    pub async fn complete(
+        self: &Arc<Self>,
+        user_id: Uuid,
+        media_id: Uuid,
+    ) -> Result<MediaObject, MediaError> {
+        let media = self.owned(user_id, media_id).await?;
+        let upload_id = in_progress(&media)?;
+        let parts: Vec<(i32, String)> = sqlx::query_as(
+            "SELECT part_number, etag FROM media_upload_parts WHERE media_id = $1 ORDER BY part_number",
+        )
+        .bind(media_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        if parts.len() as i32 != media.part_count() {
+            return Err(MediaError::InvalidRequest(format!(
+                "{} of {} parts uploaded",
+                parts.len(),
+                media.part_count()
+            )));
+        }
+
+        self.storage
+            .complete_multipart_upload(&media.object_key, upload_id, &parts)
+            .await?;
+        let media = sqlx::query_as::<_, MediaObject>(
+            r#"
+            UPDATE media_objects
+            SET status = $2, upload_id = NULL, completed_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND status = $3
+            RETURNING *
+            "#,
+        )
+        .bind(media_id)
+        .bind(MediaStatus::Processing)
+        .bind(MediaStatus::Uploading)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or_else(|| MediaError::Conflict("Upload is already complete".to_string()))?;
+        sqlx::query("DELETE FROM media_upload_parts WHERE media_id = $1")
+            .bind(media_id)
+            .execute(&self.db_pool)
+            .await?;
+
+        // Hashing a large replay takes a while; don't hold up the response.
+        let service = Arc::clone(self);
+        tokio::spawn(async move { service.process(media_id).await });
+        Ok(media)
+    }
+
+    /// Abandon the caller's unfinished upload.
+    pub async fn abort(&self, user_id: Uuid, media_id: Uuid) -> Result<(), MediaError> {
+        let media = self.owned(user_id, media_id).await?;
+        let upload_id = in_progress(&media)?;
+        self.storage
+            .abort_multipart_upload(&media.object_key, upload_id)
+            .await?;
+        sqlx::query("DELETE FROM media_objects WHERE id = $1")
+            .bind(media_id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    /// An upload with the parts received so far. Visible to the uploader and
+    /// referees.
+    pub async fn get(&self, actor: MediaActor, media_id: Uuid) -> Result<MediaUpload, MediaError> {
+        let media = self.find(media_id).await?;
+        if media.owner_id != actor.user_id && !actor.is_referee {
+            return Err(MediaError::Forbidden);
+        }
+        let parts = sqlx::query_as::<_, MediaPart>(
+            r#"
+            SELECT part_number, size_bytes, uploaded_at FROM media_upload_parts
+            WHERE media_id = $1 ORDER BY part_number
+            "#,
+        )
+        .bind(media_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(MediaUpload {
+            part_count: media.part_count(),
+            media,
+            parts,
+        })
+    }
+
+    /// A signed, expiring URL to download a verified upload. Issued to the
+    /// uploader and referees.
+    pub async fn download_url(
+        &self,
+        actor: MediaActor,
+        media_id: Uuid,
+    ) -> Result<SignedMediaUrl, MediaError> {
+        let media = self.find(media_id).await?;
+        if media.owner_id != actor.user_id && !actor.is_referee {
+            return Err(MediaError::Forbidden);
+        }
+        if media.status != MediaStatus::Ready {
+            return Err(MediaError::Conflict("Upload is not ready".to_string()));
+        }
+        Ok(self.signed_url(&media.object_key))
+    }
+
+    // ========================================================================
+    // VERIFICATION
+    // ========================================================================
+
+    /// Re-hash a dispute's evidence from storage and match it against the
+    /// evidence reference the case was opened with. Referees only; every
+    /// file is downloaded, so this is meant for reviewing one case.
+    pub async fn verify_dispute(
+        &self,
+        actor: MediaActor,
+        dispute_id: Uuid,
+    ) -> Result<EvidenceVerification, MediaError> {
+        if !actor.is_referee {
+            return Err(MediaError::Forbidden);
+        }
+        let case = sqlx::query_as::<_, DisputeCase>("SELECT * FROM match_disputes WHERE id = $1")
+            .bind(dispute_id)
+            .fetch_optional(&self.db_pool)
+            .await?
+            .ok_or(MediaError::DisputeNotFound)?;
+        let evidence = sqlx::query_as::<_, DisputeEvidence>(
+            "SELECT * FROM dispute_evidence WHERE dispute_id = $1 ORDER BY created_at ASC",
+        )
+        .bind(dispute_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut files = Vec::with_capacity(evidence.len());
+        for file in evidence {
+            let stored_sha256 = match self.hash_object(&file.object_key).await {
+                Ok((sha256, _)) => Some(sha256),
+                Err(e) => {
+                    warn!(evidence_id = %file.id, error = %e, "Evidence could not be re-hashed");
+                    None
+                }
+            };
+            files.push(EvidenceFileCheck {
+                evidence_id: file.id,
+                uploaded_by: file.uploaded_by,
+                kind: file.kind,
+                intact: stored_sha256.as_deref() == Some(file.sha256.as_str()),
+                on_chain: case.evidence_hashes.contains(&file.sha256),
+                download: self.signed_url(&file.object_key),
+                sha256: file.sha256,
+                stored_sha256,
+            });
+        }
+
+        let recomputed_ref = recompute_ref(&case.evidence_hashes, &files);
+        let verified = files.iter().all(|f| f.intact) && recomputed_ref == case.evidence_ref;
+        Ok(EvidenceVerification {
+            dispute_id,
+            chain_status: case.chain_status,
+            chain_open_tx: case.chain_open_tx,
+            evidence_ref: case.evidence_ref,
+            recomputed_ref,
+            verified,
+            files,
+        })
+    }
+
+    // ========================================================================
+    // BACKGROUND TASK
+    // ========================================================================
+
+    /// Spawn the retention sweep as a detached Tokio task.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!(
+                retention_days = self.retention_days,
+                "Media sweep started — every {}s", SWEEP_INTERVAL_SECS
+            );
+            let mut ticker = tokio::time::interval(Duration::from_secs(SWEEP_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.abort_abandoned().await {
+                    error!(error = %e, "Failed to abort abandoned media uploads");
+                }
+                if let Err(e) = self.retry_processing().await {
+                    error!(error = %e, "Failed to retry media processing");
+                }
+                if let Err(e) = self.expire().await {
+                    error!(error = %e, "Failed to expire media");
+                }
+            }
+        });
+    }
+
+    async fn abort_abandoned(&self) -> Result<(), MediaError> {
+        let abandoned = sqlx::query_as::<_, MediaObject>(
+            "SELECT * FROM media_objects WHERE status = $1 AND created_at < $2 LIMIT $3",
+        )
+        .bind(MediaStatus::Uploading)
+        .bind(Utc::now() - chrono::Duration::hours(UPLOAD_TTL_HOURS))
+        .bind(SWEEP_BATCH)
+        .fetch_all(&self.db_pool)
+        .await?;
+        for media in abandoned {
+            if let Some(upload_id) = &media.upload_id {
+                if let Err(e) = self
+                    .storage
+                    .abort_multipart_upload(&media.object_key, upload_id)
+                    .await
+                {
+                    warn!(media_id = %media.id, error = %e, "Failed to abort upload");
+                    continue;
+                }
+            }
+            sqlx::query("DELETE FROM media_objects WHERE id = $1")
+                .bind(media.id)
+                .execute(&self.db_pool)
+                .await?;
+            info!(media_id = %media.id, "Abandoned media upload aborted");
+        }
+        Ok(())
+    }
+
+    async fn retry_processing(&self) -> Result<(), MediaError> {
+        let stalled: Vec<Uuid> = sqlx::query_scalar(
+            r#"
+            UPDATE media_objects SET updated_at = NOW()
+            WHERE id IN (
+                SELECT id FROM media_objects
+                WHERE status = $1 AND updated_at < $2
+                LIMIT $3
+            )
+            RETURNING id
+            "#,
+        )
+        .bind(MediaStatus::Processing)
+        .bind(Utc::now() - chrono::Duration::minutes(PROCESSING_RETRY_MINUTES))
+        .bind(SWEEP_BATCH)
+        .fetch_all(&self.db_pool)
+        .await?;
+        for media_id in stalled {
+            self.process(media_id).await;
+        }
+        Ok(())
+    }
+
+    /// Delete objects past their retention period. Evidence in a dispute is
+    /// kept until the case has been closed for the retention period too.
+    async fn expire(&self) -> Result<(), MediaError> {
+        let cutoff = Utc::now() - chrono::Duration::days(self.retention_days);
+        let expired = sqlx::query_as::<_, MediaObject>(
+            r#"
+            SELECT m.* FROM media_objects m
+            LEFT JOIN match_disputes d ON d.id = m.dispute_id
+            WHERE m.status = $1 AND m.retain_until < NOW()
+              AND (d.id IS NULL
+                   OR (d.status = ANY($2) AND COALESCE(d.resolved_at, d.updated_at) < $3))
+            LIMIT $4
+            "#,
+        )
+        .bind(MediaStatus::Ready)
+        .bind(vec![
+            DisputeCaseStatus::Resolved as i32,
+            DisputeCaseStatus::Rejected as i32,
+        ])
+        .bind(cutoff)
+        .bind(SWEEP_BATCH)
+        .fetch_all(&self.db_pool)
+        .await?;
+        for media in expired {
+            if let Err(e) = self.storage.delete_object(&media.object_key).await {
+                warn!(media_id = %media.id, error = %e, "Failed to delete expired media");
+                continue;
+            }
+            self.set_status(media.id, MediaStatus::Ready, MediaStatus::Expired, None)
+                .await?;
+            info!(media_id = %media.id, "Media expired");
+        }
+        Ok(())
+    }
+
+    // ========================================================================
+    // HELPERS
+    // ========================================================================
+
+    /// Verify an assembled upload. Storage or scanner failures leave it
+    /// `processing` for the sweep to retry.
+    async fn process(&self, media_id: Uuid) {
+        if let Err(e) = self.try_process(media_id).await {
+            warn!(media_id = %media_id, error = %e, "Media processing failed; will retry");
+        }
+    }
+
+    async fn try_process(&self, media_id: Uuid) -> Result<(), MediaError> {
+        let media = self.find(media_id).await?;
+        if media.status != MediaStatus::Processing {
+            return Ok(());
+        }
+
+        let (sha256, size_bytes) = self.hash_object(&media.object_key).await?;
+        if sha256 != media.sha256 || size_bytes != media.size_bytes {
+            return self
+                .reject(
+                    &media,
+                    "Uploaded content does not match its declared SHA-256",
+                )
+                .await;
+        }
+        if let Some(scanner) = &self.scanner {
+            let request = ScanRequest {
+                url: self.signed_url(&media.object_key).url,
+                sha256,
+                content_type: media.content_type.clone(),
+                size_bytes,
+            };
+            if let ScanVerdict::Infected(threat) =
+                scanner.scan(&request).await.map_err(MediaError::Scanner)?
+            {
+                return self
+                    .reject(&media, &format!("Malware detected: {}", threat))
+                    .await;
+            }
+        }
+
+        sqlx::query(
+            r#"
+            UPDATE media_objects SET status = $2, retain_until = $3, updated_at = NOW()
+            WHERE id = $1 AND status = $4
+            "#,
+        )
+        .bind(media_id)
+        .bind(MediaStatus::Ready)
+        .bind(Utc::now() + chrono::Duration::days(self.retention_days))
+        .bind(MediaStatus::Processing)
+        .execute(&self.db_pool)
+        .await?;
+        info!(media_id = %media_id, "Media verified");
+        Ok(())
+    }
+
+    async fn reject(&self, media: &MediaObject, reason: &str) -> Result<(), MediaError> {
+        warn!(media_id = %media.id, reason, "Media rejected");
+        self.storage.delete_object(&media.object_key).await?;
+        self.set_status(
+            media.id,
+            MediaStatus::Processing,
+            MediaStatus::Rejected,
+            Some(reason),
+        )
+        .await
+    }
+
+    async fn set_status(
+        &self,
+        media_id: Uuid,
+        from: MediaStatus,
+        to: MediaStatus,
+        reason: Option<&str>,
+    ) -> Result<(), MediaError> {
+        sqlx::query(
+            r#"
+            UPDATE media_objects
+            SET status = $3, rejection_reason = COALESCE($4, rejection_reason), updated_at = NOW()
+            WHERE id = $1 AND status = $2
+            "#,
+        )
+        .bind(media_id)
+        .bind(from)
+        .bind(to)
+        .bind(reason)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    /// SHA-256 and size of a stored object, streamed from storage.
+    async fn hash_object(&self, key: &str) -> Result<(String, i64), MediaError> {
+        let mut response = self.storage.get_object(key).await?;
+        let mut hasher = Sha256::new();
+        let mut size_bytes = 0i64;
+        while let Some(chunk) = response.chunk().await.map_err(StorageError::from)? {
+            size_bytes += chunk.len() as i64;
+            hasher.update(&chunk);
+        }
+        Ok((hex::encode(hasher.finalize()), size_bytes))
+    }
+
+    fn signed_url(&self, key: &str) -> SignedMediaUrl {
+        SignedMediaUrl {
+            url: self.storage.presigned_get_url(key, self.download_ttl),
+            expires_at: Utc::now() + chrono::Duration::seconds(self.download_ttl.as_secs() as i64),
+        }
+    }
+
+    async fn find(&self, media_id: Uuid) -> Result<MediaObject, MediaError> {
+        sqlx::query_as::<_, MediaObject>("SELECT * FROM media_objects WHERE id = $1")
+            .bind(media_id)
+            .fetch_optional(&self.db_pool)
+            .await?
+            .ok_or(MediaError::NotFound)
+    }
+
+    async fn owned(&self, user_id: Uuid, media_id: Uuid) -> Result<MediaObject, MediaError> {
+        let media = self.find(media_id).await?;
+        if media.owner_id != user_id {
+            return Err(MediaError::Forbidden);
+        }
+        Ok(media)
+    }
+}
+
+/// Upload ID of an upload still accepting parts.
+fn in_progress(media: &MediaObject) -> Result<&str, MediaError> {
+    match (media.status, &media.upload_id) {
+        (MediaStatus::Uploading, Some(upload_id)) => Ok(upload_id),
+        _ => Err(MediaError::Conflict(
+            "Upload is already complete".to_string(),
+        )),
+    }
+}
+
+fn is_sha256_hex(value: &str) -> bool {
+    value.len() == 64 && value.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// The evidence reference recomputed from the files it was built from, in
+/// its order. `None` without a reference, or when one of those files is
+/// missing or altered in storage.
+fn recompute_ref(on_chain_hashes: &[String], files: &[EvidenceFileCheck]) -> Option<String> {
+    if on_chain_hashes.is_empty() {
+        return None;
+    }
+    let mut hashes = Vec::with_capacity(on_chain_hashes.len());
+    for hash in on_chain_hashes {
+        let file = files.iter().find(|f| &f.sha256 == hash)?;
+        hashes.push(file.stored_sha256.as_deref()?);
+    }
+    Some(evidence_hash(&hashes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(sha256: &str, stored_sha256: Option<&str>) -> EvidenceFileCheck {
+        EvidenceFileCheck {
+            evidence_id: Uuid::new_v4(),
+            uploaded_by: Uuid::new_v4(),
+            kind: EvidenceKind::Replay,
+            sha256: sha256.to_string(),
+            stored_sha256: stored_sha256.map(str::to_string),
+            intact: stored_sha256 == Some(sha256),
+            on_chain: true,
+            download: SignedMediaUrl {
+                url: String::new(),
+                expires_at: Utc::now(),
+            },
+        }
+    }
+
+    #[test]
+    fn test_recompute_ref_uses_stored_hashes_in_chain_order() {
+        let (a, b) = ("a".repeat(64), "b".repeat(64));
+        let chain = vec![a.clone(), b.clone()];
+        let files = vec![check(&b, Some(&b)), check(&a, Some(&a))];
+        assert_eq!(
+            recompute_ref(&chain, &files),
+            Some(evidence_hash(&[a.as_str(), b.as_str()]))
+        );
+
+        // An altered file no longer reproduces the reference.
+        let tampered = vec![check(&a, Some(&"c".repeat(64))), check(&b, Some(&b))];
+        assert_ne!(
+            recompute_ref(&chain, &tampered),
+            Some(evidence_hash(&[a.as_str(), b.as_str()]))
+        );
+
+        // A missing file cannot be checked at all.
+        assert_eq!(recompute_ref(&chain, &[check(&a, None)]), None);
+        assert_eq!(recompute_ref(&[], &files), None);
+    }
+
+    #[test]
+    fn test_part_layout() {
+        let mut media = MediaObject {
+            id: Uuid::new_v4(),
+            owner_id: Uuid::new_v4(),
+            kind: EvidenceKind::Replay,
+            content_type: "application/octet-stream".to_string(),
+            size_bytes: 2 * MIN_PART_BYTES + 1,
+            sha256: "a".repeat(64),
+            object_key: "media/key".to_string(),
+            upload_id: Some("upload".to_string()),
+            part_size: MIN_PART_BYTES,
+            status: MediaStatus::Uploading,
+            rejection_reason: None,
+            match_id: None,
+            dispute_id: None,
+            retain_until: None,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            completed_at: None,
+        };
+        assert_eq!(media.part_count(), 3);
+        assert_eq!(media.part_len(1), MIN_PART_BYTES);
+        assert_eq!(media.part_len(3), 1);
+        assert_eq!(in_progress(&media).unwrap(), "upload");
+
+        media.size_bytes = 10;
+        assert_eq!(media.part_count(), 1);
+        assert_eq!(media.part_len(1), 10);
+
+        media.status = MediaStatus::Processing;
+        assert!(in_progress(&media).is_err());
+        assert!(is_sha256_hex(&"aF".repeat(32)));
+        assert!(!is_sha256_hex(&"g".repeat(64)));
+    }
+}
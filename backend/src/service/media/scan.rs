@@ -0,0 +1,101 @@
+//! Hook scanning uploads for malware before they become usable.
+//!
+//! Scanners are handed a signed URL to fetch the object from, so large
+//! replays are never buffered in the backend. A scanner error leaves the
+//! upload pending; it is scanned again on the next sweep.
+
+use futures::future::BoxFuture;
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+
+const SCAN_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// An uploaded object to scan.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanRequest {
+    /// Signed URL the object can be downloaded from.
+    pub url: String,
+    pub sha256: String,
+    pub content_type: String,
+    pub size_bytes: i64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScanVerdict {
+    Clean,
+    /// Malware was found; holds the threat name.
+    Infected(String),
+}
+
+pub trait MediaScanner: Send + Sync {
+    fn scan<'a>(&'a self, request: &'a ScanRequest) -> BoxFuture<'a, Result<ScanVerdict, String>>;
+}
+
+/// Scans through an HTTP service: the [`ScanRequest`] is POSTed as JSON and
+/// the service answers `{"clean": bool, "threat": "..."}`.
+pub struct HttpScanner {
+    client: Client,
+    url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct ScanResponse {
+    clean: bool,
+    threat: Option<String>,
+}
+
+impl HttpScanner {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            client: Client::new(),
+            url: url.into(),
+        }
+    }
+}
+
+impl MediaScanner for HttpScanner {
+    fn scan<'a>(&'a self, request: &'a ScanRequest) -> BoxFuture<'a, Result<ScanVerdict, String>> {
+        Box::pin(async move {
+            let response = self
+                .client
+                .post(&self.url)
+                .timeout(SCAN_TIMEOUT)
+                .json(request)
+                .send()
+                .await
+                .map_err(|e| e.to_string())?;
+            if !response.status().is_success() {
+                return Err(format!("Scanner returned {}", response.status()));
+            }
+            let result: ScanResponse = response.json().await.map_err(|e| e.to_string())?;
+            Ok(verdict(result))
+        })
+    }
+}
+
+fn verdict(response: ScanResponse) -> ScanVerdict {
+    if response.clean {
+        ScanVerdict::Clean
+    } else {
+        ScanVerdict::Infected(response.threat.unwrap_or_else(|| "unknown".to_string()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanner_response_verdict() {
+        let clean: ScanResponse = serde_json::from_str(r#"{"clean": true}"#).unwrap();
+        assert_eq!(verdict(clean), ScanVerdict::Clean);
+
+        let infected: ScanResponse =
+            serde_json::from_str(r#"{"clean": false, "threat": "Eicar-Signature"}"#).unwrap();
+        assert_eq!(
+            verdict(infected),
+            ScanVerdict::Infected("Eicar-Signature".to_string())
+        );
+    }
+}
@@ -1,44 +1,104 @@
 // Service layer module for ArenaX
 pub mod achievement_service;
+pub mod admin_service;
 pub mod analytics_service;
+pub mod anchor;
+pub mod anticheat;
+pub mod audit_log;
 pub mod auth_service;
+pub mod bracket_engine;
+pub mod chain_indexer;
+pub mod chat;
+pub mod dispute_service;
+pub mod domain_events;
+pub mod friend_service;
 pub mod governance_service;
+pub mod health_service;
+pub mod horizon_stream;
 pub mod idempotency_service;
+pub mod kyc;
 pub mod leaderboard_service;
+pub mod ledger;
+pub mod mailer;
+pub mod media;
 pub mod match_authority_service;
+pub mod match_result_service;
 pub mod match_service;
 pub mod match_service_background;
 pub mod reaper_service;
+pub mod registration_service;
 pub mod matchmaker;
+pub mod matchmaking;
+pub mod notification_service;
+pub mod object_storage;
+pub mod payment_service;
+pub mod profile_service;
+pub mod push;
 pub mod reputation_service;
 pub mod reward_settlement_service;
+pub mod search;
+pub mod sms;
 pub mod social_service;
+pub mod spectator_service;
 pub mod soroban_service;
 pub mod staking_service;
+pub mod stellar_relayer;
 pub mod stellar_service;
+pub mod team_service;
 pub mod tournament_service;
 pub mod user_service;
 pub mod wallet_service;
+pub mod webhooks;
 
 pub use governance_service::{
     CreateProposalDto, GovernanceService, GovernanceServiceError, ProposalRecord,
     ProposalStatus as GovProposalStatus,
 };
 pub use achievement_service::AchievementService;
+pub use admin_service::AdminService;
+pub use anticheat::{AntiCheatError, AntiCheatService};
+pub use audit_log::AuditLogService;
+pub use bracket_engine::BracketEngine;
+pub use registration_service::RegistrationService;
+pub use chain_indexer::{ChainIndexer, ChainIndexerConfig, ChainIndexerError};
+pub use chat::{ChatError, ChatService};
+pub use dispute_service::DisputeService;
+pub use domain_events::{
+    DomainEvent, DomainEventHandler, EventBroker, EventConsumer, OutboxDispatcher,
+};
+pub use friend_service::{FriendError, FriendService};
 pub use idempotency_service::IdempotencyService;
+pub use kyc::{KycError, KycService};
 pub use leaderboard_service::LeaderboardService;
+pub use mailer::{EmailTemplate, MailError, Mailer};
+pub use media::{MediaError, MediaService};
 pub use match_authority_service::MatchAuthorityService;
+pub use match_result_service::MatchResultService;
 pub use match_service::MatchService;
 pub use reaper_service::ReaperService;
 pub use matchmaker::{MatchmakerService, EloEngine, MatchmakingConfig};
+pub use matchmaking::{MatchmakingService, StakeTier};
+pub use notification_service::{
+    NotificationError, NotificationService, NotificationTemplate, RenderedNotification,
+};
+pub use object_storage::{ObjectStorage, StorageError};
+pub use payment_service::PaymentService;
+pub use profile_service::ProfileService;
+pub use push::{PushError, PushGateway, PushMessage};
 pub use reputation_service::{PlayerReputation, ReputationService, ReputationTier};
+pub use search::{SearchError, SearchService};
+pub use sms::{SmsChannel, SmsError, SmsGateway};
 pub use social_service::SocialService;
+pub use spectator_service::SpectatorService;
 pub use soroban_service::{
     DecodedEvent, NetworkConfig, RetryConfig, SorobanError, SorobanService, SorobanTxResult,
     TxStatus,
 };
+pub use stellar_relayer::{RelayerConfig, RelayerError, RelayerReceipt, StellarRelayer};
 pub use stellar_service::StellarService;
+pub use team_service::{TeamError, TeamService};
 pub use tournament_service::TournamentService;
 pub use user_service::UserService;
 pub use wallet_service::WalletService;
+pub use webhooks::{WebhookError, WebhookEvent, WebhookService};
 pub use crate::realtime::event_bus::EventBus;
@@ -0,0 +1,959 @@
+//! Notifications by email, push and in the app.
+//!
+//! Services call [`NotificationService::notify`] with a
+//! [`NotificationTemplate`]. The rendered notification goes to the user's
+//! inbox right away and is published on their realtime channel; email and
+//! push deliveries are put on a Redis queue drained by
+//! [`NotificationService::run`], which retries failed ones with exponential
+//! backoff. Which channels a notification uses follows the user's
+//! [`NotificationPreferences`] for its category.
+//!
+//! Settled matches, completed payments and slashed stakes are not notified by
+//! the services making them; the service follows the domain event stream for
+//! those, see [`crate::service::domain_events`].
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{
+    ChannelPreferences, Notification, NotificationCategory, NotificationChannel,
+    NotificationPreferences, PushPlatform, UpdateNotificationPreferencesRequest,
+};
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
+use crate::service::chain_indexer::platform_id;
+use crate::service::domain_events::{DomainEvent, DomainEventEnvelope, DomainEventHandler};
+use crate::service::mailer::{EmailTemplate, MailError, Mailer};
+use crate::service::push::{PushError, PushGateway, PushMessage};
+use crate::telemetry::propagation::{self, TraceCarrier};
+use chrono::Utc;
+use futures::future::BoxFuture;
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{error, info, info_span, warn, Instrument};
+use uuid::Uuid;
+
+/// Sorted set of pending deliveries scored by when they are due (ms).
+pub(crate) const QUEUE_KEY: &str = "notifications:queue";
+/// Deliveries given up on, newest first.
+const DEAD_LETTER_KEY: &str = "notifications:dead";
+const DEAD_LETTER_CAP: isize = 1000;
+const POLL_INTERVAL_SECS: u64 = 2;
+const BATCH_SIZE: isize = 50;
+const RETRY_BASE_SECS: u64 = 30;
+const RETRY_MAX_SECS: u64 = 3600;
+const DEFAULT_MAX_ATTEMPTS: u32 = 5;
+
+const NOTIFICATION_COLUMNS: &str =
+    "id, user_id, type, category, title, message, link, link_label, read, read_at, created_at";
+
+#[derive(Debug, Error)]
+pub enum NotificationError {
+    #[error("Notification not found")]
+    NotFound,
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Redis error: {0}")]
+    Redis(#[from] redis::RedisError),
+    #[error("Invalid queued delivery: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+impl From<NotificationError> for ApiError {
+    fn from(err: NotificationError) -> Self {
+        match err {
+            NotificationError::NotFound => ApiError::NotFound,
+            NotificationError::DatabaseError(e) => ApiError::DatabaseError(e),
+            NotificationError::Redis(e) => ApiError::RedisError(e.to_string()),
+            NotificationError::Serialization(_) => ApiError::internal_error(err.to_string()),
+        }
+    }
+}
+
+/// A notification and what it needs to be rendered.
+#[derive(Debug, Clone)]
+pub enum NotificationTemplate {
+    NewSignIn {
+        device: String,
+        location: Option<String>,
+    },
+    DepositReceived {
+        amount: String,
+        asset: String,
+    },
+    WithdrawalCompleted {
+        amount: String,
+        asset: String,
+    },
+    MatchFound {
+        match_id: Uuid,
+        opponent: String,
+        game_mode: String,
+    },
+    MatchResult {
+        match_id: Uuid,
+        won: bool,
+        elo_change: i32,
+    },
+    /// The match was settled on chain; `won` is unknown without a winner.
+    MatchSettled {
+        match_id: Uuid,
+        won: Option<bool>,
+    },
+    PaymentCompleted {
+        amount: String,
+        currency: String,
+    },
+    StakeSlashed {
+        tournament_id: Option<Uuid>,
+    },
+    TournamentStarting {
+        tournament_id: Uuid,
+        name: String,
+    },
+    FriendRequest {
+        from_username: String,
+    },
+    KycReviewed {
+        approved: bool,
+        reason: Option<String>,
+    },
+    /// Already-written text, e.g. an announcement.
+    Custom(RenderedNotification),
+}
+
+/// A rendered notification, as stored in the inbox and sent to each channel.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RenderedNotification {
+    pub category: NotificationCategory,
+    /// e.g. `match_found`.
+    pub kind: String,
+    pub title: String,
+    pub message: String,
+    /// App path or URL the notification opens.
+    pub link: Option<String>,
+    pub link_label: Option<String>,
+}
+
+impl NotificationTemplate {
+    pub fn render(&self) -> RenderedNotification {
+        let (category, kind, title, message, link) = match self {
+            NotificationTemplate::NewSignIn { device, location } => (
+                NotificationCategory::Security,
+                "new_sign_in",
+                "New sign-in to your account".to_string(),
+                match location {
+                    Some(location) => format!(
+                        "Your account was signed in to from {} near {}. \
+                         If this wasn't you, reset your password.",
+                        device, location
+                    ),
+                    None => format!(
+                        "Your account was signed in to from {}. \
+                         If this wasn't you, reset your password.",
+                        device
+                    ),
+                },
+                Some(("/settings/security".to_string(), "Review devices")),
+            ),
+            NotificationTemplate::DepositReceived { amount, asset } => (
+                NotificationCategory::Wallet,
+                "deposit_received",
+                "Deposit received".to_string(),
+                format!("{} {} was added to your wallet.", amount, asset),
+                Some(("/wallet".to_string(), "View wallet")),
+            ),
+            NotificationTemplate::WithdrawalCompleted { amount, asset } => (
+                NotificationCategory::Wallet,
+                "withdrawal_completed",
+                "Withdrawal sent".to_string(),
+                format!("Your withdrawal of {} {} was sent.", amount, asset),
+                Some(("/wallet".to_string(), "View wallet")),
+            ),
+            NotificationTemplate::MatchFound {
+                match_id,
+                opponent,
+                game_mode,
+            } => (
+                NotificationCategory::Match,
+                "match_found",
+                "Match found".to_string(),
+                format!("You were matched against {} in {}.", opponent, game_mode),
+                Some((format!("/matches/{}", match_id), "Go to match")),
+            ),
+            NotificationTemplate::MatchResult {
+                match_id,
+                won,
+                elo_change,
+            } => (
+                NotificationCategory::Match,
+                "match_result",
+                if *won { "Victory" } else { "Defeat" }.to_string(),
+                format!("Your match is over. Rating change: {:+}.", elo_change),
+                Some((format!("/matches/{}", match_id), "View match")),
+            ),
+            NotificationTemplate::MatchSettled { match_id, won } => (
+                NotificationCategory::Match,
+                "match_settled",
+                "Match settled".to_string(),
+                if *won == Some(true) {
+                    "Your match was settled on chain and your winnings were released."
+                } else {
+                    "Your match was settled on chain."
+                }
+                .to_string(),
+                Some((format!("/matches/{}", match_id), "View match")),
+            ),
+            NotificationTemplate::PaymentCompleted { amount, currency } => (
+                NotificationCategory::Wallet,
+                "payment_completed",
+                "Payment completed".to_string(),
+                format!("Your payment of {} {} went through.", amount, currency),
+                Some(("/wallet".to_string(), "View wallet")),
+            ),
+            NotificationTemplate::StakeSlashed { tournament_id } => (
+                NotificationCategory::Wallet,
+                "stake_slashed",
+                "Stake slashed".to_string(),
+                "Part of your stake was slashed by the contract holding it.".to_string(),
+                Some(match tournament_id {
+                    Some(id) => (format!("/tournaments/{}", id), "View tournament"),
+                    None => ("/wallet".to_string(), "View wallet"),
+                }),
+            ),
+            NotificationTemplate::TournamentStarting {
+                tournament_id,
+                name,
+            } => (
+                NotificationCategory::Tournament,
+                "tournament_starting",
+                "Tournament starting".to_string(),
+                format!("{} is about to start. Check in to keep your spot.", name),
+                Some((format!("/tournaments/{}", tournament_id), "View bracket")),
+            ),
+            NotificationTemplate::FriendRequest { from_username } => (
+                NotificationCategory::Social,
+                "friend_request",
+                "New friend request".to_string(),
+                format!("{} wants to be your friend.", from_username),
+                Some(("/friends".to_string(), "View requests")),
+            ),
+            NotificationTemplate::KycReviewed { approved, reason } => (
+                NotificationCategory::Security,
+                "kyc_reviewed",
+                if *approved {
+                    "Identity verified"
+                } else {
+                    "Identity verification declined"
+                }
+                .to_string(),
+                match (approved, reason) {
+                    (true, _) => {
+                        "Your identity was verified and your limits were raised.".to_string()
+                    }
+                    (false, Some(reason)) => {
+                        format!("We couldn't verify your identity: {}", reason)
+                    }
+                    (false, None) => "We couldn't verify your identity.".to_string(),
+                },
+                Some(("/settings/verification".to_string(), "View status")),
+            ),
+            NotificationTemplate::Custom(rendered) => return rendered.clone(),
+        };
+
+        let (link, link_label) = match link {
+            Some((link, label)) => (Some(link), Some(label.to_string())),
+            None => (None, None),
+        };
+        RenderedNotification {
+            category,
+            kind: kind.to_string(),
+            title,
+            message,
+            link,
+            link_label,
+        }
+    }
+}
+
+/// A queued email or push delivery.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct Delivery {
+    /// Keeps identical deliveries apart in the queue.
+    id: Uuid,
+    user_id: Uuid,
+    channel: NotificationChannel,
+    notification: RenderedNotification,
+    /// Failed attempts so far.
+    attempts: u32,
+    /// Push tokens still to reach; all of the user's when `None`.
+    tokens: Option<Vec<String>>,
+    /// Trace of the request that queued it; delivery continues that trace.
+    #[serde(default)]
+    trace: TraceCarrier,
+}
+
+/// `amount` minor units of `currency` in major units, e.g. 15000000 stroops
+/// as `1.5`.
+fn format_minor_units(amount: i64, currency: &str) -> String {
+    let decimals = match currency {
+        "XLM" => 7,
+        _ => 2,
+    };
+    let scale = 10u64.pow(decimals);
+    let sign = if amount < 0 { "-" } else { "" };
+    let units = amount.unsigned_abs();
+    let whole = units / scale;
+    let fraction = format!("{:0width$}", units % scale, width = decimals as usize);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        format!("{}{}", sign, whole)
+    } else {
+        format!("{}{}.{}", sign, whole, fraction)
+    }
+}
+
+/// Wait before the next attempt after `attempts` failed ones.
+fn retry_delay(attempts: u32) -> Duration {
+    let factor = 1u64 << attempts.saturating_sub(1).min(16);
+    Duration::from_secs(RETRY_BASE_SECS.saturating_mul(factor).min(RETRY_MAX_SECS))
+}
+
+pub struct NotificationService {
+    db_pool: DbPool,
+    redis: ConnectionManager,
+    mailer: Arc<Mailer>,
+    push: Arc<PushGateway>,
+    /// Base of links in emails.
+    app_url: String,
+    event_bus: Option<EventBus>,
+    max_attempts: u32,
+}
+
+impl NotificationService {
+    pub fn new(
+        db_pool: DbPool,
+        redis: ConnectionManager,
+        mailer: Arc<Mailer>,
+        push: Arc<PushGateway>,
+        app_url: String,
+    ) -> Self {
+        Self {
+            db_pool,
+            redis,
+            mailer,
+            push,
+            app_url,
+            event_bus: None,
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+        }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Notify `user_id` on the channels they chose for the template's
+    /// category. Returns the inbox entry, if the in-app channel is on.
+    pub async fn notify(
+        &self,
+        user_id: Uuid,
+        template: &NotificationTemplate,
+    ) -> Result<Option<Notification>, NotificationError> {
+        let notification = template.render();
+        let channels = self
+            .preferences(user_id)
+            .await?
+            .channels(notification.category);
+
+        let stored = if channels.in_app {
+            Some(self.create(user_id, &notification).await?)
+        } else {
+            None
+        };
+        for channel in [NotificationChannel::Email, NotificationChannel::Push] {
+            if channels.allows(channel) {
+                let delivery = Delivery {
+                    id: Uuid::new_v4(),
+                    user_id,
+                    channel,
+                    notification: notification.clone(),
+                    attempts: 0,
+                    tokens: None,
+                    trace: propagation::current_carrier(),
+                };
+                self.enqueue(&delivery, Duration::ZERO).await?;
+            }
+        }
+        Ok(stored)
+    }
+
+    /// Put `notification` in the inbox of `user_id` only.
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        notification: &RenderedNotification,
+    ) -> Result<Notification, NotificationError> {
+        let stored = sqlx::query_as::<_, Notification>(&format!(
+            "INSERT INTO notifications (user_id, type, category, title, message, link, link_label) \
+             VALUES ($1, $2, $3, $4, $5, $6, $7) \
+             RETURNING {}",
+            NOTIFICATION_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(&notification.kind)
+        .bind(notification.category)
+        .bind(&notification.title)
+        .bind(&notification.message)
+        .bind(&notification.link)
+        .bind(&notification.link_label)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        if let Some(event_bus) = &self.event_bus {
+            let event = RealtimeEvent::Notification {
+                id: stored.id,
+                title: stored.title.clone(),
+                body: stored.message.clone(),
+                category: stored.category.as_str().to_string(),
+                timestamp: stored.created_at.to_rfc3339(),
+            };
+            event_bus.publish_to_user(user_id, &event).await;
+        }
+        Ok(stored)
+    }
+
+    /// A page of the inbox, newest first, with the total and unread counts.
+    pub async fn inbox(
+        &self,
+        user_id: Uuid,
+        unread_only: bool,
+        limit: i64,
+        offset: i64,
+    ) -> Result<(Vec<Notification>, i64, i64), NotificationError> {
+        let notifications = sqlx::query_as::<_, Notification>(&format!(
+            "SELECT {} FROM notifications \
+             WHERE user_id = $1 AND (NOT $2 OR read = FALSE) \
+             ORDER BY created_at DESC \
+             LIMIT $3 OFFSET $4",
+            NOTIFICATION_COLUMNS
+        ))
+        .bind(user_id)
+        .bind(unread_only)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let (total, unread): (i64, i64) = sqlx::query_as(
+            "SELECT COUNT(*) FILTER (WHERE NOT $2 OR read = FALSE), \
+                    COUNT(*) FILTER (WHERE read = FALSE) \
+             FROM notifications WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .bind(unread_only)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok((notifications, total, unread))
+    }
+
+    pub async fn mark_read(&self, user_id: Uuid, id: Uuid) -> Result<(), NotificationError> {
+        let result = sqlx::query(
+            "UPDATE notifications SET read = TRUE, read_at = COALESCE(read_at, NOW()) \
+             WHERE id = $1 AND user_id = $2",
+        )
+        .bind(id)
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(NotificationError::NotFound);
+        }
+        Ok(())
+    }
+
+    /// Mark every unread notification read; returns how many there were.
+    pub async fn mark_all_read(&self, user_id: Uuid) -> Result<u64, NotificationError> {
+        let result = sqlx::query(
+            "UPDATE notifications SET read = TRUE, read_at = NOW() \
+             WHERE user_id = $1 AND read = FALSE",
+        )
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    pub async fn delete(&self, user_id: Uuid, id: Uuid) -> Result<(), NotificationError> {
+        let result = sqlx::query("DELETE FROM notifications WHERE id = $1 AND user_id = $2")
+            .bind(id)
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(NotificationError::NotFound);
+        }
+        Ok(())
+    }
+
+    /// The user's channels per category, defaults included.
+    pub async fn preferences(
+        &self,
+        user_id: Uuid,
+    ) -> Result<NotificationPreferences, NotificationError> {
+        let rows = sqlx::query_as::<_, (NotificationCategory, bool, bool, bool)>(
+            "SELECT category, email, push, in_app FROM notification_preferences \
+             WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        let mut preferences = NotificationPreferences::default();
+        for (category, email, push, in_app) in rows {
+            preferences.categories.insert(
+                category,
+                ChannelPreferences {
+                    email,
+                    push,
+                    in_app,
+                },
+            );
+        }
+        Ok(preferences)
+    }
+
+    pub async fn update_preferences(
+        &self,
+        user_id: Uuid,
+        request: &UpdateNotificationPreferencesRequest,
+    ) -> Result<NotificationPreferences, NotificationError> {
+        let mut tx = self.db_pool.begin().await?;
+        for (category, channels) in &request.categories {
+            sqlx::query(
+                "INSERT INTO notification_preferences (user_id, category, email, push, in_app) \
+                 VALUES ($1, $2, $3, $4, $5) \
+                 ON CONFLICT (user_id, category) DO UPDATE \
+                 SET email = EXCLUDED.email, push = EXCLUDED.push, in_app = EXCLUDED.in_app, \
+                     updated_at = NOW()",
+            )
+            .bind(user_id)
+            .bind(category)
+            .bind(channels.email)
+            .bind(channels.push)
+            .bind(channels.in_app)
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit().await?;
+
+        self.preferences(user_id).await
+    }
+
+    /// Register a device for push. A token registered by another account
+    /// moves to this one, as the device has changed hands.
+    pub async fn register_push_token(
+        &self,
+        user_id: Uuid,
+        token: &str,
+        platform: PushPlatform,
+    ) -> Result<(), NotificationError> {
+        sqlx::query(
+            "INSERT INTO push_tokens (token, user_id, platform) VALUES ($1, $2, $3) \
+             ON CONFLICT (token) DO UPDATE \
+             SET user_id = EXCLUDED.user_id, platform = EXCLUDED.platform, last_used_at = NOW()",
+        )
+        .bind(token)
+        .bind(user_id)
+        .bind(platform)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(())
+    }
+
+    pub async fn remove_push_token(
+        &self,
+        user_id: Uuid,
+        token: &str,
+    ) -> Result<(), NotificationError> {
+        sqlx::query("DELETE FROM push_tokens WHERE token = $1 AND user_id = $2")
+            .bind(token)
+            .bind(user_id)
+            .execute(&self.db_pool)
+            .await?;
+        Ok(())
+    }
+
+    // ========================================================================
+    // DELIVERY QUEUE
+    // ========================================================================
+
+    /// Spawn the worker delivering queued email and push notifications.
+    /// Several instances can share the queue.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!(
+                max_attempts = self.max_attempts,
+                "Notification delivery worker started"
+            );
+            let mut ticker = tokio::time::interval(Duration::from_secs(POLL_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.deliver_due().await {
+                    error!(error = %e, "Notification delivery tick failed");
+                }
+            }
+        });
+    }
+
+    async fn enqueue(&self, delivery: &Delivery, delay: Duration) -> Result<(), NotificationError> {
+        let due = Utc::now().timestamp_millis() + delay.as_millis() as i64;
+        let mut conn = self.redis.clone();
+        conn.zadd::<_, _, _, ()>(QUEUE_KEY, serde_json::to_string(delivery)?, due)
+            .await?;
+        Ok(())
+    }
+
+    async fn deliver_due(&self) -> Result<(), NotificationError> {
+        let mut conn = self.redis.clone();
+        let now = Utc::now().timestamp_millis();
+        let due: Vec<String> = conn
+            .zrangebyscore_limit(QUEUE_KEY, "-inf", now, 0, BATCH_SIZE)
+            .await?;
+
+        for payload in due {
+            // Whoever removes an entry delivers it.
+            let claimed: i64 = conn.zrem(QUEUE_KEY, &payload).await?;
+            if claimed == 0 {
+                continue;
+            }
+            let delivery: Delivery = match serde_json::from_str(&payload) {
+                Ok(delivery) => delivery,
+                Err(e) => {
+                    warn!(error = %e, "Dropping unreadable notification delivery");
+                    continue;
+                }
+            };
+            let span = info_span!(
+                "notification.deliver",
+                otel.kind = "consumer",
+                channel = ?delivery.channel,
+                delivery_id = %delivery.id,
+                attempts = delivery.attempts,
+            );
+            propagation::continue_from(&span, &delivery.trace);
+            match delivery.channel {
+                NotificationChannel::Email => self.deliver_email(delivery).instrument(span).await?,
+                NotificationChannel::Push => self.deliver_push(delivery).instrument(span).await?,
+                NotificationChannel::InApp => {}
+            }
+        }
+        Ok(())
+    }
+
+    async fn deliver_email(&self, delivery: Delivery) -> Result<(), NotificationError> {
+        let recipient = sqlx::query_as::<_, (String, String)>(
+            "SELECT email, username FROM users WHERE id = $1",
+        )
+        .bind(delivery.user_id)
+        .fetch_optional(&self.db_pool)
+        .await?;
+        let Some((email, username)) = recipient else {
+            return Ok(());
+        };
+
+        let notification = &delivery.notification;
+        let action = notification.link.as_ref().map(|link| {
+            let label = notification
+                .link_label
+                .clone()
+                .unwrap_or_else(|| "Open ArenaX".to_string());
+            let url = if link.starts_with('/') {
+                format!("{}{}", self.app_url, link)
+            } else {
+                link.clone()
+            };
+            (label, url)
+        });
+        let template = EmailTemplate::Notification {
+            username,
+            title: notification.title.clone(),
+            message: notification.message.clone(),
+            action,
+        };
+
+        match self.mailer.send(&email, &template).await {
+            Ok(()) => Ok(()),
+            Err(e @ MailError::InvalidAddress(_)) => {
+                warn!(user_id = %delivery.user_id, error = %e, "Notification email not sent");
+                Ok(())
+            }
+            Err(e) => self.retry(delivery, e.to_string()).await,
+        }
+    }
+
+    async fn deliver_push(&self, mut delivery: Delivery) -> Result<(), NotificationError> {
+        let tokens = match delivery.tokens.take() {
+            Some(tokens) => tokens,
+            None => {
+                sqlx::query_scalar::<_, String>("SELECT token FROM push_tokens WHERE user_id = $1")
+                    .bind(delivery.user_id)
+                    .fetch_all(&self.db_pool)
+                    .await?
+            }
+        };
+
+        let notification = &delivery.notification;
+        let message = PushMessage {
+            title: &notification.title,
+            body: &notification.message,
+            link: notification.link.as_deref(),
+            category: notification.category.as_str(),
+        };
+        let mut failed = Vec::new();
+        let mut last_error = None;
+        for token in tokens {
+            match self.push.send(&token, &message).await {
+                Ok(()) => {}
+                Err(PushError::Unregistered) => {
+                    sqlx::query("DELETE FROM push_tokens WHERE token = $1")
+                        .bind(&token)
+                        .execute(&self.db_pool)
+                        .await?;
+                }
+                Err(e) if e.is_retryable() => {
+                    last_error = Some(e.to_string());
+                    failed.push(token);
+                }
+                Err(e) => {
+                    warn!(user_id = %delivery.user_id, error = %e, "Push notification rejected");
+                }
+            }
+        }
+
+        match last_error {
+            Some(error) => {
+                delivery.tokens = Some(failed);
+                self.retry(delivery, error).await
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Requeue a failed delivery, or give up on it after the last attempt.
+    async fn retry(&self, mut delivery: Delivery, error: String) -> Result<(), NotificationError> {
+        delivery.attempts += 1;
+        if delivery.attempts >= self.max_attempts {
+            warn!(
+                user_id = %delivery.user_id,
+                channel = ?delivery.channel,
+                attempts = delivery.attempts,
+                error = %error,
+                "Giving up on notification delivery"
+            );
+            let mut conn = self.redis.clone();
+            redis::pipe()
+                .lpush(DEAD_LETTER_KEY, serde_json::to_string(&delivery)?)
+                .ltrim(DEAD_LETTER_KEY, 0, DEAD_LETTER_CAP - 1)
+                .query_async::<()>(&mut conn)
+                .await?;
+            return Ok(());
+        }
+
+        let delay = retry_delay(delivery.attempts);
+        warn!(
+            user_id = %delivery.user_id,
+            channel = ?delivery.channel,
+            attempts = delivery.attempts,
+            retry_in_secs = delay.as_secs(),
+            error = %error,
+            "Notification delivery failed"
+        );
+        self.enqueue(&delivery, delay).await
+    }
+}
+
+/// Tells the users concerned about settled matches, completed payments and
+/// slashed stakes.
+impl DomainEventHandler for NotificationService {
+    fn name(&self) -> &'static str {
+        "notifications"
+    }
+
+    fn handle<'a>(&'a self, event: &'a DomainEventEnvelope) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            self.notify_domain_event(&event.event)
+                .await
+                .map_err(|e| e.to_string())
+        })
+    }
+}
+
+impl NotificationService {
+    async fn notify_domain_event(&self, event: &DomainEvent) -> Result<(), NotificationError> {
+        match event {
+            DomainEvent::MatchFinalized {
+                match_id,
+                player_a,
+                player_b,
+                winner,
+                ..
+            } => {
+                for player in [player_a, player_b] {
+                    let template = NotificationTemplate::MatchSettled {
+                        match_id: *match_id,
+                        won: winner.as_ref().map(|winner| winner == player),
+                    };
+                    for user_id in self.users_holding(player).await? {
+                        self.notify(user_id, &template).await?;
+                    }
+                }
+            }
+            DomainEvent::PaymentCompleted {
+                user_id,
+                amount,
+                currency,
+                ..
+            } => {
+                let template = NotificationTemplate::PaymentCompleted {
+                    amount: format_minor_units(*amount, currency),
+                    currency: currency.clone(),
+                };
+                self.notify(*user_id, &template).await?;
+            }
+            DomainEvent::StakeSlashed {
+                account,
+                tournament_id,
+                ..
+            } => {
+                let template = NotificationTemplate::StakeSlashed {
+                    tournament_id: tournament_id.as_deref().and_then(platform_id),
+                };
+                for user_id in self.users_holding(account).await? {
+                    self.notify(user_id, &template).await?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Users with the Stellar account `address`, as their own or a wallet's.
+    async fn users_holding(&self, address: &str) -> Result<Vec<Uuid>, NotificationError> {
+        Ok(sqlx::query_scalar(
+            r#"
+            SELECT user_id FROM wallets WHERE stellar_public_key = $1
+            UNION
+            SELECT id FROM users WHERE stellar_public_key = $1
+            "#,
+        )
+        .bind(address)
+        .fetch_all(&self.db_pool)
+        .await?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_templates() {
+        let match_id = Uuid::new_v4();
+        let rendered = NotificationTemplate::MatchResult {
+            match_id,
+            won: true,
+            elo_change: 12,
+        }
+        .render();
+        assert_eq!(rendered.category, NotificationCategory::Match);
+        assert_eq!(rendered.kind, "match_result");
+        assert_eq!(rendered.title, "Victory");
+        assert_eq!(rendered.message, "Your match is over. Rating change: +12.");
+        assert_eq!(rendered.link, Some(format!("/matches/{}", match_id)));
+        assert_eq!(rendered.link_label.as_deref(), Some("View match"));
+
+        let declined = NotificationTemplate::KycReviewed {
+            approved: false,
+            reason: Some("Document expired".to_string()),
+        }
+        .render();
+        assert_eq!(declined.category, NotificationCategory::Security);
+        assert_eq!(
+            declined.message,
+            "We couldn't verify your identity: Document expired"
+        );
+
+        let custom = RenderedNotification {
+            category: NotificationCategory::System,
+            kind: "announcement".to_string(),
+            title: "Maintenance".to_string(),
+            message: "Back in an hour.".to_string(),
+            link: None,
+            link_label: None,
+        };
+        assert_eq!(
+            NotificationTemplate::Custom(custom.clone()).render(),
+            custom
+        );
+    }
+
+    #[test]
+    fn test_preferences_and_retry_delay() {
+        let mut preferences = NotificationPreferences::default();
+        let social = preferences.channels(NotificationCategory::Social);
+        assert!(social.in_app && social.push && !social.email);
+        assert!(!preferences.channels(NotificationCategory::System).push);
+
+        // Security notifications can't be turned off entirely.
+        preferences.categories.insert(
+            NotificationCategory::Security,
+            ChannelPreferences {
+                email: false,
+                push: false,
+                in_app: false,
+            },
+        );
+        let security = preferences.channels(NotificationCategory::Security);
+        assert!(security.email && security.in_app && !security.push);
+
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(3), Duration::from_secs(120));
+        assert_eq!(retry_delay(20), Duration::from_secs(RETRY_MAX_SECS));
+    }
+
+    #[test]
+    fn test_domain_event_templates() {
+        assert_eq!(format_minor_units(15_000_000, "XLM"), "1.5");
+        assert_eq!(format_minor_units(50_000, "NGN"), "500");
+        assert_eq!(format_minor_units(-1_234, "NGN"), "-12.34");
+
+        let tournament_id = Uuid::new_v4();
+        let slashed = NotificationTemplate::StakeSlashed {
+            tournament_id: Some(tournament_id),
+        }
+        .render();
+        assert_eq!(slashed.category, NotificationCategory::Wallet);
+        assert_eq!(
+            slashed.link,
+            Some(format!("/tournaments/{}", tournament_id))
+        );
+
+        let settled = NotificationTemplate::MatchSettled {
+            match_id: Uuid::new_v4(),
+            won: Some(true),
+        }
+        .render();
+        assert_eq!(settled.kind, "match_settled");
+        assert!(settled.message.contains("winnings"));
+    }
+}
@@ -0,0 +1,511 @@
+//! Payments API v1.
+//!
+//! A payment is money a user pays the platform for something (its `purpose`),
+//! either from their custodied XLM or through a card provider. Payments move
+//! through `pending → processing → completed | failed` and can be cancelled
+//! only while still pending. Every transition is a conditional update on the
+//! current status, published as a `PaymentStatusChange` event on the user's
+//! channel; that feeds the WebSocket gateway and each payment's SSE status
+//! stream.
+//!
+//! Creating a payment takes a client idempotency key: a retry with the same
+//! key and request returns the original payment, while a different request
+//! under the same key is a conflict. Processing runs in the background and is
+//! safe to repeat (wallet charges are keyed by payment), so payments
+//! interrupted by a restart are simply processed again.
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{
+    CreatePaymentRequest, Payment, PaymentSource, PaymentStatus, PaymentStatusView,
+};
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::{channels, RealtimeEvent};
+use crate::service::domain_events::{self, DomainEvent};
+use crate::service::ledger::ASSET_XLM;
+use crate::service::wallet_service::{WalletError, WalletService};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{BoxStream, StreamExt};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+const MAX_IDEMPOTENCY_KEY_LEN: usize = 255;
+
+pub struct PaymentService {
+    db_pool: DbPool,
+    wallets: Arc<WalletService>,
+    event_bus: EventBus,
+    redis_url: String,
+}
+
+impl PaymentService {
+    pub fn new(
+        db_pool: DbPool,
+        wallets: Arc<WalletService>,
+        event_bus: EventBus,
+        redis_url: String,
+    ) -> Self {
+        Self {
+            db_pool,
+            wallets,
+            event_bus,
+            redis_url,
+        }
+    }
+
+    /// Create a payment and start processing it. Returns the payment and
+    /// whether it was created by this call rather than replayed for a retry.
+    pub async fn create(
+        self: &Arc<Self>,
+        user_id: Uuid,
+        idempotency_key: &str,
+        mut request: CreatePaymentRequest,
+    ) -> Result<(Payment, bool), ApiError> {
+        let idempotency_key = idempotency_key.trim();
+        if idempotency_key.is_empty() || idempotency_key.len() > MAX_IDEMPOTENCY_KEY_LEN {
+            return Err(ApiError::bad_request(format!(
+                "Idempotency-Key must be between 1 and {} characters",
+                MAX_IDEMPOTENCY_KEY_LEN
+            )));
+        }
+        request.currency = request.currency.to_ascii_uppercase();
+        match request.source {
+            PaymentSource::Wallet if request.currency != ASSET_XLM => {
+                return Err(ApiError::bad_request("Wallet payments must be in XLM"));
+            }
+            PaymentSource::Paystack | PaymentSource::Flutterwave
+                if request.provider_reference.is_none() =>
+            {
+                return Err(ApiError::bad_request(
+                    "provider_reference is required for card payments",
+                ));
+            }
+            _ => {}
+        }
+        let request_hash = request_fingerprint(&request);
+
+        if let Some(existing) = self.find_by_key(user_id, idempotency_key).await? {
+            return replay(existing, &request_hash);
+        }
+
+        let inserted: Option<Payment> = sqlx::query_as(
+            r#"
+            INSERT INTO payments (
+                id, user_id, amount, currency, source, purpose, provider_reference,
+                status, metadata, idempotency_key, request_hash
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, 'pending', $8, $9, $10)
+            ON CONFLICT (user_id, idempotency_key) DO NOTHING
+            RETURNING *
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(request.amount)
+        .bind(&request.currency)
+        .bind(request.source)
+        .bind(&request.purpose)
+        .bind(&request.provider_reference)
+        .bind(
+            request
+                .metadata
+                .clone()
+                .unwrap_or_else(|| serde_json::json!({})),
+        )
+        .bind(idempotency_key)
+        .bind(&request_hash)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+
+        let Some(payment) = inserted else {
+            // A concurrent retry inserted it first.
+            let existing = self
+                .find_by_key(user_id, idempotency_key)
+                .await?
+                .ok_or(ApiError::NotFound)?;
+            return replay(existing, &request_hash);
+        };
+
+        info!(
+            payment_id = %payment.id,
+            %user_id,
+            amount = payment.amount,
+            currency = %payment.currency,
+            source = ?payment.source,
+            "Payment created"
+        );
+        self.spawn_process(payment.id);
+        Ok((payment, true))
+    }
+
+    pub async fn get(&self, user_id: Uuid, payment_id: Uuid) -> Result<Payment, ApiError> {
+        sqlx::query_as("SELECT * FROM payments WHERE id = $1 AND user_id = $2")
+            .bind(payment_id)
+            .bind(user_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?
+            .ok_or_else(|| ApiError::not_found("Payment not found"))
+    }
+
+    /// Cancel a payment that has not started processing.
+    pub async fn cancel(&self, user_id: Uuid, payment_id: Uuid) -> Result<Payment, ApiError> {
+        let payment = self.get(user_id, payment_id).await?;
+        if !payment.status.can_transition_to(PaymentStatus::Cancelled) {
+            return Err(ApiError::conflict(format!(
+                "Payment is {}",
+                payment.status.as_str()
+            )));
+        }
+        match self
+            .transition(
+                payment_id,
+                PaymentStatus::Pending,
+                PaymentStatus::Cancelled,
+                None,
+            )
+            .await?
+        {
+            Some(payment) => Ok(payment),
+            None => {
+                let current = self.get(user_id, payment_id).await?;
+                Err(ApiError::conflict(format!(
+                    "Payment is {}",
+                    current.status.as_str()
+                )))
+            }
+        }
+    }
+
+    /// The payment's current state and a stream of its later status changes.
+    /// The subscription is made before the payment is read, so no change can
+    /// fall in between.
+    pub async fn watch(
+        &self,
+        user_id: Uuid,
+        payment_id: Uuid,
+    ) -> Result<(Payment, BoxStream<'static, PaymentStatusView>), ApiError> {
+        let redis_error = |e: redis::RedisError| ApiError::RedisError(e.to_string());
+        let client = redis::Client::open(self.redis_url.as_str()).map_err(redis_error)?;
+        let mut pubsub = client.get_async_pubsub().await.map_err(redis_error)?;
+        pubsub
+            .subscribe(channels::user_channel(user_id))
+            .await
+            .map_err(redis_error)?;
+
+        let payment = self.get(user_id, payment_id).await?;
+        let updates = pubsub
+            .into_on_message()
+            .filter_map(move |msg| async move {
+                let payload: String = msg.get_payload().ok()?;
+                status_update(payment_id, &payload)
+            })
+            .boxed();
+        Ok((payment, updates))
+    }
+
+    /// Process payments left open by a restart, in a detached task.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            let open: Result<Vec<Uuid>, sqlx::Error> = sqlx::query_scalar(
+                "SELECT id FROM payments WHERE status IN ('pending', 'processing') ORDER BY created_at",
+            )
+            .fetch_all(&self.db_pool)
+            .await;
+            match open {
+                Ok(open) => {
+                    for payment_id in open {
+                        self.spawn_process(payment_id);
+                    }
+                }
+                Err(e) => error!(error = %e, "Failed to resume open payments"),
+            }
+        });
+    }
+
+    async fn find_by_key(
+        &self,
+        user_id: Uuid,
+        idempotency_key: &str,
+    ) -> Result<Option<Payment>, ApiError> {
+        sqlx::query_as("SELECT * FROM payments WHERE user_id = $1 AND idempotency_key = $2")
+            .bind(user_id)
+            .bind(idempotency_key)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)
+    }
+
+    fn spawn_process(self: &Arc<Self>, payment_id: Uuid) {
+        let service = self.clone();
+        tokio::spawn(async move {
+            if let Err(e) = service.process(payment_id).await {
+                error!(%payment_id, error = %e, "Payment processing failed");
+            }
+        });
+    }
+
+    /// Collect the payment. Errors that may be transient leave it processing,
+    /// to be retried on the next start.
+    async fn process(&self, payment_id: Uuid) -> Result<(), ApiError> {
+        let payment: Option<Payment> = sqlx::query_as("SELECT * FROM payments WHERE id = $1")
+            .bind(payment_id)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?;
+        let payment = match payment {
+            Some(p) if p.status == PaymentStatus::Pending => {
+                match self
+                    .transition(
+                        payment_id,
+                        PaymentStatus::Pending,
+                        PaymentStatus::Processing,
+                        None,
+                    )
+                    .await?
+                {
+                    Some(p) => p,
+                    // Cancelled or claimed in the meantime.
+                    None => return Ok(()),
+                }
+            }
+            Some(p) if p.status == PaymentStatus::Processing => p,
+            _ => return Ok(()),
+        };
+
+        let outcome = match payment.source {
+            PaymentSource::Wallet => {
+                match self
+                    .wallets
+                    .charge_custodial(
+                        payment.user_id,
+                        payment.amount,
+                        &payment.id.to_string(),
+                        &format!("Payment: {}", payment.purpose),
+                    )
+                    .await
+                {
+                    Ok(()) => Ok(()),
+                    Err(
+                        e @ (WalletError::InsufficientBalance { .. }
+                        | WalletError::InvalidAmount(_)),
+                    ) => Err(e.to_string()),
+                    Err(e) => return Err(e.into()),
+                }
+            }
+            PaymentSource::Paystack | PaymentSource::Flutterwave => {
+                let reference = payment.provider_reference.as_deref().unwrap_or_default();
+                let verified = if payment.source == PaymentSource::Paystack {
+                    self.wallets
+                        .verify_paystack_payment(reference, payment.amount)
+                        .await
+                } else {
+                    self.wallets
+                        .verify_flutterwave_payment(reference, payment.amount)
+                        .await
+                };
+                match verified {
+                    Ok(true) => Ok(()),
+                    Ok(false) | Err(WalletError::PaymentVerificationFailed) => {
+                        Err("The provider did not confirm the payment".to_string())
+                    }
+                    Err(e) => return Err(e.into()),
+                }
+            }
+        };
+
+        match outcome {
+            Ok(()) => {
+                self.transition(
+                    payment_id,
+                    PaymentStatus::Processing,
+                    PaymentStatus::Completed,
+                    None,
+                )
+                .await?;
+                info!(%payment_id, "Payment completed");
+            }
+            Err(reason) => {
+                warn!(%payment_id, reason = %reason, "Payment failed");
+                self.transition(
+                    payment_id,
+                    PaymentStatus::Processing,
+                    PaymentStatus::Failed,
+                    Some(&reason),
+                )
+                .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Move a payment from `from` to `to` if it is still in `from`, and
+    /// publish the change. A completed payment is also announced on the
+    /// domain event stream, in the same transaction. Returns `None` when it
+    /// was not in `from`.
+    async fn transition(
+        &self,
+        payment_id: Uuid,
+        from: PaymentStatus,
+        to: PaymentStatus,
+        reason: Option<&str>,
+    ) -> Result<Option<Payment>, ApiError> {
+        debug_assert!(from.can_transition_to(to));
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        let payment: Option<Payment> = sqlx::query_as(
+            r#"
+            UPDATE payments
+            SET status = $3,
+                failure_reason = COALESCE($4, failure_reason),
+                completed_at = CASE WHEN $3 = 'completed' THEN NOW() ELSE completed_at END,
+                updated_at = NOW()
+            WHERE id = $1 AND status = $2
+            RETURNING *
+            "#,
+        )
+        .bind(payment_id)
+        .bind(from)
+        .bind(to)
+        .bind(reason)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        if let Some(payment) = payment.as_ref().filter(|_| to == PaymentStatus::Completed) {
+            let event = DomainEvent::PaymentCompleted {
+                payment_id,
+                user_id: payment.user_id,
+                amount: payment.amount,
+                currency: payment.currency.clone(),
+                purpose: payment.purpose.clone(),
+            };
+            domain_events::publish(&mut tx, &event)
+                .await
+                .map_err(ApiError::database_error)?;
+        }
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        if let Some(payment) = &payment {
+            let event = RealtimeEvent::PaymentStatusChange {
+                payment_id,
+                from_status: from.as_str().to_string(),
+                to_status: to.as_str().to_string(),
+                reason: payment.failure_reason.clone(),
+                timestamp: payment.updated_at.to_rfc3339(),
+            };
+            self.event_bus
+                .publish_to_user(payment.user_id, &event)
+                .await;
+        }
+        Ok(payment)
+    }
+}
+
+/// Return the payment created earlier under the same idempotency key, unless
+/// the key is being reused for a different request.
+fn replay(existing: Payment, request_hash: &str) -> Result<(Payment, bool), ApiError> {
+    if existing.request_hash != request_hash {
+        return Err(ApiError::conflict(
+            "Idempotency-Key was already used for a different payment",
+        ));
+    }
+    Ok((existing, false))
+}
+
+/// SHA-256 of the normalized request, to tell retries from key reuse.
+fn request_fingerprint(request: &CreatePaymentRequest) -> String {
+    let body = serde_json::to_vec(request).unwrap_or_default();
+    hex::encode(Sha256::digest(&body))
+}
+
+/// Decode a user channel message into a status change of `payment_id`.
+fn status_update(payment_id: Uuid, payload: &str) -> Option<PaymentStatusView> {
+    match serde_json::from_str::<RealtimeEvent>(payload).ok()? {
+        RealtimeEvent::PaymentStatusChange {
+            payment_id: id,
+            to_status,
+            reason,
+            timestamp,
+            ..
+        } if id == payment_id => Some(PaymentStatusView {
+            payment_id,
+            status: to_status.parse().ok()?,
+            failure_reason: reason,
+            updated_at: DateTime::parse_from_rfc3339(&timestamp)
+                .ok()?
+                .with_timezone(&Utc),
+        }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn request(amount: i64) -> CreatePaymentRequest {
+        CreatePaymentRequest {
+            amount,
+            currency: "XLM".to_string(),
+            source: PaymentSource::Wallet,
+            purpose: "tournament_entry".to_string(),
+            provider_reference: None,
+            metadata: Some(serde_json::json!({ "tournament_id": "t1", "round": 1 })),
+        }
+    }
+
+    #[test]
+    fn test_status_transitions() {
+        use PaymentStatus::*;
+        assert!(Pending.can_transition_to(Processing));
+        assert!(Pending.can_transition_to(Cancelled));
+        assert!(Processing.can_transition_to(Completed));
+        assert!(Processing.can_transition_to(Failed));
+        assert!(!Processing.can_transition_to(Cancelled));
+        assert!(!Completed.can_transition_to(Failed));
+        assert!(!Cancelled.can_transition_to(Processing));
+        assert!(Completed.is_terminal() && Cancelled.is_terminal() && !Pending.is_terminal());
+    }
+
+    #[test]
+    fn test_request_fingerprint_tells_retries_from_reuse() {
+        assert_eq!(
+            request_fingerprint(&request(100)),
+            request_fingerprint(&request(100))
+        );
+        assert_ne!(
+            request_fingerprint(&request(100)),
+            request_fingerprint(&request(101))
+        );
+    }
+
+    #[test]
+    fn test_status_update_filters_by_payment() {
+        let payment_id = Uuid::new_v4();
+        let event = |id: Uuid| {
+            serde_json::to_string(&RealtimeEvent::PaymentStatusChange {
+                payment_id: id,
+                from_status: "processing".to_string(),
+                to_status: "failed".to_string(),
+                reason: Some("Insufficient balance".to_string()),
+                timestamp: "2026-07-01T12:00:00+00:00".to_string(),
+            })
+            .unwrap()
+        };
+
+        let update = status_update(payment_id, &event(payment_id)).unwrap();
+        assert_eq!(update.status, PaymentStatus::Failed);
+        assert_eq!(
+            update.failure_reason.as_deref(),
+            Some("Insufficient balance")
+        );
+        assert_eq!(status_update(payment_id, &event(Uuid::new_v4())), None);
+        assert_eq!(status_update(payment_id, "not json"), None);
+    }
+}
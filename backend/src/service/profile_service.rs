@@ -0,0 +1,286 @@
+//! Player profiles.
+//!
+//! A profile aggregates the player's completed matches, reputation, prize
+//! earnings, badges and tournaments. Reputation trends and earnings come from
+//! tables the chain indexer fills. Profiles are cached in Redis for
+//! [`PROFILE_CACHE_TTL_SECS`] with earnings included; privacy settings are
+//! applied per viewer with [`PlayerProfile::visible_to`].
+
+use crate::api_error::ApiError;
+use crate::models::{
+    Badge, MatchStats, PlayerProfile, PrivacySettings, ProfileEarnings, ProfileMatch,
+    ProfileTournament, ReputationSummary, ReputationTrendPoint, Season,
+    UpdatePrivacySettingsRequest,
+};
+use chrono::{DateTime, Utc};
+use redis::aio::ConnectionManager;
+use redis::AsyncCommands;
+use sqlx::PgPool;
+use tracing::warn;
+use uuid::Uuid;
+
+pub const PROFILE_CACHE_TTL_SECS: u64 = 60;
+
+const RECENT_MATCHES: i64 = 10;
+const RECENT_TOURNAMENTS: i64 = 5;
+const REPUTATION_TREND_DAYS: i32 = 30;
+
+#[derive(sqlx::FromRow)]
+struct ProfileUser {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    bio: Option<String>,
+    country_code: Option<String>,
+    created_at: DateTime<Utc>,
+    skill_score: Option<i32>,
+    fair_play_score: Option<i32>,
+    hide_earnings: bool,
+}
+
+pub struct ProfileService {
+    db_pool: PgPool,
+    redis: ConnectionManager,
+}
+
+impl ProfileService {
+    pub fn new(db_pool: PgPool, redis: ConnectionManager) -> Self {
+        Self { db_pool, redis }
+    }
+
+    /// Profile of `user_id` as `viewer` may see it.
+    pub async fn profile(
+        &self,
+        user_id: Uuid,
+        viewer: Option<Uuid>,
+    ) -> Result<PlayerProfile, ApiError> {
+        let profile = match self.cached(user_id).await {
+            Some(profile) => profile,
+            None => {
+                let profile = self.build(user_id).await?;
+                self.cache(&profile).await;
+                profile
+            }
+        };
+        Ok(profile.visible_to(viewer))
+    }
+
+    pub async fn privacy(&self, user_id: Uuid) -> Result<PrivacySettings, ApiError> {
+        let hide_earnings = sqlx::query_scalar::<_, bool>(
+            "SELECT hide_earnings FROM player_privacy WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .unwrap_or(false);
+        Ok(PrivacySettings { hide_earnings })
+    }
+
+    pub async fn update_privacy(
+        &self,
+        user_id: Uuid,
+        request: &UpdatePrivacySettingsRequest,
+    ) -> Result<PrivacySettings, ApiError> {
+        let hide_earnings = sqlx::query_scalar::<_, bool>(
+            "INSERT INTO player_privacy (user_id, hide_earnings) \
+             VALUES ($1, COALESCE($2, FALSE)) \
+             ON CONFLICT (user_id) DO UPDATE \
+             SET hide_earnings = COALESCE($2, player_privacy.hide_earnings), updated_at = NOW() \
+             RETURNING hide_earnings",
+        )
+        .bind(user_id)
+        .bind(request.hide_earnings)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        self.invalidate(user_id).await;
+        Ok(PrivacySettings { hide_earnings })
+    }
+
+    /// Drop the cached profile of `user_id`.
+    pub async fn invalidate(&self, user_id: Uuid) {
+        let mut conn = self.redis.clone();
+        if let Err(e) = conn.del::<_, ()>(cache_key(user_id)).await {
+            warn!(user_id = %user_id, error = %e, "Failed to invalidate cached profile");
+        }
+    }
+
+    async fn cached(&self, user_id: Uuid) -> Option<PlayerProfile> {
+        let mut conn = self.redis.clone();
+        let json: Option<String> = match conn.get(cache_key(user_id)).await {
+            Ok(json) => json,
+            Err(e) => {
+                warn!(user_id = %user_id, error = %e, "Failed to read cached profile");
+                return None;
+            }
+        };
+        serde_json::from_str(&json?).ok()
+    }
+
+    async fn cache(&self, profile: &PlayerProfile) {
+        let Ok(json) = serde_json::to_string(profile) else {
+            return;
+        };
+        let mut conn = self.redis.clone();
+        if let Err(e) = conn
+            .set_ex::<_, _, ()>(cache_key(profile.user_id), json, PROFILE_CACHE_TTL_SECS)
+            .await
+        {
+            warn!(user_id = %profile.user_id, error = %e, "Failed to cache profile");
+        }
+    }
+
+    async fn build(&self, user_id: Uuid) -> Result<PlayerProfile, ApiError> {
+        let user = sqlx::query_as::<_, ProfileUser>(
+            "SELECT u.id, u.username, u.display_name, u.avatar_url, u.bio, u.country_code, \
+                    u.created_at, u.skill_score, u.fair_play_score, \
+                    COALESCE(p.hide_earnings, FALSE) AS hide_earnings \
+             FROM users u \
+             LEFT JOIN player_privacy p ON p.user_id = u.id \
+             WHERE u.id = $1 AND u.is_active = TRUE",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(ApiError::NotFound)?;
+
+        Ok(PlayerProfile {
+            user_id: user.id,
+            username: user.username,
+            display_name: user.display_name,
+            avatar_url: user.avatar_url,
+            bio: user.bio,
+            country_code: user.country_code,
+            joined_at: user.created_at,
+            stats: self.match_stats(user_id).await?,
+            recent_matches: self.recent_matches(user_id).await?,
+            reputation: ReputationSummary {
+                skill_score: user.skill_score.unwrap_or(1000),
+                fair_play_score: user.fair_play_score.unwrap_or(100),
+                trend: self.reputation_trend(user_id).await?,
+            },
+            earnings: Some(self.earnings(user_id).await?),
+            earnings_hidden: user.hide_earnings,
+            badges: self.badges(user_id).await?,
+            recent_tournaments: self.recent_tournaments(user_id).await?,
+        })
+    }
+
+    async fn match_stats(&self, user_id: Uuid) -> Result<MatchStats, ApiError> {
+        let (played, wins, losses) = sqlx::query_as::<_, (i64, i64, i64)>(
+            "SELECT COUNT(*), \
+                    COUNT(*) FILTER (WHERE winner_id = $1), \
+                    COUNT(*) FILTER (WHERE winner_id IS NOT NULL AND winner_id <> $1) \
+             FROM matches \
+             WHERE status = 3 AND (player1_id = $1 OR player2_id = $1)",
+        )
+        .bind(user_id)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(MatchStats::new(played, wins, losses))
+    }
+
+    async fn recent_matches(&self, user_id: Uuid) -> Result<Vec<ProfileMatch>, ApiError> {
+        let matches = sqlx::query_as::<_, ProfileMatch>(
+            "SELECT m.id AS match_id, m.game_mode, o.id AS opponent_id, \
+                    o.username AS opponent_username, \
+                    CASE WHEN m.winner_id = $1 THEN 'win' \
+                         WHEN m.winner_id IS NULL THEN 'draw' \
+                         ELSE 'loss' END AS result, \
+                    CASE WHEN m.player1_id = $1 THEN m.player1_score \
+                         ELSE m.player2_score END AS score, \
+                    CASE WHEN m.player1_id = $1 THEN m.player2_score \
+                         ELSE m.player1_score END AS opponent_score, \
+                    m.completed_at \
+             FROM matches m \
+             LEFT JOIN users o ON o.id = CASE WHEN m.player1_id = $1 \
+                                              THEN m.player2_id ELSE m.player1_id END \
+             WHERE m.status = 3 AND (m.player1_id = $1 OR m.player2_id = $1) \
+             ORDER BY m.completed_at DESC NULLS LAST \
+             LIMIT $2",
+        )
+        .bind(user_id)
+        .bind(RECENT_MATCHES)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(matches)
+    }
+
+    /// Daily sums of the reputation changes indexed for the player's wallets.
+    async fn reputation_trend(&self, user_id: Uuid) -> Result<Vec<ReputationTrendPoint>, ApiError> {
+        let trend = sqlx::query_as::<_, ReputationTrendPoint>(
+            "SELECT date_trunc('day', e.ledger_closed_at) AS day, \
+                    SUM(r.skill_delta)::int8 AS skill_delta, \
+                    SUM(r.fair_play_delta)::int8 AS fair_play_delta \
+             FROM chain_reputation_events r \
+             JOIN chain_events e ON e.event_id = r.event_id \
+             JOIN wallets w ON w.stellar_public_key = r.player \
+             WHERE w.user_id = $1 \
+               AND e.ledger_closed_at >= NOW() - make_interval(days => $2) \
+             GROUP BY 1 \
+             ORDER BY 1",
+        )
+        .bind(user_id)
+        .bind(REPUTATION_TREND_DAYS)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(trend)
+    }
+
+    async fn earnings(&self, user_id: Uuid) -> Result<ProfileEarnings, ApiError> {
+        let season = Season::current();
+        let (all_time, season_total) = sqlx::query_as::<_, (i64, i64)>(
+            "SELECT COALESCE(SUM(amount), 0)::int8, \
+                    COALESCE(SUM(amount) FILTER (WHERE occurred_at >= $2 AND occurred_at < $3), \
+                             0)::int8 \
+             FROM leaderboard_credits \
+             WHERE user_id = $1 AND metric = 'earnings'",
+        )
+        .bind(user_id)
+        .bind(season.starts_at)
+        .bind(season.ends_at)
+        .fetch_one(&self.db_pool)
+        .await?;
+        Ok(ProfileEarnings {
+            all_time,
+            season: season_total,
+            season_id: season.id,
+        })
+    }
+
+    async fn badges(&self, user_id: Uuid) -> Result<Vec<Badge>, ApiError> {
+        let badges = sqlx::query_as::<_, Badge>(
+            "SELECT a.id AS achievement_id, a.name, a.icon_url, a.rarity, pa.unlocked_at \
+             FROM player_achievements pa \
+             JOIN achievements a ON a.id = pa.achievement_id \
+             WHERE pa.user_id = $1 AND pa.is_unlocked = TRUE \
+             ORDER BY pa.unlocked_at DESC NULLS LAST",
+        )
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(badges)
+    }
+
+    async fn recent_tournaments(&self, user_id: Uuid) -> Result<Vec<ProfileTournament>, ApiError> {
+        let tournaments = sqlx::query_as::<_, ProfileTournament>(
+            "SELECT t.id AS tournament_id, t.name, t.game, COALESCE(t.status, 0) AS status, \
+                    tp.final_rank, tp.prize_amount, tp.registered_at \
+             FROM tournament_participants tp \
+             JOIN tournaments t ON t.id = tp.tournament_id \
+             WHERE tp.user_id = $1 \
+             ORDER BY tp.registered_at DESC \
+             LIMIT $2",
+        )
+        .bind(user_id)
+        .bind(RECENT_TOURNAMENTS)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(tournaments)
+    }
+}
+
+fn cache_key(user_id: Uuid) -> String {
+    format!("profile:{}", user_id)
+}
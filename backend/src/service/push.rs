@@ -0,0 +1,217 @@
+//! Push notifications through Firebase Cloud Messaging.
+//!
+//! Messages go to one FCM registration token at a time over the HTTP v1 API,
+//! authorized with an OAuth access token obtained by signing a JWT with the
+//! service account key. Without a service account, messages are logged.
+
+use crate::config::FcmConfig;
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing::info;
+
+const FCM_SCOPE: &str = "https://www.googleapis.com/auth/firebase.messaging";
+/// Access tokens are renewed this long before they expire.
+const TOKEN_REFRESH_MARGIN: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Error)]
+pub enum PushError {
+    #[error("FCM authorization failed: {0}")]
+    Credentials(String),
+    #[error("Push request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Registration token is no longer valid")]
+    Unregistered,
+    #[error("FCM rejected message with status {status}: {body}")]
+    Rejected { status: u16, body: String },
+}
+
+impl PushError {
+    /// Whether sending the same message again may succeed.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            PushError::Credentials(_) | PushError::Request(_) => true,
+            PushError::Unregistered => false,
+            PushError::Rejected { status, .. } => *status == 429 || *status >= 500,
+        }
+    }
+
+    fn from_response(status: u16, body: String) -> Self {
+        if status == 404 || body.contains("UNREGISTERED") {
+            PushError::Unregistered
+        } else {
+            PushError::Rejected { status, body }
+        }
+    }
+}
+
+/// A push notification.
+#[derive(Debug, Clone, Copy)]
+pub struct PushMessage<'a> {
+    pub title: &'a str,
+    pub body: &'a str,
+    /// Path the app opens when the notification is tapped.
+    pub link: Option<&'a str>,
+    pub category: &'a str,
+}
+
+#[derive(Serialize)]
+struct ServiceAccountClaims<'a> {
+    iss: &'a str,
+    scope: &'a str,
+    aud: &'a str,
+    iat: i64,
+    exp: i64,
+}
+
+#[derive(Deserialize)]
+struct AccessTokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+struct Fcm {
+    config: FcmConfig,
+    key: EncodingKey,
+    access_token: Mutex<Option<(String, Instant)>>,
+}
+
+/// Sends push notifications to registered devices.
+pub struct PushGateway {
+    client: Client,
+    fcm: Option<Fcm>,
+}
+
+impl PushGateway {
+    pub fn new(config: Option<&FcmConfig>) -> Result<Self, PushError> {
+        let fcm = config
+            .map(|config| {
+                let key = EncodingKey::from_rsa_pem(config.private_key.as_bytes())
+                    .map_err(|e| PushError::Credentials(e.to_string()))?;
+                Ok::<_, PushError>(Fcm {
+                    config: config.clone(),
+                    key,
+                    access_token: Mutex::new(None),
+                })
+            })
+            .transpose()?;
+
+        Ok(Self {
+            client: Client::new(),
+            fcm,
+        })
+    }
+
+    /// Send `message` to the device with the registration `token`.
+    pub async fn send(&self, token: &str, message: &PushMessage<'_>) -> Result<(), PushError> {
+        let Some(fcm) = &self.fcm else {
+            info!(
+                title = %message.title,
+                body = %message.body,
+                category = %message.category,
+                "Push notification (not sent)"
+            );
+            return Ok(());
+        };
+
+        let mut data = serde_json::json!({ "category": message.category });
+        if let Some(link) = message.link {
+            data["link"] = link.into();
+        }
+        let access_token = self.access_token(fcm).await?;
+        let response = self
+            .client
+            .post(format!(
+                "https://fcm.googleapis.com/v1/projects/{}/messages:send",
+                fcm.config.project_id
+            ))
+            .bearer_auth(access_token)
+            .json(&serde_json::json!({
+                "message": {
+                    "token": token,
+                    "notification": { "title": message.title, "body": message.body },
+                    "data": data,
+                }
+            }))
+            .send()
+            .await?;
+
+        let status = response.status();
+        if status.is_success() {
+            return Ok(());
+        }
+        let body = response.text().await.unwrap_or_default();
+        Err(PushError::from_response(status.as_u16(), body))
+    }
+
+    /// A cached OAuth access token, renewed when about to expire.
+    async fn access_token(&self, fcm: &Fcm) -> Result<String, PushError> {
+        let mut cached = fcm.access_token.lock().await;
+        if let Some((token, expires_at)) = cached.as_ref() {
+            if Instant::now() + TOKEN_REFRESH_MARGIN < *expires_at {
+                return Ok(token.clone());
+            }
+        }
+
+        let now = Utc::now().timestamp();
+        let assertion = jsonwebtoken::encode(
+            &Header::new(Algorithm::RS256),
+            &ServiceAccountClaims {
+                iss: &fcm.config.client_email,
+                scope: FCM_SCOPE,
+                aud: &fcm.config.token_uri,
+                iat: now,
+                exp: now + 3600,
+            },
+            &fcm.key,
+        )
+        .map_err(|e| PushError::Credentials(e.to_string()))?;
+
+        let response = self
+            .client
+            .post(&fcm.config.token_uri)
+            .form(&[
+                ("grant_type", "urn:ietf:params:oauth:grant-type:jwt-bearer"),
+                ("assertion", assertion.as_str()),
+            ])
+            .send()
+            .await?;
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(PushError::Credentials(format!("{}: {}", status, body)));
+        }
+        let token = response.json::<AccessTokenResponse>().await?;
+
+        let expires_at = Instant::now() + Duration::from_secs(token.expires_in);
+        *cached = Some((token.access_token.clone(), expires_at));
+        Ok(token.access_token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_classification() {
+        let unregistered = PushError::from_response(
+            404,
+            r#"{"error":{"status":"NOT_FOUND","details":[{"errorCode":"UNREGISTERED"}]}}"#
+                .to_string(),
+        );
+        assert!(matches!(unregistered, PushError::Unregistered));
+        assert!(!unregistered.is_retryable());
+
+        let invalid = PushError::from_response(400, "INVALID_ARGUMENT".to_string());
+        assert!(matches!(invalid, PushError::Rejected { status: 400, .. }));
+        assert!(!invalid.is_retryable());
+
+        assert!(PushError::from_response(503, String::new()).is_retryable());
+        assert!(PushError::from_response(429, String::new()).is_retryable());
+    }
+}
@@ -0,0 +1,573 @@
+//! Tournament registration with on-chain eligibility checks.
+//!
+//! Before a player joins, their Stellar address is checked against the auth
+//! gateway (player role, not banned) and their mirrored reputation against the
+//! tournament's requirements. Tournaments registered on the staking manager
+//! also require the player's stake to be on chain: until it is, the
+//! registration waits in `pending_stake` and the response tells the client what
+//! to sign. Contract state is read through simulated calls on the relayer.
+//!
+//! Team tournaments take one entry per team, made by its captain through
+//! [`RegistrationService::register_team`]; the captain's participant row is
+//! linked to the team.
+
+use crate::api_error::ApiError;
+use crate::config::StellarConfig;
+use crate::db::DbPool;
+use crate::models::{
+    JoinTournamentRequest, RegistrationRequirements, RegistrationResponse, RegistrationStatus,
+    StakeInstruction, TournamentRegistration,
+};
+use crate::service::idempotency_service::IdempotencyService;
+use crate::service::stellar_relayer::xdr::{decode_sc_scalar, ScArg};
+use crate::service::stellar_relayer::{RelayerError, StellarRelayer};
+use crate::service::tournament_service::TournamentService;
+use sqlx::Row;
+use std::sync::Arc;
+use tracing::info;
+use uuid::Uuid;
+
+/// `Role::Player` on the auth gateway.
+const ROLE_PLAYER: u32 = 4;
+
+pub struct RegistrationService {
+    db_pool: DbPool,
+    tournaments: Arc<TournamentService>,
+    relayer: Option<Arc<StellarRelayer>>,
+    auth_gateway_contract: Option<String>,
+    staking_contract: Option<String>,
+}
+
+impl RegistrationService {
+    pub fn new(db_pool: DbPool, tournaments: Arc<TournamentService>) -> Self {
+        Self {
+            db_pool,
+            tournaments,
+            relayer: None,
+            auth_gateway_contract: None,
+            staking_contract: None,
+        }
+    }
+
+    /// Enable on-chain role and stake checks for the contracts configured in
+    /// `stellar`.
+    pub fn with_relayer(mut self, relayer: Arc<StellarRelayer>, stellar: &StellarConfig) -> Self {
+        self.relayer = Some(relayer);
+        self.auth_gateway_contract = stellar.soroban_contract_auth_gateway.clone();
+        self.staking_contract = stellar.soroban_contract_staking.clone();
+        self
+    }
+
+    /// Register `user_id` for a tournament. Repeating the call with the same
+    /// idempotency key returns the existing registration, re-checking the stake
+    /// if it is still pending. A key is generated when none is supplied.
+    pub async fn register(
+        &self,
+        user_id: Uuid,
+        tournament_id: Uuid,
+        idempotency_key: Option<String>,
+        request: JoinTournamentRequest,
+    ) -> Result<RegistrationResponse, ApiError> {
+        self.register_entry(user_id, None, tournament_id, idempotency_key, request)
+            .await
+    }
+
+    /// Register `team_id` for a team tournament, with `captain` as its entrant.
+    /// The checks of [`Self::register`] apply to the captain.
+    pub async fn register_team(
+        &self,
+        captain: Uuid,
+        team_id: Uuid,
+        tournament_id: Uuid,
+        idempotency_key: Option<String>,
+        request: JoinTournamentRequest,
+    ) -> Result<RegistrationResponse, ApiError> {
+        self.register_entry(
+            captain,
+            Some(team_id),
+            tournament_id,
+            idempotency_key,
+            request,
+        )
+        .await
+    }
+
+    async fn register_entry(
+        &self,
+        user_id: Uuid,
+        team_id: Option<Uuid>,
+        tournament_id: Uuid,
+        idempotency_key: Option<String>,
+        request: JoinTournamentRequest,
+    ) -> Result<RegistrationResponse, ApiError> {
+        let key = match idempotency_key {
+            Some(key) => {
+                IdempotencyService::validate_key_format(&key)?;
+                key
+            }
+            None => IdempotencyService::generate_key(),
+        };
+
+        if let Some(existing) = self.find(tournament_id, user_id).await? {
+            let status: RegistrationStatus =
+                existing.status.parse().map_err(ApiError::internal_error)?;
+            match status {
+                RegistrationStatus::Registered if existing.idempotency_key == key => {
+                    if let (Some(participant_id), Some(team_id)) =
+                        (existing.participant_id, team_id)
+                    {
+                        self.link_team(participant_id, team_id).await?;
+                    }
+                    return Ok(response(existing, None));
+                }
+                RegistrationStatus::Registered => {
+                    return Err(ApiError::conflict("Already registered for this tournament"));
+                }
+                RegistrationStatus::PendingStake if existing.idempotency_key != key => {
+                    return Err(ApiError::conflict(
+                        "A registration is already pending under another idempotency key",
+                    ));
+                }
+                // Pending with the same key is re-checked; rejected may retry.
+                _ => {}
+            }
+        }
+
+        let team_size = self.check_capacity(tournament_id).await?;
+        match (team_size, team_id) {
+            (Some(_), None) => {
+                return Err(ApiError::bad_request(
+                    "Teams register for this tournament through /api/teams",
+                ))
+            }
+            (None, Some(_)) => {
+                return Err(ApiError::bad_request("This tournament is not a team event"))
+            }
+            _ => {}
+        }
+
+        let stellar_address = self.stellar_address(user_id).await?;
+        if let Some(reason) = self.check_reputation(tournament_id, user_id).await? {
+            return self
+                .reject(tournament_id, user_id, &key, stellar_address, reason)
+                .await;
+        }
+
+        if let (Some(relayer), Some(gateway)) = (&self.relayer, &self.auth_gateway_contract) {
+            let address = require_address(&stellar_address)?;
+            if let Some(reason) = check_role(relayer, gateway, address).await? {
+                return self
+                    .reject(tournament_id, user_id, &key, stellar_address, reason)
+                    .await;
+            }
+        }
+
+        if let (Some(relayer), Some(staking)) = (&self.relayer, &self.staking_contract) {
+            let tournament_key = tournament_key(tournament_id);
+            if is_stake_gated(relayer, staking, tournament_key).await? {
+                let address = require_address(&stellar_address)?;
+                if !has_stake(relayer, staking, address, tournament_key).await? {
+                    let stake = StakeInstruction {
+                        contract_id: staking.clone(),
+                        function: "stake".to_string(),
+                        stellar_address: address.to_string(),
+                        tournament_key: hex::encode(tournament_key),
+                    };
+                    let registration = self
+                        .upsert(
+                            tournament_id,
+                            user_id,
+                            &key,
+                            RegistrationStatus::PendingStake,
+                            stellar_address.clone(),
+                            None,
+                            None,
+                        )
+                        .await?;
+                    info!(%user_id, %tournament_id, "Registration waiting for on-chain stake");
+                    return Ok(response(registration, Some(stake)));
+                }
+            }
+        }
+
+        let participant = self
+            .tournaments
+            .join_tournament(user_id, tournament_id, request)
+            .await?;
+        if let Some(team_id) = team_id {
+            self.link_team(participant.id, team_id).await?;
+        }
+        let registration = self
+            .upsert(
+                tournament_id,
+                user_id,
+                &key,
+                RegistrationStatus::Registered,
+                stellar_address,
+                Some(participant.id),
+                None,
+            )
+            .await?;
+        info!(%user_id, %tournament_id, "Tournament registration completed");
+        Ok(response(registration, None))
+    }
+
+    pub async fn get_registration(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<RegistrationResponse, ApiError> {
+        let registration = self
+            .find(tournament_id, user_id)
+            .await?
+            .ok_or_else(|| ApiError::not_found("Registration not found"))?;
+        Ok(response(registration, None))
+    }
+
+    // ========================================================================
+    // CHECKS
+    // ========================================================================
+
+    /// Check the tournament is open and not full, returning its team size for
+    /// team events.
+    async fn check_capacity(&self, tournament_id: Uuid) -> Result<Option<i32>, ApiError> {
+        let row = sqlx::query(
+            r#"
+            SELECT t.status::TEXT AS status, t.max_participants, t.team_size,
+                   (SELECT COUNT(*) FROM tournament_participants tp
+                    WHERE tp.tournament_id = t.id) AS participant_count
+            FROM tournaments t
+            WHERE t.id = $1
+            "#,
+        )
+        .bind(tournament_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("Tournament not found"))?;
+
+        let status: String = row.try_get("status").map_err(ApiError::database_error)?;
+        if status != "registration_open" {
+            return Err(ApiError::bad_request(
+                "Tournament is not accepting registrations",
+            ));
+        }
+        let max_participants: i32 = row
+            .try_get("max_participants")
+            .map_err(ApiError::database_error)?;
+        let participant_count: i64 = row
+            .try_get("participant_count")
+            .map_err(ApiError::database_error)?;
+        if participant_count >= max_participants as i64 {
+            return Err(ApiError::bad_request("Tournament is full"));
+        }
+        row.try_get("team_size").map_err(ApiError::database_error)
+    }
+
+    /// Reason the player falls short of the tournament's reputation
+    /// requirements, if any.
+    async fn check_reputation(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<String>, ApiError> {
+        let Some(requirements) = sqlx::query_as::<_, RegistrationRequirements>(
+            "SELECT * FROM tournament_registration_requirements WHERE tournament_id = $1",
+        )
+        .bind(tournament_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        else {
+            return Ok(None);
+        };
+
+        let row = sqlx::query(
+            "SELECT COALESCE(skill_score, 0) AS skill_score, COALESCE(fair_play_score, 0) AS fair_play_score FROM users WHERE id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?
+        .ok_or_else(|| ApiError::not_found("User not found"))?;
+        let skill_score: i32 = row
+            .try_get("skill_score")
+            .map_err(ApiError::database_error)?;
+        let fair_play_score: i32 = row
+            .try_get("fair_play_score")
+            .map_err(ApiError::database_error)?;
+
+        if let Some(min) = requirements.min_reputation {
+            if skill_score < min {
+                return Ok(Some(format!(
+                    "Reputation {} is below the required {}",
+                    skill_score, min
+                )));
+            }
+        }
+        if let Some(min) = requirements.min_fair_play {
+            if fair_play_score < min {
+                return Ok(Some(format!(
+                    "Fair play score {} is below the required {}",
+                    fair_play_score, min
+                )));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn stellar_address(&self, user_id: Uuid) -> Result<Option<String>, ApiError> {
+        let address: Option<Option<String>> =
+            sqlx::query_scalar("SELECT stellar_public_key FROM wallets WHERE user_id = $1")
+                .bind(user_id)
+                .fetch_optional(&self.db_pool)
+                .await
+                .map_err(ApiError::database_error)?;
+        Ok(address.flatten())
+    }
+
+    // ========================================================================
+    // PERSISTENCE
+    // ========================================================================
+
+    async fn find(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Option<TournamentRegistration>, ApiError> {
+        sqlx::query_as::<_, TournamentRegistration>(
+            "SELECT * FROM tournament_registrations WHERE tournament_id = $1 AND user_id = $2",
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    async fn link_team(&self, participant_id: Uuid, team_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query("UPDATE tournament_participants SET team_id = $1 WHERE id = $2")
+            .bind(team_id)
+            .bind(participant_id)
+            .execute(&self.db_pool)
+            .await
+            .map_err(ApiError::database_error)?;
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn upsert(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+        key: &str,
+        status: RegistrationStatus,
+        stellar_address: Option<String>,
+        participant_id: Option<Uuid>,
+        rejection_reason: Option<String>,
+    ) -> Result<TournamentRegistration, ApiError> {
+        sqlx::query_as::<_, TournamentRegistration>(
+            r#"
+            INSERT INTO tournament_registrations (
+                tournament_id, user_id, idempotency_key, status,
+                stellar_address, participant_id, rejection_reason
+            ) VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (tournament_id, user_id) DO UPDATE SET
+                idempotency_key = EXCLUDED.idempotency_key,
+                status = EXCLUDED.status,
+                stellar_address = EXCLUDED.stellar_address,
+                participant_id = EXCLUDED.participant_id,
+                rejection_reason = EXCLUDED.rejection_reason,
+                updated_at = NOW()
+            RETURNING *
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(user_id)
+        .bind(key)
+        .bind(status.as_str())
+        .bind(stellar_address)
+        .bind(participant_id)
+        .bind(rejection_reason)
+        .fetch_one(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)
+    }
+
+    async fn reject(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+        key: &str,
+        stellar_address: Option<String>,
+        reason: String,
+    ) -> Result<RegistrationResponse, ApiError> {
+        info!(%user_id, %tournament_id, reason = %reason, "Tournament registration rejected");
+        let registration = self
+            .upsert(
+                tournament_id,
+                user_id,
+                key,
+                RegistrationStatus::Rejected,
+                stellar_address,
+                None,
+                Some(reason),
+            )
+            .await?;
+        Ok(response(registration, None))
+    }
+}
+
+// ============================================================================
+// CONTRACT VIEWS
+// ============================================================================
+
+/// Tournament id on the staking manager: the UUID bytes, zero-padded to 32.
+pub fn tournament_key(tournament_id: Uuid) -> [u8; 32] {
+    let mut key = [0u8; 32];
+    key[..16].copy_from_slice(tournament_id.as_bytes());
+    key
+}
+
+/// Reason the auth gateway refuses the address, if any.
+async fn check_role(
+    relayer: &StellarRelayer,
+    gateway: &str,
+    address: &str,
+) -> Result<Option<String>, ApiError> {
+    let banned = view_bool(
+        relayer,
+        gateway,
+        "is_banned",
+        vec![ScArg::Address(address.to_string())],
+    )
+    .await?;
+    if banned {
+        return Ok(Some("Address is banned by the auth gateway".to_string()));
+    }
+    let is_player = view_bool(
+        relayer,
+        gateway,
+        "has_role",
+        vec![ScArg::Address(address.to_string()), ScArg::U32(ROLE_PLAYER)],
+    )
+    .await?;
+    if !is_player {
+        return Ok(Some("Address does not hold the player role".to_string()));
+    }
+    Ok(None)
+}
+
+/// Whether the tournament exists on the staking manager. The view panics for
+/// unknown tournaments, which surfaces as a failed simulation.
+async fn is_stake_gated(
+    relayer: &StellarRelayer,
+    staking: &str,
+    tournament_key: [u8; 32],
+) -> Result<bool, ApiError> {
+    view_exists(
+        relayer,
+        staking,
+        "get_tournament_info",
+        vec![ScArg::Bytes(tournament_key.to_vec())],
+    )
+    .await
+}
+
+async fn has_stake(
+    relayer: &StellarRelayer,
+    staking: &str,
+    address: &str,
+    tournament_key: [u8; 32],
+) -> Result<bool, ApiError> {
+    view_exists(
+        relayer,
+        staking,
+        "get_stake",
+        vec![
+            ScArg::Address(address.to_string()),
+            ScArg::Bytes(tournament_key.to_vec()),
+        ],
+    )
+    .await
+}
+
+async fn view_bool(
+    relayer: &StellarRelayer,
+    contract_id: &str,
+    function_name: &str,
+    args: Vec<ScArg>,
+) -> Result<bool, ApiError> {
+    let value = relayer
+        .call_view(contract_id, function_name, args)
+        .await
+        .map_err(relayer_error)?;
+    match decode_sc_scalar(&value).map_err(relayer_error)? {
+        ScArg::Bool(value) => Ok(value),
+        other => Err(ApiError::internal_error(format!(
+            "{} returned {:?}, expected a bool",
+            function_name, other
+        ))),
+    }
+}
+
+async fn view_exists(
+    relayer: &StellarRelayer,
+    contract_id: &str,
+    function_name: &str,
+    args: Vec<ScArg>,
+) -> Result<bool, ApiError> {
+    match relayer.call_view(contract_id, function_name, args).await {
+        Ok(_) => Ok(true),
+        Err(RelayerError::SimulationFailed(_)) => Ok(false),
+        Err(e) => Err(relayer_error(e)),
+    }
+}
+
+fn relayer_error(e: RelayerError) -> ApiError {
+    ApiError::internal_error(format!("Contract view failed: {}", e))
+}
+
+fn require_address(address: &Option<String>) -> Result<&str, ApiError> {
+    address
+        .as_deref()
+        .ok_or_else(|| ApiError::bad_request("A Stellar wallet is required to register"))
+}
+
+fn response(
+    registration: TournamentRegistration,
+    stake: Option<StakeInstruction>,
+) -> RegistrationResponse {
+    RegistrationResponse {
+        registration_id: registration.id,
+        tournament_id: registration.tournament_id,
+        status: registration
+            .status
+            .parse()
+            .unwrap_or(RegistrationStatus::Rejected),
+        idempotency_key: registration.idempotency_key,
+        participant_id: registration.participant_id,
+        rejection_reason: registration.rejection_reason,
+        stake,
+        updated_at: registration.updated_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tournament_key_pads_uuid() {
+        let id = Uuid::new_v4();
+        let key = tournament_key(id);
+        assert_eq!(&key[..16], id.as_bytes());
+        assert!(key[16..].iter().all(|b| *b == 0));
+    }
+
+    #[test]
+    fn test_require_address() {
+        assert!(require_address(&None).is_err());
+        assert_eq!(require_address(&Some("GABC".to_string())).unwrap(), "GABC");
+    }
+}
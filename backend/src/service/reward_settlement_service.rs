@@ -151,11 +151,13 @@ mod tests {
     use super::*;
 
     fn create_test_service() -> RewardSettlementService {
-        RewardSettlementService::new(DbPool)
+        RewardSettlementService::new(
+            DbPool::connect_lazy("postgres://localhost/arenax").unwrap(),
+        )
     }
 
-    #[test]
-    fn test_compute_reward_deterministic() {
+    #[tokio::test]
+    async fn test_compute_reward_deterministic() {
         let service = create_test_service();
         let result1 = service.compute_reward("1000").unwrap();
         let result2 = service.compute_reward("1000").unwrap();
@@ -163,8 +165,8 @@ mod tests {
         assert_eq!(result1, "1000");
     }
 
-    #[test]
-    fn test_compute_reward_invalid_amount() {
+    #[tokio::test]
+    async fn test_compute_reward_invalid_amount() {
         let service = create_test_service();
         let result = service.compute_reward("invalid");
         assert!(result.is_err());
@@ -0,0 +1,455 @@
+//! Search through Meilisearch.
+//!
+//! Tournaments, players and teams are copied into the `tournaments`,
+//! `players` and `teams` indexes by [`Meilisearch::sync`], which sends the
+//! rows updated since its previous run. Meilisearch handles typo tolerance and facet counts itself.
+
+use super::{group_facets, SearchError};
+use crate::config::MeilisearchConfig;
+use crate::models::{
+    stake_bucket, PaginationParams, PlayerHit, SearchFilters, SearchResults, TeamHit,
+    TournamentHit, TOURNAMENT_STATUS_NAMES,
+};
+use chrono::{DateTime, Duration, Utc};
+use reqwest::{Client, Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+use tracing::info;
+use uuid::Uuid;
+
+pub const TOURNAMENTS_INDEX: &str = "tournaments";
+pub const PLAYERS_INDEX: &str = "players";
+pub const TEAMS_INDEX: &str = "teams";
+
+const TOURNAMENT_FACETS: &[&str] = &["game", "region", "status", "stake"];
+const PLAYER_FACETS: &[&str] = &["region"];
+const TEAM_FACETS: &[&str] = &["game"];
+/// Documents sent per request.
+const SYNC_CHUNK: usize = 1000;
+/// Rows updated this long before the previous sync are sent again, so rows
+/// committed while it ran are not missed.
+const SYNC_OVERLAP_SECS: i64 = 60;
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SearchResult<T> {
+    hits: Vec<T>,
+    estimated_total_hits: i64,
+    #[serde(default)]
+    facet_distribution: HashMap<String, HashMap<String, i64>>,
+}
+
+#[derive(sqlx::FromRow)]
+struct TournamentRow {
+    id: Uuid,
+    name: String,
+    description: Option<String>,
+    game: String,
+    region: Option<String>,
+    status_code: i32,
+    entry_fee: i64,
+    entry_fee_currency: Option<String>,
+    prize_pool: i64,
+    start_time: DateTime<Utc>,
+}
+
+impl TournamentRow {
+    fn document(&self) -> Value {
+        let status = TOURNAMENT_STATUS_NAMES
+            .get(self.status_code as usize)
+            .copied()
+            .unwrap_or("draft");
+        json!({
+            "id": self.id,
+            "name": self.name,
+            "description": self.description,
+            "game": self.game,
+            "region": self.region,
+            "status": status,
+            "entry_fee": self.entry_fee,
+            "entry_fee_currency": self.entry_fee_currency,
+            "prize_pool": self.prize_pool,
+            "stake": stake_bucket(self.entry_fee),
+            "start_time": self.start_time,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct PlayerRow {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    avatar_url: Option<String>,
+    country_code: Option<String>,
+    skill_score: Option<i32>,
+    active: bool,
+}
+
+impl PlayerRow {
+    fn document(&self) -> Value {
+        json!({
+            "id": self.id,
+            "username": self.username,
+            "display_name": self.display_name,
+            "avatar_url": self.avatar_url,
+            "country_code": self.country_code,
+            "region": self.country_code,
+            "skill_score": self.skill_score,
+            "active": self.active,
+        })
+    }
+}
+
+#[derive(sqlx::FromRow)]
+struct TeamRow {
+    id: Uuid,
+    name: String,
+    tag: String,
+    game: Option<String>,
+    avatar_url: Option<String>,
+    member_count: i64,
+    disbanded: bool,
+}
+
+impl TeamRow {
+    fn document(&self) -> Value {
+        json!({
+            "id": self.id,
+            "name": self.name,
+            "tag": self.tag,
+            "game": self.game,
+            "avatar_url": self.avatar_url,
+            "member_count": self.member_count,
+            "disbanded": self.disbanded,
+        })
+    }
+}
+
+pub struct Meilisearch {
+    client: Client,
+    url: String,
+    api_key: Option<String>,
+    /// Start of the last successful sync; `None` until the indexes are set up.
+    synced_at: Mutex<Option<DateTime<Utc>>>,
+}
+
+impl Meilisearch {
+    pub fn new(config: &MeilisearchConfig) -> Self {
+        Self {
+            client: Client::new(),
+            url: config.url.clone(),
+            api_key: config.api_key.clone(),
+            synced_at: Mutex::new(None),
+        }
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        let request = self.client.request(method, format!("{}{}", self.url, path));
+        match &self.api_key {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    async fn send<T: DeserializeOwned>(&self, request: RequestBuilder) -> Result<T, SearchError> {
+        let response = request.send().await?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(SearchError::Meilisearch {
+                status: status.as_u16(),
+                body: response.text().await.unwrap_or_default(),
+            });
+        }
+        Ok(response.json().await?)
+    }
+
+    async fn search<T: DeserializeOwned>(
+        &self,
+        index: &str,
+        text: &str,
+        filter: Vec<Value>,
+        facets: &[&str],
+        pagination: &PaginationParams,
+    ) -> Result<SearchResults<T>, SearchError> {
+        let limit = pagination.resolved_limit();
+        let body = json!({
+            "q": text,
+            "filter": filter,
+            "facets": facets,
+            "limit": limit,
+            "offset": pagination.sql_offset(),
+            "showRankingScore": true,
+        });
+        let result: SearchResult<T> = self
+            .send(
+                self.request(Method::POST, &format!("/indexes/{}/search", index))
+                    .json(&body),
+            )
+            .await?;
+
+        let counts = result
+            .facet_distribution
+            .into_iter()
+            .flat_map(|(facet, values)| {
+                values
+                    .into_iter()
+                    .map(move |(value, count)| (facet.clone(), value, count))
+            });
+        Ok(SearchResults {
+            total: result.estimated_total_hits,
+            page: pagination.resolved_page(),
+            limit,
+            hits: result.hits,
+            facets: group_facets(counts),
+        })
+    }
+
+    pub(super) async fn tournaments(
+        &self,
+        text: &str,
+        filters: &SearchFilters,
+        pagination: &PaginationParams,
+    ) -> Result<SearchResults<TournamentHit>, SearchError> {
+        self.search(
+            TOURNAMENTS_INDEX,
+            text,
+            tournament_filter(filters),
+            TOURNAMENT_FACETS,
+            pagination,
+        )
+        .await
+    }
+
+    pub(super) async fn players(
+        &self,
+        text: &str,
+        filters: &SearchFilters,
+        pagination: &PaginationParams,
+    ) -> Result<SearchResults<PlayerHit>, SearchError> {
+        self.search(
+            PLAYERS_INDEX,
+            text,
+            player_filter(filters),
+            PLAYER_FACETS,
+            pagination,
+        )
+        .await
+    }
+
+    pub(super) async fn teams(
+        &self,
+        text: &str,
+        filters: &SearchFilters,
+        pagination: &PaginationParams,
+    ) -> Result<SearchResults<TeamHit>, SearchError> {
+        self.search(
+            TEAMS_INDEX,
+            text,
+            team_filter(filters),
+            TEAM_FACETS,
+            pagination,
+        )
+        .await
+    }
+
+    /// Send tournaments, players and teams changed since the last sync, or
+    /// all of them on the first.
+    pub(super) async fn sync(&self, db_pool: &PgPool) -> Result<(), SearchError> {
+        let mut synced_at = self.synced_at.lock().await;
+        if synced_at.is_none() {
+            self.configure_indexes().await?;
+        }
+        let started = Utc::now();
+        let since = synced_at
+            .map(|at| at - Duration::seconds(SYNC_OVERLAP_SECS))
+            .unwrap_or(DateTime::<Utc>::MIN_UTC);
+
+        let tournaments = sqlx::query_as::<_, TournamentRow>(
+            "SELECT id, name, description, LOWER(game) AS game, UPPER(region) AS region, \
+                    COALESCE(status, 0) AS status_code, COALESCE(entry_fee, 0) AS entry_fee, \
+                    entry_fee_currency, COALESCE(prize_pool, 0) AS prize_pool, start_time \
+             FROM tournaments WHERE updated_at > $1",
+        )
+        .bind(since)
+        .fetch_all(db_pool)
+        .await?;
+        let tournaments: Vec<Value> = tournaments.iter().map(TournamentRow::document).collect();
+        self.add_documents(TOURNAMENTS_INDEX, &tournaments).await?;
+
+        let players = sqlx::query_as::<_, PlayerRow>(
+            "SELECT id, username, display_name, avatar_url, UPPER(country_code) AS country_code, \
+                    skill_score, COALESCE(is_active, FALSE) AS active \
+             FROM users WHERE updated_at > $1",
+        )
+        .bind(since)
+        .fetch_all(db_pool)
+        .await?;
+        let players: Vec<Value> = players.iter().map(PlayerRow::document).collect();
+        self.add_documents(PLAYERS_INDEX, &players).await?;
+
+        let teams = sqlx::query_as::<_, TeamRow>(
+            "SELECT id, name, tag, LOWER(game) AS game, avatar_url, \
+                    (SELECT COUNT(*) FROM team_members m WHERE m.team_id = teams.id) \
+                        AS member_count, \
+                    disbanded_at IS NOT NULL AS disbanded \
+             FROM teams WHERE updated_at > $1",
+        )
+        .bind(since)
+        .fetch_all(db_pool)
+        .await?;
+        let teams: Vec<Value> = teams.iter().map(TeamRow::document).collect();
+        self.add_documents(TEAMS_INDEX, &teams).await?;
+
+        if !tournaments.is_empty() || !players.is_empty() || !teams.is_empty() {
+            info!(
+                tournaments = tournaments.len(),
+                players = players.len(),
+                teams = teams.len(),
+                "Synced search indexes"
+            );
+        }
+        *synced_at = Some(started);
+        Ok(())
+    }
+
+    async fn configure_indexes(&self) -> Result<(), SearchError> {
+        let indexes = [
+            (
+                TOURNAMENTS_INDEX,
+                json!({
+                    "searchableAttributes": ["name", "game", "description"],
+                    "filterableAttributes": ["game", "region", "status", "stake", "entry_fee"],
+                }),
+            ),
+            (
+                PLAYERS_INDEX,
+                json!({
+                    "searchableAttributes": ["username", "display_name"],
+                    "filterableAttributes": ["region", "active"],
+                }),
+            ),
+            (
+                TEAMS_INDEX,
+                json!({
+                    "searchableAttributes": ["name", "tag", "game"],
+                    "filterableAttributes": ["game", "disbanded"],
+                }),
+            ),
+        ];
+        for (index, settings) in indexes {
+            self.send::<Value>(
+                self.request(Method::PATCH, &format!("/indexes/{}/settings", index))
+                    .json(&settings),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+
+    async fn add_documents(&self, index: &str, documents: &[Value]) -> Result<(), SearchError> {
+        for chunk in documents.chunks(SYNC_CHUNK) {
+            self.send::<Value>(
+                self.request(
+                    Method::POST,
+                    &format!("/indexes/{}/documents?primaryKey=id", index),
+                )
+                .json(chunk),
+            )
+            .await?;
+        }
+        Ok(())
+    }
+}
+
+/// `value` as a Meilisearch filter string.
+fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Filter matching any of `values` of `attribute`; alternatives are given as
+/// an array.
+fn any_of<'a>(attribute: &str, values: impl IntoIterator<Item = &'a str>) -> Value {
+    Value::from(
+        values
+            .into_iter()
+            .map(|value| format!("{} = {}", attribute, quote(value)))
+            .collect::<Vec<_>>(),
+    )
+}
+
+fn tournament_filter(filters: &SearchFilters) -> Vec<Value> {
+    let mut filter = vec![json!("status != \"draft\"")];
+    if !filters.games.is_empty() {
+        filter.push(any_of("game", filters.games.iter().map(String::as_str)));
+    }
+    if !filters.regions.is_empty() {
+        filter.push(any_of("region", filters.regions.iter().map(String::as_str)));
+    }
+    if !filters.statuses.is_empty() {
+        let names = filters
+            .statuses
+            .iter()
+            .filter_map(|code| TOURNAMENT_STATUS_NAMES.get(*code as usize).copied());
+        filter.push(any_of("status", names));
+    }
+    if let Some(min) = filters.stake_min {
+        filter.push(json!(format!("entry_fee >= {}", min)));
+    }
+    if let Some(max) = filters.stake_max {
+        filter.push(json!(format!("entry_fee <= {}", max)));
+    }
+    filter
+}
+
+fn player_filter(filters: &SearchFilters) -> Vec<Value> {
+    let mut filter = vec![json!("active = true")];
+    if !filters.regions.is_empty() {
+        filter.push(any_of("region", filters.regions.iter().map(String::as_str)));
+    }
+    filter
+}
+
+fn team_filter(filters: &SearchFilters) -> Vec<Value> {
+    let mut filter = vec![json!("disbanded = false")];
+    if !filters.games.is_empty() {
+        filter.push(any_of("game", filters.games.iter().map(String::as_str)));
+    }
+    filter
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tournament_filter() {
+        let filters: SearchFilters = "game:fifa|pe\"s,status:completed,stake:100.."
+            .parse()
+            .unwrap();
+        assert_eq!(
+            Value::from(tournament_filter(&filters)),
+            json!([
+                "status != \"draft\"",
+                ["game = \"fifa\"", "game = \"pe\\\"s\""],
+                ["status = \"completed\""],
+                "entry_fee >= 100",
+            ])
+        );
+        assert_eq!(
+            Value::from(player_filter(&SearchFilters::default())),
+            json!(["active = true"])
+        );
+        assert_eq!(
+            Value::from(team_filter(&filters)),
+            json!([
+                "disbanded = false",
+                ["game = \"fifa\"", "game = \"pe\\\"s\""]
+            ])
+        );
+    }
+}
@@ -0,0 +1,155 @@
+//! Search over tournaments, players and teams.
+//!
+//! By default queries run against Postgres full-text indexes on tournament
+//! names, games and descriptions, on player names and on team names and tags;
+//! trigram similarity of names catches misspelled queries. With Meilisearch
+//! configured, queries go to its `tournaments`, `players` and `teams` indexes
+//! instead, which [`SearchService::run`] keeps in sync with Postgres.
+//!
+//! Facet counts cover every hit of the query and filters, not only the page
+//! returned. Draft tournaments, deactivated players and disbanded teams are
+//! never found.
+
+pub mod meilisearch;
+mod postgres;
+
+use crate::api_error::ApiError;
+use crate::config::SearchConfig;
+use crate::models::{FacetCount, PaginationParams, SearchFilters, SearchKind, SearchResponse};
+use meilisearch::Meilisearch;
+use sqlx::PgPool;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tracing::{error, info};
+
+const SYNC_INTERVAL_SECS: u64 = 60;
+
+#[derive(Debug, Error)]
+pub enum SearchError {
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Meilisearch request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("Meilisearch rejected request with status {status}: {body}")]
+    Meilisearch { status: u16, body: String },
+}
+
+impl From<SearchError> for ApiError {
+    fn from(err: SearchError) -> Self {
+        match err {
+            SearchError::DatabaseError(e) => ApiError::DatabaseError(e),
+            SearchError::Request(_) | SearchError::Meilisearch { .. } => {
+                ApiError::internal_error(err.to_string())
+            }
+        }
+    }
+}
+
+pub struct SearchService {
+    db_pool: PgPool,
+    meilisearch: Option<Meilisearch>,
+}
+
+impl SearchService {
+    pub fn new(db_pool: PgPool, config: &SearchConfig) -> Self {
+        Self {
+            db_pool,
+            meilisearch: config.meilisearch.as_ref().map(Meilisearch::new),
+        }
+    }
+
+    /// Spawn the worker copying changed tournaments, players and teams to
+    /// Meilisearch. Does nothing when searching Postgres.
+    pub fn run(self: Arc<Self>) {
+        if self.meilisearch.is_none() {
+            return;
+        }
+        tokio::spawn(async move {
+            info!("Search index sync started");
+            let mut ticker = tokio::time::interval(Duration::from_secs(SYNC_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+                if let Some(meilisearch) = &self.meilisearch {
+                    if let Err(e) = meilisearch.sync(&self.db_pool).await {
+                        error!(error = %e, "Search index sync failed");
+                    }
+                }
+            }
+        });
+    }
+
+    /// Search `kind`, or every kind when `None`. Each kind is paged on its
+    /// own.
+    pub async fn search(
+        &self,
+        text: &str,
+        kind: Option<SearchKind>,
+        filters: &SearchFilters,
+        pagination: &PaginationParams,
+    ) -> Result<SearchResponse, SearchError> {
+        let wants = |wanted: SearchKind| kind.is_none_or(|kind| kind == wanted);
+        let mut response = SearchResponse {
+            query: text.to_string(),
+            tournaments: None,
+            players: None,
+            teams: None,
+        };
+
+        if wants(SearchKind::Tournament) {
+            response.tournaments = Some(match &self.meilisearch {
+                Some(meilisearch) => meilisearch.tournaments(text, filters, pagination).await?,
+                None => postgres::tournaments(&self.db_pool, text, filters, pagination).await?,
+            });
+        }
+        if wants(SearchKind::Player) {
+            response.players = Some(match &self.meilisearch {
+                Some(meilisearch) => meilisearch.players(text, filters, pagination).await?,
+                None => postgres::players(&self.db_pool, text, filters, pagination).await?,
+            });
+        }
+        if wants(SearchKind::Team) {
+            response.teams = Some(match &self.meilisearch {
+                Some(meilisearch) => meilisearch.teams(text, filters, pagination).await?,
+                None => postgres::teams(&self.db_pool, text, filters, pagination).await?,
+            });
+        }
+        Ok(response)
+    }
+}
+
+/// Facet counts grouped by facet, largest first.
+fn group_facets(
+    counts: impl IntoIterator<Item = (String, String, i64)>,
+) -> BTreeMap<String, Vec<FacetCount>> {
+    let mut facets: BTreeMap<String, Vec<FacetCount>> = BTreeMap::new();
+    for (facet, value, count) in counts {
+        facets
+            .entry(facet)
+            .or_default()
+            .push(FacetCount { value, count });
+    }
+    for counts in facets.values_mut() {
+        counts.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    }
+    facets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_facets() {
+        let facets = group_facets(vec![
+            ("game".to_string(), "pes".to_string(), 2),
+            ("region".to_string(), "NGA".to_string(), 1),
+            ("game".to_string(), "fifa".to_string(), 5),
+            ("game".to_string(), "cod".to_string(), 2),
+        ]);
+        let games: Vec<_> = facets["game"].iter().map(|f| f.value.as_str()).collect();
+        assert_eq!(games, vec!["fifa", "cod", "pes"]);
+        assert_eq!(facets["region"][0].count, 1);
+    }
+}
@@ -0,0 +1,250 @@
+//! Search with Postgres full-text and trigram indexes.
+
+use super::{group_facets, SearchError};
+use crate::models::{
+    PaginationParams, PlayerHit, SearchFilters, SearchResults, TeamHit, TournamentHit,
+    STAKE_BUCKETS, TOURNAMENT_STATUS_NAMES,
+};
+use sqlx::postgres::PgArguments;
+use sqlx::query::QueryAs;
+use sqlx::{PgPool, Postgres};
+
+/// Tournaments matching `$1` and the filters in `$2`..`$6`. `<%` matches
+/// names with words similar to the query, so misspellings are still found.
+const TOURNAMENT_HITS: &str = "\
+    SELECT t.id, t.name, t.game, t.region, COALESCE(t.status, 0) AS status_code, \
+           COALESCE(t.entry_fee, 0) AS entry_fee, t.entry_fee_currency, \
+           COALESCE(t.prize_pool, 0) AS prize_pool, t.start_time, \
+           (ts_rank(t.search_vector, q.query) + word_similarity($1, t.name))::float8 AS score \
+    FROM tournaments t, \
+         (websearch_to_tsquery('simple', $1) || websearch_to_tsquery('english', $1)) \
+             AS q(query) \
+    WHERE COALESCE(t.status, 0) <> 0 \
+      AND ($1 = '' OR t.search_vector @@ q.query OR $1 <% t.name) \
+      AND (cardinality($2::text[]) = 0 OR LOWER(t.game) = ANY($2)) \
+      AND (cardinality($3::text[]) = 0 OR UPPER(t.region) = ANY($3)) \
+      AND (cardinality($4::int4[]) = 0 OR COALESCE(t.status, 0) = ANY($4)) \
+      AND ($5::int8 IS NULL OR COALESCE(t.entry_fee, 0) >= $5) \
+      AND ($6::int8 IS NULL OR COALESCE(t.entry_fee, 0) <= $6)";
+
+/// Active players matching `$1` from the countries in `$2`.
+const PLAYER_HITS: &str = "\
+    SELECT u.id, u.username, u.display_name, u.avatar_url, u.country_code, u.skill_score, \
+           (ts_rank(u.search_vector, q.query) + word_similarity($1, u.username))::float8 AS score \
+    FROM users u, websearch_to_tsquery('simple', $1) AS q(query) \
+    WHERE u.is_active = TRUE \
+      AND ($1 = '' OR u.search_vector @@ q.query OR $1 <% u.username) \
+      AND (cardinality($2::text[]) = 0 OR UPPER(u.country_code) = ANY($2))";
+
+/// Teams not disbanded matching `$1` that play the games in `$2`.
+const TEAM_HITS: &str = "\
+    SELECT tm.id, tm.name, tm.tag, tm.game, tm.avatar_url, \
+           (SELECT COUNT(*) FROM team_members m WHERE m.team_id = tm.id) AS member_count, \
+           (ts_rank(tm.search_vector, q.query) + word_similarity($1, tm.name))::float8 AS score \
+    FROM teams tm, websearch_to_tsquery('simple', $1) AS q(query) \
+    WHERE tm.disbanded_at IS NULL \
+      AND ($1 = '' OR tm.search_vector @@ q.query OR $1 <% tm.name) \
+      AND (cardinality($2::text[]) = 0 OR LOWER(tm.game) = ANY($2))";
+
+/// SQL naming the [`STAKE_BUCKETS`] entry of `entry_fee`.
+fn stake_bucket_sql() -> String {
+    let mut sql = String::from("CASE");
+    for bucket in STAKE_BUCKETS {
+        match bucket.max {
+            Some(max) => sql.push_str(&format!(
+                " WHEN entry_fee <= {} THEN '{}'",
+                max, bucket.name
+            )),
+            None => sql.push_str(&format!(" ELSE '{}'", bucket.name)),
+        }
+    }
+    sql.push_str(" END");
+    sql
+}
+
+fn bind_tournament_filters<'q, O>(
+    query: QueryAs<'q, Postgres, O, PgArguments>,
+    text: &'q str,
+    filters: &'q SearchFilters,
+) -> QueryAs<'q, Postgres, O, PgArguments> {
+    query
+        .bind(text)
+        .bind(&filters.games)
+        .bind(&filters.regions)
+        .bind(&filters.statuses)
+        .bind(filters.stake_min)
+        .bind(filters.stake_max)
+}
+
+pub(super) async fn tournaments(
+    db_pool: &PgPool,
+    text: &str,
+    filters: &SearchFilters,
+    pagination: &PaginationParams,
+) -> Result<SearchResults<TournamentHit>, SearchError> {
+    let limit = pagination.resolved_limit();
+    let statuses: Vec<&str> = TOURNAMENT_STATUS_NAMES.to_vec();
+
+    let page_sql = format!(
+        "WITH hits AS ({}) \
+         SELECT id, name, game, region, ($7::text[])[status_code + 1] AS status, entry_fee, \
+                entry_fee_currency, prize_pool, start_time, score \
+         FROM hits \
+         ORDER BY score DESC, start_time DESC \
+         LIMIT $8 OFFSET $9",
+        TOURNAMENT_HITS
+    );
+    let hits =
+        bind_tournament_filters(sqlx::query_as::<_, TournamentHit>(&page_sql), text, filters)
+            .bind(&statuses)
+            .bind(limit)
+            .bind(pagination.sql_offset())
+            .fetch_all(db_pool)
+            .await?;
+
+    let count_sql = format!(
+        "WITH hits AS ({}) SELECT COUNT(*) FROM hits",
+        TOURNAMENT_HITS
+    );
+    let (total,) = bind_tournament_filters(sqlx::query_as::<_, (i64,)>(&count_sql), text, filters)
+        .fetch_one(db_pool)
+        .await?;
+
+    let facets_sql = format!(
+        "WITH hits AS ({}) \
+         SELECT 'game', LOWER(game), COUNT(*) FROM hits GROUP BY 2 \
+         UNION ALL \
+         SELECT 'region', UPPER(region), COUNT(*) FROM hits WHERE region IS NOT NULL GROUP BY 2 \
+         UNION ALL \
+         SELECT 'status', ($7::text[])[status_code + 1], COUNT(*) FROM hits GROUP BY 2 \
+         UNION ALL \
+         SELECT 'stake', {}, COUNT(*) FROM hits GROUP BY 2",
+        TOURNAMENT_HITS,
+        stake_bucket_sql()
+    );
+    let counts = bind_tournament_filters(
+        sqlx::query_as::<_, (String, String, i64)>(&facets_sql),
+        text,
+        filters,
+    )
+    .bind(&statuses)
+    .fetch_all(db_pool)
+    .await?;
+
+    Ok(SearchResults {
+        total,
+        page: pagination.resolved_page(),
+        limit,
+        hits,
+        facets: group_facets(counts),
+    })
+}
+
+pub(super) async fn players(
+    db_pool: &PgPool,
+    text: &str,
+    filters: &SearchFilters,
+    pagination: &PaginationParams,
+) -> Result<SearchResults<PlayerHit>, SearchError> {
+    let limit = pagination.resolved_limit();
+
+    let page_sql = format!(
+        "WITH hits AS ({}) SELECT * FROM hits ORDER BY score DESC, username LIMIT $3 OFFSET $4",
+        PLAYER_HITS
+    );
+    let hits = sqlx::query_as::<_, PlayerHit>(&page_sql)
+        .bind(text)
+        .bind(&filters.regions)
+        .bind(limit)
+        .bind(pagination.sql_offset())
+        .fetch_all(db_pool)
+        .await?;
+
+    let count_sql = format!("WITH hits AS ({}) SELECT COUNT(*) FROM hits", PLAYER_HITS);
+    let (total,) = sqlx::query_as::<_, (i64,)>(&count_sql)
+        .bind(text)
+        .bind(&filters.regions)
+        .fetch_one(db_pool)
+        .await?;
+
+    let facets_sql = format!(
+        "WITH hits AS ({}) \
+         SELECT 'region', UPPER(country_code), COUNT(*) FROM hits \
+         WHERE country_code IS NOT NULL GROUP BY 2",
+        PLAYER_HITS
+    );
+    let counts = sqlx::query_as::<_, (String, String, i64)>(&facets_sql)
+        .bind(text)
+        .bind(&filters.regions)
+        .fetch_all(db_pool)
+        .await?;
+
+    Ok(SearchResults {
+        total,
+        page: pagination.resolved_page(),
+        limit,
+        hits,
+        facets: group_facets(counts),
+    })
+}
+
+pub(super) async fn teams(
+    db_pool: &PgPool,
+    text: &str,
+    filters: &SearchFilters,
+    pagination: &PaginationParams,
+) -> Result<SearchResults<TeamHit>, SearchError> {
+    let limit = pagination.resolved_limit();
+
+    let page_sql = format!(
+        "WITH hits AS ({}) SELECT * FROM hits ORDER BY score DESC, name LIMIT $3 OFFSET $4",
+        TEAM_HITS
+    );
+    let hits = sqlx::query_as::<_, TeamHit>(&page_sql)
+        .bind(text)
+        .bind(&filters.games)
+        .bind(limit)
+        .bind(pagination.sql_offset())
+        .fetch_all(db_pool)
+        .await?;
+
+    let count_sql = format!("WITH hits AS ({}) SELECT COUNT(*) FROM hits", TEAM_HITS);
+    let (total,) = sqlx::query_as::<_, (i64,)>(&count_sql)
+        .bind(text)
+        .bind(&filters.games)
+        .fetch_one(db_pool)
+        .await?;
+
+    let facets_sql = format!(
+        "WITH hits AS ({}) \
+         SELECT 'game', LOWER(game), COUNT(*) FROM hits WHERE game IS NOT NULL GROUP BY 2",
+        TEAM_HITS
+    );
+    let counts = sqlx::query_as::<_, (String, String, i64)>(&facets_sql)
+        .bind(text)
+        .bind(&filters.games)
+        .fetch_all(db_pool)
+        .await?;
+
+    Ok(SearchResults {
+        total,
+        page: pagination.resolved_page(),
+        limit,
+        hits,
+        facets: group_facets(counts),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stake_bucket_sql() {
+        assert_eq!(
+            stake_bucket_sql(),
+            "CASE WHEN entry_fee <= 0 THEN 'free' WHEN entry_fee <= 100000 THEN 'low' \
+             WHEN entry_fee <= 1000000 THEN 'medium' ELSE 'high' END"
+        );
+    }
+}
@@ -0,0 +1,192 @@
+//! Text messages over SMS and WhatsApp.
+//!
+//! Each configured provider — Twilio, Termii, or the log when none is — can
+//! serve any region; `SMS_ROUTES` picks one by the recipient's calling code
+//! (the longest matching prefix wins) and the rest go to the default.
+
+use crate::config::{SmsConfig, SmsProviderKind, TermiiConfig, TwilioConfig};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use tracing::info;
+
+#[derive(Debug, Error)]
+pub enum SmsError {
+    #[error("{0:?} does not deliver over WhatsApp")]
+    UnsupportedChannel(SmsProviderKind),
+    #[error("SMS request failed: {0}")]
+    Request(#[from] reqwest::Error),
+    #[error("{provider:?} rejected message with status {status}: {body}")]
+    Rejected {
+        provider: SmsProviderKind,
+        status: u16,
+        body: String,
+    },
+}
+
+/// How a message reaches the phone.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmsChannel {
+    #[default]
+    Sms,
+    #[serde(rename = "whatsapp")]
+    WhatsApp,
+}
+
+enum Provider {
+    Log,
+    Twilio(TwilioConfig),
+    Termii(TermiiConfig),
+}
+
+impl Provider {
+    fn kind(&self) -> SmsProviderKind {
+        match self {
+            Provider::Log => SmsProviderKind::Log,
+            Provider::Twilio(_) => SmsProviderKind::Twilio,
+            Provider::Termii(_) => SmsProviderKind::Termii,
+        }
+    }
+}
+
+/// Sends text messages through the provider routed for each number.
+pub struct SmsGateway {
+    client: Client,
+    providers: Vec<Provider>,
+    default_provider: SmsProviderKind,
+    /// Calling codes, longest first.
+    routes: Vec<(String, SmsProviderKind)>,
+}
+
+impl SmsGateway {
+    pub fn new(config: &SmsConfig) -> Self {
+        let mut providers = vec![Provider::Log];
+        providers.extend(config.twilio.clone().map(Provider::Twilio));
+        providers.extend(config.termii.clone().map(Provider::Termii));
+        let mut routes = config.routes.clone();
+        routes.sort_by_key(|(code, _)| std::cmp::Reverse(code.len()));
+
+        Self {
+            client: Client::new(),
+            providers,
+            default_provider: config.default_provider,
+            routes,
+        }
+    }
+
+    /// Provider serving `phone`, an E.164 number.
+    pub fn provider_for(&self, phone: &str) -> SmsProviderKind {
+        let digits = phone.trim_start_matches('+');
+        self.routes
+            .iter()
+            .find(|(code, _)| digits.starts_with(code.as_str()))
+            .map(|(_, provider)| *provider)
+            .unwrap_or(self.default_provider)
+    }
+
+    /// Send `text` to `to`, an E.164 number.
+    pub async fn send(&self, to: &str, channel: SmsChannel, text: &str) -> Result<(), SmsError> {
+        let kind = self.provider_for(to);
+        // `SmsConfig` only routes to configured providers.
+        let provider = self
+            .providers
+            .iter()
+            .find(|provider| provider.kind() == kind)
+            .unwrap_or(&Provider::Log);
+
+        let response = match provider {
+            Provider::Log => {
+                info!(to = %to, channel = ?channel, text = %text, "Text message (not sent)");
+                return Ok(());
+            }
+            Provider::Twilio(twilio) => {
+                let (from, to) = match channel {
+                    SmsChannel::Sms => (twilio.from.clone(), to.to_string()),
+                    SmsChannel::WhatsApp => {
+                        let from = twilio
+                            .whatsapp_from
+                            .as_ref()
+                            .ok_or(SmsError::UnsupportedChannel(kind))?;
+                        (format!("whatsapp:{}", from), format!("whatsapp:{}", to))
+                    }
+                };
+                self.client
+                    .post(format!(
+                        "https://api.twilio.com/2010-04-01/Accounts/{}/Messages.json",
+                        twilio.account_sid
+                    ))
+                    .basic_auth(&twilio.account_sid, Some(&twilio.auth_token))
+                    .form(&[("To", to.as_str()), ("From", from.as_str()), ("Body", text)])
+                    .send()
+                    .await?
+            }
+            Provider::Termii(termii) => {
+                // "dnd" reaches numbers on Do-Not-Disturb lists, which
+                // transactional codes are allowed to.
+                let channel = match channel {
+                    SmsChannel::Sms => "dnd",
+                    SmsChannel::WhatsApp => "whatsapp",
+                };
+                self.client
+                    .post(format!("{}/api/sms/send", termii.base_url))
+                    .json(&serde_json::json!({
+                        "api_key": termii.api_key,
+                        "to": to.trim_start_matches('+'),
+                        "from": termii.sender_id,
+                        "sms": text,
+                        "type": "plain",
+                        "channel": channel,
+                    }))
+                    .send()
+                    .await?
+            }
+        };
+
+        let status = response.status();
+        if !status.is_success() {
+            let body = response.text().await.unwrap_or_default();
+            return Err(SmsError::Rejected {
+                provider: kind,
+                status: status.as_u16(),
+                body,
+            });
+        }
+
+        info!(provider = ?kind, channel = ?channel, "Text message sent");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_provider_for_routes_longest_prefix() {
+        let config = SmsConfig {
+            twilio: None,
+            termii: None,
+            default_provider: SmsProviderKind::Twilio,
+            routes: SmsConfig::parse_routes("2=termii, +234=log, 23=twilio").unwrap(),
+            phone_verification_required: false,
+        };
+        let gateway = SmsGateway::new(&config);
+
+        assert_eq!(gateway.provider_for("+2348012345678"), SmsProviderKind::Log);
+        assert_eq!(
+            gateway.provider_for("+233201234567"),
+            SmsProviderKind::Twilio
+        );
+        assert_eq!(
+            gateway.provider_for("+27821234567"),
+            SmsProviderKind::Termii
+        );
+        assert_eq!(
+            gateway.provider_for("+14155550100"),
+            SmsProviderKind::Twilio
+        );
+        assert!(SmsConfig::parse_routes("nigeria=termii").is_err());
+        assert!(SmsConfig::parse_routes("234=carrier-pigeon").is_err());
+    }
+}
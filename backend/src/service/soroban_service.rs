@@ -701,8 +701,9 @@ mod tests {
         let service = SorobanService::new(network);
 
         // Valid secret key format
-        let result =
-            service.secret_to_public_key("SAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA");
+        let secret =
+            crate::service::stellar_service::stellar_strkey_encode(18 << 3, &[0; 32]).unwrap();
+        let result = service.secret_to_public_key(&secret);
         assert!(result.is_ok());
 
         // Invalid secret key format
@@ -0,0 +1,383 @@
+//! Spectator mode for live matches.
+//!
+//! A spectator view is assembled from the match, the live scores its players
+//! post after each round, the contract events the chain indexer picked up for
+//! it and its dispute, all cut off at the broadcast delay so nobody can watch
+//! a match to gain an edge in it. Live updates reach spectators on the
+//! `spectate:{id}` gateway topic, which replays the match channel behind the
+//! same delay (see [`GatewayHub`](crate::http::ws::GatewayHub)).
+
+use crate::api_error::ApiError;
+use crate::config::SpectatorConfig;
+use crate::db::DbPool;
+use crate::models::{
+    LiveMatch, LiveScore, LiveScoreReport, ReportLiveScoreRequest, SpectatorDispute,
+    SpectatorPlayer, SpectatorView, TimelineEntry,
+};
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
+use crate::service::matchmaking::match_key;
+use chrono::{DateTime, Duration, Utc};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+const DEFAULT_LIVE_LIMIT: i64 = 20;
+const MAX_LIVE_LIMIT: i64 = 100;
+/// `matches.status` of a match being played.
+const STATUS_IN_PROGRESS: i32 = 2;
+
+#[derive(Debug, FromRow)]
+struct MatchRow {
+    id: Uuid,
+    tournament_id: Option<Uuid>,
+    game_mode: String,
+    map: Option<String>,
+    status: i32,
+    player1_id: Option<Uuid>,
+    player2_id: Option<Uuid>,
+    winner_id: Option<Uuid>,
+    started_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, FromRow)]
+struct ChainRow {
+    at: DateTime<Utc>,
+    contract: String,
+    action: String,
+    tx_hash: String,
+}
+
+#[derive(Debug, FromRow)]
+struct DisputeRow {
+    id: Uuid,
+    status: i32,
+    created_at: DateTime<Utc>,
+    resolved_at: Option<DateTime<Utc>>,
+}
+
+pub struct SpectatorService {
+    db_pool: DbPool,
+    delay_secs: u64,
+    event_bus: Option<EventBus>,
+}
+
+impl SpectatorService {
+    pub fn new(db_pool: DbPool, config: &SpectatorConfig) -> Self {
+        Self {
+            db_pool,
+            delay_secs: config.broadcast_delay_secs,
+            event_bus: None,
+        }
+    }
+
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Matches in progress, most recently started first.
+    pub async fn live_matches(
+        &self,
+        tournament_id: Option<Uuid>,
+        limit: Option<i64>,
+    ) -> Result<Vec<LiveMatch>, ApiError> {
+        let limit = limit.unwrap_or(DEFAULT_LIVE_LIMIT).clamp(1, MAX_LIVE_LIMIT);
+        let matches = sqlx::query_as::<_, LiveMatch>(
+            "SELECT m.id AS match_id, m.tournament_id, m.game_mode, \
+                    p1.username AS player1_username, p2.username AS player2_username, \
+                    m.started_at \
+             FROM matches m \
+             LEFT JOIN users p1 ON p1.id = m.player1_id \
+             LEFT JOIN users p2 ON p2.id = m.player2_id \
+             WHERE m.status = $1 AND ($2::uuid IS NULL OR m.tournament_id = $2) \
+             ORDER BY m.started_at DESC NULLS LAST \
+             LIMIT $3",
+        )
+        .bind(STATUS_IN_PROGRESS)
+        .bind(tournament_id)
+        .bind(limit)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(matches)
+    }
+
+    /// The match as it stood the broadcast delay ago.
+    pub async fn view(&self, match_id: Uuid) -> Result<SpectatorView, ApiError> {
+        let m = self.load_match(match_id).await?;
+        let as_of = Utc::now() - Duration::seconds(self.delay_secs as i64);
+
+        let players = sqlx::query_as::<_, SpectatorPlayer>(
+            "SELECT id, username, avatar_url FROM users WHERE id = ANY($1)",
+        )
+        .bind(
+            [m.player1_id, m.player2_id]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>(),
+        )
+        .fetch_all(&self.db_pool)
+        .await?;
+        let player = |id: Option<Uuid>| players.iter().find(|p| Some(p.id) == id).cloned();
+
+        let reports = self.reports(match_id, as_of).await?;
+        let chain = sqlx::query_as::<_, ChainRow>(
+            "SELECT e.ledger_closed_at AS at, e.contract_kind AS contract, x.action, e.tx_hash \
+             FROM ( \
+                 SELECT event_id, action FROM chain_match_events WHERE match_id = $1 \
+                 UNION ALL \
+                 SELECT event_id, action FROM chain_escrow_events WHERE match_id = $1 \
+             ) x \
+             JOIN chain_events e ON e.event_id = x.event_id \
+             WHERE e.ledger_closed_at <= $2 \
+             ORDER BY e.ledger, e.event_id",
+        )
+        .bind(hex::encode(match_key(match_id)))
+        .bind(as_of)
+        .fetch_all(&self.db_pool)
+        .await?;
+        let dispute = sqlx::query_as::<_, DisputeRow>(
+            "SELECT id, status, created_at, resolved_at FROM match_disputes \
+             WHERE match_id = $1 AND created_at <= $2 \
+             ORDER BY created_at DESC \
+             LIMIT 1",
+        )
+        .bind(match_id)
+        .bind(as_of)
+        .fetch_optional(&self.db_pool)
+        .await?;
+
+        let completed_at = m.completed_at.filter(|at| *at <= as_of);
+        let winner_id = completed_at.and(m.winner_id);
+        let status = match m.completed_at {
+            Some(at) if at > as_of => "in_progress",
+            _ => match_status(m.status),
+        };
+
+        let mut timeline: Vec<TimelineEntry> = Vec::new();
+        if let Some(at) = m.started_at.filter(|at| *at <= as_of) {
+            timeline.push(TimelineEntry::Started { at });
+        }
+        timeline.extend(reports.iter().map(|r| TimelineEntry::RoundScore {
+            at: r.created_at,
+            round: r.round,
+            player1_score: r.player1_score,
+            player2_score: r.player2_score,
+            reported_by: r.reported_by,
+        }));
+        timeline.extend(chain.into_iter().map(|c| TimelineEntry::Chain {
+            at: c.at,
+            contract: c.contract,
+            action: c.action,
+            tx_hash: c.tx_hash,
+        }));
+        if let Some(d) = &dispute {
+            timeline.push(TimelineEntry::DisputeOpened {
+                at: d.created_at,
+                dispute_id: d.id,
+            });
+        }
+        if let Some(at) = completed_at {
+            timeline.push(TimelineEntry::Completed { at, winner_id });
+        }
+        timeline.sort_by_key(TimelineEntry::at);
+
+        Ok(SpectatorView {
+            match_id: m.id,
+            tournament_id: m.tournament_id,
+            game_mode: m.game_mode,
+            map: m.map,
+            status: status.to_string(),
+            player1: player(m.player1_id),
+            player2: player(m.player2_id),
+            score: live_score(&reports, m.player1_id, m.player2_id),
+            winner_id,
+            timeline,
+            dispute: dispute.map(|d| SpectatorDispute {
+                id: d.id,
+                status: dispute_status(d.status).to_string(),
+                opened_at: d.created_at,
+                resolved_at: d.resolved_at.filter(|at| *at <= as_of),
+            }),
+            delay_secs: self.delay_secs,
+            as_of,
+        })
+    }
+
+    /// Post the score after a round of a match `user_id` is playing.
+    pub async fn report_score(
+        &self,
+        user_id: Uuid,
+        match_id: Uuid,
+        req: &ReportLiveScoreRequest,
+    ) -> Result<LiveScore, ApiError> {
+        let m = self.load_match(match_id).await?;
+        let (player1_score, player2_score) = if m.player1_id == Some(user_id) {
+            (req.score, req.opponent_score)
+        } else if m.player2_id == Some(user_id) {
+            (req.opponent_score, req.score)
+        } else {
+            return Err(ApiError::Forbidden);
+        };
+        if m.status != STATUS_IN_PROGRESS {
+            return Err(ApiError::bad_request(
+                "Live scores can only be posted while the match is in progress",
+            ));
+        }
+
+        sqlx::query(
+            "INSERT INTO match_live_scores \
+                 (match_id, reported_by, round, player1_score, player2_score) \
+             VALUES ($1, $2, $3, $4, $5)",
+        )
+        .bind(match_id)
+        .bind(user_id)
+        .bind(req.round)
+        .bind(player1_score)
+        .bind(player2_score)
+        .execute(&self.db_pool)
+        .await?;
+
+        let reports = self.reports(match_id, Utc::now()).await?;
+        let score = live_score(&reports, m.player1_id, m.player2_id)
+            .ok_or_else(|| ApiError::internal_error("Live score was not recorded"))?;
+
+        if let Some(bus) = &self.event_bus {
+            let event = RealtimeEvent::LiveScoreUpdate {
+                match_id,
+                round: score.round,
+                player1_score: score.player1_score,
+                player2_score: score.player2_score,
+                confirmed: score.confirmed,
+                timestamp: score.updated_at.to_rfc3339(),
+            };
+            bus.publish_to_match(match_id, &event).await;
+        }
+        Ok(score)
+    }
+
+    async fn load_match(&self, match_id: Uuid) -> Result<MatchRow, ApiError> {
+        sqlx::query_as::<_, MatchRow>(
+            "SELECT id, tournament_id, game_mode, map, status, player1_id, player2_id, \
+                    winner_id, started_at, completed_at \
+             FROM matches WHERE id = $1",
+        )
+        .bind(match_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(ApiError::NotFound)
+    }
+
+    async fn reports(
+        &self,
+        match_id: Uuid,
+        until: DateTime<Utc>,
+    ) -> Result<Vec<LiveScoreReport>, ApiError> {
+        let reports = sqlx::query_as::<_, LiveScoreReport>(
+            "SELECT reported_by, round, player1_score, player2_score, created_at \
+             FROM match_live_scores \
+             WHERE match_id = $1 AND created_at <= $2 \
+             ORDER BY created_at, id",
+        )
+        .bind(match_id)
+        .bind(until)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(reports)
+    }
+}
+
+/// Score after the latest reported round, from `reports` oldest first. It is
+/// confirmed when each player's latest report for the round says the same.
+fn live_score(
+    reports: &[LiveScoreReport],
+    player1_id: Option<Uuid>,
+    player2_id: Option<Uuid>,
+) -> Option<LiveScore> {
+    let round = reports.iter().map(|r| r.round).max()?;
+    let in_round: Vec<&LiveScoreReport> = reports.iter().filter(|r| r.round == round).collect();
+    let latest = *in_round.last()?;
+    let latest_by = |player: Option<Uuid>| {
+        in_round
+            .iter()
+            .rev()
+            .find(|r| Some(r.reported_by) == player)
+            .map(|r| (r.player1_score, r.player2_score))
+    };
+    let confirmed = matches!(
+        (latest_by(player1_id), latest_by(player2_id)),
+        (Some(a), Some(b)) if a == b
+    );
+    Some(LiveScore {
+        round,
+        player1_score: latest.player1_score,
+        player2_score: latest.player2_score,
+        confirmed,
+        updated_at: latest.created_at,
+    })
+}
+
+/// Name of a `matches.status` code.
+fn match_status(code: i32) -> &'static str {
+    match code {
+        0 => "pending",
+        1 => "scheduled",
+        2 => "in_progress",
+        3 => "completed",
+        4 => "disputed",
+        5 => "cancelled",
+        6 => "abandoned",
+        _ => "unknown",
+    }
+}
+
+/// Name of a `match_disputes.status` code.
+fn dispute_status(code: i32) -> &'static str {
+    match code {
+        0 => "pending",
+        1 => "under_review",
+        2 => "resolved",
+        3 => "rejected",
+        _ => "unknown",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(by: Uuid, round: i32, score: (i32, i32), secs: i64) -> LiveScoreReport {
+        LiveScoreReport {
+            reported_by: by,
+            round,
+            player1_score: score.0,
+            player2_score: score.1,
+            created_at: DateTime::from_timestamp(secs, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_live_score_is_confirmed_once_both_players_agree() {
+        let (p1, p2) = (Uuid::new_v4(), Uuid::new_v4());
+        let mut reports = vec![
+            report(p1, 1, (1, 0), 10),
+            report(p2, 1, (1, 0), 11),
+            report(p1, 2, (2, 0), 20),
+        ];
+        let score = live_score(&reports, Some(p1), Some(p2)).unwrap();
+        assert_eq!(
+            (score.round, score.player1_score, score.player2_score),
+            (2, 2, 0)
+        );
+        assert!(!score.confirmed);
+
+        reports.push(report(p2, 2, (1, 1), 21));
+        let disputed = live_score(&reports, Some(p1), Some(p2)).unwrap();
+        assert_eq!((disputed.player1_score, disputed.player2_score), (1, 1));
+        assert!(!disputed.confirmed);
+
+        reports.push(report(p1, 2, (1, 1), 22));
+        assert!(live_score(&reports, Some(p1), Some(p2)).unwrap().confirmed);
+        assert_eq!(live_score(&[], Some(p1), Some(p2)), None);
+    }
+}
@@ -0,0 +1,1195 @@
+//! # Stellar Relayer
+//!
+//! Builds, simulates, signs and submits Soroban contract invocations on behalf
+//! of the platform account:
+//!
+//! 1. The call is recorded in `relayer_submissions` as `queued`.
+//! 2. A transaction is built at the next sequence number of the platform account
+//!    and simulated to obtain its footprint, authorization entries and resource fee.
+//! 3. It is signed and, when a fee account is configured, wrapped in a fee bump
+//!    paid by that account.
+//! 4. It is sent and polled until it lands in a ledger or its time bounds expire.
+//! 5. The outcome is written to the audit trail.
+//!
+//! Each invocation runs in a `relayer.invoke` span. Its trace id is stored with
+//! the submission and, unless the caller asked for a memo of its own, sent as
+//! the transaction's hash memo, so a transaction found on chain leads back to
+//! the request that caused it.
+//!
+//! Rejections caused by a stale sequence number, a low fee or RPC congestion are
+//! retried with exponential backoff; every retry after a fee rejection raises the
+//! inclusion fee. Sequence numbers are allocated under a lock, so submissions from
+//! the platform account are serialized up to the point RPC accepts them.
+//!
+//! A background task reconciles submissions whose outcome was not observed, for
+//! example because the process restarted while polling.
+
+pub mod store;
+pub mod xdr;
+
+use crate::config::StellarConfig;
+use crate::db::DbPool;
+use crate::service::audit_log::{self, AuditRecord};
+use crate::service::soroban_service::{NetworkConfig, RetryConfig};
+use crate::telemetry::propagation;
+use chrono::{TimeZone, Utc};
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::Duration;
+use store::{Attempt, SubmissionStatus};
+use thiserror::Error;
+use tokio::sync::Mutex;
+use tracing::{error, field::Empty, info, warn, Instrument};
+use uuid::Uuid;
+use xdr::{InvokeTx, Keypair, Memo, ScArg, SignedEnvelope};
+
+pub use store::Submission;
+
+/// `TransactionResultCode`s the relayer reacts to.
+const TX_BAD_SEQ: i32 = -5;
+const TX_INSUFFICIENT_FEE: i32 = -9;
+
+/// How often in-flight submissions are reconciled (seconds).
+const RECONCILE_INTERVAL_SECS: u64 = 30;
+const RECONCILE_BATCH_SIZE: i64 = 50;
+
+#[derive(Debug, Error)]
+pub enum RelayerError {
+    #[error("RPC request failed: {0}")]
+    RpcError(String),
+    #[error("Simulation failed: {0}")]
+    SimulationFailed(String),
+    #[error("Transaction failed: {0}")]
+    TransactionFailed(String),
+    #[error("Sequence number out of date")]
+    BadSequence,
+    #[error("Fee too low")]
+    InsufficientFee,
+    #[error("RPC asked to try again later")]
+    TryAgainLater,
+    #[error("Transaction expired before inclusion")]
+    Expired,
+    #[error("Invalid response: {0}")]
+    InvalidResponse(String),
+    #[error("Invalid account: {0}")]
+    InvalidAccount(String),
+    #[error("Network error: {0}")]
+    NetworkError(#[from] reqwest::Error),
+    #[error("Serialization error: {0}")]
+    SerializationError(#[from] serde_json::Error),
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+    #[error("Retry limit exceeded")]
+    RetryLimitExceeded,
+}
+
+impl RelayerError {
+    /// Whether a fresh attempt may succeed. Simulation and on-chain failures are
+    /// final: the contract rejected the call.
+    fn is_retryable(&self) -> bool {
+        matches!(
+            self,
+            RelayerError::RpcError(_)
+                | RelayerError::BadSequence
+                | RelayerError::InsufficientFee
+                | RelayerError::TryAgainLater
+                | RelayerError::Expired
+                | RelayerError::NetworkError(_)
+        )
+    }
+
+    fn should_bump_fee(&self) -> bool {
+        matches!(
+            self,
+            RelayerError::InsufficientFee | RelayerError::TryAgainLater | RelayerError::Expired
+        )
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RelayerConfig {
+    pub network: NetworkConfig,
+    /// Secret of the platform account that signs invocations.
+    pub signer_secret: String,
+    /// Secret of the account paying fees through fee bumps; the signer pays its
+    /// own fees when unset.
+    pub fee_source_secret: Option<String>,
+    /// Inclusion fee of the first attempt, in stroops.
+    pub base_fee: i64,
+    /// Upper bound on the inclusion fee after bumping, in stroops.
+    pub max_fee: i64,
+    pub fee_bump_multiplier: f64,
+    /// Seconds a transaction stays valid after it is built.
+    pub tx_timeout_secs: u64,
+    pub poll_interval_ms: u64,
+    pub retry: RetryConfig,
+}
+
+impl RelayerConfig {
+    pub fn from_stellar_config(stellar: &StellarConfig) -> Self {
+        Self {
+            network: NetworkConfig::custom(
+                stellar.soroban_rpc_url.clone(),
+                stellar.network_passphrase().to_string(),
+            ),
+            signer_secret: stellar.admin_secret.clone(),
+            fee_source_secret: stellar.fee_bump_secret.clone(),
+            base_fee: 100,
+            max_fee: 10_000_000,
+            fee_bump_multiplier: 2.0,
+            tx_timeout_secs: 60,
+            poll_interval_ms: 2000,
+            retry: RetryConfig::default(),
+        }
+    }
+}
+
+/// Outcome of a confirmed submission.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelayerReceipt {
+    pub submission_id: Uuid,
+    pub tx_hash: String,
+    pub ledger: u64,
+}
+
+// ============================================================================
+// RPC TYPES
+// ============================================================================
+
+#[derive(Debug, Deserialize)]
+struct RpcResponse<T> {
+    result: Option<T>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct LedgerEntriesResponse {
+    #[serde(default)]
+    entries: Vec<LedgerEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+struct LedgerEntry {
+    xdr: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SimulateResponse {
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    transaction_data: Option<String>,
+    #[serde(default)]
+    min_resource_fee: Option<String>,
+    #[serde(default)]
+    results: Vec<SimulateResult>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SimulateResult {
+    #[serde(default)]
+    auth: Vec<String>,
+    /// Return value as base64 `ScVal`.
+    #[serde(default)]
+    xdr: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct SendResponse {
+    status: String,
+    #[serde(default)]
+    error_result_xdr: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TransactionResponse {
+    status: String,
+    #[serde(default)]
+    ledger: Option<u64>,
+    #[serde(default)]
+    result_xdr: Option<String>,
+}
+
+/// Simulation output spliced into the final transaction.
+struct Simulation {
+    transaction_data: Vec<u8>,
+    auth: Vec<Vec<u8>>,
+    min_resource_fee: i64,
+}
+
+// ============================================================================
+// SERVICE STRUCT
+// ============================================================================
+
+pub struct StellarRelayer {
+    db_pool: DbPool,
+    client: reqwest::Client,
+    config: RelayerConfig,
+    signer: Keypair,
+    fee_source: Option<Keypair>,
+    /// Last sequence number accepted by RPC; `None` forces a reload from chain.
+    sequence: Mutex<Option<i64>>,
+}
+
+impl StellarRelayer {
+    pub fn new(db_pool: DbPool, config: RelayerConfig) -> Result<Self, RelayerError> {
+        let signer = Keypair::from_secret(&config.signer_secret)?;
+        let fee_source = config
+            .fee_source_secret
+            .as_deref()
+            .map(Keypair::from_secret)
+            .transpose()?;
+        Ok(Self {
+            db_pool,
+            client: reqwest::Client::new(),
+            config,
+            signer,
+            fee_source,
+            sequence: Mutex::new(None),
+        })
+    }
+
+    /// Platform account that signs relayed transactions.
+    pub fn account_id(&self) -> String {
+        self.signer.account_id()
+    }
+
+    /// Co-sign a SEP-10 challenge from an anchor with the platform account.
+    /// The caller must have checked that the challenge is safe to sign.
+    pub fn cosign_challenge(&self, envelope_xdr: &str) -> Result<String, RelayerError> {
+        xdr::cosign_envelope(
+            envelope_xdr,
+            &self.config.network.network_passphrase,
+            &self.signer,
+        )
+    }
+
+    // ========================================================================
+    // CONTRACT CALLS
+    // ========================================================================
+
+    pub async fn create_escrow(
+        &self,
+        escrow_contract: &str,
+        match_id: [u8; 32],
+        player_a: &str,
+        player_b: &str,
+        amount: i128,
+        asset: &str,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            escrow_contract,
+            "create_escrow",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(player_a.to_string()),
+                ScArg::Address(player_b.to_string()),
+                ScArg::I128(amount),
+                ScArg::Address(asset.to_string()),
+            ],
+        )
+        .await
+    }
+
+    pub async fn lock_funds(
+        &self,
+        escrow_contract: &str,
+        match_id: [u8; 32],
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            escrow_contract,
+            "lock_funds",
+            vec![ScArg::Bytes(match_id.to_vec())],
+        )
+        .await
+    }
+
+    pub async fn release_to_winner(
+        &self,
+        escrow_contract: &str,
+        match_id: [u8; 32],
+        winner: &str,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            escrow_contract,
+            "release_to_winner",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(winner.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Create a staked match on the lifecycle contract.
+    pub async fn create_match(
+        &self,
+        match_contract: &str,
+        match_id: [u8; 32],
+        players: &[&str],
+        stake_asset: &str,
+        stake_amount: i128,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            match_contract,
+            "create_match",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Vec(
+                    players
+                        .iter()
+                        .map(|p| ScArg::Address(p.to_string()))
+                        .collect(),
+                ),
+                ScArg::Address(stake_asset.to_string()),
+                ScArg::I128(stake_amount),
+            ],
+        )
+        .await
+    }
+
+    /// Submit one player's result report. `winner_index` is the position of the
+    /// claimed winner in the match's player list, which is how the contract
+    /// compares the two reports.
+    pub async fn submit_result(
+        &self,
+        match_contract: &str,
+        match_id: [u8; 32],
+        reporter: &str,
+        winner_index: i64,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            match_contract,
+            "submit_result",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(reporter.to_string()),
+                ScArg::I64(winner_index),
+            ],
+        )
+        .await
+    }
+
+    /// Finalize a match on the lifecycle contract with the platform account as caller.
+    pub async fn finalize_match(
+        &self,
+        match_contract: &str,
+        match_id: [u8; 32],
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            match_contract,
+            "finalize_match",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(self.account_id()),
+            ],
+        )
+        .await
+    }
+
+    /// Cancel a match that will never be played, e.g. after a check-in forfeit.
+    pub async fn cancel_match(
+        &self,
+        match_contract: &str,
+        match_id: [u8; 32],
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            match_contract,
+            "cancel_match",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(self.account_id()),
+            ],
+        )
+        .await
+    }
+
+    /// Open a dispute for a match with the platform account as opener.
+    pub async fn open_dispute(
+        &self,
+        dispute_contract: &str,
+        match_id: [u8; 32],
+        category: u32,
+        reason: &str,
+        evidence_ref: &str,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            dispute_contract,
+            "open_dispute",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(self.account_id()),
+                ScArg::U32(category),
+                ScArg::String(reason.to_string()),
+                ScArg::String(evidence_ref.to_string()),
+            ],
+        )
+        .await
+    }
+
+    /// Assign an open dispute to the platform account so it can resolve it.
+    pub async fn claim_dispute(
+        &self,
+        dispute_contract: &str,
+        match_id: [u8; 32],
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            dispute_contract,
+            "claim_dispute",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(self.account_id()),
+            ],
+        )
+        .await
+    }
+
+    /// Resolve a claimed dispute. `decision` is the contract's `Decision` enum
+    /// encoded as a vector of its variant symbol followed by any fields.
+    pub async fn resolve_dispute(
+        &self,
+        dispute_contract: &str,
+        match_id: [u8; 32],
+        decision: ScArg,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            dispute_contract,
+            "resolve_dispute",
+            vec![
+                ScArg::Bytes(match_id.to_vec()),
+                ScArg::Address(self.account_id()),
+                decision,
+            ],
+        )
+        .await
+    }
+
+    /// Flag `player` on the anti-cheat oracle with the platform account as
+    /// oracle. `severity` is 1 (low) to 3 (high).
+    pub async fn submit_cheat_flag(
+        &self,
+        oracle_contract: &str,
+        player: &str,
+        match_id: u64,
+        severity: u32,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            oracle_contract,
+            "submit_flag",
+            vec![
+                ScArg::Address(self.account_id()),
+                ScArg::Address(player.to_string()),
+                ScArg::U64(match_id),
+                ScArg::U32(severity),
+            ],
+        )
+        .await
+    }
+
+    /// Pay out a locked prize pool, e.g. from a season's winners manifest.
+    /// `weights` are basis points summing to 10000, in `winners` order.
+    pub async fn distribute_prizes(
+        &self,
+        prize_contract: &str,
+        pool_id: u64,
+        winners: &[String],
+        weights: &[u32],
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            prize_contract,
+            "distribute",
+            vec![
+                ScArg::Address(self.account_id()),
+                ScArg::U64(pool_id),
+                ScArg::Vec(winners.iter().map(|w| ScArg::Address(w.clone())).collect()),
+                ScArg::Vec(weights.iter().map(|&w| ScArg::U32(w)).collect()),
+            ],
+        )
+        .await
+    }
+
+    /// Transfer `amount` of a Stellar Asset Contract token from the platform
+    /// account to `to`.
+    pub async fn transfer(
+        &self,
+        token_contract: &str,
+        to: &str,
+        amount: i128,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke(
+            token_contract,
+            "transfer",
+            vec![
+                ScArg::Address(self.account_id()),
+                ScArg::Address(to.to_string()),
+                ScArg::I128(amount),
+            ],
+        )
+        .await
+    }
+
+    /// [`Self::transfer`] with a transaction memo, for payees such as anchors
+    /// that attribute incoming payments by memo.
+    pub async fn transfer_with_memo(
+        &self,
+        token_contract: &str,
+        to: &str,
+        amount: i128,
+        memo: Memo,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke_with_memo(
+            token_contract,
+            "transfer",
+            vec![
+                ScArg::Address(self.account_id()),
+                ScArg::Address(to.to_string()),
+                ScArg::I128(amount),
+            ],
+            Some(memo),
+        )
+        .await
+    }
+
+    /// Simulate a read-only call and return its result as base64 `ScVal`. Nothing
+    /// is signed or submitted, so no sequence number is consumed.
+    pub async fn call_view(
+        &self,
+        contract_id: &str,
+        function_name: &str,
+        args: Vec<ScArg>,
+    ) -> Result<String, RelayerError> {
+        let tx = InvokeTx {
+            source: self.signer.public_key(),
+            fee: self.config.base_fee as u32,
+            sequence: 0,
+            max_time: Utc::now().timestamp() as u64 + self.config.tx_timeout_secs,
+            contract_id: contract_id.to_string(),
+            function_name: function_name.to_string(),
+            args,
+            auth: vec![],
+            soroban_data: None,
+            memo: None,
+        };
+        let response = self.simulate_raw(&tx).await?;
+        response
+            .results
+            .into_iter()
+            .next()
+            .and_then(|result| result.xdr)
+            .ok_or_else(|| RelayerError::InvalidResponse("missing simulation result".to_string()))
+    }
+
+    /// Relay `function_name(args)` on `contract_id`, retrying transient failures,
+    /// and wait for it to be included in a ledger.
+    pub async fn invoke(
+        &self,
+        contract_id: &str,
+        function_name: &str,
+        args: Vec<ScArg>,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        self.invoke_with_memo(contract_id, function_name, args, None)
+            .await
+    }
+
+    #[tracing::instrument(
+        name = "relayer.invoke",
+        skip(self, args, memo),
+        fields(
+            otel.kind = "internal",
+            submission_id = Empty,
+            trace_id = Empty,
+        )
+    )]
+    async fn invoke_with_memo(
+        &self,
+        contract_id: &str,
+        function_name: &str,
+        args: Vec<ScArg>,
+        memo: Option<Memo>,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        let span = tracing::Span::current();
+        let trace_id = propagation::trace_id(&span);
+        if let Some(trace_id) = &trace_id {
+            span.record("trace_id", trace_id.as_str());
+        }
+        let memo = memo.or_else(|| trace_id.as_deref().and_then(Memo::from_trace_id));
+
+        let args_json = serde_json::to_value(&args)?;
+        let submission_id = store::insert_submission(
+            &self.db_pool,
+            contract_id,
+            function_name,
+            &args_json,
+            &self.account_id(),
+            trace_id.as_deref(),
+        )
+        .await?;
+        span.record("submission_id", tracing::field::display(submission_id));
+        info!(
+            %submission_id,
+            contract_id,
+            function = function_name,
+            "Relaying contract invocation"
+        );
+
+        let result = self
+            .relay(submission_id, contract_id, function_name, &args, memo)
+            .await;
+        self.audit(submission_id, contract_id, function_name, &result)
+            .await;
+        result
+    }
+
+    /// Retry [`Self::attempt`] until the submission lands or fails for good.
+    async fn relay(
+        &self,
+        submission_id: Uuid,
+        contract_id: &str,
+        function_name: &str,
+        args: &[ScArg],
+        memo: Option<Memo>,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        let retry = &self.config.retry;
+        let mut inclusion_fee = self.config.base_fee;
+        let mut delay = retry.initial_delay_ms;
+        let mut previous_hash: Option<String> = None;
+
+        for attempt in 0..=retry.max_retries {
+            // A send that failed on the wire may still have been accepted; never
+            // build a second transaction while the first can land.
+            if let Some(hash) = previous_hash.take() {
+                if let Some(receipt) = self.settle_previous(submission_id, &hash).await? {
+                    return Ok(receipt);
+                }
+            }
+
+            let result = self
+                .attempt(
+                    submission_id,
+                    contract_id,
+                    function_name,
+                    args,
+                    memo.as_ref(),
+                    inclusion_fee,
+                    &mut previous_hash,
+                )
+                .await;
+            let error = match result {
+                Ok(receipt) => return Ok(receipt),
+                Err(e) => e,
+            };
+
+            if !error.is_retryable() {
+                error!(%submission_id, error = %error, "Relayed invocation failed");
+                store::update_status(
+                    &self.db_pool,
+                    submission_id,
+                    SubmissionStatus::Failed,
+                    Some(&error.to_string()),
+                )
+                .await?;
+                return Err(error);
+            }
+
+            warn!(%submission_id, attempt, error = %error, "Relayed invocation attempt failed");
+            store::update_status(
+                &self.db_pool,
+                submission_id,
+                SubmissionStatus::Queued,
+                Some(&error.to_string()),
+            )
+            .await?;
+            if matches!(error, RelayerError::BadSequence | RelayerError::Expired) {
+                *self.sequence.lock().await = None;
+            }
+            if error.should_bump_fee() {
+                inclusion_fee = next_inclusion_fee(
+                    inclusion_fee,
+                    self.config.fee_bump_multiplier,
+                    self.config.max_fee,
+                );
+            }
+
+            if attempt < retry.max_retries {
+                tokio::time::sleep(Duration::from_millis(delay)).await;
+                delay = ((delay as f64 * retry.backoff_multiplier) as u64).min(retry.max_delay_ms);
+            }
+        }
+
+        store::update_status(
+            &self.db_pool,
+            submission_id,
+            SubmissionStatus::Failed,
+            Some(&RelayerError::RetryLimitExceeded.to_string()),
+        )
+        .await?;
+        Err(RelayerError::RetryLimitExceeded)
+    }
+
+    /// Record the outcome of a relayed invocation in the audit trail. A
+    /// failed write is logged; the invocation's outcome stands.
+    async fn audit(
+        &self,
+        submission_id: Uuid,
+        contract_id: &str,
+        function_name: &str,
+        result: &Result<RelayerReceipt, RelayerError>,
+    ) {
+        let (action, details) = match result {
+            Ok(receipt) => (
+                "relayer_tx_confirmed",
+                serde_json::json!({
+                    "contract_id": contract_id,
+                    "function": function_name,
+                    "source_account": self.account_id(),
+                    "tx_hash": receipt.tx_hash,
+                    "ledger": receipt.ledger,
+                }),
+            ),
+            Err(e) => (
+                "relayer_tx_failed",
+                serde_json::json!({
+                    "contract_id": contract_id,
+                    "function": function_name,
+                    "source_account": self.account_id(),
+                    "error": e.to_string(),
+                }),
+            ),
+        };
+        let entry = AuditRecord {
+            action,
+            resource_type: "relayer_submission",
+            resource_id: Some(submission_id),
+            details: Some(details),
+            ..AuditRecord::default()
+        };
+        if let Err(e) = audit_log::append(&self.db_pool, entry).await {
+            error!(%submission_id, error = %e, "Failed to audit relayed invocation");
+        }
+    }
+
+    /// Build, simulate, sign, send and await one transaction. `sent_hash` is set
+    /// once the envelope has been handed to RPC.
+    async fn attempt(
+        &self,
+        submission_id: Uuid,
+        contract_id: &str,
+        function_name: &str,
+        args: &[ScArg],
+        memo: Option<&Memo>,
+        inclusion_fee: i64,
+        sent_hash: &mut Option<String>,
+    ) -> Result<RelayerReceipt, RelayerError> {
+        let mut sequence = self.sequence.lock().await;
+        let next_sequence = match *sequence {
+            Some(last) => last + 1,
+            None => self.fetch_sequence().await? + 1,
+        };
+        let max_time = Utc::now().timestamp() as u64 + self.config.tx_timeout_secs;
+
+        let mut tx = InvokeTx {
+            source: self.signer.public_key(),
+            fee: self.config.base_fee as u32,
+            sequence: next_sequence,
+            max_time,
+            contract_id: contract_id.to_string(),
+            function_name: function_name.to_string(),
+            args: args.to_vec(),
+            auth: vec![],
+            soroban_data: None,
+            memo: memo.cloned(),
+        };
+        let simulation = self.simulate(&tx).await?;
+        tx.auth = simulation.auth;
+        tx.soroban_data = Some(simulation.transaction_data);
+
+        let passphrase = &self.config.network.network_passphrase;
+        let (envelope, fee) = match &self.fee_source {
+            Some(fee_source) => {
+                // The inner transaction only has to cover its resource fee; the
+                // fee bump pays inclusion for itself and the inner transaction.
+                tx.fee = fee_to_u32(simulation.min_resource_fee + self.config.base_fee)?;
+                let fee = simulation.min_resource_fee + inclusion_fee * 2;
+                (
+                    xdr::sign_fee_bump(&tx, passphrase, &self.signer, fee_source, fee)?,
+                    fee,
+                )
+            }
+            None => {
+                let fee = simulation.min_resource_fee + inclusion_fee;
+                tx.fee = fee_to_u32(fee)?;
+                (xdr::sign(&tx, passphrase, &self.signer)?, fee)
+            }
+        };
+
+        store::record_attempt(
+            &self.db_pool,
+            submission_id,
+            &Attempt {
+                sequence: next_sequence,
+                fee,
+                fee_bumped: self.fee_source.is_some(),
+                tx_hash: &envelope.hash,
+                envelope_xdr: &envelope.xdr,
+                valid_until: Utc
+                    .timestamp_opt(max_time as i64, 0)
+                    .single()
+                    .unwrap_or_else(Utc::now),
+            },
+        )
+        .await?;
+
+        *sent_hash = Some(envelope.hash.clone());
+        self.send(&envelope).await?;
+        *sequence = Some(next_sequence);
+        drop(sequence);
+
+        store::update_status(
+            &self.db_pool,
+            submission_id,
+            SubmissionStatus::Submitted,
+            None,
+        )
+        .await?;
+        info!(%submission_id, tx_hash = %envelope.hash, fee, "Relayed transaction submitted");
+
+        let ledger = self.await_inclusion(&envelope.hash, max_time).await?;
+        *sent_hash = None;
+        store::mark_confirmed(&self.db_pool, submission_id, ledger).await?;
+        info!(%submission_id, tx_hash = %envelope.hash, ledger, "Relayed transaction confirmed");
+        Ok(RelayerReceipt {
+            submission_id,
+            tx_hash: envelope.hash,
+            ledger,
+        })
+    }
+
+    /// Resolve a transaction whose send result was not observed. Returns a receipt
+    /// if it landed, `None` if a new attempt is safe.
+    async fn settle_previous(
+        &self,
+        submission_id: Uuid,
+        tx_hash: &str,
+    ) -> Result<Option<RelayerReceipt>, RelayerError> {
+        let status = match self.get_transaction(tx_hash).await {
+            Ok(status) => status,
+            Err(e) => {
+                warn!(%submission_id, tx_hash, error = %e, "Could not check previous attempt");
+                return Ok(None);
+            }
+        };
+        match status.status.as_str() {
+            "SUCCESS" => {
+                let ledger = status.ledger.unwrap_or_default();
+                store::mark_confirmed(&self.db_pool, submission_id, ledger).await?;
+                Ok(Some(RelayerReceipt {
+                    submission_id,
+                    tx_hash: tx_hash.to_string(),
+                    ledger,
+                }))
+            }
+            "FAILED" => Err(RelayerError::TransactionFailed(failure_reason(&status))),
+            _ => Ok(None),
+        }
+    }
+
+    // ========================================================================
+    // BACKGROUND TASK
+    // ========================================================================
+
+    /// Spawn the reconciliation loop as a detached Tokio task.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!(
+                interval_secs = RECONCILE_INTERVAL_SECS,
+                account = %self.account_id(),
+                "Stellar relayer reconciler started"
+            );
+            let mut ticker = tokio::time::interval(Duration::from_secs(RECONCILE_INTERVAL_SECS));
+            // Skip the immediate first tick so startup isn't slowed down.
+            ticker.tick().await;
+            loop {
+                ticker.tick().await;
+                match self.reconcile_in_flight().await {
+                    Ok(0) => {}
+                    Ok(settled) => info!(settled, "Relayer reconciled submissions"),
+                    Err(e) => error!(error = %e, "Relayer reconcile tick failed"),
+                }
+            }
+        });
+    }
+
+    /// Settle `submitted` rows whose outcome was never observed. Returns how many
+    /// reached a final status.
+    pub async fn reconcile_in_flight(&self) -> Result<usize, RelayerError> {
+        let submissions = store::list_in_flight(&self.db_pool, RECONCILE_BATCH_SIZE).await?;
+        let mut settled = 0;
+        for submission in submissions {
+            let Some(tx_hash) = submission.tx_hash.as_deref() else {
+                continue;
+            };
+            let status = self.get_transaction(tx_hash).await?;
+            let outcome = match status.status.as_str() {
+                "SUCCESS" => {
+                    let ledger = status.ledger.unwrap_or_default();
+                    store::mark_confirmed(&self.db_pool, submission.id, ledger).await?;
+                    Ok(RelayerReceipt {
+                        submission_id: submission.id,
+                        tx_hash: tx_hash.to_string(),
+                        ledger,
+                    })
+                }
+                "FAILED" => {
+                    let reason = failure_reason(&status);
+                    store::update_status(
+                        &self.db_pool,
+                        submission.id,
+                        SubmissionStatus::Failed,
+                        Some(&reason),
+                    )
+                    .await?;
+                    Err(RelayerError::TransactionFailed(reason))
+                }
+                _ if submission.valid_until.is_some_and(|t| t < Utc::now()) => {
+                    store::update_status(
+                        &self.db_pool,
+                        submission.id,
+                        SubmissionStatus::Expired,
+                        Some(&RelayerError::Expired.to_string()),
+                    )
+                    .await?;
+                    Err(RelayerError::Expired)
+                }
+                _ => continue,
+            };
+            self.audit(
+                submission.id,
+                &submission.contract_id,
+                &submission.function_name,
+                &outcome,
+            )
+            .await;
+            settled += 1;
+        }
+        Ok(settled)
+    }
+
+    // ========================================================================
+    // RPC
+    // ========================================================================
+
+    async fn fetch_sequence(&self) -> Result<i64, RelayerError> {
+        let key = xdr::account_ledger_key(&self.signer.public_key());
+        let response: LedgerEntriesResponse = self
+            .rpc_call("getLedgerEntries", serde_json::json!({ "keys": [key] }))
+            .await?;
+        let entry = response.entries.first().ok_or_else(|| {
+            RelayerError::InvalidAccount(format!("{} does not exist", self.account_id()))
+        })?;
+        xdr::account_sequence(&entry.xdr)
+    }
+
+    async fn simulate_raw(&self, tx: &InvokeTx) -> Result<SimulateResponse, RelayerError> {
+        let response: SimulateResponse = self
+            .rpc_call(
+                "simulateTransaction",
+                serde_json::json!({ "transaction": tx.unsigned_envelope()? }),
+            )
+            .await?;
+        match response.error {
+            Some(error) => Err(RelayerError::SimulationFailed(error)),
+            None => Ok(response),
+        }
+    }
+
+    async fn simulate(&self, tx: &InvokeTx) -> Result<Simulation, RelayerError> {
+        let response = self.simulate_raw(tx).await?;
+
+        let transaction_data = response
+            .transaction_data
+            .ok_or_else(|| RelayerError::InvalidResponse("missing transactionData".to_string()))?;
+        let min_resource_fee = response
+            .min_resource_fee
+            .as_deref()
+            .unwrap_or("0")
+            .parse::<i64>()
+            .map_err(|e| RelayerError::InvalidResponse(format!("minResourceFee: {}", e)))?;
+        let auth = response
+            .results
+            .first()
+            .map(|result| result.auth.iter().map(|a| xdr::decode_base64(a)).collect())
+            .transpose()?
+            .unwrap_or_default();
+
+        Ok(Simulation {
+            transaction_data: xdr::decode_base64(&transaction_data)?,
+            auth,
+            min_resource_fee,
+        })
+    }
+
+    async fn send(&self, envelope: &SignedEnvelope) -> Result<(), RelayerError> {
+        let response: SendResponse = self
+            .rpc_call(
+                "sendTransaction",
+                serde_json::json!({ "transaction": envelope.xdr }),
+            )
+            .await?;
+        match response.status.as_str() {
+            "PENDING" | "DUPLICATE" => Ok(()),
+            "TRY_AGAIN_LATER" => Err(RelayerError::TryAgainLater),
+            _ => {
+                let code = response
+                    .error_result_xdr
+                    .as_deref()
+                    .map(xdr::transaction_result_code)
+                    .transpose()?;
+                Err(match code {
+                    Some(TX_BAD_SEQ) => RelayerError::BadSequence,
+                    Some(TX_INSUFFICIENT_FEE) => RelayerError::InsufficientFee,
+                    Some(code) => RelayerError::TransactionFailed(format!(
+                        "rejected with result code {}",
+                        code
+                    )),
+                    None => RelayerError::TransactionFailed(format!(
+                        "rejected with status {}",
+                        response.status
+                    )),
+                })
+            }
+        }
+    }
+
+    /// Poll until `tx_hash` lands in a ledger or its time bounds pass.
+    async fn await_inclusion(&self, tx_hash: &str, max_time: u64) -> Result<u64, RelayerError> {
+        loop {
+            tokio::time::sleep(Duration::from_millis(self.config.poll_interval_ms)).await;
+            let status = self.get_transaction(tx_hash).await?;
+            match status.status.as_str() {
+                "SUCCESS" => return Ok(status.ledger.unwrap_or_default()),
+                "FAILED" => return Err(RelayerError::TransactionFailed(failure_reason(&status))),
+                _ if Utc::now().timestamp() as u64 > max_time => {
+                    // One last look: the closing ledger may include it.
+                    let status = self.get_transaction(tx_hash).await?;
+                    return match status.status.as_str() {
+                        "SUCCESS" => Ok(status.ledger.unwrap_or_default()),
+                        "FAILED" => Err(RelayerError::TransactionFailed(failure_reason(&status))),
+                        _ => Err(RelayerError::Expired),
+                    };
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn get_transaction(&self, tx_hash: &str) -> Result<TransactionResponse, RelayerError> {
+        self.rpc_call("getTransaction", serde_json::json!({ "hash": tx_hash }))
+            .await
+    }
+
+    async fn rpc_call<T>(&self, method: &str, params: serde_json::Value) -> Result<T, RelayerError>
+    where
+        T: for<'de> Deserialize<'de>,
+    {
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+        let span = tracing::info_span!(
+            "soroban_rpc",
+            otel.name = %format!("soroban_rpc {}", method),
+            otel.kind = "client",
+            rpc.system = "jsonrpc",
+            rpc.method = method,
+        );
+        let (status, text) = async {
+            let response =
+                propagation::inject_headers(&span, self.client.post(&self.config.network.rpc_url))
+                    .json(&request)
+                    .send()
+                    .await?;
+            let status = response.status();
+            Ok::<_, RelayerError>((status, response.text().await?))
+        }
+        .instrument(span.clone())
+        .await?;
+        if !status.is_success() {
+            return Err(RelayerError::RpcError(format!("HTTP {}: {}", status, text)));
+        }
+
+        let rpc_response: RpcResponse<T> = serde_json::from_str(&text)?;
+        match (rpc_response.result, rpc_response.error) {
+            (Some(result), _) => Ok(result),
+            (None, Some(error)) => Err(RelayerError::RpcError(format!(
+                "RPC error {}: {}",
+                error.code, error.message
+            ))),
+            (None, None) => Err(RelayerError::InvalidResponse(format!(
+                "{} returned neither result nor error",
+                method
+            ))),
+        }
+    }
+}
+
+fn failure_reason(status: &TransactionResponse) -> String {
+    match status
+        .result_xdr
+        .as_deref()
+        .map(xdr::transaction_result_code)
+    {
+        Some(Ok(code)) => format!("failed on chain with result code {}", code),
+        _ => "failed on chain".to_string(),
+    }
+}
+
+fn fee_to_u32(fee: i64) -> Result<u32, RelayerError> {
+    u32::try_from(fee)
+        .map_err(|_| RelayerError::TransactionFailed(format!("fee {} exceeds u32", fee)))
+}
+
+fn next_inclusion_fee(current: i64, multiplier: f64, max_fee: i64) -> i64 {
+    ((current as f64 * multiplier).ceil() as i64).clamp(current, max_fee)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fee_bumps_are_capped() {
+        assert_eq!(next_inclusion_fee(100, 2.0, 1_000), 200);
+        assert_eq!(next_inclusion_fee(800, 2.0, 1_000), 1_000);
+        assert_eq!(next_inclusion_fee(1_000, 2.0, 1_000), 1_000);
+    }
+
+    #[test]
+    fn test_retry_classification() {
+        assert!(RelayerError::BadSequence.is_retryable());
+        assert!(RelayerError::TryAgainLater.should_bump_fee());
+        assert!(!RelayerError::BadSequence.should_bump_fee());
+        assert!(!RelayerError::SimulationFailed("panic".to_string()).is_retryable());
+        assert!(!RelayerError::TransactionFailed("code -1".to_string()).is_retryable());
+    }
+
+    #[test]
+    fn test_submission_status_strings() {
+        assert_eq!(SubmissionStatus::Submitted.as_str(), "submitted");
+        assert_eq!(
+            serde_json::to_string(&SubmissionStatus::Expired).unwrap(),
+            "\"expired\""
+        );
+    }
+}
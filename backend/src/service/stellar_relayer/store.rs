@@ -0,0 +1,191 @@
+//! Postgres persistence for relayer submissions.
+
+use super::RelayerError;
+use crate::db::DbPool;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionStatus {
+    /// Recorded, not yet accepted by the network.
+    Queued,
+    /// Accepted by RPC and waiting to be included in a ledger.
+    Submitted,
+    Confirmed,
+    Failed,
+    /// Not included before its time bounds ran out.
+    Expired,
+}
+
+impl SubmissionStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionStatus::Queued => "queued",
+            SubmissionStatus::Submitted => "submitted",
+            SubmissionStatus::Confirmed => "confirmed",
+            SubmissionStatus::Failed => "failed",
+            SubmissionStatus::Expired => "expired",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct Submission {
+    pub id: Uuid,
+    pub contract_id: String,
+    pub function_name: String,
+    pub args: serde_json::Value,
+    pub status: String,
+    pub source_account: String,
+    pub sequence_number: Option<i64>,
+    pub fee: Option<i64>,
+    pub fee_bumped: bool,
+    pub tx_hash: Option<String>,
+    pub valid_until: Option<DateTime<Utc>>,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub ledger: Option<i64>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+    pub confirmed_at: Option<DateTime<Utc>>,
+    pub trace_id: Option<String>,
+}
+
+/// A signed attempt handed to RPC.
+pub struct Attempt<'a> {
+    pub sequence: i64,
+    pub fee: i64,
+    pub fee_bumped: bool,
+    pub tx_hash: &'a str,
+    pub envelope_xdr: &'a str,
+    pub valid_until: DateTime<Utc>,
+}
+
+const SUBMISSION_COLUMNS: &str = "id, contract_id, function_name, args, status, source_account, \
+     sequence_number, fee, fee_bumped, tx_hash, valid_until, attempts, last_error, ledger, \
+     created_at, updated_at, confirmed_at, trace_id";
+
+pub async fn insert_submission(
+    pool: &DbPool,
+    contract_id: &str,
+    function_name: &str,
+    args: &serde_json::Value,
+    source_account: &str,
+    trace_id: Option<&str>,
+) -> Result<Uuid, RelayerError> {
+    let (id,): (Uuid,) = sqlx::query_as(
+        r#"
+        INSERT INTO relayer_submissions
+            (contract_id, function_name, args, source_account, trace_id)
+        VALUES ($1, $2, $3, $4, $5)
+        RETURNING id
+        "#,
+    )
+    .bind(contract_id)
+    .bind(function_name)
+    .bind(args)
+    .bind(source_account)
+    .bind(trace_id)
+    .fetch_one(pool)
+    .await?;
+    Ok(id)
+}
+
+pub async fn get_submission(pool: &DbPool, id: Uuid) -> Result<Option<Submission>, RelayerError> {
+    let submission = sqlx::query_as::<_, Submission>(&format!(
+        "SELECT {} FROM relayer_submissions WHERE id = $1",
+        SUBMISSION_COLUMNS
+    ))
+    .bind(id)
+    .fetch_optional(pool)
+    .await?;
+    Ok(submission)
+}
+
+/// Submissions accepted by RPC whose outcome is still unknown, oldest first.
+pub async fn list_in_flight(pool: &DbPool, limit: i64) -> Result<Vec<Submission>, RelayerError> {
+    let submissions = sqlx::query_as::<_, Submission>(&format!(
+        "SELECT {} FROM relayer_submissions WHERE status = 'submitted' \
+         ORDER BY updated_at ASC LIMIT $1",
+        SUBMISSION_COLUMNS
+    ))
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+    Ok(submissions)
+}
+
+pub async fn record_attempt(
+    pool: &DbPool,
+    id: Uuid,
+    attempt: &Attempt<'_>,
+) -> Result<(), RelayerError> {
+    sqlx::query(
+        r#"
+        UPDATE relayer_submissions
+        SET sequence_number = $2,
+            fee             = $3,
+            fee_bumped      = $4,
+            tx_hash         = $5,
+            envelope_xdr    = $6,
+            valid_until     = $7,
+            attempts        = attempts + 1,
+            updated_at      = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(attempt.sequence)
+    .bind(attempt.fee)
+    .bind(attempt.fee_bumped)
+    .bind(attempt.tx_hash)
+    .bind(attempt.envelope_xdr)
+    .bind(attempt.valid_until)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn update_status(
+    pool: &DbPool,
+    id: Uuid,
+    status: SubmissionStatus,
+    last_error: Option<&str>,
+) -> Result<(), RelayerError> {
+    sqlx::query(
+        r#"
+        UPDATE relayer_submissions
+        SET status     = $2,
+            last_error = COALESCE($3, last_error),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(status.as_str())
+    .bind(last_error)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
+pub async fn mark_confirmed(pool: &DbPool, id: Uuid, ledger: u64) -> Result<(), RelayerError> {
+    sqlx::query(
+        r#"
+        UPDATE relayer_submissions
+        SET status       = 'confirmed',
+            ledger       = $2,
+            confirmed_at = NOW(),
+            updated_at   = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(id)
+    .bind(ledger as i64)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
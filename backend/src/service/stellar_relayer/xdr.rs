@@ -0,0 +1,956 @@
+//! Soroban `InvokeHostFunction` transactions and SEP-10 challenges, built on
+//! the `stellar-xdr` types.
+//!
+//! The relayer submits a single contract invocation with `Address`, `BytesN`,
+//! integer, symbol and vector arguments, wrapped in a `TransactionV1Envelope`
+//! or a `FeeBumpTransactionEnvelope`. The `SorobanTransactionData` and
+//! authorization entries returned by `simulateTransaction` are decoded and
+//! spliced in. SEP-10 challenges, which are `ManageData` transactions that
+//! never reach the network, are built, decoded and co-signed here too.
+
+use super::RelayerError;
+use base64::{engine::general_purpose, Engine as _};
+use ed25519_dalek::{Signature, Signer, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use stellar_strkey::{ed25519, Contract, Strkey};
+use stellar_xdr::curr as xdr;
+pub use stellar_xdr::curr::{Limits, ReadXdr, Transaction, WriteXdr};
+
+const MAX_MEMO_TEXT_LEN: usize = 28;
+/// Nesting limit when decoding XDR from clients and RPC, so a deeply nested
+/// value cannot exhaust the stack.
+const MAX_DECODE_DEPTH: u32 = 500;
+
+/// A contract argument. Serialized to JSON for the submission audit trail.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum ScArg {
+    Bool(bool),
+    U32(u32),
+    U64(u64),
+    I64(i64),
+    I128(i128),
+    /// Raw bytes; also used for `BytesN<N>` parameters.
+    Bytes(Vec<u8>),
+    String(String),
+    Symbol(String),
+    /// Account (`G...`) or contract (`C...`) StrKey.
+    Address(String),
+    Vec(Vec<ScArg>),
+}
+
+impl ScArg {
+    fn to_sc_val(&self) -> Result<xdr::ScVal, RelayerError> {
+        Ok(match self {
+            ScArg::Bool(value) => xdr::ScVal::Bool(*value),
+            ScArg::U32(value) => xdr::ScVal::U32(*value),
+            ScArg::U64(value) => xdr::ScVal::U64(*value),
+            ScArg::I64(value) => xdr::ScVal::I64(*value),
+            ScArg::I128(value) => xdr::ScVal::I128(xdr::Int128Parts {
+                hi: (*value >> 64) as i64,
+                lo: *value as u64,
+            }),
+            ScArg::Bytes(bytes) => {
+                xdr::ScVal::Bytes(xdr::ScBytes(bytes.clone().try_into().map_err(invalid_xdr)?))
+            }
+            ScArg::String(string) => xdr::ScVal::String(xdr::ScString(
+                string.as_str().try_into().map_err(invalid_xdr)?,
+            )),
+            ScArg::Symbol(symbol) => xdr::ScVal::Symbol(sc_symbol(symbol)?),
+            ScArg::Address(address) => xdr::ScVal::Address(sc_address(address)?),
+            ScArg::Vec(items) => {
+                let items = items
+                    .iter()
+                    .map(ScArg::to_sc_val)
+                    .collect::<Result<Vec<_>, _>>()?;
+                xdr::ScVal::Vec(Some(xdr::ScVec(items.try_into().map_err(invalid_xdr)?)))
+            }
+        })
+    }
+}
+
+/// A transaction memo, as anchors ask for when they receive a payment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Memo {
+    Text(String),
+    Id(u64),
+    Hash([u8; 32]),
+}
+
+impl Memo {
+    /// Memo from the `memo_type`/`memo` pair used by SEP-6 and SEP-24, where
+    /// hashes are base64-encoded.
+    pub fn parse(memo_type: &str, value: &str) -> Result<Self, RelayerError> {
+        let invalid = |reason: &str| RelayerError::InvalidResponse(format!("memo {}", reason));
+        match memo_type {
+            "text" if value.len() <= MAX_MEMO_TEXT_LEN => Ok(Memo::Text(value.to_string())),
+            "text" => Err(invalid("text is longer than 28 bytes")),
+            "id" => value
+                .parse()
+                .map(Memo::Id)
+                .map_err(|_| invalid("id is not a u64")),
+            "hash" => general_purpose::STANDARD
+                .decode(value)
+                .ok()
+                .and_then(|bytes| bytes.try_into().ok())
+                .map(Memo::Hash)
+                .ok_or_else(|| invalid("hash is not 32 base64-encoded bytes")),
+            other => Err(invalid(&format!("type {} is not supported", other))),
+        }
+    }
+
+    /// Hash memo carrying a 32-hex-digit trace id in its first 16 bytes, so
+    /// the transaction on chain names the trace that submitted it.
+    pub fn from_trace_id(trace_id: &str) -> Option<Self> {
+        let bytes: [u8; 16] = hex::decode(trace_id).ok()?.try_into().ok()?;
+        let mut hash = [0u8; 32];
+        hash[..16].copy_from_slice(&bytes);
+        Some(Memo::Hash(hash))
+    }
+
+    fn to_xdr_memo(&self) -> Result<xdr::Memo, RelayerError> {
+        Ok(match self {
+            Memo::Text(text) => xdr::Memo::Text(text.as_str().try_into().map_err(invalid_xdr)?),
+            Memo::Id(id) => xdr::Memo::Id(*id),
+            Memo::Hash(hash) => xdr::Memo::Hash(xdr::Hash(*hash)),
+        })
+    }
+}
+
+/// Ed25519 signer decoded from a Stellar secret seed (`S...`).
+pub struct Keypair {
+    signing_key: SigningKey,
+}
+
+impl Keypair {
+    pub fn from_secret(secret: &str) -> Result<Self, RelayerError> {
+        let seed = ed25519::PrivateKey::from_string(secret)
+            .map_err(|_| RelayerError::InvalidAccount("invalid secret seed".to_string()))?;
+        Ok(Self {
+            signing_key: SigningKey::from_bytes(&seed.0),
+        })
+    }
+
+    pub fn public_key(&self) -> [u8; 32] {
+        self.signing_key.verifying_key().to_bytes()
+    }
+
+    pub fn account_id(&self) -> String {
+        ed25519::PublicKey(self.public_key()).to_string()
+    }
+
+    /// `DecoratedSignature` over `payload_hash`.
+    fn decorated_signature(&self, payload_hash: &[u8; 32]) -> xdr::DecoratedSignature {
+        let public_key = self.public_key();
+        let signature = self.signing_key.sign(payload_hash).to_bytes();
+        xdr::DecoratedSignature {
+            hint: xdr::SignatureHint(public_key[28..].try_into().expect("keys are 32 bytes")),
+            signature: xdr::Signature(signature.try_into().expect("signatures are 64 bytes")),
+        }
+    }
+
+    /// Envelope signatures made of this key's signature over `payload_hash`.
+    fn sole_signature(&self, payload_hash: &[u8; 32]) -> xdr::VecM<xdr::DecoratedSignature, 20> {
+        vec![self.decorated_signature(payload_hash)]
+            .try_into()
+            .expect("one signature is within the envelope limit")
+    }
+}
+
+/// One `InvokeHostFunction` call from `source` at `sequence`.
+#[derive(Debug, Clone)]
+pub struct InvokeTx {
+    pub source: [u8; 32],
+    pub fee: u32,
+    pub sequence: i64,
+    /// Unix seconds after which the network rejects the transaction.
+    pub max_time: u64,
+    pub contract_id: String,
+    pub function_name: String,
+    pub args: Vec<ScArg>,
+    /// Raw `SorobanAuthorizationEntry` XDR from simulation.
+    pub auth: Vec<Vec<u8>>,
+    /// Raw `SorobanTransactionData` XDR from simulation; `None` before simulating.
+    pub soroban_data: Option<Vec<u8>>,
+    /// Memo for the payee, e.g. the reference an anchor asked for.
+    pub memo: Option<Memo>,
+}
+
+impl InvokeTx {
+    pub fn transaction(&self) -> Result<Transaction, RelayerError> {
+        let args = self
+            .args
+            .iter()
+            .map(ScArg::to_sc_val)
+            .collect::<Result<Vec<_>, _>>()?;
+        let auth = self
+            .auth
+            .iter()
+            .map(|entry| xdr::SorobanAuthorizationEntry::from_xdr(entry, decode_limits(entry)))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(invalid_xdr)?;
+        let invoke = xdr::InvokeHostFunctionOp {
+            host_function: xdr::HostFunction::InvokeContract(xdr::InvokeContractArgs {
+                contract_address: sc_address(&self.contract_id)?,
+                function_name: sc_symbol(&self.function_name)?,
+                args: args.try_into().map_err(invalid_xdr)?,
+            }),
+            auth: auth.try_into().map_err(invalid_xdr)?,
+        };
+        let ext = match &self.soroban_data {
+            Some(data) => xdr::TransactionExt::V1(
+                xdr::SorobanTransactionData::from_xdr(data, decode_limits(data))
+                    .map_err(invalid_xdr)?,
+            ),
+            None => xdr::TransactionExt::V0,
+        };
+
+        Ok(Transaction {
+            source_account: muxed_account(&self.source),
+            fee: self.fee,
+            seq_num: xdr::SequenceNumber(self.sequence),
+            cond: time_bounds(0, self.max_time),
+            memo: match &self.memo {
+                Some(memo) => memo.to_xdr_memo()?,
+                None => xdr::Memo::None,
+            },
+            operations: vec![xdr::Operation {
+                source_account: None,
+                body: xdr::OperationBody::InvokeHostFunction(invoke),
+            }]
+            .try_into()
+            .map_err(invalid_xdr)?,
+            ext,
+        })
+    }
+
+    /// Unsigned `TransactionEnvelope` for `simulateTransaction`.
+    pub fn unsigned_envelope(&self) -> Result<String, RelayerError> {
+        xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx: self.transaction()?,
+            signatures: xdr::VecM::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .map_err(invalid_xdr)
+    }
+
+    /// Hash the network signs for this transaction.
+    pub fn hash(&self, network_passphrase: &str) -> Result<[u8; 32], RelayerError> {
+        transaction_hash(network_passphrase, &self.transaction()?)
+    }
+}
+
+/// A `ManageData` operation, with its own source account.
+#[derive(Debug, Clone)]
+pub struct ManageDataOp {
+    pub source: [u8; 32],
+    /// Entry name, at most 64 bytes.
+    pub name: String,
+    /// Entry value, at most 64 bytes.
+    pub value: Vec<u8>,
+}
+
+/// A transaction of `ManageData` operations from `source`, valid between
+/// `min_time` and `max_time`.
+#[derive(Debug, Clone)]
+pub struct ManageDataTx {
+    pub source: [u8; 32],
+    pub fee: u32,
+    pub sequence: i64,
+    pub min_time: u64,
+    pub max_time: u64,
+    pub operations: Vec<ManageDataOp>,
+}
+
+impl ManageDataTx {
+    pub fn transaction(&self) -> Result<Transaction, RelayerError> {
+        let operations = self
+            .operations
+            .iter()
+            .map(|op| {
+                Ok(xdr::Operation {
+                    source_account: Some(muxed_account(&op.source)),
+                    body: xdr::OperationBody::ManageData(xdr::ManageDataOp {
+                        data_name: xdr::String64(op.name.as_str().try_into().map_err(invalid_xdr)?),
+                        data_value: Some(xdr::DataValue(
+                            op.value.clone().try_into().map_err(invalid_xdr)?,
+                        )),
+                    }),
+                })
+            })
+            .collect::<Result<Vec<_>, RelayerError>>()?;
+
+        Ok(Transaction {
+            source_account: muxed_account(&self.source),
+            fee: self.fee,
+            seq_num: xdr::SequenceNumber(self.sequence),
+            cond: time_bounds(self.min_time, self.max_time),
+            memo: xdr::Memo::None,
+            operations: operations.try_into().map_err(invalid_xdr)?,
+            ext: xdr::TransactionExt::V0,
+        })
+    }
+
+    /// Read a transaction made only of `ManageData` operations, such as a
+    /// SEP-10 challenge from an anchor. Anything else, including a memo, is
+    /// rejected. Operations without a source account get the transaction's.
+    pub fn from_transaction(tx: &Transaction) -> Result<Self, RelayerError> {
+        let invalid = |reason: &str| RelayerError::InvalidResponse(reason.to_string());
+        let (min_time, max_time) = match &tx.cond {
+            xdr::Preconditions::None => (0, 0),
+            xdr::Preconditions::Time(bounds) => (bounds.min_time.0, bounds.max_time.0),
+            xdr::Preconditions::V2(_) => return Err(invalid("unsupported preconditions")),
+        };
+        if tx.memo != xdr::Memo::None {
+            return Err(invalid("unexpected memo"));
+        }
+        if tx.ext != xdr::TransactionExt::V0 {
+            return Err(invalid("unexpected transaction extension"));
+        }
+
+        let source = ed25519_key(&tx.source_account);
+        let operations = tx
+            .operations
+            .iter()
+            .map(|op| {
+                let xdr::OperationBody::ManageData(data) = &op.body else {
+                    return Err(invalid("operation is not ManageData"));
+                };
+                Ok(ManageDataOp {
+                    source: op.source_account.as_ref().map_or(source, ed25519_key),
+                    name: data
+                        .data_name
+                        .0
+                        .to_utf8_string()
+                        .map_err(|_| invalid("ManageData name is not UTF-8"))?,
+                    value: data
+                        .data_value
+                        .as_ref()
+                        .map(|value| value.0.to_vec())
+                        .unwrap_or_default(),
+                })
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            source,
+            fee: tx.fee,
+            sequence: tx.seq_num.0,
+            min_time,
+            max_time,
+            operations,
+        })
+    }
+}
+
+/// A signature read back from an envelope.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DecoratedSignature {
+    /// Last four bytes of the signer's public key.
+    pub hint: [u8; 4],
+    pub signature: Vec<u8>,
+}
+
+impl DecoratedSignature {
+    /// Whether this is `public_key`'s signature over `payload_hash`.
+    pub fn is_valid_for(&self, public_key: &[u8; 32], payload_hash: &[u8; 32]) -> bool {
+        if self.hint != public_key[28..] {
+            return false;
+        }
+        let (Ok(key), Ok(signature)) = (
+            VerifyingKey::from_bytes(public_key),
+            Signature::from_slice(&self.signature),
+        ) else {
+            return false;
+        };
+        key.verify_strict(payload_hash, &signature).is_ok()
+    }
+}
+
+impl From<&xdr::DecoratedSignature> for DecoratedSignature {
+    fn from(signature: &xdr::DecoratedSignature) -> Self {
+        Self {
+            hint: signature.hint.0,
+            signature: signature.signature.0.to_vec(),
+        }
+    }
+}
+
+/// A signed envelope ready for `sendTransaction`.
+#[derive(Debug, Clone)]
+pub struct SignedEnvelope {
+    pub xdr: String,
+    /// Hex hash of the outermost transaction, as reported by RPC.
+    pub hash: String,
+}
+
+/// Sign `tx` with `signer`.
+pub fn sign(
+    tx: &InvokeTx,
+    network_passphrase: &str,
+    signer: &Keypair,
+) -> Result<SignedEnvelope, RelayerError> {
+    sign_transaction(&tx.transaction()?, network_passphrase, signer)
+}
+
+/// Sign a transaction with `signer`.
+pub fn sign_transaction(
+    tx: &Transaction,
+    network_passphrase: &str,
+    signer: &Keypair,
+) -> Result<SignedEnvelope, RelayerError> {
+    let hash = transaction_hash(network_passphrase, tx)?;
+    let envelope = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+        tx: tx.clone(),
+        signatures: signer.sole_signature(&hash),
+    });
+    Ok(SignedEnvelope {
+        xdr: envelope
+            .to_xdr_base64(Limits::none())
+            .map_err(invalid_xdr)?,
+        hash: hex::encode(hash),
+    })
+}
+
+/// Hash the network signs for a transaction.
+pub fn transaction_hash(
+    network_passphrase: &str,
+    tx: &Transaction,
+) -> Result<[u8; 32], RelayerError> {
+    tx.hash(network_id(network_passphrase)).map_err(invalid_xdr)
+}
+
+/// Split a base64 `TransactionV1Envelope` into its transaction and
+/// signatures.
+pub fn split_envelope(
+    envelope_xdr: &str,
+) -> Result<(Transaction, Vec<DecoratedSignature>), RelayerError> {
+    match decode_envelope(envelope_xdr)? {
+        xdr::TransactionEnvelope::Tx(envelope) => Ok((
+            envelope.tx,
+            envelope
+                .signatures
+                .iter()
+                .map(DecoratedSignature::from)
+                .collect(),
+        )),
+        _ => Err(RelayerError::InvalidResponse(
+            "not a transaction envelope".to_string(),
+        )),
+    }
+}
+
+/// Append `signer`'s signature to a base64 `TransactionV1Envelope`, as a
+/// wallet does when it co-signs a SEP-10 challenge.
+pub fn cosign_envelope(
+    envelope_xdr: &str,
+    network_passphrase: &str,
+    signer: &Keypair,
+) -> Result<String, RelayerError> {
+    let xdr::TransactionEnvelope::Tx(mut envelope) = decode_envelope(envelope_xdr)? else {
+        return Err(RelayerError::InvalidResponse(
+            "not a transaction envelope".to_string(),
+        ));
+    };
+    let hash = transaction_hash(network_passphrase, &envelope.tx)?;
+    let mut signatures = envelope.signatures.to_vec();
+    signatures.push(signer.decorated_signature(&hash));
+    envelope.signatures = signatures.try_into().map_err(invalid_xdr)?;
+    xdr::TransactionEnvelope::Tx(envelope)
+        .to_xdr_base64(Limits::none())
+        .map_err(invalid_xdr)
+}
+
+/// Sign `tx` with `signer` and wrap it in a fee bump paid by `fee_source`.
+pub fn sign_fee_bump(
+    tx: &InvokeTx,
+    network_passphrase: &str,
+    signer: &Keypair,
+    fee_source: &Keypair,
+    fee: i64,
+) -> Result<SignedEnvelope, RelayerError> {
+    let inner = tx.transaction()?;
+    let inner_hash = transaction_hash(network_passphrase, &inner)?;
+    let fee_bump = xdr::FeeBumpTransaction {
+        fee_source: muxed_account(&fee_source.public_key()),
+        fee,
+        inner_tx: xdr::FeeBumpTransactionInnerTx::Tx(xdr::TransactionV1Envelope {
+            tx: inner,
+            signatures: signer.sole_signature(&inner_hash),
+        }),
+        ext: xdr::FeeBumpTransactionExt::V0,
+    };
+    let hash = fee_bump
+        .hash(network_id(network_passphrase))
+        .map_err(invalid_xdr)?;
+    let envelope = xdr::TransactionEnvelope::TxFeeBump(xdr::FeeBumpTransactionEnvelope {
+        tx: fee_bump,
+        signatures: fee_source.sole_signature(&hash),
+    });
+    Ok(SignedEnvelope {
+        xdr: envelope
+            .to_xdr_base64(Limits::none())
+            .map_err(invalid_xdr)?,
+        hash: hex::encode(hash),
+    })
+}
+
+/// Base64 `LedgerKey` of an account, for `getLedgerEntries`.
+pub fn account_ledger_key(account: &[u8; 32]) -> String {
+    xdr::LedgerKey::Account(xdr::LedgerKeyAccount {
+        account_id: account_id(account),
+    })
+    .to_xdr_base64(Limits::none())
+    .expect("account ledger keys always encode")
+}
+
+/// Sequence number from a base64 account `LedgerEntryData`.
+pub fn account_sequence(entry_xdr: &str) -> Result<i64, RelayerError> {
+    match xdr::LedgerEntryData::from_xdr_base64(entry_xdr, decode_limits(entry_xdr))
+        .map_err(invalid_xdr)?
+    {
+        xdr::LedgerEntryData::Account(entry) => Ok(entry.seq_num.0),
+        _ => Err(RelayerError::InvalidResponse(
+            "ledger entry is not an account".to_string(),
+        )),
+    }
+}
+
+/// `TransactionResultCode` from a base64 `TransactionResult`. For a failed fee
+/// bump, the inner transaction's code is returned instead.
+pub fn transaction_result_code(result_xdr: &str) -> Result<i32, RelayerError> {
+    let result = xdr::TransactionResult::from_xdr_base64(result_xdr, decode_limits(result_xdr))
+        .map_err(invalid_xdr)?;
+    Ok(match result.result {
+        xdr::TransactionResultResult::TxFeeBumpInnerFailed(inner) => {
+            inner.result.result.discriminant() as i32
+        }
+        other => other.discriminant() as i32,
+    })
+}
+
+/// Raw ed25519 key of an account StrKey (`G...`).
+pub fn account_public_key(account_id: &str) -> Result<[u8; 32], RelayerError> {
+    ed25519::PublicKey::from_string(account_id)
+        .map(|key| key.0)
+        .map_err(|_| RelayerError::InvalidAccount(format!("invalid account {}", account_id)))
+}
+
+/// Muxed account StrKey (`M...`) for sub-account `id` of `account`.
+pub fn muxed_account_id(account: &[u8; 32], id: u64) -> String {
+    ed25519::MuxedAccount {
+        ed25519: *account,
+        id,
+    }
+    .to_string()
+}
+
+/// Address of the Stellar Asset Contract wrapping native XLM on the network.
+pub fn native_asset_contract(network_passphrase: &str) -> String {
+    stellar_asset_contract(network_passphrase, xdr::Asset::Native)
+}
+
+/// Address of the Stellar Asset Contract wrapping the issued asset `code`
+/// (1 to 12 alphanumeric characters) of `issuer` on the network.
+pub fn issued_asset_contract(
+    network_passphrase: &str,
+    code: &str,
+    issuer: &str,
+) -> Result<String, RelayerError> {
+    if code.is_empty() || code.len() > 12 || !code.bytes().all(|b| b.is_ascii_alphanumeric()) {
+        return Err(RelayerError::InvalidAccount(format!(
+            "invalid asset code {}",
+            code
+        )));
+    }
+    let issuer = account_id(&account_public_key(issuer)?);
+    let mut padded = [0u8; 12];
+    padded[..code.len()].copy_from_slice(code.as_bytes());
+    let asset = if code.len() <= 4 {
+        xdr::Asset::CreditAlphanum4(xdr::AlphaNum4 {
+            asset_code: xdr::AssetCode4(padded[..4].try_into().expect("4-byte prefix")),
+            issuer,
+        })
+    } else {
+        xdr::Asset::CreditAlphanum12(xdr::AlphaNum12 {
+            asset_code: xdr::AssetCode12(padded),
+            issuer,
+        })
+    };
+    Ok(stellar_asset_contract(network_passphrase, asset))
+}
+
+fn stellar_asset_contract(network_passphrase: &str, asset: xdr::Asset) -> String {
+    let preimage = xdr::HashIdPreimage::ContractId(xdr::HashIdPreimageContractId {
+        network_id: xdr::Hash(network_id(network_passphrase)),
+        contract_id_preimage: xdr::ContractIdPreimage::Asset(asset),
+    })
+    .to_xdr(Limits::none())
+    .expect("asset preimages always encode");
+    Contract(Sha256::digest(preimage).into()).to_string()
+}
+
+pub fn decode_base64(value: &str) -> Result<Vec<u8>, RelayerError> {
+    general_purpose::STANDARD
+        .decode(value)
+        .map_err(|e| RelayerError::InvalidResponse(format!("invalid base64 XDR: {}", e)))
+}
+
+/// Scalar `ScVal` (bool, u32, u64 or i128) from base64 XDR, such as the return
+/// value of a simulated view call.
+pub fn decode_sc_scalar(value_xdr: &str) -> Result<ScArg, RelayerError> {
+    match xdr::ScVal::from_xdr_base64(value_xdr, decode_limits(value_xdr)).map_err(invalid_xdr)? {
+        xdr::ScVal::Bool(value) => Ok(ScArg::Bool(value)),
+        xdr::ScVal::U32(value) => Ok(ScArg::U32(value)),
+        xdr::ScVal::U64(value) => Ok(ScArg::U64(value)),
+        xdr::ScVal::I64(value) => Ok(ScArg::I64(value)),
+        xdr::ScVal::I128(parts) => Ok(ScArg::I128(((parts.hi as i128) << 64) | parts.lo as i128)),
+        other => Err(RelayerError::InvalidResponse(format!(
+            "unsupported ScVal type {}",
+            other.name()
+        ))),
+    }
+}
+
+fn invalid_xdr(e: xdr::Error) -> RelayerError {
+    RelayerError::InvalidResponse(format!("invalid XDR: {}", e))
+}
+
+fn decode_limits(input: impl AsRef<[u8]>) -> Limits {
+    Limits {
+        depth: MAX_DECODE_DEPTH,
+        len: input.as_ref().len(),
+    }
+}
+
+fn decode_envelope(envelope_xdr: &str) -> Result<xdr::TransactionEnvelope, RelayerError> {
+    xdr::TransactionEnvelope::from_xdr_base64(envelope_xdr, decode_limits(envelope_xdr))
+        .map_err(invalid_xdr)
+}
+
+fn network_id(network_passphrase: &str) -> [u8; 32] {
+    Sha256::digest(network_passphrase.as_bytes()).into()
+}
+
+fn time_bounds(min_time: u64, max_time: u64) -> xdr::Preconditions {
+    xdr::Preconditions::Time(xdr::TimeBounds {
+        min_time: xdr::TimePoint(min_time),
+        max_time: xdr::TimePoint(max_time),
+    })
+}
+
+fn account_id(key: &[u8; 32]) -> xdr::AccountId {
+    xdr::AccountId(xdr::PublicKey::PublicKeyTypeEd25519(xdr::Uint256(*key)))
+}
+
+fn muxed_account(key: &[u8; 32]) -> xdr::MuxedAccount {
+    xdr::MuxedAccount::Ed25519(xdr::Uint256(*key))
+}
+
+/// Ed25519 key of a `MuxedAccount`, dropping any muxed id.
+fn ed25519_key(account: &xdr::MuxedAccount) -> [u8; 32] {
+    match account {
+        xdr::MuxedAccount::Ed25519(key) => key.0,
+        xdr::MuxedAccount::MuxedEd25519(muxed) => muxed.ed25519.0,
+    }
+}
+
+fn sc_symbol(symbol: &str) -> Result<xdr::ScSymbol, RelayerError> {
+    Ok(xdr::ScSymbol(symbol.try_into().map_err(invalid_xdr)?))
+}
+
+fn sc_address(address: &str) -> Result<xdr::ScAddress, RelayerError> {
+    match Strkey::from_string(address) {
+        Ok(Strkey::PublicKeyEd25519(key)) => Ok(xdr::ScAddress::Account(account_id(&key.0))),
+        Ok(Strkey::Contract(contract)) => Ok(xdr::ScAddress::Contract(xdr::ContractId(xdr::Hash(
+            contract.0,
+        )))),
+        _ => Err(RelayerError::InvalidAccount(format!(
+            "{} is not an account or contract address",
+            address
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PASSPHRASE: &str = "Test SDF Network ; September 2015";
+
+    fn keypair(seed: u8) -> Keypair {
+        Keypair::from_secret(&ed25519::PrivateKey([seed; 32]).to_string()).unwrap()
+    }
+
+    fn invoke_tx(source: &Keypair) -> InvokeTx {
+        InvokeTx {
+            source: source.public_key(),
+            fee: 100,
+            sequence: 42,
+            max_time: 1_700_000_000,
+            contract_id: Contract([7; 32]).to_string(),
+            function_name: "lock_funds".to_string(),
+            args: vec![ScArg::Bytes(vec![1; 32])],
+            auth: vec![],
+            soroban_data: None,
+            memo: None,
+        }
+    }
+
+    #[test]
+    fn test_i128_splits_into_hi_lo() {
+        assert_eq!(
+            ScArg::I128(-1).to_sc_val().unwrap(),
+            xdr::ScVal::I128(xdr::Int128Parts {
+                hi: -1,
+                lo: u64::MAX,
+            })
+        );
+    }
+
+    #[test]
+    fn test_transaction_layout() {
+        let source = keypair(1);
+        let tx = invoke_tx(&source).transaction().unwrap();
+        assert_eq!(tx.source_account, muxed_account(&source.public_key()));
+        assert_eq!(tx.fee, 100);
+        assert_eq!(tx.seq_num.0, 42);
+        assert_eq!(tx.cond, time_bounds(0, 1_700_000_000));
+        // No soroban data yet: the extension is v0.
+        assert_eq!(tx.ext, xdr::TransactionExt::V0);
+
+        let xdr::OperationBody::InvokeHostFunction(op) = &tx.operations[0].body else {
+            panic!("expected an InvokeHostFunction operation");
+        };
+        let xdr::HostFunction::InvokeContract(call) = &op.host_function else {
+            panic!("expected a contract invocation");
+        };
+        assert_eq!(
+            call.contract_address,
+            xdr::ScAddress::Contract(xdr::ContractId(xdr::Hash([7; 32])))
+        );
+        assert_eq!(call.function_name.0.to_utf8_string().unwrap(), "lock_funds");
+    }
+
+    #[test]
+    fn test_unknown_addresses_are_rejected() {
+        assert!(sc_address(&keypair(1).account_id()).is_ok());
+        assert!(sc_address(&ed25519::PrivateKey([1; 32]).to_string()).is_err());
+        assert!(sc_address("not an address").is_err());
+    }
+
+    #[test]
+    fn test_fee_bump_signature_covers_outer_transaction() {
+        let signer = keypair(1);
+        let fee_source = keypair(2);
+        let tx = invoke_tx(&signer);
+        let plain = sign(&tx, PASSPHRASE, &signer).unwrap();
+        let bumped = sign_fee_bump(&tx, PASSPHRASE, &signer, &fee_source, 400).unwrap();
+        assert_ne!(plain.hash, bumped.hash);
+        assert_eq!(plain.hash, hex::encode(tx.hash(PASSPHRASE).unwrap()));
+
+        let envelope =
+            xdr::TransactionEnvelope::from_xdr_base64(&bumped.xdr, Limits::none()).unwrap();
+        assert_eq!(
+            hex::encode(envelope.hash(network_id(PASSPHRASE)).unwrap()),
+            bumped.hash
+        );
+    }
+
+    #[test]
+    fn test_result_code_reads_inner_fee_bump_code() {
+        let result = xdr::TransactionResult {
+            fee_charged: 100,
+            result: xdr::TransactionResultResult::TxFeeBumpInnerFailed(
+                xdr::InnerTransactionResultPair {
+                    transaction_hash: xdr::Hash([0; 32]),
+                    result: xdr::InnerTransactionResult {
+                        fee_charged: 100,
+                        result: xdr::InnerTransactionResultResult::TxBadSeq,
+                        ext: xdr::InnerTransactionResultExt::V0,
+                    },
+                },
+            ),
+            ext: xdr::TransactionResultExt::V0,
+        };
+        let encoded = result.to_xdr_base64(Limits::none()).unwrap();
+        assert_eq!(transaction_result_code(&encoded).unwrap(), -5);
+    }
+
+    #[test]
+    fn test_decode_sc_scalar_round_trips() {
+        for value in [
+            ScArg::Bool(true),
+            ScArg::U32(4),
+            ScArg::U64(u64::MAX - 1),
+            ScArg::I64(-1),
+            ScArg::I128(-5_000_000_000_000_000_000_000),
+        ] {
+            let encoded = value
+                .to_sc_val()
+                .unwrap()
+                .to_xdr_base64(Limits::none())
+                .unwrap();
+            assert_eq!(decode_sc_scalar(&encoded).unwrap(), value);
+        }
+
+        let symbol = ScArg::Symbol("stake".to_string())
+            .to_sc_val()
+            .unwrap()
+            .to_xdr_base64(Limits::none())
+            .unwrap();
+        assert!(decode_sc_scalar(&symbol).is_err());
+    }
+
+    #[test]
+    fn test_native_asset_contract_on_testnet() {
+        assert_eq!(
+            native_asset_contract(PASSPHRASE),
+            "CDLZFC3SYJYDZT7K67VZ75HPJVIEUVNIXF47ZG2FB2RMQQVU2HHGCYSC"
+        );
+    }
+
+    #[test]
+    fn test_issued_asset_contract_on_testnet() {
+        assert_eq!(
+            issued_asset_contract(
+                PASSPHRASE,
+                "USDC",
+                "GBBD47IF6LWK7P7MDEVSCWR7DPUWV3NY3DTQEVFL4NAT4AQH3ZLLFLA5",
+            )
+            .unwrap(),
+            "CBIELTK6YBZJU5UP2WWQEUCYKLPU6AUNZ2BQ4WWFEIE3USCIHMXQDAMA"
+        );
+        assert!(issued_asset_contract("", "", &keypair(1).account_id()).is_err());
+    }
+
+    #[test]
+    fn test_memo_parse_and_encoding() {
+        assert_eq!(Memo::parse("id", "42").unwrap(), Memo::Id(42));
+        assert!(Memo::parse("text", &"x".repeat(29)).is_err());
+        assert_eq!(
+            Memo::parse("hash", &general_purpose::STANDARD.encode([9u8; 32])).unwrap(),
+            Memo::Hash([9; 32])
+        );
+        assert!(Memo::parse("return", "abc").is_err());
+
+        let source = keypair(1);
+        assert_eq!(
+            invoke_tx(&source).transaction().unwrap().memo,
+            xdr::Memo::None
+        );
+        let mut tx = invoke_tx(&source);
+        tx.memo = Some(Memo::Id(7));
+        assert_eq!(tx.transaction().unwrap().memo, xdr::Memo::Id(7));
+        tx.memo = Some(Memo::Text("x".repeat(29)));
+        assert!(tx.transaction().is_err());
+    }
+
+    #[test]
+    fn test_memo_from_trace_id() {
+        let Some(Memo::Hash(hash)) = Memo::from_trace_id("4bf92f3577b34da6a3ce929d0e0e4736") else {
+            panic!("expected a hash memo");
+        };
+        assert_eq!(hex::encode(&hash[..16]), "4bf92f3577b34da6a3ce929d0e0e4736");
+        assert_eq!(hash[16..], [0; 16]);
+        assert!(Memo::from_trace_id("4bf92f35").is_none());
+        assert!(Memo::from_trace_id("not a trace id").is_none());
+    }
+
+    #[test]
+    fn test_manage_data_tx_round_trip() {
+        let tx = ManageDataTx {
+            source: keypair(4).public_key(),
+            fee: 200,
+            sequence: 0,
+            min_time: 1_700_000_000,
+            max_time: 1_700_000_900,
+            operations: vec![
+                ManageDataOp {
+                    source: keypair(5).public_key(),
+                    name: "anchor.example auth".to_string(),
+                    value: vec![b'n'; 64],
+                },
+                ManageDataOp {
+                    source: keypair(4).public_key(),
+                    name: "web_auth_domain".to_string(),
+                    value: b"auth.anchor.example".to_vec(),
+                },
+            ],
+        };
+        let encoded = tx.transaction().unwrap();
+        let decoded = ManageDataTx::from_transaction(&encoded).unwrap();
+        assert_eq!(decoded.transaction().unwrap(), encoded);
+        assert_eq!(decoded.operations[1].name, "web_auth_domain");
+
+        let payment_like = invoke_tx(&keypair(4)).transaction().unwrap();
+        assert!(ManageDataTx::from_transaction(&payment_like).is_err());
+
+        let mut oversized = tx.clone();
+        oversized.operations[0].value = vec![b'n'; 65];
+        assert!(oversized.transaction().is_err());
+    }
+
+    #[test]
+    fn test_split_envelope_recovers_transaction_and_signatures() {
+        let server = keypair(4);
+        let client = keypair(5);
+        let tx = ManageDataTx {
+            source: server.public_key(),
+            fee: 200,
+            sequence: 0,
+            min_time: 1_700_000_000,
+            max_time: 1_700_000_900,
+            operations: vec![ManageDataOp {
+                source: client.public_key(),
+                name: "arenax.gg auth".to_string(),
+                value: vec![b'n'; 64],
+            }],
+        }
+        .transaction()
+        .unwrap();
+        let signed = sign_transaction(&tx, PASSPHRASE, &server).unwrap();
+
+        let (unsigned, signatures) = split_envelope(&signed.xdr).unwrap();
+        assert_eq!(unsigned, tx);
+        assert_eq!(signatures.len(), 1);
+        let hash = transaction_hash(PASSPHRASE, &tx).unwrap();
+        assert_eq!(hex::encode(hash), signed.hash);
+        assert!(signatures[0].is_valid_for(&server.public_key(), &hash));
+        assert!(!signatures[0].is_valid_for(&client.public_key(), &hash));
+
+        let cosigned = cosign_envelope(&signed.xdr, PASSPHRASE, &client).unwrap();
+        let (unsigned, signatures) = split_envelope(&cosigned).unwrap();
+        assert_eq!(unsigned, tx);
+        assert_eq!(signatures.len(), 2);
+        assert!(signatures[1].is_valid_for(&client.public_key(), &hash));
+
+        let unsigned_envelope = xdr::TransactionEnvelope::Tx(xdr::TransactionV1Envelope {
+            tx: tx.clone(),
+            signatures: xdr::VecM::default(),
+        })
+        .to_xdr_base64(Limits::none())
+        .unwrap();
+        let (unsigned, signatures) = split_envelope(&unsigned_envelope).unwrap();
+        assert_eq!(unsigned, tx);
+        assert!(signatures.is_empty());
+
+        let truncated =
+            general_purpose::STANDARD.encode(&decode_base64(&signed.xdr).unwrap()[..60]);
+        assert!(split_envelope(&truncated).is_err());
+        let bumped = sign_fee_bump(&invoke_tx(&server), PASSPHRASE, &server, &client, 400).unwrap();
+        assert!(split_envelope(&bumped.xdr).is_err());
+    }
+
+    #[test]
+    fn test_muxed_account_id_round_trip() {
+        let account = keypair(3).public_key();
+        let muxed = muxed_account_id(&account, 42);
+        assert!(muxed.starts_with('M'));
+        assert_eq!(muxed.len(), 69);
+        let decoded = ed25519::MuxedAccount::from_string(&muxed).unwrap();
+        assert_eq!(decoded.ed25519, account);
+        assert_eq!(decoded.id, 42);
+    }
+}
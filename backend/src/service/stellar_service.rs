@@ -569,12 +569,14 @@ impl StellarService {
 /// `version_byte`:
 ///   - `6 << 3`  (= 0x30)  → public key  → starts with `G`
 ///   - `18 << 3` (= 0x90)  → secret key  → starts with `S`
+///   - `12 << 3` (= 0x60)  → muxed account → starts with `M`; the payload is
+///     the 32-byte key followed by the 8-byte big-endian id
 ///
 /// The format is: base32( version_byte || payload || crc16(version_byte || payload) )
 pub fn stellar_strkey_encode(version_byte: u8, payload: &[u8]) -> Result<String, String> {
-    if payload.len() != 32 {
+    if payload.len() != 32 && payload.len() != 40 {
         return Err(format!(
-            "stellar_strkey_encode: expected 32-byte payload, got {}",
+            "stellar_strkey_encode: expected 32 or 40-byte payload, got {}",
             payload.len()
         ));
     }
@@ -0,0 +1,1046 @@
+//! Teams: rosters, invitations, a shared wallet and team tournament entries.
+//!
+//! Every team has one captain, who manages the roster, the earnings splits
+//! and the wallet; players join by accepting the captain's invitation. Each
+//! member holds a share of wallet distributions in basis points. Shares always
+//! sum to [`TOTAL_SHARE_BPS`]: the captain starts with all of it and gets back
+//! the share of any member who leaves.
+//!
+//! The wallet is a [`LedgerAccount::Team`] account that members deposit into
+//! from their available balance and the captain pays out by the splits. Team
+//! tournaments are entered by the captain with a lineup of members, which is
+//! locked for the tournament so nobody plays for two teams in it.
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{
+    CreateTeamRequest, EarningsSplit, JoinTournamentRequest, PaginationParams, RegisterTeamRequest,
+    RegistrationResponse, RegistrationStatus, Team, TeamDetail, TeamDistribution, TeamInvitation,
+    TeamMember, TeamPayout, TeamRole, TeamTournament, TeamWallet, UpdateTeamRequest,
+};
+use crate::service::ledger::{self, Journal, LedgerAccount, ASSET_XLM};
+use crate::service::registration_service::RegistrationService;
+use chrono::{Duration, Utc};
+use serde_json::json;
+use sqlx::{PgExecutor, Postgres, Transaction};
+use std::collections::HashSet;
+use std::sync::Arc;
+use thiserror::Error;
+use tracing::info;
+use uuid::Uuid;
+
+/// Sum of the members' shares of distributions.
+pub const TOTAL_SHARE_BPS: i32 = 10_000;
+/// Members a team may have, captain included.
+pub const MAX_ROSTER: i64 = 20;
+const INVITATION_TTL_DAYS: i64 = 7;
+
+const TEAM_COLUMNS: &str =
+    "id, name, tag, game, description, avatar_url, created_by, created_at, updated_at, disbanded_at";
+const INVITATION_COLUMNS: &str = "i.id, i.team_id, t.name AS team_name, i.user_id, i.invited_by, \
+     i.status, i.created_at, i.expires_at, i.responded_at";
+
+#[derive(Debug, Error)]
+pub enum TeamError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("Team not found")]
+    TeamNotFound,
+    #[error("Invitation not found")]
+    InvitationNotFound,
+    #[error("You are not a member of this team")]
+    NotMember,
+    #[error("Only the team captain can do this")]
+    NotCaptain,
+    #[error("{0}")]
+    Conflict(String),
+    #[error("Insufficient balance: required {required}, available {available}")]
+    InsufficientBalance { required: i64, available: i64 },
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<TeamError> for ApiError {
+    fn from(err: TeamError) -> Self {
+        match err {
+            TeamError::InvalidRequest(_) | TeamError::InsufficientBalance { .. } => {
+                ApiError::BadRequest(err.to_string())
+            }
+            TeamError::TeamNotFound | TeamError::InvitationNotFound => ApiError::NotFound,
+            TeamError::NotMember | TeamError::NotCaptain => ApiError::Forbidden,
+            TeamError::Conflict(msg) => ApiError::Conflict(msg),
+            TeamError::DatabaseError(e) => ApiError::DatabaseError(e),
+        }
+    }
+}
+
+pub struct TeamService {
+    db_pool: DbPool,
+    registrations: Arc<RegistrationService>,
+}
+
+impl TeamService {
+    pub fn new(db_pool: DbPool, registrations: Arc<RegistrationService>) -> Self {
+        Self {
+            db_pool,
+            registrations,
+        }
+    }
+
+    // ========================================================================
+    // TEAMS
+    // ========================================================================
+
+    /// Create a team captained by `user_id`.
+    pub async fn create(
+        &self,
+        user_id: Uuid,
+        request: &CreateTeamRequest,
+    ) -> Result<TeamDetail, TeamError> {
+        validate_tag(&request.tag)?;
+
+        let mut tx = self.db_pool.begin().await?;
+        let team = sqlx::query_as::<_, Team>(&format!(
+            "INSERT INTO teams (name, tag, game, description, avatar_url, created_by) \
+             VALUES ($1, UPPER($2), LOWER($3), $4, $5, $6) RETURNING {}",
+            TEAM_COLUMNS
+        ))
+        .bind(&request.name)
+        .bind(&request.tag)
+        .bind(&request.game)
+        .bind(&request.description)
+        .bind(&request.avatar_url)
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(name_taken)?;
+        sqlx::query(
+            "INSERT INTO team_members (team_id, user_id, role, share_bps) \
+             VALUES ($1, $2, 'captain', $3)",
+        )
+        .bind(team.id)
+        .bind(user_id)
+        .bind(TOTAL_SHARE_BPS)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!(team_id = %team.id, captain = %user_id, "Team created");
+        self.team(team.id).await
+    }
+
+    pub async fn team(&self, team_id: Uuid) -> Result<TeamDetail, TeamError> {
+        let team = sqlx::query_as::<_, Team>(&format!(
+            "SELECT {} FROM teams WHERE id = $1 AND disbanded_at IS NULL",
+            TEAM_COLUMNS
+        ))
+        .bind(team_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(TeamError::TeamNotFound)?;
+        let members = sqlx::query_as::<_, TeamMember>(
+            r#"
+            SELECT m.user_id, u.username, u.avatar_url, m.role, m.share_bps, m.joined_at
+            FROM team_members m
+            JOIN users u ON u.id = m.user_id
+            WHERE m.team_id = $1
+            ORDER BY m.role = 'captain' DESC, m.joined_at
+            "#,
+        )
+        .bind(team_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(TeamDetail { team, members })
+    }
+
+    /// Teams `user_id` plays for.
+    pub async fn teams_of(&self, user_id: Uuid) -> Result<Vec<Team>, TeamError> {
+        let teams = sqlx::query_as::<_, Team>(&format!(
+            "SELECT {} FROM teams \
+             WHERE disbanded_at IS NULL \
+               AND id IN (SELECT team_id FROM team_members WHERE user_id = $1) \
+             ORDER BY name",
+            TEAM_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(teams)
+    }
+
+    pub async fn update(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        request: &UpdateTeamRequest,
+    ) -> Result<TeamDetail, TeamError> {
+        if let Some(tag) = &request.tag {
+            validate_tag(tag)?;
+        }
+        require_captain(&self.db_pool, team_id, user_id).await?;
+
+        sqlx::query(
+            r#"
+            UPDATE teams SET
+                name = COALESCE($2, name),
+                tag = COALESCE(UPPER($3), tag),
+                game = COALESCE(LOWER($4), game),
+                description = COALESCE($5, description),
+                avatar_url = COALESCE($6, avatar_url),
+                updated_at = NOW()
+            WHERE id = $1 AND disbanded_at IS NULL
+            "#,
+        )
+        .bind(team_id)
+        .bind(&request.name)
+        .bind(&request.tag)
+        .bind(&request.game)
+        .bind(&request.description)
+        .bind(&request.avatar_url)
+        .execute(&self.db_pool)
+        .await
+        .map_err(name_taken)?;
+        self.team(team_id).await
+    }
+
+    /// Disband the team, freeing its name and tag. Its wallet must be empty
+    /// and it must not be in an unfinished tournament.
+    pub async fn disband(&self, user_id: Uuid, team_id: Uuid) -> Result<(), TeamError> {
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        require_captain(&mut *tx, team_id, user_id).await?;
+
+        let balance =
+            ledger::balance_for_update(&mut tx, LedgerAccount::Team(team_id), ASSET_XLM).await?;
+        if balance != 0 {
+            return Err(TeamError::Conflict(
+                "Distribute the team wallet before disbanding".to_string(),
+            ));
+        }
+        let playing: bool = sqlx::query_scalar(
+            r#"
+            SELECT EXISTS(
+                SELECT 1 FROM tournament_participants tp
+                JOIN tournaments t ON t.id = tp.tournament_id
+                WHERE tp.team_id = $1
+                  AND t.status::TEXT NOT IN ('completed', 'cancelled')
+            )
+            "#,
+        )
+        .bind(team_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        if playing {
+            return Err(TeamError::Conflict(
+                "The team is entered in an unfinished tournament".to_string(),
+            ));
+        }
+
+        sqlx::query("UPDATE teams SET disbanded_at = NOW(), updated_at = NOW() WHERE id = $1")
+            .bind(team_id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            "UPDATE team_invitations SET status = 'revoked', responded_at = NOW() \
+             WHERE team_id = $1 AND status = 'pending'",
+        )
+        .bind(team_id)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!(%team_id, "Team disbanded");
+        Ok(())
+    }
+
+    // ========================================================================
+    // ROSTER
+    // ========================================================================
+
+    /// Invite `invitee` to the team. Inviting a player again renews their
+    /// pending invitation.
+    pub async fn invite(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        invitee: Uuid,
+    ) -> Result<TeamInvitation, TeamError> {
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        require_captain(&mut *tx, team_id, user_id).await?;
+
+        if role_in(&mut *tx, team_id, invitee).await?.is_some() {
+            return Err(TeamError::Conflict(
+                "Player is already on the team".to_string(),
+            ));
+        }
+        let seats: i64 = sqlx::query_scalar(
+            r#"
+            SELECT (SELECT COUNT(*) FROM team_members WHERE team_id = $1)
+                 + (SELECT COUNT(*) FROM team_invitations
+                    WHERE team_id = $1 AND status = 'pending' AND expires_at > NOW()
+                      AND user_id <> $2)
+            "#,
+        )
+        .bind(team_id)
+        .bind(invitee)
+        .fetch_one(&mut *tx)
+        .await?;
+        if seats >= MAX_ROSTER {
+            return Err(TeamError::InvalidRequest(format!(
+                "Teams have at most {} members, pending invitations included",
+                MAX_ROSTER
+            )));
+        }
+
+        let invitation = sqlx::query_as::<_, TeamInvitation>(&format!(
+            r#"
+            WITH i AS (
+                INSERT INTO team_invitations (team_id, user_id, invited_by, expires_at)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (team_id, user_id) WHERE status = 'pending' DO UPDATE SET
+                    invited_by = EXCLUDED.invited_by,
+                    created_at = NOW(),
+                    expires_at = EXCLUDED.expires_at
+                RETURNING *
+            )
+            SELECT {} FROM i JOIN teams t ON t.id = i.team_id
+            "#,
+            INVITATION_COLUMNS
+        ))
+        .bind(team_id)
+        .bind(invitee)
+        .bind(user_id)
+        .bind(Utc::now() + Duration::days(INVITATION_TTL_DAYS))
+        .fetch_one(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        info!(%team_id, %invitee, "Team invitation sent");
+        Ok(invitation)
+    }
+
+    pub async fn revoke_invitation(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<(), TeamError> {
+        require_captain(&self.db_pool, team_id, user_id).await?;
+        let revoked = sqlx::query(
+            "UPDATE team_invitations SET status = 'revoked', responded_at = NOW() \
+             WHERE id = $1 AND team_id = $2 AND status = 'pending'",
+        )
+        .bind(invitation_id)
+        .bind(team_id)
+        .execute(&self.db_pool)
+        .await?;
+        if revoked.rows_affected() == 0 {
+            return Err(TeamError::InvitationNotFound);
+        }
+        Ok(())
+    }
+
+    /// Pending invitations of `user_id` that have not expired.
+    pub async fn invitations(&self, user_id: Uuid) -> Result<Vec<TeamInvitation>, TeamError> {
+        let invitations = sqlx::query_as::<_, TeamInvitation>(&format!(
+            "SELECT {} FROM team_invitations i JOIN teams t ON t.id = i.team_id \
+             WHERE i.user_id = $1 AND i.status = 'pending' AND i.expires_at > NOW() \
+               AND t.disbanded_at IS NULL \
+             ORDER BY i.created_at DESC",
+            INVITATION_COLUMNS
+        ))
+        .bind(user_id)
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(invitations)
+    }
+
+    /// Join the team of an invitation. New members start with no share of
+    /// distributions.
+    pub async fn accept_invitation(
+        &self,
+        user_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<TeamDetail, TeamError> {
+        let team_id: Uuid = sqlx::query_scalar(
+            "SELECT team_id FROM team_invitations WHERE id = $1 AND user_id = $2",
+        )
+        .bind(invitation_id)
+        .bind(user_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(TeamError::InvitationNotFound)?;
+
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        let accepted = sqlx::query(
+            "UPDATE team_invitations SET status = 'accepted', responded_at = NOW() \
+             WHERE id = $1 AND status = 'pending' AND expires_at > NOW()",
+        )
+        .bind(invitation_id)
+        .execute(&mut *tx)
+        .await?;
+        if accepted.rows_affected() == 0 {
+            return Err(TeamError::InvitationNotFound);
+        }
+        let members: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM team_members WHERE team_id = $1")
+                .bind(team_id)
+                .fetch_one(&mut *tx)
+                .await?;
+        if members >= MAX_ROSTER {
+            return Err(TeamError::InvalidRequest("Team roster is full".to_string()));
+        }
+        sqlx::query(
+            "INSERT INTO team_members (team_id, user_id, role) VALUES ($1, $2, 'member') \
+             ON CONFLICT (team_id, user_id) DO NOTHING",
+        )
+        .bind(team_id)
+        .bind(user_id)
+        .execute(&mut *tx)
+        .await?;
+        touch(&mut tx, team_id).await?;
+        tx.commit().await?;
+
+        info!(%team_id, %user_id, "Player joined team");
+        self.team(team_id).await
+    }
+
+    pub async fn decline_invitation(
+        &self,
+        user_id: Uuid,
+        invitation_id: Uuid,
+    ) -> Result<(), TeamError> {
+        let declined = sqlx::query(
+            "UPDATE team_invitations SET status = 'declined', responded_at = NOW() \
+             WHERE id = $1 AND user_id = $2 AND status = 'pending'",
+        )
+        .bind(invitation_id)
+        .bind(user_id)
+        .execute(&self.db_pool)
+        .await?;
+        if declined.rows_affected() == 0 {
+            return Err(TeamError::InvitationNotFound);
+        }
+        Ok(())
+    }
+
+    /// Remove `member_id` from the team: the captain removing a member, or a
+    /// member leaving. Their share goes to the captain, who has to hand over
+    /// captaincy before leaving.
+    pub async fn remove_member(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        member_id: Uuid,
+    ) -> Result<(), TeamError> {
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        let role = role_in(&mut *tx, team_id, user_id)
+            .await?
+            .ok_or(TeamError::NotMember)?;
+        match (member_id == user_id, role) {
+            (true, TeamRole::Captain) => {
+                return Err(TeamError::InvalidRequest(
+                    "Transfer captaincy before leaving the team".to_string(),
+                ))
+            }
+            (false, TeamRole::Member) => return Err(TeamError::NotCaptain),
+            _ => {}
+        }
+
+        let share: i32 = sqlx::query_scalar(
+            "DELETE FROM team_members WHERE team_id = $1 AND user_id = $2 RETURNING share_bps",
+        )
+        .bind(team_id)
+        .bind(member_id)
+        .fetch_optional(&mut *tx)
+        .await?
+        .ok_or_else(|| TeamError::InvalidRequest("Player is not on the team".to_string()))?;
+        sqlx::query(
+            "UPDATE team_members SET share_bps = share_bps + $2 \
+             WHERE team_id = $1 AND role = 'captain'",
+        )
+        .bind(team_id)
+        .bind(share)
+        .execute(&mut *tx)
+        .await?;
+        touch(&mut tx, team_id).await?;
+        tx.commit().await?;
+
+        info!(%team_id, %member_id, "Player left team");
+        Ok(())
+    }
+
+    /// Make `new_captain` captain; the current captain stays on as a member.
+    pub async fn transfer_captaincy(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        new_captain: Uuid,
+    ) -> Result<TeamDetail, TeamError> {
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        require_captain(&mut *tx, team_id, user_id).await?;
+        if role_in(&mut *tx, team_id, new_captain).await? != Some(TeamRole::Member) {
+            return Err(TeamError::InvalidRequest(
+                "The new captain must be a member of the team".to_string(),
+            ));
+        }
+
+        // Demote first: a team has at most one captain at any time.
+        for (member, role) in [
+            (user_id, TeamRole::Member),
+            (new_captain, TeamRole::Captain),
+        ] {
+            sqlx::query("UPDATE team_members SET role = $3 WHERE team_id = $1 AND user_id = $2")
+                .bind(team_id)
+                .bind(member)
+                .bind(role)
+                .execute(&mut *tx)
+                .await?;
+        }
+        touch(&mut tx, team_id).await?;
+        tx.commit().await?;
+
+        info!(%team_id, captain = %new_captain, "Team captaincy transferred");
+        self.team(team_id).await
+    }
+
+    // ========================================================================
+    // WALLET
+    // ========================================================================
+
+    /// Replace the earnings splits. Every member must be listed once.
+    pub async fn set_splits(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        splits: &[EarningsSplit],
+    ) -> Result<Vec<EarningsSplit>, TeamError> {
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        require_captain(&mut *tx, team_id, user_id).await?;
+
+        let members: Vec<Uuid> =
+            sqlx::query_scalar("SELECT user_id FROM team_members WHERE team_id = $1")
+                .bind(team_id)
+                .fetch_all(&mut *tx)
+                .await?;
+        validate_splits(&members, splits)?;
+        for split in splits {
+            sqlx::query(
+                "UPDATE team_members SET share_bps = $3 WHERE team_id = $1 AND user_id = $2",
+            )
+            .bind(team_id)
+            .bind(split.user_id)
+            .bind(split.share_bps)
+            .execute(&mut *tx)
+            .await?;
+        }
+        let splits = splits_in(&mut *tx, team_id).await?;
+        tx.commit().await?;
+        Ok(splits)
+    }
+
+    /// The team's balance and splits, shown to its members.
+    pub async fn wallet(&self, user_id: Uuid, team_id: Uuid) -> Result<TeamWallet, TeamError> {
+        role_in(&self.db_pool, team_id, user_id)
+            .await?
+            .ok_or(TeamError::NotMember)?;
+
+        let balance: Option<i64> =
+            sqlx::query_scalar("SELECT balance FROM ledger_accounts WHERE account_key = $1")
+                .bind(LedgerAccount::Team(team_id).key(ASSET_XLM))
+                .fetch_optional(&self.db_pool)
+                .await?;
+        Ok(TeamWallet {
+            team_id,
+            asset: ASSET_XLM.to_string(),
+            balance: balance.unwrap_or(0),
+            splits: splits_in(&self.db_pool, team_id).await?,
+        })
+    }
+
+    /// Move `amount` from a member's available balance to the team wallet.
+    pub async fn deposit(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        amount: i64,
+    ) -> Result<TeamWallet, TeamError> {
+        if amount <= 0 {
+            return Err(TeamError::InvalidRequest(
+                "Amount must be positive".to_string(),
+            ));
+        }
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        role_in(&mut *tx, team_id, user_id)
+            .await?
+            .ok_or(TeamError::NotMember)?;
+
+        let available =
+            ledger::balance_for_update(&mut tx, LedgerAccount::Available(user_id), ASSET_XLM)
+                .await?;
+        if available < amount {
+            return Err(TeamError::InsufficientBalance {
+                required: amount,
+                available,
+            });
+        }
+        let journal = Journal::transfer(
+            format!("team:{}:deposit:{}", team_id, Uuid::new_v4()),
+            "team_deposit",
+            Some(user_id),
+            LedgerAccount::Available(user_id),
+            LedgerAccount::Team(team_id),
+            amount,
+        )
+        .with_metadata(json!({ "team_id": team_id }));
+        ledger::post(&mut tx, &journal).await?;
+        tx.commit().await?;
+
+        info!(%team_id, %user_id, amount, "Team wallet deposit");
+        self.wallet(user_id, team_id).await
+    }
+
+    /// Pay `amount`, or the whole balance, out to the members by their splits.
+    pub async fn distribute(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        amount: Option<i64>,
+    ) -> Result<TeamDistribution, TeamError> {
+        let mut tx = self.db_pool.begin().await?;
+        lock_team(&mut tx, team_id).await?;
+        require_captain(&mut *tx, team_id, user_id).await?;
+
+        let balance =
+            ledger::balance_for_update(&mut tx, LedgerAccount::Team(team_id), ASSET_XLM).await?;
+        let amount = amount.unwrap_or(balance);
+        if amount <= 0 {
+            return Err(TeamError::InvalidRequest(
+                "Nothing to distribute".to_string(),
+            ));
+        }
+        if amount > balance {
+            return Err(TeamError::InsufficientBalance {
+                required: amount,
+                available: balance,
+            });
+        }
+
+        let payouts = split_amount(amount, &splits_in(&mut *tx, team_id).await?);
+        let mut entries = vec![(LedgerAccount::Team(team_id), -amount)];
+        entries.extend(
+            payouts
+                .iter()
+                .map(|payout| (LedgerAccount::Available(payout.user_id), payout.amount)),
+        );
+        let journal = Journal {
+            reference: format!("team:{}:distribution:{}", team_id, Uuid::new_v4()),
+            kind: "team_distribution",
+            user_id: Some(user_id),
+            asset: ASSET_XLM.to_string(),
+            entries,
+            metadata: json!({ "team_id": team_id, "payouts": payouts }),
+        };
+        ledger::post(&mut tx, &journal).await?;
+        tx.commit().await?;
+
+        info!(%team_id, amount, members = payouts.len(), "Team wallet distributed");
+        Ok(TeamDistribution {
+            team_id,
+            amount,
+            payouts,
+        })
+    }
+
+    // ========================================================================
+    // TOURNAMENTS
+    // ========================================================================
+
+    /// Enter the team in a team tournament with `request.lineup`. The lineup is
+    /// held while the registration is checked and locked once it succeeds;
+    /// until then the captain may register again with another lineup.
+    pub async fn register(
+        &self,
+        user_id: Uuid,
+        team_id: Uuid,
+        tournament_id: Uuid,
+        idempotency_key: Option<String>,
+        request: RegisterTeamRequest,
+    ) -> Result<RegistrationResponse, ApiError> {
+        let mut tx = self
+            .db_pool
+            .begin()
+            .await
+            .map_err(ApiError::database_error)?;
+        lock_team(&mut tx, team_id).await?;
+        require_captain(&mut *tx, team_id, user_id).await?;
+
+        let team_size: Option<i32> =
+            sqlx::query_scalar::<_, Option<i32>>("SELECT team_size FROM tournaments WHERE id = $1")
+                .bind(tournament_id)
+                .fetch_optional(&mut *tx)
+                .await
+                .map_err(ApiError::database_error)?
+                .ok_or_else(|| ApiError::not_found("Tournament not found"))?;
+        let team_size = team_size
+            .ok_or_else(|| ApiError::bad_request("This tournament is not a team event"))?;
+        let members: Vec<Uuid> =
+            sqlx::query_scalar("SELECT user_id FROM team_members WHERE team_id = $1")
+                .bind(team_id)
+                .fetch_all(&mut *tx)
+                .await
+                .map_err(ApiError::database_error)?;
+        validate_lineup(&request.lineup, user_id, &members, team_size)?;
+
+        let registered: bool = sqlx::query_scalar(
+            "SELECT EXISTS(SELECT 1 FROM tournament_participants \
+                           WHERE tournament_id = $1 AND team_id = $2)",
+        )
+        .bind(tournament_id)
+        .bind(team_id)
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(ApiError::database_error)?;
+        if registered {
+            let locked: Vec<Uuid> = sqlx::query_scalar(
+                "SELECT user_id FROM tournament_team_lineups \
+                 WHERE tournament_id = $1 AND team_id = $2",
+            )
+            .bind(tournament_id)
+            .bind(team_id)
+            .fetch_all(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+            if !same_players(&locked, &request.lineup) {
+                return Err(ApiError::conflict(
+                    "The team's lineup for this tournament is locked",
+                ));
+            }
+        } else {
+            sqlx::query(
+                "DELETE FROM tournament_team_lineups WHERE tournament_id = $1 AND team_id = $2",
+            )
+            .bind(tournament_id)
+            .bind(team_id)
+            .execute(&mut *tx)
+            .await
+            .map_err(ApiError::database_error)?;
+            sqlx::query(
+                "INSERT INTO tournament_team_lineups (tournament_id, team_id, user_id) \
+                 SELECT $1, $2, UNNEST($3::uuid[])",
+            )
+            .bind(tournament_id)
+            .bind(team_id)
+            .bind(&request.lineup)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::Database(db) if db.is_unique_violation() => ApiError::conflict(
+                    "A player in the lineup already plays for another team in this tournament",
+                ),
+                e => ApiError::database_error(e),
+            })?;
+        }
+        tx.commit().await.map_err(ApiError::database_error)?;
+
+        let registration = self
+            .registrations
+            .register_team(
+                user_id,
+                team_id,
+                tournament_id,
+                idempotency_key,
+                JoinTournamentRequest {
+                    payment_method: request.payment_method,
+                    payment_reference: request.payment_reference,
+                },
+            )
+            .await;
+        match &registration {
+            Ok(response) if response.status != RegistrationStatus::Rejected => {}
+            _ => self.release_lineup(tournament_id, team_id).await?,
+        }
+        let registration = registration?;
+        info!(%team_id, %tournament_id, status = ?registration.status, "Team registration");
+        Ok(registration)
+    }
+
+    /// Tournaments the team entered, latest first, with their lineups.
+    pub async fn tournaments(
+        &self,
+        team_id: Uuid,
+        pagination: &PaginationParams,
+    ) -> Result<Vec<TeamTournament>, TeamError> {
+        let tournaments = sqlx::query_as::<_, TeamTournament>(
+            r#"
+            SELECT t.id AS tournament_id, t.name, t.game, COALESCE(t.status, 0) AS status,
+                   tp.final_rank, tp.prize_amount, tp.registered_at,
+                   ARRAY(SELECT l.user_id FROM tournament_team_lineups l
+                         WHERE l.tournament_id = t.id AND l.team_id = tp.team_id) AS lineup
+            FROM tournament_participants tp
+            JOIN tournaments t ON t.id = tp.tournament_id
+            WHERE tp.team_id = $1
+            ORDER BY tp.registered_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(team_id)
+        .bind(pagination.resolved_limit())
+        .bind(pagination.sql_offset())
+        .fetch_all(&self.db_pool)
+        .await?;
+        Ok(tournaments)
+    }
+
+    /// Drop the lineup held for a registration that did not go through.
+    async fn release_lineup(&self, tournament_id: Uuid, team_id: Uuid) -> Result<(), ApiError> {
+        sqlx::query(
+            r#"
+            DELETE FROM tournament_team_lineups
+            WHERE tournament_id = $1 AND team_id = $2
+              AND NOT EXISTS (
+                  SELECT 1 FROM tournament_participants
+                  WHERE tournament_id = $1 AND team_id = $2
+              )
+            "#,
+        )
+        .bind(tournament_id)
+        .bind(team_id)
+        .execute(&self.db_pool)
+        .await
+        .map_err(ApiError::database_error)?;
+        Ok(())
+    }
+}
+
+// ============================================================================
+// PERSISTENCE
+// ============================================================================
+
+/// Lock the team row, serializing changes to its roster and wallet.
+async fn lock_team(tx: &mut Transaction<'_, Postgres>, team_id: Uuid) -> Result<(), TeamError> {
+    sqlx::query("SELECT id FROM teams WHERE id = $1 AND disbanded_at IS NULL FOR UPDATE")
+        .bind(team_id)
+        .fetch_optional(&mut **tx)
+        .await?
+        .ok_or(TeamError::TeamNotFound)?;
+    Ok(())
+}
+
+/// Bump `updated_at` so search picks up roster changes.
+async fn touch(tx: &mut Transaction<'_, Postgres>, team_id: Uuid) -> Result<(), TeamError> {
+    sqlx::query("UPDATE teams SET updated_at = NOW() WHERE id = $1")
+        .bind(team_id)
+        .execute(&mut **tx)
+        .await?;
+    Ok(())
+}
+
+/// Role of `user_id` on a team that has not been disbanded.
+async fn role_in<'e>(
+    executor: impl PgExecutor<'e>,
+    team_id: Uuid,
+    user_id: Uuid,
+) -> Result<Option<TeamRole>, TeamError> {
+    let role: Option<Option<TeamRole>> = sqlx::query_scalar(
+        r#"
+        SELECT m.role FROM teams t
+        LEFT JOIN team_members m ON m.team_id = t.id AND m.user_id = $2
+        WHERE t.id = $1 AND t.disbanded_at IS NULL
+        "#,
+    )
+    .bind(team_id)
+    .bind(user_id)
+    .fetch_optional(executor)
+    .await?;
+    role.ok_or(TeamError::TeamNotFound)
+}
+
+async fn require_captain<'e>(
+    executor: impl PgExecutor<'e>,
+    team_id: Uuid,
+    user_id: Uuid,
+) -> Result<(), TeamError> {
+    match role_in(executor, team_id, user_id).await? {
+        Some(TeamRole::Captain) => Ok(()),
+        Some(TeamRole::Member) => Err(TeamError::NotCaptain),
+        None => Err(TeamError::NotMember),
+    }
+}
+
+/// Splits with the captain first, then members by when they joined.
+async fn splits_in<'e>(
+    executor: impl PgExecutor<'e>,
+    team_id: Uuid,
+) -> Result<Vec<EarningsSplit>, TeamError> {
+    let splits = sqlx::query_as::<_, EarningsSplit>(
+        "SELECT user_id, share_bps FROM team_members WHERE team_id = $1 \
+         ORDER BY role = 'captain' DESC, joined_at",
+    )
+    .bind(team_id)
+    .fetch_all(executor)
+    .await?;
+    Ok(splits)
+}
+
+fn name_taken(e: sqlx::Error) -> TeamError {
+    match e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            TeamError::Conflict("Team name or tag is already taken".to_string())
+        }
+        e => TeamError::DatabaseError(e),
+    }
+}
+
+// ============================================================================
+// RULES
+// ============================================================================
+
+fn validate_tag(tag: &str) -> Result<(), TeamError> {
+    if !tag.chars().all(|c| c.is_ascii_alphanumeric()) {
+        return Err(TeamError::InvalidRequest(
+            "Tag must contain only letters and digits".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Check `splits` list every one of `members` once, with shares summing to
+/// [`TOTAL_SHARE_BPS`].
+fn validate_splits(members: &[Uuid], splits: &[EarningsSplit]) -> Result<(), TeamError> {
+    let listed: Vec<Uuid> = splits.iter().map(|split| split.user_id).collect();
+    if !same_players(members, &listed) {
+        return Err(TeamError::InvalidRequest(
+            "Splits must list every team member once".to_string(),
+        ));
+    }
+    if splits
+        .iter()
+        .any(|split| !(0..=TOTAL_SHARE_BPS).contains(&split.share_bps))
+    {
+        return Err(TeamError::InvalidRequest(format!(
+            "Shares must be between 0 and {} basis points",
+            TOTAL_SHARE_BPS
+        )));
+    }
+    if splits.iter().map(|split| split.share_bps).sum::<i32>() != TOTAL_SHARE_BPS {
+        return Err(TeamError::InvalidRequest(format!(
+            "Shares must sum to {} basis points",
+            TOTAL_SHARE_BPS
+        )));
+    }
+    Ok(())
+}
+
+/// Check a lineup is `team_size` distinct members, the captain among them.
+fn validate_lineup(
+    lineup: &[Uuid],
+    captain: Uuid,
+    members: &[Uuid],
+    team_size: i32,
+) -> Result<(), TeamError> {
+    if lineup.len() != team_size as usize {
+        return Err(TeamError::InvalidRequest(format!(
+            "The lineup must have exactly {} players",
+            team_size
+        )));
+    }
+    if lineup.iter().collect::<HashSet<_>>().len() != lineup.len() {
+        return Err(TeamError::InvalidRequest(
+            "The lineup lists a player twice".to_string(),
+        ));
+    }
+    if !lineup.contains(&captain) {
+        return Err(TeamError::InvalidRequest(
+            "The captain must play in the lineup".to_string(),
+        ));
+    }
+    if lineup.iter().any(|player| !members.contains(player)) {
+        return Err(TeamError::InvalidRequest(
+            "Every player in the lineup must be a team member".to_string(),
+        ));
+    }
+    Ok(())
+}
+
+/// Whether `a` and `b` hold the same players, each once.
+fn same_players(a: &[Uuid], b: &[Uuid]) -> bool {
+    let set: HashSet<&Uuid> = a.iter().collect();
+    set.len() == a.len() && set == b.iter().collect() && a.len() == b.len()
+}
+
+/// `amount` split by share, rounded down. What rounding leaves over goes to
+/// the first split, the captain's; members with nothing to receive are left
+/// out.
+fn split_amount(amount: i64, splits: &[EarningsSplit]) -> Vec<TeamPayout> {
+    let mut payouts: Vec<TeamPayout> = splits
+        .iter()
+        .map(|split| TeamPayout {
+            user_id: split.user_id,
+            amount: (amount as i128 * split.share_bps as i128 / TOTAL_SHARE_BPS as i128) as i64,
+        })
+        .collect();
+    let paid: i64 = payouts.iter().map(|payout| payout.amount).sum();
+    if let Some(first) = payouts.first_mut() {
+        first.amount += amount - paid;
+    }
+    payouts.retain(|payout| payout.amount > 0);
+    payouts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn split(user_id: Uuid, share_bps: i32) -> EarningsSplit {
+        EarningsSplit { user_id, share_bps }
+    }
+
+    #[test]
+    fn test_split_amount_gives_remainder_to_captain() {
+        let (captain, a, b) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let payouts = split_amount(
+            1_001,
+            &[split(captain, 3_334), split(a, 3_333), split(b, 3_333)],
+        );
+        let amounts: Vec<i64> = payouts.iter().map(|payout| payout.amount).collect();
+        assert_eq!(amounts, vec![335, 333, 333]);
+
+        let payouts = split_amount(500, &[split(captain, 10_000), split(a, 0)]);
+        assert_eq!(payouts.len(), 1);
+        assert_eq!(payouts[0].user_id, captain);
+        assert_eq!(payouts[0].amount, 500);
+    }
+
+    #[test]
+    fn test_validate_splits() {
+        let (captain, member) = (Uuid::new_v4(), Uuid::new_v4());
+        let members = [captain, member];
+        assert!(validate_splits(&members, &[split(captain, 6_000), split(member, 4_000)]).is_ok());
+        assert!(validate_splits(&members, &[split(captain, 6_000), split(member, 3_000)]).is_err());
+        assert!(validate_splits(&members, &[split(captain, 10_000)]).is_err());
+        assert!(
+            validate_splits(&members, &[split(captain, 5_000), split(captain, 5_000)]).is_err()
+        );
+        assert!(
+            validate_splits(&members, &[split(captain, 11_000), split(member, -1_000)]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_validate_lineup() {
+        let (captain, a, b) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let members = [captain, a];
+        assert!(validate_lineup(&[a, captain], captain, &members, 2).is_ok());
+        assert!(validate_lineup(&[captain], captain, &members, 2).is_err());
+        assert!(validate_lineup(&[captain, captain], captain, &members, 2).is_err());
+        assert!(validate_lineup(&[a, b], captain, &members, 2).is_err());
+        assert!(validate_lineup(&[captain, b], captain, &members, 2).is_err());
+    }
+}
@@ -1,12 +1,16 @@
 use crate::api_error::ApiError;
 use crate::db::DbPool;
 use crate::models::*;
+use crate::realtime::event_bus::EventBus;
+use crate::realtime::events::RealtimeEvent;
 use crate::service::soroban_service::{SorobanService, TxStatus};
+use crate::service::stellar_relayer::StellarRelayer;
 use crate::service::stellar_service::stellar_strkey_encode;
-use chrono::{DateTime, Utc};
+use chrono::Utc;
 use ed25519_dalek::SigningKey;
 use rand::rngs::OsRng;
 use redis::Client as RedisClient;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::Row;
 use std::collections::HashMap;
@@ -19,8 +23,17 @@ pub struct TournamentService {
     soroban_service: Option<Arc<SorobanService>>,
     prize_contract_id: Option<String>,
     admin_secret: Option<String>,
+    relayer: Option<Arc<StellarRelayer>>,
+    match_contract_id: Option<String>,
+    event_bus: Option<EventBus>,
 }
 
+/// How long players have to check in once a round's window opens.
+const CHECKIN_WINDOW_MINUTES: i64 = 10;
+
+/// How often the check-in scheduler wakes up (seconds).
+const CHECKIN_POLL_SECS: u64 = 30;
+
 impl TournamentService {
     pub fn new(db_pool: DbPool) -> Self {
         Self {
@@ -29,6 +42,9 @@ impl TournamentService {
             soroban_service: None,
             prize_contract_id: None,
             admin_secret: None,
+            relayer: None,
+            match_contract_id: None,
+            event_bus: None,
         }
     }
 
@@ -51,6 +67,19 @@ impl TournamentService {
         self
     }
 
+    pub fn with_event_bus(mut self, event_bus: EventBus) -> Self {
+        self.event_bus = Some(event_bus);
+        self
+    }
+
+    /// Attach the relayer so matches forfeited at check-in are cancelled on
+    /// the match contract.
+    pub fn with_relayer(mut self, relayer: Arc<StellarRelayer>, match_contract_id: String) -> Self {
+        self.relayer = Some(relayer);
+        self.match_contract_id = Some(match_contract_id);
+        self
+    }
+
     /// Create a new tournament
     pub async fn create_tournament(
         &self,
@@ -67,10 +96,16 @@ impl TournamentService {
             INSERT INTO tournaments (
                 id, name, description, game, max_participants, entry_fee, entry_fee_currency,
                 prize_pool, prize_pool_currency, status, start_time, registration_deadline,
-                created_by, created_at, updated_at, bracket_type, rules, min_skill_level, max_skill_level
+                created_by, created_at, updated_at, bracket_type, rules, min_skill_level, max_skill_level,
+                team_size
             ) VALUES (
-                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19
-            ) RETURNING *
+                $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16, $17, $18, $19,
+                $20
+            ) RETURNING
+                id, name, description, game, max_participants, entry_fee, entry_fee_currency,
+                prize_pool, prize_pool_currency, status, start_time, end_time, registration_deadline,
+                created_by, created_at, updated_at, bracket_type, rules, min_skill_level,
+                max_skill_level, cleaned_up_at, region, team_size
             "#,
             Uuid::new_v4(),
             request.name,
@@ -90,7 +125,8 @@ impl TournamentService {
             request.bracket_type as _,
             request.rules,
             request.min_skill_level,
-            request.max_skill_level
+            request.max_skill_level,
+            request.team_size
         )
         .fetch_one(&self.db_pool)
         .await
@@ -133,52 +169,25 @@ impl TournamentService {
     ) -> Result<TournamentListResponse, ApiError> {
         let offset = (page - 1) * per_page;
 
-        let mut query = String::from(
-            "SELECT t.*, COUNT(tp.id) as current_participants FROM tournaments t
-             LEFT JOIN tournament_participants tp ON t.id = tp.tournament_id
-             WHERE 1=1",
-        );
-        let mut params: Vec<Box<dyn sqlx::Encode<'_, sqlx::Postgres> + Send + Sync>> = Vec::new();
-        let mut param_count = 0;
-
-        if let Some(status) = status_filter {
-            param_count += 1;
-            query.push_str(&format!(" AND t.status = ${}", param_count));
-            params.push(Box::new(status as i32));
-        }
-
-        if let Some(game) = game_filter {
-            param_count += 1;
-            query.push_str(&format!(" AND t.game = ${}", param_count));
-            params.push(Box::new(game));
-        }
-
-        query.push_str(" GROUP BY t.id ORDER BY t.created_at DESC");
-
-        param_count += 1;
-        query.push_str(&format!(" LIMIT ${}", param_count));
-        params.push(Box::new(per_page));
-
-        param_count += 1;
-        query.push_str(&format!(" OFFSET ${}", param_count));
-        params.push(Box::new(offset));
-
-        // For now, we'll use a simpler approach with sqlx::query
         let tournaments = sqlx::query!(
             r#"
-            SELECT t.*, COUNT(tp.id) as current_participants
+            SELECT
+                t.id, t.name, t.description, t.game, t.max_participants, t.entry_fee,
+                t.entry_fee_currency, t.prize_pool, t.prize_pool_currency, t.status, t.start_time,
+                t.end_time, t.registration_deadline, t.bracket_type,
+                COUNT(tp.id) as current_participants
             FROM tournaments t
             LEFT JOIN tournament_participants tp ON t.id = tp.tournament_id
-            WHERE ($1::text IS NULL OR t.status = $1::tournament_status)
+            WHERE ($1::int IS NULL OR t.status = $1)
             AND ($2::text IS NULL OR t.game = $2)
             GROUP BY t.id
             ORDER BY t.created_at DESC
             LIMIT $3 OFFSET $4
             "#,
             status_filter.map(|s| s as i32),
-            game_filter,
-            per_page,
-            offset
+            game_filter.as_deref(),
+            per_page as i64,
+            offset as i64
         )
         .fetch_all(&self.db_pool)
         .await
@@ -189,11 +198,11 @@ impl TournamentService {
             r#"
             SELECT COUNT(*) as count
             FROM tournaments t
-            WHERE ($1::text IS NULL OR t.status = $1::tournament_status)
+            WHERE ($1::int IS NULL OR t.status = $1)
             AND ($2::text IS NULL OR t.game = $2)
             "#,
             status_filter.map(|s| s as i32),
-            game_filter
+            game_filter.as_deref()
         )
         .fetch_one(&self.db_pool)
         .await
@@ -261,7 +270,11 @@ impl TournamentService {
     ) -> Result<TournamentResponse, ApiError> {
         let tournament = sqlx::query!(
             r#"
-            SELECT t.*, COUNT(tp.id) as current_participants
+            SELECT
+                t.id, t.name, t.description, t.game, t.max_participants, t.entry_fee,
+                t.entry_fee_currency, t.prize_pool, t.prize_pool_currency, t.status, t.start_time,
+                t.end_time, t.registration_deadline, t.bracket_type,
+                COUNT(tp.id) as current_participants
             FROM tournaments t
             LEFT JOIN tournament_participants tp ON t.id = tp.tournament_id
             WHERE t.id = $1
@@ -415,7 +428,7 @@ impl TournamentService {
         let username = self
             .get_user_username(user_id)
             .await
-            .unwrap_or_else(|| "Unknown".to_string());
+            .unwrap_or_else(|_| "Unknown".to_string());
 
         let participant_count = self
             .get_participant_count(tournament_id)
@@ -448,7 +461,11 @@ impl TournamentService {
             UPDATE tournaments
             SET status = $1, updated_at = $2
             WHERE id = $3
-            RETURNING *
+            RETURNING
+                id, name, description, game, max_participants, entry_fee, entry_fee_currency,
+                prize_pool, prize_pool_currency, status, start_time, end_time, registration_deadline,
+                created_by, created_at, updated_at, bracket_type, rules, min_skill_level,
+                max_skill_level, cleaned_up_at, region, team_size
             "#,
             new_status as _,
             Utc::now(),
@@ -658,7 +675,7 @@ impl TournamentService {
         // Check user's ArenaX token balance
         let wallet = self.get_user_wallet(user_id).await?;
 
-        if wallet.balance_arenax_tokens < tournament.entry_fee {
+        if wallet.balance_arenax_tokens.unwrap_or(0) < tournament.entry_fee {
             return Err(ApiError::bad_request("Insufficient ArenaX token balance"));
         }
 
@@ -842,7 +859,7 @@ impl TournamentService {
             Uuid::new_v4(),
             user_id,
             transaction_type as _,
-            amount,
+            Decimal::from(amount),
             currency,
             TransactionStatus::Completed as _,
             Uuid::new_v4().to_string(),
@@ -933,8 +950,8 @@ impl TournamentService {
     }
 
     async fn start_tournament(&self, tournament_id: Uuid) -> Result<(), ApiError> {
-        let seeding = crate::orchestrator::SeedingEngine::new(self.db_pool.clone());
-        seeding.seed_and_generate_bracket(tournament_id).await?;
+        let tournament = self.get_tournament_by_id(tournament_id).await?;
+        self.generate_bracket(tournament_id, tournament.bracket_type).await?;
         Ok(())
     }
 
@@ -1030,7 +1047,7 @@ impl TournamentService {
             let matches_in_round = if round_num == 1 {
                 participant_count / 2
             } else {
-                (participant_count / (2_i32.pow(round_num as u32))) as usize
+                participant_count / 2_usize.pow(round_num as u32)
             };
 
             for match_num in 1..=matches_in_round {
@@ -1075,7 +1092,14 @@ impl TournamentService {
     async fn get_tournament_by_id(&self, tournament_id: Uuid) -> Result<Tournament, ApiError> {
         sqlx::query_as!(
             Tournament,
-            "SELECT * FROM tournaments WHERE id = $1",
+            r#"
+            SELECT
+                id, name, description, game, max_participants, entry_fee, entry_fee_currency,
+                prize_pool, prize_pool_currency, status, start_time, end_time, registration_deadline,
+                created_by, created_at, updated_at, bracket_type, rules, min_skill_level,
+                max_skill_level, cleaned_up_at, region, team_size
+            FROM tournaments WHERE id = $1
+            "#,
             tournament_id
         )
         .fetch_optional(&self.db_pool)
@@ -1224,7 +1248,7 @@ impl TournamentService {
             Uuid::new_v4(),
             user_id,
             transaction_type as _,
-            amount,
+            Decimal::from(amount),
             currency,
             TransactionStatus::Completed as _,
             Uuid::new_v4().to_string(),
@@ -1261,7 +1285,7 @@ impl TournamentService {
         let verifying_key = signing_key.verifying_key();
 
         let public_key = stellar_strkey_encode(6 << 3, verifying_key.as_bytes())
-            .map_err(|e| ApiError::internal_server_error(e))?;
+            .map_err(|e| ApiError::internal_error(e))?;
 
         tracing::info!(
             public_key = %public_key,
@@ -1284,12 +1308,12 @@ impl TournamentService {
                 .get(&url)
                 .send()
                 .await
-                .map_err(|e| ApiError::internal_server_error(format!("Friendbot request failed: {}", e)))?;
+                .map_err(|e| ApiError::internal_error(format!("Friendbot request failed: {}", e)))?;
 
             if !response.status().is_success() {
                 let status = response.status();
                 let body = response.text().await.unwrap_or_default();
-                return Err(ApiError::internal_server_error(format!(
+                return Err(ApiError::internal_error(format!(
                     "Friendbot funding failed ({}): {}",
                     status, body
                 )));
@@ -1305,7 +1329,7 @@ impl TournamentService {
             // the XDR builder (tracked separately). For now, verify the admin
             // secret is configured so the failure is actionable at startup.
             if self.admin_secret.is_none() {
-                return Err(ApiError::internal_server_error(
+                return Err(ApiError::internal_error(
                     "STELLAR_ADMIN_SECRET is required to fund prize-pool accounts on mainnet"
                         .to_string(),
                 ));
@@ -1457,7 +1481,14 @@ impl TournamentService {
         // Update tournament status
         let updated = sqlx::query_as!(
             Tournament,
-            r#"UPDATE tournaments SET status = $1, updated_at = $2 WHERE id = $3 RETURNING *"#,
+            r#"
+            UPDATE tournaments SET status = $1, updated_at = $2 WHERE id = $3
+            RETURNING
+                id, name, description, game, max_participants, entry_fee, entry_fee_currency,
+                prize_pool, prize_pool_currency, status, start_time, end_time, registration_deadline,
+                created_by, created_at, updated_at, bracket_type, rules, min_skill_level,
+                max_skill_level, cleaned_up_at, region, team_size
+            "#,
             TournamentStatus::Cancelled as _,
             Utc::now(),
             tournament_id,
@@ -1475,6 +1506,196 @@ impl TournamentService {
         Ok(updated)
     }
 
+    /// Seed participants by reputation and generate the bracket for `format`.
+    pub async fn generate_bracket(
+        &self,
+        tournament_id: Uuid,
+        format: BracketType,
+    ) -> Result<BracketView, ApiError> {
+        crate::service::BracketEngine::new(self.db_pool.clone())
+            .generate(tournament_id, format)
+            .await
+    }
+
+    /// Record the winner of a bracket slot and return the advanced bracket.
+    pub async fn record_bracket_result(
+        &self,
+        tournament_id: Uuid,
+        slot_id: Uuid,
+        winner_id: Uuid,
+    ) -> Result<BracketView, ApiError> {
+        let engine = crate::service::BracketEngine::new(self.db_pool.clone());
+        engine.record_result(tournament_id, slot_id, winner_id).await?;
+        engine.get_bracket(tournament_id).await
+    }
+
+    pub async fn get_bracket(&self, tournament_id: Uuid) -> Result<BracketView, ApiError> {
+        crate::service::BracketEngine::new(self.db_pool.clone())
+            .get_bracket(tournament_id)
+            .await
+    }
+
+    /// Check a player in for their next bracket match.
+    pub async fn check_in(
+        &self,
+        tournament_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<CheckInResponse, ApiError> {
+        crate::service::BracketEngine::new(self.db_pool.clone())
+            .check_in(tournament_id, user_id)
+            .await
+    }
+
+    /// Spawn the check-in scheduler as a detached Tokio task.
+    pub fn run_checkin_scheduler(self: Arc<Self>) {
+        tokio::spawn(async move {
+            tracing::info!(
+                interval_secs = CHECKIN_POLL_SECS,
+                "Tournament check-in scheduler started"
+            );
+            let mut ticker =
+                tokio::time::interval(std::time::Duration::from_secs(CHECKIN_POLL_SECS));
+            // Skip the immediate first tick so startup is not held up.
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.process_checkins().await {
+                    tracing::error!(error = %e, "Check-in scheduler tick failed");
+                }
+            }
+        });
+    }
+
+    /// Open check-in windows for the next round of every running bracket and
+    /// forfeit players who let their window lapse.
+    pub async fn process_checkins(&self) -> Result<(), ApiError> {
+        let engine = crate::service::BracketEngine::new(self.db_pool.clone());
+
+        let opened = engine
+            .open_checkin_windows(chrono::Duration::minutes(CHECKIN_WINDOW_MINUTES))
+            .await?;
+        for slot in &opened {
+            let deadline = slot
+                .checkin_deadline
+                .map(|d| d.format("%H:%M UTC").to_string())
+                .unwrap_or_default();
+            for player in [slot.player1_id, slot.player2_id].into_iter().flatten() {
+                self.notify(
+                    player,
+                    "tournament_checkin",
+                    "Check-in is open",
+                    &format!(
+                        "Check in for your next match before {} or you will forfeit.",
+                        deadline
+                    ),
+                    slot.tournament_id,
+                )
+                .await;
+            }
+        }
+
+        for (slot, no_shows) in engine.expired_checkins().await? {
+            if let Err(e) = self.forfeit_no_shows(&engine, &slot, &no_shows).await {
+                tracing::error!(
+                    slot_id = %slot.id,
+                    error = %e,
+                    "Failed to forfeit check-in no-shows"
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn forfeit_no_shows(
+        &self,
+        engine: &crate::service::BracketEngine,
+        slot: &BracketSlot,
+        no_shows: &[Uuid],
+    ) -> Result<(), ApiError> {
+        let complete = engine.forfeit(slot.tournament_id, slot.id, no_shows).await?;
+
+        for player in [slot.player1_id, slot.player2_id].into_iter().flatten() {
+            let (title, message) = if no_shows.contains(&player) {
+                (
+                    "Match forfeited",
+                    "You missed check-in and have been removed from the tournament.",
+                )
+            } else {
+                (
+                    "Opponent did not check in",
+                    "Your opponent missed check-in; you advance by forfeit.",
+                )
+            };
+            self.notify(player, "tournament_forfeit", title, message, slot.tournament_id)
+                .await;
+        }
+
+        self.publish_tournament_event(serde_json::json!({
+            "type": "bracket_forfeit",
+            "tournament_id": slot.tournament_id,
+            "slot_id": slot.id,
+            "no_shows": no_shows,
+            "bracket_complete": complete,
+        }))
+        .await?;
+
+        if let Some(match_id) = &slot.on_chain_match_id {
+            self.cancel_on_chain_match(slot.id, match_id).await;
+        }
+        Ok(())
+    }
+
+    /// Cancel a forfeited match on the match contract. Failures are logged;
+    /// the bracket has already moved on.
+    async fn cancel_on_chain_match(&self, slot_id: Uuid, match_id: &str) {
+        let (Some(relayer), Some(contract)) = (&self.relayer, &self.match_contract_id) else {
+            return;
+        };
+        let id: [u8; 32] = match hex::decode(match_id).ok().and_then(|b| b.try_into().ok()) {
+            Some(id) => id,
+            None => {
+                tracing::warn!(slot_id = %slot_id, match_id, "Invalid on-chain match id");
+                return;
+            }
+        };
+        if let Err(e) = relayer.cancel_match(contract, id).await {
+            tracing::error!(
+                slot_id = %slot_id,
+                error = %e,
+                "Failed to cancel forfeited match on chain"
+            );
+        }
+    }
+
+    async fn notify(
+        &self,
+        user_id: Uuid,
+        typ: &str,
+        title: &str,
+        message: &str,
+        tournament_id: Uuid,
+    ) {
+        let result = sqlx::query(
+            r#"
+            INSERT INTO notifications (user_id, type, category, title, message, link, link_label)
+            VALUES ($1, $2, 'tournament', $3, $4, $5, $6)
+            "#,
+        )
+        .bind(user_id)
+        .bind(typ)
+        .bind(title)
+        .bind(message)
+        .bind(format!("/tournaments/{}", tournament_id))
+        .bind("View bracket")
+        .execute(&self.db_pool)
+        .await;
+        if let Err(e) = result {
+            tracing::warn!(user_id = %user_id, error = %e, "Failed to store notification");
+        }
+    }
+
     /// Advance the tournament bracket to the next round.
     ///
     /// Transitions the tournament to `InProgress` (generating the initial
@@ -2034,8 +2255,8 @@ impl TournamentService {
 
         // Process matches to determine rankings
         for tournament_match in matches {
-            let loser_id = if tournament_match.winner_id != Some(tournament_match.player1_id) {
-                Some(tournament_match.player1_id)
+            let loser_id = if tournament_match.winner_id != tournament_match.player1_id {
+                tournament_match.player1_id
             } else {
                 tournament_match
                     .player2_id
@@ -2095,7 +2316,6 @@ impl TournamentService {
                 "#,
                 tournament_id,
                 participant.user_id,
-                participant.user_id,
                 MatchStatus::Completed as _
             )
             .fetch_one(&self.db_pool)
@@ -2197,13 +2417,28 @@ impl TournamentService {
     }
 
     // Real-time event publishing methods
-    // TODO: Implement proper realtime module with event types
+    /// Publish `event_data` on the channel of its `tournament_id` so WebSocket
+    /// subscribers see the change. A no-op without an event bus.
     async fn publish_tournament_event(
         &self,
-        _event_data: serde_json::Value,
+        event_data: serde_json::Value,
     ) -> Result<(), ApiError> {
-        // Placeholder for real-time tournament event publishing
-        // Will be implemented when realtime module is added
+        let Some(event_bus) = &self.event_bus else {
+            return Ok(());
+        };
+        let Some(tournament_id) = event_data
+            .get("tournament_id")
+            .and_then(|id| id.as_str())
+            .and_then(|id| Uuid::parse_str(id).ok())
+        else {
+            return Ok(());
+        };
+        let event = RealtimeEvent::TournamentUpdate {
+            tournament_id,
+            payload: event_data,
+            timestamp: Utc::now().to_rfc3339(),
+        };
+        event_bus.publish_to_tournament(tournament_id, &event).await;
         Ok(())
     }
 
@@ -2294,7 +2529,6 @@ impl TournamentService {
         Ok(user.username)
     }
 
-    /// Get tournament analytics dashboard data (Issue #291)
     /// Get tournament leaderboard (Issue #286)
     pub async fn get_tournament_leaderboard(
         &self,
@@ -2310,6 +2544,36 @@ impl TournamentService {
             ORDER BY tp.final_rank ASC NULLS LAST, tp.registered_at ASC
             "#,
             tournament_id
+        )
+        .fetch_all(&self.db_pool)
+        .await
+        .map_err(|e| ApiError::database_error(e))?;
+
+        let mut leaderboard = Vec::new();
+        for p in participants {
+            let wins = sqlx::query!(
+                "SELECT COUNT(*) as count FROM tournament_matches WHERE tournament_id = $1 AND winner_id = $2",
+                tournament_id,
+                p.user_id
+            )
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| ApiError::database_error(e))?
+            .count
+            .unwrap_or(0);
+
+            leaderboard.push(TournamentLeaderboardEntry {
+                user_id: p.user_id,
+                username: p.username,
+                final_rank: p.final_rank,
+                prize_amount: p.prize_amount,
+                points: (wins * 10) as i32, // Example point system
+            });
+        }
+
+        Ok(leaderboard)
+    }
+
     /// Get comprehensive tournament statistics
     pub async fn get_tournament_statistics(
         &self,
@@ -2336,14 +2600,14 @@ impl TournamentService {
             .unwrap_or(0);
 
         // Get match statistics
-        let match_stats = sqlx::query!("SELECT 
-            COUNT(*) as total_matches,
-            COUNT(CASE WHEN tm.status = 'completed' THEN 1 END) as completed_matches,
-            COUNT(CASE WHEN tm.status = 'pending' OR tm.status = 'scheduled' THEN 1 END) as pending_matches,
-            COUNT(CASE WHEN tm.status = 'in_progress' THEN 1 END) as in_progress_matches,
-            COUNT(CASE WHEN tm.status = 'disputed' THEN 1 END) as disputed_matches
+        let match_stats = sqlx::query!(r#"SELECT 
+            COUNT(*) as "total_matches!",
+            COUNT(CASE WHEN tm.status = 'completed' THEN 1 END) as "completed_matches!",
+            COUNT(CASE WHEN tm.status = 'pending' OR tm.status = 'scheduled' THEN 1 END) as "pending_matches!",
+            COUNT(CASE WHEN tm.status = 'in_progress' THEN 1 END) as "in_progress_matches!",
+            COUNT(CASE WHEN tm.status = 'disputed' THEN 1 END) as "disputed_matches!"
             FROM tournament_matches tm
-            WHERE tm.tournament_id = $1",
+            WHERE tm.tournament_id = $1"#,
             tournament_id
         )
         .fetch_one(&self.db_pool)
@@ -2354,15 +2618,7 @@ impl TournamentService {
         let prize_pool = sqlx::query!("SELECT total_amount, currency FROM prize_pools WHERE tournament_id = $1", tournament_id)
             .fetch_optional(&self.db_pool)
             .await
-            .map_err(|e| ApiError::database_error(e))?
-            .unwrap_or_else(|| {
-                sqlx::query!("SELECT 0 as total_amount, 'USD' as currency")
-                    .fetch_one(&self.db_pool)
-                    .await
-                    .map_err(|e| ApiError::database_error(e))
-                    .ok()
-                    .unwrap_or(sqlx::query!("SELECT 0 as total_amount, 'USD' as currency").fetch_one(&self.db_pool).await.unwrap())
-            });
+            .map_err(|e| ApiError::database_error(e))?;
 
         // Calculate registration completion rate
         let registration_completion_rate = if tournament.max_participants > 0 {
@@ -2404,198 +2660,6 @@ impl TournamentService {
         })
     }
 
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentStatisticsResponse {
-        pub tournament_id: Uuid,
-        pub tournament_name: String,
-        pub game: String,
-        pub status: TournamentStatus,
-        pub participant_count: i32,
-        pub total_matches: i64,
-        pub completed_matches: i64,
-        pub pending_matches: i64,
-        pub in_progress_matches: i64,
-        pub disputed_matches: i64,
-        pub prize_pool_amount: i64,
-        pub prize_pool_currency: String,
-        pub round_count: i64,
-        pub current_round: i32,
-        pub registration_completion_rate: i32,
-        pub completion_rate: i32,
-    }
-
-    /// Get tournament leaderboard with ELO ratings and performance metrics
-    pub async fn get_tournament_leaderboard(
-        &self,
-        tournament_id: Uuid,
-        page: i32,
-        per_page: i32,
-    ) -> Result<TournamentLeaderboardResponse, ApiError> {
-        let offset = (page - 1) * per_page;
-
-        // Get tournament participants with their ELO ratings
-        let participants = sqlx::query!("SELECT 
-            tp.id as participant_id,
-            tp.user_id,
-            tp.registered_at,
-            tp.entry_fee_paid,
-            tp.status as participant_status,
-            tp.final_rank,
-            tp.prize_amount,
-            tp.prize_currency,
-            u.username,
-            u.display_name,
-            ue.current_rating as elo_rating,
-            COALESCE(wins.wins, 0) as wins,
-            COALESCE(losses.losses, 0) as losses,
-            COALESCE(draws.draws, 0) as draws,
-            COALESCE(matches.total_matches, 0) as total_matches
-            FROM tournament_participants tp
-            JOIN users u ON tp.user_id = u.id
-            LEFT JOIN user_elo ue ON tp.user_id = ue.user_id AND ue.game = (SELECT game FROM tournaments WHERE id = $1)
-            LEFT JOIN (
-                SELECT winner_id, COUNT(*) as wins 
-                FROM tournament_matches 
-                WHERE tournament_id = $1 AND winner_id IS NOT NULL 
-                GROUP BY winner_id
-            ) wins ON tp.user_id = wins.winner_id
-            LEFT JOIN (
-                SELECT player1_id as loser_id, COUNT(*) as losses 
-                FROM tournament_matches 
-                WHERE tournament_id = $1 AND winner_id = player2_id AND winner_id IS NOT NULL 
-                GROUP BY player1_id
-            ) losses ON tp.user_id = losses.loser_id
-            LEFT JOIN (
-                SELECT player2_id as loser_id, COUNT(*) as losses 
-                FROM tournament_matches 
-                WHERE tournament_id = $1 AND winner_id = player1_id AND winner_id IS NOT NULL 
-                GROUP BY player2_id
-            ) losses2 ON tp.user_id = losses2.loser_id
-            LEFT JOIN (
-                SELECT player1_id as draw_id, COUNT(*) as draws 
-                FROM tournament_matches 
-                WHERE tournament_id = $1 AND winner_id IS NULL 
-                GROUP BY player1_id
-            ) draws ON tp.user_id = draws.draw_id
-            LEFT JOIN (
-                SELECT player2_id as draw_id, COUNT(*) as draws 
-                FROM tournament_matches 
-                WHERE tournament_id = $1 AND winner_id IS NULL 
-                GROUP BY player2_id
-            ) draws2 ON tp.user_id = draws2.draw_id
-            LEFT JOIN (
-                SELECT user_id, COUNT(*) as total_matches 
-                FROM (
-                    SELECT player1_id as user_id FROM tournament_matches WHERE tournament_id = $1
-                    UNION ALL
-                    SELECT player2_id as user_id FROM tournament_matches WHERE tournament_id = $1 AND player2_id IS NOT NULL
-                ) all_players
-                GROUP BY user_id
-            ) matches ON tp.user_id = matches.user_id
-            WHERE tp.tournament_id = $1
-            ORDER BY tp.final_rank ASC, tp.registered_at ASC
-            LIMIT $2 OFFSET $3",
-            tournament_id,
-            per_page,
-            offset
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(|e| ApiError::database_error(e))?;
-
-        // Get total count for pagination
-        let total = sqlx::query!("SELECT COUNT(*) as count FROM tournament_participants WHERE tournament_id = $1", tournament_id)
-            .fetch_one(&self.db_pool)
-            .await
-            .map_err(|e| ApiError::database_error(e))?
-            .count
-            .unwrap_or(0);
-
-            leaderboard.push(TournamentLeaderboardEntry {
-                user_id: p.user_id,
-                username: p.username,
-                final_rank: p.final_rank,
-                prize_amount: p.prize_amount,
-                points: (wins * 10) as i32, // Example point system
-            });
-        }
-
-        Ok(leaderboard)
-    }
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TournamentLeaderboardEntry {
-    pub user_id: Uuid,
-    pub username: String,
-    pub final_rank: Option<i32>,
-    pub prize_amount: Option<i64>,
-    pub points: i32,
-}
-        // Convert to response format
-        let mut leaderboard_entries = Vec::new();
-        for row in participants {
-            let elo_rating = if row.elo_rating.is_some() { row.elo_rating.unwrap() } else { 1200 };
-            let win_rate = if row.total_matches > 0 {
-                ((row.wins as f32 / row.total_matches as f32) * 100.0).round() as i32
-            } else {
-                0
-            };
-
-            leaderboard_entries.push(TournamentLeaderboardEntry {
-                participant_id: row.participant_id,
-                user_id: row.user_id,
-                username: row.username,
-                display_name: row.display_name,
-                elo_rating,
-                final_rank: row.final_rank,
-                wins: row.wins,
-                losses: row.losses,
-                draws: row.draws,
-                total_matches: row.total_matches,
-                win_rate_pct: win_rate,
-                prize_amount: row.prize_amount,
-                prize_currency: row.prize_currency,
-                participant_status: row.participant_status.parse().unwrap_or(ParticipantStatus::Registered),
-            });
-        }
-
-        Ok(TournamentLeaderboardResponse {
-            tournament_id,
-            entries: leaderboard_entries,
-            total,
-            page,
-            per_page,
-        })
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentLeaderboardResponse {
-        pub tournament_id: Uuid,
-        pub entries: Vec<TournamentLeaderboardEntry>,
-        pub total: i64,
-        pub page: i32,
-        pub per_page: i32,
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentLeaderboardEntry {
-        pub participant_id: Uuid,
-        pub user_id: Uuid,
-        pub username: String,
-        pub display_name: Option<String>,
-        pub elo_rating: i32,
-        pub final_rank: Option<i32>,
-        pub wins: i64,
-        pub losses: i64,
-        pub draws: i64,
-        pub total_matches: i64,
-        pub win_rate_pct: i32,
-        pub prize_amount: Option<i64>,
-        pub prize_currency: Option<String>,
-        pub participant_status: ParticipantStatus,
-    }
-
     /// Get comprehensive tournament analytics for dashboard visualization
     pub async fn get_tournament_analytics(
         &self,
@@ -2620,43 +2684,6 @@ pub struct TournamentLeaderboardEntry {
 
         let prize_pool = sqlx::query!(
             "SELECT total_amount FROM prize_pools WHERE tournament_id = $1",
-        // Get basic tournament info
-        let tournament = self.get_tournament_by_id(tournament_id).await?;
-
-        // Get participant count
-        let participant_count = self.get_participant_count(tournament_id).await?;
-
-        // Get match statistics by round
-        let round_stats = sqlx::query!("SELECT 
-            tr.round_number,
-            tr.round_type,
-            COUNT(tm.id) as total_matches,
-            COUNT(CASE WHEN tm.status = 'completed' THEN 1 END) as completed_matches,
-            COUNT(CASE WHEN tm.status = 'pending' OR tm.status = 'scheduled' THEN 1 END) as pending_matches,
-            COUNT(CASE WHEN tm.status = 'in_progress' THEN 1 END) as in_progress_matches,
-            COUNT(CASE WHEN tm.status = 'disputed' THEN 1 END) as disputed_matches,
-            AVG(EXTRACT(EPOCH FROM (tm.completed_at - tm.started_at))) as avg_duration_secs
-            FROM tournament_rounds tr
-            LEFT JOIN tournament_matches tm ON tr.id = tm.round_id AND tr.tournament_id = $1
-            WHERE tr.tournament_id = $1
-            GROUP BY tr.round_number, tr.round_type
-            ORDER BY tr.round_number",
-            tournament_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(|e| ApiError::database_error(e))?;
-
-        // Get prize pool distribution
-        let prize_distribution = sqlx::query!("SELECT 
-            pp.total_amount as prize_pool_amount,
-            pp.currency as prize_pool_currency,
-            pp.distribution_percentages as distribution_percentages_json,
-            COALESCE(SUM(tp.prize_amount), 0) as distributed_amount
-            FROM prize_pools pp
-            LEFT JOIN tournament_participants tp ON pp.tournament_id = tp.tournament_id AND tp.prize_amount IS NOT NULL
-            WHERE pp.tournament_id = $1
-            GROUP BY pp.total_amount, pp.currency, pp.distribution_percentages",
             tournament_id
         )
         .fetch_optional(&self.db_pool)
@@ -2674,6 +2701,35 @@ pub struct TournamentLeaderboardEntry {
     }
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TournamentLeaderboardEntry {
+    pub user_id: Uuid,
+    pub username: String,
+    pub final_rank: Option<i32>,
+    pub prize_amount: Option<i64>,
+    pub points: i32,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TournamentStatisticsResponse {
+    pub tournament_id: Uuid,
+    pub tournament_name: String,
+    pub game: String,
+    pub status: TournamentStatus,
+    pub participant_count: i32,
+    pub total_matches: i64,
+    pub completed_matches: i64,
+    pub pending_matches: i64,
+    pub in_progress_matches: i64,
+    pub disputed_matches: i64,
+    pub prize_pool_amount: i64,
+    pub prize_pool_currency: String,
+    pub round_count: i64,
+    pub current_round: i32,
+    pub registration_completion_rate: i32,
+    pub completion_rate: i32,
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TournamentAnalyticsResponse {
     pub total_participants: i32,
@@ -2681,155 +2737,6 @@ pub struct TournamentAnalyticsResponse {
     pub matches_completed: i32,
     pub current_prize_pool: i64,
 }
-        .unwrap_or_else(|| {
-            sqlx::query!("SELECT 0 as prize_pool_amount, 'USD' as prize_pool_currency, '[]' as distribution_percentages_json, 0 as distributed_amount")
-                .fetch_one(&self.db_pool)
-                .await
-                .map_err(|e| ApiError::database_error(e))
-                .ok()
-                .unwrap_or(sqlx::query!("SELECT 0 as prize_pool_amount, 'USD' as prize_pool_currency, '[]' as distribution_percentages_json, 0 as distributed_amount").fetch_one(&self.db_pool).await.unwrap())
-        });
-
-        // Get registration timeline
-        let registration_timeline = sqlx::query!("SELECT 
-            COUNT(*) as total_registrations,
-            MIN(tp.registered_at) as first_registration,
-            MAX(tp.registered_at) as last_registration,
-            COUNT(CASE WHEN tp.entry_fee_paid THEN 1 END) as paid_registrations,
-            COUNT(CASE WHEN tp.status = 'active' THEN 1 END) as active_participants
-            FROM tournament_participants tp
-            WHERE tp.tournament_id = $1",
-            tournament_id
-        )
-        .fetch_one(&self.db_pool)
-        .await
-        .map_err(|e| ApiError::database_error(e))?;
-
-        // Get participant skill level distribution
-        let skill_distribution = sqlx::query!("SELECT 
-            COUNT(*) as total_participants,
-            AVG(ue.current_rating) as avg_elo,
-            MIN(ue.current_rating) as min_elo,
-            MAX(ue.current_rating) as max_elo,
-            STDDEV(ue.current_rating) as elo_stddev
-            FROM tournament_participants tp
-            LEFT JOIN user_elo ue ON tp.user_id = ue.user_id AND ue.game = $1
-            WHERE tp.tournament_id = $2",
-            tournament.game,
-            tournament_id
-        )
-        .fetch_one(&self.db_pool)
-        .await
-        .map_err(|e| ApiError::database_error(e))?
-        .unwrap_or_else(|| {
-            sqlx::query!("SELECT 0 as total_participants, 0 as avg_elo, 0 as min_elo, 0 as max_elo, 0 as elo_stddev")
-                .fetch_one(&self.db_pool)
-                .await
-                .map_err(|e| ApiError::database_error(e))
-                .ok()
-                .unwrap_or(sqlx::query!("SELECT 0 as total_participants, 0 as avg_elo, 0 as min_elo, 0 as max_elo, 0 as elo_stddev").fetch_one(&self.db_pool).await.unwrap())
-        });
-
-        // Convert JSON distribution percentages
-        let distribution_percentages: Vec<f64> = if let Some(ref json_str) = prize_distribution.distribution_percentages_json {
-            serde_json::from_str(json_str)
-                .map_err(|e| ApiError::internal_error(format!("Invalid distribution percentages JSON: {}", e)))?
-        } else {
-            vec![]
-        };
-
-        Ok(TournamentAnalyticsResponse {
-            tournament_id,
-            tournament_name: tournament.name,
-            game: tournament.game,
-            status: tournament.status,
-            participant_count,
-            registration_timeline: TournamentRegistrationTimeline {
-                total_registrations: registration_timeline.total_registrations.unwrap_or(0),
-                first_registration: registration_timeline.first_registration,
-                last_registration: registration_timeline.last_registration,
-                paid_registrations: registration_timeline.paid_registrations.unwrap_or(0),
-                active_participants: registration_timeline.active_participants.unwrap_or(0),
-            },
-            round_statistics: round_stats
-                .into_iter()
-                .map(|r| TournamentRoundStatistics {
-                    round_number: r.round_number.unwrap_or(0),
-                    round_type: r.round_type,
-                    total_matches: r.total_matches.unwrap_or(0),
-                    completed_matches: r.completed_matches.unwrap_or(0),
-                    pending_matches: r.pending_matches.unwrap_or(0),
-                    in_progress_matches: r.in_progress_matches.unwrap_or(0),
-                    disputed_matches: r.disputed_matches.unwrap_or(0),
-                    avg_duration_secs: r.avg_duration_secs.map(|d| d as f64).unwrap_or(0.0),
-                })
-                .collect(),
-            prize_pool: TournamentPrizePool {
-                total_amount: prize_distribution.prize_pool_amount.unwrap_or(0),
-                currency: prize_distribution.prize_pool_currency.unwrap_or("USD".to_string()),
-                distribution_percentages,
-                distributed_amount: prize_distribution.distributed_amount.unwrap_or(0),
-            },
-            skill_level_distribution: TournamentSkillDistribution {
-                total_participants: skill_distribution.total_participants.unwrap_or(0),
-                average_elo: skill_distribution.avg_elo.unwrap_or(0.0) as i32,
-                min_elo: skill_distribution.min_elo.unwrap_or(0.0) as i32,
-                max_elo: skill_distribution.max_elo.unwrap_or(0.0) as i32,
-                elo_stddev: skill_distribution.elo_stddev.unwrap_or(0.0) as i32,
-            },
-        })
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentAnalyticsResponse {
-        pub tournament_id: Uuid,
-        pub tournament_name: String,
-        pub game: String,
-        pub status: TournamentStatus,
-        pub participant_count: i32,
-        pub registration_timeline: TournamentRegistrationTimeline,
-        pub round_statistics: Vec<TournamentRoundStatistics>,
-        pub prize_pool: TournamentPrizePool,
-        pub skill_level_distribution: TournamentSkillDistribution,
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentRegistrationTimeline {
-        pub total_registrations: i64,
-        pub first_registration: Option<DateTime<Utc>>,
-        pub last_registration: Option<DateTime<Utc>>,
-        pub paid_registrations: i64,
-        pub active_participants: i64,
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentRoundStatistics {
-        pub round_number: i32,
-        pub round_type: String,
-        pub total_matches: i64,
-        pub completed_matches: i64,
-        pub pending_matches: i64,
-        pub in_progress_matches: i64,
-        pub disputed_matches: i64,
-        pub avg_duration_secs: f64,
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentPrizePool {
-        pub total_amount: i64,
-        pub currency: String,
-        pub distribution_percentages: Vec<f64>,
-        pub distributed_amount: i64,
-    }
-
-    #[derive(Debug, Serialize, Deserialize)]
-    pub struct TournamentSkillDistribution {
-        pub total_participants: i64,
-        pub average_elo: i32,
-        pub min_elo: i32,
-        pub max_elo: i32,
-        pub elo_stddev: i32,
-    }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TournamentBracketResponse {
@@ -2850,147 +2757,10 @@ pub struct BracketRound {
 pub struct BracketMatch {
     pub match_id: Uuid,
     pub match_number: i32,
-    pub player1_id: Uuid,
+    pub player1_id: Option<Uuid>,
     pub player2_id: Option<Uuid>,
     pub winner_id: Option<Uuid>,
     pub player1_score: Option<i32>,
     pub player2_score: Option<i32>,
     pub status: MatchStatus,
 }
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct TournamentPlayerInfo {
-    pub user_id: Uuid,
-    pub username: String,
-    pub display_name: Option<String>,
-    pub final_rank: Option<i32>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct BracketMatch {
-    pub match_id: Uuid,
-    pub match_number: i32,
-    pub player1: TournamentPlayerInfo,
-    pub player2: TournamentPlayerInfo,
-    pub winner_id: Option<Uuid>,
-    pub player1_score: Option<i32>,
-    pub player2_score: Option<i32>,
-    pub status: MatchStatus,
-    pub scheduled_time: Option<DateTime<Utc>>,
-    pub started_at: Option<DateTime<Utc>>,
-    pub completed_at: Option<DateTime<Utc>>,
-}
-
-    /// Get enhanced tournament bracket with detailed match information
-    pub async fn get_enhanced_tournament_bracket(
-        &self,
-        tournament_id: Uuid,
-    ) -> Result<TournamentBracketResponse, ApiError> {
-        // Get tournament rounds
-        let rounds = sqlx::query_as!(TournamentRound,
-            "SELECT * FROM tournament_rounds WHERE tournament_id = $1 ORDER BY round_number",
-            tournament_id
-        )
-        .fetch_all(&self.db_pool)
-        .await
-        .map_err(|e| ApiError::database_error(e))?;
-
-        // Get matches for each round with additional participant information
-        let mut bracket_rounds = Vec::new();
-        for round in rounds {
-            let matches = sqlx::query!("SELECT 
-                tm.id as match_id,
-                tm.match_number,
-                tm.player1_id,
-                tm.player2_id,
-                tm.winner_id,
-                tm.player1_score,
-                tm.player2_score,
-                tm.status as match_status,
-                tm.scheduled_time,
-                tm.started_at,
-                tm.completed_at,
-                u1.username as player1_username,
-                u2.username as player2_username,
-                u1.display_name as player1_display_name,
-                u2.display_name as player2_display_name,
-                tp1.final_rank as player1_final_rank,
-                tp2.final_rank as player2_final_rank
-                FROM tournament_matches tm
-                LEFT JOIN users u1 ON tm.player1_id = u1.id
-                LEFT JOIN users u2 ON tm.player2_id = u2.id
-                LEFT JOIN tournament_participants tp1 ON tm.player1_id = tp1.user_id AND tm.tournament_id = tp1.tournament_id
-                LEFT JOIN tournament_participants tp2 ON tm.player2_id = tp2.user_id AND tm.tournament_id = tp2.tournament_id
-                WHERE tm.round_id = $1
-                ORDER BY tm.match_number",
-                round.id
-            )
-            .fetch_all(&self.db_pool)
-            .await
-            .map_err(|e| ApiError::database_error(e))?;
-
-            let mut round_matches = Vec::new();
-            for match_row in matches {
-                // Get player names and display info
-                let player1_info = if let Some(username) = match_row.player1_username {
-                    TournamentPlayerInfo {
-                        user_id: match_row.player1_id,
-                        username,
-                        display_name: match_row.player1_display_name,
-                        final_rank: match_row.player1_final_rank,
-                    }
-                } else {
-                    TournamentPlayerInfo {
-                        user_id: match_row.player1_id,
-                        username: "Unknown".to_string(),
-                        display_name: None,
-                        final_rank: None,
-                    }
-                };
-
-                let player2_info = if let Some(username) = match_row.player2_username {
-                    TournamentPlayerInfo {
-                        user_id: match_row.player2_id.unwrap_or_default(),
-                        username,
-                        display_name: match_row.player2_display_name,
-                        final_rank: match_row.player2_final_rank,
-                    }
-                } else {
-                    TournamentPlayerInfo {
-                        user_id: match_row.player2_id.unwrap_or_default(),
-                        username: "Bye".to_string(),
-                        display_name: None,
-                        final_rank: None,
-                    }
-                };
-
-                round_matches.push(BracketMatch {
-                    match_id: match_row.match_id,
-                    match_number: match_row.match_number.unwrap_or(0),
-                    player1: player1_info,
-                    player2: player2_info,
-                    winner_id: match_row.winner_id,
-                    player1_score: match_row.player1_score,
-                    player2_score: match_row.player2_score,
-                    status: match_row.match_status.parse().unwrap_or(MatchStatus::Pending),
-                    scheduled_time: match_row.scheduled_time,
-                    started_at: match_row.started_at,
-                    completed_at: match_row.completed_at,
-                });
-            }
-
-            bracket_rounds.push(BracketRound {
-                round_id: round.id,
-                round_number: round.round_number,
-                round_type: round.round_type.parse().unwrap_or(RoundType::Elimination),
-                status: round.status.parse().unwrap_or(RoundStatus::Pending),
-                matches: round_matches,
-            });
-        }
-
-        Ok(TournamentBracketResponse {
-            tournament_id,
-            rounds: bracket_rounds,
-        })
-    }
-}
@@ -1,15 +1,40 @@
+//! Wallet balances, transaction records and payments.
+//!
+//! XLM can also be held in custody: each user gets a muxed deposit address on
+//! the platform's Stellar account, deposits are picked up from a Horizon
+//! payment stream, and withdrawals are paid out through the relayer as Stellar
+//! Asset Contract transfers, so the relayer's signer is the custody account.
+//! Custodied balances live in the double-entry ledger (see
+//! [`crate::service::ledger`]) and are mirrored into `wallets.balance_xlm`.
+//! Withdrawals at or above the approval threshold wait for an admin, and each
+//! user may withdraw at most the daily limit in any 24 hours — with KYC on,
+//! the limit of their verification level (see [`crate::service::kyc`]).
+
+use crate::api_error::ApiError;
+use crate::config::{KycLimits, StellarConfig};
 use crate::models::{
-    Transaction, TransactionResponse, TransactionStatus, TransactionType, Wallet, WalletResponse,
+    CustodialWithdrawal, DepositAddress, KycLevel, Transaction, TransactionResponse,
+    TransactionStatus, TransactionType, Wallet, WalletResponse, WithdrawalStatus,
 };
+use crate::service::horizon_stream::{HorizonError, HorizonPayment, PaymentStream};
+use crate::service::kyc;
+use crate::service::ledger::{self, Journal, LedgerAccount, ASSET_XLM};
+use crate::service::stellar_relayer::{xdr, RelayerError, StellarRelayer};
 use anyhow::Result;
 use chrono::Utc;
 // EventBus is used via crate::realtime::event_bus::EventBus
 use rust_decimal::Decimal;
-use sqlx::PgPool;
+use serde_json::json;
+use sqlx::{PgPool, Postgres};
 use std::sync::Arc;
+use std::time::Duration;
 use thiserror::Error;
+use tracing::{error, info, warn};
 use uuid::Uuid;
 
+/// Delay before reconnecting a dropped Horizon deposit stream (seconds).
+const DEPOSIT_STREAM_RECONNECT_SECS: u64 = 5;
+
 #[derive(Debug, Error)]
 pub enum WalletError {
     #[error("Wallet not found for user")]
@@ -26,14 +51,95 @@ pub enum WalletError {
     DatabaseError(#[from] sqlx::Error),
     #[error("Redis error: {0}")]
     RedisError(String),
+    #[error("Custodial wallet is not configured")]
+    CustodyDisabled,
+    #[error("Invalid destination: {0}")]
+    InvalidDestination(String),
+    #[error("Daily withdrawal limit of {limit} stroops exceeded: {used} already withdrawn")]
+    DailyLimitExceeded { limit: i64, used: i64 },
+    #[error(
+        "Daily withdrawal limit of {limit} stroops for {level} verification exceeded: {used} \
+         already withdrawn; verify your identity to raise it"
+    )]
+    KycLimitExceeded {
+        level: KycLevel,
+        limit: i64,
+        used: i64,
+    },
+    #[error("Withdrawal not found")]
+    WithdrawalNotFound,
+    #[error("Withdrawal is {0:?}")]
+    InvalidWithdrawalState(WithdrawalStatus),
+    #[error("Idempotency key was already used for a different withdrawal")]
+    IdempotencyConflict,
+    #[error("Horizon error: {0}")]
+    Horizon(#[from] HorizonError),
+}
+
+impl From<WalletError> for ApiError {
+    fn from(err: WalletError) -> Self {
+        match err {
+            WalletError::DatabaseError(e) => ApiError::DatabaseError(e),
+            WalletError::RedisError(e) => ApiError::RedisError(e),
+            WalletError::WalletNotFound
+            | WalletError::TransactionNotFound
+            | WalletError::WithdrawalNotFound => ApiError::NotFound,
+            WalletError::InvalidWithdrawalState(_) | WalletError::IdempotencyConflict => {
+                ApiError::Conflict(err.to_string())
+            }
+            WalletError::Horizon(e) => ApiError::StellarError(e.to_string()),
+            WalletError::InsufficientBalance { .. }
+            | WalletError::InvalidAmount(_)
+            | WalletError::PaymentVerificationFailed
+            | WalletError::CustodyDisabled
+            | WalletError::InvalidDestination(_)
+            | WalletError::DailyLimitExceeded { .. }
+            | WalletError::KycLimitExceeded { .. } => ApiError::BadRequest(err.to_string()),
+        }
+    }
 }
 
 pub type DbPool = Arc<PgPool>;
 
+/// Settings of the custodial Stellar wallet.
+#[derive(Debug, Clone)]
+pub struct CustodyConfig {
+    pub horizon_url: String,
+    /// Stellar Asset Contract of native XLM, used for payouts.
+    pub native_asset_contract: String,
+    /// Withdrawals at or above this amount (stroops) need admin approval.
+    pub approval_threshold: i64,
+    /// Most a user may withdraw in any 24 hours (stroops).
+    pub daily_limit: i64,
+}
+
+impl CustodyConfig {
+    pub fn from_stellar_config(stellar: &StellarConfig) -> Self {
+        Self {
+            horizon_url: stellar.horizon_url.clone(),
+            native_asset_contract: xdr::native_asset_contract(stellar.network_passphrase()),
+            approval_threshold: stellar.withdrawal_approval_threshold,
+            daily_limit: stellar.withdrawal_daily_limit,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct Custody {
+    relayer: Arc<StellarRelayer>,
+    /// The relayer's signer, which holds custodied funds.
+    account: String,
+    account_key: [u8; 32],
+    config: CustodyConfig,
+    client: reqwest::Client,
+}
+
 #[derive(Clone)]
 pub struct WalletService {
     db_pool: DbPool,
     event_bus: Option<crate::realtime::event_bus::EventBus>,
+    custody: Option<Custody>,
+    kyc_limits: Option<KycLimits>,
 }
 
 impl WalletService {
@@ -41,9 +147,33 @@ impl WalletService {
         Self {
             db_pool,
             event_bus,
+            custody: None,
+            kyc_limits: None,
         }
     }
 
+    /// Limit custodial withdrawals by the user's KYC level instead of the
+    /// configured daily limit.
+    pub fn with_kyc_limits(mut self, limits: KycLimits) -> Self {
+        self.kyc_limits = Some(limits);
+        self
+    }
+
+    /// Hold XLM in custody on the relayer's account.
+    pub fn with_custody(mut self, relayer: Arc<StellarRelayer>, stellar: &StellarConfig) -> Self {
+        let account = relayer.account_id();
+        let account_key =
+            xdr::account_public_key(&account).expect("relayer account ids are valid StrKeys");
+        self.custody = Some(Custody {
+            relayer,
+            account,
+            account_key,
+            config: CustodyConfig::from_stellar_config(stellar),
+            client: reqwest::Client::new(),
+        });
+        self
+    }
+
     // ========================================================================
     // CORE WALLET OPERATIONS
     // ========================================================================
@@ -597,6 +727,774 @@ impl WalletService {
         Ok(transaction)
     }
 
+    // ========================================================================
+    // CUSTODIAL STELLAR WALLET
+    // ========================================================================
+
+    fn custody(&self) -> Result<&Custody, WalletError> {
+        self.custody.as_ref().ok_or(WalletError::CustodyDisabled)
+    }
+
+    /// The user's muxed deposit address, allocated on first use.
+    pub async fn deposit_address(&self, user_id: Uuid) -> Result<DepositAddress, WalletError> {
+        let custody = self.custody()?;
+        let existing: Option<(i64, String)> = sqlx::query_as(
+            "SELECT muxed_id, address FROM custody_deposit_addresses WHERE user_id = $1",
+        )
+        .bind(user_id)
+        .fetch_optional(&*self.db_pool)
+        .await?;
+
+        let (muxed_id, address) = match existing {
+            Some(row) => row,
+            None => {
+                let muxed_id: i64 = sqlx::query_scalar(
+                    r#"
+                    SELECT nextval(pg_get_serial_sequence('custody_deposit_addresses', 'muxed_id'))
+                    "#,
+                )
+                .fetch_one(&*self.db_pool)
+                .await?;
+                let address = xdr::muxed_account_id(&custody.account_key, muxed_id as u64);
+                // A concurrent request may have allocated one first; keep theirs.
+                sqlx::query(
+                    r#"
+                    INSERT INTO custody_deposit_addresses (user_id, muxed_id, address)
+                    VALUES ($1, $2, $3)
+                    ON CONFLICT (user_id) DO NOTHING
+                    "#,
+                )
+                .bind(user_id)
+                .bind(muxed_id)
+                .bind(&address)
+                .execute(&*self.db_pool)
+                .await?;
+                sqlx::query_as(
+                    "SELECT muxed_id, address FROM custody_deposit_addresses WHERE user_id = $1",
+                )
+                .bind(user_id)
+                .fetch_one(&*self.db_pool)
+                .await?
+            }
+        };
+
+        Ok(DepositAddress {
+            address,
+            account: custody.account.clone(),
+            muxed_id: muxed_id.to_string(),
+            asset: ASSET_XLM.to_string(),
+        })
+    }
+
+    /// Request a payout of custodied XLM to a Stellar account. The amount is
+    /// held from the available balance straight away; withdrawals at or above
+    /// the approval threshold then wait for an admin, others are paid out
+    /// immediately. Retrying with the same idempotency key returns the original
+    /// withdrawal.
+    pub async fn request_withdrawal(
+        &self,
+        user_id: Uuid,
+        amount: i64,
+        destination: &str,
+        idempotency_key: Option<&str>,
+    ) -> Result<CustodialWithdrawal, WalletError> {
+        let custody = self.custody()?;
+        if amount <= 0 {
+            return Err(WalletError::InvalidAmount(
+                "Amount must be positive".to_string(),
+            ));
+        }
+        xdr::account_public_key(destination)
+            .map_err(|e| WalletError::InvalidDestination(e.to_string()))?;
+        if destination == custody.account {
+            return Err(WalletError::InvalidDestination(
+                "cannot withdraw to the custody account".to_string(),
+            ));
+        }
+
+        let mut tx = self.db_pool.begin().await?;
+        // Locking the available balance serializes a user's withdrawals, so the
+        // balance and daily limit checks below can't race.
+        let available =
+            ledger::balance_for_update(&mut tx, LedgerAccount::Available(user_id), ASSET_XLM)
+                .await?;
+
+        if let Some(key) = idempotency_key {
+            let existing: Option<CustodialWithdrawal> = sqlx::query_as(
+                "SELECT * FROM custody_withdrawals WHERE user_id = $1 AND idempotency_key = $2",
+            )
+            .bind(user_id)
+            .bind(key)
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(existing) = existing {
+                if existing.amount != amount || existing.destination != destination {
+                    return Err(WalletError::IdempotencyConflict);
+                }
+                return Ok(existing);
+            }
+        }
+
+        if available < amount {
+            return Err(WalletError::InsufficientBalance {
+                required: amount,
+                available,
+            });
+        }
+        let used: i64 = sqlx::query_scalar(
+            r#"
+            SELECT COALESCE(SUM(amount), 0)::BIGINT FROM custody_withdrawals
+            WHERE user_id = $1
+              AND created_at > NOW() - INTERVAL '24 hours'
+              AND status NOT IN ('rejected', 'failed')
+            "#,
+        )
+        .bind(user_id)
+        .fetch_one(&mut *tx)
+        .await?;
+        let (daily_limit, kyc_level) = match &self.kyc_limits {
+            Some(limits) => {
+                let level = kyc::level_in(&mut tx, user_id).await?;
+                (kyc::daily_limit(limits, level), Some(level))
+            }
+            None => (custody.config.daily_limit, None),
+        };
+        if used + amount > daily_limit {
+            return Err(match kyc_level {
+                Some(level) if level < KycLevel::Full => WalletError::KycLimitExceeded {
+                    level,
+                    limit: daily_limit,
+                    used,
+                },
+                _ => WalletError::DailyLimitExceeded {
+                    limit: daily_limit,
+                    used,
+                },
+            });
+        }
+
+        let withdrawal_id = Uuid::new_v4();
+        let requires_approval = amount >= custody.config.approval_threshold;
+        let status = if requires_approval {
+            WithdrawalStatus::PendingApproval
+        } else {
+            WithdrawalStatus::Approved
+        };
+
+        let transaction_id: Uuid = sqlx::query_scalar(
+            r#"
+            INSERT INTO transactions (
+                id, user_id, transaction_type, amount, currency,
+                status, reference, description, metadata
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING id
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(TransactionType::Withdrawal)
+        .bind(Decimal::from(amount))
+        .bind(ASSET_XLM)
+        .bind(TransactionStatus::Pending)
+        .bind(format!("custody-withdrawal:{}", withdrawal_id))
+        .bind(format!("Withdrawal to {}", destination))
+        .bind(json!({ "withdrawal_id": withdrawal_id, "destination": destination }).to_string())
+        .fetch_one(&mut *tx)
+        .await?;
+
+        ledger::post(
+            &mut tx,
+            &Journal::transfer(
+                format!("withdrawal:{}:hold", withdrawal_id),
+                "withdrawal_hold",
+                Some(user_id),
+                LedgerAccount::Available(user_id),
+                LedgerAccount::PendingWithdrawal(user_id),
+                amount,
+            ),
+        )
+        .await?;
+
+        let withdrawal: CustodialWithdrawal = sqlx::query_as(
+            r#"
+            INSERT INTO custody_withdrawals (
+                id, user_id, asset, amount, destination, status,
+                requires_approval, idempotency_key, transaction_id
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+            RETURNING *
+            "#,
+        )
+        .bind(withdrawal_id)
+        .bind(user_id)
+        .bind(ASSET_XLM)
+        .bind(amount)
+        .bind(destination)
+        .bind(status)
+        .bind(requires_approval)
+        .bind(idempotency_key)
+        .bind(transaction_id)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        self.sync_xlm_balance(&mut tx, user_id).await?;
+        tx.commit().await?;
+        info!(
+            %withdrawal_id,
+            %user_id,
+            amount,
+            requires_approval,
+            "Custodial withdrawal requested"
+        );
+
+        self.publish_balance_update(user_id).await;
+        if !requires_approval {
+            self.spawn_payout(withdrawal_id);
+        }
+        Ok(withdrawal)
+    }
+
+    /// A user's withdrawals, newest first.
+    pub async fn list_withdrawals(
+        &self,
+        user_id: Uuid,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<CustodialWithdrawal>, WalletError> {
+        let withdrawals = sqlx::query_as(
+            r#"
+            SELECT * FROM custody_withdrawals
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            LIMIT $2 OFFSET $3
+            "#,
+        )
+        .bind(user_id)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.db_pool)
+        .await?;
+        Ok(withdrawals)
+    }
+
+    /// Withdrawals waiting for admin approval, oldest first.
+    pub async fn pending_withdrawals(
+        &self,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<CustodialWithdrawal>, WalletError> {
+        let withdrawals = sqlx::query_as(
+            r#"
+            SELECT * FROM custody_withdrawals
+            WHERE status = 'pending_approval'
+            ORDER BY created_at
+            LIMIT $1 OFFSET $2
+            "#,
+        )
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&*self.db_pool)
+        .await?;
+        Ok(withdrawals)
+    }
+
+    /// Approve a withdrawal held for review and pay it out.
+    pub async fn approve_withdrawal(
+        &self,
+        admin_id: Uuid,
+        withdrawal_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<CustodialWithdrawal, WalletError> {
+        self.custody()?;
+        let withdrawal: Option<CustodialWithdrawal> = sqlx::query_as(
+            r#"
+            UPDATE custody_withdrawals
+            SET status = 'approved', reviewed_by = $2, reviewed_at = NOW(),
+                review_note = $3, updated_at = NOW()
+            WHERE id = $1 AND status = 'pending_approval'
+            RETURNING *
+            "#,
+        )
+        .bind(withdrawal_id)
+        .bind(admin_id)
+        .bind(note)
+        .fetch_optional(&*self.db_pool)
+        .await?;
+        let Some(withdrawal) = withdrawal else {
+            return Err(self.review_error(withdrawal_id).await);
+        };
+
+        info!(%withdrawal_id, %admin_id, "Custodial withdrawal approved");
+        self.spawn_payout(withdrawal_id);
+        Ok(withdrawal)
+    }
+
+    /// Reject a withdrawal held for review and return the funds to the user.
+    pub async fn reject_withdrawal(
+        &self,
+        admin_id: Uuid,
+        withdrawal_id: Uuid,
+        note: Option<&str>,
+    ) -> Result<CustodialWithdrawal, WalletError> {
+        let mut tx = self.db_pool.begin().await?;
+        let withdrawal: Option<CustodialWithdrawal> = sqlx::query_as(
+            r#"
+            UPDATE custody_withdrawals
+            SET status = 'rejected', reviewed_by = $2, reviewed_at = NOW(),
+                review_note = $3, updated_at = NOW()
+            WHERE id = $1 AND status = 'pending_approval'
+            RETURNING *
+            "#,
+        )
+        .bind(withdrawal_id)
+        .bind(admin_id)
+        .bind(note)
+        .fetch_optional(&mut *tx)
+        .await?;
+        let Some(withdrawal) = withdrawal else {
+            return Err(self.review_error(withdrawal_id).await);
+        };
+
+        self.release_hold(&mut tx, &withdrawal, TransactionStatus::Cancelled)
+            .await?;
+        tx.commit().await?;
+        info!(%withdrawal_id, %admin_id, "Custodial withdrawal rejected");
+
+        self.publish_balance_update(withdrawal.user_id).await;
+        Ok(withdrawal)
+    }
+
+    /// Why a withdrawal could not be reviewed.
+    async fn review_error(&self, withdrawal_id: Uuid) -> WalletError {
+        let status: Result<Option<WithdrawalStatus>, sqlx::Error> =
+            sqlx::query_scalar("SELECT status FROM custody_withdrawals WHERE id = $1")
+                .bind(withdrawal_id)
+                .fetch_optional(&*self.db_pool)
+                .await;
+        match status {
+            Ok(Some(status)) => WalletError::InvalidWithdrawalState(status),
+            Ok(None) => WalletError::WithdrawalNotFound,
+            Err(e) => WalletError::DatabaseError(e),
+        }
+    }
+
+    /// Charge a payment to the platform against the user's custodied XLM.
+    /// Keyed by `reference`: charging the same reference again is a no-op.
+    pub async fn charge_custodial(
+        &self,
+        user_id: Uuid,
+        amount: i64,
+        reference: &str,
+        description: &str,
+    ) -> Result<(), WalletError> {
+        if amount <= 0 {
+            return Err(WalletError::InvalidAmount(
+                "Amount must be positive".to_string(),
+            ));
+        }
+        let mut tx = self.db_pool.begin().await?;
+        let available =
+            ledger::balance_for_update(&mut tx, LedgerAccount::Available(user_id), ASSET_XLM)
+                .await?;
+        let journal = Journal::transfer(
+            format!("payment:{}", reference),
+            "payment",
+            Some(user_id),
+            LedgerAccount::Available(user_id),
+            LedgerAccount::Revenue,
+            amount,
+        );
+        if available < amount {
+            // A retried charge that already went through is not a shortfall.
+            let charged: bool = sqlx::query_scalar(
+                "SELECT EXISTS (SELECT 1 FROM ledger_journals WHERE reference = $1)",
+            )
+            .bind(&journal.reference)
+            .fetch_one(&mut *tx)
+            .await?;
+            if charged {
+                return Ok(());
+            }
+            return Err(WalletError::InsufficientBalance {
+                required: amount,
+                available,
+            });
+        }
+        if ledger::post(&mut tx, &journal).await?.is_none() {
+            return Ok(());
+        }
+
+        sqlx::query(
+            r#"
+            INSERT INTO transactions (
+                id, user_id, transaction_type, amount, currency,
+                status, reference, description, completed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(TransactionType::Payment)
+        .bind(Decimal::from(amount))
+        .bind(ASSET_XLM)
+        .bind(TransactionStatus::Completed)
+        .bind(reference)
+        .bind(description)
+        .execute(&mut *tx)
+        .await?;
+        self.sync_xlm_balance(&mut tx, user_id).await?;
+        tx.commit().await?;
+        info!(%user_id, amount, reference, "Custodial payment charged");
+
+        self.publish_balance_update(user_id).await;
+        Ok(())
+    }
+
+    /// Stream deposits from Horizon in a detached task, reconnecting whenever
+    /// the stream drops. Payouts interrupted by a restart are picked up first.
+    pub fn run_custody(self: Arc<Self>) {
+        let Some(custody) = self.custody.clone() else {
+            return;
+        };
+        tokio::spawn(async move {
+            if let Err(e) = self.resume_payouts().await {
+                error!(error = %e, "Failed to resume custodial payouts");
+            }
+            info!(account = %custody.account, "Custodial deposit stream started");
+            loop {
+                if let Err(e) = self.stream_deposits(&custody).await {
+                    warn!(error = %e, "Custodial deposit stream interrupted");
+                }
+                tokio::time::sleep(Duration::from_secs(DEPOSIT_STREAM_RECONNECT_SECS)).await;
+            }
+        });
+    }
+
+    /// Follow the custody account's payments from the stored cursor until
+    /// Horizon closes the stream. The first run starts from the latest ledger.
+    async fn stream_deposits(&self, custody: &Custody) -> Result<(), WalletError> {
+        let cursor: Option<String> =
+            sqlx::query_scalar("SELECT cursor FROM custody_stream_cursors WHERE account = $1")
+                .bind(&custody.account)
+                .fetch_optional(&*self.db_pool)
+                .await?;
+        let mut stream = PaymentStream::connect(
+            &custody.client,
+            &custody.config.horizon_url,
+            &custody.account,
+            cursor.as_deref().unwrap_or("now"),
+        )
+        .await?;
+
+        while let Some(payment) = stream.next().await? {
+            if let Some(amount) = payment.incoming_native_amount(&custody.account) {
+                self.credit_deposit(&payment, amount).await?;
+            }
+            sqlx::query(
+                r#"
+                INSERT INTO custody_stream_cursors (account, cursor)
+                VALUES ($1, $2)
+                ON CONFLICT (account) DO UPDATE SET cursor = EXCLUDED.cursor, updated_at = NOW()
+                "#,
+            )
+            .bind(&custody.account)
+            .bind(&payment.paging_token)
+            .execute(&*self.db_pool)
+            .await?;
+        }
+        Ok(())
+    }
+
+    /// Credit one deposit to the owner of its muxed id, or to the unallocated
+    /// account when it can't be attributed. Keyed by the Horizon operation id,
+    /// so records replayed after a reconnect are ignored.
+    async fn credit_deposit(
+        &self,
+        payment: &HorizonPayment,
+        amount: i64,
+    ) -> Result<(), WalletError> {
+        let muxed_id = payment.muxed_id().and_then(|id| i64::try_from(id).ok());
+        let user_id: Option<Uuid> = match muxed_id {
+            Some(muxed_id) => {
+                sqlx::query_scalar(
+                    "SELECT user_id FROM custody_deposit_addresses WHERE muxed_id = $1",
+                )
+                .bind(muxed_id)
+                .fetch_optional(&*self.db_pool)
+                .await?
+            }
+            None => None,
+        };
+        let metadata = json!({
+            "operation_id": payment.id,
+            "tx_hash": payment.transaction_hash,
+            "from": payment.from,
+            "muxed_id": payment.to_muxed_id,
+        });
+
+        let mut tx = self.db_pool.begin().await?;
+        let credited = match user_id {
+            Some(user_id) => LedgerAccount::Available(user_id),
+            None => LedgerAccount::Unallocated,
+        };
+        let journal = Journal::transfer(
+            format!("deposit:{}", payment.id),
+            "deposit",
+            user_id,
+            LedgerAccount::Custody,
+            credited,
+            amount,
+        )
+        .with_metadata(metadata.clone());
+        if ledger::post(&mut tx, &journal).await?.is_none() {
+            return Ok(());
+        }
+
+        let Some(user_id) = user_id else {
+            tx.commit().await?;
+            warn!(
+                operation_id = %payment.id,
+                amount,
+                "Unattributed custodial deposit held as unallocated"
+            );
+            return Ok(());
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO transactions (
+                id, user_id, transaction_type, amount, currency,
+                status, reference, description, metadata, completed_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
+            "#,
+        )
+        .bind(Uuid::new_v4())
+        .bind(user_id)
+        .bind(TransactionType::Deposit)
+        .bind(Decimal::from(amount))
+        .bind(ASSET_XLM)
+        .bind(TransactionStatus::Completed)
+        .bind(format!("stellar-deposit:{}", payment.id))
+        .bind("Stellar deposit")
+        .bind(metadata.to_string())
+        .execute(&mut *tx)
+        .await?;
+        self.sync_xlm_balance(&mut tx, user_id).await?;
+        tx.commit().await?;
+        info!(operation_id = %payment.id, %user_id, amount, "Custodial deposit credited");
+
+        self.publish_balance_update(user_id).await;
+        Ok(())
+    }
+
+    /// Pay out approved withdrawals left behind by a restart. Payouts that were
+    /// mid-submission can't be told apart from landed ones, so they are left
+    /// unconfirmed for reconciliation.
+    async fn resume_payouts(&self) -> Result<(), WalletError> {
+        sqlx::query(
+            r#"
+            UPDATE custody_withdrawals
+            SET status = 'unconfirmed', error = 'interrupted during submission', updated_at = NOW()
+            WHERE status = 'submitting'
+            "#,
+        )
+        .execute(&*self.db_pool)
+        .await?;
+        let approved: Vec<Uuid> =
+            sqlx::query_scalar("SELECT id FROM custody_withdrawals WHERE status = 'approved'")
+                .fetch_all(&*self.db_pool)
+                .await?;
+        for withdrawal_id in approved {
+            self.spawn_payout(withdrawal_id);
+        }
+        Ok(())
+    }
+
+    fn spawn_payout(&self, withdrawal_id: Uuid) {
+        let service = self.clone();
+        // Relayed calls wait for ledger inclusion; don't hold up the response.
+        tokio::spawn(async move {
+            if let Err(e) = service.execute_payout(withdrawal_id).await {
+                error!(%withdrawal_id, error = %e, "Custodial payout failed");
+            }
+        });
+    }
+
+    async fn execute_payout(&self, withdrawal_id: Uuid) -> Result<(), WalletError> {
+        let custody = self.custody()?;
+        // Claim the withdrawal so it is only ever submitted once.
+        let withdrawal: Option<CustodialWithdrawal> = sqlx::query_as(
+            r#"
+            UPDATE custody_withdrawals SET status = 'submitting', updated_at = NOW()
+            WHERE id = $1 AND status = 'approved'
+            RETURNING *
+            "#,
+        )
+        .bind(withdrawal_id)
+        .fetch_optional(&*self.db_pool)
+        .await?;
+        let Some(withdrawal) = withdrawal else {
+            return Ok(());
+        };
+
+        let result = custody
+            .relayer
+            .transfer(
+                &custody.config.native_asset_contract,
+                &withdrawal.destination,
+                withdrawal.amount as i128,
+            )
+            .await;
+        match result {
+            Ok(receipt) => self.settle_payout(&withdrawal, &receipt.tx_hash).await,
+            // The contract rejected the transfer, so nothing left custody.
+            Err(e @ (RelayerError::SimulationFailed(_) | RelayerError::TransactionFailed(_))) => {
+                warn!(%withdrawal_id, error = %e, "Custodial payout rejected; returning funds");
+                let mut tx = self.db_pool.begin().await?;
+                sqlx::query(
+                    r#"
+                    UPDATE custody_withdrawals
+                    SET status = 'failed', error = $2, updated_at = NOW()
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(withdrawal_id)
+                .bind(e.to_string())
+                .execute(&mut *tx)
+                .await?;
+                self.release_hold(&mut tx, &withdrawal, TransactionStatus::Failed)
+                    .await?;
+                tx.commit().await?;
+                self.publish_balance_update(withdrawal.user_id).await;
+                Ok(())
+            }
+            Err(e) => {
+                sqlx::query(
+                    r#"
+                    UPDATE custody_withdrawals
+                    SET status = 'unconfirmed', error = $2, updated_at = NOW()
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(withdrawal_id)
+                .bind(e.to_string())
+                .execute(&*self.db_pool)
+                .await?;
+                warn!(
+                    %withdrawal_id,
+                    error = %e,
+                    "Custodial payout outcome unknown; funds stay held"
+                );
+                Ok(())
+            }
+        }
+    }
+
+    /// Book a landed payout: the held funds leave custody.
+    async fn settle_payout(
+        &self,
+        withdrawal: &CustodialWithdrawal,
+        tx_hash: &str,
+    ) -> Result<(), WalletError> {
+        let mut tx = self.db_pool.begin().await?;
+        ledger::post(
+            &mut tx,
+            &Journal::transfer(
+                format!("withdrawal:{}:settle", withdrawal.id),
+                "withdrawal_settle",
+                Some(withdrawal.user_id),
+                LedgerAccount::PendingWithdrawal(withdrawal.user_id),
+                LedgerAccount::Custody,
+                withdrawal.amount,
+            )
+            .with_metadata(json!({ "tx_hash": tx_hash })),
+        )
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE custody_withdrawals
+            SET status = 'completed', tx_hash = $2, completed_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(withdrawal.id)
+        .bind(tx_hash)
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"
+            UPDATE transactions
+            SET status = $2, metadata = $3, completed_at = NOW(), updated_at = NOW()
+            WHERE id = $1
+            "#,
+        )
+        .bind(withdrawal.transaction_id)
+        .bind(TransactionStatus::Completed)
+        .bind(json!({ "withdrawal_id": withdrawal.id, "tx_hash": tx_hash }).to_string())
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+        info!(withdrawal_id = %withdrawal.id, tx_hash, "Custodial withdrawal paid out");
+        Ok(())
+    }
+
+    /// Return a withdrawal's held funds to the user's available balance.
+    async fn release_hold(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        withdrawal: &CustodialWithdrawal,
+        transaction_status: TransactionStatus,
+    ) -> Result<(), WalletError> {
+        ledger::post(
+            tx,
+            &Journal::transfer(
+                format!("withdrawal:{}:release", withdrawal.id),
+                "withdrawal_release",
+                Some(withdrawal.user_id),
+                LedgerAccount::PendingWithdrawal(withdrawal.user_id),
+                LedgerAccount::Available(withdrawal.user_id),
+                withdrawal.amount,
+            ),
+        )
+        .await?;
+        sqlx::query("UPDATE transactions SET status = $2, updated_at = NOW() WHERE id = $1")
+            .bind(withdrawal.transaction_id)
+            .bind(transaction_status)
+            .execute(&mut **tx)
+            .await?;
+        self.sync_xlm_balance(tx, withdrawal.user_id).await
+    }
+
+    /// Mirror the ledger's available XLM into `wallets.balance_xlm`, creating
+    /// the wallet if needed.
+    async fn sync_xlm_balance(
+        &self,
+        tx: &mut sqlx::Transaction<'_, Postgres>,
+        user_id: Uuid,
+    ) -> Result<(), WalletError> {
+        sqlx::query("INSERT INTO wallets (user_id) VALUES ($1) ON CONFLICT (user_id) DO NOTHING")
+            .bind(user_id)
+            .execute(&mut **tx)
+            .await?;
+        sqlx::query(
+            r#"
+            UPDATE wallets
+            SET balance_xlm = COALESCE(
+                    (SELECT balance FROM ledger_accounts WHERE account_key = $2), 0),
+                updated_at = NOW()
+            WHERE user_id = $1
+            "#,
+        )
+        .bind(user_id)
+        .bind(LedgerAccount::Available(user_id).key(ASSET_XLM))
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
     // ========================================================================
     // REAL-TIME UPDATES
     // ========================================================================
@@ -0,0 +1,276 @@
+//! Sending queued webhook deliveries.
+//!
+//! Each request is a JSON `POST` of the [`WebhookEvent`](super::WebhookEvent)
+//! with these headers:
+//!
+//! | Header | Value |
+//! |---|---|
+//! | `X-ArenaX-Event` | Event type |
+//! | `X-ArenaX-Delivery` | Delivery ID, to deduplicate retries |
+//! | `X-ArenaX-Signature` | `t=<unix time>,v1=<hex HMAC-SHA256 of "<t>.<body>">` |
+//!
+//! Any 2xx response counts as delivered. Other responses, timeouts and
+//! connection errors are retried with exponential backoff until
+//! [`MAX_ATTEMPTS`] attempts have failed.
+
+use super::{WebhookError, WebhookService};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+pub const SIGNATURE_HEADER: &str = "X-ArenaX-Signature";
+pub const MAX_ATTEMPTS: i32 = 10;
+
+const POLL_INTERVAL_SECS: u64 = 5;
+const BATCH_SIZE: i64 = 50;
+/// How long a claimed delivery stays hidden from other workers.
+const CLAIM_SECS: f64 = 300.0;
+const RETRY_BASE_SECS: u64 = 30;
+const RETRY_MAX_SECS: u64 = 6 * 3600;
+/// Bytes of the response body kept per attempt.
+const RESPONSE_BODY_LIMIT: usize = 2048;
+
+/// `X-ArenaX-Signature` of a request with `body` sent at `timestamp`.
+pub fn signature(secret: &str, timestamp: i64, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(timestamp.to_string().as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!(
+        "t={},v1={}",
+        timestamp,
+        hex::encode(mac.finalize().into_bytes())
+    )
+}
+
+/// Wait before the next attempt after `attempts` failed ones.
+fn retry_delay(attempts: i32) -> Duration {
+    let factor = 1u64 << (attempts.max(1) - 1).min(20);
+    Duration::from_secs(RETRY_BASE_SECS.saturating_mul(factor).min(RETRY_MAX_SECS))
+}
+
+fn truncate(mut body: String) -> String {
+    if body.len() > RESPONSE_BODY_LIMIT {
+        let mut end = RESPONSE_BODY_LIMIT;
+        while !body.is_char_boundary(end) {
+            end -= 1;
+        }
+        body.truncate(end);
+    }
+    body
+}
+
+#[derive(sqlx::FromRow)]
+struct DueDelivery {
+    id: Uuid,
+    event_type: String,
+    payload: serde_json::Value,
+    attempts: i32,
+    url: String,
+    secret: String,
+    active: bool,
+}
+
+/// Outcome of one HTTP request.
+struct Attempt {
+    status_code: Option<i32>,
+    error: Option<String>,
+    response_body: Option<String>,
+    duration_ms: i32,
+}
+
+impl Attempt {
+    fn succeeded(&self) -> bool {
+        self.status_code
+            .is_some_and(|code| (200..300).contains(&code))
+    }
+}
+
+impl WebhookService {
+    /// Spawn the worker sending due deliveries. Several instances can run
+    /// side by side.
+    pub fn run(self: Arc<Self>) {
+        tokio::spawn(async move {
+            info!("Webhook delivery worker started");
+            let mut ticker = tokio::time::interval(Duration::from_secs(POLL_INTERVAL_SECS));
+            loop {
+                ticker.tick().await;
+                if let Err(e) = self.deliver_due().await {
+                    error!(error = %e, "Webhook delivery tick failed");
+                }
+            }
+        });
+    }
+
+    async fn deliver_due(&self) -> Result<(), WebhookError> {
+        // Claim a batch by pushing its next attempt out, so a worker that
+        // dies mid-batch only delays those deliveries.
+        let due = sqlx::query_as::<_, DueDelivery>(
+            "UPDATE webhook_deliveries d \
+             SET next_attempt_at = NOW() + make_interval(secs => $2) \
+             FROM webhook_endpoints e \
+             WHERE e.id = d.endpoint_id AND d.id IN ( \
+                 SELECT id FROM webhook_deliveries \
+                 WHERE status = 'pending' AND next_attempt_at <= NOW() \
+                 ORDER BY next_attempt_at \
+                 LIMIT $1 \
+                 FOR UPDATE SKIP LOCKED) \
+             RETURNING d.id, d.event_type, d.payload, d.attempts, e.url, e.secret, e.active",
+        )
+        .bind(BATCH_SIZE)
+        .bind(CLAIM_SECS)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        for delivery in due {
+            if !delivery.active {
+                sqlx::query(
+                    "UPDATE webhook_deliveries \
+                     SET status = 'failed', last_error = 'Endpoint disabled' WHERE id = $1",
+                )
+                .bind(delivery.id)
+                .execute(&self.db_pool)
+                .await?;
+                continue;
+            }
+            let attempt = self.send(&delivery).await;
+            self.record_attempt(&delivery, &attempt).await?;
+        }
+        Ok(())
+    }
+
+    async fn send(&self, delivery: &DueDelivery) -> Attempt {
+        let body = delivery.payload.to_string();
+        let started = Instant::now();
+        let result = self
+            .client
+            .post(&delivery.url)
+            .header("Content-Type", "application/json")
+            .header("User-Agent", "ArenaX-Webhooks/1.0")
+            .header("X-ArenaX-Event", &delivery.event_type)
+            .header("X-ArenaX-Delivery", delivery.id.to_string())
+            .header(
+                SIGNATURE_HEADER,
+                signature(&delivery.secret, Utc::now().timestamp(), body.as_bytes()),
+            )
+            .body(body)
+            .send()
+            .await;
+
+        let (status_code, error, response_body) = match result {
+            Ok(response) => {
+                let status = response.status();
+                let body = response.text().await.unwrap_or_default();
+                let error = (!status.is_success()).then(|| format!("HTTP {}", status));
+                (Some(status.as_u16() as i32), error, Some(truncate(body)))
+            }
+            Err(e) => (None, Some(e.to_string()), None),
+        };
+        Attempt {
+            status_code,
+            error,
+            response_body,
+            duration_ms: started.elapsed().as_millis().min(i32::MAX as u128) as i32,
+        }
+    }
+
+    async fn record_attempt(
+        &self,
+        delivery: &DueDelivery,
+        attempt: &Attempt,
+    ) -> Result<(), WebhookError> {
+        let attempts = delivery.attempts + 1;
+        let mut tx = self.db_pool.begin().await?;
+        sqlx::query(
+            "INSERT INTO webhook_attempts \
+                 (delivery_id, attempt, status_code, error, response_body, duration_ms) \
+             VALUES ($1, $2, $3, $4, $5, $6)",
+        )
+        .bind(delivery.id)
+        .bind(attempts)
+        .bind(attempt.status_code)
+        .bind(&attempt.error)
+        .bind(&attempt.response_body)
+        .bind(attempt.duration_ms)
+        .execute(&mut *tx)
+        .await?;
+
+        if attempt.succeeded() {
+            sqlx::query(
+                "UPDATE webhook_deliveries \
+                 SET status = 'succeeded', attempts = $2, last_status_code = $3, \
+                     last_error = NULL, delivered_at = NOW() \
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .bind(attempts)
+            .bind(attempt.status_code)
+            .execute(&mut *tx)
+            .await?;
+        } else {
+            let give_up = attempts >= MAX_ATTEMPTS;
+            let delay = retry_delay(attempts);
+            sqlx::query(
+                "UPDATE webhook_deliveries \
+                 SET status = CASE WHEN $4 THEN 'failed' ELSE 'pending' END, \
+                     attempts = $2, last_status_code = $3, last_error = $5, \
+                     next_attempt_at = NOW() + make_interval(secs => $6) \
+                 WHERE id = $1",
+            )
+            .bind(delivery.id)
+            .bind(attempts)
+            .bind(attempt.status_code)
+            .bind(give_up)
+            .bind(&attempt.error)
+            .bind(delay.as_secs() as f64)
+            .execute(&mut *tx)
+            .await?;
+
+            warn!(
+                delivery_id = %delivery.id,
+                url = %delivery.url,
+                attempts,
+                give_up,
+                error = attempt.error.as_deref().unwrap_or_default(),
+                "Webhook delivery failed"
+            );
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signature_and_backoff() {
+        let body = br#"{"type":"ping"}"#;
+        let header = signature("whsec_test", 1_767_225_600, body);
+
+        let (timestamp, digest) = header.split_once(",v1=").unwrap();
+        assert_eq!(timestamp, "t=1767225600");
+        let expected = Hmac::<Sha256>::new_from_slice(b"whsec_test")
+            .unwrap()
+            .chain_update(b"1767225600.")
+            .chain_update(body)
+            .finalize()
+            .into_bytes();
+        assert_eq!(digest, hex::encode(expected));
+        assert_ne!(header, signature("whsec_other", 1_767_225_600, body));
+
+        assert_eq!(retry_delay(1), Duration::from_secs(30));
+        assert_eq!(retry_delay(4), Duration::from_secs(240));
+        assert_eq!(
+            retry_delay(MAX_ATTEMPTS + 5),
+            Duration::from_secs(RETRY_MAX_SECS)
+        );
+        assert_eq!(truncate("é".repeat(2000)).len(), RESPONSE_BODY_LIMIT);
+    }
+}
@@ -0,0 +1,586 @@
+//! Outbound webhooks for match and tournament events.
+//!
+//! Integrators register endpoints with the event types they want. Match and
+//! tournament events published on the [`EventBus`] are recorded here as one
+//! delivery per subscribed endpoint, and [`WebhookService::run`] sends them,
+//! signed with the endpoint's secret (see [`delivery`]), retrying failures
+//! with exponential backoff. Every attempt is kept so integrators can debug
+//! their endpoints through the delivery log.
+//!
+//! Match settlements and stake slashes come from the domain event stream
+//! instead, see [`crate::service::domain_events`]; their webhook keeps the
+//! domain event's id, so a redelivered event can be recognized.
+//!
+//! [`EventBus`]: crate::realtime::EventBus
+
+pub mod delivery;
+
+use crate::api_error::ApiError;
+use crate::db::DbPool;
+use crate::models::{
+    CreateWebhookEndpointRequest, UpdateWebhookEndpointRequest, WebhookAttempt, WebhookDelivery,
+    WebhookDeliveryDetail, WebhookDeliveryStatus, WebhookEndpoint, WebhookEndpointWithSecret,
+};
+use crate::realtime::events::RealtimeEvent;
+use crate::service::domain_events::{DomainEvent, DomainEventEnvelope, DomainEventHandler};
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use rand::RngCore;
+use reqwest::{Client, Url};
+use serde::Serialize;
+use std::net::IpAddr;
+use std::time::Duration;
+use thiserror::Error;
+use uuid::Uuid;
+
+pub use delivery::{signature, SIGNATURE_HEADER};
+
+const ENDPOINT_COLUMNS: &str =
+    "id, owner_id, url, description, event_types, active, created_at, updated_at";
+const DELIVERY_COLUMNS: &str = "id, endpoint_id, event_id, event_type, payload, status, \
+     attempts, next_attempt_at, last_status_code, last_error, created_at, delivered_at";
+
+/// Event types endpoints can subscribe to. `ping` is only sent on request.
+pub const EVENT_TYPES: &[&str] = &[
+    "match.status_changed",
+    "match.completed",
+    "match.disputed",
+    "match.chain_event",
+    "match.live_score",
+    "match.finalized",
+    "tournament.updated",
+    "tournament.chain_event",
+    "stake.slashed",
+];
+
+const REQUEST_TIMEOUT_SECS: u64 = 10;
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("{0}")]
+    InvalidRequest(String),
+    #[error("Webhook endpoint not found")]
+    EndpointNotFound,
+    #[error("Webhook delivery not found")]
+    DeliveryNotFound,
+    #[error("Database error: {0}")]
+    DatabaseError(#[from] sqlx::Error),
+}
+
+impl From<WebhookError> for ApiError {
+    fn from(err: WebhookError) -> Self {
+        match err {
+            WebhookError::InvalidRequest(msg) => ApiError::BadRequest(msg),
+            WebhookError::EndpointNotFound | WebhookError::DeliveryNotFound => ApiError::NotFound,
+            WebhookError::DatabaseError(e) => ApiError::DatabaseError(e),
+        }
+    }
+}
+
+/// An event as endpoints receive it.
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub id: Uuid,
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub created_at: DateTime<Utc>,
+    pub data: serde_json::Value,
+}
+
+impl WebhookEvent {
+    pub fn new(event_type: &str, data: serde_json::Value) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            event_type: event_type.to_string(),
+            created_at: Utc::now(),
+            data,
+        }
+    }
+
+    /// The webhook for an event published on a match's channel; `None` for
+    /// events not offered as webhooks.
+    pub fn for_match(match_id: Uuid, event: &RealtimeEvent) -> Option<Self> {
+        let event_type = match event {
+            RealtimeEvent::MatchStatusChange { .. } => "match.status_changed",
+            RealtimeEvent::MatchCompleted { .. } => "match.completed",
+            RealtimeEvent::MatchDisputed { .. } => "match.disputed",
+            RealtimeEvent::ChainEvent { .. } => "match.chain_event",
+            RealtimeEvent::LiveScoreUpdate { .. } => "match.live_score",
+            _ => return None,
+        };
+        Self::from_realtime(event_type, "match_id", match_id, event)
+    }
+
+    /// The webhook for an event published on a tournament's channel.
+    pub fn for_tournament(tournament_id: Uuid, event: &RealtimeEvent) -> Option<Self> {
+        let event_type = match event {
+            RealtimeEvent::TournamentUpdate { .. } => "tournament.updated",
+            RealtimeEvent::ChainEvent { .. } => "tournament.chain_event",
+            _ => return None,
+        };
+        Self::from_realtime(event_type, "tournament_id", tournament_id, event)
+    }
+
+    /// The webhook for a domain event; `None` for events not offered as
+    /// webhooks, such as payments, which concern their payer only.
+    pub fn for_domain(envelope: &DomainEventEnvelope) -> Option<Self> {
+        if matches!(envelope.event, DomainEvent::PaymentCompleted { .. }) {
+            return None;
+        }
+        let mut data = serde_json::to_value(&envelope.event).ok()?;
+        data.as_object_mut()?.remove("type");
+        Some(Self {
+            id: envelope.id,
+            event_type: envelope.event.event_type().to_string(),
+            created_at: envelope.occurred_at,
+            data,
+        })
+    }
+
+    fn from_realtime(
+        event_type: &str,
+        subject_key: &str,
+        subject_id: Uuid,
+        event: &RealtimeEvent,
+    ) -> Option<Self> {
+        let mut data = serde_json::to_value(event).ok()?;
+        let object = data.as_object_mut()?;
+        object.remove("type");
+        object.insert(subject_key.to_string(), subject_id.to_string().into());
+        Some(Self::new(event_type, data))
+    }
+}
+
+/// Check that `pattern` names an event type, a group like `match.*`, or `*`.
+fn validate_event_type(pattern: &str) -> Result<(), WebhookError> {
+    let valid = pattern == "*"
+        || EVENT_TYPES.contains(&pattern)
+        || pattern.strip_suffix(".*").is_some_and(|group| {
+            EVENT_TYPES
+                .iter()
+                .any(|t| t.starts_with(&format!("{}.", group)))
+        });
+    if valid {
+        Ok(())
+    } else {
+        Err(WebhookError::InvalidRequest(format!(
+            "Unknown event type `{}`",
+            pattern
+        )))
+    }
+}
+
+/// Endpoints must be public HTTPS URLs.
+fn validate_url(url: &str) -> Result<(), WebhookError> {
+    let invalid = |msg: &str| Err(WebhookError::InvalidRequest(msg.to_string()));
+    let Ok(parsed) = Url::parse(url) else {
+        return invalid("Invalid endpoint URL");
+    };
+    if parsed.scheme() != "https" {
+        return invalid("Endpoint URL must use https");
+    }
+    let Some(host) = parsed.host_str() else {
+        return invalid("Endpoint URL needs a host");
+    };
+    let host = host.trim_start_matches('[').trim_end_matches(']');
+    let private = match host.parse::<IpAddr>() {
+        Ok(IpAddr::V4(ip)) => {
+            ip.is_private()
+                || ip.is_loopback()
+                || ip.is_link_local()
+                || ip.is_unspecified()
+                || ip.is_broadcast()
+        }
+        Ok(IpAddr::V6(ip)) => {
+            ip.is_loopback()
+                || ip.is_unspecified()
+                // Unique local (fc00::/7) and link-local (fe80::/10).
+                || (ip.segments()[0] & 0xfe00) == 0xfc00
+                || (ip.segments()[0] & 0xffc0) == 0xfe80
+        }
+        Err(_) => {
+            let host = host.to_ascii_lowercase();
+            host == "localhost"
+                || [".localhost", ".local", ".internal"]
+                    .iter()
+                    .any(|suffix| host.ends_with(suffix))
+        }
+    };
+    if private {
+        return invalid("Endpoint URL must be publicly reachable");
+    }
+    Ok(())
+}
+
+fn validate_event_types(event_types: &[String]) -> Result<(), WebhookError> {
+    event_types
+        .iter()
+        .try_for_each(|pattern| validate_event_type(pattern))
+}
+
+fn new_secret() -> String {
+    let mut bytes = [0u8; 32];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    format!("whsec_{}", hex::encode(bytes))
+}
+
+pub struct WebhookService {
+    db_pool: DbPool,
+    client: Client,
+}
+
+impl WebhookService {
+    pub fn new(db_pool: DbPool) -> Self {
+        let client = Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            // A redirect could point the request anywhere.
+            .redirect(reqwest::redirect::Policy::none())
+            .build()
+            .expect("Failed to build webhook HTTP client");
+        Self { db_pool, client }
+    }
+
+    pub async fn create_endpoint(
+        &self,
+        owner_id: Uuid,
+        request: &CreateWebhookEndpointRequest,
+    ) -> Result<WebhookEndpointWithSecret, WebhookError> {
+        validate_url(&request.url)?;
+        validate_event_types(&request.event_types)?;
+
+        let secret = new_secret();
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "INSERT INTO webhook_endpoints (owner_id, url, description, event_types, secret) \
+             VALUES ($1, $2, $3, $4, $5) RETURNING {}",
+            ENDPOINT_COLUMNS
+        ))
+        .bind(owner_id)
+        .bind(&request.url)
+        .bind(&request.description)
+        .bind(&request.event_types)
+        .bind(&secret)
+        .fetch_one(&self.db_pool)
+        .await?;
+
+        Ok(WebhookEndpointWithSecret { endpoint, secret })
+    }
+
+    pub async fn list_endpoints(
+        &self,
+        owner_id: Uuid,
+    ) -> Result<Vec<WebhookEndpoint>, WebhookError> {
+        Ok(sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {} FROM webhook_endpoints WHERE owner_id = $1 ORDER BY created_at",
+            ENDPOINT_COLUMNS
+        ))
+        .bind(owner_id)
+        .fetch_all(&self.db_pool)
+        .await?)
+    }
+
+    pub async fn endpoint(
+        &self,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookEndpoint, WebhookError> {
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "SELECT {} FROM webhook_endpoints WHERE id = $1 AND owner_id = $2",
+            ENDPOINT_COLUMNS
+        ))
+        .bind(id)
+        .bind(owner_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(WebhookError::EndpointNotFound)
+    }
+
+    pub async fn update_endpoint(
+        &self,
+        owner_id: Uuid,
+        id: Uuid,
+        request: &UpdateWebhookEndpointRequest,
+    ) -> Result<WebhookEndpoint, WebhookError> {
+        if let Some(url) = &request.url {
+            validate_url(url)?;
+        }
+        if let Some(event_types) = &request.event_types {
+            validate_event_types(event_types)?;
+        }
+
+        sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "UPDATE webhook_endpoints \
+             SET url = COALESCE($3, url), description = COALESCE($4, description), \
+                 event_types = COALESCE($5, event_types), active = COALESCE($6, active), \
+                 updated_at = NOW() \
+             WHERE id = $1 AND owner_id = $2 \
+             RETURNING {}",
+            ENDPOINT_COLUMNS
+        ))
+        .bind(id)
+        .bind(owner_id)
+        .bind(&request.url)
+        .bind(&request.description)
+        .bind(&request.event_types)
+        .bind(request.active)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(WebhookError::EndpointNotFound)
+    }
+
+    /// Delete an endpoint with its delivery log.
+    pub async fn delete_endpoint(&self, owner_id: Uuid, id: Uuid) -> Result<(), WebhookError> {
+        let result = sqlx::query("DELETE FROM webhook_endpoints WHERE id = $1 AND owner_id = $2")
+            .bind(id)
+            .bind(owner_id)
+            .execute(&self.db_pool)
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(WebhookError::EndpointNotFound);
+        }
+        Ok(())
+    }
+
+    /// Replace the signing secret. Deliveries still queued are signed with
+    /// the new one.
+    pub async fn rotate_secret(
+        &self,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookEndpointWithSecret, WebhookError> {
+        let secret = new_secret();
+        let endpoint = sqlx::query_as::<_, WebhookEndpoint>(&format!(
+            "UPDATE webhook_endpoints SET secret = $3, updated_at = NOW() \
+             WHERE id = $1 AND owner_id = $2 RETURNING {}",
+            ENDPOINT_COLUMNS
+        ))
+        .bind(id)
+        .bind(owner_id)
+        .bind(&secret)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(WebhookError::EndpointNotFound)?;
+
+        Ok(WebhookEndpointWithSecret { endpoint, secret })
+    }
+
+    /// Queue a `ping` event to the endpoint, whatever its event types.
+    pub async fn ping(&self, owner_id: Uuid, id: Uuid) -> Result<WebhookDelivery, WebhookError> {
+        let endpoint = self.endpoint(owner_id, id).await?;
+        let event = WebhookEvent::new("ping", serde_json::json!({ "endpoint_id": endpoint.id }));
+
+        Ok(sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "INSERT INTO webhook_deliveries (endpoint_id, event_id, event_type, payload) \
+             VALUES ($1, $2, $3, $4) RETURNING {}",
+            DELIVERY_COLUMNS
+        ))
+        .bind(endpoint.id)
+        .bind(event.id)
+        .bind(&event.event_type)
+        .bind(sqlx::types::Json(&event))
+        .fetch_one(&self.db_pool)
+        .await?)
+    }
+
+    /// Queue `event` for every active endpoint subscribed to its type.
+    /// Returns the number of deliveries.
+    pub async fn record(&self, event: &WebhookEvent) -> Result<u64, WebhookError> {
+        let group = match event.event_type.split_once('.') {
+            Some((group, _)) => format!("{}.*", group),
+            None => event.event_type.clone(),
+        };
+        let result = sqlx::query(
+            "INSERT INTO webhook_deliveries (endpoint_id, event_id, event_type, payload) \
+             SELECT id, $1, $2, $3 FROM webhook_endpoints \
+             WHERE active AND ($2 = ANY(event_types) OR $4 = ANY(event_types) \
+                               OR '*' = ANY(event_types))",
+        )
+        .bind(event.id)
+        .bind(&event.event_type)
+        .bind(sqlx::types::Json(event))
+        .bind(group)
+        .execute(&self.db_pool)
+        .await?;
+        Ok(result.rows_affected())
+    }
+
+    /// Deliveries to an endpoint, newest first.
+    pub async fn deliveries(
+        &self,
+        owner_id: Uuid,
+        endpoint_id: Uuid,
+        status: Option<WebhookDeliveryStatus>,
+        limit: i64,
+        offset: i64,
+    ) -> Result<Vec<WebhookDelivery>, WebhookError> {
+        let endpoint = self.endpoint(owner_id, endpoint_id).await?;
+        Ok(sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {} FROM webhook_deliveries \
+             WHERE endpoint_id = $1 AND ($2::text IS NULL OR status = $2) \
+             ORDER BY created_at DESC LIMIT $3 OFFSET $4",
+            DELIVERY_COLUMNS
+        ))
+        .bind(endpoint.id)
+        .bind(status)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(&self.db_pool)
+        .await?)
+    }
+
+    /// A delivery with every attempt made for it.
+    pub async fn delivery(
+        &self,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookDeliveryDetail, WebhookError> {
+        let delivery = self.owned_delivery(owner_id, id).await?;
+        let attempts = sqlx::query_as::<_, WebhookAttempt>(
+            "SELECT id, delivery_id, attempt, status_code, error, response_body, duration_ms, \
+                    created_at \
+             FROM webhook_attempts WHERE delivery_id = $1 ORDER BY attempt",
+        )
+        .bind(delivery.id)
+        .fetch_all(&self.db_pool)
+        .await?;
+
+        Ok(WebhookDeliveryDetail { delivery, attempts })
+    }
+
+    /// Send a delivery again, e.g. once the endpoint is fixed. Its attempt
+    /// count starts over.
+    pub async fn redeliver(
+        &self,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookDelivery, WebhookError> {
+        let delivery = self.owned_delivery(owner_id, id).await?;
+        Ok(sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "UPDATE webhook_deliveries \
+             SET status = 'pending', attempts = 0, next_attempt_at = NOW() \
+             WHERE id = $1 RETURNING {}",
+            DELIVERY_COLUMNS
+        ))
+        .bind(delivery.id)
+        .fetch_one(&self.db_pool)
+        .await?)
+    }
+
+    async fn owned_delivery(
+        &self,
+        owner_id: Uuid,
+        id: Uuid,
+    ) -> Result<WebhookDelivery, WebhookError> {
+        sqlx::query_as::<_, WebhookDelivery>(&format!(
+            "SELECT {} FROM webhook_deliveries \
+             WHERE id = $1 \
+               AND endpoint_id IN (SELECT id FROM webhook_endpoints WHERE owner_id = $2)",
+            DELIVERY_COLUMNS
+        ))
+        .bind(id)
+        .bind(owner_id)
+        .fetch_optional(&self.db_pool)
+        .await?
+        .ok_or(WebhookError::DeliveryNotFound)
+    }
+}
+
+/// Queues deliveries of the domain events offered as webhooks.
+impl DomainEventHandler for WebhookService {
+    fn name(&self) -> &'static str {
+        "webhooks"
+    }
+
+    fn handle<'a>(&'a self, event: &'a DomainEventEnvelope) -> BoxFuture<'a, Result<(), String>> {
+        Box::pin(async move {
+            if let Some(webhook) = WebhookEvent::for_domain(event) {
+                self.record(&webhook).await.map_err(|e| e.to_string())?;
+            }
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_endpoint() {
+        assert!(validate_url("https://hooks.example.com/arenax").is_ok());
+        assert!(validate_url("http://hooks.example.com/arenax").is_err());
+        assert!(validate_url("https://localhost:8080/hook").is_err());
+        assert!(validate_url("https://10.0.0.7/hook").is_err());
+        assert!(validate_url("https://169.254.169.254/latest").is_err());
+        assert!(validate_url("https://[::1]/hook").is_err());
+        assert!(validate_url("not a url").is_err());
+
+        assert!(validate_event_type("match.completed").is_ok());
+        assert!(validate_event_type("tournament.*").is_ok());
+        assert!(validate_event_type("*").is_ok());
+        assert!(validate_event_type("wallet.*").is_err());
+        assert!(validate_event_type("match.started").is_err());
+    }
+
+    #[test]
+    fn test_event_from_realtime() {
+        let match_id = Uuid::new_v4();
+        let winner_id = Uuid::new_v4();
+        let completed = RealtimeEvent::MatchCompleted {
+            match_id,
+            winner_id,
+            elo_change: 16,
+            timestamp: "2026-10-01T12:00:00Z".to_string(),
+        };
+
+        let event = WebhookEvent::for_match(match_id, &completed).unwrap();
+        assert_eq!(event.event_type, "match.completed");
+        assert_eq!(event.data["winner_id"], winner_id.to_string());
+        assert_eq!(event.data["match_id"], match_id.to_string());
+        assert!(event.data.get("type").is_none());
+
+        assert!(WebhookEvent::for_tournament(match_id, &completed).is_none());
+        let balance = RealtimeEvent::BalanceUpdate {
+            user_id: winner_id,
+            balance_ngn: 0,
+            balance_arenax_tokens: 0,
+            balance_xlm: 0,
+            timestamp: String::new(),
+        };
+        assert!(WebhookEvent::for_match(match_id, &balance).is_none());
+    }
+
+    #[test]
+    fn test_event_from_domain() {
+        let finalized = DomainEventEnvelope {
+            id: Uuid::new_v4(),
+            occurred_at: Utc::now(),
+            trace: Default::default(),
+            event: DomainEvent::MatchFinalized {
+                match_id: Uuid::new_v4(),
+                on_chain_match_id: "42".to_string(),
+                player_a: "GA".to_string(),
+                player_b: "GB".to_string(),
+                winner: Some("GA".to_string()),
+                tx_hash: "ab".repeat(32),
+            },
+        };
+        let event = WebhookEvent::for_domain(&finalized).unwrap();
+        assert_eq!(event.id, finalized.id);
+        assert_eq!(event.event_type, "match.finalized");
+        assert_eq!(event.data["winner"], "GA");
+        assert!(event.data.get("type").is_none());
+        assert!(validate_event_type("stake.slashed").is_ok());
+
+        let payment = DomainEventEnvelope {
+            event: DomainEvent::PaymentCompleted {
+                payment_id: Uuid::new_v4(),
+                user_id: Uuid::new_v4(),
+                amount: 100,
+                currency: "NGN".to_string(),
+                purpose: "deposit".to_string(),
+            },
+            ..finalized
+        };
+        assert!(WebhookEvent::for_domain(&payment).is_none());
+    }
+}
@@ -0,0 +1,235 @@
+//! Prometheus metrics.
+//!
+//! Request counts and latencies are kept in process by [`HttpMetrics`] and
+//! fed by [`crate::middleware::metrics::MetricsMiddleware`]. Everything else
+//! (queue depths, relayer outcomes, indexer lag) is read from its source when
+//! `/metrics` is scraped, see [`crate::service::health_service`].
+//!
+//! [`Exposition`] writes the Prometheus text format.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::sync::Mutex;
+use std::time::Duration;
+
+/// Upper bounds of the request latency buckets (seconds).
+pub const LATENCY_BUCKETS: &[f64] = &[
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
+/// A request series: the route pattern rather than the path, so ids in URLs
+/// do not each make a series of their own.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+struct RequestKey {
+    method: String,
+    route: String,
+    status: String,
+}
+
+#[derive(Debug, Clone, Default)]
+struct Histogram {
+    /// Observations at or below each of [`LATENCY_BUCKETS`], not cumulative.
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn observe(&mut self, value: f64) {
+        if let Some(i) = LATENCY_BUCKETS.iter().position(|bound| value <= *bound) {
+            self.buckets[i] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+/// Counts and latencies of handled requests.
+#[derive(Debug, Default)]
+pub struct HttpMetrics {
+    requests: Mutex<BTreeMap<RequestKey, Histogram>>,
+}
+
+impl HttpMetrics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn observe(&self, method: &str, route: &str, status: u16, latency: Duration) {
+        let key = RequestKey {
+            method: method.to_string(),
+            route: route.to_string(),
+            status: status.to_string(),
+        };
+        let mut requests = self.requests.lock().unwrap_or_else(|e| e.into_inner());
+        requests
+            .entry(key)
+            .or_default()
+            .observe(latency.as_secs_f64());
+    }
+
+    pub fn write(&self, out: &mut Exposition) {
+        let requests = self
+            .requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone();
+
+        out.header(
+            "arenax_http_requests_total",
+            "counter",
+            "HTTP requests handled, by route and status.",
+        );
+        for (key, histogram) in &requests {
+            out.sample(
+                "arenax_http_requests_total",
+                &key.labels(),
+                histogram.count as f64,
+            );
+        }
+
+        out.header(
+            "arenax_http_request_duration_seconds",
+            "histogram",
+            "Time to produce a response, by route and status.",
+        );
+        for (key, histogram) in &requests {
+            let labels = key.labels();
+            let mut cumulative = 0;
+            for (bound, count) in LATENCY_BUCKETS.iter().zip(histogram.buckets) {
+                cumulative += count;
+                let le = bound.to_string();
+                let mut bucket = labels.clone();
+                bucket.push(("le", &le));
+                out.sample(
+                    "arenax_http_request_duration_seconds_bucket",
+                    &bucket,
+                    cumulative as f64,
+                );
+            }
+            let mut bucket = labels.clone();
+            bucket.push(("le", "+Inf"));
+            out.sample(
+                "arenax_http_request_duration_seconds_bucket",
+                &bucket,
+                histogram.count as f64,
+            );
+            out.sample(
+                "arenax_http_request_duration_seconds_sum",
+                &labels,
+                histogram.sum,
+            );
+            out.sample(
+                "arenax_http_request_duration_seconds_count",
+                &labels,
+                histogram.count as f64,
+            );
+        }
+    }
+}
+
+impl RequestKey {
+    fn labels(&self) -> Vec<(&str, &str)> {
+        vec![
+            ("method", self.method.as_str()),
+            ("route", self.route.as_str()),
+            ("status", self.status.as_str()),
+        ]
+    }
+}
+
+/// A scrape in the Prometheus text format.
+#[derive(Debug, Default)]
+pub struct Exposition {
+    out: String,
+}
+
+impl Exposition {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// `# HELP` and `# TYPE` lines for a metric family.
+    pub fn header(&mut self, name: &str, kind: &str, help: &str) {
+        let _ = writeln!(self.out, "# HELP {} {}", name, help.replace('\n', " "));
+        let _ = writeln!(self.out, "# TYPE {} {}", name, kind);
+    }
+
+    pub fn sample(&mut self, name: &str, labels: &[(&str, &str)], value: f64) {
+        self.out.push_str(name);
+        if !labels.is_empty() {
+            self.out.push('{');
+            for (i, (key, value)) in labels.iter().enumerate() {
+                if i > 0 {
+                    self.out.push(',');
+                }
+                let _ = write!(self.out, "{}=\"{}\"", key, escape_label(value));
+            }
+            self.out.push('}');
+        }
+        let _ = writeln!(self.out, " {}", format_value(value));
+    }
+
+    /// A family with a single unlabelled sample.
+    pub fn gauge(&mut self, name: &str, help: &str, value: f64) {
+        self.header(name, "gauge", help);
+        self.sample(name, &[], value);
+    }
+
+    pub fn finish(self) -> String {
+        self.out
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_http_metrics_exposition() {
+        let metrics = HttpMetrics::new();
+        let route = "/api/matches/{id}";
+        metrics.observe("GET", route, 200, Duration::from_millis(3));
+        metrics.observe("GET", route, 200, Duration::from_millis(40));
+        metrics.observe("GET", route, 200, Duration::from_secs(30));
+        metrics.observe("POST", "/api/\"odd\"", 599, Duration::from_millis(1));
+
+        let mut out = Exposition::new();
+        metrics.write(&mut out);
+        let text = out.finish();
+
+        let labels = r#"method="GET",route="/api/matches/{id}",status="200""#;
+        assert!(text.contains("# TYPE arenax_http_request_duration_seconds histogram\n"));
+        assert!(text.contains(&format!("arenax_http_requests_total{{{labels}}} 3\n")));
+        assert!(text.contains(&format!(
+            "arenax_http_request_duration_seconds_bucket{{{labels},le=\"0.005\"}} 1\n"
+        )));
+        assert!(text.contains(&format!(
+            "arenax_http_request_duration_seconds_bucket{{{labels},le=\"0.05\"}} 2\n"
+        )));
+        assert!(text.contains(&format!(
+            "arenax_http_request_duration_seconds_bucket{{{labels},le=\"10\"}} 2\n"
+        )));
+        assert!(text.contains(&format!(
+            "arenax_http_request_duration_seconds_bucket{{{labels},le=\"+Inf\"}} 3\n"
+        )));
+        assert!(text.contains(r#"route="/api/\"odd\"",status="599""#));
+    }
+}
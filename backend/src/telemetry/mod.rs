@@ -0,0 +1,68 @@
+pub mod metrics;
+pub mod propagation;
+
+use crate::config::TelemetryConfig;
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry::KeyValue;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use opentelemetry_sdk::trace::{Sampler, TracerProvider};
+use opentelemetry_sdk::{runtime, Resource};
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter};
+
+/// Keeps the tracer provider alive; dropping it flushes spans still waiting
+/// to be exported.
+pub struct TelemetryGuard {
+    provider: TracerProvider,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Err(e) = self.provider.shutdown() {
+            eprintln!("Failed to flush trace spans: {}", e);
+        }
+    }
+}
+
+/// Install the log subscriber and the OpenTelemetry tracer.
+///
+/// Spans get trace ids whether or not they are exported, so logs, relayer
+/// memos and outgoing `traceparent` headers can name the trace; they are
+/// shipped over OTLP only when an endpoint is configured.
+pub fn init_telemetry(config: &TelemetryConfig) -> TelemetryGuard {
+    opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+
+    let mut builder = TracerProvider::builder()
+        .with_sampler(Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(
+            config.sampling_ratio,
+        ))))
+        .with_resource(Resource::new([KeyValue::new(
+            "service.name",
+            config.service_name.clone(),
+        )]));
+
+    let mut export_error = None;
+    if let Some(endpoint) = &config.otlp_endpoint {
+        match opentelemetry_otlp::SpanExporter::builder()
+            .with_tonic()
+            .with_endpoint(endpoint.clone())
+            .build()
+        {
+            Ok(exporter) => builder = builder.with_batch_exporter(exporter, runtime::Tokio),
+            Err(e) => export_error = Some(e),
+        }
+    }
+    let provider = builder.build();
+
+    tracing_subscriber::registry()
+        .with(EnvFilter::try_from_default_env().unwrap_or_else(|_| "backend=info".into()))
+        .with(tracing_subscriber::fmt::layer())
+        .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("arenax-backend")))
+        .init();
+
+    if let Some(e) = export_error {
+        tracing::warn!(error = %e, "OTLP exporter unavailable, spans will not be exported");
+    }
+
+    TelemetryGuard { provider }
+}
@@ -0,0 +1,86 @@
+//! W3C trace context across process and queue boundaries.
+//!
+//! Incoming requests continue the trace named in their `traceparent` header;
+//! outgoing RPC calls carry the current one. Work handed to a queue takes a
+//! [`TraceCarrier`] along so the consumer's span joins the producer's trace.
+
+use std::collections::HashMap;
+
+use actix_web::http::header::HeaderMap;
+use opentelemetry::propagation::Extractor;
+use opentelemetry::trace::TraceContextExt;
+use tracing::Span;
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+/// Trace context headers (`traceparent`, `tracestate`) by name, as stored
+/// alongside queued work.
+pub type TraceCarrier = HashMap<String, String>;
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|name| name.as_str()).collect()
+    }
+}
+
+/// Make `span` a child of the trace named in request `headers`, if any.
+pub fn continue_from_headers(span: &Span, headers: &HeaderMap) {
+    let parent = opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.extract(&HeaderExtractor(headers))
+    });
+    span.set_parent(parent);
+}
+
+/// Make `span` a child of the trace captured in `carrier`, if any.
+pub fn continue_from(span: &Span, carrier: &TraceCarrier) {
+    if carrier.is_empty() {
+        return;
+    }
+    let parent =
+        opentelemetry::global::get_text_map_propagator(|propagator| propagator.extract(carrier));
+    span.set_parent(parent);
+}
+
+/// The trace context of `span`, to store with work handed to a queue.
+pub fn carrier(span: &Span) -> TraceCarrier {
+    let mut carrier = TraceCarrier::new();
+    let context = span.context();
+    opentelemetry::global::get_text_map_propagator(|propagator| {
+        propagator.inject_context(&context, &mut carrier)
+    });
+    carrier
+}
+
+/// [`carrier`] of the current span.
+pub fn current_carrier() -> TraceCarrier {
+    carrier(&Span::current())
+}
+
+/// Add `span`'s trace context headers to an outgoing request.
+pub fn inject_headers(span: &Span, request: reqwest::RequestBuilder) -> reqwest::RequestBuilder {
+    carrier(span)
+        .into_iter()
+        .fold(request, |request, (name, value)| {
+            request.header(name, value)
+        })
+}
+
+/// Hex trace id of `span`, if it belongs to a trace.
+pub fn trace_id(span: &Span) -> Option<String> {
+    let context = span.context();
+    let span_ref = context.span();
+    let span_context = span_ref.span_context();
+    span_context
+        .is_valid()
+        .then(|| span_context.trace_id().to_string())
+}
+
+/// [`trace_id`] of the current span.
+pub fn current_trace_id() -> Option<String> {
+    trace_id(&Span::current())
+}
@@ -0,0 +1,291 @@
+#![no_std]
+
+//! Achievements oracle bridging off-chain game stats on-chain.
+//!
+//! The admin defines achievements (first blood, win streaks, ...) and what each
+//! credits: a reputation unlock on the player reputation contract, season XP on
+//! the season pass, or both. Authorized backend reporters submit completions,
+//! signing each submission with their account; a player completes a given
+//! achievement once, and every reporter is held to a fixed number of submissions
+//! per time window so a compromised reporter cannot flood rewards.
+//!
+//! This contract must be an authorized updater on the player reputation contract
+//! and an XP source on the season pass.
+
+use arenax_events::achievements as events;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol};
+
+/// Highest achievement id; player reputation tracks achievements in a 64-bit mask.
+pub const MAX_ACHIEVEMENT_ID: u32 = 63;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    ReputationContract,
+    SeasonPass,
+    RateLimit,
+    Reporter(Address),
+    ReporterWindow(Address),
+    Achievement(u32),
+    Completion(Address, u32), // (player, achievement_id) -> Completion
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AchievementDef {
+    /// Unlock the achievement on the player reputation contract.
+    pub reputation: bool,
+    /// Season XP granted on completion; zero for none.
+    pub xp: u32,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RateLimit {
+    pub max_per_window: u32,
+    pub window_secs: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReporterWindow {
+    pub window_start: u64,
+    pub count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Completion {
+    pub reporter: Address,
+    pub evidence_hash: BytesN<32>,
+    pub completed_at: u64,
+}
+
+#[contract]
+pub struct AchievementsOracle;
+
+#[contractimpl]
+impl AchievementsOracle {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        reputation_contract: Address,
+        season_pass: Address,
+        max_per_window: u32,
+        window_secs: u64,
+    ) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::ReputationContract, &reputation_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::SeasonPass, &season_pass);
+        Self::store_rate_limit(&env, max_per_window, window_secs);
+    }
+
+    /// Allow or disallow `reporter` to submit completions (admin only).
+    pub fn set_reporter(env: Env, reporter: Address, enabled: bool) {
+        Self::require_admin(&env);
+        let key = DataKey::Reporter(reporter.clone());
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        events::emit_reporter_updated(&env, &reporter, enabled);
+    }
+
+    /// Cap every reporter at `max_per_window` submissions per `window_secs` (admin only).
+    pub fn set_rate_limit(env: Env, max_per_window: u32, window_secs: u64) {
+        Self::require_admin(&env);
+        Self::store_rate_limit(&env, max_per_window, window_secs);
+        events::emit_rate_limit_updated(&env, max_per_window, window_secs);
+    }
+
+    /// Create or update an achievement (admin only). Inactive achievements reject
+    /// new completions.
+    pub fn define_achievement(env: Env, achievement_id: u32, def: AchievementDef) {
+        Self::require_admin(&env);
+        if achievement_id > MAX_ACHIEVEMENT_ID {
+            panic!("invalid achievement id");
+        }
+        if !def.reputation && def.xp == 0 {
+            panic!("achievement grants nothing");
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Achievement(achievement_id), &def);
+        events::emit_achievement_defined(&env, achievement_id, def.reputation, def.xp, def.active);
+    }
+
+    /// Record that `player` completed `achievement_id`, backed by `evidence_hash` of
+    /// the off-chain stats (reporters only), and credit its rewards.
+    pub fn submit(
+        env: Env,
+        reporter: Address,
+        player: Address,
+        achievement_id: u32,
+        evidence_hash: BytesN<32>,
+    ) {
+        reporter.require_auth();
+        if !Self::is_reporter(env.clone(), reporter.clone()) {
+            panic!("not a reporter");
+        }
+        let def = Self::get_achievement(env.clone(), achievement_id);
+        if !def.active {
+            panic!("achievement inactive");
+        }
+        let key = DataKey::Completion(player.clone(), achievement_id);
+        if env.storage().persistent().has(&key) {
+            panic!("already completed");
+        }
+        Self::consume_rate_limit(&env, &reporter);
+
+        let now = env.ledger().timestamp();
+        env.storage().persistent().set(
+            &key,
+            &Completion {
+                reporter: reporter.clone(),
+                evidence_hash: evidence_hash.clone(),
+                completed_at: now,
+            },
+        );
+
+        if def.reputation {
+            env.invoke_contract::<()>(
+                &Self::get_reputation_contract(env.clone()),
+                &Symbol::new(&env, "unlock_achievement"),
+                (player.clone(), achievement_id).into_val(&env),
+            );
+        }
+        if def.xp > 0 {
+            env.invoke_contract::<u32>(
+                &Self::get_season_pass(env.clone()),
+                &Symbol::new(&env, "grant_xp"),
+                (env.current_contract_address(), player.clone(), def.xp).into_val(&env),
+            );
+        }
+
+        events::emit_achievement_completed(
+            &env,
+            achievement_id,
+            &player,
+            &reporter,
+            &evidence_hash,
+            def.xp,
+        );
+    }
+
+    pub fn get_completion(env: Env, player: Address, achievement_id: u32) -> Option<Completion> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Completion(player, achievement_id))
+    }
+
+    pub fn is_completed(env: Env, player: Address, achievement_id: u32) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Completion(player, achievement_id))
+    }
+
+    pub fn get_achievement(env: Env, achievement_id: u32) -> AchievementDef {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Achievement(achievement_id))
+            .expect("achievement not found")
+    }
+
+    /// Submissions `reporter` has left in its current window.
+    pub fn get_remaining_quota(env: Env, reporter: Address) -> u32 {
+        let limit = Self::get_rate_limit(env.clone());
+        let window = Self::current_window(&env, &reporter, &limit);
+        limit.max_per_window.saturating_sub(window.count)
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        env.storage().instance().has(&DataKey::Reporter(reporter))
+    }
+
+    pub fn get_rate_limit(env: Env) -> RateLimit {
+        env.storage()
+            .instance()
+            .get(&DataKey::RateLimit)
+            .expect("not initialized")
+    }
+
+    pub fn get_reputation_contract(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::ReputationContract)
+            .expect("not initialized")
+    }
+
+    pub fn get_season_pass(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::SeasonPass)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn store_rate_limit(env: &Env, max_per_window: u32, window_secs: u64) {
+        if max_per_window == 0 || window_secs == 0 {
+            panic!("invalid rate limit");
+        }
+        env.storage().instance().set(
+            &DataKey::RateLimit,
+            &RateLimit {
+                max_per_window,
+                window_secs,
+            },
+        );
+    }
+
+    /// The reporter's window, restarted if the stored one has elapsed.
+    fn current_window(env: &Env, reporter: &Address, limit: &RateLimit) -> ReporterWindow {
+        let now = env.ledger().timestamp();
+        let stored: Option<ReporterWindow> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ReporterWindow(reporter.clone()));
+        match stored {
+            Some(window) if now < window.window_start + limit.window_secs => window,
+            _ => ReporterWindow {
+                window_start: now,
+                count: 0,
+            },
+        }
+    }
+
+    fn consume_rate_limit(env: &Env, reporter: &Address) {
+        let limit = Self::get_rate_limit(env.clone());
+        let mut window = Self::current_window(env, reporter, &limit);
+        if window.count >= limit.max_per_window {
+            panic!("rate limit exceeded");
+        }
+        window.count += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::ReporterWindow(reporter.clone()), &window);
+    }
+}
+
+#[cfg(test)]
+mod test;
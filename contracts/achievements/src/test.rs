@@ -0,0 +1,209 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    Address, BytesN, Env,
+};
+
+/// Player reputation stand-in counting unlocks per player.
+#[contract]
+pub struct MockReputation;
+
+#[contractimpl]
+impl MockReputation {
+    pub fn unlock_achievement(env: Env, player: Address, _achievement_id: u32) {
+        let key = (symbol_short!("unlocks"), player);
+        let count: u32 = env.storage().instance().get(&key).unwrap_or(0);
+        env.storage().instance().set(&key, &(count + 1));
+    }
+
+    pub fn unlocks(env: Env, player: Address) -> u32 {
+        env.storage()
+            .instance()
+            .get(&(symbol_short!("unlocks"), player))
+            .unwrap_or(0)
+    }
+}
+
+/// Season pass stand-in accumulating XP per player.
+#[contract]
+pub struct MockSeasonPass;
+
+#[contractimpl]
+impl MockSeasonPass {
+    pub fn grant_xp(env: Env, source: Address, player: Address, amount: u32) -> u32 {
+        source.require_auth();
+        let key = (symbol_short!("xp"), player);
+        let total = env.storage().instance().get(&key).unwrap_or(0u32) + amount;
+        env.storage().instance().set(&key, &total);
+        total
+    }
+
+    pub fn xp(env: Env, player: Address) -> u32 {
+        env.storage()
+            .instance()
+            .get(&(symbol_short!("xp"), player))
+            .unwrap_or(0)
+    }
+}
+
+const FIRST_BLOOD: u32 = 1;
+const WIN_STREAK: u32 = 2;
+
+struct Setup<'a> {
+    env: Env,
+    reporter: Address,
+    client: AchievementsOracleClient<'a>,
+    reputation: MockReputationClient<'a>,
+    season_pass: MockSeasonPassClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let reputation = MockReputationClient::new(&env, &env.register(MockReputation, ()));
+    let season_pass = MockSeasonPassClient::new(&env, &env.register(MockSeasonPass, ()));
+
+    let contract_id = env.register(AchievementsOracle, ());
+    let client = AchievementsOracleClient::new(&env, &contract_id);
+    client.initialize(
+        &admin,
+        &reputation.address,
+        &season_pass.address,
+        &3,
+        &3_600,
+    );
+    client.set_reporter(&reporter, &true);
+    client.define_achievement(
+        &FIRST_BLOOD,
+        &AchievementDef {
+            reputation: true,
+            xp: 50,
+            active: true,
+        },
+    );
+    client.define_achievement(
+        &WIN_STREAK,
+        &AchievementDef {
+            reputation: false,
+            xp: 200,
+            active: true,
+        },
+    );
+
+    Setup {
+        env,
+        reporter,
+        client,
+        reputation,
+        season_pass,
+    }
+}
+
+fn evidence(env: &Env) -> BytesN<32> {
+    BytesN::from_array(env, &[9u8; 32])
+}
+
+#[test]
+fn test_completion_credits_reputation_and_xp() {
+    let s = setup();
+    let player = Address::generate(&s.env);
+    s.client
+        .submit(&s.reporter, &player, &FIRST_BLOOD, &evidence(&s.env));
+    s.client
+        .submit(&s.reporter, &player, &WIN_STREAK, &evidence(&s.env));
+
+    assert_eq!(s.reputation.unlocks(&player), 1);
+    assert_eq!(s.season_pass.xp(&player), 250);
+    let completion = s.client.get_completion(&player, &FIRST_BLOOD).unwrap();
+    assert_eq!(completion.reporter, s.reporter);
+    assert_eq!(completion.completed_at, 1_000);
+    assert!(s.client.is_completed(&player, &WIN_STREAK));
+}
+
+#[test]
+fn test_completion_deduplicated_per_player() {
+    let s = setup();
+    let player = Address::generate(&s.env);
+    let other = Address::generate(&s.env);
+    s.client
+        .submit(&s.reporter, &player, &FIRST_BLOOD, &evidence(&s.env));
+    assert!(s
+        .client
+        .try_submit(&s.reporter, &player, &FIRST_BLOOD, &evidence(&s.env))
+        .is_err());
+
+    s.client
+        .submit(&s.reporter, &other, &FIRST_BLOOD, &evidence(&s.env));
+    assert_eq!(s.season_pass.xp(&player), 50);
+    assert_eq!(s.season_pass.xp(&other), 50);
+}
+
+#[test]
+fn test_reporter_rate_limit_resets_each_window() {
+    let s = setup();
+    for _ in 0..3 {
+        let player = Address::generate(&s.env);
+        s.client
+            .submit(&s.reporter, &player, &WIN_STREAK, &evidence(&s.env));
+    }
+    assert_eq!(s.client.get_remaining_quota(&s.reporter), 0);
+    let late = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_submit(&s.reporter, &late, &WIN_STREAK, &evidence(&s.env))
+        .is_err());
+
+    s.env.ledger().set_timestamp(1_000 + 3_600);
+    assert_eq!(s.client.get_remaining_quota(&s.reporter), 3);
+    s.client
+        .submit(&s.reporter, &late, &WIN_STREAK, &evidence(&s.env));
+}
+
+#[test]
+fn test_unauthorized_reporter_and_inactive_achievement_rejected() {
+    let s = setup();
+    let player = Address::generate(&s.env);
+    let outsider = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_submit(&outsider, &player, &FIRST_BLOOD, &evidence(&s.env))
+        .is_err());
+
+    s.client.define_achievement(
+        &FIRST_BLOOD,
+        &AchievementDef {
+            reputation: true,
+            xp: 0,
+            active: false,
+        },
+    );
+    assert!(s
+        .client
+        .try_submit(&s.reporter, &player, &FIRST_BLOOD, &evidence(&s.env))
+        .is_err());
+    assert!(s
+        .client
+        .try_submit(&s.reporter, &player, &7, &evidence(&s.env))
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "invalid achievement id")]
+fn test_achievement_id_bounded() {
+    let s = setup();
+    s.client.define_achievement(
+        &64,
+        &AchievementDef {
+            reputation: true,
+            xp: 0,
+            active: true,
+        },
+    );
+}
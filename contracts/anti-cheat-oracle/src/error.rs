@@ -9,4 +9,22 @@ pub enum AntiCheatError {
     Unauthorized = 3,
     InvalidSeverity = 4,
     ReputationNotSet = 5,
+    DuplicateFlag = 6,
+    InvalidConsensusConfig = 7,
+    ConfirmationNotFound = 8,
+    AppealAlreadyFiled = 9,
+    AppealNotFound = 10,
+    AppealAlreadyResolved = 11,
+    InvalidBondConfig = 12,
+    InvalidAmount = 13,
+    InsufficientBond = 14,
+    OracleSuspended = 15,
+    InvalidEscalationConfig = 16,
+    CommitmentExists = 17,
+    CommitmentNotFound = 18,
+    CommitmentMismatch = 19,
+    AlreadyRevealed = 20,
+    MatchNotFinalized = 21,
+    InvalidPenaltyPolicy = 22,
+    BondLocked = 23,
 }
@@ -6,15 +6,50 @@ mod storage;
 use arenax_events::anti_cheat as events;
 
 use soroban_sdk::auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation};
-use soroban_sdk::{contract, contractimpl, Address, Env, IntoVal, Symbol, Vec};
+use soroban_sdk::{
+    contract, contractimpl, token, vec, xdr::ToXdr, Address, BytesN, Env, IntoVal, String, Symbol,
+    Vec,
+};
 use storage::{AntiCheatConfirmation, DataKey};
 
+pub use storage::{
+    Appeal, AppealStatus, BondConfig, ConsensusConfig, EscalationConfig, FlagCommitment,
+    PendingFlag,
+};
+
 pub use error::AntiCheatError;
 
 /// Severity levels: 1 = low, 2 = medium, 3 = high. Maps to bounded penalties (capped in Reputation Index).
+/// These are the defaults until a policy is set with `set_penalty_policy`.
 const PENALTY_LOW: i128 = 5;
 const PENALTY_MEDIUM: i128 = 15;
 const PENALTY_HIGH: i128 = 30;
+/// `EscrowState::Locked` in the Match Escrow Vault.
+const ESCROW_STATE_LOCKED: u32 = 4;
+/// `DisputeCategory::Standard` in the Dispute Resolution contract.
+const DISPUTE_CATEGORY_STANDARD: u32 = 0;
+/// Flags at this severity automatically dispute the match when dispute hooks are configured.
+const HIGH_SEVERITY: u32 = 3;
+/// Reputation Index per-flag cap, used for validation when no reputation contract is set.
+const DEFAULT_MAX_PENALTY: i128 = 100;
+
+/// Default consensus: a single oracle flag is enough, within a one-hour window.
+const DEFAULT_CONSENSUS_THRESHOLD: u32 = 1;
+const DEFAULT_CONSENSUS_WINDOW: u64 = 3600;
+
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Default escalation: +50% per prior flag in 30 days, up to 3x, ban recommendation at 3 flags.
+const DEFAULT_ESCALATION_WINDOW: u64 = 30 * 24 * 3600;
+const DEFAULT_ESCALATION_STEP_BPS: u32 = 5_000;
+const DEFAULT_MAX_MULTIPLIER_BPS: u32 = 30_000;
+const DEFAULT_BAN_THRESHOLD: u32 = 3;
+/// Upper bound on history entries inspected when counting recent offences.
+const MAX_ESCALATION_SCAN: u32 = 50;
+/// Upper bound on `limit` for `get_flag_history`.
+pub const MAX_HISTORY_PAGE: u32 = 100;
+/// Upper bound on confirmations removed by a single `prune_confirmations` call.
+pub const MAX_PRUNE_PER_CALL: u32 = 100;
 
 #[contract]
 pub struct AntiCheatOracle;
@@ -39,8 +74,9 @@ impl AntiCheatOracle {
             .ok_or(AntiCheatError::NotInitialized)?;
         admin.require_auth();
         env.storage()
-            .instance()
+            .persistent()
             .set(&DataKey::AuthorizedOracle(oracle.clone()), &true);
+        storage::bump(&env, &DataKey::AuthorizedOracle(oracle.clone()));
         Ok(())
     }
 
@@ -53,7 +89,7 @@ impl AntiCheatOracle {
             .ok_or(AntiCheatError::NotInitialized)?;
         admin.require_auth();
         env.storage()
-            .instance()
+            .persistent()
             .remove(&DataKey::AuthorizedOracle(oracle));
         Ok(())
     }
@@ -61,7 +97,7 @@ impl AntiCheatOracle {
     /// Returns true if the given address is an authorized oracle.
     pub fn is_authorized_oracle(env: Env, oracle: Address) -> bool {
         env.storage()
-            .instance()
+            .persistent()
             .get(&DataKey::AuthorizedOracle(oracle))
             .unwrap_or(false)
     }
@@ -80,8 +116,304 @@ impl AntiCheatOracle {
         Ok(())
     }
 
+    /// Configure how many distinct authorized oracles must flag the same (player, match, severity)
+    /// within `window` seconds before the penalty is applied (admin only).
+    pub fn set_consensus_config(
+        env: Env,
+        threshold: u32,
+        window: u64,
+    ) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        if threshold == 0 || window == 0 {
+            return Err(AntiCheatError::InvalidConsensusConfig);
+        }
+        env.storage().instance().set(
+            &DataKey::ConsensusConfig,
+            &ConsensusConfig { threshold, window },
+        );
+        events::emit_consensus_config_updated(&env, threshold, window);
+        Ok(())
+    }
+
+    /// Current consensus configuration.
+    pub fn get_consensus_config(env: Env) -> ConsensusConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::ConsensusConfig)
+            .unwrap_or(ConsensusConfig {
+                threshold: DEFAULT_CONSENSUS_THRESHOLD,
+                window: DEFAULT_CONSENSUS_WINDOW,
+            })
+    }
+
+    /// Oracles that have flagged (player, match_id, severity) but not yet reached consensus.
+    pub fn get_pending_flag(
+        env: Env,
+        player: Address,
+        match_id: u64,
+        severity: u32,
+    ) -> Option<PendingFlag> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PendingFlag(player, match_id, severity))
+    }
+
+    /// Configure oracle bonding (admin only). Once set, oracles must hold at least `min_bond`
+    /// of `token` in this contract to submit flags.
+    pub fn set_bond_config(env: Env, config: BondConfig) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        if config.min_bond < 0 || config.slash_bps > 10_000 || config.max_overturns == 0 {
+            return Err(AntiCheatError::InvalidBondConfig);
+        }
+        env.storage().instance().set(&DataKey::BondConfig, &config);
+        Ok(())
+    }
+
+    pub fn get_bond_config(env: Env) -> Option<BondConfig> {
+        env.storage().instance().get(&DataKey::BondConfig)
+    }
+
+    /// Bond AX tokens as an oracle. Tokens are held by this contract until withdrawn or slashed.
+    pub fn deposit_bond(env: Env, oracle: Address, amount: i128) -> Result<(), AntiCheatError> {
+        oracle.require_auth();
+        if amount <= 0 {
+            return Err(AntiCheatError::InvalidAmount);
+        }
+        let config = Self::get_bond_config(env.clone()).ok_or(AntiCheatError::InvalidBondConfig)?;
+        token::Client::new(&env, &config.token).transfer(
+            &oracle,
+            env.current_contract_address(),
+            &amount,
+        );
+        let bond = Self::get_bond(env.clone(), oracle.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Bond(oracle.clone()), &bond);
+        storage::bump(&env, &DataKey::Bond(oracle.clone()));
+        events::emit_bond_deposited(&env, &oracle, amount, bond);
+        Ok(())
+    }
+
+    /// Withdraw bonded tokens. Dropping below the minimum bond blocks further flag submissions.
+    /// The bond is locked while confirmations the oracle took part in can still be appealed, so
+    /// that it remains available for slashing if one is overturned.
+    pub fn withdraw_bond(env: Env, oracle: Address, amount: i128) -> Result<(), AntiCheatError> {
+        oracle.require_auth();
+        if amount <= 0 {
+            return Err(AntiCheatError::InvalidAmount);
+        }
+        if Self::get_open_confirmations(env.clone(), oracle.clone()) > 0 {
+            return Err(AntiCheatError::BondLocked);
+        }
+        let config = Self::get_bond_config(env.clone()).ok_or(AntiCheatError::InvalidBondConfig)?;
+        let bond = Self::get_bond(env.clone(), oracle.clone());
+        if amount > bond {
+            return Err(AntiCheatError::InsufficientBond);
+        }
+        let remaining = bond - amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Bond(oracle.clone()), &remaining);
+        storage::bump(&env, &DataKey::Bond(oracle.clone()));
+        token::Client::new(&env, &config.token).transfer(
+            &env.current_contract_address(),
+            &oracle,
+            &amount,
+        );
+        events::emit_bond_withdrawn(&env, &oracle, amount, remaining);
+        Ok(())
+    }
+
+    pub fn get_bond(env: Env, oracle: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Bond(oracle))
+            .unwrap_or(0)
+    }
+
+    /// Number of confirmations this oracle took part in that are neither resolved on appeal nor
+    /// pruned. While non-zero its bond cannot be withdrawn.
+    pub fn get_open_confirmations(env: Env, oracle: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenConfirmations(oracle))
+            .unwrap_or(0)
+    }
+
+    /// Number of this oracle's flags overturned on appeal since it was last reinstated.
+    pub fn get_overturn_count(env: Env, oracle: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Overturns(oracle))
+            .unwrap_or(0)
+    }
+
+    pub fn is_oracle_suspended(env: Env, oracle: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Suspended(oracle))
+            .unwrap_or(false)
+    }
+
+    /// Lift an oracle's suspension and reset its overturn count (admin only).
+    pub fn reinstate_oracle(env: Env, oracle: Address) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Suspended(oracle.clone()));
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Overturns(oracle.clone()));
+        events::emit_oracle_reinstated(&env, &oracle);
+        Ok(())
+    }
+
+    /// Set the [low, medium, high] penalties applied per severity (admin only). `game_id = None`
+    /// sets the default policy; a game policy overrides it for matches registered to that game.
+    /// Penalties must be non-decreasing and within the Reputation Index per-flag cap.
+    pub fn set_penalty_policy(
+        env: Env,
+        game_id: Option<Symbol>,
+        penalties: Vec<i128>,
+    ) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+
+        let cap = Self::max_penalty(&env);
+        if penalties.len() != 3 {
+            return Err(AntiCheatError::InvalidPenaltyPolicy);
+        }
+        let mut previous = 0;
+        for penalty in penalties.iter() {
+            if penalty < previous || penalty > cap {
+                return Err(AntiCheatError::InvalidPenaltyPolicy);
+            }
+            previous = penalty;
+        }
+
+        let key = match game_id.clone() {
+            Some(game) => DataKey::GamePenaltyPolicy(game),
+            None => DataKey::PenaltyPolicy,
+        };
+        env.storage().instance().set(&key, &penalties);
+        events::emit_penalty_policy_updated(&env, &game_id, &penalties);
+        Ok(())
+    }
+
+    /// Drop a game-specific policy so its matches fall back to the default (admin only).
+    pub fn remove_penalty_policy(env: Env, game_id: Symbol) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .remove(&DataKey::GamePenaltyPolicy(game_id.clone()));
+        events::emit_penalty_policy_removed(&env, &game_id);
+        Ok(())
+    }
+
+    /// Effective [low, medium, high] penalties for a game (or the default policy).
+    pub fn get_penalty_policy(env: Env, game_id: Option<Symbol>) -> Vec<i128> {
+        if let Some(game) = game_id {
+            if let Some(policy) = env
+                .storage()
+                .instance()
+                .get(&DataKey::GamePenaltyPolicy(game))
+            {
+                return policy;
+            }
+        }
+        env.storage()
+            .instance()
+            .get(&DataKey::PenaltyPolicy)
+            .unwrap_or(vec![&env, PENALTY_LOW, PENALTY_MEDIUM, PENALTY_HIGH])
+    }
+
+    /// Record which game a match belongs to, selecting its penalty policy (match authority only).
+    pub fn set_match_game(env: Env, match_id: u64, game_id: Symbol) -> Result<(), AntiCheatError> {
+        let authority: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchAuthority)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        authority.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::MatchGame(match_id), &game_id);
+        storage::bump(&env, &DataKey::MatchGame(match_id));
+        Ok(())
+    }
+
+    /// Configure the Dispute Resolution and Match Escrow Vault contracts that high-severity flags
+    /// escalate to (admin only). This contract must be set as the vault's anti-cheat oracle.
+    pub fn set_dispute_hooks(
+        env: Env,
+        dispute_contract: Address,
+        escrow_vault: Address,
+    ) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeContract, &dispute_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowVault, &escrow_vault);
+        Ok(())
+    }
+
+    /// Link an oracle match id to the 32-byte match id used by the escrow vault and dispute
+    /// contracts (match authority only).
+    pub fn link_match(
+        env: Env,
+        match_id: u64,
+        match_ref: BytesN<32>,
+    ) -> Result<(), AntiCheatError> {
+        let authority: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchAuthority)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        authority.require_auth();
+        let key = DataKey::MatchRef(match_id);
+        env.storage().persistent().set(&key, &match_ref);
+        storage::bump(&env, &key);
+        Ok(())
+    }
+
+    pub fn get_match_ref(env: Env, match_id: u64) -> Option<BytesN<32>> {
+        env.storage().persistent().get(&DataKey::MatchRef(match_id))
+    }
+
     /// Submit an anti-cheat flag for a player in a match. Only authorized oracle addresses can call.
-    /// Severity: 1 = low, 2 = medium, 3 = high. Penalties are bounded and applied to the Reputation Index.
+    /// Severity: 1 = low, 2 = medium, 3 = high. Penalties are bounded and applied to the Reputation Index
+    /// once the configured number of distinct oracles have flagged the same severity within the window.
     pub fn submit_flag(
         env: Env,
         oracle: Address,
@@ -90,85 +422,827 @@ impl AntiCheatOracle {
         severity: u32,
     ) -> Result<(), AntiCheatError> {
         oracle.require_auth();
+        Self::require_active_oracle(&env, &oracle)?;
+        if severity == 0 || severity > 3 {
+            return Err(AntiCheatError::InvalidSeverity);
+        }
+        Self::record_flag(&env, &oracle, &player, match_id, severity)
+    }
+
+    /// Set the address allowed to mark matches finalized for commit-reveal (admin only).
+    pub fn set_match_authority(env: Env, authority: Address) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&DataKey::MatchAuthority, &authority);
+        Ok(())
+    }
+
+    /// Mark a match finalized, opening the reveal phase for flags committed against it.
+    pub fn mark_match_finalized(env: Env, match_id: u64) -> Result<(), AntiCheatError> {
+        let authority: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchAuthority)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        authority.require_auth();
+        env.storage()
+            .persistent()
+            .set(&DataKey::MatchFinalized(match_id), &true);
+        storage::bump(&env, &DataKey::MatchFinalized(match_id));
+        Ok(())
+    }
+
+    pub fn is_match_finalized(env: Env, match_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchFinalized(match_id))
+            .unwrap_or(false)
+    }
+
+    /// Commitment hash an oracle should submit to `commit_flag` for the given flag.
+    pub fn compute_commitment(
+        env: Env,
+        player: Address,
+        match_id: u64,
+        severity: u32,
+        evidence_ref: String,
+        salt: BytesN<32>,
+    ) -> BytesN<32> {
+        let preimage = (player, match_id, severity, evidence_ref, salt).to_xdr(&env);
+        env.crypto().sha256(&preimage).into()
+    }
+
+    /// Commit to a flag without disclosing the player, match or severity.
+    pub fn commit_flag(
+        env: Env,
+        oracle: Address,
+        commitment: BytesN<32>,
+    ) -> Result<(), AntiCheatError> {
+        oracle.require_auth();
+        Self::require_active_oracle(&env, &oracle)?;
+        let key = DataKey::Commitment(commitment.clone());
+        if env.storage().persistent().has(&key) {
+            return Err(AntiCheatError::CommitmentExists);
+        }
+        let committed_at = env.ledger().timestamp();
+        let record = FlagCommitment {
+            oracle: oracle.clone(),
+            committed_at,
+            player: None,
+            match_id: None,
+            severity: None,
+            evidence_ref: None,
+            revealed_at: None,
+        };
+        env.storage().persistent().set(&key, &record);
+        storage::bump(&env, &key);
+        events::emit_flag_committed(&env, &oracle, &commitment, committed_at);
+        Ok(())
+    }
+
+    /// Reveal a committed flag once its match is finalized. The revealed flag then follows the
+    /// same consensus and penalty path as `submit_flag`.
+    pub fn reveal_flag(
+        env: Env,
+        oracle: Address,
+        player: Address,
+        match_id: u64,
+        severity: u32,
+        evidence_ref: String,
+        salt: BytesN<32>,
+    ) -> Result<(), AntiCheatError> {
+        oracle.require_auth();
+        Self::require_active_oracle(&env, &oracle)?;
+        if severity == 0 || severity > 3 {
+            return Err(AntiCheatError::InvalidSeverity);
+        }
+        let commitment = Self::compute_commitment(
+            env.clone(),
+            player.clone(),
+            match_id,
+            severity,
+            evidence_ref.clone(),
+            salt,
+        );
+        let key = DataKey::Commitment(commitment.clone());
+        let mut record: FlagCommitment = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(AntiCheatError::CommitmentNotFound)?;
+        if record.oracle != oracle {
+            return Err(AntiCheatError::CommitmentMismatch);
+        }
+        if record.revealed_at.is_some() {
+            return Err(AntiCheatError::AlreadyRevealed);
+        }
+        if !Self::is_match_finalized(env.clone(), match_id) {
+            return Err(AntiCheatError::MatchNotFinalized);
+        }
+
+        let now = env.ledger().timestamp();
+        record.player = Some(player.clone());
+        record.match_id = Some(match_id);
+        record.severity = Some(severity);
+        record.evidence_ref = Some(evidence_ref.clone());
+        record.revealed_at = Some(now);
+        env.storage().persistent().set(&key, &record);
+        storage::bump(&env, &key);
+        events::emit_flag_revealed(
+            &env,
+            &oracle,
+            &commitment,
+            &player,
+            match_id,
+            severity,
+            &evidence_ref,
+        );
+
+        Self::record_flag(&env, &oracle, &player, match_id, severity)
+    }
+
+    /// Commitment record, including the revealed flag once disclosed.
+    pub fn get_commitment(env: Env, commitment: BytesN<32>) -> Option<FlagCommitment> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Commitment(commitment))
+    }
+
+    /// Add a referee who may resolve appeals alongside the admin.
+    pub fn add_referee(env: Env, referee: Address) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        let key = DataKey::Referee(referee);
+        env.storage().persistent().set(&key, &true);
+        storage::bump(&env, &key);
+        Ok(())
+    }
+
+    /// Remove a referee.
+    pub fn remove_referee(env: Env, referee: Address) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Referee(referee));
+        Ok(())
+    }
+
+    /// Returns true if the given address is a referee.
+    pub fn is_referee(env: Env, referee: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Referee(referee))
+            .unwrap_or(false)
+    }
+
+    /// Appeal a confirmed flag. One appeal per (player, match_id); the evidence reference points
+    /// to off-chain material supporting the appeal.
+    pub fn appeal_flag(
+        env: Env,
+        player: Address,
+        match_id: u64,
+        evidence_ref: String,
+    ) -> Result<(), AntiCheatError> {
+        player.require_auth();
+        if !env
+            .storage()
+            .persistent()
+            .has(&DataKey::Confirmation(player.clone(), match_id))
+        {
+            return Err(AntiCheatError::ConfirmationNotFound);
+        }
+        let key = DataKey::Appeal(player.clone(), match_id);
+        if env.storage().persistent().has(&key) {
+            return Err(AntiCheatError::AppealAlreadyFiled);
+        }
+        let filed_at = env.ledger().timestamp();
+        let appeal = Appeal {
+            player: player.clone(),
+            match_id,
+            evidence_ref: evidence_ref.clone(),
+            status: AppealStatus::Pending,
+            filed_at,
+            resolver: None,
+            resolved_at: None,
+        };
+        env.storage().persistent().set(&key, &appeal);
+        storage::bump(&env, &key);
+        events::emit_flag_appealed(&env, &player, match_id, &evidence_ref, filed_at);
+        Ok(())
+    }
+
+    /// Resolve a pending appeal (admin or referee). Overturning restores the penalized fair_play
+    /// points on the Reputation Index.
+    pub fn resolve_appeal(
+        env: Env,
+        resolver: Address,
+        player: Address,
+        match_id: u64,
+        overturn: bool,
+    ) -> Result<(), AntiCheatError> {
+        resolver.require_auth();
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        if resolver != admin && !Self::is_referee(env.clone(), resolver.clone()) {
+            return Err(AntiCheatError::Unauthorized);
+        }
+
+        let key = DataKey::Appeal(player.clone(), match_id);
+        let mut appeal: Appeal = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .ok_or(AntiCheatError::AppealNotFound)?;
+        if appeal.status != AppealStatus::Pending {
+            return Err(AntiCheatError::AppealAlreadyResolved);
+        }
+        let confirmation: AntiCheatConfirmation = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Confirmation(player.clone(), match_id))
+            .ok_or(AntiCheatError::ConfirmationNotFound)?;
+
+        let now = env.ledger().timestamp();
+        appeal.status = if overturn {
+            AppealStatus::Overturned
+        } else {
+            AppealStatus::Upheld
+        };
+        appeal.resolver = Some(resolver.clone());
+        appeal.resolved_at = Some(now);
+        env.storage().persistent().set(&key, &appeal);
+        storage::bump(&env, &key);
+
+        if overturn {
+            Self::call_reputation(
+                &env,
+                "restore_anticheat_penalty",
+                &player,
+                match_id,
+                confirmation.penalty_applied,
+            );
+            if let Some(bond_config) = Self::get_bond_config(env.clone()) {
+                for oracle in confirmation.oracles.iter() {
+                    Self::slash_oracle(&env, &bond_config, &oracle);
+                }
+            }
+        }
+        Self::track_open_confirmations(&env, &confirmation.oracles, false);
+
+        events::emit_appeal_resolved(&env, &player, match_id, overturn, &resolver, now);
+        Ok(())
+    }
+
+    /// Get the appeal for a (player, match_id), if any.
+    pub fn get_appeal(env: Env, player: Address, match_id: u64) -> Option<Appeal> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Appeal(player, match_id))
+    }
+
+    /// Get the confirmation for a (player, match_id), if any. For consumers and auditing.
+    pub fn get_confirmation(
+        env: Env,
+        player: Address,
+        match_id: u64,
+    ) -> Option<AntiCheatConfirmation> {
+        let key = DataKey::Confirmation(player, match_id);
+        let confirmation = env.storage().persistent().get(&key);
+        if confirmation.is_some() {
+            storage::bump(&env, &key);
+        }
+        confirmation
+    }
+
+    /// Remove confirmations (and their appeals) older than `retention` seconds for the given
+    /// players (admin only). At most `MAX_PRUNE_PER_CALL` confirmations are removed per call;
+    /// returns the number removed.
+    pub fn prune_confirmations(
+        env: Env,
+        players: Vec<Address>,
+        retention: u64,
+    ) -> Result<u32, AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+
+        let cutoff = env.ledger().timestamp().saturating_sub(retention);
+        let mut pruned = 0u32;
+        for player in players.iter() {
+            let (mut head, total) = Self::flag_bounds(&env, &player);
+            let start = head;
+            while head < total && pruned < MAX_PRUNE_PER_CALL {
+                let entry_key = DataKey::FlagEntry(player.clone(), head);
+                let match_id: u64 = match env.storage().persistent().get(&entry_key) {
+                    Some(id) => id,
+                    None => {
+                        head += 1;
+                        continue;
+                    }
+                };
+                let conf_key = DataKey::Confirmation(player.clone(), match_id);
+                if let Some(conf) = env
+                    .storage()
+                    .persistent()
+                    .get::<DataKey, AntiCheatConfirmation>(&conf_key)
+                {
+                    if conf.timestamp >= cutoff {
+                        break;
+                    }
+                    if !Self::appeal_resolved(&env, &player, match_id) {
+                        Self::track_open_confirmations(&env, &conf.oracles, false);
+                    }
+                }
+                env.storage().persistent().remove(&conf_key);
+                env.storage()
+                    .persistent()
+                    .remove(&DataKey::Appeal(player.clone(), match_id));
+                env.storage().persistent().remove(&entry_key);
+                head += 1;
+                pruned += 1;
+            }
+            if head != start {
+                let head_key = DataKey::FlagHead(player.clone());
+                env.storage().persistent().set(&head_key, &head);
+                storage::bump(&env, &head_key);
+                events::emit_confirmations_pruned(&env, &player, head - start, cutoff);
+            }
+        }
+        Ok(pruned)
+    }
+
+    /// Confirmed flags for a player, oldest first.
+    pub fn get_flag_history(
+        env: Env,
+        player: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<AntiCheatConfirmation> {
+        let (head, total) = Self::flag_bounds(&env, &player);
+        let start = head.saturating_add(offset);
+        let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE)).min(total);
+        let mut history = Vec::new(&env);
+        for i in start..end {
+            if let Some(conf) = Self::history_entry(&env, &player, i) {
+                history.push_back(conf);
+            }
+        }
+        history
+    }
+
+    /// Number of retained (unpruned) confirmed flags for a player.
+    pub fn get_flag_count(env: Env, player: Address) -> u32 {
+        let (head, total) = Self::flag_bounds(&env, &player);
+        total - head
+    }
+
+    /// Configure repeat-offender escalation (admin only).
+    pub fn set_escalation_config(env: Env, config: EscalationConfig) -> Result<(), AntiCheatError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(AntiCheatError::NotInitialized)?;
+        admin.require_auth();
+        if config.window == 0 || config.max_multiplier_bps < 10_000 || config.ban_threshold == 0 {
+            return Err(AntiCheatError::InvalidEscalationConfig);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::EscalationConfig, &config);
+        Ok(())
+    }
+
+    pub fn get_escalation_config(env: Env) -> EscalationConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::EscalationConfig)
+            .unwrap_or(EscalationConfig {
+                window: DEFAULT_ESCALATION_WINDOW,
+                step_bps: DEFAULT_ESCALATION_STEP_BPS,
+                max_multiplier_bps: DEFAULT_MAX_MULTIPLIER_BPS,
+                ban_threshold: DEFAULT_BAN_THRESHOLD,
+            })
+    }
+
+    fn require_active_oracle(env: &Env, oracle: &Address) -> Result<(), AntiCheatError> {
         if !Self::is_authorized_oracle(env.clone(), oracle.clone()) {
             return Err(AntiCheatError::Unauthorized);
         }
+        if Self::is_oracle_suspended(env.clone(), oracle.clone()) {
+            return Err(AntiCheatError::OracleSuspended);
+        }
+        if let Some(bond_config) = Self::get_bond_config(env.clone()) {
+            if Self::get_bond(env.clone(), oracle.clone()) < bond_config.min_bond {
+                return Err(AntiCheatError::InsufficientBond);
+            }
+        }
+        Ok(())
+    }
+
+    /// Count an oracle's flag towards consensus, applying the penalty once the threshold is met.
+    fn record_flag(
+        env: &Env,
+        oracle: &Address,
+        player: &Address,
+        match_id: u64,
+        severity: u32,
+    ) -> Result<(), AntiCheatError> {
+        let config = Self::get_consensus_config(env.clone());
+        let now = env.ledger().timestamp();
+        let key = DataKey::PendingFlag(player.clone(), match_id, severity);
+        let mut pending = match env.storage().persistent().get::<DataKey, PendingFlag>(&key) {
+            // Flags from an expired window no longer count towards consensus.
+            Some(p) if now.saturating_sub(p.first_flagged_at) <= config.window => p,
+            _ => PendingFlag {
+                oracles: Vec::new(env),
+                first_flagged_at: now,
+            },
+        };
+        if pending.oracles.contains(oracle) {
+            return Err(AntiCheatError::DuplicateFlag);
+        }
+        pending.oracles.push_back(oracle.clone());
+        let votes = pending.oracles.len();
+
+        if votes < config.threshold {
+            env.storage().persistent().set(&key, &pending);
+            storage::bump(env, &key);
+            events::emit_flag_pending(
+                env,
+                player,
+                match_id,
+                severity,
+                oracle,
+                votes,
+                config.threshold,
+            );
+            return Ok(());
+        }
+
+        env.storage().persistent().remove(&key);
+        if config.threshold > 1 {
+            events::emit_consensus_reached(env, player, match_id, severity, votes);
+        }
+        Self::apply_flag(
+            env,
+            oracle,
+            &pending.oracles,
+            player,
+            match_id,
+            severity,
+            now,
+        )
+    }
+
+    /// Record the confirmation and forward the penalty to the Reputation Index, if configured.
+    /// Re-confirming a match only charges the amount by which the new penalty exceeds the one
+    /// already applied, so the confirmation always holds the total an appeal restores.
+    fn apply_flag(
+        env: &Env,
+        oracle: &Address,
+        oracles: &Vec<Address>,
+        player: &Address,
+        match_id: u64,
+        severity: u32,
+        timestamp: u64,
+    ) -> Result<(), AntiCheatError> {
         if severity == 0 || severity > 3 {
             return Err(AntiCheatError::InvalidSeverity);
         }
+        let game_id: Option<Symbol> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::MatchGame(match_id));
+        let base_penalty = Self::get_penalty_policy(env.clone(), game_id)
+            .get(severity - 1)
+            .unwrap();
 
-        let penalty = match severity {
-            1 => PENALTY_LOW,
-            2 => PENALTY_MEDIUM,
-            3 => PENALTY_HIGH,
-            _ => return Err(AntiCheatError::InvalidSeverity),
+        let escalation = Self::get_escalation_config(env.clone());
+        let key = DataKey::Confirmation(player.clone(), match_id);
+        let previous: Option<AntiCheatConfirmation> = env.storage().persistent().get(&key);
+        let is_new = previous.is_none();
+        let prior = Self::recent_offences(env, player, &escalation, timestamp, match_id);
+        let multiplier_bps = (10_000u32 + prior.saturating_mul(escalation.step_bps))
+            .min(escalation.max_multiplier_bps) as i128;
+        let penalty = base_penalty * multiplier_bps / BPS_DENOMINATOR;
+        // An overturned confirmation's penalty was already restored.
+        let already_applied = match &previous {
+            Some(p) if !Self::appeal_overturned(env, player, match_id) => p.penalty_applied,
+            _ => 0,
         };
+        let total_penalty = penalty.max(already_applied);
+        let charged = total_penalty - already_applied;
 
-        let timestamp = env.ledger().timestamp();
         let confirmation = AntiCheatConfirmation {
             player: player.clone(),
             match_id,
             severity,
-            penalty_applied: penalty,
+            penalty_applied: total_penalty,
             timestamp,
             oracle: oracle.clone(),
+            oracles: oracles.clone(),
         };
-        env.storage().instance().set(
-            &DataKey::Confirmation(player.clone(), match_id),
-            &confirmation,
-        );
+        env.storage().persistent().set(&key, &confirmation);
+        storage::bump(env, &key);
+        // A confirmation whose appeal was already resolved cannot be appealed again.
+        if !Self::appeal_resolved(env, player, match_id) {
+            if let Some(previous) = previous {
+                Self::track_open_confirmations(env, &previous.oracles, false);
+            }
+            Self::track_open_confirmations(env, oracles, true);
+        }
+
+        if is_new {
+            let count_key = DataKey::FlagCount(player.clone());
+            let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+            env.storage()
+                .persistent()
+                .set(&DataKey::FlagEntry(player.clone(), count), &match_id);
+            storage::bump(env, &DataKey::FlagEntry(player.clone(), count));
+            env.storage().persistent().set(&count_key, &(count + 1));
+            storage::bump(env, &count_key);
+        }
+
+        let offences = prior + 1;
+        if offences >= escalation.ban_threshold {
+            events::emit_temp_ban_recommended(env, player, offences, escalation.window);
+        }
 
-        if let Some(reputation_addr) = env
+        if charged > 0 {
+            Self::call_reputation(env, "apply_anticheat_penalty", player, match_id, charged);
+        }
+
+        events::emit_anticheat_flag(env, player, match_id, severity, charged, oracle, timestamp);
+
+        if severity == HIGH_SEVERITY {
+            Self::escalate_to_dispute(env, player, match_id);
+        }
+        Ok(())
+    }
+
+    /// Cross-call the Reputation Index (if configured), authorizing this contract as the caller.
+    fn call_reputation(env: &Env, fn_name: &str, player: &Address, match_id: u64, amount: i128) {
+        let reputation_addr = match env
             .storage()
             .instance()
             .get::<DataKey, Address>(&DataKey::ReputationContract)
         {
-            let mut args = Vec::new(&env);
-            args.push_back(env.current_contract_address().into_val(&env));
-            args.push_back(player.clone().into_val(&env));
-            args.push_back(match_id.into_val(&env));
-            args.push_back(penalty.into_val(&env));
-            let context = ContractContext {
-                contract: reputation_addr.clone(),
-                fn_name: Symbol::new(&env, "apply_anticheat_penalty"),
-                args,
+            Some(addr) => addr,
+            None => return,
+        };
+        let fn_name = Symbol::new(env, fn_name);
+        let args: Vec<soroban_sdk::Val> = (
+            env.current_contract_address(),
+            player.clone(),
+            match_id,
+            amount,
+        )
+            .into_val(env);
+        let context = ContractContext {
+            contract: reputation_addr.clone(),
+            fn_name: fn_name.clone(),
+            args: args.clone(),
+        };
+        let sub_invocations: Vec<InvokerContractAuthEntry> = Vec::new(env);
+        let mut auth_entries = Vec::new(env);
+        auth_entries.push_back(InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context,
+            sub_invocations,
+        }));
+        env.authorize_as_current_contract(auth_entries);
+        let _: () = env.invoke_contract(&reputation_addr, &fn_name, args);
+    }
+
+    fn appeal_resolved(env: &Env, player: &Address, match_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Appeal>(&DataKey::Appeal(player.clone(), match_id))
+            .map(|a| a.status != AppealStatus::Pending)
+            .unwrap_or(false)
+    }
+
+    fn appeal_overturned(env: &Env, player: &Address, match_id: u64) -> bool {
+        env.storage()
+            .persistent()
+            .get::<DataKey, Appeal>(&DataKey::Appeal(player.clone(), match_id))
+            .map(|a| a.status == AppealStatus::Overturned)
+            .unwrap_or(false)
+    }
+
+    /// Count a confirmation as appealable (`opened`) or no longer appealable for its oracles.
+    fn track_open_confirmations(env: &Env, oracles: &Vec<Address>, opened: bool) {
+        for oracle in oracles.iter() {
+            let open = Self::get_open_confirmations(env.clone(), oracle.clone());
+            // Saturating: confirmations recorded before tracking began were never counted.
+            let open = if opened {
+                open + 1
+            } else {
+                open.saturating_sub(1)
             };
-            let sub_invocations: Vec<InvokerContractAuthEntry> = Vec::new(&env);
-            let mut auth_entries = Vec::new(&env);
-            auth_entries.push_back(InvokerContractAuthEntry::Contract(SubContractInvocation {
-                context,
-                sub_invocations,
-            }));
-            env.authorize_as_current_contract(auth_entries);
-            let args = (
-                env.current_contract_address(),
-                player.clone(),
-                match_id,
-                penalty,
-            )
-                .into_val(&env);
-            let _: () = env.invoke_contract(
-                &reputation_addr,
-                &Symbol::new(&env, "apply_anticheat_penalty"),
-                args,
+            let key = DataKey::OpenConfirmations(oracle);
+            env.storage().persistent().set(&key, &open);
+            storage::bump(env, &key);
+        }
+    }
+
+    /// Slash part of an oracle's bond to the treasury and suspend it after too many overturns.
+    fn slash_oracle(env: &Env, config: &BondConfig, oracle: &Address) {
+        let bond = Self::get_bond(env.clone(), oracle.clone());
+        let slashed = bond * config.slash_bps as i128 / BPS_DENOMINATOR;
+        if slashed > 0 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Bond(oracle.clone()), &(bond - slashed));
+            storage::bump(env, &DataKey::Bond(oracle.clone()));
+            token::Client::new(env, &config.token).transfer(
+                &env.current_contract_address(),
+                &config.treasury,
+                &slashed,
             );
         }
 
-        events::emit_anticheat_flag(
-            &env, &player, match_id, severity, penalty, &oracle, timestamp,
-        );
-        Ok(())
+        let overturns = Self::get_overturn_count(env.clone(), oracle.clone()) + 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Overturns(oracle.clone()), &overturns);
+        storage::bump(env, &DataKey::Overturns(oracle.clone()));
+        events::emit_oracle_slashed(env, oracle, slashed, overturns);
+
+        if overturns >= config.max_overturns {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Suspended(oracle.clone()), &true);
+            storage::bump(env, &DataKey::Suspended(oracle.clone()));
+            events::emit_oracle_suspended(env, oracle, overturns);
+        }
     }
 
-    /// Get the confirmation for a (player, match_id), if any. For consumers and auditing.
-    pub fn get_confirmation(
-        env: Env,
-        player: Address,
-        match_id: u64,
-    ) -> Option<AntiCheatConfirmation> {
+    /// (index of the oldest retained history entry, total entries ever recorded) for a player.
+    fn flag_bounds(env: &Env, player: &Address) -> (u32, u32) {
+        let head = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FlagHead(player.clone()))
+            .unwrap_or(0);
+        let total = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FlagCount(player.clone()))
+            .unwrap_or(0);
+        (head, total)
+    }
+
+    fn history_entry(env: &Env, player: &Address, index: u32) -> Option<AntiCheatConfirmation> {
+        let match_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::FlagEntry(player.clone(), index))?;
         env.storage()
+            .persistent()
+            .get(&DataKey::Confirmation(player.clone(), match_id))
+    }
+
+    /// Confirmed flags for `player` within the escalation window, excluding `match_id` itself and
+    /// flags overturned on appeal. Scans newest-first and stops at the window edge.
+    fn recent_offences(
+        env: &Env,
+        player: &Address,
+        config: &EscalationConfig,
+        now: u64,
+        match_id: u64,
+    ) -> u32 {
+        let (head, total) = Self::flag_bounds(env, player);
+        let cutoff = now.saturating_sub(config.window);
+        let mut offences = 0;
+        let mut i = total;
+        while i > head && total - i < MAX_ESCALATION_SCAN {
+            i -= 1;
+            let conf = match Self::history_entry(env, player, i) {
+                Some(conf) => conf,
+                None => continue,
+            };
+            if conf.timestamp < cutoff {
+                break;
+            }
+            if conf.match_id == match_id {
+                continue;
+            }
+            let overturned = env
+                .storage()
+                .persistent()
+                .get::<DataKey, Appeal>(&DataKey::Appeal(player.clone(), conf.match_id))
+                .map(|a| a.status == AppealStatus::Overturned)
+                .unwrap_or(false);
+            if !overturned {
+                offences += 1;
+            }
+        }
+        offences
+    }
+
+    /// Per-flag cap enforced by the Reputation Index, or its default when none is configured.
+    fn max_penalty(env: &Env) -> i128 {
+        match env
+            .storage()
             .instance()
-            .get(&DataKey::Confirmation(player, match_id))
+            .get::<DataKey, Address>(&DataKey::ReputationContract)
+        {
+            Some(reputation_addr) => env.invoke_contract(
+                &reputation_addr,
+                &Symbol::new(env, "get_max_penalty_per_flag"),
+                Vec::new(env),
+            ),
+            None => DEFAULT_MAX_PENALTY,
+        }
+    }
+
+    /// Hold a locked escrow and open a dispute for a match with a high-severity confirmation.
+    /// Skipped when hooks are not configured, the match is not linked, the escrow is not locked,
+    /// or a dispute is already open.
+    fn escalate_to_dispute(env: &Env, player: &Address, match_id: u64) {
+        let (dispute_contract, escrow_vault) = match (
+            env.storage()
+                .instance()
+                .get::<DataKey, Address>(&DataKey::DisputeContract),
+            env.storage()
+                .instance()
+                .get::<DataKey, Address>(&DataKey::EscrowVault),
+        ) {
+            (Some(d), Some(e)) => (d, e),
+            _ => return,
+        };
+        let match_ref = match Self::get_match_ref(env.clone(), match_id) {
+            Some(r) => r,
+            None => return,
+        };
+
+        let has_escrow: bool = env.invoke_contract(
+            &escrow_vault,
+            &Symbol::new(env, "escrow_exists"),
+            (match_ref.clone(),).into_val(env),
+        );
+        if !has_escrow {
+            return;
+        }
+        let escrow_state: u32 = env.invoke_contract(
+            &escrow_vault,
+            &Symbol::new(env, "get_escrow_state"),
+            (match_ref.clone(),).into_val(env),
+        );
+        if escrow_state != ESCROW_STATE_LOCKED {
+            return;
+        }
+        let _: () = env.invoke_contract(
+            &escrow_vault,
+            &Symbol::new(env, "report_cheating"),
+            (env.current_contract_address(), match_ref.clone()).into_val(env),
+        );
+
+        let already_disputed: bool = env.invoke_contract(
+            &dispute_contract,
+            &Symbol::new(env, "is_disputed"),
+            (match_ref.clone(),).into_val(env),
+        );
+        if !already_disputed {
+            let reason = String::from_str(env, "anti-cheat: high severity flag");
+            let evidence_ref = String::from_str(env, "anti-cheat-oracle");
+            let _: () = env.invoke_contract(
+                &dispute_contract,
+                &Symbol::new(env, "open_dispute"),
+                (
+                    match_ref.clone(),
+                    env.current_contract_address(),
+                    DISPUTE_CATEGORY_STANDARD,
+                    reason,
+                    evidence_ref,
+                )
+                    .into_val(env),
+            );
+        }
+
+        events::emit_dispute_escalated(env, player, match_id, &match_ref);
     }
 }
 
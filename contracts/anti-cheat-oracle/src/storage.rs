@@ -1,5 +1,11 @@
-use soroban_sdk::{contracttype, Address};
+use soroban_sdk::{contracttype, Address, BytesN, Env, String, Symbol, Vec};
 
+/// Persistent entries are bumped to `PERSISTENT_BUMP_AMOUNT` ledgers whenever they are written or
+/// read through a getter and their TTL has fallen below `PERSISTENT_LIFETIME_THRESHOLD`.
+pub const PERSISTENT_LIFETIME_THRESHOLD: u32 = 100_000;
+pub const PERSISTENT_BUMP_AMOUNT: u32 = 500_000;
+
+/// Config lives in instance storage; per-player and per-oracle entries live in persistent storage.
 #[derive(Clone)]
 #[contracttype]
 pub enum DataKey {
@@ -7,6 +13,82 @@ pub enum DataKey {
     AuthorizedOracle(Address),
     Confirmation(Address, u64), // (player, match_id) -> AntiCheatConfirmation
     ReputationContract,
+    ConsensusConfig,
+    PendingFlag(Address, u64, u32), // (player, match_id, severity) -> PendingFlag
+    Referee(Address),
+    Appeal(Address, u64), // (player, match_id) -> Appeal
+    BondConfig,
+    Bond(Address),           // oracle -> bonded AX amount
+    Overturns(Address),      // oracle -> number of flags overturned on appeal
+    Suspended(Address),      // oracle -> bool
+    FlagCount(Address),      // player -> number of confirmed flags ever recorded
+    FlagHead(Address),       // player -> index of the oldest unpruned history entry
+    FlagEntry(Address, u32), // (player, n) -> match_id of the n-th confirmation
+    EscalationConfig,
+    MatchAuthority,
+    MatchFinalized(u64),
+    Commitment(BytesN<32>),    // commitment hash -> FlagCommitment
+    PenaltyPolicy,             // default [low, medium, high] penalties
+    GamePenaltyPolicy(Symbol), // game_id -> [low, medium, high] penalties
+    MatchGame(u64),            // match_id -> game_id
+    DisputeContract,
+    EscrowVault,
+    MatchRef(u64), // match_id -> BytesN<32> id used by escrow and dispute contracts
+    OpenConfirmations(Address), // oracle -> confirmations it formed that can still be appealed
+}
+
+/// A hidden flag: the oracle commits to `sha256(xdr((player, match_id, severity, evidence_ref, salt)))`
+/// and reveals the preimage once the match is finalized.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FlagCommitment {
+    pub oracle: Address,
+    pub committed_at: u64,
+    pub player: Option<Address>,
+    pub match_id: Option<u64>,
+    pub severity: Option<u32>,
+    pub evidence_ref: Option<String>,
+    pub revealed_at: Option<u64>,
+}
+
+/// Repeat-offender policy. Each prior confirmed flag within `window` seconds adds `step_bps` to the
+/// penalty multiplier (capped at `max_multiplier_bps`); reaching `ban_threshold` flags within the
+/// window emits a temporary-ban recommendation.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscalationConfig {
+    pub window: u64,
+    pub step_bps: u32,
+    pub max_multiplier_bps: u32,
+    pub ban_threshold: u32,
+}
+
+/// Oracle bonding parameters. `slash_bps` is the share of an oracle's bond sent to the treasury
+/// per overturned flag; `max_overturns` overturns suspend the oracle.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondConfig {
+    pub token: Address,
+    pub treasury: Address,
+    pub min_bond: i128,
+    pub slash_bps: u32,
+    pub max_overturns: u32,
+}
+
+/// Number of distinct oracles that must agree, and the window (seconds) in which they must do so.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConsensusConfig {
+    pub threshold: u32,
+    pub window: u64,
+}
+
+/// Flags collected for a (player, match, severity) that have not yet reached consensus.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingFlag {
+    pub oracles: Vec<Address>,
+    pub first_flagged_at: u64,
 }
 
 /// Stored confirmation for an anti-cheat flag (queryable and auditable).
@@ -19,4 +101,36 @@ pub struct AntiCheatConfirmation {
     pub penalty_applied: i128,
     pub timestamp: u64,
     pub oracle: Address,
+    /// All oracles whose flags formed the consensus for this confirmation.
+    pub oracles: Vec<Address>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum AppealStatus {
+    Pending,
+    Upheld,
+    Overturned,
+}
+
+/// A player's appeal against a confirmed flag.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Appeal {
+    pub player: Address,
+    pub match_id: u64,
+    pub evidence_ref: String,
+    pub status: AppealStatus,
+    pub filed_at: u64,
+    pub resolver: Option<Address>,
+    pub resolved_at: Option<u64>,
+}
+
+/// Extend the TTL of a persistent entry.
+pub fn bump(env: &Env, key: &DataKey) {
+    env.storage().persistent().extend_ttl(
+        key,
+        PERSISTENT_LIFETIME_THRESHOLD,
+        PERSISTENT_BUMP_AMOUNT,
+    );
 }
@@ -2,7 +2,93 @@
 #![cfg(test)]
 
 use super::*;
-use soroban_sdk::{testutils::Address as _, Address, Env};
+use soroban_sdk::{
+    contract, contractimpl,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String,
+};
+
+/// Escrow vault stand-in with every escrow locked; records reported matches.
+#[contract]
+pub struct MockEscrowVault;
+
+#[contractimpl]
+impl MockEscrowVault {
+    pub fn escrow_exists(_env: Env, _match_id: BytesN<32>) -> bool {
+        true
+    }
+
+    pub fn get_escrow_state(_env: Env, _match_id: BytesN<32>) -> u32 {
+        4
+    }
+
+    pub fn report_cheating(env: Env, _oracle: Address, match_id: BytesN<32>) {
+        env.storage().instance().set(&match_id, &true);
+    }
+
+    pub fn is_reported(env: Env, match_id: BytesN<32>) -> bool {
+        env.storage().instance().get(&match_id).unwrap_or(false)
+    }
+}
+
+#[contract]
+pub struct MockDisputeResolution;
+
+#[contractimpl]
+impl MockDisputeResolution {
+    pub fn is_disputed(env: Env, match_id: BytesN<32>) -> bool {
+        env.storage().instance().has(&match_id)
+    }
+
+    pub fn open_dispute(
+        env: Env,
+        match_id: BytesN<32>,
+        _opener: Address,
+        _category: u32,
+        reason: String,
+        _evidence_ref: String,
+    ) {
+        env.storage().instance().set(&match_id, &reason);
+    }
+}
+
+/// Minimal Reputation Index stand-in tracking a net fair_play delta per player.
+#[contract]
+pub struct MockReputation;
+
+#[contractimpl]
+impl MockReputation {
+    pub fn apply_anticheat_penalty(
+        env: Env,
+        _oracle: Address,
+        player: Address,
+        _match_id: u64,
+        penalty: i128,
+    ) {
+        let current: i128 = env.storage().instance().get(&player).unwrap_or(0);
+        env.storage().instance().set(&player, &(current - penalty));
+    }
+
+    pub fn restore_anticheat_penalty(
+        env: Env,
+        _oracle: Address,
+        player: Address,
+        _match_id: u64,
+        amount: i128,
+    ) {
+        let current: i128 = env.storage().instance().get(&player).unwrap_or(0);
+        env.storage().instance().set(&player, &(current + amount));
+    }
+
+    pub fn get_max_penalty_per_flag(_env: Env) -> i128 {
+        40
+    }
+
+    pub fn delta(env: Env, player: Address) -> i128 {
+        env.storage().instance().get(&player).unwrap_or(0)
+    }
+}
 
 #[test]
 fn test_initialize_and_add_oracle() {
@@ -96,3 +182,617 @@ fn test_submit_flag_and_get_confirmation() {
 // Integration with Reputation Index is tested by calling submit_flag with
 // set_reputation_contract set: the contract uses invoke_contract to call
 // apply_anticheat_penalty. See reputation-index tests for penalty capping and no underflow.
+
+#[test]
+fn test_consensus_requires_distinct_oracles() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle_a = Address::generate(&env);
+    let oracle_b = Address::generate(&env);
+    let player = Address::generate(&env);
+    let match_id = 7u64;
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle_a);
+    client.add_authorized_oracle(&oracle_b);
+    client.set_consensus_config(&2u32, &600u64);
+
+    client.submit_flag(&oracle_a, &player, &match_id, &3u32);
+    assert!(client.get_confirmation(&player, &match_id).is_none());
+    let pending = client.get_pending_flag(&player, &match_id, &3u32).unwrap();
+    assert_eq!(pending.oracles.len(), 1);
+
+    assert_eq!(
+        client.try_submit_flag(&oracle_a, &player, &match_id, &3u32),
+        Err(Ok(AntiCheatError::DuplicateFlag))
+    );
+
+    // A different severity class is tracked separately.
+    client.submit_flag(&oracle_b, &player, &match_id, &2u32);
+    assert!(client.get_confirmation(&player, &match_id).is_none());
+
+    client.submit_flag(&oracle_b, &player, &match_id, &3u32);
+    let conf = client.get_confirmation(&player, &match_id).unwrap();
+    assert_eq!(conf.severity, 3);
+    assert_eq!(conf.penalty_applied, 30);
+    assert!(client.get_pending_flag(&player, &match_id, &3u32).is_none());
+}
+
+#[test]
+fn test_consensus_window_expires() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle_a = Address::generate(&env);
+    let oracle_b = Address::generate(&env);
+    let player = Address::generate(&env);
+    let match_id = 8u64;
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle_a);
+    client.add_authorized_oracle(&oracle_b);
+    client.set_consensus_config(&2u32, &600u64);
+
+    env.ledger().set_timestamp(1_000);
+    client.submit_flag(&oracle_a, &player, &match_id, &1u32);
+
+    env.ledger().set_timestamp(1_601);
+    client.submit_flag(&oracle_b, &player, &match_id, &1u32);
+    assert!(client.get_confirmation(&player, &match_id).is_none());
+    let pending = client.get_pending_flag(&player, &match_id, &1u32).unwrap();
+    assert_eq!(pending.oracles.len(), 1);
+    assert_eq!(pending.first_flagged_at, 1_601);
+
+    assert_eq!(
+        client.try_set_consensus_config(&0u32, &600u64),
+        Err(Ok(AntiCheatError::InvalidConsensusConfig))
+    );
+}
+
+#[test]
+fn test_appeal_overturn_restores_reputation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let referee = Address::generate(&env);
+    let player = Address::generate(&env);
+    let match_id = 11u64;
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    let reputation_id = env.register(MockReputation, ());
+    let reputation = MockReputationClient::new(&env, &reputation_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+    client.add_referee(&referee);
+    client.set_reputation_contract(&reputation_id);
+
+    client.submit_flag(&oracle, &player, &match_id, &2u32);
+    assert_eq!(reputation.delta(&player), -15);
+
+    let evidence = String::from_str(&env, "ipfs://appeal");
+    client.appeal_flag(&player, &match_id, &evidence);
+    assert_eq!(
+        client.try_appeal_flag(&player, &match_id, &evidence),
+        Err(Ok(AntiCheatError::AppealAlreadyFiled))
+    );
+    let appeal = client.get_appeal(&player, &match_id).unwrap();
+    assert_eq!(appeal.status, AppealStatus::Pending);
+
+    client.resolve_appeal(&referee, &player, &match_id, &true);
+    let appeal = client.get_appeal(&player, &match_id).unwrap();
+    assert_eq!(appeal.status, AppealStatus::Overturned);
+    assert_eq!(appeal.resolver, Some(referee.clone()));
+    assert_eq!(reputation.delta(&player), 0);
+
+    assert_eq!(
+        client.try_resolve_appeal(&admin, &player, &match_id, &false),
+        Err(Ok(AntiCheatError::AppealAlreadyResolved))
+    );
+}
+
+#[test]
+fn test_reconfirmation_charges_only_the_difference() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let player = Address::generate(&env);
+    let match_id = 12u64;
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    let reputation_id = env.register(MockReputation, ());
+    let reputation = MockReputationClient::new(&env, &reputation_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+    client.set_reputation_contract(&reputation_id);
+
+    client.submit_flag(&oracle, &player, &match_id, &2u32);
+    client.submit_flag(&oracle, &player, &match_id, &2u32);
+    assert_eq!(reputation.delta(&player), -15);
+
+    // A higher severity charges only the increase; a lower one charges nothing.
+    client.submit_flag(&oracle, &player, &match_id, &3u32);
+    assert_eq!(reputation.delta(&player), -30);
+    client.submit_flag(&oracle, &player, &match_id, &1u32);
+    assert_eq!(reputation.delta(&player), -30);
+    assert_eq!(
+        client
+            .get_confirmation(&player, &match_id)
+            .unwrap()
+            .penalty_applied,
+        30
+    );
+    assert_eq!(client.get_flag_count(&player), 1);
+
+    // Overturning restores everything that was charged.
+    client.appeal_flag(&player, &match_id, &String::from_str(&env, "ipfs://appeal"));
+    client.resolve_appeal(&admin, &player, &match_id, &true);
+    assert_eq!(reputation.delta(&player), 0);
+}
+
+#[test]
+fn test_appeal_requires_confirmation_and_resolver() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let player = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+
+    let evidence = String::from_str(&env, "ipfs://appeal");
+    assert_eq!(
+        client.try_appeal_flag(&player, &1u64, &evidence),
+        Err(Ok(AntiCheatError::ConfirmationNotFound))
+    );
+
+    client.submit_flag(&oracle, &player, &1u64, &1u32);
+    client.appeal_flag(&player, &1u64, &evidence);
+    assert_eq!(
+        client.try_resolve_appeal(&stranger, &player, &1u64, &true),
+        Err(Ok(AntiCheatError::Unauthorized))
+    );
+
+    client.resolve_appeal(&admin, &player, &1u64, &false);
+    let appeal = client.get_appeal(&player, &1u64).unwrap();
+    assert_eq!(appeal.status, AppealStatus::Upheld);
+}
+
+fn setup_bonded(
+    env: &Env,
+) -> (
+    AntiCheatOracleClient<'_>,
+    TokenClient<'_>,
+    Address,
+    Address,
+    Address,
+) {
+    let admin = Address::generate(env);
+    let oracle = Address::generate(env);
+    let treasury = Address::generate(env);
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(env, &contract_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    StellarAssetClient::new(env, &token).mint(&oracle, &1_000);
+    client.set_bond_config(&BondConfig {
+        token: token.clone(),
+        treasury: treasury.clone(),
+        min_bond: 500,
+        slash_bps: 2_000,
+        max_overturns: 2,
+    });
+    (
+        client,
+        TokenClient::new(env, &token),
+        admin,
+        oracle,
+        treasury,
+    )
+}
+
+#[test]
+fn test_bond_required_to_flag() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, admin, oracle, _treasury) = setup_bonded(&env);
+    let player = Address::generate(&env);
+
+    assert_eq!(
+        client.try_submit_flag(&oracle, &player, &1u64, &1u32),
+        Err(Ok(AntiCheatError::InsufficientBond))
+    );
+
+    client.deposit_bond(&oracle, &600);
+    assert_eq!(client.get_bond(&oracle), 600);
+    assert_eq!(token.balance(&oracle), 400);
+    client.submit_flag(&oracle, &player, &1u64, &1u32);
+
+    // Locked until the confirmation can no longer be appealed.
+    assert_eq!(
+        client.try_withdraw_bond(&oracle, &200),
+        Err(Ok(AntiCheatError::BondLocked))
+    );
+    client.appeal_flag(&player, &1u64, &String::from_str(&env, "ipfs://appeal"));
+    client.resolve_appeal(&admin, &player, &1u64, &false);
+    assert_eq!(client.get_open_confirmations(&oracle), 0);
+
+    client.withdraw_bond(&oracle, &200);
+    assert_eq!(client.get_bond(&oracle), 400);
+    assert_eq!(token.balance(&oracle), 600);
+    assert_eq!(
+        client.try_withdraw_bond(&oracle, &401),
+        Err(Ok(AntiCheatError::InsufficientBond))
+    );
+    assert_eq!(
+        client.try_submit_flag(&oracle, &player, &2u64, &1u32),
+        Err(Ok(AntiCheatError::InsufficientBond))
+    );
+}
+
+#[test]
+fn test_overturned_flags_slash_and_suspend() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, admin, oracle, treasury) = setup_bonded(&env);
+    let player = Address::generate(&env);
+    let evidence = String::from_str(&env, "ipfs://appeal");
+
+    client.deposit_bond(&oracle, &1_000);
+
+    client.submit_flag(&oracle, &player, &1u64, &2u32);
+    client.appeal_flag(&player, &1u64, &evidence);
+    client.resolve_appeal(&admin, &player, &1u64, &true);
+    assert_eq!(client.get_bond(&oracle), 800);
+    assert_eq!(token.balance(&treasury), 200);
+    assert_eq!(client.get_overturn_count(&oracle), 1);
+    assert!(!client.is_oracle_suspended(&oracle));
+
+    // Upheld appeals do not slash.
+    client.submit_flag(&oracle, &player, &2u64, &2u32);
+    client.appeal_flag(&player, &2u64, &evidence);
+    client.resolve_appeal(&admin, &player, &2u64, &false);
+    assert_eq!(client.get_bond(&oracle), 800);
+
+    client.submit_flag(&oracle, &player, &3u64, &2u32);
+    client.appeal_flag(&player, &3u64, &evidence);
+    client.resolve_appeal(&admin, &player, &3u64, &true);
+    assert_eq!(client.get_bond(&oracle), 640);
+    assert_eq!(token.balance(&treasury), 360);
+    assert!(client.is_oracle_suspended(&oracle));
+    assert_eq!(
+        client.try_submit_flag(&oracle, &player, &4u64, &2u32),
+        Err(Ok(AntiCheatError::OracleSuspended))
+    );
+
+    client.reinstate_oracle(&oracle);
+    assert_eq!(client.get_overturn_count(&oracle), 0);
+    client.submit_flag(&oracle, &player, &4u64, &2u32);
+}
+
+#[test]
+fn test_withdraw_before_overturn_still_slashed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, token, admin, oracle, treasury) = setup_bonded(&env);
+    let player = Address::generate(&env);
+
+    client.deposit_bond(&oracle, &1_000);
+    client.submit_flag(&oracle, &player, &1u64, &2u32);
+    assert_eq!(client.get_open_confirmations(&oracle), 1);
+
+    // The oracle tries to pull its bond out before the player appeals.
+    assert_eq!(
+        client.try_withdraw_bond(&oracle, &1_000),
+        Err(Ok(AntiCheatError::BondLocked))
+    );
+    client.appeal_flag(&player, &1u64, &String::from_str(&env, "ipfs://appeal"));
+    assert_eq!(
+        client.try_withdraw_bond(&oracle, &1_000),
+        Err(Ok(AntiCheatError::BondLocked))
+    );
+
+    client.resolve_appeal(&admin, &player, &1u64, &true);
+    assert_eq!(token.balance(&treasury), 200);
+    assert_eq!(client.get_bond(&oracle), 800);
+
+    // Released once the appeal is resolved.
+    client.withdraw_bond(&oracle, &800);
+    assert_eq!(token.balance(&oracle), 800);
+}
+
+#[test]
+fn test_flag_history_and_escalation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+
+    env.ledger().set_timestamp(1_000);
+    client.submit_flag(&oracle, &player, &1u64, &2u32);
+    client.submit_flag(&oracle, &player, &2u64, &2u32);
+    client.submit_flag(&oracle, &player, &3u64, &2u32);
+    client.submit_flag(&oracle, &player, &4u64, &2u32);
+
+    // 15 base, +50% per prior flag in the window, capped at 3x.
+    let history = client.get_flag_history(&player, &0, &10);
+    assert_eq!(history.len(), 4);
+    assert_eq!(history.get(0).unwrap().penalty_applied, 15);
+    assert_eq!(history.get(1).unwrap().penalty_applied, 22);
+    assert_eq!(history.get(2).unwrap().penalty_applied, 30);
+    assert_eq!(history.get(3).unwrap().penalty_applied, 37);
+
+    let page = client.get_flag_history(&player, &3, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().match_id, 4);
+
+    // Outside the window the multiplier resets.
+    let window = client.get_escalation_config().window;
+    env.ledger().set_timestamp(1_000 + window + 1);
+    client.submit_flag(&oracle, &player, &5u64, &2u32);
+    assert_eq!(
+        client
+            .get_confirmation(&player, &5u64)
+            .unwrap()
+            .penalty_applied,
+        15
+    );
+    assert_eq!(client.get_flag_count(&player), 5);
+}
+
+#[test]
+fn test_escalation_config_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    client.initialize(&admin);
+
+    let config = EscalationConfig {
+        window: 3600,
+        step_bps: 10_000,
+        max_multiplier_bps: 5_000,
+        ban_threshold: 2,
+    };
+    assert_eq!(
+        client.try_set_escalation_config(&config),
+        Err(Ok(AntiCheatError::InvalidEscalationConfig))
+    );
+    let config = EscalationConfig {
+        max_multiplier_bps: 20_000,
+        ..config
+    };
+    client.set_escalation_config(&config);
+    assert_eq!(client.get_escalation_config(), config);
+}
+
+#[test]
+fn test_commit_reveal_flag() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let other_oracle = Address::generate(&env);
+    let authority = Address::generate(&env);
+    let player = Address::generate(&env);
+    let match_id = 21u64;
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+    client.add_authorized_oracle(&other_oracle);
+    client.set_match_authority(&authority);
+
+    let evidence = String::from_str(&env, "ipfs://evidence");
+    let salt = BytesN::from_array(&env, &[9u8; 32]);
+    let commitment = client.compute_commitment(&player, &match_id, &3u32, &evidence, &salt);
+
+    client.commit_flag(&oracle, &commitment);
+    assert_eq!(
+        client.try_commit_flag(&oracle, &commitment),
+        Err(Ok(AntiCheatError::CommitmentExists))
+    );
+    let record = client.get_commitment(&commitment).unwrap();
+    assert_eq!(record.oracle, oracle);
+    assert!(record.player.is_none());
+
+    assert_eq!(
+        client.try_reveal_flag(&oracle, &player, &match_id, &3u32, &evidence, &salt),
+        Err(Ok(AntiCheatError::MatchNotFinalized))
+    );
+    client.mark_match_finalized(&match_id);
+
+    assert_eq!(
+        client.try_reveal_flag(&other_oracle, &player, &match_id, &3u32, &evidence, &salt),
+        Err(Ok(AntiCheatError::CommitmentMismatch))
+    );
+    assert_eq!(
+        client.try_reveal_flag(&oracle, &player, &match_id, &2u32, &evidence, &salt),
+        Err(Ok(AntiCheatError::CommitmentNotFound))
+    );
+
+    client.reveal_flag(&oracle, &player, &match_id, &3u32, &evidence, &salt);
+    let record = client.get_commitment(&commitment).unwrap();
+    assert_eq!(record.player, Some(player.clone()));
+    assert_eq!(record.evidence_ref, Some(evidence.clone()));
+    assert!(record.revealed_at.is_some());
+    assert_eq!(
+        client
+            .get_confirmation(&player, &match_id)
+            .unwrap()
+            .severity,
+        3
+    );
+
+    assert_eq!(
+        client.try_reveal_flag(&oracle, &player, &match_id, &3u32, &evidence, &salt),
+        Err(Ok(AntiCheatError::AlreadyRevealed))
+    );
+}
+
+#[test]
+fn test_penalty_policy_per_game() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let authority = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    let reputation_id = env.register(MockReputation, ());
+    let reputation = MockReputationClient::new(&env, &reputation_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+    client.set_match_authority(&authority);
+    client.set_reputation_contract(&reputation_id);
+
+    let chess = Symbol::new(&env, "chess");
+    assert_eq!(
+        client.get_penalty_policy(&Some(chess.clone())),
+        vec![&env, 5i128, 15, 30]
+    );
+
+    // Above the reputation index cap (40 in the mock).
+    assert_eq!(
+        client.try_set_penalty_policy(&None, &vec![&env, 10i128, 20, 50]),
+        Err(Ok(AntiCheatError::InvalidPenaltyPolicy))
+    );
+    // Decreasing by severity.
+    assert_eq!(
+        client.try_set_penalty_policy(&None, &vec![&env, 20i128, 10, 30]),
+        Err(Ok(AntiCheatError::InvalidPenaltyPolicy))
+    );
+    assert_eq!(
+        client.try_set_penalty_policy(&None, &vec![&env, 10i128, 20]),
+        Err(Ok(AntiCheatError::InvalidPenaltyPolicy))
+    );
+
+    client.set_penalty_policy(&None, &vec![&env, 4i128, 8, 12]);
+    client.set_penalty_policy(&Some(chess.clone()), &vec![&env, 10i128, 20, 40]);
+    client.set_match_game(&1u64, &chess);
+
+    client.submit_flag(&oracle, &player, &1u64, &3u32);
+    assert_eq!(reputation.delta(&player), -40);
+
+    let other = Address::generate(&env);
+    client.submit_flag(&oracle, &other, &2u64, &3u32);
+    assert_eq!(reputation.delta(&other), -12);
+
+    client.remove_penalty_policy(&chess);
+    assert_eq!(
+        client.get_penalty_policy(&Some(chess)),
+        vec![&env, 4i128, 8, 12]
+    );
+}
+
+#[test]
+fn test_prune_old_confirmations() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+
+    env.ledger().set_timestamp(1_000);
+    client.submit_flag(&oracle, &player, &1u64, &1u32);
+    client.submit_flag(&oracle, &player, &2u64, &1u32);
+    env.ledger().set_timestamp(5_000);
+    client.submit_flag(&oracle, &player, &3u64, &1u32);
+
+    env.ledger().set_timestamp(6_000);
+    let players = vec![&env, player.clone()];
+    assert_eq!(client.prune_confirmations(&players, &2_000), 2);
+    assert!(client.get_confirmation(&player, &1u64).is_none());
+    assert!(client.get_confirmation(&player, &2u64).is_none());
+    assert!(client.get_confirmation(&player, &3u64).is_some());
+    assert_eq!(client.get_open_confirmations(&oracle), 1);
+
+    assert_eq!(client.get_flag_count(&player), 1);
+    let history = client.get_flag_history(&player, &0, &10);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().match_id, 3);
+
+    // Nothing left past the retention period.
+    assert_eq!(client.prune_confirmations(&players, &2_000), 0);
+}
+
+#[test]
+fn test_high_severity_flag_opens_dispute() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let authority = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(AntiCheatOracle, ());
+    let client = AntiCheatOracleClient::new(&env, &contract_id);
+    let escrow_id = env.register(MockEscrowVault, ());
+    let escrow = MockEscrowVaultClient::new(&env, &escrow_id);
+    let dispute_id = env.register(MockDisputeResolution, ());
+    let dispute = MockDisputeResolutionClient::new(&env, &dispute_id);
+    client.initialize(&admin);
+    client.add_authorized_oracle(&oracle);
+    client.set_match_authority(&authority);
+    client.set_dispute_hooks(&dispute_id, &escrow_id);
+
+    let high_ref = BytesN::from_array(&env, &[1u8; 32]);
+    let medium_ref = BytesN::from_array(&env, &[2u8; 32]);
+    client.link_match(&1u64, &high_ref);
+    client.link_match(&2u64, &medium_ref);
+
+    client.submit_flag(&oracle, &player, &2u64, &2u32);
+    assert!(!escrow.is_reported(&medium_ref));
+    assert!(!dispute.is_disputed(&medium_ref));
+
+    client.submit_flag(&oracle, &player, &1u64, &3u32);
+    assert!(escrow.is_reported(&high_ref));
+    assert!(dispute.is_disputed(&high_ref));
+
+    // Unlinked matches are penalized without escalation.
+    client.submit_flag(&oracle, &player, &3u64, &3u32);
+    assert!(client.get_confirmation(&player, &3u64).is_some());
+}
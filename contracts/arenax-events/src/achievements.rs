@@ -0,0 +1,83 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+pub const NAMESPACE: &str = "ArenaXAchievements";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXAch_v1", "REPORTER"])]
+pub struct ReporterUpdated {
+    pub reporter: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXAch_v1", "RATE_LIMIT"])]
+pub struct RateLimitUpdated {
+    pub max_per_window: u32,
+    pub window_secs: u64,
+}
+
+#[contractevent(topics = ["ArenaXAch_v1", "DEFINED"])]
+pub struct AchievementDefined {
+    pub achievement_id: u32,
+    pub reputation: bool,
+    pub xp: u32,
+    pub active: bool,
+}
+
+#[contractevent(topics = ["ArenaXAch_v1", "COMPLETED"])]
+pub struct AchievementCompleted {
+    pub achievement_id: u32,
+    pub player: Address,
+    pub reporter: Address,
+    pub evidence_hash: BytesN<32>,
+    pub xp: u32,
+}
+
+pub fn emit_reporter_updated(env: &Env, reporter: &Address, enabled: bool) {
+    ReporterUpdated {
+        reporter: reporter.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_rate_limit_updated(env: &Env, max_per_window: u32, window_secs: u64) {
+    RateLimitUpdated {
+        max_per_window,
+        window_secs,
+    }
+    .publish(env);
+}
+
+pub fn emit_achievement_defined(
+    env: &Env,
+    achievement_id: u32,
+    reputation: bool,
+    xp: u32,
+    active: bool,
+) {
+    AchievementDefined {
+        achievement_id,
+        reputation,
+        xp,
+        active,
+    }
+    .publish(env);
+}
+
+pub fn emit_achievement_completed(
+    env: &Env,
+    achievement_id: u32,
+    player: &Address,
+    reporter: &Address,
+    evidence_hash: &BytesN<32>,
+    xp: u32,
+) {
+    AchievementCompleted {
+        achievement_id,
+        player: player.clone(),
+        reporter: reporter.clone(),
+        evidence_hash: evidence_hash.clone(),
+        xp,
+    }
+    .publish(env);
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Address, Env, String};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Symbol, Vec};
 
 pub const NAMESPACE: &str = "ArenaXAntiCheat";
 pub const VERSION: &str = "v1";
@@ -33,6 +33,315 @@ pub fn emit_anticheat_flag(
     .publish(env);
 }
 
+#[contractevent(topics = ["ArenaXAC_v1", "FLAG_PENDING"])]
+pub struct FlagPending {
+    pub player: Address,
+    pub match_id: u64,
+    pub severity: u32,
+    pub oracle: Address,
+    pub votes: u32,
+    pub threshold: u32,
+}
+
+pub fn emit_flag_pending(
+    env: &Env,
+    player: &Address,
+    match_id: u64,
+    severity: u32,
+    oracle: &Address,
+    votes: u32,
+    threshold: u32,
+) {
+    FlagPending {
+        player: player.clone(),
+        match_id,
+        severity,
+        oracle: oracle.clone(),
+        votes,
+        threshold,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "CONSENSUS"])]
+pub struct ConsensusReached {
+    pub player: Address,
+    pub match_id: u64,
+    pub severity: u32,
+    pub votes: u32,
+}
+
+pub fn emit_consensus_reached(
+    env: &Env,
+    player: &Address,
+    match_id: u64,
+    severity: u32,
+    votes: u32,
+) {
+    ConsensusReached {
+        player: player.clone(),
+        match_id,
+        severity,
+        votes,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "CONSENSUS_CFG"])]
+pub struct ConsensusConfigUpdated {
+    pub threshold: u32,
+    pub window: u64,
+}
+
+pub fn emit_consensus_config_updated(env: &Env, threshold: u32, window: u64) {
+    ConsensusConfigUpdated { threshold, window }.publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "FLAG_APPEALED"])]
+pub struct FlagAppealed {
+    pub player: Address,
+    pub match_id: u64,
+    pub evidence_ref: String,
+    pub timestamp: u64,
+}
+
+pub fn emit_flag_appealed(
+    env: &Env,
+    player: &Address,
+    match_id: u64,
+    evidence_ref: &String,
+    timestamp: u64,
+) {
+    FlagAppealed {
+        player: player.clone(),
+        match_id,
+        evidence_ref: evidence_ref.clone(),
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "FLAG_APPEAL_RES"])]
+pub struct FlagAppealResolved {
+    pub player: Address,
+    pub match_id: u64,
+    pub overturned: bool,
+    pub resolver: Address,
+    pub timestamp: u64,
+}
+
+pub fn emit_appeal_resolved(
+    env: &Env,
+    player: &Address,
+    match_id: u64,
+    overturned: bool,
+    resolver: &Address,
+    timestamp: u64,
+) {
+    FlagAppealResolved {
+        player: player.clone(),
+        match_id,
+        overturned,
+        resolver: resolver.clone(),
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "BOND_DEPOSIT"])]
+pub struct BondDeposited {
+    pub oracle: Address,
+    pub amount: i128,
+    pub bond: i128,
+}
+
+pub fn emit_bond_deposited(env: &Env, oracle: &Address, amount: i128, bond: i128) {
+    BondDeposited {
+        oracle: oracle.clone(),
+        amount,
+        bond,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "BOND_WITHDRAW"])]
+pub struct BondWithdrawn {
+    pub oracle: Address,
+    pub amount: i128,
+    pub bond: i128,
+}
+
+pub fn emit_bond_withdrawn(env: &Env, oracle: &Address, amount: i128, bond: i128) {
+    BondWithdrawn {
+        oracle: oracle.clone(),
+        amount,
+        bond,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "ORACLE_SLASHED"])]
+pub struct OracleSlashed {
+    pub oracle: Address,
+    pub amount: i128,
+    pub overturns: u32,
+}
+
+pub fn emit_oracle_slashed(env: &Env, oracle: &Address, amount: i128, overturns: u32) {
+    OracleSlashed {
+        oracle: oracle.clone(),
+        amount,
+        overturns,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "ORACLE_SUSPENDED"])]
+pub struct OracleSuspended {
+    pub oracle: Address,
+    pub overturns: u32,
+}
+
+pub fn emit_oracle_suspended(env: &Env, oracle: &Address, overturns: u32) {
+    OracleSuspended {
+        oracle: oracle.clone(),
+        overturns,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "ORACLE_REINSTATED"])]
+pub struct OracleReinstated {
+    pub oracle: Address,
+}
+
+pub fn emit_oracle_reinstated(env: &Env, oracle: &Address) {
+    OracleReinstated {
+        oracle: oracle.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "BAN_RECOMMENDED"])]
+pub struct TempBanRecommended {
+    pub player: Address,
+    pub offences: u32,
+    pub window: u64,
+}
+
+pub fn emit_temp_ban_recommended(env: &Env, player: &Address, offences: u32, window: u64) {
+    TempBanRecommended {
+        player: player.clone(),
+        offences,
+        window,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "FLAG_COMMITTED"])]
+pub struct FlagCommitted {
+    pub oracle: Address,
+    pub commitment: BytesN<32>,
+    pub timestamp: u64,
+}
+
+pub fn emit_flag_committed(env: &Env, oracle: &Address, commitment: &BytesN<32>, timestamp: u64) {
+    FlagCommitted {
+        oracle: oracle.clone(),
+        commitment: commitment.clone(),
+        timestamp,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "FLAG_REVEALED"])]
+pub struct FlagRevealed {
+    pub oracle: Address,
+    pub commitment: BytesN<32>,
+    pub player: Address,
+    pub match_id: u64,
+    pub severity: u32,
+    pub evidence_ref: String,
+}
+
+pub fn emit_flag_revealed(
+    env: &Env,
+    oracle: &Address,
+    commitment: &BytesN<32>,
+    player: &Address,
+    match_id: u64,
+    severity: u32,
+    evidence_ref: &String,
+) {
+    FlagRevealed {
+        oracle: oracle.clone(),
+        commitment: commitment.clone(),
+        player: player.clone(),
+        match_id,
+        severity,
+        evidence_ref: evidence_ref.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "POLICY_UPDATED"])]
+pub struct PenaltyPolicyUpdated {
+    pub game_id: Option<Symbol>,
+    pub penalties: Vec<i128>,
+}
+
+pub fn emit_penalty_policy_updated(env: &Env, game_id: &Option<Symbol>, penalties: &Vec<i128>) {
+    PenaltyPolicyUpdated {
+        game_id: game_id.clone(),
+        penalties: penalties.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "POLICY_REMOVED"])]
+pub struct PenaltyPolicyRemoved {
+    pub game_id: Symbol,
+}
+
+pub fn emit_penalty_policy_removed(env: &Env, game_id: &Symbol) {
+    PenaltyPolicyRemoved {
+        game_id: game_id.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "PRUNED"])]
+pub struct ConfirmationsPruned {
+    pub player: Address,
+    pub count: u32,
+    pub cutoff: u64,
+}
+
+pub fn emit_confirmations_pruned(env: &Env, player: &Address, count: u32, cutoff: u64) {
+    ConfirmationsPruned {
+        player: player.clone(),
+        count,
+        cutoff,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXAC_v1", "DISPUTE_OPENED"])]
+pub struct DisputeEscalated {
+    pub player: Address,
+    pub match_id: u64,
+    pub match_ref: BytesN<32>,
+}
+
+pub fn emit_dispute_escalated(env: &Env, player: &Address, match_id: u64, match_ref: &BytesN<32>) {
+    DisputeEscalated {
+        player: player.clone(),
+        match_id,
+        match_ref: match_ref.clone(),
+    }
+    .publish(env);
+}
+
 // Anti-cheat contract events
 #[contractevent(topics = ["ArenaXAC_v1", "SUSPICIOUS"])]
 pub struct SuspiciousActivityReported {
@@ -101,3 +101,31 @@ pub fn emit_contract_paused(env: &Env, paused: bool, paused_by: &Address) {
     }
     .publish(env);
 }
+
+#[contractevent(topics = ["ArenaXAuth_v1", "BANNED"])]
+pub struct AddressBanned {
+    pub address: Address,
+    pub banned_by: Address,
+}
+
+#[contractevent(topics = ["ArenaXAuth_v1", "UNBANNED"])]
+pub struct AddressUnbanned {
+    pub address: Address,
+    pub unbanned_by: Address,
+}
+
+pub fn emit_address_banned(env: &Env, address: &Address, banned_by: &Address) {
+    AddressBanned {
+        address: address.clone(),
+        banned_by: banned_by.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_address_unbanned(env: &Env, address: &Address, unbanned_by: &Address) {
+    AddressUnbanned {
+        address: address.clone(),
+        unbanned_by: unbanned_by.clone(),
+    }
+    .publish(env);
+}
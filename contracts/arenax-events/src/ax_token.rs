@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Address, Env};
+use soroban_sdk::{contractevent, Address, Env, String};
 
 pub const NAMESPACE: &str = "ArenaXToken";
 pub const VERSION: &str = "v1";
@@ -46,3 +46,291 @@ pub fn emit_transfer(env: &Env, from: &Address, to: &Address, amount: i128) {
     }
     .publish(env);
 }
+
+#[contractevent(topics = ["ArenaXToken_v1", "APPROVE"])]
+pub struct ApproveEvent {
+    pub from: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+pub fn emit_approve(
+    env: &Env,
+    from: &Address,
+    spender: &Address,
+    amount: i128,
+    expiration_ledger: u32,
+) {
+    ApproveEvent {
+        from: from.clone(),
+        spender: spender.clone(),
+        amount,
+        expiration_ledger,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "EMIS_SCHED"])]
+pub struct EmissionScheduleCreated {
+    pub schedule_id: u32,
+    pub recipient: Address,
+    pub amount_per_epoch: i128,
+    pub epoch_length: u64,
+    pub start_time: u64,
+    pub total_epochs: u32,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "EPOCH_MINT"])]
+pub struct EpochMinted {
+    pub schedule_id: u32,
+    pub recipient: Address,
+    pub epochs: u32,
+    pub epochs_minted: u32,
+    pub amount: i128,
+}
+
+pub fn emit_emission_schedule_created(
+    env: &Env,
+    schedule_id: u32,
+    recipient: &Address,
+    amount_per_epoch: i128,
+    epoch_length: u64,
+    start_time: u64,
+    total_epochs: u32,
+) {
+    EmissionScheduleCreated {
+        schedule_id,
+        recipient: recipient.clone(),
+        amount_per_epoch,
+        epoch_length,
+        start_time,
+        total_epochs,
+    }
+    .publish(env);
+}
+
+pub fn emit_epoch_minted(
+    env: &Env,
+    schedule_id: u32,
+    recipient: &Address,
+    epochs: u32,
+    epochs_minted: u32,
+    amount: i128,
+) {
+    EpochMinted {
+        schedule_id,
+        recipient: recipient.clone(),
+        epochs,
+        epochs_minted,
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "FROZEN"])]
+pub struct AccountFrozen {
+    pub account: Address,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "UNFROZEN"])]
+pub struct AccountUnfrozen {
+    pub account: Address,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "CLAWBK_ROLE"])]
+pub struct ClawbackAdminSet {
+    pub clawback_admin: Address,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "CLAWBACK"])]
+pub struct Clawback {
+    pub by: Address,
+    pub from: Address,
+    pub amount: i128,
+    pub reason: String,
+}
+
+pub fn emit_account_frozen(env: &Env, account: &Address) {
+    AccountFrozen {
+        account: account.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_account_unfrozen(env: &Env, account: &Address) {
+    AccountUnfrozen {
+        account: account.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_clawback_admin_set(env: &Env, clawback_admin: &Address) {
+    ClawbackAdminSet {
+        clawback_admin: clawback_admin.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_clawback(env: &Env, by: &Address, from: &Address, amount: i128, reason: &String) {
+    Clawback {
+        by: by.clone(),
+        from: from.clone(),
+        amount,
+        reason: reason.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "LOCKED_MINT"])]
+pub struct LockedMint {
+    pub to: Address,
+    pub schedule_id: u32,
+    pub amount: i128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "VEST_RELEASE"])]
+pub struct VestingReleased {
+    pub to: Address,
+    pub amount: i128,
+}
+
+pub fn emit_locked_mint(
+    env: &Env,
+    to: &Address,
+    schedule_id: u32,
+    amount: i128,
+    start_time: u64,
+    cliff: u64,
+    duration: u64,
+) {
+    LockedMint {
+        to: to.clone(),
+        schedule_id,
+        amount,
+        start_time,
+        cliff,
+        duration,
+    }
+    .publish(env);
+}
+
+pub fn emit_vesting_released(env: &Env, to: &Address, amount: i128) {
+    VestingReleased {
+        to: to.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "FEE_CFG"])]
+pub struct TransferFeeSet {
+    pub fee_bps: u32,
+    pub treasury: Address,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "FEE_EXEMPT"])]
+pub struct FeeExemptionSet {
+    pub account: Address,
+    pub exempt: bool,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "TRANSFER_FEE"])]
+pub struct TransferFeeCharged {
+    pub from: Address,
+    pub to: Address,
+    pub gross: i128,
+    pub fee: i128,
+    pub net: i128,
+}
+
+pub fn emit_transfer_fee_set(env: &Env, fee_bps: u32, treasury: &Address) {
+    TransferFeeSet {
+        fee_bps,
+        treasury: treasury.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_fee_exemption_set(env: &Env, account: &Address, exempt: bool) {
+    FeeExemptionSet {
+        account: account.clone(),
+        exempt,
+    }
+    .publish(env);
+}
+
+pub fn emit_transfer_fee_charged(
+    env: &Env,
+    from: &Address,
+    to: &Address,
+    gross: i128,
+    fee: i128,
+    net: i128,
+) {
+    TransferFeeCharged {
+        from: from.clone(),
+        to: to.clone(),
+        gross,
+        fee,
+        net,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "PERMIT"])]
+pub struct PermitUsed {
+    pub owner: Address,
+    pub spender: Address,
+    pub amount: i128,
+    pub nonce: u64,
+}
+
+pub fn emit_permit(env: &Env, owner: &Address, spender: &Address, amount: i128, nonce: u64) {
+    PermitUsed {
+        owner: owner.clone(),
+        spender: spender.clone(),
+        amount,
+        nonce,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "AIRDROP"])]
+pub struct Airdrop {
+    pub recipients: u32,
+    pub total: i128,
+}
+
+pub fn emit_airdrop(env: &Env, recipients: u32, total: i128) {
+    Airdrop { recipients, total }.publish(env);
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "COMPLIANCE"])]
+pub struct ComplianceSourceSet {
+    pub source: Option<Address>,
+}
+
+#[contractevent(topics = ["ArenaXToken_v1", "COMPL_OVERRIDE"])]
+pub struct ComplianceOverrideSet {
+    pub account: Address,
+    pub allowed: bool,
+}
+
+pub fn emit_compliance_source_set(env: &Env, source: &Option<Address>) {
+    ComplianceSourceSet {
+        source: source.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_compliance_override_set(env: &Env, account: &Address, allowed: bool) {
+    ComplianceOverrideSet {
+        account: account.clone(),
+        allowed,
+    }
+    .publish(env);
+}
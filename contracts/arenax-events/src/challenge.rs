@@ -0,0 +1,86 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol};
+
+pub const NAMESPACE: &str = "ArenaXChallenge";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXChal_v1", "POSTED"])]
+pub struct ChallengePosted {
+    pub challenge_id: u64,
+    pub challenger: Address,
+    pub opponent: Option<Address>,
+    pub game_id: Symbol,
+    pub asset: Address,
+    pub stake: i128,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXChal_v1", "ACCEPTED"])]
+pub struct ChallengeAccepted {
+    pub challenge_id: u64,
+    pub match_id: BytesN<32>,
+    pub challenger: Address,
+    pub opponent: Address,
+}
+
+#[contractevent(topics = ["ArenaXChal_v1", "CLOSED"])]
+pub struct ChallengeClosed {
+    pub challenge_id: u64,
+    pub challenger: Address,
+    pub refund: i128,
+    pub expired: bool,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_challenge_posted(
+    env: &Env,
+    challenge_id: u64,
+    challenger: &Address,
+    opponent: &Option<Address>,
+    game_id: &Symbol,
+    asset: &Address,
+    stake: i128,
+    expires_at: u64,
+) {
+    ChallengePosted {
+        challenge_id,
+        challenger: challenger.clone(),
+        opponent: opponent.clone(),
+        game_id: game_id.clone(),
+        asset: asset.clone(),
+        stake,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_challenge_accepted(
+    env: &Env,
+    challenge_id: u64,
+    match_id: &BytesN<32>,
+    challenger: &Address,
+    opponent: &Address,
+) {
+    ChallengeAccepted {
+        challenge_id,
+        match_id: match_id.clone(),
+        challenger: challenger.clone(),
+        opponent: opponent.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_challenge_closed(
+    env: &Env,
+    challenge_id: u64,
+    challenger: &Address,
+    refund: i128,
+    expired: bool,
+) {
+    ChallengeClosed {
+        challenge_id,
+        challenger: challenger.clone(),
+        refund,
+        expired,
+    }
+    .publish(env);
+}
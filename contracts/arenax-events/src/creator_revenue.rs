@@ -0,0 +1,88 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol};
+
+pub const NAMESPACE: &str = "ArenaXCreatorRevenue";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXCreator_v1", "ORGANIZER"])]
+pub struct OrganizerUpdated {
+    pub organizer: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXCreator_v1", "STREAMS"])]
+pub struct StreamsRegistered {
+    pub match_id: BytesN<32>,
+    pub organizer: Address,
+    pub streamers: u32,
+    pub total_share_bps: u32,
+}
+
+#[contractevent(topics = ["ArenaXCreator_v1", "REVENUE"])]
+pub struct RevenueRouted {
+    pub match_id: BytesN<32>,
+    pub payer: Address,
+    pub source: Symbol,
+    pub asset: Address,
+    pub amount: i128,
+    pub to_streamers: i128,
+}
+
+#[contractevent(topics = ["ArenaXCreator_v1", "CLAIMED"])]
+pub struct RevenueClaimed {
+    pub account: Address,
+    pub asset: Address,
+    pub amount: i128,
+}
+
+pub fn emit_organizer_updated(env: &Env, organizer: &Address, enabled: bool) {
+    OrganizerUpdated {
+        organizer: organizer.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_streams_registered(
+    env: &Env,
+    match_id: &BytesN<32>,
+    organizer: &Address,
+    streamers: u32,
+    total_share_bps: u32,
+) {
+    StreamsRegistered {
+        match_id: match_id.clone(),
+        organizer: organizer.clone(),
+        streamers,
+        total_share_bps,
+    }
+    .publish(env);
+}
+
+pub fn emit_revenue_routed(
+    env: &Env,
+    match_id: &BytesN<32>,
+    payer: &Address,
+    source: &Symbol,
+    asset: &Address,
+    amount: i128,
+    to_streamers: i128,
+) {
+    RevenueRouted {
+        match_id: match_id.clone(),
+        payer: payer.clone(),
+        source: source.clone(),
+        asset: asset.clone(),
+        amount,
+        to_streamers,
+    }
+    .publish(env);
+}
+
+pub fn emit_revenue_claimed(env: &Env, account: &Address, asset: &Address, amount: i128) {
+    RevenueClaimed {
+        account: account.clone(),
+        asset: asset.clone(),
+        amount,
+    }
+    .publish(env);
+}
@@ -0,0 +1,141 @@
+use soroban_sdk::{contractevent, Address, Env, String};
+
+pub const NAMESPACE: &str = "ArenaXDao";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXDao_v1", "PROPOSED"])]
+pub struct ProposalCreated {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub description: String,
+    pub action_count: u32,
+    pub snapshot_ledger: u32,
+    pub voting_end: u64,
+}
+
+#[contractevent(topics = ["ArenaXDao_v1", "VOTE"])]
+pub struct VoteCast {
+    pub proposal_id: u64,
+    pub voter: Address,
+    pub support: u32,
+    pub weight: i128,
+}
+
+#[contractevent(topics = ["ArenaXDao_v1", "QUEUED"])]
+pub struct ProposalQueued {
+    pub proposal_id: u64,
+    pub eta: u64,
+}
+
+#[contractevent(topics = ["ArenaXDao_v1", "DEFEATED"])]
+pub struct ProposalDefeated {
+    pub proposal_id: u64,
+    pub for_votes: i128,
+    pub against_votes: i128,
+    pub abstain_votes: i128,
+    pub quorum: i128,
+}
+
+#[contractevent(topics = ["ArenaXDao_v1", "EXECUTED"])]
+pub struct ProposalExecuted {
+    pub proposal_id: u64,
+    pub executor: Address,
+}
+
+#[contractevent(topics = ["ArenaXDao_v1", "CANCELLED"])]
+pub struct ProposalCancelled {
+    pub proposal_id: u64,
+    pub cancelled_by: Address,
+}
+
+#[contractevent(topics = ["ArenaXDao_v1", "CONFIG"])]
+pub struct ConfigUpdated {
+    pub voting_period: u64,
+    pub quorum_bps: u32,
+    pub proposal_threshold: i128,
+    pub timelock: u64,
+}
+
+pub fn emit_proposal_created(
+    env: &Env,
+    proposal_id: u64,
+    proposer: &Address,
+    description: &String,
+    action_count: u32,
+    snapshot_ledger: u32,
+    voting_end: u64,
+) {
+    ProposalCreated {
+        proposal_id,
+        proposer: proposer.clone(),
+        description: description.clone(),
+        action_count,
+        snapshot_ledger,
+        voting_end,
+    }
+    .publish(env);
+}
+
+pub fn emit_vote_cast(env: &Env, proposal_id: u64, voter: &Address, support: u32, weight: i128) {
+    VoteCast {
+        proposal_id,
+        voter: voter.clone(),
+        support,
+        weight,
+    }
+    .publish(env);
+}
+
+pub fn emit_proposal_queued(env: &Env, proposal_id: u64, eta: u64) {
+    ProposalQueued { proposal_id, eta }.publish(env);
+}
+
+pub fn emit_proposal_defeated(
+    env: &Env,
+    proposal_id: u64,
+    for_votes: i128,
+    against_votes: i128,
+    abstain_votes: i128,
+    quorum: i128,
+) {
+    ProposalDefeated {
+        proposal_id,
+        for_votes,
+        against_votes,
+        abstain_votes,
+        quorum,
+    }
+    .publish(env);
+}
+
+pub fn emit_proposal_executed(env: &Env, proposal_id: u64, executor: &Address) {
+    ProposalExecuted {
+        proposal_id,
+        executor: executor.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_proposal_cancelled(env: &Env, proposal_id: u64, cancelled_by: &Address) {
+    ProposalCancelled {
+        proposal_id,
+        cancelled_by: cancelled_by.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_config_updated(
+    env: &Env,
+    voting_period: u64,
+    quorum_bps: u32,
+    proposal_threshold: i128,
+    timelock: u64,
+) {
+    ConfigUpdated {
+        voting_period,
+        quorum_bps,
+        proposal_threshold,
+        timelock,
+    }
+    .publish(env);
+}
@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Address, BytesN, Env, String};
+use soroban_sdk::{contractevent, Address, BytesN, Env, String, Vec};
 
 pub const NAMESPACE: &str = "ArenaXDispute";
 pub const VERSION: &str = "v1";
@@ -50,3 +50,343 @@ pub fn emit_dispute_resolved(
     }
     .publish(env);
 }
+
+#[contractevent(topics = ["ArenaXDisp_v1", "EVIDENCE"])]
+pub struct EvidenceSubmitted {
+    pub match_id: BytesN<32>,
+    pub submitter: Address,
+    pub evidence_ref: String,
+    pub index: u32,
+}
+
+pub fn emit_evidence_submitted(
+    env: &Env,
+    match_id: &BytesN<32>,
+    submitter: &Address,
+    evidence_ref: &String,
+    index: u32,
+) {
+    EvidenceSubmitted {
+        match_id: match_id.clone(),
+        submitter: submitter.clone(),
+        evidence_ref: evidence_ref.clone(),
+        index,
+    }
+    .publish(env);
+}
+
+/// Structured form of a resolution: `outcome` uses the match contract's dispute outcome codes;
+/// `share_a_bps` is player A's share of the escrow when `escrow_settled` is true.
+#[contractevent(topics = ["ArenaXDisp_v1", "DECISION"])]
+pub struct DecisionExecuted {
+    pub match_id: BytesN<32>,
+    pub outcome: u32,
+    pub share_a_bps: u32,
+    pub escrow_settled: bool,
+}
+
+pub fn emit_decision_executed(
+    env: &Env,
+    match_id: &BytesN<32>,
+    outcome: u32,
+    share_a_bps: u32,
+    escrow_settled: bool,
+) {
+    DecisionExecuted {
+        match_id: match_id.clone(),
+        outcome,
+        share_a_bps,
+        escrow_settled,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "PANEL_SET"])]
+pub struct PanelAssigned {
+    pub match_id: BytesN<32>,
+    pub referees: Vec<Address>,
+}
+
+pub fn emit_panel_assigned(env: &Env, match_id: &BytesN<32>, referees: &Vec<Address>) {
+    PanelAssigned {
+        match_id: match_id.clone(),
+        referees: referees.clone(),
+    }
+    .publish(env);
+}
+
+/// `support` is the number of panel votes for the same decision, including this one.
+#[contractevent(topics = ["ArenaXDisp_v1", "VOTE"])]
+pub struct VoteCast {
+    pub match_id: BytesN<32>,
+    pub referee: Address,
+    pub support: u32,
+}
+
+pub fn emit_vote_cast(env: &Env, match_id: &BytesN<32>, referee: &Address, support: u32) {
+    VoteCast {
+        match_id: match_id.clone(),
+        referee: referee.clone(),
+        support,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "ESCALATED"])]
+pub struct DisputeEscalated {
+    pub match_id: BytesN<32>,
+    pub escalated_at: u64,
+}
+
+pub fn emit_dispute_escalated(env: &Env, match_id: &BytesN<32>, escalated_at: u64) {
+    DisputeEscalated {
+        match_id: match_id.clone(),
+        escalated_at,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "BOND_CFG"])]
+pub struct BondConfigUpdated {
+    pub token: Address,
+    pub brackets: u32,
+}
+
+pub fn emit_bond_config_updated(env: &Env, token: &Address, brackets: u32) {
+    BondConfigUpdated {
+        token: token.clone(),
+        brackets,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "BOND_POSTED"])]
+pub struct BondPosted {
+    pub match_id: BytesN<32>,
+    pub opener: Address,
+    pub amount: i128,
+}
+
+pub fn emit_bond_posted(env: &Env, match_id: &BytesN<32>, opener: &Address, amount: i128) {
+    BondPosted {
+        match_id: match_id.clone(),
+        opener: opener.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+/// `refunded` is true when the bond went back to the opener, false when it was forfeited.
+#[contractevent(topics = ["ArenaXDisp_v1", "BOND_SETTLED"])]
+pub struct BondSettled {
+    pub match_id: BytesN<32>,
+    pub recipient: Address,
+    pub amount: i128,
+    pub refunded: bool,
+}
+
+pub fn emit_bond_settled(
+    env: &Env,
+    match_id: &BytesN<32>,
+    recipient: &Address,
+    amount: i128,
+    refunded: bool,
+) {
+    BondSettled {
+        match_id: match_id.clone(),
+        recipient: recipient.clone(),
+        amount,
+        refunded,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "APPEAL_CFG"])]
+pub struct AppealConfigUpdated {
+    pub window: u64,
+    pub bond: i128,
+    pub panel_size: u32,
+}
+
+pub fn emit_appeal_config_updated(env: &Env, window: u64, bond: i128, panel_size: u32) {
+    AppealConfigUpdated {
+        window,
+        bond,
+        panel_size,
+    }
+    .publish(env);
+}
+
+/// A decision was taken and is suspended until `appeal_deadline`.
+#[contractevent(topics = ["ArenaXDisp_v1", "DECIDED"])]
+pub struct DisputeDecided {
+    pub match_id: BytesN<32>,
+    pub operator: Address,
+    pub appeal_deadline: u64,
+}
+
+pub fn emit_dispute_decided(
+    env: &Env,
+    match_id: &BytesN<32>,
+    operator: &Address,
+    appeal_deadline: u64,
+) {
+    DisputeDecided {
+        match_id: match_id.clone(),
+        operator: operator.clone(),
+        appeal_deadline,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "APPEALED"])]
+pub struct DisputeAppealed {
+    pub match_id: BytesN<32>,
+    pub appellant: Address,
+    pub bond: i128,
+    pub deadline: u64,
+}
+
+pub fn emit_dispute_appealed(
+    env: &Env,
+    match_id: &BytesN<32>,
+    appellant: &Address,
+    bond: i128,
+    deadline: u64,
+) {
+    DisputeAppealed {
+        match_id: match_id.clone(),
+        appellant: appellant.clone(),
+        bond,
+        deadline,
+    }
+    .publish(env);
+}
+
+/// The resolution deadline passed without action and the default decision was applied.
+#[contractevent(topics = ["ArenaXDisp_v1", "EXPIRED"])]
+pub struct DisputeExpired {
+    pub match_id: BytesN<32>,
+    pub expired_at: u64,
+}
+
+pub fn emit_dispute_expired(env: &Env, match_id: &BytesN<32>, expired_at: u64) {
+    DisputeExpired {
+        match_id: match_id.clone(),
+        expired_at,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "CLAIMED"])]
+pub struct DisputeClaimed {
+    pub match_id: BytesN<32>,
+    pub operator: Address,
+}
+
+pub fn emit_dispute_claimed(env: &Env, match_id: &BytesN<32>, operator: &Address) {
+    DisputeClaimed {
+        match_id: match_id.clone(),
+        operator: operator.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "FEE_CFG"])]
+pub struct RefereeFeeUpdated {
+    pub fee_bps: u32,
+}
+
+pub fn emit_referee_fee_updated(env: &Env, fee_bps: u32) {
+    RefereeFeeUpdated { fee_bps }.publish(env);
+}
+
+/// `amount` of `token` was split between the `referees` who decided the dispute.
+#[contractevent(topics = ["ArenaXDisp_v1", "FEES_ACCRUED"])]
+pub struct RefereeFeesAccrued {
+    pub match_id: BytesN<32>,
+    pub token: Address,
+    pub amount: i128,
+    pub referees: u32,
+}
+
+pub fn emit_referee_fees_accrued(
+    env: &Env,
+    match_id: &BytesN<32>,
+    token: &Address,
+    amount: i128,
+    referees: u32,
+) {
+    RefereeFeesAccrued {
+        match_id: match_id.clone(),
+        token: token.clone(),
+        amount,
+        referees,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "FEES_CLAIMED"])]
+pub struct RefereeFeesClaimed {
+    pub referee: Address,
+    pub token: Address,
+    pub amount: i128,
+}
+
+pub fn emit_referee_fees_claimed(env: &Env, referee: &Address, token: &Address, amount: i128) {
+    RefereeFeesClaimed {
+        referee: referee.clone(),
+        token: token.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "CITED"])]
+pub struct ConfirmationCited {
+    pub match_id: BytesN<32>,
+    pub operator: Address,
+    pub player: Address,
+    pub confirmation_match_id: u64,
+}
+
+pub fn emit_confirmation_cited(
+    env: &Env,
+    match_id: &BytesN<32>,
+    operator: &Address,
+    player: &Address,
+    confirmation_match_id: u64,
+) {
+    ConfirmationCited {
+        match_id: match_id.clone(),
+        operator: operator.clone(),
+        player: player.clone(),
+        confirmation_match_id,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "PAUSED"])]
+pub struct PauseToggled {
+    pub paused: bool,
+}
+
+pub fn emit_paused(env: &Env, paused: bool) {
+    PauseToggled { paused }.publish(env);
+}
+
+#[contractevent(topics = ["ArenaXDisp_v1", "WINDOW_SET"])]
+pub struct ResolutionWindowUpdated {
+    pub category: u32,
+    pub previous: u64,
+    pub window: u64,
+}
+
+pub fn emit_window_updated(env: &Env, category: u32, previous: u64, window: u64) {
+    ResolutionWindowUpdated {
+        category,
+        previous,
+        window,
+    }
+    .publish(env);
+}
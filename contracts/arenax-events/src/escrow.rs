@@ -69,6 +69,37 @@ pub struct EmergencyWithdraw {
     pub asset: Address,
 }
 
+#[contractevent(topics = ["ArenaXEscrow_v1", "AC_SET"])]
+pub struct AntiCheatOracleSet {
+    pub oracle: Address,
+}
+
+#[contractevent(topics = ["ArenaXEscrow_v1", "CREATOR_SET"])]
+pub struct CreatorSet {
+    pub creator: Address,
+    pub allowed: bool,
+}
+
+#[contractevent(topics = ["ArenaXEscrow_v1", "SLASHER_SET"])]
+pub struct SlasherSet {
+    pub slasher: Address,
+    pub allowed: bool,
+}
+
+#[contractevent(topics = ["ArenaXEscrow_v1", "CHEAT_REPORTED"])]
+pub struct CheatingReported {
+    pub match_id: BytesN<32>,
+    pub oracle: Address,
+}
+
+#[contractevent(topics = ["ArenaXEscrow_v1", "SETTLED"])]
+pub struct DisputeSettled {
+    pub match_id: BytesN<32>,
+    pub payout_a: i128,
+    pub payout_b: i128,
+    pub asset: Address,
+}
+
 pub fn emit_initialized(env: &Env, admin: &Address) {
     Initialized {
         admin: admin.clone(),
@@ -185,3 +216,74 @@ pub fn emit_emergency_withdraw(
     }
     .publish(env);
 }
+
+pub fn emit_anticheat_oracle_set(env: &Env, oracle: &Address) {
+    AntiCheatOracleSet {
+        oracle: oracle.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_creator_set(env: &Env, creator: &Address, allowed: bool) {
+    CreatorSet {
+        creator: creator.clone(),
+        allowed,
+    }
+    .publish(env);
+}
+
+pub fn emit_slasher_set(env: &Env, slasher: &Address, allowed: bool) {
+    SlasherSet {
+        slasher: slasher.clone(),
+        allowed,
+    }
+    .publish(env);
+}
+
+pub fn emit_cheating_reported(env: &Env, match_id: &BytesN<32>, oracle: &Address) {
+    CheatingReported {
+        match_id: match_id.clone(),
+        oracle: oracle.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_dispute_settled(
+    env: &Env,
+    match_id: &BytesN<32>,
+    payout_a: i128,
+    payout_b: i128,
+    asset: &Address,
+) {
+    DisputeSettled {
+        match_id: match_id.clone(),
+        payout_a,
+        payout_b,
+        asset: asset.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXEscrow_v1", "DISPUTE_FEE"])]
+pub struct DisputeFeeCollected {
+    pub match_id: BytesN<32>,
+    pub recipient: Address,
+    pub fee: i128,
+    pub asset: Address,
+}
+
+pub fn emit_dispute_fee_collected(
+    env: &Env,
+    match_id: &BytesN<32>,
+    recipient: &Address,
+    fee: i128,
+    asset: &Address,
+) {
+    DisputeFeeCollected {
+        match_id: match_id.clone(),
+        recipient: recipient.clone(),
+        fee,
+        asset: asset.clone(),
+    }
+    .publish(env);
+}
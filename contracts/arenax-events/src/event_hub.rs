@@ -0,0 +1,61 @@
+use soroban_sdk::{contractevent, Address, Bytes, BytesN, Env, Symbol};
+
+pub const NAMESPACE: &str = "ArenaXEventHub";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXHub_v1", "PUBLISHER"])]
+pub struct PublisherUpdated {
+    pub publisher: Address,
+    pub source: Symbol,
+    pub enabled: bool,
+}
+
+/// The normalized form of every event published through the hub.
+#[contractevent(topics = ["ArenaXHub_v1", "EVENT"])]
+pub struct HubEvent {
+    pub sequence: u64,
+    pub source: Symbol,
+    pub kind: Symbol,
+    pub publisher: Address,
+    pub timestamp: u64,
+    pub actor: Option<Address>,
+    pub subject_id: Option<BytesN<32>>,
+    pub amount: Option<i128>,
+    pub data: Bytes,
+}
+
+pub fn emit_publisher_updated(env: &Env, publisher: &Address, source: &Symbol, enabled: bool) {
+    PublisherUpdated {
+        publisher: publisher.clone(),
+        source: source.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_hub_event(
+    env: &Env,
+    sequence: u64,
+    source: &Symbol,
+    kind: &Symbol,
+    publisher: &Address,
+    timestamp: u64,
+    actor: &Option<Address>,
+    subject_id: &Option<BytesN<32>>,
+    amount: Option<i128>,
+    data: &Bytes,
+) {
+    HubEvent {
+        sequence,
+        source: source.clone(),
+        kind: kind.clone(),
+        publisher: publisher.clone(),
+        timestamp,
+        actor: actor.clone(),
+        subject_id: subject_id.clone(),
+        amount,
+        data: data.clone(),
+    }
+    .publish(env);
+}
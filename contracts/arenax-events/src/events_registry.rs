@@ -8,9 +8,9 @@
 //! unregistered event or a version mismatch.
 
 use crate::{
-    anti_cheat, auth_gateway, ax_token, contract_registry, dispute, escrow, governance, identity,
-    match_contract, match_lifecycle, player_reputation, prize_distribution, registry, reputation,
-    reputation_index, slashing, staking, tournament, virtual_economy,
+    achievements, anti_cheat, auth_gateway, ax_token, challenge, contract_registry, creator_revenue, dao, dispute, escrow, event_hub, governance, identity, insurance, leaderboard,
+    match_contract, match_lifecycle, matchmaking, multisig, player_reputation, price_oracle, prize_distribution, referral, registry, reputation,
+    reputation_index, season_pass, slashing, slashing_coordinator, stake_lending, staking, subscription, tournament, tournament_lifecycle, treasury, trophy, upgrade_manager, vesting, virtual_economy,
 };
 
 /// A single entry in the namespace registry.
@@ -23,24 +23,44 @@ pub struct NamespaceEntry {
 /// Every `(NAMESPACE, VERSION)` known to this crate. Keep alphabetical
 /// by namespace so new entries land in a stable place.
 pub const NAMESPACES: &[NamespaceEntry] = &[
+    NamespaceEntry { namespace: achievements::NAMESPACE, version: achievements::VERSION },
     NamespaceEntry { namespace: anti_cheat::NAMESPACE, version: anti_cheat::VERSION },
     NamespaceEntry { namespace: auth_gateway::NAMESPACE, version: auth_gateway::VERSION },
     NamespaceEntry { namespace: ax_token::NAMESPACE, version: ax_token::VERSION },
+    NamespaceEntry { namespace: challenge::NAMESPACE, version: challenge::VERSION },
     NamespaceEntry { namespace: contract_registry::NAMESPACE, version: contract_registry::VERSION },
+    NamespaceEntry { namespace: creator_revenue::NAMESPACE, version: creator_revenue::VERSION },
+    NamespaceEntry { namespace: dao::NAMESPACE, version: dao::VERSION },
     NamespaceEntry { namespace: dispute::NAMESPACE, version: dispute::VERSION },
     NamespaceEntry { namespace: escrow::NAMESPACE, version: escrow::VERSION },
+    NamespaceEntry { namespace: event_hub::NAMESPACE, version: event_hub::VERSION },
     NamespaceEntry { namespace: governance::NAMESPACE, version: governance::VERSION },
     NamespaceEntry { namespace: identity::NAMESPACE, version: identity::VERSION },
+    NamespaceEntry { namespace: insurance::NAMESPACE, version: insurance::VERSION },
+    NamespaceEntry { namespace: leaderboard::NAMESPACE, version: leaderboard::VERSION },
     NamespaceEntry { namespace: match_contract::NAMESPACE, version: match_contract::VERSION },
     NamespaceEntry { namespace: match_lifecycle::NAMESPACE, version: match_lifecycle::VERSION },
+    NamespaceEntry { namespace: matchmaking::NAMESPACE, version: matchmaking::VERSION },
+    NamespaceEntry { namespace: multisig::NAMESPACE, version: multisig::VERSION },
     NamespaceEntry { namespace: player_reputation::NAMESPACE, version: player_reputation::VERSION },
+    NamespaceEntry { namespace: price_oracle::NAMESPACE, version: price_oracle::VERSION },
     NamespaceEntry { namespace: prize_distribution::NAMESPACE, version: prize_distribution::VERSION },
+    NamespaceEntry { namespace: referral::NAMESPACE, version: referral::VERSION },
     NamespaceEntry { namespace: registry::NAMESPACE, version: registry::VERSION },
     NamespaceEntry { namespace: reputation::NAMESPACE, version: reputation::VERSION },
     NamespaceEntry { namespace: reputation_index::NAMESPACE, version: reputation_index::VERSION },
+    NamespaceEntry { namespace: season_pass::NAMESPACE, version: season_pass::VERSION },
     NamespaceEntry { namespace: slashing::NAMESPACE, version: slashing::VERSION },
+    NamespaceEntry { namespace: slashing_coordinator::NAMESPACE, version: slashing_coordinator::VERSION },
+    NamespaceEntry { namespace: stake_lending::NAMESPACE, version: stake_lending::VERSION },
     NamespaceEntry { namespace: staking::NAMESPACE, version: staking::VERSION },
+    NamespaceEntry { namespace: subscription::NAMESPACE, version: subscription::VERSION },
     NamespaceEntry { namespace: tournament::NAMESPACE, version: tournament::VERSION },
+    NamespaceEntry { namespace: tournament_lifecycle::NAMESPACE, version: tournament_lifecycle::VERSION },
+    NamespaceEntry { namespace: treasury::NAMESPACE, version: treasury::VERSION },
+    NamespaceEntry { namespace: trophy::NAMESPACE, version: trophy::VERSION },
+    NamespaceEntry { namespace: upgrade_manager::NAMESPACE, version: upgrade_manager::VERSION },
+    NamespaceEntry { namespace: vesting::NAMESPACE, version: vesting::VERSION },
     NamespaceEntry { namespace: virtual_economy::NAMESPACE, version: virtual_economy::VERSION },
 ];
 
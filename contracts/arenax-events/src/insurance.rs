@@ -0,0 +1,145 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol, Vec};
+
+pub const NAMESPACE: &str = "ArenaXInsurance";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXInsure_v1", "CONTRIB"])]
+pub struct ContributionReceived {
+    pub from: Address,
+    pub source: Symbol,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXInsure_v1", "COUNCIL"])]
+pub struct CouncilUpdated {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+}
+
+#[contractevent(topics = ["ArenaXInsure_v1", "INCIDENT"])]
+pub struct IncidentOpened {
+    pub incident_id: BytesN<32>,
+    pub cap: i128,
+}
+
+#[contractevent(topics = ["ArenaXInsure_v1", "INC_CLOSE"])]
+pub struct IncidentClosed {
+    pub incident_id: BytesN<32>,
+    pub paid: i128,
+}
+
+#[contractevent(topics = ["ArenaXInsure_v1", "FILED"])]
+pub struct ClaimFiled {
+    pub claim_id: u64,
+    pub incident_id: BytesN<32>,
+    pub claimant: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXInsure_v1", "VOTED"])]
+pub struct ClaimVoted {
+    pub claim_id: u64,
+    pub member: Address,
+    pub approve: bool,
+}
+
+#[contractevent(topics = ["ArenaXInsure_v1", "PAID"])]
+pub struct ClaimPaid {
+    pub claim_id: u64,
+    pub incident_id: BytesN<32>,
+    pub claimant: Address,
+    pub requested: i128,
+    pub paid: i128,
+}
+
+#[contractevent(topics = ["ArenaXInsure_v1", "REJECTED"])]
+pub struct ClaimRejected {
+    pub claim_id: u64,
+    pub incident_id: BytesN<32>,
+    pub claimant: Address,
+}
+
+pub fn emit_contribution_received(env: &Env, from: &Address, source: &Symbol, amount: i128) {
+    ContributionReceived {
+        from: from.clone(),
+        source: source.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_council_updated(env: &Env, members: &Vec<Address>, threshold: u32) {
+    CouncilUpdated {
+        members: members.clone(),
+        threshold,
+    }
+    .publish(env);
+}
+
+pub fn emit_incident_opened(env: &Env, incident_id: &BytesN<32>, cap: i128) {
+    IncidentOpened {
+        incident_id: incident_id.clone(),
+        cap,
+    }
+    .publish(env);
+}
+
+pub fn emit_incident_closed(env: &Env, incident_id: &BytesN<32>, paid: i128) {
+    IncidentClosed {
+        incident_id: incident_id.clone(),
+        paid,
+    }
+    .publish(env);
+}
+
+pub fn emit_claim_filed(
+    env: &Env,
+    claim_id: u64,
+    incident_id: &BytesN<32>,
+    claimant: &Address,
+    amount: i128,
+) {
+    ClaimFiled {
+        claim_id,
+        incident_id: incident_id.clone(),
+        claimant: claimant.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_claim_voted(env: &Env, claim_id: u64, member: &Address, approve: bool) {
+    ClaimVoted {
+        claim_id,
+        member: member.clone(),
+        approve,
+    }
+    .publish(env);
+}
+
+pub fn emit_claim_paid(
+    env: &Env,
+    claim_id: u64,
+    incident_id: &BytesN<32>,
+    claimant: &Address,
+    requested: i128,
+    paid: i128,
+) {
+    ClaimPaid {
+        claim_id,
+        incident_id: incident_id.clone(),
+        claimant: claimant.clone(),
+        requested,
+        paid,
+    }
+    .publish(env);
+}
+
+pub fn emit_claim_rejected(env: &Env, claim_id: u64, incident_id: &BytesN<32>, claimant: &Address) {
+    ClaimRejected {
+        claim_id,
+        incident_id: incident_id.clone(),
+        claimant: claimant.clone(),
+    }
+    .publish(env);
+}
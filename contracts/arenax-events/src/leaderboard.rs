@@ -0,0 +1,54 @@
+use soroban_sdk::{contractevent, Address, Env};
+
+pub const NAMESPACE: &str = "ArenaXLeaderboard";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXLB_v1", "PUBLISHER"])]
+pub struct PublisherUpdated {
+    pub publisher: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXLB_v1", "SEASON"])]
+pub struct SeasonStarted {
+    pub season: u32,
+}
+
+#[contractevent(topics = ["ArenaXLB_v1", "SCORE"])]
+pub struct ScoreUpdated {
+    pub category: u32,
+    pub season: u32,
+    pub player: Address,
+    pub score: i128,
+    pub publisher: Address,
+}
+
+pub fn emit_publisher_updated(env: &Env, publisher: &Address, enabled: bool) {
+    PublisherUpdated {
+        publisher: publisher.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_season_started(env: &Env, season: u32) {
+    SeasonStarted { season }.publish(env);
+}
+
+pub fn emit_score_updated(
+    env: &Env,
+    category: u32,
+    season: u32,
+    player: &Address,
+    score: i128,
+    publisher: &Address,
+) {
+    ScoreUpdated {
+        category,
+        season,
+        player: player.clone(),
+        score,
+        publisher: publisher.clone(),
+    }
+    .publish(env);
+}
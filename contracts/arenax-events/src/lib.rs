@@ -20,23 +20,44 @@
 
 #![no_std]
 
+pub mod achievements;
 pub mod anti_cheat;
 pub mod auth_gateway;
 pub mod ax_token;
+pub mod challenge;
 pub mod contract_registry;
+pub mod creator_revenue;
+pub mod dao;
 pub mod dispute;
 pub mod escrow;
+pub mod event_hub;
 pub mod governance;
 pub mod identity;
+pub mod insurance;
+pub mod leaderboard;
 pub mod match_contract;
 pub mod match_lifecycle;
+pub mod matchmaking;
+pub mod multisig;
 pub mod player_reputation;
+pub mod prize_distribution;
+pub mod price_oracle;
+pub mod referral;
 pub mod registry;
 pub mod reputation;
 pub mod reputation_index;
+pub mod season_pass;
 pub mod slashing;
+pub mod slashing_coordinator;
+pub mod stake_lending;
 pub mod staking;
+pub mod subscription;
 pub mod tournament;
+pub mod tournament_lifecycle;
+pub mod treasury;
+pub mod trophy;
+pub mod upgrade_manager;
+pub mod vesting;
 pub mod access_control;
 pub mod emergency_pause;
 pub mod time_lock;
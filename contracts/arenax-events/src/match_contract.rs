@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Address, BytesN, Env};
+use soroban_sdk::{contractevent, Address, BytesN, Env, Symbol};
 
 pub const NAMESPACE: &str = "ArenaXMatch";
 pub const VERSION: &str = "v1";
@@ -89,3 +89,104 @@ pub fn emit_match_resolved(env: &Env, match_id: &BytesN<32>, winner: &Address) {
     }
     .publish(env);
 }
+
+#[contractevent(topics = ["ArenaXMatch_v1", "RESULT"])]
+pub struct ResultSubmitted {
+    pub match_id: BytesN<32>,
+    pub reporter: Address,
+    pub winner: Address,
+}
+
+#[contractevent(topics = ["ArenaXMatch_v1", "IMPORTED"])]
+pub struct MatchImported {
+    pub match_id: BytesN<32>,
+    pub state: u32,
+}
+
+pub fn emit_result_submitted(
+    env: &Env,
+    match_id: &BytesN<32>,
+    reporter: &Address,
+    winner: &Address,
+) {
+    ResultSubmitted {
+        match_id: match_id.clone(),
+        reporter: reporter.clone(),
+        winner: winner.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_match_imported(env: &Env, match_id: &BytesN<32>, state: u32) {
+    MatchImported {
+        match_id: match_id.clone(),
+        state,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXMatch_v1", "METADATA"])]
+pub struct MatchMetadata {
+    pub match_id: BytesN<32>,
+    pub game_id: Symbol,
+    pub region: Symbol,
+    pub mode: Symbol,
+}
+
+#[contractevent(topics = ["ArenaXMatch_v1", "GAME_REGISTERED"])]
+pub struct GameRegistered {
+    pub game_id: Symbol,
+    pub max_score: u32,
+    pub round_count: u32,
+}
+
+#[contractevent(topics = ["ArenaXMatch_v1", "GAME_REMOVED"])]
+pub struct GameRemoved {
+    pub game_id: Symbol,
+}
+
+pub fn emit_match_metadata(
+    env: &Env,
+    match_id: &BytesN<32>,
+    game_id: &Symbol,
+    region: &Symbol,
+    mode: &Symbol,
+) {
+    MatchMetadata {
+        match_id: match_id.clone(),
+        game_id: game_id.clone(),
+        region: region.clone(),
+        mode: mode.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_game_registered(env: &Env, game_id: &Symbol, max_score: u32, round_count: u32) {
+    GameRegistered {
+        game_id: game_id.clone(),
+        max_score,
+        round_count,
+    }
+    .publish(env);
+}
+
+pub fn emit_game_removed(env: &Env, game_id: &Symbol) {
+    GameRemoved {
+        game_id: game_id.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXMatch_v1", "DISPUTE_SETTLED"])]
+pub struct DisputeSettled {
+    pub match_id: BytesN<32>,
+    pub outcome: u32,
+}
+
+pub fn emit_dispute_settled(env: &Env, match_id: &BytesN<32>, outcome: u32) {
+    DisputeSettled {
+        match_id: match_id.clone(),
+        outcome,
+    }
+    .publish(env);
+}
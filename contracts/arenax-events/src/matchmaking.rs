@@ -0,0 +1,107 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+pub const NAMESPACE: &str = "ArenaXMatchmaking";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXMM_v1", "TIER"])]
+pub struct TierAdded {
+    pub tier_id: u32,
+    pub asset: Address,
+    pub stake: i128,
+}
+
+#[contractevent(topics = ["ArenaXMM_v1", "TIER_ACT"])]
+pub struct TierActiveSet {
+    pub tier_id: u32,
+    pub active: bool,
+}
+
+#[contractevent(topics = ["ArenaXMM_v1", "MATCHER"])]
+pub struct MatcherUpdated {
+    pub matcher: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXMM_v1", "JOINED"])]
+pub struct QueueJoined {
+    pub player: Address,
+    pub tier_id: u32,
+    pub stake: i128,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXMM_v1", "LEFT"])]
+pub struct QueueLeft {
+    pub player: Address,
+    pub tier_id: u32,
+    pub refund: i128,
+    pub expired: bool,
+}
+
+#[contractevent(topics = ["ArenaXMM_v1", "PAIRED"])]
+pub struct PlayersPaired {
+    pub match_id: BytesN<32>,
+    pub tier_id: u32,
+    pub player_a: Address,
+    pub player_b: Address,
+    pub matcher: Address,
+}
+
+pub fn emit_tier_added(env: &Env, tier_id: u32, asset: &Address, stake: i128) {
+    TierAdded {
+        tier_id,
+        asset: asset.clone(),
+        stake,
+    }
+    .publish(env);
+}
+
+pub fn emit_tier_active_set(env: &Env, tier_id: u32, active: bool) {
+    TierActiveSet { tier_id, active }.publish(env);
+}
+
+pub fn emit_matcher_updated(env: &Env, matcher: &Address, enabled: bool) {
+    MatcherUpdated {
+        matcher: matcher.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_queue_joined(env: &Env, player: &Address, tier_id: u32, stake: i128, expires_at: u64) {
+    QueueJoined {
+        player: player.clone(),
+        tier_id,
+        stake,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_queue_left(env: &Env, player: &Address, tier_id: u32, refund: i128, expired: bool) {
+    QueueLeft {
+        player: player.clone(),
+        tier_id,
+        refund,
+        expired,
+    }
+    .publish(env);
+}
+
+pub fn emit_players_paired(
+    env: &Env,
+    match_id: &BytesN<32>,
+    tier_id: u32,
+    player_a: &Address,
+    player_b: &Address,
+    matcher: &Address,
+) {
+    PlayersPaired {
+        match_id: match_id.clone(),
+        tier_id,
+        player_a: player_a.clone(),
+        player_b: player_b.clone(),
+        matcher: matcher.clone(),
+    }
+    .publish(env);
+}
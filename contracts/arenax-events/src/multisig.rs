@@ -0,0 +1,147 @@
+use soroban_sdk::{contractevent, Address, Env, Symbol};
+
+pub const NAMESPACE: &str = "ArenaXMultisig";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXMsig_v1", "SUBMITTED"])]
+pub struct TransactionSubmitted {
+    pub tx_id: u64,
+    pub proposer: Address,
+    pub target: Address,
+    pub function: Symbol,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXMsig_v1", "APPROVED"])]
+pub struct TransactionApproved {
+    pub tx_id: u64,
+    pub signer: Address,
+    pub approvals: u32,
+    pub threshold: u32,
+}
+
+#[contractevent(topics = ["ArenaXMsig_v1", "REVOKED"])]
+pub struct ApprovalRevoked {
+    pub tx_id: u64,
+    pub signer: Address,
+    pub approvals: u32,
+}
+
+#[contractevent(topics = ["ArenaXMsig_v1", "EXECUTED"])]
+pub struct TransactionExecuted {
+    pub tx_id: u64,
+    pub executor: Address,
+    pub target: Address,
+    pub function: Symbol,
+}
+
+#[contractevent(topics = ["ArenaXMsig_v1", "CANCELLED"])]
+pub struct TransactionCancelled {
+    pub tx_id: u64,
+    pub cancelled_by: Address,
+}
+
+#[contractevent(topics = ["ArenaXMsig_v1", "SIGNER_ADD"])]
+pub struct SignerAdded {
+    pub signer: Address,
+    pub signer_count: u32,
+}
+
+#[contractevent(topics = ["ArenaXMsig_v1", "SIGNER_REM"])]
+pub struct SignerRemoved {
+    pub signer: Address,
+    pub signer_count: u32,
+}
+
+#[contractevent(topics = ["ArenaXMsig_v1", "THRESHOLD"])]
+pub struct ThresholdChanged {
+    pub old: u32,
+    pub new: u32,
+}
+
+pub fn emit_transaction_submitted(
+    env: &Env,
+    tx_id: u64,
+    proposer: &Address,
+    target: &Address,
+    function: &Symbol,
+    expires_at: u64,
+) {
+    TransactionSubmitted {
+        tx_id,
+        proposer: proposer.clone(),
+        target: target.clone(),
+        function: function.clone(),
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_transaction_approved(
+    env: &Env,
+    tx_id: u64,
+    signer: &Address,
+    approvals: u32,
+    threshold: u32,
+) {
+    TransactionApproved {
+        tx_id,
+        signer: signer.clone(),
+        approvals,
+        threshold,
+    }
+    .publish(env);
+}
+
+pub fn emit_approval_revoked(env: &Env, tx_id: u64, signer: &Address, approvals: u32) {
+    ApprovalRevoked {
+        tx_id,
+        signer: signer.clone(),
+        approvals,
+    }
+    .publish(env);
+}
+
+pub fn emit_transaction_executed(
+    env: &Env,
+    tx_id: u64,
+    executor: &Address,
+    target: &Address,
+    function: &Symbol,
+) {
+    TransactionExecuted {
+        tx_id,
+        executor: executor.clone(),
+        target: target.clone(),
+        function: function.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_transaction_cancelled(env: &Env, tx_id: u64, cancelled_by: &Address) {
+    TransactionCancelled {
+        tx_id,
+        cancelled_by: cancelled_by.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_signer_added(env: &Env, signer: &Address, signer_count: u32) {
+    SignerAdded {
+        signer: signer.clone(),
+        signer_count,
+    }
+    .publish(env);
+}
+
+pub fn emit_signer_removed(env: &Env, signer: &Address, signer_count: u32) {
+    SignerRemoved {
+        signer: signer.clone(),
+        signer_count,
+    }
+    .publish(env);
+}
+
+pub fn emit_threshold_changed(env: &Env, old: u32, new: u32) {
+    ThresholdChanged { old, new }.publish(env);
+}
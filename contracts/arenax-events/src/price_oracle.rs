@@ -0,0 +1,59 @@
+use soroban_sdk::{contractevent, Address, Env};
+
+pub const NAMESPACE: &str = "ArenaXPriceOracle";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXPrice_v1", "REPORTED"])]
+pub struct RateReported {
+    pub base: Address,
+    pub quote: Address,
+    pub reporter: Address,
+    pub rate: i128,
+    pub timestamp: u64,
+}
+
+#[contractevent(topics = ["ArenaXPrice_v1", "REPORTER"])]
+pub struct ReporterUpdated {
+    pub reporter: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXPrice_v1", "CONFIG"])]
+pub struct ConfigUpdated {
+    pub max_staleness: u64,
+    pub min_reports: u32,
+}
+
+pub fn emit_rate_reported(
+    env: &Env,
+    base: &Address,
+    quote: &Address,
+    reporter: &Address,
+    rate: i128,
+    timestamp: u64,
+) {
+    RateReported {
+        base: base.clone(),
+        quote: quote.clone(),
+        reporter: reporter.clone(),
+        rate,
+        timestamp,
+    }
+    .publish(env);
+}
+
+pub fn emit_reporter_updated(env: &Env, reporter: &Address, enabled: bool) {
+    ReporterUpdated {
+        reporter: reporter.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_config_updated(env: &Env, max_staleness: u64, min_reports: u32) {
+    ConfigUpdated {
+        max_staleness,
+        min_reports,
+    }
+    .publish(env);
+}
@@ -0,0 +1,105 @@
+use soroban_sdk::{contractevent, Address, Env, Symbol};
+
+pub const NAMESPACE: &str = "ArenaXReferral";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXRefer_v1", "CODE"])]
+pub struct CodeRegistered {
+    pub player: Address,
+    pub code: Symbol,
+}
+
+#[contractevent(topics = ["ArenaXRefer_v1", "BOUND"])]
+pub struct RefereeBound {
+    pub referee: Address,
+    pub referrer: Address,
+    pub code: Symbol,
+}
+
+#[contractevent(topics = ["ArenaXRefer_v1", "ACCRUED"])]
+pub struct RewardAccrued {
+    pub referrer: Address,
+    pub referee: Address,
+    pub reporter: Address,
+    pub fee: i128,
+    pub reward: i128,
+}
+
+#[contractevent(topics = ["ArenaXRefer_v1", "CLAIMED"])]
+pub struct RewardClaimed {
+    pub referrer: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXRefer_v1", "CONFIG"])]
+pub struct ConfigUpdated {
+    pub reward_bps: u32,
+    pub referee_cap: i128,
+    pub referrer_cap: i128,
+}
+
+#[contractevent(topics = ["ArenaXRefer_v1", "REPORTER"])]
+pub struct ReporterUpdated {
+    pub reporter: Address,
+    pub enabled: bool,
+}
+
+pub fn emit_code_registered(env: &Env, player: &Address, code: &Symbol) {
+    CodeRegistered {
+        player: player.clone(),
+        code: code.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_referee_bound(env: &Env, referee: &Address, referrer: &Address, code: &Symbol) {
+    RefereeBound {
+        referee: referee.clone(),
+        referrer: referrer.clone(),
+        code: code.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_reward_accrued(
+    env: &Env,
+    referrer: &Address,
+    referee: &Address,
+    reporter: &Address,
+    fee: i128,
+    reward: i128,
+) {
+    RewardAccrued {
+        referrer: referrer.clone(),
+        referee: referee.clone(),
+        reporter: reporter.clone(),
+        fee,
+        reward,
+    }
+    .publish(env);
+}
+
+pub fn emit_reward_claimed(env: &Env, referrer: &Address, amount: i128) {
+    RewardClaimed {
+        referrer: referrer.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_config_updated(env: &Env, reward_bps: u32, referee_cap: i128, referrer_cap: i128) {
+    ConfigUpdated {
+        reward_bps,
+        referee_cap,
+        referrer_cap,
+    }
+    .publish(env);
+}
+
+pub fn emit_reporter_updated(env: &Env, reporter: &Address, enabled: bool) {
+    ReporterUpdated {
+        reporter: reporter.clone(),
+        enabled,
+    }
+    .publish(env);
+}
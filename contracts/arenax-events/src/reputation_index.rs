@@ -1,4 +1,4 @@
-use soroban_sdk::{contractevent, Address, Env};
+use soroban_sdk::{contractevent, Address, Env, Vec};
 
 pub const NAMESPACE: &str = "ArenaXReputationIndex";
 pub const VERSION: &str = "v1";
@@ -47,3 +47,145 @@ pub fn emit_reputation_decayed(
     }
     .publish(env);
 }
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "WRITER_ADDED"])]
+pub struct WriterAdded {
+    pub writer: Address,
+    pub skill: bool,
+    pub fair_play: bool,
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "WRITER_REMOVED"])]
+pub struct WriterRemoved {
+    pub writer: Address,
+}
+
+pub fn emit_writer_added(env: &Env, writer: &Address, skill: bool, fair_play: bool) {
+    WriterAdded {
+        writer: writer.clone(),
+        skill,
+        fair_play,
+    }
+    .publish(env);
+}
+
+pub fn emit_writer_removed(env: &Env, writer: &Address) {
+    WriterRemoved {
+        writer: writer.clone(),
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "TIER_CHANGED"])]
+pub struct TierChanged {
+    pub player: Address,
+    pub old_tier: u32,
+    pub new_tier: u32,
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "TIER_CONFIG"])]
+pub struct TierConfigUpdated {
+    pub thresholds: Vec<i128>,
+    pub hysteresis: i128,
+}
+
+pub fn emit_tier_changed(env: &Env, player: &Address, old_tier: u32, new_tier: u32) {
+    TierChanged {
+        player: player.clone(),
+        old_tier,
+        new_tier,
+    }
+    .publish(env);
+}
+
+pub fn emit_tier_config_updated(env: &Env, thresholds: &Vec<i128>, hysteresis: i128) {
+    TierConfigUpdated {
+        thresholds: thresholds.clone(),
+        hysteresis,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "BATCH_MATCH"])]
+pub struct BatchMatchApplied {
+    pub writer: Address,
+    pub match_id: u64,
+    pub player_count: u32,
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "BATCH_UPDATED"])]
+pub struct BatchUpdated {
+    pub writer: Address,
+    pub match_count: u32,
+    pub update_count: u32,
+}
+
+pub fn emit_batch_match_applied(env: &Env, writer: &Address, match_id: u64, player_count: u32) {
+    BatchMatchApplied {
+        writer: writer.clone(),
+        match_id,
+        player_count,
+    }
+    .publish(env);
+}
+
+pub fn emit_batch_updated(env: &Env, writer: &Address, match_count: u32, update_count: u32) {
+    BatchUpdated {
+        writer: writer.clone(),
+        match_count,
+        update_count,
+    }
+    .publish(env);
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "FROZEN"])]
+pub struct ReputationFrozen {
+    pub player: Address,
+    pub match_id: u64,
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "UNFROZEN"])]
+pub struct ReputationUnfrozen {
+    pub player: Address,
+    pub match_id: u64,
+}
+
+#[contractevent(topics = ["ArenaXRepIdx_v1", "UPDATE_QUEUED"])]
+pub struct ReputationUpdateQueued {
+    pub player: Address,
+    pub skill_delta: i128,
+    pub fair_play_delta: i128,
+    pub match_id: u64,
+}
+
+pub fn emit_frozen(env: &Env, player: &Address, match_id: u64) {
+    ReputationFrozen {
+        player: player.clone(),
+        match_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_unfrozen(env: &Env, player: &Address, match_id: u64) {
+    ReputationUnfrozen {
+        player: player.clone(),
+        match_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_update_queued(
+    env: &Env,
+    player: &Address,
+    skill_delta: i128,
+    fair_play_delta: i128,
+    match_id: u64,
+) {
+    ReputationUpdateQueued {
+        player: player.clone(),
+        skill_delta,
+        fair_play_delta,
+        match_id,
+    }
+    .publish(env);
+}
@@ -0,0 +1,132 @@
+use soroban_sdk::{contractevent, Address, Env};
+
+pub const NAMESPACE: &str = "ArenaXSeasonPass";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXSeason_v1", "STARTED"])]
+pub struct SeasonStarted {
+    pub season_id: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub tier_count: u32,
+    pub premium_price: i128,
+}
+
+#[contractevent(topics = ["ArenaXSeason_v1", "ENDED"])]
+pub struct SeasonEnded {
+    pub season_id: u32,
+    pub end_time: u64,
+}
+
+#[contractevent(topics = ["ArenaXSeason_v1", "XP"])]
+pub struct XpGranted {
+    pub season_id: u32,
+    pub player: Address,
+    pub source: Address,
+    pub amount: u32,
+    pub total: u32,
+}
+
+#[contractevent(topics = ["ArenaXSeason_v1", "PREMIUM"])]
+pub struct PremiumPurchased {
+    pub season_id: u32,
+    pub player: Address,
+    pub price: i128,
+}
+
+#[contractevent(topics = ["ArenaXSeason_v1", "CLAIMED"])]
+pub struct RewardClaimed {
+    pub season_id: u32,
+    pub player: Address,
+    pub tier: u32,
+    pub premium: bool,
+    pub tokens: i128,
+    pub badge_token_id: Option<u64>,
+}
+
+#[contractevent(topics = ["ArenaXSeason_v1", "XP_SOURCE"])]
+pub struct XpSourceUpdated {
+    pub source: Address,
+    pub enabled: bool,
+}
+
+pub fn emit_season_started(
+    env: &Env,
+    season_id: u32,
+    start_time: u64,
+    end_time: u64,
+    tier_count: u32,
+    premium_price: i128,
+) {
+    SeasonStarted {
+        season_id,
+        start_time,
+        end_time,
+        tier_count,
+        premium_price,
+    }
+    .publish(env);
+}
+
+pub fn emit_season_ended(env: &Env, season_id: u32, end_time: u64) {
+    SeasonEnded {
+        season_id,
+        end_time,
+    }
+    .publish(env);
+}
+
+pub fn emit_xp_granted(
+    env: &Env,
+    season_id: u32,
+    player: &Address,
+    source: &Address,
+    amount: u32,
+    total: u32,
+) {
+    XpGranted {
+        season_id,
+        player: player.clone(),
+        source: source.clone(),
+        amount,
+        total,
+    }
+    .publish(env);
+}
+
+pub fn emit_premium_purchased(env: &Env, season_id: u32, player: &Address, price: i128) {
+    PremiumPurchased {
+        season_id,
+        player: player.clone(),
+        price,
+    }
+    .publish(env);
+}
+
+pub fn emit_reward_claimed(
+    env: &Env,
+    season_id: u32,
+    player: &Address,
+    tier: u32,
+    premium: bool,
+    tokens: i128,
+    badge_token_id: Option<u64>,
+) {
+    RewardClaimed {
+        season_id,
+        player: player.clone(),
+        tier,
+        premium,
+        tokens,
+        badge_token_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_xp_source_updated(env: &Env, source: &Address, enabled: bool) {
+    XpSourceUpdated {
+        source: source.clone(),
+        enabled,
+    }
+    .publish(env);
+}
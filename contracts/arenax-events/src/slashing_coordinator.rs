@@ -0,0 +1,144 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+pub const NAMESPACE: &str = "ArenaXSlashCoordinator";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "TARGETS"])]
+pub struct TargetsUpdated {
+    pub vault: Address,
+    pub staking_manager: Address,
+}
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "REPORTER"])]
+pub struct ReporterUpdated {
+    pub reporter: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "POLICY"])]
+pub struct PolicyUpdated {
+    pub violation: u32,
+    pub steps: u32,
+}
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "REPORTED"])]
+pub struct ViolationReported {
+    pub report_id: u64,
+    pub reporter: Address,
+    pub subject: Address,
+    pub violation: u32,
+    pub evidence_hash: BytesN<32>,
+    pub executable_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "APPEALED"])]
+pub struct ReportAppealed {
+    pub report_id: u64,
+    pub subject: Address,
+    pub reason_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "APPEAL_RES"])]
+pub struct AppealResolved {
+    pub report_id: u64,
+    pub upheld: bool,
+}
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "EXECUTED"])]
+pub struct PenaltyExecuted {
+    pub report_id: u64,
+    pub subject: Address,
+    pub penalty: u32,
+    pub amount: i128,
+    pub offense: u32,
+}
+
+#[contractevent(topics = ["ArenaXSlashC_v1", "DISMISSED"])]
+pub struct ReportDismissed {
+    pub report_id: u64,
+    pub subject: Address,
+}
+
+pub fn emit_targets_updated(env: &Env, vault: &Address, staking_manager: &Address) {
+    TargetsUpdated {
+        vault: vault.clone(),
+        staking_manager: staking_manager.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_reporter_updated(env: &Env, reporter: &Address, enabled: bool) {
+    ReporterUpdated {
+        reporter: reporter.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_policy_updated(env: &Env, violation: u32, steps: u32) {
+    PolicyUpdated { violation, steps }.publish(env);
+}
+
+pub fn emit_violation_reported(
+    env: &Env,
+    report_id: u64,
+    reporter: &Address,
+    subject: &Address,
+    violation: u32,
+    evidence_hash: &BytesN<32>,
+    executable_at: u64,
+) {
+    ViolationReported {
+        report_id,
+        reporter: reporter.clone(),
+        subject: subject.clone(),
+        violation,
+        evidence_hash: evidence_hash.clone(),
+        executable_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_report_appealed(
+    env: &Env,
+    report_id: u64,
+    subject: &Address,
+    reason_hash: &BytesN<32>,
+) {
+    ReportAppealed {
+        report_id,
+        subject: subject.clone(),
+        reason_hash: reason_hash.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_appeal_resolved(env: &Env, report_id: u64, upheld: bool) {
+    AppealResolved { report_id, upheld }.publish(env);
+}
+
+pub fn emit_penalty_executed(
+    env: &Env,
+    report_id: u64,
+    subject: &Address,
+    penalty: u32,
+    amount: i128,
+    offense: u32,
+) {
+    PenaltyExecuted {
+        report_id,
+        subject: subject.clone(),
+        penalty,
+        amount,
+        offense,
+    }
+    .publish(env);
+}
+
+pub fn emit_report_dismissed(env: &Env, report_id: u64, subject: &Address) {
+    ReportDismissed {
+        report_id,
+        subject: subject.clone(),
+    }
+    .publish(env);
+}
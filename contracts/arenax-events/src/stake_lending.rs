@@ -0,0 +1,182 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+pub const NAMESPACE: &str = "ArenaXStakeLending";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXLend_v1", "REPORTER"])]
+pub struct ReporterUpdated {
+    pub reporter: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXLend_v1", "OFFERED"])]
+pub struct LoanOffered {
+    pub loan_id: u64,
+    pub backer: Address,
+    pub principal: i128,
+    pub profit_share_bps: u32,
+    pub expires_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXLend_v1", "WITHDRAWN"])]
+pub struct OfferWithdrawn {
+    pub loan_id: u64,
+    pub backer: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXLend_v1", "ACCEPTED"])]
+pub struct LoanAccepted {
+    pub loan_id: u64,
+    pub player: Address,
+    pub tournament_id: BytesN<32>,
+    pub principal: i128,
+}
+
+#[contractevent(topics = ["ArenaXLend_v1", "PAYOUT"])]
+pub struct PayoutSplit {
+    pub loan_id: u64,
+    pub player: Address,
+    pub amount: i128,
+    pub to_principal: i128,
+    pub backer_profit: i128,
+    pub player_profit: i128,
+}
+
+#[contractevent(topics = ["ArenaXLend_v1", "RECLAIMED"])]
+pub struct StakeReclaimed {
+    pub loan_id: u64,
+    pub player: Address,
+    pub amount: i128,
+    pub to_principal: i128,
+    pub to_player: i128,
+}
+
+#[contractevent(topics = ["ArenaXLend_v1", "REPAID"])]
+pub struct LoanRepaid {
+    pub loan_id: u64,
+    pub player: Address,
+    pub principal: i128,
+}
+
+#[contractevent(topics = ["ArenaXLend_v1", "DEFAULTED"])]
+pub struct LoanDefaulted {
+    pub loan_id: u64,
+    pub player: Address,
+    pub slashed: i128,
+    pub outstanding: i128,
+}
+
+pub fn emit_reporter_updated(env: &Env, reporter: &Address, enabled: bool) {
+    ReporterUpdated {
+        reporter: reporter.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_loan_offered(
+    env: &Env,
+    loan_id: u64,
+    backer: &Address,
+    principal: i128,
+    profit_share_bps: u32,
+    expires_at: u64,
+) {
+    LoanOffered {
+        loan_id,
+        backer: backer.clone(),
+        principal,
+        profit_share_bps,
+        expires_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_offer_withdrawn(env: &Env, loan_id: u64, backer: &Address, amount: i128) {
+    OfferWithdrawn {
+        loan_id,
+        backer: backer.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_loan_accepted(
+    env: &Env,
+    loan_id: u64,
+    player: &Address,
+    tournament_id: &BytesN<32>,
+    principal: i128,
+) {
+    LoanAccepted {
+        loan_id,
+        player: player.clone(),
+        tournament_id: tournament_id.clone(),
+        principal,
+    }
+    .publish(env);
+}
+
+pub fn emit_payout_split(
+    env: &Env,
+    loan_id: u64,
+    player: &Address,
+    amount: i128,
+    to_principal: i128,
+    backer_profit: i128,
+    player_profit: i128,
+) {
+    PayoutSplit {
+        loan_id,
+        player: player.clone(),
+        amount,
+        to_principal,
+        backer_profit,
+        player_profit,
+    }
+    .publish(env);
+}
+
+pub fn emit_stake_reclaimed(
+    env: &Env,
+    loan_id: u64,
+    player: &Address,
+    amount: i128,
+    to_principal: i128,
+    to_player: i128,
+) {
+    StakeReclaimed {
+        loan_id,
+        player: player.clone(),
+        amount,
+        to_principal,
+        to_player,
+    }
+    .publish(env);
+}
+
+pub fn emit_loan_repaid(env: &Env, loan_id: u64, player: &Address, principal: i128) {
+    LoanRepaid {
+        loan_id,
+        player: player.clone(),
+        principal,
+    }
+    .publish(env);
+}
+
+pub fn emit_loan_defaulted(
+    env: &Env,
+    loan_id: u64,
+    player: &Address,
+    slashed: i128,
+    outstanding: i128,
+) {
+    LoanDefaulted {
+        loan_id,
+        player: player.clone(),
+        slashed,
+        outstanding,
+    }
+    .publish(env);
+}
@@ -24,6 +24,12 @@ pub struct DisputeContractSet {
     pub contract: Address,
 }
 
+#[contractevent(topics = ["ArenaXStake_v1", "SLASHER_SET"])]
+pub struct SlasherSet {
+    pub slasher: Address,
+    pub allowed: bool,
+}
+
 #[contractevent(topics = ["ArenaXStake_v1", "STAKED"])]
 pub struct Staked {
     pub user: Address,
@@ -93,6 +99,14 @@ pub fn emit_dispute_contract_set(env: &Env, contract: &Address) {
     .publish(env);
 }
 
+pub fn emit_slasher_set(env: &Env, slasher: &Address, allowed: bool) {
+    SlasherSet {
+        slasher: slasher.clone(),
+        allowed,
+    }
+    .publish(env);
+}
+
 pub fn emit_staked(env: &Env, user: &Address, tournament_id: &BytesN<32>, amount: i128) {
     Staked {
         user: user.clone(),
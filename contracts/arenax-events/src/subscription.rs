@@ -0,0 +1,84 @@
+use soroban_sdk::{contractevent, Address, Env};
+
+pub const NAMESPACE: &str = "ArenaXSubscription";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXSub_v1", "TIER"])]
+pub struct TierUpdated {
+    pub tier_id: u32,
+    pub price_per_period: i128,
+    pub active: bool,
+}
+
+#[contractevent(topics = ["ArenaXSub_v1", "SUBSCRIBED"])]
+pub struct Subscribed {
+    pub subscriber: Address,
+    pub tier_id: u32,
+    pub periods: u32,
+    pub locked: i128,
+    pub ends_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXSub_v1", "CLAIMED"])]
+pub struct PeriodsClaimed {
+    pub subscriber: Address,
+    pub periods: u32,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXSub_v1", "CANCELLED"])]
+pub struct SubscriptionCancelled {
+    pub subscriber: Address,
+    pub refunded_periods: u32,
+    pub refund: i128,
+}
+
+pub fn emit_tier_updated(env: &Env, tier_id: u32, price_per_period: i128, active: bool) {
+    TierUpdated {
+        tier_id,
+        price_per_period,
+        active,
+    }
+    .publish(env);
+}
+
+pub fn emit_subscribed(
+    env: &Env,
+    subscriber: &Address,
+    tier_id: u32,
+    periods: u32,
+    locked: i128,
+    ends_at: u64,
+) {
+    Subscribed {
+        subscriber: subscriber.clone(),
+        tier_id,
+        periods,
+        locked,
+        ends_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_periods_claimed(env: &Env, subscriber: &Address, periods: u32, amount: i128) {
+    PeriodsClaimed {
+        subscriber: subscriber.clone(),
+        periods,
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_subscription_cancelled(
+    env: &Env,
+    subscriber: &Address,
+    refunded_periods: u32,
+    refund: i128,
+) {
+    SubscriptionCancelled {
+        subscriber: subscriber.clone(),
+        refunded_periods,
+        refund,
+    }
+    .publish(env);
+}
@@ -0,0 +1,143 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+pub const NAMESPACE: &str = "ArenaXTournamentLifecycle";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXTournLc_v1", "CREATED"])]
+pub struct TournamentCreated {
+    pub tournament_id: BytesN<32>,
+    pub organizer: Address,
+    pub max_players: u32,
+    pub prize_amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXTournLc_v1", "REGISTERED"])]
+pub struct PlayerRegistered {
+    pub tournament_id: BytesN<32>,
+    pub player: Address,
+    pub seed: u32,
+}
+
+#[contractevent(topics = ["ArenaXTournLc_v1", "STARTED"])]
+pub struct TournamentStarted {
+    pub tournament_id: BytesN<32>,
+    pub player_count: u32,
+    pub bracket_size: u32,
+}
+
+#[contractevent(topics = ["ArenaXTournLc_v1", "ROUND"])]
+pub struct RoundStarted {
+    pub tournament_id: BytesN<32>,
+    pub round: u32,
+    pub matches: u32,
+}
+
+#[contractevent(topics = ["ArenaXTournLc_v1", "SLOT_DECIDED"])]
+pub struct SlotDecided {
+    pub tournament_id: BytesN<32>,
+    pub round: u32,
+    pub slot: u32,
+    pub winner: Address,
+    pub walkover: bool,
+}
+
+#[contractevent(topics = ["ArenaXTournLc_v1", "COMPLETED"])]
+pub struct TournamentCompleted {
+    pub tournament_id: BytesN<32>,
+    pub champion: Address,
+    pub pool_id: Option<u64>,
+}
+
+#[contractevent(topics = ["ArenaXTournLc_v1", "CANCELLED"])]
+pub struct TournamentCancelled {
+    pub tournament_id: BytesN<32>,
+    pub refunded: i128,
+}
+
+pub fn emit_tournament_created(
+    env: &Env,
+    tournament_id: &BytesN<32>,
+    organizer: &Address,
+    max_players: u32,
+    prize_amount: i128,
+) {
+    TournamentCreated {
+        tournament_id: tournament_id.clone(),
+        organizer: organizer.clone(),
+        max_players,
+        prize_amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_player_registered(env: &Env, tournament_id: &BytesN<32>, player: &Address, seed: u32) {
+    PlayerRegistered {
+        tournament_id: tournament_id.clone(),
+        player: player.clone(),
+        seed,
+    }
+    .publish(env);
+}
+
+pub fn emit_tournament_started(
+    env: &Env,
+    tournament_id: &BytesN<32>,
+    player_count: u32,
+    bracket_size: u32,
+) {
+    TournamentStarted {
+        tournament_id: tournament_id.clone(),
+        player_count,
+        bracket_size,
+    }
+    .publish(env);
+}
+
+pub fn emit_round_started(env: &Env, tournament_id: &BytesN<32>, round: u32, matches: u32) {
+    RoundStarted {
+        tournament_id: tournament_id.clone(),
+        round,
+        matches,
+    }
+    .publish(env);
+}
+
+pub fn emit_slot_decided(
+    env: &Env,
+    tournament_id: &BytesN<32>,
+    round: u32,
+    slot: u32,
+    winner: &Address,
+    walkover: bool,
+) {
+    SlotDecided {
+        tournament_id: tournament_id.clone(),
+        round,
+        slot,
+        winner: winner.clone(),
+        walkover,
+    }
+    .publish(env);
+}
+
+pub fn emit_tournament_completed(
+    env: &Env,
+    tournament_id: &BytesN<32>,
+    champion: &Address,
+    pool_id: Option<u64>,
+) {
+    TournamentCompleted {
+        tournament_id: tournament_id.clone(),
+        champion: champion.clone(),
+        pool_id,
+    }
+    .publish(env);
+}
+
+pub fn emit_tournament_cancelled(env: &Env, tournament_id: &BytesN<32>, refunded: i128) {
+    TournamentCancelled {
+        tournament_id: tournament_id.clone(),
+        refunded,
+    }
+    .publish(env);
+}
@@ -0,0 +1,142 @@
+use soroban_sdk::{contractevent, Address, Env, String, Symbol, Vec};
+
+pub const NAMESPACE: &str = "ArenaXTreasury";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXTreas_v1", "INCOME"])]
+pub struct IncomeRecorded {
+    pub asset: Address,
+    pub source: Symbol,
+    pub from: Option<Address>,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXTreas_v1", "COUNCIL"])]
+pub struct CouncilUpdated {
+    pub members: Vec<Address>,
+    pub threshold: u32,
+    pub timelock: u64,
+}
+
+#[contractevent(topics = ["ArenaXTreas_v1", "PROPOSED"])]
+pub struct SpendProposed {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub asset: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub memo: String,
+}
+
+#[contractevent(topics = ["ArenaXTreas_v1", "APPROVED"])]
+pub struct SpendApproved {
+    pub proposal_id: u64,
+    pub approver: Address,
+    pub approvals: u32,
+}
+
+#[contractevent(topics = ["ArenaXTreas_v1", "QUEUED"])]
+pub struct SpendQueued {
+    pub proposal_id: u64,
+    pub executable_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXTreas_v1", "EXECUTED"])]
+pub struct SpendExecuted {
+    pub proposal_id: u64,
+    pub asset: Address,
+    pub recipient: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXTreas_v1", "CANCELLED"])]
+pub struct SpendCancelled {
+    pub proposal_id: u64,
+    pub cancelled_by: Address,
+}
+
+pub fn emit_income_recorded(
+    env: &Env,
+    asset: &Address,
+    source: &Symbol,
+    from: Option<Address>,
+    amount: i128,
+) {
+    IncomeRecorded {
+        asset: asset.clone(),
+        source: source.clone(),
+        from,
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_council_updated(env: &Env, members: &Vec<Address>, threshold: u32, timelock: u64) {
+    CouncilUpdated {
+        members: members.clone(),
+        threshold,
+        timelock,
+    }
+    .publish(env);
+}
+
+pub fn emit_spend_proposed(
+    env: &Env,
+    proposal_id: u64,
+    proposer: &Address,
+    asset: &Address,
+    recipient: &Address,
+    amount: i128,
+    memo: &String,
+) {
+    SpendProposed {
+        proposal_id,
+        proposer: proposer.clone(),
+        asset: asset.clone(),
+        recipient: recipient.clone(),
+        amount,
+        memo: memo.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_spend_approved(env: &Env, proposal_id: u64, approver: &Address, approvals: u32) {
+    SpendApproved {
+        proposal_id,
+        approver: approver.clone(),
+        approvals,
+    }
+    .publish(env);
+}
+
+pub fn emit_spend_queued(env: &Env, proposal_id: u64, executable_at: u64) {
+    SpendQueued {
+        proposal_id,
+        executable_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_spend_executed(
+    env: &Env,
+    proposal_id: u64,
+    asset: &Address,
+    recipient: &Address,
+    amount: i128,
+) {
+    SpendExecuted {
+        proposal_id,
+        asset: asset.clone(),
+        recipient: recipient.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_spend_cancelled(env: &Env, proposal_id: u64, cancelled_by: &Address) {
+    SpendCancelled {
+        proposal_id,
+        cancelled_by: cancelled_by.clone(),
+    }
+    .publish(env);
+}
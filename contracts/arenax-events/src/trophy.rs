@@ -0,0 +1,86 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+pub const NAMESPACE: &str = "ArenaXTrophy";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXTrophy_v1", "MINTED"])]
+pub struct TrophyMinted {
+    pub token_id: u64,
+    pub owner: Address,
+    pub minter: Address,
+    pub tournament_id: BytesN<32>,
+    pub placement: u32,
+    pub season: u32,
+    pub soulbound: bool,
+}
+
+#[contractevent(topics = ["ArenaXTrophy_v1", "TRANSFER"])]
+pub struct TrophyTransferred {
+    pub token_id: u64,
+    pub from: Address,
+    pub to: Address,
+}
+
+#[contractevent(topics = ["ArenaXTrophy_v1", "MINTER"])]
+pub struct MinterUpdated {
+    pub minter: Address,
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXTrophy_v1", "SOULBOUND"])]
+pub struct SoulboundModeUpdated {
+    pub enabled: bool,
+}
+
+#[contractevent(topics = ["ArenaXTrophy_v1", "SEASON"])]
+pub struct SeasonUpdated {
+    pub season: u32,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_trophy_minted(
+    env: &Env,
+    token_id: u64,
+    owner: &Address,
+    minter: &Address,
+    tournament_id: &BytesN<32>,
+    placement: u32,
+    season: u32,
+    soulbound: bool,
+) {
+    TrophyMinted {
+        token_id,
+        owner: owner.clone(),
+        minter: minter.clone(),
+        tournament_id: tournament_id.clone(),
+        placement,
+        season,
+        soulbound,
+    }
+    .publish(env);
+}
+
+pub fn emit_trophy_transferred(env: &Env, token_id: u64, from: &Address, to: &Address) {
+    TrophyTransferred {
+        token_id,
+        from: from.clone(),
+        to: to.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_minter_updated(env: &Env, minter: &Address, enabled: bool) {
+    MinterUpdated {
+        minter: minter.clone(),
+        enabled,
+    }
+    .publish(env);
+}
+
+pub fn emit_soulbound_mode_updated(env: &Env, enabled: bool) {
+    SoulboundModeUpdated { enabled }.publish(env);
+}
+
+pub fn emit_season_updated(env: &Env, season: u32) {
+    SeasonUpdated { season }.publish(env);
+}
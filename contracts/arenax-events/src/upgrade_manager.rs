@@ -0,0 +1,123 @@
+use soroban_sdk::{contractevent, Address, BytesN, Env};
+
+pub const NAMESPACE: &str = "ArenaXUpgradeManager";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXUpgM_v1", "GUARDIAN"])]
+pub struct GuardianUpdated {
+    pub guardian: Address,
+}
+
+#[contractevent(topics = ["ArenaXUpgM_v1", "REGISTERED"])]
+pub struct ContractRegistered {
+    pub contract: Address,
+    pub wasm_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["ArenaXUpgM_v1", "PROPOSED"])]
+pub struct UpgradeProposed {
+    pub proposal_id: u64,
+    pub contract: Address,
+    pub wasm_hash: BytesN<32>,
+    pub executable_at: u64,
+}
+
+#[contractevent(topics = ["ArenaXUpgM_v1", "CANCELLED"])]
+pub struct UpgradeCancelled {
+    pub proposal_id: u64,
+    pub contract: Address,
+    pub cancelled_by: Address,
+}
+
+#[contractevent(topics = ["ArenaXUpgM_v1", "UPGRADED"])]
+pub struct ContractUpgraded {
+    pub proposal_id: u64,
+    pub contract: Address,
+    pub old_hash: BytesN<32>,
+    pub new_hash: BytesN<32>,
+}
+
+#[contractevent(topics = ["ArenaXUpgM_v1", "ROLLBACK"])]
+pub struct ContractRolledBack {
+    pub contract: Address,
+    pub from_hash: BytesN<32>,
+    pub to_hash: BytesN<32>,
+    pub initiator: Address,
+}
+
+pub fn emit_guardian_updated(env: &Env, guardian: &Address) {
+    GuardianUpdated {
+        guardian: guardian.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_contract_registered(env: &Env, contract: &Address, wasm_hash: &BytesN<32>) {
+    ContractRegistered {
+        contract: contract.clone(),
+        wasm_hash: wasm_hash.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_upgrade_proposed(
+    env: &Env,
+    proposal_id: u64,
+    contract: &Address,
+    wasm_hash: &BytesN<32>,
+    executable_at: u64,
+) {
+    UpgradeProposed {
+        proposal_id,
+        contract: contract.clone(),
+        wasm_hash: wasm_hash.clone(),
+        executable_at,
+    }
+    .publish(env);
+}
+
+pub fn emit_upgrade_cancelled(
+    env: &Env,
+    proposal_id: u64,
+    contract: &Address,
+    cancelled_by: &Address,
+) {
+    UpgradeCancelled {
+        proposal_id,
+        contract: contract.clone(),
+        cancelled_by: cancelled_by.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_contract_upgraded(
+    env: &Env,
+    proposal_id: u64,
+    contract: &Address,
+    old_hash: &BytesN<32>,
+    new_hash: &BytesN<32>,
+) {
+    ContractUpgraded {
+        proposal_id,
+        contract: contract.clone(),
+        old_hash: old_hash.clone(),
+        new_hash: new_hash.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_contract_rolled_back(
+    env: &Env,
+    contract: &Address,
+    from_hash: &BytesN<32>,
+    to_hash: &BytesN<32>,
+    initiator: &Address,
+) {
+    ContractRolledBack {
+        contract: contract.clone(),
+        from_hash: from_hash.clone(),
+        to_hash: to_hash.clone(),
+        initiator: initiator.clone(),
+    }
+    .publish(env);
+}
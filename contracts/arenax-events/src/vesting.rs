@@ -0,0 +1,93 @@
+use soroban_sdk::{contractevent, Address, Env};
+
+pub const NAMESPACE: &str = "ArenaXVesting";
+pub const VERSION: &str = "v1";
+
+#[contractevent(topics = ["ArenaXVest_v1", "CREATED"])]
+pub struct ScheduleCreated {
+    pub schedule_id: u64,
+    pub beneficiary: Address,
+    pub total: i128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub revocable: bool,
+}
+
+#[contractevent(topics = ["ArenaXVest_v1", "CLAIMED"])]
+pub struct TokensClaimed {
+    pub schedule_id: u64,
+    pub beneficiary: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["ArenaXVest_v1", "REVOKED"])]
+pub struct ScheduleRevoked {
+    pub schedule_id: u64,
+    pub beneficiary: Address,
+    pub vested: i128,
+    pub returned: i128,
+    pub treasury: Address,
+}
+
+#[contractevent(topics = ["ArenaXVest_v1", "TREASURY"])]
+pub struct TreasuryUpdated {
+    pub treasury: Address,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn emit_schedule_created(
+    env: &Env,
+    schedule_id: u64,
+    beneficiary: &Address,
+    total: i128,
+    start_time: u64,
+    cliff: u64,
+    duration: u64,
+    revocable: bool,
+) {
+    ScheduleCreated {
+        schedule_id,
+        beneficiary: beneficiary.clone(),
+        total,
+        start_time,
+        cliff,
+        duration,
+        revocable,
+    }
+    .publish(env);
+}
+
+pub fn emit_tokens_claimed(env: &Env, schedule_id: u64, beneficiary: &Address, amount: i128) {
+    TokensClaimed {
+        schedule_id,
+        beneficiary: beneficiary.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+pub fn emit_schedule_revoked(
+    env: &Env,
+    schedule_id: u64,
+    beneficiary: &Address,
+    vested: i128,
+    returned: i128,
+    treasury: &Address,
+) {
+    ScheduleRevoked {
+        schedule_id,
+        beneficiary: beneficiary.clone(),
+        vested,
+        returned,
+        treasury: treasury.clone(),
+    }
+    .publish(env);
+}
+
+pub fn emit_treasury_updated(env: &Env, treasury: &Address) {
+    TreasuryUpdated {
+        treasury: treasury.clone(),
+    }
+    .publish(env);
+}
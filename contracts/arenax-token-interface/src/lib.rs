@@ -0,0 +1,37 @@
+#![no_std]
+
+//! Token interface shared by every ArenaX contract that moves tokens (staking manager,
+//! escrow vault, prize distribution).
+//!
+//! The interface is the SEP-41 token surface, so the AX token and any Stellar Asset
+//! Contract both conform and a downstream contract can switch tokens by address alone.
+//! Downstream crates import it as `use arenax_token_interface as token;` and keep
+//! calling `token::Client::new(..)`.
+
+use soroban_sdk::{contractclient, Address, Env, String};
+
+#[contractclient(name = "ArenaXTokenClient")]
+pub trait ArenaXTokenInterface {
+    fn allowance(env: Env, from: Address, spender: Address) -> i128;
+
+    fn approve(env: Env, from: Address, spender: Address, amount: i128, expiration_ledger: u32);
+
+    fn balance(env: Env, id: Address) -> i128;
+
+    fn transfer(env: Env, from: Address, to: Address, amount: i128);
+
+    fn transfer_from(env: Env, spender: Address, from: Address, to: Address, amount: i128);
+
+    fn burn(env: Env, from: Address, amount: i128);
+
+    fn burn_from(env: Env, spender: Address, from: Address, amount: i128);
+
+    fn decimals(env: Env) -> u32;
+
+    fn name(env: Env) -> String;
+
+    fn symbol(env: Env) -> String;
+}
+
+/// Alias matching `soroban_sdk::token::Client`.
+pub type Client<'a> = ArenaXTokenClient<'a>;
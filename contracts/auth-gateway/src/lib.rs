@@ -11,6 +11,7 @@ pub enum DataKey {
     Role(Address),
     ContractWhitelist(Address),
     Paused,
+    Banned(Address),
 }
 
 #[contract]
@@ -157,6 +158,55 @@ impl AuthGateway {
         events::emit_contract_removed(&env, &contract_address, &admin);
     }
 
+    /// Add an address to the denylist consulted by compliance-aware contracts
+    /// (e.g. the AX token)
+    ///
+    /// # Arguments
+    /// * `address` - The address to ban
+    ///
+    /// # Panics
+    /// * If contract is paused
+    /// * If caller is not admin
+    /// * If address is already banned
+    pub fn ban_address(env: Env, address: Address) {
+        Self::require_admin(&env);
+        Self::require_not_paused(&env);
+
+        if Self::is_banned(env.clone(), address.clone()) {
+            panic!("address already banned");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Banned(address.clone()), &true);
+
+        events::emit_address_banned(&env, &address, &Self::get_admin(env.clone()));
+    }
+
+    /// Remove an address from the denylist
+    ///
+    /// # Arguments
+    /// * `address` - The address to unban
+    ///
+    /// # Panics
+    /// * If contract is paused
+    /// * If caller is not admin
+    /// * If address is not banned
+    pub fn unban_address(env: Env, address: Address) {
+        Self::require_admin(&env);
+        Self::require_not_paused(&env);
+
+        if !Self::is_banned(env.clone(), address.clone()) {
+            panic!("address not banned");
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Banned(address.clone()));
+
+        events::emit_address_unbanned(&env, &address, &Self::get_admin(env.clone()));
+    }
+
     /// Pause/unpause the contract
     ///
     /// # Arguments
@@ -226,6 +276,17 @@ impl AuthGateway {
             .has(&DataKey::ContractWhitelist(contract_address))
     }
 
+    /// Check if an address is on the denylist
+    ///
+    /// # Arguments
+    /// * `address` - The address to check
+    ///
+    /// # Returns
+    /// True if the address is banned, false otherwise
+    pub fn is_banned(env: Env, address: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Banned(address))
+    }
+
     /// Get the admin address
     ///
     /// # Returns
@@ -1,7 +1,27 @@
 #![no_std]
 
 use arenax_events::ax_token as events;
-use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, Address, Env, IntoVal, String, Vec,
+};
+
+pub const DECIMALS: u32 = 7;
+pub const NAME: &str = "ArenaX Token";
+pub const SYMBOL: &str = "AX";
+
+const DAY_IN_LEDGERS: u32 = 17_280;
+const BALANCE_BUMP_AMOUNT: u32 = 30 * DAY_IN_LEDGERS;
+const BALANCE_LIFETIME_THRESHOLD: u32 = BALANCE_BUMP_AMOUNT - DAY_IN_LEDGERS;
+
+/// Upper bound on epochs released by a single schedule per `mint_epoch` call.
+pub const MAX_EPOCHS_PER_MINT: u32 = 100;
+
+/// Hard cap on the transfer fee the admin can configure (5%).
+pub const MAX_TRANSFER_FEE_BPS: u32 = 500;
+const BPS_DENOMINATOR: i128 = 10_000;
+
+/// Maximum recipients per `batch_transfer` / `airdrop` invocation.
+pub const MAX_BATCH_SIZE: u32 = 200;
 
 #[derive(Clone)]
 #[contracttype]
@@ -9,6 +29,75 @@ pub enum DataKey {
     Admin,
     Balance(Address),
     TotalSupply,
+    Allowance(Address, Address), // (from, spender) -> AllowanceValue, temporary storage
+    MaxSupply,
+    EmissionCount,
+    Emission(u32),    // schedule_id -> EmissionSchedule
+    EmissionReserved, // supply committed to schedules but not yet minted
+    Frozen(Address),
+    ClawbackAdmin,
+    Locked(Address), // total unreleased vesting balance
+    VestingCount(Address),
+    Vesting(Address, u32), // (holder, schedule_id) -> VestingSchedule
+    TransferFeeBps,
+    Treasury,
+    FeeExempt(Address),
+    PermitNonce(Address),
+    CheckpointCount(Address),
+    Checkpoint(Address, u32), // (holder, index) -> Checkpoint
+    SupplyCheckpointCount,
+    SupplyCheckpoint(u32),
+    ComplianceSource,
+    ComplianceOverride(Address),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct AllowanceValue {
+    pub amount: i128,
+    pub expiration_ledger: u32,
+}
+
+/// Epoch-based mint schedule. Epoch `n` (zero-based) matures at
+/// `start_time + (n + 1) * epoch_length`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmissionSchedule {
+    pub schedule_id: u32,
+    pub recipient: Address,
+    pub amount_per_epoch: i128,
+    pub epoch_length: u64,
+    pub start_time: u64,
+    pub total_epochs: u32,
+    pub epochs_minted: u32,
+}
+
+/// Linear vesting of locked tokens. Nothing vests before `start_time + cliff`; the full
+/// amount has vested at `start_time + duration`.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct VestingSchedule {
+    pub schedule_id: u32,
+    pub total: i128,
+    pub released: i128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
+}
+
+/// Value of a balance or of the total supply from `ledger` onwards.
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct Checkpoint {
+    pub ledger: u32,
+    pub value: i128,
+}
+
+/// Denylist consulted in compliance mode. Implemented by the Auth Gateway and the
+/// Slashing contract.
+#[contractclient(name = "DenylistClient")]
+pub trait Denylist {
+    fn is_banned(env: Env, address: Address) -> bool;
 }
 
 #[contract]
@@ -16,15 +105,24 @@ pub struct AxToken;
 
 #[contractimpl]
 impl AxToken {
-    pub fn initialize(env: &Env, admin: Address) {
+    /// Initialize the token. `max_supply` is fixed for the lifetime of the contract.
+    pub fn initialize(env: &Env, admin: Address, max_supply: i128) {
         if Self::has_admin(env) {
             panic!("already initialized");
         }
 
+        if max_supply <= 0 {
+            panic!("max supply must be positive");
+        }
+
         env.storage().instance().set(&DataKey::Admin, &admin);
         env.storage().instance().set(&DataKey::TotalSupply, &0i128);
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxSupply, &max_supply);
     }
 
+    /// Admin mint. Supply reserved for emission schedules is not available here.
     pub fn mint(env: &Env, to: Address, amount: i128) {
         Self::require_admin(env);
 
@@ -32,45 +130,301 @@ impl AxToken {
             panic!("amount must be positive");
         }
 
-        let current_balance = Self::balance(env, to.clone());
-        let new_balance = current_balance + amount;
+        Self::require_mintable(env, amount);
+        Self::mint_balance(env, &to, amount);
+    }
+
+    /// Define a schedule releasing `amount_per_epoch` to `recipient` every `epoch_length`
+    /// seconds from `start_time`, for `total_epochs` epochs. The full amount is reserved
+    /// against the max supply up front.
+    pub fn create_emission_schedule(
+        env: &Env,
+        recipient: Address,
+        amount_per_epoch: i128,
+        epoch_length: u64,
+        start_time: u64,
+        total_epochs: u32,
+    ) -> u32 {
+        Self::require_admin(env);
+
+        if amount_per_epoch <= 0 {
+            panic!("amount must be positive");
+        }
+        if epoch_length == 0 {
+            panic!("epoch length must be positive");
+        }
+        if total_epochs == 0 {
+            panic!("schedule must have at least one epoch");
+        }
+
+        let total = amount_per_epoch
+            .checked_mul(total_epochs as i128)
+            .expect("max supply exceeded");
+        let reserved = Self::emission_reserved(env)
+            .checked_add(total)
+            .expect("max supply exceeded");
+        let committed = Self::total_supply(env)
+            .checked_add(reserved)
+            .expect("max supply exceeded");
+        if committed > Self::max_supply(env) {
+            panic!("max supply exceeded");
+        }
+
+        let schedule_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmissionCount)
+            .unwrap_or(0);
+        let schedule = EmissionSchedule {
+            schedule_id,
+            recipient: recipient.clone(),
+            amount_per_epoch,
+            epoch_length,
+            start_time,
+            total_epochs,
+            epochs_minted: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Emission(schedule_id), &schedule);
         env.storage()
             .instance()
-            .set(&DataKey::Balance(to.clone()), &new_balance);
-
-        let current_supply = Self::total_supply(env);
-        let new_supply = current_supply + amount;
+            .set(&DataKey::EmissionCount, &(schedule_id + 1));
         env.storage()
             .instance()
-            .set(&DataKey::TotalSupply, &new_supply);
+            .set(&DataKey::EmissionReserved, &reserved);
 
-        events::emit_mint(env, &to, amount);
+        events::emit_emission_schedule_created(
+            env,
+            schedule_id,
+            &recipient,
+            amount_per_epoch,
+            epoch_length,
+            start_time,
+            total_epochs,
+        );
+        schedule_id
     }
 
-    pub fn burn(env: &Env, from: Address, amount: i128) {
+    /// Mint every matured, unminted epoch across all schedules. Callable by anyone;
+    /// returns the total amount minted.
+    pub fn mint_epoch(env: &Env) -> i128 {
+        let now = env.ledger().timestamp();
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::EmissionCount)
+            .unwrap_or(0);
+
+        let mut minted: i128 = 0;
+        for schedule_id in 0..count {
+            let key = DataKey::Emission(schedule_id);
+            let mut schedule: EmissionSchedule = env.storage().persistent().get(&key).unwrap();
+
+            let matured = Self::matured_epochs(&schedule, now);
+            if matured <= schedule.epochs_minted {
+                continue;
+            }
+
+            let epochs = (matured - schedule.epochs_minted).min(MAX_EPOCHS_PER_MINT);
+            let amount = schedule.amount_per_epoch * epochs as i128;
+            schedule.epochs_minted += epochs;
+            env.storage().persistent().set(&key, &schedule);
+
+            Self::mint_balance(env, &schedule.recipient, amount);
+            events::emit_epoch_minted(
+                env,
+                schedule_id,
+                &schedule.recipient,
+                epochs,
+                schedule.epochs_minted,
+                amount,
+            );
+            minted += amount;
+        }
+
+        if minted == 0 {
+            panic!("no epoch has matured");
+        }
+
+        let reserved = Self::emission_reserved(env) - minted;
+        env.storage()
+            .instance()
+            .set(&DataKey::EmissionReserved, &reserved);
+        minted
+    }
+
+    /// Mint `amount` to `to` as a locked balance vesting linearly over `duration` seconds
+    /// from now, with nothing releasable before `cliff` seconds have passed.
+    pub fn mint_locked(env: &Env, to: Address, amount: i128, cliff: u64, duration: u64) -> u32 {
         Self::require_admin(env);
 
         if amount <= 0 {
             panic!("amount must be positive");
         }
+        if duration == 0 {
+            panic!("duration must be positive");
+        }
+        if cliff > duration {
+            panic!("cliff exceeds duration");
+        }
+        Self::require_mintable(env, amount);
 
-        let current_balance = Self::balance(env, from.clone());
-        if current_balance < amount {
-            panic!("insufficient balance");
+        let count_key = DataKey::VestingCount(to.clone());
+        let schedule_id: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let start_time = env.ledger().timestamp();
+        let schedule = VestingSchedule {
+            schedule_id,
+            total: amount,
+            released: 0,
+            start_time,
+            cliff,
+            duration,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Vesting(to.clone(), schedule_id), &schedule);
+        env.storage()
+            .persistent()
+            .set(&count_key, &(schedule_id + 1));
+
+        let locked = Self::locked_balance(env, to.clone()) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Locked(to.clone()), &locked);
+        Self::write_balance_checkpoint(env, &to);
+
+        let new_supply = Self::total_supply(env) + amount;
+        Self::write_total_supply(env, new_supply);
+
+        events::emit_mint(env, &to, amount);
+        events::emit_locked_mint(env, &to, schedule_id, amount, start_time, cliff, duration);
+        schedule_id
+    }
+
+    /// Move every vested, unreleased amount of `addr` into its spendable balance.
+    /// Callable by anyone; returns the amount released.
+    pub fn release_vested(env: &Env, addr: Address) -> i128 {
+        let now = env.ledger().timestamp();
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VestingCount(addr.clone()))
+            .unwrap_or(0);
+
+        let mut released: i128 = 0;
+        for schedule_id in 0..count {
+            let key = DataKey::Vesting(addr.clone(), schedule_id);
+            let mut schedule: VestingSchedule = env.storage().persistent().get(&key).unwrap();
+            let releasable = Self::vested(&schedule, now) - schedule.released;
+            if releasable > 0 {
+                schedule.released += releasable;
+                env.storage().persistent().set(&key, &schedule);
+                released += releasable;
+            }
+        }
+
+        if released == 0 {
+            panic!("nothing to release");
+        }
+
+        let locked = Self::locked_balance(env, addr.clone()) - released;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Locked(addr.clone()), &locked);
+        Self::receive_balance(env, &addr, released);
+
+        events::emit_vesting_released(env, &addr, released);
+        released
+    }
+
+    /// Tokens of `addr` still held in vesting schedules, vested or not.
+    pub fn locked_balance(env: &Env, addr: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Locked(addr))
+            .unwrap_or(0)
+    }
+
+    pub fn get_vesting_schedule(env: &Env, addr: Address, schedule_id: u32) -> VestingSchedule {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Vesting(addr, schedule_id))
+            .expect("vesting schedule not found")
+    }
+
+    pub fn get_vesting_schedules(env: &Env, addr: Address) -> Vec<VestingSchedule> {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::VestingCount(addr.clone()))
+            .unwrap_or(0);
+        let mut schedules = Vec::new(env);
+        for schedule_id in 0..count {
+            schedules.push_back(Self::get_vesting_schedule(env, addr.clone(), schedule_id));
         }
+        schedules
+    }
 
-        let new_balance = current_balance - amount;
+    /// Amount of a schedule that has vested and not yet been released.
+    pub fn releasable_amount(env: &Env, addr: Address, schedule_id: u32) -> i128 {
+        let schedule = Self::get_vesting_schedule(env, addr, schedule_id);
+        Self::vested(&schedule, env.ledger().timestamp()) - schedule.released
+    }
+
+    pub fn get_emission_schedule(env: &Env, schedule_id: u32) -> EmissionSchedule {
         env.storage()
+            .persistent()
+            .get(&DataKey::Emission(schedule_id))
+            .expect("emission schedule not found")
+    }
+
+    pub fn get_emission_schedules(env: &Env) -> Vec<EmissionSchedule> {
+        let count: u32 = env
+            .storage()
             .instance()
-            .set(&DataKey::Balance(from.clone()), &new_balance);
+            .get(&DataKey::EmissionCount)
+            .unwrap_or(0);
+        let mut schedules = Vec::new(env);
+        for schedule_id in 0..count {
+            schedules.push_back(Self::get_emission_schedule(env, schedule_id));
+        }
+        schedules
+    }
 
-        let current_supply = Self::total_supply(env);
-        let new_supply = current_supply - amount;
+    pub fn max_supply(env: &Env) -> i128 {
+        env.storage().instance().get(&DataKey::MaxSupply).unwrap()
+    }
+
+    /// Supply committed to emission schedules but not yet minted.
+    pub fn emission_reserved(env: &Env) -> i128 {
         env.storage()
             .instance()
-            .set(&DataKey::TotalSupply, &new_supply);
+            .get(&DataKey::EmissionReserved)
+            .unwrap_or(0)
+    }
+
+    /// Burn `amount` from the holder's own balance (SEP-41).
+    pub fn burn(env: &Env, from: Address, amount: i128) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        Self::burn_balance(env, &from, amount);
+    }
+
+    /// Burn `amount` from `from` using the spender's allowance (SEP-41).
+    pub fn burn_from(env: &Env, spender: Address, from: Address, amount: i128) {
+        spender.require_auth();
+
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
 
-        events::emit_burn(env, &from, amount);
+        Self::spend_allowance(env, &from, &spender, amount);
+        Self::burn_balance(env, &from, amount);
     }
 
     pub fn transfer(env: &Env, from: Address, to: Address, amount: i128) {
@@ -84,39 +438,357 @@ impl AxToken {
             panic!("cannot transfer to self");
         }
 
-        let from_balance = Self::balance(env, from.clone());
-        if from_balance < amount {
+        Self::move_balance(env, &from, &to, amount);
+    }
+
+    /// Transfer to many recipients under a single authorization. Every entry is
+    /// validated before any balance moves, and any failure reverts the whole batch.
+    pub fn batch_transfer(env: &Env, from: Address, recipients: Vec<(Address, i128)>) {
+        from.require_auth();
+
+        let total = Self::validate_batch(&recipients);
+        for (to, _) in recipients.iter() {
+            if to == from {
+                panic!("cannot transfer to self");
+            }
+        }
+        if Self::available_balance(env, from.clone()) < total {
             panic!("insufficient balance");
         }
 
-        let new_from_balance = from_balance - amount;
+        for (to, amount) in recipients.iter() {
+            Self::move_balance(env, &from, &to, amount);
+        }
+    }
+
+    /// Admin mint to many recipients (e.g. season rewards). All-or-nothing; the total
+    /// counts against the max supply.
+    pub fn airdrop(env: &Env, recipients: Vec<(Address, i128)>) {
+        Self::require_admin(env);
+
+        let total = Self::validate_batch(&recipients);
+        Self::require_mintable(env, total);
+
+        for (to, amount) in recipients.iter() {
+            Self::mint_balance(env, &to, amount);
+        }
+
+        events::emit_airdrop(env, recipients.len(), total);
+    }
+
+    /// Transfer `amount` from `from` to `to` using the spender's allowance (SEP-41).
+    pub fn transfer_from(env: &Env, spender: Address, from: Address, to: Address, amount: i128) {
+        spender.require_auth();
+
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        if from == to {
+            panic!("cannot transfer to self");
+        }
+
+        Self::spend_allowance(env, &from, &spender, amount);
+        Self::move_balance(env, &from, &to, amount);
+    }
+
+    /// Allow `spender` to move up to `amount` of the holder's tokens until `expiration_ledger`
+    /// (SEP-41). A non-zero allowance must not expire in the past.
+    pub fn approve(
+        env: &Env,
+        from: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        from.require_auth();
+
+        if amount < 0 {
+            panic!("amount must not be negative");
+        }
+
+        Self::write_allowance(env, &from, &spender, amount, expiration_ledger);
+    }
+
+    /// Set an allowance from an authorization the holder signed off-chain, so a relayer
+    /// can submit it and pay the fee. The holder signs the call arguments (spender,
+    /// amount, expiration ledger, nonce, deadline); `nonce` must equal
+    /// `permit_nonce(owner)` and is consumed, so each authorization is usable once.
+    pub fn permit(
+        env: &Env,
+        owner: Address,
+        spender: Address,
+        amount: i128,
+        expiration_ledger: u32,
+        nonce: u64,
+        deadline: u64,
+    ) {
+        owner.require_auth_for_args(
+            (spender.clone(), amount, expiration_ledger, nonce, deadline).into_val(env),
+        );
+
+        if env.ledger().timestamp() > deadline {
+            panic!("permit expired");
+        }
+        if amount < 0 {
+            panic!("amount must not be negative");
+        }
+
+        let expected = Self::permit_nonce(env, owner.clone());
+        if nonce != expected {
+            panic!("invalid nonce");
+        }
         env.storage()
-            .instance()
-            .set(&DataKey::Balance(from.clone()), &new_from_balance);
+            .persistent()
+            .set(&DataKey::PermitNonce(owner.clone()), &(nonce + 1));
+
+        Self::write_allowance(env, &owner, &spender, amount, expiration_ledger);
+        events::emit_permit(env, &owner, &spender, amount, nonce);
+    }
 
-        let to_balance = Self::balance(env, to.clone());
-        let new_to_balance = to_balance + amount;
+    /// Next nonce `owner` must sign for `permit`.
+    pub fn permit_nonce(env: &Env, owner: Address) -> u64 {
         env.storage()
-            .instance()
-            .set(&DataKey::Balance(to.clone()), &new_to_balance);
+            .persistent()
+            .get(&DataKey::PermitNonce(owner))
+            .unwrap_or(0)
+    }
 
-        events::emit_transfer(env, &from, &to, amount);
+    /// Remaining allowance of `spender` over the holder's tokens; zero once expired.
+    pub fn allowance(env: &Env, from: Address, spender: Address) -> i128 {
+        Self::read_allowance(env, &from, &spender).amount
     }
 
+    /// Total holdings of `addr`, including tokens still locked in vesting schedules.
     pub fn balance(env: &Env, addr: Address) -> i128 {
+        Self::available_balance(env, addr.clone()) + Self::locked_balance(env, addr)
+    }
+
+    /// Spendable balance of `addr`; excludes unreleased vesting tokens.
+    pub fn available_balance(env: &Env, addr: Address) -> i128 {
+        let key = DataKey::Balance(addr);
+        match env.storage().persistent().get::<DataKey, i128>(&key) {
+            Some(balance) => {
+                env.storage().persistent().extend_ttl(
+                    &key,
+                    BALANCE_LIFETIME_THRESHOLD,
+                    BALANCE_BUMP_AMOUNT,
+                );
+                balance
+            }
+            None => 0,
+        }
+    }
+
+    pub fn total_supply(env: &Env) -> i128 {
         env.storage()
             .instance()
-            .get(&DataKey::Balance(addr))
+            .get(&DataKey::TotalSupply)
             .unwrap_or(0)
     }
 
-    pub fn total_supply(env: &Env) -> i128 {
+    /// Total holdings of `addr` (including locked tokens) at the end of `ledger`, which
+    /// must be in the past. Used for snapshot-based voting power.
+    pub fn get_past_balance(env: &Env, addr: Address, ledger: u32) -> i128 {
+        Self::require_past_ledger(env, ledger);
+
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::CheckpointCount(addr.clone()))
+            .unwrap_or(0);
+        Self::lookup_checkpoint(count, ledger, |index| {
+            env.storage()
+                .persistent()
+                .get(&DataKey::Checkpoint(addr.clone(), index))
+                .unwrap()
+        })
+    }
+
+    /// Total supply at the end of `ledger`, which must be in the past.
+    pub fn get_past_total_supply(env: &Env, ledger: u32) -> i128 {
+        Self::require_past_ledger(env, ledger);
+
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SupplyCheckpointCount)
+            .unwrap_or(0);
+        Self::lookup_checkpoint(count, ledger, |index| {
+            env.storage()
+                .persistent()
+                .get(&DataKey::SupplyCheckpoint(index))
+                .unwrap()
+        })
+    }
+
+    pub fn decimals(_env: &Env) -> u32 {
+        DECIMALS
+    }
+
+    pub fn name(env: &Env) -> String {
+        String::from_str(env, NAME)
+    }
+
+    pub fn symbol(env: &Env) -> String {
+        String::from_str(env, SYMBOL)
+    }
+
+    /// Block all outgoing transfers and burns from `addr` (e.g. a confirmed cheater
+    /// pending investigation). Incoming transfers are still accepted.
+    pub fn freeze_account(env: &Env, addr: Address) {
+        Self::require_admin(env);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Frozen(addr.clone()), &true);
+        events::emit_account_frozen(env, &addr);
+    }
+
+    pub fn unfreeze_account(env: &Env, addr: Address) {
+        Self::require_admin(env);
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Frozen(addr.clone()));
+        events::emit_account_unfrozen(env, &addr);
+    }
+
+    pub fn is_frozen(env: &Env, addr: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Frozen(addr))
+            .unwrap_or(false)
+    }
+
+    /// Grant the clawback role. Kept separate from the admin so that clawbacks are
+    /// attributable to a dedicated compliance key.
+    pub fn set_clawback_admin(env: &Env, clawback_admin: Address) {
+        Self::require_admin(env);
+
         env.storage()
             .instance()
-            .get(&DataKey::TotalSupply)
+            .set(&DataKey::ClawbackAdmin, &clawback_admin);
+        events::emit_clawback_admin_set(env, &clawback_admin);
+    }
+
+    pub fn get_clawback_admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::ClawbackAdmin)
+    }
+
+    /// Remove `amount` from `from` and destroy it, recording `reason`. Works on frozen
+    /// accounts.
+    pub fn clawback(env: &Env, from: Address, amount: i128, reason: String) {
+        let clawback_admin = Self::get_clawback_admin(env).expect("clawback role not set");
+        clawback_admin.require_auth();
+
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        let balance = Self::available_balance(env, from.clone());
+        if balance < amount {
+            panic!("insufficient balance");
+        }
+        Self::write_balance(env, &from, balance - amount);
+
+        let new_supply = Self::total_supply(env) - amount;
+        Self::write_total_supply(env, new_supply);
+
+        events::emit_clawback(env, &clawback_admin, &from, amount, &reason);
+    }
+
+    /// Charge `fee_bps` on transfers, paid by the sender out of the transferred amount
+    /// and routed to `treasury`. A zero fee disables the hook.
+    pub fn set_transfer_fee(env: &Env, fee_bps: u32, treasury: Address) {
+        Self::require_admin(env);
+
+        if fee_bps > MAX_TRANSFER_FEE_BPS {
+            panic!("transfer fee exceeds cap");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::TransferFeeBps, &fee_bps);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        events::emit_transfer_fee_set(env, fee_bps, &treasury);
+    }
+
+    pub fn get_transfer_fee(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TransferFeeBps)
             .unwrap_or(0)
     }
 
+    pub fn get_treasury(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::Treasury)
+    }
+
+    /// Exempt a system contract (escrow vault, staking manager, prize distribution)
+    /// from the transfer fee. Transfers are exempt when either side is.
+    pub fn set_fee_exempt(env: &Env, addr: Address, exempt: bool) {
+        Self::require_admin(env);
+
+        let key = DataKey::FeeExempt(addr.clone());
+        if exempt {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        events::emit_fee_exemption_set(env, &addr, exempt);
+    }
+
+    pub fn is_fee_exempt(env: &Env, addr: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::FeeExempt(addr))
+            .unwrap_or(false)
+    }
+
+    /// Enable compliance mode: transfers to or from addresses banned by `source` (the
+    /// Auth Gateway or another denylist contract) are rejected. Rejections leave no
+    /// on-chain record; monitor failed transactions to audit them.
+    pub fn set_compliance_source(env: &Env, source: Address) {
+        Self::require_admin(env);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::ComplianceSource, &source);
+        events::emit_compliance_source_set(env, &Some(source));
+    }
+
+    pub fn disable_compliance(env: &Env) {
+        Self::require_admin(env);
+
+        env.storage().instance().remove(&DataKey::ComplianceSource);
+        events::emit_compliance_source_set(env, &None);
+    }
+
+    pub fn get_compliance_source(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::ComplianceSource)
+    }
+
+    /// Admin override: let `addr` transact even while the denylist bans it.
+    pub fn set_compliance_override(env: &Env, addr: Address, allowed: bool) {
+        Self::require_admin(env);
+
+        let key = DataKey::ComplianceOverride(addr.clone());
+        if allowed {
+            env.storage().persistent().set(&key, &true);
+        } else {
+            env.storage().persistent().remove(&key);
+        }
+        events::emit_compliance_override_set(env, &addr, allowed);
+    }
+
+    pub fn has_compliance_override(env: &Env, addr: Address) -> bool {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ComplianceOverride(addr))
+            .unwrap_or(false)
+    }
+
     pub fn get_admin(env: &Env) -> Address {
         env.storage().instance().get(&DataKey::Admin).unwrap()
     }
@@ -137,4 +809,278 @@ impl AxToken {
         let admin = Self::get_admin(env);
         admin.require_auth();
     }
+
+    fn write_balance(env: &Env, addr: &Address, amount: i128) {
+        let key = DataKey::Balance(addr.clone());
+        env.storage().persistent().set(&key, &amount);
+        env.storage().persistent().extend_ttl(
+            &key,
+            BALANCE_LIFETIME_THRESHOLD,
+            BALANCE_BUMP_AMOUNT,
+        );
+        Self::write_balance_checkpoint(env, addr);
+    }
+
+    fn write_total_supply(env: &Env, supply: i128) {
+        env.storage().instance().set(&DataKey::TotalSupply, &supply);
+
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::SupplyCheckpointCount)
+            .unwrap_or(0);
+        let count = Self::push_checkpoint(env, count, supply, DataKey::SupplyCheckpoint);
+        env.storage()
+            .persistent()
+            .set(&DataKey::SupplyCheckpointCount, &count);
+    }
+
+    fn write_balance_checkpoint(env: &Env, addr: &Address) {
+        let value = Self::balance(env, addr.clone());
+        let count_key = DataKey::CheckpointCount(addr.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let count = Self::push_checkpoint(env, count, value, |index| {
+            DataKey::Checkpoint(addr.clone(), index)
+        });
+        env.storage().persistent().set(&count_key, &count);
+    }
+
+    /// Record `value` for the current ledger, overwriting the latest checkpoint if it was
+    /// written in the same ledger. Returns the new checkpoint count.
+    fn push_checkpoint(env: &Env, count: u32, value: i128, key: impl Fn(u32) -> DataKey) -> u32 {
+        let ledger = env.ledger().sequence();
+        if count > 0 {
+            let last: Checkpoint = env.storage().persistent().get(&key(count - 1)).unwrap();
+            if last.ledger == ledger {
+                env.storage()
+                    .persistent()
+                    .set(&key(count - 1), &Checkpoint { ledger, value });
+                return count;
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&key(count), &Checkpoint { ledger, value });
+        count + 1
+    }
+
+    /// Binary search for the latest checkpoint at or before `ledger`.
+    fn lookup_checkpoint(count: u32, ledger: u32, read: impl Fn(u32) -> Checkpoint) -> i128 {
+        let (mut low, mut high) = (0u32, count);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if read(mid).ledger > ledger {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        if low == 0 {
+            0
+        } else {
+            read(low - 1).value
+        }
+    }
+
+    fn require_past_ledger(env: &Env, ledger: u32) {
+        if ledger >= env.ledger().sequence() {
+            panic!("ledger must be in the past");
+        }
+    }
+
+    fn receive_balance(env: &Env, addr: &Address, amount: i128) {
+        let balance = Self::available_balance(env, addr.clone());
+        Self::write_balance(env, addr, balance + amount);
+    }
+
+    fn spend_balance(env: &Env, addr: &Address, amount: i128) {
+        if Self::is_frozen(env, addr.clone()) {
+            panic!("account is frozen");
+        }
+        let balance = Self::available_balance(env, addr.clone());
+        if balance < amount {
+            panic!("insufficient balance");
+        }
+        Self::write_balance(env, addr, balance - amount);
+    }
+
+    fn mint_balance(env: &Env, to: &Address, amount: i128) {
+        Self::receive_balance(env, to, amount);
+
+        let new_supply = Self::total_supply(env) + amount;
+        Self::write_total_supply(env, new_supply);
+
+        events::emit_mint(env, to, amount);
+    }
+
+    fn validate_batch(recipients: &Vec<(Address, i128)>) -> i128 {
+        if recipients.is_empty() {
+            panic!("no recipients");
+        }
+        if recipients.len() > MAX_BATCH_SIZE {
+            panic!("too many recipients");
+        }
+
+        let mut total: i128 = 0;
+        for (_, amount) in recipients.iter() {
+            if amount <= 0 {
+                panic!("amount must be positive");
+            }
+            total = total.checked_add(amount).expect("batch total overflow");
+        }
+        total
+    }
+
+    fn require_mintable(env: &Env, amount: i128) {
+        let committed = Self::total_supply(env)
+            .checked_add(Self::emission_reserved(env))
+            .and_then(|c| c.checked_add(amount))
+            .expect("max supply exceeded");
+        if committed > Self::max_supply(env) {
+            panic!("max supply exceeded");
+        }
+    }
+
+    fn vested(schedule: &VestingSchedule, now: u64) -> i128 {
+        let elapsed = now.saturating_sub(schedule.start_time);
+        if elapsed < schedule.cliff {
+            0
+        } else if elapsed >= schedule.duration {
+            schedule.total
+        } else {
+            schedule.total * elapsed as i128 / schedule.duration as i128
+        }
+    }
+
+    fn matured_epochs(schedule: &EmissionSchedule, now: u64) -> u32 {
+        if now < schedule.start_time {
+            return 0;
+        }
+        let elapsed = (now - schedule.start_time) / schedule.epoch_length;
+        elapsed.min(schedule.total_epochs as u64) as u32
+    }
+
+    /// Move `amount` from `from` to `to`, deducting the transfer fee (if any) for the
+    /// treasury. The recipient receives the net amount.
+    fn move_balance(env: &Env, from: &Address, to: &Address, amount: i128) {
+        Self::require_compliant(env, from, to);
+        Self::spend_balance(env, from, amount);
+
+        let fee = Self::transfer_fee(env, from, to, amount);
+        let net = amount - fee;
+        Self::receive_balance(env, to, net);
+        events::emit_transfer(env, from, to, net);
+
+        if fee > 0 {
+            let treasury = Self::get_treasury(env).unwrap();
+            Self::receive_balance(env, &treasury, fee);
+            events::emit_transfer(env, from, &treasury, fee);
+            events::emit_transfer_fee_charged(env, from, to, amount, fee, net);
+        }
+    }
+
+    /// In compliance mode, reject transfers touching a banned address. No event is
+    /// emitted: the panic reverts the whole invocation, events included, so blocked
+    /// attempts are only visible as failed transactions.
+    fn require_compliant(env: &Env, from: &Address, to: &Address) {
+        let source = match Self::get_compliance_source(env) {
+            Some(source) => source,
+            None => return,
+        };
+
+        let denylist = DenylistClient::new(env, &source);
+        for addr in [from, to] {
+            if !Self::has_compliance_override(env, addr.clone()) && denylist.is_banned(addr) {
+                panic!("transfer blocked by compliance");
+            }
+        }
+    }
+
+    fn transfer_fee(env: &Env, from: &Address, to: &Address, amount: i128) -> i128 {
+        let fee_bps = Self::get_transfer_fee(env);
+        if fee_bps == 0
+            || Self::is_fee_exempt(env, from.clone())
+            || Self::is_fee_exempt(env, to.clone())
+        {
+            return 0;
+        }
+        amount * fee_bps as i128 / BPS_DENOMINATOR
+    }
+
+    fn burn_balance(env: &Env, from: &Address, amount: i128) {
+        Self::spend_balance(env, from, amount);
+
+        let current_supply = Self::total_supply(env);
+        let new_supply = current_supply - amount;
+        Self::write_total_supply(env, new_supply);
+
+        events::emit_burn(env, from, amount);
+    }
+
+    fn write_allowance(
+        env: &Env,
+        from: &Address,
+        spender: &Address,
+        amount: i128,
+        expiration_ledger: u32,
+    ) {
+        let current_ledger = env.ledger().sequence();
+        if amount > 0 && expiration_ledger < current_ledger {
+            panic!("expiration ledger is in the past");
+        }
+
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        env.storage().temporary().set(
+            &key,
+            &AllowanceValue {
+                amount,
+                expiration_ledger,
+            },
+        );
+        if amount > 0 {
+            let live_for = expiration_ledger - current_ledger;
+            env.storage()
+                .temporary()
+                .extend_ttl(&key, live_for, live_for);
+        }
+
+        events::emit_approve(env, from, spender, amount, expiration_ledger);
+    }
+
+    fn read_allowance(env: &Env, from: &Address, spender: &Address) -> AllowanceValue {
+        let key = DataKey::Allowance(from.clone(), spender.clone());
+        match env
+            .storage()
+            .temporary()
+            .get::<DataKey, AllowanceValue>(&key)
+        {
+            Some(allowance) if allowance.expiration_ledger >= env.ledger().sequence() => allowance,
+            Some(allowance) => AllowanceValue {
+                amount: 0,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+            None => AllowanceValue {
+                amount: 0,
+                expiration_ledger: 0,
+            },
+        }
+    }
+
+    fn spend_allowance(env: &Env, from: &Address, spender: &Address, amount: i128) {
+        let allowance = Self::read_allowance(env, from, spender);
+        if allowance.amount < amount {
+            panic!("insufficient allowance");
+        }
+
+        env.storage().temporary().set(
+            &DataKey::Allowance(from.clone(), spender.clone()),
+            &AllowanceValue {
+                amount: allowance.amount - amount,
+                expiration_ledger: allowance.expiration_ledger,
+            },
+        );
+    }
 }
+
+#[cfg(test)]
+mod test;
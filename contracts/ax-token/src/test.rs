@@ -1,11 +1,28 @@
 #![cfg(test)]
 
+extern crate std;
+
 use super::*;
 use soroban_sdk::{
-    testutils::{Address as _, Ledger as _},
-    Address, Env,
+    contract, contractimpl,
+    testutils::{Address as _, AuthorizedFunction, AuthorizedInvocation, Ledger as _},
+    vec, Address, Env, IntoVal, String, Symbol,
 };
 
+#[contract]
+pub struct MockDenylist;
+
+#[contractimpl]
+impl MockDenylist {
+    pub fn ban(env: Env, address: Address) {
+        env.storage().persistent().set(&address, &true);
+    }
+
+    pub fn is_banned(env: Env, address: Address) -> bool {
+        env.storage().persistent().has(&address)
+    }
+}
+
 fn create_test_env() -> (Env, Address, Address, Address) {
     let env = Env::default();
     let admin = Address::generate(&env);
@@ -15,10 +32,9 @@ fn create_test_env() -> (Env, Address, Address, Address) {
 }
 
 fn initialize_contract(env: &Env, admin: &Address) -> Address {
-    let contract_id = Address::generate(env);
-    env.register_contract(&contract_id, AxToken);
+    let contract_id = env.register(AxToken, ());
     let client = AxTokenClient::new(env, &contract_id);
-    client.initialize(admin);
+    client.initialize(admin, &i128::MAX);
     contract_id
 }
 
@@ -41,7 +57,7 @@ fn test_double_initialization() {
     let contract_id = initialize_contract(&env, &admin);
     let client = AxTokenClient::new(&env, &contract_id);
 
-    client.initialize(&admin);
+    client.initialize(&admin, &i128::MAX);
 }
 
 #[test]
@@ -52,11 +68,11 @@ fn test_mint() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
+    client.mint(&user1, &1000);
     assert_eq!(client.balance(&user1), 1000);
     assert_eq!(client.total_supply(), 1000);
 
-    client.mint(&user2, 500);
+    client.mint(&user2, &500);
     assert_eq!(client.balance(&user2), 500);
     assert_eq!(client.total_supply(), 1500);
 }
@@ -69,7 +85,7 @@ fn test_mint_zero_amount() {
     let client = AxTokenClient::new(&env, &contract_id);
 
     env.mock_all_auths();
-    client.mint(&user1, 0);
+    client.mint(&user1, &0);
 }
 
 #[test]
@@ -80,17 +96,17 @@ fn test_mint_negative_amount() {
     let client = AxTokenClient::new(&env, &contract_id);
 
     env.mock_all_auths();
-    client.mint(&user1, -100);
+    client.mint(&user1, &-100);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
 fn test_mint_unauthorized() {
     let (env, admin, user1, _) = create_test_env();
     let contract_id = initialize_contract(&env, &admin);
     let client = AxTokenClient::new(&env, &contract_id);
 
-    client.mint(&user1, 1000);
+    client.mint(&user1, &1000);
 }
 
 #[test]
@@ -101,11 +117,11 @@ fn test_burn() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
+    client.mint(&user1, &1000);
     assert_eq!(client.balance(&user1), 1000);
     assert_eq!(client.total_supply(), 1000);
 
-    client.burn(&user1, 300);
+    client.burn(&user1, &300);
     assert_eq!(client.balance(&user1), 700);
     assert_eq!(client.total_supply(), 700);
 }
@@ -119,8 +135,8 @@ fn test_burn_insufficient_balance() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.burn(&user1, 1500);
+    client.mint(&user1, &1000);
+    client.burn(&user1, &1500);
 }
 
 #[test]
@@ -132,21 +148,22 @@ fn test_burn_zero_amount() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.burn(&user1, 0);
+    client.mint(&user1, &1000);
+    client.burn(&user1, &0);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
 fn test_burn_unauthorized() {
     let (env, admin, user1, _) = create_test_env();
     let contract_id = initialize_contract(&env, &admin);
     let client = AxTokenClient::new(&env, &contract_id);
 
     env.mock_all_auths();
-    client.mint(&user1, 1000);
+    client.mint(&user1, &1000);
 
-    client.burn(&user1, 100);
+    env.set_auths(&[]);
+    client.burn(&user1, &100);
 }
 
 #[test]
@@ -157,10 +174,10 @@ fn test_transfer() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.mint(&user2, 500);
+    client.mint(&user1, &1000);
+    client.mint(&user2, &500);
 
-    client.transfer(&user1, &user2, 300);
+    client.transfer(&user1, &user2, &300);
     assert_eq!(client.balance(&user1), 700);
     assert_eq!(client.balance(&user2), 800);
     assert_eq!(client.total_supply(), 1500);
@@ -175,8 +192,8 @@ fn test_transfer_insufficient_balance() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.transfer(&user1, &user2, 1500);
+    client.mint(&user1, &1000);
+    client.transfer(&user1, &user2, &1500);
 }
 
 #[test]
@@ -188,8 +205,8 @@ fn test_transfer_zero_amount() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.transfer(&user1, &user2, 0);
+    client.mint(&user1, &1000);
+    client.transfer(&user1, &user2, &0);
 }
 
 #[test]
@@ -201,12 +218,12 @@ fn test_transfer_to_self() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.transfer(&user1, &user1, 100);
+    client.mint(&user1, &1000);
+    client.transfer(&user1, &user1, &100);
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
 fn test_transfer_unauthorized() {
     let (env, admin, user1, user2) = create_test_env();
     let contract_id = initialize_contract(&env, &admin);
@@ -214,15 +231,16 @@ fn test_transfer_unauthorized() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.mint(&user2, 500);
+    client.mint(&user1, &1000);
+    client.mint(&user2, &500);
 
-    client.transfer(&user1, &user2, 100);
+    env.set_auths(&[]);
+    client.transfer(&user1, &user2, &100);
 }
 
 #[test]
 fn test_set_admin() {
-    let (env, admin, user1, user2) = create_test_env();
+    let (env, admin, user1, _) = create_test_env();
     let contract_id = initialize_contract(&env, &admin);
     let client = AxTokenClient::new(&env, &contract_id);
 
@@ -234,7 +252,7 @@ fn test_set_admin() {
 }
 
 #[test]
-#[should_panic(expected = "already initialized")]
+#[should_panic(expected = "Error(Auth, InvalidAction)")]
 fn test_set_admin_unauthorized() {
     let (env, admin, user1, _) = create_test_env();
     let contract_id = initialize_contract(&env, &admin);
@@ -251,16 +269,16 @@ fn test_full_lifecycle() {
 
     env.mock_all_auths();
 
-    client.mint(&user1, 1000);
-    client.mint(&user2, 1000);
+    client.mint(&user1, &1000);
+    client.mint(&user2, &1000);
     assert_eq!(client.total_supply(), 2000);
 
-    client.transfer(&user1, &user2, 300);
+    client.transfer(&user1, &user2, &300);
     assert_eq!(client.balance(&user1), 700);
     assert_eq!(client.balance(&user2), 1300);
 
-    client.burn(&user1, 200);
-    client.burn(&user2, 400);
+    client.burn(&user1, &200);
+    client.burn(&user2, &400);
     assert_eq!(client.balance(&user1), 500);
     assert_eq!(client.balance(&user2), 900);
     assert_eq!(client.total_supply(), 1400);
@@ -275,16 +293,16 @@ fn test_large_amounts() {
     env.mock_all_auths();
 
     let large_amount = i128::MAX / 4;
-    client.mint(&user1, large_amount);
-    client.mint(&user2, large_amount);
+    client.mint(&user1, &large_amount);
+    client.mint(&user2, &large_amount);
 
     assert_eq!(client.total_supply(), large_amount * 2);
     assert_eq!(client.balance(&user1), large_amount);
     assert_eq!(client.balance(&user2), large_amount);
 
-    client.transfer(&user1, &user2, large_amount / 2);
-    assert_eq!(client.balance(&user1), large_amount / 2);
-    assert_eq!(client.balance(&user2), large_amount * 3 / 2);
+    client.transfer(&user1, &user2, &(large_amount / 2));
+    assert_eq!(client.balance(&user1), large_amount - large_amount / 2);
+    assert_eq!(client.balance(&user2), large_amount + large_amount / 2);
 }
 
 #[test]
@@ -297,17 +315,17 @@ fn test_multiple_users() {
 
     env.mock_all_auths();
 
-    let users = vec![user1.clone(), user2.clone(), user3.clone(), user4.clone()];
-    let amounts = vec![1000, 2000, 3000, 4000];
+    let users = [user1.clone(), user2.clone(), user3.clone(), user4.clone()];
+    let amounts = [1000, 2000, 3000, 4000];
 
     for (i, user) in users.iter().enumerate() {
-        client.mint(user, amounts[i]);
+        client.mint(user, &amounts[i]);
     }
 
     assert_eq!(client.total_supply(), 10000);
 
-    client.transfer(&user1, &user2, 500);
-    client.transfer(&user3, &user4, 1000);
+    client.transfer(&user1, &user2, &500);
+    client.transfer(&user3, &user4, &1000);
 
     assert_eq!(client.balance(&user1), 500);
     assert_eq!(client.balance(&user2), 2500);
@@ -315,3 +333,509 @@ fn test_multiple_users() {
     assert_eq!(client.balance(&user4), 5000);
     assert_eq!(client.total_supply(), 10000);
 }
+
+#[test]
+fn test_metadata() {
+    let (env, admin, _, _) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    assert_eq!(client.decimals(), 7);
+    assert_eq!(client.name(), String::from_str(&env, "ArenaX Token"));
+    assert_eq!(client.symbol(), String::from_str(&env, "AX"));
+}
+
+#[test]
+fn test_approve_and_transfer_from() {
+    let (env, admin, user1, user2) = create_test_env();
+    let spender = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1000);
+
+    client.approve(&user1, &spender, &300, &100);
+    assert_eq!(client.allowance(&user1, &spender), 300);
+
+    client.transfer_from(&spender, &user1, &user2, &200);
+    assert_eq!(client.balance(&user1), 800);
+    assert_eq!(client.balance(&user2), 200);
+    assert_eq!(client.allowance(&user1, &spender), 100);
+
+    client.burn_from(&spender, &user1, &100);
+    assert_eq!(client.balance(&user1), 700);
+    assert_eq!(client.total_supply(), 900);
+    assert_eq!(client.allowance(&user1, &spender), 0);
+}
+
+#[test]
+#[should_panic(expected = "insufficient allowance")]
+fn test_transfer_from_exceeds_allowance() {
+    let (env, admin, user1, user2) = create_test_env();
+    let spender = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1000);
+    client.approve(&user1, &spender, &100, &100);
+    client.transfer_from(&spender, &user1, &user2, &101);
+}
+
+#[test]
+fn test_allowance_expires() {
+    let (env, admin, user1, _) = create_test_env();
+    let spender = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.approve(&user1, &spender, &100, &10);
+    assert_eq!(client.allowance(&user1, &spender), 100);
+
+    env.ledger().set_sequence_number(11);
+    assert_eq!(client.allowance(&user1, &spender), 0);
+}
+
+#[test]
+fn test_standard_token_client_interop() {
+    let (env, admin, user1, user2) = create_test_env();
+    let spender = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+    let token = soroban_sdk::token::TokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1000);
+
+    token.approve(&user1, &spender, &500, &100);
+    token.transfer_from(&spender, &user1, &user2, &500);
+    token.transfer(&user2, &user1, &100);
+    assert_eq!(token.balance(&user1), 600);
+    assert_eq!(token.balance(&user2), 400);
+    assert_eq!(token.decimals(), 7);
+}
+
+fn initialize_capped(env: &Env, admin: &Address, max_supply: i128) -> AxTokenClient<'static> {
+    let contract_id = env.register(AxToken, ());
+    let client = AxTokenClient::new(env, &contract_id);
+    client.initialize(admin, &max_supply);
+    client
+}
+
+#[test]
+#[should_panic(expected = "max supply exceeded")]
+fn test_mint_respects_max_supply() {
+    let (env, admin, user1, _) = create_test_env();
+    let client = initialize_capped(&env, &admin, 1000);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1000);
+    assert_eq!(client.max_supply(), 1000);
+    client.mint(&user1, &1);
+}
+
+#[test]
+fn test_emission_schedule_mints_matured_epochs() {
+    let (env, admin, user1, _) = create_test_env();
+    let pool = Address::generate(&env);
+    let client = initialize_capped(&env, &admin, 10_000);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    let schedule_id = client.create_emission_schedule(&pool, &500, &100, &1_000, &4);
+    assert_eq!(client.emission_reserved(), 2_000);
+
+    // Reserved emissions are not available to admin mints.
+    client.mint(&user1, &8_000);
+    assert!(client.try_mint(&user1, &1).is_err());
+
+    env.ledger().set_timestamp(1_250);
+    assert_eq!(client.mint_epoch(), 1_000);
+    assert_eq!(client.balance(&pool), 1_000);
+    assert_eq!(client.get_emission_schedule(&schedule_id).epochs_minted, 2);
+
+    // Not yet matured again.
+    assert!(client.try_mint_epoch().is_err());
+
+    env.ledger().set_timestamp(5_000);
+    assert_eq!(client.mint_epoch(), 1_000);
+    assert_eq!(client.balance(&pool), 2_000);
+    assert_eq!(client.total_supply(), 10_000);
+    assert_eq!(client.emission_reserved(), 0);
+    assert!(client.try_mint_epoch().is_err());
+}
+
+#[test]
+#[should_panic(expected = "max supply exceeded")]
+fn test_emission_schedule_respects_max_supply() {
+    let (env, admin, _, _) = create_test_env();
+    let pool = Address::generate(&env);
+    let client = initialize_capped(&env, &admin, 1_000);
+
+    env.mock_all_auths();
+    client.create_emission_schedule(&pool, &300, &100, &0, &4);
+}
+
+#[test]
+fn test_freeze_blocks_outgoing_transfers() {
+    let (env, admin, user1, user2) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1000);
+    client.mint(&user2, &1000);
+
+    client.freeze_account(&user1);
+    assert!(client.is_frozen(&user1));
+    assert!(client.try_transfer(&user1, &user2, &100).is_err());
+    assert!(client.try_burn(&user1, &100).is_err());
+
+    // Incoming transfers are still accepted.
+    client.transfer(&user2, &user1, &100);
+    assert_eq!(client.balance(&user1), 1100);
+
+    client.unfreeze_account(&user1);
+    assert!(!client.is_frozen(&user1));
+    client.transfer(&user1, &user2, &100);
+    assert_eq!(client.balance(&user1), 1000);
+}
+
+#[test]
+fn test_clawback() {
+    let (env, admin, user1, _) = create_test_env();
+    let compliance = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1000);
+    client.freeze_account(&user1);
+    client.set_clawback_admin(&compliance);
+    assert_eq!(client.get_clawback_admin(), Some(compliance));
+
+    client.clawback(&user1, &400, &String::from_str(&env, "cheating confirmed"));
+    assert_eq!(client.balance(&user1), 600);
+    assert_eq!(client.total_supply(), 600);
+}
+
+#[test]
+#[should_panic(expected = "clawback role not set")]
+fn test_clawback_requires_role() {
+    let (env, admin, user1, _) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1000);
+    client.clawback(&user1, &400, &String::from_str(&env, "cheating confirmed"));
+}
+
+#[test]
+fn test_locked_balance_vesting() {
+    let (env, admin, user1, user2) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    client.mint(&user1, &100);
+    let schedule_id = client.mint_locked(&user1, &1_000, &100, &1_000);
+
+    assert_eq!(client.balance(&user1), 1_100);
+    assert_eq!(client.available_balance(&user1), 100);
+    assert_eq!(client.locked_balance(&user1), 1_000);
+    assert_eq!(client.total_supply(), 1_100);
+
+    // Locked tokens cannot be transferred.
+    assert!(client.try_transfer(&user1, &user2, &101).is_err());
+
+    // Nothing releasable before the cliff.
+    env.ledger().set_timestamp(1_050);
+    assert_eq!(client.releasable_amount(&user1, &schedule_id), 0);
+    assert!(client.try_release_vested(&user1).is_err());
+
+    env.ledger().set_timestamp(1_250);
+    assert_eq!(client.releasable_amount(&user1, &schedule_id), 250);
+    assert_eq!(client.release_vested(&user1), 250);
+    assert_eq!(client.available_balance(&user1), 350);
+    assert_eq!(client.locked_balance(&user1), 750);
+    client.transfer(&user1, &user2, &350);
+
+    env.ledger().set_timestamp(5_000);
+    assert_eq!(client.release_vested(&user1), 750);
+    assert_eq!(client.locked_balance(&user1), 0);
+    assert_eq!(client.balance(&user1), 750);
+
+    let schedules = client.get_vesting_schedules(&user1);
+    assert_eq!(schedules.len(), 1);
+    assert_eq!(schedules.get(0).unwrap().released, 1_000);
+}
+
+#[test]
+#[should_panic(expected = "max supply exceeded")]
+fn test_mint_locked_respects_max_supply() {
+    let (env, admin, user1, _) = create_test_env();
+    let client = initialize_capped(&env, &admin, 1_000);
+
+    env.mock_all_auths();
+    client.mint_locked(&user1, &1_001, &0, &100);
+}
+
+#[test]
+fn test_transfer_fee() {
+    let (env, admin, user1, user2) = create_test_env();
+    let treasury = Address::generate(&env);
+    let escrow = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &10_000);
+    client.set_transfer_fee(&250, &treasury);
+    assert_eq!(client.get_transfer_fee(), 250);
+
+    client.transfer(&user1, &user2, &1_000);
+    assert_eq!(client.balance(&user1), 9_000);
+    assert_eq!(client.balance(&user2), 975);
+    assert_eq!(client.balance(&treasury), 25);
+
+    // Transfers to or from an exempt system contract are not charged.
+    client.set_fee_exempt(&escrow, &true);
+    assert!(client.is_fee_exempt(&escrow));
+    client.transfer(&user1, &escrow, &1_000);
+    assert_eq!(client.balance(&escrow), 1_000);
+    client.transfer(&escrow, &user2, &1_000);
+    assert_eq!(client.balance(&user2), 1_975);
+    assert_eq!(client.balance(&treasury), 25);
+    assert_eq!(client.total_supply(), 10_000);
+}
+
+#[test]
+#[should_panic(expected = "transfer fee exceeds cap")]
+fn test_transfer_fee_capped() {
+    let (env, admin, _, _) = create_test_env();
+    let treasury = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.set_transfer_fee(&(MAX_TRANSFER_FEE_BPS + 1), &treasury);
+}
+
+#[test]
+fn test_permit_sets_allowance_once() {
+    let (env, admin, user1, user2) = create_test_env();
+    let spender = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1_000);
+    env.ledger().set_timestamp(100);
+
+    assert_eq!(client.permit_nonce(&user1), 0);
+    client.permit(&user1, &spender, &400, &1_000, &0, &200);
+    assert_eq!(
+        env.auths()[0],
+        (
+            user1.clone(),
+            AuthorizedInvocation {
+                function: AuthorizedFunction::Contract((
+                    contract_id.clone(),
+                    Symbol::new(&env, "permit"),
+                    (spender.clone(), 400_i128, 1_000_u32, 0_u64, 200_u64).into_val(&env),
+                )),
+                sub_invocations: std::vec::Vec::new(),
+            }
+        )
+    );
+    assert_eq!(client.allowance(&user1, &spender), 400);
+    assert_eq!(client.permit_nonce(&user1), 1);
+
+    client.transfer_from(&spender, &user1, &user2, &400);
+    assert_eq!(client.balance(&user2), 400);
+
+    // Replaying the same authorization is rejected.
+    assert!(client
+        .try_permit(&user1, &spender, &400, &1_000, &0, &200)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "permit expired")]
+fn test_permit_deadline() {
+    let (env, admin, user1, _) = create_test_env();
+    let spender = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_timestamp(300);
+    client.permit(&user1, &spender, &400, &1_000, &0, &200);
+}
+
+#[test]
+fn test_batch_transfer() {
+    let (env, admin, user1, user2) = create_test_env();
+    let user3 = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1_000);
+
+    client.batch_transfer(
+        &user1,
+        &vec![&env, (user2.clone(), 300_i128), (user3.clone(), 200_i128)],
+    );
+    assert_eq!(client.balance(&user1), 500);
+    assert_eq!(client.balance(&user2), 300);
+    assert_eq!(client.balance(&user3), 200);
+
+    // A batch exceeding the balance moves nothing.
+    assert!(client
+        .try_batch_transfer(
+            &user1,
+            &vec![&env, (user2.clone(), 400_i128), (user3.clone(), 400_i128)],
+        )
+        .is_err());
+    assert_eq!(client.balance(&user1), 500);
+    assert_eq!(client.balance(&user2), 300);
+}
+
+#[test]
+fn test_airdrop() {
+    let (env, admin, user1, user2) = create_test_env();
+    let client = initialize_capped(&env, &admin, 1_000);
+
+    env.mock_all_auths();
+    client.airdrop(&vec![
+        &env,
+        (user1.clone(), 400_i128),
+        (user2.clone(), 100_i128),
+    ]);
+    assert_eq!(client.balance(&user1), 400);
+    assert_eq!(client.balance(&user2), 100);
+    assert_eq!(client.total_supply(), 500);
+
+    // Over the cap, or any invalid entry, rejects the whole airdrop.
+    assert!(client
+        .try_airdrop(&vec![
+            &env,
+            (user1.clone(), 400_i128),
+            (user2.clone(), 200_i128)
+        ])
+        .is_err());
+    assert!(client
+        .try_airdrop(&vec![
+            &env,
+            (user1.clone(), 100_i128),
+            (user2.clone(), 0_i128)
+        ])
+        .is_err());
+    assert_eq!(client.total_supply(), 500);
+}
+
+#[test]
+fn test_past_balances_and_supply() {
+    let (env, admin, user1, user2) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    env.ledger().set_sequence_number(10);
+    client.mint(&user1, &1_000);
+
+    env.ledger().set_sequence_number(20);
+    client.transfer(&user1, &user2, &300);
+    client.transfer(&user1, &user2, &100);
+
+    env.ledger().set_sequence_number(30);
+    client.mint_locked(&user1, &500, &0, &100);
+    client.burn(&user2, &50);
+
+    env.ledger().set_sequence_number(40);
+    assert_eq!(client.get_past_balance(&user1, &5), 0);
+    assert_eq!(client.get_past_balance(&user1, &10), 1_000);
+    assert_eq!(client.get_past_balance(&user1, &19), 1_000);
+    assert_eq!(client.get_past_balance(&user1, &20), 600);
+    assert_eq!(client.get_past_balance(&user1, &30), 1_100);
+    assert_eq!(client.get_past_balance(&user2, &25), 400);
+    assert_eq!(client.get_past_balance(&user2, &39), 350);
+
+    assert_eq!(client.get_past_total_supply(&9), 0);
+    assert_eq!(client.get_past_total_supply(&10), 1_000);
+    assert_eq!(client.get_past_total_supply(&30), 1_450);
+}
+
+#[test]
+#[should_panic(expected = "ledger must be in the past")]
+fn test_past_balance_requires_past_ledger() {
+    let (env, admin, user1, _) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+
+    env.ledger().set_sequence_number(10);
+    client.get_past_balance(&user1, &10);
+}
+
+#[test]
+fn test_compliance_mode_blocks_banned_addresses() {
+    let (env, admin, user1, user2) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+    let denylist_id = env.register(MockDenylist, ());
+    let denylist = MockDenylistClient::new(&env, &denylist_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1_000);
+    denylist.ban(&user2);
+
+    // Compliance mode is off by default.
+    client.transfer(&user1, &user2, &100);
+
+    client.set_compliance_source(&denylist_id);
+    assert_eq!(client.get_compliance_source(), Some(denylist_id));
+    assert!(client.try_transfer(&user1, &user2, &100).is_err());
+    assert!(client.try_transfer(&user2, &user1, &100).is_err());
+
+    // Admin override lets the address transact again.
+    client.set_compliance_override(&user2, &true);
+    client.transfer(&user2, &user1, &100);
+    assert_eq!(client.balance(&user1), 1_000);
+
+    client.set_compliance_override(&user2, &false);
+    assert!(client.try_transfer(&user1, &user2, &100).is_err());
+
+    client.disable_compliance();
+    client.transfer(&user1, &user2, &100);
+    assert_eq!(client.balance(&user2), 100);
+}
+
+#[test]
+fn test_conforms_to_arenax_token_interface() {
+    let (env, admin, user1, user2) = create_test_env();
+    let spender = Address::generate(&env);
+    let contract_id = initialize_contract(&env, &admin);
+    let client = AxTokenClient::new(&env, &contract_id);
+    let token = arenax_token_interface::Client::new(&env, &contract_id);
+
+    env.mock_all_auths();
+    client.mint(&user1, &1_000);
+
+    token.approve(&user1, &spender, &300, &100);
+    assert_eq!(token.allowance(&user1, &spender), 300);
+    token.transfer_from(&spender, &user1, &user2, &200);
+    token.burn_from(&spender, &user1, &100);
+    token.transfer(&user2, &user1, &50);
+    token.burn(&user2, &50);
+
+    assert_eq!(token.balance(&user1), 750);
+    assert_eq!(token.balance(&user2), 100);
+    assert_eq!(token.decimals(), DECIMALS);
+    assert_eq!(token.name(), String::from_str(&env, NAME));
+    assert_eq!(token.symbol(), String::from_str(&env, SYMBOL));
+}
@@ -0,0 +1,336 @@
+#![no_std]
+
+//! Peer challenges.
+//!
+//! A player posts a challenge naming the game, region, mode, stake and an expiry,
+//! either open to anyone or directed at one opponent, and commits the stake to this
+//! contract. An opponent accepts by matching the stake; acceptance creates the match
+//! in the Match Contract and its escrow in the Match Escrow Vault and funds both
+//! stakes in the same transaction. The challenger can cancel an open challenge, and
+//! anyone can refund one once it has expired. Open challenges are indexed by game.
+
+use arenax_events::challenge as events;
+use arenax_token_interface as token;
+use cross_contract_utils::staked_match::{self, StakedMatch};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec};
+
+/// Upper bound on open challenges per game.
+pub const MAX_OPEN_PER_GAME: u32 = 200;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    MatchContract,
+    EscrowVault,
+    NextChallengeId,
+    NextMatchNonce,
+    Challenge(u64),
+    OpenByGame(Symbol), // game_id -> Vec<challenge_id>
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ChallengeState {
+    Open = 0,
+    Accepted = 1,
+    Cancelled = 2,
+    Expired = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ChallengeTerms {
+    pub asset: Address,
+    /// Stake each side commits.
+    pub stake: i128,
+    pub game_id: Symbol,
+    pub region: Symbol,
+    pub mode: Symbol,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Challenge {
+    pub challenge_id: u64,
+    pub challenger: Address,
+    /// Only this player may accept; anyone may when `None`.
+    pub opponent: Option<Address>,
+    pub terms: ChallengeTerms,
+    pub state: u32,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub match_id: Option<BytesN<32>>,
+}
+
+#[contract]
+pub struct ChallengeContract;
+
+#[contractimpl]
+impl ChallengeContract {
+    /// This contract must be a registered creator in both `match_contract` and
+    /// `escrow_vault`.
+    pub fn initialize(env: Env, admin: Address, match_contract: Address, escrow_vault: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MatchContract, &match_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowVault, &escrow_vault);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextChallengeId, &1u64);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextMatchNonce, &0u64);
+    }
+
+    /// Post a challenge open until `expires_at`, transferring the stake to this
+    /// contract. Pass `opponent` to direct it at one player. Returns the challenge id.
+    pub fn post(
+        env: Env,
+        challenger: Address,
+        opponent: Option<Address>,
+        terms: ChallengeTerms,
+        expires_at: u64,
+    ) -> u64 {
+        challenger.require_auth();
+        if terms.stake <= 0 {
+            panic!("stake must be positive");
+        }
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            panic!("invalid expiry");
+        }
+        if opponent.as_ref() == Some(&challenger) {
+            panic!("cannot challenge yourself");
+        }
+        let mut open = Self::get_open_challenges(env.clone(), terms.game_id.clone());
+        if open.len() >= MAX_OPEN_PER_GAME {
+            panic!("too many open challenges");
+        }
+
+        token::Client::new(&env, &terms.asset).transfer(
+            &challenger,
+            &env.current_contract_address(),
+            &terms.stake,
+        );
+
+        let challenge_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextChallengeId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextChallengeId, &(challenge_id + 1));
+
+        let challenge = Challenge {
+            challenge_id,
+            challenger: challenger.clone(),
+            opponent: opponent.clone(),
+            terms: terms.clone(),
+            state: ChallengeState::Open as u32,
+            created_at: now,
+            expires_at,
+            match_id: None,
+        };
+        Self::save_challenge(&env, &challenge);
+        open.push_back(challenge_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::OpenByGame(terms.game_id.clone()), &open);
+
+        events::emit_challenge_posted(
+            &env,
+            challenge_id,
+            &challenger,
+            &opponent,
+            &terms.game_id,
+            &terms.asset,
+            terms.stake,
+            expires_at,
+        );
+        challenge_id
+    }
+
+    /// Accept an open challenge by matching its stake. Creates the match and its
+    /// escrow, funds both stakes, and returns the match id.
+    pub fn accept(env: Env, challenge_id: u64, opponent: Address) -> BytesN<32> {
+        opponent.require_auth();
+        let mut challenge = Self::get_challenge(env.clone(), challenge_id);
+        if challenge.state != ChallengeState::Open as u32 {
+            panic!("challenge not open");
+        }
+        if env.ledger().timestamp() >= challenge.expires_at {
+            panic!("challenge expired");
+        }
+        if opponent == challenge.challenger {
+            panic!("cannot challenge yourself");
+        }
+        if let Some(directed) = &challenge.opponent {
+            if directed != &opponent {
+                panic!("challenge directed at another player");
+            }
+        }
+
+        let terms = challenge.terms.clone();
+        token::Client::new(&env, &terms.asset).transfer(
+            &opponent,
+            &env.current_contract_address(),
+            &terms.stake,
+        );
+
+        let match_id = Self::next_match_id(&env);
+        let (match_contract, escrow_vault) = Self::match_contracts(&env);
+        staked_match::open_staked_match(
+            &env,
+            &match_contract,
+            &escrow_vault,
+            &StakedMatch {
+                match_id: match_id.clone(),
+                player_a: challenge.challenger.clone(),
+                player_b: opponent.clone(),
+                game_id: terms.game_id.clone(),
+                region: terms.region.clone(),
+                mode: terms.mode.clone(),
+                asset: terms.asset.clone(),
+                stake: terms.stake,
+            },
+        );
+
+        Self::remove_open(&env, &terms.game_id, challenge_id);
+        challenge.state = ChallengeState::Accepted as u32;
+        challenge.opponent = Some(opponent.clone());
+        challenge.match_id = Some(match_id.clone());
+        Self::save_challenge(&env, &challenge);
+
+        events::emit_challenge_accepted(
+            &env,
+            challenge_id,
+            &match_id,
+            &challenge.challenger,
+            &opponent,
+        );
+        match_id
+    }
+
+    /// Withdraw an open challenge and get the stake back (challenger only).
+    pub fn cancel(env: Env, challenge_id: u64) -> i128 {
+        let challenge = Self::get_challenge(env.clone(), challenge_id);
+        challenge.challenger.require_auth();
+        Self::close(&env, challenge, false)
+    }
+
+    /// Refund an open challenge past its expiry. Callable by anyone.
+    pub fn refund_expired(env: Env, challenge_id: u64) -> i128 {
+        let challenge = Self::get_challenge(env.clone(), challenge_id);
+        if env.ledger().timestamp() < challenge.expires_at {
+            panic!("challenge not expired");
+        }
+        Self::close(&env, challenge, true)
+    }
+
+    pub fn get_challenge(env: Env, challenge_id: u64) -> Challenge {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Challenge(challenge_id))
+            .expect("challenge not found")
+    }
+
+    /// Open challenges for `game_id`, oldest first. May include expired challenges
+    /// that have not been refunded yet.
+    pub fn get_open_challenges(env: Env, game_id: Symbol) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OpenByGame(game_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn close(env: &Env, mut challenge: Challenge, expired: bool) -> i128 {
+        if challenge.state != ChallengeState::Open as u32 {
+            panic!("challenge not open");
+        }
+        challenge.state = if expired {
+            ChallengeState::Expired as u32
+        } else {
+            ChallengeState::Cancelled as u32
+        };
+        Self::save_challenge(env, &challenge);
+        Self::remove_open(env, &challenge.terms.game_id, challenge.challenge_id);
+
+        let refund = challenge.terms.stake;
+        token::Client::new(env, &challenge.terms.asset).transfer(
+            &env.current_contract_address(),
+            &challenge.challenger,
+            &refund,
+        );
+
+        events::emit_challenge_closed(
+            env,
+            challenge.challenge_id,
+            &challenge.challenger,
+            refund,
+            expired,
+        );
+        refund
+    }
+
+    fn remove_open(env: &Env, game_id: &Symbol, challenge_id: u64) {
+        let mut open = Self::get_open_challenges(env.clone(), game_id.clone());
+        if let Some(index) = open.first_index_of(challenge_id) {
+            open.remove(index);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::OpenByGame(game_id.clone()), &open);
+    }
+
+    fn save_challenge(env: &Env, challenge: &Challenge) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Challenge(challenge.challenge_id), challenge);
+    }
+
+    fn next_match_id(env: &Env) -> BytesN<32> {
+        let nonce: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextMatchNonce)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextMatchNonce, &(nonce + 1));
+        staked_match::next_match_id(env, nonce)
+    }
+
+    fn match_contracts(env: &Env) -> (Address, Address) {
+        let match_contract = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchContract)
+            .expect("not initialized");
+        let escrow_vault = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowVault)
+            .expect("not initialized");
+        (match_contract, escrow_vault)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,169 @@
+#![cfg(test)]
+
+use super::*;
+use match_contract::{GameConfig, MatchContract, MatchContractClient};
+use match_escrow_vault::{EscrowState, MatchEscrowVault, MatchEscrowVaultClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env,
+};
+
+const STAKE: i128 = 250;
+const NOW: u64 = 1_000;
+const EXPIRY: u64 = NOW + 600;
+
+struct Setup<'a> {
+    env: Env,
+    client: ChallengeContractClient<'a>,
+    matches: MatchContractClient<'a>,
+    escrow: MatchEscrowVaultClient<'a>,
+    token: TokenClient<'a>,
+    minter: StellarAssetClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(NOW);
+
+    let admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+
+    let matches = MatchContractClient::new(&env, &env.register(MatchContract, ()));
+    matches.initialize(&admin);
+    matches.register_game(
+        &symbol_short!("chess"),
+        &GameConfig {
+            max_score: 1,
+            round_count: 1,
+            modes: Vec::new(&env),
+        },
+    );
+
+    let escrow = MatchEscrowVaultClient::new(&env, &env.register(MatchEscrowVault, ()));
+    escrow.initialize(&admin);
+
+    let contract_id = env.register(ChallengeContract, ());
+    let client = ChallengeContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &matches.address, &escrow.address);
+    matches.set_creator(&contract_id, &true);
+    escrow.set_creator(&contract_id, &true);
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        minter: StellarAssetClient::new(&env, &sac.address()),
+        env,
+        client,
+        matches,
+        escrow,
+    }
+}
+
+fn terms(s: &Setup) -> ChallengeTerms {
+    ChallengeTerms {
+        asset: s.token.address.clone(),
+        stake: STAKE,
+        game_id: symbol_short!("chess"),
+        region: symbol_short!("eu"),
+        mode: symbol_short!("blitz"),
+    }
+}
+
+fn player(s: &Setup) -> Address {
+    let player = Address::generate(&s.env);
+    s.minter.mint(&player, &1_000);
+    player
+}
+
+#[test]
+fn test_accept_creates_funded_match() {
+    let s = setup();
+    let challenger = player(&s);
+    let opponent = player(&s);
+    let id = s.client.post(&challenger, &None, &terms(&s), &EXPIRY);
+    assert_eq!(
+        s.client.get_open_challenges(&symbol_short!("chess")),
+        vec![&s.env, id]
+    );
+    assert_eq!(s.token.balance(&s.client.address), STAKE);
+
+    let match_id = s.client.accept(&id, &opponent);
+
+    let created = s.matches.get_match(&match_id);
+    assert_eq!(created.player_a, challenger);
+    assert_eq!(created.player_b, opponent);
+    let escrow = s.escrow.get_escrow(&match_id);
+    assert_eq!(escrow.state, EscrowState::FullyFunded as u32);
+    assert_eq!(s.token.balance(&s.escrow.address), 2 * STAKE);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    let challenge = s.client.get_challenge(&id);
+    assert_eq!(challenge.state, ChallengeState::Accepted as u32);
+    assert_eq!(challenge.match_id, Some(match_id));
+    assert!(s
+        .client
+        .get_open_challenges(&symbol_short!("chess"))
+        .is_empty());
+    assert!(s.client.try_accept(&id, &player(&s)).is_err());
+}
+
+#[test]
+fn test_directed_challenge_only_accepted_by_opponent() {
+    let s = setup();
+    let challenger = player(&s);
+    let opponent = player(&s);
+    let id = s
+        .client
+        .post(&challenger, &Some(opponent.clone()), &terms(&s), &EXPIRY);
+
+    assert!(s.client.try_accept(&id, &player(&s)).is_err());
+    assert!(s.client.try_accept(&id, &challenger).is_err());
+    s.client.accept(&id, &opponent);
+}
+
+#[test]
+fn test_expired_challenge_refunds() {
+    let s = setup();
+    let challenger = player(&s);
+    let id = s.client.post(&challenger, &None, &terms(&s), &EXPIRY);
+    assert!(s.client.try_refund_expired(&id).is_err());
+
+    s.env.ledger().set_timestamp(EXPIRY);
+    assert!(s.client.try_accept(&id, &player(&s)).is_err());
+    assert_eq!(s.client.refund_expired(&id), STAKE);
+    assert_eq!(s.token.balance(&challenger), 1_000);
+    assert_eq!(
+        s.client.get_challenge(&id).state,
+        ChallengeState::Expired as u32
+    );
+    assert!(s
+        .client
+        .get_open_challenges(&symbol_short!("chess"))
+        .is_empty());
+    assert!(s.client.try_refund_expired(&id).is_err());
+}
+
+#[test]
+fn test_cancel_open_challenge() {
+    let s = setup();
+    let challenger = player(&s);
+    let first = s.client.post(&challenger, &None, &terms(&s), &EXPIRY);
+    let second = s.client.post(&challenger, &None, &terms(&s), &EXPIRY);
+
+    assert_eq!(s.client.cancel(&first), STAKE);
+    assert_eq!(
+        s.client.get_open_challenges(&symbol_short!("chess")),
+        vec![&s.env, second]
+    );
+    assert_eq!(s.token.balance(&challenger), 1_000 - STAKE);
+    assert!(s.client.try_accept(&first, &player(&s)).is_err());
+}
+
+#[test]
+#[should_panic(expected = "invalid expiry")]
+fn test_expiry_must_be_in_future() {
+    let s = setup();
+    s.client.post(&player(&s), &None, &terms(&s), &NOW);
+}
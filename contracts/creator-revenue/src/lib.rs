@@ -0,0 +1,237 @@
+#![no_std]
+
+//! Creator revenue share for streamed matches.
+//!
+//! Approved tournament organizers register the streamers covering a match, each with
+//! a share of the match's revenue in basis points. Spectator side-pot and sponsorship
+//! revenue for the match is routed here with `route_revenue`: each streamer's share
+//! is credited to their claimable balance and the remainder to the organizer's.
+//! Balances are kept per asset and withdrawn with `claim`. A match's splits can be
+//! changed until the first revenue for it arrives.
+
+use arenax_events::creator_revenue as events;
+use arenax_token_interface as token;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec};
+
+/// Basis point denominator for streamer shares.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Upper bound on streamers per match.
+pub const MAX_STREAMERS_PER_MATCH: u32 = 10;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Organizer(Address),
+    MatchStreams(BytesN<32>),
+    Claimable(Address, Address), // (account, asset) -> i128
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StreamerShare {
+    pub streamer: Address,
+    pub share_bps: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchStreams {
+    pub match_id: BytesN<32>,
+    pub organizer: Address,
+    pub streamers: Vec<StreamerShare>,
+    /// Number of `route_revenue` calls for the match; splits are locked once non-zero.
+    pub payouts: u32,
+}
+
+#[contract]
+pub struct CreatorRevenueContract;
+
+#[contractimpl]
+impl CreatorRevenueContract {
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+    }
+
+    /// Allow or disallow `organizer` to register streamers (admin only).
+    pub fn set_organizer(env: Env, organizer: Address, enabled: bool) {
+        Self::require_admin(&env);
+        let key = DataKey::Organizer(organizer.clone());
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        events::emit_organizer_updated(&env, &organizer, enabled);
+    }
+
+    /// Register the streamers for `match_id` and their shares, replacing any earlier
+    /// registration (organizers only). Only the organizer that first registered a
+    /// match can change it, and only before revenue has been routed to it.
+    pub fn register_streams(
+        env: Env,
+        organizer: Address,
+        match_id: BytesN<32>,
+        streamers: Vec<StreamerShare>,
+    ) {
+        organizer.require_auth();
+        if !Self::is_organizer(env.clone(), organizer.clone()) {
+            panic!("not an organizer");
+        }
+        if let Some(existing) = Self::get_streams(env.clone(), match_id.clone()) {
+            if existing.organizer != organizer {
+                panic!("match registered by another organizer");
+            }
+            if existing.payouts > 0 {
+                panic!("splits locked");
+            }
+        }
+
+        if streamers.is_empty() || streamers.len() > MAX_STREAMERS_PER_MATCH {
+            panic!("invalid streamers");
+        }
+        let mut total_bps = 0u32;
+        for (i, share) in streamers.iter().enumerate() {
+            if share.share_bps == 0 {
+                panic!("share must be positive");
+            }
+            if streamers
+                .slice(i as u32 + 1..)
+                .iter()
+                .any(|other| other.streamer == share.streamer)
+            {
+                panic!("duplicate streamer");
+            }
+            total_bps += share.share_bps;
+        }
+        if total_bps > BPS_DENOMINATOR {
+            panic!("shares exceed 100%");
+        }
+
+        let streams = MatchStreams {
+            match_id: match_id.clone(),
+            organizer: organizer.clone(),
+            streamers: streamers.clone(),
+            payouts: 0,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::MatchStreams(match_id.clone()), &streams);
+
+        events::emit_streams_registered(&env, &match_id, &organizer, streamers.len(), total_bps);
+    }
+
+    /// Pay `amount` of `asset` revenue for `match_id`, tagged with its `source` (e.g.
+    /// `sidepot`, `sponsor`). Streamers are credited their shares and the organizer the
+    /// remainder. Returns the total credited to streamers.
+    pub fn route_revenue(
+        env: Env,
+        payer: Address,
+        match_id: BytesN<32>,
+        source: Symbol,
+        asset: Address,
+        amount: i128,
+    ) -> i128 {
+        payer.require_auth();
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        let mut streams =
+            Self::get_streams(env.clone(), match_id.clone()).expect("match not registered");
+
+        token::Client::new(&env, &asset).transfer(&payer, &env.current_contract_address(), &amount);
+
+        let mut to_streamers = 0i128;
+        for share in streams.streamers.iter() {
+            let cut = amount * share.share_bps as i128 / BPS_DENOMINATOR as i128;
+            if cut > 0 {
+                Self::credit(&env, &share.streamer, &asset, cut);
+                to_streamers += cut;
+            }
+        }
+        if amount > to_streamers {
+            Self::credit(&env, &streams.organizer, &asset, amount - to_streamers);
+        }
+
+        streams.payouts += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::MatchStreams(match_id.clone()), &streams);
+
+        events::emit_revenue_routed(
+            &env,
+            &match_id,
+            &payer,
+            &source,
+            &asset,
+            amount,
+            to_streamers,
+        );
+        to_streamers
+    }
+
+    /// Withdraw `account`'s whole claimable balance of `asset`.
+    pub fn claim(env: Env, account: Address, asset: Address) -> i128 {
+        account.require_auth();
+        let amount = Self::get_claimable(env.clone(), account.clone(), asset.clone());
+        if amount == 0 {
+            panic!("nothing to claim");
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Claimable(account.clone(), asset.clone()));
+
+        token::Client::new(&env, &asset).transfer(
+            &env.current_contract_address(),
+            &account,
+            &amount,
+        );
+
+        events::emit_revenue_claimed(&env, &account, &asset, amount);
+        amount
+    }
+
+    pub fn get_streams(env: Env, match_id: BytesN<32>) -> Option<MatchStreams> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::MatchStreams(match_id))
+    }
+
+    pub fn get_claimable(env: Env, account: Address, asset: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claimable(account, asset))
+            .unwrap_or(0)
+    }
+
+    pub fn is_organizer(env: Env, organizer: Address) -> bool {
+        env.storage().instance().has(&DataKey::Organizer(organizer))
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn credit(env: &Env, account: &Address, asset: &Address, amount: i128) {
+        let balance = Self::get_claimable(env.clone(), account.clone(), asset.clone());
+        env.storage().persistent().set(
+            &DataKey::Claimable(account.clone(), asset.clone()),
+            &(balance + amount),
+        );
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,144 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env,
+};
+
+struct Setup<'a> {
+    env: Env,
+    organizer: Address,
+    sponsor: Address,
+    client: CreatorRevenueContractClient<'a>,
+    token: TokenClient<'a>,
+    match_id: BytesN<32>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+    let sponsor = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &sac.address()).mint(&sponsor, &100_000);
+
+    let contract_id = env.register(CreatorRevenueContract, ());
+    let client = CreatorRevenueContractClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.set_organizer(&organizer, &true);
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        match_id: BytesN::from_array(&env, &[5u8; 32]),
+        env,
+        organizer,
+        sponsor,
+        client,
+    }
+}
+
+fn share(streamer: &Address, share_bps: u32) -> StreamerShare {
+    StreamerShare {
+        streamer: streamer.clone(),
+        share_bps,
+    }
+}
+
+fn route(s: &Setup, amount: i128) -> i128 {
+    s.client.route_revenue(
+        &s.sponsor,
+        &s.match_id,
+        &symbol_short!("sponsor"),
+        &s.token.address,
+        &amount,
+    )
+}
+
+#[test]
+fn test_revenue_split_between_streamers_and_organizer() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    s.client.register_streams(
+        &s.organizer,
+        &s.match_id,
+        &vec![&s.env, share(&alice, 3_000), share(&bob, 1_000)],
+    );
+
+    assert_eq!(route(&s, 1_000), 400);
+    assert_eq!(route(&s, 500), 200);
+    assert_eq!(s.client.get_claimable(&alice, &s.token.address), 450);
+    assert_eq!(s.client.get_claimable(&bob, &s.token.address), 150);
+    assert_eq!(s.client.get_claimable(&s.organizer, &s.token.address), 900);
+
+    assert_eq!(s.client.claim(&alice, &s.token.address), 450);
+    assert_eq!(s.token.balance(&alice), 450);
+    assert!(s.client.try_claim(&alice, &s.token.address).is_err());
+}
+
+#[test]
+fn test_splits_locked_after_first_revenue() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let streams = vec![&s.env, share(&alice, 2_000)];
+    s.client
+        .register_streams(&s.organizer, &s.match_id, &streams);
+    // Splits can change until revenue arrives.
+    s.client
+        .register_streams(&s.organizer, &s.match_id, &streams);
+
+    route(&s, 100);
+    assert!(s
+        .client
+        .try_register_streams(&s.organizer, &s.match_id, &streams)
+        .is_err());
+}
+
+#[test]
+fn test_registration_rules() {
+    let s = setup();
+    let alice = Address::generate(&s.env);
+    let outsider = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_register_streams(&outsider, &s.match_id, &vec![&s.env, share(&alice, 100)])
+        .is_err());
+    assert!(s
+        .client
+        .try_register_streams(
+            &s.organizer,
+            &s.match_id,
+            &vec![&s.env, share(&alice, 100), share(&alice, 200)]
+        )
+        .is_err());
+    assert!(s
+        .client
+        .try_register_streams(
+            &s.organizer,
+            &s.match_id,
+            &vec![&s.env, share(&alice, 6_000), share(&outsider, 5_000)]
+        )
+        .is_err());
+
+    // Another organizer cannot take over a registered match.
+    s.client
+        .register_streams(&s.organizer, &s.match_id, &vec![&s.env, share(&alice, 100)]);
+    let other = Address::generate(&s.env);
+    s.client.set_organizer(&other, &true);
+    assert!(s
+        .client
+        .try_register_streams(&other, &s.match_id, &vec![&s.env, share(&alice, 100)])
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "match not registered")]
+fn test_revenue_requires_registered_match() {
+    let s = setup();
+    route(&s, 100);
+}
@@ -11,6 +11,8 @@
 //! * [`CallResult`] — alias for `Result<T, CallError>`.
 //! * Macro [`cross_call!`] — ergonomic wrapper that assembles the arg `Vec`
 //!   and delegates to `CrossContractCaller`.
+//! * [`staked_match`] — match ids and match/escrow creation for contracts that
+//!   hold both players' stakes.
 //!
 //! ## Usage
 //!
@@ -27,6 +29,8 @@
 
 #![no_std]
 
+pub mod staked_match;
+
 use soroban_sdk::{contracterror, contracttype, Address, Env, IntoVal, Symbol, Val, Vec};
 
 // ---------------------------------------------------------------------------
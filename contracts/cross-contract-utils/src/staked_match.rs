@@ -0,0 +1,97 @@
+//! Opening a staked match from a contract that already holds both players'
+//! stakes, such as the matchmaking queue and peer challenges.
+//!
+//! The calling contract must be a registered creator in both the Match Contract
+//! and the Match Escrow Vault. Match ids are predictable, but since only
+//! creators can use them, nobody else can claim an id before the caller does.
+
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    vec,
+    xdr::ToXdr,
+    Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+/// Terms of a match opened by [`open_staked_match`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakedMatch {
+    pub match_id: BytesN<32>,
+    pub player_a: Address,
+    pub player_b: Address,
+    pub game_id: Symbol,
+    pub region: Symbol,
+    pub mode: Symbol,
+    pub asset: Address,
+    /// Stake each player commits.
+    pub stake: i128,
+}
+
+/// Match id for the calling contract's `nonce`-th match.
+pub fn next_match_id(env: &Env, nonce: u64) -> BytesN<32> {
+    let mut data = env.current_contract_address().to_xdr(env);
+    data.extend_from_array(&nonce.to_be_bytes());
+    env.crypto().sha256(&data).to_bytes()
+}
+
+/// Create the match and its escrow as the calling contract, then fund both
+/// stakes from the calling contract's balance.
+pub fn open_staked_match(
+    env: &Env,
+    match_contract: &Address,
+    escrow_vault: &Address,
+    terms: &StakedMatch,
+) {
+    let this = env.current_contract_address();
+    env.invoke_contract::<()>(
+        match_contract,
+        &Symbol::new(env, "create_match"),
+        (
+            terms.match_id.clone(),
+            terms.player_a.clone(),
+            terms.player_b.clone(),
+            terms.game_id.clone(),
+            terms.region.clone(),
+            terms.mode.clone(),
+            this.clone(),
+        )
+            .into_val(env),
+    );
+    env.invoke_contract::<()>(
+        escrow_vault,
+        &Symbol::new(env, "create_escrow"),
+        (
+            terms.match_id.clone(),
+            terms.player_a.clone(),
+            terms.player_b.clone(),
+            terms.stake,
+            terms.asset.clone(),
+            this,
+        )
+            .into_val(env),
+    );
+    for player in [&terms.player_a, &terms.player_b] {
+        fund_escrow(env, escrow_vault, terms, player);
+    }
+}
+
+fn fund_escrow(env: &Env, escrow_vault: &Address, terms: &StakedMatch, player: &Address) {
+    let this = env.current_contract_address();
+
+    // `deposit_for` pulls the stake from this contract.
+    env.authorize_as_current_contract(vec![
+        env,
+        InvokerContractAuthEntry::Contract(SubContractInvocation {
+            context: ContractContext {
+                contract: terms.asset.clone(),
+                fn_name: Symbol::new(env, "transfer"),
+                args: (this.clone(), escrow_vault.clone(), terms.stake).into_val(env),
+            },
+            sub_invocations: Vec::new(env),
+        }),
+    ]);
+    env.invoke_contract::<()>(
+        escrow_vault,
+        &Symbol::new(env, "deposit_for"),
+        (terms.match_id.clone(), player.clone(), this).into_val(env),
+    );
+}
@@ -0,0 +1,410 @@
+#![no_std]
+
+//! ArenaX DAO.
+//!
+//! AX holders propose and vote on parameter changes (fee bps, decay rates, penalty
+//! tables). Voting weight is the holder's AX balance at the ledger before the
+//! proposal was created, read from the token's checkpoints, so tokens moved after a
+//! proposal opens cannot vote twice. Proposals that pass quorum wait out a timelock
+//! and then execute as cross-contract calls made by this contract, which is installed
+//! as the admin of the target contracts in place of a single key.
+
+use arenax_events::dao as events;
+use soroban_sdk::{
+    contract, contractclient, contractimpl, contracttype, Address, Env, IntoVal, String, Symbol,
+    TryFromVal, Val, Vec,
+};
+
+/// Upper bound on the calls bundled into one proposal.
+pub const MAX_ACTIONS: u32 = 10;
+
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+pub const SUPPORT_AGAINST: u32 = 0;
+pub const SUPPORT_FOR: u32 = 1;
+pub const SUPPORT_ABSTAIN: u32 = 2;
+
+/// Checkpointed balance reads exposed by the AX token.
+#[contractclient(name = "VotesClient")]
+pub trait Votes {
+    fn get_past_balance(env: Env, addr: Address, ledger: u32) -> i128;
+    fn get_past_total_supply(env: Env, ledger: u32) -> i128;
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Guardian,
+    Token,
+    Config,
+    NextProposalId,
+    Proposal(u64),
+    Receipt(u64, Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DaoConfig {
+    /// Seconds a proposal stays open for voting.
+    pub voting_period: u64,
+    /// Share of the snapshot total supply that must vote (for + abstain).
+    pub quorum_bps: u32,
+    /// Snapshot balance needed to open a proposal.
+    pub proposal_threshold: i128,
+    /// Seconds between a proposal passing and becoming executable.
+    pub timelock: u64,
+}
+
+/// A single cross-contract call made when a proposal executes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParamCall {
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProposalState {
+    Active = 0,
+    Queued = 1,
+    Executed = 2,
+    Defeated = 3,
+    Cancelled = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Proposal {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub description: String,
+    pub actions: Vec<ParamCall>,
+    pub snapshot_ledger: u32,
+    pub voting_end: u64,
+    pub for_votes: i128,
+    pub against_votes: i128,
+    pub abstain_votes: i128,
+    pub state: u32,
+    /// Earliest execution time; set when the proposal is queued.
+    pub eta: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Receipt {
+    pub support: u32,
+    pub weight: i128,
+}
+
+#[contract]
+pub struct DaoContract;
+
+#[contractimpl]
+impl DaoContract {
+    /// `guardian` may cancel proposals before they execute; it has no other powers.
+    pub fn initialize(env: Env, guardian: Address, token: Address, config: DaoConfig) {
+        if env.storage().instance().has(&DataKey::Guardian) {
+            panic!("already initialized");
+        }
+        guardian.require_auth();
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &1u64);
+        Self::write_config(&env, &config);
+    }
+
+    pub fn propose(
+        env: Env,
+        proposer: Address,
+        description: String,
+        actions: Vec<ParamCall>,
+    ) -> u64 {
+        proposer.require_auth();
+
+        if actions.is_empty() || actions.len() > MAX_ACTIONS {
+            panic!("invalid action count");
+        }
+
+        let config = Self::get_config(env.clone());
+        let snapshot_ledger = env
+            .ledger()
+            .sequence()
+            .checked_sub(1)
+            .expect("no snapshot ledger");
+        if Self::votes(&env).get_past_balance(&proposer, &snapshot_ledger)
+            < config.proposal_threshold
+        {
+            panic!("below proposal threshold");
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(proposal_id + 1));
+
+        let proposal = Proposal {
+            proposal_id,
+            proposer: proposer.clone(),
+            description: description.clone(),
+            actions: actions.clone(),
+            snapshot_ledger,
+            voting_end: env.ledger().timestamp() + config.voting_period,
+            for_votes: 0,
+            against_votes: 0,
+            abstain_votes: 0,
+            state: ProposalState::Active as u32,
+            eta: 0,
+        };
+        Self::save_proposal(&env, &proposal);
+
+        events::emit_proposal_created(
+            &env,
+            proposal_id,
+            &proposer,
+            &description,
+            actions.len(),
+            snapshot_ledger,
+            proposal.voting_end,
+        );
+        proposal_id
+    }
+
+    /// Vote with the voter's balance at the proposal snapshot. Returns the weight used.
+    pub fn cast_vote(env: Env, voter: Address, proposal_id: u64, support: u32) -> i128 {
+        voter.require_auth();
+
+        if support > SUPPORT_ABSTAIN {
+            panic!("invalid support value");
+        }
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if proposal.state != ProposalState::Active as u32 {
+            panic!("proposal is not active");
+        }
+        if env.ledger().timestamp() >= proposal.voting_end {
+            panic!("voting period has ended");
+        }
+
+        let receipt_key = DataKey::Receipt(proposal_id, voter.clone());
+        if env.storage().persistent().has(&receipt_key) {
+            panic!("already voted");
+        }
+
+        let weight = Self::votes(&env).get_past_balance(&voter, &proposal.snapshot_ledger);
+        if weight <= 0 {
+            panic!("no voting power");
+        }
+
+        match support {
+            SUPPORT_FOR => proposal.for_votes += weight,
+            SUPPORT_AGAINST => proposal.against_votes += weight,
+            _ => proposal.abstain_votes += weight,
+        }
+        Self::save_proposal(&env, &proposal);
+        env.storage()
+            .persistent()
+            .set(&receipt_key, &Receipt { support, weight });
+
+        events::emit_vote_cast(&env, proposal_id, &voter, support, weight);
+        weight
+    }
+
+    /// Close voting. A proposal that met quorum with more for than against votes is
+    /// queued behind the timelock; anything else is defeated. Callable by anyone.
+    pub fn queue(env: Env, proposal_id: u64) -> u32 {
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if proposal.state != ProposalState::Active as u32 {
+            panic!("proposal is not active");
+        }
+        if env.ledger().timestamp() < proposal.voting_end {
+            panic!("voting period has not ended");
+        }
+
+        let config = Self::get_config(env.clone());
+        let quorum = Self::quorum(&env, &config, proposal.snapshot_ledger);
+        let turnout = proposal.for_votes + proposal.abstain_votes;
+
+        if turnout >= quorum && proposal.for_votes > proposal.against_votes {
+            proposal.state = ProposalState::Queued as u32;
+            proposal.eta = env.ledger().timestamp() + config.timelock;
+            events::emit_proposal_queued(&env, proposal_id, proposal.eta);
+        } else {
+            proposal.state = ProposalState::Defeated as u32;
+            events::emit_proposal_defeated(
+                &env,
+                proposal_id,
+                proposal.for_votes,
+                proposal.against_votes,
+                proposal.abstain_votes,
+                quorum,
+            );
+        }
+
+        Self::save_proposal(&env, &proposal);
+        proposal.state
+    }
+
+    /// Run a queued proposal's calls once its timelock has passed. Callable by anyone;
+    /// the calls are authorized as this contract.
+    pub fn execute(env: Env, executor: Address, proposal_id: u64) {
+        executor.require_auth();
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if proposal.state != ProposalState::Queued as u32 {
+            panic!("proposal is not queued");
+        }
+        if env.ledger().timestamp() < proposal.eta {
+            panic!("timelock has not expired");
+        }
+
+        proposal.state = ProposalState::Executed as u32;
+        Self::save_proposal(&env, &proposal);
+
+        for action in proposal.actions.iter() {
+            if action.target == env.current_contract_address() {
+                // Contracts cannot re-enter themselves, so self-calls are applied here.
+                Self::apply_self_call(&env, &action);
+            } else {
+                env.invoke_contract::<Val>(&action.target, &action.function, action.args);
+            }
+        }
+
+        events::emit_proposal_executed(&env, proposal_id, &executor);
+    }
+
+    /// Cancel a proposal before it executes (guardian or its proposer).
+    pub fn cancel(env: Env, proposal_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if caller != proposal.proposer && caller != Self::get_guardian(env.clone()) {
+            panic!("unauthorized caller");
+        }
+        if proposal.state != ProposalState::Active as u32
+            && proposal.state != ProposalState::Queued as u32
+        {
+            panic!("proposal is closed");
+        }
+
+        proposal.state = ProposalState::Cancelled as u32;
+        Self::save_proposal(&env, &proposal);
+        events::emit_proposal_cancelled(&env, proposal_id, &caller);
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> Proposal {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .expect("proposal not found")
+    }
+
+    pub fn get_receipt(env: Env, proposal_id: u64, voter: Address) -> Option<Receipt> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Receipt(proposal_id, voter))
+    }
+
+    /// Votes (for + abstain) a proposal needs to reach quorum.
+    pub fn get_quorum(env: Env, proposal_id: u64) -> i128 {
+        let proposal = Self::get_proposal(env.clone(), proposal_id);
+        Self::quorum(
+            &env,
+            &Self::get_config(env.clone()),
+            proposal.snapshot_ledger,
+        )
+    }
+
+    pub fn get_config(env: Env) -> DaoConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .expect("not initialized")
+    }
+
+    pub fn get_guardian(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Guardian)
+            .expect("not initialized")
+    }
+
+    pub fn get_token(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Token)
+            .expect("not initialized")
+    }
+
+    fn votes(env: &Env) -> VotesClient<'_> {
+        VotesClient::new(env, &Self::get_token(env.clone()))
+    }
+
+    fn quorum(env: &Env, config: &DaoConfig, snapshot_ledger: u32) -> i128 {
+        let supply = Self::votes(env).get_past_total_supply(&snapshot_ledger);
+        supply * config.quorum_bps as i128 / BPS_DENOMINATOR as i128
+    }
+
+    /// Voting rules change through a proposal whose action targets this contract with
+    /// `set_config(DaoConfig)`.
+    fn apply_self_call(env: &Env, action: &ParamCall) {
+        if action.function != Symbol::new(env, "set_config") || action.args.len() != 1 {
+            panic!("unsupported self call");
+        }
+        let config = DaoConfig::try_from_val(env, &action.args.get(0).unwrap())
+            .unwrap_or_else(|_| panic!("invalid config"));
+        Self::write_config(env, &config);
+    }
+
+    fn write_config(env: &Env, config: &DaoConfig) {
+        if config.voting_period == 0 {
+            panic!("invalid voting period");
+        }
+        if config.quorum_bps == 0 || config.quorum_bps > BPS_DENOMINATOR {
+            panic!("invalid quorum");
+        }
+        if config.proposal_threshold < 0 {
+            panic!("invalid proposal threshold");
+        }
+
+        env.storage().instance().set(&DataKey::Config, config);
+        events::emit_config_updated(
+            env,
+            config.voting_period,
+            config.quorum_bps,
+            config.proposal_threshold,
+            config.timelock,
+        );
+    }
+
+    fn save_proposal(env: &Env, proposal: &Proposal) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal.proposal_id), proposal);
+    }
+}
+
+/// Build a `ParamCall` from a target, function name and argument tuple.
+pub fn param_call<A: IntoVal<Env, Vec<Val>>>(
+    env: &Env,
+    target: &Address,
+    function: &str,
+    args: A,
+) -> ParamCall {
+    ParamCall {
+        target: target.clone(),
+        function: Symbol::new(env, function),
+        args: args.into_val(env),
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,281 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    Address, Env, IntoVal, String, Vec,
+};
+
+const DAY: u64 = 86_400;
+
+/// AX stand-in: a balance counts from the ledger after it was set.
+#[contract]
+pub struct MockVotesToken;
+
+#[contractimpl]
+impl MockVotesToken {
+    pub fn set_balance(env: Env, addr: Address, amount: i128) {
+        let sequence = env.ledger().sequence();
+        env.storage().persistent().set(&addr, &(sequence, amount));
+        let supply: i128 = env
+            .storage()
+            .persistent()
+            .get(&symbol_short!("supply"))
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&symbol_short!("supply"), &(supply + amount));
+    }
+
+    pub fn get_past_balance(env: Env, addr: Address, ledger: u32) -> i128 {
+        assert!(ledger < env.ledger().sequence());
+        match env.storage().persistent().get::<_, (u32, i128)>(&addr) {
+            Some((set_at, amount)) if set_at <= ledger => amount,
+            _ => 0,
+        }
+    }
+
+    pub fn get_past_total_supply(env: Env, ledger: u32) -> i128 {
+        assert!(ledger < env.ledger().sequence());
+        env.storage()
+            .persistent()
+            .get(&symbol_short!("supply"))
+            .unwrap_or(0)
+    }
+}
+
+/// A contract with a tunable parameter behind its admin.
+#[contract]
+pub struct MockFeeConfig;
+
+#[contractimpl]
+impl MockFeeConfig {
+    pub fn init(env: Env, admin: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+    }
+
+    pub fn set_fee_bps(env: Env, fee_bps: u32) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("fee"), &fee_bps);
+    }
+
+    pub fn fee_bps(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("fee"))
+            .unwrap_or(0)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    guardian: Address,
+    client: DaoContractClient<'a>,
+    token: MockVotesTokenClient<'a>,
+    target: MockFeeConfigClient<'a>,
+}
+
+fn config() -> DaoConfig {
+    DaoConfig {
+        voting_period: 3 * DAY,
+        quorum_bps: 2_000,
+        proposal_threshold: 100,
+        timelock: DAY,
+    }
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+    env.ledger().set_sequence_number(10);
+
+    let guardian = Address::generate(&env);
+    let token_id = env.register(MockVotesToken, ());
+    let contract_id = env.register(DaoContract, ());
+    let client = DaoContractClient::new(&env, &contract_id);
+    client.initialize(&guardian, &token_id, &config());
+
+    // The DAO, not a key, administers the tunable contract.
+    let target_id = env.register(MockFeeConfig, ());
+    let target = MockFeeConfigClient::new(&env, &target_id);
+    target.init(&contract_id);
+
+    Setup {
+        token: MockVotesTokenClient::new(&env, &token_id),
+        env,
+        guardian,
+        client,
+        target,
+    }
+}
+
+fn holder(s: &Setup, amount: i128) -> Address {
+    let addr = Address::generate(&s.env);
+    s.token.set_balance(&addr, &amount);
+    addr
+}
+
+fn next_ledger(s: &Setup) {
+    s.env
+        .ledger()
+        .set_sequence_number(s.env.ledger().sequence() + 1);
+}
+
+fn fee_actions(s: &Setup, fee_bps: u32) -> Vec<ParamCall> {
+    Vec::from_array(
+        &s.env,
+        [param_call(
+            &s.env,
+            &s.target.address,
+            "set_fee_bps",
+            (fee_bps,),
+        )],
+    )
+}
+
+fn propose(s: &Setup, proposer: &Address, actions: Vec<ParamCall>) -> u64 {
+    s.client.propose(
+        proposer,
+        &String::from_str(&s.env, "Lower the platform fee"),
+        &actions,
+    )
+}
+
+#[test]
+fn test_passed_proposal_executes_after_timelock() {
+    let s = setup();
+    let alice = holder(&s, 600);
+    let bob = holder(&s, 300);
+    holder(&s, 100);
+    next_ledger(&s);
+
+    let id = propose(&s, &alice, fee_actions(&s, 250));
+    assert_eq!(s.client.cast_vote(&alice, &id, &SUPPORT_FOR), 600);
+    s.client.cast_vote(&bob, &id, &SUPPORT_AGAINST);
+    assert!(s.client.try_cast_vote(&alice, &id, &SUPPORT_FOR).is_err());
+
+    // Voting still open.
+    assert!(s.client.try_queue(&id).is_err());
+
+    s.env.ledger().set_timestamp(1_000 + 3 * DAY);
+    assert!(s.client.try_cast_vote(&bob, &id, &SUPPORT_FOR).is_err());
+    assert_eq!(s.client.queue(&id), ProposalState::Queued as u32);
+
+    let executor = Address::generate(&s.env);
+    assert!(s.client.try_execute(&executor, &id).is_err());
+
+    s.env.ledger().set_timestamp(1_000 + 4 * DAY);
+    s.client.execute(&executor, &id);
+    assert_eq!(s.target.fee_bps(), 250);
+    assert_eq!(
+        s.client.get_proposal(&id).state,
+        ProposalState::Executed as u32
+    );
+    assert!(s.client.try_execute(&executor, &id).is_err());
+}
+
+#[test]
+fn test_votes_use_snapshot_balances() {
+    let s = setup();
+    let alice = holder(&s, 1_000);
+    next_ledger(&s);
+
+    let id = propose(&s, &alice, fee_actions(&s, 100));
+
+    // Tokens acquired after the snapshot carry no weight.
+    let late = holder(&s, 5_000);
+    next_ledger(&s);
+    assert!(s
+        .client
+        .try_cast_vote(&late, &id, &SUPPORT_AGAINST)
+        .is_err());
+
+    s.client.cast_vote(&alice, &id, &SUPPORT_FOR);
+    assert_eq!(
+        s.client.get_receipt(&id, &alice),
+        Some(Receipt {
+            support: SUPPORT_FOR,
+            weight: 1_000
+        })
+    );
+}
+
+#[test]
+fn test_quorum_not_met_defeats_proposal() {
+    let s = setup();
+    let alice = holder(&s, 150);
+    holder(&s, 850);
+    next_ledger(&s);
+
+    let id = propose(&s, &alice, fee_actions(&s, 100));
+    s.client.cast_vote(&alice, &id, &SUPPORT_FOR);
+    assert_eq!(s.client.get_quorum(&id), 200);
+
+    s.env.ledger().set_timestamp(1_000 + 3 * DAY);
+    assert_eq!(s.client.queue(&id), ProposalState::Defeated as u32);
+    assert!(s
+        .client
+        .try_execute(&Address::generate(&s.env), &id)
+        .is_err());
+    assert_eq!(s.target.fee_bps(), 0);
+}
+
+#[test]
+fn test_proposal_threshold_and_cancel() {
+    let s = setup();
+    let small = holder(&s, 50);
+    let alice = holder(&s, 500);
+    next_ledger(&s);
+
+    assert!(s
+        .client
+        .try_propose(&small, &String::from_str(&s.env, ""), &fee_actions(&s, 100))
+        .is_err());
+    assert!(s
+        .client
+        .try_propose(&alice, &String::from_str(&s.env, ""), &Vec::new(&s.env))
+        .is_err());
+
+    let id = propose(&s, &alice, fee_actions(&s, 100));
+    assert!(s.client.try_cancel(&id, &small).is_err());
+    s.client.cancel(&id, &s.guardian);
+    assert!(s.client.try_cast_vote(&alice, &id, &SUPPORT_FOR).is_err());
+}
+
+#[test]
+fn test_config_changes_through_proposal() {
+    let s = setup();
+    let alice = holder(&s, 1_000);
+    next_ledger(&s);
+
+    let mut new_config = config();
+    new_config.quorum_bps = 5_000;
+    let actions = Vec::from_array(
+        &s.env,
+        [ParamCall {
+            target: s.client.address.clone(),
+            function: Symbol::new(&s.env, "set_config"),
+            args: (new_config.clone(),).into_val(&s.env),
+        }],
+    );
+
+    let id = propose(&s, &alice, actions);
+    s.client.cast_vote(&alice, &id, &SUPPORT_FOR);
+    s.env.ledger().set_timestamp(1_000 + 3 * DAY);
+    s.client.queue(&id);
+    s.env.ledger().set_timestamp(1_000 + 4 * DAY);
+    s.client.execute(&alice, &id);
+
+    assert_eq!(s.client.get_config(), new_config);
+}
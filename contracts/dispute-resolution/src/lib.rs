@@ -2,16 +2,87 @@
 
 use arenax_events::dispute as events;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, String, Symbol,
+    contract, contractimpl, contracttype, token, Address, BytesN, Env, IntoVal, String, Symbol, Vec,
 };
 
+/// Maximum evidence entries attached to a single dispute.
+pub const MAX_EVIDENCE_PER_DISPUTE: u32 = 20;
+
+/// Bounds on the number of referees assigned to a voting panel.
+pub const MIN_PANEL_SIZE: u32 = 3;
+pub const MAX_PANEL_SIZE: u32 = 5;
+
+/// Upper bound on the size of an appeal panel.
+pub const MAX_APPEAL_PANEL_SIZE: u32 = 9;
+
+/// Concurrent disputes an operator may hold when no caseload limit is configured.
+pub const DEFAULT_MAX_CASELOAD: u32 = 10;
+
+/// Maximum number of entries returned by a single paginated query.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Upper bound on the referee fee, in basis points of the losing stake or forfeited bond.
+pub const MAX_REFEREE_FEE_BPS: u32 = 2_000;
+
+/// Most recent anti-cheat confirmations per player included in a dispute context.
+pub const CONTEXT_FLAG_LIMIT: u32 = 5;
+
+/// Maximum confirmations a dispute resolution may cite.
+pub const MAX_CITATIONS: u32 = 10;
+
+/// Dispute categories, each with its own resolution window. Categories without a configured
+/// window fall back to the window given at initialization.
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DisputeCategory {
+    Standard = 0,
+    HighStakes = 1,
+    TournamentFinal = 2,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DisputeStatus {
     Open = 0,
     Resolved = 1,
+    /// A panel failed to reach a majority before the deadline; only the admin may resolve.
+    Escalated = 2,
+    /// A decision was taken but is suspended until the appeal window closes.
+    Decided = 3,
+    /// The decision was appealed and awaits the appeal panel or admin.
+    Appealed = 4,
+}
+
+/// Outcome of a dispute. `Split(a, b)` divides the escrowed pot between player A and player B
+/// in basis points (`a + b` must equal 10_000).
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Decision {
+    AwardPlayerA,
+    AwardPlayerB,
+    Split(u32, u32),
+    Replay,
+    Void,
 }
 
+/// Outcome codes understood by the match contract's `settle_dispute`.
+const OUTCOME_AWARD_A: u32 = 0;
+const OUTCOME_AWARD_B: u32 = 1;
+const OUTCOME_SPLIT: u32 = 2;
+const OUTCOME_REPLAY: u32 = 3;
+const OUTCOME_VOID: u32 = 4;
+
+/// Match contract `MatchState` codes read before raising or settling a match dispute.
+const MATCH_STARTED: u32 = 1;
+const MATCH_DISPUTED: u32 = 3;
+const MATCH_PENDING_RESULT: u32 = 5;
+
+/// Escrow vault `EscrowState::Disputed`.
+const ESCROW_DISPUTED: u32 = 7;
+
+const BPS_DENOMINATOR: u32 = 10_000;
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub struct DisputeData {
@@ -21,10 +92,114 @@ pub struct DisputeData {
     pub status: u32,
     pub opened_at: u64,
     pub deadline: u64,
-    pub decision: Option<String>,
     pub resolved_at: Option<u64>,
 }
 
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Evidence {
+    pub submitter: Address,
+    pub evidence_ref: String,
+    pub description: String,
+    pub submitted_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Vote {
+    pub referee: Address,
+    pub decision: Decision,
+    pub cast_at: u64,
+}
+
+/// Bond owed for disputes over matches whose per-player stake is at least `min_stake`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondBracket {
+    pub min_stake: i128,
+    pub bond: i128,
+}
+
+/// Dispute bond settings. `brackets` are sorted by ascending `min_stake`; a forfeited bond goes
+/// to the counterparty when `forfeit_to_counterparty` is set, otherwise to `treasury`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BondConfig {
+    pub token: Address,
+    pub treasury: Address,
+    pub brackets: Vec<BondBracket>,
+    pub forfeit_to_counterparty: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeBond {
+    pub opener: Address,
+    pub counterparty: Address,
+    /// True when the opener is the match's player A.
+    pub opener_is_a: bool,
+    pub token: Address,
+    pub amount: i128,
+}
+
+/// Appeal tier settings. A decision can be appealed once within `window` seconds by posting
+/// `bond` of `token`; appeal panels need at least `panel_size` referees.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AppealConfig {
+    pub window: u64,
+    pub token: Address,
+    pub bond: i128,
+    pub treasury: Address,
+    pub panel_size: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Appeal {
+    pub appellant: Address,
+    pub reason: String,
+    pub original: Decision,
+    pub bond: i128,
+    pub filed_at: u64,
+    pub deadline: u64,
+}
+
+/// Mirror of the anti-cheat oracle's `AntiCheatConfirmation`, decoded from its views.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct CheatConfirmation {
+    pub player: Address,
+    pub match_id: u64,
+    pub severity: u32,
+    pub penalty_applied: i128,
+    pub timestamp: u64,
+    pub oracle: Address,
+    pub oracles: Vec<Address>,
+}
+
+/// A confirmation is identified by the flagged player and the oracle's match id.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ConfirmationRef {
+    pub player: Address,
+    pub match_id: u64,
+}
+
+/// Everything a referee needs to adjudicate: the dispute, its evidence and each player's recent
+/// anti-cheat confirmations. Players are known only when the match has an escrow.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DisputeContext {
+    pub dispute: DisputeData,
+    pub evidence: Vec<Evidence>,
+    pub citations: Vec<ConfirmationRef>,
+    pub player_a: Option<Address>,
+    pub player_b: Option<Address>,
+    pub flags_a: Vec<CheatConfirmation>,
+    pub flags_b: Vec<CheatConfirmation>,
+}
+
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
@@ -32,6 +207,34 @@ pub enum DataKey {
     IdentityContract,
     ResolutionWindow,
     Dispute(BytesN<32>),
+    MatchContract,
+    EscrowVault,
+    Decision(BytesN<32>), // match_id -> Decision, once resolved
+    Evidence(BytesN<32>), // match_id -> Vec<Evidence>
+    Panel(BytesN<32>),    // match_id -> Vec<Address>
+    Votes(BytesN<32>),    // match_id -> Vec<Vote>
+    BondConfig,
+    Bond(BytesN<32>), // match_id -> DisputeBond
+    AntiCheatOracle,
+    AppealConfig,
+    AppealWindowEnd(BytesN<32>), // match_id -> end of the appeal window for a Decided dispute
+    Appeal(BytesN<32>),          // match_id -> Appeal
+    AppealPanel(BytesN<32>),     // match_id -> Vec<Address>
+    AppealVotes(BytesN<32>),     // match_id -> Vec<Vote>
+    DefaultDecision,
+    MaxCaseload,
+    QueueCount,
+    QueueEntry(u32),        // queue position -> match_id
+    QueueSlot(BytesN<32>),  // match_id -> queue position
+    Assignee(BytesN<32>),   // match_id -> operator
+    OperatorCases(Address), // operator -> Vec<match_id>
+    RefereeFeeBps,
+    RefereeFees(Address, Address), // (referee, token) -> claimable balance
+    Deciders(BytesN<32>),          // match_id -> referees credited for a suspended decision
+    Citations(BytesN<32>),         // match_id -> Vec<ConfirmationRef>
+    Paused,
+    CategoryWindow(u32),  // category -> resolution window
+    Category(BytesN<32>), // match_id -> category
 }
 
 #[contract]
@@ -58,7 +261,132 @@ impl DisputeResolutionContract {
             .set(&DataKey::ResolutionWindow, &resolution_window);
     }
 
-    pub fn open_dispute(env: Env, match_id: BytesN<32>, reason: String, evidence_ref: String) {
+    pub fn set_paused(env: Env, paused: bool) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Paused, &paused);
+
+        events::emit_paused(&env, paused);
+    }
+
+    pub fn is_paused(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false)
+    }
+
+    /// Set the resolution window for disputes opened under `category`. Disputes already open
+    /// keep their deadline.
+    pub fn set_resolution_window(env: Env, category: u32, window: u64) {
+        Self::require_admin(&env);
+        Self::category_from_u32(category);
+        if window == 0 {
+            panic!("resolution window must be positive");
+        }
+
+        let previous = Self::get_resolution_window(env.clone(), category);
+        env.storage()
+            .instance()
+            .set(&DataKey::CategoryWindow(category), &window);
+
+        events::emit_window_updated(&env, category, previous, window);
+    }
+
+    pub fn get_resolution_window(env: Env, category: u32) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CategoryWindow(category))
+            .unwrap_or_else(|| {
+                env.storage()
+                    .instance()
+                    .get(&DataKey::ResolutionWindow)
+                    .expect("contract not initialized")
+            })
+    }
+
+    pub fn get_dispute_category(env: Env, match_id: BytesN<32>) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Category(match_id))
+            .unwrap_or(DisputeCategory::Standard as u32)
+    }
+
+    pub fn set_match_contract(env: Env, match_contract: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::MatchContract, &match_contract);
+    }
+
+    pub fn set_escrow_vault(env: Env, escrow_vault: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowVault, &escrow_vault);
+    }
+
+    /// Set the AX bond schedule for opening disputes.
+    pub fn set_bond_config(env: Env, config: BondConfig) {
+        Self::require_admin(&env);
+
+        let mut prev: Option<i128> = None;
+        for bracket in config.brackets.iter() {
+            if bracket.min_stake < 0 || bracket.bond < 0 {
+                panic!("bond brackets must be non-negative");
+            }
+            if let Some(p) = prev {
+                if bracket.min_stake <= p {
+                    panic!("bond brackets must be sorted by min_stake");
+                }
+            }
+            prev = Some(bracket.min_stake);
+        }
+
+        env.storage().instance().set(&DataKey::BondConfig, &config);
+
+        events::emit_bond_config_updated(&env, &config.token, config.brackets.len());
+    }
+
+    pub fn get_bond_config(env: Env) -> Option<BondConfig> {
+        env.storage().instance().get(&DataKey::BondConfig)
+    }
+
+    /// Anti-cheat oracle whose confirmations back dispute contexts and citations. It may open
+    /// disputes without posting a bond.
+    pub fn set_anticheat_oracle(env: Env, oracle: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::AntiCheatOracle, &oracle);
+    }
+
+    /// Bond owed to open a dispute on `match_id`, based on its escrowed stake.
+    pub fn get_required_bond(env: Env, match_id: BytesN<32>) -> i128 {
+        match Self::bond_terms(&env, &match_id) {
+            Some((_, _, amount)) => amount,
+            None => 0,
+        }
+    }
+
+    pub fn get_bond(env: Env, match_id: BytesN<32>) -> Option<DisputeBond> {
+        env.storage().persistent().get(&DataKey::Bond(match_id))
+    }
+
+    /// Open a dispute under a `DisputeCategory`, which selects its resolution window. When a
+    /// bond schedule is configured and the match has an escrow, a player opener must post the
+    /// bracket's bond, held by this contract until resolution. A player disputing a match that
+    /// is still in play also moves it to `Disputed` in the match contract.
+    pub fn open_dispute(
+        env: Env,
+        match_id: BytesN<32>,
+        opener: Address,
+        category: u32,
+        reason: String,
+        evidence_ref: String,
+    ) {
+        Self::require_not_paused(&env);
+        opener.require_auth();
+
         if env
             .storage()
             .persistent()
@@ -67,11 +395,8 @@ impl DisputeResolutionContract {
             panic!("dispute already opened");
         }
 
-        let resolution_window: u64 = env
-            .storage()
-            .instance()
-            .get(&DataKey::ResolutionWindow)
-            .expect("contract not initialized");
+        Self::category_from_u32(category);
+        let resolution_window = Self::get_resolution_window(env.clone(), category);
 
         let opened_at = env.ledger().timestamp();
         let deadline = opened_at + resolution_window;
@@ -83,7 +408,6 @@ impl DisputeResolutionContract {
             status: DisputeStatus::Open as u32,
             opened_at,
             deadline,
-            decision: None,
             resolved_at: None,
         };
 
@@ -91,77 +415,1428 @@ impl DisputeResolutionContract {
             .persistent()
             .set(&DataKey::Dispute(match_id.clone()), &dispute);
 
+        env.storage()
+            .persistent()
+            .set(&DataKey::Category(match_id.clone()), &category);
+
+        Self::post_bond(&env, &match_id, &opener);
+        Self::raise_match_dispute(&env, &match_id, &opener);
+        Self::enqueue(&env, &match_id);
+
         events::emit_dispute_opened(&env, &match_id, &reason, &evidence_ref, deadline);
     }
 
-    pub fn resolve_dispute(env: Env, match_id: BytesN<32>, caller: Address, decision: String) {
+    /// Resolve a dispute and execute the decision on the configured match contract and escrow
+    /// vault in the same transaction. `caller` must be an operator on all three contracts and
+    /// must have claimed the dispute. Disputes with a panel are decided by its votes; once
+    /// escalated, only the admin may resolve.
+    pub fn resolve_dispute(env: Env, match_id: BytesN<32>, caller: Address, decision: Decision) {
+        Self::require_not_paused(&env);
         caller.require_auth();
 
-        if !Self::is_operator(&env, &caller) {
+        let dispute = Self::load_dispute(&env, &match_id);
+
+        if dispute.status == DisputeStatus::Escalated as u32 {
+            if caller != Self::admin(&env) {
+                panic!("only admin can resolve an escalated dispute");
+            }
+        } else {
+            if !Self::is_operator(&env, &caller) {
+                panic!("unauthorized call: only operators can adjudicate disputes");
+            }
+
+            if dispute.status != DisputeStatus::Open as u32 {
+                panic!("dispute is not open");
+            }
+
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Panel(match_id.clone()))
+            {
+                panic!("dispute is decided by its panel");
+            }
+
+            if env.ledger().timestamp() > dispute.deadline {
+                panic!("resolution deadline has passed");
+            }
+
+            let assignee: Option<Address> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::Assignee(match_id.clone()));
+            if assignee.as_ref() != Some(&caller) {
+                panic!("dispute is not assigned to caller");
+            }
+        }
+
+        let deciders = Vec::from_array(&env, [caller.clone()]);
+        Self::finalize(&env, &match_id, dispute, &caller, &deciders, decision);
+    }
+
+    pub fn set_max_caseload(env: Env, max_caseload: u32) {
+        Self::require_admin(&env);
+        if max_caseload == 0 {
+            panic!("caseload must be positive");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::MaxCaseload, &max_caseload);
+    }
+
+    pub fn get_max_caseload(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::MaxCaseload)
+            .unwrap_or(DEFAULT_MAX_CASELOAD)
+    }
+
+    /// Take an unassigned dispute off the queue. The operator becomes the only one allowed to
+    /// resolve it.
+    pub fn claim_dispute(env: Env, match_id: BytesN<32>, operator: Address) {
+        Self::require_not_paused(&env);
+        operator.require_auth();
+
+        if !Self::is_operator(&env, &operator) {
             panic!("unauthorized call: only operators can adjudicate disputes");
         }
 
-        let mut dispute: DisputeData = env
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Open as u32 {
+            panic!("dispute is not open");
+        }
+
+        if !env
             .storage()
             .persistent()
-            .get(&DataKey::Dispute(match_id.clone()))
-            .expect("dispute not found");
+            .has(&DataKey::QueueSlot(match_id.clone()))
+        {
+            panic!("dispute is not in the queue");
+        }
 
-        if dispute.status != DisputeStatus::Open as u32 {
-            panic!("dispute is not open");
+        let cases_key = DataKey::OperatorCases(operator.clone());
+        let mut cases: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&cases_key)
+            .unwrap_or(Vec::new(&env));
+        if cases.len() >= Self::get_max_caseload(env.clone()) {
+            panic!("operator caseload is full");
         }
 
-        let current_time = env.ledger().timestamp();
-        if current_time > dispute.deadline {
-            panic!("resolution deadline has passed");
+        Self::dequeue(&env, &match_id);
+        cases.push_back(match_id.clone());
+        env.storage().persistent().set(&cases_key, &cases);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Assignee(match_id.clone()), &operator);
+
+        events::emit_dispute_claimed(&env, &match_id, &operator);
+    }
+
+    /// Open disputes not yet claimed by an operator. Queue order is not preserved when
+    /// disputes leave it.
+    pub fn get_open_disputes(env: Env, offset: u32, limit: u32) -> Vec<BytesN<32>> {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::QueueCount)
+            .unwrap_or(0);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+
+        let mut page = Vec::new(&env);
+        for i in offset..end {
+            if let Some(match_id) = env.storage().persistent().get(&DataKey::QueueEntry(i)) {
+                page.push_back(match_id);
+            }
         }
+        page
+    }
 
-        dispute.status = DisputeStatus::Resolved as u32;
-        dispute.decision = Some(decision.clone());
-        dispute.resolved_at = Some(current_time);
+    /// Disputes currently assigned to `operator`.
+    pub fn get_disputes_by_operator(env: Env, operator: Address) -> Vec<BytesN<32>> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::OperatorCases(operator))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_assignee(env: Env, match_id: BytesN<32>) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Assignee(match_id))
+    }
+
+    /// Referee fee in basis points, taken from the losing stake when a player is awarded the
+    /// match and from forfeited dispute bonds.
+    pub fn set_referee_fee(env: Env, fee_bps: u32) {
+        Self::require_admin(&env);
+        if fee_bps > MAX_REFEREE_FEE_BPS {
+            panic!("referee fee too high");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::RefereeFeeBps, &fee_bps);
+
+        events::emit_referee_fee_updated(&env, fee_bps);
+    }
+
+    pub fn get_referee_fee(env: Env) -> u32 {
+        Self::referee_fee_bps(&env)
+    }
 
+    pub fn get_referee_fees(env: Env, referee: Address, token: Address) -> i128 {
         env.storage()
             .persistent()
-            .set(&DataKey::Dispute(match_id.clone()), &dispute);
+            .get(&DataKey::RefereeFees(referee, token))
+            .unwrap_or(0)
+    }
+
+    /// Withdraw the referee's accrued fees in `token`. Returns the amount paid out.
+    pub fn claim_referee_fees(env: Env, referee: Address, token: Address) -> i128 {
+        Self::require_not_paused(&env);
+        referee.require_auth();
+
+        let key = DataKey::RefereeFees(referee.clone(), token.clone());
+        let amount: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if amount <= 0 {
+            panic!("no fees to claim");
+        }
+        env.storage().persistent().remove(&key);
+
+        token::Client::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &referee,
+            &amount,
+        );
 
-        events::emit_dispute_resolved(&env, &match_id, &decision, current_time, &caller);
+        events::emit_referee_fees_claimed(&env, &referee, &token, amount);
+        amount
     }
 
-    pub fn is_disputed(env: Env, match_id: BytesN<32>) -> bool {
-        if let Some(dispute) = env
+    /// Put an open dispute in panel mode. Every referee must be an operator; the decision is
+    /// then taken by majority vote instead of a single operator.
+    pub fn assign_panel(env: Env, match_id: BytesN<32>, referees: Vec<Address>) {
+        Self::require_admin(&env);
+
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Open as u32 {
+            panic!("dispute is not open");
+        }
+
+        let panel_key = DataKey::Panel(match_id.clone());
+        if env.storage().persistent().has(&panel_key) {
+            panic!("panel already assigned");
+        }
+
+        if referees.len() < MIN_PANEL_SIZE || referees.len() > MAX_PANEL_SIZE {
+            panic!("panel must have 3 to 5 referees");
+        }
+        Self::validate_panel(&env, &referees);
+
+        env.storage().persistent().set(&panel_key, &referees);
+        Self::release_case(&env, &match_id);
+
+        events::emit_panel_assigned(&env, &match_id, &referees);
+    }
+
+    /// Cast a panel vote. The first decision to gather a strict majority of the panel is
+    /// executed immediately, with the deciding voter as the executing operator.
+    pub fn cast_vote(env: Env, match_id: BytesN<32>, referee: Address, decision: Decision) {
+        Self::require_not_paused(&env);
+        referee.require_auth();
+
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Open as u32 {
+            panic!("dispute is not open");
+        }
+
+        let panel: Vec<Address> = env
             .storage()
             .persistent()
-            .get::<DataKey, DisputeData>(&DataKey::Dispute(match_id))
+            .get(&DataKey::Panel(match_id.clone()))
+            .expect("no panel assigned");
+        if !panel.contains(&referee) {
+            panic!("referee is not on the panel");
+        }
+
+        if env.ledger().timestamp() > dispute.deadline {
+            panic!("resolution deadline has passed");
+        }
+
+        let votes_key = DataKey::Votes(match_id.clone());
+        if let Some(deciders) =
+            Self::record_vote(&env, &match_id, votes_key, &panel, &referee, &decision)
         {
-            return dispute.status == DisputeStatus::Open as u32;
+            Self::finalize(&env, &match_id, dispute, &referee, &deciders, decision);
         }
-        false
     }
 
-    fn is_operator(env: &Env, addr: &Address) -> bool {
-        let admin: Address = env
+    /// Hand a deadlocked panel dispute to the admin once its deadline has passed. Callable by
+    /// anyone.
+    pub fn escalate_dispute(env: Env, match_id: BytesN<32>) {
+        Self::require_not_paused(&env);
+        let mut dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Open as u32 {
+            panic!("dispute is not open");
+        }
+
+        if !env
             .storage()
+            .persistent()
+            .has(&DataKey::Panel(match_id.clone()))
+        {
+            panic!("no panel assigned");
+        }
+
+        let now = env.ledger().timestamp();
+        if now <= dispute.deadline {
+            panic!("resolution deadline has not passed");
+        }
+
+        dispute.status = DisputeStatus::Escalated as u32;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(match_id.clone()), &dispute);
+        Self::release_case(&env, &match_id);
+
+        events::emit_dispute_escalated(&env, &match_id, now);
+    }
+
+    /// Decision applied by `expire_dispute`. Defaults to `Void`, which refunds both stakes.
+    pub fn set_default_decision(env: Env, decision: Decision) {
+        Self::require_admin(&env);
+        Self::validate_decision(&decision);
+        env.storage()
             .instance()
-            .get(&DataKey::Admin)
-            .expect("contract not initialized");
+            .set(&DataKey::DefaultDecision, &decision);
+    }
 
-        if addr == &admin {
-            return true;
+    pub fn get_default_decision(env: Env) -> Decision {
+        env.storage()
+            .instance()
+            .get(&DataKey::DefaultDecision)
+            .unwrap_or(Decision::Void)
+    }
+
+    /// Apply the default judgment to an open dispute nobody resolved before its deadline.
+    /// Callable by anyone. The decision is executed with this contract as the resolver, so it
+    /// must hold the referee role on the match contract and the resolver role on the escrow
+    /// vault. Panel disputes are escalated instead.
+    pub fn expire_dispute(env: Env, match_id: BytesN<32>) {
+        Self::require_not_paused(&env);
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Open as u32 {
+            panic!("dispute is not open");
         }
 
-        if let Some(identity_contract) = env
+        if env
             .storage()
-            .instance()
-            .get::<DataKey, Address>(&DataKey::IdentityContract)
+            .persistent()
+            .has(&DataKey::Panel(match_id.clone()))
         {
-            let role: u32 = env.invoke_contract(
-                &identity_contract,
-                &Symbol::new(env, "get_role"),
-                (addr.clone(),).into_val(env),
-            );
-            return role == 1 || role == 2;
+            panic!("dispute is decided by its panel");
         }
 
-        false
+        let now = env.ledger().timestamp();
+        if now <= dispute.deadline {
+            panic!("resolution deadline has not passed");
+        }
+
+        let decision = Self::get_default_decision(env.clone());
+        let executor = env.current_contract_address();
+        Self::execute(
+            &env,
+            &match_id,
+            dispute,
+            &executor,
+            &Vec::new(&env),
+            decision,
+        );
+
+        events::emit_dispute_expired(&env, &match_id, now);
     }
-}
+
+    /// Enable the appeal tier. While set, decisions are suspended for `window` seconds before
+    /// they are executed.
+    pub fn set_appeal_config(env: Env, config: AppealConfig) {
+        Self::require_admin(&env);
+
+        if config.window == 0 || config.bond <= 0 {
+            panic!("invalid appeal config");
+        }
+        if config.panel_size <= MAX_PANEL_SIZE || config.panel_size > MAX_APPEAL_PANEL_SIZE {
+            panic!("appeal panel must be larger than a dispute panel");
+        }
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AppealConfig, &config);
+
+        events::emit_appeal_config_updated(&env, config.window, config.bond, config.panel_size);
+    }
+
+    pub fn get_appeal_config(env: Env) -> Option<AppealConfig> {
+        env.storage().instance().get(&DataKey::AppealConfig)
+    }
+
+    /// Execute a decision whose appeal window closed without an appeal.
+    pub fn finalize_dispute(env: Env, match_id: BytesN<32>, caller: Address) {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+
+        if !Self::is_operator(&env, &caller) {
+            panic!("unauthorized call: only operators can adjudicate disputes");
+        }
+
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Decided as u32 {
+            panic!("dispute is not awaiting appeal");
+        }
+
+        let window_end: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AppealWindowEnd(match_id.clone()))
+            .expect("appeal window not found");
+        if env.ledger().timestamp() <= window_end {
+            panic!("appeal window has not closed");
+        }
+
+        let decision: Decision = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Decision(match_id.clone()))
+            .expect("decision not found");
+        let deciders: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Deciders(match_id.clone()))
+            .unwrap_or(Vec::new(&env));
+        Self::execute(&env, &match_id, dispute, &caller, &deciders, decision);
+    }
+
+    /// Appeal a suspended decision. Each dispute can be appealed once, by a match participant,
+    /// who posts the appeal bond. The bond is refunded if the appeal changes the decision.
+    pub fn appeal_dispute(env: Env, match_id: BytesN<32>, appellant: Address, reason: String) {
+        Self::require_not_paused(&env);
+        appellant.require_auth();
+
+        let mut dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Decided as u32 {
+            panic!("dispute is not awaiting appeal");
+        }
+
+        let appeal_key = DataKey::Appeal(match_id.clone());
+        if env.storage().persistent().has(&appeal_key) {
+            panic!("appeal already filed");
+        }
+
+        let filed_at = env.ledger().timestamp();
+        let window_end: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AppealWindowEnd(match_id.clone()))
+            .expect("appeal window not found");
+        if filed_at > window_end {
+            panic!("appeal window has closed");
+        }
+
+        let match_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchContract)
+            .expect("match contract not set");
+        let is_participant: bool = env.invoke_contract(
+            &match_contract,
+            &Symbol::new(&env, "is_participant"),
+            (match_id.clone(), appellant.clone()).into_val(&env),
+        );
+        if !is_participant {
+            panic!("appellant is not a match participant");
+        }
+
+        let config: AppealConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::AppealConfig)
+            .expect("appeals not enabled");
+        token::Client::new(&env, &config.token).transfer(
+            &appellant,
+            env.current_contract_address(),
+            &config.bond,
+        );
+
+        let category = Self::get_dispute_category(env.clone(), match_id.clone());
+        let resolution_window = Self::get_resolution_window(env.clone(), category);
+        let original: Decision = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Decision(match_id.clone()))
+            .expect("decision not found");
+        let deadline = filed_at + resolution_window;
+
+        env.storage().persistent().set(
+            &appeal_key,
+            &Appeal {
+                appellant: appellant.clone(),
+                reason,
+                original,
+                bond: config.bond,
+                filed_at,
+                deadline,
+            },
+        );
+
+        dispute.status = DisputeStatus::Appealed as u32;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(match_id.clone()), &dispute);
+
+        events::emit_dispute_appealed(&env, &match_id, &appellant, config.bond, deadline);
+    }
+
+    /// Assign the appeal panel. It must be at least the configured appeal panel size.
+    pub fn assign_appeal_panel(env: Env, match_id: BytesN<32>, referees: Vec<Address>) {
+        Self::require_admin(&env);
+
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Appealed as u32 {
+            panic!("dispute is not under appeal");
+        }
+
+        let panel_key = DataKey::AppealPanel(match_id.clone());
+        if env.storage().persistent().has(&panel_key) {
+            panic!("panel already assigned");
+        }
+
+        let config: AppealConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::AppealConfig)
+            .expect("appeals not enabled");
+        if referees.len() < config.panel_size || referees.len() > MAX_APPEAL_PANEL_SIZE {
+            panic!("appeal panel size out of range");
+        }
+        Self::validate_panel(&env, &referees);
+
+        env.storage().persistent().set(&panel_key, &referees);
+
+        events::emit_panel_assigned(&env, &match_id, &referees);
+    }
+
+    /// Cast an appeal panel vote. A strict majority decides the appeal and executes it.
+    pub fn cast_appeal_vote(env: Env, match_id: BytesN<32>, referee: Address, decision: Decision) {
+        Self::require_not_paused(&env);
+        referee.require_auth();
+
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Appealed as u32 {
+            panic!("dispute is not under appeal");
+        }
+
+        let panel: Vec<Address> = env
+            .storage()
+            .persistent()
+            .get(&DataKey::AppealPanel(match_id.clone()))
+            .expect("no panel assigned");
+        if !panel.contains(&referee) {
+            panic!("referee is not on the panel");
+        }
+
+        let appeal = Self::load_appeal(&env, &match_id);
+        if env.ledger().timestamp() > appeal.deadline {
+            panic!("appeal deadline has passed");
+        }
+
+        let votes_key = DataKey::AppealVotes(match_id.clone());
+        if let Some(deciders) =
+            Self::record_vote(&env, &match_id, votes_key, &panel, &referee, &decision)
+        {
+            Self::execute(&env, &match_id, dispute, &referee, &deciders, decision);
+        }
+    }
+
+    /// Decide an appeal directly. Admin only; the outcome is final.
+    pub fn resolve_appeal(env: Env, match_id: BytesN<32>, caller: Address, decision: Decision) {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+        if caller != Self::admin(&env) {
+            panic!("only admin can resolve an appeal");
+        }
+
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status != DisputeStatus::Appealed as u32 {
+            panic!("dispute is not under appeal");
+        }
+
+        Self::validate_decision(&decision);
+        let deciders = Vec::from_array(&env, [caller.clone()]);
+        Self::execute(&env, &match_id, dispute, &caller, &deciders, decision);
+    }
+
+    pub fn get_appeal(env: Env, match_id: BytesN<32>) -> Option<Appeal> {
+        env.storage().persistent().get(&DataKey::Appeal(match_id))
+    }
+
+    pub fn get_appeal_votes(env: Env, match_id: BytesN<32>) -> Vec<Vote> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::AppealVotes(match_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_panel(env: Env, match_id: BytesN<32>) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Panel(match_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_votes(env: Env, match_id: BytesN<32>) -> Vec<Vote> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Votes(match_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// The decision a dispute was closed with; while an appeal is pending this is the suspended
+    /// original decision.
+    pub fn get_decision(env: Env, match_id: BytesN<32>) -> Option<Decision> {
+        env.storage().persistent().get(&DataKey::Decision(match_id))
+    }
+
+    /// Attach evidence to an open dispute. Only the match's players may submit, and only before
+    /// the resolution deadline.
+    pub fn submit_evidence(
+        env: Env,
+        match_id: BytesN<32>,
+        submitter: Address,
+        evidence_ref: String,
+        description: String,
+    ) {
+        Self::require_not_paused(&env);
+        submitter.require_auth();
+
+        let dispute: DisputeData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Dispute(match_id.clone()))
+            .expect("dispute not found");
+
+        if dispute.status != DisputeStatus::Open as u32 {
+            panic!("dispute is not open");
+        }
+
+        let submitted_at = env.ledger().timestamp();
+        if submitted_at > dispute.deadline {
+            panic!("evidence deadline has passed");
+        }
+
+        let match_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchContract)
+            .expect("match contract not set");
+        let is_participant: bool = env.invoke_contract(
+            &match_contract,
+            &Symbol::new(&env, "is_participant"),
+            (match_id.clone(), submitter.clone()).into_val(&env),
+        );
+        if !is_participant {
+            panic!("submitter is not a match participant");
+        }
+
+        let key = DataKey::Evidence(match_id.clone());
+        let mut evidence: Vec<Evidence> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if evidence.len() >= MAX_EVIDENCE_PER_DISPUTE {
+            panic!("evidence limit reached");
+        }
+        evidence.push_back(Evidence {
+            submitter: submitter.clone(),
+            evidence_ref: evidence_ref.clone(),
+            description,
+            submitted_at,
+        });
+        env.storage().persistent().set(&key, &evidence);
+
+        events::emit_evidence_submitted(
+            &env,
+            &match_id,
+            &submitter,
+            &evidence_ref,
+            evidence.len() - 1,
+        );
+    }
+
+    /// Evidence submitted by the participants, in submission order.
+    pub fn get_evidence(env: Env, match_id: BytesN<32>) -> Vec<Evidence> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Evidence(match_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Aggregate an unresolved dispute with both players' most recent anti-cheat confirmations,
+    /// read from the configured anti-cheat oracle.
+    pub fn get_dispute_context(env: Env, match_id: BytesN<32>) -> DisputeContext {
+        let dispute = Self::load_dispute(&env, &match_id);
+        let evidence = Self::get_evidence(env.clone(), match_id.clone());
+        let citations = Self::get_citations(env.clone(), match_id.clone());
+
+        let players: Option<(Address, Address)> = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::EscrowVault)
+            .filter(|escrow_vault| {
+                env.invoke_contract::<bool>(
+                    escrow_vault,
+                    &Symbol::new(&env, "escrow_exists"),
+                    (match_id.clone(),).into_val(&env),
+                )
+            })
+            .map(|escrow_vault| {
+                env.invoke_contract(
+                    &escrow_vault,
+                    &Symbol::new(&env, "get_players"),
+                    (match_id.clone(),).into_val(&env),
+                )
+            });
+
+        let (player_a, player_b, flags_a, flags_b) = match players {
+            Some((a, b)) => {
+                let flags_a = Self::recent_confirmations(&env, &a);
+                let flags_b = Self::recent_confirmations(&env, &b);
+                (Some(a), Some(b), flags_a, flags_b)
+            }
+            None => (None, None, Vec::new(&env), Vec::new(&env)),
+        };
+
+        DisputeContext {
+            dispute,
+            evidence,
+            citations,
+            player_a,
+            player_b,
+            flags_a,
+            flags_b,
+        }
+    }
+
+    /// Cite an anti-cheat confirmation as grounds for the resolution of an unresolved dispute.
+    /// The confirmation must exist on the configured anti-cheat oracle.
+    pub fn cite_confirmation(
+        env: Env,
+        match_id: BytesN<32>,
+        caller: Address,
+        player: Address,
+        confirmation_match_id: u64,
+    ) {
+        Self::require_not_paused(&env);
+        caller.require_auth();
+
+        if !Self::is_operator(&env, &caller) {
+            panic!("unauthorized call: only operators can adjudicate disputes");
+        }
+
+        let dispute = Self::load_dispute(&env, &match_id);
+        if dispute.status == DisputeStatus::Resolved as u32 {
+            panic!("dispute already resolved");
+        }
+
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::AntiCheatOracle)
+            .expect("anti-cheat oracle not set");
+        let confirmation: Option<CheatConfirmation> = env.invoke_contract(
+            &oracle,
+            &Symbol::new(&env, "get_confirmation"),
+            (player.clone(), confirmation_match_id).into_val(&env),
+        );
+        if confirmation.is_none() {
+            panic!("confirmation not found");
+        }
+
+        let key = DataKey::Citations(match_id.clone());
+        let mut citations: Vec<ConfirmationRef> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let citation = ConfirmationRef {
+            player: player.clone(),
+            match_id: confirmation_match_id,
+        };
+        if citations.contains(&citation) {
+            panic!("confirmation already cited");
+        }
+        if citations.len() >= MAX_CITATIONS {
+            panic!("citation limit reached");
+        }
+        citations.push_back(citation);
+        env.storage().persistent().set(&key, &citations);
+
+        events::emit_confirmation_cited(&env, &match_id, &caller, &player, confirmation_match_id);
+    }
+
+    pub fn get_citations(env: Env, match_id: BytesN<32>) -> Vec<ConfirmationRef> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Citations(match_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn is_disputed(env: Env, match_id: BytesN<32>) -> bool {
+        if let Some(dispute) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, DisputeData>(&DataKey::Dispute(match_id))
+        {
+            return dispute.status != DisputeStatus::Resolved as u32;
+        }
+        false
+    }
+
+    fn require_not_paused(env: &Env) {
+        let paused: bool = env
+            .storage()
+            .instance()
+            .get(&DataKey::Paused)
+            .unwrap_or(false);
+        if paused {
+            panic!("contract is paused");
+        }
+    }
+
+    fn category_from_u32(category: u32) -> DisputeCategory {
+        match category {
+            0 => DisputeCategory::Standard,
+            1 => DisputeCategory::HighStakes,
+            2 => DisputeCategory::TournamentFinal,
+            _ => panic!("invalid dispute category"),
+        }
+    }
+
+    fn load_dispute(env: &Env, match_id: &BytesN<32>) -> DisputeData {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Dispute(match_id.clone()))
+            .expect("dispute not found")
+    }
+
+    fn validate_decision(decision: &Decision) {
+        if let Decision::Split(a, b) = decision {
+            if a.checked_add(*b) != Some(BPS_DENOMINATOR) {
+                panic!("split shares must total 10000");
+            }
+        }
+    }
+
+    fn recent_confirmations(env: &Env, player: &Address) -> Vec<CheatConfirmation> {
+        let Some(oracle) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::AntiCheatOracle)
+        else {
+            return Vec::new(env);
+        };
+
+        let count: u32 = env.invoke_contract(
+            &oracle,
+            &Symbol::new(env, "get_flag_count"),
+            (player.clone(),).into_val(env),
+        );
+        let offset = count.saturating_sub(CONTEXT_FLAG_LIMIT);
+        env.invoke_contract(
+            &oracle,
+            &Symbol::new(env, "get_flag_history"),
+            (player.clone(), offset, CONTEXT_FLAG_LIMIT).into_val(env),
+        )
+    }
+
+    fn enqueue(env: &Env, match_id: &BytesN<32>) {
+        let count: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::QueueCount)
+            .unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::QueueEntry(count), match_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::QueueSlot(match_id.clone()), &count);
+        env.storage()
+            .instance()
+            .set(&DataKey::QueueCount, &(count + 1));
+    }
+
+    /// Swap-remove `match_id` from the unassigned queue, if present.
+    fn dequeue(env: &Env, match_id: &BytesN<32>) {
+        let slot_key = DataKey::QueueSlot(match_id.clone());
+        let Some(slot) = env.storage().persistent().get::<DataKey, u32>(&slot_key) else {
+            return;
+        };
+        let last: u32 = env
+            .storage()
+            .instance()
+            .get::<DataKey, u32>(&DataKey::QueueCount)
+            .unwrap_or(1)
+            - 1;
+
+        if slot != last {
+            let moved: BytesN<32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::QueueEntry(last))
+                .expect("queue entry missing");
+            env.storage()
+                .persistent()
+                .set(&DataKey::QueueEntry(slot), &moved);
+            env.storage()
+                .persistent()
+                .set(&DataKey::QueueSlot(moved), &slot);
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::QueueEntry(last));
+        env.storage().persistent().remove(&slot_key);
+        env.storage().instance().set(&DataKey::QueueCount, &last);
+    }
+
+    /// Drop the dispute from the queue or its operator's caseload once no single operator
+    /// is responsible for it any more.
+    fn release_case(env: &Env, match_id: &BytesN<32>) {
+        Self::dequeue(env, match_id);
+
+        let assignee_key = DataKey::Assignee(match_id.clone());
+        let Some(operator) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Address>(&assignee_key)
+        else {
+            return;
+        };
+        env.storage().persistent().remove(&assignee_key);
+
+        let cases_key = DataKey::OperatorCases(operator);
+        let mut cases: Vec<BytesN<32>> = env
+            .storage()
+            .persistent()
+            .get(&cases_key)
+            .unwrap_or(Vec::new(env));
+        if let Some(i) = cases.first_index_of(match_id) {
+            cases.remove(i);
+        }
+        env.storage().persistent().set(&cases_key, &cases);
+    }
+
+    fn load_appeal(env: &Env, match_id: &BytesN<32>) -> Appeal {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Appeal(match_id.clone()))
+            .expect("appeal not found")
+    }
+
+    fn validate_panel(env: &Env, referees: &Vec<Address>) {
+        for (i, referee) in referees.iter().enumerate() {
+            if referees.first_index_of(&referee) != Some(i as u32) {
+                panic!("duplicate referee");
+            }
+            if !Self::is_operator(env, &referee) {
+                panic!("panel member is not an operator");
+            }
+        }
+    }
+
+    /// Record a panel vote under `votes_key`; once `decision` holds a strict majority of
+    /// `panel`, returns the referees who voted for it.
+    fn record_vote(
+        env: &Env,
+        match_id: &BytesN<32>,
+        votes_key: DataKey,
+        panel: &Vec<Address>,
+        referee: &Address,
+        decision: &Decision,
+    ) -> Option<Vec<Address>> {
+        Self::validate_decision(decision);
+
+        let mut votes: Vec<Vote> = env
+            .storage()
+            .persistent()
+            .get(&votes_key)
+            .unwrap_or(Vec::new(env));
+        if votes.iter().any(|v| &v.referee == referee) {
+            panic!("referee already voted");
+        }
+
+        votes.push_back(Vote {
+            referee: referee.clone(),
+            decision: decision.clone(),
+            cast_at: env.ledger().timestamp(),
+        });
+        env.storage().persistent().set(&votes_key, &votes);
+
+        let mut majority = Vec::new(env);
+        for vote in votes.iter() {
+            if &vote.decision == decision {
+                majority.push_back(vote.referee);
+            }
+        }
+        events::emit_vote_cast(env, match_id, referee, majority.len());
+
+        if majority.len() * 2 > panel.len() {
+            Some(majority)
+        } else {
+            None
+        }
+    }
+
+    /// Record a first-tier decision. With the appeal tier enabled the decision is suspended
+    /// until the appeal window closes; otherwise it is executed straight away.
+    fn finalize(
+        env: &Env,
+        match_id: &BytesN<32>,
+        mut dispute: DisputeData,
+        executor: &Address,
+        deciders: &Vec<Address>,
+        decision: Decision,
+    ) {
+        Self::validate_decision(&decision);
+
+        let Some(config) = env
+            .storage()
+            .instance()
+            .get::<DataKey, AppealConfig>(&DataKey::AppealConfig)
+        else {
+            Self::execute(env, match_id, dispute, executor, deciders, decision);
+            return;
+        };
+
+        let window_end = env.ledger().timestamp() + config.window;
+        dispute.status = DisputeStatus::Decided as u32;
+        Self::release_case(env, match_id);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(match_id.clone()), &dispute);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Decision(match_id.clone()), &decision);
+        env.storage()
+            .persistent()
+            .set(&DataKey::AppealWindowEnd(match_id.clone()), &window_end);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Deciders(match_id.clone()), deciders);
+
+        events::emit_dispute_decided(env, match_id, executor, window_end);
+    }
+
+    /// Close the dispute with `decision` and apply it to the match, escrow and bonds. Final.
+    /// Referee fees are credited to `deciders`.
+    fn execute(
+        env: &Env,
+        match_id: &BytesN<32>,
+        mut dispute: DisputeData,
+        executor: &Address,
+        deciders: &Vec<Address>,
+        decision: Decision,
+    ) {
+        // (match outcome, player A's share of the escrow; None keeps the stakes locked)
+        let (outcome, share_a_bps, label) = match decision {
+            Decision::AwardPlayerA => (OUTCOME_AWARD_A, Some(BPS_DENOMINATOR), "AWARD_A"),
+            Decision::AwardPlayerB => (OUTCOME_AWARD_B, Some(0), "AWARD_B"),
+            Decision::Split(a, _) => (OUTCOME_SPLIT, Some(a), "SPLIT"),
+            Decision::Replay => (OUTCOME_REPLAY, None, "REPLAY"),
+            Decision::Void => (OUTCOME_VOID, Some(BPS_DENOMINATOR / 2), "VOID"),
+        };
+
+        let current_time = env.ledger().timestamp();
+        dispute.status = DisputeStatus::Resolved as u32;
+        dispute.resolved_at = Some(current_time);
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Dispute(match_id.clone()), &dispute);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Decision(match_id.clone()), &decision);
+
+        Self::release_case(env, match_id);
+        Self::execute_decision(env, match_id, executor, deciders, outcome, share_a_bps);
+        Self::settle_bond(env, match_id, deciders, &decision);
+        Self::settle_appeal_bond(env, match_id, &decision);
+
+        let decision_label = String::from_str(env, label);
+        events::emit_dispute_resolved(env, match_id, &decision_label, current_time, executor);
+        events::emit_decision_executed(
+            env,
+            match_id,
+            outcome,
+            share_a_bps.unwrap_or(0),
+            share_a_bps.is_some(),
+        );
+    }
+
+    /// Refund the appeal bond if the final decision differs from the appealed one, otherwise
+    /// forfeit it to the treasury.
+    fn settle_appeal_bond(env: &Env, match_id: &BytesN<32>, decision: &Decision) {
+        let Some(appeal) = env
+            .storage()
+            .persistent()
+            .get::<DataKey, Appeal>(&DataKey::Appeal(match_id.clone()))
+        else {
+            return;
+        };
+        let config: AppealConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::AppealConfig)
+            .expect("appeals not enabled");
+
+        let upheld = &appeal.original != decision;
+        let recipient = if upheld {
+            appeal.appellant.clone()
+        } else {
+            config.treasury.clone()
+        };
+
+        token::Client::new(env, &config.token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &appeal.bond,
+        );
+
+        events::emit_bond_settled(env, match_id, &recipient, appeal.bond, upheld);
+    }
+
+    /// `(players, config, bond amount)` for a bonded dispute on `match_id`, or None when no bond applies.
+    fn bond_terms(
+        env: &Env,
+        match_id: &BytesN<32>,
+    ) -> Option<((Address, Address), BondConfig, i128)> {
+        let config: BondConfig = env.storage().instance().get(&DataKey::BondConfig)?;
+        let escrow_vault: Address = env.storage().instance().get(&DataKey::EscrowVault)?;
+
+        let has_escrow: bool = env.invoke_contract(
+            &escrow_vault,
+            &Symbol::new(env, "escrow_exists"),
+            (match_id.clone(),).into_val(env),
+        );
+        if !has_escrow {
+            return None;
+        }
+
+        let stake: i128 = env.invoke_contract(
+            &escrow_vault,
+            &Symbol::new(env, "get_stake_amount"),
+            (match_id.clone(),).into_val(env),
+        );
+        let mut amount = 0;
+        for bracket in config.brackets.iter() {
+            if stake >= bracket.min_stake {
+                amount = bracket.bond;
+            }
+        }
+        if amount == 0 {
+            return None;
+        }
+
+        let players: (Address, Address) = env.invoke_contract(
+            &escrow_vault,
+            &Symbol::new(env, "get_players"),
+            (match_id.clone(),).into_val(env),
+        );
+        Some((players, config, amount))
+    }
+
+    fn post_bond(env: &Env, match_id: &BytesN<32>, opener: &Address) {
+        let oracle: Option<Address> = env.storage().instance().get(&DataKey::AntiCheatOracle);
+        if oracle.as_ref() == Some(opener) {
+            return;
+        }
+
+        let Some(((player_a, player_b), config, amount)) = Self::bond_terms(env, match_id) else {
+            return;
+        };
+
+        let (opener_is_a, counterparty) = if opener == &player_a {
+            (true, player_b)
+        } else if opener == &player_b {
+            (false, player_a)
+        } else {
+            panic!("opener is not a match participant");
+        };
+
+        token::Client::new(env, &config.token).transfer(
+            opener,
+            env.current_contract_address(),
+            &amount,
+        );
+
+        env.storage().persistent().set(
+            &DataKey::Bond(match_id.clone()),
+            &DisputeBond {
+                opener: opener.clone(),
+                counterparty,
+                opener_is_a,
+                token: config.token.clone(),
+                amount,
+            },
+        );
+
+        events::emit_bond_posted(env, match_id, opener, amount);
+    }
+
+    /// Refund the bond unless the decision awarded the match to the opener's counterparty. A
+    /// referee fee is taken out of a forfeited bond.
+    fn settle_bond(env: &Env, match_id: &BytesN<32>, deciders: &Vec<Address>, decision: &Decision) {
+        let key = DataKey::Bond(match_id.clone());
+        let Some(bond) = env.storage().persistent().get::<DataKey, DisputeBond>(&key) else {
+            return;
+        };
+        env.storage().persistent().remove(&key);
+
+        let rejected = matches!(
+            (decision, bond.opener_is_a),
+            (Decision::AwardPlayerB, true) | (Decision::AwardPlayerA, false)
+        );
+
+        let recipient = if !rejected {
+            bond.opener.clone()
+        } else {
+            let config: BondConfig = env
+                .storage()
+                .instance()
+                .get(&DataKey::BondConfig)
+                .expect("bond config not set");
+            if config.forfeit_to_counterparty {
+                bond.counterparty.clone()
+            } else {
+                config.treasury
+            }
+        };
+
+        let fee = if rejected && !deciders.is_empty() {
+            Self::referee_fee(env, bond.amount)
+        } else {
+            0
+        };
+        let payout = bond.amount - fee;
+
+        token::Client::new(env, &bond.token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &payout,
+        );
+        Self::credit_referees(env, match_id, deciders, &bond.token, fee);
+
+        events::emit_bond_settled(env, match_id, &recipient, payout, !rejected);
+    }
+
+    /// Called by a player opener; the match contract checks the opener's auth.
+    fn raise_match_dispute(env: &Env, match_id: &BytesN<32>, opener: &Address) {
+        let Some(match_contract) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::MatchContract)
+        else {
+            return;
+        };
+
+        let is_player: bool = env.invoke_contract(
+            &match_contract,
+            &Symbol::new(env, "is_participant"),
+            (match_id.clone(), opener.clone()).into_val(env),
+        );
+        if !is_player {
+            return;
+        }
+
+        let state: Option<u32> = env.invoke_contract(
+            &match_contract,
+            &Symbol::new(env, "get_match_state"),
+            (match_id.clone(),).into_val(env),
+        );
+        if state == Some(MATCH_STARTED) || state == Some(MATCH_PENDING_RESULT) {
+            let _: () = env.invoke_contract(
+                &match_contract,
+                &Symbol::new(env, "raise_dispute"),
+                (match_id.clone(), opener.clone()).into_val(env),
+            );
+        }
+    }
+
+    /// Apply the decision to the match and escrow, each only if it is disputed there; an
+    /// escrow that was never disputed is left to the match's normal release or refund. When a
+    /// player is awarded the match, a referee fee is taken out of the losing stake.
+    fn execute_decision(
+        env: &Env,
+        match_id: &BytesN<32>,
+        caller: &Address,
+        deciders: &Vec<Address>,
+        outcome: u32,
+        share_a_bps: Option<u32>,
+    ) {
+        if let Some(match_contract) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::MatchContract)
+        {
+            let state: Option<u32> = env.invoke_contract(
+                &match_contract,
+                &Symbol::new(env, "get_match_state"),
+                (match_id.clone(),).into_val(env),
+            );
+            if state == Some(MATCH_DISPUTED) {
+                let _: () = env.invoke_contract(
+                    &match_contract,
+                    &Symbol::new(env, "settle_dispute"),
+                    (match_id.clone(), outcome, caller.clone()).into_val(env),
+                );
+            }
+        }
+
+        if let Some(escrow_vault) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::EscrowVault)
+        {
+            let has_escrow: bool = env.invoke_contract(
+                &escrow_vault,
+                &Symbol::new(env, "escrow_exists"),
+                (match_id.clone(),).into_val(env),
+            );
+            if !has_escrow {
+                return;
+            }
+            let escrow_state: u32 = env.invoke_contract(
+                &escrow_vault,
+                &Symbol::new(env, "get_escrow_state"),
+                (match_id.clone(),).into_val(env),
+            );
+            if escrow_state != ESCROW_DISPUTED {
+                return;
+            }
+
+            let Some(share) = share_a_bps else {
+                let _: () = env.invoke_contract(
+                    &escrow_vault,
+                    &Symbol::new(env, "reopen_escrow"),
+                    (match_id.clone(), caller.clone()).into_val(env),
+                );
+                return;
+            };
+            let awarded = outcome == OUTCOME_AWARD_A || outcome == OUTCOME_AWARD_B;
+            let charge_fee = awarded && !deciders.is_empty() && Self::referee_fee_bps(env) > 0;
+            let fee = if charge_fee {
+                let stake: i128 = env.invoke_contract(
+                    &escrow_vault,
+                    &Symbol::new(env, "get_stake_amount"),
+                    (match_id.clone(),).into_val(env),
+                );
+                Self::referee_fee(env, stake)
+            } else {
+                0
+            };
+
+            let _: () = env.invoke_contract(
+                &escrow_vault,
+                &Symbol::new(env, "settle_dispute"),
+                (
+                    match_id.clone(),
+                    share,
+                    fee,
+                    env.current_contract_address(),
+                    caller.clone(),
+                )
+                    .into_val(env),
+            );
+
+            if fee > 0 {
+                let asset: Address = env.invoke_contract(
+                    &escrow_vault,
+                    &Symbol::new(env, "get_escrow_asset"),
+                    (match_id.clone(),).into_val(env),
+                );
+                Self::credit_referees(env, match_id, deciders, &asset, fee);
+            }
+        }
+    }
+
+    fn referee_fee_bps(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::RefereeFeeBps)
+            .unwrap_or(0)
+    }
+
+    fn referee_fee(env: &Env, amount: i128) -> i128 {
+        amount * Self::referee_fee_bps(env) as i128 / BPS_DENOMINATOR as i128
+    }
+
+    /// Split `amount` of `token` evenly between `referees`; the first referee gets the
+    /// remainder.
+    fn credit_referees(
+        env: &Env,
+        match_id: &BytesN<32>,
+        referees: &Vec<Address>,
+        token: &Address,
+        amount: i128,
+    ) {
+        if amount <= 0 || referees.is_empty() {
+            return;
+        }
+
+        let share = amount / referees.len() as i128;
+        let remainder = amount - share * referees.len() as i128;
+        for (i, referee) in referees.iter().enumerate() {
+            let credit = if i == 0 { share + remainder } else { share };
+            let key = DataKey::RefereeFees(referee, token.clone());
+            let balance: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+            env.storage().persistent().set(&key, &(balance + credit));
+        }
+
+        events::emit_referee_fees_accrued(env, match_id, token, amount, referees.len());
+    }
+
+    fn admin(env: &Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("contract not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::admin(env).require_auth();
+    }
+
+    fn is_operator(env: &Env, addr: &Address) -> bool {
+        if addr == &Self::admin(env) {
+            return true;
+        }
+
+        if let Some(identity_contract) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::IdentityContract)
+        {
+            let role: u32 = env.invoke_contract(
+                &identity_contract,
+                &Symbol::new(env, "get_role"),
+                (addr.clone(),).into_val(env),
+            );
+            return role == 1 || role == 2;
+        }
+
+        false
+    }
+}
+
+#[cfg(test)]
+mod test;
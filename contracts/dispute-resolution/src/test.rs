@@ -0,0 +1,1180 @@
+#![cfg(test)]
+
+use super::*;
+use match_contract::{GameConfig, MatchContract, MatchContractClient, MatchState};
+use match_escrow_vault::{EscrowState, MatchEscrowVault, MatchEscrowVaultClient};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, String, Vec,
+};
+
+#[contract]
+pub struct MockIdentityContract;
+
+#[contractimpl]
+impl MockIdentityContract {
+    pub fn set_role(env: Env, user: Address, role: u32) {
+        env.storage().persistent().set(&user, &role);
+    }
+
+    pub fn get_role(env: Env, user: Address) -> u32 {
+        env.storage().persistent().get(&user).unwrap_or(0)
+    }
+}
+
+/// Match contract stand-in: players are registered per match id, and every match reads as
+/// disputed.
+#[contract]
+pub struct MockMatchContract;
+
+#[contractimpl]
+impl MockMatchContract {
+    pub fn add_player(env: Env, match_id: BytesN<32>, player: Address) {
+        env.storage().persistent().set(&(match_id, player), &true);
+    }
+
+    pub fn is_participant(env: Env, match_id: BytesN<32>, player: Address) -> bool {
+        env.storage().persistent().has(&(match_id, player))
+    }
+
+    pub fn get_match_state(_env: Env, _match_id: BytesN<32>) -> Option<u32> {
+        Some(MATCH_DISPUTED)
+    }
+
+    pub fn settle_dispute(
+        env: Env,
+        match_id: BytesN<32>,
+        outcome: u32,
+        _resolver: Address,
+    ) {
+        env.storage().persistent().set(&match_id, &outcome);
+    }
+
+    pub fn outcome(env: Env, match_id: BytesN<32>) -> Option<u32> {
+        env.storage().persistent().get(&match_id)
+    }
+}
+
+/// Escrow vault stand-in recording player A's settled share (or u32::MAX when reopened).
+/// Created escrows read as disputed.
+#[contract]
+pub struct MockEscrowVault;
+
+#[contractimpl]
+impl MockEscrowVault {
+    pub fn settle_dispute(
+        env: Env,
+        match_id: BytesN<32>,
+        share_a_bps: u32,
+        fee: i128,
+        fee_recipient: Address,
+        _resolver: Address,
+    ) {
+        if fee > 0 {
+            let asset: Address = env
+                .storage()
+                .instance()
+                .get(&symbol_short!("asset"))
+                .unwrap();
+            TokenClient::new(&env, &asset).transfer(
+                &env.current_contract_address(),
+                &fee_recipient,
+                &fee,
+            );
+        }
+        env.storage().persistent().set(&match_id, &share_a_bps);
+    }
+
+    pub fn set_asset(env: Env, asset: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("asset"), &asset);
+    }
+
+    pub fn get_escrow_asset(env: Env, _match_id: BytesN<32>) -> Address {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("asset"))
+            .unwrap()
+    }
+
+    pub fn reopen_escrow(env: Env, match_id: BytesN<32>, _resolver: Address) {
+        env.storage().persistent().set(&match_id, &u32::MAX);
+    }
+
+    pub fn share(env: Env, match_id: BytesN<32>) -> Option<u32> {
+        env.storage().persistent().get(&match_id)
+    }
+
+    pub fn create(
+        env: Env,
+        match_id: BytesN<32>,
+        player_a: Address,
+        player_b: Address,
+        amount: i128,
+    ) {
+        env.storage().persistent().set(
+            &(match_id, symbol_short!("escrow")),
+            &(player_a, player_b, amount),
+        );
+    }
+
+    pub fn escrow_exists(env: Env, match_id: BytesN<32>) -> bool {
+        env.storage()
+            .persistent()
+            .has(&(match_id, symbol_short!("escrow")))
+    }
+
+    pub fn get_escrow_state(_env: Env, _match_id: BytesN<32>) -> u32 {
+        ESCROW_DISPUTED
+    }
+
+    pub fn get_players(env: Env, match_id: BytesN<32>) -> (Address, Address) {
+        let (a, b, _): (Address, Address, i128) = env
+            .storage()
+            .persistent()
+            .get(&(match_id, symbol_short!("escrow")))
+            .unwrap();
+        (a, b)
+    }
+
+    pub fn get_stake_amount(env: Env, match_id: BytesN<32>) -> i128 {
+        let (_, _, amount): (Address, Address, i128) = env
+            .storage()
+            .persistent()
+            .get(&(match_id, symbol_short!("escrow")))
+            .unwrap();
+        amount
+    }
+}
+
+/// Anti-cheat oracle stand-in holding a confirmation history per player.
+#[contract]
+pub struct MockAntiCheatOracle;
+
+#[contractimpl]
+impl MockAntiCheatOracle {
+    pub fn confirm(env: Env, player: Address, match_id: u64, severity: u32) {
+        let mut history: Vec<CheatConfirmation> = env
+            .storage()
+            .persistent()
+            .get(&player)
+            .unwrap_or(Vec::new(&env));
+        history.push_back(CheatConfirmation {
+            player: player.clone(),
+            match_id,
+            severity,
+            penalty_applied: 10,
+            timestamp: env.ledger().timestamp(),
+            oracle: env.current_contract_address(),
+            oracles: Vec::new(&env),
+        });
+        env.storage().persistent().set(&player, &history);
+    }
+
+    pub fn get_flag_count(env: Env, player: Address) -> u32 {
+        Self::history(&env, &player).len()
+    }
+
+    pub fn get_flag_history(
+        env: Env,
+        player: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<CheatConfirmation> {
+        let history = Self::history(&env, &player);
+        let end = (offset + limit).min(history.len());
+        history.slice(offset..end)
+    }
+
+    pub fn get_confirmation(env: Env, player: Address, match_id: u64) -> Option<CheatConfirmation> {
+        Self::history(&env, &player)
+            .iter()
+            .find(|c| c.match_id == match_id)
+    }
+}
+
+impl MockAntiCheatOracle {
+    fn history(env: &Env, player: &Address) -> Vec<CheatConfirmation> {
+        env.storage()
+            .persistent()
+            .get(player)
+            .unwrap_or(Vec::new(env))
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    client: DisputeResolutionContractClient<'a>,
+    matches: MockMatchContractClient<'a>,
+    identity: MockIdentityContractClient<'a>,
+    admin: Address,
+}
+
+fn setup<'a>() -> Setup<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let identity_id = env.register(MockIdentityContract, ());
+    let match_id = env.register(MockMatchContract, ());
+    let contract_id = env.register(DisputeResolutionContract, ());
+    let client = DisputeResolutionContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &identity_id, &1_000);
+    client.set_match_contract(&match_id);
+    let matches = MockMatchContractClient::new(&env, &match_id);
+    let identity = MockIdentityContractClient::new(&env, &identity_id);
+
+    Setup {
+        env,
+        client,
+        matches,
+        identity,
+        admin,
+    }
+}
+
+fn open_test_dispute(s: &Setup, seed: u8) -> BytesN<32> {
+    let match_id = BytesN::from_array(&s.env, &[seed; 32]);
+    s.client.open_dispute(
+        &match_id,
+        &Address::generate(&s.env),
+        &0,
+        &String::from_str(&s.env, "score mismatch"),
+        &String::from_str(&s.env, "ipfs://opening"),
+    );
+    s.client.claim_dispute(&match_id, &s.admin);
+    match_id
+}
+
+fn create_escrow(s: &Setup, escrow: &MockEscrowVaultClient, match_id: &BytesN<32>) {
+    escrow.create(
+        match_id,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &100,
+    );
+}
+
+#[test]
+fn test_open_and_resolve_dispute() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 1);
+    assert!(s.client.is_disputed(&match_id));
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    assert!(!s.client.is_disputed(&match_id));
+    assert_eq!(
+        s.client.get_decision(&match_id),
+        Some(Decision::AwardPlayerA)
+    );
+}
+
+#[test]
+fn test_submit_evidence_by_participants() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 2);
+    let player_a = Address::generate(&s.env);
+    let player_b = Address::generate(&s.env);
+    s.matches.add_player(&match_id, &player_a);
+    s.matches.add_player(&match_id, &player_b);
+
+    s.client.submit_evidence(
+        &match_id,
+        &player_a,
+        &String::from_str(&s.env, "ipfs://a-1"),
+        &String::from_str(&s.env, "replay"),
+    );
+    s.client.submit_evidence(
+        &match_id,
+        &player_b,
+        &String::from_str(&s.env, "ipfs://b-1"),
+        &String::from_str(&s.env, "screenshot"),
+    );
+    s.client.submit_evidence(
+        &match_id,
+        &player_a,
+        &String::from_str(&s.env, "ipfs://a-2"),
+        &String::from_str(&s.env, "chat log"),
+    );
+
+    let evidence = s.client.get_evidence(&match_id);
+    assert_eq!(evidence.len(), 3);
+    assert_eq!(evidence.get(1).unwrap().submitter, player_b);
+    assert_eq!(
+        evidence.get(2).unwrap().evidence_ref,
+        String::from_str(&s.env, "ipfs://a-2")
+    );
+}
+
+#[test]
+#[should_panic(expected = "submitter is not a match participant")]
+fn test_submit_evidence_non_participant() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 3);
+    s.client.submit_evidence(
+        &match_id,
+        &Address::generate(&s.env),
+        &String::from_str(&s.env, "ipfs://x"),
+        &String::from_str(&s.env, "spam"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "evidence deadline has passed")]
+fn test_submit_evidence_after_deadline() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 4);
+    let player = Address::generate(&s.env);
+    s.matches.add_player(&match_id, &player);
+
+    s.env.ledger().set_timestamp(1_001);
+    s.client.submit_evidence(
+        &match_id,
+        &player,
+        &String::from_str(&s.env, "ipfs://late"),
+        &String::from_str(&s.env, "late"),
+    );
+}
+
+#[test]
+fn test_decisions_drive_match_and_escrow() {
+    let s = setup();
+    let escrow_id = s.env.register(MockEscrowVault, ());
+    let escrow = MockEscrowVaultClient::new(&s.env, &escrow_id);
+    s.client.set_escrow_vault(&escrow_id);
+
+    let cases = [
+        (Decision::AwardPlayerA, 0u32, 10_000u32),
+        (Decision::AwardPlayerB, 1, 0),
+        (Decision::Split(7_000, 3_000), 2, 7_000),
+        (Decision::Replay, 3, u32::MAX),
+        (Decision::Void, 4, 5_000),
+    ];
+    for (i, (decision, outcome, share)) in cases.iter().enumerate() {
+        let match_id = open_test_dispute(&s, 10 + i as u8);
+        create_escrow(&s, &escrow, &match_id);
+        s.client.resolve_dispute(&match_id, &s.admin, decision);
+        assert_eq!(s.matches.outcome(&match_id), Some(*outcome));
+        assert_eq!(escrow.share(&match_id), Some(*share));
+        assert_eq!(s.client.get_decision(&match_id), Some(decision.clone()));
+    }
+}
+
+#[test]
+#[should_panic(expected = "split shares must total 10000")]
+fn test_split_must_total_full_pot() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 20);
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::Split(6_000, 3_000));
+}
+
+fn assign_test_panel(s: &Setup, match_id: &BytesN<32>, size: u32) -> Vec<Address> {
+    let mut panel = Vec::new(&s.env);
+    for _ in 0..size {
+        let referee = Address::generate(&s.env);
+        s.identity.set_role(&referee, &2);
+        panel.push_back(referee);
+    }
+    s.client.assign_panel(match_id, &panel);
+    panel
+}
+
+#[test]
+fn test_panel_majority_resolves() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 30);
+    let panel = assign_test_panel(&s, &match_id, 3);
+
+    s.client
+        .cast_vote(&match_id, &panel.get(0).unwrap(), &Decision::AwardPlayerB);
+    s.client
+        .cast_vote(&match_id, &panel.get(1).unwrap(), &Decision::Void);
+    assert!(s.client.is_disputed(&match_id));
+
+    s.client
+        .cast_vote(&match_id, &panel.get(2).unwrap(), &Decision::AwardPlayerB);
+    assert!(!s.client.is_disputed(&match_id));
+    assert_eq!(
+        s.client.get_decision(&match_id),
+        Some(Decision::AwardPlayerB)
+    );
+    assert_eq!(s.matches.outcome(&match_id), Some(1));
+
+    let votes = s.client.get_votes(&match_id);
+    assert_eq!(votes.len(), 3);
+    assert_eq!(votes.get(1).unwrap().referee, panel.get(1).unwrap());
+    assert_eq!(votes.get(1).unwrap().decision, Decision::Void);
+}
+
+#[test]
+#[should_panic(expected = "referee is not on the panel")]
+fn test_panel_rejects_outside_voter() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 31);
+    assign_test_panel(&s, &match_id, 3);
+    s.client
+        .cast_vote(&match_id, &s.admin, &Decision::AwardPlayerA);
+}
+
+#[test]
+#[should_panic(expected = "referee already voted")]
+fn test_panel_rejects_double_vote() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 32);
+    let panel = assign_test_panel(&s, &match_id, 5);
+    let referee = panel.get(0).unwrap();
+    s.client
+        .cast_vote(&match_id, &referee, &Decision::AwardPlayerA);
+    s.client.cast_vote(&match_id, &referee, &Decision::Replay);
+}
+
+#[test]
+#[should_panic(expected = "dispute is decided by its panel")]
+fn test_panel_blocks_single_operator() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 33);
+    assign_test_panel(&s, &match_id, 3);
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+}
+
+#[test]
+fn test_panel_deadlock_escalates_to_admin() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 34);
+    let panel = assign_test_panel(&s, &match_id, 4);
+    s.client
+        .cast_vote(&match_id, &panel.get(0).unwrap(), &Decision::AwardPlayerA);
+    s.client
+        .cast_vote(&match_id, &panel.get(1).unwrap(), &Decision::AwardPlayerB);
+
+    s.env.ledger().set_timestamp(1_001);
+    s.client.escalate_dispute(&match_id);
+    assert!(s.client.is_disputed(&match_id));
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::Replay);
+    assert_eq!(s.client.get_decision(&match_id), Some(Decision::Replay));
+}
+
+#[test]
+#[should_panic(expected = "panel must have 3 to 5 referees")]
+fn test_panel_size_bounds() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 35);
+    assign_test_panel(&s, &match_id, 2);
+}
+
+struct BondSetup {
+    token: TokenClient<'static>,
+    escrow: MockEscrowVaultClient<'static>,
+    treasury: Address,
+    player_a: Address,
+    player_b: Address,
+}
+
+fn setup_bonds(s: &Setup<'static>, forfeit_to_counterparty: bool) -> BondSetup {
+    let token_admin = Address::generate(&s.env);
+    let token_id = s
+        .env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let escrow_id = s.env.register(MockEscrowVault, ());
+    s.client.set_escrow_vault(&escrow_id);
+
+    let treasury = Address::generate(&s.env);
+    let mut brackets = Vec::new(&s.env);
+    brackets.push_back(BondBracket {
+        min_stake: 0,
+        bond: 10,
+    });
+    brackets.push_back(BondBracket {
+        min_stake: 1_000,
+        bond: 50,
+    });
+    s.client.set_bond_config(&BondConfig {
+        token: token_id.clone(),
+        treasury: treasury.clone(),
+        brackets,
+        forfeit_to_counterparty,
+    });
+
+    let player_a = Address::generate(&s.env);
+    let player_b = Address::generate(&s.env);
+    let minter = StellarAssetClient::new(&s.env, &token_id);
+    minter.mint(&player_a, &100);
+    minter.mint(&player_b, &100);
+
+    BondSetup {
+        token: TokenClient::new(&s.env, &token_id),
+        escrow: MockEscrowVaultClient::new(&s.env, &escrow_id),
+        treasury,
+        player_a,
+        player_b,
+    }
+}
+
+fn open_bonded_dispute(s: &Setup, b: &BondSetup, seed: u8, stake: i128) -> BytesN<32> {
+    let match_id = BytesN::from_array(&s.env, &[seed; 32]);
+    b.escrow.create(&match_id, &b.player_a, &b.player_b, &stake);
+    s.client.open_dispute(
+        &match_id,
+        &b.player_a,
+        &0,
+        &String::from_str(&s.env, "score mismatch"),
+        &String::from_str(&s.env, "ipfs://opening"),
+    );
+    s.client.claim_dispute(&match_id, &s.admin);
+    match_id
+}
+
+#[test]
+fn test_bond_refunded_when_upheld() {
+    let s = setup();
+    let b = setup_bonds(&s, false);
+
+    let match_id = open_bonded_dispute(&s, &b, 40, 2_000);
+    assert_eq!(s.client.get_required_bond(&match_id), 50);
+    assert_eq!(b.token.balance(&b.player_a), 50);
+    assert_eq!(s.client.get_bond(&match_id).unwrap().amount, 50);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    assert_eq!(b.token.balance(&b.player_a), 100);
+    assert_eq!(s.client.get_bond(&match_id), None);
+}
+
+#[test]
+fn test_bond_forfeited_when_rejected() {
+    let s = setup();
+    let b = setup_bonds(&s, false);
+    let match_id = open_bonded_dispute(&s, &b, 41, 500);
+    assert_eq!(b.token.balance(&b.player_a), 90);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerB);
+    assert_eq!(b.token.balance(&b.player_a), 90);
+    assert_eq!(b.token.balance(&b.treasury), 10);
+
+    let s = setup();
+    let b = setup_bonds(&s, true);
+    let match_id = open_bonded_dispute(&s, &b, 42, 500);
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerB);
+    assert_eq!(b.token.balance(&b.player_b), 110);
+}
+
+#[test]
+#[should_panic(expected = "opener is not a match participant")]
+fn test_bond_requires_participant_opener() {
+    let s = setup();
+    let b = setup_bonds(&s, false);
+    let match_id = BytesN::from_array(&s.env, &[43; 32]);
+    b.escrow.create(&match_id, &b.player_a, &b.player_b, &500);
+    s.client.open_dispute(
+        &match_id,
+        &Address::generate(&s.env),
+        &0,
+        &String::from_str(&s.env, "spam"),
+        &String::from_str(&s.env, "ipfs://x"),
+    );
+}
+
+#[test]
+fn test_anticheat_oracle_opens_without_bond() {
+    let s = setup();
+    let b = setup_bonds(&s, false);
+    let oracle = Address::generate(&s.env);
+    s.client.set_anticheat_oracle(&oracle);
+
+    let match_id = BytesN::from_array(&s.env, &[44; 32]);
+    b.escrow.create(&match_id, &b.player_a, &b.player_b, &500);
+    s.client.open_dispute(
+        &match_id,
+        &oracle,
+        &0,
+        &String::from_str(&s.env, "anti-cheat"),
+        &String::from_str(&s.env, "oracle"),
+    );
+    assert_eq!(s.client.get_bond(&match_id), None);
+}
+
+struct AppealSetup {
+    token: TokenClient<'static>,
+    treasury: Address,
+    player_a: Address,
+    player_b: Address,
+}
+
+fn enable_appeals(s: &Setup<'static>, match_id: &BytesN<32>) -> AppealSetup {
+    let token_admin = Address::generate(&s.env);
+    let token_id = s
+        .env
+        .register_stellar_asset_contract_v2(token_admin)
+        .address();
+    let treasury = Address::generate(&s.env);
+    s.client.set_appeal_config(&AppealConfig {
+        window: 100,
+        token: token_id.clone(),
+        bond: 200,
+        treasury: treasury.clone(),
+        panel_size: 7,
+    });
+
+    let player_a = Address::generate(&s.env);
+    let player_b = Address::generate(&s.env);
+    s.matches.add_player(match_id, &player_a);
+    s.matches.add_player(match_id, &player_b);
+    StellarAssetClient::new(&s.env, &token_id).mint(&player_b, &500);
+
+    AppealSetup {
+        token: TokenClient::new(&s.env, &token_id),
+        treasury,
+        player_a,
+        player_b,
+    }
+}
+
+#[test]
+fn test_decision_suspended_until_window_closes() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 50);
+    enable_appeals(&s, &match_id);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    assert!(s.client.is_disputed(&match_id));
+    assert_eq!(s.matches.outcome(&match_id), None);
+
+    s.env.ledger().set_timestamp(101);
+    s.client.finalize_dispute(&match_id, &s.admin);
+    assert!(!s.client.is_disputed(&match_id));
+    assert_eq!(s.matches.outcome(&match_id), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "appeal window has not closed")]
+fn test_finalize_before_window_fails() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 51);
+    enable_appeals(&s, &match_id);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    s.client.finalize_dispute(&match_id, &s.admin);
+}
+
+#[test]
+fn test_appeal_overturned_by_panel_refunds_bond() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 52);
+    let a = enable_appeals(&s, &match_id);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    s.client.appeal_dispute(
+        &match_id,
+        &a.player_b,
+        &String::from_str(&s.env, "new footage"),
+    );
+    assert_eq!(a.token.balance(&a.player_b), 300);
+    assert_eq!(
+        s.client.get_appeal(&match_id).unwrap().original,
+        Decision::AwardPlayerA
+    );
+
+    let mut panel = Vec::new(&s.env);
+    for _ in 0..7 {
+        let referee = Address::generate(&s.env);
+        s.identity.set_role(&referee, &2);
+        panel.push_back(referee);
+    }
+    s.client.assign_appeal_panel(&match_id, &panel);
+    for i in 0..4 {
+        s.client
+            .cast_appeal_vote(&match_id, &panel.get(i).unwrap(), &Decision::AwardPlayerB);
+    }
+
+    assert!(!s.client.is_disputed(&match_id));
+    assert_eq!(
+        s.client.get_decision(&match_id),
+        Some(Decision::AwardPlayerB)
+    );
+    assert_eq!(s.matches.outcome(&match_id), Some(1));
+    assert_eq!(a.token.balance(&a.player_b), 500);
+    assert_eq!(s.client.get_appeal_votes(&match_id).len(), 4);
+}
+
+#[test]
+fn test_appeal_rejected_by_admin_forfeits_bond() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 53);
+    let a = enable_appeals(&s, &match_id);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    s.client
+        .appeal_dispute(&match_id, &a.player_b, &String::from_str(&s.env, "unfair"));
+    s.client
+        .resolve_appeal(&match_id, &s.admin, &Decision::AwardPlayerA);
+
+    assert_eq!(a.token.balance(&a.treasury), 200);
+    assert_eq!(s.matches.outcome(&match_id), Some(0));
+}
+
+#[test]
+#[should_panic(expected = "dispute is not awaiting appeal")]
+fn test_appeal_only_once() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 54);
+    let a = enable_appeals(&s, &match_id);
+    StellarAssetClient::new(&s.env, &a.token.address).mint(&a.player_a, &500);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    s.client
+        .appeal_dispute(&match_id, &a.player_b, &String::from_str(&s.env, "first"));
+    s.client
+        .appeal_dispute(&match_id, &a.player_a, &String::from_str(&s.env, "second"));
+}
+
+#[test]
+#[should_panic(expected = "appeal window has closed")]
+fn test_appeal_after_window_fails() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 55);
+    let a = enable_appeals(&s, &match_id);
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    s.env.ledger().set_timestamp(101);
+    s.client
+        .appeal_dispute(&match_id, &a.player_b, &String::from_str(&s.env, "late"));
+}
+
+#[test]
+fn test_expire_dispute_applies_default() {
+    let s = setup();
+    let escrow_id = s.env.register(MockEscrowVault, ());
+    let escrow = MockEscrowVaultClient::new(&s.env, &escrow_id);
+    s.client.set_escrow_vault(&escrow_id);
+
+    let match_id = open_test_dispute(&s, 60);
+    create_escrow(&s, &escrow, &match_id);
+    s.env.ledger().set_timestamp(1_001);
+    s.client.expire_dispute(&match_id);
+
+    assert!(!s.client.is_disputed(&match_id));
+    assert_eq!(s.client.get_decision(&match_id), Some(Decision::Void));
+    assert_eq!(s.matches.outcome(&match_id), Some(4));
+    assert_eq!(escrow.share(&match_id), Some(5_000));
+
+    s.client.set_default_decision(&Decision::Replay);
+    let match_id = open_test_dispute(&s, 61);
+    s.env.ledger().set_timestamp(2_002);
+    s.client.expire_dispute(&match_id);
+    assert_eq!(s.matches.outcome(&match_id), Some(3));
+}
+
+#[test]
+#[should_panic(expected = "resolution deadline has not passed")]
+fn test_expire_before_deadline_fails() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 62);
+    s.client.expire_dispute(&match_id);
+}
+
+#[test]
+fn test_claim_queue_and_caseload() {
+    let s = setup();
+    let operator = Address::generate(&s.env);
+    s.identity.set_role(&operator, &1);
+
+    let mut ids = Vec::new(&s.env);
+    for seed in 70..73u8 {
+        let match_id = BytesN::from_array(&s.env, &[seed; 32]);
+        s.client.open_dispute(
+            &match_id,
+            &Address::generate(&s.env),
+            &0,
+            &String::from_str(&s.env, "score mismatch"),
+            &String::from_str(&s.env, "ipfs://opening"),
+        );
+        ids.push_back(match_id);
+    }
+    assert_eq!(s.client.get_open_disputes(&0, &10), ids);
+
+    let first = ids.get(0).unwrap();
+    s.client.claim_dispute(&first, &operator);
+    assert_eq!(s.client.get_open_disputes(&0, &10).len(), 2);
+    assert_eq!(s.client.get_assignee(&first), Some(operator.clone()));
+    assert_eq!(s.client.get_disputes_by_operator(&operator).len(), 1);
+
+    s.client
+        .resolve_dispute(&first, &operator, &Decision::AwardPlayerA);
+    assert_eq!(s.client.get_disputes_by_operator(&operator).len(), 0);
+    assert_eq!(s.client.get_assignee(&first), None);
+}
+
+#[test]
+#[should_panic(expected = "operator caseload is full")]
+fn test_claim_respects_caseload() {
+    let s = setup();
+    s.client.set_max_caseload(&1);
+    open_test_dispute(&s, 74);
+    open_test_dispute(&s, 75);
+}
+
+#[test]
+#[should_panic(expected = "dispute is not assigned to caller")]
+fn test_only_assignee_resolves() {
+    let s = setup();
+    let operator = Address::generate(&s.env);
+    s.identity.set_role(&operator, &1);
+    let match_id = open_test_dispute(&s, 76);
+    s.client
+        .resolve_dispute(&match_id, &operator, &Decision::AwardPlayerA);
+}
+
+#[test]
+fn test_referee_fees_from_losing_stake_and_bond() {
+    let s = setup();
+    let b = setup_bonds(&s, false);
+    s.client.set_referee_fee(&1_000);
+    b.escrow.set_asset(&b.token.address);
+    StellarAssetClient::new(&s.env, &b.token.address).mint(&b.escrow.address, &1_000);
+
+    // Player A opens and loses: 10% of the 500 losing stake and of the 10 bond go to the referee.
+    let match_id = open_bonded_dispute(&s, &b, 80, 500);
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerB);
+
+    assert_eq!(s.client.get_referee_fees(&s.admin, &b.token.address), 51);
+    assert_eq!(b.token.balance(&b.treasury), 9);
+
+    let claimed = s.client.claim_referee_fees(&s.admin, &b.token.address);
+    assert_eq!(claimed, 51);
+    assert_eq!(b.token.balance(&s.admin), 51);
+    assert_eq!(s.client.get_referee_fees(&s.admin, &b.token.address), 0);
+}
+
+#[test]
+fn test_panel_majority_shares_fees() {
+    let s = setup();
+    let escrow_id = s.env.register(MockEscrowVault, ());
+    let escrow = MockEscrowVaultClient::new(&s.env, &escrow_id);
+    s.client.set_escrow_vault(&escrow_id);
+    s.client.set_referee_fee(&2_000);
+
+    let token_id = s
+        .env
+        .register_stellar_asset_contract_v2(Address::generate(&s.env))
+        .address();
+    escrow.set_asset(&token_id);
+    StellarAssetClient::new(&s.env, &token_id).mint(&escrow_id, &1_000);
+
+    let match_id = open_test_dispute(&s, 81);
+    escrow.create(
+        &match_id,
+        &Address::generate(&s.env),
+        &Address::generate(&s.env),
+        &101,
+    );
+    let panel = assign_test_panel(&s, &match_id, 3);
+    s.client
+        .cast_vote(&match_id, &panel.get(0).unwrap(), &Decision::AwardPlayerA);
+    s.client
+        .cast_vote(&match_id, &panel.get(1).unwrap(), &Decision::Void);
+    s.client
+        .cast_vote(&match_id, &panel.get(2).unwrap(), &Decision::AwardPlayerA);
+
+    // 20% of a 101 stake is 20, split between the two majority voters.
+    assert_eq!(
+        s.client.get_referee_fees(&panel.get(0).unwrap(), &token_id),
+        10
+    );
+    assert_eq!(
+        s.client.get_referee_fees(&panel.get(1).unwrap(), &token_id),
+        0
+    );
+    assert_eq!(
+        s.client.get_referee_fees(&panel.get(2).unwrap(), &token_id),
+        10
+    );
+}
+
+#[test]
+fn test_dispute_context_includes_recent_flags() {
+    let s = setup();
+    let b = setup_bonds(&s, false);
+    let oracle_id = s.env.register(MockAntiCheatOracle, ());
+    let oracle = MockAntiCheatOracleClient::new(&s.env, &oracle_id);
+    s.client.set_anticheat_oracle(&oracle_id);
+
+    for i in 0..7u64 {
+        oracle.confirm(&b.player_b, &i, &2);
+    }
+    oracle.confirm(&b.player_a, &100, &1);
+
+    let match_id = open_bonded_dispute(&s, &b, 90, 500);
+    let context = s.client.get_dispute_context(&match_id);
+    assert_eq!(context.dispute.match_id, match_id);
+    assert_eq!(context.player_a, Some(b.player_a.clone()));
+    assert_eq!(context.flags_a.len(), 1);
+    assert_eq!(context.flags_b.len(), CONTEXT_FLAG_LIMIT);
+    assert_eq!(context.flags_b.get(0).unwrap().match_id, 2);
+
+    s.client
+        .cite_confirmation(&match_id, &s.admin, &b.player_b, &6);
+    let context = s.client.get_dispute_context(&match_id);
+    assert_eq!(
+        context.citations,
+        Vec::from_array(
+            &s.env,
+            [ConfirmationRef {
+                player: b.player_b.clone(),
+                match_id: 6,
+            }]
+        )
+    );
+
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    assert_eq!(s.client.get_citations(&match_id).len(), 1);
+}
+
+#[test]
+#[should_panic(expected = "confirmation not found")]
+fn test_cite_unknown_confirmation_fails() {
+    let s = setup();
+    let oracle_id = s.env.register(MockAntiCheatOracle, ());
+    s.client.set_anticheat_oracle(&oracle_id);
+    let match_id = open_test_dispute(&s, 91);
+    s.client
+        .cite_confirmation(&match_id, &s.admin, &Address::generate(&s.env), &1);
+}
+
+#[test]
+fn test_category_windows() {
+    let s = setup();
+    s.client.set_resolution_window(&1, &5_000);
+    assert_eq!(s.client.get_resolution_window(&0), 1_000);
+    assert_eq!(s.client.get_resolution_window(&1), 5_000);
+
+    let match_id = BytesN::from_array(&s.env, &[100; 32]);
+    s.client.open_dispute(
+        &match_id,
+        &Address::generate(&s.env),
+        &(DisputeCategory::HighStakes as u32),
+        &String::from_str(&s.env, "score mismatch"),
+        &String::from_str(&s.env, "ipfs://opening"),
+    );
+    assert_eq!(s.client.get_dispute_category(&match_id), 1);
+
+    // Still within the high-stakes window after the standard one has elapsed.
+    s.env.ledger().set_timestamp(2_000);
+    s.client.claim_dispute(&match_id, &s.admin);
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+    assert!(!s.client.is_disputed(&match_id));
+}
+
+#[test]
+#[should_panic(expected = "invalid dispute category")]
+fn test_invalid_category_fails() {
+    let s = setup();
+    s.client.open_dispute(
+        &BytesN::from_array(&s.env, &[101; 32]),
+        &Address::generate(&s.env),
+        &3,
+        &String::from_str(&s.env, "score mismatch"),
+        &String::from_str(&s.env, "ipfs://opening"),
+    );
+}
+
+#[test]
+#[should_panic(expected = "contract is paused")]
+fn test_paused_blocks_resolution() {
+    let s = setup();
+    let match_id = open_test_dispute(&s, 102);
+    s.client.set_paused(&true);
+    assert!(s.client.is_paused());
+    s.client
+        .resolve_dispute(&match_id, &s.admin, &Decision::AwardPlayerA);
+}
+
+/// Dispute contract wired to the real Match Contract and Match Escrow Vault, with a referee
+/// recognised by all three.
+struct Live<'a> {
+    env: Env,
+    client: DisputeResolutionContractClient<'a>,
+    matches: MatchContractClient<'a>,
+    vault: MatchEscrowVaultClient<'a>,
+    token: TokenClient<'a>,
+    admin: Address,
+    referee: Address,
+    player_a: Address,
+    player_b: Address,
+}
+
+fn setup_live() -> Live<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let referee = Address::generate(&env);
+    let identity = MockIdentityContractClient::new(&env, &env.register(MockIdentityContract, ()));
+    identity.set_role(&referee, &1);
+
+    let client =
+        DisputeResolutionContractClient::new(&env, &env.register(DisputeResolutionContract, ()));
+    client.initialize(&admin, &identity.address, &1_000);
+
+    let matches = MatchContractClient::new(&env, &env.register(MatchContract, ()));
+    matches.initialize(&admin);
+    matches.set_identity_contract(&identity.address);
+    matches.register_game(
+        &symbol_short!("chess"),
+        &GameConfig {
+            max_score: 1,
+            round_count: 1,
+            modes: Vec::new(&env),
+        },
+    );
+
+    let vault = MatchEscrowVaultClient::new(&env, &env.register(MatchEscrowVault, ()));
+    vault.initialize(&admin);
+    vault.set_identity_contract(&identity.address);
+
+    client.set_match_contract(&matches.address);
+    client.set_escrow_vault(&vault.address);
+
+    let token_id = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let minter = StellarAssetClient::new(&env, &token_id);
+    minter.mint(&player_a, &1_000);
+    minter.mint(&player_b, &1_000);
+
+    Live {
+        token: TokenClient::new(&env, &token_id),
+        env,
+        client,
+        matches,
+        vault,
+        admin,
+        referee,
+        player_a,
+        player_b,
+    }
+}
+
+/// Create and start a match; with `stake`, also create, fund and lock its escrow.
+fn start_live_match(l: &Live, seed: u8, stake: Option<i128>) -> BytesN<32> {
+    let match_id = BytesN::from_array(&l.env, &[seed; 32]);
+    l.matches.create_match(
+        &match_id,
+        &l.player_a,
+        &l.player_b,
+        &symbol_short!("chess"),
+        &symbol_short!("eu"),
+        &symbol_short!("ranked"),
+        &l.admin,
+    );
+    l.matches.start_match(&match_id, &l.player_a);
+    if let Some(stake) = stake {
+        l.vault.create_escrow(
+            &match_id,
+            &l.player_a,
+            &l.player_b,
+            &stake,
+            &l.token.address,
+            &l.admin,
+        );
+        l.vault.deposit(&match_id, &l.player_a);
+        l.vault.deposit(&match_id, &l.player_b);
+        l.vault.lock_funds(&match_id);
+    }
+    match_id
+}
+
+fn open_live_dispute(l: &Live, match_id: &BytesN<32>, opener: &Address) {
+    l.client.open_dispute(
+        match_id,
+        opener,
+        &0,
+        &String::from_str(&l.env, "score mismatch"),
+        &String::from_str(&l.env, "ipfs://opening"),
+    );
+    l.client.claim_dispute(match_id, &l.referee);
+}
+
+#[test]
+fn test_live_player_dispute_settles_match_and_escrow() {
+    let l = setup_live();
+    let match_id = start_live_match(&l, 90, Some(100));
+    l.vault.mark_disputed(&match_id);
+
+    open_live_dispute(&l, &match_id, &l.player_a);
+    assert_eq!(
+        l.matches.get_match(&match_id).state,
+        MatchState::Disputed as u32
+    );
+
+    l.client
+        .resolve_dispute(&match_id, &l.referee, &Decision::AwardPlayerB);
+
+    let settled = l.matches.get_match(&match_id);
+    assert_eq!(settled.state, MatchState::Completed as u32);
+    assert_eq!(settled.winner, Some(l.player_b.clone()));
+    assert_eq!(
+        l.vault.get_escrow_state(&match_id),
+        EscrowState::Released as u32
+    );
+    assert_eq!(l.token.balance(&l.player_a), 900);
+    assert_eq!(l.token.balance(&l.player_b), 1_100);
+}
+
+#[test]
+fn test_live_ruling_leaves_undisputed_escrow_locked() {
+    let l = setup_live();
+    let match_id = start_live_match(&l, 91, Some(100));
+
+    open_live_dispute(&l, &match_id, &l.player_b);
+    l.client
+        .resolve_dispute(&match_id, &l.referee, &Decision::AwardPlayerA);
+
+    assert_eq!(
+        l.matches.get_match(&match_id).winner,
+        Some(l.player_a.clone())
+    );
+    assert_eq!(
+        l.vault.get_escrow_state(&match_id),
+        EscrowState::Locked as u32
+    );
+    l.vault.release_to_winner(&match_id, &l.player_a);
+    assert_eq!(l.token.balance(&l.player_a), 1_100);
+}
+
+#[test]
+fn test_live_ruling_on_match_without_escrow() {
+    let l = setup_live();
+    let match_id = start_live_match(&l, 92, None);
+
+    open_live_dispute(&l, &match_id, &l.player_a);
+    l.client
+        .resolve_dispute(&match_id, &l.referee, &Decision::Replay);
+
+    assert_eq!(
+        l.matches.get_match(&match_id).state,
+        MatchState::Created as u32
+    );
+    assert!(!l.vault.escrow_exists(&match_id));
+}
@@ -0,0 +1,168 @@
+#![no_std]
+
+//! Cross-contract event bus.
+//!
+//! Indexers would otherwise watch every ArenaX contract and decode each one's
+//! topics. Registered system contracts also publish their notable events here as
+//! a typed `EventPayload`; the hub stamps each with the next global sequence
+//! number and re-emits it under a single normalized topic, so the backend ingests
+//! one ordered stream. Gaps in the sequence reveal missed events, and recent
+//! records are kept on-chain so a consumer can replay from a known sequence.
+
+use arenax_events::event_hub as events;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+/// Published events retained for replay; older records are evicted.
+pub const MAX_RETAINED: u64 = 10_000;
+
+/// Upper bound on records returned by one `get_events` call.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    LastSequence,
+    Publisher(Address), // publisher -> source name
+    Event(u64),
+}
+
+/// Event as submitted by a publisher. Fields that do not apply are left empty;
+/// anything else goes in `data`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventPayload {
+    pub kind: Symbol,
+    pub actor: Option<Address>,
+    pub subject_id: Option<BytesN<32>>,
+    pub amount: Option<i128>,
+    pub data: Bytes,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventRecord {
+    pub sequence: u64,
+    pub source: Symbol,
+    pub publisher: Address,
+    pub ledger: u32,
+    pub timestamp: u64,
+    pub payload: EventPayload,
+}
+
+#[contract]
+pub struct EventHub;
+
+#[contractimpl]
+impl EventHub {
+    pub fn initialize(env: Env, admin: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::LastSequence, &0u64);
+    }
+
+    /// Allow `publisher` to publish under the `source` name, or revoke it (admin only).
+    pub fn set_publisher(env: Env, publisher: Address, source: Symbol, enabled: bool) {
+        Self::require_admin(&env);
+        let key = DataKey::Publisher(publisher.clone());
+        if enabled {
+            env.storage().instance().set(&key, &source);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        events::emit_publisher_updated(&env, &publisher, &source, enabled);
+    }
+
+    /// Publish `payload` from a registered contract. Returns its sequence number.
+    pub fn publish(env: Env, publisher: Address, payload: EventPayload) -> u64 {
+        publisher.require_auth();
+        let source = Self::get_source(env.clone(), publisher.clone()).expect("not a publisher");
+
+        let sequence = Self::get_last_sequence(env.clone()) + 1;
+        env.storage()
+            .instance()
+            .set(&DataKey::LastSequence, &sequence);
+
+        let record = EventRecord {
+            sequence,
+            source: source.clone(),
+            publisher: publisher.clone(),
+            ledger: env.ledger().sequence(),
+            timestamp: env.ledger().timestamp(),
+            payload: payload.clone(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Event(sequence), &record);
+        if sequence > MAX_RETAINED {
+            env.storage()
+                .persistent()
+                .remove(&DataKey::Event(sequence - MAX_RETAINED));
+        }
+
+        events::emit_hub_event(
+            &env,
+            sequence,
+            &source,
+            &payload.kind,
+            &publisher,
+            record.timestamp,
+            &payload.actor,
+            &payload.subject_id,
+            payload.amount,
+            &payload.data,
+        );
+        sequence
+    }
+
+    /// Retained records from `from_sequence` on, oldest first, at most `limit`.
+    pub fn get_events(env: Env, from_sequence: u64, limit: u32) -> Vec<EventRecord> {
+        let last = Self::get_last_sequence(env.clone());
+        let first = from_sequence
+            .max(1)
+            .max(last.saturating_sub(MAX_RETAINED) + 1);
+        let mut records = Vec::new(&env);
+        let mut sequence = first;
+        while sequence <= last && records.len() < limit.min(MAX_PAGE_SIZE) {
+            if let Some(record) = Self::get_event(env.clone(), sequence) {
+                records.push_back(record);
+            }
+            sequence += 1;
+        }
+        records
+    }
+
+    pub fn get_event(env: Env, sequence: u64) -> Option<EventRecord> {
+        env.storage().persistent().get(&DataKey::Event(sequence))
+    }
+
+    /// Sequence number of the latest published event; zero before the first.
+    pub fn get_last_sequence(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::LastSequence)
+            .expect("not initialized")
+    }
+
+    /// Source name `publisher` is registered under, if any.
+    pub fn get_source(env: Env, publisher: Address) -> Option<Symbol> {
+        env.storage().instance().get(&DataKey::Publisher(publisher))
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,138 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Events as _},
+    vec, Address, Bytes, BytesN, Env, Event as _,
+};
+
+struct Setup<'a> {
+    env: Env,
+    escrow: Address,
+    matches: Address,
+    client: EventHubClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let escrow = Address::generate(&env);
+    let matches = Address::generate(&env);
+    let contract_id = env.register(EventHub, ());
+    let client = EventHubClient::new(&env, &contract_id);
+    client.initialize(&admin);
+    client.set_publisher(&escrow, &symbol_short!("escrow"), &true);
+    client.set_publisher(&matches, &symbol_short!("match"), &true);
+
+    Setup {
+        env,
+        escrow,
+        matches,
+        client,
+    }
+}
+
+fn payload(env: &Env, kind: Symbol, amount: Option<i128>) -> EventPayload {
+    EventPayload {
+        kind,
+        actor: Some(Address::generate(env)),
+        subject_id: Some(BytesN::from_array(env, &[1u8; 32])),
+        amount,
+        data: Bytes::new(env),
+    }
+}
+
+#[test]
+fn test_sequence_is_global_across_publishers() {
+    let s = setup();
+    assert_eq!(s.client.get_last_sequence(), 0);
+    let deposit = payload(&s.env, symbol_short!("deposit"), Some(500));
+    assert_eq!(s.client.publish(&s.escrow, &deposit), 1);
+    assert_eq!(
+        s.client
+            .publish(&s.matches, &payload(&s.env, symbol_short!("started"), None)),
+        2
+    );
+    assert_eq!(
+        s.client.publish(
+            &s.escrow,
+            &payload(&s.env, symbol_short!("release"), Some(500))
+        ),
+        3
+    );
+    assert_eq!(s.client.get_last_sequence(), 3);
+
+    let record = s.client.get_event(&1).unwrap();
+    assert_eq!(record.source, symbol_short!("escrow"));
+    assert_eq!(record.publisher, s.escrow);
+    assert_eq!(record.payload, deposit);
+}
+
+#[test]
+fn test_publish_re_emits_normalized_event() {
+    let s = setup();
+    let started = payload(&s.env, symbol_short!("started"), None);
+    s.client.publish(&s.matches, &started);
+
+    let expected = events::HubEvent {
+        sequence: 1,
+        source: symbol_short!("match"),
+        kind: started.kind,
+        publisher: s.matches.clone(),
+        timestamp: s.env.ledger().timestamp(),
+        actor: started.actor,
+        subject_id: started.subject_id,
+        amount: None,
+        data: started.data,
+    };
+    assert_eq!(
+        s.env.events().all(),
+        vec![
+            &s.env,
+            (
+                s.client.address.clone(),
+                expected.topics(&s.env),
+                expected.data(&s.env),
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_replay_from_sequence() {
+    let s = setup();
+    for _ in 0..5 {
+        s.client.publish(
+            &s.escrow,
+            &payload(&s.env, symbol_short!("deposit"), Some(1)),
+        );
+    }
+    let page = s.client.get_events(&3, &10);
+    assert_eq!(page.len(), 3);
+    assert_eq!(page.get(0).unwrap().sequence, 3);
+    assert_eq!(page.get(2).unwrap().sequence, 5);
+
+    assert_eq!(s.client.get_events(&0, &2).len(), 2);
+    assert_eq!(s.client.get_events(&6, &10).len(), 0);
+}
+
+#[test]
+fn test_unregistered_publisher_rejected() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_publish(&outsider, &payload(&s.env, symbol_short!("fake"), None))
+        .is_err());
+
+    s.client
+        .set_publisher(&s.escrow, &symbol_short!("escrow"), &false);
+    assert!(s
+        .client
+        .try_publish(&s.escrow, &payload(&s.env, symbol_short!("deposit"), None))
+        .is_err());
+    assert_eq!(s.client.get_last_sequence(), 0);
+}
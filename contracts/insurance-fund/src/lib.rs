@@ -0,0 +1,414 @@
+#![no_std]
+
+//! Insurance fund for exploit compensation.
+//!
+//! System contracts route a share of platform fees and slashes here with
+//! `contribute`. After an exploit the admin opens an incident with a payout cap;
+//! affected players file claims against it, and the council votes on each claim. A
+//! claim reaching the approval threshold is paid at once, up to what remains of the
+//! incident's cap. Every claim, vote and payout is kept on-chain as a public ledger.
+
+use arenax_events::insurance as events;
+use arenax_token_interface as token;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, BytesN, Env, String, Symbol, Vec,
+};
+
+/// Upper bound on council size.
+pub const MAX_COUNCIL_SIZE: u32 = 20;
+
+/// Upper bound on claims per incident.
+pub const MAX_CLAIMS_PER_INCIDENT: u32 = 200;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Asset,
+    Council,
+    Threshold,
+    Contributed(Symbol), // source -> lifetime contributions
+    TotalContributed,
+    TotalPaid,
+    Incident(BytesN<32>),
+    IncidentClaims(BytesN<32>), // incident_id -> Vec<claim_id>
+    NextClaimId,
+    Claim(u64),
+    Voted(u64, Address),
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ClaimState {
+    Pending = 0,
+    Paid = 1,
+    Rejected = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Incident {
+    pub incident_id: BytesN<32>,
+    pub description: String,
+    /// Maximum paid out across all claims on the incident.
+    pub cap: i128,
+    pub paid: i128,
+    pub open: bool,
+    pub opened_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub claim_id: u64,
+    pub incident_id: BytesN<32>,
+    pub claimant: Address,
+    pub amount: i128,
+    pub evidence: String,
+    pub state: u32,
+    pub approvals: u32,
+    pub rejections: u32,
+    /// Amount actually paid; may be below `amount` when the incident cap binds.
+    pub paid: i128,
+    pub filed_at: u64,
+    pub settled_at: u64,
+}
+
+#[contract]
+pub struct InsuranceFund;
+
+#[contractimpl]
+impl InsuranceFund {
+    /// The fund holds and pays out `asset` (AX).
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        asset: Address,
+        council: Vec<Address>,
+        threshold: u32,
+    ) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Asset, &asset);
+        env.storage().instance().set(&DataKey::NextClaimId, &1u64);
+        Self::write_council(&env, &council, threshold);
+    }
+
+    /// Replace the council and approval threshold (admin only).
+    pub fn set_council(env: Env, council: Vec<Address>, threshold: u32) {
+        Self::require_admin(&env);
+        Self::write_council(&env, &council, threshold);
+    }
+
+    /// Pull `amount` from `from` into the fund, recorded under `source` (e.g.
+    /// `platform`, `slash`).
+    pub fn contribute(env: Env, from: Address, amount: i128, source: Symbol) {
+        from.require_auth();
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        Self::asset(&env).transfer(&from, &env.current_contract_address(), &amount);
+
+        let key = DataKey::Contributed(source.clone());
+        let contributed: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&key, &(contributed + amount));
+        let total = Self::get_total_contributed(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalContributed, &(total + amount));
+
+        events::emit_contribution_received(&env, &from, &source, amount);
+    }
+
+    /// Open an incident that claims can reference, paying out at most `cap` in total
+    /// (admin only).
+    pub fn open_incident(env: Env, incident_id: BytesN<32>, cap: i128, description: String) {
+        Self::require_admin(&env);
+        if cap <= 0 {
+            panic!("cap must be positive");
+        }
+        let key = DataKey::Incident(incident_id.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("incident already exists");
+        }
+
+        let incident = Incident {
+            incident_id: incident_id.clone(),
+            description,
+            cap,
+            paid: 0,
+            open: true,
+            opened_at: env.ledger().timestamp(),
+        };
+        env.storage().persistent().set(&key, &incident);
+        events::emit_incident_opened(&env, &incident_id, cap);
+    }
+
+    /// Stop accepting new claims on an incident (admin only). Pending claims can
+    /// still be voted on.
+    pub fn close_incident(env: Env, incident_id: BytesN<32>) {
+        Self::require_admin(&env);
+        let mut incident = Self::get_incident(env.clone(), incident_id.clone());
+        if !incident.open {
+            panic!("incident closed");
+        }
+        incident.open = false;
+        Self::save_incident(&env, &incident);
+        events::emit_incident_closed(&env, &incident_id, incident.paid);
+    }
+
+    /// File a claim for `amount` against an open incident. Returns the claim id.
+    pub fn file_claim(
+        env: Env,
+        claimant: Address,
+        incident_id: BytesN<32>,
+        amount: i128,
+        evidence: String,
+    ) -> u64 {
+        claimant.require_auth();
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        let incident = Self::get_incident(env.clone(), incident_id.clone());
+        if !incident.open {
+            panic!("incident closed");
+        }
+        if incident.paid >= incident.cap {
+            panic!("incident cap reached");
+        }
+        let mut claims = Self::get_incident_claims(env.clone(), incident_id.clone());
+        if claims.len() >= MAX_CLAIMS_PER_INCIDENT {
+            panic!("too many claims");
+        }
+
+        let claim_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextClaimId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextClaimId, &(claim_id + 1));
+
+        let claim = Claim {
+            claim_id,
+            incident_id: incident_id.clone(),
+            claimant: claimant.clone(),
+            amount,
+            evidence,
+            state: ClaimState::Pending as u32,
+            approvals: 0,
+            rejections: 0,
+            paid: 0,
+            filed_at: env.ledger().timestamp(),
+            settled_at: 0,
+        };
+        Self::save_claim(&env, &claim);
+        claims.push_back(claim_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::IncidentClaims(incident_id.clone()), &claims);
+
+        events::emit_claim_filed(&env, claim_id, &incident_id, &claimant, amount);
+        claim_id
+    }
+
+    /// Vote on a pending claim (council members only). The claim is paid once
+    /// approvals reach the threshold, and rejected once enough members reject it
+    /// that the threshold can no longer be reached.
+    pub fn vote(env: Env, claim_id: u64, member: Address, approve: bool) {
+        member.require_auth();
+        let council = Self::get_council(env.clone());
+        if !council.contains(&member) {
+            panic!("not a council member");
+        }
+
+        let mut claim = Self::get_claim(env.clone(), claim_id);
+        if claim.state != ClaimState::Pending as u32 {
+            panic!("claim is not pending");
+        }
+        let voted_key = DataKey::Voted(claim_id, member.clone());
+        if env.storage().persistent().has(&voted_key) {
+            panic!("already voted");
+        }
+        env.storage().persistent().set(&voted_key, &approve);
+        events::emit_claim_voted(&env, claim_id, &member, approve);
+
+        let threshold = Self::get_threshold(env.clone());
+        if approve {
+            claim.approvals += 1;
+            if claim.approvals >= threshold {
+                Self::pay_claim(&env, &mut claim);
+            }
+        } else {
+            claim.rejections += 1;
+            if claim.rejections > council.len() - threshold {
+                claim.state = ClaimState::Rejected as u32;
+                claim.settled_at = env.ledger().timestamp();
+                events::emit_claim_rejected(&env, claim_id, &claim.incident_id, &claim.claimant);
+            }
+        }
+        Self::save_claim(&env, &claim);
+    }
+
+    pub fn get_claim(env: Env, claim_id: u64) -> Claim {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claim(claim_id))
+            .expect("claim not found")
+    }
+
+    pub fn get_incident(env: Env, incident_id: BytesN<32>) -> Incident {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Incident(incident_id))
+            .expect("incident not found")
+    }
+
+    /// Claims filed against `incident_id`, in filing order.
+    pub fn get_incident_claims(env: Env, incident_id: BytesN<32>) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IncidentClaims(incident_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// How `member` voted on `claim_id`, if they did.
+    pub fn get_vote(env: Env, claim_id: u64, member: Address) -> Option<bool> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Voted(claim_id, member))
+    }
+
+    /// Lifetime contributions from `source`.
+    pub fn get_contributed(env: Env, source: Symbol) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Contributed(source))
+            .unwrap_or(0)
+    }
+
+    pub fn get_total_contributed(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalContributed)
+            .unwrap_or(0)
+    }
+
+    pub fn get_total_paid(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalPaid)
+            .unwrap_or(0)
+    }
+
+    pub fn get_council(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Council)
+            .expect("not initialized")
+    }
+
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn asset(env: &Env) -> token::Client<'_> {
+        let address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Asset)
+            .expect("not initialized");
+        token::Client::new(env, &address)
+    }
+
+    fn pay_claim(env: &Env, claim: &mut Claim) {
+        let mut incident = Self::get_incident(env.clone(), claim.incident_id.clone());
+        let payout = claim.amount.min(incident.cap - incident.paid);
+        if payout <= 0 {
+            panic!("incident cap reached");
+        }
+
+        let asset = Self::asset(env);
+        if asset.balance(&env.current_contract_address()) < payout {
+            panic!("insufficient fund balance");
+        }
+
+        incident.paid += payout;
+        Self::save_incident(env, &incident);
+        claim.state = ClaimState::Paid as u32;
+        claim.paid = payout;
+        claim.settled_at = env.ledger().timestamp();
+        let total = Self::get_total_paid(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalPaid, &(total + payout));
+
+        asset.transfer(&env.current_contract_address(), &claim.claimant, &payout);
+        events::emit_claim_paid(
+            env,
+            claim.claim_id,
+            &claim.incident_id,
+            &claim.claimant,
+            claim.amount,
+            payout,
+        );
+    }
+
+    fn write_council(env: &Env, council: &Vec<Address>, threshold: u32) {
+        if council.len() > MAX_COUNCIL_SIZE {
+            panic!("council too large");
+        }
+        for i in 0..council.len() {
+            let member = council.get(i).unwrap();
+            if council.slice(i + 1..).contains(&member) {
+                panic!("duplicate council member");
+            }
+        }
+        if threshold == 0 || threshold > council.len() {
+            panic!("invalid threshold");
+        }
+
+        env.storage().instance().set(&DataKey::Council, council);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        events::emit_council_updated(env, council, threshold);
+    }
+
+    fn save_incident(env: &Env, incident: &Incident) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Incident(incident.incident_id.clone()), incident);
+    }
+
+    fn save_claim(env: &Env, claim: &Claim) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claim(claim.claim_id), claim);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,163 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, String,
+};
+
+struct Setup<'a> {
+    env: Env,
+    council: Vec<Address>,
+    client: InsuranceFundClient<'a>,
+    token: TokenClient<'a>,
+    incident: BytesN<32>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &sac.address()).mint(&admin, &100_000);
+
+    let council = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let contract_id = env.register(InsuranceFund, ());
+    let client = InsuranceFundClient::new(&env, &contract_id);
+    client.initialize(&admin, &sac.address(), &council, &2);
+    client.contribute(&admin, &6_000, &symbol_short!("platform"));
+    client.contribute(&admin, &4_000, &symbol_short!("slash"));
+
+    let incident = BytesN::from_array(&env, &[7u8; 32]);
+    client.open_incident(&incident, &5_000, &String::from_str(&env, "bridge exploit"));
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        env,
+        council,
+        client,
+        incident,
+    }
+}
+
+fn member(s: &Setup, index: u32) -> Address {
+    s.council.get(index).unwrap()
+}
+
+fn file(s: &Setup, claimant: &Address, amount: i128) -> u64 {
+    s.client.file_claim(
+        claimant,
+        &s.incident,
+        &amount,
+        &String::from_str(&s.env, "tx hashes"),
+    )
+}
+
+#[test]
+fn test_contributions_tracked_by_source() {
+    let s = setup();
+    assert_eq!(s.client.get_contributed(&symbol_short!("platform")), 6_000);
+    assert_eq!(s.client.get_contributed(&symbol_short!("slash")), 4_000);
+    assert_eq!(s.client.get_total_contributed(), 10_000);
+    assert_eq!(s.token.balance(&s.client.address), 10_000);
+}
+
+#[test]
+fn test_approved_claim_is_paid() {
+    let s = setup();
+    let claimant = Address::generate(&s.env);
+    let claim_id = file(&s, &claimant, 1_200);
+
+    s.client.vote(&claim_id, &member(&s, 0), &true);
+    assert_eq!(s.token.balance(&claimant), 0);
+    assert!(s.client.try_vote(&claim_id, &member(&s, 0), &true).is_err());
+
+    s.client.vote(&claim_id, &member(&s, 1), &true);
+    let claim = s.client.get_claim(&claim_id);
+    assert_eq!(claim.state, ClaimState::Paid as u32);
+    assert_eq!(claim.paid, 1_200);
+    assert_eq!(s.token.balance(&claimant), 1_200);
+    assert_eq!(s.client.get_incident(&s.incident).paid, 1_200);
+    assert_eq!(s.client.get_total_paid(), 1_200);
+    assert_eq!(s.client.get_vote(&claim_id, &member(&s, 1)), Some(true));
+
+    // Settled claims take no more votes.
+    assert!(s.client.try_vote(&claim_id, &member(&s, 2), &true).is_err());
+}
+
+#[test]
+fn test_incident_cap_limits_payouts() {
+    let s = setup();
+    let first = Address::generate(&s.env);
+    let second = Address::generate(&s.env);
+    let a = file(&s, &first, 4_000);
+    let b = file(&s, &second, 4_000);
+    assert_eq!(
+        s.client.get_incident_claims(&s.incident),
+        vec![&s.env, a, b]
+    );
+
+    for claim_id in [a, b] {
+        s.client.vote(&claim_id, &member(&s, 0), &true);
+        s.client.vote(&claim_id, &member(&s, 1), &true);
+    }
+    assert_eq!(s.client.get_claim(&b).paid, 1_000);
+    assert_eq!(s.token.balance(&second), 1_000);
+
+    // The cap is exhausted, so no new claims.
+    assert!(s
+        .client
+        .try_file_claim(&first, &s.incident, &1, &String::from_str(&s.env, ""))
+        .is_err());
+}
+
+#[test]
+fn test_rejected_claim() {
+    let s = setup();
+    let claimant = Address::generate(&s.env);
+    let claim_id = file(&s, &claimant, 500);
+
+    s.client.vote(&claim_id, &member(&s, 0), &false);
+    assert_eq!(
+        s.client.get_claim(&claim_id).state,
+        ClaimState::Pending as u32
+    );
+    s.client.vote(&claim_id, &member(&s, 1), &false);
+    assert_eq!(
+        s.client.get_claim(&claim_id).state,
+        ClaimState::Rejected as u32
+    );
+    assert_eq!(s.token.balance(&claimant), 0);
+}
+
+#[test]
+fn test_claim_rules() {
+    let s = setup();
+    let claimant = Address::generate(&s.env);
+    let claim_id = file(&s, &claimant, 500);
+    assert!(s.client.try_vote(&claim_id, &claimant, &true).is_err());
+
+    let unknown = BytesN::from_array(&s.env, &[1u8; 32]);
+    assert!(s
+        .client
+        .try_file_claim(&claimant, &unknown, &500, &String::from_str(&s.env, ""))
+        .is_err());
+
+    s.client.close_incident(&s.incident);
+    assert!(s
+        .client
+        .try_file_claim(&claimant, &s.incident, &500, &String::from_str(&s.env, ""))
+        .is_err());
+    // Claims filed before closing can still be settled.
+    s.client.vote(&claim_id, &member(&s, 0), &true);
+    s.client.vote(&claim_id, &member(&s, 2), &true);
+    assert_eq!(s.token.balance(&claimant), 500);
+}
@@ -0,0 +1,257 @@
+#![no_std]
+
+//! Canonical on-chain leaderboard.
+//!
+//! Authorized publishers (the match, prize distribution and reputation contracts)
+//! push score updates per category: match wins, prize earnings and reputation. Each
+//! update is applied to the all-time board (season 0) and the current season's board.
+//! Boards keep only the top `board_size` players, highest score first; full scores
+//! are stored for every player, so a player outside a board still has a score and
+//! re-enters the board once it beats the lowest entry.
+
+use arenax_events::leaderboard as events;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+
+/// Season id of the all-time boards.
+pub const ALL_TIME: u32 = 0;
+
+/// Upper bound on entries kept per board.
+pub const MAX_BOARD_SIZE: u32 = 100;
+
+/// Upper bound on entries returned by one `get_board` call.
+pub const MAX_PAGE_SIZE: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Publisher(Address),
+    CurrentSeason,
+    BoardSize,
+    Board(u32, u32),          // (category, season) -> Vec<LeaderboardEntry>
+    Score(u32, u32, Address), // (category, season, player) -> i128
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Category {
+    Wins = 0,
+    Earnings = 1,
+    Reputation = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LeaderboardEntry {
+    pub player: Address,
+    pub score: i128,
+}
+
+#[contract]
+pub struct LeaderboardContract;
+
+#[contractimpl]
+impl LeaderboardContract {
+    /// Starts in season 1 with boards of `board_size` entries.
+    pub fn initialize(env: Env, admin: Address, board_size: u32) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        Self::check_board_size(board_size);
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::BoardSize, &board_size);
+        env.storage().instance().set(&DataKey::CurrentSeason, &1u32);
+    }
+
+    /// Allow or disallow `publisher` to push scores (admin only).
+    pub fn set_publisher(env: Env, publisher: Address, enabled: bool) {
+        Self::require_admin(&env);
+        let key = DataKey::Publisher(publisher.clone());
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        events::emit_publisher_updated(&env, &publisher, enabled);
+    }
+
+    /// Change how many entries boards keep (admin only). Shrinking trims boards as
+    /// they are next updated.
+    pub fn set_board_size(env: Env, board_size: u32) {
+        Self::require_admin(&env);
+        Self::check_board_size(board_size);
+        env.storage()
+            .instance()
+            .set(&DataKey::BoardSize, &board_size);
+    }
+
+    /// Start a new season (admin only). Later updates go to its boards; earlier
+    /// seasons stay readable.
+    pub fn start_season(env: Env, season: u32) {
+        Self::require_admin(&env);
+        if season <= Self::get_current_season(env.clone()) {
+            panic!("season must increase");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::CurrentSeason, &season);
+        events::emit_season_started(&env, season);
+    }
+
+    /// Add `delta` to `player`'s score in `category`, for both the all-time and the
+    /// current season board (publishers only). Used for wins and earnings.
+    pub fn add_score(env: Env, publisher: Address, category: u32, player: Address, delta: i128) {
+        Self::require_publisher(&env, &publisher);
+        Self::check_category(category);
+        if delta <= 0 {
+            panic!("delta must be positive");
+        }
+        for season in [ALL_TIME, Self::get_current_season(env.clone())] {
+            let score = Self::get_score(env.clone(), category, season, player.clone()) + delta;
+            Self::write_score(&env, category, season, &player, score, &publisher);
+        }
+    }
+
+    /// Set `player`'s score in `category` outright, for both the all-time and the
+    /// current season board (publishers only). Used for reputation.
+    pub fn set_score(env: Env, publisher: Address, category: u32, player: Address, score: i128) {
+        Self::require_publisher(&env, &publisher);
+        Self::check_category(category);
+        for season in [ALL_TIME, Self::get_current_season(env.clone())] {
+            Self::write_score(&env, category, season, &player, score, &publisher);
+        }
+    }
+
+    /// Up to `limit` entries of a board starting at rank `offset + 1`.
+    pub fn get_board(
+        env: Env,
+        category: u32,
+        season: u32,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<LeaderboardEntry> {
+        let board = Self::board(&env, category, season);
+        let start = offset.min(board.len());
+        let end = (start + limit.min(MAX_PAGE_SIZE)).min(board.len());
+        board.slice(start..end)
+    }
+
+    pub fn get_score(env: Env, category: u32, season: u32, player: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Score(category, season, player))
+            .unwrap_or(0)
+    }
+
+    /// 1-based rank of `player` on a board, if they are on it.
+    pub fn get_rank(env: Env, category: u32, season: u32, player: Address) -> Option<u32> {
+        Self::board(&env, category, season)
+            .iter()
+            .position(|entry| entry.player == player)
+            .map(|index| index as u32 + 1)
+    }
+
+    pub fn get_current_season(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CurrentSeason)
+            .expect("not initialized")
+    }
+
+    pub fn get_board_size(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::BoardSize)
+            .expect("not initialized")
+    }
+
+    pub fn is_publisher(env: Env, publisher: Address) -> bool {
+        env.storage().instance().has(&DataKey::Publisher(publisher))
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn require_publisher(env: &Env, publisher: &Address) {
+        publisher.require_auth();
+        if !Self::is_publisher(env.clone(), publisher.clone()) {
+            panic!("not a publisher");
+        }
+    }
+
+    fn check_category(category: u32) {
+        if category > Category::Reputation as u32 {
+            panic!("invalid category");
+        }
+    }
+
+    fn check_board_size(board_size: u32) {
+        if board_size == 0 || board_size > MAX_BOARD_SIZE {
+            panic!("invalid board size");
+        }
+    }
+
+    fn board(env: &Env, category: u32, season: u32) -> Vec<LeaderboardEntry> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Board(category, season))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn write_score(
+        env: &Env,
+        category: u32,
+        season: u32,
+        player: &Address,
+        score: i128,
+        publisher: &Address,
+    ) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Score(category, season, player.clone()), &score);
+
+        let mut board = Self::board(env, category, season);
+        if let Some(index) = board.iter().position(|entry| &entry.player == player) {
+            board.remove(index as u32);
+        }
+        // Ties keep the player who got there first ahead.
+        let index = board
+            .iter()
+            .position(|entry| entry.score < score)
+            .map(|index| index as u32)
+            .unwrap_or(board.len());
+        let board_size = Self::get_board_size(env.clone());
+        if index < board_size {
+            board.insert(
+                index,
+                LeaderboardEntry {
+                    player: player.clone(),
+                    score,
+                },
+            );
+        }
+        while board.len() > board_size {
+            board.pop_back();
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Board(category, season), &board);
+
+        events::emit_score_updated(env, category, season, player, score, publisher);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,116 @@
+#![cfg(test)]
+
+extern crate std;
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, Address, Env};
+
+const WINS: u32 = Category::Wins as u32;
+const REPUTATION: u32 = Category::Reputation as u32;
+
+struct Setup<'a> {
+    env: Env,
+    publisher: Address,
+    client: LeaderboardContractClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let publisher = Address::generate(&env);
+    let contract_id = env.register(LeaderboardContract, ());
+    let client = LeaderboardContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &3);
+    client.set_publisher(&publisher, &true);
+
+    Setup {
+        env,
+        publisher,
+        client,
+    }
+}
+
+fn players(s: &Setup, count: usize) -> std::vec::Vec<Address> {
+    (0..count).map(|_| Address::generate(&s.env)).collect()
+}
+
+#[test]
+fn test_board_keeps_top_n_in_order() {
+    let s = setup();
+    let p = players(&s, 4);
+    for (player, wins) in p.iter().zip([5, 9, 2, 7]) {
+        s.client.add_score(&s.publisher, &WINS, player, &wins);
+    }
+
+    let board = s.client.get_board(&WINS, &ALL_TIME, &0, &10);
+    assert_eq!(board.len(), 3);
+    assert_eq!(board.get(0).unwrap().player, p[1]);
+    assert_eq!(board.get(1).unwrap().player, p[3]);
+    assert_eq!(board.get(2).unwrap().player, p[0]);
+    assert_eq!(s.client.get_rank(&WINS, &ALL_TIME, &p[2]), None);
+
+    // A player outside the board re-enters once they beat the lowest entry.
+    s.client.add_score(&s.publisher, &WINS, &p[2], &8);
+    assert_eq!(s.client.get_score(&WINS, &ALL_TIME, &p[2]), 10);
+    assert_eq!(s.client.get_rank(&WINS, &ALL_TIME, &p[2]), Some(1));
+    assert_eq!(s.client.get_rank(&WINS, &ALL_TIME, &p[0]), None);
+}
+
+#[test]
+fn test_pagination() {
+    let s = setup();
+    let p = players(&s, 3);
+    for (player, wins) in p.iter().zip([3, 2, 1]) {
+        s.client.add_score(&s.publisher, &WINS, player, &wins);
+    }
+
+    let page = s.client.get_board(&WINS, &ALL_TIME, &1, &1);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().player, p[1]);
+    assert_eq!(s.client.get_board(&WINS, &ALL_TIME, &2, &5).len(), 1);
+    assert!(s.client.get_board(&WINS, &ALL_TIME, &9, &5).is_empty());
+}
+
+#[test]
+fn test_seasons_track_separately_from_all_time() {
+    let s = setup();
+    let player = Address::generate(&s.env);
+    s.client.add_score(&s.publisher, &WINS, &player, &4);
+
+    s.client.start_season(&2);
+    s.client.add_score(&s.publisher, &WINS, &player, &1);
+    assert_eq!(s.client.get_score(&WINS, &ALL_TIME, &player), 5);
+    assert_eq!(s.client.get_score(&WINS, &1, &player), 4);
+    assert_eq!(s.client.get_score(&WINS, &2, &player), 1);
+    assert!(s.client.try_start_season(&2).is_err());
+}
+
+#[test]
+fn test_set_score_can_lower_rank() {
+    let s = setup();
+    let p = players(&s, 2);
+    s.client.set_score(&s.publisher, &REPUTATION, &p[0], &900);
+    s.client.set_score(&s.publisher, &REPUTATION, &p[1], &500);
+    s.client.set_score(&s.publisher, &REPUTATION, &p[0], &100);
+
+    let board = s.client.get_board(&REPUTATION, &1, &0, &10);
+    assert_eq!(board.get(0).unwrap().player, p[1]);
+    assert_eq!(board.get(1).unwrap().score, 100);
+}
+
+#[test]
+fn test_only_publishers_push_scores() {
+    let s = setup();
+    let outsider = Address::generate(&s.env);
+    let player = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_add_score(&outsider, &WINS, &player, &1)
+        .is_err());
+    assert!(s
+        .client
+        .try_add_score(&s.publisher, &7, &player, &1)
+        .is_err());
+}
@@ -5,6 +5,9 @@
 //! Manages creation, participation, result submission, and finalization of matches
 //! with strict state transitions and authorization. Supports dual-reporting:
 //! two participants must submit matching results before a match can be finalized.
+//!
+//! Superseded by `match_contract`, which now implements the same dual-reporting flow.
+//! Existing records can be moved over with `MatchContract::import_legacy_matches`.
 
 use arenax_events::match_lifecycle as events;
 use soroban_sdk::{
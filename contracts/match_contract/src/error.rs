@@ -0,0 +1,25 @@
+use soroban_sdk::contracterror;
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum MatchError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    NotAdmin = 3,
+    Unauthorized = 4,
+    MatchNotFound = 5,
+    MatchAlreadyExists = 6,
+    InvalidStateTransition = 7,
+    InvalidWinner = 8,
+    ContractPaused = 9,
+    NotParticipant = 10,
+    DuplicateReport = 11,
+    UnsupportedGame = 12,
+    UnsupportedMode = 13,
+    InvalidGameConfig = 14,
+    GameNotFound = 15,
+    InvalidLegacyRecord = 16,
+    NotReferee = 17,
+    InvalidOutcome = 18,
+}
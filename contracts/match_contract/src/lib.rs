@@ -1,12 +1,41 @@
 #![no_std]
+
+mod error;
+
 use arenax_events::match_contract as events;
-use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal};
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum DataKey {
     Match(BytesN<32>),
     PauseContract,
+    Admin,
+    IdentityContract,
+    TournamentContract,
+    Creator(Address), // contracts allowed to create matches, e.g. matchmaking
+    Game(Symbol), // game_id -> GameConfig
+    Games,        // Vec<Symbol> of registered game ids
+    PlayerMatchCount(Address),
+    PlayerMatch(Address, u32), // (player, n) -> match_id, append-only
+    StateMatchCount(u32),
+    StateMatch(u32, u32),      // (state, slot) -> match_id, swap-removed on transition
+    StateSlot(BytesN<32>),     // match_id -> slot in its current state index
+}
+
+/// Upper bound on `limit` for paginated queries.
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Per-game settings for a supported game title.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GameConfig {
+    pub max_score: u32,
+    pub round_count: u32,
+    /// Allowed match modes; empty means any mode is accepted.
+    pub modes: Vec<Symbol>,
 }
 
 #[contracttype]
@@ -18,6 +47,22 @@ pub enum MatchState {
     Completed = 2,
     Disputed = 3,
     Cancelled = 4,
+    /// Both players reported the same winner; awaiting `finalize_match`.
+    PendingResult = 5,
+}
+
+/// Outcome applied to a disputed match by `settle_dispute`.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DisputeOutcome {
+    AwardPlayerA = 0,
+    AwardPlayerB = 1,
+    /// Completed without a winner.
+    Split = 2,
+    /// Back to `Created` so the match can be played again.
+    Replay = 3,
+    Void = 4,
 }
 
 #[contracttype]
@@ -29,19 +74,284 @@ pub struct MatchData {
     pub winner: Option<Address>,
     pub started_at: u64,
     pub ended_at: Option<u64>,
+    /// Winner as reported by player_a / player_b (dual reporting).
+    pub report_a: Option<Address>,
+    pub report_b: Option<Address>,
+    pub game_id: Symbol,
+    pub region: Symbol,
+    pub mode: Symbol,
 }
 
+/// Match record as stored by the legacy `match-lifecycle` contract, accepted by
+/// `import_legacy_matches`. Scores are player indices into `players`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LegacyMatchData {
+    pub players: Vec<Address>,
+    pub stake_asset: Address,
+    pub stake_amount: i128,
+    pub state: u32,
+    pub created_at: u64,
+    pub report1_reporter: Option<Address>,
+    pub report1_score: Option<i64>,
+    pub report2_reporter: Option<Address>,
+    pub report2_score: Option<i64>,
+    pub winner: Option<Address>,
+    pub finalized_at: Option<u64>,
+}
+
+pub use error::MatchError;
+
 #[contract]
 pub struct MatchContract;
 
 #[contractimpl]
 impl MatchContract {
-    pub fn set_pause_contract(env: Env, admin: Address, pause_contract: Address) {
+    /// Initialize the contract with an admin. The admin is always an operator.
+    pub fn initialize(env: Env, admin: Address) -> Result<(), MatchError> {
+        if env.storage().instance().has(&DataKey::Admin) {
+            return Err(MatchError::AlreadyInitialized);
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        Ok(())
+    }
+
+    /// Set the Identity Contract used to recognise Referee/Admin roles as operators (admin only).
+    pub fn set_identity_contract(env: Env, identity_contract: Address) -> Result<(), MatchError> {
+        Self::require_admin(&env)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::IdentityContract, &identity_contract);
+        Ok(())
+    }
+
+    /// Set the tournament contract allowed to drive transitions for its matches (admin only).
+    pub fn set_tournament_contract(env: Env, tournament_contract: Address) -> Result<(), MatchError> {
+        Self::require_admin(&env)?;
+        env.storage()
+            .instance()
+            .set(&DataKey::TournamentContract, &tournament_contract);
+        Ok(())
+    }
+
+    /// Allow or revoke a contract (e.g. matchmaking, challenges) to create matches (admin only).
+    pub fn set_creator(env: Env, creator: Address, allowed: bool) -> Result<(), MatchError> {
+        Self::require_admin(&env)?;
+        let key = DataKey::Creator(creator);
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        Ok(())
+    }
+
+    pub fn is_creator(env: Env, creator: Address) -> bool {
+        env.storage().instance().has(&DataKey::Creator(creator))
+    }
+
+    pub fn set_pause_contract(
+        env: Env,
+        admin: Address,
+        pause_contract: Address,
+    ) -> Result<(), MatchError> {
+        let saved_admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(MatchError::NotInitialized)?;
+        if admin != saved_admin {
+            return Err(MatchError::NotAdmin);
+        }
         admin.require_auth();
         env.storage().instance().set(&DataKey::PauseContract, &pause_contract);
+        Ok(())
+    }
+
+    fn require_admin(env: &Env) -> Result<(), MatchError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(MatchError::NotInitialized)?;
+        admin.require_auth();
+        Ok(())
+    }
+
+    /// Persist a match and keep the player and state indexes in sync.
+    fn save_match(env: &Env, match_id: &BytesN<32>, match_data: &MatchData) {
+        let key = DataKey::Match(match_id.clone());
+        let previous: Option<MatchData> = env.storage().persistent().get(&key);
+        env.storage().persistent().set(&key, match_data);
+
+        match previous {
+            None => {
+                Self::index_player(env, &match_data.player_a, match_id);
+                Self::index_player(env, &match_data.player_b, match_id);
+                Self::index_state_add(env, match_data.state, match_id);
+            }
+            Some(prev) if prev.state != match_data.state => {
+                Self::index_state_remove(env, prev.state, match_id);
+                Self::index_state_add(env, match_data.state, match_id);
+            }
+            _ => {}
+        }
+    }
+
+    fn index_player(env: &Env, player: &Address, match_id: &BytesN<32>) {
+        let count_key = DataKey::PlayerMatchCount(player.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::PlayerMatch(player.clone(), count), match_id);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    fn index_state_add(env: &Env, state: u32, match_id: &BytesN<32>) {
+        let count_key = DataKey::StateMatchCount(state);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StateMatch(state, count), match_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::StateSlot(match_id.clone()), &count);
+        env.storage().persistent().set(&count_key, &(count + 1));
+    }
+
+    fn index_state_remove(env: &Env, state: u32, match_id: &BytesN<32>) {
+        let count_key = DataKey::StateMatchCount(state);
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        let slot: u32 = match env
+            .storage()
+            .persistent()
+            .get(&DataKey::StateSlot(match_id.clone()))
+        {
+            Some(slot) => slot,
+            None => return,
+        };
+        let last = count - 1;
+        if slot != last {
+            // Move the last entry into the vacated slot.
+            let moved: BytesN<32> = env
+                .storage()
+                .persistent()
+                .get(&DataKey::StateMatch(state, last))
+                .unwrap();
+            env.storage()
+                .persistent()
+                .set(&DataKey::StateMatch(state, slot), &moved);
+            env.storage()
+                .persistent()
+                .set(&DataKey::StateSlot(moved), &slot);
+        }
+        env.storage()
+            .persistent()
+            .remove(&DataKey::StateMatch(state, last));
+        env.storage().persistent().set(&count_key, &last);
+    }
+
+    fn load_match(env: &Env, match_id: &BytesN<32>) -> Result<MatchData, MatchError> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Match(match_id.clone()))
+            .ok_or(MatchError::MatchNotFound)
+    }
+
+    /// Operators are the admin and, if an identity contract is configured, Referees (1) and Admins (2).
+    fn is_operator(env: &Env, addr: &Address) -> Result<bool, MatchError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(MatchError::NotInitialized)?;
+        if addr == &admin {
+            return Ok(true);
+        }
+        if let Some(identity_contract) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::IdentityContract)
+        {
+            let role: u32 = env.invoke_contract(
+                &identity_contract,
+                &soroban_sdk::Symbol::new(env, "get_role"),
+                (addr.clone(),).into_val(env),
+            );
+            return Ok(role == 1 || role == 2);
+        }
+        Ok(false)
+    }
+
+    /// Player-driven transitions (start, dispute, finalize) may be made by a participant,
+    /// an operator, or the configured tournament contract.
+    fn require_participant_auth(
+        env: &Env,
+        match_data: &MatchData,
+        caller: &Address,
+    ) -> Result<(), MatchError> {
+        if caller == &match_data.player_a || caller == &match_data.player_b {
+            caller.require_auth();
+            return Ok(());
+        }
+        Self::require_operator_auth(env, caller)
+    }
+
+    /// Transitions that decide or abandon a match may only be made by an operator or the
+    /// configured tournament contract.
+    fn require_operator_auth(env: &Env, caller: &Address) -> Result<(), MatchError> {
+        caller.require_auth();
+        if let Some(tournament) = env
+            .storage()
+            .instance()
+            .get::<DataKey, Address>(&DataKey::TournamentContract)
+        {
+            if caller == &tournament {
+                return Ok(());
+            }
+        }
+        if !Self::is_operator(env, caller)? {
+            return Err(MatchError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    /// Matches may be created by registered creators, operators, or the configured
+    /// tournament contract.
+    fn require_creator_auth(env: &Env, caller: &Address) -> Result<(), MatchError> {
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::Creator(caller.clone()))
+        {
+            caller.require_auth();
+            return Ok(());
+        }
+        Self::require_operator_auth(env, caller)
+    }
+
+    /// Referee (1) or Admin (2) role via the configured identity contract's
+    /// `get_role(Address) -> u32`.
+    fn require_referee(env: &Env, resolver: &Address) -> Result<(), MatchError> {
+        let identity_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::IdentityContract)
+            .ok_or(MatchError::NotReferee)?;
+        let role: u32 = env.invoke_contract(
+            &identity_contract,
+            &soroban_sdk::Symbol::new(env, "get_role"),
+            (resolver.clone(),).into_val(env),
+        );
+
+        if role != 1 && role != 2 {
+            return Err(MatchError::NotReferee);
+        }
+        Ok(())
     }
 
-    fn check_pause(env: &Env) {
+    fn check_pause(env: &Env) -> Result<(), MatchError> {
         if let Some(pause_contract) = env.storage().instance().get::<_, Address>(&DataKey::PauseContract) {
             let is_paused: bool = env.invoke_contract(
                 &pause_contract,
@@ -49,20 +359,95 @@ impl MatchContract {
                 (env.current_contract_address(), Option::<soroban_sdk::Symbol>::None).into_val(env),
             );
             if is_paused {
-                panic!("contract execution is paused");
+                return Err(MatchError::ContractPaused);
             }
         }
+        Ok(())
     }
 
-    pub fn create_match(env: Env, match_id: BytesN<32>, player_a: Address, player_b: Address) {
-        Self::check_pause(&env);
+    /// Register or update a supported game (admin only).
+    pub fn register_game(env: Env, game_id: Symbol, config: GameConfig) -> Result<(), MatchError> {
+        Self::require_admin(&env)?;
+        if config.max_score == 0 || config.round_count == 0 {
+            return Err(MatchError::InvalidGameConfig);
+        }
+        let mut games = Self::get_games(env.clone());
+        if !games.contains(&game_id) {
+            games.push_back(game_id.clone());
+            env.storage().instance().set(&DataKey::Games, &games);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Game(game_id.clone()), &config);
+        events::emit_game_registered(&env, &game_id, config.max_score, config.round_count);
+        Ok(())
+    }
+
+    /// Remove a game from the registry (admin only). Existing matches are unaffected.
+    pub fn remove_game(env: Env, game_id: Symbol) -> Result<(), MatchError> {
+        Self::require_admin(&env)?;
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::Game(game_id.clone()))
+        {
+            return Err(MatchError::GameNotFound);
+        }
+        env.storage().instance().remove(&DataKey::Game(game_id.clone()));
+        let games = Self::get_games(env.clone());
+        let mut remaining = Vec::new(&env);
+        for g in games.iter() {
+            if g != game_id {
+                remaining.push_back(g);
+            }
+        }
+        env.storage().instance().set(&DataKey::Games, &remaining);
+        events::emit_game_removed(&env, &game_id);
+        Ok(())
+    }
+
+    pub fn get_game(env: Env, game_id: Symbol) -> Option<GameConfig> {
+        env.storage().instance().get(&DataKey::Game(game_id))
+    }
+
+    pub fn get_games(env: Env) -> Vec<Symbol> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Games)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Create a match between two players. `caller` must be a registered creator, an
+    /// operator, or the configured tournament contract.
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_match(
+        env: Env,
+        match_id: BytesN<32>,
+        player_a: Address,
+        player_b: Address,
+        game_id: Symbol,
+        region: Symbol,
+        mode: Symbol,
+        caller: Address,
+    ) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        Self::require_creator_auth(&env, &caller)?;
 
         if env
             .storage()
             .persistent()
             .has(&DataKey::Match(match_id.clone()))
         {
-            panic!("match already exists");
+            return Err(MatchError::MatchAlreadyExists);
+        }
+
+        let game: GameConfig = env
+            .storage()
+            .instance()
+            .get(&DataKey::Game(game_id.clone()))
+            .ok_or(MatchError::UnsupportedGame)?;
+        if !game.modes.is_empty() && !game.modes.contains(&mode) {
+            return Err(MatchError::UnsupportedMode);
         }
 
         let match_data = MatchData {
@@ -72,158 +457,429 @@ impl MatchContract {
             winner: None,
             started_at: 0,
             ended_at: None,
+            report_a: None,
+            report_b: None,
+            game_id,
+            region,
+            mode,
         };
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Match(match_id.clone()), &match_data);
+        Self::save_match(&env, &match_id, &match_data);
 
         events::emit_match_created(&env, &match_id, &match_data.player_a, &match_data.player_b);
+        events::emit_match_metadata(
+            &env,
+            &match_id,
+            &match_data.game_id,
+            &match_data.region,
+            &match_data.mode,
+        );
+        Ok(())
     }
 
-    pub fn start_match(env: Env, match_id: BytesN<32>) {
-        Self::check_pause(&env);
-        let mut match_data: MatchData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Match(match_id.clone()))
-            .expect("match not found");
+    pub fn start_match(env: Env, match_id: BytesN<32>, caller: Address) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        let mut match_data = Self::load_match(&env, &match_id)?;
+        Self::require_participant_auth(&env, &match_data, &caller)?;
 
         if match_data.state != MatchState::Created as u32 {
-            panic!("invalid state transition");
+            return Err(MatchError::InvalidStateTransition);
         }
 
         match_data.state = MatchState::Started as u32;
         match_data.started_at = env.ledger().timestamp();
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Match(match_id.clone()), &match_data);
+        Self::save_match(&env, &match_id, &match_data);
 
         events::emit_match_started(&env, &match_id, match_data.started_at);
+        Ok(())
     }
 
-    pub fn complete_match(env: Env, match_id: BytesN<32>, winner: Address) {
-        Self::check_pause(&env);
-        let mut match_data: MatchData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Match(match_id.clone()))
-            .expect("match not found");
+    /// Record the winner of a started match directly, bypassing dual reporting. Operators
+    /// and the tournament contract only; players report through `submit_result`.
+    pub fn complete_match(env: Env, match_id: BytesN<32>, winner: Address, caller: Address) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        let mut match_data = Self::load_match(&env, &match_id)?;
+        Self::require_operator_auth(&env, &caller)?;
 
         if match_data.state != MatchState::Started as u32 {
-            panic!("invalid state transition");
+            return Err(MatchError::InvalidStateTransition);
         }
 
         if winner != match_data.player_a && winner != match_data.player_b {
-            panic!("winner must be one of the players");
+            return Err(MatchError::InvalidWinner);
         }
 
         match_data.state = MatchState::Completed as u32;
         match_data.winner = Some(winner.clone());
         match_data.ended_at = Some(env.ledger().timestamp());
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Match(match_id.clone()), &match_data);
+        Self::save_match(&env, &match_id, &match_data);
 
         events::emit_match_completed(&env, &match_id, &winner);
+        Ok(())
     }
 
-    pub fn raise_dispute(env: Env, match_id: BytesN<32>) {
-        Self::check_pause(&env);
-        let mut match_data: MatchData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Match(match_id.clone()))
-            .expect("match not found");
+    pub fn raise_dispute(env: Env, match_id: BytesN<32>, caller: Address) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        let mut match_data = Self::load_match(&env, &match_id)?;
+        Self::require_participant_auth(&env, &match_data, &caller)?;
 
-        if match_data.state != MatchState::Started as u32 {
-            panic!("invalid state transition");
+        if match_data.state != MatchState::Started as u32
+            && match_data.state != MatchState::PendingResult as u32
+        {
+            return Err(MatchError::InvalidStateTransition);
         }
 
         match_data.state = MatchState::Disputed as u32;
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Match(match_id.clone()), &match_data);
+        Self::save_match(&env, &match_id, &match_data);
 
         events::emit_match_disputed(&env, &match_id);
+        Ok(())
     }
 
-    pub fn cancel_match(env: Env, match_id: BytesN<32>) {
-        Self::check_pause(&env);
-        let mut match_data: MatchData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Match(match_id.clone()))
-            .expect("match not found");
+    /// Report the winner of a started match. Each player reports once. When both reports
+    /// agree the match moves to PendingResult; if they disagree it moves to Disputed.
+    pub fn submit_result(env: Env, match_id: BytesN<32>, reporter: Address, winner: Address) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        reporter.require_auth();
+
+        let mut match_data = Self::load_match(&env, &match_id)?;
+
+        if match_data.state != MatchState::Started as u32 {
+            return Err(MatchError::InvalidStateTransition);
+        }
+        if winner != match_data.player_a && winner != match_data.player_b {
+            return Err(MatchError::InvalidWinner);
+        }
+
+        if reporter == match_data.player_a {
+            if match_data.report_a.is_some() {
+                return Err(MatchError::DuplicateReport);
+            }
+            match_data.report_a = Some(winner.clone());
+        } else if reporter == match_data.player_b {
+            if match_data.report_b.is_some() {
+                return Err(MatchError::DuplicateReport);
+            }
+            match_data.report_b = Some(winner.clone());
+        } else {
+            return Err(MatchError::NotParticipant);
+        }
+
+        let mismatch = match (&match_data.report_a, &match_data.report_b) {
+            (Some(a), Some(b)) => {
+                if a == b {
+                    match_data.state = MatchState::PendingResult as u32;
+                    false
+                } else {
+                    match_data.state = MatchState::Disputed as u32;
+                    true
+                }
+            }
+            _ => false,
+        };
+
+        Self::save_match(&env, &match_id, &match_data);
+
+        events::emit_result_submitted(&env, &match_id, &reporter, &winner);
+        if mismatch {
+            events::emit_match_disputed(&env, &match_id);
+        }
+        Ok(())
+    }
+
+    /// Finalize a match whose dual reports agree (PendingResult -> Completed).
+    pub fn finalize_match(env: Env, match_id: BytesN<32>, caller: Address) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        let mut match_data = Self::load_match(&env, &match_id)?;
+        Self::require_participant_auth(&env, &match_data, &caller)?;
+
+        if match_data.state != MatchState::PendingResult as u32 {
+            return Err(MatchError::InvalidStateTransition);
+        }
+
+        let winner = match_data
+            .report_a
+            .clone()
+            .ok_or(MatchError::InvalidStateTransition)?;
+        match_data.state = MatchState::Completed as u32;
+        match_data.winner = Some(winner.clone());
+        match_data.ended_at = Some(env.ledger().timestamp());
+
+        Self::save_match(&env, &match_id, &match_data);
+
+        events::emit_match_completed(&env, &match_id, &winner);
+        Ok(())
+    }
+
+    /// Import match records from the legacy match-lifecycle contract (admin only).
+    /// Only two-player matches can be imported. Stakes are tracked by the escrow vault
+    /// and are not carried over; game metadata is recorded as `legacy`.
+    pub fn import_legacy_matches(env: Env, records: Vec<(BytesN<32>, LegacyMatchData)>) -> Result<(), MatchError> {
+        Self::require_admin(&env)?;
+
+        for (match_id, legacy) in records.iter() {
+            if env
+                .storage()
+                .persistent()
+                .has(&DataKey::Match(match_id.clone()))
+            {
+                return Err(MatchError::MatchAlreadyExists);
+            }
+            let match_data = Self::from_legacy(&legacy)?;
+            Self::save_match(&env, &match_id, &match_data);
+            events::emit_match_imported(&env, &match_id, match_data.state);
+        }
+        Ok(())
+    }
+
+    fn from_legacy(legacy: &LegacyMatchData) -> Result<MatchData, MatchError> {
+        if legacy.players.len() != 2 {
+            return Err(MatchError::InvalidLegacyRecord);
+        }
+        let player_a = legacy.players.get(0).unwrap();
+        let player_b = legacy.players.get(1).unwrap();
+
+        // match-lifecycle states: Created, InProgress, PendingResult, Finalized, Disputed
+        let state = match legacy.state {
+            0 => MatchState::Created,
+            1 => MatchState::Started,
+            2 => MatchState::PendingResult,
+            3 => MatchState::Completed,
+            4 => MatchState::Disputed,
+            _ => return Err(MatchError::InvalidLegacyRecord),
+        };
+
+        let mut report_a = None;
+        let mut report_b = None;
+        for (reporter, score) in [
+            (&legacy.report1_reporter, legacy.report1_score),
+            (&legacy.report2_reporter, legacy.report2_score),
+        ] {
+            if let (Some(reporter), Some(score)) = (reporter, score) {
+                let reported = match score {
+                    0 => player_a.clone(),
+                    1 => player_b.clone(),
+                    _ => return Err(MatchError::InvalidLegacyRecord),
+                };
+                if *reporter == player_a {
+                    report_a = Some(reported);
+                } else if *reporter == player_b {
+                    report_b = Some(reported);
+                }
+            }
+        }
+
+        Ok(MatchData {
+            player_a,
+            player_b,
+            state: state as u32,
+            winner: legacy.winner.clone(),
+            started_at: legacy.created_at,
+            ended_at: legacy.finalized_at,
+            report_a,
+            report_b,
+            game_id: symbol_short!("legacy"),
+            region: symbol_short!("legacy"),
+            mode: symbol_short!("legacy"),
+        })
+    }
+
+    pub fn cancel_match(env: Env, match_id: BytesN<32>, caller: Address) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        let mut match_data = Self::load_match(&env, &match_id)?;
+        Self::require_operator_auth(&env, &caller)?;
 
         if match_data.state != MatchState::Created as u32 {
-            panic!("invalid state transition");
+            return Err(MatchError::InvalidStateTransition);
         }
 
         match_data.state = MatchState::Cancelled as u32;
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Match(match_id.clone()), &match_data);
+        Self::save_match(&env, &match_id, &match_data);
 
         events::emit_match_cancelled(&env, &match_id);
+        Ok(())
     }
 
+    /// Award a disputed match to `winner`. The resolver's role is checked against the
+    /// configured identity contract.
     pub fn resolve_dispute(
         env: Env,
         match_id: BytesN<32>,
         winner: Address,
-        identity_contract: Address,
         resolver: Address,
-    ) {
-        Self::check_pause(&env);
+    ) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
         resolver.require_auth();
+        Self::require_referee(&env, &resolver)?;
 
-        // Check if resolver is Referee (1) or Admin (2) via identity contract
-        // We'll use get_role(Address) -> u32
-        let role: u32 = env.invoke_contract(
-            &identity_contract,
-            &soroban_sdk::Symbol::new(&env, "get_role"),
-            (resolver,).into_val(&env),
-        );
-
-        if role != 1 && role != 2 {
-            panic!("only referee or admin can resolve disputes");
-        }
-
-        let mut match_data: MatchData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Match(match_id.clone()))
-            .expect("match not found");
+        let mut match_data = Self::load_match(&env, &match_id)?;
 
         if match_data.state != MatchState::Disputed as u32 {
-            panic!("invalid state transition");
+            return Err(MatchError::InvalidStateTransition);
         }
 
         if winner != match_data.player_a && winner != match_data.player_b {
-            panic!("winner must be one of the players");
+            return Err(MatchError::InvalidWinner);
         }
 
         match_data.state = MatchState::Completed as u32;
         match_data.winner = Some(winner.clone());
         match_data.ended_at = Some(env.ledger().timestamp());
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Match(match_id.clone()), &match_data);
+        Self::save_match(&env, &match_id, &match_data);
 
         events::emit_match_resolved(&env, &match_id, &winner);
+        Ok(())
+    }
+
+    /// Apply a dispute decision (see `DisputeOutcome`) to a disputed match. Called by the
+    /// dispute resolution contract on behalf of a referee, whose role is checked against the
+    /// configured identity contract.
+    pub fn settle_dispute(
+        env: Env,
+        match_id: BytesN<32>,
+        outcome: u32,
+        resolver: Address,
+    ) -> Result<(), MatchError> {
+        Self::check_pause(&env)?;
+        resolver.require_auth();
+        Self::require_referee(&env, &resolver)?;
+
+        let mut match_data = Self::load_match(&env, &match_id)?;
+
+        if match_data.state != MatchState::Disputed as u32 {
+            return Err(MatchError::InvalidStateTransition);
+        }
+
+        let outcome_kind = match outcome {
+            0 => DisputeOutcome::AwardPlayerA,
+            1 => DisputeOutcome::AwardPlayerB,
+            2 => DisputeOutcome::Split,
+            3 => DisputeOutcome::Replay,
+            4 => DisputeOutcome::Void,
+            _ => return Err(MatchError::InvalidOutcome),
+        };
+
+        let now = env.ledger().timestamp();
+        match outcome_kind {
+            DisputeOutcome::AwardPlayerA | DisputeOutcome::AwardPlayerB => {
+                let winner = if outcome_kind == DisputeOutcome::AwardPlayerA {
+                    match_data.player_a.clone()
+                } else {
+                    match_data.player_b.clone()
+                };
+                match_data.state = MatchState::Completed as u32;
+                match_data.winner = Some(winner.clone());
+                match_data.ended_at = Some(now);
+                events::emit_match_resolved(&env, &match_id, &winner);
+            }
+            DisputeOutcome::Split => {
+                match_data.state = MatchState::Completed as u32;
+                match_data.winner = None;
+                match_data.ended_at = Some(now);
+            }
+            DisputeOutcome::Replay => {
+                match_data.state = MatchState::Created as u32;
+                match_data.winner = None;
+                match_data.started_at = 0;
+                match_data.ended_at = None;
+                match_data.report_a = None;
+                match_data.report_b = None;
+            }
+            DisputeOutcome::Void => {
+                match_data.state = MatchState::Cancelled as u32;
+                match_data.ended_at = Some(now);
+            }
+        }
+
+        Self::save_match(&env, &match_id, &match_data);
+
+        events::emit_dispute_settled(&env, &match_id, outcome);
+        Ok(())
     }
 
-    pub fn get_match(env: Env, match_id: BytesN<32>) -> MatchData {
+    /// Match ids the player has taken part in, oldest first.
+    pub fn get_matches_by_player(
+        env: Env,
+        player: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<BytesN<32>> {
+        let count = Self::get_player_match_count(env.clone(), player.clone());
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut ids = Vec::new(&env);
+        for i in offset..end {
+            if let Some(id) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::PlayerMatch(player.clone(), i))
+            {
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    /// Match ids currently in `state` (see `MatchState`). Order is not stable across
+    /// transitions, so callers reconciling a full state should page from offset 0 in
+    /// a single ledger.
+    pub fn get_matches_by_state(env: Env, state: u32, offset: u32, limit: u32) -> Vec<BytesN<32>> {
+        let count = Self::get_state_match_count(env.clone(), state);
+        let end = offset.saturating_add(limit.min(MAX_PAGE_SIZE)).min(count);
+        let mut ids = Vec::new(&env);
+        for i in offset..end {
+            if let Some(id) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::StateMatch(state, i))
+            {
+                ids.push_back(id);
+            }
+        }
+        ids
+    }
+
+    pub fn get_player_match_count(env: Env, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PlayerMatchCount(player))
+            .unwrap_or(0)
+    }
+
+    pub fn get_state_match_count(env: Env, state: u32) -> u32 {
         env.storage()
             .persistent()
-            .get(&DataKey::Match(match_id))
-            .expect("match not found")
+            .get(&DataKey::StateMatchCount(state))
+            .unwrap_or(0)
+    }
+
+    pub fn get_admin(env: Env) -> Result<Address, MatchError> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .ok_or(MatchError::NotInitialized)
+    }
+
+    pub fn get_match(env: Env, match_id: BytesN<32>) -> Result<MatchData, MatchError> {
+        Self::load_match(&env, &match_id)
+    }
+
+    /// Whether `player` is one of the match's two players. False for unknown matches, so other
+    /// contracts can call it without handling an error.
+    pub fn is_participant(env: Env, match_id: BytesN<32>, player: Address) -> bool {
+        match Self::load_match(&env, &match_id) {
+            Ok(m) => m.player_a == player || m.player_b == player,
+            Err(_) => false,
+        }
+    }
+
+    /// The match's `MatchState`, or None for unknown matches.
+    pub fn get_match_state(env: Env, match_id: BytesN<32>) -> Option<u32> {
+        Self::load_match(&env, &match_id).ok().map(|m| m.state)
     }
 }
 
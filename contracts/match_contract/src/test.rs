@@ -1,7 +1,7 @@
 #![cfg(test)]
 use super::*;
 use soroban_sdk::testutils::{Address as _, Ledger as _};
-use soroban_sdk::{BytesN, Env, contract, contractimpl};
+use soroban_sdk::{contract, contractimpl, symbol_short, vec, BytesN, Env};
 
 // Mock User Identity Contract for testing
 #[contract]
@@ -14,12 +14,26 @@ impl MockIdentityContract {
     }
 }
 
+#[contract]
+pub struct MockUnauthorizedIdentityContract;
+
+#[contractimpl]
+impl MockUnauthorizedIdentityContract {
+    pub fn get_role(_env: Env, _user: Address) -> u32 {
+        0 // Not authorized
+    }
+}
+
 #[contract]
 pub struct MockEmergencyPauseContract;
 
 #[contractimpl]
 impl MockEmergencyPauseContract {
-    pub fn is_paused(_env: Env, _contract: Address) -> bool {
+    pub fn is_paused(
+        _env: Env,
+        _contract: Address,
+        _function: Option<soroban_sdk::Symbol>,
+    ) -> bool {
         false
     }
 }
@@ -29,11 +43,43 @@ pub struct MockPausedEmergencyContract;
 
 #[contractimpl]
 impl MockPausedEmergencyContract {
-    pub fn is_paused(_env: Env, _contract: Address) -> bool {
+    pub fn is_paused(
+        _env: Env,
+        _contract: Address,
+        _function: Option<soroban_sdk::Symbol>,
+    ) -> bool {
         true
     }
 }
 
+fn register_test_game(env: &Env, client: &MatchContractClient) {
+    client.register_game(
+        &symbol_short!("chess"),
+        &GameConfig {
+            max_score: 1,
+            round_count: 1,
+            modes: vec![env, symbol_short!("ranked")],
+        },
+    );
+}
+
+fn create_test_match(
+    client: &MatchContractClient,
+    match_id: &BytesN<32>,
+    player_a: &Address,
+    player_b: &Address,
+) {
+    client.create_match(
+        match_id,
+        player_a,
+        player_b,
+        &symbol_short!("chess"),
+        &symbol_short!("eu"),
+        &symbol_short!("ranked"),
+        &client.get_admin(),
+    );
+}
+
 #[test]
 fn test_match_lifecycle_success() {
     let env = Env::default();
@@ -42,23 +88,26 @@ fn test_match_lifecycle_success() {
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[0u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
+    create_test_match(&client, &match_id, &player_a, &player_b);
     let data = client.get_match(&match_id);
     assert_eq!(data.state, MatchState::Created as u32);
 
-    client.start_match(&match_id);
+    client.start_match(&match_id, &player_a);
     let data = client.get_match(&match_id);
     assert_eq!(data.state, MatchState::Started as u32);
     assert_eq!(data.started_at, 12345);
 
     // Advance time for completion
     env.ledger().set_timestamp(12346);
-    client.complete_match(&match_id, &player_a);
+    client.complete_match(&match_id, &player_a, &admin);
     let data = client.get_match(&match_id);
     assert_eq!(data.state, MatchState::Completed as u32);
     assert_eq!(data.winner, Some(player_a));
@@ -73,23 +122,26 @@ fn test_dispute_resolution() {
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
-    let identity_contract_id = env.register(MockIdentityContract, ());
+    client.set_identity_contract(&env.register(MockIdentityContract, ()));
 
     let match_id = BytesN::from_array(&env, &[1u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
     let referee = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.start_match(&match_id);
-    client.raise_dispute(&match_id);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    client.raise_dispute(&match_id, &player_a);
 
     let data = client.get_match(&match_id);
     assert_eq!(data.state, MatchState::Disputed as u32);
 
     env.ledger().set_timestamp(12346);
-    client.resolve_dispute(&match_id, &player_b, &identity_contract_id, &referee);
+    client.resolve_dispute(&match_id, &player_b, &referee);
     let data = client.get_match(&match_id);
     assert_eq!(data.state, MatchState::Completed as u32);
     assert_eq!(data.winner, Some(player_b));
@@ -97,20 +149,106 @@ fn test_dispute_resolution() {
 }
 
 #[test]
-#[should_panic(expected = "invalid state transition")]
+fn test_settle_dispute_outcomes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    client.set_identity_contract(&env.register(MockIdentityContract, ()));
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let referee = Address::generate(&env);
+
+    let split_id = BytesN::from_array(&env, &[40u8; 32]);
+    create_test_match(&client, &split_id, &player_a, &player_b);
+    client.start_match(&split_id, &player_a);
+    client.raise_dispute(&split_id, &player_a);
+    client.settle_dispute(&split_id, &2, &referee);
+    let data = client.get_match(&split_id);
+    assert_eq!(data.state, MatchState::Completed as u32);
+    assert_eq!(data.winner, None);
+
+    let replay_id = BytesN::from_array(&env, &[41u8; 32]);
+    create_test_match(&client, &replay_id, &player_a, &player_b);
+    client.start_match(&replay_id, &player_a);
+    client.raise_dispute(&replay_id, &player_b);
+    client.settle_dispute(&replay_id, &3, &referee);
+    assert_eq!(
+        client.get_match(&replay_id).state,
+        MatchState::Created as u32
+    );
+
+    client.start_match(&replay_id, &player_a);
+    client.raise_dispute(&replay_id, &player_a);
+    let result = client.try_settle_dispute(&replay_id, &9, &referee);
+    assert_eq!(result, Err(Ok(MatchError::InvalidOutcome)));
+}
+
+#[test]
+fn test_settle_dispute_ignores_rogue_identity_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[44u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let resolver = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    client.raise_dispute(&match_id, &player_a);
+
+    // Without a configured identity contract nobody can settle.
+    assert_eq!(
+        client.try_settle_dispute(&match_id, &0, &resolver),
+        Err(Ok(MatchError::NotReferee))
+    );
+
+    // Roles come from the configured identity contract only, whatever other contracts
+    // would vouch for the resolver.
+    env.register(MockIdentityContract, ());
+    client.set_identity_contract(&env.register(MockUnauthorizedIdentityContract, ()));
+    assert_eq!(
+        client.try_settle_dispute(&match_id, &0, &resolver),
+        Err(Ok(MatchError::NotReferee))
+    );
+    assert_eq!(
+        client.get_match(&match_id).state,
+        MatchState::Disputed as u32
+    );
+}
+
+#[test]
 fn test_invalid_transition() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[2u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.complete_match(&match_id, &player_a);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    assert_eq!(
+        client.try_complete_match(&match_id, &player_a, &admin),
+        Err(Ok(MatchError::InvalidStateTransition))
+    );
 }
 
 #[test]
@@ -120,193 +258,269 @@ fn test_cancel_match() {
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[3u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.cancel_match(&match_id);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.cancel_match(&match_id, &admin);
     let data = client.get_match(&match_id);
     assert_eq!(data.state, MatchState::Cancelled as u32);
 }
 
 #[test]
-#[should_panic(expected = "match already exists")]
+fn test_cancel_by_player_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[42u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    assert_eq!(
+        client.try_cancel_match(&match_id, &player_a),
+        Err(Ok(MatchError::Unauthorized))
+    );
+}
+
+#[test]
 fn test_create_duplicate_match() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[4u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.create_match(&match_id, &player_a, &player_b);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    assert_eq!(
+        client.try_create_match(
+            &match_id,
+            &player_a,
+            &player_b,
+            &symbol_short!("chess"),
+            &symbol_short!("eu"),
+            &symbol_short!("ranked"),
+            &admin,
+        ),
+        Err(Ok(MatchError::MatchAlreadyExists))
+    );
 }
 
 #[test]
-#[should_panic(expected = "winner must be one of the players")]
 fn test_complete_with_invalid_winner() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[5u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
     let invalid_winner = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.start_match(&match_id);
-    client.complete_match(&match_id, &invalid_winner);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    assert_eq!(
+        client.try_complete_match(&match_id, &invalid_winner, &admin),
+        Err(Ok(MatchError::InvalidWinner))
+    );
 }
 
 #[test]
-#[should_panic(expected = "winner must be one of the players")]
 fn test_resolve_dispute_with_invalid_winner() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
-    let identity_contract_id = env.register(MockIdentityContract, ());
+    client.set_identity_contract(&env.register(MockIdentityContract, ()));
     let match_id = BytesN::from_array(&env, &[6u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
     let referee = Address::generate(&env);
     let invalid_winner = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.start_match(&match_id);
-    client.raise_dispute(&match_id);
-    client.resolve_dispute(&match_id, &invalid_winner, &identity_contract_id, &referee);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    client.raise_dispute(&match_id, &player_a);
+    assert_eq!(
+        client.try_resolve_dispute(&match_id, &invalid_winner, &referee),
+        Err(Ok(MatchError::InvalidWinner))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid state transition")]
 fn test_start_from_wrong_state() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[7u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.cancel_match(&match_id);
-    client.start_match(&match_id);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.cancel_match(&match_id, &admin);
+    assert_eq!(
+        client.try_start_match(&match_id, &player_a),
+        Err(Ok(MatchError::InvalidStateTransition))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid state transition")]
 fn test_cancel_from_wrong_state() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[8u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.start_match(&match_id);
-    client.cancel_match(&match_id);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    assert_eq!(
+        client.try_cancel_match(&match_id, &admin),
+        Err(Ok(MatchError::InvalidStateTransition))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid state transition")]
 fn test_dispute_from_wrong_state() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
 
     let match_id = BytesN::from_array(&env, &[9u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.raise_dispute(&match_id);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    assert_eq!(
+        client.try_raise_dispute(&match_id, &player_a),
+        Err(Ok(MatchError::InvalidStateTransition))
+    );
 }
 
 #[test]
-#[should_panic(expected = "invalid state transition")]
 fn test_resolve_dispute_from_wrong_state() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
-    let identity_contract_id = env.register(MockIdentityContract, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+    client.set_identity_contract(&env.register(MockIdentityContract, ()));
 
     let match_id = BytesN::from_array(&env, &[10u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
     let referee = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.resolve_dispute(&match_id, &player_a, &identity_contract_id, &referee);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    assert_eq!(
+        client.try_resolve_dispute(&match_id, &player_a, &referee),
+        Err(Ok(MatchError::InvalidStateTransition))
+    );
 }
 
 #[test]
-#[should_panic(expected = "only referee or admin can resolve disputes")]
 fn test_resolve_dispute_unauthorized_role() {
-    #[contract]
-    struct MockUnauthorizedIdentityContract;
-    #[contractimpl]
-    impl MockUnauthorizedIdentityContract {
-        pub fn get_role(_env: Env, _user: Address) -> u32 {
-            0 // Not authorized
-        }
-    }
-
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
-    let identity_contract_id = env.register(MockUnauthorizedIdentityContract, ());
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+    client.set_identity_contract(&env.register(MockUnauthorizedIdentityContract, ()));
     let match_id = BytesN::from_array(&env, &[11u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
     let referee = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
-    client.start_match(&match_id);
-    client.raise_dispute(&match_id);
-    client.resolve_dispute(&match_id, &player_a, &identity_contract_id, &referee);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    client.raise_dispute(&match_id, &player_a);
+    assert_eq!(
+        client.try_resolve_dispute(&match_id, &player_a, &referee),
+        Err(Ok(MatchError::NotReferee))
+    );
 }
 
 #[test]
-#[should_panic(expected = "contract execution is paused")]
 fn test_create_match_paused() {
     let env = Env::default();
     env.mock_all_auths();
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
-    let pause_contract_id = env.register(MockPausedEmergencyContract, ());
     let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+    let pause_contract_id = env.register(MockPausedEmergencyContract, ());
     client.set_pause_contract(&admin, &pause_contract_id);
 
     let match_id = BytesN::from_array(&env, &[12u8; 32]);
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
+    assert_eq!(
+        client.try_create_match(
+            &match_id,
+            &player_a,
+            &player_b,
+            &symbol_short!("chess"),
+            &symbol_short!("eu"),
+            &symbol_short!("ranked"),
+            &admin,
+        ),
+        Err(Ok(MatchError::ContractPaused))
+    );
 }
 
 #[test]
@@ -316,8 +530,10 @@ fn test_set_pause_contract() {
 
     let contract_id = env.register(MatchContract, ());
     let client = MatchContractClient::new(&env, &contract_id);
-    let pause_contract_id = env.register(MockEmergencyPauseContract, ());
     let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+    let pause_contract_id = env.register(MockEmergencyPauseContract, ());
 
     client.set_pause_contract(&admin, &pause_contract_id);
 
@@ -326,7 +542,484 @@ fn test_set_pause_contract() {
     let player_a = Address::generate(&env);
     let player_b = Address::generate(&env);
 
-    client.create_match(&match_id, &player_a, &player_b);
+    create_test_match(&client, &match_id, &player_a, &player_b);
     let data = client.get_match(&match_id);
     assert_eq!(data.state, MatchState::Created as u32);
 }
+
+#[test]
+fn test_transitions_by_operator_and_tournament() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let tournament = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+    client.set_tournament_contract(&tournament);
+
+    let match_id = BytesN::from_array(&env, &[14u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &tournament);
+    client.complete_match(&match_id, &player_b, &admin);
+    let data = client.get_match(&match_id);
+    assert_eq!(data.state, MatchState::Completed as u32);
+}
+
+#[test]
+fn test_referee_role_is_operator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+    client.set_identity_contract(&env.register(MockIdentityContract, ()));
+
+    let match_id = BytesN::from_array(&env, &[15u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let referee = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.cancel_match(&match_id, &referee);
+    let data = client.get_match(&match_id);
+    assert_eq!(data.state, MatchState::Cancelled as u32);
+}
+
+#[test]
+fn test_transition_by_outsider_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[16u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let outsider = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    assert_eq!(
+        client.try_start_match(&match_id, &outsider),
+        Err(Ok(MatchError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_dual_report_agreement_and_finalize() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[17u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+
+    client.submit_result(&match_id, &player_a, &player_b);
+    assert_eq!(
+        client.get_match(&match_id).state,
+        MatchState::Started as u32
+    );
+
+    client.submit_result(&match_id, &player_b, &player_b);
+    assert_eq!(
+        client.get_match(&match_id).state,
+        MatchState::PendingResult as u32
+    );
+
+    client.finalize_match(&match_id, &player_a);
+    let data = client.get_match(&match_id);
+    assert_eq!(data.state, MatchState::Completed as u32);
+    assert_eq!(data.winner, Some(player_b));
+}
+
+#[test]
+fn test_dual_report_mismatch_disputes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[18u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    client.submit_result(&match_id, &player_a, &player_a);
+    client.submit_result(&match_id, &player_b, &player_b);
+
+    assert_eq!(
+        client.get_match(&match_id).state,
+        MatchState::Disputed as u32
+    );
+}
+
+#[test]
+fn test_dual_report_same_reporter_twice() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[19u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    client.submit_result(&match_id, &player_a, &player_a);
+    assert_eq!(
+        client.try_submit_result(&match_id, &player_a, &player_a),
+        Err(Ok(MatchError::DuplicateReport))
+    );
+}
+
+#[test]
+fn test_complete_by_player_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[43u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    // A player may not declare themselves the winner without the opponent's report.
+    assert_eq!(
+        client.try_complete_match(&match_id, &player_a, &player_a),
+        Err(Ok(MatchError::Unauthorized))
+    );
+    assert_eq!(
+        client.get_match(&match_id).state,
+        MatchState::Started as u32
+    );
+}
+
+#[test]
+fn test_import_legacy_matches() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[20u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let legacy = LegacyMatchData {
+        players: vec![&env, player_a.clone(), player_b.clone()],
+        stake_asset: Address::generate(&env),
+        stake_amount: 100,
+        state: 2, // PendingResult
+        created_at: 500,
+        report1_reporter: Some(player_b.clone()),
+        report1_score: Some(0),
+        report2_reporter: Some(player_a.clone()),
+        report2_score: Some(0),
+        winner: None,
+        finalized_at: None,
+    };
+
+    client.import_legacy_matches(&vec![&env, (match_id.clone(), legacy)]);
+
+    let data = client.get_match(&match_id);
+    assert_eq!(data.state, MatchState::PendingResult as u32);
+    assert_eq!(data.report_a, Some(player_a.clone()));
+    assert_eq!(data.report_b, Some(player_a.clone()));
+    assert_eq!(data.started_at, 500);
+
+    client.finalize_match(&match_id, &admin);
+    assert_eq!(client.get_match(&match_id).winner, Some(player_a));
+}
+
+#[test]
+fn test_match_metadata_recorded() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    assert_eq!(client.get_games(), vec![&env, symbol_short!("chess")]);
+
+    let match_id = BytesN::from_array(&env, &[21u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+
+    let data = client.get_match(&match_id);
+    assert_eq!(data.game_id, symbol_short!("chess"));
+    assert_eq!(data.region, symbol_short!("eu"));
+    assert_eq!(data.mode, symbol_short!("ranked"));
+
+    client.remove_game(&symbol_short!("chess"));
+    assert_eq!(client.get_game(&symbol_short!("chess")), None);
+    assert_eq!(client.get_games().len(), 0);
+}
+
+#[test]
+fn test_create_match_unknown_game() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+
+    let match_id = BytesN::from_array(&env, &[22u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    assert_eq!(
+        client.try_create_match(
+            &match_id,
+            &player_a,
+            &player_b,
+            &symbol_short!("chess"),
+            &symbol_short!("eu"),
+            &symbol_short!("ranked"),
+            &admin,
+        ),
+        Err(Ok(MatchError::UnsupportedGame))
+    );
+}
+
+#[test]
+fn test_create_match_unsupported_mode() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[23u8; 32]);
+    assert_eq!(
+        client.try_create_match(
+            &match_id,
+            &Address::generate(&env),
+            &Address::generate(&env),
+            &symbol_short!("chess"),
+            &symbol_short!("eu"),
+            &symbol_short!("casual"),
+            &admin,
+        ),
+        Err(Ok(MatchError::UnsupportedMode))
+    );
+}
+
+#[test]
+fn test_matches_by_player_paginated() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let player_a = Address::generate(&env);
+    let ids = [
+        BytesN::from_array(&env, &[30u8; 32]),
+        BytesN::from_array(&env, &[31u8; 32]),
+        BytesN::from_array(&env, &[32u8; 32]),
+    ];
+    for id in ids.iter() {
+        create_test_match(&client, id, &player_a, &Address::generate(&env));
+    }
+
+    assert_eq!(client.get_player_match_count(&player_a), 3);
+    let page = client.get_matches_by_player(&player_a, &0, &2);
+    assert_eq!(page, vec![&env, ids[0].clone(), ids[1].clone()]);
+    let page = client.get_matches_by_player(&player_a, &2, &2);
+    assert_eq!(page, vec![&env, ids[2].clone()]);
+    assert_eq!(client.get_matches_by_player(&player_a, &5, &2).len(), 0);
+}
+
+#[test]
+fn test_matches_by_state_follow_transitions() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let first = BytesN::from_array(&env, &[33u8; 32]);
+    let second = BytesN::from_array(&env, &[34u8; 32]);
+    let third = BytesN::from_array(&env, &[35u8; 32]);
+    create_test_match(&client, &first, &player_a, &player_b);
+    create_test_match(&client, &second, &player_a, &player_b);
+    create_test_match(&client, &third, &player_a, &player_b);
+
+    let created = MatchState::Created as u32;
+    let started = MatchState::Started as u32;
+    assert_eq!(client.get_state_match_count(&created), 3);
+
+    client.start_match(&first, &player_a);
+    assert_eq!(client.get_state_match_count(&created), 2);
+    assert_eq!(client.get_state_match_count(&started), 1);
+    assert_eq!(
+        client.get_matches_by_state(&started, &0, &10),
+        vec![&env, first.clone()]
+    );
+
+    let remaining = client.get_matches_by_state(&created, &0, &10);
+    assert_eq!(remaining.len(), 2);
+    assert!(remaining.contains(&second));
+    assert!(remaining.contains(&third));
+
+    client.start_match(&third, &player_b);
+    assert_eq!(
+        client.get_matches_by_state(&created, &0, &10),
+        vec![&env, second]
+    );
+    assert_eq!(client.get_state_match_count(&started), 2);
+}
+
+#[test]
+fn test_is_participant() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[36u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+
+    assert!(client.is_participant(&match_id, &player_a));
+    assert!(client.is_participant(&match_id, &player_b));
+    assert!(!client.is_participant(&match_id, &Address::generate(&env)));
+    let unknown = BytesN::from_array(&env, &[37u8; 32]);
+    assert!(!client.is_participant(&unknown, &player_a));
+}
+
+#[test]
+fn test_create_match_requires_creator() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[45u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    let creator = Address::generate(&env);
+    let create = |caller: &Address| {
+        client.try_create_match(
+            &match_id,
+            &player_a,
+            &player_b,
+            &symbol_short!("chess"),
+            &symbol_short!("eu"),
+            &symbol_short!("ranked"),
+            caller,
+        )
+    };
+
+    assert_eq!(create(&player_a), Err(Ok(MatchError::Unauthorized)));
+    assert_eq!(create(&creator), Err(Ok(MatchError::Unauthorized)));
+
+    client.set_creator(&creator, &true);
+    assert!(client.is_creator(&creator));
+    create(&creator).unwrap().unwrap();
+
+    client.set_creator(&creator, &false);
+    assert!(!client.is_creator(&creator));
+    let next_id = BytesN::from_array(&env, &[46u8; 32]);
+    assert_eq!(
+        client.try_create_match(
+            &next_id,
+            &player_a,
+            &player_b,
+            &symbol_short!("chess"),
+            &symbol_short!("eu"),
+            &symbol_short!("ranked"),
+            &creator,
+        ),
+        Err(Ok(MatchError::Unauthorized))
+    );
+}
+
+#[test]
+fn test_resolve_dispute_without_identity_contract() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let contract_id = env.register(MatchContract, ());
+    let client = MatchContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.initialize(&admin);
+    register_test_game(&env, &client);
+
+    let match_id = BytesN::from_array(&env, &[47u8; 32]);
+    let player_a = Address::generate(&env);
+    let player_b = Address::generate(&env);
+    create_test_match(&client, &match_id, &player_a, &player_b);
+    client.start_match(&match_id, &player_a);
+    client.raise_dispute(&match_id, &player_a);
+
+    assert_eq!(
+        client.try_resolve_dispute(&match_id, &player_a, &Address::generate(&env)),
+        Err(Ok(MatchError::NotReferee))
+    );
+}
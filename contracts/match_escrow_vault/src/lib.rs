@@ -23,9 +23,10 @@
 //! - All actions emit events for auditability
 
 use arenax_events::escrow as events;
-use soroban_sdk::{
-    contract, contractimpl, contracttype, token, Address, BytesN, Env, IntoVal, Symbol,
-};
+use arenax_token_interface as token;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol};
+
+const BPS_DENOMINATOR: u32 = 10_000;
 
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -37,6 +38,10 @@ pub enum DataKey {
     Escrow(BytesN<32>),
     ReentrancyGuard(BytesN<32>),
     Paused,
+    AntiCheatOracle,
+    Creator(Address),
+    Slasher(Address),
+    Slashed(BytesN<32>, Address), // (match_id, player) -> amount slashed from their stake
 }
 
 #[contracttype]
@@ -110,6 +115,23 @@ impl MatchEscrowVault {
         events::emit_match_contract_set(&env, &match_contract);
     }
 
+    /// Set the Anti-Cheat Oracle address allowed to dispute locked escrows
+    ///
+    /// # Arguments
+    /// * `oracle` - Address of the deployed Anti-Cheat Oracle contract
+    ///
+    /// # Panics
+    /// * If caller is not admin
+    pub fn set_anticheat_oracle(env: Env, oracle: Address) {
+        Self::require_admin(&env);
+
+        env.storage()
+            .instance()
+            .set(&DataKey::AntiCheatOracle, &oracle);
+
+        events::emit_anticheat_oracle_set(&env, &oracle);
+    }
+
     /// Set the Identity Contract address for role verification
     ///
     /// # Arguments
@@ -142,6 +164,48 @@ impl MatchEscrowVault {
         events::emit_treasury_set(&env, &treasury);
     }
 
+    /// Allow or revoke a contract (e.g. matchmaking, challenges) to create escrows
+    ///
+    /// # Arguments
+    /// * `creator` - Address of the creating contract
+    /// * `allowed` - Whether it may create escrows
+    ///
+    /// # Panics
+    /// * If caller is not admin
+    pub fn set_creator(env: Env, creator: Address, allowed: bool) {
+        Self::require_admin(&env);
+
+        let key = DataKey::Creator(creator.clone());
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+
+        events::emit_creator_set(&env, &creator, allowed);
+    }
+
+    /// Allow or revoke a contract (e.g. the slashing coordinator) to slash escrowed stakes
+    ///
+    /// # Arguments
+    /// * `slasher` - Address of the slashing contract
+    /// * `allowed` - Whether it may slash
+    ///
+    /// # Panics
+    /// * If caller is not admin
+    pub fn set_slasher(env: Env, slasher: Address, allowed: bool) {
+        Self::require_admin(&env);
+
+        let key = DataKey::Slasher(slasher.clone());
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+
+        events::emit_slasher_set(&env, &slasher, allowed);
+    }
+
     /// Pause/unpause the contract
     ///
     /// # Arguments
@@ -162,9 +226,11 @@ impl MatchEscrowVault {
     /// * `player_b` - Address of player B
     /// * `amount` - Stake amount required from each player
     /// * `asset` - Token address for the stake
+    /// * `creator` - The admin or a registered creator
     ///
     /// # Panics
     /// * If contract is paused
+    /// * If creator is neither the admin nor a registered creator
     /// * If escrow already exists for this match
     /// * If amount is not positive
     /// * If players are the same address
@@ -175,8 +241,10 @@ impl MatchEscrowVault {
         player_b: Address,
         amount: i128,
         asset: Address,
+        creator: Address,
     ) {
         Self::require_not_paused(&env);
+        Self::require_creator(&env, &creator);
 
         if env
             .storage()
@@ -227,71 +295,25 @@ impl MatchEscrowVault {
     /// * If escrow is not in a valid state for deposits
     /// * If re-entrancy is detected
     pub fn deposit(env: Env, match_id: BytesN<32>, player: Address) {
-        Self::require_not_paused(&env);
-        Self::acquire_reentrancy_guard(&env, &match_id);
-
         player.require_auth();
+        Self::fund_stake(&env, &match_id, &player, &player);
+    }
 
-        let mut escrow: EscrowData = env
-            .storage()
-            .persistent()
-            .get(&DataKey::Escrow(match_id.clone()))
-            .expect("escrow not found");
-
-        let is_player_a = player == escrow.player_a;
-        let is_player_b = player == escrow.player_b;
-
-        if !is_player_a && !is_player_b {
-            Self::release_reentrancy_guard(&env, &match_id);
-            panic!("player not in match");
-        }
-
-        let valid_states = [
-            EscrowState::AwaitingDeposits as u32,
-            EscrowState::PlayerADeposited as u32,
-            EscrowState::PlayerBDeposited as u32,
-        ];
-        if !valid_states.contains(&escrow.state) {
-            Self::release_reentrancy_guard(&env, &match_id);
-            panic!("invalid escrow state for deposit");
-        }
-
-        if is_player_a && escrow.player_a_deposited {
-            Self::release_reentrancy_guard(&env, &match_id);
-            panic!("player A already deposited");
-        }
-        if is_player_b && escrow.player_b_deposited {
-            Self::release_reentrancy_guard(&env, &match_id);
-            panic!("player B already deposited");
-        }
-
-        let contract_address = env.current_contract_address();
-        let token_client = token::Client::new(&env, &escrow.asset);
-        token_client.transfer(&player, &contract_address, &escrow.amount);
-
-        if is_player_a {
-            escrow.player_a_deposited = true;
-            if escrow.player_b_deposited {
-                escrow.state = EscrowState::FullyFunded as u32;
-            } else {
-                escrow.state = EscrowState::PlayerADeposited as u32;
-            }
-        } else {
-            escrow.player_b_deposited = true;
-            if escrow.player_a_deposited {
-                escrow.state = EscrowState::FullyFunded as u32;
-            } else {
-                escrow.state = EscrowState::PlayerBDeposited as u32;
-            }
-        }
-
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(match_id.clone()), &escrow);
-
-        Self::release_reentrancy_guard(&env, &match_id);
-
-        events::emit_deposited(&env, &match_id, &player, escrow.amount, &escrow.asset);
+    /// Deposit `player`'s stake on their behalf, paid by `payer`
+    ///
+    /// Used by contracts that already hold the stake, such as the matchmaking
+    /// queue funding both sides of a match it creates.
+    ///
+    /// # Arguments
+    /// * `match_id` - The match identifier
+    /// * `player` - The player whose stake is deposited
+    /// * `payer` - The address the stake is transferred from
+    ///
+    /// # Panics
+    /// * Same conditions as `deposit`
+    pub fn deposit_for(env: Env, match_id: BytesN<32>, player: Address, payer: Address) {
+        payer.require_auth();
+        Self::fund_stake(&env, &match_id, &player, &payer);
     }
 
     /// Lock funds when match starts
@@ -368,8 +390,9 @@ impl MatchEscrowVault {
             panic!("winner not in match");
         }
 
-        // Calculate total amount (both players' stakes)
-        let total_amount = escrow.amount * 2;
+        // Calculate total amount (both players' stakes, less anything slashed)
+        let (slashed_a, slashed_b) = Self::slashed_amounts(&env, &escrow);
+        let total_amount = escrow.amount * 2 - slashed_a - slashed_b;
 
         // Transfer to winner
         let contract_address = env.current_contract_address();
@@ -421,13 +444,22 @@ impl MatchEscrowVault {
 
         let contract_address = env.current_contract_address();
         let token_client = token::Client::new(&env, &escrow.asset);
+        let (slashed_a, slashed_b) = Self::slashed_amounts(&env, &escrow);
 
-        if escrow.player_a_deposited {
-            token_client.transfer(&contract_address, &escrow.player_a, &escrow.amount);
+        if escrow.player_a_deposited && escrow.amount > slashed_a {
+            token_client.transfer(
+                &contract_address,
+                &escrow.player_a,
+                &(escrow.amount - slashed_a),
+            );
         }
 
-        if escrow.player_b_deposited {
-            token_client.transfer(&contract_address, &escrow.player_b, &escrow.amount);
+        if escrow.player_b_deposited && escrow.amount > slashed_b {
+            token_client.transfer(
+                &contract_address,
+                &escrow.player_b,
+                &(escrow.amount - slashed_b),
+            );
         }
 
         escrow.state = EscrowState::Refunded as u32;
@@ -461,22 +493,34 @@ impl MatchEscrowVault {
     /// * If caller is not authorized
     pub fn mark_disputed(env: Env, match_id: BytesN<32>) {
         Self::require_match_contract_or_admin(&env);
+        Self::set_disputed(&env, &match_id);
+    }
 
-        let mut escrow: EscrowData = env
+    /// Mark escrow as disputed following a high-severity anti-cheat flag
+    /// Can only be called by the configured Anti-Cheat Oracle
+    ///
+    /// # Arguments
+    /// * `oracle` - The Anti-Cheat Oracle address
+    /// * `match_id` - The match identifier
+    ///
+    /// # Panics
+    /// * If no Anti-Cheat Oracle is configured or `oracle` does not match it
+    /// * If escrow doesn't exist
+    /// * If escrow is not locked
+    pub fn report_cheating(env: Env, oracle: Address, match_id: BytesN<32>) {
+        oracle.require_auth();
+        let configured: Address = env
             .storage()
-            .persistent()
-            .get(&DataKey::Escrow(match_id.clone()))
-            .expect("escrow not found");
-
-        if escrow.state != EscrowState::Locked as u32 {
-            panic!("escrow not locked");
+            .instance()
+            .get(&DataKey::AntiCheatOracle)
+            .expect("anti-cheat oracle not set");
+        if oracle != configured {
+            panic!("not anti-cheat oracle");
         }
 
-        escrow.state = EscrowState::Disputed as u32;
+        Self::set_disputed(&env, &match_id);
 
-        env.storage()
-            .persistent()
-            .set(&DataKey::Escrow(match_id), &escrow);
+        events::emit_cheating_reported(&env, &match_id, &oracle);
     }
 
     /// Resolve a disputed match and release funds to winner
@@ -516,8 +560,9 @@ impl MatchEscrowVault {
             panic!("winner not in match");
         }
 
-        // Calculate total amount (both players' stakes)
-        let total_amount = escrow.amount * 2;
+        // Calculate total amount (both players' stakes, less anything slashed)
+        let (slashed_a, slashed_b) = Self::slashed_amounts(&env, &escrow);
+        let total_amount = escrow.amount * 2 - slashed_a - slashed_b;
 
         // Transfer to winner
         let contract_address = env.current_contract_address();
@@ -537,6 +582,122 @@ impl MatchEscrowVault {
         events::emit_funds_released(&env, &match_id, &winner, total_amount, &escrow.asset);
     }
 
+    /// Settle a disputed escrow by splitting the pot between the players
+    /// Can only be called by authorized resolvers (Referee or Admin)
+    ///
+    /// # Arguments
+    /// * `match_id` - The match identifier
+    /// * `share_a_bps` - Player A's share of the pot in basis points; player B receives the rest
+    /// * `fee` - Dispute fee taken out of the pot before it is split
+    /// * `fee_recipient` - Receiver of the dispute fee
+    /// * `resolver` - The resolver's address (must be Referee or Admin)
+    ///
+    /// # Panics
+    /// * If contract is paused
+    /// * If escrow doesn't exist
+    /// * If escrow is not disputed
+    /// * If `share_a_bps` exceeds 10_000
+    /// * If `fee` is negative or exceeds the pot
+    /// * If resolver is not authorized
+    /// * If re-entrancy is detected
+    pub fn settle_dispute(
+        env: Env,
+        match_id: BytesN<32>,
+        share_a_bps: u32,
+        fee: i128,
+        fee_recipient: Address,
+        resolver: Address,
+    ) {
+        Self::require_not_paused(&env);
+        resolver.require_auth();
+        Self::require_resolver_role(&env, &resolver);
+
+        if share_a_bps > BPS_DENOMINATOR {
+            panic!("invalid share");
+        }
+
+        Self::acquire_reentrancy_guard(&env, &match_id);
+
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id.clone()))
+            .expect("escrow not found");
+
+        if escrow.state != EscrowState::Disputed as u32 {
+            Self::release_reentrancy_guard(&env, &match_id);
+            panic!("escrow not disputed");
+        }
+
+        let (slashed_a, slashed_b) = Self::slashed_amounts(&env, &escrow);
+        let total_amount = escrow.amount * 2 - slashed_a - slashed_b;
+        if fee < 0 || fee > total_amount {
+            Self::release_reentrancy_guard(&env, &match_id);
+            panic!("invalid fee");
+        }
+        let pot = total_amount - fee;
+        let payout_a = pot * share_a_bps as i128 / BPS_DENOMINATOR as i128;
+        let payout_b = pot - payout_a;
+
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(&env, &escrow.asset);
+        if fee > 0 {
+            token_client.transfer(&contract_address, &fee_recipient, &fee);
+            events::emit_dispute_fee_collected(&env, &match_id, &fee_recipient, fee, &escrow.asset);
+        }
+        if payout_a > 0 {
+            token_client.transfer(&contract_address, &escrow.player_a, &payout_a);
+        }
+        if payout_b > 0 {
+            token_client.transfer(&contract_address, &escrow.player_b, &payout_b);
+        }
+
+        escrow.state = EscrowState::Released as u32;
+        escrow.released_at = Some(env.ledger().timestamp());
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(match_id.clone()), &escrow);
+
+        Self::release_reentrancy_guard(&env, &match_id);
+
+        events::emit_dispute_settled(&env, &match_id, payout_a, payout_b, &escrow.asset);
+    }
+
+    /// Return a disputed escrow to `Locked` so the match can be replayed with the same stakes
+    /// Can only be called by authorized resolvers (Referee or Admin)
+    ///
+    /// # Arguments
+    /// * `match_id` - The match identifier
+    /// * `resolver` - The resolver's address (must be Referee or Admin)
+    ///
+    /// # Panics
+    /// * If escrow doesn't exist
+    /// * If escrow is not disputed
+    /// * If resolver is not authorized
+    pub fn reopen_escrow(env: Env, match_id: BytesN<32>, resolver: Address) {
+        resolver.require_auth();
+        Self::require_resolver_role(&env, &resolver);
+
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id.clone()))
+            .expect("escrow not found");
+
+        if escrow.state != EscrowState::Disputed as u32 {
+            panic!("escrow not disputed");
+        }
+
+        escrow.state = EscrowState::Locked as u32;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(match_id.clone()), &escrow);
+
+        events::emit_match_locked(&env, &match_id);
+    }
+
     /// Slash a player's stake (called by Slashing Contract)
     ///
     /// # Arguments
@@ -579,6 +740,106 @@ impl MatchEscrowVault {
         Self::slash_stake(env, subject, amount, asset);
     }
 
+    /// Slash part of one player's deposited stake in a match (called by registered slashers)
+    /// The slashed amount goes to the treasury and is deducted from whatever that stake
+    /// would otherwise pay out.
+    ///
+    /// # Arguments
+    /// * `slasher` - The registered slasher's address
+    /// * `match_id` - The match identifier
+    /// * `subject` - The player whose stake is slashed
+    /// * `amount` - Amount to slash
+    ///
+    /// # Panics
+    /// * If contract is paused
+    /// * If slasher is not registered
+    /// * If escrow doesn't exist or is already released or refunded
+    /// * If subject is not in the match or has not deposited
+    /// * If amount is not positive or exceeds the subject's remaining stake
+    /// * If treasury is not set
+    /// * If re-entrancy is detected
+    pub fn slash_match_stake(
+        env: Env,
+        slasher: Address,
+        match_id: BytesN<32>,
+        subject: Address,
+        amount: i128,
+    ) {
+        Self::require_not_paused(&env);
+        slasher.require_auth();
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::Slasher(slasher.clone()))
+        {
+            panic!("not a slasher");
+        }
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        Self::acquire_reentrancy_guard(&env, &match_id);
+
+        let escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id.clone()))
+            .expect("escrow not found");
+
+        if escrow.state == EscrowState::Released as u32
+            || escrow.state == EscrowState::Refunded as u32
+        {
+            Self::release_reentrancy_guard(&env, &match_id);
+            panic!("escrow already finalized");
+        }
+
+        let deposited = if subject == escrow.player_a {
+            escrow.player_a_deposited
+        } else if subject == escrow.player_b {
+            escrow.player_b_deposited
+        } else {
+            Self::release_reentrancy_guard(&env, &match_id);
+            panic!("player not in match");
+        };
+        if !deposited {
+            Self::release_reentrancy_guard(&env, &match_id);
+            panic!("player has not deposited");
+        }
+
+        let slashed_key = DataKey::Slashed(match_id.clone(), subject.clone());
+        let slashed: i128 = env.storage().persistent().get(&slashed_key).unwrap_or(0);
+        if amount > escrow.amount - slashed {
+            Self::release_reentrancy_guard(&env, &match_id);
+            panic!("slash exceeds stake");
+        }
+
+        let treasury: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .expect("treasury not set");
+        token::Client::new(&env, &escrow.asset).transfer(
+            &env.current_contract_address(),
+            &treasury,
+            &amount,
+        );
+        env.storage()
+            .persistent()
+            .set(&slashed_key, &(slashed + amount));
+
+        Self::release_reentrancy_guard(&env, &match_id);
+
+        events::emit_stake_slashed(&env, &match_id, &subject, amount, &escrow.asset);
+    }
+
+    /// Amount slashed so far from `player`'s stake in a match
+    pub fn get_slashed(env: Env, match_id: BytesN<32>, player: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Slashed(match_id, player))
+            .unwrap_or(0)
+    }
+
     /// Emergency withdraw for a specific match (admin only)
     /// Use only in case of critical bugs or exploits
     ///
@@ -602,12 +863,13 @@ impl MatchEscrowVault {
         let contract_address = env.current_contract_address();
         let token_client = token::Client::new(&env, &escrow.asset);
 
+        let (slashed_a, slashed_b) = Self::slashed_amounts(&env, &escrow);
         let mut total = 0i128;
         if escrow.player_a_deposited {
-            total += escrow.amount;
+            total += escrow.amount - slashed_a;
         }
         if escrow.player_b_deposited {
-            total += escrow.amount;
+            total += escrow.amount - slashed_b;
         }
 
         if total > 0 {
@@ -648,6 +910,36 @@ impl MatchEscrowVault {
         escrow.state
     }
 
+    /// Get the two players of a match escrow as `(player_a, player_b)`
+    pub fn get_players(env: Env, match_id: BytesN<32>) -> (Address, Address) {
+        let escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id))
+            .expect("escrow not found");
+        (escrow.player_a, escrow.player_b)
+    }
+
+    /// Get the asset a match escrow is denominated in
+    pub fn get_escrow_asset(env: Env, match_id: BytesN<32>) -> Address {
+        let escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id))
+            .expect("escrow not found");
+        escrow.asset
+    }
+
+    /// Get the per-player stake amount of a match escrow
+    pub fn get_stake_amount(env: Env, match_id: BytesN<32>) -> i128 {
+        let escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id))
+            .expect("escrow not found");
+        escrow.amount
+    }
+
     /// Check if contract is paused
     pub fn is_paused(env: Env) -> bool {
         env.storage()
@@ -657,6 +949,14 @@ impl MatchEscrowVault {
     }
 
     /// Get admin address
+    pub fn is_creator(env: Env, creator: Address) -> bool {
+        env.storage().instance().has(&DataKey::Creator(creator))
+    }
+
+    pub fn is_slasher(env: Env, slasher: Address) -> bool {
+        env.storage().instance().has(&DataKey::Slasher(slasher))
+    }
+
     pub fn get_admin(env: Env) -> Address {
         env.storage()
             .instance()
@@ -664,6 +964,72 @@ impl MatchEscrowVault {
             .expect("not initialized")
     }
 
+    fn fund_stake(env: &Env, match_id: &BytesN<32>, player: &Address, payer: &Address) {
+        Self::require_not_paused(env);
+        Self::acquire_reentrancy_guard(env, match_id);
+
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id.clone()))
+            .expect("escrow not found");
+
+        let is_player_a = *player == escrow.player_a;
+        let is_player_b = *player == escrow.player_b;
+
+        if !is_player_a && !is_player_b {
+            Self::release_reentrancy_guard(env, match_id);
+            panic!("player not in match");
+        }
+
+        let valid_states = [
+            EscrowState::AwaitingDeposits as u32,
+            EscrowState::PlayerADeposited as u32,
+            EscrowState::PlayerBDeposited as u32,
+        ];
+        if !valid_states.contains(&escrow.state) {
+            Self::release_reentrancy_guard(env, match_id);
+            panic!("invalid escrow state for deposit");
+        }
+
+        if is_player_a && escrow.player_a_deposited {
+            Self::release_reentrancy_guard(env, match_id);
+            panic!("player A already deposited");
+        }
+        if is_player_b && escrow.player_b_deposited {
+            Self::release_reentrancy_guard(env, match_id);
+            panic!("player B already deposited");
+        }
+
+        let contract_address = env.current_contract_address();
+        let token_client = token::Client::new(env, &escrow.asset);
+        token_client.transfer(payer, &contract_address, &escrow.amount);
+
+        if is_player_a {
+            escrow.player_a_deposited = true;
+            if escrow.player_b_deposited {
+                escrow.state = EscrowState::FullyFunded as u32;
+            } else {
+                escrow.state = EscrowState::PlayerADeposited as u32;
+            }
+        } else {
+            escrow.player_b_deposited = true;
+            if escrow.player_a_deposited {
+                escrow.state = EscrowState::FullyFunded as u32;
+            } else {
+                escrow.state = EscrowState::PlayerBDeposited as u32;
+            }
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(match_id.clone()), &escrow);
+
+        Self::release_reentrancy_guard(env, match_id);
+
+        events::emit_deposited(env, match_id, player, escrow.amount, &escrow.asset);
+    }
+
     fn require_admin(env: &Env) {
         let admin: Address = env
             .storage()
@@ -673,6 +1039,30 @@ impl MatchEscrowVault {
         admin.require_auth();
     }
 
+    fn slashed_amounts(env: &Env, escrow: &EscrowData) -> (i128, i128) {
+        let slashed = |player: &Address| -> i128 {
+            env.storage()
+                .persistent()
+                .get(&DataKey::Slashed(escrow.match_id.clone(), player.clone()))
+                .unwrap_or(0)
+        };
+        (slashed(&escrow.player_a), slashed(&escrow.player_b))
+    }
+
+    fn require_creator(env: &Env, creator: &Address) {
+        creator.require_auth();
+        if creator == &Self::get_admin(env.clone()) {
+            return;
+        }
+        if !env
+            .storage()
+            .instance()
+            .has(&DataKey::Creator(creator.clone()))
+        {
+            panic!("not a creator");
+        }
+    }
+
     fn require_not_paused(env: &Env) {
         let paused: bool = env
             .storage()
@@ -684,6 +1074,24 @@ impl MatchEscrowVault {
         }
     }
 
+    fn set_disputed(env: &Env, match_id: &BytesN<32>) {
+        let mut escrow: EscrowData = env
+            .storage()
+            .persistent()
+            .get(&DataKey::Escrow(match_id.clone()))
+            .expect("escrow not found");
+
+        if escrow.state != EscrowState::Locked as u32 {
+            panic!("escrow not locked");
+        }
+
+        escrow.state = EscrowState::Disputed as u32;
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Escrow(match_id.clone()), &escrow);
+    }
+
     fn require_match_contract_or_admin(env: &Env) {
         let admin: Address = env
             .storage()
@@ -60,7 +60,7 @@ fn setup_escrow_with_deposits(
     client.set_treasury(treasury);
     mint_tokens(env, &token, admin, player_a, amount);
     mint_tokens(env, &token, admin, player_b, amount);
-    client.create_escrow(&match_id, player_a, player_b, &amount, &token);
+    client.create_escrow(&match_id, player_a, player_b, &amount, &token, admin);
     client.deposit(&match_id, player_a);
     client.deposit(&match_id, player_b);
 
@@ -154,7 +154,7 @@ fn test_create_escrow_success() {
     let amount = 1000i128;
 
     env.mock_all_auths();
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
 
     assert!(client.escrow_exists(&match_id));
 
@@ -179,8 +179,9 @@ fn test_create_escrow_duplicate_fails() {
     let match_id = generate_match_id(&env, 1);
 
     env.mock_all_auths();
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token);
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token); // Should panic
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
+    // Should panic
 }
 
 #[test]
@@ -194,7 +195,7 @@ fn test_create_escrow_zero_amount_fails() {
     let match_id = generate_match_id(&env, 1);
 
     env.mock_all_auths();
-    client.create_escrow(&match_id, &player_a, &player_b, &0, &token); // Should panic
+    client.create_escrow(&match_id, &player_a, &player_b, &0, &token, &admin); // Should panic
 }
 
 #[test]
@@ -208,7 +209,8 @@ fn test_create_escrow_negative_amount_fails() {
     let match_id = generate_match_id(&env, 1);
 
     env.mock_all_auths();
-    client.create_escrow(&match_id, &player_a, &player_b, &-100, &token); // Should panic
+    client.create_escrow(&match_id, &player_a, &player_b, &-100, &token, &admin);
+    // Should panic
 }
 
 #[test]
@@ -222,7 +224,8 @@ fn test_create_escrow_same_player_fails() {
     let match_id = generate_match_id(&env, 1);
 
     env.mock_all_auths();
-    client.create_escrow(&match_id, &player_a, &player_a, &1000, &token); // Should panic
+    client.create_escrow(&match_id, &player_a, &player_a, &1000, &token, &admin);
+    // Should panic
 }
 
 #[test]
@@ -237,7 +240,58 @@ fn test_create_escrow_when_paused_fails() {
 
     env.mock_all_auths();
     client.set_paused(&true);
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token); // Should panic
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
+    // Should panic
+}
+
+#[test]
+#[should_panic(expected = "not a creator")]
+fn test_create_escrow_by_non_creator_fails() {
+    let (env, admin, player_a, player_b, _) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let token = create_token(&env, &admin);
+    let match_id = generate_match_id(&env, 1);
+
+    env.mock_all_auths();
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &player_a);
+    // Should panic
+}
+
+#[test]
+fn test_registered_creator_can_create_escrow() {
+    let (env, admin, player_a, player_b, _) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let token = create_token(&env, &admin);
+    let creator = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.set_creator(&creator, &true);
+    assert!(client.is_creator(&creator));
+    client.create_escrow(
+        &generate_match_id(&env, 1),
+        &player_a,
+        &player_b,
+        &1000,
+        &token,
+        &creator,
+    );
+    assert!(client.escrow_exists(&generate_match_id(&env, 1)));
+
+    client.set_creator(&creator, &false);
+    assert!(!client.is_creator(&creator));
+    let result = client.try_create_escrow(
+        &generate_match_id(&env, 2),
+        &player_a,
+        &player_b,
+        &1000,
+        &token,
+        &creator,
+    );
+    assert!(result.is_err());
 }
 
 #[test]
@@ -253,7 +307,7 @@ fn test_deposit_player_a_success() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_a, amount);
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
     client.deposit(&match_id, &player_a);
 
     let escrow = client.get_escrow(&match_id);
@@ -279,7 +333,7 @@ fn test_deposit_player_b_success() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_b, amount);
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
     client.deposit(&match_id, &player_b);
 
     let escrow = client.get_escrow(&match_id);
@@ -302,7 +356,7 @@ fn test_deposit_both_players_fully_funded() {
 
     mint_tokens(&env, &token, &admin, &player_a, amount);
     mint_tokens(&env, &token, &admin, &player_b, amount);
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
     client.deposit(&match_id, &player_a);
     client.deposit(&match_id, &player_b);
 
@@ -315,6 +369,32 @@ fn test_deposit_both_players_fully_funded() {
     assert_eq!(token_client.balance(&contract_id), amount * 2);
 }
 
+#[test]
+fn test_deposit_for_pulls_from_payer() {
+    let (env, admin, player_a, player_b, _) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let token = create_token(&env, &admin);
+    let match_id = generate_match_id(&env, 1);
+    let payer = Address::generate(&env);
+    let amount = 1000i128;
+
+    env.mock_all_auths();
+
+    mint_tokens(&env, &token, &admin, &payer, amount);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
+    client.deposit_for(&match_id, &player_a, &payer);
+
+    let escrow = client.get_escrow(&match_id);
+    assert!(escrow.player_a_deposited);
+    assert_eq!(escrow.state, EscrowState::PlayerADeposited as u32);
+
+    let token_client = SdkTokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&contract_id), amount);
+    assert_eq!(token_client.balance(&payer), 0);
+}
+
 #[test]
 #[should_panic(expected = "player not in match")]
 fn test_deposit_non_player_fails() {
@@ -328,7 +408,7 @@ fn test_deposit_non_player_fails() {
 
     env.mock_all_auths();
 
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
     client.deposit(&match_id, &random_player); // Should panic
 }
 
@@ -346,7 +426,7 @@ fn test_deposit_player_a_twice_fails() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_a, amount * 2);
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
     client.deposit(&match_id, &player_a);
     client.deposit(&match_id, &player_a); // Should panic
 }
@@ -365,7 +445,7 @@ fn test_deposit_player_b_twice_fails() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_b, amount * 2);
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
     client.deposit(&match_id, &player_b);
     client.deposit(&match_id, &player_b); // Should panic
 }
@@ -396,7 +476,7 @@ fn test_deposit_when_paused_fails() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_a, 1000);
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
     client.set_paused(&true);
     client.deposit(&match_id, &player_a); // Should panic
 }
@@ -439,7 +519,7 @@ fn test_lock_funds_not_fully_funded_fails() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_a, 1000);
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
     client.deposit(&match_id, &player_a);
     client.lock_funds(&match_id);
 }
@@ -628,7 +708,7 @@ fn test_refund_partial_deposit_player_a_only() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_a, amount);
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
     client.deposit(&match_id, &player_a);
 
     client.refund(&match_id);
@@ -754,6 +834,53 @@ fn test_mark_disputed_not_locked_fails() {
     client.mark_disputed(&match_id);
 }
 
+#[test]
+fn test_report_cheating_by_anticheat_oracle() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+    let oracle = Address::generate(&env);
+
+    let (match_id, _) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+
+    client.set_anticheat_oracle(&oracle);
+    client.lock_funds(&match_id);
+    client.report_cheating(&oracle, &match_id);
+
+    let escrow = client.get_escrow(&match_id);
+    assert_eq!(escrow.state, EscrowState::Disputed as u32);
+}
+
+#[test]
+#[should_panic(expected = "not anti-cheat oracle")]
+fn test_report_cheating_wrong_oracle_fails() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let (match_id, _) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+
+    client.set_anticheat_oracle(&Address::generate(&env));
+    client.lock_funds(&match_id);
+    client.report_cheating(&Address::generate(&env), &match_id);
+}
+
 #[test]
 fn test_resolve_dispute_success() {
     let (env, admin, player_a, player_b, treasury) = create_test_env();
@@ -782,6 +909,61 @@ fn test_resolve_dispute_success() {
     assert_eq!(token_client.balance(&player_b), 2000);
 }
 
+#[test]
+fn test_settle_dispute_split() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let (match_id, token) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+
+    client.lock_funds(&match_id);
+    client.mark_disputed(&match_id);
+
+    let fee_recipient = Address::generate(&env);
+    client.settle_dispute(&match_id, &2500, &200, &fee_recipient, &admin);
+
+    let escrow = client.get_escrow(&match_id);
+    assert_eq!(escrow.state, EscrowState::Released as u32);
+
+    let token_client = SdkTokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&fee_recipient), 200);
+    assert_eq!(token_client.balance(&player_a), 450);
+    assert_eq!(token_client.balance(&player_b), 1350);
+}
+
+#[test]
+fn test_reopen_escrow() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let (match_id, _) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+
+    client.lock_funds(&match_id);
+    client.mark_disputed(&match_id);
+    client.reopen_escrow(&match_id, &admin);
+
+    let escrow = client.get_escrow(&match_id);
+    assert_eq!(escrow.state, EscrowState::Locked as u32);
+}
+
 #[test]
 #[should_panic(expected = "escrow not disputed")]
 fn test_resolve_dispute_not_disputed_fails() {
@@ -890,6 +1072,107 @@ fn test_slash_stake_insufficient_balance_fails() {
     client.slash_stake(&player_a, &5000, &token);
 }
 
+#[test]
+fn test_slash_match_stake_reduces_payout() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let (match_id, token) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+    let slasher = Address::generate(&env);
+    client.set_slasher(&slasher, &true);
+    assert!(client.is_slasher(&slasher));
+
+    client.slash_match_stake(&slasher, &match_id, &player_a, &300);
+    assert_eq!(client.get_slashed(&match_id, &player_a), 300);
+
+    client.lock_funds(&match_id);
+    client.release_to_winner(&match_id, &player_b);
+
+    let token_client = SdkTokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&treasury), 300);
+    assert_eq!(token_client.balance(&player_b), 1700);
+    assert_eq!(token_client.balance(&contract_id), 0);
+}
+
+#[test]
+fn test_slash_match_stake_reduces_refund() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let (match_id, token) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+    let slasher = Address::generate(&env);
+    client.set_slasher(&slasher, &true);
+
+    client.slash_match_stake(&slasher, &match_id, &player_b, &1000);
+    client.refund(&match_id);
+
+    let token_client = SdkTokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&player_a), 1000);
+    assert_eq!(token_client.balance(&player_b), 0);
+    assert_eq!(token_client.balance(&treasury), 1000);
+}
+
+#[test]
+#[should_panic(expected = "not a slasher")]
+fn test_slash_match_stake_by_non_slasher_fails() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let (match_id, _) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+
+    client.slash_match_stake(&Address::generate(&env), &match_id, &player_a, &100);
+}
+
+#[test]
+#[should_panic(expected = "slash exceeds stake")]
+fn test_slash_match_stake_cannot_take_other_players_stake() {
+    let (env, admin, player_a, player_b, treasury) = create_test_env();
+    let contract_id = initialize_contract(&env, &admin);
+    let client = MatchEscrowVaultClient::new(&env, &contract_id);
+
+    let (match_id, _) = setup_escrow_with_deposits(
+        &env,
+        &contract_id,
+        &admin,
+        &player_a,
+        &player_b,
+        &treasury,
+        1000,
+    );
+    let slasher = Address::generate(&env);
+    client.set_slasher(&slasher, &true);
+
+    client.slash_match_stake(&slasher, &match_id, &player_a, &600);
+    client.slash_match_stake(&slasher, &match_id, &player_a, &600);
+}
+
 #[test]
 fn test_emergency_withdraw_success() {
     let (env, admin, player_a, player_b, treasury) = create_test_env();
@@ -926,7 +1209,7 @@ fn test_emergency_withdraw_partial_deposits() {
     env.mock_all_auths();
 
     mint_tokens(&env, &token, &admin, &player_a, 1000);
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
     client.deposit(&match_id, &player_a);
 
     let emergency_recipient = Address::generate(&env);
@@ -949,7 +1232,7 @@ fn test_reentrancy_guard_released_after_deposit() {
 
     mint_tokens(&env, &token, &admin, &player_a, 2000);
     mint_tokens(&env, &token, &admin, &player_b, 1000);
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
 
     client.deposit(&match_id, &player_a);
     client.deposit(&match_id, &player_b);
@@ -976,7 +1259,7 @@ fn test_full_lifecycle_happy_path() {
     mint_tokens(&env, &token, &admin, &player_a, amount);
     mint_tokens(&env, &token, &admin, &player_b, amount);
 
-    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &amount, &token, &admin);
     assert_eq!(
         client.get_escrow_state(&match_id),
         EscrowState::AwaitingDeposits as u32
@@ -1094,8 +1377,8 @@ fn test_multiple_escrows_independent() {
     mint_tokens(&env, &token, &admin, &player_a, 3000);
     mint_tokens(&env, &token, &admin, &player_b, 3000);
 
-    client.create_escrow(&match_id_1, &player_a, &player_b, &1000, &token);
-    client.create_escrow(&match_id_2, &player_a, &player_b, &500, &token);
+    client.create_escrow(&match_id_1, &player_a, &player_b, &1000, &token, &admin);
+    client.create_escrow(&match_id_2, &player_a, &player_b, &500, &token, &admin);
 
     client.deposit(&match_id_1, &player_a);
     client.deposit(&match_id_1, &player_b);
@@ -1133,7 +1416,14 @@ fn test_large_amounts() {
     mint_tokens(&env, &token, &admin, &player_a, large_amount);
     mint_tokens(&env, &token, &admin, &player_b, large_amount);
 
-    client.create_escrow(&match_id, &player_a, &player_b, &large_amount, &token);
+    client.create_escrow(
+        &match_id,
+        &player_a,
+        &player_b,
+        &large_amount,
+        &token,
+        &admin,
+    );
     client.deposit(&match_id, &player_a);
     client.deposit(&match_id, &player_b);
     client.lock_funds(&match_id);
@@ -1181,7 +1471,7 @@ fn test_view_functions() {
     client.set_treasury(&treasury);
 
     assert!(!client.escrow_exists(&match_id));
-    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token);
+    client.create_escrow(&match_id, &player_a, &player_b, &1000, &token, &admin);
     assert!(client.escrow_exists(&match_id));
     assert!(!client.escrow_exists(&nonexistent_match));
     assert_eq!(
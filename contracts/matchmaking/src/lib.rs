@@ -0,0 +1,333 @@
+#![no_std]
+
+//! Matchmaking queue.
+//!
+//! The admin defines stake tiers (asset, stake amount and the game, region and mode
+//! of the resulting match). A player joins one tier's queue by committing its stake,
+//! which this contract holds. A matcher pairs two players queued in the same tier;
+//! the pairing creates the match in the Match Contract and its escrow in the Match
+//! Escrow Vault, and funds both stakes, in one transaction. Players can leave the
+//! queue at any time, and anyone can refund a ticket once its queue timeout passes.
+
+use arenax_events::matchmaking as events;
+use arenax_token_interface as token;
+use cross_contract_utils::staked_match::{self, StakedMatch};
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Symbol, Vec};
+
+/// Upper bound on players waiting in a single tier.
+pub const MAX_QUEUE_SIZE: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    MatchContract,
+    EscrowVault,
+    Matcher(Address),
+    NextTierId,
+    NextMatchNonce,
+    Tier(u32),
+    Queue(u32),      // tier_id -> Vec<Address>
+    Ticket(Address), // player -> QueueTicket
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakeTier {
+    pub asset: Address,
+    /// Stake each player commits.
+    pub stake: i128,
+    pub game_id: Symbol,
+    pub region: Symbol,
+    pub mode: Symbol,
+    /// Seconds a player waits before their ticket can be refunded.
+    pub queue_timeout: u64,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueueTicket {
+    pub tier_id: u32,
+    pub stake: i128,
+    pub joined_at: u64,
+    pub expires_at: u64,
+}
+
+#[contract]
+pub struct MatchmakingContract;
+
+#[contractimpl]
+impl MatchmakingContract {
+    /// This contract must be a registered creator in both `match_contract` and
+    /// `escrow_vault`.
+    pub fn initialize(env: Env, admin: Address, match_contract: Address, escrow_vault: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MatchContract, &match_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::EscrowVault, &escrow_vault);
+        env.storage().instance().set(&DataKey::NextTierId, &1u32);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextMatchNonce, &0u64);
+    }
+
+    /// Allow or revoke a matcher (admin only).
+    pub fn set_matcher(env: Env, matcher: Address, enabled: bool) {
+        Self::require_admin(&env);
+        if enabled {
+            env.storage()
+                .instance()
+                .set(&DataKey::Matcher(matcher.clone()), &true);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&DataKey::Matcher(matcher.clone()));
+        }
+        events::emit_matcher_updated(&env, &matcher, enabled);
+    }
+
+    /// Add a stake tier (admin only). Returns its id.
+    pub fn add_tier(env: Env, tier: StakeTier) -> u32 {
+        Self::require_admin(&env);
+        if tier.stake <= 0 {
+            panic!("stake must be positive");
+        }
+        if tier.queue_timeout == 0 {
+            panic!("invalid queue timeout");
+        }
+
+        let tier_id: u32 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTierId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTierId, &(tier_id + 1));
+        env.storage()
+            .persistent()
+            .set(&DataKey::Tier(tier_id), &tier);
+
+        events::emit_tier_added(&env, tier_id, &tier.asset, tier.stake);
+        tier_id
+    }
+
+    /// Open or close a tier to new players (admin only). Queued players can still
+    /// be paired or refunded.
+    pub fn set_tier_active(env: Env, tier_id: u32, active: bool) {
+        Self::require_admin(&env);
+        let mut tier = Self::get_tier(env.clone(), tier_id);
+        tier.active = active;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Tier(tier_id), &tier);
+        events::emit_tier_active_set(&env, tier_id, active);
+    }
+
+    /// Join `tier_id`'s queue, transferring the tier's stake to this contract. A
+    /// player waits in one queue at a time.
+    pub fn join(env: Env, player: Address, tier_id: u32) {
+        player.require_auth();
+
+        let tier = Self::get_tier(env.clone(), tier_id);
+        if !tier.active {
+            panic!("tier not active");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Ticket(player.clone()))
+        {
+            panic!("already queued");
+        }
+        let mut queue = Self::get_queue(env.clone(), tier_id);
+        if queue.len() >= MAX_QUEUE_SIZE {
+            panic!("queue full");
+        }
+
+        token::Client::new(&env, &tier.asset).transfer(
+            &player,
+            &env.current_contract_address(),
+            &tier.stake,
+        );
+
+        let now = env.ledger().timestamp();
+        let ticket = QueueTicket {
+            tier_id,
+            stake: tier.stake,
+            joined_at: now,
+            expires_at: now + tier.queue_timeout,
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Ticket(player.clone()), &ticket);
+        queue.push_back(player.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Queue(tier_id), &queue);
+
+        events::emit_queue_joined(&env, &player, tier_id, tier.stake, ticket.expires_at);
+    }
+
+    /// Leave the queue and get the stake back.
+    pub fn leave(env: Env, player: Address) -> i128 {
+        player.require_auth();
+        Self::refund_ticket(&env, &player, false)
+    }
+
+    /// Refund a ticket whose queue timeout has passed. Callable by anyone.
+    pub fn refund_expired(env: Env, player: Address) -> i128 {
+        let ticket = Self::get_ticket(env.clone(), player.clone()).expect("not queued");
+        if env.ledger().timestamp() < ticket.expires_at {
+            panic!("ticket not expired");
+        }
+        Self::refund_ticket(&env, &player, true)
+    }
+
+    /// Pair two players queued in the same tier (matchers only). Creates the match
+    /// and its escrow, funds both stakes, and returns the match id.
+    pub fn pair(env: Env, matcher: Address, player_a: Address, player_b: Address) -> BytesN<32> {
+        matcher.require_auth();
+        if !Self::is_matcher(env.clone(), matcher.clone()) {
+            panic!("not a matcher");
+        }
+        if player_a == player_b {
+            panic!("players must be different");
+        }
+
+        let ticket_a = Self::get_ticket(env.clone(), player_a.clone()).expect("player not queued");
+        let ticket_b = Self::get_ticket(env.clone(), player_b.clone()).expect("player not queued");
+        if ticket_a.tier_id != ticket_b.tier_id {
+            panic!("players in different tiers");
+        }
+        let now = env.ledger().timestamp();
+        if now >= ticket_a.expires_at || now >= ticket_b.expires_at {
+            panic!("ticket expired");
+        }
+
+        let tier_id = ticket_a.tier_id;
+        let tier = Self::get_tier(env.clone(), tier_id);
+        Self::dequeue(&env, &player_a, tier_id);
+        Self::dequeue(&env, &player_b, tier_id);
+
+        let match_id = Self::next_match_id(&env);
+        let (match_contract, escrow_vault) = Self::match_contracts(&env);
+        staked_match::open_staked_match(
+            &env,
+            &match_contract,
+            &escrow_vault,
+            &StakedMatch {
+                match_id: match_id.clone(),
+                player_a: player_a.clone(),
+                player_b: player_b.clone(),
+                game_id: tier.game_id,
+                region: tier.region,
+                mode: tier.mode,
+                asset: tier.asset,
+                stake: tier.stake,
+            },
+        );
+
+        events::emit_players_paired(&env, &match_id, tier_id, &player_a, &player_b, &matcher);
+        match_id
+    }
+
+    pub fn get_tier(env: Env, tier_id: u32) -> StakeTier {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tier(tier_id))
+            .expect("tier not found")
+    }
+
+    /// Players waiting in `tier_id`, oldest first.
+    pub fn get_queue(env: Env, tier_id: u32) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Queue(tier_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_ticket(env: Env, player: Address) -> Option<QueueTicket> {
+        env.storage().persistent().get(&DataKey::Ticket(player))
+    }
+
+    pub fn is_matcher(env: Env, matcher: Address) -> bool {
+        env.storage().instance().has(&DataKey::Matcher(matcher))
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn refund_ticket(env: &Env, player: &Address, expired: bool) -> i128 {
+        let ticket = Self::get_ticket(env.clone(), player.clone()).expect("not queued");
+        let tier = Self::get_tier(env.clone(), ticket.tier_id);
+        Self::dequeue(env, player, ticket.tier_id);
+
+        token::Client::new(env, &tier.asset).transfer(
+            &env.current_contract_address(),
+            player,
+            &ticket.stake,
+        );
+
+        events::emit_queue_left(env, player, ticket.tier_id, ticket.stake, expired);
+        ticket.stake
+    }
+
+    fn dequeue(env: &Env, player: &Address, tier_id: u32) {
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Ticket(player.clone()));
+        let mut queue = Self::get_queue(env.clone(), tier_id);
+        if let Some(index) = queue.first_index_of(player) {
+            queue.remove(index);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Queue(tier_id), &queue);
+    }
+
+    fn next_match_id(env: &Env) -> BytesN<32> {
+        let nonce: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextMatchNonce)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextMatchNonce, &(nonce + 1));
+        staked_match::next_match_id(env, nonce)
+    }
+
+    fn match_contracts(env: &Env) -> (Address, Address) {
+        let match_contract = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchContract)
+            .expect("not initialized");
+        let escrow_vault = env
+            .storage()
+            .instance()
+            .get(&DataKey::EscrowVault)
+            .expect("not initialized");
+        (match_contract, escrow_vault)
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,188 @@
+#![cfg(test)]
+
+use super::*;
+use match_contract::{GameConfig, MatchContract, MatchContractClient, MatchError};
+use match_escrow_vault::{EscrowState, MatchEscrowVault, MatchEscrowVaultClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const STAKE: i128 = 100;
+const TIMEOUT: u64 = 600;
+
+struct Setup<'a> {
+    env: Env,
+    matcher: Address,
+    client: MatchmakingContractClient<'a>,
+    matches: MatchContractClient<'a>,
+    escrow: MatchEscrowVaultClient<'a>,
+    token: TokenClient<'a>,
+    minter: StellarAssetClient<'a>,
+    tier_id: u32,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let matcher = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+
+    let matches = MatchContractClient::new(&env, &env.register(MatchContract, ()));
+    matches.initialize(&admin);
+    matches.register_game(
+        &symbol_short!("chess"),
+        &GameConfig {
+            max_score: 1,
+            round_count: 1,
+            modes: Vec::new(&env),
+        },
+    );
+
+    let escrow = MatchEscrowVaultClient::new(&env, &env.register(MatchEscrowVault, ()));
+    escrow.initialize(&admin);
+
+    let contract_id = env.register(MatchmakingContract, ());
+    let client = MatchmakingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &matches.address, &escrow.address);
+    matches.set_creator(&contract_id, &true);
+    escrow.set_creator(&contract_id, &true);
+    client.set_matcher(&matcher, &true);
+    let tier_id = client.add_tier(&tier(&sac.address()));
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        minter: StellarAssetClient::new(&env, &sac.address()),
+        env,
+        matcher,
+        client,
+        matches,
+        escrow,
+        tier_id,
+    }
+}
+
+fn tier(asset: &Address) -> StakeTier {
+    StakeTier {
+        asset: asset.clone(),
+        stake: STAKE,
+        game_id: symbol_short!("chess"),
+        region: symbol_short!("eu"),
+        mode: symbol_short!("ranked"),
+        queue_timeout: TIMEOUT,
+        active: true,
+    }
+}
+
+fn queued_player(s: &Setup, tier_id: u32) -> Address {
+    let player = Address::generate(&s.env);
+    s.minter.mint(&player, &1_000);
+    s.client.join(&player, &tier_id);
+    player
+}
+
+#[test]
+fn test_pair_creates_funded_match() {
+    let s = setup();
+    let a = queued_player(&s, s.tier_id);
+    let b = queued_player(&s, s.tier_id);
+    assert_eq!(s.client.get_queue(&s.tier_id).len(), 2);
+    assert_eq!(s.token.balance(&s.client.address), 2 * STAKE);
+
+    let match_id = s.client.pair(&s.matcher, &a, &b);
+
+    let created = s.matches.get_match(&match_id);
+    assert_eq!(created.player_a, a);
+    assert_eq!(created.mode, symbol_short!("ranked"));
+
+    let escrow = s.escrow.get_escrow(&match_id);
+    assert_eq!(escrow.state, EscrowState::FullyFunded as u32);
+    assert_eq!(escrow.amount, STAKE);
+    assert_eq!(s.token.balance(&s.escrow.address), 2 * STAKE);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+
+    assert!(s.client.get_queue(&s.tier_id).is_empty());
+    assert_eq!(s.client.get_ticket(&a), None);
+
+    // Match ids are unique per pairing.
+    let c = queued_player(&s, s.tier_id);
+    let d = queued_player(&s, s.tier_id);
+    assert_ne!(s.client.pair(&s.matcher, &c, &d), match_id);
+}
+
+#[test]
+fn test_next_match_id_cannot_be_front_run() {
+    let s = setup();
+    let a = queued_player(&s, s.tier_id);
+    let b = queued_player(&s, s.tier_id);
+
+    // The first pairing's id is predictable from this contract's address.
+    let next_id = s
+        .env
+        .as_contract(&s.client.address, || staked_match::next_match_id(&s.env, 0));
+
+    let attacker = Address::generate(&s.env);
+    let created = s.matches.try_create_match(
+        &next_id,
+        &a,
+        &b,
+        &symbol_short!("chess"),
+        &symbol_short!("eu"),
+        &symbol_short!("ranked"),
+        &attacker,
+    );
+    assert_eq!(created, Err(Ok(MatchError::Unauthorized)));
+    let escrowed =
+        s.escrow
+            .try_create_escrow(&next_id, &a, &b, &STAKE, &s.token.address, &attacker);
+    assert!(escrowed.is_err());
+
+    assert_eq!(s.client.pair(&s.matcher, &a, &b), next_id);
+}
+
+#[test]
+fn test_pairing_rules() {
+    let s = setup();
+    let other_tier = s.client.add_tier(&tier(&s.token.address));
+    let a = queued_player(&s, s.tier_id);
+    let b = queued_player(&s, other_tier);
+    let c = queued_player(&s, s.tier_id);
+
+    assert!(s.client.try_pair(&s.matcher, &a, &b).is_err());
+    assert!(s.client.try_pair(&s.matcher, &a, &a).is_err());
+    assert!(s.client.try_pair(&a, &a, &c).is_err());
+
+    s.env.ledger().set_timestamp(1_000 + TIMEOUT);
+    assert!(s.client.try_pair(&s.matcher, &a, &c).is_err());
+}
+
+#[test]
+fn test_leave_and_timeout_refunds() {
+    let s = setup();
+    let a = queued_player(&s, s.tier_id);
+    assert!(s.client.try_join(&a, &s.tier_id).is_err());
+
+    assert_eq!(s.client.leave(&a), STAKE);
+    assert_eq!(s.token.balance(&a), 1_000);
+    assert!(s.client.try_leave(&a).is_err());
+
+    let b = queued_player(&s, s.tier_id);
+    assert!(s.client.try_refund_expired(&b).is_err());
+    s.env.ledger().set_timestamp(1_000 + TIMEOUT);
+    assert_eq!(s.client.refund_expired(&b), STAKE);
+    assert_eq!(s.token.balance(&b), 1_000);
+    assert!(s.client.get_queue(&s.tier_id).is_empty());
+}
+
+#[test]
+#[should_panic(expected = "tier not active")]
+fn test_closed_tier_rejects_players() {
+    let s = setup();
+    s.client.set_tier_active(&s.tier_id, &false);
+    queued_player(&s, s.tier_id);
+}
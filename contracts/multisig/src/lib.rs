@@ -0,0 +1,320 @@
+#![no_std]
+
+//! M-of-N multisig wallet.
+//!
+//! Installed as the admin of the registry, tokens and vaults in place of a single
+//! key. A signer submits an arbitrary cross-contract call, other signers approve it,
+//! and once the threshold is reached anyone can execute it; the call is made by this
+//! contract, which satisfies the target's `admin.require_auth()`.
+//!
+//! Signer rotation goes through the same flow: a transaction targeting this contract
+//! with `add_signer`, `remove_signer` or `set_threshold` is applied directly on
+//! execution, since a contract cannot call itself.
+
+use arenax_events::multisig as events;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, Env, Symbol, TryFromVal, Val, Vec,
+};
+
+/// Upper bound on signers.
+pub const MAX_SIGNERS: u32 = 20;
+
+/// Seconds a submitted transaction stays executable.
+pub const TX_TTL: u64 = 7 * 24 * 60 * 60;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Signers,
+    Threshold,
+    NextTxId,
+    Transaction(u64),
+    Approvals(u64), // tx_id -> Vec<Address>
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TxState {
+    Pending = 0,
+    Executed = 1,
+    Cancelled = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transaction {
+    pub tx_id: u64,
+    pub proposer: Address,
+    pub target: Address,
+    pub function: Symbol,
+    pub args: Vec<Val>,
+    pub state: u32,
+    pub created_at: u64,
+    pub expires_at: u64,
+}
+
+#[contract]
+pub struct MultisigWallet;
+
+#[contractimpl]
+impl MultisigWallet {
+    pub fn initialize(env: Env, signers: Vec<Address>, threshold: u32) {
+        if env.storage().instance().has(&DataKey::Signers) {
+            panic!("already initialized");
+        }
+        if signers.is_empty() || signers.len() > MAX_SIGNERS {
+            panic!("invalid signer count");
+        }
+        for i in 0..signers.len() {
+            let signer = signers.get(i).unwrap();
+            if signers.slice(i + 1..).contains(&signer) {
+                panic!("duplicate signer");
+            }
+        }
+        if threshold == 0 || threshold > signers.len() {
+            panic!("invalid threshold");
+        }
+
+        env.storage().instance().set(&DataKey::Signers, &signers);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        env.storage().instance().set(&DataKey::NextTxId, &1u64);
+    }
+
+    /// Submit a call for approval (signers only). The proposer's approval is recorded.
+    pub fn submit(
+        env: Env,
+        proposer: Address,
+        target: Address,
+        function: Symbol,
+        args: Vec<Val>,
+    ) -> u64 {
+        proposer.require_auth();
+        Self::require_signer(&env, &proposer);
+
+        let tx_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTxId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTxId, &(tx_id + 1));
+
+        let now = env.ledger().timestamp();
+        let tx = Transaction {
+            tx_id,
+            proposer: proposer.clone(),
+            target: target.clone(),
+            function: function.clone(),
+            args,
+            state: TxState::Pending as u32,
+            created_at: now,
+            expires_at: now + TX_TTL,
+        };
+        Self::save_transaction(&env, &tx);
+        events::emit_transaction_submitted(
+            &env,
+            tx_id,
+            &proposer,
+            &target,
+            &function,
+            tx.expires_at,
+        );
+
+        Self::record_approval(&env, tx_id, &proposer);
+        tx_id
+    }
+
+    /// Approve a pending transaction (signers only).
+    pub fn approve(env: Env, signer: Address, tx_id: u64) {
+        signer.require_auth();
+        Self::require_signer(&env, &signer);
+        Self::require_pending(&env, tx_id);
+        Self::record_approval(&env, tx_id, &signer);
+    }
+
+    /// Withdraw an approval from a pending transaction.
+    pub fn revoke(env: Env, signer: Address, tx_id: u64) {
+        signer.require_auth();
+        Self::require_pending(&env, tx_id);
+
+        let mut approvals = Self::get_approvals(env.clone(), tx_id);
+        let index = approvals.first_index_of(&signer).expect("not approved");
+        approvals.remove(index);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approvals(tx_id), &approvals);
+
+        events::emit_approval_revoked(&env, tx_id, &signer, approvals.len());
+    }
+
+    /// Execute a transaction that has reached the threshold. Only approvals from
+    /// current signers count. Returns the call's result.
+    pub fn execute(env: Env, executor: Address, tx_id: u64) -> Val {
+        executor.require_auth();
+
+        let mut tx = Self::require_pending(&env, tx_id);
+        if Self::approval_count(env.clone(), tx_id) < Self::get_threshold(env.clone()) {
+            panic!("insufficient approvals");
+        }
+
+        tx.state = TxState::Executed as u32;
+        Self::save_transaction(&env, &tx);
+
+        let result = if tx.target == env.current_contract_address() {
+            Self::apply_self_call(&env, &tx.function, &tx.args);
+            Val::VOID.into()
+        } else {
+            env.invoke_contract::<Val>(&tx.target, &tx.function, tx.args.clone())
+        };
+
+        events::emit_transaction_executed(&env, tx_id, &executor, &tx.target, &tx.function);
+        result
+    }
+
+    /// Cancel a pending transaction (its proposer only).
+    pub fn cancel(env: Env, proposer: Address, tx_id: u64) {
+        proposer.require_auth();
+
+        let mut tx = Self::get_transaction(env.clone(), tx_id);
+        if tx.proposer != proposer {
+            panic!("only the proposer can cancel");
+        }
+        if tx.state != TxState::Pending as u32 {
+            panic!("transaction is not pending");
+        }
+
+        tx.state = TxState::Cancelled as u32;
+        Self::save_transaction(&env, &tx);
+        events::emit_transaction_cancelled(&env, tx_id, &proposer);
+    }
+
+    pub fn get_transaction(env: Env, tx_id: u64) -> Transaction {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Transaction(tx_id))
+            .expect("transaction not found")
+    }
+
+    pub fn get_approvals(env: Env, tx_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Approvals(tx_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Approvals on `tx_id` from addresses that are still signers.
+    pub fn approval_count(env: Env, tx_id: u64) -> u32 {
+        let signers = Self::get_signers(env.clone());
+        let mut count = 0;
+        for approver in Self::get_approvals(env, tx_id).iter() {
+            if signers.contains(&approver) {
+                count += 1;
+            }
+        }
+        count
+    }
+
+    pub fn get_signers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Signers)
+            .expect("not initialized")
+    }
+
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .expect("not initialized")
+    }
+
+    pub fn is_signer(env: Env, addr: Address) -> bool {
+        Self::get_signers(env).contains(&addr)
+    }
+
+    fn require_signer(env: &Env, addr: &Address) {
+        if !Self::is_signer(env.clone(), addr.clone()) {
+            panic!("not a signer");
+        }
+    }
+
+    fn require_pending(env: &Env, tx_id: u64) -> Transaction {
+        let tx = Self::get_transaction(env.clone(), tx_id);
+        if tx.state != TxState::Pending as u32 {
+            panic!("transaction is not pending");
+        }
+        if env.ledger().timestamp() >= tx.expires_at {
+            panic!("transaction expired");
+        }
+        tx
+    }
+
+    fn record_approval(env: &Env, tx_id: u64, signer: &Address) {
+        let mut approvals = Self::get_approvals(env.clone(), tx_id);
+        if approvals.contains(signer) {
+            panic!("already approved");
+        }
+        approvals.push_back(signer.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approvals(tx_id), &approvals);
+
+        events::emit_transaction_approved(
+            env,
+            tx_id,
+            signer,
+            Self::approval_count(env.clone(), tx_id),
+            Self::get_threshold(env.clone()),
+        );
+    }
+
+    fn apply_self_call(env: &Env, function: &Symbol, args: &Vec<Val>) {
+        let arg = |index: u32| args.get(index).expect("missing argument");
+        let mut signers = Self::get_signers(env.clone());
+        let threshold = Self::get_threshold(env.clone());
+
+        if *function == Symbol::new(env, "add_signer") {
+            let signer = Address::try_from_val(env, &arg(0)).expect("invalid argument");
+            if signers.contains(&signer) {
+                panic!("already a signer");
+            }
+            if signers.len() >= MAX_SIGNERS {
+                panic!("too many signers");
+            }
+            signers.push_back(signer.clone());
+            env.storage().instance().set(&DataKey::Signers, &signers);
+            events::emit_signer_added(env, &signer, signers.len());
+        } else if *function == Symbol::new(env, "remove_signer") {
+            let signer = Address::try_from_val(env, &arg(0)).expect("invalid argument");
+            let index = signers.first_index_of(&signer).expect("not a signer");
+            if signers.len() - 1 < threshold {
+                panic!("threshold exceeds signers");
+            }
+            signers.remove(index);
+            env.storage().instance().set(&DataKey::Signers, &signers);
+            events::emit_signer_removed(env, &signer, signers.len());
+        } else if *function == Symbol::new(env, "set_threshold") {
+            let new = u32::try_from_val(env, &arg(0)).expect("invalid argument");
+            if new == 0 || new > signers.len() {
+                panic!("invalid threshold");
+            }
+            env.storage().instance().set(&DataKey::Threshold, &new);
+            events::emit_threshold_changed(env, threshold, new);
+        } else {
+            panic!("unsupported self call");
+        }
+    }
+
+    fn save_transaction(env: &Env, tx: &Transaction) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Transaction(tx.tx_id), tx);
+    }
+}
+
+#[cfg(test)]
+mod test;
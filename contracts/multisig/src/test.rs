@@ -0,0 +1,192 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    vec, Address, Env, IntoVal,
+};
+
+/// An admin-gated contract such as the registry or a vault.
+#[contract]
+pub struct MockAdminTarget;
+
+#[contractimpl]
+impl MockAdminTarget {
+    pub fn init(env: Env, admin: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+    }
+
+    pub fn set_value(env: Env, value: u32) -> u32 {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("value"), &value);
+        value * 2
+    }
+
+    pub fn value(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("value"))
+            .unwrap_or(0)
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    signers: Vec<Address>,
+    client: MultisigWalletClient<'a>,
+    target: MockAdminTargetClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let signers = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    let contract_id = env.register(MultisigWallet, ());
+    let client = MultisigWalletClient::new(&env, &contract_id);
+    client.initialize(&signers, &2);
+
+    let target_id = env.register(MockAdminTarget, ());
+    let target = MockAdminTargetClient::new(&env, &target_id);
+    target.init(&contract_id);
+
+    Setup {
+        env,
+        signers,
+        client,
+        target,
+    }
+}
+
+fn signer(s: &Setup, index: u32) -> Address {
+    s.signers.get(index).unwrap()
+}
+
+fn submit_set_value(s: &Setup, value: u32) -> u64 {
+    s.client.submit(
+        &signer(s, 0),
+        &s.target.address,
+        &Symbol::new(&s.env, "set_value"),
+        &(value,).into_val(&s.env),
+    )
+}
+
+fn submit_self_call(s: &Setup, function: &str, args: Vec<Val>) -> u64 {
+    s.client.submit(
+        &signer(s, 0),
+        &s.client.address,
+        &Symbol::new(&s.env, function),
+        &args,
+    )
+}
+
+#[test]
+fn test_threshold_approval_executes_call() {
+    let s = setup();
+    let tx_id = submit_set_value(&s, 21);
+    assert_eq!(s.client.approval_count(&tx_id), 1);
+
+    let executor = Address::generate(&s.env);
+    assert!(s.client.try_execute(&executor, &tx_id).is_err());
+
+    s.client.approve(&signer(&s, 1), &tx_id);
+    let result = s.client.execute(&executor, &tx_id);
+    assert_eq!(u32::try_from_val(&s.env, &result).unwrap(), 42);
+    assert_eq!(s.target.value(), 21);
+    assert_eq!(
+        s.client.get_transaction(&tx_id).state,
+        TxState::Executed as u32
+    );
+    assert!(s.client.try_execute(&executor, &tx_id).is_err());
+}
+
+#[test]
+fn test_approval_rules() {
+    let s = setup();
+    let tx_id = submit_set_value(&s, 1);
+    let outsider = Address::generate(&s.env);
+
+    assert!(s.client.try_approve(&outsider, &tx_id).is_err());
+    assert!(s.client.try_approve(&signer(&s, 0), &tx_id).is_err());
+
+    s.client.approve(&signer(&s, 1), &tx_id);
+    s.client.revoke(&signer(&s, 1), &tx_id);
+    assert_eq!(s.client.approval_count(&tx_id), 1);
+    assert!(s.client.try_execute(&signer(&s, 0), &tx_id).is_err());
+
+    // Only the proposer can cancel.
+    assert!(s.client.try_cancel(&signer(&s, 1), &tx_id).is_err());
+    s.client.cancel(&signer(&s, 0), &tx_id);
+    assert!(s.client.try_approve(&signer(&s, 1), &tx_id).is_err());
+}
+
+#[test]
+fn test_transactions_expire() {
+    let s = setup();
+    let tx_id = submit_set_value(&s, 1);
+    s.env.ledger().set_timestamp(1_000 + TX_TTL);
+    assert!(s.client.try_approve(&signer(&s, 1), &tx_id).is_err());
+}
+
+#[test]
+fn test_signer_rotation() {
+    let s = setup();
+    let newcomer = Address::generate(&s.env);
+    let departing = signer(&s, 2);
+
+    let add = submit_self_call(&s, "add_signer", (newcomer.clone(),).into_val(&s.env));
+    s.client.approve(&signer(&s, 1), &add);
+    s.client.execute(&signer(&s, 0), &add);
+    assert!(s.client.is_signer(&newcomer));
+
+    let remove = submit_self_call(&s, "remove_signer", (departing.clone(),).into_val(&s.env));
+    s.client.approve(&newcomer, &remove);
+    s.client.execute(&signer(&s, 0), &remove);
+    assert!(!s.client.is_signer(&departing));
+    assert_eq!(s.client.get_signers().len(), 3);
+
+    let raise = submit_self_call(&s, "set_threshold", (3u32,).into_val(&s.env));
+    s.client.approve(&newcomer, &raise);
+    s.client.execute(&signer(&s, 0), &raise);
+    assert_eq!(s.client.get_threshold(), 3);
+}
+
+#[test]
+fn test_removed_signer_approvals_do_not_count() {
+    let s = setup();
+    let departing = signer(&s, 2);
+    let tx_id = submit_set_value(&s, 5);
+    s.client.approve(&departing, &tx_id);
+
+    let remove = submit_self_call(&s, "remove_signer", (departing.clone(),).into_val(&s.env));
+    s.client.approve(&signer(&s, 1), &remove);
+    s.client.execute(&signer(&s, 0), &remove);
+
+    assert_eq!(s.client.approval_count(&tx_id), 1);
+    assert!(s.client.try_execute(&signer(&s, 0), &tx_id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "invalid threshold")]
+fn test_threshold_bounded_by_signers() {
+    let s = setup();
+    let tx_id = submit_self_call(&s, "set_threshold", (4u32,).into_val(&s.env));
+    s.client.approve(&signer(&s, 1), &tx_id);
+    s.client.execute(&signer(&s, 0), &tx_id);
+}
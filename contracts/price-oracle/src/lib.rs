@@ -0,0 +1,220 @@
+#![no_std]
+
+//! Price oracle adapter.
+//!
+//! Authorized reporters post the rate of `base` in units of `quote`, scaled by
+//! `RATE_SCALE`. Reads take the median of every current reporter's latest rate that
+//! is no older than the staleness window, and fail if fewer than `min_reports` such
+//! rates exist. The escrow vault and prize pools use this to accept and value stakes
+//! in assets other than AX.
+
+use arenax_events::price_oracle as events;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Map, Vec};
+
+/// Fixed-point scale of reported rates (7 decimals, matching Stellar assets).
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// Upper bound on authorized reporters.
+pub const MAX_REPORTERS: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Config,
+    Reporters,
+    Submissions(Address, Address), // (base, quote) -> Map<reporter, Submission>
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct OracleConfig {
+    /// Default maximum age, in seconds, of a rate used by `get_rate`.
+    pub max_staleness: u64,
+    /// Fresh reports required before a rate is served.
+    pub min_reports: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Submission {
+    pub rate: i128,
+    pub timestamp: u64,
+}
+
+#[contract]
+pub struct PriceOracle;
+
+#[contractimpl]
+impl PriceOracle {
+    pub fn initialize(env: Env, admin: Address, config: OracleConfig) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &Vec::<Address>::new(&env));
+        Self::write_config(&env, &config);
+    }
+
+    pub fn set_config(env: Env, config: OracleConfig) {
+        Self::require_admin(&env);
+        Self::write_config(&env, &config);
+    }
+
+    /// Allow or revoke a reporter (admin only). A revoked reporter's past
+    /// submissions no longer count towards the median.
+    pub fn set_reporter(env: Env, reporter: Address, enabled: bool) {
+        Self::require_admin(&env);
+
+        let mut reporters = Self::get_reporters(env.clone());
+        match reporters.first_index_of(&reporter) {
+            Some(_) if enabled => {}
+            Some(index) => reporters.remove(index).unwrap(),
+            None if enabled => {
+                if reporters.len() >= MAX_REPORTERS {
+                    panic!("too many reporters");
+                }
+                reporters.push_back(reporter.clone());
+            }
+            None => {}
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Reporters, &reporters);
+
+        events::emit_reporter_updated(&env, &reporter, enabled);
+    }
+
+    /// Post the rate of one unit of `base` in `quote`, scaled by `RATE_SCALE`
+    /// (reporters only). Replaces the reporter's previous rate for the pair.
+    pub fn report(env: Env, reporter: Address, base: Address, quote: Address, rate: i128) {
+        reporter.require_auth();
+        if !Self::is_reporter(env.clone(), reporter.clone()) {
+            panic!("not a reporter");
+        }
+        if base == quote {
+            panic!("base and quote must differ");
+        }
+        if rate <= 0 {
+            panic!("rate must be positive");
+        }
+
+        let timestamp = env.ledger().timestamp();
+        let mut submissions = Self::get_submissions(env.clone(), base.clone(), quote.clone());
+        submissions.set(reporter.clone(), Submission { rate, timestamp });
+        env.storage().persistent().set(
+            &DataKey::Submissions(base.clone(), quote.clone()),
+            &submissions,
+        );
+
+        events::emit_rate_reported(&env, &base, &quote, &reporter, rate, timestamp);
+    }
+
+    /// Median rate of `base` in `quote` within the configured staleness window.
+    pub fn get_rate(env: Env, base: Address, quote: Address) -> i128 {
+        let max_staleness = Self::get_config(env.clone()).max_staleness;
+        Self::get_rate_with_staleness(env, base, quote, max_staleness)
+    }
+
+    /// Median rate of `base` in `quote`, counting only reports at most `max_age`
+    /// seconds old.
+    pub fn get_rate_with_staleness(env: Env, base: Address, quote: Address, max_age: u64) -> i128 {
+        if base == quote {
+            return RATE_SCALE;
+        }
+
+        let now = env.ledger().timestamp();
+        let submissions = Self::get_submissions(env.clone(), base, quote);
+        let mut rates: Vec<i128> = Vec::new(&env);
+        for reporter in Self::get_reporters(env.clone()).iter() {
+            if let Some(submission) = submissions.get(reporter) {
+                if now.saturating_sub(submission.timestamp) <= max_age {
+                    Self::insert_sorted(&mut rates, submission.rate);
+                }
+            }
+        }
+
+        let count = rates.len();
+        if count == 0 {
+            panic!("stale rate");
+        }
+        if count < Self::get_config(env.clone()).min_reports {
+            panic!("insufficient reports");
+        }
+
+        let mid = count / 2;
+        if count % 2 == 1 {
+            rates.get(mid).unwrap()
+        } else {
+            (rates.get(mid - 1).unwrap() + rates.get(mid).unwrap()) / 2
+        }
+    }
+
+    /// Value `amount` of `base` in `quote` at the current rate.
+    pub fn convert(env: Env, base: Address, quote: Address, amount: i128) -> i128 {
+        amount * Self::get_rate(env, base, quote) / RATE_SCALE
+    }
+
+    /// Latest submission per reporter for the pair, including revoked reporters.
+    pub fn get_submissions(env: Env, base: Address, quote: Address) -> Map<Address, Submission> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Submissions(base, quote))
+            .unwrap_or(Map::new(&env))
+    }
+
+    pub fn get_reporters(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Reporters)
+            .expect("not initialized")
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        Self::get_reporters(env).contains(&reporter)
+    }
+
+    pub fn get_config(env: Env) -> OracleConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn insert_sorted(rates: &mut Vec<i128>, rate: i128) {
+        let index = rates
+            .iter()
+            .position(|r| r > rate)
+            .unwrap_or(rates.len() as usize);
+        rates.insert(index as u32, rate);
+    }
+
+    fn write_config(env: &Env, config: &OracleConfig) {
+        if config.max_staleness == 0 {
+            panic!("invalid staleness window");
+        }
+        if config.min_reports == 0 || config.min_reports > MAX_REPORTERS {
+            panic!("invalid min reports");
+        }
+
+        env.storage().instance().set(&DataKey::Config, config);
+        events::emit_config_updated(env, config.max_staleness, config.min_reports);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,129 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    Address, Env,
+};
+
+const MAX_STALENESS: u64 = 300;
+
+struct Setup<'a> {
+    env: Env,
+    reporters: [Address; 3],
+    base: Address,
+    quote: Address,
+    client: PriceOracleClient<'a>,
+}
+
+fn setup(min_reports: u32) -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(10_000);
+
+    let admin = Address::generate(&env);
+    let contract_id = env.register(PriceOracle, ());
+    let client = PriceOracleClient::new(&env, &contract_id);
+    client.initialize(
+        &admin,
+        &OracleConfig {
+            max_staleness: MAX_STALENESS,
+            min_reports,
+        },
+    );
+
+    let reporters = [
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+    for reporter in reporters.iter() {
+        client.set_reporter(reporter, &true);
+    }
+
+    Setup {
+        base: Address::generate(&env),
+        quote: Address::generate(&env),
+        env,
+        reporters,
+        client,
+    }
+}
+
+fn report(s: &Setup, index: usize, rate: i128) {
+    s.client
+        .report(&s.reporters[index], &s.base, &s.quote, &rate);
+}
+
+#[test]
+fn test_median_of_reports() {
+    let s = setup(1);
+    report(&s, 0, 12_000_000);
+    assert_eq!(s.client.get_rate(&s.base, &s.quote), 12_000_000);
+
+    // An outlier does not move the median.
+    report(&s, 1, 10_000_000);
+    report(&s, 2, 90_000_000);
+    assert_eq!(s.client.get_rate(&s.base, &s.quote), 12_000_000);
+    assert_eq!(s.client.convert(&s.base, &s.quote, &50), 60);
+
+    // Even counts average the middle pair.
+    s.client.set_reporter(&s.reporters[2], &false);
+    assert_eq!(s.client.get_rate(&s.base, &s.quote), 11_000_000);
+
+    assert_eq!(s.client.get_rate(&s.base, &s.base), RATE_SCALE);
+}
+
+#[test]
+fn test_stale_reports_are_ignored() {
+    let s = setup(1);
+    report(&s, 0, 10_000_000);
+    s.env.ledger().set_timestamp(10_200);
+    report(&s, 1, 20_000_000);
+
+    s.env.ledger().set_timestamp(10_000 + MAX_STALENESS + 1);
+    assert_eq!(s.client.get_rate(&s.base, &s.quote), 20_000_000);
+    assert_eq!(
+        s.client
+            .get_rate_with_staleness(&s.base, &s.quote, &(MAX_STALENESS + 1)),
+        15_000_000
+    );
+    assert!(s
+        .client
+        .try_get_rate_with_staleness(&s.base, &s.quote, &60)
+        .is_err());
+}
+
+#[test]
+fn test_min_reports_enforced() {
+    let s = setup(2);
+    report(&s, 0, 10_000_000);
+    assert!(s.client.try_get_rate(&s.base, &s.quote).is_err());
+    report(&s, 1, 10_000_000);
+    assert_eq!(s.client.get_rate(&s.base, &s.quote), 10_000_000);
+}
+
+#[test]
+fn test_only_reporters_report() {
+    let s = setup(1);
+    let outsider = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_report(&outsider, &s.base, &s.quote, &10_000_000)
+        .is_err());
+    assert!(s
+        .client
+        .try_report(&s.reporters[0], &s.base, &s.quote, &0)
+        .is_err());
+    assert!(s
+        .client
+        .try_report(&s.reporters[0], &s.base, &s.base, &10_000_000)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "stale rate")]
+fn test_unreported_pair_panics() {
+    let s = setup(1);
+    s.client.get_rate(&s.base, &s.quote);
+}
@@ -1,8 +1,9 @@
 #![no_std]
 
 use arenax_events::prize_distribution as events;
+use arenax_token_interface as token;
 use soroban_sdk::{
-    contract, contractimpl, contracttype, token, Address, BytesN, Env, IntoVal, Vec,
+    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Vec,
 };
 
 #[contracttype]
@@ -14,6 +15,8 @@ pub enum DataKey {
     NextPoolId,
     PrizePool(u64),
     Paused,
+    TournamentContract,
+    PayoutHook,
 }
 
 #[contracttype]
@@ -121,7 +124,8 @@ impl PrizeDistributionContract {
         pool_id
     }
 
-    /// Distribute the prize pool atomically to the winners based on weights
+    /// Distribute the prize pool atomically to the winners based on weights. If the
+    /// match is disputed the pool is put on hold and nothing is paid out.
     pub fn distribute(
         env: Env,
         caller: Address,
@@ -132,10 +136,14 @@ impl PrizeDistributionContract {
         Self::require_not_paused(&env);
         caller.require_auth();
 
-        // Enforce authorization: only admin or the match contract can distribute
+        // Enforce authorization: only admin, the match contract or the tournament
+        // contract can distribute
         let admin = Self::get_admin(env.clone());
         let match_contract = Self::get_match_contract(&env);
-        if caller != admin && caller != match_contract {
+        let tournament_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::TournamentContract);
+        if caller != admin && caller != match_contract && Some(caller.clone()) != tournament_contract
+        {
             panic!("unauthorized caller");
         }
 
@@ -158,14 +166,15 @@ impl PrizeDistributionContract {
         );
 
         if is_disp {
-            // Automatically place on hold, save state, and revert
+            // Place the pool on hold instead of paying out. Returning rather than
+            // panicking keeps the hold, which a revert would roll back.
             pool.state = PoolState::Held as u32;
             env.storage()
                 .persistent()
                 .set(&DataKey::PrizePool(pool_id), &pool);
 
             events::emit_payout_held(&env, pool_id, &pool.match_id);
-            panic!("match is disputed, payout held");
+            return;
         }
 
         // Validate winners and weights
@@ -188,7 +197,6 @@ impl PrizeDistributionContract {
 
         // Distribute funds atomically
         let token_client = token::Client::new(&env, &pool.asset);
-        let contract_address = env.current_contract_address();
 
         let mut distributed_amount: i128 = 0;
         for i in 0..len {
@@ -203,7 +211,7 @@ impl PrizeDistributionContract {
             };
 
             if payout > 0 {
-                token_client.transfer(&contract_address, &winner, &payout);
+                Self::pay_winner(&env, &token_client, &pool.asset, &winner, payout);
                 distributed_amount += payout;
             }
         }
@@ -312,6 +320,27 @@ impl PrizeDistributionContract {
             .set(&DataKey::DisputeContract, &dispute_contract);
     }
 
+    /// Set tournament contract address allowed to distribute its pools (admin only)
+    pub fn set_tournament_contract(env: Env, tournament_contract: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::TournamentContract, &tournament_contract);
+    }
+
+    /// Set the payout hook consulted for every winner (admin only). Winners with an
+    /// open loan on the hook have their payout routed through it instead, so backed
+    /// stakes are repaid before the player is paid.
+    pub fn set_payout_hook(env: Env, hook: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::PayoutHook, &hook);
+    }
+
+    /// Get the payout hook, if one is set
+    pub fn get_payout_hook(env: Env) -> Option<Address> {
+        env.storage().instance().get(&DataKey::PayoutHook)
+    }
+
     /// Get prize pool details
     pub fn get_pool(env: Env, pool_id: u64) -> PrizePool {
         env.storage()
@@ -343,6 +372,33 @@ impl PrizeDistributionContract {
             .expect("not initialized")
     }
 
+    fn pay_winner(
+        env: &Env,
+        token_client: &token::Client,
+        asset: &Address,
+        winner: &Address,
+        payout: i128,
+    ) {
+        let contract_address = env.current_contract_address();
+        if let Some(hook) = Self::get_payout_hook(env.clone()) {
+            let has_loan: bool = env.invoke_contract(
+                &hook,
+                &soroban_sdk::Symbol::new(env, "has_open_loan"),
+                (winner.clone(), asset.clone()).into_val(env),
+            );
+            if has_loan {
+                token_client.transfer(&contract_address, &hook, &payout);
+                env.invoke_contract::<()>(
+                    &hook,
+                    &soroban_sdk::Symbol::new(env, "on_prize_payout"),
+                    (winner.clone(), asset.clone(), payout).into_val(env),
+                );
+                return;
+            }
+        }
+        token_client.transfer(&contract_address, winner, &payout);
+    }
+
     fn require_admin(env: &Env) {
         let admin = Self::get_admin(env.clone());
         admin.require_auth();
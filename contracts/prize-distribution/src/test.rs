@@ -2,12 +2,13 @@
 extern crate std;
 
 use super::*;
-use dispute_resolution::DisputeResolutionContractClient;
-use match_contract::MatchContractClient;
+use dispute_resolution::{Decision, DisputeResolutionContractClient};
+use match_contract::{GameConfig, MatchContractClient};
 use soroban_sdk::{
-    testutils::{Address as _, Ledger as _},
+    symbol_short,
+    testutils::Address as _,
     token::{StellarAssetClient, TokenClient as SdkTokenClient},
-    Address, BytesN, Env, String, Symbol,
+    Address, BytesN, Env, String,
 };
 
 // Mock Identity Contract for dispute resolution and match operator roles
@@ -54,11 +55,21 @@ fn setup_test() -> TestContext {
     // 2. Setup Match Contract
     let match_id = env.register(match_contract::MatchContract, ());
     let match_client = MatchContractClient::new(&env, &match_id);
+    let identity_id = env.register(MockIdentityContract, ());
+    match_client.initialize(&admin);
+    match_client.set_identity_contract(&identity_id);
+    match_client.register_game(
+        &symbol_short!("chess"),
+        &GameConfig {
+            max_score: 1,
+            round_count: 1,
+            modes: Vec::new(&env),
+        },
+    );
 
     // 3. Setup Dispute Resolution Contract
     let dispute_id = env.register(dispute_resolution::DisputeResolutionContract, ());
     let dispute_client = DisputeResolutionContractClient::new(&env, &dispute_id);
-    let identity_id = env.register(MockIdentityContract, ());
     dispute_client.initialize(&admin, &identity_id, &3600u64);
 
     // 4. Setup Prize Distribution Contract
@@ -86,6 +97,18 @@ fn setup_test() -> TestContext {
     }
 }
 
+fn create_match(ctx: &TestContext, match_id: &BytesN<32>) {
+    ctx.match_client.create_match(
+        match_id,
+        &ctx.player_a,
+        &ctx.player_b,
+        &symbol_short!("chess"),
+        &symbol_short!("eu"),
+        &symbol_short!("ranked"),
+        &ctx.admin,
+    );
+}
+
 fn generate_match_id(env: &Env, id: u8) -> BytesN<32> {
     let mut bytes = [0u8; 32];
     bytes[0] = id;
@@ -112,7 +135,7 @@ fn test_create_pool_success() {
     let match_id = generate_match_id(&ctx.env, 1);
 
     // Create match in MatchContract first
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
 
     let amount = 1000i128;
     let pool_id = ctx.prize_client.create_pool(
@@ -142,7 +165,7 @@ fn test_create_pool_success() {
 fn test_create_pool_invalid_amount_fails() {
     let ctx = setup_test();
     let match_id = generate_match_id(&ctx.env, 1);
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
 
     ctx.prize_client.create_pool(
         &ctx.creator,
@@ -158,7 +181,7 @@ fn test_distribute_single_winner_success() {
     let match_id = generate_match_id(&ctx.env, 1);
 
     // Setup match and pool
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     let amount = 5000i128;
     let pool_id = ctx.prize_client.create_pool(
         &ctx.creator,
@@ -193,7 +216,7 @@ fn test_distribute_multiple_winners_success() {
     let match_id = generate_match_id(&ctx.env, 1);
 
     // Setup match and pool
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     let amount = 10000i128;
     let pool_id = ctx.prize_client.create_pool(
         &ctx.creator,
@@ -229,7 +252,7 @@ fn test_distribute_rounding_adjustment() {
     let match_id = generate_match_id(&ctx.env, 1);
 
     // Setup match and pool
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     
     // Amount is 1003 tokens (cannot be split cleanly 33.33%, 33.33%, 33.34%)
     let amount = 1003i128;
@@ -269,7 +292,7 @@ fn test_distribute_rounding_adjustment() {
 fn test_distribute_invalid_weights_sum_fails() {
     let ctx = setup_test();
     let match_id = generate_match_id(&ctx.env, 1);
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     let pool_id = ctx.prize_client.create_pool(&ctx.creator, &match_id, &ctx.token_address, &1000);
 
     let mut winners = Vec::new(&ctx.env);
@@ -288,7 +311,7 @@ fn test_distribute_invalid_weights_sum_fails() {
 fn test_distribute_unauthorized_caller_fails() {
     let ctx = setup_test();
     let match_id = generate_match_id(&ctx.env, 1);
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     let pool_id = ctx.prize_client.create_pool(&ctx.creator, &match_id, &ctx.token_address, &1000);
 
     let mut winners = Vec::new(&ctx.env);
@@ -305,37 +328,35 @@ fn test_distribute_unauthorized_caller_fails() {
 fn test_distribute_blocked_by_dispute() {
     let ctx = setup_test();
     let match_id = generate_match_id(&ctx.env, 1);
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     let pool_id = ctx.prize_client.create_pool(&ctx.creator, &match_id, &ctx.token_address, &1000);
 
     // Open a dispute in DisputeResolutionContract
     let reason = String::from_str(&ctx.env, "Cheated");
     let evidence = String::from_str(&ctx.env, "ipfs://some-proof");
-    ctx.dispute_client.open_dispute(&match_id, &reason, &evidence);
+    ctx.dispute_client.open_dispute(&match_id, &ctx.player_a, &0, &reason, &evidence);
 
     let mut winners = Vec::new(&ctx.env);
     winners.push_back(ctx.player_a.clone());
     let mut weights = Vec::new(&ctx.env);
     weights.push_back(10000u32);
 
-    // Call distribute. It should auto-transition to Held, and then panic.
-    let result = ctx.env.as_contract(&ctx.prize_client.address, || {
-        let res = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
-            ctx.prize_client.distribute(&ctx.admin, &pool_id, &winners, &weights);
-        }));
-        assert!(res.is_err());
-    });
+    // Distributing a disputed pool puts it on hold without paying anyone.
+    ctx.prize_client
+        .distribute(&ctx.admin, &pool_id, &winners, &weights);
 
-    // Verify state is Held
     let pool = ctx.prize_client.get_pool(&pool_id);
     assert_eq!(pool.state, PoolState::Held as u32);
+    let token_sdk = SdkTokenClient::new(&ctx.env, &ctx.token_address);
+    assert_eq!(token_sdk.balance(&ctx.prize_client.address), 1000);
+    assert_eq!(token_sdk.balance(&ctx.player_a), 0);
 }
 
 #[test]
 fn test_manual_hold_and_release() {
     let ctx = setup_test();
     let match_id = generate_match_id(&ctx.env, 1);
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     let pool_id = ctx.prize_client.create_pool(&ctx.creator, &match_id, &ctx.token_address, &1000);
 
     // Admin holds the payout manually
@@ -353,13 +374,13 @@ fn test_manual_hold_and_release() {
 fn test_resolve_dispute_and_release_success() {
     let ctx = setup_test();
     let match_id = generate_match_id(&ctx.env, 1);
-    ctx.match_client.create_match(&match_id, &ctx.player_a, &ctx.player_b);
+    create_match(&ctx, &match_id);
     let pool_id = ctx.prize_client.create_pool(&ctx.creator, &match_id, &ctx.token_address, &1000);
 
     // Open a dispute
     let reason = String::from_str(&ctx.env, "Collusion");
     let evidence = String::from_str(&ctx.env, "ipfs://evidence");
-    ctx.dispute_client.open_dispute(&match_id, &reason, &evidence);
+    ctx.dispute_client.open_dispute(&match_id, &ctx.player_a, &0, &reason, &evidence);
 
     // Payout hold
     ctx.prize_client.hold_payout(&ctx.admin, &pool_id);
@@ -371,8 +392,8 @@ fn test_resolve_dispute_and_release_success() {
     assert!(release_res.is_err());
 
     // Resolve dispute in DisputeResolution
-    let decision = String::from_str(&ctx.env, "resolved");
-    ctx.dispute_client.resolve_dispute(&match_id, &ctx.admin, &decision);
+    ctx.dispute_client.claim_dispute(&match_id, &ctx.admin);
+    ctx.dispute_client.resolve_dispute(&match_id, &ctx.admin, &Decision::AwardPlayerB);
 
     // Release payout
     ctx.prize_client.release_payout(&pool_id);
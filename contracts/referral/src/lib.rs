@@ -0,0 +1,332 @@
+#![no_std]
+
+//! Referral and affiliate rewards.
+//!
+//! Players register a referral code; a new player binds to a referrer once by code.
+//! Reporter contracts (Prize Distribution, Staking Manager) report the fees their
+//! users pay, and the referrer accrues a share as claimable AX, subject to per-referee
+//! and per-referrer lifetime caps. Claims are paid from AX funded into this contract.
+
+use arenax_events::referral as events;
+use arenax_token_interface as token;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Symbol};
+
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Upper bound on the referral reward rate.
+pub const MAX_REWARD_BPS: u32 = 5_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    RewardToken,
+    Config,
+    Reporter(Address),
+    Code(Address),     // player -> code
+    CodeOwner(Symbol), // code -> player
+    Referrer(Address), // referee -> referrer
+    RefereeCount(Address),
+    RefereeEarned(Address),  // referee -> rewards generated for their referrer
+    ReferrerEarned(Address), // referrer -> lifetime rewards accrued
+    Claimable(Address),
+    TotalClaimable,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferralConfig {
+    /// Share of each reported fee credited to the referrer.
+    pub reward_bps: u32,
+    /// Lifetime rewards a single referee can generate; zero means uncapped.
+    pub referee_cap: i128,
+    /// Lifetime rewards a single referrer can accrue; zero means uncapped.
+    pub referrer_cap: i128,
+}
+
+#[contract]
+pub struct ReferralContract;
+
+#[contractimpl]
+impl ReferralContract {
+    pub fn initialize(env: Env, admin: Address, reward_token: Address, config: ReferralConfig) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::RewardToken, &reward_token);
+        Self::write_config(&env, &config);
+    }
+
+    pub fn set_config(env: Env, config: ReferralConfig) {
+        Self::require_admin(&env);
+        Self::write_config(&env, &config);
+    }
+
+    /// Allow or revoke a contract that reports fees (admin only).
+    pub fn set_reporter(env: Env, reporter: Address, enabled: bool) {
+        Self::require_admin(&env);
+        if enabled {
+            env.storage()
+                .instance()
+                .set(&DataKey::Reporter(reporter.clone()), &true);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&DataKey::Reporter(reporter.clone()));
+        }
+        events::emit_reporter_updated(&env, &reporter, enabled);
+    }
+
+    /// Move AX into the reward pool.
+    pub fn fund(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        Self::reward_token(&env).transfer(&from, &env.current_contract_address(), &amount);
+    }
+
+    /// Register `code` for `player`. Each player has one code and codes are unique.
+    pub fn register_code(env: Env, player: Address, code: Symbol) {
+        player.require_auth();
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Code(player.clone()))
+        {
+            panic!("player already has a code");
+        }
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::CodeOwner(code.clone()))
+        {
+            panic!("code already taken");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Code(player.clone()), &code);
+        env.storage()
+            .persistent()
+            .set(&DataKey::CodeOwner(code.clone()), &player);
+        events::emit_code_registered(&env, &player, &code);
+    }
+
+    /// Bind `referee` to the owner of `code`. A referee binds once and can neither
+    /// refer themselves nor the player who referred them.
+    pub fn bind(env: Env, referee: Address, code: Symbol) {
+        referee.require_auth();
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Referrer(referee.clone()))
+        {
+            panic!("referrer already bound");
+        }
+        let referrer = Self::get_code_owner(env.clone(), code.clone()).expect("unknown code");
+        if referrer == referee {
+            panic!("self referral");
+        }
+        if Self::get_referrer(env.clone(), referrer.clone()) == Some(referee.clone()) {
+            panic!("circular referral");
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Referrer(referee.clone()), &referrer);
+        let count = Self::get_referee_count(env.clone(), referrer.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::RefereeCount(referrer.clone()), &(count + 1));
+
+        events::emit_referee_bound(&env, &referee, &referrer, &code);
+    }
+
+    /// Report a fee paid by `player` (reporters only). Credits the player's referrer
+    /// and returns the reward accrued, which is zero without a referrer or once a
+    /// cap is reached.
+    pub fn report_fee(env: Env, reporter: Address, player: Address, fee: i128) -> i128 {
+        reporter.require_auth();
+        if !Self::is_reporter(env.clone(), reporter.clone()) {
+            panic!("not a reporter");
+        }
+        if fee < 0 {
+            panic!("fee must not be negative");
+        }
+
+        let referrer = match Self::get_referrer(env.clone(), player.clone()) {
+            Some(referrer) => referrer,
+            None => return 0,
+        };
+
+        let config = Self::get_config(env.clone());
+        let referee_earned = Self::get_referee_earned(env.clone(), player.clone());
+        let referrer_earned = Self::get_referrer_earned(env.clone(), referrer.clone());
+
+        let mut reward = fee * config.reward_bps as i128 / BPS_DENOMINATOR as i128;
+        if config.referee_cap > 0 {
+            reward = reward.min(config.referee_cap - referee_earned);
+        }
+        if config.referrer_cap > 0 {
+            reward = reward.min(config.referrer_cap - referrer_earned);
+        }
+        if reward <= 0 {
+            return 0;
+        }
+
+        env.storage().persistent().set(
+            &DataKey::RefereeEarned(player.clone()),
+            &(referee_earned + reward),
+        );
+        env.storage().persistent().set(
+            &DataKey::ReferrerEarned(referrer.clone()),
+            &(referrer_earned + reward),
+        );
+        let claimable = Self::get_claimable(env.clone(), referrer.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Claimable(referrer.clone()), &(claimable + reward));
+        let total = Self::get_total_claimable(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalClaimable, &(total + reward));
+
+        events::emit_reward_accrued(&env, &referrer, &player, &reporter, fee, reward);
+        reward
+    }
+
+    /// Pay out everything `referrer` has accrued.
+    pub fn claim(env: Env, referrer: Address) -> i128 {
+        referrer.require_auth();
+
+        let amount = Self::get_claimable(env.clone(), referrer.clone());
+        if amount == 0 {
+            panic!("nothing to claim");
+        }
+
+        let token = Self::reward_token(&env);
+        if token.balance(&env.current_contract_address()) < amount {
+            panic!("insufficient reward funds");
+        }
+
+        env.storage()
+            .persistent()
+            .remove(&DataKey::Claimable(referrer.clone()));
+        let total = Self::get_total_claimable(env.clone());
+        env.storage()
+            .instance()
+            .set(&DataKey::TotalClaimable, &(total - amount));
+        token.transfer(&env.current_contract_address(), &referrer, &amount);
+
+        events::emit_reward_claimed(&env, &referrer, amount);
+        amount
+    }
+
+    pub fn get_code(env: Env, player: Address) -> Option<Symbol> {
+        env.storage().persistent().get(&DataKey::Code(player))
+    }
+
+    pub fn get_code_owner(env: Env, code: Symbol) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::CodeOwner(code))
+    }
+
+    pub fn get_referrer(env: Env, referee: Address) -> Option<Address> {
+        env.storage().persistent().get(&DataKey::Referrer(referee))
+    }
+
+    pub fn get_referee_count(env: Env, referrer: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefereeCount(referrer))
+            .unwrap_or(0)
+    }
+
+    /// Rewards `referee`'s fees have generated for their referrer.
+    pub fn get_referee_earned(env: Env, referee: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::RefereeEarned(referee))
+            .unwrap_or(0)
+    }
+
+    /// Lifetime rewards accrued by `referrer`, claimed or not.
+    pub fn get_referrer_earned(env: Env, referrer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::ReferrerEarned(referrer))
+            .unwrap_or(0)
+    }
+
+    pub fn get_claimable(env: Env, referrer: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Claimable(referrer))
+            .unwrap_or(0)
+    }
+
+    /// Accrued rewards not yet claimed across all referrers.
+    pub fn get_total_claimable(env: Env) -> i128 {
+        env.storage()
+            .instance()
+            .get(&DataKey::TotalClaimable)
+            .unwrap_or(0)
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        env.storage().instance().has(&DataKey::Reporter(reporter))
+    }
+
+    pub fn get_config(env: Env) -> ReferralConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::Config)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn reward_token(env: &Env) -> token::Client<'_> {
+        let address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::RewardToken)
+            .expect("not initialized");
+        token::Client::new(env, &address)
+    }
+
+    fn write_config(env: &Env, config: &ReferralConfig) {
+        if config.reward_bps > MAX_REWARD_BPS {
+            panic!("reward rate too high");
+        }
+        if config.referee_cap < 0 || config.referrer_cap < 0 {
+            panic!("invalid cap");
+        }
+
+        env.storage().instance().set(&DataKey::Config, config);
+        events::emit_config_updated(
+            env,
+            config.reward_bps,
+            config.referee_cap,
+            config.referrer_cap,
+        );
+    }
+}
+
+#[cfg(test)]
+mod test;
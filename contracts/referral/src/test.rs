@@ -0,0 +1,151 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+struct Setup<'a> {
+    env: Env,
+    reporter: Address,
+    client: ReferralContractClient<'a>,
+    token: TokenClient<'a>,
+}
+
+fn config(reward_bps: u32, referee_cap: i128, referrer_cap: i128) -> ReferralConfig {
+    ReferralConfig {
+        reward_bps,
+        referee_cap,
+        referrer_cap,
+    }
+}
+
+fn setup(config: ReferralConfig) -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &sac.address()).mint(&admin, &1_000_000);
+
+    let contract_id = env.register(ReferralContract, ());
+    let client = ReferralContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &sac.address(), &config);
+    client.set_reporter(&reporter, &true);
+    client.fund(&admin, &10_000);
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        env,
+        reporter,
+        client,
+    }
+}
+
+/// A referrer with code `ace` and one bound referee.
+fn referral_pair(s: &Setup) -> (Address, Address) {
+    let referrer = Address::generate(&s.env);
+    let referee = Address::generate(&s.env);
+    s.client.register_code(&referrer, &symbol_short!("ace"));
+    s.client.bind(&referee, &symbol_short!("ace"));
+    (referrer, referee)
+}
+
+#[test]
+fn test_fees_accrue_claimable_rewards() {
+    let s = setup(config(1_000, 0, 0));
+    let (referrer, referee) = referral_pair(&s);
+    assert_eq!(s.client.get_referrer(&referee), Some(referrer.clone()));
+    assert_eq!(s.client.get_referee_count(&referrer), 1);
+
+    assert_eq!(s.client.report_fee(&s.reporter, &referee, &2_000), 200);
+    assert_eq!(s.client.report_fee(&s.reporter, &referee, &500), 50);
+    assert_eq!(s.client.get_claimable(&referrer), 250);
+
+    // Players without a referrer generate nothing.
+    let loner = Address::generate(&s.env);
+    assert_eq!(s.client.report_fee(&s.reporter, &loner, &2_000), 0);
+
+    assert_eq!(s.client.claim(&referrer), 250);
+    assert_eq!(s.token.balance(&referrer), 250);
+    assert_eq!(s.client.get_claimable(&referrer), 0);
+    assert_eq!(s.client.get_referrer_earned(&referrer), 250);
+    assert!(s.client.try_claim(&referrer).is_err());
+}
+
+#[test]
+fn test_caps_limit_rewards() {
+    let s = setup(config(1_000, 150, 250));
+    let (referrer, first) = referral_pair(&s);
+    let second = Address::generate(&s.env);
+    s.client.bind(&second, &symbol_short!("ace"));
+
+    assert_eq!(s.client.report_fee(&s.reporter, &first, &1_000), 100);
+    assert_eq!(s.client.report_fee(&s.reporter, &first, &1_000), 50);
+    assert_eq!(s.client.report_fee(&s.reporter, &first, &1_000), 0);
+
+    assert_eq!(s.client.report_fee(&s.reporter, &second, &5_000), 100);
+    assert_eq!(s.client.report_fee(&s.reporter, &second, &5_000), 0);
+    assert_eq!(s.client.get_referrer_earned(&referrer), 250);
+}
+
+#[test]
+fn test_binding_rules() {
+    let s = setup(config(1_000, 0, 0));
+    let (referrer, referee) = referral_pair(&s);
+
+    // Codes are unique and each player has one.
+    let other = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_register_code(&other, &symbol_short!("ace"))
+        .is_err());
+    assert!(s
+        .client
+        .try_register_code(&referrer, &symbol_short!("two"))
+        .is_err());
+
+    // Self referral, rebinding and referring your own referrer are rejected.
+    assert!(s.client.try_bind(&referrer, &symbol_short!("ace")).is_err());
+    assert!(s.client.try_bind(&referee, &symbol_short!("ace")).is_err());
+    s.client.register_code(&referee, &symbol_short!("bee"));
+    assert!(s.client.try_bind(&referrer, &symbol_short!("bee")).is_err());
+    assert!(s.client.try_bind(&other, &symbol_short!("zzz")).is_err());
+}
+
+#[test]
+fn test_only_reporters_report() {
+    let s = setup(config(1_000, 0, 0));
+    let (_, referee) = referral_pair(&s);
+    let outsider = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_report_fee(&outsider, &referee, &1_000)
+        .is_err());
+
+    s.client.set_reporter(&s.reporter, &false);
+    assert!(s
+        .client
+        .try_report_fee(&s.reporter, &referee, &1_000)
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "insufficient reward funds")]
+fn test_claim_limited_to_funded_rewards() {
+    let s = setup(config(5_000, 0, 0));
+    let (referrer, referee) = referral_pair(&s);
+    s.client.report_fee(&s.reporter, &referee, &40_000);
+    s.client.claim(&referrer);
+}
+
+#[test]
+#[should_panic(expected = "reward rate too high")]
+fn test_reward_rate_bounded() {
+    let s = setup(config(1_000, 0, 0));
+    s.client.set_config(&config(MAX_REWARD_BPS + 1, 0, 0));
+}
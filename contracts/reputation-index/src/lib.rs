@@ -9,13 +9,97 @@ pub struct Reputation {
     pub last_update_ts: u64,
 }
 
+/// A player's reputation as returned by `get_reputation`. Not stored; `frozen` is derived from
+/// the player's open disputes.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReputationView {
+    pub skill: i128,
+    pub fair_play: i128,
+    pub last_update_ts: u64,
+    /// True while the player is involved in an open dispute; updates are queued.
+    pub frozen: bool,
+}
+
+/// Point-in-time rating, recorded on every reputation write for charting.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RatingSnapshot {
+    pub timestamp: u64,
+    pub skill: i128,
+    pub fair_play: i128,
+}
+
+/// A reputation change deferred while the player was frozen.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct QueuedUpdate {
+    pub match_id: u64,
+    pub skill_delta: i128,
+    pub fair_play_delta: i128,
+}
+
+/// What an authorized writer contract is allowed to change on update.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WriterScope {
+    pub skill: bool,
+    pub fair_play: bool,
+}
+
+/// Rank tiers derived from skill, lowest to highest. Stored and returned as the
+/// `u32` discriminant, which is also the index into `TierConfig::thresholds`.
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Tier {
+    Bronze = 0,
+    Silver = 1,
+    Gold = 2,
+    Platinum = 3,
+    Diamond = 4,
+    Master = 5,
+    Grandmaster = 6,
+}
+
+pub const TIER_COUNT: u32 = 7;
+
+/// Number of rating snapshots kept per player; older entries are overwritten.
+pub const HISTORY_CAPACITY: u32 = 64;
+
+/// Bounds for `batch_update`, keeping a single invocation within resource limits.
+pub const MAX_BATCH_MATCHES: u32 = 50;
+pub const MAX_BATCH_UPDATES: u32 = 200;
+/// Largest absolute skill delta a single match may apply to one player.
+pub const MAX_SKILL_DELTA: i128 = 500;
+/// Largest fair_play penalty (or restoration) a single anti-cheat flag may apply.
+pub const MAX_PENALTY_PER_FLAG: i128 = 100;
+
+/// Tier table. `thresholds[i]` is the minimum skill to be promoted into tier `i`;
+/// a player only drops out of tier `i` once skill falls below
+/// `thresholds[i] - hysteresis`, so ratings hovering on a boundary don't flap.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierConfig {
+    pub thresholds: Vec<i128>,
+    pub hysteresis: i128,
+}
+
 #[contracttype]
 pub enum DataKey {
     Reputation(Address),
     Admin,
-    AuthorizedMatchContract,
+    Writer(Address), // writer contract -> WriterScope
+    Writers,         // Vec<Address> of all writers, for enumeration
     AuthorizedAntiCheatOracle,
     DecayRate, // points per day (as i128)
+    TierConfig,
+    Tier(Address), // player -> current tier (u32)
+    DisputeContract,
+    Frozen(Address),       // player -> Vec<u64> of match ids with open disputes
+    Queued(Address),       // player -> Vec<QueuedUpdate> applied on unfreeze
+    HistoryCount(Address), // player -> total snapshots ever written (u32)
+    History(Address, u32), // (player, slot) -> RatingSnapshot, slot = count % HISTORY_CAPACITY
 }
 
 #[contract]
@@ -25,77 +109,324 @@ use arenax_events::reputation_index;
 
 #[contractimpl]
 impl ReputationIndex {
-    /// Initialize the contract
+    /// Initialize the contract. The initial match contract is registered as a writer
+    /// with full (skill + fair_play) scope.
     pub fn initialize(env: Env, admin: Address, match_contract: Address, decay_rate: i128) {
         if env.storage().instance().has(&DataKey::Admin) {
             panic!("already initialized");
         }
         env.storage().instance().set(&DataKey::Admin, &admin);
-        env.storage()
-            .instance()
-            .set(&DataKey::AuthorizedMatchContract, &match_contract);
         env.storage()
             .instance()
             .set(&DataKey::DecayRate, &decay_rate);
+        Self::internal_set_writer(
+            &env,
+            &match_contract,
+            &WriterScope {
+                skill: true,
+                fair_play: true,
+            },
+        );
     }
 
-    /// Update reputation after a match outcome is finalized.
-    /// outcome: skill delta for each player corresponding to the players list.
-    pub fn update_on_match(env: Env, match_id: u64, players: Vec<Address>, outcome: Vec<i128>) {
-        let match_contract: Address = env
+    /// Authorize a writer contract (admin only), or replace the scope of an existing one.
+    pub fn add_writer(env: Env, admin: Address, writer: Address, scope: WriterScope) {
+        Self::require_admin(&env, &admin);
+        if !scope.skill && !scope.fair_play {
+            panic!("writer scope must allow at least one field");
+        }
+        Self::internal_set_writer(&env, &writer, &scope);
+    }
+
+    /// Revoke a writer contract (admin only).
+    pub fn remove_writer(env: Env, admin: Address, writer: Address) {
+        Self::require_admin(&env, &admin);
+        if !env
             .storage()
             .instance()
-            .get(&DataKey::AuthorizedMatchContract)
-            .expect("match contract not set");
+            .has(&DataKey::Writer(writer.clone()))
+        {
+            panic!("writer not found");
+        }
+        env.storage()
+            .instance()
+            .remove(&DataKey::Writer(writer.clone()));
+
+        let writers = Self::get_writers(env.clone());
+        let mut remaining = Vec::new(&env);
+        for w in writers.iter() {
+            if w != writer {
+                remaining.push_back(w);
+            }
+        }
+        env.storage().instance().set(&DataKey::Writers, &remaining);
 
-        match_contract.require_auth();
+        reputation_index::emit_writer_removed(&env, &writer);
+    }
+
+    /// Get the scope of a writer contract, if it is authorized.
+    pub fn get_writer_scope(env: Env, writer: Address) -> Option<WriterScope> {
+        env.storage().instance().get(&DataKey::Writer(writer))
+    }
+
+    /// List all authorized writer contracts.
+    pub fn get_writers(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Writers)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Update reputation after a match outcome is finalized.
+    /// outcome: skill delta for each player corresponding to the players list.
+    /// Only the fields covered by the writer's scope are changed.
+    pub fn update_on_match(
+        env: Env,
+        writer: Address,
+        match_id: u64,
+        players: Vec<Address>,
+        outcome: Vec<i128>,
+    ) {
+        writer.require_auth();
+        let scope = Self::get_writer_scope(env.clone(), writer).expect("not authorized writer");
 
         if players.len() != outcome.len() {
             panic!("players and outcome length mismatch");
         }
 
         let now = env.ledger().timestamp();
+        Self::apply_match(&env, &scope, match_id, &players, &outcome, now);
+    }
+
+    /// Apply several finalized matches in one invocation (e.g. when settling a tournament).
+    /// Each entry is `(match_id, players, skill_deltas)`. Either every match is applied or,
+    /// if any is rejected, none is.
+    pub fn batch_update(env: Env, writer: Address, matches: Vec<(u64, Vec<Address>, Vec<i128>)>) {
+        writer.require_auth();
+        let scope =
+            Self::get_writer_scope(env.clone(), writer.clone()).expect("not authorized writer");
+
+        if matches.is_empty() {
+            panic!("empty batch");
+        }
+        if matches.len() > MAX_BATCH_MATCHES {
+            panic!("too many matches in batch");
+        }
+
+        let mut total_updates = 0u32;
+        for (_, players, outcome) in matches.iter() {
+            if players.len() != outcome.len() {
+                panic!("players and outcome length mismatch");
+            }
+            total_updates += players.len();
+        }
+        if total_updates > MAX_BATCH_UPDATES {
+            panic!("too many player updates in batch");
+        }
+
+        let now = env.ledger().timestamp();
+        for (match_id, players, outcome) in matches.iter() {
+            Self::apply_match(&env, &scope, match_id, &players, &outcome, now);
+            reputation_index::emit_batch_match_applied(&env, &writer, match_id, players.len());
+        }
 
+        reputation_index::emit_batch_updated(&env, &writer, matches.len(), total_updates);
+    }
+
+    fn apply_match(
+        env: &Env,
+        scope: &WriterScope,
+        match_id: u64,
+        players: &Vec<Address>,
+        outcome: &Vec<i128>,
+        now: u64,
+    ) {
+        for delta in outcome.iter() {
+            if delta.abs() > MAX_SKILL_DELTA {
+                panic!("skill delta out of bounds");
+            }
+        }
         for i in 0..players.len() {
             let player = players.get(i).unwrap();
-            let skill_delta = outcome.get(i).unwrap();
+            let skill_delta = if scope.skill {
+                outcome.get(i).unwrap()
+            } else {
+                0
+            };
+            let fair_play_delta = if scope.fair_play { 1i128 } else { 0 }; // Completion bonus
+
+            Self::apply_delta(env, &player, match_id, skill_delta, fair_play_delta, now);
+        }
+    }
+
+    /// Apply a reputation change, or queue it if the player is frozen by a dispute.
+    fn apply_delta(
+        env: &Env,
+        player: &Address,
+        match_id: u64,
+        skill_delta: i128,
+        fair_play_delta: i128,
+        now: u64,
+    ) {
+        if Self::is_frozen(env.clone(), player.clone()) {
+            let key = DataKey::Queued(player.clone());
+            let mut queued: Vec<QueuedUpdate> = env
+                .storage()
+                .persistent()
+                .get(&key)
+                .unwrap_or(Vec::new(env));
+            queued.push_back(QueuedUpdate {
+                match_id,
+                skill_delta,
+                fair_play_delta,
+            });
+            env.storage().persistent().set(&key, &queued);
+            reputation_index::emit_update_queued(
+                env,
+                player,
+                skill_delta,
+                fair_play_delta,
+                match_id,
+            );
+            return;
+        }
+        Self::commit_delta(env, player, match_id, skill_delta, fair_play_delta, now);
+    }
 
-            let mut rep = Self::get_reputation(env.clone(), player.clone());
+    fn commit_delta(
+        env: &Env,
+        player: &Address,
+        match_id: u64,
+        skill_delta: i128,
+        fair_play_delta: i128,
+        now: u64,
+    ) {
+        let mut rep = Self::load_reputation(env, player);
 
-            // Apply decay before updating
-            rep = Self::internal_apply_decay(&env, rep, now);
+        // Apply decay before updating
+        rep = Self::internal_apply_decay(env, rep, now);
 
-            let fair_play_delta = 1i128; // Completion bonus
+        rep.skill = rep.skill.saturating_add(skill_delta).max(0);
+        rep.fair_play = rep.fair_play.saturating_add(fair_play_delta).max(0);
+        rep.last_update_ts = now;
 
-            rep.skill = rep.skill.saturating_add(skill_delta).max(0);
-            rep.fair_play = rep.fair_play.saturating_add(fair_play_delta).max(0);
-            rep.last_update_ts = now;
+        Self::store_reputation(env, player, &rep);
 
-            env.storage()
-                .persistent()
-                .set(&DataKey::Reputation(player.clone()), &rep);
+        // Emit reputation_changed event
+        reputation_index::emit_reputation_changed(
+            env,
+            player,
+            skill_delta,
+            fair_play_delta,
+            match_id,
+        );
+    }
+
+    /// Set the dispute-resolution contract allowed to freeze players (admin only).
+    pub fn set_dispute_contract(env: Env, admin: Address, dispute_contract: Address) {
+        Self::require_admin(&env, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::DisputeContract, &dispute_contract);
+    }
+
+    /// Freeze a player's reputation while a dispute on `match_id` is open. Callable only by
+    /// the dispute-resolution contract. A player stays frozen until every dispute they are
+    /// involved in has been unfrozen.
+    pub fn freeze(env: Env, player: Address, match_id: u64) {
+        Self::require_dispute_contract(&env);
+        let key = DataKey::Frozen(player.clone());
+        let mut matches: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        if matches.contains(match_id) {
+            panic!("already frozen for match");
+        }
+        matches.push_back(match_id);
+        env.storage().persistent().set(&key, &matches);
+        reputation_index::emit_frozen(&env, &player, match_id);
+    }
+
+    /// Lift the freeze for `match_id`. Once no disputes remain, queued updates are applied
+    /// in the order they were received. Callable only by the dispute-resolution contract.
+    pub fn unfreeze(env: Env, player: Address, match_id: u64) {
+        Self::require_dispute_contract(&env);
+        let key = DataKey::Frozen(player.clone());
+        let matches: Vec<u64> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or(Vec::new(&env));
+        let idx = matches
+            .first_index_of(match_id)
+            .expect("not frozen for match");
+        let mut matches = matches;
+        matches.remove(idx);
+        reputation_index::emit_unfrozen(&env, &player, match_id);
+
+        if !matches.is_empty() {
+            env.storage().persistent().set(&key, &matches);
+            return;
+        }
+        env.storage().persistent().remove(&key);
+
+        let queue_key = DataKey::Queued(player.clone());
+        let queued: Vec<QueuedUpdate> = env
+            .storage()
+            .persistent()
+            .get(&queue_key)
+            .unwrap_or(Vec::new(&env));
+        env.storage().persistent().remove(&queue_key);
 
-            // Emit reputation_changed event
-            reputation_index::emit_reputation_changed(
+        let now = env.ledger().timestamp();
+        for update in queued.iter() {
+            Self::commit_delta(
                 &env,
                 &player,
-                skill_delta,
-                fair_play_delta,
-                match_id,
+                update.match_id,
+                update.skill_delta,
+                update.fair_play_delta,
+                now,
             );
         }
     }
 
+    /// Returns true while the player has at least one open dispute.
+    pub fn is_frozen(env: Env, addr: Address) -> bool {
+        env.storage().persistent().has(&DataKey::Frozen(addr))
+    }
+
+    /// Updates waiting to be applied when the player is unfrozen.
+    pub fn get_queued_updates(env: Env, addr: Address) -> Vec<QueuedUpdate> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Queued(addr))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    fn require_dispute_contract(env: &Env) {
+        let dispute_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::DisputeContract)
+            .expect("dispute contract not set");
+        dispute_contract.require_auth();
+    }
+
     /// Explicitly apply decay to a player's reputation based on a timestamp.
+    /// No-op while the player is frozen.
     pub fn apply_decay(env: Env, addr: Address, now_ts: u64) {
-        let mut rep = Self::get_reputation(env.clone(), addr.clone());
+        if Self::is_frozen(env.clone(), addr.clone()) {
+            return;
+        }
+        let mut rep = Self::load_reputation(&env, &addr);
         let old_skill = rep.skill;
         let old_fair_play = rep.fair_play;
 
         rep = Self::internal_apply_decay(&env, rep, now_ts);
-        env.storage()
-            .persistent()
-            .set(&DataKey::Reputation(addr.clone()), &rep);
+        Self::store_reputation(&env, &addr, &rep);
 
         // Emit decay event
         reputation_index::emit_reputation_decayed(
@@ -107,10 +438,64 @@ impl ReputationIndex {
     }
 
     /// Get current reputation for a player.
-    pub fn get_reputation(env: Env, addr: Address) -> Reputation {
+    pub fn get_reputation(env: Env, addr: Address) -> ReputationView {
+        let rep = Self::load_reputation(&env, &addr);
+        ReputationView {
+            skill: rep.skill,
+            fair_play: rep.fair_play,
+            last_update_ts: rep.last_update_ts,
+            frozen: Self::is_frozen(env, addr),
+        }
+    }
+
+    /// Get the player's current tier (see `Tier`). Players without a stored tier
+    /// are placed purely by their current skill.
+    pub fn get_tier(env: Env, addr: Address) -> u32 {
+        if let Some(tier) = env.storage().persistent().get(&DataKey::Tier(addr.clone())) {
+            return tier;
+        }
+        let rep = Self::load_reputation(&env, &addr);
+        Self::compute_tier(&Self::get_tier_config(env), rep.skill, None)
+    }
+
+    /// Get the active tier table.
+    pub fn get_tier_config(env: Env) -> TierConfig {
+        env.storage()
+            .instance()
+            .get(&DataKey::TierConfig)
+            .unwrap_or(TierConfig {
+                thresholds: Vec::from_array(&env, [0, 1100, 1250, 1400, 1600, 1850, 2100]),
+                hysteresis: 25,
+            })
+    }
+
+    /// Replace the tier table (admin only). Thresholds must list one strictly increasing
+    /// minimum skill per tier starting at 0; hysteresis must be non-negative.
+    pub fn set_tier_config(env: Env, admin: Address, config: TierConfig) {
+        Self::require_admin(&env, &admin);
+        if config.thresholds.len() != TIER_COUNT {
+            panic!("tier table must have one threshold per tier");
+        }
+        if config.thresholds.get(0).unwrap() != 0 {
+            panic!("lowest tier threshold must be 0");
+        }
+        for i in 1..config.thresholds.len() {
+            if config.thresholds.get(i).unwrap() <= config.thresholds.get(i - 1).unwrap() {
+                panic!("tier thresholds must be strictly increasing");
+            }
+        }
+        if config.hysteresis < 0 {
+            panic!("hysteresis must be non-negative");
+        }
+        env.storage().instance().set(&DataKey::TierConfig, &config);
+        reputation_index::emit_tier_config_updated(&env, &config.thresholds, config.hysteresis);
+    }
+
+    /// Stored reputation, or the starting values for a player without one.
+    fn load_reputation(env: &Env, player: &Address) -> Reputation {
         env.storage()
             .persistent()
-            .get(&DataKey::Reputation(addr))
+            .get(&DataKey::Reputation(player.clone()))
             .unwrap_or(Reputation {
                 skill: 1000,
                 fair_play: 100,
@@ -118,6 +503,88 @@ impl ReputationIndex {
             })
     }
 
+    /// Persist a reputation record and move the player between tiers if needed.
+    fn store_reputation(env: &Env, player: &Address, rep: &Reputation) {
+        let key = DataKey::Tier(player.clone());
+        let old_tier = Self::get_tier(env.clone(), player.clone());
+        let config = Self::get_tier_config(env.clone());
+        let new_tier = Self::compute_tier(&config, rep.skill, Some(old_tier));
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Reputation(player.clone()), rep);
+        env.storage().persistent().set(&key, &new_tier);
+
+        if old_tier != new_tier {
+            reputation_index::emit_tier_changed(env, player, old_tier, new_tier);
+        }
+
+        Self::record_snapshot(env, player, rep);
+    }
+
+    fn record_snapshot(env: &Env, player: &Address, rep: &Reputation) {
+        let count_key = DataKey::HistoryCount(player.clone());
+        let count: u32 = env.storage().persistent().get(&count_key).unwrap_or(0);
+        env.storage().persistent().set(
+            &DataKey::History(player.clone(), count % HISTORY_CAPACITY),
+            &RatingSnapshot {
+                timestamp: rep.last_update_ts,
+                skill: rep.skill,
+                fair_play: rep.fair_play,
+            },
+        );
+        env.storage()
+            .persistent()
+            .set(&count_key, &count.saturating_add(1));
+    }
+
+    /// Get up to `limit` of the player's most recent rating snapshots, oldest first.
+    /// At most `HISTORY_CAPACITY` snapshots are retained.
+    pub fn get_history(env: Env, addr: Address, limit: u32) -> Vec<RatingSnapshot> {
+        let count: u32 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::HistoryCount(addr.clone()))
+            .unwrap_or(0);
+        let available = count.min(HISTORY_CAPACITY);
+        let take = limit.min(available);
+
+        let mut history = Vec::new(&env);
+        for i in (count - take)..count {
+            if let Some(snapshot) = env
+                .storage()
+                .persistent()
+                .get(&DataKey::History(addr.clone(), i % HISTORY_CAPACITY))
+            {
+                history.push_back(snapshot);
+            }
+        }
+        history
+    }
+
+    /// Promotion happens as soon as skill reaches a threshold; demotion only once skill
+    /// drops below the current tier's threshold minus the hysteresis band.
+    fn compute_tier(config: &TierConfig, skill: i128, current: Option<u32>) -> u32 {
+        let mut raw = 0u32;
+        for i in 0..config.thresholds.len() {
+            if skill >= config.thresholds.get(i).unwrap() {
+                raw = i;
+            }
+        }
+        let current = match current {
+            Some(t) if t < config.thresholds.len() => t,
+            _ => return raw,
+        };
+        if raw >= current {
+            return raw;
+        }
+        let mut tier = current;
+        while tier > 0 && skill < config.thresholds.get(tier).unwrap() - config.hysteresis {
+            tier -= 1;
+        }
+        tier
+    }
+
     fn internal_apply_decay(env: &Env, mut rep: Reputation, now: u64) -> Reputation {
         let elapsed = now.saturating_sub(rep.last_update_ts);
         if elapsed == 0 {
@@ -145,23 +612,35 @@ impl ReputationIndex {
         rep
     }
 
-    pub fn set_decay_rate(env: Env, admin: Address, new_rate: i128) {
+    fn require_admin(env: &Env, admin: &Address) {
         let saved_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != saved_admin {
+        if *admin != saved_admin {
             panic!("not admin");
         }
         admin.require_auth();
+    }
+
+    fn internal_set_writer(env: &Env, writer: &Address, scope: &WriterScope) {
+        let mut writers = Self::get_writers(env.clone());
+        if !writers.contains(writer) {
+            writers.push_back(writer.clone());
+            env.storage().instance().set(&DataKey::Writers, &writers);
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::Writer(writer.clone()), scope);
+        reputation_index::emit_writer_added(env, writer, scope.skill, scope.fair_play);
+    }
+
+    pub fn set_decay_rate(env: Env, admin: Address, new_rate: i128) {
+        Self::require_admin(&env, &admin);
         env.storage().instance().set(&DataKey::DecayRate, &new_rate);
     }
 
     /// Set the authorized anti-cheat oracle contract (admin only). That contract may call
     /// apply_anticheat_penalty to apply bounded fair_play penalties.
     pub fn set_authorized_anticheat_oracle(env: Env, admin: Address, oracle: Address) {
-        let saved_admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
-        if admin != saved_admin {
-            panic!("not admin");
-        }
-        admin.require_auth();
+        Self::require_admin(&env, &admin);
         env.storage()
             .instance()
             .set(&DataKey::AuthorizedAntiCheatOracle, &oracle);
@@ -177,30 +656,48 @@ impl ReputationIndex {
         match_id: u64,
         penalty: i128,
     ) {
+        Self::require_anticheat_oracle(&env, &oracle);
+        let capped = penalty.clamp(0, MAX_PENALTY_PER_FLAG);
+        if capped == 0 {
+            return;
+        }
+        let now = env.ledger().timestamp();
+        Self::apply_delta(&env, &player, match_id, 0, -capped, now);
+    }
+
+    /// Restore fair_play points taken by an anti-cheat penalty that was overturned on appeal.
+    /// Callable only by the authorized anti-cheat oracle contract; bounded by the same cap.
+    pub fn restore_anticheat_penalty(
+        env: Env,
+        oracle: Address,
+        player: Address,
+        match_id: u64,
+        amount: i128,
+    ) {
+        Self::require_anticheat_oracle(&env, &oracle);
+        let capped = amount.clamp(0, MAX_PENALTY_PER_FLAG);
+        if capped == 0 {
+            return;
+        }
+        let now = env.ledger().timestamp();
+        Self::apply_delta(&env, &player, match_id, 0, capped, now);
+    }
+
+    /// Largest fair_play penalty a single anti-cheat flag can apply.
+    pub fn get_max_penalty_per_flag(_env: Env) -> i128 {
+        MAX_PENALTY_PER_FLAG
+    }
+
+    fn require_anticheat_oracle(env: &Env, oracle: &Address) {
         oracle.require_auth();
         let authorized: Address = env
             .storage()
             .instance()
             .get(&DataKey::AuthorizedAntiCheatOracle)
             .expect("anticheat oracle not set");
-        if oracle != authorized {
+        if *oracle != authorized {
             panic!("not authorized anticheat oracle");
         }
-        // Cap penalty at a maximum (e.g. 100 per call) to keep penalties bounded
-        const MAX_PENALTY_PER_FLAG: i128 = 100;
-        let capped = penalty.clamp(0, MAX_PENALTY_PER_FLAG);
-        if capped == 0 {
-            return;
-        }
-        let now = env.ledger().timestamp();
-        let mut rep = Self::get_reputation(env.clone(), player.clone());
-        rep = Self::internal_apply_decay(&env, rep, now);
-        rep.fair_play = rep.fair_play.saturating_sub(capped).max(0);
-        rep.last_update_ts = now;
-        env.storage()
-            .persistent()
-            .set(&DataKey::Reputation(player.clone()), &rep);
-        reputation_index::emit_reputation_changed(&env, &player, 0, -capped, match_id);
     }
 }
 
@@ -26,7 +26,7 @@ fn test_reputation_index() {
     // Update match outcome
     let players = vec![&env, player1.clone()];
     let outcomes = vec![&env, 25i128]; // +25 skill
-    client.update_on_match(&1, &players, &outcomes);
+    client.update_on_match(&match_contract, &1, &players, &outcomes);
 
     let rep = client.get_reputation(&player1);
     assert_eq!(rep.skill, 1025);
@@ -43,3 +43,356 @@ fn test_reputation_index() {
     assert_eq!(rep.fair_play, 91);
     assert_eq!(rep.last_update_ts, one_day_later);
 }
+
+#[test]
+fn test_writer_scopes() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let lifecycle = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    assert_eq!(client.get_writers(), vec![&env, match_contract.clone()]);
+
+    // Skill-only writer: the completion bonus is not applied.
+    client.add_writer(
+        &admin,
+        &lifecycle,
+        &WriterScope {
+            skill: true,
+            fair_play: false,
+        },
+    );
+    assert_eq!(client.get_writers().len(), 2);
+
+    let players = vec![&env, player.clone()];
+    client.update_on_match(&lifecycle, &1, &players, &vec![&env, 10i128]);
+    let rep = client.get_reputation(&player);
+    assert_eq!(rep.skill, 1010);
+    assert_eq!(rep.fair_play, 100);
+
+    // Fair-play-only writer: skill delta is ignored.
+    client.add_writer(
+        &admin,
+        &lifecycle,
+        &WriterScope {
+            skill: false,
+            fair_play: true,
+        },
+    );
+    assert_eq!(client.get_writers().len(), 2);
+    client.update_on_match(&lifecycle, &2, &players, &vec![&env, 10i128]);
+    let rep = client.get_reputation(&player);
+    assert_eq!(rep.skill, 1010);
+    assert_eq!(rep.fair_play, 101);
+
+    client.remove_writer(&admin, &lifecycle);
+    assert_eq!(client.get_writer_scope(&lifecycle), None);
+    assert_eq!(client.get_writers(), vec![&env, match_contract]);
+}
+
+#[test]
+#[should_panic(expected = "not authorized writer")]
+fn test_unknown_writer_rejected() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    client.update_on_match(&stranger, &1, &vec![&env, player], &vec![&env, 10i128]);
+}
+
+#[test]
+fn test_tier_hysteresis() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    assert_eq!(client.get_tier(&player), Tier::Bronze as u32);
+
+    let players = vec![&env, player.clone()];
+    // 1000 -> 1100: promoted to Silver
+    client.update_on_match(&match_contract, &1, &players, &vec![&env, 100i128]);
+    assert_eq!(client.get_tier(&player), Tier::Silver as u32);
+
+    // 1100 -> 1090: inside the 25 point band, stays Silver
+    client.update_on_match(&match_contract, &2, &players, &vec![&env, -10i128]);
+    assert_eq!(client.get_tier(&player), Tier::Silver as u32);
+
+    // 1090 -> 1070: below 1100 - 25, demoted
+    client.update_on_match(&match_contract, &3, &players, &vec![&env, -20i128]);
+    assert_eq!(client.get_tier(&player), Tier::Bronze as u32);
+}
+
+#[test]
+fn test_set_tier_config() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    let config = TierConfig {
+        thresholds: vec![&env, 0i128, 500, 900, 1200, 1500, 1800, 2000],
+        hysteresis: 0,
+    };
+    client.set_tier_config(&admin, &config);
+    assert_eq!(client.get_tier_config(), config);
+    assert_eq!(client.get_tier(&player), Tier::Gold as u32);
+}
+
+#[test]
+#[should_panic(expected = "tier thresholds must be strictly increasing")]
+fn test_set_tier_config_rejects_unordered() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    client.set_tier_config(
+        &admin,
+        &TierConfig {
+            thresholds: vec![&env, 0i128, 500, 400, 1200, 1500, 1800, 2000],
+            hysteresis: 10,
+        },
+    );
+}
+
+#[test]
+fn test_batch_update() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    let matches = vec![
+        &env,
+        (
+            1u64,
+            vec![&env, p1.clone(), p2.clone()],
+            vec![&env, 20i128, -20i128],
+        ),
+        (
+            2u64,
+            vec![&env, p1.clone(), p2.clone()],
+            vec![&env, 15i128, -15i128],
+        ),
+    ];
+    client.batch_update(&match_contract, &matches);
+
+    let rep1 = client.get_reputation(&p1);
+    let rep2 = client.get_reputation(&p2);
+    assert_eq!(rep1.skill, 1035);
+    assert_eq!(rep1.fair_play, 102);
+    assert_eq!(rep2.skill, 965);
+    assert_eq!(rep2.fair_play, 102);
+}
+
+#[test]
+#[should_panic(expected = "skill delta out of bounds")]
+fn test_batch_update_rejects_out_of_bounds_delta() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let p1 = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    let matches = vec![
+        &env,
+        (1u64, vec![&env, p1.clone()], vec![&env, 20i128]),
+        (
+            2u64,
+            vec![&env, p1.clone()],
+            vec![&env, MAX_SKILL_DELTA + 1],
+        ),
+    ];
+    client.batch_update(&match_contract, &matches);
+}
+
+#[test]
+#[should_panic(expected = "skill delta out of bounds")]
+fn test_update_on_match_rejects_out_of_bounds_delta() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let p1 = Address::generate(&env);
+    let p2 = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    client.update_on_match(
+        &match_contract,
+        &1u64,
+        &vec![&env, p1, p2],
+        &vec![&env, 20i128, -(MAX_SKILL_DELTA + 1)],
+    );
+}
+
+#[test]
+fn test_freeze_queues_updates_until_unfreeze() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let dispute_contract = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+    client.set_dispute_contract(&admin, &dispute_contract);
+
+    client.freeze(&player, &7);
+    client.freeze(&player, &8);
+    assert!(client.get_reputation(&player).frozen);
+
+    let players = vec![&env, player.clone()];
+    client.update_on_match(&match_contract, &9, &players, &vec![&env, 30i128]);
+    let rep = client.get_reputation(&player);
+    assert_eq!(rep.skill, 1000);
+    assert_eq!(rep.fair_play, 100);
+    assert_eq!(client.get_queued_updates(&player).len(), 1);
+
+    // Still frozen by the second dispute.
+    client.unfreeze(&player, &7);
+    assert!(client.is_frozen(&player));
+    assert_eq!(client.get_reputation(&player).skill, 1000);
+
+    client.unfreeze(&player, &8);
+    let rep = client.get_reputation(&player);
+    assert!(!rep.frozen);
+    assert_eq!(rep.skill, 1030);
+    assert_eq!(rep.fair_play, 101);
+    assert_eq!(client.get_queued_updates(&player).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "not frozen for match")]
+fn test_unfreeze_unknown_match() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let dispute_contract = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+    client.set_dispute_contract(&admin, &dispute_contract);
+
+    client.freeze(&player, &1);
+    client.unfreeze(&player, &2);
+}
+
+#[test]
+fn test_history_ring_buffer() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+
+    assert_eq!(client.get_history(&player, &10).len(), 0);
+
+    let players = vec![&env, player.clone()];
+    for i in 0..(HISTORY_CAPACITY as u64 + 5) {
+        client.update_on_match(&match_contract, &i, &players, &vec![&env, 1i128]);
+    }
+
+    let recent = client.get_history(&player, &3);
+    assert_eq!(recent.len(), 3);
+    assert_eq!(
+        recent.get(2).unwrap().skill,
+        1000 + HISTORY_CAPACITY as i128 + 5
+    );
+    assert_eq!(
+        recent.get(0).unwrap().skill,
+        1000 + HISTORY_CAPACITY as i128 + 3
+    );
+
+    let all = client.get_history(&player, &1000);
+    assert_eq!(all.len(), HISTORY_CAPACITY);
+    assert_eq!(all.get(0).unwrap().skill, 1006);
+}
+
+#[test]
+fn test_anticheat_penalty_restore() {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let match_contract = Address::generate(&env);
+    let oracle = Address::generate(&env);
+    let player = Address::generate(&env);
+
+    let contract_id = env.register(ReputationIndex, ());
+    let client = ReputationIndexClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_contract, &0);
+    client.set_authorized_anticheat_oracle(&admin, &oracle);
+
+    let before = client.get_reputation(&player).fair_play;
+    client.apply_anticheat_penalty(&oracle, &player, &1, &30);
+    assert_eq!(client.get_reputation(&player).fair_play, before - 30);
+
+    client.restore_anticheat_penalty(&oracle, &player, &1, &30);
+    assert_eq!(client.get_reputation(&player).fair_play, before);
+
+    let other = Address::generate(&env);
+    assert!(client
+        .try_restore_anticheat_penalty(&other, &player, &1, &30)
+        .is_err());
+}
@@ -0,0 +1,372 @@
+#![no_std]
+
+//! Season pass.
+//!
+//! Authorized contracts (match finalization, tournaments) grant XP during a season.
+//! XP unlocks tiers on a free track and, for players who bought the pass in AX, a
+//! premium track. Each unlocked tier's reward (AX and/or a badge minted through the
+//! trophy contract) is claimed once per track. Rewards stay claimable after the season
+//! ends; XP and passes do not carry over to the next season.
+
+use arenax_events::season_pass as events;
+use arenax_token_interface as token;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+/// Upper bound on tiers per season.
+pub const MAX_TIERS: u32 = 100;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    PayToken,
+    Treasury,
+    TrophyContract,
+    XpSource(Address),
+    CurrentSeason,
+    Season(u32),
+    Xp(u32, Address),
+    Premium(u32, Address),
+    Claimed(u32, Address, u32, bool), // (season, player, tier, premium)
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TierReward {
+    /// AX paid from this contract's balance.
+    pub tokens: i128,
+    /// Badge minted through the trophy contract.
+    pub badge: Option<BytesN<32>>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tier {
+    pub xp_required: u32,
+    pub free_reward: TierReward,
+    pub premium_reward: TierReward,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Season {
+    pub season_id: u32,
+    pub start_time: u64,
+    pub end_time: u64,
+    pub premium_price: i128,
+    /// Ordered by strictly increasing `xp_required`.
+    pub tiers: Vec<Tier>,
+}
+
+#[contract]
+pub struct SeasonPassContract;
+
+#[contractimpl]
+impl SeasonPassContract {
+    /// Premium purchases are paid in `pay_token` (AX) to `treasury`.
+    pub fn initialize(env: Env, admin: Address, pay_token: Address, treasury: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::PayToken, &pay_token);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.storage().instance().set(&DataKey::CurrentSeason, &0u32);
+    }
+
+    /// Trophy contract used for badge rewards (admin only). This contract must be
+    /// registered there as a minter.
+    pub fn set_trophy_contract(env: Env, trophy_contract: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::TrophyContract, &trophy_contract);
+    }
+
+    /// Allow or revoke a contract that grants XP (admin only).
+    pub fn set_xp_source(env: Env, source: Address, enabled: bool) {
+        Self::require_admin(&env);
+        if enabled {
+            env.storage()
+                .instance()
+                .set(&DataKey::XpSource(source.clone()), &true);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&DataKey::XpSource(source.clone()));
+        }
+        events::emit_xp_source_updated(&env, &source, enabled);
+    }
+
+    /// Move AX into the reward pool.
+    pub fn fund(env: Env, from: Address, amount: i128) {
+        from.require_auth();
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        Self::pay_token(&env).transfer(&from, &env.current_contract_address(), &amount);
+    }
+
+    /// Open the next season, running until `end_time` (admin only). The previous
+    /// season must have ended.
+    pub fn start_season(env: Env, end_time: u64, premium_price: i128, tiers: Vec<Tier>) -> u32 {
+        Self::require_admin(&env);
+
+        let now = env.ledger().timestamp();
+        if let Some(current) = Self::get_current_season(env.clone()) {
+            if now < current.end_time {
+                panic!("season still active");
+            }
+        }
+        if end_time <= now {
+            panic!("invalid end time");
+        }
+        if premium_price < 0 {
+            panic!("invalid premium price");
+        }
+        Self::validate_tiers(&tiers);
+
+        let season_id = Self::current_season_id(&env) + 1;
+        let season = Season {
+            season_id,
+            start_time: now,
+            end_time,
+            premium_price,
+            tiers: tiers.clone(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Season(season_id), &season);
+        env.storage()
+            .instance()
+            .set(&DataKey::CurrentSeason, &season_id);
+
+        events::emit_season_started(&env, season_id, now, end_time, tiers.len(), premium_price);
+        season_id
+    }
+
+    /// End the current season early (admin only).
+    pub fn end_season(env: Env) {
+        Self::require_admin(&env);
+
+        let mut season = Self::get_current_season(env.clone()).expect("no season");
+        let now = env.ledger().timestamp();
+        if now >= season.end_time {
+            panic!("season already ended");
+        }
+        season.end_time = now;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Season(season.season_id), &season);
+
+        events::emit_season_ended(&env, season.season_id, now);
+    }
+
+    /// Grant `amount` XP to `player` in the active season (XP sources only). Returns
+    /// the player's season total.
+    pub fn grant_xp(env: Env, source: Address, player: Address, amount: u32) -> u32 {
+        source.require_auth();
+        if !Self::is_xp_source(env.clone(), source.clone()) {
+            panic!("not an xp source");
+        }
+
+        let season = Self::active_season(&env);
+        let total =
+            Self::get_xp(env.clone(), season.season_id, player.clone()).saturating_add(amount);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Xp(season.season_id, player.clone()), &total);
+
+        events::emit_xp_granted(&env, season.season_id, &player, &source, amount, total);
+        total
+    }
+
+    /// Buy the premium track for the active season.
+    pub fn purchase_premium(env: Env, player: Address) {
+        player.require_auth();
+
+        let season = Self::active_season(&env);
+        let key = DataKey::Premium(season.season_id, player.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("premium already purchased");
+        }
+
+        if season.premium_price > 0 {
+            let treasury: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::Treasury)
+                .expect("not initialized");
+            Self::pay_token(&env).transfer(&player, &treasury, &season.premium_price);
+        }
+        env.storage().persistent().set(&key, &true);
+
+        events::emit_premium_purchased(&env, season.season_id, &player, season.premium_price);
+    }
+
+    /// Claim the reward for `tier` (zero-based) on the free or premium track.
+    pub fn claim_reward(env: Env, player: Address, season_id: u32, tier: u32, premium: bool) {
+        player.require_auth();
+
+        let season = Self::get_season(env.clone(), season_id);
+        let tier_config = season.tiers.get(tier).expect("tier not found");
+        if Self::get_xp(env.clone(), season_id, player.clone()) < tier_config.xp_required {
+            panic!("tier not reached");
+        }
+        if premium && !Self::has_premium(env.clone(), season_id, player.clone()) {
+            panic!("premium not purchased");
+        }
+
+        let claimed_key = DataKey::Claimed(season_id, player.clone(), tier, premium);
+        if env.storage().persistent().has(&claimed_key) {
+            panic!("reward already claimed");
+        }
+        env.storage().persistent().set(&claimed_key, &true);
+
+        let reward = if premium {
+            tier_config.premium_reward
+        } else {
+            tier_config.free_reward
+        };
+
+        if reward.tokens > 0 {
+            let token = Self::pay_token(&env);
+            if token.balance(&env.current_contract_address()) < reward.tokens {
+                panic!("insufficient reward funds");
+            }
+            token.transfer(&env.current_contract_address(), &player, &reward.tokens);
+        }
+
+        let badge_token_id = reward.badge.map(|badge_id| {
+            let trophy_contract: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::TrophyContract)
+                .expect("trophy contract not set");
+            env.invoke_contract::<u64>(
+                &trophy_contract,
+                &Symbol::new(&env, "mint_badge"),
+                (env.current_contract_address(), player.clone(), badge_id).into_val(&env),
+            )
+        });
+
+        events::emit_reward_claimed(
+            &env,
+            season_id,
+            &player,
+            tier,
+            premium,
+            reward.tokens,
+            badge_token_id,
+        );
+    }
+
+    pub fn get_season(env: Env, season_id: u32) -> Season {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Season(season_id))
+            .expect("season not found")
+    }
+
+    /// The most recently started season, whether or not it has ended.
+    pub fn get_current_season(env: Env) -> Option<Season> {
+        match Self::current_season_id(&env) {
+            0 => None,
+            season_id => Some(Self::get_season(env, season_id)),
+        }
+    }
+
+    pub fn get_xp(env: Env, season_id: u32, player: Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Xp(season_id, player))
+            .unwrap_or(0)
+    }
+
+    /// Number of tiers `player` has unlocked in `season_id`.
+    pub fn get_tier(env: Env, season_id: u32, player: Address) -> u32 {
+        let xp = Self::get_xp(env.clone(), season_id, player);
+        let season = Self::get_season(env, season_id);
+        let mut unlocked = 0;
+        for tier in season.tiers.iter() {
+            if xp < tier.xp_required {
+                break;
+            }
+            unlocked += 1;
+        }
+        unlocked
+    }
+
+    pub fn has_premium(env: Env, season_id: u32, player: Address) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Premium(season_id, player))
+    }
+
+    pub fn is_claimed(env: Env, season_id: u32, player: Address, tier: u32, premium: bool) -> bool {
+        env.storage()
+            .persistent()
+            .has(&DataKey::Claimed(season_id, player, tier, premium))
+    }
+
+    pub fn is_xp_source(env: Env, source: Address) -> bool {
+        env.storage().instance().has(&DataKey::XpSource(source))
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn current_season_id(env: &Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::CurrentSeason)
+            .expect("not initialized")
+    }
+
+    fn active_season(env: &Env) -> Season {
+        let season = Self::get_current_season(env.clone()).expect("no season");
+        if env.ledger().timestamp() >= season.end_time {
+            panic!("season has ended");
+        }
+        season
+    }
+
+    fn pay_token(env: &Env) -> token::Client<'_> {
+        let address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::PayToken)
+            .expect("not initialized");
+        token::Client::new(env, &address)
+    }
+
+    fn validate_tiers(tiers: &Vec<Tier>) {
+        if tiers.is_empty() || tiers.len() > MAX_TIERS {
+            panic!("invalid tier count");
+        }
+        let mut previous: Option<u32> = None;
+        for tier in tiers.iter() {
+            if previous.is_some_and(|xp| tier.xp_required <= xp) {
+                panic!("tier thresholds must increase");
+            }
+            if tier.free_reward.tokens < 0 || tier.premium_reward.tokens < 0 {
+                panic!("invalid reward");
+            }
+            previous = Some(tier.xp_required);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
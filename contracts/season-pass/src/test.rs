@@ -0,0 +1,178 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env,
+};
+use trophy::{TrophyContract, TrophyContractClient};
+
+const PRICE: i128 = 500;
+
+struct Setup<'a> {
+    env: Env,
+    treasury: Address,
+    xp_source: Address,
+    client: SeasonPassContractClient<'a>,
+    token: TokenClient<'a>,
+    minter: StellarAssetClient<'a>,
+    trophies: TrophyContractClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let xp_source = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let minter = StellarAssetClient::new(&env, &sac.address());
+    minter.mint(&admin, &100_000);
+
+    let contract_id = env.register(SeasonPassContract, ());
+    let client = SeasonPassContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &sac.address(), &treasury);
+    client.set_xp_source(&xp_source, &true);
+    client.fund(&admin, &10_000);
+
+    let trophy_id = env.register(TrophyContract, ());
+    let trophies = TrophyContractClient::new(&env, &trophy_id);
+    trophies.initialize(&admin, &true);
+    trophies.set_minter(&contract_id, &true);
+    client.set_trophy_contract(&trophy_id);
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        env,
+        treasury,
+        xp_source,
+        client,
+        minter,
+        trophies,
+    }
+}
+
+fn reward(tokens: i128, badge: Option<BytesN<32>>) -> TierReward {
+    TierReward { tokens, badge }
+}
+
+fn tiers(env: &Env) -> Vec<Tier> {
+    vec![
+        env,
+        Tier {
+            xp_required: 100,
+            free_reward: reward(10, None),
+            premium_reward: reward(50, None),
+        },
+        Tier {
+            xp_required: 300,
+            free_reward: reward(0, None),
+            premium_reward: reward(100, Some(BytesN::from_array(env, &[3u8; 32]))),
+        },
+    ]
+}
+
+fn player(s: &Setup) -> Address {
+    let player = Address::generate(&s.env);
+    s.minter.mint(&player, &1_000);
+    player
+}
+
+#[test]
+fn test_xp_unlocks_free_track() {
+    let s = setup();
+    let season = s.client.start_season(&10_000, &PRICE, &tiers(&s.env));
+    let p = player(&s);
+
+    assert_eq!(s.client.grant_xp(&s.xp_source, &p, &60), 60);
+    assert!(s.client.try_claim_reward(&p, &season, &0, &false).is_err());
+
+    assert_eq!(s.client.grant_xp(&s.xp_source, &p, &60), 120);
+    assert_eq!(s.client.get_tier(&season, &p), 1);
+    s.client.claim_reward(&p, &season, &0, &false);
+    assert_eq!(s.token.balance(&p), 1_010);
+    assert!(s.client.is_claimed(&season, &p, &0, &false));
+    assert!(s.client.try_claim_reward(&p, &season, &0, &false).is_err());
+
+    // Premium rewards need the pass.
+    assert!(s.client.try_claim_reward(&p, &season, &0, &true).is_err());
+}
+
+#[test]
+fn test_premium_track_pays_tokens_and_badges() {
+    let s = setup();
+    let season = s.client.start_season(&10_000, &PRICE, &tiers(&s.env));
+    let p = player(&s);
+
+    s.client.purchase_premium(&p);
+    assert_eq!(s.token.balance(&s.treasury), PRICE);
+    assert!(s.client.has_premium(&season, &p));
+    assert!(s.client.try_purchase_premium(&p).is_err());
+
+    s.client.grant_xp(&s.xp_source, &p, &300);
+    s.client.claim_reward(&p, &season, &0, &true);
+    s.client.claim_reward(&p, &season, &1, &true);
+    assert_eq!(s.token.balance(&p), 1_000 - PRICE + 150);
+
+    let badge = BytesN::from_array(&s.env, &[3u8; 32]);
+    let token_id = s.trophies.get_badge(&badge, &p).unwrap();
+    assert_eq!(s.trophies.owner_of(&token_id), p);
+}
+
+#[test]
+fn test_season_rollover() {
+    let s = setup();
+    let first = s.client.start_season(&5_000, &PRICE, &tiers(&s.env));
+    let p = player(&s);
+    s.client.grant_xp(&s.xp_source, &p, &150);
+
+    // Only one season runs at a time.
+    assert!(s
+        .client
+        .try_start_season(&20_000, &PRICE, &tiers(&s.env))
+        .is_err());
+
+    s.env.ledger().set_timestamp(5_000);
+    assert!(s.client.try_grant_xp(&s.xp_source, &p, &10).is_err());
+    assert!(s.client.try_purchase_premium(&p).is_err());
+
+    let second = s.client.start_season(&20_000, &PRICE, &tiers(&s.env));
+    assert_eq!(second, first + 1);
+    assert_eq!(s.client.get_xp(&second, &p), 0);
+    s.client.grant_xp(&s.xp_source, &p, &10);
+
+    // Past-season rewards stay claimable.
+    s.client.claim_reward(&p, &first, &0, &false);
+    assert_eq!(s.client.get_xp(&first, &p), 150);
+}
+
+#[test]
+fn test_end_season_early() {
+    let s = setup();
+    let season = s.client.start_season(&50_000, &PRICE, &tiers(&s.env));
+    s.client.end_season();
+    assert_eq!(s.client.get_season(&season).end_time, 1_000);
+    s.client.start_season(&60_000, &PRICE, &tiers(&s.env));
+}
+
+#[test]
+fn test_only_sources_grant_xp() {
+    let s = setup();
+    s.client.start_season(&10_000, &PRICE, &tiers(&s.env));
+    let p = player(&s);
+    assert!(s.client.try_grant_xp(&p, &p, &1_000).is_err());
+}
+
+#[test]
+#[should_panic(expected = "tier thresholds must increase")]
+fn test_tiers_validated() {
+    let s = setup();
+    let mut bad = tiers(&s.env);
+    let mut tier = bad.get(1).unwrap();
+    tier.xp_required = 100;
+    bad.set(1, tier);
+    s.client.start_season(&10_000, &PRICE, &bad);
+}
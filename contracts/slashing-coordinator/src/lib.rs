@@ -0,0 +1,455 @@
+#![no_std]
+
+//! Slashing coordinator.
+//!
+//! Authorized reporters (the anti-cheat and dispute contracts) file violation reports
+//! against a subject, naming the stake at risk: an escrowed stake held by the match
+//! escrow vault or a tournament stake held by the staking manager. Each violation
+//! type has a graduated policy, a list of steps indexed by how many times the subject
+//! has already been penalized for that violation, so repeat offenders move from a
+//! warning to a partial slash, a full slash and finally a ban recommendation.
+//!
+//! A report only takes effect after the appeal window. Until then the subject can
+//! appeal, which holds the report until the admin upholds or dismisses it. Anyone can
+//! execute a report once its window has passed; execution applies the policy step and
+//! calls `slash_match_stake` on the vault, which takes only from the subject's own
+//! stake in that match, or `slash` on the staking manager. The coordinator must be a
+//! registered slasher (`set_slasher`) in both contracts for those calls to succeed.
+
+use arenax_events::slashing_coordinator as events;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+/// Basis point denominator for partial slashes.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+/// Upper bound on steps in a policy.
+pub const MAX_POLICY_STEPS: u32 = 10;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Vault,
+    StakingManager,
+    AppealWindow,
+    Reporter(Address),
+    Policy(u32), // violation -> Vec<PolicyStep>
+    NextReportId,
+    Report(u64),
+    Offenses(Address, u32),  // (subject, violation) -> executed penalties
+    BanRecommended(Address), // subject -> report_id
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PenaltyKind {
+    Warning = 0,
+    PartialSlash = 1,
+    FullSlash = 2,
+    BanRecommendation = 3,
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ReportState {
+    Pending = 0,
+    Appealed = 1,
+    Executed = 2,
+    Dismissed = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyStep {
+    pub penalty: u32,
+    /// Share of the stake slashed by a partial slash, in basis points.
+    pub slash_bps: u32,
+}
+
+/// The stake a penalty is taken from.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum StakeRef {
+    None,
+    /// Match id and amount of the subject's stake held by the match escrow vault.
+    Escrow(BytesN<32>, i128),
+    /// Tournament id and amount staked by the subject in the staking manager.
+    Tournament(BytesN<32>, i128),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ViolationReport {
+    pub report_id: u64,
+    pub reporter: Address,
+    pub subject: Address,
+    pub violation: u32,
+    pub evidence_hash: BytesN<32>,
+    pub stake: StakeRef,
+    pub state: u32,
+    pub reported_at: u64,
+    pub executable_at: u64,
+    /// Set on execution.
+    pub penalty: Option<u32>,
+    pub slashed: i128,
+    /// 1-based offense count for `(subject, violation)` this report was executed as.
+    pub offense: u32,
+}
+
+#[contract]
+pub struct SlashingCoordinator;
+
+#[contractimpl]
+impl SlashingCoordinator {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        vault: Address,
+        staking_manager: Address,
+        appeal_window: u64,
+    ) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Vault, &vault);
+        env.storage()
+            .instance()
+            .set(&DataKey::StakingManager, &staking_manager);
+        env.storage()
+            .instance()
+            .set(&DataKey::AppealWindow, &appeal_window);
+        env.storage().instance().set(&DataKey::NextReportId, &1u64);
+    }
+
+    /// Point the coordinator at new vault and staking manager contracts (admin only).
+    pub fn set_targets(env: Env, vault: Address, staking_manager: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Vault, &vault);
+        env.storage()
+            .instance()
+            .set(&DataKey::StakingManager, &staking_manager);
+        events::emit_targets_updated(&env, &vault, &staking_manager);
+    }
+
+    /// Seconds between a report and its earliest execution (admin only). Applies to
+    /// reports filed afterwards.
+    pub fn set_appeal_window(env: Env, appeal_window: u64) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::AppealWindow, &appeal_window);
+    }
+
+    /// Allow or disallow `reporter` to file violation reports (admin only).
+    pub fn set_reporter(env: Env, reporter: Address, enabled: bool) {
+        Self::require_admin(&env);
+        let key = DataKey::Reporter(reporter.clone());
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        events::emit_reporter_updated(&env, &reporter, enabled);
+    }
+
+    /// Set the graduated policy for `violation` (admin only). The n-th executed
+    /// offense uses step n-1; offenses past the last step reuse it.
+    pub fn set_policy(env: Env, violation: u32, steps: Vec<PolicyStep>) {
+        Self::require_admin(&env);
+        if steps.is_empty() || steps.len() > MAX_POLICY_STEPS {
+            panic!("invalid policy");
+        }
+        for step in steps.iter() {
+            let valid = match step.penalty {
+                p if p == PenaltyKind::PartialSlash as u32 => {
+                    step.slash_bps > 0 && step.slash_bps < BPS_DENOMINATOR
+                }
+                p if p <= PenaltyKind::BanRecommendation as u32 => step.slash_bps == 0,
+                _ => false,
+            };
+            if !valid {
+                panic!("invalid policy step");
+            }
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Policy(violation), &steps);
+        events::emit_policy_updated(&env, violation, steps.len());
+    }
+
+    /// File a violation report against `subject` (reporters only). Returns the report
+    /// id.
+    pub fn report_violation(
+        env: Env,
+        reporter: Address,
+        subject: Address,
+        violation: u32,
+        evidence_hash: BytesN<32>,
+        stake: StakeRef,
+    ) -> u64 {
+        reporter.require_auth();
+        if !Self::is_reporter(env.clone(), reporter.clone()) {
+            panic!("not a reporter");
+        }
+        Self::get_policy(env.clone(), violation);
+        match &stake {
+            StakeRef::Escrow(_, amount) | StakeRef::Tournament(_, amount) if *amount <= 0 => {
+                panic!("amount must be positive")
+            }
+            _ => {}
+        }
+
+        let report_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextReportId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextReportId, &(report_id + 1));
+
+        let now = env.ledger().timestamp();
+        let report = ViolationReport {
+            report_id,
+            reporter: reporter.clone(),
+            subject: subject.clone(),
+            violation,
+            evidence_hash: evidence_hash.clone(),
+            stake,
+            state: ReportState::Pending as u32,
+            reported_at: now,
+            executable_at: now + Self::get_appeal_window(env.clone()),
+            penalty: None,
+            slashed: 0,
+            offense: 0,
+        };
+        Self::save_report(&env, &report);
+
+        events::emit_violation_reported(
+            &env,
+            report_id,
+            &reporter,
+            &subject,
+            violation,
+            &evidence_hash,
+            report.executable_at,
+        );
+        report_id
+    }
+
+    /// Appeal a pending report within its appeal window (subject only). The report is
+    /// held until the admin resolves the appeal.
+    pub fn appeal(env: Env, report_id: u64, reason_hash: BytesN<32>) {
+        let mut report = Self::get_report(env.clone(), report_id);
+        report.subject.require_auth();
+        if report.state != ReportState::Pending as u32 {
+            panic!("report is not pending");
+        }
+        if env.ledger().timestamp() >= report.executable_at {
+            panic!("appeal window closed");
+        }
+        report.state = ReportState::Appealed as u32;
+        Self::save_report(&env, &report);
+        events::emit_report_appealed(&env, report_id, &report.subject, &reason_hash);
+    }
+
+    /// Resolve an appeal (admin only). An upheld report becomes executable at once; a
+    /// successful appeal dismisses it.
+    pub fn resolve_appeal(env: Env, report_id: u64, uphold: bool) {
+        Self::require_admin(&env);
+        let mut report = Self::get_report(env.clone(), report_id);
+        if report.state != ReportState::Appealed as u32 {
+            panic!("report is not appealed");
+        }
+        if uphold {
+            report.state = ReportState::Pending as u32;
+            report.executable_at = env.ledger().timestamp();
+        } else {
+            report.state = ReportState::Dismissed as u32;
+            events::emit_report_dismissed(&env, report_id, &report.subject);
+        }
+        Self::save_report(&env, &report);
+        events::emit_appeal_resolved(&env, report_id, uphold);
+    }
+
+    /// Dismiss a pending or appealed report (admin only).
+    pub fn dismiss(env: Env, report_id: u64) {
+        Self::require_admin(&env);
+        let mut report = Self::get_report(env.clone(), report_id);
+        if report.state != ReportState::Pending as u32
+            && report.state != ReportState::Appealed as u32
+        {
+            panic!("report already resolved");
+        }
+        report.state = ReportState::Dismissed as u32;
+        Self::save_report(&env, &report);
+        events::emit_report_dismissed(&env, report_id, &report.subject);
+    }
+
+    /// Apply the policy step for a pending report whose appeal window has passed.
+    /// Callable by anyone. Returns the amount slashed.
+    pub fn execute(env: Env, report_id: u64) -> i128 {
+        let mut report = Self::get_report(env.clone(), report_id);
+        if report.state != ReportState::Pending as u32 {
+            panic!("report is not pending");
+        }
+        if env.ledger().timestamp() < report.executable_at {
+            panic!("appeal window open");
+        }
+
+        let offenses_key = DataKey::Offenses(report.subject.clone(), report.violation);
+        let offense: u32 = env
+            .storage()
+            .persistent()
+            .get(&offenses_key)
+            .unwrap_or(0u32)
+            + 1;
+        let steps = Self::get_policy(env.clone(), report.violation);
+        let step = steps.get(offense.min(steps.len()) - 1).unwrap();
+
+        let slashed = match (&report.stake, step.penalty) {
+            (_, p) if p == PenaltyKind::Warning as u32 => 0,
+            (StakeRef::None, _) => 0,
+            (StakeRef::Escrow(_, stake), p) | (StakeRef::Tournament(_, stake), p) => {
+                if p == PenaltyKind::PartialSlash as u32 {
+                    stake * step.slash_bps as i128 / BPS_DENOMINATOR as i128
+                } else {
+                    *stake
+                }
+            }
+        };
+        if slashed > 0 {
+            Self::slash(&env, &report.subject, &report.stake, slashed);
+        }
+        if step.penalty == PenaltyKind::BanRecommendation as u32 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::BanRecommended(report.subject.clone()), &report_id);
+        }
+
+        env.storage().persistent().set(&offenses_key, &offense);
+        report.state = ReportState::Executed as u32;
+        report.penalty = Some(step.penalty);
+        report.slashed = slashed;
+        report.offense = offense;
+        Self::save_report(&env, &report);
+
+        events::emit_penalty_executed(
+            &env,
+            report_id,
+            &report.subject,
+            step.penalty,
+            slashed,
+            offense,
+        );
+        slashed
+    }
+
+    pub fn get_report(env: Env, report_id: u64) -> ViolationReport {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Report(report_id))
+            .expect("report not found")
+    }
+
+    pub fn get_policy(env: Env, violation: u32) -> Vec<PolicyStep> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Policy(violation))
+            .expect("policy not found")
+    }
+
+    /// Executed penalties against `subject` for `violation`.
+    pub fn get_offenses(env: Env, subject: Address, violation: u32) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Offenses(subject, violation))
+            .unwrap_or(0)
+    }
+
+    /// The report that led to a ban recommendation for `subject`, if any.
+    pub fn get_ban_recommendation(env: Env, subject: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::BanRecommended(subject))
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        env.storage().instance().has(&DataKey::Reporter(reporter))
+    }
+
+    pub fn get_appeal_window(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::AppealWindow)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn slash(env: &Env, subject: &Address, stake: &StakeRef, amount: i128) {
+        match stake {
+            StakeRef::Escrow(match_id, _) => {
+                let vault: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::Vault)
+                    .expect("not initialized");
+                env.invoke_contract::<()>(
+                    &vault,
+                    &Symbol::new(env, "slash_match_stake"),
+                    (
+                        env.current_contract_address(),
+                        match_id.clone(),
+                        subject.clone(),
+                        amount,
+                    )
+                        .into_val(env),
+                );
+            }
+            StakeRef::Tournament(tournament_id, _) => {
+                let staking_manager: Address = env
+                    .storage()
+                    .instance()
+                    .get(&DataKey::StakingManager)
+                    .expect("not initialized");
+                env.invoke_contract::<()>(
+                    &staking_manager,
+                    &Symbol::new(env, "slash"),
+                    (
+                        subject.clone(),
+                        tournament_id.clone(),
+                        amount,
+                        env.current_contract_address(),
+                    )
+                        .into_val(env),
+                );
+            }
+            StakeRef::None => {}
+        }
+    }
+
+    fn save_report(env: &Env, report: &ViolationReport) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Report(report.report_id), report);
+    }
+}
+
+#[cfg(test)]
+mod test;
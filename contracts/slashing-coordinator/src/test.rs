@@ -0,0 +1,298 @@
+#![cfg(test)]
+
+use super::*;
+use match_escrow_vault::{MatchEscrowVault, MatchEscrowVaultClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env,
+};
+use staking_manager::{StakingManager, StakingManagerClient};
+
+const WINDOW: u64 = 3_600;
+const CHEATING: u32 = 1;
+const STAKE: i128 = 1_000;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    reporter: Address,
+    subject: Address,
+    treasury: Address,
+    token: TokenClient<'a>,
+    client: SlashingCoordinatorClient<'a>,
+    vault: MatchEscrowVaultClient<'a>,
+    staking: StakingManagerClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let token = env
+        .register_stellar_asset_contract_v2(admin.clone())
+        .address();
+
+    let contract_id = env.register(SlashingCoordinator, ());
+    let vault = MatchEscrowVaultClient::new(&env, &env.register(MatchEscrowVault, ()));
+    vault.initialize(&admin);
+    vault.set_treasury(&treasury);
+    vault.set_slasher(&contract_id, &true);
+    let staking = StakingManagerClient::new(&env, &env.register(StakingManager, ()));
+    staking.initialize(&admin, &token);
+    staking.set_slasher(&contract_id, &true);
+
+    let client = SlashingCoordinatorClient::new(&env, &contract_id);
+    client.initialize(&admin, &vault.address, &staking.address, &WINDOW);
+    client.set_reporter(&reporter, &true);
+    client.set_policy(
+        &CHEATING,
+        &vec![
+            &env,
+            PolicyStep {
+                penalty: PenaltyKind::Warning as u32,
+                slash_bps: 0,
+            },
+            PolicyStep {
+                penalty: PenaltyKind::PartialSlash as u32,
+                slash_bps: 2_500,
+            },
+            PolicyStep {
+                penalty: PenaltyKind::FullSlash as u32,
+                slash_bps: 0,
+            },
+            PolicyStep {
+                penalty: PenaltyKind::BanRecommendation as u32,
+                slash_bps: 0,
+            },
+        ],
+    );
+
+    Setup {
+        subject: Address::generate(&env),
+        token: TokenClient::new(&env, &token),
+        env,
+        admin,
+        reporter,
+        treasury,
+        client,
+        vault,
+        staking,
+    }
+}
+
+/// Open a fully funded escrow between the subject and a fresh opponent. Returns the
+/// match id and the opponent.
+fn fund_escrow(s: &Setup, seed: u8) -> (BytesN<32>, Address) {
+    let match_id = BytesN::from_array(&s.env, &[seed; 32]);
+    let opponent = Address::generate(&s.env);
+    let minter = StellarAssetClient::new(&s.env, &s.token.address);
+    minter.mint(&s.subject, &STAKE);
+    minter.mint(&opponent, &STAKE);
+    s.vault.create_escrow(
+        &match_id,
+        &s.subject,
+        &opponent,
+        &STAKE,
+        &s.token.address,
+        &s.admin,
+    );
+    s.vault.deposit(&match_id, &s.subject);
+    s.vault.deposit(&match_id, &opponent);
+    (match_id, opponent)
+}
+
+/// Stake `amount` for the subject in a new active tournament.
+fn stake_in_tournament(s: &Setup, amount: i128) -> BytesN<32> {
+    let tournament_id = BytesN::from_array(&s.env, &[1u8; 32]);
+    s.staking.create_tournament(&tournament_id, &100);
+    s.staking.update_tournament_state(&tournament_id, &1);
+    StellarAssetClient::new(&s.env, &s.token.address).mint(&s.subject, &amount);
+    s.staking.stake(&s.subject, &tournament_id, &amount);
+    tournament_id
+}
+
+fn report(s: &Setup, stake: &StakeRef) -> u64 {
+    s.client.report_violation(
+        &s.reporter,
+        &s.subject,
+        &CHEATING,
+        &BytesN::from_array(&s.env, &[9u8; 32]),
+        stake,
+    )
+}
+
+fn pass_window(s: &Setup) {
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + WINDOW);
+}
+
+#[test]
+fn test_penalties_escalate_with_repeat_offenses() {
+    let s = setup();
+
+    let expected = [0, 250, 1_000, 1_000];
+    for (i, amount) in expected.iter().enumerate() {
+        let (match_id, _) = fund_escrow(&s, i as u8 + 10);
+        let report_id = report(&s, &StakeRef::Escrow(match_id.clone(), STAKE));
+        pass_window(&s);
+        assert_eq!(s.client.execute(&report_id), *amount);
+        assert_eq!(s.client.get_report(&report_id).offense, i as u32 + 1);
+        assert_eq!(s.vault.get_slashed(&match_id, &s.subject), *amount);
+    }
+    assert_eq!(s.token.balance(&s.treasury), 2_250);
+    assert_eq!(s.client.get_offenses(&s.subject, &CHEATING), 4);
+    assert_eq!(s.client.get_ban_recommendation(&s.subject), Some(4));
+
+    // Offenses past the last step reuse it.
+    let (match_id, _) = fund_escrow(&s, 20);
+    let report_id = report(&s, &StakeRef::Escrow(match_id, STAKE));
+    pass_window(&s);
+    s.client.execute(&report_id);
+    assert_eq!(
+        s.client.get_report(&report_id).penalty,
+        Some(PenaltyKind::BanRecommendation as u32)
+    );
+}
+
+#[test]
+fn test_escrow_slash_only_takes_subjects_stake() {
+    let s = setup();
+    s.client.set_policy(
+        &CHEATING,
+        &vec![
+            &s.env,
+            PolicyStep {
+                penalty: PenaltyKind::FullSlash as u32,
+                slash_bps: 0,
+            },
+        ],
+    );
+    let (match_id, opponent) = fund_escrow(&s, 10);
+    let stake = StakeRef::Escrow(match_id.clone(), STAKE);
+
+    let report_id = report(&s, &stake);
+    pass_window(&s);
+    assert_eq!(s.client.execute(&report_id), STAKE);
+
+    // The opponent's deposit is still in the vault but cannot be slashed for the
+    // subject.
+    let again = report(&s, &stake);
+    pass_window(&s);
+    assert!(s.client.try_execute(&again).is_err());
+
+    s.vault.refund(&match_id);
+    assert_eq!(s.token.balance(&opponent), STAKE);
+    assert_eq!(s.token.balance(&s.subject), 0);
+    assert_eq!(s.token.balance(&s.treasury), STAKE);
+}
+
+#[test]
+fn test_tournament_stake_slashed_through_staking_manager() {
+    let s = setup();
+    let tournament_id = stake_in_tournament(&s, 800);
+    let stake = StakeRef::Tournament(tournament_id.clone(), 800);
+    for _ in 0..2 {
+        let report_id = report(&s, &stake);
+        pass_window(&s);
+        s.client.execute(&report_id);
+    }
+    assert_eq!(s.staking.get_stake(&s.subject, &tournament_id).amount, 600);
+    assert_eq!(s.staking.get_user_stake_info(&s.subject).total_slashed, 200);
+    // The staking manager sends slashed stakes to its admin.
+    assert_eq!(s.token.balance(&s.admin), 200);
+    assert_eq!(s.token.balance(&s.treasury), 0);
+}
+
+#[test]
+fn test_slash_requires_slasher_role() {
+    let s = setup();
+    let tournament_id = stake_in_tournament(&s, 800);
+    let (match_id, _) = fund_escrow(&s, 10);
+    s.staking.set_slasher(&s.client.address, &false);
+    s.vault.set_slasher(&s.client.address, &false);
+
+    // Skip the warning step so both reports slash.
+    let warning = report(&s, &StakeRef::None);
+    pass_window(&s);
+    s.client.execute(&warning);
+
+    let tournament_report = report(&s, &StakeRef::Tournament(tournament_id, 800));
+    let escrow_report = report(&s, &StakeRef::Escrow(match_id, STAKE));
+    pass_window(&s);
+    assert!(s.client.try_execute(&tournament_report).is_err());
+    assert!(s.client.try_execute(&escrow_report).is_err());
+}
+
+#[test]
+fn test_execution_waits_for_appeal_window() {
+    let s = setup();
+    let report_id = report(&s, &StakeRef::None);
+    assert!(s.client.try_execute(&report_id).is_err());
+
+    pass_window(&s);
+    assert!(s
+        .client
+        .try_appeal(&report_id, &BytesN::from_array(&s.env, &[0u8; 32]))
+        .is_err());
+    s.client.execute(&report_id);
+    assert!(s.client.try_execute(&report_id).is_err());
+}
+
+#[test]
+fn test_appeal_holds_report_until_resolved() {
+    let s = setup();
+    let reason = BytesN::from_array(&s.env, &[3u8; 32]);
+    let upheld = report(&s, &StakeRef::None);
+    let overturned = report(&s, &StakeRef::None);
+    s.client.appeal(&upheld, &reason);
+    s.client.appeal(&overturned, &reason);
+
+    pass_window(&s);
+    assert!(s.client.try_execute(&upheld).is_err());
+
+    s.client.resolve_appeal(&upheld, &true);
+    s.client.execute(&upheld);
+    s.client.resolve_appeal(&overturned, &false);
+    assert_eq!(
+        s.client.get_report(&overturned).state,
+        ReportState::Dismissed as u32
+    );
+    assert!(s.client.try_execute(&overturned).is_err());
+    assert_eq!(s.client.get_offenses(&s.subject, &CHEATING), 1);
+}
+
+#[test]
+fn test_only_reporters_can_report() {
+    let s = setup();
+    s.client.set_reporter(&s.reporter, &false);
+    assert!(s
+        .client
+        .try_report_violation(
+            &s.reporter,
+            &s.subject,
+            &CHEATING,
+            &BytesN::from_array(&s.env, &[9u8; 32]),
+            &StakeRef::None,
+        )
+        .is_err());
+}
+
+#[test]
+#[should_panic(expected = "invalid policy step")]
+fn test_partial_slash_requires_bps() {
+    let s = setup();
+    s.client.set_policy(
+        &2,
+        &vec![
+            &s.env,
+            PolicyStep {
+                penalty: PenaltyKind::PartialSlash as u32,
+                slash_bps: 0,
+            },
+        ],
+    );
+}
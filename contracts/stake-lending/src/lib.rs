@@ -0,0 +1,447 @@
+#![no_std]
+
+//! Collateral lending for tournament stakes.
+//!
+//! Backers deposit AX as loan offers, each with the share of the player's profit
+//! they take in return. A player accepting an offer has the principal staked for
+//! them in a tournament through the staking manager in the same call, and holds at
+//! most one open loan at a time. The stake is funded by this contract, so when the
+//! tournament ends the staking manager returns it here: `reclaim_stake` repays the
+//! backer's outstanding principal from it and hands any excess to the player.
+//!
+//! The prize distribution contract is configured with this contract as its payout
+//! hook: prizes won by a player with an open loan are routed here, repay the
+//! outstanding principal to the backer first, and only the remainder is split
+//! between backer and player. Players can also repay directly. When an authorized
+//! reporter (such as the slashing coordinator) reports the player slashed, the
+//! loan is marked defaulted; it stays open and keeps absorbing the player's prizes
+//! until the principal is recovered, blocking new loans meanwhile.
+
+use arenax_events::stake_lending as events;
+use arenax_token_interface as token;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, contracttype, vec, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+/// Basis point denominator for profit shares.
+pub const BPS_DENOMINATOR: u32 = 10_000;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    AxToken,
+    StakingManager,
+    PrizeDistribution,
+    Reporter(Address),
+    NextLoanId,
+    Loan(u64),
+    PlayerLoan(Address), // player -> open loan id
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum LoanState {
+    Offered = 0,
+    Active = 1,
+    Repaid = 2,
+    Defaulted = 3,
+    Withdrawn = 4,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Loan {
+    pub loan_id: u64,
+    pub backer: Address,
+    pub player: Option<Address>,
+    pub tournament_id: Option<BytesN<32>>,
+    pub principal: i128,
+    pub repaid: i128,
+    /// Backer's share of winnings left after the principal is repaid.
+    pub profit_share_bps: u32,
+    pub state: u32,
+    pub created_at: u64,
+    /// Last timestamp at which the offer can be accepted.
+    pub expires_at: u64,
+    /// Whether the staked principal has come back from the staking manager.
+    pub stake_reclaimed: bool,
+}
+
+#[contract]
+pub struct StakeLendingContract;
+
+#[contractimpl]
+impl StakeLendingContract {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        ax_token: Address,
+        staking_manager: Address,
+        prize_distribution: Address,
+    ) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::AxToken, &ax_token);
+        env.storage()
+            .instance()
+            .set(&DataKey::StakingManager, &staking_manager);
+        env.storage()
+            .instance()
+            .set(&DataKey::PrizeDistribution, &prize_distribution);
+        env.storage().instance().set(&DataKey::NextLoanId, &1u64);
+    }
+
+    /// Allow or disallow `reporter` to report slashed players (admin only).
+    pub fn set_reporter(env: Env, reporter: Address, enabled: bool) {
+        Self::require_admin(&env);
+        let key = DataKey::Reporter(reporter.clone());
+        if enabled {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        events::emit_reporter_updated(&env, &reporter, enabled);
+    }
+
+    /// Deposit `amount` AX as a loan offer acceptable until `expires_at`. Returns the
+    /// loan id.
+    pub fn offer(
+        env: Env,
+        backer: Address,
+        amount: i128,
+        profit_share_bps: u32,
+        expires_at: u64,
+    ) -> u64 {
+        backer.require_auth();
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        if profit_share_bps > BPS_DENOMINATOR {
+            panic!("invalid profit share");
+        }
+        let now = env.ledger().timestamp();
+        if expires_at <= now {
+            panic!("expiry must be in the future");
+        }
+
+        token::Client::new(&env, &Self::get_ax_token(env.clone())).transfer(
+            &backer,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        let loan_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextLoanId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextLoanId, &(loan_id + 1));
+
+        Self::save_loan(
+            &env,
+            &Loan {
+                loan_id,
+                backer: backer.clone(),
+                player: None,
+                tournament_id: None,
+                principal: amount,
+                repaid: 0,
+                profit_share_bps,
+                state: LoanState::Offered as u32,
+                created_at: now,
+                expires_at,
+                stake_reclaimed: false,
+            },
+        );
+
+        events::emit_loan_offered(&env, loan_id, &backer, amount, profit_share_bps, expires_at);
+        loan_id
+    }
+
+    /// Take back an offer nobody has accepted (backer only).
+    pub fn withdraw_offer(env: Env, loan_id: u64) {
+        let mut loan = Self::get_loan(env.clone(), loan_id);
+        loan.backer.require_auth();
+        if loan.state != LoanState::Offered as u32 {
+            panic!("loan not offered");
+        }
+        loan.state = LoanState::Withdrawn as u32;
+        Self::save_loan(&env, &loan);
+
+        token::Client::new(&env, &Self::get_ax_token(env.clone())).transfer(
+            &env.current_contract_address(),
+            &loan.backer,
+            &loan.principal,
+        );
+        events::emit_offer_withdrawn(&env, loan_id, &loan.backer, loan.principal);
+    }
+
+    /// Accept an offer and stake its principal in `tournament_id` on `player`'s behalf.
+    /// A player can hold one open loan at a time, including a defaulted one.
+    pub fn accept(env: Env, player: Address, loan_id: u64, tournament_id: BytesN<32>) {
+        player.require_auth();
+        let mut loan = Self::get_loan(env.clone(), loan_id);
+        if loan.state != LoanState::Offered as u32 {
+            panic!("loan not offered");
+        }
+        if env.ledger().timestamp() > loan.expires_at {
+            panic!("offer expired");
+        }
+        if loan.backer == player {
+            panic!("cannot borrow own offer");
+        }
+        let player_key = DataKey::PlayerLoan(player.clone());
+        if env.storage().persistent().has(&player_key) {
+            panic!("player has open loan");
+        }
+
+        loan.player = Some(player.clone());
+        loan.tournament_id = Some(tournament_id.clone());
+        loan.state = LoanState::Active as u32;
+        Self::save_loan(&env, &loan);
+        env.storage().persistent().set(&player_key, &loan_id);
+
+        // The stake is paid from, and returned to, this contract.
+        let this = env.current_contract_address();
+        let staking_manager = Self::get_staking_manager(env.clone());
+        env.authorize_as_current_contract(vec![
+            &env,
+            InvokerContractAuthEntry::Contract(SubContractInvocation {
+                context: ContractContext {
+                    contract: Self::get_ax_token(env.clone()),
+                    fn_name: Symbol::new(&env, "transfer"),
+                    args: (this.clone(), staking_manager.clone(), loan.principal).into_val(&env),
+                },
+                sub_invocations: Vec::new(&env),
+            }),
+        ]);
+        env.invoke_contract::<()>(
+            &staking_manager,
+            &Symbol::new(&env, "stake_for"),
+            (this, player.clone(), tournament_id.clone(), loan.principal).into_val(&env),
+        );
+
+        events::emit_loan_accepted(&env, loan_id, &player, &tournament_id, loan.principal);
+    }
+
+    /// Payout hook called by the prize distribution contract after transferring a
+    /// prize of `amount` won by `winner` here. Repays outstanding principal first and
+    /// splits the rest by the loan's profit share.
+    pub fn on_prize_payout(env: Env, winner: Address, asset: Address, amount: i128) {
+        Self::get_prize_distribution(env.clone()).require_auth();
+        if asset != Self::get_ax_token(env.clone()) {
+            panic!("unsupported asset");
+        }
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        let mut loan = Self::get_open_loan(&env, &winner).expect("no open loan");
+
+        let to_principal = amount.min(loan.principal - loan.repaid);
+        let profit = amount - to_principal;
+        let backer_profit = profit * loan.profit_share_bps as i128 / BPS_DENOMINATOR as i128;
+        let player_profit = profit - backer_profit;
+
+        let ax = token::Client::new(&env, &asset);
+        let contract_address = env.current_contract_address();
+        if to_principal + backer_profit > 0 {
+            ax.transfer(
+                &contract_address,
+                &loan.backer,
+                &(to_principal + backer_profit),
+            );
+        }
+        if player_profit > 0 {
+            ax.transfer(&contract_address, &winner, &player_profit);
+        }
+
+        loan.repaid += to_principal;
+        events::emit_payout_split(
+            &env,
+            loan.loan_id,
+            &winner,
+            amount,
+            to_principal,
+            backer_profit,
+            player_profit,
+        );
+        Self::settle(&env, &winner, &mut loan);
+    }
+
+    /// Repay up to `amount` of `player`'s outstanding principal directly to the
+    /// backer. Returns the amount repaid.
+    pub fn repay(env: Env, player: Address, amount: i128) -> i128 {
+        player.require_auth();
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        let mut loan = Self::get_open_loan(&env, &player).expect("no open loan");
+        let repaid = amount.min(loan.principal - loan.repaid);
+
+        token::Client::new(&env, &Self::get_ax_token(env.clone())).transfer(
+            &player,
+            &loan.backer,
+            &repaid,
+        );
+        loan.repaid += repaid;
+        Self::settle(&env, &player, &mut loan);
+        repaid
+    }
+
+    /// Withdraw a loan's stake from the staking manager once its tournament has ended
+    /// (callable by anyone). The returned amount, less anything slashed, repays the
+    /// backer's outstanding principal first; any excess goes to the player. Returns the
+    /// amount returned by the staking manager.
+    pub fn reclaim_stake(env: Env, loan_id: u64) -> i128 {
+        let mut loan = Self::get_loan(env.clone(), loan_id);
+        let (player, tournament_id) = match (loan.player.clone(), loan.tournament_id.clone()) {
+            (Some(player), Some(tournament_id)) => (player, tournament_id),
+            _ => panic!("loan not accepted"),
+        };
+        if loan.stake_reclaimed {
+            panic!("stake already reclaimed");
+        }
+
+        let ax = token::Client::new(&env, &Self::get_ax_token(env.clone()));
+        let contract_address = env.current_contract_address();
+        let before = ax.balance(&contract_address);
+        env.invoke_contract::<()>(
+            &Self::get_staking_manager(env.clone()),
+            &Symbol::new(&env, "withdraw"),
+            (player.clone(), tournament_id).into_val(&env),
+        );
+        let returned = ax.balance(&contract_address) - before;
+
+        let to_principal = returned.min(loan.principal - loan.repaid);
+        let to_player = returned - to_principal;
+        if to_principal > 0 {
+            ax.transfer(&contract_address, &loan.backer, &to_principal);
+        }
+        if to_player > 0 {
+            ax.transfer(&contract_address, &player, &to_player);
+        }
+
+        loan.repaid += to_principal;
+        loan.stake_reclaimed = true;
+        events::emit_stake_reclaimed(&env, loan_id, &player, returned, to_principal, to_player);
+        if loan.state == LoanState::Active as u32 || loan.state == LoanState::Defaulted as u32 {
+            Self::settle(&env, &player, &mut loan);
+        } else {
+            Self::save_loan(&env, &loan);
+        }
+        returned
+    }
+
+    /// Report that `player`'s stake was slashed by `amount` (reporters only). Their
+    /// active loan is marked defaulted.
+    pub fn report_slash(env: Env, reporter: Address, player: Address, amount: i128) {
+        reporter.require_auth();
+        if !Self::is_reporter(env.clone(), reporter) {
+            panic!("not a reporter");
+        }
+        let mut loan = Self::get_open_loan(&env, &player).expect("no open loan");
+        if loan.state != LoanState::Active as u32 {
+            panic!("loan not active");
+        }
+        loan.state = LoanState::Defaulted as u32;
+        Self::save_loan(&env, &loan);
+        events::emit_loan_defaulted(
+            &env,
+            loan.loan_id,
+            &player,
+            amount,
+            loan.principal - loan.repaid,
+        );
+    }
+
+    /// Whether prizes in `asset` won by `player` should be routed through this
+    /// contract. Queried by the prize distribution contract.
+    pub fn has_open_loan(env: Env, player: Address, asset: Address) -> bool {
+        asset == Self::get_ax_token(env.clone())
+            && env.storage().persistent().has(&DataKey::PlayerLoan(player))
+    }
+
+    pub fn get_player_loan(env: Env, player: Address) -> Option<Loan> {
+        Self::get_open_loan(&env, &player)
+    }
+
+    pub fn get_loan(env: Env, loan_id: u64) -> Loan {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Loan(loan_id))
+            .expect("loan not found")
+    }
+
+    pub fn is_reporter(env: Env, reporter: Address) -> bool {
+        env.storage().instance().has(&DataKey::Reporter(reporter))
+    }
+
+    pub fn get_ax_token(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::AxToken)
+            .expect("not initialized")
+    }
+
+    pub fn get_staking_manager(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::StakingManager)
+            .expect("not initialized")
+    }
+
+    pub fn get_prize_distribution(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::PrizeDistribution)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn get_open_loan(env: &Env, player: &Address) -> Option<Loan> {
+        let loan_id: u64 = env
+            .storage()
+            .persistent()
+            .get(&DataKey::PlayerLoan(player.clone()))?;
+        Some(Self::get_loan(env.clone(), loan_id))
+    }
+
+    /// Save `loan`, closing it once its principal is fully repaid.
+    fn settle(env: &Env, player: &Address, loan: &mut Loan) {
+        if loan.repaid >= loan.principal {
+            loan.state = LoanState::Repaid as u32;
+            env.storage()
+                .persistent()
+                .remove(&DataKey::PlayerLoan(player.clone()));
+            events::emit_loan_repaid(env, loan.loan_id, player, loan.principal);
+        }
+        Self::save_loan(env, loan);
+    }
+
+    fn save_loan(env: &Env, loan: &Loan) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Loan(loan.loan_id), loan);
+    }
+}
+
+#[cfg(test)]
+mod test;
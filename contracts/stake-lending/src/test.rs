@@ -0,0 +1,296 @@
+#![cfg(test)]
+
+use super::*;
+use dispute_resolution::{DisputeResolutionContract, DisputeResolutionContractClient};
+use match_contract::{GameConfig, MatchContract, MatchContractClient};
+use prize_distribution::{PrizeDistributionContract, PrizeDistributionContractClient};
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, Vec,
+};
+use staking_manager::{StakingManager, StakingManagerClient, TournamentState};
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    backer: Address,
+    player: Address,
+    reporter: Address,
+    client: StakeLendingContractClient<'a>,
+    ax: TokenClient<'a>,
+    ax_admin: StellarAssetClient<'a>,
+    staking: StakingManagerClient<'a>,
+    matches: MatchContractClient<'a>,
+    prizes: PrizeDistributionContractClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let backer = Address::generate(&env);
+    let player = Address::generate(&env);
+    let reporter = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    let ax_admin = StellarAssetClient::new(&env, &sac.address());
+    ax_admin.mint(&backer, &10_000);
+
+    let staking = StakingManagerClient::new(&env, &env.register(StakingManager, ()));
+    staking.initialize(&admin, &sac.address());
+    for seed in [7, 8] {
+        let tournament_id = tournament(&env, seed);
+        staking.create_tournament(&tournament_id, &100);
+        staking.update_tournament_state(&tournament_id, &(TournamentState::Active as u32));
+    }
+
+    let matches = MatchContractClient::new(&env, &env.register(MatchContract, ()));
+    matches.initialize(&admin);
+    matches.register_game(
+        &symbol_short!("chess"),
+        &GameConfig {
+            max_score: 1,
+            round_count: 1,
+            modes: Vec::new(&env),
+        },
+    );
+    let disputes =
+        DisputeResolutionContractClient::new(&env, &env.register(DisputeResolutionContract, ()));
+    disputes.initialize(&admin, &Address::generate(&env), &3_600);
+    let prizes =
+        PrizeDistributionContractClient::new(&env, &env.register(PrizeDistributionContract, ()));
+    prizes.initialize(&admin, &matches.address, &disputes.address);
+
+    let contract_id = env.register(StakeLendingContract, ());
+    let client = StakeLendingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &sac.address(), &staking.address, &prizes.address);
+    client.set_reporter(&reporter, &true);
+    prizes.set_payout_hook(&contract_id);
+
+    Setup {
+        ax: TokenClient::new(&env, &sac.address()),
+        env,
+        admin,
+        backer,
+        player,
+        reporter,
+        client,
+        ax_admin,
+        staking,
+        matches,
+        prizes,
+    }
+}
+
+fn tournament(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+/// Lend 1_000 AX to the player for a 30% profit share.
+fn lend(s: &Setup) -> u64 {
+    let loan_id = s.client.offer(&s.backer, &1_000, &3_000, &2_000);
+    s.client.accept(&s.player, &loan_id, &tournament(&s.env, 7));
+    loan_id
+}
+
+/// Win a match and have its prize pool of `amount` distributed to the player, which
+/// routes the prize through the payout hook.
+fn pay_prize(s: &Setup, seed: u8, amount: i128) {
+    let match_id = BytesN::from_array(&s.env, &[seed; 32]);
+    s.matches.create_match(
+        &match_id,
+        &s.player,
+        &Address::generate(&s.env),
+        &symbol_short!("chess"),
+        &symbol_short!("eu"),
+        &symbol_short!("ranked"),
+        &s.admin,
+    );
+    let sponsor = Address::generate(&s.env);
+    s.ax_admin.mint(&sponsor, &amount);
+    let pool_id = s
+        .prizes
+        .create_pool(&sponsor, &match_id, &s.ax.address, &amount);
+    s.prizes.distribute(
+        &s.admin,
+        &pool_id,
+        &vec![&s.env, s.player.clone()],
+        &vec![&s.env, 10_000u32],
+    );
+}
+
+fn end_tournament(s: &Setup) {
+    s.staking
+        .update_tournament_state(&tournament(&s.env, 7), &(TournamentState::Completed as u32));
+}
+
+#[test]
+fn test_accept_stakes_principal() {
+    let s = setup();
+    let loan_id = lend(&s);
+    assert_eq!(s.ax.balance(&s.staking.address), 1_000);
+    assert_eq!(s.ax.balance(&s.player), 0);
+    assert_eq!(s.ax.balance(&s.backer), 9_000);
+    assert_eq!(
+        s.staking
+            .get_stake(&s.player, &tournament(&s.env, 7))
+            .amount,
+        1_000
+    );
+    assert_eq!(
+        s.staking
+            .get_stake_funder(&s.player, &tournament(&s.env, 7)),
+        Some(s.client.address.clone())
+    );
+
+    let loan = s.client.get_loan(&loan_id);
+    assert_eq!(loan.state, LoanState::Active as u32);
+    assert_eq!(loan.player, Some(s.player.clone()));
+    assert!(s.client.has_open_loan(&s.player, &s.ax.address));
+    assert!(!s.client.has_open_loan(&s.player, &s.staking.address));
+
+    // One open loan per player.
+    let second = s.client.offer(&s.backer, &500, &0, &2_000);
+    assert!(s
+        .client
+        .try_accept(&s.player, &second, &tournament(&s.env, 8))
+        .is_err());
+}
+
+#[test]
+fn test_prize_repays_principal_before_profit_split() {
+    let s = setup();
+    let loan_id = lend(&s);
+
+    pay_prize(&s, 1, 600);
+    assert_eq!(s.ax.balance(&s.backer), 9_600);
+    assert_eq!(s.ax.balance(&s.player), 0);
+    assert_eq!(s.client.get_loan(&loan_id).repaid, 600);
+
+    // 400 closes the principal; 30% of the remaining 1_000 goes to the backer.
+    pay_prize(&s, 2, 1_400);
+    assert_eq!(s.ax.balance(&s.backer), 10_300);
+    assert_eq!(s.ax.balance(&s.player), 700);
+    assert_eq!(s.ax.balance(&s.prizes.address), 0);
+    assert_eq!(s.client.get_loan(&loan_id).state, LoanState::Repaid as u32);
+    assert!(!s.client.has_open_loan(&s.player, &s.ax.address));
+    assert_eq!(s.client.get_player_loan(&s.player), None);
+
+    // Without an open loan, prizes go straight to the player.
+    pay_prize(&s, 3, 500);
+    assert_eq!(s.ax.balance(&s.player), 1_200);
+}
+
+#[test]
+fn test_direct_repayment_closes_loan() {
+    let s = setup();
+    let loan_id = lend(&s);
+    s.ax_admin.mint(&s.player, &2_000);
+
+    assert_eq!(s.client.repay(&s.player, &2_000), 1_000);
+    assert_eq!(s.ax.balance(&s.player), 1_000);
+    assert_eq!(s.ax.balance(&s.backer), 10_000);
+    assert_eq!(s.client.get_loan(&loan_id).state, LoanState::Repaid as u32);
+    assert!(s.client.try_repay(&s.player, &1).is_err());
+}
+
+#[test]
+fn test_slashed_player_defaults_until_recovered() {
+    let s = setup();
+    let loan_id = lend(&s);
+    let outsider = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_report_slash(&outsider, &s.player, &1_000)
+        .is_err());
+
+    s.client.report_slash(&s.reporter, &s.player, &1_000);
+    assert_eq!(
+        s.client.get_loan(&loan_id).state,
+        LoanState::Defaulted as u32
+    );
+
+    // A defaulted player cannot borrow again...
+    let other = s.client.offer(&s.backer, &500, &0, &2_000);
+    assert!(s
+        .client
+        .try_accept(&s.player, &other, &tournament(&s.env, 8))
+        .is_err());
+
+    // ...until later winnings recover the principal.
+    pay_prize(&s, 1, 1_000);
+    assert_eq!(s.client.get_loan(&loan_id).state, LoanState::Repaid as u32);
+    s.client.accept(&s.player, &other, &tournament(&s.env, 8));
+}
+
+#[test]
+fn test_reclaimed_stake_repays_backer() {
+    let s = setup();
+    let loan_id = lend(&s);
+    assert!(s.client.try_reclaim_stake(&loan_id).is_err());
+
+    end_tournament(&s);
+    assert_eq!(s.client.reclaim_stake(&loan_id), 1_000);
+    assert_eq!(s.ax.balance(&s.backer), 10_000);
+    assert_eq!(s.ax.balance(&s.player), 0);
+
+    let loan = s.client.get_loan(&loan_id);
+    assert_eq!(loan.state, LoanState::Repaid as u32);
+    assert!(loan.stake_reclaimed);
+    assert!(s.client.try_reclaim_stake(&loan_id).is_err());
+}
+
+#[test]
+fn test_reclaimed_stake_goes_to_player_once_prizes_repaid() {
+    let s = setup();
+    let loan_id = lend(&s);
+    pay_prize(&s, 1, 1_000);
+    assert_eq!(s.client.get_loan(&loan_id).state, LoanState::Repaid as u32);
+
+    end_tournament(&s);
+    s.client.reclaim_stake(&loan_id);
+    assert_eq!(s.ax.balance(&s.backer), 10_000);
+    assert_eq!(s.ax.balance(&s.player), 1_000);
+}
+
+#[test]
+fn test_slashed_stake_reclaim_leaves_remainder_outstanding() {
+    let s = setup();
+    let loan_id = lend(&s);
+    s.staking
+        .slash(&s.player, &tournament(&s.env, 7), &400, &s.admin);
+    s.client.report_slash(&s.reporter, &s.player, &400);
+
+    end_tournament(&s);
+    assert_eq!(s.client.reclaim_stake(&loan_id), 600);
+    assert_eq!(s.ax.balance(&s.backer), 9_600);
+    let loan = s.client.get_loan(&loan_id);
+    assert_eq!(loan.state, LoanState::Defaulted as u32);
+    assert_eq!(loan.repaid, 600);
+
+    pay_prize(&s, 1, 400);
+    assert_eq!(s.ax.balance(&s.backer), 10_000);
+    assert_eq!(s.client.get_loan(&loan_id).state, LoanState::Repaid as u32);
+}
+
+#[test]
+fn test_offer_withdrawal_and_expiry() {
+    let s = setup();
+    let loan_id = s.client.offer(&s.backer, &1_000, &3_000, &2_000);
+    s.env.ledger().set_timestamp(2_001);
+    assert!(s
+        .client
+        .try_accept(&s.player, &loan_id, &tournament(&s.env, 7))
+        .is_err());
+
+    s.client.withdraw_offer(&loan_id);
+    assert_eq!(s.ax.balance(&s.backer), 10_000);
+    assert_eq!(
+        s.client.get_loan(&loan_id).state,
+        LoanState::Withdrawn as u32
+    );
+    assert!(s.client.try_withdraw_offer(&loan_id).is_err());
+}
@@ -1,7 +1,8 @@
 #![no_std]
 
 use arenax_events::staking as events;
-use soroban_sdk::{contract, contractimpl, contracttype, token, Address, BytesN, Env, Vec};
+use arenax_token_interface as token;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env};
 
 // ─── Storage Keys ────────────────────────────────────────────────────────────
 
@@ -12,7 +13,9 @@ pub enum DataKey {
     AxToken,
     TournamentContract,
     DisputeContract,
+    Slasher(Address),
     Stake(BytesN<32>, Address),
+    StakeFunder(BytesN<32>, Address), // (tournament_id, user) -> who paid for the stake
     TournamentInfo(BytesN<32>),
     UserStakeInfo(Address),
     // Reward staking (general, non-tournament)
@@ -164,6 +167,18 @@ impl StakingManager {
         events::emit_dispute_contract_set(&env, &dispute_contract);
     }
 
+    /// Allow or revoke a contract (e.g. the slashing coordinator) to slash stakes.
+    pub fn set_slasher(env: Env, slasher: Address, allowed: bool) {
+        Self::require_admin(&env);
+        let key = DataKey::Slasher(slasher.clone());
+        if allowed {
+            env.storage().instance().set(&key, &true);
+        } else {
+            env.storage().instance().remove(&key);
+        }
+        events::emit_slasher_set(&env, &slasher, allowed);
+    }
+
     pub fn set_reward_config(env: Env, annual_rate_bps: u32, min_stake: i128) {
         Self::require_admin(&env);
         if annual_rate_bps > 10_000 {
@@ -475,71 +490,54 @@ impl StakingManager {
     pub fn stake(env: Env, user: Address, tournament_id: BytesN<32>, amount: i128) {
         Self::require_not_paused(&env);
         user.require_auth();
-        if amount <= 0 {
-            panic!("amount must be positive");
-        }
-        let info: TournamentInfo = env
-            .storage()
-            .persistent()
-            .get(&DataKey::TournamentInfo(tournament_id.clone()))
-            .expect("tournament not found");
-        if info.state != TournamentState::Active as u32 {
-            panic!("tournament not active");
-        }
-        if amount < info.stake_requirement {
-            panic!("below stake requirement");
-        }
-        let stake_key = DataKey::Stake(tournament_id.clone(), user.clone());
-        if env.storage().persistent().has(&stake_key) {
-            panic!("already staked");
-        }
-
-        let ax_token = Self::get_ax_token(env.clone());
-        token::Client::new(&env, &ax_token).transfer(
-            &user,
-            &env.current_contract_address(),
-            &amount,
-        );
+        Self::add_stake(&env, &user, &user, &tournament_id, amount);
+    }
 
-        env.storage().persistent().set(
-            &stake_key,
-            &StakeInfo {
-                user: user.clone(),
-                tournament_id: tournament_id.clone(),
-                amount,
-                staked_at: env.ledger().timestamp(),
-                is_locked: true,
-                can_withdraw: false,
-            },
-        );
-        let mut updated = info;
-        updated.total_staked += amount;
-        updated.participant_count += 1;
+    /// Stake `amount` paid by `funder` in `user`'s name, e.g. a loan from the stake
+    /// lending contract. Only the funder can withdraw the stake, and it is paid back
+    /// to the funder.
+    pub fn stake_for(
+        env: Env,
+        funder: Address,
+        user: Address,
+        tournament_id: BytesN<32>,
+        amount: i128,
+    ) {
+        Self::require_not_paused(&env);
+        funder.require_auth();
+        Self::add_stake(&env, &funder, &user, &tournament_id, amount);
         env.storage()
             .persistent()
-            .set(&DataKey::TournamentInfo(tournament_id.clone()), &updated);
-        Self::update_user_stake_info(&env, &user, amount, 0, 1, 0);
-        events::emit_staked(&env, &user, &tournament_id, amount);
+            .set(&DataKey::StakeFunder(tournament_id, user), &funder);
     }
 
+    /// Withdraw a stake once it is released or its tournament has ended. Stakes paid
+    /// for with `stake_for` are withdrawn by, and returned to, their funder.
     pub fn withdraw(env: Env, user: Address, tournament_id: BytesN<32>) {
         Self::require_not_paused(&env);
-        user.require_auth();
+        let funder_key = DataKey::StakeFunder(tournament_id.clone(), user.clone());
+        let recipient: Address = env
+            .storage()
+            .persistent()
+            .get(&funder_key)
+            .unwrap_or(user.clone());
+        recipient.require_auth();
         let stake_key = DataKey::Stake(tournament_id.clone(), user.clone());
         let info: StakeInfo = env
             .storage()
             .persistent()
             .get(&stake_key)
             .expect("no stake");
-        if !info.can_withdraw {
+        if !Self::is_withdrawable(&env, &info) {
             panic!("stake not withdrawable");
         }
         token::Client::new(&env, &Self::get_ax_token(env.clone())).transfer(
             &env.current_contract_address(),
-            &user,
+            &recipient,
             &info.amount,
         );
         env.storage().persistent().remove(&stake_key);
+        env.storage().persistent().remove(&funder_key);
         Self::update_user_stake_info(&env, &user, -info.amount, 0, -1, 1);
         events::emit_withdrawn(&env, &user, &tournament_id, info.amount);
     }
@@ -552,7 +550,8 @@ impl StakingManager {
         slashed_by: Address,
     ) {
         Self::require_not_paused(&env);
-        Self::require_dispute_contract_or_admin(&env, &slashed_by);
+        slashed_by.require_auth();
+        Self::require_slasher(&env, &slashed_by);
         if amount <= 0 {
             panic!("amount must be positive");
         }
@@ -574,6 +573,9 @@ impl StakingManager {
         info.amount -= amount;
         if info.amount == 0 {
             env.storage().persistent().remove(&stake_key);
+            env.storage()
+                .persistent()
+                .remove(&DataKey::StakeFunder(tournament_id.clone(), user.clone()));
         } else {
             env.storage().persistent().set(&stake_key, &info);
         }
@@ -626,10 +628,17 @@ impl StakingManager {
         env.storage()
             .persistent()
             .get::<DataKey, StakeInfo>(&DataKey::Stake(tournament_id, user))
-            .map(|s| s.can_withdraw)
+            .map(|s| Self::is_withdrawable(&env, &s))
             .unwrap_or(false)
     }
 
+    /// Who paid for `user`'s stake in `tournament_id`, if it was staked with `stake_for`.
+    pub fn get_stake_funder(env: Env, user: Address, tournament_id: BytesN<32>) -> Option<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::StakeFunder(tournament_id, user))
+    }
+
     pub fn get_admin(env: Env) -> Address {
         env.storage()
             .instance()
@@ -644,6 +653,10 @@ impl StakingManager {
             .expect("AX token not set")
     }
 
+    pub fn is_slasher(env: Env, slasher: Address) -> bool {
+        env.storage().instance().has(&DataKey::Slasher(slasher))
+    }
+
     pub fn is_paused(env: Env) -> bool {
         env.storage()
             .instance()
@@ -693,6 +706,74 @@ impl StakingManager {
         admin.require_auth();
     }
 
+    fn add_stake(
+        env: &Env,
+        payer: &Address,
+        user: &Address,
+        tournament_id: &BytesN<32>,
+        amount: i128,
+    ) {
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+        let info: TournamentInfo = env
+            .storage()
+            .persistent()
+            .get(&DataKey::TournamentInfo(tournament_id.clone()))
+            .expect("tournament not found");
+        if info.state != TournamentState::Active as u32 {
+            panic!("tournament not active");
+        }
+        if amount < info.stake_requirement {
+            panic!("below stake requirement");
+        }
+        let stake_key = DataKey::Stake(tournament_id.clone(), user.clone());
+        if env.storage().persistent().has(&stake_key) {
+            panic!("already staked");
+        }
+
+        let ax_token = Self::get_ax_token(env.clone());
+        token::Client::new(env, &ax_token).transfer(
+            payer,
+            &env.current_contract_address(),
+            &amount,
+        );
+
+        env.storage().persistent().set(
+            &stake_key,
+            &StakeInfo {
+                user: user.clone(),
+                tournament_id: tournament_id.clone(),
+                amount,
+                staked_at: env.ledger().timestamp(),
+                is_locked: true,
+                can_withdraw: false,
+            },
+        );
+        let mut updated = info;
+        updated.total_staked += amount;
+        updated.participant_count += 1;
+        env.storage()
+            .persistent()
+            .set(&DataKey::TournamentInfo(tournament_id.clone()), &updated);
+        Self::update_user_stake_info(env, user, amount, 0, 1, 0);
+        events::emit_staked(env, user, tournament_id, amount);
+    }
+
+    /// Released explicitly, or its tournament has completed or been cancelled.
+    fn is_withdrawable(env: &Env, info: &StakeInfo) -> bool {
+        if info.can_withdraw {
+            return true;
+        }
+        let state = env
+            .storage()
+            .persistent()
+            .get::<DataKey, TournamentInfo>(&DataKey::TournamentInfo(info.tournament_id.clone()))
+            .map(|t| t.state)
+            .unwrap_or(TournamentState::NotStarted as u32);
+        state == TournamentState::Completed as u32 || state == TournamentState::Cancelled as u32
+    }
+
     fn require_not_paused(env: &Env) {
         if env
             .storage()
@@ -704,11 +785,19 @@ impl StakingManager {
         }
     }
 
-    fn require_dispute_contract_or_admin(env: &Env, caller: &Address) {
+    /// Admin, the dispute contract, or a registered slasher.
+    fn require_slasher(env: &Env, caller: &Address) {
         let admin: Address = env.storage().instance().get(&DataKey::Admin).unwrap();
         if caller == &admin {
             return;
         }
+        if env
+            .storage()
+            .instance()
+            .has(&DataKey::Slasher(caller.clone()))
+        {
+            return;
+        }
         if let Some(dc) = env
             .storage()
             .instance()
@@ -0,0 +1,279 @@
+#![no_std]
+
+//! Escrowed premium memberships.
+//!
+//! Users subscribe to a premium tier for a number of periods (30 days each by
+//! default), locking the full AX price in this contract up front. As each period
+//! completes it matures and can be claimed to the platform account by anyone.
+//! Cancelling refunds every period that has not started yet; the current period
+//! stays paid, so the subscriber keeps premium until it ends. Other contracts gate
+//! features on `is_premium`.
+
+use arenax_events::subscription as events;
+use arenax_token_interface as token;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env};
+
+/// Default billing period: 30 days.
+pub const DEFAULT_PERIOD_SECS: u64 = 30 * 24 * 60 * 60;
+
+/// Upper bound on periods locked by a single subscription.
+pub const MAX_PERIODS: u32 = 36;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    AxToken,
+    Platform,
+    PeriodSecs,
+    Tier(u32),
+    Subscription(Address),
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tier {
+    pub price_per_period: i128,
+    pub active: bool,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Subscription {
+    pub tier_id: u32,
+    /// Price locked in at subscription time.
+    pub price_per_period: i128,
+    pub started_at: u64,
+    pub period_secs: u64,
+    /// Periods paid for; reduced to the periods already started on cancellation.
+    pub periods: u32,
+    pub claimed_periods: u32,
+    pub cancelled: bool,
+}
+
+#[contract]
+pub struct SubscriptionContract;
+
+#[contractimpl]
+impl SubscriptionContract {
+    /// `platform` receives claimed periods.
+    pub fn initialize(env: Env, admin: Address, ax_token: Address, platform: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::AxToken, &ax_token);
+        env.storage().instance().set(&DataKey::Platform, &platform);
+        env.storage()
+            .instance()
+            .set(&DataKey::PeriodSecs, &DEFAULT_PERIOD_SECS);
+    }
+
+    /// Set the account claimed periods are paid to (admin only).
+    pub fn set_platform(env: Env, platform: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Platform, &platform);
+    }
+
+    /// Set the period length for new subscriptions (admin only). Existing
+    /// subscriptions keep the length they started with.
+    pub fn set_period_secs(env: Env, period_secs: u64) {
+        Self::require_admin(&env);
+        if period_secs == 0 {
+            panic!("invalid period");
+        }
+        env.storage()
+            .instance()
+            .set(&DataKey::PeriodSecs, &period_secs);
+    }
+
+    /// Create or update a premium tier (admin only). Price changes only apply to
+    /// new subscriptions.
+    pub fn set_tier(env: Env, tier_id: u32, price_per_period: i128, active: bool) {
+        Self::require_admin(&env);
+        if price_per_period <= 0 {
+            panic!("price must be positive");
+        }
+        env.storage().persistent().set(
+            &DataKey::Tier(tier_id),
+            &Tier {
+                price_per_period,
+                active,
+            },
+        );
+        events::emit_tier_updated(&env, tier_id, price_per_period, active);
+    }
+
+    /// Subscribe to `tier_id` for `periods` periods starting now, locking the whole
+    /// price. A lapsed or cancelled subscription is settled and replaced.
+    pub fn subscribe(env: Env, subscriber: Address, tier_id: u32, periods: u32) {
+        subscriber.require_auth();
+        if periods == 0 || periods > MAX_PERIODS {
+            panic!("invalid periods");
+        }
+        let tier = Self::get_tier(env.clone(), tier_id);
+        if !tier.active {
+            panic!("tier inactive");
+        }
+        if Self::get_subscription(env.clone(), subscriber.clone()).is_some() {
+            if Self::is_premium(env.clone(), subscriber.clone()) {
+                panic!("already subscribed");
+            }
+            Self::claim(env.clone(), subscriber.clone());
+        }
+
+        let locked = tier.price_per_period * periods as i128;
+        token::Client::new(&env, &Self::get_ax_token(env.clone())).transfer(
+            &subscriber,
+            &env.current_contract_address(),
+            &locked,
+        );
+
+        let sub = Subscription {
+            tier_id,
+            price_per_period: tier.price_per_period,
+            started_at: env.ledger().timestamp(),
+            period_secs: Self::get_period_secs(env.clone()),
+            periods,
+            claimed_periods: 0,
+            cancelled: false,
+        };
+        Self::save_subscription(&env, &subscriber, &sub);
+        events::emit_subscribed(
+            &env,
+            &subscriber,
+            tier_id,
+            periods,
+            locked,
+            Self::ends_at(&sub),
+        );
+    }
+
+    /// Pay `subscriber`'s matured, unclaimed periods to the platform. Callable by
+    /// anyone; returns the amount paid.
+    pub fn claim(env: Env, subscriber: Address) -> i128 {
+        let mut sub =
+            Self::get_subscription(env.clone(), subscriber.clone()).expect("no subscription");
+        let elapsed = env.ledger().timestamp() - sub.started_at;
+        let matured = ((elapsed / sub.period_secs) as u32).min(sub.periods);
+        let periods = matured - sub.claimed_periods;
+        if periods == 0 {
+            return 0;
+        }
+
+        let amount = sub.price_per_period * periods as i128;
+        sub.claimed_periods = matured;
+        Self::save_subscription(&env, &subscriber, &sub);
+
+        token::Client::new(&env, &Self::get_ax_token(env.clone())).transfer(
+            &env.current_contract_address(),
+            &Self::get_platform(env.clone()),
+            &amount,
+        );
+        events::emit_periods_claimed(&env, &subscriber, periods, amount);
+        amount
+    }
+
+    /// Cancel the subscription and refund the periods that have not started.
+    /// Premium stays on until the current period ends. Returns the refund.
+    pub fn cancel(env: Env, subscriber: Address) -> i128 {
+        subscriber.require_auth();
+        let mut sub =
+            Self::get_subscription(env.clone(), subscriber.clone()).expect("no subscription");
+        if sub.cancelled {
+            panic!("already cancelled");
+        }
+        let elapsed = env.ledger().timestamp() - sub.started_at;
+        let started = ((elapsed / sub.period_secs) as u32 + 1).min(sub.periods);
+        let refunded_periods = sub.periods - started;
+        let refund = sub.price_per_period * refunded_periods as i128;
+
+        sub.periods = started;
+        sub.cancelled = true;
+        Self::save_subscription(&env, &subscriber, &sub);
+
+        if refund > 0 {
+            token::Client::new(&env, &Self::get_ax_token(env.clone())).transfer(
+                &env.current_contract_address(),
+                &subscriber,
+                &refund,
+            );
+        }
+        events::emit_subscription_cancelled(&env, &subscriber, refunded_periods, refund);
+        refund
+    }
+
+    /// Whether `subscriber` is inside a paid period.
+    pub fn is_premium(env: Env, subscriber: Address) -> bool {
+        Self::get_premium_tier(env, subscriber).is_some()
+    }
+
+    /// The tier `subscriber` currently has premium on, if any.
+    pub fn get_premium_tier(env: Env, subscriber: Address) -> Option<u32> {
+        let sub = Self::get_subscription(env.clone(), subscriber)?;
+        if env.ledger().timestamp() < Self::ends_at(&sub) {
+            Some(sub.tier_id)
+        } else {
+            None
+        }
+    }
+
+    pub fn get_subscription(env: Env, subscriber: Address) -> Option<Subscription> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Subscription(subscriber))
+    }
+
+    pub fn get_tier(env: Env, tier_id: u32) -> Tier {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tier(tier_id))
+            .expect("tier not found")
+    }
+
+    pub fn get_period_secs(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::PeriodSecs)
+            .expect("not initialized")
+    }
+
+    pub fn get_platform(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Platform)
+            .expect("not initialized")
+    }
+
+    pub fn get_ax_token(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::AxToken)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn ends_at(sub: &Subscription) -> u64 {
+        sub.started_at + sub.period_secs * sub.periods as u64
+    }
+
+    fn save_subscription(env: &Env, subscriber: &Address, sub: &Subscription) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Subscription(subscriber.clone()), sub);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,127 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const PERIOD: u64 = DEFAULT_PERIOD_SECS;
+const GOLD: u32 = 1;
+
+struct Setup<'a> {
+    env: Env,
+    user: Address,
+    platform: Address,
+    client: SubscriptionContractClient<'a>,
+    ax: TokenClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let user = Address::generate(&env);
+    let platform = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &sac.address()).mint(&user, &10_000);
+
+    let contract_id = env.register(SubscriptionContract, ());
+    let client = SubscriptionContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &sac.address(), &platform);
+    client.set_tier(&GOLD, &100, &true);
+
+    Setup {
+        ax: TokenClient::new(&env, &sac.address()),
+        env,
+        user,
+        platform,
+        client,
+    }
+}
+
+fn advance(s: &Setup, secs: u64) {
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + secs);
+}
+
+#[test]
+fn test_subscribe_locks_and_grants_premium() {
+    let s = setup();
+    assert!(!s.client.is_premium(&s.user));
+    s.client.subscribe(&s.user, &GOLD, &3);
+
+    assert_eq!(s.ax.balance(&s.user), 9_700);
+    assert_eq!(s.ax.balance(&s.client.address), 300);
+    assert!(s.client.is_premium(&s.user));
+    assert_eq!(s.client.get_premium_tier(&s.user), Some(GOLD));
+    assert!(s.client.try_subscribe(&s.user, &GOLD, &1).is_err());
+
+    advance(&s, 3 * PERIOD);
+    assert!(!s.client.is_premium(&s.user));
+}
+
+#[test]
+fn test_platform_claims_matured_periods() {
+    let s = setup();
+    s.client.subscribe(&s.user, &GOLD, &3);
+    assert_eq!(s.client.claim(&s.user), 0);
+
+    advance(&s, PERIOD + 10);
+    assert_eq!(s.client.claim(&s.user), 100);
+    assert_eq!(s.client.claim(&s.user), 0);
+
+    advance(&s, 5 * PERIOD);
+    assert_eq!(s.client.claim(&s.user), 200);
+    assert_eq!(s.ax.balance(&s.platform), 300);
+    assert_eq!(
+        s.client.get_subscription(&s.user).unwrap().claimed_periods,
+        3
+    );
+}
+
+#[test]
+fn test_cancel_refunds_unstarted_periods() {
+    let s = setup();
+    s.client.subscribe(&s.user, &GOLD, &6);
+    advance(&s, PERIOD + PERIOD / 2);
+
+    // Periods one and two have started; four are refunded.
+    assert_eq!(s.client.cancel(&s.user), 400);
+    assert_eq!(s.ax.balance(&s.user), 9_800);
+    assert!(s.client.is_premium(&s.user));
+    assert!(s.client.try_cancel(&s.user).is_err());
+
+    assert_eq!(s.client.claim(&s.user), 100);
+    advance(&s, PERIOD);
+    assert!(!s.client.is_premium(&s.user));
+    assert_eq!(s.client.claim(&s.user), 100);
+    assert_eq!(s.ax.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_resubscribe_after_lapse_settles_old_subscription() {
+    let s = setup();
+    s.client.subscribe(&s.user, &GOLD, &1);
+    advance(&s, PERIOD);
+    s.client.set_tier(&GOLD, &150, &true);
+
+    s.client.subscribe(&s.user, &GOLD, &2);
+    assert_eq!(s.ax.balance(&s.platform), 100);
+    assert_eq!(s.ax.balance(&s.client.address), 300);
+    assert_eq!(
+        s.client.get_subscription(&s.user).unwrap().price_per_period,
+        150
+    );
+}
+
+#[test]
+fn test_inactive_tier_rejected() {
+    let s = setup();
+    s.client.set_tier(&GOLD, &100, &false);
+    assert!(s.client.try_subscribe(&s.user, &GOLD, &1).is_err());
+    assert!(s.client.try_subscribe(&s.user, &7, &1).is_err());
+}
@@ -0,0 +1,742 @@
+#![no_std]
+
+//! Tournament lifecycle contract.
+//!
+//! - Registration, gated by an optional stake in the Staking Manager and an optional
+//!   minimum skill in the Reputation Index
+//! - Single-elimination bracket generation with byes for the top seeds
+//! - Round progression that creates each round's matches in the Match Contract
+//! - Completion that hands the prize pool to the Prize Distribution contract and mints
+//!   trophies for the podium
+
+use arenax_events::tournament_lifecycle as events;
+use arenax_token_interface as token;
+use soroban_sdk::{
+    auth::{ContractContext, InvokerContractAuthEntry, SubContractInvocation},
+    contract, contractimpl, contracttype, vec, Address, Bytes, BytesN, Env, IntoVal, Symbol, Val,
+    Vec,
+};
+
+/// Largest supported bracket.
+pub const MAX_PLAYERS: u32 = 64;
+/// Placements that receive a trophy on completion.
+pub const TROPHY_PLACEMENTS: u32 = 3;
+const BPS_DENOMINATOR: u32 = 10_000;
+
+/// `MatchState::Completed` / `MatchState::Cancelled` in the Match Contract.
+const MATCH_STATE_COMPLETED: u32 = 2;
+const MATCH_STATE_CANCELLED: u32 = 4;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    MatchContract,
+    PrizeContract,
+    StakingContract,
+    ReputationContract,
+    TrophyContract,
+    Tournament(BytesN<32>),
+    Players(BytesN<32>), // Vec<Address> in seed order
+    Registered(BytesN<32>, Address),
+    Slot(BytesN<32>, u32, u32), // (tournament, round, slot) -> BracketSlot
+    Eliminated(BytesN<32>),     // Vec<Address> in elimination order
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum TournamentState {
+    Registration = 0,
+    InProgress = 1,
+    Completed = 2,
+    Cancelled = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TournamentConfig {
+    pub game_id: Symbol,
+    pub region: Symbol,
+    pub mode: Symbol,
+    /// Power of two between 2 and `MAX_PLAYERS`.
+    pub max_players: u32,
+    pub registration_deadline: u64,
+    /// Require a stake for this tournament id in the Staking Manager.
+    pub stake_required: bool,
+    /// Minimum Reputation Index skill; zero disables the gate.
+    pub min_skill: i128,
+    pub prize_asset: Address,
+    /// Prize share per placement in basis points: champion, runner-up, then the
+    /// remaining players from latest to earliest elimination.
+    pub prize_weights: Vec<u32>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Tournament {
+    pub tournament_id: BytesN<32>,
+    pub organizer: Address,
+    pub config: TournamentConfig,
+    pub prize_amount: i128,
+    pub state: u32,
+    pub player_count: u32,
+    pub bracket_size: u32,
+    pub current_round: u32,
+    pub created_at: u64,
+}
+
+/// One pairing in the bracket. A slot with a single player is a bye.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BracketSlot {
+    pub player_a: Option<Address>,
+    pub player_b: Option<Address>,
+    pub match_id: Option<BytesN<32>>,
+    pub winner: Option<Address>,
+}
+
+/// Mirror of the Match Contract's `MatchData`, decoded from `get_match`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MatchRecord {
+    pub player_a: Address,
+    pub player_b: Address,
+    pub state: u32,
+    pub winner: Option<Address>,
+    pub started_at: u64,
+    pub ended_at: Option<u64>,
+    pub report_a: Option<Address>,
+    pub report_b: Option<Address>,
+    pub game_id: Symbol,
+    pub region: Symbol,
+    pub mode: Symbol,
+}
+
+/// Mirror of the Reputation Index's `Reputation`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PlayerReputation {
+    pub skill: i128,
+    pub fair_play: i128,
+    pub last_update_ts: u64,
+    pub frozen: bool,
+}
+
+#[contract]
+pub struct TournamentContract;
+
+#[contractimpl]
+impl TournamentContract {
+    /// This contract must be allowed to create matches in `match_contract`, either as
+    /// its tournament contract or as a registered creator.
+    pub fn initialize(env: Env, admin: Address, match_contract: Address, prize_contract: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::MatchContract, &match_contract);
+        env.storage()
+            .instance()
+            .set(&DataKey::PrizeContract, &prize_contract);
+    }
+
+    /// Staking Manager consulted for `stake_required` tournaments (admin only).
+    pub fn set_staking_contract(env: Env, staking_contract: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::StakingContract, &staking_contract);
+    }
+
+    /// Reputation Index consulted for `min_skill` (admin only).
+    pub fn set_reputation_contract(env: Env, reputation_contract: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::ReputationContract, &reputation_contract);
+    }
+
+    /// Trophy contract that mints podium trophies on completion (admin only). This
+    /// contract must be registered there as a minter.
+    pub fn set_trophy_contract(env: Env, trophy_contract: Address) {
+        Self::require_admin(&env);
+        env.storage()
+            .instance()
+            .set(&DataKey::TrophyContract, &trophy_contract);
+    }
+
+    /// Create a tournament and escrow `prize_amount` of the prize asset from the organizer.
+    pub fn create_tournament(
+        env: Env,
+        organizer: Address,
+        tournament_id: BytesN<32>,
+        config: TournamentConfig,
+        prize_amount: i128,
+    ) {
+        organizer.require_auth();
+
+        if env
+            .storage()
+            .persistent()
+            .has(&DataKey::Tournament(tournament_id.clone()))
+        {
+            panic!("tournament already exists");
+        }
+        if config.max_players < 2
+            || config.max_players > MAX_PLAYERS
+            || !config.max_players.is_power_of_two()
+        {
+            panic!("invalid max players");
+        }
+        if config.registration_deadline <= env.ledger().timestamp() {
+            panic!("registration deadline must be in the future");
+        }
+        if prize_amount < 0 {
+            panic!("prize amount must not be negative");
+        }
+        if prize_amount > 0 {
+            Self::validate_weights(&config);
+            token::Client::new(&env, &config.prize_asset).transfer(
+                &organizer,
+                &env.current_contract_address(),
+                &prize_amount,
+            );
+        }
+
+        let tournament = Tournament {
+            tournament_id: tournament_id.clone(),
+            organizer: organizer.clone(),
+            config: config.clone(),
+            prize_amount,
+            state: TournamentState::Registration as u32,
+            player_count: 0,
+            bracket_size: 0,
+            current_round: 0,
+            created_at: env.ledger().timestamp(),
+        };
+        Self::save_tournament(&env, &tournament);
+        env.storage().persistent().set(
+            &DataKey::Players(tournament_id.clone()),
+            &Vec::<Address>::new(&env),
+        );
+
+        events::emit_tournament_created(
+            &env,
+            &tournament_id,
+            &organizer,
+            config.max_players,
+            prize_amount,
+        );
+    }
+
+    /// Register `player`. Seeds follow registration order.
+    pub fn register(env: Env, tournament_id: BytesN<32>, player: Address) {
+        player.require_auth();
+
+        let mut tournament = Self::load_tournament(&env, &tournament_id);
+        if tournament.state != TournamentState::Registration as u32 {
+            panic!("registration is closed");
+        }
+        if env.ledger().timestamp() > tournament.config.registration_deadline {
+            panic!("registration deadline has passed");
+        }
+        if tournament.player_count >= tournament.config.max_players {
+            panic!("tournament is full");
+        }
+        let registered_key = DataKey::Registered(tournament_id.clone(), player.clone());
+        if env.storage().persistent().has(&registered_key) {
+            panic!("player already registered");
+        }
+
+        if tournament.config.stake_required {
+            Self::require_stake(&env, &tournament_id, &player);
+        }
+        if tournament.config.min_skill > 0 {
+            Self::require_skill(&env, &player, tournament.config.min_skill);
+        }
+
+        let mut players = Self::get_players(env.clone(), tournament_id.clone());
+        players.push_back(player.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Players(tournament_id.clone()), &players);
+        env.storage().persistent().set(&registered_key, &true);
+
+        let seed = tournament.player_count;
+        tournament.player_count += 1;
+        Self::save_tournament(&env, &tournament);
+
+        events::emit_player_registered(&env, &tournament_id, &player, seed);
+    }
+
+    /// Close registration, generate the bracket and create the first round's matches
+    /// (organizer or admin). Seed `i` meets seed `bracket_size - 1 - i`; top seeds
+    /// without an opponent receive a bye.
+    pub fn start_tournament(env: Env, tournament_id: BytesN<32>, caller: Address) {
+        let mut tournament = Self::load_tournament(&env, &tournament_id);
+        Self::require_organizer_or_admin(&env, &tournament, &caller);
+
+        if tournament.state != TournamentState::Registration as u32 {
+            panic!("tournament already started");
+        }
+        if tournament.player_count < 2 {
+            panic!("not enough players");
+        }
+        if tournament.prize_amount > 0
+            && tournament.config.prize_weights.len() > tournament.player_count
+        {
+            panic!("not enough players for prize structure");
+        }
+
+        let bracket_size = tournament.player_count.next_power_of_two();
+        let players = Self::get_players(env.clone(), tournament_id.clone());
+        let mut round = Vec::new(&env);
+        for slot in 0..bracket_size / 2 {
+            round.push_back(players.get(slot));
+            round.push_back(players.get(bracket_size - 1 - slot));
+        }
+
+        tournament.state = TournamentState::InProgress as u32;
+        tournament.bracket_size = bracket_size;
+        tournament.current_round = 0;
+        Self::save_tournament(&env, &tournament);
+
+        events::emit_tournament_started(
+            &env,
+            &tournament_id,
+            tournament.player_count,
+            bracket_size,
+        );
+        Self::open_round(&env, &tournament, &round);
+    }
+
+    /// Record the winners of the current round from the Match Contract and open the next
+    /// round, or complete the tournament after the final. Callable by anyone.
+    pub fn advance_round(env: Env, tournament_id: BytesN<32>) {
+        let mut tournament = Self::load_tournament(&env, &tournament_id);
+        if tournament.state != TournamentState::InProgress as u32 {
+            panic!("tournament not in progress");
+        }
+
+        let round = tournament.current_round;
+        let slot_count = Self::slot_count(&tournament, round);
+        let mut eliminated = Self::get_eliminated(&env, &tournament_id);
+        let mut winners = Vec::new(&env);
+        let mut final_match = None;
+
+        for slot in 0..slot_count {
+            let key = DataKey::Slot(tournament_id.clone(), round, slot);
+            let mut pairing: BracketSlot = env.storage().persistent().get(&key).unwrap();
+
+            if pairing.winner.is_none() {
+                let match_id = pairing.match_id.clone().unwrap();
+                let record = Self::get_match_record(&env, &match_id);
+                let winner = match record.winner {
+                    Some(winner) if record.state == MATCH_STATE_COMPLETED => winner,
+                    _ => panic!("round not complete"),
+                };
+                pairing.winner = Some(winner.clone());
+                env.storage().persistent().set(&key, &pairing);
+                events::emit_slot_decided(&env, &tournament_id, round, slot, &winner, false);
+            }
+
+            let winner = pairing.winner.clone().unwrap();
+            if let (Some(a), Some(b)) = (&pairing.player_a, &pairing.player_b) {
+                eliminated.push_back(if *a == winner { b.clone() } else { a.clone() });
+            }
+            final_match = pairing.match_id.clone();
+            winners.push_back(Some(winner));
+        }
+
+        env.storage()
+            .persistent()
+            .set(&DataKey::Eliminated(tournament_id.clone()), &eliminated);
+
+        if slot_count == 1 {
+            let champion = winners.get(0).unwrap().unwrap();
+            Self::complete(&env, &mut tournament, &champion, &final_match.unwrap());
+            return;
+        }
+
+        tournament.current_round += 1;
+        Self::save_tournament(&env, &tournament);
+        Self::open_round(&env, &tournament, &winners);
+    }
+
+    /// Decide a slot whose match was cancelled or settled without a winner (organizer or
+    /// admin). `winner` must be one of the slot's players.
+    pub fn award_walkover(
+        env: Env,
+        tournament_id: BytesN<32>,
+        caller: Address,
+        slot: u32,
+        winner: Address,
+    ) {
+        let tournament = Self::load_tournament(&env, &tournament_id);
+        Self::require_organizer_or_admin(&env, &tournament, &caller);
+
+        if tournament.state != TournamentState::InProgress as u32 {
+            panic!("tournament not in progress");
+        }
+
+        let round = tournament.current_round;
+        let key = DataKey::Slot(tournament_id.clone(), round, slot);
+        let mut pairing: BracketSlot = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .expect("slot not found");
+        if pairing.winner.is_some() {
+            panic!("slot already decided");
+        }
+        if pairing.player_a != Some(winner.clone()) && pairing.player_b != Some(winner.clone()) {
+            panic!("winner is not in this slot");
+        }
+
+        let record = Self::get_match_record(&env, &pairing.match_id.clone().unwrap());
+        let no_result = record.state == MATCH_STATE_CANCELLED
+            || (record.state == MATCH_STATE_COMPLETED && record.winner.is_none());
+        if !no_result {
+            panic!("match has a result");
+        }
+
+        pairing.winner = Some(winner.clone());
+        env.storage().persistent().set(&key, &pairing);
+        events::emit_slot_decided(&env, &tournament_id, round, slot, &winner, true);
+    }
+
+    /// Cancel a tournament that has not started and refund the prize to the organizer
+    /// (organizer or admin).
+    pub fn cancel_tournament(env: Env, tournament_id: BytesN<32>, caller: Address) {
+        let mut tournament = Self::load_tournament(&env, &tournament_id);
+        Self::require_organizer_or_admin(&env, &tournament, &caller);
+
+        if tournament.state != TournamentState::Registration as u32 {
+            panic!("tournament already started");
+        }
+
+        let refunded = tournament.prize_amount;
+        if refunded > 0 {
+            token::Client::new(&env, &tournament.config.prize_asset).transfer(
+                &env.current_contract_address(),
+                &tournament.organizer,
+                &refunded,
+            );
+        }
+
+        tournament.state = TournamentState::Cancelled as u32;
+        Self::save_tournament(&env, &tournament);
+        events::emit_tournament_cancelled(&env, &tournament_id, refunded);
+    }
+
+    pub fn get_tournament(env: Env, tournament_id: BytesN<32>) -> Tournament {
+        Self::load_tournament(&env, &tournament_id)
+    }
+
+    pub fn get_players(env: Env, tournament_id: BytesN<32>) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Players(tournament_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_round(env: Env, tournament_id: BytesN<32>, round: u32) -> Vec<BracketSlot> {
+        let tournament = Self::load_tournament(&env, &tournament_id);
+        let mut slots = Vec::new(&env);
+        if tournament.bracket_size == 0 || round > tournament.current_round {
+            return slots;
+        }
+        for slot in 0..Self::slot_count(&tournament, round) {
+            slots.push_back(
+                env.storage()
+                    .persistent()
+                    .get(&DataKey::Slot(tournament_id.clone(), round, slot))
+                    .unwrap(),
+            );
+        }
+        slots
+    }
+
+    /// Final standings once completed: champion, runner-up, then the remaining players
+    /// from latest to earliest elimination.
+    pub fn get_placements(env: Env, tournament_id: BytesN<32>) -> Vec<Address> {
+        let tournament = Self::load_tournament(&env, &tournament_id);
+        if tournament.state != TournamentState::Completed as u32 {
+            panic!("tournament not completed");
+        }
+        Self::placements(&env, &tournament_id)
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    // ── Internal helpers ─────────────────────────────────────────────────────
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn require_organizer_or_admin(env: &Env, tournament: &Tournament, caller: &Address) {
+        caller.require_auth();
+        if *caller != tournament.organizer && *caller != Self::get_admin(env.clone()) {
+            panic!("unauthorized caller");
+        }
+    }
+
+    fn load_tournament(env: &Env, tournament_id: &BytesN<32>) -> Tournament {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Tournament(tournament_id.clone()))
+            .expect("tournament not found")
+    }
+
+    fn save_tournament(env: &Env, tournament: &Tournament) {
+        env.storage().persistent().set(
+            &DataKey::Tournament(tournament.tournament_id.clone()),
+            tournament,
+        );
+    }
+
+    fn validate_weights(config: &TournamentConfig) {
+        let weights = &config.prize_weights;
+        if weights.is_empty() || weights.len() > config.max_players {
+            panic!("invalid prize weights");
+        }
+        let sum: u32 = weights.iter().sum();
+        if sum != BPS_DENOMINATOR {
+            panic!("prize weights must sum to 10000");
+        }
+    }
+
+    fn require_stake(env: &Env, tournament_id: &BytesN<32>, player: &Address) {
+        let staking: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::StakingContract)
+            .expect("staking contract not set");
+        let stake = env.try_invoke_contract::<Val, soroban_sdk::Error>(
+            &staking,
+            &Symbol::new(env, "get_stake"),
+            (player.clone(), tournament_id.clone()).into_val(env),
+        );
+        if !matches!(stake, Ok(Ok(_))) {
+            panic!("stake required");
+        }
+    }
+
+    fn require_skill(env: &Env, player: &Address, min_skill: i128) {
+        let reputation: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::ReputationContract)
+            .expect("reputation contract not set");
+        let rep: PlayerReputation = env.invoke_contract(
+            &reputation,
+            &Symbol::new(env, "get_reputation"),
+            (player.clone(),).into_val(env),
+        );
+        if rep.frozen || rep.skill < min_skill {
+            panic!("reputation below requirement");
+        }
+    }
+
+    fn get_match_record(env: &Env, match_id: &BytesN<32>) -> MatchRecord {
+        let match_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchContract)
+            .expect("not initialized");
+        env.invoke_contract(
+            &match_contract,
+            &Symbol::new(env, "get_match"),
+            (match_id.clone(),).into_val(env),
+        )
+    }
+
+    fn get_eliminated(env: &Env, tournament_id: &BytesN<32>) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Eliminated(tournament_id.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn slot_count(tournament: &Tournament, round: u32) -> u32 {
+        tournament.bracket_size >> (round + 1)
+    }
+
+    /// Deterministic match id for a bracket slot.
+    fn slot_match_id(env: &Env, tournament_id: &BytesN<32>, round: u32, slot: u32) -> BytesN<32> {
+        let mut data = Bytes::from_array(env, &tournament_id.to_array());
+        data.extend_from_array(&round.to_be_bytes());
+        data.extend_from_array(&slot.to_be_bytes());
+        env.crypto().sha256(&data).to_bytes()
+    }
+
+    /// Write the current round's slots from `entrants` (consecutive pairs) and create a
+    /// match for every slot with two players.
+    fn open_round(env: &Env, tournament: &Tournament, entrants: &Vec<Option<Address>>) {
+        let match_contract: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::MatchContract)
+            .expect("not initialized");
+        let tournament_id = &tournament.tournament_id;
+        let round = tournament.current_round;
+        let config = &tournament.config;
+
+        let mut matches = 0u32;
+        for slot in 0..entrants.len() / 2 {
+            let player_a = entrants.get(slot * 2).unwrap();
+            let player_b = entrants.get(slot * 2 + 1).unwrap();
+            let mut pairing = BracketSlot {
+                player_a: player_a.clone(),
+                player_b: player_b.clone(),
+                match_id: None,
+                winner: None,
+            };
+
+            match (player_a, player_b) {
+                (Some(a), Some(b)) => {
+                    let match_id = Self::slot_match_id(env, tournament_id, round, slot);
+                    env.invoke_contract::<()>(
+                        &match_contract,
+                        &Symbol::new(env, "create_match"),
+                        (
+                            match_id.clone(),
+                            a,
+                            b,
+                            config.game_id.clone(),
+                            config.region.clone(),
+                            config.mode.clone(),
+                            env.current_contract_address(),
+                        )
+                            .into_val(env),
+                    );
+                    pairing.match_id = Some(match_id);
+                    matches += 1;
+                }
+                (Some(a), None) | (None, Some(a)) => {
+                    pairing.winner = Some(a.clone());
+                    events::emit_slot_decided(env, tournament_id, round, slot, &a, true);
+                }
+                (None, None) => panic!("empty bracket slot"),
+            }
+
+            env.storage()
+                .persistent()
+                .set(&DataKey::Slot(tournament_id.clone(), round, slot), &pairing);
+        }
+
+        events::emit_round_started(env, tournament_id, round, matches);
+    }
+
+    fn placements(env: &Env, tournament_id: &BytesN<32>) -> Vec<Address> {
+        let eliminated = Self::get_eliminated(env, tournament_id);
+        let mut placements = Vec::new(env);
+        let final_slot: BracketSlot = {
+            let tournament = Self::load_tournament(env, tournament_id);
+            env.storage()
+                .persistent()
+                .get(&DataKey::Slot(
+                    tournament_id.clone(),
+                    tournament.current_round,
+                    0,
+                ))
+                .unwrap()
+        };
+        placements.push_back(final_slot.winner.unwrap());
+        for i in (0..eliminated.len()).rev() {
+            placements.push_back(eliminated.get(i).unwrap());
+        }
+        placements
+    }
+
+    /// Mark the tournament completed and pay out through Prize Distribution: a pool is
+    /// created against the final match and distributed to the top placements. The
+    /// podium then receives trophies when a trophy contract is configured.
+    fn complete(
+        env: &Env,
+        tournament: &mut Tournament,
+        champion: &Address,
+        final_match: &BytesN<32>,
+    ) {
+        tournament.state = TournamentState::Completed as u32;
+        Self::save_tournament(env, tournament);
+
+        let tournament_id = tournament.tournament_id.clone();
+        let mut pool_id = None;
+        if tournament.prize_amount > 0 {
+            let prize_contract: Address = env
+                .storage()
+                .instance()
+                .get(&DataKey::PrizeContract)
+                .expect("not initialized");
+            let this = env.current_contract_address();
+            let asset = tournament.config.prize_asset.clone();
+            let amount = tournament.prize_amount;
+
+            // `create_pool` pulls the funds from this contract.
+            env.authorize_as_current_contract(vec![
+                env,
+                InvokerContractAuthEntry::Contract(SubContractInvocation {
+                    context: ContractContext {
+                        contract: asset.clone(),
+                        fn_name: Symbol::new(env, "transfer"),
+                        args: (this.clone(), prize_contract.clone(), amount).into_val(env),
+                    },
+                    sub_invocations: Vec::new(env),
+                }),
+            ]);
+            let id: u64 = env.invoke_contract(
+                &prize_contract,
+                &Symbol::new(env, "create_pool"),
+                (this.clone(), final_match.clone(), asset, amount).into_val(env),
+            );
+
+            let weights = tournament.config.prize_weights.clone();
+            let placements = Self::placements(env, &tournament_id);
+            let mut winners = Vec::new(env);
+            for i in 0..weights.len() {
+                winners.push_back(placements.get(i).unwrap());
+            }
+            env.invoke_contract::<()>(
+                &prize_contract,
+                &Symbol::new(env, "distribute"),
+                (this, id, winners, weights).into_val(env),
+            );
+            pool_id = Some(id);
+        }
+
+        let trophy_contract: Option<Address> =
+            env.storage().instance().get(&DataKey::TrophyContract);
+        if let Some(trophy_contract) = trophy_contract {
+            let placements = Self::placements(env, &tournament_id);
+            for i in 0..placements.len().min(TROPHY_PLACEMENTS) {
+                env.invoke_contract::<u64>(
+                    &trophy_contract,
+                    &Symbol::new(env, "mint"),
+                    (
+                        env.current_contract_address(),
+                        placements.get(i).unwrap(),
+                        tournament_id.clone(),
+                        i + 1,
+                    )
+                        .into_val(env),
+                );
+            }
+        }
+
+        events::emit_tournament_completed(env, &tournament_id, champion, pool_id);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,414 @@
+#![cfg(test)]
+
+use super::*;
+use match_contract::{GameConfig, MatchContract, MatchContractClient, MatchError};
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, BytesN, Env, Vec,
+};
+use trophy::{TrophyContract, TrophyContractClient};
+
+/// Prize Distribution stand-in: holds pools and pays them out by weight.
+#[contract]
+pub struct MockPrizeDistribution;
+
+#[contractimpl]
+impl MockPrizeDistribution {
+    pub fn create_pool(
+        env: Env,
+        creator: Address,
+        _match_id: BytesN<32>,
+        asset: Address,
+        amount: i128,
+    ) -> u64 {
+        creator.require_auth();
+        token::Client::new(&env, &asset).transfer(
+            &creator,
+            &env.current_contract_address(),
+            &amount,
+        );
+        env.storage().persistent().set(&1u64, &(asset, amount));
+        1
+    }
+
+    pub fn distribute(
+        env: Env,
+        caller: Address,
+        pool_id: u64,
+        winners: Vec<Address>,
+        weights: Vec<u32>,
+    ) {
+        caller.require_auth();
+        let (asset, amount): (Address, i128) = env.storage().persistent().get(&pool_id).unwrap();
+        let client = token::Client::new(&env, &asset);
+        for i in 0..winners.len() {
+            let payout = amount * weights.get(i).unwrap() as i128 / 10_000;
+            client.transfer(
+                &env.current_contract_address(),
+                &winners.get(i).unwrap(),
+                &payout,
+            );
+        }
+    }
+}
+
+/// Staking Manager stand-in: `get_stake` panics for users without a stake.
+#[contract]
+pub struct MockStakingManager;
+
+#[contractimpl]
+impl MockStakingManager {
+    pub fn set_stake(env: Env, user: Address, tournament_id: BytesN<32>, amount: i128) {
+        env.storage()
+            .persistent()
+            .set(&(user, tournament_id), &amount);
+    }
+
+    pub fn get_stake(env: Env, user: Address, tournament_id: BytesN<32>) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&(user, tournament_id))
+            .expect("no stake")
+    }
+}
+
+#[contract]
+pub struct MockReputationIndex;
+
+#[contractimpl]
+impl MockReputationIndex {
+    pub fn set_skill(env: Env, player: Address, skill: i128) {
+        env.storage().persistent().set(&player, &skill);
+    }
+
+    pub fn get_reputation(env: Env, player: Address) -> PlayerReputation {
+        PlayerReputation {
+            skill: env.storage().persistent().get(&player).unwrap_or(1000),
+            fair_play: 100,
+            last_update_ts: 0,
+            frozen: false,
+        }
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    organizer: Address,
+    client: TournamentContractClient<'a>,
+    matches: MatchContractClient<'a>,
+    token: TokenClient<'a>,
+    staking: MockStakingManagerClient<'a>,
+    reputation: MockReputationIndexClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let organizer = Address::generate(&env);
+
+    let match_id = env.register(MatchContract, ());
+    let matches = MatchContractClient::new(&env, &match_id);
+    matches.initialize(&admin);
+    matches.register_game(
+        &symbol_short!("chess"),
+        &GameConfig {
+            max_score: 1,
+            round_count: 1,
+            modes: Vec::new(&env),
+        },
+    );
+
+    let prize_id = env.register(MockPrizeDistribution, ());
+    let staking_id = env.register(MockStakingManager, ());
+    let reputation_id = env.register(MockReputationIndex, ());
+
+    let contract_id = env.register(TournamentContract, ());
+    let client = TournamentContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &match_id, &prize_id);
+    matches.set_tournament_contract(&contract_id);
+    client.set_staking_contract(&staking_id);
+    client.set_reputation_contract(&reputation_id);
+
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &sac.address()).mint(&organizer, &100_000);
+
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        staking: MockStakingManagerClient::new(&env, &staking_id),
+        reputation: MockReputationIndexClient::new(&env, &reputation_id),
+        env,
+        admin,
+        organizer,
+        client,
+        matches,
+    }
+}
+
+fn config(s: &Setup, max_players: u32, weights: Vec<u32>) -> TournamentConfig {
+    TournamentConfig {
+        game_id: symbol_short!("chess"),
+        region: symbol_short!("eu"),
+        mode: symbol_short!("ranked"),
+        max_players,
+        registration_deadline: 2_000,
+        stake_required: false,
+        min_skill: 0,
+        prize_asset: s.token.address.clone(),
+        prize_weights: weights,
+    }
+}
+
+fn create(s: &Setup, config: TournamentConfig, prize: i128) -> BytesN<32> {
+    let tournament_id = BytesN::from_array(&s.env, &[7u8; 32]);
+    s.client
+        .create_tournament(&s.organizer, &tournament_id, &config, &prize);
+    tournament_id
+}
+
+fn register_players(s: &Setup, tournament_id: &BytesN<32>, count: u32) -> Vec<Address> {
+    let mut players = Vec::new(&s.env);
+    for _ in 0..count {
+        let player = Address::generate(&s.env);
+        s.client.register(tournament_id, &player);
+        players.push_back(player);
+    }
+    players
+}
+
+/// Play every open match of `round`, with the first listed player winning.
+fn play_round(s: &Setup, tournament_id: &BytesN<32>, round: u32) {
+    for slot in s.client.get_round(tournament_id, &round).iter() {
+        if let Some(match_id) = slot.match_id {
+            let a = slot.player_a.unwrap();
+            let b = slot.player_b.unwrap();
+            s.matches.start_match(&match_id, &a);
+            s.matches.submit_result(&match_id, &a, &a);
+            s.matches.submit_result(&match_id, &b, &a);
+            s.matches.finalize_match(&match_id, &a);
+        }
+    }
+}
+
+#[test]
+fn test_full_bracket_with_prizes() {
+    let s = setup();
+    let tournament_id = create(&s, config(&s, 4, vec![&s.env, 6000, 3000, 1000]), 10_000);
+    assert_eq!(s.token.balance(&s.client.address), 10_000);
+
+    let players = register_players(&s, &tournament_id, 4);
+    s.client.start_tournament(&tournament_id, &s.organizer);
+
+    // Seed i meets seed bracket_size - 1 - i.
+    let round0 = s.client.get_round(&tournament_id, &0);
+    assert_eq!(round0.len(), 2);
+    let slot0 = round0.get(0).unwrap();
+    assert_eq!(slot0.player_a, players.get(0));
+    assert_eq!(slot0.player_b, players.get(3));
+    let match_id = slot0.match_id.unwrap();
+    assert_eq!(
+        s.matches.get_match(&match_id).player_a,
+        players.get(0).unwrap()
+    );
+
+    // Matches still open.
+    assert!(s.client.try_advance_round(&tournament_id).is_err());
+
+    play_round(&s, &tournament_id, 0);
+    s.client.advance_round(&tournament_id);
+    let tournament = s.client.get_tournament(&tournament_id);
+    assert_eq!(tournament.current_round, 1);
+
+    let final_slot = s.client.get_round(&tournament_id, &1).get(0).unwrap();
+    assert_eq!(final_slot.player_a, players.get(0));
+    assert_eq!(final_slot.player_b, players.get(1));
+
+    play_round(&s, &tournament_id, 1);
+    s.client.advance_round(&tournament_id);
+
+    let tournament = s.client.get_tournament(&tournament_id);
+    assert_eq!(tournament.state, TournamentState::Completed as u32);
+
+    let placements = s.client.get_placements(&tournament_id);
+    assert_eq!(placements.get(0), players.get(0));
+    assert_eq!(placements.get(1), players.get(1));
+    assert_eq!(placements.len(), 4);
+
+    assert_eq!(s.token.balance(&players.get(0).unwrap()), 6_000);
+    assert_eq!(s.token.balance(&players.get(1).unwrap()), 3_000);
+    assert_eq!(s.token.balance(&placements.get(2).unwrap()), 1_000);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_slot_match_ids_cannot_be_precreated() {
+    let s = setup();
+    let tournament_id = create(&s, config(&s, 2, Vec::new(&s.env)), 0);
+    let players = register_players(&s, &tournament_id, 2);
+
+    // Slot ids are predictable; only creators may use them.
+    let slot_id = TournamentContract::slot_match_id(&s.env, &tournament_id, 0, 0);
+    let attacker = Address::generate(&s.env);
+    assert_eq!(
+        s.matches.try_create_match(
+            &slot_id,
+            &attacker,
+            &Address::generate(&s.env),
+            &symbol_short!("chess"),
+            &symbol_short!("eu"),
+            &symbol_short!("ranked"),
+            &attacker,
+        ),
+        Err(Ok(MatchError::Unauthorized))
+    );
+
+    s.client.start_tournament(&tournament_id, &s.organizer);
+    let slot = s.client.get_round(&tournament_id, &0).get(0).unwrap();
+    assert_eq!(slot.match_id, Some(slot_id.clone()));
+    assert_eq!(s.matches.get_match(&slot_id).player_a, players.get(0).unwrap());
+}
+
+#[test]
+fn test_podium_receives_trophies() {
+    let s = setup();
+    let trophy_id = s.env.register(TrophyContract, ());
+    let trophies = TrophyContractClient::new(&s.env, &trophy_id);
+    trophies.initialize(&s.admin, &true);
+    trophies.set_minter(&s.client.address, &true);
+    s.client.set_trophy_contract(&trophy_id);
+
+    let tournament_id = create(&s, config(&s, 4, Vec::new(&s.env)), 0);
+    register_players(&s, &tournament_id, 4);
+    s.client.start_tournament(&tournament_id, &s.organizer);
+    for round in 0..2 {
+        play_round(&s, &tournament_id, round);
+        s.client.advance_round(&tournament_id);
+    }
+
+    let placements = s.client.get_placements(&tournament_id);
+    assert_eq!(trophies.total_supply(), 3);
+    for placement in 1..=3u32 {
+        let token_id = trophies.get_awarded(&tournament_id, &placement).unwrap();
+        let trophy = trophies.get_trophy(&token_id);
+        assert_eq!(Some(trophy.owner), placements.get(placement - 1));
+        assert!(trophy.soulbound);
+    }
+    assert_eq!(trophies.balance(&placements.get(3).unwrap()), 0);
+}
+
+#[test]
+fn test_byes_for_top_seeds() {
+    let s = setup();
+    let tournament_id = create(&s, config(&s, 4, Vec::new(&s.env)), 0);
+    let players = register_players(&s, &tournament_id, 3);
+    s.client.start_tournament(&tournament_id, &s.admin);
+
+    let round0 = s.client.get_round(&tournament_id, &0);
+    let bye = round0.get(0).unwrap();
+    assert_eq!(bye.winner, players.get(0));
+    assert_eq!(bye.match_id, None);
+
+    play_round(&s, &tournament_id, 0);
+    s.client.advance_round(&tournament_id);
+
+    let final_slot = s.client.get_round(&tournament_id, &1).get(0).unwrap();
+    assert_eq!(final_slot.player_a, players.get(0));
+    assert_eq!(final_slot.player_b, players.get(1));
+
+    play_round(&s, &tournament_id, 1);
+    s.client.advance_round(&tournament_id);
+    assert_eq!(
+        s.client.get_placements(&tournament_id),
+        vec![
+            &s.env,
+            players.get(0).unwrap(),
+            players.get(1).unwrap(),
+            players.get(2).unwrap()
+        ]
+    );
+}
+
+#[test]
+fn test_walkover_on_cancelled_match() {
+    let s = setup();
+    let tournament_id = create(&s, config(&s, 2, Vec::new(&s.env)), 0);
+    let players = register_players(&s, &tournament_id, 2);
+    s.client.start_tournament(&tournament_id, &s.organizer);
+
+    let slot = s.client.get_round(&tournament_id, &0).get(0).unwrap();
+    let match_id = slot.match_id.unwrap();
+    let b = players.get(1).unwrap();
+
+    // Walkovers are only for matches without a result.
+    assert!(s
+        .client
+        .try_award_walkover(&tournament_id, &s.organizer, &0, &b)
+        .is_err());
+
+    s.matches.cancel_match(&match_id, &s.admin);
+    s.client
+        .award_walkover(&tournament_id, &s.organizer, &0, &b);
+    s.client.advance_round(&tournament_id);
+
+    assert_eq!(s.client.get_placements(&tournament_id).get(0), Some(b));
+}
+
+#[test]
+fn test_registration_gates() {
+    let s = setup();
+    let mut cfg = config(&s, 4, Vec::new(&s.env));
+    cfg.stake_required = true;
+    cfg.min_skill = 1_200;
+    let tournament_id = create(&s, cfg, 0);
+
+    let player = Address::generate(&s.env);
+    assert!(s.client.try_register(&tournament_id, &player).is_err());
+
+    s.staking.set_stake(&player, &tournament_id, &500);
+    assert!(s.client.try_register(&tournament_id, &player).is_err());
+
+    s.reputation.set_skill(&player, &1_250);
+    s.client.register(&tournament_id, &player);
+    assert_eq!(s.client.get_players(&tournament_id).len(), 1);
+
+    // Duplicate registration and registration after the deadline are rejected.
+    assert!(s.client.try_register(&tournament_id, &player).is_err());
+    let late = Address::generate(&s.env);
+    s.staking.set_stake(&late, &tournament_id, &500);
+    s.reputation.set_skill(&late, &1_250);
+    s.env.ledger().set_timestamp(2_001);
+    assert!(s.client.try_register(&tournament_id, &late).is_err());
+}
+
+#[test]
+fn test_cancel_refunds_prize() {
+    let s = setup();
+    let tournament_id = create(&s, config(&s, 4, vec![&s.env, 10_000]), 5_000);
+    assert_eq!(s.token.balance(&s.organizer), 95_000);
+
+    let stranger = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_cancel_tournament(&tournament_id, &stranger)
+        .is_err());
+
+    s.client.cancel_tournament(&tournament_id, &s.organizer);
+    assert_eq!(s.token.balance(&s.organizer), 100_000);
+    assert_eq!(
+        s.client.get_tournament(&tournament_id).state,
+        TournamentState::Cancelled as u32
+    );
+    assert!(s.client.try_register(&tournament_id, &stranger).is_err());
+}
+
+#[test]
+#[should_panic(expected = "invalid max players")]
+fn test_max_players_must_be_power_of_two() {
+    let s = setup();
+    create(&s, config(&s, 6, Vec::new(&s.env)), 0);
+}
@@ -0,0 +1,409 @@
+#![no_std]
+
+//! Treasury contract.
+//!
+//! Receives slashed stakes, platform fees and dispute fees from system contracts and
+//! tracks income per asset and source. Funds only leave through spend proposals that
+//! reach the council approval threshold and then wait out a timelock.
+
+use arenax_events::treasury as events;
+use arenax_token_interface as token;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, symbol_short, Address, Env, String, Symbol, Vec,
+};
+
+/// Upper bound on council size.
+pub const MAX_COUNCIL_SIZE: u32 = 20;
+
+/// Source recorded by `sync` for funds sent with a plain token transfer.
+pub const DIRECT_SOURCE: Symbol = symbol_short!("direct");
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Council,
+    Threshold,
+    Timelock,
+    Income(Address, Symbol), // (asset, source) -> lifetime income
+    Sources(Address),        // asset -> Vec<Symbol>
+    Spent(Address),          // asset -> lifetime spending
+    Accounted(Address),      // asset -> balance already attributed to a source
+    NextProposalId,
+    Proposal(u64),
+    Approvals(u64), // proposal_id -> Vec<Address>
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProposalState {
+    Pending = 0,
+    /// Approved; executable once the timelock has passed.
+    Queued = 1,
+    Executed = 2,
+    Cancelled = 3,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SpendProposal {
+    pub proposal_id: u64,
+    pub proposer: Address,
+    pub asset: Address,
+    pub recipient: Address,
+    pub amount: i128,
+    pub memo: String,
+    pub state: u32,
+    pub created_at: u64,
+    /// Set when the proposal is queued.
+    pub executable_at: u64,
+}
+
+#[contract]
+pub struct Treasury;
+
+#[contractimpl]
+impl Treasury {
+    pub fn initialize(
+        env: Env,
+        admin: Address,
+        council: Vec<Address>,
+        threshold: u32,
+        timelock: u64,
+    ) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &1u64);
+        Self::write_council(&env, &council, threshold, timelock);
+    }
+
+    /// Replace the council, approval threshold and timelock (admin only).
+    pub fn set_council(env: Env, council: Vec<Address>, threshold: u32, timelock: u64) {
+        Self::require_admin(&env);
+        Self::write_council(&env, &council, threshold, timelock);
+    }
+
+    /// Pull `amount` of `asset` from `from` and record it as income from `source`
+    /// (e.g. `slash`, `platform`, `dispute`).
+    pub fn deposit(env: Env, from: Address, asset: Address, amount: i128, source: Symbol) {
+        from.require_auth();
+
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        token::Client::new(&env, &asset).transfer(&from, &env.current_contract_address(), &amount);
+        let accounted = Self::accounted(&env, &asset) + amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Accounted(asset.clone()), &accounted);
+        Self::record_income(&env, &asset, &source, Some(from), amount);
+    }
+
+    /// Attribute any balance that arrived by plain token transfer to `DIRECT_SOURCE`.
+    /// Callable by anyone; returns the amount recorded.
+    pub fn sync(env: Env, asset: Address) -> i128 {
+        let balance = token::Client::new(&env, &asset).balance(&env.current_contract_address());
+        let untracked = balance - Self::accounted(&env, &asset);
+        if untracked > 0 {
+            env.storage()
+                .persistent()
+                .set(&DataKey::Accounted(asset.clone()), &balance);
+            Self::record_income(&env, &asset, &DIRECT_SOURCE, None, untracked);
+        }
+        untracked.max(0)
+    }
+
+    /// Propose a payment out of the treasury (admin or council member).
+    pub fn propose_spend(
+        env: Env,
+        proposer: Address,
+        asset: Address,
+        recipient: Address,
+        amount: i128,
+        memo: String,
+    ) -> u64 {
+        proposer.require_auth();
+        Self::require_approver(&env, &proposer);
+
+        if amount <= 0 {
+            panic!("amount must be positive");
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(proposal_id + 1));
+
+        let proposal = SpendProposal {
+            proposal_id,
+            proposer: proposer.clone(),
+            asset: asset.clone(),
+            recipient: recipient.clone(),
+            amount,
+            memo: memo.clone(),
+            state: ProposalState::Pending as u32,
+            created_at: env.ledger().timestamp(),
+            executable_at: 0,
+        };
+        Self::save_proposal(&env, &proposal);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approvals(proposal_id), &Vec::<Address>::new(&env));
+
+        events::emit_spend_proposed(
+            &env,
+            proposal_id,
+            &proposer,
+            &asset,
+            &recipient,
+            amount,
+            &memo,
+        );
+        proposal_id
+    }
+
+    /// Approve a pending proposal (admin or council member). Reaching the threshold
+    /// queues it behind the timelock.
+    pub fn approve_spend(env: Env, proposal_id: u64, approver: Address) {
+        approver.require_auth();
+        Self::require_approver(&env, &approver);
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if proposal.state != ProposalState::Pending as u32 {
+            panic!("proposal is not pending");
+        }
+
+        let mut approvals = Self::get_approvals(env.clone(), proposal_id);
+        if approvals.contains(&approver) {
+            panic!("already approved");
+        }
+        approvals.push_back(approver.clone());
+        env.storage()
+            .persistent()
+            .set(&DataKey::Approvals(proposal_id), &approvals);
+        events::emit_spend_approved(&env, proposal_id, &approver, approvals.len());
+
+        if approvals.len() >= Self::get_threshold(env.clone()) {
+            proposal.state = ProposalState::Queued as u32;
+            proposal.executable_at = env.ledger().timestamp() + Self::get_timelock(env.clone());
+            Self::save_proposal(&env, &proposal);
+            events::emit_spend_queued(&env, proposal_id, proposal.executable_at);
+        }
+    }
+
+    /// Pay out a queued proposal once its timelock has passed. Callable by anyone.
+    pub fn execute_spend(env: Env, proposal_id: u64) {
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if proposal.state != ProposalState::Queued as u32 {
+            panic!("proposal is not queued");
+        }
+        if env.ledger().timestamp() < proposal.executable_at {
+            panic!("timelock has not expired");
+        }
+
+        let accounted = Self::accounted(&env, &proposal.asset);
+        if accounted < proposal.amount {
+            panic!("insufficient treasury balance");
+        }
+
+        proposal.state = ProposalState::Executed as u32;
+        Self::save_proposal(&env, &proposal);
+        env.storage().persistent().set(
+            &DataKey::Accounted(proposal.asset.clone()),
+            &(accounted - proposal.amount),
+        );
+        let spent = Self::get_total_spent(env.clone(), proposal.asset.clone()) + proposal.amount;
+        env.storage()
+            .persistent()
+            .set(&DataKey::Spent(proposal.asset.clone()), &spent);
+
+        token::Client::new(&env, &proposal.asset).transfer(
+            &env.current_contract_address(),
+            &proposal.recipient,
+            &proposal.amount,
+        );
+
+        events::emit_spend_executed(
+            &env,
+            proposal_id,
+            &proposal.asset,
+            &proposal.recipient,
+            proposal.amount,
+        );
+    }
+
+    /// Cancel a proposal that has not been executed (admin or its proposer).
+    pub fn cancel_spend(env: Env, proposal_id: u64, caller: Address) {
+        caller.require_auth();
+
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if caller != proposal.proposer && caller != Self::get_admin(env.clone()) {
+            panic!("unauthorized caller");
+        }
+        if proposal.state != ProposalState::Pending as u32
+            && proposal.state != ProposalState::Queued as u32
+        {
+            panic!("proposal is closed");
+        }
+
+        proposal.state = ProposalState::Cancelled as u32;
+        Self::save_proposal(&env, &proposal);
+        events::emit_spend_cancelled(&env, proposal_id, &caller);
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> SpendProposal {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .expect("proposal not found")
+    }
+
+    pub fn get_approvals(env: Env, proposal_id: u64) -> Vec<Address> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Approvals(proposal_id))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    /// Lifetime income of `asset` from `source`.
+    pub fn get_income(env: Env, asset: Address, source: Symbol) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Income(asset, source))
+            .unwrap_or(0)
+    }
+
+    /// Sources that have paid `asset` into the treasury, in first-seen order.
+    pub fn get_income_sources(env: Env, asset: Address) -> Vec<Symbol> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Sources(asset))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_total_income(env: Env, asset: Address) -> i128 {
+        let mut total = 0;
+        for source in Self::get_income_sources(env.clone(), asset.clone()).iter() {
+            total += Self::get_income(env.clone(), asset.clone(), source);
+        }
+        total
+    }
+
+    pub fn get_total_spent(env: Env, asset: Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Spent(asset))
+            .unwrap_or(0)
+    }
+
+    pub fn get_council(env: Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&DataKey::Council)
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_threshold(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Threshold)
+            .expect("not initialized")
+    }
+
+    pub fn get_timelock(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Timelock)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    /// Approvers are the admin and the council members.
+    fn require_approver(env: &Env, addr: &Address) {
+        if *addr != Self::get_admin(env.clone()) && !Self::get_council(env.clone()).contains(addr) {
+            panic!("not an approver");
+        }
+    }
+
+    fn write_council(env: &Env, council: &Vec<Address>, threshold: u32, timelock: u64) {
+        if council.len() > MAX_COUNCIL_SIZE {
+            panic!("council too large");
+        }
+        for i in 0..council.len() {
+            let member = council.get(i).unwrap();
+            if council.slice(i + 1..).contains(&member) {
+                panic!("duplicate council member");
+            }
+        }
+        // The admin can always approve, so the threshold may exceed the council by one.
+        if threshold == 0 || threshold > council.len() + 1 {
+            panic!("invalid threshold");
+        }
+
+        env.storage().instance().set(&DataKey::Council, council);
+        env.storage()
+            .instance()
+            .set(&DataKey::Threshold, &threshold);
+        env.storage().instance().set(&DataKey::Timelock, &timelock);
+        events::emit_council_updated(env, council, threshold, timelock);
+    }
+
+    fn accounted(env: &Env, asset: &Address) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Accounted(asset.clone()))
+            .unwrap_or(0)
+    }
+
+    fn record_income(
+        env: &Env,
+        asset: &Address,
+        source: &Symbol,
+        from: Option<Address>,
+        amount: i128,
+    ) {
+        let key = DataKey::Income(asset.clone(), source.clone());
+        let income: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        if income == 0 {
+            let mut sources = Self::get_income_sources(env.clone(), asset.clone());
+            if !sources.contains(source) {
+                sources.push_back(source.clone());
+                env.storage()
+                    .persistent()
+                    .set(&DataKey::Sources(asset.clone()), &sources);
+            }
+        }
+        env.storage().persistent().set(&key, &(income + amount));
+        events::emit_income_recorded(env, asset, source, from, amount);
+    }
+
+    fn save_proposal(env: &Env, proposal: &SpendProposal) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal.proposal_id), proposal);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,183 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    symbol_short,
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    vec, Address, Env, String,
+};
+
+const TIMELOCK: u64 = 86_400;
+
+struct Setup<'a> {
+    env: Env,
+    admin: Address,
+    council: Vec<Address>,
+    client: TreasuryClient<'a>,
+    token: TokenClient<'a>,
+    minter: StellarAssetClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let council = vec![
+        &env,
+        Address::generate(&env),
+        Address::generate(&env),
+        Address::generate(&env),
+    ];
+
+    let contract_id = env.register(Treasury, ());
+    let client = TreasuryClient::new(&env, &contract_id);
+    client.initialize(&admin, &council, &2, &TIMELOCK);
+
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    Setup {
+        token: TokenClient::new(&env, &sac.address()),
+        minter: StellarAssetClient::new(&env, &sac.address()),
+        env,
+        admin,
+        council,
+        client,
+    }
+}
+
+fn fund(s: &Setup, source: Symbol, amount: i128) {
+    let from = Address::generate(&s.env);
+    s.minter.mint(&from, &amount);
+    s.client.deposit(&from, &s.token.address, &amount, &source);
+}
+
+#[test]
+fn test_income_tracked_by_source() {
+    let s = setup();
+    fund(&s, symbol_short!("slash"), 500);
+    fund(&s, symbol_short!("platform"), 300);
+    fund(&s, symbol_short!("slash"), 200);
+
+    assert_eq!(
+        s.client
+            .get_income(&s.token.address, &symbol_short!("slash")),
+        700
+    );
+    assert_eq!(
+        s.client.get_income_sources(&s.token.address),
+        vec![&s.env, symbol_short!("slash"), symbol_short!("platform")]
+    );
+
+    // Plain transfers are attributed on sync.
+    s.minter.mint(&s.client.address, &150);
+    assert_eq!(s.client.sync(&s.token.address), 150);
+    assert_eq!(s.client.sync(&s.token.address), 0);
+    assert_eq!(s.client.get_income(&s.token.address, &DIRECT_SOURCE), 150);
+    assert_eq!(s.client.get_total_income(&s.token.address), 1_150);
+}
+
+#[test]
+fn test_spend_requires_threshold_and_timelock() {
+    let s = setup();
+    fund(&s, symbol_short!("dispute"), 1_000);
+    let recipient = Address::generate(&s.env);
+    let member_a = s.council.get(0).unwrap();
+    let member_b = s.council.get(1).unwrap();
+
+    let id = s.client.propose_spend(
+        &member_a,
+        &s.token.address,
+        &recipient,
+        &400,
+        &String::from_str(&s.env, "referee payroll"),
+    );
+
+    s.client.approve_spend(&id, &member_a);
+    assert!(s.client.try_execute_spend(&id).is_err());
+    assert!(s.client.try_approve_spend(&id, &member_a).is_err());
+
+    s.client.approve_spend(&id, &member_b);
+    let proposal = s.client.get_proposal(&id);
+    assert_eq!(proposal.state, ProposalState::Queued as u32);
+    assert_eq!(proposal.executable_at, 1_000 + TIMELOCK);
+
+    // Timelock still running.
+    assert!(s.client.try_execute_spend(&id).is_err());
+
+    s.env.ledger().set_timestamp(1_000 + TIMELOCK);
+    s.client.execute_spend(&id);
+    assert_eq!(s.token.balance(&recipient), 400);
+    assert_eq!(s.client.get_total_spent(&s.token.address), 400);
+    assert_eq!(
+        s.client.get_proposal(&id).state,
+        ProposalState::Executed as u32
+    );
+    assert!(s.client.try_execute_spend(&id).is_err());
+}
+
+#[test]
+fn test_cancel_and_authorization() {
+    let s = setup();
+    fund(&s, symbol_short!("platform"), 1_000);
+    let outsider = Address::generate(&s.env);
+    let member = s.council.get(0).unwrap();
+
+    assert!(s
+        .client
+        .try_propose_spend(
+            &outsider,
+            &s.token.address,
+            &outsider,
+            &100,
+            &String::from_str(&s.env, "")
+        )
+        .is_err());
+
+    let id = s.client.propose_spend(
+        &member,
+        &s.token.address,
+        &outsider,
+        &100,
+        &String::from_str(&s.env, "tooling"),
+    );
+    assert!(s.client.try_approve_spend(&id, &outsider).is_err());
+    assert!(s.client.try_cancel_spend(&id, &outsider).is_err());
+
+    s.client.approve_spend(&id, &s.admin);
+    s.client.approve_spend(&id, &member);
+    s.client.cancel_spend(&id, &s.admin);
+
+    s.env.ledger().set_timestamp(1_000 + TIMELOCK);
+    assert!(s.client.try_execute_spend(&id).is_err());
+    assert_eq!(s.token.balance(&s.client.address), 1_000);
+}
+
+#[test]
+#[should_panic(expected = "insufficient treasury balance")]
+fn test_spend_limited_to_treasury_balance() {
+    let s = setup();
+    fund(&s, symbol_short!("platform"), 100);
+    let member_a = s.council.get(0).unwrap();
+    let member_b = s.council.get(1).unwrap();
+
+    let id = s.client.propose_spend(
+        &member_a,
+        &s.token.address,
+        &member_a,
+        &500,
+        &String::from_str(&s.env, "too much"),
+    );
+    s.client.approve_spend(&id, &member_a);
+    s.client.approve_spend(&id, &member_b);
+    s.env.ledger().set_timestamp(1_000 + TIMELOCK);
+    s.client.execute_spend(&id);
+}
+
+#[test]
+#[should_panic(expected = "invalid threshold")]
+fn test_council_threshold_validated() {
+    let s = setup();
+    s.client.set_council(&s.council, &5, &TIMELOCK);
+}
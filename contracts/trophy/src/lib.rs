@@ -0,0 +1,306 @@
+#![no_std]
+
+//! Tournament trophies.
+//!
+//! Non-fungible trophies and badges minted when a tournament is finalized. Only
+//! registered minters (the Tournament and Prize Distribution contracts) can mint.
+//! Trophies minted while soulbound mode is on can never be transferred. Minters can
+//! also award badges (e.g. season pass tiers), recorded with placement 0.
+
+use arenax_events::trophy as events;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, BytesN, Env, Vec};
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Minter(Address),
+    Soulbound,
+    Season,
+    NextTokenId,
+    Trophy(u64),
+    Owned(Address),             // owner -> Vec<u64> of token ids
+    Awarded(BytesN<32>, u32),   // (tournament_id, placement) -> token id
+    Badge(BytesN<32>, Address), // (badge_id, owner) -> token id
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TrophyMetadata {
+    /// Tournament id, or the badge id for badges.
+    pub tournament_id: BytesN<32>,
+    /// 1 for the champion; 0 for badges.
+    pub placement: u32,
+    pub season: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Trophy {
+    pub token_id: u64,
+    pub owner: Address,
+    pub metadata: TrophyMetadata,
+    pub soulbound: bool,
+    pub minted_at: u64,
+}
+
+#[contract]
+pub struct TrophyContract;
+
+#[contractimpl]
+impl TrophyContract {
+    pub fn initialize(env: Env, admin: Address, soulbound: bool) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage()
+            .instance()
+            .set(&DataKey::Soulbound, &soulbound);
+        env.storage().instance().set(&DataKey::Season, &1u32);
+        env.storage().instance().set(&DataKey::NextTokenId, &1u64);
+    }
+
+    /// Allow or revoke a minting contract (admin only).
+    pub fn set_minter(env: Env, minter: Address, enabled: bool) {
+        Self::require_admin(&env);
+        if enabled {
+            env.storage()
+                .instance()
+                .set(&DataKey::Minter(minter.clone()), &true);
+        } else {
+            env.storage()
+                .instance()
+                .remove(&DataKey::Minter(minter.clone()));
+        }
+        events::emit_minter_updated(&env, &minter, enabled);
+    }
+
+    /// Toggle soulbound mode for trophies minted from now on (admin only).
+    pub fn set_soulbound(env: Env, enabled: bool) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Soulbound, &enabled);
+        events::emit_soulbound_mode_updated(&env, enabled);
+    }
+
+    /// Season stamped into newly minted trophies (admin only).
+    pub fn set_season(env: Env, season: u32) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Season, &season);
+        events::emit_season_updated(&env, season);
+    }
+
+    /// Mint the trophy for `placement` in `tournament_id` to `to`. Each placement of a
+    /// tournament can be awarded once.
+    pub fn mint(
+        env: Env,
+        minter: Address,
+        to: Address,
+        tournament_id: BytesN<32>,
+        placement: u32,
+    ) -> u64 {
+        Self::require_minter(&env, &minter);
+        if placement == 0 {
+            panic!("invalid placement");
+        }
+
+        let awarded_key = DataKey::Awarded(tournament_id.clone(), placement);
+        if env.storage().persistent().has(&awarded_key) {
+            panic!("trophy already minted");
+        }
+
+        let token_id = Self::mint_token(&env, &minter, &to, &tournament_id, placement);
+        env.storage().persistent().set(&awarded_key, &token_id);
+        token_id
+    }
+
+    /// Mint badge `badge_id` to `to`. Each holder can receive a given badge once.
+    pub fn mint_badge(env: Env, minter: Address, to: Address, badge_id: BytesN<32>) -> u64 {
+        Self::require_minter(&env, &minter);
+
+        let badge_key = DataKey::Badge(badge_id.clone(), to.clone());
+        if env.storage().persistent().has(&badge_key) {
+            panic!("badge already minted");
+        }
+
+        let token_id = Self::mint_token(&env, &minter, &to, &badge_id, 0);
+        env.storage().persistent().set(&badge_key, &token_id);
+        token_id
+    }
+
+    pub fn transfer(env: Env, from: Address, to: Address, token_id: u64) {
+        from.require_auth();
+
+        let mut trophy = Self::get_trophy(env.clone(), token_id);
+        if trophy.owner != from {
+            panic!("not the owner");
+        }
+        if trophy.soulbound {
+            panic!("trophy is soulbound");
+        }
+
+        Self::remove_owned(&env, &from, token_id);
+        Self::add_owned(&env, &to, token_id);
+        trophy.owner = to.clone();
+        env.storage()
+            .persistent()
+            .set(&DataKey::Trophy(token_id), &trophy);
+
+        events::emit_trophy_transferred(&env, token_id, &from, &to);
+    }
+
+    pub fn get_trophy(env: Env, token_id: u64) -> Trophy {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Trophy(token_id))
+            .expect("trophy not found")
+    }
+
+    pub fn owner_of(env: Env, token_id: u64) -> Address {
+        Self::get_trophy(env, token_id).owner
+    }
+
+    pub fn balance(env: Env, owner: Address) -> u32 {
+        Self::tokens_of(env, owner).len()
+    }
+
+    /// Token ids held by `owner`.
+    pub fn tokens_of(env: Env, owner: Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Owned(owner))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn token_of_owner_by_index(env: Env, owner: Address, index: u32) -> u64 {
+        Self::tokens_of(env, owner)
+            .get(index)
+            .expect("index out of bounds")
+    }
+
+    /// Token id of badge `badge_id` held by `owner`, if minted.
+    pub fn get_badge(env: Env, badge_id: BytesN<32>, owner: Address) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Badge(badge_id, owner))
+    }
+
+    /// Token id awarded for `placement` in `tournament_id`, if minted.
+    pub fn get_awarded(env: Env, tournament_id: BytesN<32>, placement: u32) -> Option<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Awarded(tournament_id, placement))
+    }
+
+    pub fn total_supply(env: Env) -> u64 {
+        let next: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTokenId)
+            .unwrap_or(1);
+        next - 1
+    }
+
+    pub fn is_minter(env: Env, minter: Address) -> bool {
+        env.storage().instance().has(&DataKey::Minter(minter))
+    }
+
+    pub fn is_soulbound_mode(env: Env) -> bool {
+        env.storage()
+            .instance()
+            .get(&DataKey::Soulbound)
+            .unwrap_or(false)
+    }
+
+    pub fn get_season(env: Env) -> u32 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Season)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn require_minter(env: &Env, minter: &Address) {
+        minter.require_auth();
+        if !Self::is_minter(env.clone(), minter.clone()) {
+            panic!("not a minter");
+        }
+    }
+
+    fn mint_token(
+        env: &Env,
+        minter: &Address,
+        to: &Address,
+        tournament_id: &BytesN<32>,
+        placement: u32,
+    ) -> u64 {
+        let token_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextTokenId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextTokenId, &(token_id + 1));
+
+        let trophy = Trophy {
+            token_id,
+            owner: to.clone(),
+            metadata: TrophyMetadata {
+                tournament_id: tournament_id.clone(),
+                placement,
+                season: Self::get_season(env.clone()),
+            },
+            soulbound: Self::is_soulbound_mode(env.clone()),
+            minted_at: env.ledger().timestamp(),
+        };
+        env.storage()
+            .persistent()
+            .set(&DataKey::Trophy(token_id), &trophy);
+        Self::add_owned(env, to, token_id);
+
+        events::emit_trophy_minted(
+            env,
+            token_id,
+            to,
+            minter,
+            tournament_id,
+            placement,
+            trophy.metadata.season,
+            trophy.soulbound,
+        );
+        token_id
+    }
+
+    fn add_owned(env: &Env, owner: &Address, token_id: u64) {
+        let mut owned = Self::tokens_of(env.clone(), owner.clone());
+        owned.push_back(token_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owned(owner.clone()), &owned);
+    }
+
+    fn remove_owned(env: &Env, owner: &Address, token_id: u64) {
+        let mut owned = Self::tokens_of(env.clone(), owner.clone());
+        if let Some(index) = owned.first_index_of(token_id) {
+            owned.remove(index);
+        }
+        env.storage()
+            .persistent()
+            .set(&DataKey::Owned(owner.clone()), &owned);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,132 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{testutils::Address as _, vec, Address, BytesN, Env};
+
+struct Setup<'a> {
+    env: Env,
+    minter: Address,
+    client: TrophyContractClient<'a>,
+}
+
+fn setup(soulbound: bool) -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let minter = Address::generate(&env);
+    let contract_id = env.register(TrophyContract, ());
+    let client = TrophyContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &soulbound);
+    client.set_minter(&minter, &true);
+
+    Setup {
+        env,
+        minter,
+        client,
+    }
+}
+
+fn tournament(env: &Env, seed: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[seed; 32])
+}
+
+#[test]
+fn test_mint_and_enumerate() {
+    let s = setup(false);
+    let player = Address::generate(&s.env);
+    s.client.set_season(&3);
+
+    let first = s
+        .client
+        .mint(&s.minter, &player, &tournament(&s.env, 1), &1);
+    let second = s
+        .client
+        .mint(&s.minter, &player, &tournament(&s.env, 2), &2);
+
+    let trophy = s.client.get_trophy(&first);
+    assert_eq!(trophy.owner, player);
+    assert_eq!(
+        trophy.metadata,
+        TrophyMetadata {
+            tournament_id: tournament(&s.env, 1),
+            placement: 1,
+            season: 3,
+        }
+    );
+    assert_eq!(s.client.balance(&player), 2);
+    assert_eq!(s.client.tokens_of(&player), vec![&s.env, first, second]);
+    assert_eq!(s.client.token_of_owner_by_index(&player, &1), second);
+    assert_eq!(
+        s.client.get_awarded(&tournament(&s.env, 2), &2),
+        Some(second)
+    );
+    assert_eq!(s.client.total_supply(), 2);
+
+    // One trophy per placement.
+    assert!(s
+        .client
+        .try_mint(&s.minter, &player, &tournament(&s.env, 1), &1)
+        .is_err());
+}
+
+#[test]
+fn test_only_minters_can_mint() {
+    let s = setup(false);
+    let outsider = Address::generate(&s.env);
+    assert!(s
+        .client
+        .try_mint(&outsider, &outsider, &tournament(&s.env, 1), &1)
+        .is_err());
+
+    s.client.set_minter(&s.minter, &false);
+    assert!(s
+        .client
+        .try_mint(&s.minter, &outsider, &tournament(&s.env, 1), &1)
+        .is_err());
+}
+
+#[test]
+fn test_transfer_updates_enumeration() {
+    let s = setup(false);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let id = s.client.mint(&s.minter, &alice, &tournament(&s.env, 1), &1);
+
+    assert!(s.client.try_transfer(&bob, &alice, &id).is_err());
+
+    s.client.transfer(&alice, &bob, &id);
+    assert_eq!(s.client.owner_of(&id), bob);
+    assert_eq!(s.client.balance(&alice), 0);
+    assert_eq!(s.client.tokens_of(&bob), vec![&s.env, id]);
+}
+
+#[test]
+fn test_soulbound_trophies_stay_put() {
+    let s = setup(true);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let bound = s.client.mint(&s.minter, &alice, &tournament(&s.env, 1), &1);
+    assert!(s.client.try_transfer(&alice, &bob, &bound).is_err());
+
+    // The mode applies at mint time.
+    s.client.set_soulbound(&false);
+    let free = s.client.mint(&s.minter, &alice, &tournament(&s.env, 1), &2);
+    s.client.transfer(&alice, &bob, &free);
+    assert!(s.client.get_trophy(&bound).soulbound);
+    assert_eq!(s.client.owner_of(&bound), alice);
+}
+
+#[test]
+fn test_badges_once_per_holder() {
+    let s = setup(false);
+    let alice = Address::generate(&s.env);
+    let bob = Address::generate(&s.env);
+    let badge = tournament(&s.env, 9);
+
+    let id = s.client.mint_badge(&s.minter, &alice, &badge);
+    s.client.mint_badge(&s.minter, &bob, &badge);
+    assert_eq!(s.client.get_trophy(&id).metadata.placement, 0);
+    assert_eq!(s.client.get_badge(&badge, &alice), Some(id));
+    assert!(s.client.try_mint_badge(&s.minter, &alice, &badge).is_err());
+}
@@ -0,0 +1,347 @@
+#![no_std]
+
+//! Upgrade manager for ArenaX contracts.
+//!
+//! A participating contract exposes `upgrade(new_wasm_hash)` gated on its admin and
+//! calling `env.deployer().update_current_contract_wasm`, as the protocol params
+//! contract does, and makes this contract its admin. Upgrading in place keeps the
+//! contract's address and storage, so the registry never needs repointing.
+//!
+//! The admin registers participants with their deployed wasm hash and proposes
+//! upgrades; a proposal becomes executable after the timelock and can then be
+//! executed by anyone. Either the admin or the guardian can cancel a proposal, or
+//! roll a participant back to its previous wasm at once in an emergency. Every hash
+//! a participant has run is kept in its history.
+
+use arenax_events::upgrade_manager as events;
+use soroban_sdk::{
+    contract, contractimpl, contracttype, Address, BytesN, Env, IntoVal, Symbol, Vec,
+};
+
+/// Upper bound on history records kept per participant; the oldest are dropped.
+pub const MAX_HISTORY: u32 = 50;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Guardian,
+    Timelock,
+    NextProposalId,
+    Participant(Address),
+    Proposal(u64),
+    History(Address), // contract -> Vec<WasmRecord>
+}
+
+#[contracttype]
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProposalState {
+    Pending = 0,
+    Executed = 1,
+    Cancelled = 2,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Participant {
+    pub current_hash: BytesN<32>,
+    /// Target of an emergency rollback; cleared once used.
+    pub previous_hash: Option<BytesN<32>>,
+    /// Proposal waiting for execution, if any.
+    pub pending_proposal: Option<u64>,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct UpgradeProposal {
+    pub proposal_id: u64,
+    pub contract: Address,
+    pub wasm_hash: BytesN<32>,
+    pub proposed_at: u64,
+    pub executable_at: u64,
+    pub state: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WasmRecord {
+    pub wasm_hash: BytesN<32>,
+    pub applied_at: u64,
+    /// The proposal that applied the hash; `None` for registration and rollbacks.
+    pub proposal_id: Option<u64>,
+    pub rollback: bool,
+}
+
+#[contract]
+pub struct UpgradeManager;
+
+#[contractimpl]
+impl UpgradeManager {
+    /// `timelock` is the delay in seconds between proposing and executing an upgrade.
+    pub fn initialize(env: Env, admin: Address, guardian: Address, timelock: u64) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+        env.storage().instance().set(&DataKey::Timelock, &timelock);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &1u64);
+    }
+
+    /// Replace the guardian (admin only).
+    pub fn set_guardian(env: Env, guardian: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Guardian, &guardian);
+        events::emit_guardian_updated(&env, &guardian);
+    }
+
+    /// Put `contract`, currently running `wasm_hash`, under management (admin only).
+    /// The contract's admin must be this contract for upgrades to succeed.
+    pub fn register(env: Env, contract: Address, wasm_hash: BytesN<32>) {
+        Self::require_admin(&env);
+        let key = DataKey::Participant(contract.clone());
+        if env.storage().persistent().has(&key) {
+            panic!("already registered");
+        }
+        env.storage().persistent().set(
+            &key,
+            &Participant {
+                current_hash: wasm_hash.clone(),
+                previous_hash: None,
+                pending_proposal: None,
+            },
+        );
+        Self::record(&env, &contract, &wasm_hash, None, false);
+        events::emit_contract_registered(&env, &contract, &wasm_hash);
+    }
+
+    /// Propose upgrading `contract` to `wasm_hash` (admin only). A participant has at
+    /// most one pending proposal. Returns the proposal id.
+    pub fn propose(env: Env, contract: Address, wasm_hash: BytesN<32>) -> u64 {
+        Self::require_admin(&env);
+        let mut participant = Self::get_participant(env.clone(), contract.clone());
+        if participant.pending_proposal.is_some() {
+            panic!("upgrade already pending");
+        }
+        if participant.current_hash == wasm_hash {
+            panic!("wasm already current");
+        }
+
+        let proposal_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextProposalId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextProposalId, &(proposal_id + 1));
+
+        let now = env.ledger().timestamp();
+        let proposal = UpgradeProposal {
+            proposal_id,
+            contract: contract.clone(),
+            wasm_hash: wasm_hash.clone(),
+            proposed_at: now,
+            executable_at: now + Self::get_timelock(env.clone()),
+            state: ProposalState::Pending as u32,
+        };
+        Self::save_proposal(&env, &proposal);
+        participant.pending_proposal = Some(proposal_id);
+        Self::save_participant(&env, &contract, &participant);
+
+        events::emit_upgrade_proposed(
+            &env,
+            proposal_id,
+            &contract,
+            &wasm_hash,
+            proposal.executable_at,
+        );
+        proposal_id
+    }
+
+    /// Cancel a pending proposal (admin or guardian).
+    pub fn cancel(env: Env, caller: Address, proposal_id: u64) {
+        Self::require_admin_or_guardian(&env, &caller);
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if proposal.state != ProposalState::Pending as u32 {
+            panic!("proposal not pending");
+        }
+        proposal.state = ProposalState::Cancelled as u32;
+        Self::save_proposal(&env, &proposal);
+
+        let mut participant = Self::get_participant(env.clone(), proposal.contract.clone());
+        participant.pending_proposal = None;
+        Self::save_participant(&env, &proposal.contract, &participant);
+
+        events::emit_upgrade_cancelled(&env, proposal_id, &proposal.contract, &caller);
+    }
+
+    /// Apply a pending proposal whose timelock has passed. Callable by anyone.
+    pub fn execute(env: Env, proposal_id: u64) {
+        let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+        if proposal.state != ProposalState::Pending as u32 {
+            panic!("proposal not pending");
+        }
+        if env.ledger().timestamp() < proposal.executable_at {
+            panic!("timelock not elapsed");
+        }
+
+        let contract = proposal.contract.clone();
+        let mut participant = Self::get_participant(env.clone(), contract.clone());
+        let old_hash = participant.current_hash.clone();
+
+        proposal.state = ProposalState::Executed as u32;
+        Self::save_proposal(&env, &proposal);
+        participant.previous_hash = Some(old_hash.clone());
+        participant.current_hash = proposal.wasm_hash.clone();
+        participant.pending_proposal = None;
+        Self::save_participant(&env, &contract, &participant);
+        Self::record(
+            &env,
+            &contract,
+            &proposal.wasm_hash,
+            Some(proposal_id),
+            false,
+        );
+
+        Self::upgrade(&env, &contract, &proposal.wasm_hash);
+        events::emit_contract_upgraded(
+            &env,
+            proposal_id,
+            &contract,
+            &old_hash,
+            &proposal.wasm_hash,
+        );
+    }
+
+    /// Revert `contract` to the wasm it ran before its last upgrade, skipping the
+    /// timelock (admin or guardian). Any pending proposal for it is cancelled.
+    pub fn rollback(env: Env, caller: Address, contract: Address) {
+        Self::require_admin_or_guardian(&env, &caller);
+        let mut participant = Self::get_participant(env.clone(), contract.clone());
+        let previous = participant
+            .previous_hash
+            .clone()
+            .expect("no rollback available");
+
+        if let Some(proposal_id) = participant.pending_proposal {
+            let mut proposal = Self::get_proposal(env.clone(), proposal_id);
+            proposal.state = ProposalState::Cancelled as u32;
+            Self::save_proposal(&env, &proposal);
+            events::emit_upgrade_cancelled(&env, proposal_id, &contract, &caller);
+        }
+
+        let from = participant.current_hash.clone();
+        participant.current_hash = previous.clone();
+        participant.previous_hash = None;
+        participant.pending_proposal = None;
+        Self::save_participant(&env, &contract, &participant);
+        Self::record(&env, &contract, &previous, None, true);
+
+        Self::upgrade(&env, &contract, &previous);
+        events::emit_contract_rolled_back(&env, &contract, &from, &previous, &caller);
+    }
+
+    pub fn get_participant(env: Env, contract: Address) -> Participant {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Participant(contract))
+            .expect("contract not registered")
+    }
+
+    pub fn get_proposal(env: Env, proposal_id: u64) -> UpgradeProposal {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Proposal(proposal_id))
+            .expect("proposal not found")
+    }
+
+    /// Hashes `contract` has run under management, oldest first.
+    pub fn get_history(env: Env, contract: Address) -> Vec<WasmRecord> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::History(contract))
+            .unwrap_or(Vec::new(&env))
+    }
+
+    pub fn get_timelock(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&DataKey::Timelock)
+            .expect("not initialized")
+    }
+
+    pub fn get_guardian(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Guardian)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn require_admin_or_guardian(env: &Env, caller: &Address) {
+        caller.require_auth();
+        if caller != &Self::get_admin(env.clone()) && caller != &Self::get_guardian(env.clone()) {
+            panic!("not authorized");
+        }
+    }
+
+    fn upgrade(env: &Env, contract: &Address, wasm_hash: &BytesN<32>) {
+        env.invoke_contract::<()>(
+            contract,
+            &Symbol::new(env, "upgrade"),
+            (wasm_hash.clone(),).into_val(env),
+        );
+    }
+
+    fn record(
+        env: &Env,
+        contract: &Address,
+        wasm_hash: &BytesN<32>,
+        proposal_id: Option<u64>,
+        rollback: bool,
+    ) {
+        let mut history = Self::get_history(env.clone(), contract.clone());
+        if history.len() >= MAX_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(WasmRecord {
+            wasm_hash: wasm_hash.clone(),
+            applied_at: env.ledger().timestamp(),
+            proposal_id,
+            rollback,
+        });
+        env.storage()
+            .persistent()
+            .set(&DataKey::History(contract.clone()), &history);
+    }
+
+    fn save_participant(env: &Env, contract: &Address, participant: &Participant) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Participant(contract.clone()), participant);
+    }
+
+    fn save_proposal(env: &Env, proposal: &UpgradeProposal) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Proposal(proposal.proposal_id), proposal);
+    }
+}
+
+#[cfg(test)]
+mod test;
@@ -0,0 +1,159 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    contract, contractimpl, symbol_short,
+    testutils::{Address as _, Ledger as _},
+    Address, BytesN, Env,
+};
+
+const TIMELOCK: u64 = 86_400;
+
+/// A participating contract. Real participants call
+/// `update_current_contract_wasm` here; the mock records the hash instead.
+#[contract]
+pub struct MockParticipant;
+
+#[contractimpl]
+impl MockParticipant {
+    pub fn init(env: Env, admin: Address) {
+        env.storage()
+            .instance()
+            .set(&symbol_short!("admin"), &admin);
+    }
+
+    pub fn upgrade(env: Env, new_wasm_hash: BytesN<32>) {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("admin"))
+            .unwrap();
+        admin.require_auth();
+        env.storage()
+            .instance()
+            .set(&symbol_short!("wasm"), &new_wasm_hash);
+    }
+
+    pub fn wasm(env: Env) -> Option<BytesN<32>> {
+        env.storage().instance().get(&symbol_short!("wasm"))
+    }
+}
+
+struct Setup<'a> {
+    env: Env,
+    guardian: Address,
+    client: UpgradeManagerClient<'a>,
+    target: MockParticipantClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(1_000);
+
+    let admin = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let contract_id = env.register(UpgradeManager, ());
+    let client = UpgradeManagerClient::new(&env, &contract_id);
+    client.initialize(&admin, &guardian, &TIMELOCK);
+
+    let target = MockParticipantClient::new(&env, &env.register(MockParticipant, ()));
+    target.init(&contract_id);
+    client.register(&target.address, &hash(&env, 1));
+
+    Setup {
+        env,
+        guardian,
+        client,
+        target,
+    }
+}
+
+fn hash(env: &Env, byte: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[byte; 32])
+}
+
+fn pass_timelock(s: &Setup) {
+    let now = s.env.ledger().timestamp();
+    s.env.ledger().set_timestamp(now + TIMELOCK);
+}
+
+#[test]
+fn test_timelocked_upgrade() {
+    let s = setup();
+    let id = s.client.propose(&s.target.address, &hash(&s.env, 2));
+    assert!(s.client.try_execute(&id).is_err());
+    assert!(s
+        .client
+        .try_propose(&s.target.address, &hash(&s.env, 3))
+        .is_err());
+
+    pass_timelock(&s);
+    s.client.execute(&id);
+    assert_eq!(s.target.wasm(), Some(hash(&s.env, 2)));
+
+    let participant = s.client.get_participant(&s.target.address);
+    assert_eq!(participant.current_hash, hash(&s.env, 2));
+    assert_eq!(participant.previous_hash, Some(hash(&s.env, 1)));
+    assert_eq!(
+        s.client.get_proposal(&id).state,
+        ProposalState::Executed as u32
+    );
+    assert!(s.client.try_execute(&id).is_err());
+
+    let history = s.client.get_history(&s.target.address);
+    assert_eq!(history.len(), 2);
+    assert_eq!(history.get(1).unwrap().proposal_id, Some(id));
+}
+
+#[test]
+fn test_emergency_rollback() {
+    let s = setup();
+    let id = s.client.propose(&s.target.address, &hash(&s.env, 2));
+    pass_timelock(&s);
+    s.client.execute(&id);
+    let pending = s.client.propose(&s.target.address, &hash(&s.env, 3));
+
+    s.client.rollback(&s.guardian, &s.target.address);
+    assert_eq!(s.target.wasm(), Some(hash(&s.env, 1)));
+    assert_eq!(
+        s.client.get_proposal(&pending).state,
+        ProposalState::Cancelled as u32
+    );
+    let history = s.client.get_history(&s.target.address);
+    assert_eq!(history.len(), 3);
+    assert!(history.get(2).unwrap().rollback);
+
+    // Only one step back is kept.
+    assert!(s
+        .client
+        .try_rollback(&s.guardian, &s.target.address)
+        .is_err());
+}
+
+#[test]
+fn test_guardian_cancels_proposal() {
+    let s = setup();
+    let id = s.client.propose(&s.target.address, &hash(&s.env, 2));
+    let outsider = Address::generate(&s.env);
+    assert!(s.client.try_cancel(&outsider, &id).is_err());
+
+    s.client.cancel(&s.guardian, &id);
+    pass_timelock(&s);
+    assert!(s.client.try_execute(&id).is_err());
+    assert_eq!(s.target.wasm(), None);
+
+    // The participant can take a new proposal.
+    s.client.propose(&s.target.address, &hash(&s.env, 2));
+}
+
+#[test]
+fn test_unregistered_contract_rejected() {
+    let s = setup();
+    let other = Address::generate(&s.env);
+    assert!(s.client.try_propose(&other, &hash(&s.env, 2)).is_err());
+    assert!(s
+        .client
+        .try_register(&s.target.address, &hash(&s.env, 1))
+        .is_err());
+}
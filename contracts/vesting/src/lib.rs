@@ -0,0 +1,283 @@
+#![no_std]
+
+//! Vesting contract for team and investor allocations.
+//!
+//! The admin creates a schedule per grant, moving its tokens into this contract.
+//! Nothing vests before `start_time + cliff`; after that the grant vests linearly and
+//! is fully vested at `start_time + duration`. Beneficiaries claim vested tokens at
+//! any time. Revoking a revocable schedule freezes it at the amount vested so far,
+//! which stays claimable, and returns the unvested remainder to the treasury.
+
+use arenax_events::vesting as events;
+use arenax_token_interface as token;
+use soroban_sdk::{contract, contractimpl, contracttype, Address, Env, Vec};
+
+/// Upper bound on schedules per beneficiary.
+pub const MAX_SCHEDULES_PER_BENEFICIARY: u32 = 20;
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum DataKey {
+    Admin,
+    Token,
+    Treasury,
+    NextScheduleId,
+    Schedule(u64),
+    Beneficiary(Address), // beneficiary -> Vec<schedule_id>
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct VestingSchedule {
+    pub schedule_id: u64,
+    pub beneficiary: Address,
+    /// Tokens granted; reduced to the vested amount on revocation.
+    pub total: i128,
+    pub released: i128,
+    pub start_time: u64,
+    pub cliff: u64,
+    pub duration: u64,
+    pub revocable: bool,
+    pub revoked_at: Option<u64>,
+}
+
+#[contract]
+pub struct VestingContract;
+
+#[contractimpl]
+impl VestingContract {
+    pub fn initialize(env: Env, admin: Address, token: Address, treasury: Address) {
+        if env.storage().instance().has(&DataKey::Admin) {
+            panic!("already initialized");
+        }
+        admin.require_auth();
+        env.storage().instance().set(&DataKey::Admin, &admin);
+        env.storage().instance().set(&DataKey::Token, &token);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        env.storage()
+            .instance()
+            .set(&DataKey::NextScheduleId, &1u64);
+    }
+
+    /// Destination of unvested tokens from revoked schedules (admin only).
+    pub fn set_treasury(env: Env, treasury: Address) {
+        Self::require_admin(&env);
+        env.storage().instance().set(&DataKey::Treasury, &treasury);
+        events::emit_treasury_updated(&env, &treasury);
+    }
+
+    /// Grant `total` tokens to `beneficiary`, transferred from the admin (admin only).
+    /// Returns the schedule id.
+    pub fn create_schedule(
+        env: Env,
+        beneficiary: Address,
+        total: i128,
+        start_time: u64,
+        cliff: u64,
+        duration: u64,
+        revocable: bool,
+    ) -> u64 {
+        let admin = Self::get_admin(env.clone());
+        admin.require_auth();
+        if total <= 0 {
+            panic!("amount must be positive");
+        }
+        if duration == 0 || cliff > duration {
+            panic!("invalid schedule");
+        }
+
+        let mut schedule_ids = Self::schedule_ids(&env, &beneficiary);
+        if schedule_ids.len() >= MAX_SCHEDULES_PER_BENEFICIARY {
+            panic!("too many schedules");
+        }
+
+        Self::token(&env).transfer(&admin, &env.current_contract_address(), &total);
+
+        let schedule_id: u64 = env
+            .storage()
+            .instance()
+            .get(&DataKey::NextScheduleId)
+            .expect("not initialized");
+        env.storage()
+            .instance()
+            .set(&DataKey::NextScheduleId, &(schedule_id + 1));
+
+        let schedule = VestingSchedule {
+            schedule_id,
+            beneficiary: beneficiary.clone(),
+            total,
+            released: 0,
+            start_time,
+            cliff,
+            duration,
+            revocable,
+            revoked_at: None,
+        };
+        Self::save_schedule(&env, &schedule);
+        schedule_ids.push_back(schedule_id);
+        env.storage()
+            .persistent()
+            .set(&DataKey::Beneficiary(beneficiary.clone()), &schedule_ids);
+
+        events::emit_schedule_created(
+            &env,
+            schedule_id,
+            &beneficiary,
+            total,
+            start_time,
+            cliff,
+            duration,
+            revocable,
+        );
+        schedule_id
+    }
+
+    /// Release everything vested across `beneficiary`'s schedules. Returns the amount
+    /// transferred.
+    pub fn claim(env: Env, beneficiary: Address) -> i128 {
+        beneficiary.require_auth();
+
+        let now = env.ledger().timestamp();
+        let mut claimed = 0;
+        for schedule_id in Self::schedule_ids(&env, &beneficiary).iter() {
+            let mut schedule = Self::get_schedule(env.clone(), schedule_id);
+            let amount = Self::vested(&schedule, now) - schedule.released;
+            if amount > 0 {
+                schedule.released += amount;
+                Self::save_schedule(&env, &schedule);
+                events::emit_tokens_claimed(&env, schedule_id, &beneficiary, amount);
+                claimed += amount;
+            }
+        }
+        if claimed == 0 {
+            panic!("nothing to claim");
+        }
+
+        Self::token(&env).transfer(&env.current_contract_address(), &beneficiary, &claimed);
+        claimed
+    }
+
+    /// Revoke a revocable schedule (admin only). Tokens vested so far stay claimable;
+    /// the rest goes to the treasury. Returns the amount returned.
+    pub fn revoke(env: Env, schedule_id: u64) -> i128 {
+        Self::require_admin(&env);
+
+        let mut schedule = Self::get_schedule(env.clone(), schedule_id);
+        if !schedule.revocable {
+            panic!("schedule not revocable");
+        }
+        if schedule.revoked_at.is_some() {
+            panic!("schedule already revoked");
+        }
+
+        let now = env.ledger().timestamp();
+        let vested = Self::vested(&schedule, now);
+        let returned = schedule.total - vested;
+        schedule.total = vested;
+        schedule.revoked_at = Some(now);
+        Self::save_schedule(&env, &schedule);
+
+        let treasury = Self::get_treasury(env.clone());
+        if returned > 0 {
+            Self::token(&env).transfer(&env.current_contract_address(), &treasury, &returned);
+        }
+
+        events::emit_schedule_revoked(
+            &env,
+            schedule_id,
+            &schedule.beneficiary,
+            vested,
+            returned,
+            &treasury,
+        );
+        returned
+    }
+
+    pub fn get_schedule(env: Env, schedule_id: u64) -> VestingSchedule {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Schedule(schedule_id))
+            .expect("schedule not found")
+    }
+
+    pub fn get_schedules(env: Env, beneficiary: Address) -> Vec<VestingSchedule> {
+        let mut schedules = Vec::new(&env);
+        for schedule_id in Self::schedule_ids(&env, &beneficiary).iter() {
+            schedules.push_back(Self::get_schedule(env.clone(), schedule_id));
+        }
+        schedules
+    }
+
+    /// Tokens of `schedule_id` vested as of now, claimed or not.
+    pub fn vested_amount(env: Env, schedule_id: u64) -> i128 {
+        let schedule = Self::get_schedule(env.clone(), schedule_id);
+        Self::vested(&schedule, env.ledger().timestamp())
+    }
+
+    /// Tokens `beneficiary` can claim now.
+    pub fn claimable(env: Env, beneficiary: Address) -> i128 {
+        let now = env.ledger().timestamp();
+        let mut amount = 0;
+        for schedule in Self::get_schedules(env.clone(), beneficiary).iter() {
+            amount += Self::vested(&schedule, now) - schedule.released;
+        }
+        amount
+    }
+
+    pub fn get_treasury(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Treasury)
+            .expect("not initialized")
+    }
+
+    pub fn get_admin(env: Env) -> Address {
+        env.storage()
+            .instance()
+            .get(&DataKey::Admin)
+            .expect("not initialized")
+    }
+
+    fn require_admin(env: &Env) {
+        Self::get_admin(env.clone()).require_auth();
+    }
+
+    fn token(env: &Env) -> token::Client<'_> {
+        let address: Address = env
+            .storage()
+            .instance()
+            .get(&DataKey::Token)
+            .expect("not initialized");
+        token::Client::new(env, &address)
+    }
+
+    fn schedule_ids(env: &Env, beneficiary: &Address) -> Vec<u64> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::Beneficiary(beneficiary.clone()))
+            .unwrap_or(Vec::new(env))
+    }
+
+    fn save_schedule(env: &Env, schedule: &VestingSchedule) {
+        env.storage()
+            .persistent()
+            .set(&DataKey::Schedule(schedule.schedule_id), schedule);
+    }
+
+    fn vested(schedule: &VestingSchedule, now: u64) -> i128 {
+        if schedule.revoked_at.is_some() {
+            return schedule.total;
+        }
+        let elapsed = now.saturating_sub(schedule.start_time);
+        if now < schedule.start_time || elapsed < schedule.cliff {
+            0
+        } else if elapsed >= schedule.duration {
+            schedule.total
+        } else {
+            schedule.total * elapsed as i128 / schedule.duration as i128
+        }
+    }
+}
+
+#[cfg(test)]
+mod test;
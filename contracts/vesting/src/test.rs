@@ -0,0 +1,119 @@
+#![cfg(test)]
+
+use super::*;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger as _},
+    token::{StellarAssetClient, TokenClient},
+    Address, Env,
+};
+
+const START: u64 = 10_000;
+const CLIFF: u64 = 1_000;
+const DURATION: u64 = 4_000;
+
+struct Setup<'a> {
+    env: Env,
+    treasury: Address,
+    beneficiary: Address,
+    client: VestingContractClient<'a>,
+    token: TokenClient<'a>,
+}
+
+fn setup() -> Setup<'static> {
+    let env = Env::default();
+    env.mock_all_auths();
+    env.ledger().set_timestamp(START);
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let sac = env.register_stellar_asset_contract_v2(admin.clone());
+    StellarAssetClient::new(&env, &sac.address()).mint(&admin, &100_000);
+
+    let contract_id = env.register(VestingContract, ());
+    let client = VestingContractClient::new(&env, &contract_id);
+    client.initialize(&admin, &sac.address(), &treasury);
+
+    Setup {
+        beneficiary: Address::generate(&env),
+        token: TokenClient::new(&env, &sac.address()),
+        env,
+        treasury,
+        client,
+    }
+}
+
+fn grant(s: &Setup, total: i128, revocable: bool) -> u64 {
+    s.client.create_schedule(
+        &s.beneficiary,
+        &total,
+        &START,
+        &CLIFF,
+        &DURATION,
+        &revocable,
+    )
+}
+
+#[test]
+fn test_cliff_then_linear_release() {
+    let s = setup();
+    let id = grant(&s, 4_000, false);
+    assert_eq!(s.token.balance(&s.client.address), 4_000);
+
+    s.env.ledger().set_timestamp(START + CLIFF - 1);
+    assert_eq!(s.client.claimable(&s.beneficiary), 0);
+    assert!(s.client.try_claim(&s.beneficiary).is_err());
+
+    s.env.ledger().set_timestamp(START + CLIFF);
+    assert_eq!(s.client.claim(&s.beneficiary), 1_000);
+
+    s.env.ledger().set_timestamp(START + 3_000);
+    assert_eq!(s.client.vested_amount(&id), 3_000);
+    assert_eq!(s.client.claim(&s.beneficiary), 2_000);
+
+    s.env.ledger().set_timestamp(START + DURATION + 500);
+    assert_eq!(s.client.claim(&s.beneficiary), 1_000);
+    assert_eq!(s.token.balance(&s.beneficiary), 4_000);
+    assert_eq!(s.client.get_schedule(&id).released, 4_000);
+}
+
+#[test]
+fn test_claim_spans_schedules() {
+    let s = setup();
+    grant(&s, 4_000, false);
+    grant(&s, 8_000, true);
+    assert_eq!(s.client.get_schedules(&s.beneficiary).len(), 2);
+
+    s.env.ledger().set_timestamp(START + 2_000);
+    assert_eq!(s.client.claim(&s.beneficiary), 6_000);
+}
+
+#[test]
+fn test_revoke_returns_unvested_to_treasury() {
+    let s = setup();
+    let id = grant(&s, 4_000, true);
+
+    s.env.ledger().set_timestamp(START + 1_500);
+    assert_eq!(s.client.revoke(&id), 2_500);
+    assert_eq!(s.token.balance(&s.treasury), 2_500);
+    assert!(s.client.try_revoke(&id).is_err());
+
+    // The vested part stays claimable and nothing more vests.
+    s.env.ledger().set_timestamp(START + DURATION);
+    assert_eq!(s.client.claim(&s.beneficiary), 1_500);
+    assert_eq!(s.token.balance(&s.client.address), 0);
+}
+
+#[test]
+fn test_irrevocable_schedule() {
+    let s = setup();
+    let id = grant(&s, 4_000, false);
+    assert!(s.client.try_revoke(&id).is_err());
+}
+
+#[test]
+#[should_panic(expected = "invalid schedule")]
+fn test_cliff_beyond_duration_rejected() {
+    let s = setup();
+    s.client
+        .create_schedule(&s.beneficiary, &1_000, &START, &5_000, &DURATION, &true);
+}